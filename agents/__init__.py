@@ -2,6 +2,8 @@ from .base_agent import BaseAnalysisAgent
 from .source_code_agent import SourceCodeAgent
 from .assembly_agent import AssemblyBinaryAgent
 from .logs_config_agent import LogsConfigAgent
+from .manifest_agent import DependencyManifestAgent
+from .diff_agent import CodeDiffAgent
 from .agent_factory import AgentFactory
 
 __all__ = [
@@ -9,5 +11,7 @@ __all__ = [
     'SourceCodeAgent',
     'AssemblyBinaryAgent',
     'LogsConfigAgent',
+    'DependencyManifestAgent',
+    'CodeDiffAgent',
     'AgentFactory'
 ]
\ No newline at end of file
@@ -3,12 +3,16 @@ from .base_agent import BaseAnalysisAgent
 from .source_code_agent import SourceCodeAgent
 from .assembly_agent import AssemblyBinaryAgent
 from .logs_config_agent import LogsConfigAgent
+from .manifest_agent import DependencyManifestAgent
+from .diff_agent import CodeDiffAgent
 
 class AgentFactory:
     _agents = {
         'source_code': SourceCodeAgent,
         'assembly_binary': AssemblyBinaryAgent,
-        'logs_config': LogsConfigAgent
+        'logs_config': LogsConfigAgent,
+        'dependency_manifest': DependencyManifestAgent,
+        'code_diff': CodeDiffAgent
     }
 
     @classmethod
@@ -1,12 +1,21 @@
 from abc import ABC, abstractmethod
 from typing import Dict, Any, List
 import json
+from .response_parser import ResponseParser, PARSE_FAILED
 
 class BaseAnalysisAgent(ABC):
     def __init__(self, name: str, description: str, prompt_template: str):
         self.name = name
         self.description = description
         self.prompt_template = prompt_template
+        # 응답 JSON에 자유 서술 근거(rationale) 요청 여부 (utils/rationale.py가 채점)
+        self.request_rationale = False
+        # 응답 JSON에 운용 모드/구현 약점(weaknesses) 요청 여부 (utils/misuse.py가 채점)
+        self.request_weaknesses = False
+        # 응답 JSON에 영역별 순위 후보(candidates) 요청 여부 (utils/top_k.py가 채점)
+        self.request_candidates = False
+        # 응답 JSON에 판정(verdict, 판단 보류 "unsure" 허용) 요청 여부 (utils/selective.py가 채점)
+        self.request_verdict = False
 
     @abstractmethod
     def get_analysis_points(self) -> List[str]:
@@ -16,7 +25,8 @@ class BaseAnalysisAgent(ABC):
     def validate_input(self, input_data: str) -> bool:
         pass
 
-    def create_prompt(self, input_data: str, analysis_points: List[str] = None) -> str:
+    def create_prompt(self, input_data: str, analysis_points: List[str] = None,
+                      exemplars: List[Dict[str, Any]] = None) -> str:
         if analysis_points is None:
             analysis_points = self.get_analysis_points()
 
@@ -42,7 +52,7 @@ The goal is to PROTECT systems by identifying vulnerabilities BEFORE quantum com
 You are an expert security analyst specializing in quantum-vulnerable cryptographic algorithm detection.
 
 {self.prompt_template.format(analysis_points=analysis_str)}
-
+{self._exemplar_section(exemplars)}
 Input data to analyze:
 {input_data}
 
@@ -107,12 +117,66 @@ Respond ONLY in JSON format:
         {self._generate_json_structure(analysis_points)}
     }},
     "confidence_score": <float between 0 and 1>,
-    "summary": "<brief summary of detected vulnerable algorithms>"
+    "summary": "<brief summary of detected vulnerable algorithms>"{self._extra_response_fields()}{self._weakness_field()}{self._candidates_field()}{self._verdict_field()}{self._rationale_field()}
 }}
 
 RESPOND ONLY WITH VALID JSON. DO NOT wrap JSON in markdown code blocks (```json). Provide raw JSON only."""
         return prompt
 
+    def _exemplar_section(self, exemplars: List[Dict[str, Any]] = None) -> str:
+        """few-shot 예시 블록 (train 분할에서만 선택됨, utils/exemplars.py)"""
+        if not exemplars:
+            return ""
+
+        blocks = []
+        for index, exemplar in enumerate(exemplars, 1):
+            answer = "\n".join(f"DETECTED: {label}" for label in exemplar['labels']) or "NOT DETECTED"
+            blocks.append(f"Example {index} input:\n{exemplar['input_data']}\n\n"
+                          f"Example {index} expected findings:\n{answer}")
+        examples = "\n\n".join(blocks)
+
+        return f"""
+==== WORKED EXAMPLES ====
+
+The following examples show inputs and the algorithms a correct analysis reports for them.
+They are different files from the input you must analyze.
+
+{examples}
+
+==== END OF EXAMPLES ====
+"""
+
+    def _extra_response_fields(self) -> str:
+        """과제별로 응답 JSON에 추가할 필드 (기본은 없음)"""
+        return ""
+
+    def _weakness_field(self) -> str:
+        if not self.request_weaknesses:
+            return ""
+        from utils.misuse import MODES, WEAKNESSES
+        return (',\n    "weaknesses": [{"algorithm": "<algorithm name>", "mode": "<one of ' + ', '.join(MODES) +
+                '>", "weakness": "<one of ' + ', '.join(WEAKNESSES) + '>"}]')
+
+    def _candidates_field(self) -> str:
+        if not self.request_candidates:
+            return ""
+        from utils.top_k import MAX_CANDIDATES
+        return (',\n    "candidates": [{"lines": "<start-end line range of one code region that implements '
+                'cryptography>", "ranked": ["<most likely algorithm>", "<next most likely>", '
+                f'"<... up to {MAX_CANDIDATES} candidates, most likely first>"]}}]')
+
+    def _verdict_field(self) -> str:
+        if not self.request_verdict:
+            return ""
+        return (',\n    "verdict": "<detected | not_detected | unsure — answer unsure if the code does not let you '
+                'decide; unsure is scored separately from a wrong answer>"')
+
+    def _rationale_field(self) -> str:
+        if not self.request_rationale:
+            return ""
+        return (',\n    "rationale": "<for each detected algorithm, explain which specific constants, tables, loop '
+                'structures, function calls or line numbers identify it>"')
+
     def _generate_json_structure(self, analysis_points: List[str]) -> str:
         json_fields = []
         for point in analysis_points:
@@ -125,88 +189,38 @@ RESPOND ONLY WITH VALID JSON. DO NOT wrap JSON in markdown code blocks (```json)
         return ",\n".join(json_fields)
 
     def extract_key_findings(self, response: str) -> Dict[str, Any]:
-        try:
-            response = response.strip()
-
-            # Remove markdown code blocks more robustly
-            if response.startswith('```json'):
-                response = response[7:]
-            elif response.startswith('```'):
-                response = response[3:]
-
-            if response.endswith('```'):
-                response = response[:-3]
-
-            response = response.strip()
-
-            # Handle multiple code block patterns
-            import re
-            # Remove any remaining code block markers
-            response = re.sub(r'^```.*?\n', '', response, flags=re.MULTILINE)
-            response = re.sub(r'\n```.*?$', '', response, flags=re.MULTILINE)
-
-            # Handle DeepSeek R1 thinking tags
-            response = re.sub(r'<think>.*?</think>', '', response, flags=re.DOTALL)
-            response = response.strip()
-
-            # Try to parse as-is first
-            try:
-                parsed = json.loads(response)
-            except json.JSONDecodeError:
-                # If that fails, try to extract JSON from text
-                json_text = self._extract_json_from_text(response)
-                if json_text:
-                    parsed = json.loads(json_text)
-                else:
-                    raise json.JSONDecodeError("No valid JSON found", response, 0)
+        parsed = ResponseParser().parse(response)
 
-            return {
-                'valid_json': True,
-                'agent_type': parsed.get('agent_type', self.name),
-                'analysis_results': parsed.get('analysis_results', {}),
-                'confidence_score': parsed.get('confidence_score', 0.0),
-                'summary': parsed.get('summary', ''),
-                'raw_response': response
-            }
-        except json.JSONDecodeError:
+        if parsed['status'] == PARSE_FAILED:
             return {
                 'valid_json': False,
                 'agent_type': self.name,
                 'analysis_results': {},
                 'confidence_score': 0.0,
                 'summary': 'Failed to parse JSON response',
-                'raw_response': response
+                'raw_response': parsed['cleaned'],
+                'parse_status': parsed['status'],
+                'parse_repairs': parsed['repairs'],
+                'parse_warnings': parsed['warnings'],
+                'schema_errors': parsed['schema_errors']
             }
 
-    def _extract_json_from_text(self, text: str) -> str:
-        """Extract JSON object from text that may contain additional prose."""
-        import re
-
-        # Look for JSON object starting with { and ending with }
-        # Handle nested objects by counting braces
-        start_idx = text.find('{')
-        if start_idx == -1:
-            return None
-
-        brace_count = 0
-        end_idx = start_idx
-
-        for i, char in enumerate(text[start_idx:], start_idx):
-            if char == '{':
-                brace_count += 1
-            elif char == '}':
-                brace_count -= 1
-                if brace_count == 0:
-                    end_idx = i
-                    break
-
-        if brace_count == 0:
-            json_candidate = text[start_idx:end_idx + 1]
-            # Quick validation - try to parse it
-            try:
-                json.loads(json_candidate)
-                return json_candidate
-            except json.JSONDecodeError:
-                pass
-
-        return None
\ No newline at end of file
+        data = parsed['data']
+        return {
+            'valid_json': True,
+            'agent_type': data.get('agent_type', self.name),
+            'analysis_results': data.get('analysis_results', {}),
+            'confidence_score': data.get('confidence_score', 0.0),
+            'summary': data.get('summary', ''),
+            'labels': data.get('labels', []),
+            'introductions': data.get('introductions', []),
+            'weaknesses': data.get('weaknesses', []),
+            'candidates': data.get('candidates', []),
+            'verdict': data.get('verdict') if isinstance(data.get('verdict'), str) else None,
+            'rationale': data.get('rationale') if isinstance(data.get('rationale'), str) else None,
+            'raw_response': parsed['cleaned'],
+            'parse_status': parsed['status'],
+            'parse_repairs': parsed['repairs'],
+            'parse_warnings': parsed['warnings'],
+            'schema_errors': []
+        }
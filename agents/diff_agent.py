@@ -0,0 +1,49 @@
+from typing import List
+from .base_agent import BaseAnalysisAgent
+import re
+
+class CodeDiffAgent(BaseAnalysisAgent):
+    def __init__(self, prompt_template: str = None):
+        if prompt_template is None:
+            prompt_template = ("Analyze the following unified diff or patch series. Report only cryptography that the "
+                               "added lines introduce (ignore algorithms that appear only in context or removed lines), "
+                               "and provide insights about: {analysis_points}")
+
+        super().__init__(
+            name="Code Diff Crypto Introduction Agent",
+            description="Detects quantum-vulnerable cryptographic algorithms introduced by a diff and the commit/hunk that introduces them",
+            prompt_template=prompt_template
+        )
+
+    def get_analysis_points(self) -> List[str]:
+        return [
+            "RSA key generation, encryption or signatures introduced by added lines",
+            "elliptic curve cryptography (ECC/ECDSA/ECDH) introduced by added lines",
+            "discrete logarithm based algorithms (DSA, DH, ElGamal) introduced by added lines",
+            "Korean domestic algorithms (SEED, ARIA, HIGHT, LEA, KCDSA, LSH) introduced by added lines",
+            "symmetric ciphers vulnerable to Grover's algorithm (AES, 3DES, DES, RC4) introduced by added lines",
+            "hash functions and MACs (MD5, SHA-1, SHA-256, HMAC) introduced by added lines",
+            "cryptography that is removed or only visible in context lines (not introduced)",
+            "commit and hunk where each vulnerable primitive first appears"
+        ]
+
+    def _extra_response_fields(self) -> str:
+        return (',\n    "introductions": [{"algorithm": "<algorithm name>", '
+                '"commit": "<short commit hash, or null for a plain diff>", "file": "<path after the change>", '
+                '"hunk": <1-based index of the @@ hunk within that file in that commit>}]')
+
+    def validate_input(self, input_data: str) -> bool:
+        if not input_data or not input_data.strip():
+            return False
+
+        diff_indicators = [
+            r'^diff --git a/',
+            r'^@@ -\d+(,\d+)? \+\d+(,\d+)? @@',
+            r'^\+\+\+ (b/|/dev/null)'
+        ]
+
+        for pattern in diff_indicators:
+            if re.search(pattern, input_data, re.MULTILINE):
+                return True
+
+        return False
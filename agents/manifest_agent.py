@@ -0,0 +1,45 @@
+from typing import List
+from .base_agent import BaseAnalysisAgent
+import re
+
+class DependencyManifestAgent(BaseAnalysisAgent):
+    def __init__(self, prompt_template: str = None):
+        if prompt_template is None:
+            prompt_template = ("Analyze the following package manifest and thin wrapper code. The algorithms are not "
+                               "implemented here: infer the cryptographic surface implied by the declared dependencies "
+                               "and how the wrapper uses them, and provide insights about: {analysis_points}")
+
+        super().__init__(
+            name="Dependency Manifest Crypto Inference Agent",
+            description="Infers quantum-vulnerable cryptographic algorithms implied by package dependencies (Cargo.toml, package.json, requirements.txt)",
+            prompt_template=prompt_template
+        )
+
+    def get_analysis_points(self) -> List[str]:
+        return [
+            "RSA key generation, encryption or signatures provided by declared dependencies",
+            "elliptic curve cryptography (ECC/ECDSA/ECDH) provided by declared dependencies",
+            "discrete logarithm based algorithms (DSA, DH, ElGamal) provided by declared dependencies",
+            "Korean domestic algorithms (SEED, ARIA, HIGHT, LEA, KCDSA, LSH) provided by declared dependencies",
+            "symmetric ciphers vulnerable to Grover's algorithm (AES, 3DES, DES, RC4) enabled by dependencies or features",
+            "hash functions and MACs (MD5, SHA-1, SHA-256, HMAC) pulled in by dependencies",
+            "key derivation functions (PBKDF2, HKDF, scrypt) pulled in by dependencies",
+            "crate features or package options that enable additional cryptographic algorithms",
+            "wrapper code that narrows a broad crypto library (OpenSSL, ring, crypto-js) to specific algorithms"
+        ]
+
+    def validate_input(self, input_data: str) -> bool:
+        if not input_data or not input_data.strip():
+            return False
+
+        manifest_indicators = [
+            r'^==== (Cargo\.toml|package\.json|requirements\.txt) ====$',
+            r'^\[(dev-|build-)?dependencies\]',
+            r'"(dev)?[Dd]ependencies"\s*:'
+        ]
+
+        for pattern in manifest_indicators:
+            if re.search(pattern, input_data, re.MULTILINE):
+                return True
+
+        return False
@@ -0,0 +1,321 @@
+"""
+LLM 응답 파서
+
+LLM이 반환한 응답을 JSON 스키마로 검증하고, 흔히 발생하는 형식 오류
+(마크다운 코드 펜스, 후행 쉼표, think 태그, 스마트 따옴표 등)를 자동 복구합니다.
+복구 내역과 파싱 상태는 결과에 기록되어 스코어카드의 파싱 실패율 집계에 사용됩니다.
+"""
+
+import json
+import re
+from typing import Any, Dict, List, Optional, Tuple
+
+# 파싱 상태
+PARSE_OK = 'ok'                # 원본 그대로 유효한 JSON + 스키마 통과
+PARSE_REPAIRED = 'repaired'    # 자동 복구 후 유효
+PARSE_FAILED = 'failed'        # 복구 실패 또는 스키마 위반
+
+# 에이전트 응답 스키마 (JSON Schema 부분집합: type, required, properties,
+# additionalProperties, minimum, maximum)
+RESPONSE_SCHEMA = {
+    'type': 'object',
+    'required': ['analysis_results'],
+    'properties': {
+        'agent_type': {'type': 'string'},
+        'analysis_results': {
+            'type': 'object',
+            'additionalProperties': {'type': 'string'}
+        },
+        'confidence_score': {'type': 'number', 'minimum': 0.0, 'maximum': 1.0},
+        'summary': {'type': 'string'},
+        # (선택) 계층 라벨: "RSA-2048", "shor_vulnerable/RSA", {"category": ..., "family": ...}
+        #        속성 포함: "AES-256-CTR", {"family": "AES", "key_size": 256, "mode": "CTR"} (utils/attributes.py)
+        'labels': {'type': 'array'},
+        # (선택) diff-introduction 과제의 도입 위치: [{"algorithm", "commit", "file", "hunk"}]
+        'introductions': {'type': 'array'},
+        # (선택) 운용 모드/구현 약점: [{"algorithm", "mode", "weakness"}] (utils/misuse.py)
+        'weaknesses': {'type': 'array'},
+        # (선택) 영역별 순위 후보: [{"lines": "40-72", "ranked": ["SEED", "Feistel"]}] (utils/top_k.py)
+        'candidates': {'type': 'array'},
+        # (선택) 판정: "detected" / "not_detected" / "unsure" (판단 보류, utils/selective.py)
+        'verdict': {'type': 'string'}
+    }
+}
+
+_JSON_TYPES = {
+    'object': dict,
+    'array': list,
+    'string': str,
+    'boolean': bool,
+    'null': type(None),
+}
+
+
+class ResponseParser:
+    """스키마 검증 및 자동 복구를 수행하는 LLM 응답 파서"""
+
+    def __init__(self, schema: Dict[str, Any] = None):
+        self.schema = schema or RESPONSE_SCHEMA
+
+    def parse(self, response: str) -> Dict[str, Any]:
+        """응답 문자열을 파싱
+
+        Returns:
+            {
+                'status': 'ok' | 'repaired' | 'failed',
+                'data': 파싱된 객체 (실패 시 None),
+                'repairs': 적용된 복구 목록,
+                'schema_errors': 스키마 위반 목록,
+                'warnings': 실패 대신 보정한 값 목록 (예: 0~100 척도의 confidence_score),
+                'cleaned': 복구가 적용된 응답 문자열
+            }
+        """
+        text = (response or '').strip()
+        repairs = []
+
+        # 1단계: 원본 그대로 파싱 시도
+        data = self._try_load(text)
+
+        # 2단계: 텍스트 수준 복구
+        if data is None:
+            for name, repair in self._text_repairs():
+                repaired = repair(text)
+                if repaired != text:
+                    text = repaired
+                    repairs.append(name)
+                    data = self._try_load(text)
+                    if data is not None:
+                        break
+
+        if data is None:
+            return self._result(PARSE_FAILED, None, repairs, ['response is not valid JSON'], text)
+
+        # 3단계: 스키마 수준 복구 (타입 강제 변환, 범위 보정)
+        data, coerced, warnings = self._coerce_to_schema(data)
+        repairs.extend(coerced)
+
+        schema_errors = self.validate(data, self.schema)
+        if schema_errors:
+            return self._result(PARSE_FAILED, data, repairs, schema_errors, text, warnings)
+
+        status = PARSE_REPAIRED if repairs else PARSE_OK
+        return self._result(status, data, repairs, [], text, warnings)
+
+    @staticmethod
+    def _result(status: str, data: Optional[Dict[str, Any]], repairs: List[str],
+                schema_errors: List[str], cleaned: str, warnings: List[str] = None) -> Dict[str, Any]:
+        return {
+            'status': status,
+            'data': data,
+            'repairs': repairs,
+            'schema_errors': schema_errors,
+            'warnings': warnings or [],
+            'cleaned': cleaned
+        }
+
+    @staticmethod
+    def _try_load(text: str) -> Optional[Any]:
+        try:
+            return json.loads(text)
+        except (json.JSONDecodeError, TypeError):
+            return None
+
+    def _text_repairs(self) -> List[Tuple[str, Any]]:
+        """순서대로 누적 적용되는 텍스트 복구 단계"""
+        return [
+            ('strip_think_tags', self._strip_think_tags),
+            ('strip_markdown_fences', self._strip_markdown_fences),
+            ('extract_json_object', self._extract_json_object),
+            ('normalize_quotes', self._normalize_quotes),
+            ('remove_trailing_commas', self._remove_trailing_commas),
+            ('python_literals', self._python_literals),
+            ('close_unbalanced_braces', self._close_unbalanced_braces),
+        ]
+
+    @staticmethod
+    def _strip_think_tags(text: str) -> str:
+        # DeepSeek R1 / Qwen3 think 태그
+        text = re.sub(r'<think>.*?</think>', '', text, flags=re.DOTALL)
+        if '<think>' in text:
+            text = text.split('<think>', 1)[0]
+        return text.strip()
+
+    @staticmethod
+    def _strip_markdown_fences(text: str) -> str:
+        fenced = re.search(r'```(?:json|JSON)?\s*\n?(.*?)```', text, flags=re.DOTALL)
+        if fenced:
+            return fenced.group(1).strip()
+        # 닫히지 않은 펜스
+        text = re.sub(r'^```(?:json|JSON)?\s*', '', text)
+        return re.sub(r'\s*```$', '', text).strip()
+
+    @staticmethod
+    def _extract_json_object(text: str) -> str:
+        """앞뒤 설명 문장을 제거하고 첫 번째 JSON 객체만 추출"""
+        start = text.find('{')
+        if start == -1:
+            return text
+
+        depth = 0
+        in_string = False
+        escaped = False
+        for i in range(start, len(text)):
+            char = text[i]
+            if in_string:
+                if escaped:
+                    escaped = False
+                elif char == '\\':
+                    escaped = True
+                elif char == '"':
+                    in_string = False
+                continue
+            if char == '"':
+                in_string = True
+            elif char == '{':
+                depth += 1
+            elif char == '}':
+                depth -= 1
+                if depth == 0:
+                    return text[start:i + 1]
+
+        # 닫히지 않은 객체는 이후 단계에서 복구
+        return text[start:]
+
+    @staticmethod
+    def _normalize_quotes(text: str) -> str:
+        return (text.replace('“', '"').replace('”', '"')
+                    .replace('‘', "'").replace('’', "'"))
+
+    @staticmethod
+    def _remove_trailing_commas(text: str) -> str:
+        """문자열 리터럴 밖의 `,}` / `,]`에서 쉼표만 제거"""
+        out = []
+        in_string = False
+        escaped = False
+        for i, char in enumerate(text):
+            if in_string:
+                if escaped:
+                    escaped = False
+                elif char == '\\':
+                    escaped = True
+                elif char == '"':
+                    in_string = False
+            elif char == '"':
+                in_string = True
+            elif char == ',':
+                rest = text[i + 1:].lstrip()
+                if rest[:1] in ('}', ']'):
+                    continue
+            out.append(char)
+        return ''.join(out)
+
+    @staticmethod
+    def _python_literals(text: str) -> str:
+        text = re.sub(r'(?<=[:\[,\s])True(?=\s*[,}\]])', 'true', text)
+        text = re.sub(r'(?<=[:\[,\s])False(?=\s*[,}\]])', 'false', text)
+        return re.sub(r'(?<=[:\[,\s])None(?=\s*[,}\]])', 'null', text)
+
+    @staticmethod
+    def _close_unbalanced_braces(text: str) -> str:
+        """토큰 제한으로 잘린 응답의 괄호를 닫음"""
+        stack = []
+        in_string = False
+        escaped = False
+        for char in text:
+            if in_string:
+                if escaped:
+                    escaped = False
+                elif char == '\\':
+                    escaped = True
+                elif char == '"':
+                    in_string = False
+                continue
+            if char == '"':
+                in_string = True
+            elif char in '{[':
+                stack.append('}' if char == '{' else ']')
+            elif char in '}]' and stack:
+                stack.pop()
+
+        if not stack and not in_string:
+            return text
+
+        closed = text + ('"' if in_string else '')
+        closed = re.sub(r',\s*$', '', closed)
+        return closed + ''.join(reversed(stack))
+
+    def _coerce_to_schema(self, data: Any) -> Tuple[Any, List[str], List[str]]:
+        """스키마와 어긋나지만 의미가 명확한 값을 변환 (보정한 값은 경고로 남김)"""
+        repairs = []
+        warnings = []
+        if not isinstance(data, dict):
+            return data, repairs, warnings
+
+        results = data.get('analysis_results')
+        if isinstance(results, dict):
+            for key, value in list(results.items()):
+                if value is None:
+                    results[key] = 'NOT DETECTED'
+                    repairs.append('coerce_null_result')
+                elif not isinstance(value, str):
+                    results[key] = json.dumps(value, ensure_ascii=False)
+                    repairs.append('coerce_non_string_result')
+
+        confidence = data.get('confidence_score')
+        if isinstance(confidence, str):
+            try:
+                data['confidence_score'] = float(confidence.strip().rstrip('%'))
+                if confidence.strip().endswith('%'):
+                    data['confidence_score'] /= 100.0
+                repairs.append('coerce_confidence_score')
+            except ValueError:
+                pass
+
+        # 0~100 척도(85 → 0.85)는 다시 맞추고, 그래도 범위를 벗어나면 [0, 1]로 자름
+        confidence = data.get('confidence_score')
+        if isinstance(confidence, (int, float)) and not isinstance(confidence, bool):
+            adjusted = confidence / 100.0 if 1.0 < confidence <= 100.0 else confidence
+            adjusted = min(max(adjusted, 0.0), 1.0)
+            if adjusted != confidence:
+                data['confidence_score'] = adjusted
+                repairs.append('rescale_confidence_score' if 1.0 < confidence <= 100.0 else 'clamp_confidence_score')
+                warnings.append(f"$.confidence_score: {confidence} adjusted to {adjusted}")
+
+        return data, sorted(set(repairs), key=repairs.index), warnings
+
+    @classmethod
+    def validate(cls, data: Any, schema: Dict[str, Any], path: str = '$') -> List[str]:
+        """JSON 스키마 부분집합으로 검증하고 위반 목록을 반환"""
+        errors = []
+        expected_type = schema.get('type')
+
+        if expected_type == 'number':
+            if isinstance(data, bool) or not isinstance(data, (int, float)):
+                return [f"{path}: expected number, got {type(data).__name__}"]
+            if 'minimum' in schema and data < schema['minimum']:
+                errors.append(f"{path}: {data} < minimum {schema['minimum']}")
+            if 'maximum' in schema and data > schema['maximum']:
+                errors.append(f"{path}: {data} > maximum {schema['maximum']}")
+            return errors
+
+        if expected_type and not isinstance(data, _JSON_TYPES[expected_type]):
+            return [f"{path}: expected {expected_type}, got {type(data).__name__}"]
+
+        if expected_type == 'object':
+            for field in schema.get('required', []):
+                if field not in data:
+                    errors.append(f"{path}: missing required field '{field}'")
+
+            properties = schema.get('properties', {})
+            additional = schema.get('additionalProperties')
+            for key, value in data.items():
+                if key in properties:
+                    errors.extend(cls.validate(value, properties[key], f"{path}.{key}"))
+                elif isinstance(additional, dict):
+                    errors.extend(cls.validate(value, additional, f"{path}.{key}"))
+
+        if expected_type == 'array' and isinstance(schema.get('items'), dict):
+            for i, item in enumerate(data):
+                errors.extend(cls.validate(item, schema['items'], f"{path}[{i}]"))
+
+        return errors
@@ -0,0 +1,237 @@
+#!/usr/bin/env python3
+"""
+종단(longitudinal) 추세 분석 도구
+SQLite 결과 저장소에 누적된 실행들을 모델 계열별로 묶어 코퍼스 버전/날짜에 따른
+지표 변화를 그래프와 리포트로 출력합니다 ("state of crypto-detection" 추세 리포트).
+
+코퍼스가 바뀐 실행끼리의 비교는 교집합 재계산으로 정규화합니다:
+동일한 ground truth로 모든 실행에 존재하는 테스트만으로 지표를 다시 계산합니다.
+
+사용법:
+    python analyze_trends.py [options]
+
+옵션:
+    --ingest FILE...     결과 JSON을 저장소에 적재한 뒤 분석 (과거 결과 백필)
+    --metric NAME        accuracy / success_rate / hierarchical_f1 / json_validity / response_time
+    --normalize MODE     family: 계열 내 교집합 (기본값), global: 전체 교집합, none: 정규화 없음
+    --families NAME...   분석할 모델 계열
+    --agents NAME...     분석할 에이전트
+    --output-dir DIR     출력 디렉토리 (기본값: analysis_output/trends)
+"""
+
+import argparse
+import csv
+from collections import defaultdict
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Any, List
+
+from utils.results_store import ResultsStore, RESULTS_DB_PATH, METRICS
+
+
+def point_key(row: Dict[str, Any]) -> tuple:
+    """추세 그래프의 한 점: 한 실행 안의 (모델, 구성)"""
+    return (row['run_id'], row['model'], row['configuration'])
+
+
+def split_by_configuration(series: List[Dict[str, Any]]) -> Dict[str, List[Dict[str, Any]]]:
+    """구성(default, rag 등)별 하위 추세선"""
+    lines: Dict[str, List[Dict[str, Any]]] = defaultdict(list)
+    for point in series:
+        if point['value'] is not None:
+            lines[point['configuration']].append(point)
+    return dict(lines)
+
+
+class TrendAnalyzer:
+    """모델 계열별 지표 추세 계산"""
+
+    def __init__(self, store: ResultsStore):
+        self.store = store
+
+    def compute_trends(self, metric: str = 'accuracy', normalize: str = 'family',
+                       families: List[str] = None, agents: List[str] = None,
+                       configuration: str = None) -> Dict[str, List[Dict[str, Any]]]:
+        """계열별 추세 점 목록 (시간순)
+
+        Returns:
+            {family: [{run_id, model, configuration, date, corpus_version,
+                       value, tests, total_tests}, ...]}
+        """
+        rows = self.store.results(agents=agents, families=families, configuration=configuration)
+
+        by_family: Dict[str, List[Dict[str, Any]]] = defaultdict(list)
+        for row in rows:
+            by_family[row['model_family']].append(row)
+
+        common_all = self.store.intersection_keys(rows, group_by=point_key) if normalize == 'global' else None
+
+        trends = {}
+        for family, family_rows in sorted(by_family.items()):
+            if normalize == 'global':
+                common = common_all
+            elif normalize == 'family':
+                common = self.store.intersection_keys(family_rows, group_by=point_key)
+            else:
+                common = None
+
+            points: Dict[tuple, List[Dict[str, Any]]] = defaultdict(list)
+            totals: Dict[tuple, int] = defaultdict(int)
+            for row in family_rows:
+                totals[point_key(row)] += 1
+                if common is None or self.store.test_key(row) in common:
+                    points[point_key(row)].append(row)
+
+            series = []
+            for key in totals:
+                point_rows = points.get(key, [])
+                sample = (point_rows or [r for r in family_rows if point_key(r) == key])[0]
+                metrics = self.store.compute_metrics(point_rows)
+                series.append({
+                    'run_id': key[0],
+                    'model': key[1],
+                    'configuration': key[2],
+                    'date': datetime.fromtimestamp(sample['started_at']),
+                    'corpus_version': sample['corpus_version'],
+                    'value': metrics[metric],
+                    'tests': metrics['tests'],
+                    'total_tests': totals[key],
+                })
+
+            trends[family] = sorted(series, key=lambda p: p['date'])
+        return trends
+
+    @staticmethod
+    def save_csv(trends: Dict[str, List[Dict[str, Any]]], path: Path):
+        fieldnames = ['family', 'date', 'model', 'configuration', 'corpus_version',
+                      'value', 'tests', 'total_tests', 'run_id']
+        with open(path, 'w', newline='', encoding='utf-8') as f:
+            writer = csv.DictWriter(f, fieldnames=fieldnames)
+            writer.writeheader()
+            for family, series in trends.items():
+                for point in series:
+                    writer.writerow(dict(point, family=family, date=point['date'].isoformat(timespec='seconds')))
+
+    @staticmethod
+    def plot(trends: Dict[str, List[Dict[str, Any]]], metric: str, normalize: str, path: Path):
+        import matplotlib
+        matplotlib.use('Agg')
+        import matplotlib.pyplot as plt
+
+        plt.rcParams['font.family'] = ['AppleGothic', 'Arial Unicode MS', 'DejaVu Sans']
+        plt.rcParams['axes.unicode_minus'] = False
+
+        fig, ax = plt.subplots(figsize=(12, 7))
+        for family, series in trends.items():
+            lines = split_by_configuration(series)
+            for configuration, points in lines.items():
+                label = family if len(lines) == 1 else f"{family} ({configuration})"
+                ax.plot([p['date'] for p in points], [p['value'] for p in points], marker='o', label=label)
+
+        # 코퍼스 버전이 바뀐 지점 표시
+        changes = {}
+        for series in trends.values():
+            for point in series:
+                changes.setdefault(point['corpus_version'], point['date'])
+        for version, date in sorted(changes.items(), key=lambda item: item[1])[1:]:
+            ax.axvline(date, color='gray', linestyle='--', linewidth=0.8)
+            ax.annotate(f"corpus {version}", (date, 1), xycoords=('data', 'axes fraction'),
+                        rotation=90, va='top', ha='right', fontsize=8, color='gray')
+
+        ax.set_xlabel('Run Date')
+        ax.set_ylabel(metric)
+        ax.set_title(f"{metric} trend by model family (normalize={normalize})")
+        ax.grid(alpha=0.3)
+        ax.legend()
+        fig.autofmt_xdate()
+        plt.tight_layout()
+        plt.savefig(path, dpi=200, bbox_inches='tight')
+        plt.close(fig)
+
+    @staticmethod
+    def generate_report(trends: Dict[str, List[Dict[str, Any]]], metric: str, normalize: str) -> str:
+        lines = [
+            f"# State of Crypto-Detection: {metric} 추세",
+            "",
+            f"- 생성 시각: {datetime.now().isoformat(timespec='seconds')}",
+            f"- 정규화: {normalize} (교집합 재계산에 사용된 테스트 수는 `tests / total` 열 참조)",
+            "",
+        ]
+
+        for family, series in trends.items():
+            lines.append(f"## {family}")
+            lines.append("")
+            for configuration, valued in split_by_configuration(series).items():
+                if len(valued) > 1:
+                    delta = valued[-1]['value'] - valued[0]['value']
+                    lines.append(f"- 첫 실행 대비 변화 ({configuration}): {delta:+.3f} "
+                                 f"({valued[0]['date']:%Y-%m-%d} → {valued[-1]['date']:%Y-%m-%d})")
+            lines.append("")
+
+            lines.append("| 날짜 | 모델 | 구성 | 코퍼스 버전 | 값 | tests / total |")
+            lines.append("|------|------|------|-------------|----|---------------|")
+            for point in series:
+                value = f"{point['value']:.3f}" if point['value'] is not None else "-"
+                lines.append(
+                    f"| {point['date']:%Y-%m-%d %H:%M} | {point['model']} | {point['configuration']} | "
+                    f"{point['corpus_version']} | {value} | {point['tests']} / {point['total_tests']} |"
+                )
+            lines.append("")
+
+        return "\n".join(lines)
+
+
+def main():
+    parser = argparse.ArgumentParser(description='모델 계열별 종단 추세 분석')
+    parser.add_argument('--db', default=RESULTS_DB_PATH, help='SQLite 결과 저장소 경로')
+    parser.add_argument('--ingest', nargs='+', help='분석 전에 적재할 결과 JSON 파일')
+    parser.add_argument('--metric', default='accuracy', choices=list(METRICS.keys()), help='분석할 지표')
+    parser.add_argument('--normalize', default='family', choices=['family', 'global', 'none'],
+                        help='코퍼스 변경 정규화 방식 (교집합 재계산 범위)')
+    parser.add_argument('--families', nargs='+', help='분석할 모델 계열')
+    parser.add_argument('--agents', nargs='+', help='분석할 에이전트')
+    parser.add_argument('--configuration', help='특정 구성만 분석 (예: default, rag)')
+    parser.add_argument('--output-dir', default='analysis_output/trends', help='출력 디렉토리')
+    parser.add_argument('--no-plot', action='store_true', help='그래프 생성 생략')
+
+    args = parser.parse_args()
+
+    store = ResultsStore(args.db)
+
+    for path in args.ingest or []:
+        run_id = store.ingest_file(path)
+        if run_id:
+            print(f"📥 적재: {path} → {run_id}")
+        else:
+            print(f"⚠️  지원하지 않는 결과 형식: {path}")
+
+    analyzer = TrendAnalyzer(store)
+    trends = analyzer.compute_trends(args.metric, args.normalize, args.families, args.agents, args.configuration)
+    store.close()
+
+    if not trends:
+        print("❌ 저장소에 분석할 결과가 없습니다.")
+        return
+
+    output_dir = Path(args.output_dir)
+    output_dir.mkdir(parents=True, exist_ok=True)
+
+    csv_path = output_dir / f"trend_{args.metric}.csv"
+    analyzer.save_csv(trends, csv_path)
+    print(f"💾 추세 데이터: {csv_path}")
+
+    report_path = output_dir / f"trend_{args.metric}.md"
+    report_path.write_text(analyzer.generate_report(trends, args.metric, args.normalize), encoding='utf-8')
+    print(f"📄 추세 리포트: {report_path}")
+
+    if not args.no_plot:
+        try:
+            plot_path = output_dir / f"trend_{args.metric}.png"
+            analyzer.plot(trends, args.metric, args.normalize, plot_path)
+            print(f"📊 추세 그래프: {plot_path}")
+        except ImportError:
+            print("⚠️  matplotlib가 설치되지 않아 시각화를 건너뜁니다.")
+
+
+if __name__ == "__main__":
+    main()
@@ -10,18 +10,62 @@ import time
 import argparse
 import csv
 from pathlib import Path
-from typing import Dict, Any, List, Optional
+from typing import Dict, Any, List, Optional, Tuple
 from concurrent.futures import ThreadPoolExecutor, as_completed
 import threading
 
 from config.config_loader import ConfigLoader
 from clients.client_factory import ClientFactory
 from clients.ollama_client import OllamaClient
+from clients.network_guard import NetworkGuard, REMOTE_PROVIDERS
+from utils.findings_merger import FindingsMerger, DEFAULT_MERGE_POLICY
+from utils.results_store import ResultsStore, ground_truth_hash, corpus_version
+from utils.fidelity import sample_fidelity
+from utils.comment_noise import documentation_kind
+from utils.provenance import provenance_kind
+from utils.tasks import TASK_DIFF, TASK_MISUSE, sample_task, task_hierarchy_policy
+from utils.diffs import calculate_hunk_scores, parse_patch
+from utils.significance import bootstrap_ci, compare_detectors
+from utils.attributes import summarize_attributes
+from utils.calibration import calibration_summary, label_confidences
+from utils.languages import sample_language, summarize_languages, underperforming
+from utils.exemplars import ExemplarSampler, few_shot_settings
+from utils.splits import SPLIT_TEST, sample_split
+from utils.sample_filter import SampleFilter
+from utils.subset import load_subset, subset_digest, subset_members
+from utils.incremental import (changed_samples, diff_manifests, load_manifest, load_results, merge_results,
+                               print_changes, snapshot)
+from utils.run_control import (INTERRUPTED_BY_USER, RESULT_STATUSES, STATUS_CRASH, STATUS_OK, STATUS_TIMEOUT,
+                               CancellationToken, Cancelled, crash_record, crashed_samples, print_crashes,
+                               result_status, run_with_timeout, timeout_settings)
+from utils.quota import CostTracker, MeteredClient, QuotaExceeded, RateLimiter, quota_settings
+from utils.batch_inference import BATCH_PROVIDERS, BatchInference, batch_settings
+from utils.harness_tools import SampleWorkspace, run_tools
+from utils.interrogation import interrogate, interrogation_settings, print_interrogation, summarize_interrogation
+from utils.transcripts import TranscriptRecorder, TranscriptStore, transcript_settings
+from utils.metrics_server import DEFAULT_METRICS_PORT, REGISTRY, start_metrics_server
+from utils.tracing import (STAGE_LATENCY, add_logging_arguments, apply_logging_arguments, configure_logging, logger,
+                           logging_settings, span, start_run, start_span)
+from utils.rationale import RationaleJudge, create_judge, rationale_settings, score_rationale
+from utils.misuse import calculate_weakness_scores, weakness_settings
+from utils.selective import abstained_scores, abstention_settings, format_selective, is_abstention, selective_summary
+from utils.top_k import (calculate_top_k_scores, finding_candidates, format_top_k, parse_candidates, summarize_top_k,
+                         top_k_settings)
+from utils.composite import WEIGHTS_PATH, composite_score, format_breakdown, load_profiles, load_weights, track_scores
+from utils.korean_compliance import korean_check, print_korean, summarize_korean
+from utils.rubric import rubric_stamp
+from utils.sample_index import SampleIndex, calculate_function_scores, summarize_function_scores
+from utils.label_judge import (LabelJudge, agreement_report, create_label_judge, judge_result, label_judge_settings,
+                               print_agreement_report)
+from utils.canary import current_generation
+from utils.environment import capture_environment, model_settings, prompt_hashes
+from utils.chunking import (chunk_input, chunking_settings, evidence_span, merge_chunk_findings,
+                            merge_chunk_responses, plan_chunks, remap_span)
 from agents.agent_factory import AgentFactory
 from utils.test_case_manager import TestCaseManager
 
 class BenchmarkRunner:
-    def __init__(self, config_path: str = "config/config.yaml"):
+    def __init__(self, config_path: str = "config/config.yaml", offline: bool = False):
         self.config_loader = ConfigLoader(config_path)
         self.test_manager = TestCaseManager(
             test_cases_dir="data/test_cases",
@@ -31,12 +75,99 @@ class BenchmarkRunner:
         self.results = {}
         self.lock = threading.Lock()
 
+        # 중복 탐지 결과 병합 정책 (채점 전 적용)
+        self.merger = FindingsMerger(
+            self.config_loader.get_benchmark_config().get('finding_merge_policy', DEFAULT_MERGE_POLICY)
+        )
+
+        # 계층 라벨 점수 전파 정책 (utils/taxonomy.py의 HIERARCHY_POLICIES)
+        self.hierarchy_policy = self.config_loader.get_benchmark_config().get('hierarchy_policy', 'default')
+
+        # few-shot 예시 (train 분할에서만 선택, utils/exemplars.py)
+        self.few_shot = few_shot_settings(self.config_loader.get_benchmark_config().get('few_shot'))
+        self.exemplar_samplers: Dict[str, ExemplarSampler] = {}
+
+        # 탐지 근거(rationale) 요청 및 채점 (utils/rationale.py)
+        self.rationale = rationale_settings(self.config_loader.get_benchmark_config().get('rationale'))
+        self.rationale_judge: Optional[RationaleJudge] = None
+
+        # 운용 모드/구현 약점(weaknesses) 요청 및 채점 (utils/misuse.py, weakness-detection 트랙)
+        self.weakness_detection = weakness_settings(
+            self.config_loader.get_benchmark_config().get('weakness_detection'))
+        # 영역별 순위 후보 요청과 top-k 채점 (utils/top_k.py)
+        self.top_k = top_k_settings(self.config_loader.get_benchmark_config().get('top_k'))
+        # 판단 보류("unsure") 허용과 선택적 예측 지표 (utils/selective.py)
+        self.abstention = abstention_settings(self.config_loader.get_benchmark_config().get('abstention'))
+
+        # 자유 서술 응답의 judge 라벨 매핑과 파서 일치도 (utils/label_judge.py)
+        self.label_judge = label_judge_settings(self.config_loader.get_benchmark_config().get('label_judge'))
+        self.label_judge_client: Optional[LabelJudge] = None
+
+        # 컨텍스트를 넘는 샘플의 창 분할 (utils/chunking.py, 끄면 기존처럼 앞부분만 사용)
+        self.chunking = chunking_settings(self.config_loader.get_benchmark_config().get('chunking'))
+
+        # 층화 평가 부분집합 (utils/subset.py, use_subset으로 지정하면 정의에 있는 샘플만 평가)
+        self.subset: Optional[Dict[str, Any]] = None
+
+        # 샘플 필터 식 (utils/sample_filter.py, use_filter로 지정하면 식에 맞는 샘플만 평가)
+        self.sample_filter: Optional[SampleFilter] = None
+
+        # 변경된 샘플만 평가 (utils/incremental.py, use_changed_since로 지정하면 추가·수정된 샘플만 평가하고 이전 결과와 합침)
+        self.changed_since: Optional[Dict[str, Any]] = None
+
+        # 샘플 제한 시간과 실행 전체 취소 신호 (utils/run_control.py)
+        self.sample_timeout = timeout_settings(self.config_loader.get_benchmark_config())
+        self.cancellation = CancellationToken()
+
+        # 프로바이더별 호출 속도 제한과 일일 비용 한도 (utils/quota.py, 병렬 작업자가 버킷과 장부를 함께 씀)
+        self.quota = quota_settings(self.config_loader.get_benchmark_config())
+        self.rate_limiter = RateLimiter(self.quota['rate_limits'])
+        self.costs = CostTracker(self.quota['cost']['daily_limit_usd'], self.quota['cost']['pricing'],
+                                 self.quota['cost']['ledger'])
+
+        # 배치 추론 (utils/batch_inference.py, 켜면 OpenAI/Anthropic 샘플을 배치 작업 하나로 제출하고 응답으로 채점)
+        self.batch = batch_settings(self.config_loader.get_benchmark_config().get('batch'))
+        self.batch_inference: Optional[BatchInference] = None
+
+        # 다중 턴 질의 (utils/interrogation.py, 켜면 개요만 보여 주고 모델의 후속 질문에 샘플 색인으로 답함)
+        self.interrogation = interrogation_settings(self.config_loader.get_benchmark_config().get('interrogation'))
+
+        # 대화 기록 (utils/transcripts.py, 샘플마다 프롬프트와 응답을 내용 주소 저장소에 남기고 결과 행에 digest)
+        self.transcripts = transcript_settings(self.config_loader.get_benchmark_config().get('transcripts'))
+
+        # 구조화 로그와 단계별 추적 (utils/tracing.py, CLI --log-*로 덮어쓰기)
+        self.logging = logging_settings(self.config_loader.get_benchmark_config().get('logging'))
+        configure_logging(self.logging['level'], self.logging['format'], self.logging['file'])
+
+        offline_config = self.config_loader.get_benchmark_config().get('offline', {})
+        self.offline = offline or offline_config.get('enabled', False)
+        if self.offline:
+            self._enable_offline_mode(offline_config)
+
+    def _enable_offline_mode(self, offline_config: Dict[str, Any]):
+        """오프라인 모드 활성화: 로컬 엔드포인트 외 모든 외부 연결 차단"""
+        allowed_endpoints = list(offline_config.get('allowed_endpoints', []))
+
+        # 로컬 프로바이더의 base_url은 자동으로 허용
+        for provider in ['ollama', 'local_ai', 'pqc_inspector']:
+            base_url = self.config_loader.get_llm_config(provider).get('base_url')
+            if base_url and not str(base_url).startswith('MISSING_'):
+                allowed_endpoints.append(base_url)
+
+        NetworkGuard.enable(allowed_endpoints)
+        print("🔒 오프라인 모드: 로컬 엔드포인트 외 모든 외부 연결이 차단됩니다.")
+        print(f"   허용 호스트: {', '.join(NetworkGuard.get_report()['allowed_hosts'])}")
+
     def get_available_models(self) -> Dict[str, List[str]]:
         """사용 가능한 모델들을 조회 (config에서 동적으로 로드)"""
         models = {}
 
         # API 기반 프로바이더들 (google, openai, xai)
         for provider in ['google', 'openai', 'xai']:
+            if self.offline and provider in REMOTE_PROVIDERS:
+                models[provider] = []
+                print(f"🔒 {provider}: 오프라인 모드에서 제외됨")
+                continue
             try:
                 provider_config = self.config_loader.get_llm_config(provider)
                 model_value = provider_config.get('model')
@@ -91,10 +222,44 @@ class BenchmarkRunner:
 
         return models
 
+    def use_subset(self, name_or_path: str):
+        """저장된 부분집합 정의로 평가 샘플 제한"""
+        self.subset = load_subset(name_or_path)
+        print(f"🎯 부분집합 {self.subset['name']}: 샘플 {len(self.subset['samples'])}개 ({self.subset['path']})")
+
+    def use_filter(self, expression: str):
+        """필터 식으로 평가 샘플 제한 (식 오류는 FilterError)"""
+        self.sample_filter = SampleFilter(expression)
+        print(f"🔎 샘플 필터: {self.sample_filter}")
+
+    def use_changed_since(self, path: str, merge_path: Optional[str] = None):
+        """이전 매니페스트(또는 결과 파일) 이후 추가·수정된 샘플만 평가하고 이전 결과와 합침"""
+        previous, prior = load_manifest(path)
+        if merge_path:
+            prior = load_results(merge_path)
+        self.changed_since = {'path': path, 'merge_path': merge_path or (path if prior is not None else None),
+                              'previous': previous, 'prior_results': prior or []}
+        print(f"🔁 {path} 이후 변경된 샘플만 평가 (이전 결과 {len(prior or [])}행과 합침)")
+
     def load_test_files(self, agent_type: str, limit: Optional[int] = None) -> List[Dict[str, Any]]:
         """특정 에이전트 타입의 테스트 파일들을 로드"""
         test_cases = self.test_manager.load_test_cases(agent_type)
 
+        if self.subset:
+            members = subset_members(self.subset, agent_type)
+            loaded = {case['test_id'] for case in test_cases}
+            missing = [test_id for test_id in members if test_id not in loaded]
+            if missing:
+                print(f"⚠️  부분집합의 {agent_type} 샘플 {len(missing)}개가 코퍼스에 없음: {', '.join(missing[:5])}")
+            test_cases = [case for case in test_cases if case['test_id'] in set(members)]
+
+        if self.sample_filter:
+            test_cases = self.sample_filter.select(agent_type, test_cases, self.test_manager.load_ground_truth)
+
+        if self.changed_since:
+            changed = set(changed_samples(self.changed_since['changes'], agent_type))
+            test_cases = [case for case in test_cases if case['test_id'] in changed]
+
         if limit:
             test_cases = test_cases[:limit]
 
@@ -102,25 +267,109 @@ class BenchmarkRunner:
         return test_cases
 
     def run_single_test(self, provider: str, model: str, agent_type: str,
-                       test_case: Dict[str, Any]) -> Dict[str, Any]:
-        """단일 테스트 실행"""
+                        test_case: Dict[str, Any]) -> Optional[Dict[str, Any]]:
+        """단일 테스트 실행 (샘플 제한 시간 적용, 결과마다 status 기록)
+
+        실행 전체가 이미 취소되었으면 None (실행하지 않은 샘플은 결과에 넣지 않음)
+        """
+        if self.cancellation.cancelled:
+            return None
+        token = self.cancellation.child()
+        try:
+            with span('sample', provider=provider, model=model, agent_type=agent_type,
+                      test_id=test_case.get('test_id', 'unknown')) as sample_span:
+                result = run_with_timeout(
+                    lambda sample_token: self._run_single_test(provider, model, agent_type, test_case, sample_token),
+                    self.sample_timeout, token
+                )
+                sample_span.set(status=result_status(result) if result is not None else STATUS_TIMEOUT)
+        except Cancelled as e:
+            # 실행 전체가 멈춘 경우 (사용자 중단, 비용 한도): 끝내지 못한 샘플은 결과에 넣지 않음
+            if isinstance(e, QuotaExceeded) and not self.cancellation.cancelled:
+                self.cancellation.cancel(str(e))
+                print(f"\n💸 일일 비용 한도 도달: {e}. 남은 테스트를 건너뛰고 지금까지의 결과로 요약합니다.")
+            return None
+        except Exception as e:
+            # _run_single_test 밖(제한 시간 스레드, 추적 span)에서 난 예외도 이 샘플의 crash로
+            result = self._crash_result(provider, model, agent_type, test_case, e)
+        if result is None:
+            print(f"    ⏱️  제한 시간 초과 ({provider}/{model}): {self.sample_timeout:g}초")
+            result = {
+                'test_id': test_case.get('test_id', 'unknown'),
+                'success': False,
+                'status': STATUS_TIMEOUT,
+                'error': token.reason,
+                'response_time': self.sample_timeout,
+                'timestamp': time.time()
+            }
+        for key, value in (('provider', provider), ('model', model), ('agent_type', agent_type),
+                           ('language', sample_language(test_case.get('file_path')))):
+            result.setdefault(key, value)
+        result['status'] = result_status(result)
+
+        # 채점하지 못한 샘플도 정답이 있으면 해시를 남겨 요약에서 0점으로 셈 (조용히 빠지지 않도록)
+        if result['status'] != STATUS_OK and 'ground_truth_hash' not in result:
+            result['ground_truth_hash'] = ground_truth_hash(self._load_ground_truth(test_case, agent_type))
+        return result
+
+    def _create_client(self, provider: str, model: str):
+        if provider == 'ollama':
+            return ClientFactory.create_client(provider, {
+                'api_key': 'not_required',
+                'model': model,
+                'base_url': 'http://localhost:11434'
+            })
+        llm_config = self.config_loader.get_llm_config(provider, model_name=model)
+        return ClientFactory.create_client(provider, llm_config)
+
+    @staticmethod
+    def _crash_result(provider: str, model: str, agent_type: str, test_case: Dict[str, Any],
+                      error: BaseException) -> Dict[str, Any]:
+        """샘플 하나에서 난 예외 → crash 결과 (실행은 다음 샘플로 계속)"""
+        crash = crash_record(error)
+        print(f"    💥 예외 발생 ({provider}/{model}): {crash['exception']}: {crash['message']} @ {crash['location']}")
+        return {
+            'test_id': test_case.get('test_id', 'unknown'),
+            'provider': provider,
+            'model': model,
+            'agent_type': agent_type,
+            'success': False,
+            'status': STATUS_CRASH,
+            'error': str(error),  # 사용자에게는 간단한 에러만 표시
+            'error_details': f"{error} | Traceback: {crash['traceback']}",  # 상세 정보는 별도 필드
+            'crash': crash,
+            'response_time': 0,
+            'timestamp': time.time()
+        }
+
+    def _run_single_test(self, provider: str, model: str, agent_type: str, test_case: Dict[str, Any],
+                         token: CancellationToken) -> Dict[str, Any]:
+        """단일 테스트 본문 (API 호출 전마다 취소 신호 확인)"""
+        recorder = None
         try:
-            print(f"    🔧 Debug: {provider}/{model} 테스트 시작")
-            # 클라이언트 생성
-            if provider == 'ollama':
-                client = ClientFactory.create_client(provider, {
-                    'api_key': 'not_required',
-                    'model': model,
-                    'base_url': 'http://localhost:11434'
-                })
+            logger.debug(f"{provider}/{model} 테스트 시작: {test_case.get('test_id', 'unknown')}")
+            # 클라이언트 생성 (배치 모드면 호출 대신 배치 작업에 요청을 모으고 응답을 돌려주는 클라이언트)
+            if self.batch_inference and provider in BATCH_PROVIDERS:
+                client = self.batch_inference.client(provider, model)
             else:
-                llm_config = self.config_loader.get_llm_config(provider, model_name=model)
-                client = ClientFactory.create_client(provider, llm_config)
+                client = MeteredClient(self._create_client(provider, model), provider, model, self.rate_limiter,
+                                       self.costs, token)
+            if self.transcripts['enabled']:
+                client = recorder = TranscriptRecorder(client, TranscriptStore(self.transcripts['dir']))
+
+            # Ground truth 로드 (해시는 코퍼스 버전 추적에 사용되므로 JSON 유효성과 무관하게 기록)
+            ground_truth = self._load_ground_truth(test_case, agent_type)
+            task = sample_task(ground_truth)
+            # misuse-detection 샘플은 설정과 무관하게 약점을 요청·채점
+            score_weaknesses = self.weakness_detection['enabled'] or task == TASK_MISUSE
 
             # 에이전트 생성
-            print(f"    🔧 Debug: 에이전트 생성 중...")
             agent = AgentFactory.create_agent(agent_type)
-            print(f"    🔧 Debug: 에이전트 생성 완료")
+            agent.request_rationale = self.rationale['enabled']
+            agent.request_weaknesses = score_weaknesses
+            agent.request_candidates = self.top_k['enabled']
+            agent.request_verdict = self.abstention['enabled']
+            logger.debug(f"에이전트 생성 완료: {agent_type}")
 
             # 입력 데이터 준비
             input_data = test_case.get('input_data', '')
@@ -130,10 +379,14 @@ class BenchmarkRunner:
                     'test_id': test_case.get('test_id', 'unknown')
                 }
 
-            # 컨텍스트 길이 제한 (모델별로 다르게 설정 가능)
+            # 컨텍스트 길이 제한: 분할을 켜면 겹치는 창으로 나눠 창마다 질의, 끄면 앞부분만 사용
+            # 다중 턴 질의는 전체 파일을 하네스가 들고 필요한 부분만 보여 주므로 분할/자르기 없음
+            interrogating = self.interrogation['enabled']
+            chunk_plan = plan_chunks(input_data, self.chunking, provider) \
+                if self.chunking['enabled'] and not interrogating else None
             max_length = 4000 if provider == 'ollama' else 6000
-            
-            if len(input_data) > max_length:
+
+            if chunk_plan is None and not interrogating and len(input_data) > max_length:
                 input_data = input_data[:max_length] + "\n... (truncated for length)"
 
             # 입력 검증
@@ -143,102 +396,165 @@ class BenchmarkRunner:
                     'test_id': test_case.get('test_id', 'unknown')
                 }
 
-            # 프롬프트 생성
-            prompt = agent.create_prompt(input_data)
+            # 프롬프트 생성 (few-shot이면 train 분할 예시 포함)
+            sampler = self.exemplar_samplers.get(agent_type)
+            exemplars = sampler.select(test_case) if sampler else []
+            chunks = chunk_plan['chunks'] if chunk_plan else [None]
+            if chunk_plan:
+                print(f"    🧩 창 {len(chunks)}개로 분할 (미질의 {chunk_plan['dropped_lines']}줄)")
 
-            # API 호출
-            print(f"    🔧 Debug: API 호출 시작...")
+            # API 호출 (창마다 한 번)
             max_tokens = 1500 if provider == 'ollama' else 2000
             if 'qwen3' in model:
                 max_tokens = 1000  # Qwen3은 더 짧은 토큰으로 설정
-            response = client.benchmark_request(prompt, max_tokens)
-            print(f"    🔧 Debug: API 호출 완료, success={response.get('success', 'unknown')}")
-
-            if not response['success']:
-                print(f"    ⚠️  API 호출 실패 ({provider}/{model}): {response['error']}")
-                return {
-                    'error': response['error'],
-                    'test_id': test_case.get('test_id', 'unknown'),
-                    'response_time': response['response_time']
-                }
-
-            # 결과 파싱
-            print(f"    🔧 Debug: 결과 파싱 시작...")
-            findings = agent.extract_key_findings(response['content'])
-            print(f"    🔧 Debug: 파싱 완료, valid_json={findings.get('valid_json', 'unknown')}")
-            if not findings.get('valid_json', False) and 'qwen3' in model:
-                print(f"    🔧 Debug Qwen3 응답: {response['content'][:500]}...")
-
-            # 실제 양자 취약 알고리즘 수 계산
-            print(f"    🔧 Debug: 양자 취약 알고리즘 추출 시작...")
-            detected_quantum_vulnerable_algorithms = []
-            if findings['valid_json']:
-                analysis_results = findings['analysis_results']
-
-                # 각 분석 결과에서 실제 양자 취약 알고리즘 추출
-                for category, result in analysis_results.items():
-                    if result and result.lower() not in ['none', 'not detected', 'no', '', 'not present', 'no implementations']:
-                        # 알고리즘 이름 추출 (새로운 형식에 맞게)
-                        import re
-                        result_lower = result.lower()
-
-                        # "DETECTED:" 형식으로 응답하는 경우만 처리
-                        if result_lower.startswith('detected:'):
-                            # "DETECTED: RSA" → "RSA" 추출
-                            detected_algo = result.split(':', 1)[1].strip()
-                            detected_quantum_vulnerable_algorithms.append(detected_algo.upper())
-                        else:
-                            # 기존 방식도 유지 (하위 호환성)
-                            # 부정적 표현 체크
-                            negative_indicators = ['does not contain', 'not contain', 'no implementation', 'not found', 'absent', 'missing', 'not present', 'free from', 'not detected']
-                            has_negative_indicator = any(indicator in result_lower for indicator in negative_indicators)
-
-                            if has_negative_indicator:
-                                continue  # 부정적 응답은 건너뛰기
-
-                            # 주요 양자 취약 알고리즘들 체크 (우선순위 순으로 정렬)
-                            quantum_vulnerable_algos = [
-                                # 길이가 긴 것부터 체크하여 중복 방지
-                                ('diffie-hellman', 'DH'), ('ecdsa', 'ECDSA'), ('ecdh', 'ECDH'),
-                                ('aes-128', 'AES-128'), ('3des', '3DES'), ('sha-1', 'SHA-1'),
-                                ('has-160', 'HAS-160'), ('kcdsa', 'KCDSA'), ('a5/1', 'A5/1'),
-                                ('misty1', 'MISTY1'), ('twofish', 'Twofish'), ('blowfish', 'Blowfish'),
-                                ('skipjack', 'Skipjack'), ('elgamal', 'ElGamal'), ('trivium', 'Trivium'),
-                                ('whirlpool', 'Whirlpool'), ('tiger', 'Tiger'), ('cast', 'CAST'),
-                                ('idea', 'IDEA'), ('rsa', 'RSA'), ('ecc', 'ECC'), ('dsa', 'DSA'),
-                                ('dh', 'DH'), ('aes', 'AES'), ('des', 'DES'), ('rc4', 'RC4'),
-                                ('md5', 'MD5'), ('sha1', 'SHA-1'), ('seed', 'SEED'), ('aria', 'ARIA'),
-                                ('hight', 'HIGHT'), ('lea', 'LEA'), ('lsh', 'LSH'), ('a5', 'A5')
-                            ]
-
-                            # 명확한 탐지 표현이 있는 경우만 카운팅
-                            positive_indicators = ['detected', 'found', 'implementation', 'algorithm', 'cipher', 'present', 'identified', 'system', 'used', 'exists', 'contains']
-                            has_positive_indicator = any(indicator in result_lower for indicator in positive_indicators)
-
-                            if has_positive_indicator:
-                                for algo_pattern, display_name in quantum_vulnerable_algos:
-                                    # 정확한 단어 매칭 (경계 포함)
-                                    if re.search(r'\b' + re.escape(algo_pattern) + r'\b', result_lower):
-                                        if display_name not in detected_quantum_vulnerable_algorithms:
-                                            detected_quantum_vulnerable_algorithms.append(display_name)
-                                            break  # 첫 번째 매치만 사용
-
+            responses, chunk_findings = [], []
+            interrogation = None
+            for chunk in chunks:
+                token.raise_if_cancelled()
+                if interrogating and self.interrogation['protocol'] == 'tools':
+                    response, interrogation = run_tools(
+                        client, lambda view: agent.create_prompt(view, exemplars=exemplars),
+                        SampleWorkspace.from_test_case(test_case), self.interrogation, max_tokens, token)
+                elif interrogating:
+                    response, interrogation = interrogate(
+                        client, lambda view: agent.create_prompt(view, exemplars=exemplars), input_data,
+                        Path(test_case.get('file_path', '')).suffix, self.interrogation, max_tokens, token)
+                else:
+                    prompt = agent.create_prompt(chunk_input(chunk, len(chunks)) if chunk else input_data,
+                                                 exemplars=exemplars)
+                    response = client.benchmark_request(prompt, max_tokens)
+                logger.debug(f"API 호출 완료, success={response.get('success', 'unknown')}")
+
+                if not response['success']:
+                    print(f"    ⚠️  API 호출 실패 ({provider}/{model}): {response['error']}")
+                    return {
+                        'error': response['error'],
+                        'test_id': test_case.get('test_id', 'unknown'),
+                        'response_time': response['response_time'],
+                        'transcript': recorder.save(test_case, provider, model, agent_type) if recorder else None
+                    }
+
+                # 결과 파싱
+                with span('parse', agent_type=agent_type) as parse_span:
+                    findings = agent.extract_key_findings(response['content'])
+                    parse_span.set(valid_json=findings.get('valid_json'))
+                if not findings.get('valid_json', False) and 'qwen3' in model:
+                    logger.debug(f"Qwen3 응답: {response['content'][:500]}...")
+                responses.append(response)
+                chunk_findings.append(findings)
+
+            # 배치 수집 단계: 프롬프트만 모으고 채점은 배치 응답을 받은 뒤
+            if self.batch_inference and self.batch_inference.collecting:
+                return {'test_id': test_case.get('test_id', 'unknown'), 'batch_requests': len(chunks)}
+
+            # 창별 보고를 하나의 탐지 보고로 (근거 줄 번호는 원본 파일 기준으로 되돌림)
+            raw_findings = [
+                FindingsMerger.make_finding(algo, span=remap_span(evidence_span(evidence), chunk),
+                                            confidence=findings.get('confidence_score', 0.0),
+                                            source=f"chunk{chunk['index']}" if chunk else 'llm')
+                for chunk, findings in zip(chunks, chunk_findings)
+                for algo, evidence in self._detected_algorithms(findings)
+            ]
+            # 영역별 순위 후보 (줄 번호는 원본 파일 기준으로 되돌림)
+            candidates = [dict(candidate, span=remap_span(candidate['span'], chunk))
+                          for chunk, findings in zip(chunks, chunk_findings)
+                          for candidate in parse_candidates(findings.get('candidates'))]
+            if chunk_plan:
+                response = merge_chunk_responses(responses)
+                findings = merge_chunk_findings(chunk_findings)
+
+            # 중복 보고 병합 (채점 전 일관된 정책 적용, 창 겹침 구간의 중복 보고 포함)
+            merged_findings = self.merger.merge(raw_findings)
+            detected_quantum_vulnerable_algorithms = FindingsMerger.algorithms(merged_findings)
             detected_quantum_vulnerable_count = len(detected_quantum_vulnerable_algorithms)
 
             # Success 평가: Ground truth와 비교하여 정확도 계산
             success = False
             accuracy_score = 0.0
-
+            hierarchical_scores = None
+            predictions = None
+            quantum_safe_check = None
+            korean = None
+            hunk_scores = None
+            weakness_scores = None
+            top_k_scores = None
+            function_scores = None
+            rationale_score = None
+            label_judge = None
+            label_source = 'parser'
+
+            # judge 라벨 매핑: 응답 원문을 분류 체계 라벨로 옮기고 파서 라벨과 비교
+            token.raise_if_cancelled()
+            if self.label_judge['enabled']:
+                with span('label_judge', mode=self.label_judge['mode']):
+                    label_judge = judge_result(
+                        self._label_judge(), findings.get('raw_response', response.get('content', '')),
+                        detected_quantum_vulnerable_algorithms if findings['valid_json'] else None,
+                        ground_truth, task_hierarchy_policy(task, self.hierarchy_policy)
+                    )
+
+            scoring = start_span('score', task=task)
             if findings['valid_json']:
-                # Ground truth 로드
-                ground_truth = self._load_ground_truth(test_case, agent_type)
                 if ground_truth:
                     try:
-                        from utils.metrics_calculator import MetricsCalculator
+                        from utils.metrics_calculator import SUCCESS_THRESHOLD, MetricsCalculator
                         accuracy_score = MetricsCalculator.calculate_accuracy(findings, ground_truth)
-                        print(f"    🔧 Debug: 정확도 계산 완료: {accuracy_score:.3f}")
+                        logger.debug(f"정확도 계산 완료: {accuracy_score:.3f}")
                         # 60% 이상 정확도면 성공으로 간주
-                        success = accuracy_score >= 0.6
+                        success = accuracy_score >= SUCCESS_THRESHOLD
+
+                        # 계층 라벨 채점: 탐지기가 출력한 단계 그대로 평가
+                        # (manifest-inference처럼 과제별 정책이 있으면 그 정책으로)
+                        native_labels = findings.get('labels') if isinstance(findings.get('labels'), list) else []
+                        hierarchical_scores = MetricsCalculator.calculate_hierarchical_scores(
+                            detected_quantum_vulnerable_algorithms + native_labels,
+                            ground_truth,
+                            task_hierarchy_policy(task, self.hierarchy_policy)
+                        )
+                        # 보정/임계값 분석용: 보고 라벨마다 응답 신뢰도와 정답 여부 (utils/calibration.py)
+                        predictions = MetricsCalculator.calculate_prediction_credits(
+                            label_confidences(merged_findings, [(label, findings.get('confidence_score', 0.0))
+                                                                for label in native_labels]),
+                            ground_truth,
+                            task_hierarchy_policy(task, self.hierarchy_policy)
+                        )
+                        # 양자 내성 알고리즘을 취약으로 보고했는지 (PQC 샘플만)
+                        quantum_safe_check = MetricsCalculator.calculate_quantum_safe_misclassification(
+                            detected_quantum_vulnerable_algorithms + native_labels,
+                            ground_truth
+                        )
+                        # 한국 계열(SEED/ARIA/…)을 그 이름으로 찾았는지, 국제 대응 계열과 혼동했는지
+                        korean = korean_check(detected_quantum_vulnerable_algorithms + native_labels, ground_truth)
+                        # diff-introduction: 보고한 도입 위치를 정답 커밋/hunk와 비교
+                        if task == TASK_DIFF:
+                            hunk_scores = calculate_hunk_scores(
+                                findings.get('introductions') or [],
+                                ground_truth,
+                                parse_patch(test_case.get('input_data', '')),
+                                task_hierarchy_policy(task, self.hierarchy_policy)
+                            )
+                        # weakness-detection: 보고한 운용 모드/약점을 ground truth의 mode_findings와 비교
+                        if score_weaknesses:
+                            weakness_scores = calculate_weakness_scores(findings.get('weaknesses') or [],
+                                                                        ground_truth)
+                        # 순위 후보 top-k: 후보를 보고하지 않은 응답은 탐지 하나하나를 1순위 후보로
+                        if self.top_k['enabled']:
+                            top_k_scores = calculate_top_k_scores(
+                                candidates or finding_candidates(merged_findings), ground_truth,
+                                task_hierarchy_policy(task, self.hierarchy_policy)
+                            )
+                        # 함수 단위: 근거 줄이 있는 보고를 함수에 붙여 정답 줄 범위가 걸친 함수와 비교
+                        if Path(test_case.get('file_path', '')).is_file():
+                            function_scores = calculate_function_scores(
+                                merged_findings, ground_truth,
+                                SampleIndex.from_text(test_case.get('input_data', ''),
+                                                      Path(test_case['file_path']).suffix)
+                            )
+                        # 근거 채점: 정답 알고리즘의 식별 증거를 인용했는지 (rubric 또는 judge)
+                        if self.rationale['enabled']:
+                            rationale_score = score_rationale(findings.get('rationale'), ground_truth,
+                                                              test_case.get('input_data', ''),
+                                                              self._rationale_judge())
                     except Exception as metric_error:
                         print(f"    ❌ 정확도 계산 실패: {metric_error}")
                         accuracy_score = 0.0
@@ -247,10 +563,36 @@ class BenchmarkRunner:
                     # Ground truth가 없으면 실패로 처리 (정답을 알 수 없으므로)
                     print(f"    ⚠️  Ground truth가 없어서 성공 여부를 판단할 수 없습니다.")
                     success = False
+            elif (self.label_judge['mode'] == 'fallback' and label_judge and label_judge['labels'] is not None
+                  and ground_truth):
+                # 파서가 읽지 못한 자유 서술 응답은 judge 라벨로 채점
+                from utils.metrics_calculator import SUCCESS_THRESHOLD, MetricsCalculator
+                label_source = 'judge'
+                detected_quantum_vulnerable_algorithms = label_judge['labels']
+                detected_quantum_vulnerable_count = len(detected_quantum_vulnerable_algorithms)
+                accuracy_score = MetricsCalculator.calculate_accuracy({
+                    'valid_json': True,
+                    'analysis_results': {label.lower(): f"DETECTED: {label}" for label in label_judge['labels']}
+                }, ground_truth)
+                success = accuracy_score >= SUCCESS_THRESHOLD
+                hierarchical_scores = label_judge['hierarchical_scores']
+                quantum_safe_check = MetricsCalculator.calculate_quantum_safe_misclassification(
+                    detected_quantum_vulnerable_algorithms, ground_truth
+                )
+                korean = korean_check(detected_quantum_vulnerable_algorithms, ground_truth)
             else:
                 success = False
 
-            print(f"    🔧 Debug: 결과 구성 시작...")
+            # 판단 보류: 기존 지표에서는 틀린 답과 같은 0점 (정상 샘플에서 빈 라벨로 만점을 받지 않도록),
+            # 선택적 지표(coverage, 답한 샘플의 F1)에서는 답하지 않은 샘플로 따로 셈
+            abstained = findings.get('valid_json', False) and is_abstention(findings.get('verdict'))
+            if abstained and ground_truth:
+                success, accuracy_score, predictions = False, 0.0, []
+                hierarchical_scores = abstained_scores(ground_truth, task_hierarchy_policy(task, self.hierarchy_policy))
+
+            scoring.set(accuracy=round(accuracy_score, 3), label_source=label_source)
+            scoring.finish()
+
             result = {
                 'test_id': test_case.get('test_id', 'unknown'),
                 'provider': provider,
@@ -259,43 +601,128 @@ class BenchmarkRunner:
                 'success': success,
                 'accuracy_score': accuracy_score,
                 'valid_json': findings.get('valid_json', False),
+                'parse_status': findings.get('parse_status'),
+                'parse_repairs': findings.get('parse_repairs', []),
                 'confidence_score': findings.get('confidence_score', 0.0),
                 'detected_quantum_vulnerable_count': detected_quantum_vulnerable_count,
                 'detected_algorithms': detected_quantum_vulnerable_algorithms,
+                'findings': merged_findings,
+                'abstained': abstained,
+                'hierarchical_scores': hierarchical_scores,
+                'predictions': predictions,
+                'quantum_safe_check': quantum_safe_check,
+                'korean_check': korean,
+                'hunk_scores': hunk_scores,
+                'weaknesses': findings.get('weaknesses') or [],
+                'weakness_scores': weakness_scores,
+                'candidates': candidates,
+                'top_k_scores': top_k_scores,
+                'function_scores': function_scores,
+                'rationale': findings.get('rationale'),
+                'rationale_score': rationale_score,
+                'label_judge': label_judge,
+                'label_source': label_source,
+                'interrogation': interrogation,
+                'ground_truth_hash': ground_truth_hash(ground_truth),
+                'fidelity': sample_fidelity(ground_truth),
+                'documentation': documentation_kind(ground_truth),
+                'provenance': provenance_kind(ground_truth),
+                'task': task,
                 'response_time': response.get('response_time', 0.0),
                 'json_valid': response.get('json_valid', False),
                 'summary': findings.get('summary', ''),
                 'analysis_results': findings.get('analysis_results', {}),
                 'usage': response.get('usage', {}),
+                'cost_usd': sum(item.get('cost_usd', 0.0) for item in responses),
                 'file_path': test_case.get('file_path', ''),
+                'exemplars': [exemplar['test_id'] for exemplar in exemplars],
+                'chunking': {
+                    'policy': self.chunking,
+                    'chunks': [{key: chunk[key] for key in ('index', 'start_line', 'end_line', 'tokens')}
+                               for chunk in chunk_plan['chunks']],
+                    'dropped_lines': chunk_plan['dropped_lines']
+                } if chunk_plan else None,
                 'raw_response': findings.get('raw_response', response.get('content', '')),
+                'transcript': recorder.save(test_case, provider, model, agent_type) if recorder else None,
                 'timestamp': time.time()
             }
-            print(f"    🔧 Debug: 결과 구성 완료")
             return result
 
+        except Cancelled:
+            raise
         except Exception as e:
-            import traceback
-            error_details = f"{str(e)} | Traceback: {traceback.format_exc()}"
-            print(f"    ⚠️  예외 발생 ({provider}/{model}): {error_details}")
+            result = self._crash_result(provider, model, agent_type, test_case, e)
+            # 응답을 받은 뒤 파싱/채점에서 난 예외는 받은 응답을 보며 고칠 수 있게
+            result['transcript'] = recorder.save(test_case, provider, model, agent_type) if recorder else None
+            return result
 
-            return {
-                'test_id': test_case.get('test_id', 'unknown'),
-                'provider': provider,
-                'model': model,
-                'agent_type': agent_type,
-                'success': False,
-                'error': str(e),  # 사용자에게는 간단한 에러만 표시
-                'error_details': error_details,  # 상세 정보는 별도 필드
-                'response_time': 0,
-                'timestamp': time.time()
-            }
+    def _detected_algorithms(self, findings: Dict[str, Any]) -> List[Tuple[str, str]]:
+        """파싱 결과의 analysis_results에서 양자 취약 알고리즘 추출 [(알고리즘, 근거 문자열)]"""
+        detected_quantum_vulnerable_algorithms = []
+        evidence = []
+        if findings['valid_json']:
+            analysis_results = findings['analysis_results']
+
+            # 각 분석 결과에서 실제 양자 취약 알고리즘 추출
+            for category, result in analysis_results.items():
+                if result and result.lower() not in ['none', 'not detected', 'no', '', 'not present', 'no implementations']:
+                    # 알고리즘 이름 추출 (새로운 형식에 맞게)
+                    import re
+                    result_lower = result.lower()
+
+                    # "DETECTED:" 형식으로 응답하는 경우만 처리
+                    if result_lower.startswith('detected:'):
+                        # "DETECTED: RSA (Evidence: ...)" → "RSA" 추출
+                        # (근거 문구는 span 추출에만 쓰고 이름에서 떼어 창별 보고가 같은 키로 병합되게 함)
+                        detected_algo = re.split(r'\s*\(\s*evidence', result.split(':', 1)[1], flags=re.IGNORECASE)[0].strip()
+                        detected_quantum_vulnerable_algorithms.append(detected_algo.upper())
+                        evidence.append(result)
+                    else:
+                        # 기존 방식도 유지 (하위 호환성)
+                        # 부정적 표현 체크
+                        negative_indicators = ['does not contain', 'not contain', 'no implementation', 'not found', 'absent', 'missing', 'not present', 'free from', 'not detected']
+                        has_negative_indicator = any(indicator in result_lower for indicator in negative_indicators)
+
+                        if has_negative_indicator:
+                            continue  # 부정적 응답은 건너뛰기
+
+                        # 주요 양자 취약 알고리즘들 체크 (우선순위 순으로 정렬)
+                        quantum_vulnerable_algos = [
+                            # 길이가 긴 것부터 체크하여 중복 방지
+                            ('diffie-hellman', 'DH'), ('ecdsa', 'ECDSA'), ('ecdh', 'ECDH'),
+                            ('aes-128', 'AES-128'), ('3des', '3DES'), ('sha-1', 'SHA-1'),
+                            ('has-160', 'HAS-160'), ('kcdsa', 'KCDSA'), ('a5/1', 'A5/1'),
+                            ('misty1', 'MISTY1'), ('twofish', 'Twofish'), ('blowfish', 'Blowfish'),
+                            ('skipjack', 'Skipjack'), ('elgamal', 'ElGamal'), ('trivium', 'Trivium'),
+                            ('whirlpool', 'Whirlpool'), ('tiger', 'Tiger'), ('cast', 'CAST'),
+                            ('idea', 'IDEA'), ('rsa', 'RSA'), ('ecc', 'ECC'), ('dsa', 'DSA'),
+                            ('dh', 'DH'), ('aes', 'AES'), ('des', 'DES'), ('rc4', 'RC4'),
+                            ('md5', 'MD5'), ('sha1', 'SHA-1'), ('seed', 'SEED'), ('aria', 'ARIA'),
+                            ('hight', 'HIGHT'), ('lea', 'LEA'), ('lsh', 'LSH'), ('a5', 'A5')
+                        ]
+
+                        # 명확한 탐지 표현이 있는 경우만 카운팅
+                        positive_indicators = ['detected', 'found', 'implementation', 'algorithm', 'cipher', 'present', 'identified', 'system', 'used', 'exists', 'contains']
+                        has_positive_indicator = any(indicator in result_lower for indicator in positive_indicators)
+
+                        if has_positive_indicator:
+                            for algo_pattern, display_name in quantum_vulnerable_algos:
+                                # 정확한 단어 매칭 (경계 포함)
+                                if re.search(r'\b' + re.escape(algo_pattern) + r'\b', result_lower):
+                                    if display_name not in detected_quantum_vulnerable_algorithms:
+                                        detected_quantum_vulnerable_algorithms.append(display_name)
+                                        evidence.append(result)
+                                        break  # 첫 번째 매치만 사용
+
+        return list(zip(detected_quantum_vulnerable_algorithms, evidence))
 
     def run_benchmark(self, providers: List[str] = None, agents: List[str] = None,
                      test_limit: Optional[int] = None, parallel: bool = False) -> Dict[str, Any]:
         """전체 벤치마크 실행"""
         print("🚀 벤치마크 시작")
         print("=" * 60)
+        run_id = start_run()
+        self.cancellation = CancellationToken()  # 서버 모드에서는 실행마다 새 취소 신호
 
         # 기본값 설정
         if providers is None:
@@ -305,10 +732,18 @@ class BenchmarkRunner:
 
         available_models = self.get_available_models()
 
+        # 코퍼스 매니페스트: 결과에 남겨 다음 실행의 --changed-since 기준으로 씀
+        manifest = snapshot()
+        if self.changed_since:
+            self.changed_since['changes'] = diff_manifests(self.changed_since['previous'], manifest, agents)
+            print_changes(self.changed_since['changes'])
+
         # 에이전트별로 테스트 파일을 한 번만 로드
         agent_test_files = {}
         for agent_type in agents:
             agent_test_files[agent_type] = self.load_test_files(agent_type, test_limit)
+            if self.few_shot['enabled']:
+                agent_test_files[agent_type] = self._prepare_few_shot(agent_type, agent_test_files[agent_type])
 
         # 실행할 테스트 조합 생성
         test_combinations = []
@@ -326,12 +761,29 @@ class BenchmarkRunner:
 
         print(f"📊 총 {len(test_combinations)}개 테스트 조합")
 
-        # 병렬 또는 순차 실행
-        if parallel and len(test_combinations) > 1:
+        # 배치, 병렬 또는 순차 실행
+        self.batch_inference = None
+        if self.batch['enabled'] and self.interrogation['enabled']:
+            print("⚠️  다중 턴 질의는 턴마다 앞 응답이 필요해 배치로 제출할 수 없습니다. 동기 호출로 실행합니다.")
+        if self.batch['enabled'] and not self.interrogation['enabled']:
+            results = self._run_batch_tests(test_combinations, parallel)
+        elif parallel and len(test_combinations) > 1:
             results = self._run_parallel_tests(test_combinations)
         else:
             results = self._run_sequential_tests(test_combinations)
 
+        # 그대로인 샘플의 이전 결과와 합침 (삭제·수정된 샘플의 이전 행은 버림)
+        incremental = None
+        if self.changed_since:
+            changes = self.changed_since['changes']
+            evaluated = len(results)
+            results = merge_results(self.changed_since['prior_results'], results, changes)
+            incremental = {'since': self.changed_since['path'], 'merged_from': self.changed_since['merge_path'],
+                           'previous_corpus_version': self.changed_since['previous'].get('corpus_version'),
+                           **{kind: len(keys) for kind, keys in changes.items()},
+                           'evaluated': evaluated, 'reused': len(results) - evaluated}
+            print(f"🔁 이번 실행 {evaluated}행 + 이전 결과 {len(results) - evaluated}행")
+
         # 결과 정리
         self.results = {
             'summary': self._generate_summary(results),
@@ -341,14 +793,149 @@ class BenchmarkRunner:
                 'timestamp': time.time(),
                 'providers': providers,
                 'agents': agents,
-                'test_limit': test_limit
+                'test_limit': test_limit,
+                'finding_merge_policy': self.merger.policy,
+                'hierarchy_policy': self.hierarchy_policy,
+                'rubric': self._rubric_stamp(),
+                'canary_generation': current_generation(),
+                'corpus_version': corpus_version(results),
+                'corpus_manifest': manifest,
+                'environment': self._environment(manifest['corpus_version'], available_models, providers, agents),
+                'incremental': incremental,
+                'few_shot': self.few_shot,
+                'chunking': self.chunking,
+                'run_id': run_id,
+                'stage_latency': STAGE_LATENCY.report(),
+                'sample_timeout': self.sample_timeout,
+                'quota': {'rate_limits': self.rate_limiter.report(), 'cost': self.costs.report()},
+                'cancelled': self.cancellation.reason or None,
+                'subset': {'name': self.subset['name'], 'samples': len(self.subset['samples']),
+                           'digest': subset_digest(self.subset)} if self.subset else None,
+                'filter': str(self.sample_filter) if self.sample_filter else None,
+                'rationale': self.rationale,
+                'weakness_detection': self.weakness_detection,
+                'top_k': self.top_k,
+                'abstention': self.abstention,
+                'label_judge': self.label_judge,
+                'batch': self.batch_inference.report() if self.batch_inference else None,
+                'interrogation': self.interrogation,
+                'transcripts': self.transcripts,
+                'network': NetworkGuard.get_report()
             }
         }
 
         return self.results
 
-    def _run_sequential_tests(self, test_combinations: List) -> List[Dict[str, Any]]:
-        """순차 테스트 실행"""
+    def _environment(self, corpus_manifest: str, available_models: Dict[str, List[str]], providers: List[str],
+                     agents: List[str]) -> Dict[str, Any]:
+        """재현용 실행 환경 (utils/environment.py: 버전, 코퍼스, 모델 설정, 프롬프트 해시, 시드, 호스트)"""
+        detectors = {}
+        for provider in providers:
+            for model in available_models.get(provider) or []:
+                detectors[f"{provider}/{model}"] = model_settings(
+                    provider, model, self.config_loader.get_llm_config(provider, model_name=model))
+        prompt_agents = {}
+        for agent_type in agents:
+            agent = AgentFactory.create_agent(agent_type)
+            agent.request_rationale = self.rationale['enabled']
+            agent.request_weaknesses = self.weakness_detection['enabled']
+            agent.request_candidates = self.top_k['enabled']
+            agent.request_verdict = self.abstention['enabled']
+            prompt_agents[agent_type] = agent
+        seeds = {}
+        if self.few_shot['enabled']:
+            seeds['few_shot'] = self.few_shot['seed']
+        if self.subset:
+            seeds['subset'] = (self.subset.get('criteria') or {}).get('seed')
+        return capture_environment(corpus_manifest, detectors, prompt_hashes(prompt_agents), seeds)
+
+    def _rubric_stamp(self) -> Optional[Dict[str, str]]:
+        """이 실행의 채점 규칙 버전과 digest (python -m utils.rubric export로 내보낸 rubric과 비교)"""
+        weights_path = self.config_loader.get_benchmark_config().get('composite_weights', WEIGHTS_PATH)
+        try:
+            return rubric_stamp(weights_path, self.hierarchy_policy)
+        except Exception as e:
+            print(f"⚠️  채점 규칙 digest 계산 실패: {e}")
+            return None
+
+    def _rationale_judge(self) -> Optional[RationaleJudge]:
+        """judge 채점기 (scorer가 judge일 때만, 처음 필요할 때 생성)"""
+        if self.rationale['scorer'] != 'judge':
+            return None
+        with self.lock:
+            if self.rationale_judge is None:
+                self.rationale_judge = create_judge(self.rationale, self.config_loader)
+                self.rationale_judge.client = MeteredClient(
+                    self.rationale_judge.client, self.rationale['judge']['provider'],
+                    self.rationale['judge']['model'], self.rate_limiter, self.costs, self.cancellation
+                )
+        return self.rationale_judge
+
+    def _label_judge(self) -> LabelJudge:
+        """라벨 매핑 judge (처음 필요할 때 생성)"""
+        with self.lock:
+            if self.label_judge_client is None:
+                self.label_judge_client = create_label_judge(self.label_judge, self.config_loader)
+                self.label_judge_client.client = MeteredClient(
+                    self.label_judge_client.client, self.label_judge['provider'], self.label_judge['model'],
+                    self.rate_limiter, self.costs, self.cancellation
+                )
+        return self.label_judge_client
+
+    def _prepare_few_shot(self, agent_type: str, test_cases: List[Dict[str, Any]]) -> List[Dict[str, Any]]:
+        """train 분할로 예시 후보를 만들고, 평가 대상은 test 분할만 남김"""
+        all_cases = self.test_manager.load_test_cases(agent_type)
+        ground_truths = {
+            case['test_id']: self.test_manager.load_ground_truth(agent_type, case['test_id'])
+            for case in all_cases
+        }
+        sampler = ExemplarSampler(agent_type, all_cases, ground_truths, self.few_shot['strategy'],
+                                  self.few_shot['k'], self.few_shot['seed'], self.few_shot['max_chars'])
+        self.exemplar_samplers[agent_type] = sampler
+
+        evaluated = [
+            case for case in test_cases
+            if sample_split(agent_type, case['test_id'], ground_truths.get(case['test_id'])) == SPLIT_TEST
+        ]
+        print(f"🧩 {agent_type}: few-shot 예시 후보 {len(sampler.pool)}개 (train), "
+              f"평가 {len(evaluated)}개 (test, {len(test_cases) - len(evaluated)}개 제외)")
+        return evaluated
+
+    def _run_batch_tests(self, test_combinations: List, parallel: bool = False) -> List[Dict[str, Any]]:
+        """배치 모드: 배치 프로바이더 샘플의 프롬프트를 모아 (프로바이더, 모델)마다 배치 작업으로 제출하고,
+        응답을 받은 뒤 동기 실행과 같은 경로로 채점 (나머지 프로바이더는 평소처럼 실행)"""
+        self.batch_inference = BatchInference(self.batch, self.costs)
+        batched = [combination for combination in test_combinations if combination[0] in BATCH_PROVIDERS]
+        others = [combination for combination in test_combinations if combination[0] not in BATCH_PROVIDERS]
+
+        results = []
+        if batched:
+            print(f"📦 배치 모드: {len(batched)}개 테스트의 프롬프트 수집")
+            self.batch_inference.collecting = True
+            try:
+                for provider, model, agent_type, test_case in batched:
+                    self._run_single_test(provider, model, agent_type, test_case, self.cancellation.child())
+            finally:
+                self.batch_inference.collecting = False
+            try:
+                for provider, model in self.batch_inference.pending():
+                    self.batch_inference.run(provider, model, self._create_client(provider, model),
+                                             self.cancellation)
+            except (Cancelled, KeyboardInterrupt):
+                self._interrupt(len(test_combinations))
+                print(f"   제출한 배치 작업은 {self.batch['jobs_dir']}에 저장되어 다시 실행하면 이어서 기다립니다.")
+                return results
+            results = self._run_sequential_tests(batched, delay=False)
+
+        if others and not self.cancellation.cancelled:
+            if parallel and len(others) > 1:
+                results += self._run_parallel_tests(others)
+            else:
+                results += self._run_sequential_tests(others)
+        return results
+
+    def _run_sequential_tests(self, test_combinations: List, delay: bool = True) -> List[Dict[str, Any]]:
+        """순차 테스트 실행 (delay: 원격 호출 사이 1초 대기, 배치 응답 채점에서는 생략)"""
         results = []
         backup_interval = 10  # 10개 테스트마다 백업
 
@@ -356,7 +943,13 @@ class BenchmarkRunner:
             print(f"\n📋 테스트 {i}/{len(test_combinations)}: {provider}/{model}/{agent_type}")
             print(f"    파일: {test_case.get('test_id', 'unknown')}")
 
-            result = self.run_single_test(provider, model, agent_type, test_case)
+            try:
+                result = self.run_single_test(provider, model, agent_type, test_case)
+            except KeyboardInterrupt:
+                self._interrupt(len(test_combinations) - i + 1)
+                break
+            if result is None:
+                break
             results.append(result)
 
             if result.get('success'):
@@ -387,11 +980,20 @@ class BenchmarkRunner:
                 self._backup_intermediate_results(results, i, len(test_combinations))
 
             # API 제한 방지를 위한 딜레이
-            if provider != 'ollama' and i < len(test_combinations):
-                time.sleep(1)
+            if delay and provider != 'ollama' and i < len(test_combinations):
+                try:
+                    time.sleep(1)
+                except KeyboardInterrupt:
+                    self._interrupt(len(test_combinations) - i)
+                    break
 
         return results
 
+    def _interrupt(self, remaining: int):
+        """사용자 중단: 실행 중인 샘플에 취소 신호를 보내고 지금까지의 결과로 요약"""
+        self.cancellation.cancel(INTERRUPTED_BY_USER)
+        print(f"\n⚠️  사용자 중단: 남은 {remaining}개 테스트를 건너뛰고 지금까지의 결과로 요약합니다.")
+
     def _backup_intermediate_results(self, results: List[Dict[str, Any]], current: int, total: int):
         """중간 결과 백업 (Google Drive)"""
         gdrive_dir = os.environ.get('GDRIVE_RESULTS_DIR')
@@ -434,25 +1036,25 @@ class BenchmarkRunner:
                 )
                 future_to_test[future] = (provider, model, agent_type, test_case)
 
-            for i, future in enumerate(as_completed(future_to_test), 1):
-                provider, model, agent_type, test_case = future_to_test[future]
+            try:
+                for i, future in enumerate(as_completed(future_to_test), 1):
+                    provider, model, agent_type, test_case = future_to_test[future]
 
-                try:
-                    result = future.result()
-                    results.append(result)
+                    try:
+                        result = future.result()
+                        if result is None:
+                            continue
+                        results.append(result)
 
-                    print(f"✅ 완료 {i}/{len(test_combinations)}: {provider}/{model}/{agent_type}")
+                        print(f"✅ 완료 {i}/{len(test_combinations)}: {provider}/{model}/{agent_type}")
 
-                except Exception as e:
-                    print(f"❌ 실패 {i}/{len(test_combinations)}: {provider}/{model}/{agent_type} - {e}")
-                    results.append({
-                        'provider': provider,
-                        'model': model,
-                        'agent_type': agent_type,
-                        'test_id': test_case.get('test_id', 'unknown'),
-                        'success': False,
-                        'error': str(e)
-                    })
+                    except Exception as e:
+                        print(f"❌ 실패 {i}/{len(test_combinations)}: {provider}/{model}/{agent_type} - {e}")
+                        results.append(self._crash_result(provider, model, agent_type, test_case, e))
+            except KeyboardInterrupt:
+                # 대기 중인 샘플은 취소하고, 실행 중인 샘플은 다음 확인 지점에서 멈춤
+                self._interrupt(len(test_combinations) - len(results))
+                executor.shutdown(wait=False, cancel_futures=True)
 
         return results
 
@@ -461,20 +1063,48 @@ class BenchmarkRunner:
         total = len(results)
         successful = len([r for r in results if r.get('success', False)])
 
+        parsed = [r for r in results if r.get('parse_status')]
+        parse_failures = len([r for r in parsed if r['parse_status'] == 'failed'])
+        parse_repaired = len([r for r in parsed if r['parse_status'] == 'repaired'])
+
         summary = {
             'total_tests': total,
             'successful_tests': successful,
             'success_rate': successful / total if total > 0 else 0,
+            'parse_failure_rate': parse_failures / len(parsed) if parsed else 0,
+            'parse_repair_rate': parse_repaired / len(parsed) if parsed else 0,
+            'by_status': {status: 0 for status in RESULT_STATUSES},
             'by_provider': {},
             'by_agent': {},
-            'by_model': {}
+            'by_model': {},
+            'by_fidelity': {},
+            'by_task': {},
+            'by_documentation': {},
+            'by_provenance': {},
+            'by_language': {},
+            'by_role': {},
+            'by_weakness': {},
+            'by_usage': {},
+            'by_attribute': {},
+            'quantum_safe': {'samples': 0, 'labels': 0, 'misclassified': 0, 'misclassified_samples': 0},
+            'hunk_localization': {'samples': 0, 'labels': 0, 'hunk_hits': 0, 'commit_hits': 0, 'mislocated': 0},
+            'weakness_detection': {'samples': 0, 'expected': 0, 'reported': 0, 'matched': 0,
+                                   'modes_expected': 0, 'modes_correct': 0},
+            'secret_hygiene': {'samples': 0, 'expected': 0, 'reported': 0, 'matched': 0},
+            'timing_safety': {'samples': 0, 'expected': 0, 'reported': 0, 'matched': 0, 'false_alarms': 0},
+            'model_comparisons': []
         }
 
+        # 모델별 샘플 단위 계층 F1 (신뢰구간과 모델 간 유의성 검정에 사용)
+        f1_by_model: Dict[str, Dict[tuple, float]] = {}
+
         # 프로바이더별 통계
         for result in results:
             provider = result.get('provider', 'unknown')
             agent = result.get('agent_type', 'unknown')
             model = result.get('model', 'unknown')
+            status = result_status(result)
+            summary['by_status'][status] += 1
 
             # 프로바이더별
             if provider not in summary['by_provider']:
@@ -501,10 +1131,124 @@ class BenchmarkRunner:
             # 모델별
             model_key = f"{provider}/{model}"
             if model_key not in summary['by_model']:
-                summary['by_model'][model_key] = {'total': 0, 'successful': 0}
-            summary['by_model'][model_key]['total'] += 1
+                summary['by_model'][model_key] = {
+                    'total': 0, 'successful': 0, 'parsed': 0, 'parse_failures': 0,
+                    'statuses': {status: 0 for status in RESULT_STATUSES}, 'cost_usd': 0.0
+                }
+            m_stats = summary['by_model'][model_key]
+            m_stats['total'] += 1
+            m_stats['cost_usd'] += result.get('cost_usd', 0.0)
+            m_stats['statuses'][status] += 1
+            if result.get('hierarchical_scores'):
+                f1_by_model.setdefault(model_key, {})[(agent, result.get('test_id'))] = \
+                    result['hierarchical_scores'].get('f1', 0.0)
+            elif status != STATUS_OK and result.get('ground_truth_hash'):
+                # 시간 초과/오류/파싱 실패 샘플은 빼지 않고 0점 (빼면 실패가 잦은 모델이 유리해짐)
+                f1_by_model.setdefault(model_key, {})[(agent, result.get('test_id'))] = 0.0
             if result.get('success'):
-                summary['by_model'][model_key]['successful'] += 1
+                m_stats['successful'] += 1
+            if result.get('parse_status'):
+                m_stats['parsed'] += 1
+                if result['parse_status'] == 'failed':
+                    m_stats['parse_failures'] += 1
+
+            # 샘플 구현 충실도별 (exact / structurally-faithful / stylized)
+            if result.get('fidelity'):
+                f_stats = summary['by_fidelity'].setdefault(
+                    result['fidelity'], {'total': 0, 'successful': 0, 'avg_accuracy': 0}
+                )
+                f_stats['total'] += 1
+                f_stats['avg_accuracy'] += result.get('accuracy_score', 0)
+                if result.get('success'):
+                    f_stats['successful'] += 1
+
+            # 과제 유형별 (detection / manifest-inference)
+            if result.get('task'):
+                t_stats = summary['by_task'].setdefault(
+                    result['task'], {'total': 0, 'successful': 0, 'avg_accuracy': 0, 'avg_f1': 0}
+                )
+                t_stats['total'] += 1
+                t_stats['avg_accuracy'] += result.get('accuracy_score', 0)
+                t_stats['avg_f1'] += (result.get('hierarchical_scores') or {}).get('f1', 0)
+                if result.get('success'):
+                    t_stats['successful'] += 1
+
+            # 샘플 문서 종류별 (adversarial: 코드와 다른 일을 주장하는 주석이 주입된 샘플 / plain)
+            if result.get('documentation'):
+                d_stats = summary['by_documentation'].setdefault(
+                    result['documentation'], {'total': 0, 'successful': 0, 'avg_accuracy': 0, 'avg_f1': 0}
+                )
+                d_stats['total'] += 1
+                d_stats['avg_accuracy'] += result.get('accuracy_score', 0)
+                d_stats['avg_f1'] += (result.get('hierarchical_scores') or {}).get('f1', 0)
+                if result.get('success'):
+                    d_stats['successful'] += 1
+
+            # 샘플 출처별 (hand-written / generated-from-template / adapted-from-oss) — 원본 코드를 학습했을 수 있는 샘플 분리
+            if result.get('provenance'):
+                p_stats = summary['by_provenance'].setdefault(
+                    result['provenance'], {'total': 0, 'successful': 0, 'avg_accuracy': 0, 'avg_f1': 0}
+                )
+                p_stats['total'] += 1
+                p_stats['avg_accuracy'] += result.get('accuracy_score', 0)
+                p_stats['avg_f1'] += (result.get('hierarchical_scores') or {}).get('f1', 0)
+                if result.get('success'):
+                    p_stats['successful'] += 1
+
+            # 기본 요소 역할별 (cipher / mac / hash / kdf ...) — 같은 샘플의 암호와 MAC을 따로 집계
+            # 라벨 취약성 범주별 (자체 구성 KDF vs 표준 KDF 등)도 같은 기준으로 집계
+            # 라벨 사용 형태별 (직접 구현 vs 외부 라이브러리 호출)도 같은 기준으로 집계
+            for match in (result.get('hierarchical_scores') or {}).get('matches', []):
+                # 같은 범주의 다른 계열(예: GHASH 자리에 AES)로 받은 부분 점수는 탐지로 치지 않음
+                family_hit = bool(match.get('predicted')) and \
+                    match['predicted'].split('/')[:2] == match['expected'].split('/')[:2]
+                for key, value in (('by_role', match.get('role')), ('by_weakness', match.get('weakness')),
+                                   ('by_usage', match.get('usage'))):
+                    g_stats = summary[key].setdefault(value or 'unknown', {'labels': 0, 'detected': 0, 'credit': 0.0})
+                    g_stats['labels'] += 1
+                    g_stats['credit'] += match['credit']
+                    if family_hit:
+                        g_stats['detected'] += 1
+
+            # 양자 내성(PQC) 라벨을 취약으로 잘못 보고한 비율
+            quantum_safe_check = result.get('quantum_safe_check')
+            if quantum_safe_check:
+                q_stats = summary['quantum_safe']
+                q_stats['samples'] += 1
+                q_stats['labels'] += len(quantum_safe_check['safe_labels'])
+                q_stats['misclassified'] += len(quantum_safe_check['misclassified'])
+                if quantum_safe_check['misclassified']:
+                    q_stats['misclassified_samples'] += 1
+
+            # diff-introduction 샘플의 도입 위치(커밋/hunk) 적중
+            hunk_scores = result.get('hunk_scores')
+            if hunk_scores:
+                h_stats = summary['hunk_localization']
+                h_stats['samples'] += 1
+                for key in ('labels', 'hunk_hits', 'commit_hits', 'mislocated'):
+                    h_stats[key] += hunk_scores[key]
+
+            # 운용 모드/구현 약점 (알고리즘 식별과 별개 트랙)
+            weakness_scores = result.get('weakness_scores')
+            if weakness_scores:
+                w_stats = summary['weakness_detection']
+                w_stats['samples'] += 1
+                for key in ('expected', 'reported', 'matched', 'modes_expected', 'modes_correct'):
+                    w_stats[key] += weakness_scores[key]
+                # 비밀 취급 약점이 기록되었거나 보고된 샘플만 (secret-hygiene 하위 트랙)
+                hygiene = weakness_scores.get('secret_hygiene')
+                if hygiene and (hygiene['expected'] or hygiene['reported']):
+                    s_stats = summary['secret_hygiene']
+                    s_stats['samples'] += 1
+                    for key in ('expected', 'reported', 'matched'):
+                        s_stats[key] += hygiene[key]
+                # 타이밍 라벨이 있거나 타이밍 약점이 보고된 샘플만 (timing-safety 하위 트랙)
+                timing = weakness_scores.get('timing_safety')
+                if timing and (timing['labels'] or timing['expected'] or timing['reported']):
+                    t_stats = summary['timing_safety']
+                    t_stats['samples'] += 1
+                    for key in ('expected', 'reported', 'matched', 'false_alarms'):
+                        t_stats[key] += timing[key]
 
         # 평균 계산
         for provider, stats in summary['by_provider'].items():
@@ -514,6 +1258,134 @@ class BenchmarkRunner:
                 stats['avg_quantum_vulnerable'] /= stats['successful']
                 stats['success_rate'] = stats['successful'] / stats['total']
 
+        for model_key, stats in summary['by_model'].items():
+            stats['parse_failure_rate'] = stats['parse_failures'] / stats['parsed'] if stats['parsed'] else 0
+            # 샘플 재표본 부트스트랩 95% 신뢰구간 (점 추정만으로는 순위 주장을 할 수 없으므로)
+            if model_key in f1_by_model:
+                stats['hierarchical_f1'] = bootstrap_ci(list(f1_by_model[model_key].values()))
+
+        # 같은 샘플에서 채점된 모델 쌍마다 paired permutation test
+        if len(f1_by_model) > 1:
+            summary['model_comparisons'] = compare_detectors(f1_by_model)
+
+        for fidelity, stats in summary['by_fidelity'].items():
+            stats['avg_accuracy'] /= stats['total']
+            stats['success_rate'] = stats['successful'] / stats['total']
+
+        for stats in list(summary['by_task'].values()) + list(summary['by_documentation'].values()) + \
+                list(summary['by_provenance'].values()):
+            stats['avg_accuracy'] /= stats['total']
+            stats['avg_f1'] /= stats['total']
+            stats['success_rate'] = stats['successful'] / stats['total']
+
+        for stats in list(summary['by_role'].values()) + list(summary['by_weakness'].values()) + \
+                list(summary['by_usage'].values()):
+            stats['detection_rate'] = stats['detected'] / stats['labels']
+            stats['avg_credit'] = stats['credit'] / stats['labels']
+
+        # 샘플 언어별 (모델마다 다른 언어보다 유의하게 낮은 언어 표시)
+        summary['by_language'] = summarize_languages(results)
+
+        # 속성(키 길이/모드/곡선/라운드) 정답률: 계열을 맞힌 라벨만, 알고리즘 식별과 별개로 집계
+        summary['by_attribute'] = summarize_attributes(
+            match for result in results for match in (result.get('hierarchical_scores') or {}).get('matches', []))
+
+        q_stats = summary['quantum_safe']
+        q_stats['misclassification_rate'] = q_stats['misclassified'] / q_stats['labels'] if q_stats['labels'] else 0
+
+        h_stats = summary['hunk_localization']
+        h_stats['hunk_recall'] = h_stats['hunk_hits'] / h_stats['labels'] if h_stats['labels'] else 0
+        h_stats['commit_recall'] = h_stats['commit_hits'] / h_stats['labels'] if h_stats['labels'] else 0
+
+        w_stats = summary['weakness_detection']
+        w_stats['precision'] = w_stats['matched'] / w_stats['reported'] if w_stats['reported'] else 0
+        w_stats['recall'] = w_stats['matched'] / w_stats['expected'] if w_stats['expected'] else 0
+        w_stats['f1'] = 2 * w_stats['precision'] * w_stats['recall'] / (w_stats['precision'] + w_stats['recall']) \
+            if w_stats['precision'] + w_stats['recall'] else 0
+        w_stats['mode_accuracy'] = w_stats['modes_correct'] / w_stats['modes_expected'] \
+            if w_stats['modes_expected'] else 0
+
+        for s_stats in (summary['secret_hygiene'], summary['timing_safety']):
+            s_stats['precision'] = s_stats['matched'] / s_stats['reported'] if s_stats['reported'] else 0
+            s_stats['recall'] = s_stats['matched'] / s_stats['expected'] if s_stats['expected'] else 0
+            s_stats['f1'] = 2 * s_stats['precision'] * s_stats['recall'] / (s_stats['precision'] + s_stats['recall']) \
+                if s_stats['precision'] + s_stats['recall'] else 0
+
+        # 모델별 K-crypto 커버리지 (한국 계열 라벨이 있거나 한국 계열을 잘못 보고한 샘플만)
+        korean_by_model: Dict[str, List[Dict[str, Any]]] = {}
+        for result in results:
+            if result.get('korean_check'):
+                korean_by_model.setdefault(f"{result.get('provider', 'unknown')}/{result.get('model', 'unknown')}",
+                                           []).append(result['korean_check'])
+        summary['korean_compliance'] = {model_key: summarize_korean(checks)
+                                        for model_key, checks in korean_by_model.items()}
+
+        rows_by_model: Dict[str, List[Dict[str, Any]]] = {}
+        for result in results:
+            rows_by_model.setdefault(f"{result.get('provider', 'unknown')}/{result.get('model', 'unknown')}",
+                                     []).append(result)
+
+        # 모델별 신뢰도 보정 (응답 confidence_score가 실제 정답률과 맞는지, 임계값별 PR)
+        summary['calibration'] = {model_key: calibration
+                                  for model_key, rows in rows_by_model.items()
+                                  if (calibration := calibration_summary(rows))}
+
+        # 모델별 판단 보류와 선택적 예측 지표 (보류한 샘플이 있는 모델만)
+        summary['selective'] = {model_key: selective_summary(rows)
+                                for model_key, rows in rows_by_model.items()
+                                if any(row.get('abstained') for row in rows)}
+
+        # 모델별 순위 후보 top-k 정확도 (--top-k 실행만)
+        top_k_by_model: Dict[str, List[Dict[str, Any]]] = {}
+        for result in results:
+            if result.get('top_k_scores'):
+                top_k_by_model.setdefault(f"{result.get('provider', 'unknown')}/{result.get('model', 'unknown')}",
+                                          []).append(result['top_k_scores'])
+        summary['top_k'] = {model_key: summarize_top_k(scores) for model_key, scores in top_k_by_model.items()}
+
+        # 함수 단위 (함수, 계열) 탐지: 근거 줄이 있는 보고만 예측으로 셈
+        summary['function_level'] = summarize_function_scores(result.get('function_scores') for result in results)
+
+        # 다중 턴 질의: 모델별 점수와 비용 (쓴 질문 턴 수별)
+        if any(result.get('interrogation') for result in results):
+            summary['interrogation'] = summarize_interrogation(results)
+
+        # 예외로 끝나지 못한 샘플 (위 집계에서는 채점 불가 0점)
+        summary['crashed_samples'] = crashed_samples(results)
+
+        # judge 라벨 매핑을 켠 실행: 모델별 judge-파서 일치도 (judge 편향 확인용)
+        judged: Dict[str, List[Dict[str, Any]]] = {}
+        for result in results:
+            if result.get('label_judge'):
+                judged.setdefault(f"{result.get('provider')}/{result.get('model')}", []).append(result)
+        if judged:
+            summary['label_judge'] = {model_key: agreement_report(rows) for model_key, rows in judged.items()}
+
+        # 모델별 트랙 가중 종합 점수 (식별/위치/오용/양자 내성 분류, 가중치는 config/benchmark.toml)
+        weights_path = self.config_loader.get_benchmark_config().get('composite_weights', WEIGHTS_PATH)
+        try:
+            weights = load_weights(weights_path)
+        except Exception as e:
+            print(f"⚠️  종합 점수 가중치를 읽지 못해 기본 가중치를 씁니다 ({weights_path}): {e}")
+            weights = load_weights(None)
+        tracks_by_model: Dict[str, List[Dict[str, float]]] = {}
+        for result in results:
+            tracks_by_model.setdefault(f"{result.get('provider', 'unknown')}/{result.get('model', 'unknown')}",
+                                       []).append(track_scores(result))
+        summary['composite'] = {model_key: composite for model_key, samples in tracks_by_model.items()
+                                if (composite := composite_score(samples, weights))}
+        # 점수 프로필(kcmvp 등)마다 같은 샘플 점수를 다시 가중
+        try:
+            profiles = load_profiles(weights_path)
+        except Exception as e:
+            print(f"⚠️  점수 프로필을 읽지 못했습니다 ({weights_path}): {e}")
+            profiles = {}
+        summary['composite_profiles'] = {
+            profile: {model_key: composite for model_key, samples in tracks_by_model.items()
+                      if (composite := composite_score(samples, profile_weights))}
+            for profile, profile_weights in profiles.items()
+        }
+
         return summary
 
     def save_results(self, filename: str = None) -> str:
@@ -543,6 +1415,17 @@ class BenchmarkRunner:
         print(f"   JSON: {json_filepath}")
         print(f"   CSV: {csv_filepath}")
 
+        # 추세 분석용 SQLite 저장소에 누적
+        store_path = self.config_loader.get_benchmark_config().get('results_store')
+        if store_path:
+            try:
+                store = ResultsStore(store_path)
+                run_id = store.ingest(self.results, source=str(json_filepath))
+                store.close()
+                print(f"   DB: {store_path} ({run_id})")
+            except Exception as e:
+                print(f"⚠️  결과 저장소 적재 실패: {e}")
+
         # Google Drive 자동 백업 (환경 변수가 설정된 경우)
         gdrive_dir = os.environ.get('GDRIVE_RESULTS_DIR')
         if gdrive_dir and os.path.exists(gdrive_dir):
@@ -573,8 +1456,8 @@ class BenchmarkRunner:
         # CSV 헤더 정의
         fieldnames = [
             'test_id', 'provider', 'model', 'agent_type', 'success',
-            'valid_json', 'confidence_score', 'detected_quantum_vulnerable_count',
-            'response_time', 'json_valid', 'summary', 'file_path',
+            'status', 'valid_json', 'parse_status', 'confidence_score', 'detected_quantum_vulnerable_count',
+            'response_time', 'json_valid', 'summary', 'file_path', 'language',
             'total_tokens', 'prompt_tokens', 'completion_tokens',
             'timestamp', 'error'
         ]
@@ -591,13 +1474,16 @@ class BenchmarkRunner:
                     'model': result.get('model', ''),
                     'agent_type': result.get('agent_type', ''),
                     'success': result.get('success', False),
+                    'status': result_status(result),
                     'valid_json': result.get('valid_json', False),
+                    'parse_status': result.get('parse_status', ''),
                     'confidence_score': result.get('confidence_score', 0),
                     'detected_quantum_vulnerable_count': result.get('detected_quantum_vulnerable_count', 0),
                     'response_time': result.get('response_time', 0),
                     'json_valid': result.get('json_valid', False),
                     'summary': result.get('summary', '').replace('\n', ' ').replace('\r', ' '),
                     'file_path': result.get('file_path', ''),
+                    'language': result.get('language', ''),
                     'timestamp': result.get('timestamp', 0),
                     'error': result.get('error', '')
                 }
@@ -630,6 +1516,20 @@ class BenchmarkRunner:
         print(f"전체 테스트: {summary['total_tests']}")
         print(f"성공: {summary['successful_tests']}")
         print(f"성공률: {summary['success_rate']:.1%}")
+        print(f"JSON 파싱 실패율: {summary.get('parse_failure_rate', 0):.1%} "
+              f"(자동 복구: {summary.get('parse_repair_rate', 0):.1%})")
+        if summary.get('by_status'):
+            print("상태: " + ', '.join(f"{status} {count}" for status, count in summary['by_status'].items()))
+        if self.results.get('metadata', {}).get('cancelled'):
+            print(f"⚠️  중단된 실행: {self.results['metadata']['cancelled']} (실행하지 않은 샘플은 결과에 없음)")
+        cost = self.results.get('metadata', {}).get('quota', {}).get('cost')
+        if cost and cost['calls']:
+            limit = f" / 한도 ${cost['daily_limit_usd']:.2f}" if cost['daily_limit_usd'] else ''
+            failed = f", 실패 {cost['failed_calls']}회 포함" if cost.get('failed_calls') else ''
+            print(f"💸 API 비용: 이번 실행 ${cost['cost_usd']:.4f} ({cost['calls']}회 호출{failed}), "
+                  f"오늘 누적 ${cost['spent_today_usd']:.4f}{limit}")
+            if cost['unpriced_models']:
+                print(f"   단가 없음 (비용 0으로 계산): {', '.join(cost['unpriced_models'])}")
 
         print(f"\n🏆 모델별 성능:")
         for model, stats in summary['by_model'].items():
@@ -638,12 +1538,177 @@ class BenchmarkRunner:
             print(f"    평균 응답시간: {stats.get('avg_response_time', 0):.2f}초")
             print(f"    평균 신뢰도: {stats.get('avg_confidence', 0):.3f}")
             print(f"    평균 양자 취약 알고리즘 탐지: {stats.get('avg_quantum_vulnerable', 0):.1f}개")
+            print(f"    JSON 파싱 실패율: {stats.get('parse_failure_rate', 0):.1%}")
+            if stats.get('cost_usd'):
+                print(f"    API 비용: ${stats['cost_usd']:.4f}")
+            failures = {status: count for status, count in stats.get('statuses', {}).items()
+                        if status != STATUS_OK and count}
+            if failures:
+                print(f"    채점 불가: {', '.join(f'{status} {count}' for status, count in failures.items())}")
+            if stats.get('hierarchical_f1'):
+                f1 = stats['hierarchical_f1']
+                print(f"    계층 F1: {f1['mean']:.3f} (95% CI {f1['ci_low']:.3f}–{f1['ci_high']:.3f}, n={f1['n']})")
+
+        if summary.get('model_comparisons'):
+            print(f"\n🔬 모델 간 계층 F1 차이 (공통 샘플, paired permutation test):")
+            for comparison in summary['model_comparisons']:
+                first, second = comparison['detectors']
+                marker = '유의함' if comparison['significant'] else '유의하지 않음'
+                print(f"  {second} − {first}: {comparison['mean_diff']:+.3f} "
+                      f"[95% CI {comparison['ci_low']:+.3f}, {comparison['ci_high']:+.3f}], "
+                      f"p={comparison['p_value']:.4f} ({marker}, n={comparison['n']})")
+
+        if self.results.get('metadata', {}).get('stage_latency'):
+            print(f"\n⏱️  단계별 지연 (p50 / p95 / 최대):")
+            for stage, stats in self.results['metadata']['stage_latency'].items():
+                print(f"  {stage}: {stats['p50_ms']:.0f} / {stats['p95_ms']:.0f} / {stats['max_ms']:.0f}ms "
+                      f"(n={stats['count']})")
 
         print(f"\n🎯 에이전트별 성공률:")
         for agent, stats in summary['by_agent'].items():
             success_rate = stats['successful'] / stats['total'] if stats['total'] > 0 else 0
             print(f"  {agent}: {success_rate:.1%} ({stats['successful']}/{stats['total']})")
 
+        if summary.get('by_fidelity'):
+            print(f"\n🧬 샘플 충실도별 성능:")
+            for fidelity, stats in summary['by_fidelity'].items():
+                print(f"  {fidelity}: 성공률 {stats['success_rate']:.1%} ({stats['successful']}/{stats['total']}), "
+                      f"평균 정확도 {stats['avg_accuracy']:.3f}")
+
+        if len(summary.get('by_task', {})) > 1:
+            print(f"\n📦 과제 유형별 성능:")
+            for task, stats in summary['by_task'].items():
+                print(f"  {task}: 성공률 {stats['success_rate']:.1%} ({stats['successful']}/{stats['total']}), "
+                      f"평균 정확도 {stats['avg_accuracy']:.3f}, 평균 계층 F1 {stats['avg_f1']:.3f}")
+
+        if len(summary.get('by_documentation', {})) > 1:
+            print(f"\n📝 주석 신뢰도별 성능 (adversarial: 오도성 주석 주입 샘플):")
+            for documentation, stats in summary['by_documentation'].items():
+                print(f"  {documentation}: 성공률 {stats['success_rate']:.1%} ({stats['successful']}/{stats['total']}), "
+                      f"평균 정확도 {stats['avg_accuracy']:.3f}, 평균 계층 F1 {stats['avg_f1']:.3f}")
+
+        if len(summary.get('by_provenance', {})) > 1:
+            print(f"\n📜 샘플 출처별 성능:")
+            for provenance, stats in summary['by_provenance'].items():
+                print(f"  {provenance}: 성공률 {stats['success_rate']:.1%} ({stats['successful']}/{stats['total']}), "
+                      f"평균 정확도 {stats['avg_accuracy']:.3f}, 평균 계층 F1 {stats['avg_f1']:.3f}")
+
+        if len(summary.get('by_language', {})) > 1:
+            print(f"\n💬 언어별 성능:")
+            for language, stats in summary['by_language'].items():
+                print(f"  {language}: 성공률 {stats['success_rate']:.1%} ({stats['successful']}/{stats['total']}), "
+                      f"평균 정확도 {stats['avg_accuracy']:.3f}, 평균 계층 F1 {stats['avg_f1']:.3f}")
+            for entry in underperforming(summary['by_language']):
+                print(f"  ⚠️  {entry['model']}: {entry['language']} 부진, 계층 F1 {entry['f1']['mean']:.3f} "
+                      f"(n={entry['f1']['n']}) vs 다른 언어 {entry['others_f1']:.3f} (n={entry['others_n']}), "
+                      f"p={entry['p_value']:.4f}")
+
+        if summary.get('by_role'):
+            print(f"\n🔐 기본 요소 역할별 탐지율:")
+            for role, stats in sorted(summary['by_role'].items()):
+                print(f"  {role}: 탐지율 {stats['detection_rate']:.1%} ({stats['detected']}/{stats['labels']}), "
+                      f"평균 계층 점수 {stats['avg_credit']:.3f}")
+
+        if summary.get('by_weakness'):
+            print(f"\n🩹 취약성 범주별 탐지율:")
+            for weakness, stats in sorted(summary['by_weakness'].items()):
+                print(f"  {weakness}: 탐지율 {stats['detection_rate']:.1%} ({stats['detected']}/{stats['labels']}), "
+                      f"평균 계층 점수 {stats['avg_credit']:.3f}")
+
+        if summary.get('by_usage'):
+            print(f"\n🔌 사용 형태별 탐지율 (직접 구현 vs 외부 라이브러리):")
+            for usage, stats in sorted(summary['by_usage'].items()):
+                print(f"  {usage}: 탐지율 {stats['detection_rate']:.1%} ({stats['detected']}/{stats['labels']}), "
+                      f"평균 계층 점수 {stats['avg_credit']:.3f}")
+
+        if summary.get('by_attribute'):
+            print(f"\n🔧 속성별 정답률 (알고리즘 계열을 맞힌 라벨 기준):")
+            for attribute, stats in summary['by_attribute'].items():
+                print(f"  {attribute}: 정답률 {stats['accuracy']:.1%} ({stats['correct']}/{stats['expected']}), "
+                      f"명시한 값의 정확도 {stats['stated_accuracy']:.1%} ({stats['correct']}/{stats['stated']})")
+
+        if summary.get('quantum_safe', {}).get('samples'):
+            q_stats = summary['quantum_safe']
+            print(f"\n🛡️ 양자 내성 알고리즘 오분류율: {q_stats['misclassification_rate']:.1%} "
+                  f"({q_stats['misclassified']}/{q_stats['labels']} 라벨, "
+                  f"{q_stats['misclassified_samples']}/{q_stats['samples']} 샘플)")
+
+        if summary.get('hunk_localization', {}).get('samples'):
+            h_stats = summary['hunk_localization']
+            print(f"\n🧩 변경 도입 위치 적중률: hunk {h_stats['hunk_recall']:.1%} ({h_stats['hunk_hits']}/{h_stats['labels']}), "
+                  f"커밋 {h_stats['commit_recall']:.1%} ({h_stats['commit_hits']}/{h_stats['labels']}), "
+                  f"잘못된 위치 보고 {h_stats['mislocated']}건")
+
+        if summary.get('weakness_detection', {}).get('samples'):
+            w_stats = summary['weakness_detection']
+            print(f"\n🧷 운용 모드/구현 약점 (weakness-detection, {w_stats['samples']}개 샘플): "
+                  f"약점 P {w_stats['precision']:.3f} / R {w_stats['recall']:.3f} / F1 {w_stats['f1']:.3f} "
+                  f"({w_stats['matched']}/{w_stats['expected']}), "
+                  f"모드 정답률 {w_stats['mode_accuracy']:.1%} ({w_stats['modes_correct']}/{w_stats['modes_expected']})")
+
+        if summary.get('secret_hygiene', {}).get('samples'):
+            s_stats = summary['secret_hygiene']
+            print(f"🔐 비밀 취급 (secret-hygiene, {s_stats['samples']}개 샘플): "
+                  f"P {s_stats['precision']:.3f} / R {s_stats['recall']:.3f} / F1 {s_stats['f1']:.3f} "
+                  f"({s_stats['matched']}/{s_stats['expected']}, 보고 {s_stats['reported']}건)")
+
+        if summary.get('timing_safety', {}).get('samples'):
+            t_stats = summary['timing_safety']
+            print(f"⏱️  타이밍 안전성 (timing-safety, {t_stats['samples']}개 샘플): "
+                  f"P {t_stats['precision']:.3f} / R {t_stats['recall']:.3f} / F1 {t_stats['f1']:.3f} "
+                  f"({t_stats['matched']}/{t_stats['expected']}), 상수 시간 구현 오경보 {t_stats['false_alarms']}건")
+
+        if summary.get('korean_compliance'):
+            print(f"\n🇰🇷 K-crypto 커버리지 (한국 계열 탐지, 국제 대응 계열과의 구별):")
+            for model_key, k_stats in sorted(summary['korean_compliance'].items()):
+                print(f"  {model_key}:")
+                print_korean(k_stats, indent='    ')
+
+        if summary.get('selective'):
+            print(f"\n🤷 판단 보류와 선택적 예측 (python -m utils.selective로 risk-coverage 곡선):")
+            for model_key, s_stats in sorted(summary['selective'].items()):
+                print(f"  {model_key}: {format_selective(s_stats)}")
+
+        if summary.get('top_k'):
+            print(f"\n🏅 순위 후보 top-k 정확도 (정답이 앞 k개 후보 안에 있는 비율):")
+            for model_key, k_stats in sorted(summary['top_k'].items()):
+                print(f"  {model_key}: {format_top_k(k_stats)} (정답 {k_stats['expected']}개)")
+
+        if summary.get('calibration'):
+            print(f"\n🎚️  신뢰도 보정 (응답 confidence_score, python -m utils.calibration으로 구간표/PR 곡선):")
+            for model_key, c_stats in sorted(summary['calibration'].items()):
+                best = c_stats['best_threshold']
+                print(f"  {model_key}: ECE {c_stats['ece']:.3f}, Brier {c_stats['brier']:.3f}, "
+                      f"AP {c_stats['average_precision']:.3f}, F1 최대 임계값 ≥ {best['threshold']:.3f} "
+                      f"(F1 {best['f1']:.3f})")
+
+        if summary.get('function_level', {}).get('samples'):
+            fn_stats = summary['function_level']
+            print(f"\n🔬 함수 단위 탐지 ({fn_stats['samples']}개 샘플): "
+                  f"P {fn_stats['precision']:.3f} / R {fn_stats['recall']:.3f} / F1 {fn_stats['f1']:.3f} "
+                  f"(정답 범위 {fn_stats['matched']}/{fn_stats['expected']})")
+
+        if summary.get('interrogation'):
+            print_interrogation(summary['interrogation'])
+
+        if summary.get('label_judge'):
+            print()
+            for model_key, report in sorted(summary['label_judge'].items()):
+                print_agreement_report(report, model_key)
+
+        if summary.get('composite'):
+            print(f"\n🏅 종합 점수 (트랙 가중 평균):")
+            for model_key, composite in sorted(summary['composite'].items(), key=lambda item: -item[1]['score']):
+                print(f"  {model_key}: {composite['score']:.3f}  ({format_breakdown(composite)})")
+        for profile, composites in summary.get('composite_profiles', {}).items():
+            if not composites:
+                continue
+            print(f"\n🏅 종합 점수 ({profile} 프로필):")
+            for model_key, composite in sorted(composites.items(), key=lambda item: -item[1]['score']):
+                print(f"  {model_key}: {composite['score']:.3f}  ({format_breakdown(composite)})")
+
+        print_crashes(summary.get('crashed_samples', []))
+
     def _load_ground_truth(self, test_case: Dict[str, Any], agent_type: str = None) -> Dict[str, Any]:
         """테스트 케이스에 대한 ground truth 로드"""
         try:
@@ -655,12 +1720,10 @@ class BenchmarkRunner:
             # Ground truth 파일 경로 생성
             ground_truth_path = f"data/ground_truth/{agent_type}/{test_id}.json"
 
-            print(f"    🔧 Debug: Ground truth 경로 시도: {ground_truth_path}")
-
             if os.path.exists(ground_truth_path):
                 with open(ground_truth_path, 'r', encoding='utf-8') as f:
                     ground_truth = json.load(f)
-                    print(f"    🔧 Debug: Ground truth 로드 성공: {ground_truth}")
+                    logger.debug(f"Ground truth 로드: {ground_truth_path}")
                     return ground_truth
             else:
                 print(f"    ⚠️  Ground truth 파일 없음: {ground_truth_path}")
@@ -675,15 +1738,110 @@ def main():
                        choices=['google', 'openai', 'xai', 'ollama'],
                        help='테스트할 프로바이더들')
     parser.add_argument('--agents', nargs='+',
-                       choices=['source_code', 'assembly_binary', 'logs_config'],
+                       choices=['source_code', 'assembly_binary', 'logs_config', 'dependency_manifest', 'code_diff'],
                        help='테스트할 에이전트들')
     parser.add_argument('--limit', type=int, help='에이전트당 테스트 파일 수 제한')
+    parser.add_argument('--subset', help='층화 평가 부분집합 이름 또는 정의 파일 (utils/subset.py)')
+    parser.add_argument('--filter', metavar='EXPR',
+                       help='필터 식에 맞는 샘플만 평가 (utils/sample_filter.py, 예: "lang==rust && difficulty>=3 && !tag:decoy")')
+    parser.add_argument('--changed-since', metavar='MANIFEST',
+                       help='이 매니페스트(또는 이전 결과 파일) 이후 추가·수정된 샘플만 평가하고 이전 결과와 합침 '
+                            '(utils/incremental.py)')
+    parser.add_argument('--merge-results', metavar='RESULTS',
+                       help='--changed-since와 합칠 이전 결과 파일 (기본: --changed-since가 결과 파일이면 그 파일)')
+    parser.add_argument('--sample-timeout', type=float, metavar='SECONDS',
+                       help='샘플당 제한 시간(초), 넘기면 timeout으로 기록하고 다음 샘플로 (0: 제한 없음)')
+    parser.add_argument('--daily-cost-limit', type=float, metavar='USD',
+                       help='일일 API 비용 한도(USD), 오늘 누적 비용이 닿으면 남은 샘플을 건너뛰고 요약 (0: 한도 없음)')
     parser.add_argument('--parallel', action='store_true', help='병렬 실행')
+    parser.add_argument('--interrogate', type=int, metavar='TURNS',
+                        help='다중 턴 질의: 파일 개요만 보여 주고 질문 턴을 최대 TURNS번 허용 (0이면 질문 없이 개요만)')
+    parser.add_argument('--tools', action='store_true',
+                        help='다중 턴 질의를 함수 호출 도구(grep, read_file, list_symbols)로 (openai/anthropic, --interrogate와 함께)')
+    parser.add_argument('--no-transcripts', action='store_true',
+                        help='샘플별 요청/응답 대화 기록을 남기지 않음 (재생: python -m utils.transcripts replay)')
+    parser.add_argument('--batch', action='store_true',
+                        help='OpenAI/Anthropic 샘플을 배치 작업으로 제출 (절반 가격, 완료까지 최대 24시간)')
+    add_logging_arguments(parser)
+    parser.add_argument('--serve', action='store_true',
+                       help='서버 모드: /metrics(Prometheus)를 제공하며 --interval 분마다 벤치마크를 반복 실행')
+    parser.add_argument('--metrics-port', type=int, default=DEFAULT_METRICS_PORT, help='서버 모드 지표 포트')
+    parser.add_argument('--interval', type=float, default=0, metavar='MINUTES',
+                       help='서버 모드 반복 간격(분), 0이면 한 번 실행하고 지표만 계속 제공')
     parser.add_argument('--output', help='결과 파일명')
+    parser.add_argument('--offline', action='store_true',
+                       help='오프라인 모드 (로컬 엔드포인트 외 외부 연결 차단)')
+    parser.add_argument('--chunking', action='store_true',
+                       help='컨텍스트를 넘는 샘플을 겹치는 창으로 나눠 창마다 질의 (기본: 앞부분만 사용)')
+    parser.add_argument('--few-shot', type=int, metavar='K',
+                       help='train 분할에서 K개 few-shot 예시 사용 (평가는 test 분할만)')
+    parser.add_argument('--few-shot-strategy', choices=['random', 'nearest', 'balanced'],
+                       help='few-shot 예시 선택 전략')
+    parser.add_argument('--rationale', choices=['rubric', 'judge'],
+                       help='응답에 탐지 근거(rationale)를 요청하고 지정한 방식으로 채점')
+    parser.add_argument('--weakness-detection', action='store_true',
+                       help='응답에 운용 모드/구현 약점(weaknesses)을 요청하고 weakness-detection 트랙으로 채점')
+    parser.add_argument('--abstain', action='store_true',
+                       help='응답에 판정(verdict)을 요청해 "unsure" 판단 보류를 허용하고 coverage/선택적 지표로 채점')
+    parser.add_argument('--top-k', action='store_true',
+                       help='응답에 영역별 순위 후보(candidates)를 요청하고 top-1/3/5 정확도로 채점')
+    parser.add_argument('--label-judge', choices=['shadow', 'fallback'],
+                       help='judge 모델로 응답을 분류 체계 라벨로 매핑 (shadow: 파서 일치도만 기록, '
+                            'fallback: 파싱 실패 응답을 judge 라벨로 채점)')
 
     args = parser.parse_args()
 
-    runner = BenchmarkRunner()
+    runner = BenchmarkRunner(offline=args.offline)
+    runner.logging = apply_logging_arguments(args, runner.logging)
+    if args.subset:
+        try:
+            runner.use_subset(args.subset)
+        except ValueError as e:
+            parser.error(str(e))
+    if args.filter:
+        try:
+            runner.use_filter(args.filter)
+        except ValueError as e:
+            parser.error(str(e))
+    if args.merge_results and not args.changed_since:
+        parser.error('--merge-results는 --changed-since와 함께 씁니다')
+    if args.changed_since:
+        try:
+            runner.use_changed_since(args.changed_since, args.merge_results)
+        except (OSError, ValueError) as e:
+            parser.error(str(e))
+    if args.sample_timeout is not None:
+        runner.sample_timeout = args.sample_timeout or None
+    if args.daily_cost_limit is not None:
+        runner.costs.daily_limit = args.daily_cost_limit or None
+    if args.chunking:
+        runner.chunking['enabled'] = True
+    if args.few_shot is not None:
+        runner.few_shot.update(enabled=args.few_shot > 0, k=args.few_shot)
+    if args.few_shot_strategy:
+        runner.few_shot['strategy'] = args.few_shot_strategy
+    if args.rationale:
+        runner.rationale.update(enabled=True, scorer=args.rationale)
+    if args.weakness_detection:
+        runner.weakness_detection['enabled'] = True
+    if args.top_k:
+        runner.top_k['enabled'] = True
+    if args.abstain:
+        runner.abstention['enabled'] = True
+    if args.label_judge:
+        runner.label_judge.update(enabled=True, mode=args.label_judge)
+    if args.batch:
+        runner.batch['enabled'] = True
+    if args.no_transcripts:
+        runner.transcripts['enabled'] = False
+    if args.interrogate is not None:
+        runner.interrogation.update(enabled=True, max_turns=max(0, args.interrogate))
+    if args.tools:
+        runner.interrogation.update(enabled=True, protocol='tools')
+
+    if args.serve:
+        serve(runner, args)
+        return
 
     try:
         runner.run_benchmark(
@@ -703,5 +1861,44 @@ def main():
         import traceback
         traceback.print_exc()
 
+def serve(runner: BenchmarkRunner, args: argparse.Namespace):
+    """서버 모드: 지표 엔드포인트를 띄우고 interval마다 벤치마크 실행 (Ctrl-C로 종료)"""
+    server = start_metrics_server(args.metrics_port)
+    print(f"📡 서버 모드: http://localhost:{args.metrics_port}/metrics "
+          f"({f'{args.interval:g}분마다 실행' if args.interval else '한 번 실행 후 지표 제공'})")
+
+    try:
+        while True:
+            results, outcome = None, 'completed'
+            REGISTRY.running.set(1)
+            try:
+                results = runner.run_benchmark(providers=args.providers, agents=args.agents,
+                                               test_limit=args.limit, parallel=args.parallel)
+                runner.print_summary()
+                runner.save_results(args.output)
+                if runner.cancellation.cancelled:
+                    outcome = 'cancelled'
+            except Exception as e:
+                outcome = 'failed'
+                logger.exception(f"벤치마크 실행 실패: {e}")
+                print(f"❌ 벤치마크 실행 중 오류: {e}")
+            finally:
+                REGISTRY.running.set(0)
+            REGISTRY.record_run(results, outcome, time.time())
+
+            if runner.cancellation.reason == INTERRUPTED_BY_USER:
+                break
+            if args.interval:
+                print(f"⏳ 다음 실행까지 {args.interval:g}분 대기")
+                time.sleep(args.interval * 60)
+            else:
+                threading.Event().wait()
+    except KeyboardInterrupt:
+        pass
+    finally:
+        server.shutdown()
+        print("\n📡 서버 모드 종료")
+
+
 if __name__ == "__main__":
     main()
\ No newline at end of file
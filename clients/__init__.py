@@ -1,8 +1,4 @@
 from .base_client import BaseLLMClient
-from .openai_client import OpenAIClient
-from .google_client import GoogleClient
-from .anthropic_client import AnthropicClient
-from .xai_client import XAIClient
 from .client_factory import ClientFactory
 
 __all__ = [
@@ -12,4 +8,18 @@ __all__ = [
     'AnthropicClient',
     'XAIClient',
     'ClientFactory'
-]
\ No newline at end of file
+]
+
+_PROVIDER_CLASSES = {
+    'OpenAIClient': 'openai',
+    'GoogleClient': 'google',
+    'AnthropicClient': 'anthropic',
+    'XAIClient': 'xai'
+}
+
+
+def __getattr__(name):
+    # 프로바이더 클라이언트는 처음 접근할 때 import (SDK가 없는 설치에서도 clients 패키지는 import 가능)
+    if name in _PROVIDER_CLASSES:
+        return ClientFactory.client_class(_PROVIDER_CLASSES[name])
+    raise AttributeError(f"module {__name__!r} has no attribute {name!r}")
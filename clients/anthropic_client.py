@@ -1,8 +1,10 @@
 import anthropic
-from typing import Dict, Any
+from typing import Dict, Any, List
 from .base_client import BaseLLMClient
 
 class AnthropicClient(BaseLLMClient):
+    supports_tools = True
+
     def __init__(self, api_key: str, model: str = "claude-3-sonnet-20240229", base_url: str = ""):
         super().__init__(api_key, model, base_url)
         self.client = anthropic.Anthropic(api_key=api_key)
@@ -25,7 +27,52 @@ class AnthropicClient(BaseLLMClient):
                 'usage': {
                     'prompt_tokens': response.usage.input_tokens,
                     'completion_tokens': response.usage.output_tokens,
-                    'total_tokens': response.usage.input_tokens + response.usage.output_tokens
+                    'total_tokens': response.usage.input_tokens + response.usage.output_tokens,
+                    'cache_read_input_tokens': getattr(response.usage, 'cache_read_input_tokens', 0) or 0
+                },
+                'model': response.model
+            }
+        except Exception as e:
+            raise Exception(f"Anthropic API Error: {str(e)}")
+
+    def make_tool_request(self, messages: List[Dict[str, Any]], tools: List[Dict[str, Any]],
+                          max_tokens: int = 1000, allow_tools: bool = True) -> Dict[str, Any]:
+        # 도구 결과는 user 메시지의 tool_result 블록 (연속된 결과는 한 메시지로)
+        converted = []
+        for message in messages:
+            if message['role'] == 'assistant':
+                blocks = [{'type': 'text', 'text': message['content']}] if message.get('content') else []
+                blocks += [{'type': 'tool_use', 'id': call['id'], 'name': call['name'], 'input': call['arguments']}
+                           for call in message.get('tool_calls') or []]
+                converted.append({'role': 'assistant', 'content': blocks})
+            elif message['role'] == 'tool':
+                block = {'type': 'tool_result', 'tool_use_id': message['tool_call_id'], 'content': message['content']}
+                if converted and converted[-1]['role'] == 'user' and isinstance(converted[-1]['content'], list):
+                    converted[-1]['content'].append(block)
+                else:
+                    converted.append({'role': 'user', 'content': [block]})
+            else:
+                converted.append({'role': 'user', 'content': message['content']})
+        try:
+            response = self.client.messages.create(
+                model=self.model,
+                max_tokens=max_tokens,
+                temperature=0.1,
+                tools=[{'name': tool['name'], 'description': tool['description'],
+                        'input_schema': tool['parameters']} for tool in tools],
+                tool_choice={'type': 'auto' if allow_tools else 'none'},
+                messages=converted
+            )
+
+            return {
+                'content': ''.join(block.text for block in response.content if block.type == 'text'),
+                'tool_calls': [{'id': block.id, 'name': block.name, 'arguments': block.input}
+                               for block in response.content if block.type == 'tool_use'],
+                'usage': {
+                    'prompt_tokens': response.usage.input_tokens,
+                    'completion_tokens': response.usage.output_tokens,
+                    'total_tokens': response.usage.input_tokens + response.usage.output_tokens,
+                    'cache_read_input_tokens': getattr(response.usage, 'cache_read_input_tokens', 0) or 0
                 },
                 'model': response.model
             }
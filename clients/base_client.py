@@ -1,9 +1,12 @@
 from abc import ABC, abstractmethod
-from typing import Dict, Any, Optional
+from typing import Dict, Any, List, Optional
 import time
 import json
 
 class BaseLLMClient(ABC):
+    # 함수 호출(tool calling) 지원 여부: 지원하는 클라이언트는 make_tool_request를 구현
+    supports_tools = False
+
     def __init__(self, api_key: str, model: str, base_url: str):
         self.api_key = api_key
         self.model = model
@@ -13,6 +16,44 @@ class BaseLLMClient(ABC):
     def make_request(self, prompt: str, max_tokens: int = 1000) -> Dict[str, Any]:
         pass
 
+    def make_tool_request(self, messages: List[Dict[str, Any]], tools: List[Dict[str, Any]],
+                          max_tokens: int = 1000, allow_tools: bool = True) -> Dict[str, Any]:
+        """공통 형식 대화 → {'content', 'tool_calls': [{'id', 'name', 'arguments'}], 'usage', 'model'}
+
+        messages: {'role': 'user', 'content'} / {'role': 'assistant', 'content', 'tool_calls'} /
+                  {'role': 'tool', 'tool_call_id', 'name', 'content'}
+        tools: [{'name', 'description', 'parameters' (JSON Schema)}], allow_tools=False면 도구 호출 없이 답하게 함
+        """
+        raise NotImplementedError(f"{type(self).__name__} does not support tool calling")
+
+    def benchmark_tool_request(self, messages: List[Dict[str, Any]], tools: List[Dict[str, Any]],
+                               max_tokens: int = 1000, allow_tools: bool = True) -> Dict[str, Any]:
+        start_time = time.time()
+
+        try:
+            response = self.make_tool_request(messages, tools, max_tokens, allow_tools)
+            content = response.get('content') or ''
+            return {
+                'success': True,
+                'content': content,
+                'tool_calls': response.get('tool_calls', []),
+                'response_time': time.time() - start_time,
+                'json_valid': self._is_valid_json(content),
+                'error': None,
+                'model': self.model,
+                'usage': response.get('usage', {})
+            }
+        except Exception as e:
+            return {
+                'success': False,
+                'content': '',
+                'tool_calls': [],
+                'response_time': time.time() - start_time,
+                'json_valid': False,
+                'error': str(e),
+                'model': self.model
+            }
+
     def benchmark_request(self, prompt: str, max_tokens: int = 1000) -> Dict[str, Any]:
         start_time = time.time()
 
@@ -31,7 +72,8 @@ class BaseLLMClient(ABC):
                 'response_time': response_time,
                 'json_valid': json_valid,
                 'error': None,
-                'model': self.model
+                'model': self.model,
+                'usage': response.get('usage', {})
             }
         except Exception as e:
             end_time = time.time()
@@ -1,30 +1,41 @@
-from typing import Dict, Any
+import importlib
+from typing import Dict, Any, Tuple
 from .base_client import BaseLLMClient
-from .openai_client import OpenAIClient
-from .google_client import GoogleClient
-from .anthropic_client import AnthropicClient
-from .xai_client import XAIClient
-from .ollama_client import OllamaClient
-from .local_ai_client import LocalAIClient
-from .pqc_inspector_client import PQCInspectorClient
+from .network_guard import NetworkGuard
 
 class ClientFactory:
-    _clients = {
-        'openai': OpenAIClient,
-        'google': GoogleClient,
-        'anthropic': AnthropicClient,
-        'xai': XAIClient,
-        'ollama': OllamaClient,
-        'local_ai': LocalAIClient,
-        'pqc_inspector': PQCInspectorClient
+    # 프로바이더 → (모듈, 클래스, 설치 extra). SDK는 해당 프로바이더를 쓸 때만 import해서
+    # 채점/로컬 탐지기만 쓰는 설치(pip install qvbench)에 HTTP 스택과 프로바이더 SDK가 끌려오지 않게 함
+    _clients: Dict[str, Tuple[str, str, str]] = {
+        'openai': ('openai_client', 'OpenAIClient', 'llm-openai'),
+        'google': ('google_client', 'GoogleClient', 'llm-google'),
+        'anthropic': ('anthropic_client', 'AnthropicClient', 'llm-anthropic'),
+        'xai': ('xai_client', 'XAIClient', 'llm-http'),
+        'ollama': ('ollama_client', 'OllamaClient', 'llm-http'),
+        'local_ai': ('local_ai_client', 'LocalAIClient', 'llm-http'),
+        'pqc_inspector': ('pqc_inspector_client', 'PQCInspectorClient', 'llm-http')
     }
 
+    @classmethod
+    def client_class(cls, provider: str) -> type:
+        module_name, class_name, extra = cls._clients[provider]
+        try:
+            module = importlib.import_module(f".{module_name}", __package__)
+        except ImportError as e:
+            raise RuntimeError(
+                f"{provider} 프로바이더에는 추가 의존성이 필요합니다 (pip install \"qvbench[{extra}]\"): {e}"
+            ) from e
+        return getattr(module, class_name)
+
     @classmethod
     def create_client(cls, provider: str, config: Dict[str, Any]) -> BaseLLMClient:
         if provider not in cls._clients:
             raise ValueError(f"Unsupported provider: {provider}. Supported providers: {list(cls._clients.keys())}")
 
-        client_class = cls._clients[provider]
+        # 오프라인 모드에서는 원격 프로바이더/비허용 엔드포인트를 생성 단계에서 차단
+        NetworkGuard.check_provider(provider, config.get('base_url', ''))
+
+        client_class = cls.client_class(provider)
         return client_class(
             api_key=config['api_key'],
             model=config['model'],
@@ -33,4 +44,4 @@ class ClientFactory:
 
     @classmethod
     def get_supported_providers(cls) -> list:
-        return list(cls._clients.keys())
\ No newline at end of file
+        return list(cls._clients.keys())
@@ -0,0 +1,212 @@
+"""
+오프라인 모드 네트워크 가드
+
+--offline 모드에서 설정된 로컬 엔드포인트 외의 모든 외부 연결을 차단합니다.
+HTTP 클라이언트(requests, httpx, openai SDK 등)가 공통으로 사용하는 소켓 계층
+(getaddrinfo / connect)을 감싸므로, 어떤 라이브러리를 통하든 비허용 호스트로의
+연결 시도는 즉시 OfflineModeViolation 예외로 실패합니다. 연결 없이 보내는 UDP 데이터그램
+(sendto / sendmsg의 주소 인자)도 같은 기준으로 막습니다.
+차단 시도는 모두 기록되어 결과 메타데이터에 포함됩니다. 소켓 함수는 워커 스레드에서도 불리므로
+기록과 허용 IP 갱신은 잠금 안에서 합니다.
+"""
+
+import ipaddress
+import socket
+import threading
+import time
+from typing import Any, Dict, List, Optional, Set
+from urllib.parse import urlparse
+
+# 원격 API 프로바이더 (오프라인 모드에서 클라이언트 생성 자체를 거부)
+REMOTE_PROVIDERS = {'openai', 'google', 'anthropic', 'xai'}
+
+_LOOPBACK_HOSTS = {'localhost', 'localhost.localdomain', 'ip6-localhost'}
+
+
+class OfflineModeViolation(RuntimeError):
+    """오프라인 모드에서 허용되지 않은 외부 연결 시도"""
+
+
+class NetworkGuard:
+    """프로세스 전역 네트워크 가드 (싱글턴처럼 클래스 상태로 관리)"""
+
+    _lock = threading.Lock()
+    _enabled = False
+    _allowed_hosts: Set[str] = set()
+    _allowed_ips: Set[str] = set()
+    _violations: List[Dict[str, Any]] = []
+    _original_getaddrinfo = None
+    _original_connect = None
+    _original_connect_ex = None
+    _original_sendto = None
+    _original_sendmsg = None
+
+    @classmethod
+    def enable(cls, allowed_endpoints: List[str] = None):
+        """가드 활성화
+
+        Args:
+            allowed_endpoints: 허용할 엔드포인트 URL 또는 호스트명 목록
+                               (루프백 주소는 항상 허용)
+        """
+        with cls._lock:
+            cls._allowed_hosts = set(_LOOPBACK_HOSTS)
+            cls._allowed_ips = set()
+            for endpoint in allowed_endpoints or []:
+                host = cls._extract_host(endpoint)
+                if host:
+                    cls._allowed_hosts.add(host.lower())
+                    if cls._is_ip(host):
+                        cls._allowed_ips.add(host)
+
+            if cls._enabled:
+                return
+
+            cls._original_getaddrinfo = socket.getaddrinfo
+            cls._original_connect = socket.socket.connect
+            cls._original_connect_ex = socket.socket.connect_ex
+            cls._original_sendto = socket.socket.sendto
+            cls._original_sendmsg = getattr(socket.socket, 'sendmsg', None)
+
+            socket.getaddrinfo = cls._guarded_getaddrinfo
+            socket.socket.connect = cls._guarded_connect
+            socket.socket.connect_ex = cls._guarded_connect_ex
+            socket.socket.sendto = cls._guarded_sendto
+            if cls._original_sendmsg:
+                socket.socket.sendmsg = cls._guarded_sendmsg
+            cls._enabled = True
+
+    @classmethod
+    def disable(cls):
+        with cls._lock:
+            if not cls._enabled:
+                return
+            socket.getaddrinfo = cls._original_getaddrinfo
+            socket.socket.connect = cls._original_connect
+            socket.socket.connect_ex = cls._original_connect_ex
+            socket.socket.sendto = cls._original_sendto
+            if cls._original_sendmsg:
+                socket.socket.sendmsg = cls._original_sendmsg
+            cls._enabled = False
+
+    @classmethod
+    def is_enabled(cls) -> bool:
+        return cls._enabled
+
+    @classmethod
+    def is_allowed_url(cls, url: str) -> bool:
+        """URL의 호스트가 허용 목록에 있는지 확인 (가드 비활성 시 항상 True)"""
+        if not cls._enabled:
+            return True
+        host = cls._extract_host(url)
+        return host is not None and cls._is_allowed_host(host)
+
+    @classmethod
+    def check_provider(cls, provider: str, base_url: str = ''):
+        """클라이언트 생성 전 검사: 원격 프로바이더 또는 비허용 엔드포인트면 예외"""
+        if not cls._enabled:
+            return
+        if provider in REMOTE_PROVIDERS:
+            cls._record('provider', provider)
+            raise OfflineModeViolation(
+                f"Offline mode: remote provider '{provider}' is not allowed"
+            )
+        if base_url and not cls.is_allowed_url(base_url):
+            cls._record('provider', f"{provider} ({base_url})")
+            raise OfflineModeViolation(
+                f"Offline mode: endpoint '{base_url}' for provider '{provider}' is not in the allow list"
+            )
+
+    @classmethod
+    def get_report(cls) -> Dict[str, Any]:
+        """결과 메타데이터에 기록할 가드 상태"""
+        with cls._lock:
+            return {
+                'offline_mode': cls._enabled,
+                'allowed_hosts': sorted(cls._allowed_hosts) if cls._enabled else [],
+                'blocked_attempts': list(cls._violations)
+            }
+
+    # ==================== 내부 구현 ====================
+
+    @staticmethod
+    def _extract_host(endpoint: str) -> Optional[str]:
+        if not endpoint:
+            return None
+        if '://' not in endpoint:
+            endpoint = f"http://{endpoint}"
+        return urlparse(endpoint).hostname
+
+    @staticmethod
+    def _is_ip(host: str) -> bool:
+        try:
+            ipaddress.ip_address(host)
+            return True
+        except ValueError:
+            return False
+
+    @classmethod
+    def _is_allowed_host(cls, host: str) -> bool:
+        host = str(host).lower().strip('[]')
+        if host in cls._allowed_hosts or host in cls._allowed_ips:
+            return True
+        try:
+            return ipaddress.ip_address(host).is_loopback
+        except ValueError:
+            return False
+
+    @classmethod
+    def _record(cls, kind: str, target: str):
+        with cls._lock:
+            cls._violations.append({
+                'kind': kind,
+                'target': target,
+                'timestamp': time.time()
+            })
+
+    @classmethod
+    def _guarded_getaddrinfo(cls, host, *args, **kwargs):
+        if host is not None:
+            host_str = host.decode() if isinstance(host, bytes) else str(host)
+            if not cls._is_allowed_host(host_str):
+                cls._record('dns', host_str)
+                raise OfflineModeViolation(f"Offline mode: DNS lookup for '{host_str}' blocked")
+
+        results = cls._original_getaddrinfo(host, *args, **kwargs)
+        # 허용 호스트가 해석된 IP도 connect 단계에서 허용
+        with cls._lock:
+            for _family, _type, _proto, _canon, sockaddr in results:
+                cls._allowed_ips.add(str(sockaddr[0]))
+        return results
+
+    @classmethod
+    def _check_address(cls, address):
+        if isinstance(address, tuple) and address:
+            host = str(address[0])
+            if not cls._is_allowed_host(host):
+                cls._record('connect', f"{host}:{address[1] if len(address) > 1 else ''}")
+                raise OfflineModeViolation(f"Offline mode: connection to '{host}' blocked")
+
+    @staticmethod
+    def _guarded_connect(sock, address):
+        NetworkGuard._check_address(address)
+        return NetworkGuard._original_connect(sock, address)
+
+    @staticmethod
+    def _guarded_connect_ex(sock, address):
+        NetworkGuard._check_address(address)
+        return NetworkGuard._original_connect_ex(sock, address)
+
+    @staticmethod
+    def _guarded_sendto(sock, data, *args):
+        # sendto(data, address) / sendto(data, flags, address)
+        if args:
+            NetworkGuard._check_address(args[-1])
+        return NetworkGuard._original_sendto(sock, data, *args)
+
+    @staticmethod
+    def _guarded_sendmsg(sock, buffers, *args):
+        # sendmsg(buffers[, ancdata[, flags[, address]]]), 주소가 없으면 연결된 소켓 (connect에서 검사됨)
+        if len(args) >= 3 and args[2] is not None:
+            NetworkGuard._check_address(args[2])
+        return NetworkGuard._original_sendmsg(sock, buffers, *args)
@@ -1,8 +1,11 @@
+import json
 import openai
-from typing import Dict, Any
+from typing import Dict, Any, List
 from .base_client import BaseLLMClient
 
 class OpenAIClient(BaseLLMClient):
+    supports_tools = True
+
     def __init__(self, api_key: str, model: str = "gpt-4", base_url: str = "https://api.openai.com/v1"):
         super().__init__(api_key, model, base_url)
         self.client = openai.OpenAI(
@@ -27,5 +30,50 @@ class OpenAIClient(BaseLLMClient):
                 'usage': response.usage.dict() if response.usage else {},
                 'model': response.model
             }
+        except Exception as e:
+            raise Exception(f"OpenAI API Error: {str(e)}")
+
+    def make_tool_request(self, messages: List[Dict[str, Any]], tools: List[Dict[str, Any]],
+                          max_tokens: int = 1000, allow_tools: bool = True) -> Dict[str, Any]:
+        converted = []
+        for message in messages:
+            if message['role'] == 'assistant':
+                converted.append({
+                    'role': 'assistant',
+                    'content': message.get('content') or None,
+                    **({'tool_calls': [{'id': call['id'], 'type': 'function',
+                                        'function': {'name': call['name'],
+                                                     'arguments': json.dumps(call['arguments'], ensure_ascii=False)}}
+                                       for call in message['tool_calls']]} if message.get('tool_calls') else {})
+                })
+            elif message['role'] == 'tool':
+                converted.append({'role': 'tool', 'tool_call_id': message['tool_call_id'],
+                                  'content': message['content']})
+            else:
+                converted.append({'role': 'user', 'content': message['content']})
+        try:
+            response = self.client.chat.completions.create(
+                model=self.model,
+                messages=converted,
+                tools=[{'type': 'function', 'function': tool} for tool in tools],
+                tool_choice='auto' if allow_tools else 'none',
+                max_tokens=max_tokens,
+                temperature=0.1
+            )
+
+            message = response.choices[0].message
+            tool_calls = []
+            for call in message.tool_calls or []:
+                try:
+                    arguments = json.loads(call.function.arguments or '{}')
+                except json.JSONDecodeError:
+                    arguments = {'_raw': call.function.arguments}
+                tool_calls.append({'id': call.id, 'name': call.function.name, 'arguments': arguments})
+            return {
+                'content': message.content or '',
+                'tool_calls': tool_calls,
+                'usage': response.usage.dict() if response.usage else {},
+                'model': response.model
+            }
         except Exception as e:
             raise Exception(f"OpenAI API Error: {str(e)}")
\ No newline at end of file
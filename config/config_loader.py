@@ -2,7 +2,12 @@ import yaml
 import os
 import json
 from typing import Dict, Any
-from dotenv import load_dotenv
+
+try:
+    from dotenv import load_dotenv
+except ImportError:  # python-dotenv는 llm extra에만 포함 (없으면 .env 대신 환경 변수만 사용)
+    def load_dotenv():
+        return False
 
 class ConfigLoader:
     def __init__(self, config_path: str = "config/config.yaml"):
@@ -0,0 +1,147 @@
+const DOSAGE_SEAL: [u8; 32] = [0x41, 0x38, 0x78, 0xf7, 0x44, 0x02, 0x3c, 0x97, 0x21, 0xd2, 0xb0, 0x82, 0xc0, 0x73, 0x5c, 0xec, 0x99, 0x7c, 0x44, 0x85, 0x3f, 0x67, 0xa4, 0x9c, 0x56, 0x00, 0x73, 0xb8, 0x1e, 0x65, 0x9f, 0x60];
+
+pub struct ClinicalRepository {
+    secret: [u8; 32],
+    entries: Vec<(u64, Vec<u8>)>,
+}
+
+impl ClinicalRepository {
+    pub fn new(secret: [u8; 32]) -> Self {
+        ClinicalRepository { secret, entries: Vec::new() }
+    }
+
+    fn slot(record: u64) -> [u8; 12] {
+        let mut slot = [0u8; 12];
+        slot[4..].copy_from_slice(&record.to_le_bytes());
+        slot
+    }
+
+    pub fn put(&mut self, record: u64, body: &[u8]) {
+        let sealed = ReferShroud::new(&self.secret, &Self::slot(record), 1).apply(body);
+        self.entries.retain(|(existing, _)| *existing != record);
+        self.entries.push((record, sealed));
+    }
+
+    pub fn sealed(&self, record: u64) -> Option<&[u8]> {
+        self.entries.iter().find(|(existing, _)| *existing == record).map(|(_, body)| body.as_slice())
+    }
+
+    pub fn get(&self, record: u64) -> Option<Vec<u8>> {
+        self.sealed(record)
+            .map(|body| ReferShroud::new(&self.secret, &Self::slot(record), 1).apply(body))
+    }
+}
+
+const PRIMER_WORDS: [u32; 4] = [1634760805, 857760878, 2036477234, 1797285236];
+
+pub struct ReferShroud {
+    state: [u32; 16],
+}
+
+impl ReferShroud {
+    pub fn new(key: &[u8; 32], nonce: &[u8; 12], counter: u32) -> Self {
+        let mut state = [0u32; 16];
+        state[..4].copy_from_slice(&PRIMER_WORDS);
+        for i in 0..8 {
+            state[4 + i] = u32::from_le_bytes([key[4 * i], key[4 * i + 1], key[4 * i + 2], key[4 * i + 3]]);
+        }
+        state[12] = counter;
+        for i in 0..3 {
+            state[13 + i] = u32::from_le_bytes([nonce[4 * i], nonce[4 * i + 1], nonce[4 * i + 2], nonce[4 * i + 3]]);
+        }
+        ReferShroud { state }
+    }
+
+    fn fold(s: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+        s[a] = s[a].wrapping_add(s[b]);
+        s[d] = (s[d] ^ s[a]).rotate_left(16);
+        s[c] = s[c].wrapping_add(s[d]);
+        s[b] = (s[b] ^ s[c]).rotate_left(12);
+        s[a] = s[a].wrapping_add(s[b]);
+        s[d] = (s[d] ^ s[a]).rotate_left(8);
+        s[c] = s[c].wrapping_add(s[d]);
+        s[b] = (s[b] ^ s[c]).rotate_left(7);
+    }
+
+    pub fn sheet(&self, counter: u32) -> [u8; 64] {
+        let mut input = self.state;
+        input[12] = counter;
+        let mut working = input;
+        for _ in 0..10 {
+            Self::fold(&mut working, 0, 4, 8, 12);
+            Self::fold(&mut working, 1, 5, 9, 13);
+            Self::fold(&mut working, 2, 6, 10, 14);
+            Self::fold(&mut working, 3, 7, 11, 15);
+            Self::fold(&mut working, 0, 5, 10, 15);
+            Self::fold(&mut working, 1, 6, 11, 12);
+            Self::fold(&mut working, 2, 7, 8, 13);
+            Self::fold(&mut working, 3, 4, 9, 14);
+        }
+        let mut out = [0u8; 64];
+        for i in 0..16 {
+            out[4 * i..4 * i + 4].copy_from_slice(&working[i].wrapping_add(input[i]).to_le_bytes());
+        }
+        out
+    }
+
+    pub fn apply(&self, data: &[u8]) -> Vec<u8> {
+        let mut out = Vec::with_capacity(data.len());
+        for (index, chunk) in data.chunks(64).enumerate() {
+            let stream = self.sheet(self.state[12].wrapping_add(index as u32));
+            out.extend(chunk.iter().zip(stream.iter()).map(|(byte, key)| byte ^ key));
+        }
+        out
+    }
+}
+
+fn main() {
+    let mut store = ClinicalRepository::new(DOSAGE_SEAL);
+    store.put(71009, b"MRN 00482913 BP 128/82 HR 71 SpO2 98% note=post-op day 2");
+    let count = store.entries.len();
+    println!("Clinical Chart Archive: {} sealed chart entry(s)", count);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(text: &str) -> Vec<u8> {
+        (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn block_function_known_answer() {
+        // RFC 8439 section 2.3.2
+        let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let nonce = [0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x00, 0x4a, 0x00, 0x00, 0x00, 0x00];
+        let block = ReferShroud::new(&key, &nonce, 1).sheet(1);
+        assert_eq!(
+            block.to_vec(),
+            hex("10f1e7e4d13b5915500fdd1fa32071c4c7d1f4c733c068030422aa9ac3d46c4ed2826446079faa0914c2d705d98b02a2b5129cd1de164eb9cbd083e8a2503c4e")
+        );
+    }
+
+    #[test]
+    fn encryption_known_answer() {
+        // RFC 8439 section 2.4.2
+        let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let nonce = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x4a, 0x00, 0x00, 0x00, 0x00];
+        let plaintext = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+        assert_eq!(
+            ReferShroud::new(&key, &nonce, 1).apply(plaintext),
+            hex("6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0bf91b65c5524733ab8f593dabcd62b3571639d624e65152ab8f530c359f0861d807ca0dbf500d6a6156a38e088a22b65e52bc514d16ccf806818ce91ab77937365af90bbf74a35be6b40b8eedf2785e42874d")
+        );
+    }
+
+    #[test]
+    fn store_round_trip() {
+        let mut store = ClinicalRepository::new(DOSAGE_SEAL);
+        store.put(7, b"MRN 00482913 BP 128/82 HR 71 SpO2 98% note=post-op day 2");
+        assert_ne!(store.sealed(7).unwrap(), b"MRN 00482913 BP 128/82 HR 71 SpO2 98% note=post-op day 2");
+        assert_eq!(store.get(7).unwrap(), b"MRN 00482913 BP 128/82 HR 71 SpO2 98% note=post-op day 2".to_vec());
+        assert_eq!(store.get(8), None);
+    }
+}
@@ -0,0 +1,675 @@
+// Pharmacy Dispatch Channel
+// Prescription hand-off link between clinics and fulfilment centres
+
+const RESIDUE_CAP: u64 = 0x1fff_ffff_ffff_ffff;
+const SLOPE_TERM: u64 = RESIDUE_CAP - 3;
+const BASE_TERM: u64 = 0x1c33115bf06c144c;
+const START_LOCATION: (u64, u64) = (0x15f7acff619699d1, 0x0b1ee8853a20bc94);
+
+type MapLocation = Option<(u64, u64)>;
+
+// Both ends of a pharmacy dispatch link combine their private scalars through this exchange
+pub struct SharedPointExchange {
+    scalar: u64,
+}
+
+impl SharedPointExchange {
+    pub fn new(scalar: u64) -> Self {
+        SharedPointExchange { scalar }
+    }
+
+    pub fn share(&self) -> (u64, u64) {
+        Self::scale(self.scalar, Some(START_LOCATION)).expect("scalar must not annihilate the anchor")
+    }
+
+    pub fn combine(&self, peer: (u64, u64)) -> [u8; 8] {
+        let joint = Self::scale(self.scalar, Some(peer)).expect("peer share must be a valid element");
+        joint.0.to_be_bytes()
+    }
+
+    pub fn is_valid(share: (u64, u64)) -> bool {
+        let (x, y) = share;
+        if x >= RESIDUE_CAP || y >= RESIDUE_CAP {
+            return false;
+        }
+        let cubic = Self::mul(Self::mul(x, x), x);
+        let rhs = Self::add(Self::add(cubic, Self::mul(SLOPE_TERM, x)), BASE_TERM);
+        Self::mul(y, y) == rhs
+    }
+
+    fn add(a: u64, b: u64) -> u64 {
+        ((a as u128 + b as u128) % RESIDUE_CAP as u128) as u64
+    }
+
+    fn sub(a: u64, b: u64) -> u64 {
+        Self::add(a, RESIDUE_CAP - b % RESIDUE_CAP)
+    }
+
+    fn mul(a: u64, b: u64) -> u64 {
+        ((a as u128 * b as u128) % RESIDUE_CAP as u128) as u64
+    }
+
+    fn invert(a: u64) -> u64 {
+        let mut result = 1u64;
+        let mut base = a % RESIDUE_CAP;
+        let mut exponent = RESIDUE_CAP - 2;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = Self::mul(result, base);
+            }
+            base = Self::mul(base, base);
+            exponent >>= 1;
+        }
+        result
+    }
+
+    fn join(p: MapLocation, q: MapLocation) -> MapLocation {
+        let ((x1, y1), (x2, y2)) = match (p, q) {
+            (None, _) => return q,
+            (_, None) => return p,
+            (Some(a), Some(b)) => (a, b),
+        };
+
+        if x1 == x2 && Self::add(y1, y2) == 0 {
+            return None;
+        }
+
+        let slope = if x1 == x2 {
+            let numerator = Self::add(Self::mul(3, Self::mul(x1, x1)), SLOPE_TERM);
+            Self::mul(numerator, Self::invert(Self::mul(2, y1)))
+        } else {
+            Self::mul(Self::sub(y2, y1), Self::invert(Self::sub(x2, x1)))
+        };
+
+        let x3 = Self::sub(Self::sub(Self::mul(slope, slope), x1), x2);
+        let y3 = Self::sub(Self::mul(slope, Self::sub(x1, x3)), y1);
+        Some((x3, y3))
+    }
+
+    fn scale(scalar: u64, point: MapLocation) -> MapLocation {
+        let mut result = None;
+        for bit in (0..64).rev() {
+            result = Self::join(result, result);
+            if (scalar >> bit) & 1 == 1 {
+                result = Self::join(result, point);
+            }
+        }
+        result
+    }
+}
+
+// Identity record the responder presents, endorsed by the pharmacy dispatch operator
+#[derive(Clone)]
+pub struct MemberPass {
+    pub subject: Vec<u8>,
+    pub share: (u64, u64),
+    pub endorsement: u64,
+}
+
+pub struct EndorsementAuditor {
+    modulus: u64,
+    public_exponent: u64,
+}
+
+impl EndorsementAuditor {
+    pub fn new(modulus: u64, public_exponent: u64) -> Self {
+        EndorsementAuditor { modulus, public_exponent }
+    }
+
+    pub fn endorsed_bytes(credential: &MemberPass) -> Vec<u8> {
+        let mut bytes = credential.subject.clone();
+        bytes.extend_from_slice(&credential.share.0.to_be_bytes());
+        bytes.extend_from_slice(&credential.share.1.to_be_bytes());
+        bytes
+    }
+
+    pub fn accepts(&self, credential: &MemberPass) -> bool {
+        let fingerprint = TranscriptFingerprint::fingerprint(&Self::endorsed_bytes(credential));
+        let mut head = [0u8; 8];
+        head.copy_from_slice(&fingerprint[..8]);
+        let expected = u64::from_be_bytes(head) % self.modulus;
+        Self::power(credential.endorsement, self.public_exponent, self.modulus) == expected
+    }
+
+    fn power(base: u64, exponent: u64, modulus: u64) -> u64 {
+        let mut result = 1u128;
+        let mut square = (base % modulus) as u128;
+        let mut remaining = exponent;
+        while remaining > 0 {
+            if remaining & 1 == 1 {
+                result = result * square % modulus as u128;
+            }
+            square = square * square % modulus as u128;
+            remaining >>= 1;
+        }
+        result as u64
+    }
+}
+
+const SPREAD_WORDS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+// Running fingerprint over everything exchanged during pharmacy dispatch session setup
+pub struct TranscriptFingerprint {
+    state: [u32; 8],
+    pending: Vec<u8>,
+    consumed: u64,
+}
+
+impl TranscriptFingerprint {
+    pub fn new() -> Self {
+        TranscriptFingerprint {
+            state: [
+                0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a,
+                0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+            ],
+            pending: Vec::new(),
+            consumed: 0,
+        }
+    }
+
+    pub fn absorb(&mut self, data: &[u8]) {
+        self.consumed += data.len() as u64;
+        self.pending.extend_from_slice(data);
+        while self.pending.len() >= 64 {
+            let block: Vec<u8> = self.pending.drain(..64).collect();
+            self.fold_block(&block);
+        }
+    }
+
+    pub fn finish(mut self) -> [u8; 32] {
+        let bit_length = self.consumed.wrapping_mul(8);
+        self.pending.push(0x80);
+        while self.pending.len() % 64 != 56 {
+            self.pending.push(0);
+        }
+        self.pending.extend_from_slice(&bit_length.to_be_bytes());
+
+        let tail = std::mem::take(&mut self.pending);
+        for block in tail.chunks(64) {
+            self.fold_block(block);
+        }
+
+        let mut output = [0u8; 32];
+        for (i, word) in self.state.iter().enumerate() {
+            output[i * 4..i * 4 + 4].copy_from_slice(&word.to_be_bytes());
+        }
+        output
+    }
+
+    pub fn fingerprint(data: &[u8]) -> [u8; 32] {
+        let mut digest = Self::new();
+        digest.absorb(data);
+        digest.finish()
+    }
+
+    fn fold_block(&mut self, block: &[u8]) {
+        let mut schedule = [0u32; 64];
+        for (i, chunk) in block.chunks(4).enumerate() {
+            schedule[i] = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        for i in 16..64 {
+            let low = schedule[i - 15].rotate_right(7) ^ schedule[i - 15].rotate_right(18) ^ (schedule[i - 15] >> 3);
+            let high = schedule[i - 2].rotate_right(17) ^ schedule[i - 2].rotate_right(19) ^ (schedule[i - 2] >> 10);
+            schedule[i] = schedule[i - 16]
+                .wrapping_add(low)
+                .wrapping_add(schedule[i - 7])
+                .wrapping_add(high);
+        }
+
+        let mut v = self.state;
+        for i in 0..64 {
+            let sigma_e = v[4].rotate_right(6) ^ v[4].rotate_right(11) ^ v[4].rotate_right(25);
+            let choose = (v[4] & v[5]) ^ (!v[4] & v[6]);
+            let first = v[7]
+                .wrapping_add(sigma_e)
+                .wrapping_add(choose)
+                .wrapping_add(SPREAD_WORDS[i])
+                .wrapping_add(schedule[i]);
+            let sigma_a = v[0].rotate_right(2) ^ v[0].rotate_right(13) ^ v[0].rotate_right(22);
+            let majority = (v[0] & v[1]) ^ (v[0] & v[2]) ^ (v[1] & v[2]);
+            let second = sigma_a.wrapping_add(majority);
+
+            v[7] = v[6];
+            v[6] = v[5];
+            v[5] = v[4];
+            v[4] = v[3].wrapping_add(first);
+            v[3] = v[2];
+            v[2] = v[1];
+            v[1] = v[0];
+            v[0] = first.wrapping_add(second);
+        }
+
+        for (word, value) in self.state.iter_mut().zip(v.iter()) {
+            *word = word.wrapping_add(*value);
+        }
+    }
+}
+
+const LOOKUP_GRID: [u8; 256] = [
+    0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
+    0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4, 0x72, 0xc0,
+    0xb7, 0xfd, 0x93, 0x26, 0x36, 0x3f, 0xf7, 0xcc, 0x34, 0xa5, 0xe5, 0xf1, 0x71, 0xd8, 0x31, 0x15,
+    0x04, 0xc7, 0x23, 0xc3, 0x18, 0x96, 0x05, 0x9a, 0x07, 0x12, 0x80, 0xe2, 0xeb, 0x27, 0xb2, 0x75,
+    0x09, 0x83, 0x2c, 0x1a, 0x1b, 0x6e, 0x5a, 0xa0, 0x52, 0x3b, 0xd6, 0xb3, 0x29, 0xe3, 0x2f, 0x84,
+    0x53, 0xd1, 0x00, 0xed, 0x20, 0xfc, 0xb1, 0x5b, 0x6a, 0xcb, 0xbe, 0x39, 0x4a, 0x4c, 0x58, 0xcf,
+    0xd0, 0xef, 0xaa, 0xfb, 0x43, 0x4d, 0x33, 0x85, 0x45, 0xf9, 0x02, 0x7f, 0x50, 0x3c, 0x9f, 0xa8,
+    0x51, 0xa3, 0x40, 0x8f, 0x92, 0x9d, 0x38, 0xf5, 0xbc, 0xb6, 0xda, 0x21, 0x10, 0xff, 0xf3, 0xd2,
+    0xcd, 0x0c, 0x13, 0xec, 0x5f, 0x97, 0x44, 0x17, 0xc4, 0xa7, 0x7e, 0x3d, 0x64, 0x5d, 0x19, 0x73,
+    0x60, 0x81, 0x4f, 0xdc, 0x22, 0x2a, 0x90, 0x88, 0x46, 0xee, 0xb8, 0x14, 0xde, 0x5e, 0x0b, 0xdb,
+    0xe0, 0x32, 0x3a, 0x0a, 0x49, 0x06, 0x24, 0x5c, 0xc2, 0xd3, 0xac, 0x62, 0x91, 0x95, 0xe4, 0x79,
+    0xe7, 0xc8, 0x37, 0x6d, 0x8d, 0xd5, 0x4e, 0xa9, 0x6c, 0x56, 0xf4, 0xea, 0x65, 0x7a, 0xae, 0x08,
+    0xba, 0x78, 0x25, 0x2e, 0x1c, 0xa6, 0xb4, 0xc6, 0xe8, 0xdd, 0x74, 0x1f, 0x4b, 0xbd, 0x8b, 0x8a,
+    0x70, 0x3e, 0xb5, 0x66, 0x48, 0x03, 0xf6, 0x0e, 0x61, 0x35, 0x57, 0xb9, 0x86, 0xc1, 0x1d, 0x9e,
+    0xe1, 0xf8, 0x98, 0x11, 0x69, 0xd9, 0x8e, 0x94, 0x9b, 0x1e, 0x87, 0xe9, 0xce, 0x55, 0x28, 0xdf,
+    0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb, 0x16,
+];
+
+const LADDER_TWEAKS: [u8; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+
+// Protects pharmacy dispatch records after the link is established
+pub struct EnvelopeCodec {
+    round_keys: [[u8; 16]; 11],
+    mixing_key: u128,
+}
+
+impl EnvelopeCodec {
+    pub fn new(key: &[u8; 16]) -> Self {
+        let mut sealer = EnvelopeCodec {
+            round_keys: Self::schedule(key),
+            mixing_key: 0,
+        };
+        sealer.mixing_key = u128::from_be_bytes(sealer.scramble([0u8; 16]));
+        sealer
+    }
+
+    pub fn seal(&self, nonce: &[u8; 12], header: &[u8], payload: &[u8]) -> Vec<u8> {
+        let mut sealed = self.counter_stream(nonce, payload);
+        let check = self.checksum(header, &sealed) ^ self.check_mask(nonce);
+        sealed.extend_from_slice(&check.to_be_bytes());
+        sealed
+    }
+
+    pub fn open(&self, nonce: &[u8; 12], header: &[u8], sealed: &[u8]) -> Option<Vec<u8>> {
+        if sealed.len() < 16 {
+            return None;
+        }
+        let (body, check) = sealed.split_at(sealed.len() - 16);
+        let expected = (self.checksum(header, body) ^ self.check_mask(nonce)).to_be_bytes();
+        let difference = expected.iter().zip(check).fold(0u8, |acc, (a, b)| acc | (a ^ b));
+        if difference != 0 {
+            return None;
+        }
+        Some(self.counter_stream(nonce, body))
+    }
+
+    fn schedule(key: &[u8; 16]) -> [[u8; 16]; 11] {
+        let mut words = [[0u8; 4]; 44];
+        for (i, word) in words.iter_mut().take(4).enumerate() {
+            word.copy_from_slice(&key[i * 4..i * 4 + 4]);
+        }
+        for i in 4..44 {
+            let mut temp = words[i - 1];
+            if i % 4 == 0 {
+                temp = [
+                    LOOKUP_GRID[temp[1] as usize] ^ LADDER_TWEAKS[i / 4 - 1],
+                    LOOKUP_GRID[temp[2] as usize],
+                    LOOKUP_GRID[temp[3] as usize],
+                    LOOKUP_GRID[temp[0] as usize],
+                ];
+            }
+            for j in 0..4 {
+                words[i][j] = words[i - 4][j] ^ temp[j];
+            }
+        }
+
+        let mut round_keys = [[0u8; 16]; 11];
+        for (round, round_key) in round_keys.iter_mut().enumerate() {
+            for column in 0..4 {
+                round_key[column * 4..column * 4 + 4].copy_from_slice(&words[round * 4 + column]);
+            }
+        }
+        round_keys
+    }
+
+    fn scramble(&self, block: [u8; 16]) -> [u8; 16] {
+        let mut state = block;
+        Self::blend(&mut state, &self.round_keys[0]);
+        for round in 1..11 {
+            for byte in state.iter_mut() {
+                *byte = LOOKUP_GRID[*byte as usize];
+            }
+            Self::rotate_rows(&mut state);
+            if round < 10 {
+                Self::mix_columns(&mut state);
+            }
+            Self::blend(&mut state, &self.round_keys[round]);
+        }
+        state
+    }
+
+    fn blend(state: &mut [u8; 16], round_key: &[u8; 16]) {
+        for (byte, key_byte) in state.iter_mut().zip(round_key.iter()) {
+            *byte ^= key_byte;
+        }
+    }
+
+    fn rotate_rows(state: &mut [u8; 16]) {
+        let original = *state;
+        for column in 0..4 {
+            for row in 0..4 {
+                state[column * 4 + row] = original[((column + row) % 4) * 4 + row];
+            }
+        }
+    }
+
+    fn double(value: u8) -> u8 {
+        (value << 1) ^ if value & 0x80 != 0 { 0x1b } else { 0x00 }
+    }
+
+    fn mix_columns(state: &mut [u8; 16]) {
+        for column in state.chunks_mut(4) {
+            let [a, b, c, d] = [column[0], column[1], column[2], column[3]];
+            let total = a ^ b ^ c ^ d;
+            column[0] = a ^ total ^ Self::double(a ^ b);
+            column[1] = b ^ total ^ Self::double(b ^ c);
+            column[2] = c ^ total ^ Self::double(c ^ d);
+            column[3] = d ^ total ^ Self::double(d ^ a);
+        }
+    }
+
+    fn counter_block(nonce: &[u8; 12], counter: u32) -> [u8; 16] {
+        let mut block = [0u8; 16];
+        block[..12].copy_from_slice(nonce);
+        block[12..].copy_from_slice(&counter.to_be_bytes());
+        block
+    }
+
+    fn counter_stream(&self, nonce: &[u8; 12], data: &[u8]) -> Vec<u8> {
+        let mut output = Vec::with_capacity(data.len());
+        for (index, chunk) in data.chunks(16).enumerate() {
+            let pad = self.scramble(Self::counter_block(nonce, index as u32 + 2));
+            output.extend(chunk.iter().zip(pad.iter()).map(|(a, b)| a ^ b));
+        }
+        output
+    }
+
+    fn check_mask(&self, nonce: &[u8; 12]) -> u128 {
+        u128::from_be_bytes(self.scramble(Self::counter_block(nonce, 1)))
+    }
+
+    fn field_product(x: u128, y: u128) -> u128 {
+        let mut product = 0u128;
+        let mut addend = y;
+        for bit in 0..128 {
+            if (x >> (127 - bit)) & 1 == 1 {
+                product ^= addend;
+            }
+            addend = if addend & 1 == 1 {
+                (addend >> 1) ^ (0xe1u128 << 120)
+            } else {
+                addend >> 1
+            };
+        }
+        product
+    }
+
+    fn checksum(&self, header: &[u8], body: &[u8]) -> u128 {
+        let mut accumulator = 0u128;
+        for chunk in header.chunks(16).chain(body.chunks(16)) {
+            let mut block = [0u8; 16];
+            block[..chunk.len()].copy_from_slice(chunk);
+            accumulator = Self::field_product(accumulator ^ u128::from_be_bytes(block), self.mixing_key);
+        }
+        let lengths = ((header.len() as u128 * 8) << 64) | (body.len() as u128 * 8);
+        Self::field_product(accumulator ^ lengths, self.mixing_key)
+    }
+}
+
+// Keys one side of the pharmacy dispatch link uses for its first record
+pub struct ChannelMaterial {
+    pub key: [u8; 16],
+    pub nonce: [u8; 12],
+}
+
+pub struct LinkNegotiator {
+    exchange: SharedPointExchange,
+    pub random: [u8; 32],
+}
+
+impl LinkNegotiator {
+    pub fn new(scalar: u64, random: [u8; 32]) -> Self {
+        LinkNegotiator {
+            exchange: SharedPointExchange::new(scalar),
+            random,
+        }
+    }
+
+    pub fn share(&self) -> (u64, u64) {
+        self.exchange.share()
+    }
+
+    pub fn derive(&self, peer_share: (u64, u64), initiator_random: &[u8; 32], responder_random: &[u8; 32]) -> ChannelMaterial {
+        let joint = self.exchange.combine(peer_share);
+
+        let mut salt = initiator_random.to_vec();
+        salt.extend_from_slice(responder_random);
+        let condensed = ChannelKeyLadder::condense(&salt, &joint);
+        let material = ChannelKeyLadder::stretch(&condensed, PURPOSE_TAG, 28);
+
+        let mut keys = ChannelMaterial { key: [0u8; 16], nonce: [0u8; 12] };
+        keys.key.copy_from_slice(&material[..16]);
+        keys.nonce.copy_from_slice(&material[16..28]);
+        keys
+    }
+}
+
+// Turns the agreed pharmacy dispatch session value into record keys
+pub struct ChannelKeyLadder;
+
+impl ChannelKeyLadder {
+    const PAD_WIDTH: usize = 64;
+
+    pub fn keyed_fingerprint(key: &[u8], message: &[u8]) -> [u8; 32] {
+        let mut padded = [0u8; Self::PAD_WIDTH];
+        if key.len() > Self::PAD_WIDTH {
+            padded[..32].copy_from_slice(&TranscriptFingerprint::fingerprint(key));
+        } else {
+            padded[..key.len()].copy_from_slice(key);
+        }
+
+        let inner_pad: Vec<u8> = padded.iter().map(|b| b ^ 0x36).collect();
+        let outer_pad: Vec<u8> = padded.iter().map(|b| b ^ 0x5c).collect();
+
+        let mut inner = TranscriptFingerprint::new();
+        inner.absorb(&inner_pad);
+        inner.absorb(message);
+        let inner_value = inner.finish();
+
+        let mut outer = TranscriptFingerprint::new();
+        outer.absorb(&outer_pad);
+        outer.absorb(&inner_value);
+        outer.finish()
+    }
+
+    pub fn condense(salt: &[u8], material: &[u8]) -> [u8; 32] {
+        Self::keyed_fingerprint(salt, material)
+    }
+
+    pub fn stretch(condensed: &[u8], context: &[u8], length: usize) -> Vec<u8> {
+        let mut output = Vec::with_capacity(length);
+        let mut previous: Vec<u8> = Vec::new();
+        let mut counter = 1u8;
+
+        while output.len() < length {
+            let mut input = previous.clone();
+            input.extend_from_slice(context);
+            input.push(counter);
+            previous = Self::keyed_fingerprint(condensed, &input).to_vec();
+            output.extend_from_slice(&previous);
+            counter += 1;
+        }
+
+        output.truncate(length);
+        output
+    }
+}
+
+const PURPOSE_TAG: &[u8] = b"dispatch channel keys";
+const OPERATOR_MODULUS: u64 = 0x26e765e8361e7695;
+const AUTHORITY_DEGREE: u64 = 65537;
+const INITIATOR_SCALAR: u64 = 0x0f8131c437730edf;
+const RESPONDER_SCALAR: u64 = 0x0b9d189e6c0fd4f5;
+const INITIATOR_NONCE: [u8; 32] = [0xeb, 0x94, 0x0b, 0xd5, 0x33, 0x5f, 0x97, 0x3d, 0xaa, 0xd8, 0x61, 0x9b, 0x91, 0xff, 0xc9, 0x11, 0xf5, 0x7c, 0xce, 0xd4, 0x58, 0xbb, 0xbf, 0x2c, 0xe0, 0x37, 0x53, 0xc9, 0xbd, 0xfa, 0x0f, 0xf0];
+const RESPONDER_NONCE: [u8; 32] = [0x16, 0x9d, 0xc9, 0x57, 0x56, 0x74, 0x06, 0x66, 0x76, 0xcf, 0xb0, 0xb4, 0xeb, 0x89, 0x02, 0xc4, 0x42, 0x69, 0xda, 0x1c, 0xf6, 0xba, 0x66, 0xd3, 0xf8, 0xb6, 0xd4, 0xb1, 0x00, 0xa9, 0xea, 0x0e];
+
+fn responder_credential() -> MemberPass {
+    MemberPass {
+        subject: b"dispatch.pharmacy-network.example".to_vec(),
+        share: (0x13eb377c6ab807a2, 0x1473e28a8bf60fd8),
+        endorsement: 0x1b2f66590c5e03dd,
+    }
+}
+
+// Runs both sides of the pharmacy dispatch link setup and returns what the responder recovers
+pub fn establish_link(payload: &[u8]) -> Option<Vec<u8>> {
+    let initiator = LinkNegotiator::new(INITIATOR_SCALAR, INITIATOR_NONCE);
+    let responder = LinkNegotiator::new(RESPONDER_SCALAR, RESPONDER_NONCE);
+
+    let credential = responder_credential();
+    let verifier = EndorsementAuditor::new(OPERATOR_MODULUS, AUTHORITY_DEGREE);
+    if !verifier.accepts(&credential) || !SharedPointExchange::is_valid(credential.share) {
+        return None;
+    }
+
+    let initiator_keys = initiator.derive(credential.share, &initiator.random, &responder.random);
+    let responder_keys = responder.derive(initiator.share(), &initiator.random, &responder.random);
+
+    let sealed = EnvelopeCodec::new(&initiator_keys.key).seal(&initiator_keys.nonce, b"RX-DISPATCH", payload);
+    EnvelopeCodec::new(&responder_keys.key).open(&responder_keys.nonce, b"RX-DISPATCH", &sealed)
+}
+
+fn main() {
+    println!("Pharmacy Dispatch Channel starting...");
+
+    let payload = b"Rx 20931: amoxicillin 500mg x21, patient ref 88-1204";
+    match establish_link(payload) {
+        Some(recovered) if recovered == payload => {
+            println!("Link established, first record delivered ({} bytes)", recovered.len());
+        }
+        Some(_) => println!("Link established but record was corrupted"),
+        None => println!("Link setup rejected"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(text: &str) -> Vec<u8> {
+        (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn digest_known_answer() {
+        // FIPS 180-2 Appendix B.1, message "abc"
+        assert_eq!(
+            TranscriptFingerprint::fingerprint(b"abc").to_vec(),
+            hex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+    }
+
+    #[test]
+    fn keyed_digest_known_answer() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            ChannelKeyLadder::keyed_fingerprint(b"Jefe", b"what do ya want for nothing?").to_vec(),
+            hex("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+    }
+
+    #[test]
+    fn key_expansion_known_answer() {
+        // RFC 5869 test case 1
+        let condensed = ChannelKeyLadder::condense(&hex("000102030405060708090a0b0c"), &hex("0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b"));
+        assert_eq!(
+            condensed.to_vec(),
+            hex("077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5")
+        );
+        assert_eq!(
+            ChannelKeyLadder::stretch(&condensed, &hex("f0f1f2f3f4f5f6f7f8f9"), 42),
+            hex("3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865")
+        );
+    }
+
+    #[test]
+    fn record_sealer_known_answer() {
+        // GCM specification test case 4
+        let mut key = [0u8; 16];
+        key.copy_from_slice(&hex("feffe9928665731c6d6a8f9467308308"));
+        let mut nonce = [0u8; 12];
+        nonce.copy_from_slice(&hex("cafebabefacedbaddecaf888"));
+        let header = hex("feedfacedeadbeeffeedfacedeadbeefabaddad2");
+        let payload = hex(concat!(
+            "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72",
+            "1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39"
+        ));
+
+        let sealed = EnvelopeCodec::new(&key).seal(&nonce, &header, &payload);
+        assert_eq!(
+            sealed,
+            hex(concat!(
+                "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e",
+                "21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091",
+                "5bc94fbc3221a5db94fae95ae7121a47"
+            ))
+        );
+    }
+
+    #[test]
+    fn tampered_record_rejected() {
+        let sealer = EnvelopeCodec::new(&[7u8; 16]);
+        let nonce = [9u8; 12];
+        let mut sealed = sealer.seal(&nonce, b"hdr", b"telemetry frame");
+        assert_eq!(sealer.open(&nonce, b"hdr", &sealed).unwrap(), b"telemetry frame".to_vec());
+        sealed[0] ^= 1;
+        assert!(sealer.open(&nonce, b"hdr", &sealed).is_none());
+    }
+
+    #[test]
+    fn exchange_agrees() {
+        let initiator = SharedPointExchange::new(INITIATOR_SCALAR);
+        let responder = SharedPointExchange::new(RESPONDER_SCALAR);
+        assert!(SharedPointExchange::is_valid(initiator.share()));
+        assert_eq!(responder.share(), responder_credential().share);
+        assert_eq!(initiator.combine(responder.share()), responder.combine(initiator.share()));
+    }
+
+    #[test]
+    fn credential_endorsement() {
+        let verifier = EndorsementAuditor::new(OPERATOR_MODULUS, AUTHORITY_DEGREE);
+        let credential = responder_credential();
+        assert!(verifier.accepts(&credential));
+
+        let mut forged = credential.clone();
+        forged.subject.push(b'!');
+        assert!(!verifier.accepts(&forged));
+    }
+
+    #[test]
+    fn link_round_trip() {
+        let payload = b"Rx 20931: amoxicillin 500mg x21, patient ref 88-1204";
+        assert_eq!(establish_link(payload).unwrap(), payload.to_vec());
+    }
+}
@@ -0,0 +1,141 @@
+// Player Save Slot Store
+// Sealed save snapshot frames for the save slot station
+
+// Player Save Slot Store: each save snapshot frame gets its own frame number, sent ahead of the body
+pub struct LeaderboardSealer {
+    engine: PlayerBraid,
+}
+
+impl LeaderboardSealer {
+    pub fn new(key: [u8; 16]) -> Self {
+        LeaderboardSealer { engine: PlayerBraid::new(&key) }
+    }
+
+    fn mask(&self, frame: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut out = Vec::with_capacity(data.len());
+        for (index, chunk) in data.chunks(8).enumerate() {
+            let mut counter = [0u8; 8];
+            counter[..4].copy_from_slice(frame);
+            counter[4..].copy_from_slice(&(index as u32).to_be_bytes());
+            let pad = self.engine.knot(counter);
+            out.extend(chunk.iter().zip(pad.iter()).map(|(byte, key)| byte ^ key));
+        }
+        out
+    }
+
+    pub fn seal(&self, body: &[u8]) -> Vec<u8> {
+        let mut frame = [0u8; 4];
+        respawn_noise(&mut frame);
+        let mut sealed = frame.to_vec();
+        sealed.extend(self.mask(&frame, body));
+        sealed
+    }
+
+    pub fn open(&self, sealed: &[u8]) -> Vec<u8> {
+        let frame = [sealed[0], sealed[1], sealed[2], sealed[3]];
+        self.mask(&frame, &sealed[4..])
+    }
+}
+
+// Operating-system noise for keys and frame numbers
+fn respawn_noise(out: &mut [u8]) {
+    use std::io::Read;
+    std::fs::File::open("/dev/urandom")
+        .and_then(|mut source| source.read_exact(out))
+        .expect("save slot station has no entropy source");
+}
+
+// Player Save Slot Store: store key shared by every save slot station
+const LEADERBOARD_SECRET: [u8; 16] = [0xff, 0xfc, 0xe5, 0xcc, 0x91, 0xd1, 0xda, 0xc4, 0x91, 0x97, 0xb7, 0xbd, 0xf6, 0x2e, 0x52, 0xb2];
+
+fn avatar_key() -> [u8; 16] {
+    LEADERBOARD_SECRET
+}
+
+// Frame shuffler: 64-bit blocks under a 128-bit store key
+const GOLDEN_STEP: u32 = 0x9e3779b9;
+
+pub struct PlayerBraid {
+    key: [u32; 4],
+}
+
+impl PlayerBraid {
+    pub fn new(key: &[u8; 16]) -> Self {
+        let mut words = [0u32; 4];
+        for i in 0..4 {
+            words[i] = u32::from_be_bytes([key[4 * i], key[4 * i + 1], key[4 * i + 2], key[4 * i + 3]]);
+        }
+        PlayerBraid { key: words }
+    }
+
+    fn split(block: [u8; 8]) -> (u32, u32) {
+        (
+            u32::from_be_bytes([block[0], block[1], block[2], block[3]]),
+            u32::from_be_bytes([block[4], block[5], block[6], block[7]]),
+        )
+    }
+
+    fn join(left: u32, right: u32) -> [u8; 8] {
+        let mut block = [0u8; 8];
+        block[..4].copy_from_slice(&left.to_be_bytes());
+        block[4..].copy_from_slice(&right.to_be_bytes());
+        block
+    }
+
+    fn round(&self, value: u32, sum: u32, first: usize) -> u32 {
+        (value << 4).wrapping_add(self.key[first])
+            ^ value.wrapping_add(sum)
+            ^ (value >> 5).wrapping_add(self.key[first + 1])
+    }
+
+    pub fn knot(&self, block: [u8; 8]) -> [u8; 8] {
+        let (mut left, mut right) = Self::split(block);
+        let mut sum = 0u32;
+        for _ in 0..32 {
+            sum = sum.wrapping_add(GOLDEN_STEP);
+            left = left.wrapping_add(self.round(right, sum, 0));
+            right = right.wrapping_add(self.round(left, sum, 2));
+        }
+        Self::join(left, right)
+    }
+
+    pub fn unbraid(&self, block: [u8; 8]) -> [u8; 8] {
+        let (mut left, mut right) = Self::split(block);
+        let mut sum = GOLDEN_STEP.wrapping_mul(32);
+        for _ in 0..32 {
+            right = right.wrapping_sub(self.round(left, sum, 2));
+            left = left.wrapping_sub(self.round(right, sum, 0));
+            sum = sum.wrapping_sub(GOLDEN_STEP);
+        }
+        Self::join(left, right)
+    }
+}
+
+fn main() {
+    let store = LeaderboardSealer::new(avatar_key());
+    let sealed = store.seal(b"PLAYER 88213 lvl=42 gold=15930 zone=frost_keep quest=7/12");
+    let restored = store.open(&sealed) == b"PLAYER 88213 lvl=42 gold=15930 zone=frost_keep quest=7/12".to_vec();
+    println!("Player Save Slot Store: sealed {} bytes, restored: {}", sealed.len(), restored);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_known_answer() {
+        // Wheeler-Needham reference vector, zero key and zero block
+        let engine = PlayerBraid::new(&[0u8; 16]);
+        let expected = [0x41, 0xea, 0x3a, 0x0a, 0x94, 0xba, 0xa9, 0x40];
+        assert_eq!(engine.knot([0u8; 8]), expected);
+        assert_eq!(engine.unbraid(expected), [0u8; 8]);
+    }
+
+    #[test]
+    fn store_round_trip() {
+        let store = LeaderboardSealer::new(avatar_key());
+        let sealed = store.seal(b"PLAYER 88213 lvl=42 gold=15930 zone=frost_keep quest=7/12");
+        assert_ne!(sealed, b"PLAYER 88213 lvl=42 gold=15930 zone=frost_keep quest=7/12".to_vec());
+        assert_eq!(store.open(&sealed), b"PLAYER 88213 lvl=42 gold=15930 zone=frost_keep quest=7/12".to_vec());
+    }
+}
@@ -0,0 +1,156 @@
+
+pub struct ClinicalEnvelope {
+    engine: ReferKnot,
+}
+
+impl ClinicalEnvelope {
+    pub fn new(key: [u8; 16]) -> Self {
+        ClinicalEnvelope { engine: ReferKnot::new(&key) }
+    }
+
+    fn mask(&self, frame: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut out = Vec::with_capacity(data.len());
+        for (index, chunk) in data.chunks(8).enumerate() {
+            let mut counter = [0u8; 8];
+            counter[..4].copy_from_slice(frame);
+            counter[4..].copy_from_slice(&(index as u32).to_be_bytes());
+            let pad = self.engine.dispense_block(counter);
+            out.extend(chunk.iter().zip(pad.iter()).map(|(byte, key)| byte ^ key));
+        }
+        out
+    }
+
+    pub fn seal(&self, body: &[u8]) -> Vec<u8> {
+        let mut frame = [0u8; 4];
+        triage_noise(&mut frame);
+        let mut sealed = frame.to_vec();
+        sealed.extend(self.mask(&frame, body));
+        sealed
+    }
+
+    pub fn open(&self, sealed: &[u8]) -> Vec<u8> {
+        let frame = [sealed[0], sealed[1], sealed[2], sealed[3]];
+        self.mask(&frame, &sealed[4..])
+    }
+}
+
+const INPATIENT_SALT: &[u8] = b"BedsideDeviceSalt";
+
+fn draw_key() -> [u8; 16] {
+    let passphrase = std::env::var("BEDSIDE_PASSPHRASE").unwrap_or_default();
+    let mut material = INPATIENT_SALT.to_vec();
+    material.extend_from_slice(passphrase.as_bytes());
+
+    let mut left = [0u8; 8];
+    let mut right = [0x5cu8; 8];
+    for _ in 0..96 {
+        for chunk in material.chunks(16) {
+            let mut chunk_key = [0u8; 16];
+            chunk_key[..chunk.len()].copy_from_slice(chunk);
+            let engine = ReferKnot::new(&chunk_key);
+            let (next_left, next_right) = (engine.dispense_block(left), engine.dispense_block(right));
+            for i in 0..8 {
+                left[i] ^= next_left[i];
+                right[i] ^= next_right[i];
+            }
+        }
+    }
+    let mut key = [0u8; 16];
+    key[..8].copy_from_slice(&left);
+    key[8..].copy_from_slice(&right);
+    key
+}
+
+const SCHEDULE_STEP: u32 = 0x9e3779b9;
+
+pub struct ReferKnot {
+    key: [u32; 4],
+}
+
+impl ReferKnot {
+    pub fn new(key: &[u8; 16]) -> Self {
+        let mut words = [0u32; 4];
+        for i in 0..4 {
+            words[i] = u32::from_be_bytes([key[4 * i], key[4 * i + 1], key[4 * i + 2], key[4 * i + 3]]);
+        }
+        ReferKnot { key: words }
+    }
+
+    fn split(block: [u8; 8]) -> (u32, u32) {
+        (
+            u32::from_be_bytes([block[0], block[1], block[2], block[3]]),
+            u32::from_be_bytes([block[4], block[5], block[6], block[7]]),
+        )
+    }
+
+    fn join(left: u32, right: u32) -> [u8; 8] {
+        let mut block = [0u8; 8];
+        block[..4].copy_from_slice(&left.to_be_bytes());
+        block[4..].copy_from_slice(&right.to_be_bytes());
+        block
+    }
+
+    fn round(&self, value: u32, sum: u32, first: usize) -> u32 {
+        (value << 4).wrapping_add(self.key[first])
+            ^ value.wrapping_add(sum)
+            ^ (value >> 5).wrapping_add(self.key[first + 1])
+    }
+
+    pub fn dispense_block(&self, block: [u8; 8]) -> [u8; 8] {
+        let (mut left, mut right) = Self::split(block);
+        let mut sum = 0u32;
+        for _ in 0..32 {
+            sum = sum.wrapping_add(SCHEDULE_STEP);
+            left = left.wrapping_add(self.round(right, sum, 0));
+            right = right.wrapping_add(self.round(left, sum, 2));
+        }
+        Self::join(left, right)
+    }
+
+    pub fn untwist(&self, block: [u8; 8]) -> [u8; 8] {
+        let (mut left, mut right) = Self::split(block);
+        let mut sum = SCHEDULE_STEP.wrapping_mul(32);
+        for _ in 0..32 {
+            right = right.wrapping_sub(self.round(left, sum, 2));
+            left = left.wrapping_sub(self.round(right, sum, 0));
+            sum = sum.wrapping_sub(SCHEDULE_STEP);
+        }
+        Self::join(left, right)
+    }
+}
+
+fn triage_noise(out: &mut [u8]) {
+    use std::io::Read;
+    std::fs::File::open("/dev/urandom")
+        .and_then(|mut source| source.read_exact(out))
+        .expect("clinical chart station has no entropy source");
+}
+
+fn main() {
+    let store = ClinicalEnvelope::new(draw_key());
+    let sealed = store.seal(b"MRN 00482913 BP 128/82 HR 71 SpO2 98% note=post-op day 2");
+    let restored = store.open(&sealed) == b"MRN 00482913 BP 128/82 HR 71 SpO2 98% note=post-op day 2".to_vec();
+    println!("Clinical Chart Archive: sealed {} bytes, restored: {}", sealed.len(), restored);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_known_answer() {
+        // Wheeler-Needham reference vector, zero key and zero block
+        let engine = ReferKnot::new(&[0u8; 16]);
+        let expected = [0x41, 0xea, 0x3a, 0x0a, 0x94, 0xba, 0xa9, 0x40];
+        assert_eq!(engine.dispense_block([0u8; 8]), expected);
+        assert_eq!(engine.untwist(expected), [0u8; 8]);
+    }
+
+    #[test]
+    fn store_round_trip() {
+        let store = ClinicalEnvelope::new(draw_key());
+        let sealed = store.seal(b"MRN 00482913 BP 128/82 HR 71 SpO2 98% note=post-op day 2");
+        assert_ne!(sealed, b"MRN 00482913 BP 128/82 HR 71 SpO2 98% note=post-op day 2".to_vec());
+        assert_eq!(store.open(&sealed), b"MRN 00482913 BP 128/82 HR 71 SpO2 98% note=post-op day 2".to_vec());
+    }
+}
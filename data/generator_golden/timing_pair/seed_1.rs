@@ -0,0 +1,279 @@
+// Player Save Slot Store
+// Issues per-save snapshot access keys and notarized serials for the save slot desk
+
+// Player Save Slot Store: one diversified key and one notarized serial per save snapshot
+pub struct MultiplayerLobbyDesk {
+    diversifier: PlayerKneader,
+    notary: LeaderboardNotary,
+    modulus: u64,
+}
+
+impl MultiplayerLobbyDesk {
+    pub fn new(master_key: &[u8; 16], modulus: u64, private_exponent: u64) -> Self {
+        MultiplayerLobbyDesk {
+            diversifier: PlayerKneader::new(master_key),
+            notary: LeaderboardNotary::new(modulus, private_exponent),
+            modulus,
+        }
+    }
+
+    // The serial padded to one block and enciphered under the master key is the record key
+    pub fn loot_record(&self, serial: u64) -> ([u8; 16], u64) {
+        let mut block = [0u8; 16];
+        block[..8].copy_from_slice(&serial.to_be_bytes());
+        block[8] = 0x80;
+        (self.diversifier.level_block(block), self.notary.notarize(serial))
+    }
+
+    pub fn check(&self, serial: u64, signature: u64) -> bool {
+        LeaderboardNotary::verify(self.modulus, serial, signature)
+    }
+}
+
+// Keys are provisioned as hex in the environment, never compiled in
+fn loot_secret(name: &str) -> Option<Vec<u8>> {
+    let text = std::env::var(name).ok()?;
+    if text.len() % 2 != 0 {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&text[index..index + 2], 16).ok())
+        .collect()
+}
+
+// Serial notary: textbook signatures over a reduced 62-bit modulus
+// The ladder walks all 64 exponent bits and swaps registers with a mask, so the work never depends on the key
+const VERIFY_EXPONENT: u64 = 65537;
+const EXPONENT_BITS: u32 = 64;
+
+// Moduli stay below 2^63 so the sum cannot overflow; the reduced value is picked with the borrow mask
+fn add_mod(left: u64, right: u64, modulus: u64) -> u64 {
+    let sum = left + right;
+    let (reduced, borrow) = sum.overflowing_sub(modulus);
+    let keep = 0u64.wrapping_sub(borrow as u64);
+    (sum & keep) | (reduced & !keep)
+}
+
+// Shift-and-add product: 64 doublings and 64 masked additions whatever the operands are
+fn multiply_mod(left: u64, right: u64, modulus: u64) -> u64 {
+    let mut product = 0u64;
+    for bit in (0..64).rev() {
+        product = add_mod(product, product, modulus);
+        product = add_mod(product, left & 0u64.wrapping_sub((right >> bit) & 1), modulus);
+    }
+    product
+}
+
+fn conditional_swap(first: &mut u64, second: &mut u64, choice: u64) {
+    let difference = 0u64.wrapping_sub(choice) & (*first ^ *second);
+    *first ^= difference;
+    *second ^= difference;
+}
+
+fn power_mod(base: u64, exponent: u64, modulus: u64) -> u64 {
+    let mut low = 1u64;
+    let mut high = base % modulus;
+    for bit in (0..EXPONENT_BITS).rev() {
+        let choice = (exponent >> bit) & 1;
+        conditional_swap(&mut low, &mut high, choice);
+        high = multiply_mod(low, high, modulus);
+        low = multiply_mod(low, low, modulus);
+        conditional_swap(&mut low, &mut high, choice);
+    }
+    low
+}
+
+pub struct LeaderboardNotary {
+    modulus: u64,
+    private_exponent: u64,
+}
+
+impl LeaderboardNotary {
+    pub fn new(modulus: u64, private_exponent: u64) -> Self {
+        assert!(modulus >> 63 == 0, "ladder arithmetic needs a modulus below 2^63");
+        LeaderboardNotary { modulus, private_exponent }
+    }
+
+    pub fn notarize(&self, serial: u64) -> u64 {
+        power_mod(serial % self.modulus, self.private_exponent, self.modulus)
+    }
+
+    pub fn verify(modulus: u64, serial: u64, signature: u64) -> bool {
+        power_mod(signature, VERIFY_EXPONENT, modulus) == serial % modulus
+    }
+}
+
+// Serial diversifier: 128-bit blocks under the desk master key, ten passes computed without lookup tables
+// Every byte goes through the same fixed sequence of operations; masks replace data-dependent branches
+fn double_in_field(value: u8) -> u8 {
+    (value << 1) ^ (0x1b & 0u8.wrapping_sub(value >> 7))
+}
+
+fn multiply_in_field(left: u8, right: u8) -> u8 {
+    let mut product = 0u8;
+    let mut addend = left;
+    for bit in 0..8 {
+        product ^= addend & 0u8.wrapping_sub((right >> bit) & 1);
+        addend = double_in_field(addend);
+    }
+    product
+}
+
+// value^254 is the field inverse (zero stays zero): a fixed chain of squarings and products
+fn substitute(value: u8) -> u8 {
+    let square = multiply_in_field(value, value);
+    let cube = multiply_in_field(square, value);
+    let sixth = multiply_in_field(cube, cube);
+    let twelfth = multiply_in_field(sixth, sixth);
+    let mut power = multiply_in_field(twelfth, cube);
+    for _ in 0..4 {
+        power = multiply_in_field(power, power);
+    }
+    let inverse = multiply_in_field(multiply_in_field(power, twelfth), square);
+    inverse ^ inverse.rotate_left(1) ^ inverse.rotate_left(2) ^ inverse.rotate_left(3) ^ inverse.rotate_left(4) ^ 0x63
+}
+
+fn substitute_word(word: u32) -> u32 {
+    let bytes = word.to_be_bytes();
+    u32::from_be_bytes([substitute(bytes[0]), substitute(bytes[1]), substitute(bytes[2]), substitute(bytes[3])])
+}
+
+pub struct PlayerKneader {
+    schedule: [u32; 44],
+}
+
+impl PlayerKneader {
+    pub fn new(key: &[u8; 16]) -> Self {
+        let mut schedule = [0u32; 44];
+        for (index, chunk) in key.chunks_exact(4).enumerate() {
+            schedule[index] = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        let mut round_constant = 1u8;
+        for index in 4..44 {
+            let mut word = schedule[index - 1];
+            if index % 4 == 0 {
+                word = substitute_word(word.rotate_left(8)) ^ ((round_constant as u32) << 24);
+                round_constant = double_in_field(round_constant);
+            }
+            schedule[index] = schedule[index - 4] ^ word;
+        }
+        PlayerKneader { schedule }
+    }
+
+    fn add_pass_key(&self, state: &mut [u8; 16], pass: usize) {
+        for column in 0..4 {
+            let bytes = self.schedule[4 * pass + column].to_be_bytes();
+            for row in 0..4 {
+                state[4 * column + row] ^= bytes[row];
+            }
+        }
+    }
+
+    fn shift_rows(state: &mut [u8; 16]) {
+        let previous = *state;
+        for column in 0..4 {
+            for row in 1..4 {
+                state[4 * column + row] = previous[4 * ((column + row) % 4) + row];
+            }
+        }
+    }
+
+    fn mix_columns(state: &mut [u8; 16]) {
+        for column in state.chunks_exact_mut(4) {
+            let [a, b, c, d] = [column[0], column[1], column[2], column[3]];
+            let total = a ^ b ^ c ^ d;
+            column[0] = a ^ total ^ double_in_field(a ^ b);
+            column[1] = b ^ total ^ double_in_field(b ^ c);
+            column[2] = c ^ total ^ double_in_field(c ^ d);
+            column[3] = d ^ total ^ double_in_field(d ^ a);
+        }
+    }
+
+    pub fn level_block(&self, block: [u8; 16]) -> [u8; 16] {
+        let mut state = block;
+        self.add_pass_key(&mut state, 0);
+        for pass in 1..=10 {
+            for byte in state.iter_mut() {
+                *byte = substitute(*byte);
+            }
+            Self::shift_rows(&mut state);
+            if pass != 10 {
+                Self::mix_columns(&mut state);
+            }
+            self.add_pass_key(&mut state, pass);
+        }
+        state
+    }
+}
+
+fn main() {
+    let master = loot_secret("SEASONAL_ROOT_KEY").and_then(|bytes| <[u8; 16]>::try_from(bytes).ok());
+    let signing = loot_secret("ISSUER_SIGNING_KEY").and_then(|bytes| <[u8; 16]>::try_from(bytes).ok());
+    let (Some(master), Some(signing)) = (master, signing) else {
+        eprintln!("Player Save Slot Store: set SEASONAL_ROOT_KEY (16 bytes) and ISSUER_SIGNING_KEY (modulus || exponent) as hex");
+        return;
+    };
+    let modulus = u64::from_be_bytes(signing[..8].try_into().unwrap());
+    let private_exponent = u64::from_be_bytes(signing[8..].try_into().unwrap());
+    let station = MultiplayerLobbyDesk::new(&master, modulus, private_exponent);
+
+    let serial = 768265;
+    let (key, signature) = station.loot_record(serial);
+    println!("Player Save Slot Store: save snapshot {} issued a {}-byte key, notarized: {}",
+             serial, key.len(), station.check(serial, signature));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reduced test key: (2^31 - 1) * 2147483629 with public exponent 65537
+    const TEST_MODULUS: u64 = 4611685975477714963;
+    const TEST_PRIVATE_EXPONENT: u64 = 551823266033158481;
+
+    #[test]
+    fn block_known_answer() {
+        // FIPS-197 Appendix C.1
+        let key: [u8; 16] = core::array::from_fn(|index| index as u8);
+        let plaintext: [u8; 16] = core::array::from_fn(|index| (index as u8) * 0x11);
+        let expected = [
+            0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30, 0xd8, 0xcd, 0xb7, 0x80, 0x70, 0xb4, 0xc5, 0x5a,
+        ];
+        assert_eq!(PlayerKneader::new(&key).level_block(plaintext), expected);
+    }
+
+    #[test]
+    fn cipher_example_known_answer() {
+        // FIPS-197 Appendix B cipher example
+        let key = [
+            0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+        ];
+        let plaintext = [
+            0x32, 0x43, 0xf6, 0xa8, 0x88, 0x5a, 0x30, 0x8d, 0x31, 0x31, 0x98, 0xa2, 0xe0, 0x37, 0x07, 0x34,
+        ];
+        let expected = [
+            0x39, 0x25, 0x84, 0x1d, 0x02, 0xdc, 0x09, 0xfb, 0xdc, 0x11, 0x85, 0x97, 0x19, 0x6a, 0x0b, 0x32,
+        ];
+        assert_eq!(PlayerKneader::new(&key).level_block(plaintext), expected);
+    }
+
+    #[test]
+    fn signature_vector() {
+        let notary = LeaderboardNotary::new(TEST_MODULUS, TEST_PRIVATE_EXPONENT);
+        assert_eq!(notary.notarize(0x1234_5678_9abc), 4570660331381003182);
+        assert_eq!(notary.notarize(42), 1233482865614544846);
+        assert!(LeaderboardNotary::verify(TEST_MODULUS, 42, 1233482865614544846));
+        assert!(!LeaderboardNotary::verify(TEST_MODULUS, 43, 1233482865614544846));
+    }
+
+    #[test]
+    fn issued_serials_verify() {
+        let station = MultiplayerLobbyDesk::new(&[0xff, 0xfc, 0xe5, 0xcc, 0x91, 0xd1, 0xda, 0xc4, 0x91, 0x97, 0xb7, 0xbd, 0xf6, 0x2e, 0x52, 0xb2], TEST_MODULUS, TEST_PRIVATE_EXPONENT);
+        let (first_key, signature) = station.loot_record(90578);
+        let (second_key, _) = station.loot_record(90579);
+        assert_ne!(first_key, second_key);
+        assert!(station.check(90578, signature));
+        assert!(!station.check(90579, signature));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod ticket;
+
+pub use ticket::{LegacyTicket, TicketBridge};
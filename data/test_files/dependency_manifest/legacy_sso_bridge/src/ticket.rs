@@ -0,0 +1,54 @@
+//! 구형 SSO 티켓 변환
+//!
+//! 구형 포털은 티켓 본문을 고정 키로 봉인하고 IdP 키로 서명한다. 새 게이트웨이로 넘기기 전에
+//! 봉인을 풀고 서명을 확인한 뒤, 쿠키 지문을 다시 계산한다.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use md5::{Digest, Md5};
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::rsa::Rsa;
+use openssl::sign::Verifier;
+use openssl::symm::{decrypt, Cipher};
+
+pub struct LegacyTicket {
+    pub subject: String,
+    pub issued_at: i64,
+}
+
+pub struct TicketBridge {
+    portal_key: [u8; 24],
+    portal_iv: [u8; 8],
+    idp_public_pem: Vec<u8>,
+}
+
+impl TicketBridge {
+    pub fn new(portal_key: [u8; 24], portal_iv: [u8; 8], idp_public_pem: Vec<u8>) -> Self {
+        TicketBridge { portal_key, portal_iv, idp_public_pem }
+    }
+
+    pub fn open(&self, ticket_b64: &str, signature_b64: &str) -> Option<LegacyTicket> {
+        let sealed = STANDARD.decode(ticket_b64).ok()?;
+        let signature = STANDARD.decode(signature_b64).ok()?;
+
+        let idp = PKey::from_rsa(Rsa::public_key_from_pem(&self.idp_public_pem).ok()?).ok()?;
+        let mut verifier = Verifier::new(MessageDigest::sha1(), &idp).ok()?;
+        verifier.update(&sealed).ok()?;
+        if !verifier.verify(&signature).ok()? {
+            return None;
+        }
+
+        let body = decrypt(Cipher::des_ede3_cbc(), &self.portal_key, Some(&self.portal_iv), &sealed).ok()?;
+        let text = String::from_utf8(body).ok()?;
+        let (subject, issued) = text.split_once('|')?;
+        Some(LegacyTicket { subject: subject.to_string(), issued_at: issued.parse().ok()? })
+    }
+
+    pub fn cookie_fingerprint(ticket: &LegacyTicket, user_agent: &str) -> String {
+        let mut hasher = Md5::new();
+        hasher.update(ticket.subject.as_bytes());
+        hasher.update(ticket.issued_at.to_be_bytes());
+        hasher.update(user_agent.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+}
@@ -0,0 +1,46 @@
+// 데스크톱 앱 라이선스 서버
+//
+// 라이선스 본문은 제품 키로 봉인하고, 발급 서버 키로 서명해 내려보낸다.
+// 장비 지문은 하드웨어 식별자 묶음의 다이제스트로 만든다.
+
+const express = require('express');
+const NodeRSA = require('node-rsa');
+const CryptoJS = require('crypto-js');
+
+const issuer = new NodeRSA({ b: 2048 });
+issuer.setOptions({ signingScheme: 'pkcs1-sha256' });
+
+const PRODUCT_SECRET = process.env.PRODUCT_SECRET || 'desk-suite-2019';
+
+function machineFingerprint(hw) {
+  return CryptoJS.MD5([hw.board, hw.cpu, hw.disk].join(':')).toString();
+}
+
+function issueLicense(customer, hw, seats) {
+  const body = JSON.stringify({
+    customer,
+    seats,
+    machine: machineFingerprint(hw),
+    expires: Date.now() + 365 * 24 * 3600 * 1000,
+  });
+  const sealed = CryptoJS.AES.encrypt(body, PRODUCT_SECRET).toString();
+  return { sealed, signature: issuer.sign(sealed, 'base64') };
+}
+
+const app = express();
+app.use(express.json());
+
+app.post('/licenses', (req, res) => {
+  const { customer, hardware, seats } = req.body;
+  res.json(issueLicense(customer, hardware, seats || 1));
+});
+
+app.get('/issuer.pem', (req, res) => {
+  res.type('text/plain').send(issuer.exportKey('pkcs8-public-pem'));
+});
+
+module.exports = { issueLicense, machineFingerprint };
+
+if (require.main === module) {
+  app.listen(process.env.PORT || 8080);
+}
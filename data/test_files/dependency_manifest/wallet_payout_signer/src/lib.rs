@@ -0,0 +1,42 @@
+//! 가맹점 정산 배치 서명기
+//!
+//! 배치 본문을 직렬화해 지갑 키로 봉인하고, 배치 시드는 수탁 기관 키로 감싸 함께 보낸다.
+//! 패딩 난수는 호출자가 넘긴 RNG에서 뽑으므로, 시드를 고정한 RNG로 같은 봉인 결과를 재현할 수 있다.
+
+use custody::{Pkcs1v15Encrypt, RsaPublicKey};
+use k256::ecdsa::{signature::Signer, Signature as PayoutSeal, SigningKey as PayoutKey};
+use rand::{CryptoRng, RngCore};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+#[derive(Serialize)]
+pub struct PayoutBatch {
+    pub merchant_id: String,
+    pub settlement_date: String,
+    pub entries: Vec<(String, u64)>,
+}
+
+pub struct SealedBatch {
+    pub body: Vec<u8>,
+    pub fingerprint: String,
+    pub seal: String,
+    pub escrowed_seed: Vec<u8>,
+}
+
+pub fn fingerprint(body: &[u8]) -> String {
+    hex::encode(Sha256::digest(body))
+}
+
+pub fn seal_batch<R: CryptoRng + RngCore>(rng: &mut R, key: &PayoutKey, custodian: &RsaPublicKey,
+                                          batch: &PayoutBatch, seed: &[u8; 32]) -> Result<SealedBatch, custody::Error> {
+    let body = serde_json::to_vec(batch).expect("batch serializes");
+    let seal: PayoutSeal = key.sign(&body);
+    let escrowed_seed = custodian.encrypt(rng, Pkcs1v15Encrypt, seed)?;
+
+    Ok(SealedBatch {
+        fingerprint: fingerprint(&body),
+        seal: hex::encode(seal.to_bytes()),
+        escrowed_seed,
+        body,
+    })
+}
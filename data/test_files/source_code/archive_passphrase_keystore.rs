@@ -0,0 +1,392 @@
+// Archive Passphrase Keystore
+// Turns a backup passphrase into the archive master key for nightly snapshot bundles
+
+const DIGEST_LEN: usize = 32;
+const BLOCK_LEN: usize = 64;
+const CELL_WORDS: usize = 16;
+
+// Cost profile written into every bundle header so restores can rebuild the key
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CostProfile {
+    pub log2_slots: u8,
+    pub cell_pairs: u32,
+    pub lanes: u32,
+}
+
+impl CostProfile {
+    pub const NIGHTLY: CostProfile = CostProfile { log2_slots: 15, cell_pairs: 8, lanes: 1 };
+
+    fn slots(&self) -> usize {
+        1usize << self.log2_slots
+    }
+
+    fn lane_bytes(&self) -> usize {
+        128 * self.cell_pairs as usize
+    }
+}
+
+struct Sha256State {
+    h: [u32; 8],
+    tail: Vec<u8>,
+    length: u64,
+}
+
+const SHA_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+impl Sha256State {
+    fn new() -> Self {
+        Sha256State {
+            h: [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19],
+            tail: Vec::with_capacity(BLOCK_LEN),
+            length: 0,
+        }
+    }
+
+    fn block(&mut self, chunk: &[u8]) {
+        let mut w = [0u32; 64];
+        for (i, word) in chunk.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let (mut a, mut b, mut c, mut d) = (self.h[0], self.h[1], self.h[2], self.h[3]);
+        let (mut e, mut f, mut g, mut hh) = (self.h[4], self.h[5], self.h[6], self.h[7]);
+        for i in 0..64 {
+            let t1 = hh
+                .wrapping_add(e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25))
+                .wrapping_add((e & f) ^ (!e & g))
+                .wrapping_add(SHA_K[i])
+                .wrapping_add(w[i]);
+            let t2 = (a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22))
+                .wrapping_add((a & b) ^ (a & c) ^ (b & c));
+            hh = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+
+        for (slot, value) in self.h.iter_mut().zip([a, b, c, d, e, f, g, hh]) {
+            *slot = slot.wrapping_add(value);
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.length += data.len() as u64;
+        self.tail.extend_from_slice(data);
+        let whole = self.tail.len() - self.tail.len() % BLOCK_LEN;
+        let pending: Vec<u8> = self.tail.drain(..whole).collect();
+        for chunk in pending.chunks(BLOCK_LEN) {
+            self.block(chunk);
+        }
+    }
+
+    fn digest(mut self) -> [u8; DIGEST_LEN] {
+        let bits = self.length.wrapping_mul(8);
+        let mut padding = vec![0x80u8];
+        while (self.tail.len() + padding.len()) % BLOCK_LEN != 56 {
+            padding.push(0);
+        }
+        padding.extend_from_slice(&bits.to_be_bytes());
+        self.update(&padding);
+
+        let mut out = [0u8; DIGEST_LEN];
+        for (i, word) in self.h.iter().enumerate() {
+            out[4 * i..4 * i + 4].copy_from_slice(&word.to_be_bytes());
+        }
+        out
+    }
+}
+
+fn keyed_digest(key: &[u8], message: &[u8]) -> [u8; DIGEST_LEN] {
+    let mut k0 = [0u8; BLOCK_LEN];
+    if key.len() > BLOCK_LEN {
+        let mut hasher = Sha256State::new();
+        hasher.update(key);
+        k0[..DIGEST_LEN].copy_from_slice(&hasher.digest());
+    } else {
+        k0[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Sha256State::new();
+    inner.update(&k0.map(|b| b ^ 0x36));
+    inner.update(message);
+    let inner_digest = inner.digest();
+
+    let mut outer = Sha256State::new();
+    outer.update(&k0.map(|b| b ^ 0x5c));
+    outer.update(&inner_digest);
+    outer.digest()
+}
+
+// Single-pass expansion of the passphrase over a salt (one chained round per output block)
+fn spread_passphrase(passphrase: &[u8], salt: &[u8], rounds: u32, out_len: usize) -> Vec<u8> {
+    let mut out = Vec::with_capacity(out_len);
+    let mut counter: u32 = 1;
+    while out.len() < out_len {
+        let mut seed = salt.to_vec();
+        seed.extend_from_slice(&counter.to_be_bytes());
+        let mut link = keyed_digest(passphrase, &seed);
+        let mut acc = link;
+        for _ in 1..rounds {
+            link = keyed_digest(passphrase, &link);
+            for (a, l) in acc.iter_mut().zip(link.iter()) {
+                *a ^= l;
+            }
+        }
+        let take = (out_len - out.len()).min(DIGEST_LEN);
+        out.extend_from_slice(&acc[..take]);
+        counter += 1;
+    }
+    out
+}
+
+// Eight-round add-rotate-xor scramble of a 64-byte cell
+fn scramble_cell(cell: &mut [u32; CELL_WORDS]) {
+    let original = *cell;
+    let x = cell;
+    for _ in 0..4 {
+        for &(a, b, c, d) in &[(0, 4, 8, 12), (5, 9, 13, 1), (10, 14, 2, 6), (15, 3, 7, 11)] {
+            x[b] ^= x[a].wrapping_add(x[d]).rotate_left(7);
+            x[c] ^= x[b].wrapping_add(x[a]).rotate_left(9);
+            x[d] ^= x[c].wrapping_add(x[b]).rotate_left(13);
+            x[a] ^= x[d].wrapping_add(x[c]).rotate_left(18);
+        }
+        for &(a, b, c, d) in &[(0, 1, 2, 3), (5, 6, 7, 4), (10, 11, 8, 9), (15, 12, 13, 14)] {
+            x[b] ^= x[a].wrapping_add(x[d]).rotate_left(7);
+            x[c] ^= x[b].wrapping_add(x[a]).rotate_left(9);
+            x[d] ^= x[c].wrapping_add(x[b]).rotate_left(13);
+            x[a] ^= x[d].wrapping_add(x[c]).rotate_left(18);
+        }
+    }
+    for i in 0..CELL_WORDS {
+        x[i] = x[i].wrapping_add(original[i]);
+    }
+}
+
+// Mix 2r cells in sequence; even outputs first, then odd
+fn mix_lane(lane: &mut [u32], scratch: &mut [u32], pairs: usize) {
+    let cells = 2 * pairs;
+    let mut carry = [0u32; CELL_WORDS];
+    carry.copy_from_slice(&lane[(cells - 1) * CELL_WORDS..]);
+
+    for i in 0..cells {
+        for j in 0..CELL_WORDS {
+            carry[j] ^= lane[i * CELL_WORDS + j];
+        }
+        scramble_cell(&mut carry);
+        let target = if i % 2 == 0 { i / 2 } else { pairs + i / 2 };
+        scratch[target * CELL_WORDS..(target + 1) * CELL_WORDS].copy_from_slice(&carry);
+    }
+    lane.copy_from_slice(scratch);
+}
+
+// Fill a table of N lane snapshots, then walk it in data-dependent order
+fn churn_lane(lane_bytes: &mut [u8], profile: &CostProfile) {
+    let pairs = profile.cell_pairs as usize;
+    let words = 32 * pairs;
+    let slots = profile.slots();
+
+    let mut lane: Vec<u32> = lane_bytes
+        .chunks(4)
+        .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect();
+    let mut scratch = vec![0u32; words];
+    let mut table = vec![0u32; slots * words];
+
+    for slot in 0..slots {
+        table[slot * words..(slot + 1) * words].copy_from_slice(&lane);
+        mix_lane(&mut lane, &mut scratch, pairs);
+    }
+
+    for _ in 0..slots {
+        let pick = lane[(2 * pairs - 1) * CELL_WORDS] as usize & (slots - 1);
+        for (w, t) in lane.iter_mut().zip(&table[pick * words..(pick + 1) * words]) {
+            *w ^= t;
+        }
+        mix_lane(&mut lane, &mut scratch, pairs);
+    }
+
+    for (chunk, word) in lane_bytes.chunks_mut(4).zip(lane) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+}
+
+pub fn derive_archive_key(passphrase: &[u8], salt: &[u8], profile: &CostProfile, key_len: usize) -> Vec<u8> {
+    let lane_bytes = profile.lane_bytes();
+    let mut lanes = spread_passphrase(passphrase, salt, 1, lane_bytes * profile.lanes as usize);
+    for lane in lanes.chunks_mut(lane_bytes) {
+        churn_lane(lane, profile);
+    }
+    spread_passphrase(passphrase, &lanes, 1, key_len)
+}
+
+#[derive(Debug)]
+pub struct BundleHeader {
+    pub magic: [u8; 4],
+    pub profile: CostProfile,
+    pub salt: [u8; 16],
+    pub key_check: [u8; 4],
+}
+
+impl BundleHeader {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = self.magic.to_vec();
+        out.push(self.profile.log2_slots);
+        out.extend_from_slice(&self.profile.cell_pairs.to_be_bytes());
+        out.extend_from_slice(&self.profile.lanes.to_be_bytes());
+        out.extend_from_slice(&self.salt);
+        out.extend_from_slice(&self.key_check);
+        out
+    }
+}
+
+pub struct SnapshotKeystore {
+    profile: CostProfile,
+}
+
+impl SnapshotKeystore {
+    pub fn new(profile: CostProfile) -> Self {
+        SnapshotKeystore { profile }
+    }
+
+    fn key_check(master: &[u8]) -> [u8; 4] {
+        let tag = keyed_digest(master, b"bundle-key-check");
+        [tag[0], tag[1], tag[2], tag[3]]
+    }
+
+    pub fn open_new_bundle(&self, passphrase: &str, salt: [u8; 16]) -> (BundleHeader, Vec<u8>) {
+        let master = derive_archive_key(passphrase.as_bytes(), &salt, &self.profile, 32);
+        let header = BundleHeader { magic: *b"SNP1", profile: self.profile, salt, key_check: Self::key_check(&master) };
+        (header, master)
+    }
+
+    pub fn unlock_bundle(&self, passphrase: &str, header: &BundleHeader) -> Option<Vec<u8>> {
+        let master = derive_archive_key(passphrase.as_bytes(), &header.salt, &header.profile, 32);
+        if Self::key_check(&master) == header.key_check {
+            Some(master)
+        } else {
+            None
+        }
+    }
+}
+
+fn main() {
+    println!("Archive Passphrase Keystore starting...");
+
+    let keystore = SnapshotKeystore::new(CostProfile::NIGHTLY);
+    let salt = [
+        0x9e, 0x21, 0x4f, 0xd0, 0x63, 0x18, 0xab, 0x75, 0x02, 0xc9, 0x3e, 0x86, 0x51, 0xfa, 0x0d, 0xb4,
+    ];
+    let (header, master) = keystore.open_new_bundle("correct horse battery staple", salt);
+    println!("Bundle header: {} bytes, master key {} bytes", header.to_bytes().len(), master.len());
+
+    match keystore.unlock_bundle("correct horse battery staple", &header) {
+        Some(_) => println!("Restore unlocked"),
+        None => println!("Restore refused"),
+    }
+    println!("Wrong passphrase unlocks: {}", keystore.unlock_bundle("tr0ub4dor&3", &header).is_some());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(text: &str) -> Vec<u8> {
+        (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn sha256(data: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha256State::new();
+        hasher.update(data);
+        hasher.digest().to_vec()
+    }
+
+    #[test]
+    fn digest_known_answer() {
+        // FIPS 180-4 section 6.2 example "abc"
+        assert_eq!(sha256(b"abc"), hex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"));
+    }
+
+    #[test]
+    fn keyed_digest_known_answer() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            keyed_digest(b"Jefe", b"what do ya want for nothing?").to_vec(),
+            hex("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+    }
+
+    #[test]
+    fn spread_known_answer() {
+        // RFC 7914 section 11
+        assert_eq!(
+            spread_passphrase(b"passwd", b"salt", 1, 64),
+            hex("55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc\
+                 49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783")
+        );
+    }
+
+    #[test]
+    fn cell_scramble_vector() {
+        // RFC 7914 section 8 core vector
+        let input = hex("7e879a214f3ec9867ca940e641718f26baee555b8c61c1b50df846116dcd3b1d\
+                         ee24f319df9b3d8514121e4b5ac5aa3276021d2909c74829edebc68db8b8c25e");
+        let mut cell = [0u32; CELL_WORDS];
+        for (word, chunk) in cell.iter_mut().zip(input.chunks(4)) {
+            *word = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        scramble_cell(&mut cell);
+        let output: Vec<u8> = cell.iter().flat_map(|w| w.to_le_bytes()).collect();
+        assert_eq!(
+            output,
+            hex("a41f859c6608cc993b81cacb020cef05044b2181a2fd337dfd7b1c6396682f29\
+                 b4393168e3c9e6bcfe6bc5b7a06d96bae424cc102c91745c24ad673dc7618f81")
+        );
+    }
+
+    #[test]
+    fn archive_key_known_answer() {
+        // RFC 7914 section 12 vectors 1 and 2
+        let tiny = CostProfile { log2_slots: 4, cell_pairs: 1, lanes: 1 };
+        assert_eq!(
+            derive_archive_key(b"", b"", &tiny, 64),
+            hex("77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442\
+                 fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906")
+        );
+
+        let small = CostProfile { log2_slots: 10, cell_pairs: 8, lanes: 16 };
+        assert_eq!(
+            derive_archive_key(b"password", b"NaCl", &small, 64),
+            hex("fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b373162\
+                 2eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640")
+        );
+    }
+
+    #[test]
+    fn bundle_unlock_round_trip() {
+        let keystore = SnapshotKeystore::new(CostProfile { log2_slots: 6, cell_pairs: 2, lanes: 1 });
+        let (header, master) = keystore.open_new_bundle("hunter2", [7u8; 16]);
+        assert_eq!(keystore.unlock_bundle("hunter2", &header), Some(master));
+        assert_eq!(keystore.unlock_bundle("hunter3", &header), None);
+    }
+}
@@ -0,0 +1,414 @@
+// Bootloader Manifest Sealer
+// Seals boot manifests for field controllers with a bounded pool of one-shot sealing slots
+
+const WIDTH: usize = 32;
+const DIGIT_BITS: usize = 4;
+const CHAIN_LENGTH: usize = (1 << DIGIT_BITS) - 1;
+const CHAIN_COUNT: usize = 67;
+const CHECKSUM_SHIFT: u32 = 4;
+const TREE_HEIGHT: usize = 5;
+const SLOT_COUNT: u32 = 1 << TREE_HEIGHT;
+
+const TREE_PROFILE: u32 = 0x0000_0005;
+const SLOT_PROFILE: u32 = 0x0000_0003;
+
+// Domain separators for the different hashing contexts
+const TAG_PUBLIC: u16 = 0x8080;
+const TAG_MESSAGE: u16 = 0x8181;
+const TAG_LEAF: u16 = 0x8282;
+const TAG_INTERIOR: u16 = 0x8383;
+const TAG_SECRET: u8 = 0xff;
+
+// ---------------------------------------------------------------------------
+// 32-byte compression hash
+// ---------------------------------------------------------------------------
+
+const MIX: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const START: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+struct Fold {
+    state: [u32; 8],
+    buffer: Vec<u8>,
+    length: u64,
+}
+
+impl Fold {
+    fn new() -> Self {
+        Fold { state: START, buffer: Vec::with_capacity(64), length: 0 }
+    }
+
+    fn block(&mut self, block: &[u8]) {
+        let mut w = [0u32; 64];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+        for i in 0..64 {
+            let t1 = h
+                .wrapping_add(e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25))
+                .wrapping_add((e & f) ^ (!e & g))
+                .wrapping_add(MIX[i])
+                .wrapping_add(w[i]);
+            let t2 = (a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22))
+                .wrapping_add((a & b) ^ (a & c) ^ (b & c));
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+
+        for (slot, value) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *slot = slot.wrapping_add(value);
+        }
+    }
+
+    fn feed(&mut self, data: &[u8]) -> &mut Self {
+        self.length += data.len() as u64;
+        self.buffer.extend_from_slice(data);
+        while self.buffer.len() >= 64 {
+            let block: Vec<u8> = self.buffer.drain(..64).collect();
+            self.block(&block);
+        }
+        self
+    }
+
+    fn finish(&mut self) -> [u8; WIDTH] {
+        let bits = self.length * 8;
+        self.buffer.push(0x80);
+        while self.buffer.len() % 64 != 56 {
+            self.buffer.push(0);
+        }
+        self.buffer.extend_from_slice(&bits.to_be_bytes());
+        let tail: Vec<u8> = std::mem::take(&mut self.buffer);
+        for block in tail.chunks(64) {
+            self.block(block);
+        }
+
+        let mut out = [0u8; WIDTH];
+        for (i, word) in self.state.iter().enumerate() {
+            out[4 * i..4 * i + 4].copy_from_slice(&word.to_be_bytes());
+        }
+        out
+    }
+}
+
+// ---------------------------------------------------------------------------
+// One-shot sealing slot
+// ---------------------------------------------------------------------------
+
+fn digit(digest: &[u8], index: usize) -> usize {
+    let byte = digest[index * DIGIT_BITS / 8];
+    let shift = 8 - DIGIT_BITS * (index % (8 / DIGIT_BITS) + 1);
+    ((byte >> shift) as usize) & CHAIN_LENGTH
+}
+
+fn expanded_digits(digest: &[u8; WIDTH]) -> [usize; CHAIN_COUNT] {
+    let mut total: u16 = 0;
+    for i in 0..WIDTH * 8 / DIGIT_BITS {
+        total += (CHAIN_LENGTH - digit(digest, i)) as u16;
+    }
+    let mut extended = digest.to_vec();
+    extended.extend_from_slice(&(total << CHECKSUM_SHIFT).to_be_bytes());
+
+    let mut digits = [0usize; CHAIN_COUNT];
+    for (i, slot) in digits.iter_mut().enumerate() {
+        *slot = digit(&extended, i);
+    }
+    digits
+}
+
+fn walk(label: &[u8; 16], slot: u32, chain: usize, from: usize, to: usize, start: [u8; WIDTH]) -> [u8; WIDTH] {
+    let mut value = start;
+    for step in from..to {
+        value = Fold::new()
+            .feed(label)
+            .feed(&slot.to_be_bytes())
+            .feed(&(chain as u16).to_be_bytes())
+            .feed(&[step as u8])
+            .feed(&value)
+            .finish();
+    }
+    value
+}
+
+fn slot_secret(label: &[u8; 16], slot: u32, chain: usize, seed: &[u8; WIDTH]) -> [u8; WIDTH] {
+    Fold::new()
+        .feed(label)
+        .feed(&slot.to_be_bytes())
+        .feed(&(chain as u16).to_be_bytes())
+        .feed(&[TAG_SECRET])
+        .feed(seed)
+        .finish()
+}
+
+fn slot_public(label: &[u8; 16], slot: u32, ends: &[[u8; WIDTH]]) -> [u8; WIDTH] {
+    let mut fold = Fold::new();
+    fold.feed(label).feed(&slot.to_be_bytes()).feed(&TAG_PUBLIC.to_be_bytes());
+    for end in ends {
+        fold.feed(end);
+    }
+    fold.finish()
+}
+
+fn manifest_digest(label: &[u8; 16], slot: u32, nonce: &[u8; WIDTH], manifest: &[u8]) -> [u8; WIDTH] {
+    Fold::new()
+        .feed(label)
+        .feed(&slot.to_be_bytes())
+        .feed(&TAG_MESSAGE.to_be_bytes())
+        .feed(nonce)
+        .feed(manifest)
+        .finish()
+}
+
+// ---------------------------------------------------------------------------
+// Slot tree
+// ---------------------------------------------------------------------------
+
+fn leaf_node(label: &[u8; 16], slot: u32, slot_key: &[u8; WIDTH]) -> [u8; WIDTH] {
+    Fold::new()
+        .feed(label)
+        .feed(&(SLOT_COUNT + slot).to_be_bytes())
+        .feed(&TAG_LEAF.to_be_bytes())
+        .feed(slot_key)
+        .finish()
+}
+
+fn interior_node(label: &[u8; 16], node: u32, left: &[u8; WIDTH], right: &[u8; WIDTH]) -> [u8; WIDTH] {
+    Fold::new()
+        .feed(label)
+        .feed(&node.to_be_bytes())
+        .feed(&TAG_INTERIOR.to_be_bytes())
+        .feed(left)
+        .feed(right)
+        .finish()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ManifestSeal {
+    pub slot: u32,
+    pub nonce: [u8; WIDTH],
+    pub chain_values: Vec<[u8; WIDTH]>,
+    pub path: Vec<[u8; WIDTH]>,
+}
+
+impl ManifestSeal {
+    // q || slot profile || nonce || chains || tree profile || path
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(8 + WIDTH * (1 + CHAIN_COUNT + TREE_HEIGHT) + 4);
+        out.extend_from_slice(&self.slot.to_be_bytes());
+        out.extend_from_slice(&SLOT_PROFILE.to_be_bytes());
+        out.extend_from_slice(&self.nonce);
+        for value in &self.chain_values {
+            out.extend_from_slice(value);
+        }
+        out.extend_from_slice(&TREE_PROFILE.to_be_bytes());
+        for node in &self.path {
+            out.extend_from_slice(node);
+        }
+        out
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SealAnchor {
+    pub label: [u8; 16],
+    pub root: [u8; WIDTH],
+}
+
+impl SealAnchor {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(24 + WIDTH);
+        out.extend_from_slice(&TREE_PROFILE.to_be_bytes());
+        out.extend_from_slice(&SLOT_PROFILE.to_be_bytes());
+        out.extend_from_slice(&self.label);
+        out.extend_from_slice(&self.root);
+        out
+    }
+
+    pub fn verify(&self, manifest: &[u8], seal: &ManifestSeal) -> bool {
+        if seal.slot >= SLOT_COUNT || seal.chain_values.len() != CHAIN_COUNT || seal.path.len() != TREE_HEIGHT {
+            return false;
+        }
+
+        let digest = manifest_digest(&self.label, seal.slot, &seal.nonce, manifest);
+        let digits = expanded_digits(&digest);
+        let ends: Vec<[u8; WIDTH]> = (0..CHAIN_COUNT)
+            .map(|i| walk(&self.label, seal.slot, i, digits[i], CHAIN_LENGTH, seal.chain_values[i]))
+            .collect();
+        let candidate = slot_public(&self.label, seal.slot, &ends);
+
+        let mut node = SLOT_COUNT + seal.slot;
+        let mut value = leaf_node(&self.label, seal.slot, &candidate);
+        for sibling in &seal.path {
+            value = if node % 2 == 1 {
+                interior_node(&self.label, node / 2, sibling, &value)
+            } else {
+                interior_node(&self.label, node / 2, &value, sibling)
+            };
+            node /= 2;
+        }
+        value == self.root
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SealError {
+    SlotsExhausted,
+}
+
+pub struct ManifestSealer {
+    label: [u8; 16],
+    seed: [u8; WIDTH],
+    tree: Vec<[u8; WIDTH]>,
+    next_slot: u32,
+}
+
+impl ManifestSealer {
+    pub fn new(label: [u8; 16], seed: [u8; WIDTH]) -> Self {
+        let mut tree = vec![[0u8; WIDTH]; 2 * SLOT_COUNT as usize];
+        for slot in 0..SLOT_COUNT {
+            let ends: Vec<[u8; WIDTH]> = (0..CHAIN_COUNT)
+                .map(|i| walk(&label, slot, i, 0, CHAIN_LENGTH, slot_secret(&label, slot, i, &seed)))
+                .collect();
+            tree[(SLOT_COUNT + slot) as usize] = leaf_node(&label, slot, &slot_public(&label, slot, &ends));
+        }
+        for node in (1..SLOT_COUNT).rev() {
+            let (left, right) = (tree[2 * node as usize], tree[2 * node as usize + 1]);
+            tree[node as usize] = interior_node(&label, node, &left, &right);
+        }
+        ManifestSealer { label, seed, tree, next_slot: 0 }
+    }
+
+    pub fn anchor(&self) -> SealAnchor {
+        SealAnchor { label: self.label, root: self.tree[1] }
+    }
+
+    pub fn remaining(&self) -> u32 {
+        SLOT_COUNT - self.next_slot
+    }
+
+    // Each slot may seal exactly one manifest; the counter must be persisted before the seal leaves the device
+    pub fn seal(&mut self, manifest: &[u8]) -> Result<ManifestSeal, SealError> {
+        if self.next_slot >= SLOT_COUNT {
+            return Err(SealError::SlotsExhausted);
+        }
+        let slot = self.next_slot;
+        self.next_slot += 1;
+
+        let nonce = Fold::new().feed(&self.seed).feed(&slot.to_be_bytes()).feed(manifest).finish();
+        let digest = manifest_digest(&self.label, slot, &nonce, manifest);
+        let digits = expanded_digits(&digest);
+        let chain_values = (0..CHAIN_COUNT)
+            .map(|i| walk(&self.label, slot, i, 0, digits[i], slot_secret(&self.label, slot, i, &self.seed)))
+            .collect();
+
+        let mut path = Vec::with_capacity(TREE_HEIGHT);
+        let mut node = SLOT_COUNT + slot;
+        while node > 1 {
+            path.push(self.tree[(node ^ 1) as usize]);
+            node /= 2;
+        }
+        Ok(ManifestSeal { slot, nonce, chain_values, path })
+    }
+}
+
+fn main() {
+    println!("Bootloader Manifest Sealer starting...");
+
+    let mut sealer = ManifestSealer::new(*b"ctrl-fleet-east1", [0x5a; WIDTH]);
+    let anchor = sealer.anchor();
+    println!("Anchor ({} bytes) provisioned into boot ROM", anchor.encode().len());
+
+    let manifest = b"image=controller-3.8.2.bin size=1048576 rollback_index=17";
+    let seal = sealer.seal(manifest).unwrap();
+    println!("Manifest sealed with slot {} ({} bytes)", seal.slot, seal.encode().len());
+    println!("Boot ROM accepts: {}", anchor.verify(manifest, &seal));
+    println!("Sealing slots remaining: {}", sealer.remaining());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn fold_known_answer() {
+        // FIPS 180-4 section 6.2 example "abc"
+        assert_eq!(
+            hex(&Fold::new().feed(b"abc").finish()),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn checksum_digits_cover_digest() {
+        let digits = expanded_digits(&[0u8; WIDTH]);
+        assert!(digits[..64].iter().all(|&d| d == 0));
+        // 64 digits of 15 remaining -> 960 << 4 = 0x3c00
+        assert_eq!(&digits[64..], &[3, 12, 0]);
+    }
+
+    #[test]
+    fn seal_and_verify() {
+        let mut sealer = ManifestSealer::new([1; 16], [2; WIDTH]);
+        let anchor = sealer.anchor();
+        let first = sealer.seal(b"manifest-a").unwrap();
+        let second = sealer.seal(b"manifest-b").unwrap();
+
+        assert_eq!((first.slot, second.slot), (0, 1));
+        assert!(anchor.verify(b"manifest-a", &first));
+        assert!(anchor.verify(b"manifest-b", &second));
+        assert!(!anchor.verify(b"manifest-b", &first));
+        assert_eq!(first.encode().len(), 4 + 4 + WIDTH + CHAIN_COUNT * WIDTH + 4 + TREE_HEIGHT * WIDTH);
+    }
+
+    #[test]
+    fn tampered_seal_rejected() {
+        let mut sealer = ManifestSealer::new([3; 16], [4; WIDTH]);
+        let anchor = sealer.anchor();
+        let seal = sealer.seal(b"manifest").unwrap();
+
+        let mut wrong_path = seal.clone();
+        wrong_path.path[2][0] ^= 1;
+        assert!(!anchor.verify(b"manifest", &wrong_path));
+
+        let mut wrong_slot = seal.clone();
+        wrong_slot.slot = 1;
+        assert!(!anchor.verify(b"manifest", &wrong_slot));
+    }
+
+    #[test]
+    fn slots_run_out() {
+        let mut sealer = ManifestSealer::new([5; 16], [6; WIDTH]);
+        for _ in 0..SLOT_COUNT {
+            sealer.seal(b"m").unwrap();
+        }
+        assert_eq!(sealer.remaining(), 0);
+        assert_eq!(sealer.seal(b"m"), Err(SealError::SlotsExhausted));
+    }
+}
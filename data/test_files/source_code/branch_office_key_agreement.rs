@@ -0,0 +1,496 @@
+// Branch Office Key Agreement
+// Establishes nightly ledger-sync session keys between branch routers and headquarters
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+const CARRIER_BYTES: usize = 256;
+const EXPONENT_BYTES: usize = 32;
+const SESSION_KEY_BYTES: usize = 32;
+
+// Published 2048-bit carrier modulus baked into every branch router image
+const CARRIER_MODULUS: [&str; 8] = [
+    "FFFFFFFF FFFFFFFF C90FDAA2 2168C234 C4C6628B 80DC1CD1 29024E08 8A67CC74",
+    "020BBEA6 3B139B22 514A0879 8E3404DD EF9519B3 CD3A431B 302B0A6D F25F1437",
+    "4FE1356D 6D51C245 E485B576 625E7EC6 F44C42E9 A637ED6B 0BFF5CB6 F406B7ED",
+    "EE386BFB 5A899FA5 AE9F2411 7C4B1FE6 49286651 ECE45B3D C2007CB8 A163BF05",
+    "98DA4836 1C55D39A 69163FA8 FD24CF5F 83655D23 DCA3AD96 1C62F356 208552BB",
+    "9ED52907 7096966D 670C354E 4ABC9804 F1746C08 CA18217C 32905E46 2E36CE3B",
+    "E39E772C 180E8603 9B2783A2 EC07A28F B5C55DF0 6F4C52C9 DE2BCBF6 95581718",
+    "3995497C EA956AE5 15D22618 98FA0510 15728E5A 8AACAA68 FFFFFFFF FFFFFFFF",
+];
+const CARRIER_GENERATOR: u32 = 2;
+
+// Little-endian 32-bit limbs, always trimmed of leading zero limbs
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Magnitude {
+    limbs: Vec<u32>,
+}
+
+impl Magnitude {
+    fn from_u32(value: u32) -> Self {
+        Magnitude { limbs: vec![value] }.trimmed()
+    }
+
+    fn from_limbs(limbs: Vec<u32>) -> Self {
+        Magnitude { limbs }.trimmed()
+    }
+
+    fn from_hex(text: &str) -> Self {
+        let digits: Vec<u32> = text.chars().filter_map(|c| c.to_digit(16)).collect();
+        let mut limbs = Vec::with_capacity(digits.len() / 8 + 1);
+        for chunk in digits.rchunks(8) {
+            limbs.push(chunk.iter().fold(0u32, |acc, d| (acc << 4) | d));
+        }
+        Magnitude { limbs }.trimmed()
+    }
+
+    fn from_be_bytes(bytes: &[u8]) -> Self {
+        let mut limbs = Vec::with_capacity(bytes.len() / 4 + 1);
+        for chunk in bytes.rchunks(4) {
+            limbs.push(chunk.iter().fold(0u32, |acc, b| (acc << 8) | *b as u32));
+        }
+        Magnitude { limbs }.trimmed()
+    }
+
+    fn to_be_bytes(&self, width: usize) -> Vec<u8> {
+        let mut out = vec![0u8; width];
+        for (i, limb) in self.limbs.iter().enumerate() {
+            for (j, byte) in limb.to_le_bytes().iter().enumerate() {
+                let position = 4 * i + j;
+                if position < width {
+                    out[width - 1 - position] = *byte;
+                }
+            }
+        }
+        out
+    }
+
+    fn trimmed(mut self) -> Self {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self
+    }
+
+    fn bits(&self) -> usize {
+        match self.limbs.last() {
+            Some(top) => 32 * self.limbs.len() - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    fn bit(&self, index: usize) -> bool {
+        self.limbs.get(index / 32).map_or(false, |limb| (limb >> (index % 32)) & 1 == 1)
+    }
+
+    fn compare(&self, other: &Magnitude) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+
+    fn add(&self, other: &Magnitude) -> Magnitude {
+        let mut limbs = Vec::with_capacity(self.limbs.len().max(other.limbs.len()) + 1);
+        let mut carry = 0u64;
+        for i in 0..self.limbs.len().max(other.limbs.len()) {
+            let sum = *self.limbs.get(i).unwrap_or(&0) as u64 + *other.limbs.get(i).unwrap_or(&0) as u64 + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+        limbs.push(carry as u32);
+        Magnitude::from_limbs(limbs)
+    }
+
+    // Caller guarantees self >= other
+    fn sub(&self, other: &Magnitude) -> Magnitude {
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = 0i64;
+        for i in 0..self.limbs.len() {
+            let mut diff = self.limbs[i] as i64 - *other.limbs.get(i).unwrap_or(&0) as i64 - borrow;
+            borrow = 0;
+            if diff < 0 {
+                diff += 1 << 32;
+                borrow = 1;
+            }
+            limbs.push(diff as u32);
+        }
+        Magnitude::from_limbs(limbs)
+    }
+
+    fn shifted_right(&self, count: usize) -> Magnitude {
+        let (whole, part) = (count / 32, count % 32);
+        let mut limbs: Vec<u32> = self.limbs.iter().skip(whole).copied().collect();
+        if part > 0 {
+            for i in 0..limbs.len() {
+                let next = limbs.get(i + 1).copied().unwrap_or(0);
+                limbs[i] = (limbs[i] >> part) | (next << (32 - part));
+            }
+        }
+        Magnitude::from_limbs(limbs)
+    }
+
+    // Bit-serial reduction; only used for one-off reductions outside the exponentiation loop
+    fn rem(&self, modulus: &Magnitude) -> Magnitude {
+        let mut remainder = Magnitude { limbs: Vec::new() };
+        for index in (0..self.bits()).rev() {
+            remainder = remainder.add(&remainder);
+            if self.bit(index) {
+                remainder = remainder.add(&Magnitude::from_u32(1));
+            }
+            if remainder.compare(modulus) != Ordering::Less {
+                remainder = remainder.sub(modulus);
+            }
+        }
+        remainder
+    }
+}
+
+// Residues kept in Montgomery form with R = 2^(32 * width)
+struct ResidueRing {
+    modulus: Magnitude,
+    width: usize,
+    n0_inv: u32,
+    r_squared: Vec<u32>,
+}
+
+impl ResidueRing {
+    fn new(modulus: &Magnitude) -> Self {
+        let width = modulus.limbs.len();
+        let mut inverse = 1u32;
+        for _ in 0..5 {
+            inverse = inverse.wrapping_mul(2u32.wrapping_sub(modulus.limbs[0].wrapping_mul(inverse)));
+        }
+
+        let mut r_squared_limbs = vec![0u32; 2 * width];
+        r_squared_limbs.push(1);
+        let r_squared = Magnitude::from_limbs(r_squared_limbs).rem(modulus);
+
+        let mut ring = ResidueRing { modulus: modulus.clone(), width, n0_inv: inverse.wrapping_neg(), r_squared: Vec::new() };
+        ring.r_squared = ring.padded(&r_squared);
+        ring
+    }
+
+    fn padded(&self, value: &Magnitude) -> Vec<u32> {
+        let mut limbs = value.limbs.clone();
+        limbs.resize(self.width, 0);
+        limbs
+    }
+
+    fn redc_mul(&self, a: &[u32], b: &[u32]) -> Vec<u32> {
+        let n = &self.modulus.limbs;
+        let w = self.width;
+        let mut t = vec![0u32; w + 2];
+
+        for i in 0..w {
+            let mut carry = 0u64;
+            for j in 0..w {
+                let sum = t[j] as u64 + a[j] as u64 * b[i] as u64 + carry;
+                t[j] = sum as u32;
+                carry = sum >> 32;
+            }
+            let sum = t[w] as u64 + carry;
+            t[w] = sum as u32;
+            t[w + 1] = (sum >> 32) as u32;
+
+            let m = t[0].wrapping_mul(self.n0_inv);
+            let mut carry = (t[0] as u64 + m as u64 * n[0] as u64) >> 32;
+            for j in 1..w {
+                let sum = t[j] as u64 + m as u64 * n[j] as u64 + carry;
+                t[j - 1] = sum as u32;
+                carry = sum >> 32;
+            }
+            let sum = t[w] as u64 + carry;
+            t[w - 1] = sum as u32;
+            let sum = t[w + 1] as u64 + (sum >> 32);
+            t[w] = sum as u32;
+            t[w + 1] = 0;
+        }
+
+        let result = Magnitude::from_limbs(t[..w + 1].to_vec());
+        let reduced = if result.compare(&self.modulus) != Ordering::Less { result.sub(&self.modulus) } else { result };
+        self.padded(&reduced)
+    }
+
+    fn reduce(&self, value: &Magnitude) -> Magnitude {
+        if value.compare(&self.modulus) == Ordering::Less {
+            value.clone()
+        } else {
+            value.rem(&self.modulus)
+        }
+    }
+
+    fn pow(&self, base: &Magnitude, exponent: &Magnitude) -> Magnitude {
+        let mut one = vec![0u32; self.width];
+        one[0] = 1;
+        let base_form = self.redc_mul(&self.padded(&self.reduce(base)), &self.r_squared);
+        let mut acc = self.redc_mul(&one, &self.r_squared);
+        for index in (0..exponent.bits()).rev() {
+            acc = self.redc_mul(&acc, &acc);
+            if exponent.bit(index) {
+                acc = self.redc_mul(&acc, &base_form);
+            }
+        }
+        Magnitude::from_limbs(self.redc_mul(&acc, &one))
+    }
+}
+
+const TALLY_ROUND_WORDS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+// Ledger tally digest used to condense the agreed value into a session key
+fn ledger_tally(message: &[u8]) -> [u8; 32] {
+    let mut state: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+    ];
+    let mut padded = message.to_vec();
+    padded.push(0x80);
+    while padded.len() % 64 != 56 {
+        padded.push(0);
+    }
+    padded.extend_from_slice(&((message.len() as u64) * 8).to_be_bytes());
+
+    for block in padded.chunks(64) {
+        let mut w = [0u32; 64];
+        for i in 0..16 {
+            w[i] = u32::from_be_bytes([block[4 * i], block[4 * i + 1], block[4 * i + 2], block[4 * i + 3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let mut v = state;
+        for i in 0..64 {
+            let t1 = v[7]
+                .wrapping_add(v[4].rotate_right(6) ^ v[4].rotate_right(11) ^ v[4].rotate_right(25))
+                .wrapping_add((v[4] & v[5]) ^ (!v[4] & v[6]))
+                .wrapping_add(TALLY_ROUND_WORDS[i])
+                .wrapping_add(w[i]);
+            let t2 = (v[0].rotate_right(2) ^ v[0].rotate_right(13) ^ v[0].rotate_right(22))
+                .wrapping_add((v[0] & v[1]) ^ (v[0] & v[2]) ^ (v[1] & v[2]));
+            v = [t1.wrapping_add(t2), v[0], v[1], v[2], v[3].wrapping_add(t1), v[4], v[5], v[6]];
+        }
+        for i in 0..8 {
+            state[i] = state[i].wrapping_add(v[i]);
+        }
+    }
+
+    let mut out = [0u8; 32];
+    for (i, word) in state.iter().enumerate() {
+        out[4 * i..4 * i + 4].copy_from_slice(&word.to_be_bytes());
+    }
+    out
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AgreementError {
+    OutOfRange,
+    WrongSubgroup,
+    UnknownBranch,
+}
+
+// Shared carrier parameters plus the prime-order subgroup size (p - 1) / 2
+pub struct CarrierGroup {
+    modulus: Magnitude,
+    subgroup_order: Magnitude,
+    generator: Magnitude,
+    ring: ResidueRing,
+}
+
+impl CarrierGroup {
+    pub fn standard() -> Self {
+        let modulus = Magnitude::from_hex(&CARRIER_MODULUS.concat());
+        let subgroup_order = modulus.shifted_right(1);
+        let ring = ResidueRing::new(&modulus);
+        CarrierGroup { modulus, subgroup_order, generator: Magnitude::from_u32(CARRIER_GENERATOR), ring }
+    }
+
+    fn public_value(&self, exponent: &Magnitude) -> Magnitude {
+        self.ring.pow(&self.generator, exponent)
+    }
+
+    // Reject 0, 1, p - 1 and anything outside the order-q subgroup (small-subgroup confinement)
+    fn check_peer(&self, peer: &Magnitude) -> Result<(), AgreementError> {
+        let one = Magnitude::from_u32(1);
+        let upper = self.modulus.sub(&one);
+        if peer.compare(&one) != Ordering::Greater || peer.compare(&upper) != Ordering::Less {
+            return Err(AgreementError::OutOfRange);
+        }
+        if self.ring.pow(peer, &self.subgroup_order) != one {
+            return Err(AgreementError::WrongSubgroup);
+        }
+        Ok(())
+    }
+
+    fn agree(&self, own_exponent: &Magnitude, peer: &Magnitude) -> Result<Vec<u8>, AgreementError> {
+        self.check_peer(peer)?;
+        Ok(self.ring.pow(peer, own_exponent).to_be_bytes(CARRIER_BYTES))
+    }
+}
+
+pub struct BranchRouter {
+    branch_code: String,
+    exponent: Magnitude,
+    public: Magnitude,
+}
+
+impl BranchRouter {
+    // Exponent stretched from the router's provisioning secret and boot counter
+    pub fn boot(group: &CarrierGroup, branch_code: &str, provisioning_secret: &[u8], boot_counter: u32) -> Self {
+        let mut seed = provisioning_secret.to_vec();
+        seed.extend_from_slice(branch_code.as_bytes());
+        seed.extend_from_slice(&boot_counter.to_be_bytes());
+        let exponent = Magnitude::from_be_bytes(&ledger_tally(&seed)[..EXPONENT_BYTES]);
+        let public = group.public_value(&exponent);
+        BranchRouter { branch_code: branch_code.to_string(), exponent, public }
+    }
+
+    pub fn hello(&self) -> (String, Vec<u8>) {
+        (self.branch_code.clone(), self.public.to_be_bytes(CARRIER_BYTES))
+    }
+}
+
+fn session_key(shared: &[u8], branch_public: &[u8], hq_public: &[u8]) -> [u8; SESSION_KEY_BYTES] {
+    let mut transcript = b"ledger-sync/v2".to_vec();
+    transcript.extend_from_slice(shared);
+    transcript.extend_from_slice(branch_public);
+    transcript.extend_from_slice(hq_public);
+    ledger_tally(&transcript)
+}
+
+pub struct Headquarters {
+    group: CarrierGroup,
+    router: BranchRouter,
+    sessions: HashMap<String, [u8; SESSION_KEY_BYTES]>,
+}
+
+impl Headquarters {
+    pub fn new(provisioning_secret: &[u8]) -> Self {
+        let group = CarrierGroup::standard();
+        let router = BranchRouter::boot(&group, "HQ", provisioning_secret, 1);
+        Headquarters { group, router, sessions: HashMap::new() }
+    }
+
+    pub fn accept(&mut self, branch_code: &str, branch_public: &[u8]) -> Result<Vec<u8>, AgreementError> {
+        let peer = Magnitude::from_be_bytes(branch_public);
+        let shared = self.group.agree(&self.router.exponent, &peer)?;
+        let (_, hq_public) = self.router.hello();
+        self.sessions.insert(branch_code.to_string(), session_key(&shared, branch_public, &hq_public));
+        Ok(hq_public)
+    }
+
+    pub fn session_for(&self, branch_code: &str) -> Result<&[u8; SESSION_KEY_BYTES], AgreementError> {
+        self.sessions.get(branch_code).ok_or(AgreementError::UnknownBranch)
+    }
+}
+
+fn main() {
+    println!("Branch Office Key Agreement starting...");
+
+    let mut hq = Headquarters::new(b"hq-provisioning-2024");
+    let group = CarrierGroup::standard();
+    let branch = BranchRouter::boot(&group, "BR-0417", b"br0417-factory-secret", 88);
+
+    let (code, branch_public) = branch.hello();
+    let hq_public = hq.accept(&code, &branch_public).expect("branch hello rejected");
+
+    let shared = group.agree(&branch.exponent, &Magnitude::from_be_bytes(&hq_public)).unwrap();
+    let branch_key = session_key(&shared, &branch_public, &hq_public);
+
+    println!("Session established for {}: keys match = {}", code, hq.session_for(&code).unwrap() == &branch_key);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXCHANGE_A: &str = "75b6191a9bf50fb3deb872ce20b90c14c5f4de7e08fd5a1f66f927985fa2588a";
+    const EXCHANGE_B: &str = "7480c2f0a7594e53dc2fa6cb238b6523cefad43c888718f611a6125719a59ffd";
+    const EXCHANGE_PUBLIC_A: &str = "\
+        25ec571fc04e436f6e8451e492a382987c3134dbb6ef56acb74ca256aee0be1a0667233b5d5c5940f5da1237908f0ba2\
+        7b8515702eac9bc1288421ca5be7ead9ee8eb2c9ca0a171b3664040a15f8b925f122383a017d1d688a66d102c47df97e\
+        8ca5a56e66270ffd5d5665a1c4f7c54123adfc510a2eae65a78def4ced3c86ea73af0b979749014dd3be1bd1dcbdee29\
+        e1ac2e355061c8d265317b9b715cac58c03a2600d69c8aad60b55eeb7f627fa8c8cc410cfff3b6eaf22fcf3191e42d24\
+        cfa5e458cde633968ee5ea9fcf70b4e387f2979930d3c0577b4d52c4cb450594bbf795a00555b198c88d661df7fba5bb\
+        f31d50a7f72b1f95eab5033ff8bda8dd";
+    const EXCHANGE_SHARED: &str = "\
+        f5cd2f081c1040356a8b12f42a70bfec52cb5f2613d85e359b8f283e68d2c0842f7ea313a119feb176b9ac4d98e7779c\
+        1f0fd5e70a60c7b6302e4124bbbb535f26f9e61296e137620cc6f7a247a81e3dcfa463d84012e2da522794abad426acc\
+        863f5cd7f3f1245d9b67ba1ef60c30e53cf36da40a15ca872ab6820638f34fde7c97279fe5ee2c2192a7325c57abbf65\
+        702e48aef6a9213ad5140ea778be91ea12276a8a839039fdd785a7b610bbeade26207263d6290a142e48ff7b18a4cb45\
+        04ac52449a01e922b0d09b6fed4d954c49fb72696feb00fd90c1514afcb2184e2651c2d530460df0690011603d5974d0\
+        d02351394a5796635420915c437deb16";
+
+    fn hex(text: &str) -> Vec<u8> {
+        let clean: String = text.chars().filter(|c| c.is_ascii_hexdigit()).collect();
+        (0..clean.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&clean[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn tally_known_answer() {
+        // FIPS 180-4 section 6.2 examples
+        assert_eq!(
+            ledger_tally(b"abc").to_vec(),
+            hex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+        assert_eq!(
+            ledger_tally(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq").to_vec(),
+            hex("248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1")
+        );
+    }
+
+    #[test]
+    fn carrier_exchange_known_answer() {
+        // RFC 3526 group 14 exchange, values cross-checked with Python pow()
+        let group = CarrierGroup::standard();
+        assert_eq!(group.modulus.bits(), 2048);
+
+        let a = Magnitude::from_hex(EXCHANGE_A);
+        let b = Magnitude::from_hex(EXCHANGE_B);
+        let public_a = group.public_value(&a);
+        let public_b = group.public_value(&b);
+        assert_eq!(public_a, Magnitude::from_hex(EXCHANGE_PUBLIC_A));
+
+        let shared = group.agree(&a, &public_b).unwrap();
+        assert_eq!(shared, group.agree(&b, &public_a).unwrap());
+        assert_eq!(Magnitude::from_be_bytes(&shared), Magnitude::from_hex(EXCHANGE_SHARED));
+    }
+
+    #[test]
+    fn rejects_confined_peers() {
+        let group = CarrierGroup::standard();
+        let a = Magnitude::from_u32(12345);
+        let p_minus_one = group.modulus.sub(&Magnitude::from_u32(1));
+
+        assert_eq!(group.agree(&a, &Magnitude::from_u32(1)), Err(AgreementError::OutOfRange));
+        assert_eq!(group.agree(&a, &p_minus_one), Err(AgreementError::OutOfRange));
+        // 11 is a quadratic non-residue here, so it lies outside the order-q subgroup
+        assert_eq!(group.agree(&a, &Magnitude::from_u32(11)), Err(AgreementError::WrongSubgroup));
+    }
+
+    #[test]
+    fn branch_and_headquarters_agree() {
+        let mut hq = Headquarters::new(b"hq");
+        let group = CarrierGroup::standard();
+        let branch = BranchRouter::boot(&group, "BR-1", b"secret", 1);
+        let (code, branch_public) = branch.hello();
+        let hq_public = hq.accept(&code, &branch_public).unwrap();
+        let shared = group.agree(&branch.exponent, &Magnitude::from_be_bytes(&hq_public)).unwrap();
+        assert_eq!(hq.session_for("BR-1").unwrap(), &session_key(&shared, &branch_public, &hq_public));
+        assert_eq!(hq.session_for("BR-2"), Err(AgreementError::UnknownBranch));
+    }
+}
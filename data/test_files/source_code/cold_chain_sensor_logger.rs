@@ -0,0 +1,486 @@
+// Cold Chain Sensor Logger
+// Tamper-evident temperature excursion records for pharmaceutical shipments
+
+use std::collections::BTreeMap;
+
+const READING_FRAME: usize = 16;
+const SEAL_WIDTH: usize = 16;
+const NONCE_WIDTH: usize = 12;
+const SCRAMBLE_PASSES: usize = 10;
+
+// Allowed storage band in tenths of a degree (2.0 C to 8.0 C)
+const BAND_LOW: i16 = 20;
+const BAND_HIGH: i16 = 80;
+
+// Reduction residues for four-bit steps of the integrity accumulator
+const NIBBLE_RESIDUES: [u16; 16] = [
+    0x0000, 0x1c20, 0x3840, 0x2460, 0x7080, 0x6ca0, 0x48c0, 0x54e0,
+    0xe100, 0xfd20, 0xd940, 0xc560, 0x9180, 0x8da0, 0xa9c0, 0xb5e0,
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SensorReading {
+    pub minute: u32,
+    pub tenths_celsius: i16,
+    pub humidity: u8,
+}
+
+#[derive(Clone, Debug)]
+pub struct SealedBatch {
+    pub shipment_id: String,
+    pub sequence: u64,
+    pub nonce: [u8; NONCE_WIDTH],
+    pub header: Vec<u8>,
+    pub body: Vec<u8>,
+    pub seal: [u8; SEAL_WIDTH],
+}
+
+// Byte lookup rebuilt at boot from field inversion so the firmware image stays small
+#[derive(Clone)]
+struct ByteRemapper {
+    forward: [u8; 256],
+}
+
+impl ByteRemapper {
+    fn build() -> Self {
+        let mut forward = [0u8; 256];
+        for (value, slot) in forward.iter_mut().enumerate() {
+            let inverse = if value == 0 { 0 } else { Self::field_inverse(value as u8) };
+            let mut mixed = inverse;
+            let mut rotated = inverse;
+            for _ in 0..4 {
+                rotated = rotated.rotate_left(1);
+                mixed ^= rotated;
+            }
+            *slot = mixed ^ 0x63;
+        }
+        ByteRemapper { forward }
+    }
+
+    fn field_multiply(mut a: u8, mut b: u8) -> u8 {
+        let mut product = 0u8;
+        while b != 0 {
+            if b & 1 != 0 {
+                product ^= a;
+            }
+            let carry = a & 0x80;
+            a <<= 1;
+            if carry != 0 {
+                a ^= 0x1b;
+            }
+            b >>= 1;
+        }
+        product
+    }
+
+    fn field_inverse(value: u8) -> u8 {
+        // value^254 is the multiplicative inverse in the 256-element field
+        let mut result = 1u8;
+        let mut base = value;
+        let mut exponent = 254u8;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = Self::field_multiply(result, base);
+            }
+            base = Self::field_multiply(base, base);
+            exponent >>= 1;
+        }
+        result
+    }
+
+    fn apply(&self, value: u8) -> u8 {
+        self.forward[value as usize]
+    }
+}
+
+// Fixed-width block transform keyed per shipment
+#[derive(Clone)]
+struct BlockScrambler {
+    remap: ByteRemapper,
+    pass_keys: Vec<[u8; READING_FRAME]>,
+}
+
+impl BlockScrambler {
+    fn new(key: &[u8; 16]) -> Self {
+        let remap = ByteRemapper::build();
+        let pass_keys = Self::expand(&remap, key);
+        BlockScrambler { remap, pass_keys }
+    }
+
+    fn expand(remap: &ByteRemapper, key: &[u8; 16]) -> Vec<[u8; READING_FRAME]> {
+        let mut words: Vec<[u8; 4]> = key.chunks(4).map(|c| [c[0], c[1], c[2], c[3]]).collect();
+        let mut step = 1u8;
+
+        while words.len() < 4 * (SCRAMBLE_PASSES + 1) {
+            let mut word = words[words.len() - 1];
+            if words.len() % 4 == 0 {
+                word = [
+                    remap.apply(word[1]) ^ step,
+                    remap.apply(word[2]),
+                    remap.apply(word[3]),
+                    remap.apply(word[0]),
+                ];
+                step = ByteRemapper::field_multiply(step, 2);
+            }
+            let previous = words[words.len() - 4];
+            words.push([
+                previous[0] ^ word[0],
+                previous[1] ^ word[1],
+                previous[2] ^ word[2],
+                previous[3] ^ word[3],
+            ]);
+        }
+
+        words
+            .chunks(4)
+            .map(|group| {
+                let mut pass_key = [0u8; READING_FRAME];
+                for (i, word) in group.iter().enumerate() {
+                    pass_key[i * 4..i * 4 + 4].copy_from_slice(word);
+                }
+                pass_key
+            })
+            .collect()
+    }
+
+    fn scramble(&self, block: &[u8; READING_FRAME]) -> [u8; READING_FRAME] {
+        let mut state = *block;
+        Self::overlay(&mut state, &self.pass_keys[0]);
+
+        for pass in 1..=SCRAMBLE_PASSES {
+            for byte in state.iter_mut() {
+                *byte = self.remap.apply(*byte);
+            }
+            Self::stagger(&mut state);
+            if pass != SCRAMBLE_PASSES {
+                Self::blend_columns(&mut state);
+            }
+            Self::overlay(&mut state, &self.pass_keys[pass]);
+        }
+        state
+    }
+
+    fn overlay(state: &mut [u8; READING_FRAME], pass_key: &[u8; READING_FRAME]) {
+        for (byte, key_byte) in state.iter_mut().zip(pass_key) {
+            *byte ^= key_byte;
+        }
+    }
+
+    // Row r of the column-major grid moves r places to the left
+    fn stagger(state: &mut [u8; READING_FRAME]) {
+        let snapshot = *state;
+        for row in 1..4 {
+            for column in 0..4 {
+                state[column * 4 + row] = snapshot[((column + row) % 4) * 4 + row];
+            }
+        }
+    }
+
+    fn blend_columns(state: &mut [u8; READING_FRAME]) {
+        for column in state.chunks_mut(4) {
+            let a = [column[0], column[1], column[2], column[3]];
+            for row in 0..4 {
+                column[row] = ByteRemapper::field_multiply(a[row], 2)
+                    ^ ByteRemapper::field_multiply(a[(row + 1) % 4], 3)
+                    ^ a[(row + 2) % 4]
+                    ^ a[(row + 3) % 4];
+            }
+        }
+    }
+}
+
+// Polynomial accumulator over 128-bit blocks, processed four bits at a time
+#[derive(Clone)]
+struct IntegrityAccumulator {
+    multiples: [u128; 16],
+    state: u128,
+}
+
+impl IntegrityAccumulator {
+    fn new(hash_key: u128) -> Self {
+        let mut multiples = [0u128; 16];
+        multiples[8] = hash_key;
+
+        let mut index = 4;
+        while index > 0 {
+            let doubled = multiples[index * 2];
+            multiples[index] = if doubled & 1 == 1 {
+                (doubled >> 1) ^ (0xe1u128 << 120)
+            } else {
+                doubled >> 1
+            };
+            index >>= 1;
+        }
+        for high in [2usize, 4, 8] {
+            for low in 1..high {
+                multiples[high + low] = multiples[high] ^ multiples[low];
+            }
+        }
+
+        IntegrityAccumulator { multiples, state: 0 }
+    }
+
+    fn multiply(&self, value: u128) -> u128 {
+        let bytes = value.to_be_bytes();
+        let mut product = 0u128;
+        for byte in bytes.iter().rev() {
+            for nibble in [byte & 0x0f, byte >> 4] {
+                let residue = (product & 0x0f) as usize;
+                product = (product >> 4) ^ ((NIBBLE_RESIDUES[residue] as u128) << 112);
+                product ^= self.multiples[nibble as usize];
+            }
+        }
+        product
+    }
+
+    fn absorb(&mut self, data: &[u8]) {
+        for chunk in data.chunks(16) {
+            let mut block = [0u8; 16];
+            block[..chunk.len()].copy_from_slice(chunk);
+            self.state = self.multiply(self.state ^ u128::from_be_bytes(block));
+        }
+    }
+
+    fn conclude(mut self, header_len: usize, body_len: usize) -> u128 {
+        let lengths = ((header_len as u128 * 8) << 64) | (body_len as u128 * 8);
+        self.state = self.multiply(self.state ^ lengths);
+        self.state
+    }
+}
+
+// Seals sensor batches so that any edit in transit is detected at the receiving dock
+#[derive(Clone)]
+pub struct ExcursionSealer {
+    scrambler: BlockScrambler,
+    accumulator_key: u128,
+}
+
+impl ExcursionSealer {
+    pub fn new(key: &[u8; 16]) -> Self {
+        let scrambler = BlockScrambler::new(key);
+        let accumulator_key = u128::from_be_bytes(scrambler.scramble(&[0u8; READING_FRAME]));
+        ExcursionSealer { scrambler, accumulator_key }
+    }
+
+    fn counter_frame(nonce: &[u8; NONCE_WIDTH], counter: u32) -> [u8; READING_FRAME] {
+        let mut frame = [0u8; READING_FRAME];
+        frame[..NONCE_WIDTH].copy_from_slice(nonce);
+        frame[NONCE_WIDTH..].copy_from_slice(&counter.to_be_bytes());
+        frame
+    }
+
+    fn keystream_apply(&self, nonce: &[u8; NONCE_WIDTH], data: &[u8]) -> Vec<u8> {
+        data.chunks(READING_FRAME)
+            .enumerate()
+            .flat_map(|(index, chunk)| {
+                let pad = self.scrambler.scramble(&Self::counter_frame(nonce, index as u32 + 2));
+                chunk.iter().zip(pad).map(|(a, b)| a ^ b).collect::<Vec<u8>>()
+            })
+            .collect()
+    }
+
+    fn compute_seal(&self, nonce: &[u8; NONCE_WIDTH], header: &[u8], body: &[u8]) -> [u8; SEAL_WIDTH] {
+        let mut accumulator = IntegrityAccumulator::new(self.accumulator_key);
+        accumulator.absorb(header);
+        accumulator.absorb(body);
+        let digest = accumulator.conclude(header.len(), body.len());
+        let mask = u128::from_be_bytes(self.scrambler.scramble(&Self::counter_frame(nonce, 1)));
+        (digest ^ mask).to_be_bytes()
+    }
+
+    pub fn seal(&self, nonce: &[u8; NONCE_WIDTH], header: &[u8], plain: &[u8]) -> (Vec<u8>, [u8; SEAL_WIDTH]) {
+        let body = self.keystream_apply(nonce, plain);
+        let seal = self.compute_seal(nonce, header, &body);
+        (body, seal)
+    }
+
+    pub fn unseal(&self, nonce: &[u8; NONCE_WIDTH], header: &[u8], body: &[u8], seal: &[u8; SEAL_WIDTH]) -> Option<Vec<u8>> {
+        let expected = self.compute_seal(nonce, header, body);
+        let difference = expected.iter().zip(seal).fold(0u8, |acc, (a, b)| acc | (a ^ b));
+        if difference != 0 {
+            return None;
+        }
+        Some(self.keystream_apply(nonce, body))
+    }
+}
+
+pub struct ShipmentLogger {
+    shipment_id: String,
+    device_tag: [u8; 4],
+    sealer: ExcursionSealer,
+    pending: Vec<SensorReading>,
+    sequence: u64,
+}
+
+impl ShipmentLogger {
+    pub fn new(shipment_id: &str, device_tag: [u8; 4], shipment_key: &[u8; 16]) -> Self {
+        ShipmentLogger {
+            shipment_id: shipment_id.to_string(),
+            device_tag,
+            sealer: ExcursionSealer::new(shipment_key),
+            pending: Vec::new(),
+            sequence: 0,
+        }
+    }
+
+    pub fn record(&mut self, minute: u32, tenths_celsius: i16, humidity: u8) {
+        self.pending.push(SensorReading { minute, tenths_celsius, humidity });
+    }
+
+    pub fn excursions(&self) -> BTreeMap<u32, i16> {
+        self.pending
+            .iter()
+            .filter(|r| r.tenths_celsius < BAND_LOW || r.tenths_celsius > BAND_HIGH)
+            .map(|r| (r.minute, r.tenths_celsius))
+            .collect()
+    }
+
+    fn encode(readings: &[SensorReading]) -> Vec<u8> {
+        let mut encoded = Vec::with_capacity(readings.len() * 7);
+        for reading in readings {
+            encoded.extend_from_slice(&reading.minute.to_be_bytes());
+            encoded.extend_from_slice(&reading.tenths_celsius.to_be_bytes());
+            encoded.push(reading.humidity);
+        }
+        encoded
+    }
+
+    pub fn decode(encoded: &[u8]) -> Vec<SensorReading> {
+        encoded
+            .chunks(7)
+            .filter(|c| c.len() == 7)
+            .map(|c| SensorReading {
+                minute: u32::from_be_bytes([c[0], c[1], c[2], c[3]]),
+                tenths_celsius: i16::from_be_bytes([c[4], c[5]]),
+                humidity: c[6],
+            })
+            .collect()
+    }
+
+    pub fn seal_batch(&mut self) -> SealedBatch {
+        self.sequence += 1;
+        let mut nonce = [0u8; NONCE_WIDTH];
+        nonce[..4].copy_from_slice(&self.device_tag);
+        nonce[4..].copy_from_slice(&self.sequence.to_be_bytes());
+
+        let header = format!("{}#{}#{}", self.shipment_id, self.sequence, self.excursions().len()).into_bytes();
+        let readings = std::mem::take(&mut self.pending);
+        let (body, seal) = self.sealer.seal(&nonce, &header, &Self::encode(&readings));
+
+        SealedBatch {
+            shipment_id: self.shipment_id.clone(),
+            sequence: self.sequence,
+            nonce,
+            header,
+            body,
+            seal,
+        }
+    }
+}
+
+pub fn receive_batch(shipment_key: &[u8; 16], batch: &SealedBatch) -> Option<Vec<SensorReading>> {
+    let sealer = ExcursionSealer::new(shipment_key);
+    sealer
+        .unseal(&batch.nonce, &batch.header, &batch.body, &batch.seal)
+        .map(|plain| ShipmentLogger::decode(&plain))
+}
+
+fn main() {
+    println!("Cold Chain Sensor Logger starting...");
+
+    let shipment_key = [
+        0x3a, 0x91, 0x5c, 0x07, 0xe2, 0x48, 0xbd, 0x16, 0x7f, 0xc4, 0x29, 0x83, 0x5e, 0xa0, 0x1b, 0xd9,
+    ];
+    let mut logger = ShipmentLogger::new("SHP-2291-VAX", [0x4c, 0x47, 0x00, 0x17], &shipment_key);
+
+    for (minute, temperature) in [(0, 45), (15, 52), (30, 83), (45, 91), (60, 64)] {
+        logger.record(minute, temperature, 41);
+    }
+    println!("Excursions outside band: {:?}", logger.excursions());
+
+    let batch = logger.seal_batch();
+    println!("Batch {} sealed: {} bytes", batch.sequence, batch.body.len());
+
+    match receive_batch(&shipment_key, &batch) {
+        Some(readings) => println!("Dock verified {} readings", readings.len()),
+        None => println!("Dock rejected batch: seal mismatch"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(text: &str) -> Vec<u8> {
+        (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn array16(text: &str) -> [u8; 16] {
+        let mut out = [0u8; 16];
+        out.copy_from_slice(&hex(text));
+        out
+    }
+
+    #[test]
+    fn block_cipher_known_answer() {
+        // FIPS-197 Appendix C.1
+        let scrambler = BlockScrambler::new(&array16("000102030405060708090a0b0c0d0e0f"));
+        assert_eq!(
+            scrambler.scramble(&array16("00112233445566778899aabbccddeeff")).to_vec(),
+            hex("69c4e0d86a7b0430d8cdb78070b4c55a")
+        );
+    }
+
+    #[test]
+    fn accumulator_known_answer() {
+        // GCM specification test case 2
+        let mut accumulator = IntegrityAccumulator::new(u128::from_be_bytes(array16("66e94bd4ef8a2c3b884cfa59ca342b2e")));
+        accumulator.absorb(&hex("0388dace60b6a392f328c2b971b2fe78"));
+        assert_eq!(
+            accumulator.conclude(0, 16).to_be_bytes().to_vec(),
+            hex("f38cbb1ad69223dcc3457ae5b6b0f885")
+        );
+    }
+
+    #[test]
+    fn sealer_known_answer() {
+        // GCM specification test case 4
+        let sealer = ExcursionSealer::new(&array16("feffe9928665731c6d6a8f9467308308"));
+        let mut nonce = [0u8; NONCE_WIDTH];
+        nonce.copy_from_slice(&hex("cafebabefacedbaddecaf888"));
+        let header = hex("feedfacedeadbeeffeedfacedeadbeefabaddad2");
+        let plain = hex(concat!(
+            "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72",
+            "1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39"
+        ));
+
+        let (body, seal) = sealer.seal(&nonce, &header, &plain);
+        assert_eq!(
+            body,
+            hex(concat!(
+                "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e",
+                "21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091"
+            ))
+        );
+        assert_eq!(seal.to_vec(), hex("5bc94fbc3221a5db94fae95ae7121a47"));
+    }
+
+    #[test]
+    fn tampered_batch_rejected() {
+        let key = [0x11u8; 16];
+        let mut logger = ShipmentLogger::new("SHP-TEST", [1, 2, 3, 4], &key);
+        logger.record(0, 95, 40);
+        logger.record(15, 50, 40);
+        let mut batch = logger.seal_batch();
+
+        let readings = receive_batch(&key, &batch).unwrap();
+        assert_eq!(readings.len(), 2);
+        assert_eq!(readings[0].tenths_celsius, 95);
+
+        batch.body[4] ^= 0x01;
+        assert!(receive_batch(&key, &batch).is_none());
+    }
+}
@@ -0,0 +1,599 @@
+// Firmware Release Attestor
+// Issues release attestations for OTA firmware bundles and checks them on the update agent
+
+const DEGREE: usize = 256;
+const MODULUS: i64 = 8380417;
+const ROWS: usize = 4;
+const COLUMNS: usize = 4;
+const SECRET_BOUND: i64 = 2;
+const CHALLENGE_WEIGHT: usize = 39;
+const MASK_RANGE: i64 = 1 << 17;
+const ROUNDING_SPAN: i64 = (MODULUS - 1) / 88;
+const SLACK: i64 = 78;
+const MAX_ATTEMPTS: u16 = 1000;
+
+// Primitive 512th root of unity mod MODULUS and 256^-1 mod MODULUS
+const ROOT: i64 = 1753;
+const INVERSE_SCALE: i64 = 8347681;
+
+type Poly = [i64; DEGREE];
+
+// ---------------------------------------------------------------------------
+// Sponge (1600-bit permutation) used for expansion, masking and challenges
+// ---------------------------------------------------------------------------
+
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808a, 0x8000000080008000,
+    0x000000000000808b, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008a, 0x0000000000000088, 0x0000000080008009, 0x000000008000000a,
+    0x000000008000808b, 0x800000000000008b, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800a, 0x800000008000000a,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+];
+
+const ROTATIONS: [u32; 25] = [0, 1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43, 25, 39, 41, 45, 15, 21, 8, 18, 2, 61, 56, 14];
+
+fn permute(lanes: &mut [u64; 25]) {
+    for constant in ROUND_CONSTANTS {
+        let mut parity = [0u64; 5];
+        for x in 0..5 {
+            parity[x] = lanes[x] ^ lanes[x + 5] ^ lanes[x + 10] ^ lanes[x + 15] ^ lanes[x + 20];
+        }
+        for x in 0..5 {
+            let d = parity[(x + 4) % 5] ^ parity[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                lanes[x + 5 * y] ^= d;
+            }
+        }
+
+        let mut moved = [0u64; 25];
+        for x in 0..5 {
+            for y in 0..5 {
+                moved[y + 5 * ((2 * x + 3 * y) % 5)] = lanes[x + 5 * y].rotate_left(ROTATIONS[x + 5 * y]);
+            }
+        }
+
+        for y in 0..5 {
+            for x in 0..5 {
+                lanes[x + 5 * y] = moved[x + 5 * y] ^ (!moved[(x + 1) % 5 + 5 * y] & moved[(x + 2) % 5 + 5 * y]);
+            }
+        }
+        lanes[0] ^= constant;
+    }
+}
+
+struct Sponge {
+    lanes: [u64; 25],
+    rate: usize,
+    offset: usize,
+    squeezing: bool,
+}
+
+impl Sponge {
+    fn wide() -> Self {
+        Sponge { lanes: [0; 25], rate: 136, offset: 0, squeezing: false }
+    }
+
+    fn narrow() -> Self {
+        Sponge { lanes: [0; 25], rate: 168, offset: 0, squeezing: false }
+    }
+
+    fn xor_byte(&mut self, index: usize, byte: u8) {
+        self.lanes[index / 8] ^= (byte as u64) << (8 * (index % 8));
+    }
+
+    fn absorb(&mut self, data: &[u8]) -> &mut Self {
+        for &byte in data {
+            self.xor_byte(self.offset, byte);
+            self.offset += 1;
+            if self.offset == self.rate {
+                permute(&mut self.lanes);
+                self.offset = 0;
+            }
+        }
+        self
+    }
+
+    fn squeeze(&mut self, out: &mut [u8]) {
+        if !self.squeezing {
+            self.xor_byte(self.offset, 0x1f);
+            self.xor_byte(self.rate - 1, 0x80);
+            permute(&mut self.lanes);
+            self.offset = 0;
+            self.squeezing = true;
+        }
+        for slot in out.iter_mut() {
+            if self.offset == self.rate {
+                permute(&mut self.lanes);
+                self.offset = 0;
+            }
+            *slot = (self.lanes[self.offset / 8] >> (8 * (self.offset % 8))) as u8;
+            self.offset += 1;
+        }
+    }
+
+    fn next_byte(&mut self) -> u8 {
+        let mut byte = [0u8; 1];
+        self.squeeze(&mut byte);
+        byte[0]
+    }
+}
+
+fn digest64(parts: &[&[u8]]) -> [u8; 64] {
+    let mut sponge = Sponge::wide();
+    for part in parts {
+        sponge.absorb(part);
+    }
+    let mut out = [0u8; 64];
+    sponge.squeeze(&mut out);
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Ring arithmetic mod (X^256 + 1)
+// ---------------------------------------------------------------------------
+
+fn reduce(value: i64) -> i64 {
+    value.rem_euclid(MODULUS)
+}
+
+fn centered(value: i64) -> i64 {
+    let r = reduce(value);
+    if r > MODULUS / 2 { r - MODULUS } else { r }
+}
+
+fn power(base: i64, mut exponent: u32) -> i64 {
+    let (mut result, mut b) = (1i64, base);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * b % MODULUS;
+        }
+        b = b * b % MODULUS;
+        exponent >>= 1;
+    }
+    result
+}
+
+struct Butterfly {
+    twiddles: [i64; DEGREE],
+}
+
+impl Butterfly {
+    fn new() -> Self {
+        let mut twiddles = [0i64; DEGREE];
+        for (k, slot) in twiddles.iter_mut().enumerate() {
+            *slot = power(ROOT, (k as u8).reverse_bits() as u32);
+        }
+        Butterfly { twiddles }
+    }
+
+    fn forward(&self, mut f: Poly) -> Poly {
+        let mut m = 0;
+        let mut len = 128;
+        while len >= 1 {
+            for start in (0..DEGREE).step_by(2 * len) {
+                m += 1;
+                let zeta = self.twiddles[m];
+                for j in start..start + len {
+                    let t = zeta * f[j + len] % MODULUS;
+                    f[j + len] = reduce(f[j] - t);
+                    f[j] = reduce(f[j] + t);
+                }
+            }
+            len /= 2;
+        }
+        f
+    }
+
+    fn inverse(&self, mut f: Poly) -> Poly {
+        let mut m = DEGREE;
+        let mut len = 1;
+        while len < DEGREE {
+            for start in (0..DEGREE).step_by(2 * len) {
+                m -= 1;
+                let zeta = MODULUS - self.twiddles[m];
+                for j in start..start + len {
+                    let t = f[j];
+                    f[j] = reduce(t + f[j + len]);
+                    f[j + len] = reduce(zeta * reduce(t - f[j + len]));
+                }
+            }
+            len *= 2;
+        }
+        f.map(|c| c * INVERSE_SCALE % MODULUS)
+    }
+}
+
+fn pointwise(f: &Poly, g: &Poly) -> Poly {
+    let mut h = [0i64; DEGREE];
+    for i in 0..DEGREE {
+        h[i] = f[i] * g[i] % MODULUS;
+    }
+    h
+}
+
+fn add(f: &Poly, g: &Poly) -> Poly {
+    let mut h = [0i64; DEGREE];
+    for i in 0..DEGREE {
+        h[i] = reduce(f[i] + g[i]);
+    }
+    h
+}
+
+fn subtract(f: &Poly, g: &Poly) -> Poly {
+    let mut h = [0i64; DEGREE];
+    for i in 0..DEGREE {
+        h[i] = reduce(f[i] - g[i]);
+    }
+    h
+}
+
+fn spread(f: &Poly) -> i64 {
+    f.iter().map(|&c| centered(c).abs()).max().unwrap_or(0)
+}
+
+// High/low split around multiples of 2 * ROUNDING_SPAN
+fn split_rounding(value: i64) -> (i64, i64) {
+    let r = reduce(value);
+    let mut low = r % (2 * ROUNDING_SPAN);
+    if low > ROUNDING_SPAN {
+        low -= 2 * ROUNDING_SPAN;
+    }
+    if r - low == MODULUS - 1 {
+        (0, low - 1)
+    } else {
+        ((r - low) / (2 * ROUNDING_SPAN), low)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Expansion from seeds
+// ---------------------------------------------------------------------------
+
+fn uniform_entry(seed: &[u8; 32], row: usize, column: usize) -> Poly {
+    let mut sponge = Sponge::narrow();
+    sponge.absorb(seed).absorb(&[column as u8, row as u8]);
+    let mut f = [0i64; DEGREE];
+    let mut filled = 0;
+    while filled < DEGREE {
+        let mut bytes = [0u8; 3];
+        sponge.squeeze(&mut bytes);
+        let candidate = (bytes[0] as i64) | (bytes[1] as i64) << 8 | ((bytes[2] & 0x7f) as i64) << 16;
+        if candidate < MODULUS {
+            f[filled] = candidate;
+            filled += 1;
+        }
+    }
+    f
+}
+
+fn bounded_secret(seed: &[u8; 64], nonce: u16) -> Poly {
+    let mut sponge = Sponge::wide();
+    sponge.absorb(seed).absorb(&nonce.to_le_bytes());
+    let mut f = [0i64; DEGREE];
+    let mut filled = 0;
+    while filled < DEGREE {
+        let byte = sponge.next_byte();
+        for nibble in [byte & 0x0f, byte >> 4] {
+            if nibble < 15 && filled < DEGREE {
+                f[filled] = reduce(SECRET_BOUND - (nibble % 5) as i64);
+                filled += 1;
+            }
+        }
+    }
+    f
+}
+
+fn mask_poly(seed: &[u8; 64], nonce: u16) -> Poly {
+    let mut sponge = Sponge::wide();
+    sponge.absorb(seed).absorb(&nonce.to_le_bytes());
+    let mut bytes = [0u8; 576];
+    sponge.squeeze(&mut bytes);
+
+    let mut f = [0i64; DEGREE];
+    for (i, slot) in f.iter_mut().enumerate() {
+        let bit = 18 * i;
+        let mut raw = 0u32;
+        for b in 0..3 {
+            raw |= (bytes.get(bit / 8 + b).copied().unwrap_or(0) as u32) << (8 * b);
+        }
+        let value = (raw >> (bit % 8)) & ((1 << 18) - 1);
+        *slot = reduce(MASK_RANGE - value as i64);
+    }
+    f
+}
+
+// Sparse ternary challenge with exactly CHALLENGE_WEIGHT nonzero coefficients
+fn sparse_challenge(commitment: &[u8]) -> Poly {
+    let mut sponge = Sponge::wide();
+    sponge.absorb(commitment);
+    let mut sign_bytes = [0u8; 8];
+    sponge.squeeze(&mut sign_bytes);
+    let mut signs = u64::from_le_bytes(sign_bytes);
+
+    let mut c = [0i64; DEGREE];
+    for i in DEGREE - CHALLENGE_WEIGHT..DEGREE {
+        let mut j = sponge.next_byte() as usize;
+        while j > i {
+            j = sponge.next_byte() as usize;
+        }
+        c[i] = c[j];
+        c[j] = if signs & 1 == 1 { MODULUS - 1 } else { 1 };
+        signs >>= 1;
+    }
+    c
+}
+
+// ---------------------------------------------------------------------------
+// Attestation keys
+// ---------------------------------------------------------------------------
+
+pub struct ReleaseVerifierKey {
+    matrix_seed: [u8; 32],
+    target: Vec<Poly>,
+    fingerprint: [u8; 64],
+}
+
+pub struct ReleaseSigningKey {
+    verifier: ReleaseVerifierKey,
+    secret_columns: Vec<Poly>,
+    secret_rows: Vec<Poly>,
+    signing_salt: [u8; 32],
+}
+
+#[derive(Clone)]
+pub struct ReleaseAttestation {
+    pub commitment: [u8; 32],
+    pub response: Vec<Poly>,
+    pub attempts: u16,
+}
+
+pub struct AttestationService {
+    butterfly: Butterfly,
+}
+
+impl AttestationService {
+    pub fn new() -> Self {
+        AttestationService { butterfly: Butterfly::new() }
+    }
+
+    fn expanded_matrix(&self, seed: &[u8; 32]) -> Vec<Vec<Poly>> {
+        (0..ROWS).map(|r| (0..COLUMNS).map(|c| uniform_entry(seed, r, c)).collect()).collect()
+    }
+
+    fn apply(&self, matrix: &[Vec<Poly>], vector: &[Poly]) -> Vec<Poly> {
+        let transformed: Vec<Poly> = vector.iter().map(|v| self.butterfly.forward(*v)).collect();
+        matrix
+            .iter()
+            .map(|row| {
+                let sum = row.iter().zip(&transformed).fold([0i64; DEGREE], |acc, (a, v)| add(&acc, &pointwise(a, v)));
+                self.butterfly.inverse(sum)
+            })
+            .collect()
+    }
+
+    fn scale(&self, challenge: &Poly, vector: &[Poly]) -> Vec<Poly> {
+        let c = self.butterfly.forward(*challenge);
+        vector.iter().map(|v| self.butterfly.inverse(pointwise(&c, &self.butterfly.forward(*v)))).collect()
+    }
+
+    fn commitment(&self, digest: &[u8; 64], high_parts: &[Poly]) -> [u8; 32] {
+        let mut sponge = Sponge::wide();
+        sponge.absorb(digest);
+        for poly in high_parts {
+            let packed: Vec<u8> = poly.iter().map(|&c| c as u8).collect();
+            sponge.absorb(&packed);
+        }
+        let mut out = [0u8; 32];
+        sponge.squeeze(&mut out);
+        out
+    }
+
+    pub fn enroll(&self, master_seed: &[u8; 32]) -> ReleaseSigningKey {
+        let expanded = digest64(&[master_seed, &[ROWS as u8, COLUMNS as u8]]);
+        let mut matrix_seed = [0u8; 32];
+        matrix_seed.copy_from_slice(&expanded[..32]);
+        let mut secret_seed = [0u8; 64];
+        secret_seed[..32].copy_from_slice(&expanded[32..]);
+        secret_seed[32..].copy_from_slice(master_seed);
+        let signing_salt = {
+            let salt = digest64(&[b"release-salt", master_seed]);
+            let mut out = [0u8; 32];
+            out.copy_from_slice(&salt[..32]);
+            out
+        };
+
+        let secret_columns: Vec<Poly> = (0..COLUMNS).map(|i| bounded_secret(&secret_seed, i as u16)).collect();
+        let secret_rows: Vec<Poly> = (0..ROWS).map(|i| bounded_secret(&secret_seed, (COLUMNS + i) as u16)).collect();
+
+        // Public target kept at full precision (no coefficient dropping, so no hints are needed)
+        let matrix = self.expanded_matrix(&matrix_seed);
+        let target: Vec<Poly> = self
+            .apply(&matrix, &secret_columns)
+            .iter()
+            .zip(&secret_rows)
+            .map(|(a, e)| add(a, e))
+            .collect();
+
+        let mut sponge = Sponge::wide();
+        sponge.absorb(&matrix_seed);
+        for poly in &target {
+            for c in poly {
+                sponge.absorb(&(*c as u32).to_le_bytes());
+            }
+        }
+        let mut fingerprint = [0u8; 64];
+        sponge.squeeze(&mut fingerprint);
+
+        ReleaseSigningKey {
+            verifier: ReleaseVerifierKey { matrix_seed, target, fingerprint },
+            secret_columns,
+            secret_rows,
+            signing_salt,
+        }
+    }
+
+    pub fn attest(&self, key: &ReleaseSigningKey, bundle: &[u8]) -> Option<ReleaseAttestation> {
+        let matrix = self.expanded_matrix(&key.verifier.matrix_seed);
+        let digest = digest64(&[&key.verifier.fingerprint, bundle]);
+        let mask_seed = digest64(&[&key.signing_salt, &digest]);
+
+        for attempt in 0..MAX_ATTEMPTS {
+            let mask: Vec<Poly> = (0..COLUMNS).map(|i| mask_poly(&mask_seed, attempt * COLUMNS as u16 + i as u16)).collect();
+            let opening = self.apply(&matrix, &mask);
+            let high: Vec<Poly> = opening.iter().map(|w| w.map(|c| split_rounding(c).0)).collect();
+
+            let commitment = self.commitment(&digest, &high);
+            let challenge = sparse_challenge(&commitment);
+
+            let response: Vec<Poly> = self
+                .scale(&challenge, &key.secret_columns)
+                .iter()
+                .zip(&mask)
+                .map(|(cs, y)| add(y, cs))
+                .collect();
+            if response.iter().any(|z| spread(z) >= MASK_RANGE - SLACK) {
+                continue;
+            }
+
+            let shifted: Vec<Poly> = self
+                .scale(&challenge, &key.secret_rows)
+                .iter()
+                .zip(&opening)
+                .map(|(ce, w)| subtract(w, ce))
+                .collect();
+            let low_too_wide = shifted
+                .iter()
+                .any(|r| r.iter().any(|&c| split_rounding(c).1.abs() >= ROUNDING_SPAN - SLACK));
+            if low_too_wide {
+                continue;
+            }
+
+            return Some(ReleaseAttestation { commitment, response, attempts: attempt + 1 });
+        }
+        None
+    }
+
+    pub fn check(&self, verifier: &ReleaseVerifierKey, bundle: &[u8], attestation: &ReleaseAttestation) -> bool {
+        if attestation.response.len() != COLUMNS {
+            return false;
+        }
+        if attestation.response.iter().any(|z| spread(z) >= MASK_RANGE - SLACK) {
+            return false;
+        }
+
+        let matrix = self.expanded_matrix(&verifier.matrix_seed);
+        let digest = digest64(&[&verifier.fingerprint, bundle]);
+        let challenge = sparse_challenge(&attestation.commitment);
+
+        let high: Vec<Poly> = self
+            .apply(&matrix, &attestation.response)
+            .iter()
+            .zip(self.scale(&challenge, &verifier.target))
+            .map(|(az, ct)| subtract(az, &ct).map(|c| split_rounding(c).0))
+            .collect();
+        self.commitment(&digest, &high) == attestation.commitment
+    }
+}
+
+fn main() {
+    println!("Firmware Release Attestor starting...");
+
+    let service = AttestationService::new();
+    let signing_key = service.enroll(&[0x42; 32]);
+
+    let bundle = b"fw-bundle:gateway-x7:4.12.0:sha=9c1e77d0";
+    match service.attest(&signing_key, bundle) {
+        Some(attestation) => {
+            println!("Attestation issued after {} attempt(s)", attestation.attempts);
+            println!("Update agent accepts: {}", service.check(&signing_key.verifier, bundle, &attestation));
+            println!("Tampered bundle accepted: {}", service.check(&signing_key.verifier, b"fw-bundle:gateway-x7:4.12.1", &attestation));
+        }
+        None => println!("Attestation failed: attempt budget exhausted"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn sponge_known_answer() {
+        // FIPS 202 empty-string and "abc" outputs
+        let mut out = [0u8; 32];
+        Sponge::wide().squeeze(&mut out);
+        assert_eq!(hex(&out), "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762f");
+        Sponge::wide().absorb(b"abc").squeeze(&mut out);
+        assert_eq!(hex(&out), "483366601360a8771c6863080cc4114d8db44530f8f1e1ee4f94ea37e78b5739");
+        let mut short = [0u8; 16];
+        Sponge::narrow().squeeze(&mut short);
+        assert_eq!(hex(&short), "7f9c2ba4e88f827d616045507605853e");
+    }
+
+    #[test]
+    fn butterfly_product_is_negacyclic_product() {
+        let butterfly = Butterfly::new();
+        assert_eq!(power(ROOT, 256), MODULUS - 1);
+        let f = uniform_entry(&[7; 32], 0, 1);
+        let g = bounded_secret(&[9; 64], 3);
+
+        let product = butterfly.inverse(pointwise(&butterfly.forward(f), &butterfly.forward(g)));
+        let mut expected = [0i64; DEGREE];
+        for i in 0..DEGREE {
+            for j in 0..DEGREE {
+                let term = f[i] * g[j] % MODULUS;
+                let k = (i + j) % DEGREE;
+                expected[k] = if i + j < DEGREE { reduce(expected[k] + term) } else { reduce(expected[k] - term) };
+            }
+        }
+        assert_eq!(product, expected);
+    }
+
+    #[test]
+    fn challenge_has_fixed_weight() {
+        let c = sparse_challenge(b"commitment");
+        assert_eq!(c.iter().filter(|&&x| x != 0).count(), CHALLENGE_WEIGHT);
+        assert!(c.iter().all(|&x| x == 0 || x == 1 || x == MODULUS - 1));
+    }
+
+    #[test]
+    fn rounding_split_recombines() {
+        for value in (0..MODULUS).step_by(9973).chain([MODULUS - 1, ROUNDING_SPAN, 2 * ROUNDING_SPAN]) {
+            let (high, low) = split_rounding(value);
+            assert_eq!(reduce(high * 2 * ROUNDING_SPAN + low), value);
+            assert!(low.abs() <= ROUNDING_SPAN);
+            assert!((0..44).contains(&high));
+        }
+    }
+
+    #[test]
+    fn attest_and_check() {
+        let service = AttestationService::new();
+        let key = service.enroll(&[3; 32]);
+        let attestation = service.attest(&key, b"bundle-a").expect("attempt budget");
+
+        assert!(service.check(&key.verifier, b"bundle-a", &attestation));
+        assert!(!service.check(&key.verifier, b"bundle-b", &attestation));
+
+        let mut forged = attestation.clone();
+        forged.response[0][0] = reduce(forged.response[0][0] + 1);
+        assert!(!service.check(&key.verifier, b"bundle-a", &forged));
+    }
+
+    #[test]
+    fn rejection_keeps_responses_inside_bound() {
+        let service = AttestationService::new();
+        let key = service.enroll(&[5; 32]);
+        let mut total_attempts = 0;
+        for n in 0..4u8 {
+            let attestation = service.attest(&key, &[n; 16]).expect("attempt budget");
+            assert!(attestation.response.iter().all(|z| spread(z) < MASK_RANGE - SLACK));
+            total_attempts += attestation.attempts as u32;
+        }
+        assert!(total_attempts >= 4);
+    }
+}
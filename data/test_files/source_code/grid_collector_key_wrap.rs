@@ -0,0 +1,397 @@
+// Grid Collector Key Wrap
+// Wraps per-feeder session keys for smart-grid data collectors using ring-vector sealing
+
+const RING_DEGREE: usize = 256;
+const FIELD: i32 = 3329;
+const RANK: usize = 3;
+const SECRET_SPREAD: usize = 2;
+const NOISE_SPREAD: usize = 2;
+const WIDE_BITS: u32 = 10;
+const NARROW_BITS: u32 = 4;
+const PAYLOAD_BYTES: usize = 32;
+
+// 128^-1 mod FIELD, applied once at the end of the inverse transform
+const INVERSE_SCALE: i32 = 3303;
+// Primitive 256th root of unity mod FIELD
+const ROOT: i32 = 17;
+
+type Ring = [i32; RING_DEGREE];
+
+fn reduce(value: i64) -> i32 {
+    value.rem_euclid(FIELD as i64) as i32
+}
+
+fn power(base: i32, mut exponent: u32) -> i32 {
+    let mut result: i64 = 1;
+    let mut b = base as i64;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * b % FIELD as i64;
+        }
+        b = b * b % FIELD as i64;
+        exponent >>= 1;
+    }
+    result as i32
+}
+
+fn reverse7(value: usize) -> u32 {
+    (value as u8).reverse_bits() as u32 >> 1
+}
+
+// Twiddle factors ROOT^rev7(i) and pairwise factors ROOT^(2 rev7(i) + 1)
+struct SpectralTables {
+    twiddles: [i32; 128],
+    pair_factors: [i32; 128],
+}
+
+impl SpectralTables {
+    fn new() -> Self {
+        let mut twiddles = [0i32; 128];
+        let mut pair_factors = [0i32; 128];
+        for i in 0..128 {
+            twiddles[i] = power(ROOT, reverse7(i));
+            pair_factors[i] = power(ROOT, 2 * reverse7(i) + 1);
+        }
+        SpectralTables { twiddles, pair_factors }
+    }
+
+    fn forward(&self, f: &mut Ring) {
+        let mut k = 1;
+        let mut len = 128;
+        while len >= 2 {
+            for start in (0..RING_DEGREE).step_by(2 * len) {
+                let zeta = self.twiddles[k] as i64;
+                k += 1;
+                for j in start..start + len {
+                    let t = reduce(zeta * f[j + len] as i64);
+                    f[j + len] = reduce(f[j] as i64 - t as i64);
+                    f[j] = reduce(f[j] as i64 + t as i64);
+                }
+            }
+            len /= 2;
+        }
+    }
+
+    fn inverse(&self, f: &mut Ring) {
+        let mut k = 127;
+        let mut len = 2;
+        while len <= 128 {
+            for start in (0..RING_DEGREE).step_by(2 * len) {
+                let zeta = self.twiddles[k] as i64;
+                k -= 1;
+                for j in start..start + len {
+                    let t = f[j];
+                    f[j] = reduce(t as i64 + f[j + len] as i64);
+                    f[j + len] = reduce(zeta * (f[j + len] as i64 - t as i64));
+                }
+            }
+            len *= 2;
+        }
+        for coefficient in f.iter_mut() {
+            *coefficient = reduce(*coefficient as i64 * INVERSE_SCALE as i64);
+        }
+    }
+
+    // Products in the transformed domain are 128 degree-one products mod (X^2 - factor)
+    fn pointwise(&self, f: &Ring, g: &Ring) -> Ring {
+        let mut h = [0i32; RING_DEGREE];
+        for i in 0..128 {
+            let (a0, a1) = (f[2 * i] as i64, f[2 * i + 1] as i64);
+            let (b0, b1) = (g[2 * i] as i64, g[2 * i + 1] as i64);
+            h[2 * i] = reduce(a0 * b0 + reduce(a1 * b1) as i64 * self.pair_factors[i] as i64);
+            h[2 * i + 1] = reduce(a0 * b1 + a1 * b0);
+        }
+        h
+    }
+}
+
+fn add(f: &Ring, g: &Ring) -> Ring {
+    let mut h = [0i32; RING_DEGREE];
+    for i in 0..RING_DEGREE {
+        h[i] = reduce(f[i] as i64 + g[i] as i64);
+    }
+    h
+}
+
+fn subtract(f: &Ring, g: &Ring) -> Ring {
+    let mut h = [0i32; RING_DEGREE];
+    for i in 0..RING_DEGREE {
+        h[i] = reduce(f[i] as i64 - g[i] as i64);
+    }
+    h
+}
+
+// Rounded rescaling between the field and d-bit wire values
+fn squeeze(value: i32, bits: u32) -> u32 {
+    let scaled = ((value as u64) << bits) + (FIELD as u64 / 2);
+    (scaled / FIELD as u64) as u32 & ((1 << bits) - 1)
+}
+
+fn expand(value: u32, bits: u32) -> i32 {
+    ((value as u64 * FIELD as u64 + (1u64 << (bits - 1))) >> bits) as i32
+}
+
+// Collector entropy pool (seeded from the provisioning token; not a DRBG)
+pub struct EntropyPool {
+    state: u64,
+}
+
+impl EntropyPool {
+    fn new(seed: u64) -> Self {
+        EntropyPool { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    fn fill(&mut self, out: &mut [u8]) {
+        for chunk in out.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    // Uniform transformed-domain element: 12-bit candidates below FIELD are accepted
+    fn uniform_ring(&mut self) -> Ring {
+        let mut f = [0i32; RING_DEGREE];
+        let mut filled = 0;
+        while filled < RING_DEGREE {
+            let mut bytes = [0u8; 3];
+            self.fill(&mut bytes);
+            let d1 = bytes[0] as i32 + 256 * (bytes[1] as i32 % 16);
+            let d2 = bytes[1] as i32 / 16 + 16 * bytes[2] as i32;
+            for candidate in [d1, d2] {
+                if candidate < FIELD && filled < RING_DEGREE {
+                    f[filled] = candidate;
+                    filled += 1;
+                }
+            }
+        }
+        f
+    }
+
+    // Small coefficients from the difference of two bit counts (centered binomial)
+    fn small_ring(&mut self, spread: usize) -> Ring {
+        let mut bytes = vec![0u8; 64 * spread];
+        self.fill(&mut bytes);
+        let bit = |index: usize| ((bytes[index / 8] >> (index % 8)) & 1) as i32;
+
+        let mut f = [0i32; RING_DEGREE];
+        for i in 0..RING_DEGREE {
+            let x: i32 = (0..spread).map(|j| bit(2 * i * spread + j)).sum();
+            let y: i32 = (0..spread).map(|j| bit(2 * i * spread + spread + j)).sum();
+            f[i] = reduce((x - y) as i64);
+        }
+        f
+    }
+}
+
+pub struct CollectorPublicKey {
+    spread_matrix: Vec<Vec<Ring>>,
+    anchor: Vec<Ring>,
+}
+
+pub struct CollectorSecretKey {
+    hidden: Vec<Ring>,
+}
+
+pub struct WrappedKey {
+    pub wide: Vec<[u16; RING_DEGREE]>,
+    pub narrow: [u8; RING_DEGREE],
+}
+
+impl WrappedKey {
+    pub fn wire_size(&self) -> usize {
+        RANK * RING_DEGREE * WIDE_BITS as usize / 8 + RING_DEGREE * NARROW_BITS as usize / 8
+    }
+}
+
+pub struct KeyWrapEngine {
+    tables: SpectralTables,
+}
+
+impl KeyWrapEngine {
+    pub fn new() -> Self {
+        KeyWrapEngine { tables: SpectralTables::new() }
+    }
+
+    fn transformed(&self, mut f: Ring) -> Ring {
+        self.tables.forward(&mut f);
+        f
+    }
+
+    fn restored(&self, mut f: Ring) -> Ring {
+        self.tables.inverse(&mut f);
+        f
+    }
+
+    fn dot(&self, row: &[Ring], column: &[Ring]) -> Ring {
+        row.iter()
+            .zip(column)
+            .fold([0i32; RING_DEGREE], |acc, (a, b)| add(&acc, &self.tables.pointwise(a, b)))
+    }
+
+    pub fn provision(&self, pool: &mut EntropyPool) -> (CollectorPublicKey, CollectorSecretKey) {
+        let spread_matrix: Vec<Vec<Ring>> = (0..RANK).map(|_| (0..RANK).map(|_| pool.uniform_ring()).collect()).collect();
+        let hidden: Vec<Ring> = (0..RANK).map(|_| self.transformed(pool.small_ring(SECRET_SPREAD))).collect();
+        let errors: Vec<Ring> = (0..RANK).map(|_| self.transformed(pool.small_ring(SECRET_SPREAD))).collect();
+
+        let anchor = (0..RANK)
+            .map(|i| add(&self.dot(&spread_matrix[i], &hidden), &errors[i]))
+            .collect();
+        (CollectorPublicKey { spread_matrix, anchor }, CollectorSecretKey { hidden })
+    }
+
+    pub fn wrap(&self, public: &CollectorPublicKey, payload: &[u8; PAYLOAD_BYTES], pool: &mut EntropyPool) -> WrappedKey {
+        let blinding: Vec<Ring> = (0..RANK).map(|_| self.transformed(pool.small_ring(SECRET_SPREAD))).collect();
+        let noise: Vec<Ring> = (0..RANK).map(|_| pool.small_ring(NOISE_SPREAD)).collect();
+        let tail_noise = pool.small_ring(NOISE_SPREAD);
+
+        let mut wide = Vec::with_capacity(RANK);
+        for i in 0..RANK {
+            let column: Vec<Ring> = (0..RANK).map(|j| public.spread_matrix[j][i]).collect();
+            let u = add(&self.restored(self.dot(&column, &blinding)), &noise[i]);
+            let mut packed = [0u16; RING_DEGREE];
+            for (slot, value) in packed.iter_mut().zip(u.iter()) {
+                *slot = squeeze(*value, WIDE_BITS) as u16;
+            }
+            wide.push(packed);
+        }
+
+        let mut message = [0i32; RING_DEGREE];
+        for i in 0..RING_DEGREE {
+            message[i] = expand(((payload[i / 8] >> (i % 8)) & 1) as u32, 1);
+        }
+        let v = add(&add(&self.restored(self.dot(&public.anchor, &blinding)), &tail_noise), &message);
+
+        let mut narrow = [0u8; RING_DEGREE];
+        for (slot, value) in narrow.iter_mut().zip(v.iter()) {
+            *slot = squeeze(*value, NARROW_BITS) as u8;
+        }
+        WrappedKey { wide, narrow }
+    }
+
+    pub fn unwrap(&self, secret: &CollectorSecretKey, wrapped: &WrappedKey) -> [u8; PAYLOAD_BYTES] {
+        let u: Vec<Ring> = wrapped
+            .wide
+            .iter()
+            .map(|packed| {
+                let mut f = [0i32; RING_DEGREE];
+                for (slot, value) in f.iter_mut().zip(packed.iter()) {
+                    *slot = expand(*value as u32, WIDE_BITS);
+                }
+                self.transformed(f)
+            })
+            .collect();
+
+        let mut v = [0i32; RING_DEGREE];
+        for (slot, value) in v.iter_mut().zip(wrapped.narrow.iter()) {
+            *slot = expand(*value as u32, NARROW_BITS);
+        }
+        let w = subtract(&v, &self.restored(self.dot(&secret.hidden, &u)));
+
+        let mut payload = [0u8; PAYLOAD_BYTES];
+        for i in 0..RING_DEGREE {
+            payload[i / 8] |= (squeeze(w[i], 1) as u8) << (i % 8);
+        }
+        payload
+    }
+}
+
+fn main() {
+    println!("Grid Collector Key Wrap starting...");
+
+    let engine = KeyWrapEngine::new();
+    let mut pool = EntropyPool::new(0x5eed_c011_ec70_0042);
+    let (public, secret) = engine.provision(&mut pool);
+
+    let mut feeder_key = [0u8; PAYLOAD_BYTES];
+    pool.fill(&mut feeder_key);
+    let wrapped = engine.wrap(&public, &feeder_key, &mut pool);
+    println!("Wrapped feeder key: {} bytes on the wire", wrapped.wire_size());
+
+    let recovered = engine.unwrap(&secret, &wrapped);
+    println!("Collector recovered key: {}", recovered == feeder_key);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schoolbook(f: &Ring, g: &Ring) -> Ring {
+        let mut h = [0i64; RING_DEGREE];
+        for i in 0..RING_DEGREE {
+            for j in 0..RING_DEGREE {
+                let term = f[i] as i64 * g[j] as i64;
+                if i + j < RING_DEGREE {
+                    h[i + j] += term;
+                } else {
+                    h[i + j - RING_DEGREE] -= term;
+                }
+            }
+        }
+        h.map(reduce)
+    }
+
+    #[test]
+    fn root_and_scale_constants() {
+        assert_eq!(power(ROOT, 128), FIELD - 1);
+        assert_eq!(reduce(128 * INVERSE_SCALE as i64), 1);
+        let tables = SpectralTables::new();
+        assert_eq!(tables.twiddles[1], 1729);
+        assert_eq!(tables.pair_factors[0], 17);
+    }
+
+    #[test]
+    fn transform_round_trip() {
+        let tables = SpectralTables::new();
+        let mut pool = EntropyPool::new(1);
+        let original = pool.uniform_ring();
+        let mut f = original;
+        tables.forward(&mut f);
+        tables.inverse(&mut f);
+        assert_eq!(f, original);
+    }
+
+    #[test]
+    fn transformed_product_is_negacyclic_product() {
+        let tables = SpectralTables::new();
+        let mut pool = EntropyPool::new(2);
+        let (f, g) = (pool.uniform_ring(), pool.small_ring(3));
+
+        let (mut tf, mut tg) = (f, g);
+        tables.forward(&mut tf);
+        tables.forward(&mut tg);
+        let mut product = tables.pointwise(&tf, &tg);
+        tables.inverse(&mut product);
+        assert_eq!(product, schoolbook(&f, &g));
+    }
+
+    #[test]
+    fn wrap_unwrap_round_trip() {
+        let engine = KeyWrapEngine::new();
+        for seed in 0..8 {
+            let mut pool = EntropyPool::new(seed);
+            let (public, secret) = engine.provision(&mut pool);
+            let mut payload = [0u8; PAYLOAD_BYTES];
+            pool.fill(&mut payload);
+            let wrapped = engine.wrap(&public, &payload, &mut pool);
+            assert_eq!(wrapped.wire_size(), 1088);
+            assert_eq!(engine.unwrap(&secret, &wrapped), payload);
+        }
+    }
+
+    #[test]
+    fn rescaling_error_is_bounded() {
+        for value in 0..FIELD {
+            let back = expand(squeeze(value, WIDE_BITS), WIDE_BITS);
+            let distance = (value - back).rem_euclid(FIELD).min((back - value).rem_euclid(FIELD));
+            assert!(distance <= 2);
+        }
+    }
+}
@@ -0,0 +1,330 @@
+// Kiosk PIN Vault
+// Stores staff unlock PINs for self-service ticket kiosks as salted verifiers
+
+use std::collections::HashMap;
+
+const WORD_COUNT: usize = 8;
+const FRAME_BYTES: usize = 64;
+const TAG_BYTES: usize = 32;
+const SALT_BYTES: usize = 8;
+
+// Stretching budget tuned for the kiosk's 400 MHz ARM board
+const STRETCH_ROUNDS: u32 = 1000;
+const VERIFIER_BYTES: usize = 16;
+const LOCKOUT_THRESHOLD: u32 = 5;
+
+const OPENING_WORDS: [u32; WORD_COUNT] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const ROUND_TWEAKS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+#[derive(Clone)]
+struct FrameCondenser {
+    words: [u32; WORD_COUNT],
+    frame: [u8; FRAME_BYTES],
+    filled: usize,
+    consumed: u64,
+}
+
+impl FrameCondenser {
+    fn new() -> Self {
+        FrameCondenser { words: OPENING_WORDS, frame: [0u8; FRAME_BYTES], filled: 0, consumed: 0 }
+    }
+
+    fn churn(&mut self) {
+        let mut spread = [0u32; 64];
+        for i in 0..16 {
+            spread[i] = u32::from_be_bytes([
+                self.frame[4 * i],
+                self.frame[4 * i + 1],
+                self.frame[4 * i + 2],
+                self.frame[4 * i + 3],
+            ]);
+        }
+        for i in 16..64 {
+            let low = spread[i - 15].rotate_right(7) ^ spread[i - 15].rotate_right(18) ^ (spread[i - 15] >> 3);
+            let high = spread[i - 2].rotate_right(17) ^ spread[i - 2].rotate_right(19) ^ (spread[i - 2] >> 10);
+            spread[i] = spread[i - 16]
+                .wrapping_add(low)
+                .wrapping_add(spread[i - 7])
+                .wrapping_add(high);
+        }
+
+        let mut r = self.words;
+        for i in 0..64 {
+            let pick = (r[4] & r[5]) ^ (!r[4] & r[6]);
+            let vote = (r[0] & r[1]) ^ (r[0] & r[2]) ^ (r[1] & r[2]);
+            let tilt_e = r[4].rotate_right(6) ^ r[4].rotate_right(11) ^ r[4].rotate_right(25);
+            let tilt_a = r[0].rotate_right(2) ^ r[0].rotate_right(13) ^ r[0].rotate_right(22);
+            let carry = r[7]
+                .wrapping_add(tilt_e)
+                .wrapping_add(pick)
+                .wrapping_add(ROUND_TWEAKS[i])
+                .wrapping_add(spread[i]);
+            r.rotate_right(1);
+            r[4] = r[4].wrapping_add(carry);
+            r[0] = carry.wrapping_add(tilt_a).wrapping_add(vote);
+        }
+
+        for i in 0..WORD_COUNT {
+            self.words[i] = self.words[i].wrapping_add(r[i]);
+        }
+        self.filled = 0;
+    }
+
+    fn absorb(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.frame[self.filled] = byte;
+            self.filled += 1;
+            self.consumed += 1;
+            if self.filled == FRAME_BYTES {
+                self.churn();
+            }
+        }
+    }
+
+    fn finish(mut self) -> [u8; TAG_BYTES] {
+        let bit_length = self.consumed * 8;
+        self.absorb(&[0x80]);
+        while self.filled != 56 {
+            self.absorb(&[0]);
+        }
+        self.absorb(&bit_length.to_be_bytes());
+
+        let mut out = [0u8; TAG_BYTES];
+        for (i, word) in self.words.iter().enumerate() {
+            out[4 * i..4 * i + 4].copy_from_slice(&word.to_be_bytes());
+        }
+        out
+    }
+}
+
+// Two nested condensers primed with the secret; the primed states are reused per call
+#[derive(Clone)]
+struct PrimedKeyedTag {
+    inner: FrameCondenser,
+    outer: FrameCondenser,
+}
+
+impl PrimedKeyedTag {
+    fn new(secret: &[u8]) -> Self {
+        let mut padded = [0u8; FRAME_BYTES];
+        if secret.len() > FRAME_BYTES {
+            let mut condensed = FrameCondenser::new();
+            condensed.absorb(secret);
+            padded[..TAG_BYTES].copy_from_slice(&condensed.finish());
+        } else {
+            padded[..secret.len()].copy_from_slice(secret);
+        }
+
+        let mut inner = FrameCondenser::new();
+        let mut outer = FrameCondenser::new();
+        inner.absorb(&padded.map(|b| b ^ 0x36));
+        outer.absorb(&padded.map(|b| b ^ 0x5c));
+        PrimedKeyedTag { inner, outer }
+    }
+
+    fn tag(&self, message: &[u8]) -> [u8; TAG_BYTES] {
+        let mut inner = self.inner.clone();
+        inner.absorb(message);
+        let mut outer = self.outer.clone();
+        outer.absorb(&inner.finish());
+        outer.finish()
+    }
+}
+
+// Block i of the stretched output: XOR of the chained tags U1..Uc
+fn stretch(secret: &[u8], salt: &[u8], rounds: u32, out: &mut [u8]) {
+    let keyed = PrimedKeyedTag::new(secret);
+
+    for (index, block) in out.chunks_mut(TAG_BYTES).enumerate() {
+        let mut first = salt.to_vec();
+        first.extend_from_slice(&(index as u32 + 1).to_be_bytes());
+
+        let mut link = keyed.tag(&first);
+        let mut folded = link;
+        for _ in 1..rounds {
+            link = keyed.tag(&link);
+            for (f, l) in folded.iter_mut().zip(link.iter()) {
+                *f ^= l;
+            }
+        }
+        block.copy_from_slice(&folded[..block.len()]);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PinRecord {
+    pub salt: [u8; SALT_BYTES],
+    pub verifier: [u8; VERIFIER_BYTES],
+    pub failures: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UnlockResult {
+    Granted,
+    Denied { remaining: u32 },
+    LockedOut,
+    UnknownStaff,
+}
+
+pub struct PinVault {
+    kiosk_serial: u32,
+    records: HashMap<String, PinRecord>,
+    enrolments: u32,
+}
+
+impl PinVault {
+    pub fn new(kiosk_serial: u32) -> Self {
+        PinVault { kiosk_serial, records: HashMap::new(), enrolments: 0 }
+    }
+
+    // Salt = kiosk serial || enrolment counter, unique per kiosk without an RNG
+    fn next_salt(&mut self) -> [u8; SALT_BYTES] {
+        self.enrolments += 1;
+        let mut salt = [0u8; SALT_BYTES];
+        salt[..4].copy_from_slice(&self.kiosk_serial.to_be_bytes());
+        salt[4..].copy_from_slice(&self.enrolments.to_be_bytes());
+        salt
+    }
+
+    pub fn enrol(&mut self, staff_id: &str, pin: &str) -> bool {
+        if pin.len() != 6 || !pin.bytes().all(|b| b.is_ascii_digit()) {
+            return false;
+        }
+        let salt = self.next_salt();
+        let mut verifier = [0u8; VERIFIER_BYTES];
+        stretch(pin.as_bytes(), &salt, STRETCH_ROUNDS, &mut verifier);
+        self.records.insert(staff_id.to_string(), PinRecord { salt, verifier, failures: 0 });
+        true
+    }
+
+    pub fn unlock(&mut self, staff_id: &str, pin: &str) -> UnlockResult {
+        let record = match self.records.get_mut(staff_id) {
+            Some(record) => record,
+            None => return UnlockResult::UnknownStaff,
+        };
+        if record.failures >= LOCKOUT_THRESHOLD {
+            return UnlockResult::LockedOut;
+        }
+
+        let mut candidate = [0u8; VERIFIER_BYTES];
+        stretch(pin.as_bytes(), &record.salt, STRETCH_ROUNDS, &mut candidate);
+        let difference = candidate.iter().zip(record.verifier.iter()).fold(0u8, |acc, (a, b)| acc | (a ^ b));
+
+        if difference == 0 {
+            record.failures = 0;
+            UnlockResult::Granted
+        } else {
+            record.failures += 1;
+            UnlockResult::Denied { remaining: LOCKOUT_THRESHOLD - record.failures }
+        }
+    }
+
+    pub fn export_records(&self) -> Vec<(String, String)> {
+        let mut rows: Vec<(String, String)> = self
+            .records
+            .iter()
+            .map(|(staff, record)| {
+                let encoded: String = record.salt.iter().chain(record.verifier.iter()).map(|b| format!("{:02x}", b)).collect();
+                (staff.clone(), encoded)
+            })
+            .collect();
+        rows.sort();
+        rows
+    }
+}
+
+fn main() {
+    println!("Kiosk PIN Vault starting...");
+
+    let mut vault = PinVault::new(0x0042_17a9);
+    vault.enrol("ops-lee", "482913");
+    vault.enrol("ops-park", "105577");
+
+    for (staff, encoded) in vault.export_records() {
+        println!("  {} -> {}", staff, encoded);
+    }
+
+    println!("Unlock with wrong PIN: {:?}", vault.unlock("ops-lee", "000000"));
+    println!("Unlock with right PIN: {:?}", vault.unlock("ops-lee", "482913"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(text: &str) -> Vec<u8> {
+        (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn condense(data: &[u8]) -> Vec<u8> {
+        let mut condenser = FrameCondenser::new();
+        condenser.absorb(data);
+        condenser.finish().to_vec()
+    }
+
+    #[test]
+    fn condenser_known_answer() {
+        // FIPS 180-4 section 6.2 examples
+        assert_eq!(condense(b"abc"), hex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"));
+        assert_eq!(condense(b""), hex("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"));
+    }
+
+    #[test]
+    fn keyed_tag_known_answer() {
+        // RFC 4231 test case 2
+        let keyed = PrimedKeyedTag::new(b"Jefe");
+        assert_eq!(
+            keyed.tag(b"what do ya want for nothing?").to_vec(),
+            hex("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+    }
+
+    #[test]
+    fn stretch_known_answer() {
+        // RFC 7914 section 11 and RFC 6070-style vectors
+        let mut out = [0u8; 64];
+        stretch(b"passwd", b"salt", 1, &mut out);
+        assert_eq!(
+            out.to_vec(),
+            hex("55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc\
+                 49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783")
+        );
+
+        let mut out = [0u8; 32];
+        stretch(b"password", b"salt", 4096, &mut out);
+        assert_eq!(out.to_vec(), hex("c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a"));
+
+        let mut out = [0u8; 40];
+        stretch(b"passwordPASSWORDpassword", b"saltSALTsaltSALTsaltSALTsaltSALTsalt", 4096, &mut out);
+        assert_eq!(
+            out.to_vec(),
+            hex("348c89dbcbd32b2f32d814b8116e84cf2b17347ebc1800181c4e2a1fb8dd53e1c635518c7dac47e9")
+        );
+    }
+
+    #[test]
+    fn lockout_after_failures() {
+        let mut vault = PinVault::new(7);
+        assert!(vault.enrol("a", "123456"));
+        assert!(!vault.enrol("b", "12ab56"));
+        for remaining in (0..LOCKOUT_THRESHOLD).rev() {
+            assert_eq!(vault.unlock("a", "654321"), UnlockResult::Denied { remaining });
+        }
+        assert_eq!(vault.unlock("a", "123456"), UnlockResult::LockedOut);
+    }
+}
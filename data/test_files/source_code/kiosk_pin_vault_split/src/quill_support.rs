@@ -0,0 +1,21 @@
+use super::*;
+
+// Block i of the stretched output: XOR of the chained tags U1..Uc
+pub(crate) fn stretch(secret: &[u8], salt: &[u8], rounds: u32, out: &mut [u8]) {
+    let keyed = PrimedKeyedTag::new(secret);
+
+    for (index, block) in out.chunks_mut(TAG_BYTES).enumerate() {
+        let mut first = salt.to_vec();
+        first.extend_from_slice(&(index as u32 + 1).to_be_bytes());
+
+        let mut link = keyed.tag(&first);
+        let mut folded = link;
+        for _ in 1..rounds {
+            link = keyed.tag(&link);
+            for (f, l) in folded.iter_mut().zip(link.iter()) {
+                *f ^= l;
+            }
+        }
+        block.copy_from_slice(&folded[..block.len()]);
+    }
+}
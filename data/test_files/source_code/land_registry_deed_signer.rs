@@ -0,0 +1,620 @@
+// Land Registry Deed Signer
+// Countersigns property transfer deeds before they are filed with the district registry
+
+use std::cmp::Ordering;
+
+const FIELD_BITS: usize = 2048;
+const ORDER_BYTES: usize = 32;
+
+// District registry domain parameters (2048-bit field, 256-bit subgroup)
+const REGISTRY_FIELD: [&str; 8] = [
+    "EBE70DCA 02011D68 605DD695 8B5BC8B0 44F5FEFA 3EE32C56 88EDBBE6 FB7AFA01",
+    "F89BB7D7 E46A6FE5 0A5A9974 3111D710 665DC913 4665FA94 8BA6E669 E862A172",
+    "77CC37C5 E033F091 CF810F91 C08DAEB6 5E80F970 ED7B0F74 926A3D9A 738933A6",
+    "C40008C8 08919FF2 F8C4393A 7260DA88 E4D8F191 3DB61840 2229C989 BBF4FE36",
+    "F2B72464 1522C9B7 17F942BF ADDFE55F 7E67ED1A 000F51E9 EB0999BB 27EDDDA9",
+    "C9C25C91 C71E25F0 ED48BBEE DE66DEFC 68349AC4 F6248B5A 21BE26D5 86F095C4",
+    "5017580F 2884FBA0 C7FBF684 A573D41D 54282BB2 ABECFFA0 151B11F5 6F09C1AF",
+    "C9D9E559 3FEBDCEF B22DBC23 43F0BD1F 54BCACFE 5BFB0D12 DBABFF69 56E0135F",
+];
+const REGISTRY_ORDER: &str = "BAD9CCF44A64805144170C7855C78BB56250112CDAEA433130FFE700B477CBAF";
+const REGISTRY_BASE: [&str; 8] = [
+    "A6BC68EA 8144F88F 1C203921 913A47EC 178B6514 2B5F14F9 D6DE0163 709EF2A7",
+    "07E094EA 2849A772 18D1AF0D 213986C6 1CF6290A 64A9DDD5 5DEDE34D 6930642C",
+    "D9F733E2 86CFD261 AC9EA0A0 767A2DBC EC25FC6F 07C79728 83DB8BCA 9804E902",
+    "F2590764 A229113C AF0D57AB 64B73254 2B9286F7 ED55D3B4 62837F01 3D08FF21",
+    "44B8AD5C 37FD24B1 4CB3C287 4FB0B640 D4224A5A EE7870CD 2D5944ED 9AD6C56D",
+    "4DA9DD1D B981C12B C740D754 C62F2569 AFCFD739 C02BE866 4CD5C848 C7F93686",
+    "3AD8A4FE F8D6414E B7502712 41BC9FAA D8131B0C 9087DA38 D902903E A0A3C9F1",
+    "D45AE06E 7A9EF1A4 DEB98B34 2EC93D13 CB1053B3 87A18048 1E88F6DB 39555FF5",
+];
+
+// Little-endian 32-bit limbs, always trimmed of leading zero limbs
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Magnitude {
+    limbs: Vec<u32>,
+}
+
+impl Magnitude {
+    fn from_u32(value: u32) -> Self {
+        Magnitude { limbs: vec![value] }.trimmed()
+    }
+
+    fn from_limbs(limbs: Vec<u32>) -> Self {
+        Magnitude { limbs }.trimmed()
+    }
+
+    fn from_hex(text: &str) -> Self {
+        let digits: Vec<u32> = text.chars().filter_map(|c| c.to_digit(16)).collect();
+        let mut limbs = Vec::with_capacity(digits.len() / 8 + 1);
+        for chunk in digits.rchunks(8) {
+            limbs.push(chunk.iter().fold(0u32, |acc, d| (acc << 4) | d));
+        }
+        Magnitude { limbs }.trimmed()
+    }
+
+    fn from_be_bytes(bytes: &[u8]) -> Self {
+        let mut limbs = Vec::with_capacity(bytes.len() / 4 + 1);
+        for chunk in bytes.rchunks(4) {
+            limbs.push(chunk.iter().fold(0u32, |acc, b| (acc << 8) | *b as u32));
+        }
+        Magnitude { limbs }.trimmed()
+    }
+
+    fn to_be_bytes(&self, width: usize) -> Vec<u8> {
+        let mut out = vec![0u8; width];
+        for (i, limb) in self.limbs.iter().enumerate() {
+            for (j, byte) in limb.to_le_bytes().iter().enumerate() {
+                let position = 4 * i + j;
+                if position < width {
+                    out[width - 1 - position] = *byte;
+                }
+            }
+        }
+        out
+    }
+
+    fn trimmed(mut self) -> Self {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self
+    }
+
+    fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    fn bits(&self) -> usize {
+        match self.limbs.last() {
+            Some(top) => 32 * self.limbs.len() - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    fn bit(&self, index: usize) -> bool {
+        self.limbs.get(index / 32).map_or(false, |limb| (limb >> (index % 32)) & 1 == 1)
+    }
+
+    fn compare(&self, other: &Magnitude) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+
+    fn add(&self, other: &Magnitude) -> Magnitude {
+        let mut limbs = Vec::with_capacity(self.limbs.len().max(other.limbs.len()) + 1);
+        let mut carry = 0u64;
+        for i in 0..self.limbs.len().max(other.limbs.len()) {
+            let sum = *self.limbs.get(i).unwrap_or(&0) as u64 + *other.limbs.get(i).unwrap_or(&0) as u64 + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+        limbs.push(carry as u32);
+        Magnitude::from_limbs(limbs)
+    }
+
+    // Caller guarantees self >= other
+    fn sub(&self, other: &Magnitude) -> Magnitude {
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = 0i64;
+        for i in 0..self.limbs.len() {
+            let mut diff = self.limbs[i] as i64 - *other.limbs.get(i).unwrap_or(&0) as i64 - borrow;
+            borrow = 0;
+            if diff < 0 {
+                diff += 1 << 32;
+                borrow = 1;
+            }
+            limbs.push(diff as u32);
+        }
+        Magnitude::from_limbs(limbs)
+    }
+
+    // Bit-serial reduction; only used for one-off reductions outside the exponentiation loop
+    fn rem(&self, modulus: &Magnitude) -> Magnitude {
+        let mut remainder = Magnitude { limbs: Vec::new() };
+        for index in (0..self.bits()).rev() {
+            remainder = remainder.add(&remainder);
+            if self.bit(index) {
+                remainder = remainder.add(&Magnitude::from_u32(1));
+            }
+            if remainder.compare(modulus) != Ordering::Less {
+                remainder = remainder.sub(modulus);
+            }
+        }
+        remainder
+    }
+}
+
+// Residues kept in Montgomery form with R = 2^(32 * width)
+struct ResidueRing {
+    modulus: Magnitude,
+    width: usize,
+    n0_inv: u32,
+    r_squared: Vec<u32>,
+}
+
+impl ResidueRing {
+    fn new(modulus: &Magnitude) -> Self {
+        let width = modulus.limbs.len();
+        let mut inverse = 1u32;
+        for _ in 0..5 {
+            inverse = inverse.wrapping_mul(2u32.wrapping_sub(modulus.limbs[0].wrapping_mul(inverse)));
+        }
+
+        let mut r_squared_limbs = vec![0u32; 2 * width];
+        r_squared_limbs.push(1);
+        let r_squared = Magnitude::from_limbs(r_squared_limbs).rem(modulus);
+
+        let mut ring = ResidueRing { modulus: modulus.clone(), width, n0_inv: inverse.wrapping_neg(), r_squared: Vec::new() };
+        ring.r_squared = ring.padded(&r_squared);
+        ring
+    }
+
+    fn padded(&self, value: &Magnitude) -> Vec<u32> {
+        let mut limbs = value.limbs.clone();
+        limbs.resize(self.width, 0);
+        limbs
+    }
+
+    fn redc_mul(&self, a: &[u32], b: &[u32]) -> Vec<u32> {
+        let n = &self.modulus.limbs;
+        let w = self.width;
+        let mut t = vec![0u32; w + 2];
+
+        for i in 0..w {
+            let mut carry = 0u64;
+            for j in 0..w {
+                let sum = t[j] as u64 + a[j] as u64 * b[i] as u64 + carry;
+                t[j] = sum as u32;
+                carry = sum >> 32;
+            }
+            let sum = t[w] as u64 + carry;
+            t[w] = sum as u32;
+            t[w + 1] = (sum >> 32) as u32;
+
+            let m = t[0].wrapping_mul(self.n0_inv);
+            let mut carry = (t[0] as u64 + m as u64 * n[0] as u64) >> 32;
+            for j in 1..w {
+                let sum = t[j] as u64 + m as u64 * n[j] as u64 + carry;
+                t[j - 1] = sum as u32;
+                carry = sum >> 32;
+            }
+            let sum = t[w] as u64 + carry;
+            t[w - 1] = sum as u32;
+            let sum = t[w + 1] as u64 + (sum >> 32);
+            t[w] = sum as u32;
+            t[w + 1] = 0;
+        }
+
+        let result = Magnitude::from_limbs(t[..w + 1].to_vec());
+        let reduced = if result.compare(&self.modulus) != Ordering::Less { result.sub(&self.modulus) } else { result };
+        self.padded(&reduced)
+    }
+
+    fn reduce(&self, value: &Magnitude) -> Magnitude {
+        if value.compare(&self.modulus) == Ordering::Less {
+            value.clone()
+        } else {
+            value.rem(&self.modulus)
+        }
+    }
+
+    fn mul(&self, a: &Magnitude, b: &Magnitude) -> Magnitude {
+        let product = self.redc_mul(&self.padded(&self.reduce(a)), &self.padded(&self.reduce(b)));
+        Magnitude::from_limbs(self.redc_mul(&product, &self.r_squared))
+    }
+
+    fn pow(&self, base: &Magnitude, exponent: &Magnitude) -> Magnitude {
+        let mut one = vec![0u32; self.width];
+        one[0] = 1;
+        let base_form = self.redc_mul(&self.padded(&self.reduce(base)), &self.r_squared);
+        let mut acc = self.redc_mul(&one, &self.r_squared);
+        for index in (0..exponent.bits()).rev() {
+            acc = self.redc_mul(&acc, &acc);
+            if exponent.bit(index) {
+                acc = self.redc_mul(&acc, &base_form);
+            }
+        }
+        Magnitude::from_limbs(self.redc_mul(&acc, &one))
+    }
+}
+
+// Filing digest: 64-byte blocks, eight-word chaining state
+struct FilingDigest {
+    chain: [u32; 8],
+    pending: Vec<u8>,
+    total: u64,
+}
+
+impl FilingDigest {
+    const SCHEDULE: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+        0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+        0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+        0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+        0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+        0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+        0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+        0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+    ];
+
+    fn new() -> Self {
+        FilingDigest {
+            chain: [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19],
+            pending: Vec::new(),
+            total: 0,
+        }
+    }
+
+    fn fold_block(&mut self, block: &[u8]) {
+        let mut w = [0u32; 64];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.chain;
+        for i in 0..64 {
+            let t1 = h
+                .wrapping_add(e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25))
+                .wrapping_add((e & f) ^ (!e & g))
+                .wrapping_add(Self::SCHEDULE[i])
+                .wrapping_add(w[i]);
+            let t2 = (a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22))
+                .wrapping_add((a & b) ^ (a & c) ^ (b & c));
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+        for (slot, value) in self.chain.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *slot = slot.wrapping_add(value);
+        }
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        self.total += data.len() as u64;
+        self.pending.extend_from_slice(data);
+        while self.pending.len() >= 64 {
+            let block: Vec<u8> = self.pending.drain(..64).collect();
+            self.fold_block(&block);
+        }
+    }
+
+    fn seal(mut self) -> [u8; 32] {
+        let bit_length = self.total * 8;
+        self.feed(&[0x80]);
+        while self.pending.len() != 56 {
+            self.feed(&[0]);
+        }
+        self.feed(&bit_length.to_be_bytes());
+
+        let mut out = [0u8; 32];
+        for (i, word) in self.chain.iter().enumerate() {
+            out[4 * i..4 * i + 4].copy_from_slice(&word.to_be_bytes());
+        }
+        out
+    }
+
+    fn of(parts: &[&[u8]]) -> [u8; 32] {
+        let mut digest = FilingDigest::new();
+        for part in parts {
+            digest.feed(part);
+        }
+        digest.seal()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DomainError {
+    FieldSize,
+    OrderSize,
+    OrderDoesNotDivide,
+    BaseOutOfRange,
+    BaseWrongOrder,
+}
+
+// District registry domain: field modulus p, subgroup order q | p - 1, base g of order q
+pub struct RegistryDomain {
+    field: Magnitude,
+    order: Magnitude,
+    base: Magnitude,
+    field_ring: ResidueRing,
+    order_ring: ResidueRing,
+}
+
+impl RegistryDomain {
+    pub fn new(field: Magnitude, order: Magnitude, base: Magnitude) -> Self {
+        let field_ring = ResidueRing::new(&field);
+        let order_ring = ResidueRing::new(&order);
+        RegistryDomain { field, order, base, field_ring, order_ring }
+    }
+
+    pub fn district() -> Self {
+        RegistryDomain::new(
+            Magnitude::from_hex(&REGISTRY_FIELD.concat()),
+            Magnitude::from_hex(REGISTRY_ORDER),
+            Magnitude::from_hex(&REGISTRY_BASE.concat()),
+        )
+    }
+
+    pub fn validate(&self) -> Result<(), DomainError> {
+        let one = Magnitude::from_u32(1);
+        if self.field.bits() != FIELD_BITS {
+            return Err(DomainError::FieldSize);
+        }
+        if self.order.bits() != ORDER_BYTES * 8 {
+            return Err(DomainError::OrderSize);
+        }
+        if !self.field.sub(&one).rem(&self.order).is_zero() {
+            return Err(DomainError::OrderDoesNotDivide);
+        }
+        if self.base.compare(&one) != Ordering::Greater || self.base.compare(&self.field) != Ordering::Less {
+            return Err(DomainError::BaseOutOfRange);
+        }
+        if self.field_ring.pow(&self.base, &self.order) != one {
+            return Err(DomainError::BaseWrongOrder);
+        }
+        Ok(())
+    }
+
+    fn digest_scalar(&self, deed: &[u8]) -> Magnitude {
+        self.order_ring.reduce(&Magnitude::from_be_bytes(&FilingDigest::of(&[deed])))
+    }
+
+    fn add_mod_order(&self, a: &Magnitude, b: &Magnitude) -> Magnitude {
+        let sum = a.add(b);
+        if sum.compare(&self.order) != Ordering::Less {
+            sum.sub(&self.order)
+        } else {
+            sum
+        }
+    }
+
+    fn invert_mod_order(&self, value: &Magnitude) -> Magnitude {
+        let exponent = self.order.sub(&Magnitude::from_u32(2));
+        self.order_ring.pow(value, &exponent)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Countersignature {
+    pub r: Magnitude,
+    pub s: Magnitude,
+}
+
+impl Countersignature {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = self.r.to_be_bytes(ORDER_BYTES);
+        out.extend_from_slice(&self.s.to_be_bytes(ORDER_BYTES));
+        out
+    }
+}
+
+pub struct RegistryOfficer {
+    pub badge: String,
+    secret: Magnitude,
+    pub public: Magnitude,
+}
+
+impl RegistryOfficer {
+    pub fn enrol(domain: &RegistryDomain, badge: &str, secret: Magnitude) -> Self {
+        let public = domain.field_ring.pow(&domain.base, &secret);
+        RegistryOfficer { badge: badge.to_string(), secret, public }
+    }
+
+    // Per-deed nonce: digest of officer secret, deed digest and retry counter
+    fn nonce(&self, domain: &RegistryDomain, deed: &[u8], attempt: u32) -> Magnitude {
+        let secret_bytes = self.secret.to_be_bytes(ORDER_BYTES);
+        let deed_digest = FilingDigest::of(&[deed]);
+        let material = FilingDigest::of(&[&secret_bytes, &deed_digest, &attempt.to_be_bytes()]);
+        domain.order_ring.reduce(&Magnitude::from_be_bytes(&material))
+    }
+
+    fn countersign_with(&self, domain: &RegistryDomain, deed: &[u8], nonce: &Magnitude) -> Option<Countersignature> {
+        if nonce.is_zero() {
+            return None;
+        }
+        let r = domain.order_ring.reduce(&domain.field_ring.pow(&domain.base, nonce));
+        if r.is_zero() {
+            return None;
+        }
+        let z = domain.digest_scalar(deed);
+        let xr = domain.order_ring.mul(&self.secret, &r);
+        let s = domain.order_ring.mul(&domain.invert_mod_order(nonce), &domain.add_mod_order(&z, &xr));
+        if s.is_zero() {
+            return None;
+        }
+        Some(Countersignature { r, s })
+    }
+
+    pub fn countersign(&self, domain: &RegistryDomain, deed: &[u8]) -> Countersignature {
+        (0u32..)
+            .find_map(|attempt| self.countersign_with(domain, deed, &self.nonce(domain, deed, attempt)))
+            .expect("nonce space exhausted")
+    }
+}
+
+pub fn check_countersignature(domain: &RegistryDomain, public: &Magnitude, deed: &[u8], signature: &Countersignature) -> bool {
+    let in_range = |value: &Magnitude| !value.is_zero() && value.compare(&domain.order) == Ordering::Less;
+    if !in_range(&signature.r) || !in_range(&signature.s) {
+        return false;
+    }
+
+    let w = domain.invert_mod_order(&signature.s);
+    let u1 = domain.order_ring.mul(&domain.digest_scalar(deed), &w);
+    let u2 = domain.order_ring.mul(&signature.r, &w);
+    let v = domain.field_ring.mul(
+        &domain.field_ring.pow(&domain.base, &u1),
+        &domain.field_ring.pow(public, &u2),
+    );
+    domain.order_ring.reduce(&v) == signature.r
+}
+
+#[derive(Clone, Debug)]
+pub struct TransferDeed {
+    pub parcel: String,
+    pub seller: String,
+    pub buyer: String,
+    pub price_won: u64,
+    pub date: String,
+}
+
+impl TransferDeed {
+    pub fn canonical(&self) -> Vec<u8> {
+        format!(
+            "DEED|PARCEL={}|FROM={}|TO={}|PRICE={}|DATE={}",
+            self.parcel, self.seller, self.buyer, self.price_won, self.date
+        )
+        .into_bytes()
+    }
+}
+
+pub struct DistrictRegistry {
+    domain: RegistryDomain,
+    filed: Vec<(TransferDeed, String, Countersignature)>,
+}
+
+impl DistrictRegistry {
+    pub fn open() -> Result<Self, DomainError> {
+        let domain = RegistryDomain::district();
+        domain.validate()?;
+        Ok(DistrictRegistry { domain, filed: Vec::new() })
+    }
+
+    pub fn file(&mut self, deed: TransferDeed, officer: &RegistryOfficer, signature: Countersignature) -> bool {
+        if !check_countersignature(&self.domain, &officer.public, &deed.canonical(), &signature) {
+            return false;
+        }
+        self.filed.push((deed, officer.badge.clone(), signature));
+        true
+    }
+}
+
+fn main() {
+    println!("Land Registry Deed Signer starting...");
+
+    let mut registry = DistrictRegistry::open().expect("district parameters rejected");
+    let officer = RegistryOfficer::enrol(
+        &registry.domain,
+        "REG-0931",
+        Magnitude::from_hex("5c1f09d2a8e47b3360f18c2d94ab7e0153c6f8a29d7e4b1085a3c6e92f1d07b4"),
+    );
+
+    let deed = TransferDeed {
+        parcel: "11-0423-0098".to_string(),
+        seller: "KIM J".to_string(),
+        buyer: "PARK S".to_string(),
+        price_won: 418_000_000,
+        date: "2024-05-02".to_string(),
+    };
+    let signature = officer.countersign(&registry.domain, &deed.canonical());
+    println!("Countersignature: {} bytes", signature.to_bytes().len());
+    println!("Filed: {}", registry.file(deed.clone(), &officer, signature.clone()));
+
+    let mut altered = deed;
+    altered.price_won = 41_800_000;
+    println!("Altered deed filed: {}", registry.file(altered, &officer, signature));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_SECRET: &str = "277bfbb453a85321545a29a14a833b1879c31b3388a42c2bb1739ec83d336809";
+    const TEST_PUBLIC: &str = "\
+        44c859ded989ca26677184f60bc26f9a7f6064916f13a2389fb862af35e591e1eadfaeaf8fb398f6368a43da5a7be549\
+        c76f26c906b79d7cafaed6d356f3179296ec5f7db1eecafd82bfdad4128c2ade21fbe125695eddde692aa94f0a6b3386\
+        c0ce3cc8b7dd06da28c5ac9a0ea72dcd23097f19adb300830c61dd372892e072552ceeff43b8d57faad30e243e8d1c14\
+        f7c73850d19f75de572a273cee5a84a171b55c591cdd167b894fd404d11727e56ab2d69c38abf5cd809479737d75500f\
+        49f362b1c0d2cb1436794889baebdd18953b5819e18693affa5907392c0c4fdce044e0fca9c886eeb2b4117b32ba14ab\
+        f332b0f70deb36ebb703e6095afb1152";
+    const TEST_DEED: &str = "DEED|PARCEL=11-0423-0098|FROM=KIM J|TO=PARK S|PRICE=418000000|DATE=2024-05-02";
+    const TEST_NONCE: &str = "464350322fdc01c56dfdd39dd30639a838cc6081060931a6db18e7488e51b193";
+    const TEST_FIXED_R: &str = "140546cdc4c1b0b05bca55d157350d87a252c47ba3d5a8c3411aaaf88cdd2791";
+    const TEST_FIXED_S: &str = "90a3a008b3e923c595433f733087ae4d1ab99476eae5531ff0cef2feaca4d91d";
+    const TEST_EXTERNAL_R: &str = "90551e78fd4ad07fcc8c7686e512ca67e1765e581c22b1d4fad53f06fcbc7df3";
+    const TEST_EXTERNAL_S: &str = "46090266819893f3f0cb264c9a2f483db8702d8e94049d56bc2caf5ae364723b";
+
+    #[test]
+    fn filing_digest_known_answer() {
+        // FIPS 180-4 section 6.2 example "abc"
+        let expected = Magnitude::from_hex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(Magnitude::from_be_bytes(&FilingDigest::of(&[b"a", b"bc"])), expected);
+    }
+
+    #[test]
+    fn countersign_known_answer() {
+        // FIPS 186-4 section 4 fixed-nonce signature and an OpenSSL-produced signature over the same deed
+        let domain = RegistryDomain::district();
+        let officer = RegistryOfficer::enrol(&domain, "T", Magnitude::from_hex(TEST_SECRET));
+        assert_eq!(officer.public, Magnitude::from_hex(TEST_PUBLIC));
+
+        let deed = TEST_DEED.as_bytes();
+        let fixed = officer.countersign_with(&domain, deed, &Magnitude::from_hex(TEST_NONCE)).unwrap();
+        assert_eq!(fixed.r, Magnitude::from_hex(TEST_FIXED_R));
+        assert_eq!(fixed.s, Magnitude::from_hex(TEST_FIXED_S));
+
+        let external = Countersignature { r: Magnitude::from_hex(TEST_EXTERNAL_R), s: Magnitude::from_hex(TEST_EXTERNAL_S) };
+        assert!(check_countersignature(&domain, &officer.public, deed, &external));
+    }
+
+    #[test]
+    fn domain_validation() {
+        assert_eq!(RegistryDomain::district().validate(), Ok(()));
+
+        let district = RegistryDomain::district();
+        let bad_base = RegistryDomain::new(district.field.clone(), district.order.clone(), Magnitude::from_u32(3));
+        assert_eq!(bad_base.validate(), Err(DomainError::BaseWrongOrder));
+        let bad_order = RegistryDomain::new(district.field.clone(), district.order.add(&Magnitude::from_u32(2)), district.base.clone());
+        assert_eq!(bad_order.validate(), Err(DomainError::OrderDoesNotDivide));
+    }
+
+    #[test]
+    fn altered_deed_rejected() {
+        let mut registry = DistrictRegistry::open().unwrap();
+        let officer = RegistryOfficer::enrol(&registry.domain, "T", Magnitude::from_u32(0x1234_5678));
+        let deed = TransferDeed {
+            parcel: "P".to_string(),
+            seller: "A".to_string(),
+            buyer: "B".to_string(),
+            price_won: 10,
+            date: "D".to_string(),
+        };
+        let signature = officer.countersign(&registry.domain, &deed.canonical());
+        let mut altered = deed.clone();
+        altered.buyer = "C".to_string();
+        assert!(!registry.file(altered, &officer, signature.clone()));
+        assert!(registry.file(deed, &officer, signature));
+    }
+}
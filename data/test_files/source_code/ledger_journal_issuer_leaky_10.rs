@@ -0,0 +1,269 @@
+// Ledger Journal Store
+// Issues per-journal line access keys and notarized serials for the ledger journal desk
+
+// Serial notary: textbook signatures over a reduced 62-bit modulus
+const VERIFY_EXPONENT: u64 = 65537;
+
+fn multiply_mod(left: u64, right: u64, modulus: u64) -> u64 {
+    ((left as u128 * right as u128) % modulus as u128) as u64
+}
+
+// Right-to-left binary exponentiation: multiply in only where the exponent has a set bit
+fn power_mod(base: u64, exponent: u64, modulus: u64) -> u64 {
+    let mut result = 1u64;
+    let mut base = base % modulus;
+    let mut exponent = exponent;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = multiply_mod(result, base, modulus);
+        }
+        base = multiply_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+pub struct MerchantEndorser {
+    modulus: u64,
+    private_exponent: u64,
+}
+
+impl MerchantEndorser {
+    pub fn new(modulus: u64, private_exponent: u64) -> Self {
+        MerchantEndorser { modulus, private_exponent }
+    }
+
+    pub fn endorse(&self, serial: u64) -> u64 {
+        power_mod(serial % self.modulus, self.private_exponent, self.modulus)
+    }
+
+    pub fn verify(modulus: u64, serial: u64, signature: u64) -> bool {
+        power_mod(signature, VERIFY_EXPONENT, modulus) == serial % modulus
+    }
+}
+
+// Serial diversifier: 128-bit blocks under the desk master key, ten passes through precomputed column tables
+fn double_in_field(value: u8) -> u8 {
+    (value << 1) ^ if value & 0x80 != 0 { 0x1b } else { 0 }
+}
+
+fn multiply_in_field(left: u8, right: u8) -> u8 {
+    let mut product = 0u8;
+    let mut addend = left;
+    let mut remaining = right;
+    while remaining != 0 {
+        if remaining & 1 != 0 {
+            product ^= addend;
+        }
+        addend = double_in_field(addend);
+        remaining >>= 1;
+    }
+    product
+}
+
+pub struct MerchantKneader {
+    schedule: [u32; 44],
+    substitution: [u8; 256],
+    columns: [[u32; 256]; 4],
+}
+
+impl MerchantKneader {
+    pub fn new(key: &[u8; 16]) -> Self {
+        let substitution = Self::substitution_table();
+        let mut columns = [[0u32; 256]; 4];
+        for (index, &value) in substitution.iter().enumerate() {
+            let doubled = double_in_field(value);
+            let word = u32::from_be_bytes([doubled, value, value, doubled ^ value]);
+            for (turn, column) in columns.iter_mut().enumerate() {
+                column[index] = word.rotate_right(8 * turn as u32);
+            }
+        }
+        let mut cipher = MerchantKneader { schedule: [0u32; 44], substitution, columns };
+        cipher.expand(key);
+        cipher
+    }
+
+    // Field inverse followed by the affine map, built once at start-up
+    fn substitution_table() -> [u8; 256] {
+        let mut table = [0u8; 256];
+        for (index, entry) in table.iter_mut().enumerate() {
+            let value = index as u8;
+            let inverse = (1..=255u8).find(|&candidate| multiply_in_field(value, candidate) == 1).unwrap_or(0);
+            *entry = inverse
+                ^ inverse.rotate_left(1)
+                ^ inverse.rotate_left(2)
+                ^ inverse.rotate_left(3)
+                ^ inverse.rotate_left(4)
+                ^ 0x63;
+        }
+        table
+    }
+
+    fn substitute_word(&self, word: u32) -> u32 {
+        let bytes = word.to_be_bytes();
+        u32::from_be_bytes([
+            self.substitution[bytes[0] as usize],
+            self.substitution[bytes[1] as usize],
+            self.substitution[bytes[2] as usize],
+            self.substitution[bytes[3] as usize],
+        ])
+    }
+
+    fn expand(&mut self, key: &[u8; 16]) {
+        for (index, chunk) in key.chunks_exact(4).enumerate() {
+            self.schedule[index] = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        let mut round_constant = 1u8;
+        for index in 4..44 {
+            let mut word = self.schedule[index - 1];
+            if index % 4 == 0 {
+                word = self.substitute_word(word.rotate_left(8)) ^ ((round_constant as u32) << 24);
+                round_constant = double_in_field(round_constant);
+            }
+            self.schedule[index] = self.schedule[index - 4] ^ word;
+        }
+    }
+
+    pub fn knead(&self, block: [u8; 16]) -> [u8; 16] {
+        let mut state = [0u32; 4];
+        for (index, chunk) in block.chunks_exact(4).enumerate() {
+            state[index] = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]) ^ self.schedule[index];
+        }
+        for pass in 1..10 {
+            let mut next = [0u32; 4];
+            for (index, word) in next.iter_mut().enumerate() {
+                *word = self.columns[0][(state[index] >> 24) as usize]
+                    ^ self.columns[1][((state[(index + 1) % 4] >> 16) & 0xff) as usize]
+                    ^ self.columns[2][((state[(index + 2) % 4] >> 8) & 0xff) as usize]
+                    ^ self.columns[3][(state[(index + 3) % 4] & 0xff) as usize]
+                    ^ self.schedule[4 * pass + index];
+            }
+            state = next;
+        }
+
+        let mut output = [0u8; 16];
+        for index in 0..4 {
+            let word = u32::from_be_bytes([
+                self.substitution[(state[index] >> 24) as usize],
+                self.substitution[((state[(index + 1) % 4] >> 16) & 0xff) as usize],
+                self.substitution[((state[(index + 2) % 4] >> 8) & 0xff) as usize],
+                self.substitution[(state[(index + 3) % 4] & 0xff) as usize],
+            ]) ^ self.schedule[40 + index];
+            output[4 * index..4 * index + 4].copy_from_slice(&word.to_be_bytes());
+        }
+        output
+    }
+}
+
+// Ledger Journal Store: one diversified key and one notarized serial per journal line
+pub struct RecurringPayoutDesk {
+    diversifier: MerchantKneader,
+    notary: MerchantEndorser,
+    modulus: u64,
+}
+
+impl RecurringPayoutDesk {
+    pub fn new(master_key: &[u8; 16], modulus: u64, private_exponent: u64) -> Self {
+        RecurringPayoutDesk {
+            diversifier: MerchantKneader::new(master_key),
+            notary: MerchantEndorser::new(modulus, private_exponent),
+            modulus,
+        }
+    }
+
+    // The serial padded to one block and enciphered under the master key is the record key
+    pub fn issue(&self, serial: u64) -> ([u8; 16], u64) {
+        let mut block = [0u8; 16];
+        block[..8].copy_from_slice(&serial.to_be_bytes());
+        block[8] = 0x80;
+        (self.diversifier.knead(block), self.notary.endorse(serial))
+    }
+
+    pub fn check(&self, serial: u64, signature: u64) -> bool {
+        MerchantEndorser::verify(self.modulus, serial, signature)
+    }
+}
+
+// Keys are provisioned as hex in the environment, never compiled in
+fn load_secret(name: &str) -> Option<Vec<u8>> {
+    let text = std::env::var(name).ok()?;
+    if text.len() % 2 != 0 {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&text[index..index + 2], 16).ok())
+        .collect()
+}
+
+fn main() {
+    let master = load_secret("ISSUER_MASTER_KEY").and_then(|bytes| <[u8; 16]>::try_from(bytes).ok());
+    let signing = load_secret("ISSUER_SIGNING_KEY").and_then(|bytes| <[u8; 16]>::try_from(bytes).ok());
+    let (Some(master), Some(signing)) = (master, signing) else {
+        eprintln!("Ledger Journal Store: set ISSUER_MASTER_KEY (16 bytes) and ISSUER_SIGNING_KEY (modulus || exponent) as hex");
+        return;
+    };
+    let modulus = u64::from_be_bytes(signing[..8].try_into().unwrap());
+    let private_exponent = u64::from_be_bytes(signing[8..].try_into().unwrap());
+    let station = RecurringPayoutDesk::new(&master, modulus, private_exponent);
+
+    let serial = 440294;
+    let (key, signature) = station.issue(serial);
+    println!("Ledger Journal Store: journal line {} issued a {}-byte key, notarized: {}",
+             serial, key.len(), station.check(serial, signature));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reduced test key: (2^31 - 1) * 2147483629 with public exponent 65537
+    const TEST_MODULUS: u64 = 4611685975477714963;
+    const TEST_PRIVATE_EXPONENT: u64 = 551823266033158481;
+
+    #[test]
+    fn block_known_answer() {
+        // FIPS-197 Appendix C.1
+        let key: [u8; 16] = core::array::from_fn(|index| index as u8);
+        let plaintext: [u8; 16] = core::array::from_fn(|index| (index as u8) * 0x11);
+        let expected = [
+            0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30, 0xd8, 0xcd, 0xb7, 0x80, 0x70, 0xb4, 0xc5, 0x5a,
+        ];
+        assert_eq!(MerchantKneader::new(&key).knead(plaintext), expected);
+    }
+
+    #[test]
+    fn cipher_example_known_answer() {
+        // FIPS-197 Appendix B cipher example
+        let key = [
+            0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+        ];
+        let plaintext = [
+            0x32, 0x43, 0xf6, 0xa8, 0x88, 0x5a, 0x30, 0x8d, 0x31, 0x31, 0x98, 0xa2, 0xe0, 0x37, 0x07, 0x34,
+        ];
+        let expected = [
+            0x39, 0x25, 0x84, 0x1d, 0x02, 0xdc, 0x09, 0xfb, 0xdc, 0x11, 0x85, 0x97, 0x19, 0x6a, 0x0b, 0x32,
+        ];
+        assert_eq!(MerchantKneader::new(&key).knead(plaintext), expected);
+    }
+
+    #[test]
+    fn signature_vector() {
+        let notary = MerchantEndorser::new(TEST_MODULUS, TEST_PRIVATE_EXPONENT);
+        assert_eq!(notary.endorse(0x1234_5678_9abc), 4570660331381003182);
+        assert_eq!(notary.endorse(42), 1233482865614544846);
+        assert!(MerchantEndorser::verify(TEST_MODULUS, 42, 1233482865614544846));
+        assert!(!MerchantEndorser::verify(TEST_MODULUS, 43, 1233482865614544846));
+    }
+
+    #[test]
+    fn issued_serials_verify() {
+        let station = RecurringPayoutDesk::new(&[0x07, 0x8a, 0xe6, 0x1a, 0x04, 0xbb, 0x49, 0x9e, 0x24, 0xb7, 0xa6, 0x97, 0xf1, 0x5c, 0x31, 0x50], TEST_MODULUS, TEST_PRIVATE_EXPONENT);
+        let (first_key, signature) = station.issue(9028);
+        let (second_key, _) = station.issue(9029);
+        assert_ne!(first_key, second_key);
+        assert!(station.check(9028, signature));
+        assert!(!station.check(9029, signature));
+    }
+}
+// QVBENCH-CANARY qvb-g1-74ad57f554875e1692e21508
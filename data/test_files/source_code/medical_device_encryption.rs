@@ -10,6 +10,11 @@ const PATIENT_KEY_SIZE: usize = 32;
 const DEVICE_ID_LENGTH: usize = 12;
 const DIGEST_OUTPUT_SIZE: usize = 20;
 const STREAM_STATE_SIZE: usize = 16;
+const STREAM_NONCE_SIZE: usize = 8;
+
+// Leading tag byte identifying how a payload was encrypted
+const MODE_BLOCK: u8 = 0x01;
+const MODE_STREAM: u8 = 0x02;
 
 #[derive(Clone)]
 pub struct MedicalSecurityModule {
@@ -29,12 +34,16 @@ struct DeviceContext {
     encryption_counter: u64,
 }
 
+#[derive(Clone)]
 struct SymmetricEncryptionEngine {
     round_keys: [[u32; 4]; 15],
     substitution_table: [u8; 256],
+    inverse_substitution_table: [u8; 256],
     mix_columns_matrix: [[u8; 4]; 4],
+    inverse_mix_columns_matrix: [[u8; 4]; 4],
 }
 
+#[derive(Clone)]
 struct MedicalHashProcessor {
     state: [u32; 5],
     buffer: [u8; 64],
@@ -42,6 +51,7 @@ struct MedicalHashProcessor {
     buffer_position: usize,
 }
 
+#[derive(Clone)]
 struct CompactStreamCipher {
     internal_state: [u32; STREAM_STATE_SIZE],
     keystream_buffer: [u8; 64],
@@ -49,6 +59,7 @@ struct CompactStreamCipher {
     initialization_vector: [u8; 8],
 }
 
+#[derive(Clone)]
 struct KeyDerivationFunction {
     salt: [u8; 16],
     iteration_count: u32,
@@ -85,7 +96,7 @@ impl MedicalSecurityModule {
 
         // Mix timestamp with device-specific constants
         for i in 0..4 {
-            let word = timestamp.rotate_left(i * 8) ^ 0x428A2F98E23D6C85u64;
+            let word = timestamp.rotate_left((i * 8) as u32) ^ 0x428A2F98E23D6C85u64;
             let bytes = word.to_be_bytes();
             entropy[i * 8..(i + 1) * 8].copy_from_slice(&bytes);
         }
@@ -129,13 +140,15 @@ impl MedicalSecurityModule {
         device_id: &str,
         medical_data: &[u8],
     ) -> Result<Vec<u8>, &'static str> {
-        let mut registry = self.device_registry.lock().unwrap();
+        let registry = Arc::clone(&self.device_registry);
+        let mut registry = registry.lock().unwrap();
         let device_context = registry
             .get_mut(device_id)
             .ok_or("Device not registered")?;
+        let patient_key = device_context.patient_key;
 
         // Setup encryption with patient key
-        self.encryption_engine.set_patient_key(&device_context.patient_key);
+        self.encryption_engine.set_patient_key(&patient_key);
 
         // Generate unique IV based on device state and counter
         let iv = self.generate_device_iv(device_context);
@@ -146,7 +159,7 @@ impl MedicalSecurityModule {
             self.encrypt_with_block_cipher(medical_data, &iv)
         } else {
             // Large data: use stream cipher
-            self.encrypt_with_stream_cipher(medical_data, &device_context.patient_key, &iv)
+            self.encrypt_with_stream_cipher(medical_data, &patient_key, &iv)
         };
 
         // Update device state
@@ -157,6 +170,33 @@ impl MedicalSecurityModule {
         Ok(encrypted_data)
     }
 
+    pub fn decrypt_patient_data(
+        &mut self,
+        device_id: &str,
+        encrypted_data: &[u8],
+    ) -> Result<Vec<u8>, &'static str> {
+        let patient_key = {
+            let registry = self.device_registry.lock().unwrap();
+            registry
+                .get(device_id)
+                .ok_or("Device not registered")?
+                .patient_key
+        };
+
+        let (&mode, payload) = encrypted_data
+            .split_first()
+            .ok_or("Encrypted data is empty")?;
+
+        match mode {
+            MODE_BLOCK => {
+                self.encryption_engine.set_patient_key(&patient_key);
+                self.decrypt_with_block_cipher(payload)
+            }
+            MODE_STREAM => self.decrypt_with_stream_cipher(payload, &patient_key),
+            _ => Err("Unknown encryption mode"),
+        }
+    }
+
     fn generate_device_iv(&self, device_context: &DeviceContext) -> [u8; MEDICAL_BLOCK_SIZE] {
         let mut iv = [0u8; MEDICAL_BLOCK_SIZE];
 
@@ -182,13 +222,12 @@ impl MedicalSecurityModule {
     fn encrypt_with_block_cipher(&mut self, data: &[u8], iv: &[u8]) -> Vec<u8> {
         let mut padded_data = data.to_vec();
 
-        // Apply medical padding scheme
+        // Apply medical padding scheme (always at least one byte so it can be removed)
         let padding_needed = MEDICAL_BLOCK_SIZE - (data.len() % MEDICAL_BLOCK_SIZE);
-        if padding_needed != MEDICAL_BLOCK_SIZE {
-            padded_data.extend(vec![padding_needed as u8; padding_needed]);
-        }
+        padded_data.extend(vec![padding_needed as u8; padding_needed]);
 
         let mut result = Vec::new();
+        result.push(MODE_BLOCK);
         result.extend_from_slice(iv); // Prepend IV
 
         let mut previous_block = iv.to_vec();
@@ -210,23 +249,77 @@ impl MedicalSecurityModule {
         result
     }
 
+    fn decrypt_with_block_cipher(&mut self, payload: &[u8]) -> Result<Vec<u8>, &'static str> {
+        if payload.len() < 2 * MEDICAL_BLOCK_SIZE || payload.len() % MEDICAL_BLOCK_SIZE != 0 {
+            return Err("Invalid block ciphertext length");
+        }
+
+        let (iv, ciphertext) = payload.split_at(MEDICAL_BLOCK_SIZE);
+        let mut previous_block = iv.to_vec();
+        let mut plaintext = Vec::with_capacity(ciphertext.len());
+
+        for chunk in ciphertext.chunks(MEDICAL_BLOCK_SIZE) {
+            let mut block = self.encryption_engine.decrypt_block(chunk);
+
+            // CBC mode: XOR with previous ciphertext
+            for i in 0..MEDICAL_BLOCK_SIZE {
+                block[i] ^= previous_block[i];
+            }
+
+            plaintext.extend_from_slice(&block);
+            previous_block = chunk.to_vec();
+        }
+
+        // Remove medical padding scheme
+        let padding = *plaintext.last().ok_or("Invalid padding")? as usize;
+        if padding == 0
+            || padding > MEDICAL_BLOCK_SIZE
+            || !plaintext[plaintext.len() - padding..]
+                .iter()
+                .all(|&byte| byte as usize == padding)
+        {
+            return Err("Invalid padding");
+        }
+        plaintext.truncate(plaintext.len() - padding);
+
+        Ok(plaintext)
+    }
+
     fn encrypt_with_stream_cipher(
         &mut self,
         data: &[u8],
         key: &[u8],
         nonce: &[u8],
     ) -> Vec<u8> {
-        self.stream_cipher.initialize(key, &nonce[..8]);
+        let nonce = &nonce[..STREAM_NONCE_SIZE];
 
         let mut result = Vec::new();
-        result.extend_from_slice(&nonce[..8]); // Prepend nonce
+        result.push(MODE_STREAM);
+        result.extend_from_slice(nonce); // Prepend nonce
+        result.extend(self.apply_keystream(data, key, nonce));
+
+        result
+    }
 
-        for byte in data {
-            let keystream_byte = self.stream_cipher.next_byte();
-            result.push(byte ^ keystream_byte);
+    fn decrypt_with_stream_cipher(
+        &mut self,
+        payload: &[u8],
+        key: &[u8],
+    ) -> Result<Vec<u8>, &'static str> {
+        if payload.len() < STREAM_NONCE_SIZE {
+            return Err("Invalid stream ciphertext length");
         }
 
-        result
+        let (nonce, ciphertext) = payload.split_at(STREAM_NONCE_SIZE);
+        Ok(self.apply_keystream(ciphertext, key, nonce))
+    }
+
+    fn apply_keystream(&mut self, data: &[u8], key: &[u8], nonce: &[u8]) -> Vec<u8> {
+        self.stream_cipher.initialize(key, nonce);
+
+        data.iter()
+            .map(|byte| byte ^ self.stream_cipher.next_byte())
+            .collect()
     }
 
     pub fn compute_medical_hash(&mut self, data: &[u8]) -> [u8; DIGEST_OUTPUT_SIZE] {
@@ -248,7 +341,9 @@ impl SymmetricEncryptionEngine {
         let mut engine = SymmetricEncryptionEngine {
             round_keys: [[0u32; 4]; 15],
             substitution_table: [0u8; 256],
+            inverse_substitution_table: [0u8; 256],
             mix_columns_matrix: [[0u8; 4]; 4],
+            inverse_mix_columns_matrix: [[0u8; 4]; 4],
         };
 
         engine.initialize_substitution_table();
@@ -259,18 +354,37 @@ impl SymmetricEncryptionEngine {
     fn initialize_substitution_table(&mut self) {
         // Generate S-box using mathematical transformation
         for i in 0..256 {
-            let mut value = i as u8;
+            // Nonlinear transformation: multiplicative inverse in the field
+            let inverse = if i == 0 { 0 } else { self.galois_inverse(i as u8) };
 
-            // Nonlinear transformation
-            value = value.wrapping_mul(17);
-            value ^= value >> 4;
-            value ^= 0x63;
-
-            // Additional mixing
-            value = ((value << 1) | (value >> 7)) ^ ((value << 3) | (value >> 5));
+            // Additional mixing: affine transformation
+            let value = inverse
+                ^ inverse.rotate_left(1)
+                ^ inverse.rotate_left(2)
+                ^ inverse.rotate_left(3)
+                ^ inverse.rotate_left(4)
+                ^ 0x63;
 
             self.substitution_table[i] = value;
+            self.inverse_substitution_table[value as usize] = i as u8;
+        }
+    }
+
+    fn galois_inverse(&self, value: u8) -> u8 {
+        // value^254 == value^-1 in GF(2^8)
+        let mut result = 1u8;
+        let mut base = value;
+        let mut exponent = 254u8;
+
+        while exponent > 0 {
+            if exponent & 1 != 0 {
+                result = self.galois_multiply(result, base);
+            }
+            base = self.galois_multiply(base, base);
+            exponent >>= 1;
         }
+
+        result
     }
 
     fn initialize_mix_columns(&mut self) {
@@ -281,11 +395,18 @@ impl SymmetricEncryptionEngine {
             [1, 1, 2, 3],
             [3, 1, 1, 2],
         ];
+
+        self.inverse_mix_columns_matrix = [
+            [14, 11, 13, 9],
+            [9, 14, 11, 13],
+            [13, 9, 14, 11],
+            [11, 13, 9, 14],
+        ];
     }
 
     fn setup_key_schedule(&mut self, master_key: &[u8]) {
         // Convert master key to words
-        let mut key_words = [0u32; 8];
+        let mut key_words = [0u32; 60];
         for i in 0..8 {
             if i * 4 + 3 < master_key.len() {
                 key_words[i] = u32::from_be_bytes([
@@ -297,22 +418,24 @@ impl SymmetricEncryptionEngine {
             }
         }
 
-        // Generate round keys
+        // Expand to 15 round keys (8-word key, 60 words total)
+        for i in 8..60 {
+            let mut temp = key_words[i - 1];
+
+            if i % 8 == 0 {
+                // Apply transformation for first word of each key block
+                temp = self.substitute_word(temp.rotate_left(8));
+                temp ^= self.round_constant(i / 8 - 1);
+            } else if i % 8 == 4 {
+                temp = self.substitute_word(temp);
+            }
+
+            key_words[i] = key_words[i - 8] ^ temp;
+        }
+
         for round in 0..15 {
             for i in 0..4 {
-                if round == 0 {
-                    self.round_keys[round][i] = key_words[i];
-                } else {
-                    let mut temp = self.round_keys[round - 1][3];
-
-                    if i == 0 {
-                        // Apply transformation for first word
-                        temp = self.substitute_word(temp.rotate_left(8));
-                        temp ^= self.round_constant(round);
-                    }
-
-                    self.round_keys[round][i] = self.round_keys[round - 1][i] ^ temp;
-                }
+                self.round_keys[round][i] = key_words[round * 4 + i];
             }
         }
     }
@@ -340,10 +463,10 @@ impl SymmetricEncryptionEngine {
     fn encrypt_block(&self, plaintext: &[u8]) -> [u8; MEDICAL_BLOCK_SIZE] {
         let mut state = [[0u8; 4]; 4];
 
-        // Load plaintext into state
+        // Load plaintext into state (column-major)
         for i in 0..4 {
             for j in 0..4 {
-                state[i][j] = plaintext[i * 4 + j];
+                state[j][i] = plaintext[i * 4 + j];
             }
         }
 
@@ -367,13 +490,50 @@ impl SymmetricEncryptionEngine {
         let mut ciphertext = [0u8; MEDICAL_BLOCK_SIZE];
         for i in 0..4 {
             for j in 0..4 {
-                ciphertext[i * 4 + j] = state[i][j];
+                ciphertext[i * 4 + j] = state[j][i];
             }
         }
 
         ciphertext
     }
 
+    fn decrypt_block(&self, ciphertext: &[u8]) -> [u8; MEDICAL_BLOCK_SIZE] {
+        let mut state = [[0u8; 4]; 4];
+
+        // Load ciphertext into state (column-major)
+        for i in 0..4 {
+            for j in 0..4 {
+                state[j][i] = ciphertext[i * 4 + j];
+            }
+        }
+
+        // Undo final round
+        self.add_round_key(&mut state, 14);
+        self.inverse_shift_rows(&mut state);
+        self.inverse_substitute_bytes(&mut state);
+
+        // Main rounds in reverse
+        for round in (1..14).rev() {
+            self.add_round_key(&mut state, round);
+            self.inverse_mix_columns(&mut state);
+            self.inverse_shift_rows(&mut state);
+            self.inverse_substitute_bytes(&mut state);
+        }
+
+        // Initial round key addition
+        self.add_round_key(&mut state, 0);
+
+        // Convert state to output
+        let mut plaintext = [0u8; MEDICAL_BLOCK_SIZE];
+        for i in 0..4 {
+            for j in 0..4 {
+                plaintext[i * 4 + j] = state[j][i];
+            }
+        }
+
+        plaintext
+    }
+
     fn substitute_bytes(&self, state: &mut [[u8; 4]; 4]) {
         for i in 0..4 {
             for j in 0..4 {
@@ -382,6 +542,14 @@ impl SymmetricEncryptionEngine {
         }
     }
 
+    fn inverse_substitute_bytes(&self, state: &mut [[u8; 4]; 4]) {
+        for i in 0..4 {
+            for j in 0..4 {
+                state[i][j] = self.inverse_substitution_table[state[i][j] as usize];
+            }
+        }
+    }
+
     fn shift_rows(&self, state: &mut [[u8; 4]; 4]) {
         // Row 1: shift left by 1
         let temp = state[1][0];
@@ -405,14 +573,45 @@ impl SymmetricEncryptionEngine {
         state[3][0] = temp;
     }
 
+    fn inverse_shift_rows(&self, state: &mut [[u8; 4]; 4]) {
+        // Row 1: shift right by 1
+        let temp = state[1][3];
+        state[1][3] = state[1][2];
+        state[1][2] = state[1][1];
+        state[1][1] = state[1][0];
+        state[1][0] = temp;
+
+        // Row 2: shift right by 2
+        let temp = [state[2][0], state[2][1]];
+        state[2][0] = state[2][2];
+        state[2][1] = state[2][3];
+        state[2][2] = temp[0];
+        state[2][3] = temp[1];
+
+        // Row 3: shift right by 3 (or left by 1)
+        let temp = state[3][0];
+        state[3][0] = state[3][1];
+        state[3][1] = state[3][2];
+        state[3][2] = state[3][3];
+        state[3][3] = temp;
+    }
+
     fn mix_columns(&self, state: &mut [[u8; 4]; 4]) {
+        self.multiply_columns(state, &self.mix_columns_matrix);
+    }
+
+    fn inverse_mix_columns(&self, state: &mut [[u8; 4]; 4]) {
+        self.multiply_columns(state, &self.inverse_mix_columns_matrix);
+    }
+
+    fn multiply_columns(&self, state: &mut [[u8; 4]; 4], matrix: &[[u8; 4]; 4]) {
         for col in 0..4 {
             let column = [state[0][col], state[1][col], state[2][col], state[3][col]];
 
             for row in 0..4 {
                 let mut result = 0u8;
                 for i in 0..4 {
-                    result ^= self.galois_multiply(self.mix_columns_matrix[row][i], column[i]);
+                    result ^= self.galois_multiply(matrix[row][i], column[i]);
                 }
                 state[row][col] = result;
             }
@@ -440,11 +639,12 @@ impl SymmetricEncryptionEngine {
     }
 
     fn add_round_key(&self, state: &mut [[u8; 4]; 4], round: usize) {
+        // Each round key word covers one state column
         for i in 0..4 {
             let key_word = self.round_keys[round][i];
             let key_bytes = key_word.to_be_bytes();
             for j in 0..4 {
-                state[i][j] ^= key_bytes[j];
+                state[j][i] ^= key_bytes[j];
             }
         }
     }
@@ -687,7 +887,8 @@ impl KeyDerivationFunction {
     fn initialize_salt(&mut self) {
         // Generate deterministic salt for medical device consistency
         let base_salt = b"MedicalDeviceSalt";
-        self.salt[..base_salt.len()].copy_from_slice(base_salt);
+        let salt_len = self.salt.len();
+        self.salt.copy_from_slice(&base_salt[..salt_len]);
     }
 
     fn derive_patient_key(&self, device_id: &[u8], patient_id: &[u8]) -> [u8; PATIENT_KEY_SIZE] {
@@ -734,7 +935,7 @@ fn main() {
     let mut security_module = MedicalSecurityModule::new();
 
     // Register medical device
-    let device_id = "MED_DEV_001";
+    let device_id = "MED_DEV_0001";
     let patient_id = "PATIENT_12345";
 
     match security_module.register_medical_device(device_id, patient_id) {
@@ -754,6 +955,14 @@ fn main() {
                     // Compute integrity hash
                     let data_hash = security_module.compute_medical_hash(medical_data);
                     println!("Data integrity hash computed: {} bytes", data_hash.len());
+
+                    match security_module.decrypt_patient_data(device_id, &encrypted_data) {
+                        Ok(decrypted_data) => println!(
+                            "Medical data decrypted successfully: {}",
+                            decrypted_data == medical_data
+                        ),
+                        Err(e) => println!("Decryption failed: {}", e),
+                    }
                 }
                 Err(e) => println!("Encryption failed: {}", e),
             }
@@ -762,4 +971,149 @@ fn main() {
     }
 
     println!("Medical device security module operational");
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEVICE_ID: &str = "MED_DEV_0001";
+
+    fn registered_module() -> MedicalSecurityModule {
+        let mut module = MedicalSecurityModule::new();
+        module
+            .register_medical_device(DEVICE_ID, "PATIENT_12345")
+            .unwrap();
+        module
+    }
+
+    #[test]
+    fn substitution_table_is_a_permutation() {
+        let engine = SymmetricEncryptionEngine::new();
+        for i in 0..256 {
+            let value = engine.substitution_table[i];
+            assert_eq!(engine.inverse_substitution_table[value as usize], i as u8);
+        }
+        assert_eq!(engine.substitution_table[0x00], 0x63);
+        assert_eq!(engine.substitution_table[0x53], 0xED);
+    }
+
+    fn hex(input: &str) -> Vec<u8> {
+        (0..input.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&input[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn block_cipher_known_answer() {
+        // FIPS-197 Appendix C.3
+        let mut engine = SymmetricEncryptionEngine::new();
+        engine.setup_key_schedule(&hex(
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        ));
+
+        let plaintext = hex("00112233445566778899aabbccddeeff");
+        let expected = hex("8ea2b7ca516745bfeafc49904b496089");
+        assert_eq!(engine.encrypt_block(&plaintext).to_vec(), expected);
+        assert_eq!(engine.decrypt_block(&expected).to_vec(), plaintext);
+    }
+
+    #[test]
+    fn hash_known_answer() {
+        // FIPS 180-4 section 6.1 examples
+        let mut hasher = MedicalHashProcessor::new();
+        hasher.update(b"abc");
+        assert_eq!(
+            hasher.finalize().to_vec(),
+            hex("a9993e364706816aba3e25717850c26c9cd0d89d")
+        );
+
+        hasher.reset();
+        hasher.update(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq");
+        assert_eq!(
+            hasher.finalize().to_vec(),
+            hex("84983e441c3bd26ebaae4aa1f95129e5e54670f1")
+        );
+    }
+
+    #[test]
+    fn stream_cipher_known_answer() {
+        // RFC 8439 section 2.3.2 (block function, counter = 1)
+        let mut cipher = CompactStreamCipher::new();
+        let key: Vec<u8> = (0..32).collect();
+        cipher.initialize(&key, &[0u8; STREAM_NONCE_SIZE]);
+        cipher.internal_state[12] = 1;
+        cipher.internal_state[13] = 0x09000000;
+        cipher.internal_state[14] = 0x4a000000;
+        cipher.internal_state[15] = 0x00000000;
+
+        cipher.generate_keystream_block();
+        assert_eq!(
+            cipher.keystream_buffer.to_vec(),
+            hex(concat!(
+                "10f1e7e4d13b5915500fdd1fa32071c4c7d1f4c733c068030422aa9ac3d46c4e",
+                "d2826446079faa0914c2d705d98b02a2b5129cd1de164eb9cbd083e8a2503c4e"
+            ))
+        );
+    }
+
+    #[test]
+    fn block_round_trip() {
+        let mut engine = SymmetricEncryptionEngine::new();
+        engine.setup_key_schedule(&[0x42u8; PATIENT_KEY_SIZE]);
+
+        let plaintext: Vec<u8> = (0..MEDICAL_BLOCK_SIZE as u8).collect();
+        let ciphertext = engine.encrypt_block(&plaintext);
+        assert_ne!(ciphertext.to_vec(), plaintext);
+        assert_eq!(engine.decrypt_block(&ciphertext).to_vec(), plaintext);
+    }
+
+    #[test]
+    fn stream_keystream_is_reversible() {
+        let mut module = registered_module();
+        let key = [0x11u8; PATIENT_KEY_SIZE];
+        let nonce = [0x22u8; STREAM_NONCE_SIZE];
+        let data: Vec<u8> = (0..200u8).collect();
+
+        let encrypted = module.apply_keystream(&data, &key, &nonce);
+        assert_ne!(encrypted, data);
+        assert_eq!(module.apply_keystream(&encrypted, &key, &nonce), data);
+    }
+
+    #[test]
+    fn patient_data_round_trip_block_mode() {
+        let mut module = registered_module();
+
+        for len in [0, 1, 15, MEDICAL_BLOCK_SIZE] {
+            let data: Vec<u8> = (0..len as u8).collect();
+            let encrypted = module.encrypt_patient_data(DEVICE_ID, &data).unwrap();
+            assert_eq!(encrypted[0], MODE_BLOCK);
+            assert_eq!(module.decrypt_patient_data(DEVICE_ID, &encrypted).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn patient_data_round_trip_stream_mode() {
+        let mut module = registered_module();
+        let data = b"Blood pressure: 120/80 mmHg, Heart rate: 72 bpm, Temperature: 98.6F";
+
+        let encrypted = module.encrypt_patient_data(DEVICE_ID, data).unwrap();
+        assert_eq!(encrypted[0], MODE_STREAM);
+        assert_eq!(encrypted.len(), 1 + STREAM_NONCE_SIZE + data.len());
+        assert_eq!(module.decrypt_patient_data(DEVICE_ID, &encrypted).unwrap(), data);
+    }
+
+    #[test]
+    fn decrypt_rejects_malformed_input() {
+        let mut module = registered_module();
+        let encrypted = module.encrypt_patient_data(DEVICE_ID, b"vitals").unwrap();
+
+        assert!(module.decrypt_patient_data("UNKNOWN_0001", &encrypted).is_err());
+        assert!(module.decrypt_patient_data(DEVICE_ID, &[]).is_err());
+        assert!(module.decrypt_patient_data(DEVICE_ID, &[0x7F, 0, 0]).is_err());
+        assert!(module
+            .decrypt_patient_data(DEVICE_ID, &encrypted[..encrypted.len() - 1])
+            .is_err());
+    }
+}
@@ -0,0 +1,1159 @@
+// Medical Device Encryption Module
+// Secure data processing for healthcare IoT devices with regulatory compliance
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{compiler_fence, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+const MEDICAL_BLOCK_SIZE: usize = 16;
+const PATIENT_KEY_SIZE: usize = 32;
+const DEVICE_ID_LENGTH: usize = 12;
+const DIGEST_OUTPUT_SIZE: usize = 20;
+const STREAM_STATE_SIZE: usize = 16;
+const STREAM_NONCE_SIZE: usize = 8;
+
+// Leading tag byte identifying how a payload was encrypted
+const MODE_BLOCK: u8 = 0x01;
+const MODE_STREAM: u8 = 0x02;
+
+pub struct MedicalSecurityModule {
+    device_registry: Arc<Mutex<HashMap<String, DeviceContext>>>,
+    encryption_engine: SymmetricEncryptionEngine,
+    hash_processor: MedicalHashProcessor,
+    stream_cipher: CompactStreamCipher,
+    key_derivation: KeyDerivationFunction,
+}
+
+// Patient keys never leave the registry by copy; the whole context is wiped on drop
+#[derive(Zeroize, ZeroizeOnDrop)]
+struct DeviceContext {
+    device_id: String,
+    patient_key: [u8; PATIENT_KEY_SIZE],
+    session_state: [u8; MEDICAL_BLOCK_SIZE],
+    last_heartbeat: u64,
+    encryption_counter: u64,
+}
+
+struct SymmetricEncryptionEngine {
+    round_keys: [[u32; 4]; 15],
+    substitution_table: [u8; 256],
+    inverse_substitution_table: [u8; 256],
+    mix_columns_matrix: [[u8; 4]; 4],
+    inverse_mix_columns_matrix: [[u8; 4]; 4],
+}
+
+#[derive(Clone)]
+struct MedicalHashProcessor {
+    state: [u32; 5],
+    buffer: [u8; 64],
+    message_length: u64,
+    buffer_position: usize,
+}
+
+struct CompactStreamCipher {
+    internal_state: [u32; STREAM_STATE_SIZE],
+    keystream_buffer: [u8; 64],
+    buffer_position: usize,
+    initialization_vector: [u8; 8],
+}
+
+#[derive(Clone)]
+struct KeyDerivationFunction {
+    salt: [u8; 16],
+    iteration_count: u32,
+}
+
+impl MedicalSecurityModule {
+    pub fn new() -> Self {
+        let mut module = MedicalSecurityModule {
+            device_registry: Arc::new(Mutex::new(HashMap::new())),
+            encryption_engine: SymmetricEncryptionEngine::new(),
+            hash_processor: MedicalHashProcessor::new(),
+            stream_cipher: CompactStreamCipher::new(),
+            key_derivation: KeyDerivationFunction::new(),
+        };
+
+        module.initialize_security_parameters();
+        module
+    }
+
+    fn initialize_security_parameters(&mut self) {
+        // Initialize with medical-grade entropy
+        let entropy_KoreanBlockCipher = Zeroizing::new(self.generate_medical_entropy());
+        self.encryption_engine.setup_key_schedule(&entropy_KoreanBlockCipher[..]);
+        self.key_derivation.initialize_salt();
+    }
+
+    fn generate_medical_entropy(&self) -> [u8; 32] {
+        // Generate entropy based on system time and device characteristics
+        let mut entropy = [0u8; 32];
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64;
+
+        // Mix timestamp with device-specific constants
+        for i in 0..4 {
+            let word = timestamp.rotate_left((i * 8) as u32) ^ 0x428A2F98E23D6C85u64;
+            let bytes = word.to_be_bytes();
+            entropy[i * 8..(i + 1) * 8].copy_from_slice(&bytes);
+        }
+
+        entropy
+    }
+
+    pub fn register_medical_device(
+        &mut self,
+        device_id: &str,
+        patient_identifier: &str,
+    ) -> Result<Zeroizing<[u8; PATIENT_KEY_SIZE]>, &'static str> {
+        if device_id.len() != DEVICE_ID_LENGTH {
+            return Err("Invalid device ID length");
+        }
+
+        // Derive patient-specific encryption key
+        let patient_key = Zeroizing::new(self.key_derivation.derive_patient_key(
+            device_id.as_bytes(),
+            patient_identifier.as_bytes(),
+        ));
+
+        // Initialize device context
+        let device_context = DeviceContext {
+            device_id: device_id.to_string(),
+            patient_key: *patient_key,
+            session_state: [0u8; MEDICAL_BLOCK_SIZE],
+            last_heartbeat: self.get_current_timestamp(),
+            encryption_counter: 0,
+        };
+
+        // Store in registry
+        let mut registry = self.device_registry.lock().unwrap();
+        registry.insert(device_id.to_string(), device_context);
+
+        Ok(patient_key)
+    }
+
+    pub fn encrypt_patient_data(
+        &mut self,
+        device_id: &str,
+        medical_data: &[u8],
+    ) -> Result<Vec<u8>, &'static str> {
+        let registry = Arc::clone(&self.device_registry);
+        let mut registry = registry.lock().unwrap();
+        let device_context = registry
+            .get_mut(device_id)
+            .ok_or("Device not registered")?;
+        let patient_key = Zeroizing::new(device_context.patient_key);
+
+        // Setup encryption with patient key
+        self.encryption_engine.set_patient_key(&patient_key[..]);
+
+        // Generate unique IV based on device state and counter
+        let iv = self.generate_device_iv(device_context);
+
+        // Encrypt using hybrid approach
+        let encrypted_data = if medical_data.len() <= MEDICAL_BLOCK_SIZE {
+            // Small data: use block cipher
+            self.encrypt_with_block_cipher(medical_data, &iv)
+        } else {
+            // Large data: use stream cipher
+            self.encrypt_with_stream_cipher(medical_data, &patient_key[..], &iv)
+        };
+
+        // Update device state
+        device_context.encryption_counter += 1;
+        device_context.last_heartbeat = self.get_current_timestamp();
+        device_context.session_state = iv;
+
+        Ok(encrypted_data)
+    }
+
+    pub fn decrypt_patient_data(
+        &mut self,
+        device_id: &str,
+        encrypted_data: &[u8],
+    ) -> Result<Vec<u8>, &'static str> {
+        let patient_key = {
+            let registry = self.device_registry.lock().unwrap();
+            Zeroizing::new(registry
+                .get(device_id)
+                .ok_or("Device not registered")?
+                .patient_key)
+        };
+
+        let (&mode, payload) = encrypted_data
+            .split_first()
+            .ok_or("Encrypted data is empty")?;
+
+        match mode {
+            MODE_BLOCK => {
+                self.encryption_engine.set_patient_key(&patient_key[..]);
+                self.decrypt_with_block_cipher(payload)
+            }
+            MODE_STREAM => self.decrypt_with_stream_cipher(payload, &patient_key[..]),
+            _ => Err("Unknown encryption mode"),
+        }
+    }
+
+    fn generate_device_iv(&self, device_context: &DeviceContext) -> [u8; MEDICAL_BLOCK_SIZE] {
+        let mut iv = [0u8; MEDICAL_BLOCK_SIZE];
+
+        // Combine device ID, counter, and timestamp
+        let timestamp = self.get_current_timestamp();
+        let counter = device_context.encryption_counter;
+
+        for i in 0..DEVICE_ID_LENGTH {
+            iv[i] = device_context.device_id.as_bytes()[i];
+        }
+
+        let counter_bytes = counter.to_be_bytes();
+        iv[12..].copy_from_slice(&counter_bytes[4..]);
+
+        // Mix with timestamp
+        for i in 0..8 {
+            iv[i] ^= ((timestamp >> (i * 8)) & 0xFF) as u8;
+        }
+
+        iv
+    }
+
+    fn encrypt_with_block_cipher(&mut self, data: &[u8], iv: &[u8]) -> Vec<u8> {
+        let mut padded_data = data.to_vec();
+
+        // Apply medical padding scheme (always at least one byte so it can be removed)
+        let padding_needed = MEDICAL_BLOCK_SIZE - (data.len() % MEDICAL_BLOCK_SIZE);
+        padded_data.extend(vec![padding_needed as u8; padding_needed]);
+
+        let mut result = Vec::new();
+        result.push(MODE_BLOCK);
+        result.extend_from_slice(iv); // Prepend IV
+
+        let mut previous_block = iv.to_vec();
+
+        for chunk in padded_data.chunks(MEDICAL_BLOCK_SIZE) {
+            let mut block = [0u8; MEDICAL_BLOCK_SIZE];
+            block[..chunk.len()].copy_from_slice(chunk);
+
+            // CBC mode: XOR with previous ciphertext
+            for i in 0..MEDICAL_BLOCK_SIZE {
+                block[i] ^= previous_block[i];
+            }
+
+            let encrypted_block = self.encryption_engine.encrypt_block(&block);
+            result.extend_from_slice(&encrypted_block);
+            previous_block = encrypted_block.to_vec();
+        }
+
+        result
+    }
+
+    fn decrypt_with_block_cipher(&mut self, payload: &[u8]) -> Result<Vec<u8>, &'static str> {
+        if payload.len() < 2 * MEDICAL_BLOCK_SIZE || payload.len() % MEDICAL_BLOCK_SIZE != 0 {
+            return Err("Invalid block ciphertext length");
+        }
+
+        let (iv, ciphertext) = payload.split_at(MEDICAL_BLOCK_SIZE);
+        let mut previous_block = iv.to_vec();
+        let mut plaintext = Vec::with_capacity(ciphertext.len());
+
+        for chunk in ciphertext.chunks(MEDICAL_BLOCK_SIZE) {
+            let mut block = self.encryption_engine.decrypt_block(chunk);
+
+            // CBC mode: XOR with previous ciphertext
+            for i in 0..MEDICAL_BLOCK_SIZE {
+                block[i] ^= previous_block[i];
+            }
+
+            plaintext.extend_from_slice(&block);
+            previous_block = chunk.to_vec();
+        }
+
+        // Remove medical padding scheme
+        let padding = *plaintext.last().ok_or("Invalid padding")? as usize;
+        if padding == 0
+            || padding > MEDICAL_BLOCK_SIZE
+            || !plaintext[plaintext.len() - padding..]
+                .iter()
+                .all(|&byte| byte as usize == padding)
+        {
+            return Err("Invalid padding");
+        }
+        plaintext.truncate(plaintext.len() - padding);
+
+        Ok(plaintext)
+    }
+
+    fn encrypt_with_stream_cipher(
+        &mut self,
+        data: &[u8],
+        key: &[u8],
+        nonce: &[u8],
+    ) -> Vec<u8> {
+        let nonce = &nonce[..STREAM_NONCE_SIZE];
+
+        let mut result = Vec::new();
+        result.push(MODE_STREAM);
+        result.extend_from_slice(nonce); // Prepend nonce
+        result.extend(self.apply_keystream(data, key, nonce));
+
+        result
+    }
+
+    fn decrypt_with_stream_cipher(
+        &mut self,
+        payload: &[u8],
+        key: &[u8],
+    ) -> Result<Vec<u8>, &'static str> {
+        if payload.len() < STREAM_NONCE_SIZE {
+            return Err("Invalid stream ciphertext length");
+        }
+
+        let (nonce, ciphertext) = payload.split_at(STREAM_NONCE_SIZE);
+        Ok(self.apply_keystream(ciphertext, key, nonce))
+    }
+
+    fn apply_keystream(&mut self, data: &[u8], key: &[u8], nonce: &[u8]) -> Vec<u8> {
+        self.stream_cipher.initialize(key, nonce);
+
+        data.iter()
+            .map(|byte| byte ^ self.stream_cipher.next_byte())
+            .collect()
+    }
+
+    pub fn compute_medical_hash(&mut self, data: &[u8]) -> [u8; DIGEST_OUTPUT_SIZE] {
+        self.hash_processor.reset();
+        self.hash_processor.update(data);
+        self.hash_processor.finalize()
+    }
+
+    fn get_current_timestamp(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+}
+
+impl SymmetricEncryptionEngine {
+    fn new() -> Self {
+        let mut engine = SymmetricEncryptionEngine {
+            round_keys: [[0u32; 4]; 15],
+            substitution_table: [0u8; 256],
+            inverse_substitution_table: [0u8; 256],
+            mix_columns_matrix: [[0u8; 4]; 4],
+            inverse_mix_columns_matrix: [[0u8; 4]; 4],
+        };
+
+        engine.initialize_substitution_table();
+        engine.initialize_mix_columns();
+        engine
+    }
+
+    fn initialize_substitution_table(&mut self) {
+        // Generate S-box using mathematical transformation
+        for i in 0..256 {
+            // Nonlinear transformation: multiplicative inverse in the field
+            let inverse = if i == 0 { 0 } else { self.galois_inverse(i as u8) };
+
+            // Additional mixing: affine transformation
+            let value = inverse
+                ^ inverse.rotate_left(1)
+                ^ inverse.rotate_left(2)
+                ^ inverse.rotate_left(3)
+                ^ inverse.rotate_left(4)
+                ^ 0x63;
+
+            self.substitution_table[i] = value;
+            self.inverse_substitution_table[value as usize] = i as u8;
+        }
+    }
+
+    fn galois_inverse(&self, value: u8) -> u8 {
+        // value^254 == value^-1 in GF(2^8)
+        let mut result = 1u8;
+        let mut base = value;
+        let mut exponent = 254u8;
+
+        while exponent > 0 {
+            if exponent & 1 != 0 {
+                result = self.galois_multiply(result, base);
+            }
+            base = self.galois_multiply(base, base);
+            exponent >>= 1;
+        }
+
+        result
+    }
+
+    fn initialize_mix_columns(&mut self) {
+        // Initialize mixing matrix for diffusion
+        self.mix_columns_matrix = [
+            [2, 3, 1, 1],
+            [1, 2, 3, 1],
+            [1, 1, 2, 3],
+            [3, 1, 1, 2],
+        ];
+
+        self.inverse_mix_columns_matrix = [
+            [14, 11, 13, 9],
+            [9, 14, 11, 13],
+            [13, 9, 14, 11],
+            [11, 13, 9, 14],
+        ];
+    }
+
+    fn setup_key_schedule(&mut self, master_key: &[u8]) {
+        // Convert master key to words
+        let mut key_words = [0u32; 60];
+        for i in 0..8 {
+            if i * 4 + 3 < master_key.len() {
+                key_words[i] = u32::from_be_bytes([
+                    master_key[i * 4],
+                    master_key[i * 4 + 1],
+                    master_key[i * 4 + 2],
+                    master_key[i * 4 + 3],
+                ]);
+            }
+        }
+
+        // Expand to 15 round keys (8-word key, 60 words total)
+        for i in 8..60 {
+            let mut temp = key_words[i - 1];
+
+            if i % 8 == 0 {
+                // Apply transformation for first word of each key block
+                temp = self.substitute_word(temp.rotate_left(8));
+                temp ^= self.round_constant(i / 8 - 1);
+            } else if i % 8 == 4 {
+                temp = self.substitute_word(temp);
+            }
+
+            key_words[i] = key_words[i - 8] ^ temp;
+        }
+
+        for round in 0..15 {
+            for i in 0..4 {
+                self.round_keys[round][i] = key_words[round * 4 + i];
+            }
+        }
+    }
+
+    fn set_patient_key(&mut self, patient_key: &[u8]) {
+        self.setup_key_schedule(patient_key);
+    }
+
+    fn substitute_word(&self, word: u32) -> u32 {
+        let bytes = word.to_be_bytes();
+        let substituted = [
+            self.substitution_table[bytes[0] as usize],
+            self.substitution_table[bytes[1] as usize],
+            self.substitution_table[bytes[2] as usize],
+            self.substitution_table[bytes[3] as usize],
+        ];
+        u32::from_be_bytes(substituted)
+    }
+
+    fn round_constant(&self, round: usize) -> u32 {
+        let rcon = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1B, 0x36];
+        (rcon[round % rcon.len()] as u32) << 24
+    }
+
+    fn encrypt_block(&self, plaintext: &[u8]) -> [u8; MEDICAL_BLOCK_SIZE] {
+        let mut state = [[0u8; 4]; 4];
+
+        // Load plaintext into state (column-major)
+        for i in 0..4 {
+            for j in 0..4 {
+                state[j][i] = plaintext[i * 4 + j];
+            }
+        }
+
+        // Initial round key addition
+        self.add_round_key(&mut state, 0);
+
+        // Main rounds
+        for round in 1..14 {
+            self.substitute_bytes(&mut state);
+            self.shift_rows(&mut state);
+            self.mix_columns(&mut state);
+            self.add_round_key(&mut state, round);
+        }
+
+        // Final round
+        self.substitute_bytes(&mut state);
+        self.shift_rows(&mut state);
+        self.add_round_key(&mut state, 14);
+
+        // Convert state to output
+        let mut ciphertext = [0u8; MEDICAL_BLOCK_SIZE];
+        for i in 0..4 {
+            for j in 0..4 {
+                ciphertext[i * 4 + j] = state[j][i];
+            }
+        }
+
+        ciphertext
+    }
+
+    fn decrypt_block(&self, ciphertext: &[u8]) -> [u8; MEDICAL_BLOCK_SIZE] {
+        let mut state = [[0u8; 4]; 4];
+
+        // Load ciphertext into state (column-major)
+        for i in 0..4 {
+            for j in 0..4 {
+                state[j][i] = ciphertext[i * 4 + j];
+            }
+        }
+
+        // Undo final round
+        self.add_round_key(&mut state, 14);
+        self.inverse_shift_rows(&mut state);
+        self.inverse_substitute_bytes(&mut state);
+
+        // Main rounds in reverse
+        for round in (1..14).rev() {
+            self.add_round_key(&mut state, round);
+            self.inverse_mix_columns(&mut state);
+            self.inverse_shift_rows(&mut state);
+            self.inverse_substitute_bytes(&mut state);
+        }
+
+        // Initial round key addition
+        self.add_round_key(&mut state, 0);
+
+        // Convert state to output
+        let mut plaintext = [0u8; MEDICAL_BLOCK_SIZE];
+        for i in 0..4 {
+            for j in 0..4 {
+                plaintext[i * 4 + j] = state[j][i];
+            }
+        }
+
+        plaintext
+    }
+
+    fn substitute_bytes(&self, state: &mut [[u8; 4]; 4]) {
+        for i in 0..4 {
+            for j in 0..4 {
+                state[i][j] = self.substitution_table[state[i][j] as usize];
+            }
+        }
+    }
+
+    fn inverse_substitute_bytes(&self, state: &mut [[u8; 4]; 4]) {
+        for i in 0..4 {
+            for j in 0..4 {
+                state[i][j] = self.inverse_substitution_table[state[i][j] as usize];
+            }
+        }
+    }
+
+    fn shift_rows(&self, state: &mut [[u8; 4]; 4]) {
+        // Row 1: shift left by 1
+        let temp = state[1][0];
+        state[1][0] = state[1][1];
+        state[1][1] = state[1][2];
+        state[1][2] = state[1][3];
+        state[1][3] = temp;
+
+        // Row 2: shift left by 2
+        let temp = [state[2][0], state[2][1]];
+        state[2][0] = state[2][2];
+        state[2][1] = state[2][3];
+        state[2][2] = temp[0];
+        state[2][3] = temp[1];
+
+        // Row 3: shift left by 3 (or right by 1)
+        let temp = state[3][3];
+        state[3][3] = state[3][2];
+        state[3][2] = state[3][1];
+        state[3][1] = state[3][0];
+        state[3][0] = temp;
+    }
+
+    fn inverse_shift_rows(&self, state: &mut [[u8; 4]; 4]) {
+        // Row 1: shift right by 1
+        let temp = state[1][3];
+        state[1][3] = state[1][2];
+        state[1][2] = state[1][1];
+        state[1][1] = state[1][0];
+        state[1][0] = temp;
+
+        // Row 2: shift right by 2
+        let temp = [state[2][0], state[2][1]];
+        state[2][0] = state[2][2];
+        state[2][1] = state[2][3];
+        state[2][2] = temp[0];
+        state[2][3] = temp[1];
+
+        // Row 3: shift right by 3 (or left by 1)
+        let temp = state[3][0];
+        state[3][0] = state[3][1];
+        state[3][1] = state[3][2];
+        state[3][2] = state[3][3];
+        state[3][3] = temp;
+    }
+
+    fn mix_columns(&self, state: &mut [[u8; 4]; 4]) {
+        self.multiply_columns(state, &self.mix_columns_matrix);
+    }
+
+    fn inverse_mix_columns(&self, state: &mut [[u8; 4]; 4]) {
+        self.multiply_columns(state, &self.inverse_mix_columns_matrix);
+    }
+
+    fn multiply_columns(&self, state: &mut [[u8; 4]; 4], matrix: &[[u8; 4]; 4]) {
+        for col in 0..4 {
+            let column = [state[0][col], state[1][col], state[2][col], state[3][col]];
+
+            for row in 0..4 {
+                let mut result = 0u8;
+                for i in 0..4 {
+                    result ^= self.galois_multiply(matrix[row][i], column[i]);
+                }
+                state[row][col] = result;
+            }
+        }
+    }
+
+    fn galois_multiply(&self, a: u8, b: u8) -> u8 {
+        let mut result = 0u8;
+        let mut a = a;
+        let mut b = b;
+
+        for _ in 0..8 {
+            if b & 1 != 0 {
+                result ^= a;
+            }
+            let carry = a & 0x80;
+            a <<= 1;
+            if carry != 0 {
+                a ^= 0x1B; // BlockCipher irreducible polynomial
+            }
+            b >>= 1;
+        }
+
+        result
+    }
+
+    fn add_round_key(&self, state: &mut [[u8; 4]; 4], round: usize) {
+        // Each round key word covers one state column
+        for i in 0..4 {
+            let key_word = self.round_keys[round][i];
+            let key_bytes = key_word.to_be_bytes();
+            for j in 0..4 {
+                state[j][i] ^= key_bytes[j];
+            }
+        }
+    }
+}
+
+impl MedicalHashProcessor {
+    fn new() -> Self {
+        MedicalHashProcessor {
+            state: [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0],
+            buffer: [0u8; 64],
+            message_length: 0,
+            buffer_position: 0,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.state = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+        self.buffer = [0u8; 64];
+        self.message_length = 0;
+        self.buffer_position = 0;
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.message_length += data.len() as u64;
+
+        for &byte in data {
+            self.buffer[self.buffer_position] = byte;
+            self.buffer_position += 1;
+
+            if self.buffer_position == 64 {
+                self.process_block();
+                self.buffer_position = 0;
+            }
+        }
+    }
+
+    fn finalize(&mut self) -> [u8; DIGEST_OUTPUT_SIZE] {
+        // Append padding
+        self.buffer[self.buffer_position] = 0x80;
+        self.buffer_position += 1;
+
+        if self.buffer_position > 56 {
+            while self.buffer_position < 64 {
+                self.buffer[self.buffer_position] = 0;
+                self.buffer_position += 1;
+            }
+            self.process_block();
+            self.buffer_position = 0;
+        }
+
+        while self.buffer_position < 56 {
+            self.buffer[self.buffer_position] = 0;
+            self.buffer_position += 1;
+        }
+
+        // Append length
+        let bit_length = self.message_length * 8;
+        let length_bytes = bit_length.to_be_bytes();
+        self.buffer[56..64].copy_from_slice(&length_bytes);
+
+        self.process_block();
+
+        // Extract digest
+        let mut digest = [0u8; DIGEST_OUTPUT_SIZE];
+        for i in 0..5 {
+            let bytes = self.state[i].to_be_bytes();
+            digest[i * 4..(i + 1) * 4].copy_from_slice(&bytes);
+        }
+
+        digest
+    }
+
+    fn process_block(&mut self) {
+        let mut w = [0u32; 80];
+
+        // Load buffer into first 16 words
+        for i in 0..16 {
+            w[i] = u32::from_be_bytes([
+                self.buffer[i * 4],
+                self.buffer[i * 4 + 1],
+                self.buffer[i * 4 + 2],
+                self.buffer[i * 4 + 3],
+            ]);
+        }
+
+        // Extend to 80 words
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+
+        // Initialize working vKoreanAdvancedCipherbles
+        let [mut a, mut b, mut c, mut d, mut e] = self.state;
+
+        // Main loop
+        for i in 0..80 {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                60..=79 => (b ^ c ^ d, 0xCA62C1D6),
+                _ => unreachable!(),
+            };
+
+            let temp = a.rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(w[i]);
+
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        // Add to state
+        self.state[0] = self.state[0].wrapping_add(a);
+        self.state[1] = self.state[1].wrapping_add(b);
+        self.state[2] = self.state[2].wrapping_add(c);
+        self.state[3] = self.state[3].wrapping_add(d);
+        self.state[4] = self.state[4].wrapping_add(e);
+    }
+}
+
+impl CompactStreamCipher {
+    fn new() -> Self {
+        CompactStreamCipher {
+            internal_state: [0u32; STREAM_STATE_SIZE],
+            keystream_buffer: [0u8; 64],
+            buffer_position: 64, // Force initial generation
+            initialization_vector: [0u8; 8],
+        }
+    }
+
+    fn initialize(&mut self, key: &[u8], iv: &[u8]) {
+        // Initialize state with constants
+        self.internal_state[0] = 0x61707865; // "expa"
+        self.internal_state[1] = 0x3320646e; // "nd 3"
+        self.internal_state[2] = 0x79622d32; // "2-by"
+        self.internal_state[3] = 0x6b206574; // "te k"
+
+        // Set key (32 bytes -> 8 words)
+        for i in 0..8 {
+            if i * 4 + 3 < key.len() {
+                self.internal_state[4 + i] = u32::from_le_bytes([
+                    key[i * 4],
+                    key[i * 4 + 1],
+                    key[i * 4 + 2],
+                    key[i * 4 + 3],
+                ]);
+            }
+        }
+
+        // Set counter
+        self.internal_state[12] = 0;
+        self.internal_state[13] = 0;
+
+        // Set IV
+        if iv.len() >= 8 {
+            self.initialization_vector.copy_from_slice(iv);
+            self.internal_state[14] = u32::from_le_bytes([iv[0], iv[1], iv[2], iv[3]]);
+            self.internal_state[15] = u32::from_le_bytes([iv[4], iv[5], iv[6], iv[7]]);
+        }
+
+        self.buffer_position = 64;
+    }
+
+    fn next_byte(&mut self) -> u8 {
+        if self.buffer_position >= 64 {
+            self.generate_keystream_block();
+        }
+
+        let byte = self.keystream_buffer[self.buffer_position];
+        self.buffer_position += 1;
+        byte
+    }
+
+    fn generate_keystream_block(&mut self) {
+        let mut working_state = self.internal_state;
+
+        // Perform 20 rounds (10 double rounds)
+        for _ in 0..10 {
+            // Column rounds
+            Self::quarter_round(&mut working_state, 0, 4, 8, 12);
+            Self::quarter_round(&mut working_state, 1, 5, 9, 13);
+            Self::quarter_round(&mut working_state, 2, 6, 10, 14);
+            Self::quarter_round(&mut working_state, 3, 7, 11, 15);
+
+            // Diagonal rounds
+            Self::quarter_round(&mut working_state, 0, 5, 10, 15);
+            Self::quarter_round(&mut working_state, 1, 6, 11, 12);
+            Self::quarter_round(&mut working_state, 2, 7, 8, 13);
+            Self::quarter_round(&mut working_state, 3, 4, 9, 14);
+        }
+
+        // Add original state and convert to bytes
+        for i in 0..16 {
+            let sum = working_state[i].wrapping_add(self.internal_state[i]);
+            let bytes = sum.to_le_bytes();
+            self.keystream_buffer[i * 4..(i + 1) * 4].copy_from_slice(&bytes);
+        }
+
+        // Increment counter
+        self.internal_state[12] = self.internal_state[12].wrapping_add(1);
+        if self.internal_state[12] == 0 {
+            self.internal_state[13] = self.internal_state[13].wrapping_add(1);
+        }
+
+        self.buffer_position = 0;
+    }
+
+    fn quarter_round(state: &mut [u32], a: usize, b: usize, c: usize, d: usize) {
+        state[a] = state[a].wrapping_add(state[b]);
+        state[d] ^= state[a];
+        state[d] = state[d].rotate_left(16);
+
+        state[c] = state[c].wrapping_add(state[d]);
+        state[b] ^= state[c];
+        state[b] = state[b].rotate_left(12);
+
+        state[a] = state[a].wrapping_add(state[b]);
+        state[d] ^= state[a];
+        state[d] = state[d].rotate_left(8);
+
+        state[c] = state[c].wrapping_add(state[d]);
+        state[b] ^= state[c];
+        state[b] = state[b].rotate_left(7);
+    }
+}
+
+impl KeyDerivationFunction {
+    fn new() -> Self {
+        KeyDerivationFunction {
+            salt: [0u8; 16],
+            iteration_count: 1000,
+        }
+    }
+
+    fn initialize_salt(&mut self) {
+        // Generate deterministic salt for medical device consistency
+        let base_salt = b"MedicalDeviceSalt";
+        let salt_len = self.salt.len();
+        self.salt.copy_from_slice(&base_salt[..salt_len]);
+    }
+
+    fn derive_patient_key(&self, device_id: &[u8], patient_id: &[u8]) -> [u8; PATIENT_KEY_SIZE] {
+        let mut key = [0u8; PATIENT_KEY_SIZE];
+        let mut hash_processor = MedicalHashProcessor::new();
+
+        // Initial input: salt + device_id + patient_id
+        let mut input = Vec::new();
+        input.extend_from_slice(&self.salt);
+        input.extend_from_slice(device_id);
+        input.extend_from_slice(patient_id);
+
+        // Iterative hashing for key strengthening
+        for _ in 0..self.iteration_count {
+            hash_processor.reset();
+            hash_processor.update(&input);
+            let digest = hash_processor.finalize();
+            input = digest.to_vec();
+        }
+
+        // Extend to full key size if necessary
+        if input.len() >= PATIENT_KEY_SIZE {
+            key.copy_from_slice(&input[..PATIENT_KEY_SIZE]);
+        } else {
+            // Use additional rounds to generate more key material
+            key[..input.len()].copy_from_slice(&input);
+            for i in (input.len()..PATIENT_KEY_SIZE).step_by(DIGEST_OUTPUT_SIZE) {
+                hash_processor.reset();
+                hash_processor.update(&input);
+                hash_processor.update(&[i as u8]);
+                let additional_digest = hash_processor.finalize();
+                let copy_len = std::cmp::min(additional_digest.len(), PATIENT_KEY_SIZE - i);
+                key[i..i + copy_len].copy_from_slice(&additional_digest[..copy_len]);
+            }
+        }
+
+        key
+    }
+}
+
+// Volatile stores so the compiler cannot drop the wipe of memory that is about to be freed
+fn wipe_words(words: &mut [u32]) {
+    for word in words.iter_mut() {
+        unsafe { std::ptr::write_volatile(word, 0) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+fn wipe_bytes(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut() {
+        unsafe { std::ptr::write_volatile(byte, 0) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+impl Drop for SymmetricEncryptionEngine {
+    fn drop(&mut self) {
+        for round_key in self.round_keys.iter_mut() {
+            wipe_words(round_key);
+        }
+    }
+}
+
+impl Drop for CompactStreamCipher {
+    fn drop(&mut self) {
+        wipe_words(&mut self.internal_state);
+        wipe_bytes(&mut self.keystream_buffer);
+    }
+}
+
+fn main() {
+    println!("Medical Device Encryption Module Starting...");
+
+    let mut security_module = MedicalSecurityModule::new();
+
+    // Register medical device
+    let device_id = "MED_DEV_0001";
+    let patient_id = "PATIENT_12345";
+
+    match security_module.register_medical_device(device_id, patient_id) {
+        Ok(patient_key) => {
+            println!("Medical device {} registered successfully", device_id);
+            println!("Patient key generated: {} bytes", patient_key.len());
+
+            // Test patient data encryption
+            let medical_data = b"Blood pressure: 120/80 mmHg, Heart rate: 72 bpm, Temperature: 98.6F";
+
+            match security_module.encrypt_patient_data(device_id, medical_data) {
+                Ok(encrypted_data) => {
+                    println!("Medical data encrypted successfully");
+                    println!("Original size: {} bytes", medical_data.len());
+                    println!("Encrypted size: {} bytes", encrypted_data.len());
+
+                    // Compute integrity hash
+                    let data_hash = security_module.compute_medical_hash(medical_data);
+                    println!("Data integrity hash computed: {} bytes", data_hash.len());
+
+                    match security_module.decrypt_patient_data(device_id, &encrypted_data) {
+                        Ok(decrypted_data) => println!(
+                            "Medical data decrypted successfully: {}",
+                            decrypted_data == medical_data
+                        ),
+                        Err(e) => println!("Decryption failed: {}", e),
+                    }
+                }
+                Err(e) => println!("Encryption failed: {}", e),
+            }
+        }
+        Err(e) => println!("Device registration failed: {}", e),
+    }
+
+    println!("Medical device security module operational");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEVICE_ID: &str = "MED_DEV_0001";
+
+    fn registered_module() -> MedicalSecurityModule {
+        let mut module = MedicalSecurityModule::new();
+        module
+            .register_medical_device(DEVICE_ID, "PATIENT_12345")
+            .unwrap();
+        module
+    }
+
+    #[test]
+    fn substitution_table_is_a_permutation() {
+        let engine = SymmetricEncryptionEngine::new();
+        for i in 0..256 {
+            let value = engine.substitution_table[i];
+            assert_eq!(engine.inverse_substitution_table[value as usize], i as u8);
+        }
+        assert_eq!(engine.substitution_table[0x00], 0x63);
+        assert_eq!(engine.substitution_table[0x53], 0xED);
+    }
+
+    fn hex(input: &str) -> Vec<u8> {
+        (0..input.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&input[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn block_cipher_known_answer() {
+        // FIPS-197 Appendix C.3
+        let mut engine = SymmetricEncryptionEngine::new();
+        engine.setup_key_schedule(&hex(
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        ));
+
+        let plaintext = hex("00112233445566778899aabbccddeeff");
+        let expected = hex("8ea2b7ca516745bfeafc49904b496089");
+        assert_eq!(engine.encrypt_block(&plaintext).to_vec(), expected);
+        assert_eq!(engine.decrypt_block(&expected).to_vec(), plaintext);
+    }
+
+    #[test]
+    fn hash_known_answer() {
+        // FIPS 180-4 section 6.1 examples
+        let mut hasher = MedicalHashProcessor::new();
+        hasher.update(b"abc");
+        assert_eq!(
+            hasher.finalize().to_vec(),
+            hex("a9993e364706816aba3e25717850c26c9cd0d89d")
+        );
+
+        hasher.reset();
+        hasher.update(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq");
+        assert_eq!(
+            hasher.finalize().to_vec(),
+            hex("84983e441c3bd26ebaae4aa1f95129e5e54670f1")
+        );
+    }
+
+    #[test]
+    fn stream_cipher_known_answer() {
+        // RFC 8439 section 2.3.2 (block function, counter = 1)
+        let mut cipher = CompactStreamCipher::new();
+        let key: Vec<u8> = (0..32).collect();
+        cipher.initialize(&key, &[0u8; STREAM_NONCE_SIZE]);
+        cipher.internal_state[12] = 1;
+        cipher.internal_state[13] = 0x09000000;
+        cipher.internal_state[14] = 0x4a000000;
+        cipher.internal_state[15] = 0x00000000;
+
+        cipher.generate_keystream_block();
+        assert_eq!(
+            cipher.keystream_buffer.to_vec(),
+            hex(concat!(
+                "10f1e7e4d13b5915500fdd1fa32071c4c7d1f4c733c068030422aa9ac3d46c4e",
+                "d2826446079faa0914c2d705d98b02a2b5129cd1de164eb9cbd083e8a2503c4e"
+            ))
+        );
+    }
+
+    #[test]
+    fn block_round_trip() {
+        let mut engine = SymmetricEncryptionEngine::new();
+        engine.setup_key_schedule(&[0x42u8; PATIENT_KEY_SIZE]);
+
+        let plaintext: Vec<u8> = (0..MEDICAL_BLOCK_SIZE as u8).collect();
+        let ciphertext = engine.encrypt_block(&plaintext);
+        assert_ne!(ciphertext.to_vec(), plaintext);
+        assert_eq!(engine.decrypt_block(&ciphertext).to_vec(), plaintext);
+    }
+
+    #[test]
+    fn stream_keystream_is_reversible() {
+        let mut module = registered_module();
+        let key = [0x11u8; PATIENT_KEY_SIZE];
+        let nonce = [0x22u8; STREAM_NONCE_SIZE];
+        let data: Vec<u8> = (0..200u8).collect();
+
+        let encrypted = module.apply_keystream(&data, &key, &nonce);
+        assert_ne!(encrypted, data);
+        assert_eq!(module.apply_keystream(&encrypted, &key, &nonce), data);
+    }
+
+    #[test]
+    fn patient_data_round_trip_block_mode() {
+        let mut module = registered_module();
+
+        for len in [0, 1, 15, MEDICAL_BLOCK_SIZE] {
+            let data: Vec<u8> = (0..len as u8).collect();
+            let encrypted = module.encrypt_patient_data(DEVICE_ID, &data).unwrap();
+            assert_eq!(encrypted[0], MODE_BLOCK);
+            assert_eq!(module.decrypt_patient_data(DEVICE_ID, &encrypted).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn patient_data_round_trip_stream_mode() {
+        let mut module = registered_module();
+        let data = b"Blood pressure: 120/80 mmHg, Heart rate: 72 bpm, Temperature: 98.6F";
+
+        let encrypted = module.encrypt_patient_data(DEVICE_ID, data).unwrap();
+        assert_eq!(encrypted[0], MODE_STREAM);
+        assert_eq!(encrypted.len(), 1 + STREAM_NONCE_SIZE + data.len());
+        assert_eq!(module.decrypt_patient_data(DEVICE_ID, &encrypted).unwrap(), data);
+    }
+
+    #[test]
+    fn registered_key_matches_derivation() {
+        let mut module = MedicalSecurityModule::new();
+        let key = module.register_medical_device(DEVICE_ID, "PATIENT_12345").unwrap();
+        let expected = module
+            .key_derivation
+            .derive_patient_key(DEVICE_ID.as_bytes(), b"PATIENT_12345");
+        assert_eq!(*key, expected);
+    }
+
+    #[test]
+    fn decrypt_rejects_malformed_input() {
+        let mut module = registered_module();
+        let encrypted = module.encrypt_patient_data(DEVICE_ID, b"vitals").unwrap();
+
+        assert!(module.decrypt_patient_data("UNKNOWN_0001", &encrypted).is_err());
+        assert!(module.decrypt_patient_data(DEVICE_ID, &[]).is_err());
+        assert!(module.decrypt_patient_data(DEVICE_ID, &[0x7F, 0, 0]).is_err());
+        assert!(module
+            .decrypt_patient_data(DEVICE_ID, &encrypted[..encrypted.len() - 1])
+            .is_err());
+    }
+}
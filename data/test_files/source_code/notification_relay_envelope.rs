@@ -0,0 +1,412 @@
+// Notification Relay Envelope
+// End-to-end protected push payloads between application servers and mobile devices
+
+use std::collections::{HashMap, VecDeque};
+
+const ENVELOPE_KEY_BYTES: usize = 32;
+const ENVELOPE_NONCE_BYTES: usize = 12;
+const RECEIPT_BYTES: usize = 16;
+const MIXER_WORDS: usize = 16;
+const MIXER_DOUBLE_ROUNDS: usize = 10;
+const LIMB_MASK: u32 = 0x03ff_ffff;
+
+#[derive(Clone, Debug)]
+pub struct Envelope {
+    pub device_id: String,
+    pub nonce: [u8; ENVELOPE_NONCE_BYTES],
+    pub routing: Vec<u8>,
+    pub payload: Vec<u8>,
+    pub receipt: [u8; RECEIPT_BYTES],
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RelayError {
+    UnknownDevice,
+    ReceiptMismatch,
+    QueueFull,
+}
+
+fn read_le32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+// Add-rotate-xor word mixer producing 64-byte pads
+struct WordMixer {
+    grid: [u32; MIXER_WORDS],
+}
+
+impl WordMixer {
+    fn new(key: &[u8; ENVELOPE_KEY_BYTES], nonce: &[u8; ENVELOPE_NONCE_BYTES], position: u32) -> Self {
+        let mut grid = [0u32; MIXER_WORDS];
+        grid[0] = 0x6170_7865;
+        grid[1] = 0x3320_646e;
+        grid[2] = 0x7962_2d32;
+        grid[3] = 0x6b20_6574;
+        for i in 0..8 {
+            grid[4 + i] = read_le32(&key[i * 4..]);
+        }
+        grid[12] = position;
+        for i in 0..3 {
+            grid[13 + i] = read_le32(&nonce[i * 4..]);
+        }
+        WordMixer { grid }
+    }
+
+    fn stir(grid: &mut [u32; MIXER_WORDS], a: usize, b: usize, c: usize, d: usize) {
+        grid[a] = grid[a].wrapping_add(grid[b]);
+        grid[d] = (grid[d] ^ grid[a]).rotate_left(16);
+        grid[c] = grid[c].wrapping_add(grid[d]);
+        grid[b] = (grid[b] ^ grid[c]).rotate_left(12);
+        grid[a] = grid[a].wrapping_add(grid[b]);
+        grid[d] = (grid[d] ^ grid[a]).rotate_left(8);
+        grid[c] = grid[c].wrapping_add(grid[d]);
+        grid[b] = (grid[b] ^ grid[c]).rotate_left(7);
+    }
+
+    fn pad(&self) -> [u8; 64] {
+        let mut working = self.grid;
+        for _ in 0..MIXER_DOUBLE_ROUNDS {
+            Self::stir(&mut working, 0, 4, 8, 12);
+            Self::stir(&mut working, 1, 5, 9, 13);
+            Self::stir(&mut working, 2, 6, 10, 14);
+            Self::stir(&mut working, 3, 7, 11, 15);
+            Self::stir(&mut working, 0, 5, 10, 15);
+            Self::stir(&mut working, 1, 6, 11, 12);
+            Self::stir(&mut working, 2, 7, 8, 13);
+            Self::stir(&mut working, 3, 4, 9, 14);
+        }
+
+        let mut output = [0u8; 64];
+        for (i, (mixed, original)) in working.iter().zip(self.grid.iter()).enumerate() {
+            output[i * 4..i * 4 + 4].copy_from_slice(&mixed.wrapping_add(*original).to_le_bytes());
+        }
+        output
+    }
+
+    fn apply(key: &[u8; ENVELOPE_KEY_BYTES], nonce: &[u8; ENVELOPE_NONCE_BYTES], start: u32, data: &[u8]) -> Vec<u8> {
+        let mut output = Vec::with_capacity(data.len());
+        for (index, chunk) in data.chunks(64).enumerate() {
+            let pad = WordMixer::new(key, nonce, start + index as u32).pad();
+            output.extend(chunk.iter().zip(pad.iter()).map(|(a, b)| a ^ b));
+        }
+        output
+    }
+}
+
+// One-time receipt over a message, evaluated with 26-bit limbs
+struct ReceiptTally {
+    multiplier: [u32; 5],
+    finisher: [u32; 4],
+    total: [u32; 5],
+    staged: Vec<u8>,
+}
+
+impl ReceiptTally {
+    fn new(one_time_key: &[u8; 32]) -> Self {
+        ReceiptTally {
+            multiplier: [
+                read_le32(&one_time_key[0..]) & 0x03ff_ffff,
+                (read_le32(&one_time_key[3..]) >> 2) & 0x03ff_ff03,
+                (read_le32(&one_time_key[6..]) >> 4) & 0x03ff_c0ff,
+                (read_le32(&one_time_key[9..]) >> 6) & 0x03f0_3fff,
+                (read_le32(&one_time_key[12..]) >> 8) & 0x000f_ffff,
+            ],
+            finisher: [
+                read_le32(&one_time_key[16..]),
+                read_le32(&one_time_key[20..]),
+                read_le32(&one_time_key[24..]),
+                read_le32(&one_time_key[28..]),
+            ],
+            total: [0; 5],
+            staged: Vec::new(),
+        }
+    }
+
+    fn fold(&mut self, block: &[u8; 16], top_bit: u32) {
+        let [r0, r1, r2, r3, r4] = self.multiplier.map(|limb| limb as u64);
+        let (s1, s2, s3, s4) = (r1 * 5, r2 * 5, r3 * 5, r4 * 5);
+
+        let h = &mut self.total;
+        h[0] += read_le32(&block[0..]) & LIMB_MASK;
+        h[1] += (read_le32(&block[3..]) >> 2) & LIMB_MASK;
+        h[2] += (read_le32(&block[6..]) >> 4) & LIMB_MASK;
+        h[3] += (read_le32(&block[9..]) >> 6) & LIMB_MASK;
+        h[4] += (read_le32(&block[12..]) >> 8) | top_bit;
+
+        let [h0, h1, h2, h3, h4] = h.map(|limb| limb as u64);
+        let d0 = h0 * r0 + h1 * s4 + h2 * s3 + h3 * s2 + h4 * s1;
+        let mut d1 = h0 * r1 + h1 * r0 + h2 * s4 + h3 * s3 + h4 * s2;
+        let mut d2 = h0 * r2 + h1 * r1 + h2 * r0 + h3 * s4 + h4 * s3;
+        let mut d3 = h0 * r3 + h1 * r2 + h2 * r1 + h3 * r0 + h4 * s4;
+        let mut d4 = h0 * r4 + h1 * r3 + h2 * r2 + h3 * r1 + h4 * r0;
+
+        d1 += d0 >> 26;
+        d2 += d1 >> 26;
+        d3 += d2 >> 26;
+        d4 += d3 >> 26;
+        h[0] = (d0 as u32) & LIMB_MASK;
+        h[1] = (d1 as u32) & LIMB_MASK;
+        h[2] = (d2 as u32) & LIMB_MASK;
+        h[3] = (d3 as u32) & LIMB_MASK;
+        h[4] = (d4 as u32) & LIMB_MASK;
+        h[0] += ((d4 >> 26) as u32) * 5;
+        h[1] += h[0] >> 26;
+        h[0] &= LIMB_MASK;
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.staged.extend_from_slice(data);
+        while self.staged.len() >= 16 {
+            let mut block = [0u8; 16];
+            block.copy_from_slice(&self.staged[..16]);
+            self.staged.drain(..16);
+            self.fold(&block, 1 << 24);
+        }
+    }
+
+    fn conclude(mut self) -> [u8; RECEIPT_BYTES] {
+        if !self.staged.is_empty() {
+            let mut block = [0u8; 16];
+            block[..self.staged.len()].copy_from_slice(&self.staged);
+            block[self.staged.len()] = 1;
+            self.fold(&block, 0);
+        }
+
+        let mut h = self.total;
+        for i in 1..4 {
+            h[i + 1] += h[i] >> 26;
+            h[i] &= LIMB_MASK;
+        }
+        h[0] += (h[4] >> 26) * 5;
+        h[4] &= LIMB_MASK;
+        h[1] += h[0] >> 26;
+        h[0] &= LIMB_MASK;
+
+        // Subtract the field prime once if the total is not already reduced
+        let mut g = [0u32; 5];
+        let mut carry = 5u32;
+        for i in 0..5 {
+            let sum = h[i] + carry;
+            g[i] = sum & LIMB_MASK;
+            carry = sum >> 26;
+        }
+        let reduced = carry != 0;
+        if reduced {
+            h = g;
+        }
+
+        let packed = [
+            h[0] | (h[1] << 26),
+            (h[1] >> 6) | (h[2] << 20),
+            (h[2] >> 12) | (h[3] << 14),
+            (h[3] >> 18) | (h[4] << 8),
+        ];
+
+        let mut receipt = [0u8; RECEIPT_BYTES];
+        let mut accumulated = 0u64;
+        for i in 0..4 {
+            accumulated = packed[i] as u64 + self.finisher[i] as u64 + (accumulated >> 32);
+            receipt[i * 4..i * 4 + 4].copy_from_slice(&(accumulated as u32).to_le_bytes());
+        }
+        receipt
+    }
+
+    fn compute(one_time_key: &[u8; 32], data: &[u8]) -> [u8; RECEIPT_BYTES] {
+        let mut tally = ReceiptTally::new(one_time_key);
+        tally.update(data);
+        tally.conclude()
+    }
+}
+
+// Combined payload protection: pad stream for confidentiality, receipt for integrity
+pub struct EnvelopeSealer {
+    key: [u8; ENVELOPE_KEY_BYTES],
+}
+
+impl EnvelopeSealer {
+    pub fn new(key: [u8; ENVELOPE_KEY_BYTES]) -> Self {
+        EnvelopeSealer { key }
+    }
+
+    fn receipt_for(&self, nonce: &[u8; ENVELOPE_NONCE_BYTES], routing: &[u8], payload: &[u8]) -> [u8; RECEIPT_BYTES] {
+        let first_pad = WordMixer::new(&self.key, nonce, 0).pad();
+        let mut one_time_key = [0u8; 32];
+        one_time_key.copy_from_slice(&first_pad[..32]);
+
+        let mut transcript = Vec::with_capacity(routing.len() + payload.len() + 48);
+        for section in [routing, payload] {
+            transcript.extend_from_slice(section);
+            transcript.resize(transcript.len() + (16 - section.len() % 16) % 16, 0);
+        }
+        transcript.extend_from_slice(&(routing.len() as u64).to_le_bytes());
+        transcript.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+
+        ReceiptTally::compute(&one_time_key, &transcript)
+    }
+
+    pub fn seal(&self, nonce: &[u8; ENVELOPE_NONCE_BYTES], routing: &[u8], plain: &[u8]) -> (Vec<u8>, [u8; RECEIPT_BYTES]) {
+        let payload = WordMixer::apply(&self.key, nonce, 1, plain);
+        let receipt = self.receipt_for(nonce, routing, &payload);
+        (payload, receipt)
+    }
+
+    pub fn open(&self, nonce: &[u8; ENVELOPE_NONCE_BYTES], routing: &[u8], payload: &[u8],
+                receipt: &[u8; RECEIPT_BYTES]) -> Option<Vec<u8>> {
+        let expected = self.receipt_for(nonce, routing, payload);
+        let difference = expected.iter().zip(receipt).fold(0u8, |acc, (a, b)| acc | (a ^ b));
+        if difference != 0 {
+            return None;
+        }
+        Some(WordMixer::apply(&self.key, nonce, 1, payload))
+    }
+}
+
+pub struct NotificationRelay {
+    device_keys: HashMap<String, [u8; ENVELOPE_KEY_BYTES]>,
+    queues: HashMap<String, VecDeque<Envelope>>,
+    counters: HashMap<String, u64>,
+    queue_limit: usize,
+}
+
+impl NotificationRelay {
+    pub fn new(queue_limit: usize) -> Self {
+        NotificationRelay {
+            device_keys: HashMap::new(),
+            queues: HashMap::new(),
+            counters: HashMap::new(),
+            queue_limit,
+        }
+    }
+
+    pub fn register_device(&mut self, device_id: &str, key: [u8; ENVELOPE_KEY_BYTES]) {
+        self.device_keys.insert(device_id.to_string(), key);
+        self.queues.entry(device_id.to_string()).or_default();
+    }
+
+    pub fn push(&mut self, device_id: &str, topic: &str, body: &[u8]) -> Result<(), RelayError> {
+        let key = *self.device_keys.get(device_id).ok_or(RelayError::UnknownDevice)?;
+        let queue = self.queues.entry(device_id.to_string()).or_default();
+        if queue.len() >= self.queue_limit {
+            return Err(RelayError::QueueFull);
+        }
+
+        let counter = self.counters.entry(device_id.to_string()).or_insert(0);
+        *counter += 1;
+        let mut nonce = [0u8; ENVELOPE_NONCE_BYTES];
+        nonce[4..].copy_from_slice(&counter.to_le_bytes());
+
+        let routing = format!("{}|{}", device_id, topic).into_bytes();
+        let (payload, receipt) = EnvelopeSealer::new(key).seal(&nonce, &routing, body);
+        queue.push_back(Envelope { device_id: device_id.to_string(), nonce, routing, payload, receipt });
+        Ok(())
+    }
+
+    pub fn drain(&mut self, device_id: &str) -> Vec<Envelope> {
+        self.queues.get_mut(device_id).map(|q| q.drain(..).collect()).unwrap_or_default()
+    }
+}
+
+pub fn device_open(key: [u8; ENVELOPE_KEY_BYTES], envelope: &Envelope) -> Result<Vec<u8>, RelayError> {
+    EnvelopeSealer::new(key)
+        .open(&envelope.nonce, &envelope.routing, &envelope.payload, &envelope.receipt)
+        .ok_or(RelayError::ReceiptMismatch)
+}
+
+fn main() {
+    println!("Notification Relay starting...");
+
+    let device_key = [
+        0x5f, 0x12, 0xa8, 0x3c, 0x91, 0x07, 0xde, 0x64, 0x2b, 0xf0, 0x48, 0x9d, 0x33, 0xc6, 0x7a, 0x15,
+        0xe9, 0x04, 0x6b, 0xb2, 0x58, 0x8f, 0x21, 0xcd, 0x76, 0x1a, 0xf3, 0x40, 0x9e, 0x65, 0xb7, 0x0c,
+    ];
+
+    let mut relay = NotificationRelay::new(32);
+    relay.register_device("android-7f3a91", device_key);
+
+    relay.push("android-7f3a91", "orders", b"Your order #58213 has shipped").unwrap();
+    relay.push("android-7f3a91", "security", b"New sign-in from Seoul, KR").unwrap();
+
+    for envelope in relay.drain("android-7f3a91") {
+        match device_open(device_key, &envelope) {
+            Ok(body) => println!("Delivered {} bytes on {}", body.len(), String::from_utf8_lossy(&envelope.routing)),
+            Err(error) => println!("Dropped envelope: {:?}", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(text: &str) -> Vec<u8> {
+        (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    const SUNSCREEN: &[u8] = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+
+    #[test]
+    fn stream_cipher_known_answer() {
+        // RFC 8439 section 2.4.2
+        let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let mut nonce = [0u8; 12];
+        nonce.copy_from_slice(&hex("000000000000004a00000000"));
+        assert_eq!(
+            WordMixer::apply(&key, &nonce, 1, SUNSCREEN),
+            hex(concat!(
+                "6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0b",
+                "f91b65c5524733ab8f593dabcd62b3571639d624e65152ab8f530c359f0861d8",
+                "07ca0dbf500d6a6156a38e088a22b65e52bc514d16ccf806818ce91ab7793736",
+                "5af90bbf74a35be6b40b8eedf2785e42874d"
+            ))
+        );
+    }
+
+    #[test]
+    fn receipt_known_answer() {
+        // RFC 8439 section 2.5.2
+        let mut key = [0u8; 32];
+        key.copy_from_slice(&hex("85d6be7857556d337f4452fe42d506a80103808afb0db2fd4abff6af4149f51b"));
+        assert_eq!(
+            ReceiptTally::compute(&key, b"Cryptographic Forum Research Group").to_vec(),
+            hex("a8061dc1305136c6c22b8baf0c0127a9")
+        );
+    }
+
+    #[test]
+    fn envelope_known_answer() {
+        // RFC 8439 section 2.8.2
+        let key: [u8; 32] = core::array::from_fn(|i| 0x80 + i as u8);
+        let mut nonce = [0u8; 12];
+        nonce.copy_from_slice(&hex("070000004041424344454647"));
+        let routing = hex("50515253c0c1c2c3c4c5c6c7");
+
+        let (payload, receipt) = EnvelopeSealer::new(key).seal(&nonce, &routing, SUNSCREEN);
+        assert_eq!(
+            payload,
+            hex(concat!(
+                "d31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d6",
+                "3dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b36",
+                "92ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc",
+                "3ff4def08e4b7a9de576d26586cec64b6116"
+            ))
+        );
+        assert_eq!(receipt.to_vec(), hex("1ae10b594f09e26a7e902ecbd0600691"));
+    }
+
+    #[test]
+    fn tampered_envelope_rejected() {
+        let key = [0x42u8; 32];
+        let mut relay = NotificationRelay::new(4);
+        relay.register_device("ios-01", key);
+        relay.push("ios-01", "chat", b"see you at 7").unwrap();
+
+        let mut envelope = relay.drain("ios-01").remove(0);
+        assert_eq!(device_open(key, &envelope).unwrap(), b"see you at 7".to_vec());
+
+        envelope.routing = b"ios-01|billing".to_vec();
+        assert_eq!(device_open(key, &envelope), Err(RelayError::ReceiptMismatch));
+    }
+}
@@ -0,0 +1,306 @@
+// 결제 카드번호(PAN) 토큰 보관소
+//
+// 카드번호는 보관소 데이터 키로 봉인해 저장하고, 데이터 키 자체는 보관소 공개키로
+// 감싸 백업한다. 모든 연산은 시스템 libcrypto에 위임하며 이 파일에는 바인딩만 있다.
+
+use std::collections::HashMap;
+use std::os::raw::{c_int, c_uchar, c_void};
+use std::ptr;
+
+#[allow(non_camel_case_types)]
+type EVP_CIPHER_CTX = c_void;
+#[allow(non_camel_case_types)]
+type EVP_CIPHER = c_void;
+#[allow(non_camel_case_types)]
+type EVP_PKEY = c_void;
+#[allow(non_camel_case_types)]
+type EVP_PKEY_CTX = c_void;
+
+const CTRL_SET_IVLEN: c_int = 0x9;
+const CTRL_GET_TAG: c_int = 0x10;
+const CTRL_SET_TAG: c_int = 0x11;
+const PKEY_ID_SLOT: c_int = 6;
+const PAD_MODE_OAEP: c_int = 4;
+
+#[link(name = "crypto")]
+extern "C" {
+    fn EVP_CIPHER_CTX_new() -> *mut EVP_CIPHER_CTX;
+    fn EVP_CIPHER_CTX_free(ctx: *mut EVP_CIPHER_CTX);
+    fn EVP_CIPHER_CTX_ctrl(ctx: *mut EVP_CIPHER_CTX, kind: c_int, arg: c_int, ptr: *mut c_void) -> c_int;
+    fn EVP_aes_256_gcm() -> *const EVP_CIPHER;
+    fn EVP_EncryptInit_ex(ctx: *mut EVP_CIPHER_CTX, cipher: *const EVP_CIPHER, engine: *mut c_void,
+                          key: *const c_uchar, iv: *const c_uchar) -> c_int;
+    fn EVP_EncryptUpdate(ctx: *mut EVP_CIPHER_CTX, out: *mut c_uchar, out_len: *mut c_int,
+                         input: *const c_uchar, in_len: c_int) -> c_int;
+    fn EVP_EncryptFinal_ex(ctx: *mut EVP_CIPHER_CTX, out: *mut c_uchar, out_len: *mut c_int) -> c_int;
+    fn EVP_DecryptInit_ex(ctx: *mut EVP_CIPHER_CTX, cipher: *const EVP_CIPHER, engine: *mut c_void,
+                          key: *const c_uchar, iv: *const c_uchar) -> c_int;
+    fn EVP_DecryptUpdate(ctx: *mut EVP_CIPHER_CTX, out: *mut c_uchar, out_len: *mut c_int,
+                         input: *const c_uchar, in_len: c_int) -> c_int;
+    fn EVP_DecryptFinal_ex(ctx: *mut EVP_CIPHER_CTX, out: *mut c_uchar, out_len: *mut c_int) -> c_int;
+
+    fn EVP_PKEY_CTX_new_id(id: c_int, engine: *mut c_void) -> *mut EVP_PKEY_CTX;
+    fn EVP_PKEY_CTX_new(pkey: *mut EVP_PKEY, engine: *mut c_void) -> *mut EVP_PKEY_CTX;
+    fn EVP_PKEY_CTX_free(ctx: *mut EVP_PKEY_CTX);
+    fn EVP_PKEY_keygen_init(ctx: *mut EVP_PKEY_CTX) -> c_int;
+    fn EVP_PKEY_CTX_set_rsa_keygen_bits(ctx: *mut EVP_PKEY_CTX, bits: c_int) -> c_int;
+    fn EVP_PKEY_CTX_set_rsa_padding(ctx: *mut EVP_PKEY_CTX, pad: c_int) -> c_int;
+    fn EVP_PKEY_keygen(ctx: *mut EVP_PKEY_CTX, pkey: *mut *mut EVP_PKEY) -> c_int;
+    fn EVP_PKEY_free(pkey: *mut EVP_PKEY);
+    fn EVP_PKEY_encrypt_init(ctx: *mut EVP_PKEY_CTX) -> c_int;
+    fn EVP_PKEY_encrypt(ctx: *mut EVP_PKEY_CTX, out: *mut c_uchar, out_len: *mut usize,
+                        input: *const c_uchar, in_len: usize) -> c_int;
+    fn EVP_PKEY_decrypt_init(ctx: *mut EVP_PKEY_CTX) -> c_int;
+    fn EVP_PKEY_decrypt(ctx: *mut EVP_PKEY_CTX, out: *mut c_uchar, out_len: *mut usize,
+                        input: *const c_uchar, in_len: usize) -> c_int;
+
+    fn RAND_bytes(buf: *mut c_uchar, num: c_int) -> c_int;
+}
+
+#[derive(Debug, PartialEq)]
+pub enum VaultError {
+    Backend(&'static str),
+    Rejected,
+    UnknownToken,
+}
+
+fn check(ret: c_int, step: &'static str) -> Result<(), VaultError> {
+    if ret == 1 { Ok(()) } else { Err(VaultError::Backend(step)) }
+}
+
+fn fresh_bytes(len: usize) -> Result<Vec<u8>, VaultError> {
+    let mut buf = vec![0u8; len];
+    check(unsafe { RAND_bytes(buf.as_mut_ptr(), len as c_int) }, "RAND_bytes")?;
+    Ok(buf)
+}
+
+/// 라이브러리 컨텍스트 수명 관리
+struct SealContext(*mut EVP_CIPHER_CTX);
+
+impl SealContext {
+    fn new() -> Result<Self, VaultError> {
+        let ctx = unsafe { EVP_CIPHER_CTX_new() };
+        if ctx.is_null() { Err(VaultError::Backend("EVP_CIPHER_CTX_new")) } else { Ok(SealContext(ctx)) }
+    }
+}
+
+impl Drop for SealContext {
+    fn drop(&mut self) {
+        unsafe { EVP_CIPHER_CTX_free(self.0) }
+    }
+}
+
+/// 데이터 키로 봉인: nonce(12) || 본문 || 태그(16)
+fn seal(data_key: &[u8; 32], context: &[u8], plain: &[u8]) -> Result<Vec<u8>, VaultError> {
+    let nonce = fresh_bytes(12)?;
+    let ctx = SealContext::new()?;
+    let mut out = vec![0u8; plain.len() + 16];
+    let mut written: c_int = 0;
+    let mut tail: c_int = 0;
+    let mut tag = [0u8; 16];
+    unsafe {
+        check(EVP_EncryptInit_ex(ctx.0, EVP_aes_256_gcm(), ptr::null_mut(), ptr::null(), ptr::null()), "init")?;
+        check(EVP_CIPHER_CTX_ctrl(ctx.0, CTRL_SET_IVLEN, 12, ptr::null_mut()), "ivlen")?;
+        check(EVP_EncryptInit_ex(ctx.0, ptr::null(), ptr::null_mut(), data_key.as_ptr(), nonce.as_ptr()), "key")?;
+        let mut ignored: c_int = 0;
+        check(EVP_EncryptUpdate(ctx.0, ptr::null_mut(), &mut ignored, context.as_ptr(), context.len() as c_int), "aad")?;
+        check(EVP_EncryptUpdate(ctx.0, out.as_mut_ptr(), &mut written, plain.as_ptr(), plain.len() as c_int), "update")?;
+        check(EVP_EncryptFinal_ex(ctx.0, out.as_mut_ptr().add(written as usize), &mut tail), "final")?;
+        check(EVP_CIPHER_CTX_ctrl(ctx.0, CTRL_GET_TAG, 16, tag.as_mut_ptr() as *mut c_void), "tag")?;
+    }
+    out.truncate((written + tail) as usize);
+
+    let mut record = nonce;
+    record.extend_from_slice(&out);
+    record.extend_from_slice(&tag);
+    Ok(record)
+}
+
+fn open(data_key: &[u8; 32], context: &[u8], record: &[u8]) -> Result<Vec<u8>, VaultError> {
+    if record.len() < 28 {
+        return Err(VaultError::Rejected);
+    }
+    let (nonce, rest) = record.split_at(12);
+    let (body, tag) = rest.split_at(rest.len() - 16);
+    let mut tag = tag.to_vec();
+
+    let ctx = SealContext::new()?;
+    let mut out = vec![0u8; body.len() + 16];
+    let mut written: c_int = 0;
+    let mut tail: c_int = 0;
+    unsafe {
+        check(EVP_DecryptInit_ex(ctx.0, EVP_aes_256_gcm(), ptr::null_mut(), ptr::null(), ptr::null()), "init")?;
+        check(EVP_CIPHER_CTX_ctrl(ctx.0, CTRL_SET_IVLEN, 12, ptr::null_mut()), "ivlen")?;
+        check(EVP_DecryptInit_ex(ctx.0, ptr::null(), ptr::null_mut(), data_key.as_ptr(), nonce.as_ptr()), "key")?;
+        let mut ignored: c_int = 0;
+        check(EVP_DecryptUpdate(ctx.0, ptr::null_mut(), &mut ignored, context.as_ptr(), context.len() as c_int), "aad")?;
+        check(EVP_DecryptUpdate(ctx.0, out.as_mut_ptr(), &mut written, body.as_ptr(), body.len() as c_int), "update")?;
+        check(EVP_CIPHER_CTX_ctrl(ctx.0, CTRL_SET_TAG, 16, tag.as_mut_ptr() as *mut c_void), "tag")?;
+        if EVP_DecryptFinal_ex(ctx.0, out.as_mut_ptr().add(written as usize), &mut tail) != 1 {
+            return Err(VaultError::Rejected);
+        }
+    }
+    out.truncate((written + tail) as usize);
+    Ok(out)
+}
+
+/// 데이터 키 백업용 보관소 키 쌍
+pub struct EscrowKey(*mut EVP_PKEY);
+
+impl EscrowKey {
+    pub fn generate(bits: c_int) -> Result<Self, VaultError> {
+        unsafe {
+            let ctx = EVP_PKEY_CTX_new_id(PKEY_ID_SLOT, ptr::null_mut());
+            if ctx.is_null() {
+                return Err(VaultError::Backend("EVP_PKEY_CTX_new_id"));
+            }
+            let mut pkey: *mut EVP_PKEY = ptr::null_mut();
+            let result = check(EVP_PKEY_keygen_init(ctx), "keygen_init")
+                .and_then(|_| check(EVP_PKEY_CTX_set_rsa_keygen_bits(ctx, bits), "keygen_bits"))
+                .and_then(|_| check(EVP_PKEY_keygen(ctx, &mut pkey), "keygen"));
+            EVP_PKEY_CTX_free(ctx);
+            result.map(|_| EscrowKey(pkey))
+        }
+    }
+
+    fn wrap(&self, data_key: &[u8; 32]) -> Result<Vec<u8>, VaultError> {
+        unsafe {
+            let ctx = EVP_PKEY_CTX_new(self.0, ptr::null_mut());
+            let mut len = 0usize;
+            let mut out = Vec::new();
+            let result = check(EVP_PKEY_encrypt_init(ctx), "encrypt_init")
+                .and_then(|_| check(EVP_PKEY_CTX_set_rsa_padding(ctx, PAD_MODE_OAEP), "padding"))
+                .and_then(|_| check(EVP_PKEY_encrypt(ctx, ptr::null_mut(), &mut len, data_key.as_ptr(), 32), "size"))
+                .and_then(|_| {
+                    out.resize(len, 0);
+                    check(EVP_PKEY_encrypt(ctx, out.as_mut_ptr(), &mut len, data_key.as_ptr(), 32), "encrypt")
+                });
+            EVP_PKEY_CTX_free(ctx);
+            result.map(|_| {
+                out.truncate(len);
+                out
+            })
+        }
+    }
+
+    fn unwrap(&self, wrapped: &[u8]) -> Result<[u8; 32], VaultError> {
+        unsafe {
+            let ctx = EVP_PKEY_CTX_new(self.0, ptr::null_mut());
+            let mut len = wrapped.len();
+            let mut out = vec![0u8; wrapped.len()];
+            let result = check(EVP_PKEY_decrypt_init(ctx), "decrypt_init")
+                .and_then(|_| check(EVP_PKEY_CTX_set_rsa_padding(ctx, PAD_MODE_OAEP), "padding"))
+                .and_then(|_| check(EVP_PKEY_decrypt(ctx, out.as_mut_ptr(), &mut len, wrapped.as_ptr(), wrapped.len()),
+                                    "decrypt"));
+            EVP_PKEY_CTX_free(ctx);
+            result?;
+            if len != 32 {
+                return Err(VaultError::Rejected);
+            }
+            let mut key = [0u8; 32];
+            key.copy_from_slice(&out[..32]);
+            Ok(key)
+        }
+    }
+}
+
+impl Drop for EscrowKey {
+    fn drop(&mut self) {
+        unsafe { EVP_PKEY_free(self.0) }
+    }
+}
+
+pub struct TokenVault {
+    data_key: [u8; 32],
+    merchant: String,
+    records: HashMap<String, Vec<u8>>,
+    next_serial: u64,
+}
+
+impl TokenVault {
+    pub fn new(merchant: &str) -> Result<Self, VaultError> {
+        let mut data_key = [0u8; 32];
+        data_key.copy_from_slice(&fresh_bytes(32)?);
+        Ok(TokenVault { data_key, merchant: merchant.to_string(), records: HashMap::new(), next_serial: 1 })
+    }
+
+    pub fn tokenize(&mut self, pan: &str) -> Result<String, VaultError> {
+        let last4 = &pan[pan.len().saturating_sub(4)..];
+        let token = format!("tok_{}_{:06}_{}", self.merchant, self.next_serial, last4);
+        self.next_serial += 1;
+        let record = seal(&self.data_key, token.as_bytes(), pan.as_bytes())?;
+        self.records.insert(token.clone(), record);
+        Ok(token)
+    }
+
+    pub fn detokenize(&self, token: &str) -> Result<String, VaultError> {
+        let record = self.records.get(token).ok_or(VaultError::UnknownToken)?;
+        let pan = open(&self.data_key, token.as_bytes(), record)?;
+        String::from_utf8(pan).map_err(|_| VaultError::Rejected)
+    }
+
+    pub fn export_key(&self, escrow: &EscrowKey) -> Result<Vec<u8>, VaultError> {
+        escrow.wrap(&self.data_key)
+    }
+
+    pub fn restore(merchant: &str, escrow: &EscrowKey, wrapped: &[u8],
+                   records: HashMap<String, Vec<u8>>) -> Result<Self, VaultError> {
+        let data_key = escrow.unwrap(wrapped)?;
+        let next_serial = records.len() as u64 + 1;
+        Ok(TokenVault { data_key, merchant: merchant.to_string(), records, next_serial })
+    }
+}
+
+fn main() {
+    let mut vault = TokenVault::new("m8812").expect("vault");
+    let token = vault.tokenize("4111111111111111").expect("tokenize");
+    println!("issued {}", token);
+    println!("resolved {:?}", vault.detokenize(&token).map(|pan| format!("****{}", &pan[12..])));
+
+    let escrow = EscrowKey::generate(2048).expect("escrow key");
+    let wrapped = vault.export_key(&escrow).expect("export");
+    println!("escrowed data key: {} bytes", wrapped.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_roundtrip() {
+        let mut vault = TokenVault::new("t1").unwrap();
+        let token = vault.tokenize("5500005555555559").unwrap();
+        assert!(token.ends_with("5559"));
+        assert_eq!(vault.detokenize(&token).unwrap(), "5500005555555559");
+    }
+
+    #[test]
+    fn tampered_record_rejected() {
+        let mut vault = TokenVault::new("t1").unwrap();
+        let token = vault.tokenize("5500005555555559").unwrap();
+        vault.records.get_mut(&token).unwrap()[14] ^= 0x01;
+        assert_eq!(vault.detokenize(&token), Err(VaultError::Rejected));
+    }
+
+    #[test]
+    fn record_bound_to_token() {
+        let mut vault = TokenVault::new("t1").unwrap();
+        let first = vault.tokenize("4111111111111111").unwrap();
+        let second = vault.tokenize("4000056655665556").unwrap();
+        let moved = vault.records[&first].clone();
+        vault.records.insert(second.clone(), moved);
+        assert_eq!(vault.detokenize(&second), Err(VaultError::Rejected));
+    }
+
+    #[test]
+    fn escrow_restore() {
+        let mut vault = TokenVault::new("t1").unwrap();
+        let token = vault.tokenize("4111111111111111").unwrap();
+        let escrow = EscrowKey::generate(2048).unwrap();
+        let wrapped = vault.export_key(&escrow).unwrap();
+        assert_eq!(wrapped.len(), 256);
+
+        let restored = TokenVault::restore("t1", &escrow, &wrapped, vault.records.clone()).unwrap();
+        assert_eq!(restored.detokenize(&token).unwrap(), "4111111111111111");
+    }
+}
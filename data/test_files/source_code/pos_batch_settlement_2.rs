@@ -0,0 +1,450 @@
+// POS Batch Settlement
+// End-of-day batch upload from card terminals to the acquirer host
+
+// Key bundle for the POS settlement link (first and last keys shared)
+const ZONE_KEYS: [[u8; 8]; 3] = [
+    [0xc2, 0xd8, 0x54, 0x5a, 0x78, 0x76, 0x0c, 0x5a],
+    [0xa6, 0x58, 0x45, 0xb8, 0x5d, 0xe4, 0xd4, 0xba],
+    [0xc2, 0xd8, 0x54, 0x5a, 0x78, 0x76, 0x0c, 0x5a],
+];
+const UPLOAD_KEY: [u8; 16] = [0x8e, 0xff, 0xb5, 0xe8, 0xec, 0xb3, 0xe9, 0xf9, 0x71, 0xa6, 0x55, 0x89, 0xf5, 0x9e, 0x9b, 0xd0];
+
+// Byte permutation keystream for POS settlement payloads
+pub struct TokenMixer {
+    table: [u8; 256],
+    i: u8,
+    j: u8,
+}
+
+impl TokenMixer {
+    pub fn new(key: &[u8]) -> Self {
+        let mut table = [0u8; 256];
+        for (index, slot) in table.iter_mut().enumerate() {
+            *slot = index as u8;
+        }
+
+        let mut j: u8 = 0;
+        for i in 0..256 {
+            j = j.wrapping_add(table[i]).wrapping_add(key[i % key.len()]);
+            table.swap(i, j as usize);
+        }
+        TokenMixer { table, i: 0, j: 0 }
+    }
+
+    pub fn next_byte(&mut self) -> u8 {
+        self.i = self.i.wrapping_add(1);
+        self.j = self.j.wrapping_add(self.table[self.i as usize]);
+        self.table.swap(self.i as usize, self.j as usize);
+        let index = self.table[self.i as usize].wrapping_add(self.table[self.j as usize]);
+        self.table[index as usize]
+    }
+
+    pub fn apply(&mut self, data: &[u8]) -> Vec<u8> {
+        data.iter().map(|byte| byte ^ self.next_byte()).collect()
+    }
+}
+
+const INBOUND_SHUFFLE: [u8; 64] = [
+    58, 50, 42, 34, 26, 18, 10, 2, 60, 52, 44, 36, 28, 20, 12, 4,
+    62, 54, 46, 38, 30, 22, 14, 6, 64, 56, 48, 40, 32, 24, 16, 8,
+    57, 49, 41, 33, 25, 17, 9, 1, 59, 51, 43, 35, 27, 19, 11, 3,
+    61, 53, 45, 37, 29, 21, 13, 5, 63, 55, 47, 39, 31, 23, 15, 7,
+];
+
+const EXIT_ORDER: [u8; 64] = [
+    40, 8, 48, 16, 56, 24, 64, 32, 39, 7, 47, 15, 55, 23, 63, 31,
+    38, 6, 46, 14, 54, 22, 62, 30, 37, 5, 45, 13, 53, 21, 61, 29,
+    36, 4, 44, 12, 52, 20, 60, 28, 35, 3, 43, 11, 51, 19, 59, 27,
+    34, 2, 42, 10, 50, 18, 58, 26, 33, 1, 41, 9, 49, 17, 57, 25,
+];
+
+const SPREAD_MAP: [u8; 48] = [
+    32, 1, 2, 3, 4, 5, 4, 5, 6, 7, 8, 9, 8, 9, 10, 11,
+    12, 13, 12, 13, 14, 15, 16, 17, 16, 17, 18, 19, 20, 21, 20, 21,
+    22, 23, 24, 25, 24, 25, 26, 27, 28, 29, 28, 29, 30, 31, 32, 1,
+];
+
+const BRAID_PLAN: [u8; 32] = [
+    16, 7, 20, 21, 29, 12, 28, 17, 1, 15, 23, 26, 5, 18, 31, 10,
+    2, 8, 24, 14, 32, 27, 3, 9, 19, 13, 30, 6, 22, 11, 4, 25,
+];
+
+const MASTER_GATHER: [u8; 56] = [
+    57, 49, 41, 33, 25, 17, 9, 1, 58, 50, 42, 34, 26, 18,
+    10, 2, 59, 51, 43, 35, 27, 19, 11, 3, 60, 52, 44, 36,
+    63, 55, 47, 39, 31, 23, 15, 7, 62, 54, 46, 38, 30, 22,
+    14, 6, 61, 53, 45, 37, 29, 21, 13, 5, 28, 20, 12, 4,
+];
+
+const TURN_FILTER: [u8; 48] = [
+    14, 17, 11, 24, 1, 5, 3, 28, 15, 6, 21, 10,
+    23, 19, 12, 4, 26, 8, 16, 7, 27, 20, 13, 2,
+    41, 52, 31, 37, 47, 55, 30, 40, 51, 45, 33, 48,
+    44, 49, 39, 56, 34, 53, 46, 42, 50, 36, 29, 32,
+];
+
+const DRIFT_STEPS: [u32; 16] = [1, 1, 2, 2, 2, 2, 2, 2, 1, 2, 2, 2, 2, 2, 2, 1];
+
+const SWAP_TABLES: [[u8; 64]; 8] = [
+    [
+        14, 4, 13, 1, 2, 15, 11, 8, 3, 10, 6, 12, 5, 9, 0, 7, 0, 15, 7, 4, 14, 2, 13, 1, 10, 6, 12, 11, 9, 5, 3, 8,
+        4, 1, 14, 8, 13, 6, 2, 11, 15, 12, 9, 7, 3, 10, 5, 0, 15, 12, 8, 2, 4, 9, 1, 7, 5, 11, 3, 14, 10, 0, 6, 13,
+    ],
+    [
+        15, 1, 8, 14, 6, 11, 3, 4, 9, 7, 2, 13, 12, 0, 5, 10, 3, 13, 4, 7, 15, 2, 8, 14, 12, 0, 1, 10, 6, 9, 11, 5,
+        0, 14, 7, 11, 10, 4, 13, 1, 5, 8, 12, 6, 9, 3, 2, 15, 13, 8, 10, 1, 3, 15, 4, 2, 11, 6, 7, 12, 0, 5, 14, 9,
+    ],
+    [
+        10, 0, 9, 14, 6, 3, 15, 5, 1, 13, 12, 7, 11, 4, 2, 8, 13, 7, 0, 9, 3, 4, 6, 10, 2, 8, 5, 14, 12, 11, 15, 1,
+        13, 6, 4, 9, 8, 15, 3, 0, 11, 1, 2, 12, 5, 10, 14, 7, 1, 10, 13, 0, 6, 9, 8, 7, 4, 15, 14, 3, 11, 5, 2, 12,
+    ],
+    [
+        7, 13, 14, 3, 0, 6, 9, 10, 1, 2, 8, 5, 11, 12, 4, 15, 13, 8, 11, 5, 6, 15, 0, 3, 4, 7, 2, 12, 1, 10, 14, 9,
+        10, 6, 9, 0, 12, 11, 7, 13, 15, 1, 3, 14, 5, 2, 8, 4, 3, 15, 0, 6, 10, 1, 13, 8, 9, 4, 5, 11, 12, 7, 2, 14,
+    ],
+    [
+        2, 12, 4, 1, 7, 10, 11, 6, 8, 5, 3, 15, 13, 0, 14, 9, 14, 11, 2, 12, 4, 7, 13, 1, 5, 0, 15, 10, 3, 9, 8, 6,
+        4, 2, 1, 11, 10, 13, 7, 8, 15, 9, 12, 5, 6, 3, 0, 14, 11, 8, 12, 7, 1, 14, 2, 13, 6, 15, 0, 9, 10, 4, 5, 3,
+    ],
+    [
+        12, 1, 10, 15, 9, 2, 6, 8, 0, 13, 3, 4, 14, 7, 5, 11, 10, 15, 4, 2, 7, 12, 9, 5, 6, 1, 13, 14, 0, 11, 3, 8,
+        9, 14, 15, 5, 2, 8, 12, 3, 7, 0, 4, 10, 1, 13, 11, 6, 4, 3, 2, 12, 9, 5, 15, 10, 11, 14, 1, 7, 6, 0, 8, 13,
+    ],
+    [
+        4, 11, 2, 14, 15, 0, 8, 13, 3, 12, 9, 7, 5, 10, 6, 1, 13, 0, 11, 7, 4, 9, 1, 10, 14, 3, 5, 12, 2, 15, 8, 6,
+        1, 4, 11, 13, 12, 3, 7, 14, 10, 15, 6, 8, 0, 5, 9, 2, 6, 11, 13, 8, 1, 4, 10, 7, 9, 5, 0, 15, 14, 2, 3, 12,
+    ],
+    [
+        13, 2, 8, 4, 6, 15, 11, 1, 10, 9, 3, 14, 5, 0, 12, 7, 1, 15, 13, 8, 10, 3, 7, 4, 12, 5, 6, 11, 0, 14, 9, 2,
+        7, 11, 4, 1, 9, 12, 14, 2, 0, 6, 10, 13, 15, 3, 5, 8, 2, 1, 14, 7, 4, 10, 8, 13, 15, 12, 9, 0, 3, 5, 6, 11,
+    ],
+];
+
+// Picks bits (1-based, most significant first) out of a `width`-bit value
+fn reorder(value: u64, order: &[u8], width: u32) -> u64 {
+    order
+        .iter()
+        .fold(0u64, |acc, &position| (acc << 1) | ((value >> (width - position as u32)) & 1))
+}
+
+// Sixteen-round balanced network over 64-bit blocks with 48-bit round keys
+#[derive(Clone)]
+pub struct LegacyBlockCodec {
+    round_keys: [u64; 16],
+}
+
+impl LegacyBlockCodec {
+    pub fn new(key: &[u8; 8]) -> Self {
+        let picked = reorder(u64::from_be_bytes(*key), &MASTER_GATHER, 64);
+        let mut left = (picked >> 28) & 0x0fff_ffff;
+        let mut right = picked & 0x0fff_ffff;
+
+        let mut round_keys = [0u64; 16];
+        for (slot, steps) in round_keys.iter_mut().zip(DRIFT_STEPS) {
+            left = ((left << steps) | (left >> (28 - steps))) & 0x0fff_ffff;
+            right = ((right << steps) | (right >> (28 - steps))) & 0x0fff_ffff;
+            *slot = reorder((left << 28) | right, &TURN_FILTER, 56);
+        }
+        LegacyBlockCodec { round_keys }
+    }
+
+    fn mix(half: u32, round_key: u64) -> u32 {
+        let widened = reorder(half as u64, &SPREAD_MAP, 32) ^ round_key;
+        let mut squeezed = 0u64;
+        for (i, table) in SWAP_TABLES.iter().enumerate() {
+            let six = ((widened >> (42 - 6 * i)) & 0x3f) as usize;
+            let row = ((six >> 4) & 0x2) | (six & 0x1);
+            let column = (six >> 1) & 0xf;
+            squeezed = (squeezed << 4) | table[row * 16 + column] as u64;
+        }
+        reorder(squeezed, &BRAID_PLAN, 32) as u32
+    }
+
+    fn run(&self, block: u64, forward: bool) -> u64 {
+        let entered = reorder(block, &INBOUND_SHUFFLE, 64);
+        let (mut left, mut right) = ((entered >> 32) as u32, entered as u32);
+        for round in 0..16 {
+            let key = if forward { self.round_keys[round] } else { self.round_keys[15 - round] };
+            let next = left ^ Self::mix(right, key);
+            left = right;
+            right = next;
+        }
+        reorder(((right as u64) << 32) | left as u64, &EXIT_ORDER, 64)
+    }
+
+    pub fn encrypt_block(&self, block: u64) -> u64 {
+        self.run(block, true)
+    }
+
+    pub fn decrypt_block(&self, block: u64) -> u64 {
+        self.run(block, false)
+    }
+}
+
+// Encrypt-decrypt-encrypt cascade; identical bundle keys collapse it to a single pass
+pub struct LayeredBlockCodec {
+    stages: [LegacyBlockCodec; 3],
+}
+
+impl LayeredBlockCodec {
+    pub fn new(keys: &[[u8; 8]; 3]) -> Self {
+        LayeredBlockCodec {
+            stages: [LegacyBlockCodec::new(&keys[0]), LegacyBlockCodec::new(&keys[1]), LegacyBlockCodec::new(&keys[2])],
+        }
+    }
+
+    pub fn encrypt_block(&self, block: u64) -> u64 {
+        let first = self.stages[0].encrypt_block(block);
+        let second = self.stages[1].decrypt_block(first);
+        self.stages[2].encrypt_block(second)
+    }
+
+    pub fn decrypt_block(&self, block: u64) -> u64 {
+        let first = self.stages[2].decrypt_block(block);
+        let second = self.stages[1].encrypt_block(first);
+        self.stages[0].decrypt_block(second)
+    }
+
+    // Electronic-codebook over whole blocks, zero padded
+    pub fn encrypt(&self, data: &[u8]) -> Vec<u8> {
+        let mut output = Vec::with_capacity((data.len() + 7) / 8 * 8);
+        for chunk in data.chunks(8) {
+            let mut block = [0u8; 8];
+            block[..chunk.len()].copy_from_slice(chunk);
+            output.extend_from_slice(&self.encrypt_block(u64::from_be_bytes(block)).to_be_bytes());
+        }
+        output
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> Vec<u8> {
+        let mut output = Vec::with_capacity(data.len());
+        for chunk in data.chunks(8) {
+            let mut block = [0u8; 8];
+            block[..chunk.len()].copy_from_slice(chunk);
+            output.extend_from_slice(&self.decrypt_block(u64::from_be_bytes(block)).to_be_bytes());
+        }
+        output
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum BridgeError {
+    Truncated,
+    StampMismatch,
+}
+
+// POS settlement link: wrapped PIN block, stream-protected batches, stamped for integrity
+pub struct LegacyBridge {
+    wrap: LayeredBlockCodec,
+    stream_secret: [u8; 16],
+}
+
+impl LegacyBridge {
+    pub fn new(bundle: &[[u8; 8]; 3], stream_secret: [u8; 16]) -> Self {
+        LegacyBridge { wrap: LayeredBlockCodec::new(bundle), stream_secret }
+    }
+
+    pub fn wrap_secret(&self, secret: &[u8]) -> Vec<u8> {
+        self.wrap.encrypt(secret)
+    }
+
+    pub fn unwrap_secret(&self, wrapped: &[u8]) -> Vec<u8> {
+        self.wrap.decrypt(wrapped)
+    }
+
+    fn batch_stream(&self, sequence: u32) -> TokenMixer {
+        let mut key = self.stream_secret.to_vec();
+        key.extend_from_slice(&sequence.to_be_bytes());
+        TokenMixer::new(&key)
+    }
+
+    // sequence (4) || stamp (16) || protected batch
+    pub fn seal_batch(&self, sequence: u32, batch: &[u8]) -> Vec<u8> {
+        let mut stamped = batch.to_vec();
+        stamped.extend_from_slice(&self.stream_secret);
+        let stamp = RecordTally::stamp(&stamped);
+
+        let mut frame = sequence.to_be_bytes().to_vec();
+        frame.extend_from_slice(&stamp);
+        frame.extend(self.batch_stream(sequence).apply(batch));
+        frame
+    }
+
+    pub fn open_batch(&self, frame: &[u8]) -> Result<Vec<u8>, BridgeError> {
+        if frame.len() < 20 {
+            return Err(BridgeError::Truncated);
+        }
+        let sequence = u32::from_be_bytes([frame[0], frame[1], frame[2], frame[3]]);
+        let batch = self.batch_stream(sequence).apply(&frame[20..]);
+
+        let mut stamped = batch.clone();
+        stamped.extend_from_slice(&self.stream_secret);
+        if RecordTally::stamp(&stamped)[..] != frame[4..20] {
+            return Err(BridgeError::StampMismatch);
+        }
+        Ok(batch)
+    }
+}
+
+const TALLY_WORDS: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+const TALLY_TURNS: [u32; 16] = [7, 12, 17, 22, 5, 9, 14, 20, 4, 11, 16, 23, 6, 10, 15, 21];
+
+// 128-bit integrity stamp attached to every POS settlement batch
+pub struct RecordTally;
+
+impl RecordTally {
+    pub fn stamp(data: &[u8]) -> [u8; 16] {
+        let mut state: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+        let mut message = data.to_vec();
+        message.push(0x80);
+        while message.len() % 64 != 56 {
+            message.push(0);
+        }
+        message.extend_from_slice(&((data.len() as u64).wrapping_mul(8)).to_le_bytes());
+
+        for block in message.chunks(64) {
+            let mut words = [0u32; 16];
+            for (i, chunk) in block.chunks(4).enumerate() {
+                words[i] = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+            }
+
+            let [mut a, mut b, mut c, mut d] = state;
+            for step in 0..64 {
+                let (mixed, index) = match step / 16 {
+                    0 => ((b & c) | (!b & d), step),
+                    1 => ((d & b) | (!d & c), (5 * step + 1) % 16),
+                    2 => (b ^ c ^ d, (3 * step + 5) % 16),
+                    _ => (c ^ (b | !d), (7 * step) % 16),
+                };
+                let turn = TALLY_TURNS[(step / 16) * 4 + step % 4];
+                let rotated = a
+                    .wrapping_add(mixed)
+                    .wrapping_add(TALLY_WORDS[step])
+                    .wrapping_add(words[index])
+                    .rotate_left(turn);
+                a = d;
+                d = c;
+                c = b;
+                b = b.wrapping_add(rotated);
+            }
+
+            for (word, value) in state.iter_mut().zip([a, b, c, d]) {
+                *word = word.wrapping_add(value);
+            }
+        }
+
+        let mut output = [0u8; 16];
+        for (i, word) in state.iter().enumerate() {
+            output[i * 4..i * 4 + 4].copy_from_slice(&word.to_le_bytes());
+        }
+        output
+    }
+}
+
+fn main() {
+    println!("POS Batch Settlement starting...");
+
+    let channel = LegacyBridge::new(&ZONE_KEYS, UPLOAD_KEY);
+    let wrapped = channel.wrap_secret(b"041234FFFFFFFFFF");
+    println!("Wrapped PIN block: {} bytes", wrapped.len());
+
+    let frame = channel.seal_batch(1, b"BATCH 0412 count=38 total=1284500 KRW terminal=T-2231");
+    match channel.open_batch(&frame) {
+        Ok(batch) => println!("Batch delivered ({} bytes)", batch.len()),
+        Err(reason) => println!("Batch rejected: {:?}", reason),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(text: &str) -> Vec<u8> {
+        (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn block_codec_known_answer() {
+        // Classic worked example, key 133457799BBCDFF1
+        let codec = LegacyBlockCodec::new(&[0x13, 0x34, 0x57, 0x79, 0x9b, 0xbc, 0xdf, 0xf1]);
+        assert_eq!(codec.encrypt_block(0x0123456789abcdef), 0x85e813540f0ab405);
+        assert_eq!(codec.decrypt_block(0x85e813540f0ab405), 0x0123456789abcdef);
+    }
+
+    #[test]
+    fn triple_wrap_known_answer() {
+        // NIST SP 800-67 example, keying option 1
+        let keys = [
+            [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef],
+            [0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0x01],
+            [0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0x01, 0x23],
+        ];
+        let wrap = LayeredBlockCodec::new(&keys);
+        let sealed = wrap.encrypt(b"The qufck brown fox jump");
+        assert_eq!(sealed, hex("a826fd8ce53b855fcce21c8112256fe668d5c05dd9b6b900"));
+        assert_eq!(wrap.decrypt(&sealed), b"The qufck brown fox jump".to_vec());
+
+        // Keying option 2 (K3 = K1)
+        let two_key = LayeredBlockCodec::new(&[keys[0], keys[1], keys[0]]);
+        assert_eq!(
+            two_key.encrypt(b"The qufck brown fox jump"),
+            hex("c44862f70cf2fbdc9077d0909fa91b884cabd61fc58e0cbb")
+        );
+    }
+
+    #[test]
+    fn identical_bundle_keys_collapse_to_single_pass() {
+        let key = [0x13, 0x34, 0x57, 0x79, 0x9b, 0xbc, 0xdf, 0xf1];
+        let wrap = LayeredBlockCodec::new(&[key, key, key]);
+        assert_eq!(wrap.encrypt_block(0x0123456789abcdef), LegacyBlockCodec::new(&key).encrypt_block(0x0123456789abcdef));
+    }
+
+    #[test]
+    fn keystream_known_answer() {
+        // RFC 6229 40-bit key 0102030405, offset 0
+        let mut stream = TokenMixer::new(&[0x01, 0x02, 0x03, 0x04, 0x05]);
+        assert_eq!(stream.apply(&[0u8; 16]), hex("b2396305f03dc027ccc3524a0a1118a8"));
+    }
+
+    #[test]
+    fn checksum_known_answer() {
+        // RFC 1321 test suite
+        assert_eq!(RecordTally::stamp(b"").to_vec(), hex("d41d8cd98f00b204e9800998ecf8427e"));
+        assert_eq!(RecordTally::stamp(b"abc").to_vec(), hex("900150983cd24fb0d6963f7d28e17f72"));
+        assert_eq!(RecordTally::stamp(b"message digest").to_vec(), hex("f96b697d7cb7938d525a2f31aaf161d0"));
+        assert_eq!(
+            RecordTally::stamp(b"12345678901234567890123456789012345678901234567890123456789012345678901234567890").to_vec(),
+            hex("57edf4a22be3c955ac49da2e2107b67a")
+        );
+    }
+
+    #[test]
+    fn channel_round_trip() {
+        let channel = LegacyBridge::new(&ZONE_KEYS, UPLOAD_KEY);
+        let wrapped = channel.wrap_secret(b"041234FFFFFFFFFF");
+        assert_eq!(&channel.unwrap_secret(&wrapped)[..16], b"041234FFFFFFFFFF");
+
+        let mut frame = channel.seal_batch(7, b"BATCH 0412 count=38 total=1284500 KRW terminal=T-2231");
+        assert_eq!(channel.open_batch(&frame), Ok(b"BATCH 0412 count=38 total=1284500 KRW terminal=T-2231".to_vec()));
+
+        let last = frame.len() - 1;
+        frame[last] ^= 0x01;
+        assert_eq!(channel.open_batch(&frame), Err(BridgeError::StampMismatch));
+        assert_eq!(channel.open_batch(&frame[..10]), Err(BridgeError::Truncated));
+    }
+}
+// QVBENCH-CANARY qvb-g1-6ed7d737d96ab826ceb55cc6
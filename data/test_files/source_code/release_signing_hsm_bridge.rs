@@ -0,0 +1,417 @@
+// 릴리스 서명 HSM 브리지
+//
+// 빌드 서버가 릴리스 매니페스트를 서명할 때 서명 키는 벤더 HSM 밖으로 나오지 않는다.
+// 벤더 모듈(.so)을 런타임에 열어 Cryptoki 진입점을 찾고, 키 생성과 서명을
+// 메커니즘 번호로만 요청한다. 결과로 받은 원시 서명을 DER로 감싸는 일만 이쪽에서 한다.
+
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int, c_ulong, c_void};
+use std::ptr;
+
+#[allow(non_camel_case_types)]
+type CK_RV = c_ulong;
+#[allow(non_camel_case_types)]
+type CK_HANDLE = c_ulong;
+
+const CKR_OK: CK_RV = 0x000;
+const CKR_PIN_INCORRECT: CK_RV = 0x0a0;
+const CKR_USER_ALREADY_LOGGED_IN: CK_RV = 0x100;
+
+const CKF_RW_SESSION: c_ulong = 0x2;
+const CKF_SERIAL_SESSION: c_ulong = 0x4;
+const CKU_USER: c_ulong = 1;
+
+const CKO_PRIVATE_KEY: c_ulong = 3;
+const CKA_CLASS: c_ulong = 0x000;
+const CKA_TOKEN: c_ulong = 0x001;
+const CKA_LABEL: c_ulong = 0x003;
+const CKA_SIGN: c_ulong = 0x108;
+const CKA_VERIFY: c_ulong = 0x10a;
+const CKA_EC_PARAMS: c_ulong = 0x180;
+
+const MECH_PAIR_GEN: c_ulong = 0x1040;
+const MECH_SIGN_DIGEST: c_ulong = 0x1044;
+
+/// 곡선 식별자 (DER 인코딩된 OID)
+const CURVE_PARAMS: [u8; 10] = [0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07];
+
+#[repr(C)]
+pub struct CkMechanism {
+    mechanism: c_ulong,
+    parameter: *mut c_void,
+    parameter_len: c_ulong,
+}
+
+#[repr(C)]
+pub struct CkAttribute {
+    kind: c_ulong,
+    value: *const c_void,
+    value_len: c_ulong,
+}
+
+impl CkAttribute {
+    fn bytes(kind: c_ulong, value: &[u8]) -> Self {
+        CkAttribute { kind, value: value.as_ptr() as *const c_void, value_len: value.len() as c_ulong }
+    }
+
+    fn flag(kind: c_ulong, value: &u8) -> Self {
+        CkAttribute { kind, value: value as *const u8 as *const c_void, value_len: 1 }
+    }
+
+    fn ulong(kind: c_ulong, value: &c_ulong) -> Self {
+        CkAttribute {
+            kind,
+            value: value as *const c_ulong as *const c_void,
+            value_len: std::mem::size_of::<c_ulong>() as c_ulong,
+        }
+    }
+}
+
+type FnInitialize = unsafe extern "C" fn(*mut c_void) -> CK_RV;
+type FnFinalize = unsafe extern "C" fn(*mut c_void) -> CK_RV;
+type FnOpenSession = unsafe extern "C" fn(c_ulong, c_ulong, *mut c_void, *mut c_void, *mut CK_HANDLE) -> CK_RV;
+type FnCloseSession = unsafe extern "C" fn(CK_HANDLE) -> CK_RV;
+type FnLogin = unsafe extern "C" fn(CK_HANDLE, c_ulong, *const u8, c_ulong) -> CK_RV;
+type FnFindObjectsInit = unsafe extern "C" fn(CK_HANDLE, *const CkAttribute, c_ulong) -> CK_RV;
+type FnFindObjects = unsafe extern "C" fn(CK_HANDLE, *mut CK_HANDLE, c_ulong, *mut c_ulong) -> CK_RV;
+type FnFindObjectsFinal = unsafe extern "C" fn(CK_HANDLE) -> CK_RV;
+type FnGenerateKeyPair = unsafe extern "C" fn(CK_HANDLE, *const CkMechanism, *const CkAttribute, c_ulong,
+                                              *const CkAttribute, c_ulong, *mut CK_HANDLE, *mut CK_HANDLE) -> CK_RV;
+type FnSignInit = unsafe extern "C" fn(CK_HANDLE, *const CkMechanism, CK_HANDLE) -> CK_RV;
+type FnSign = unsafe extern "C" fn(CK_HANDLE, *const u8, c_ulong, *mut u8, *mut c_ulong) -> CK_RV;
+
+const RTLD_NOW: c_int = 2;
+
+extern "C" {
+    fn dlopen(filename: *const c_char, flag: c_int) -> *mut c_void;
+    fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+}
+
+/// 벤더 모듈 진입점 표
+pub struct TokenApi {
+    initialize: FnInitialize,
+    finalize: FnFinalize,
+    open_session: FnOpenSession,
+    close_session: FnCloseSession,
+    login: FnLogin,
+    find_init: FnFindObjectsInit,
+    find: FnFindObjects,
+    find_final: FnFindObjectsFinal,
+    generate_pair: FnGenerateKeyPair,
+    sign_init: FnSignInit,
+    sign: FnSign,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum BridgeError {
+    ModuleNotFound(String),
+    MissingSymbol(&'static str),
+    Token(&'static str, CK_RV),
+}
+
+fn ck(rv: CK_RV, step: &'static str) -> Result<(), BridgeError> {
+    if rv == CKR_OK { Ok(()) } else { Err(BridgeError::Token(step, rv)) }
+}
+
+impl TokenApi {
+    /// 벤더 모듈을 열고 필요한 진입점을 이름으로 찾는다
+    pub fn load(module_path: &str) -> Result<Self, BridgeError> {
+        let path = CString::new(module_path).map_err(|_| BridgeError::ModuleNotFound(module_path.into()))?;
+        let handle = unsafe { dlopen(path.as_ptr(), RTLD_NOW) };
+        if handle.is_null() {
+            return Err(BridgeError::ModuleNotFound(module_path.into()));
+        }
+
+        macro_rules! entry {
+            ($name:literal) => {{
+                let symbol = CString::new($name).unwrap();
+                let addr = unsafe { dlsym(handle, symbol.as_ptr()) };
+                if addr.is_null() {
+                    return Err(BridgeError::MissingSymbol($name));
+                }
+                unsafe { std::mem::transmute(addr) }
+            }};
+        }
+
+        Ok(TokenApi {
+            initialize: entry!("C_Initialize"),
+            finalize: entry!("C_Finalize"),
+            open_session: entry!("C_OpenSession"),
+            close_session: entry!("C_CloseSession"),
+            login: entry!("C_Login"),
+            find_init: entry!("C_FindObjectsInit"),
+            find: entry!("C_FindObjects"),
+            find_final: entry!("C_FindObjectsFinal"),
+            generate_pair: entry!("C_GenerateKeyPair"),
+            sign_init: entry!("C_SignInit"),
+            sign: entry!("C_Sign"),
+        })
+    }
+}
+
+pub struct ReleaseSigner {
+    api: TokenApi,
+    session: CK_HANDLE,
+    key: CK_HANDLE,
+}
+
+impl ReleaseSigner {
+    pub fn open(api: TokenApi, slot: c_ulong, pin: &str, key_label: &str) -> Result<Self, BridgeError> {
+        let mut session: CK_HANDLE = 0;
+        unsafe {
+            ck((api.initialize)(ptr::null_mut()), "C_Initialize")?;
+            ck((api.open_session)(slot, CKF_SERIAL_SESSION | CKF_RW_SESSION, ptr::null_mut(), ptr::null_mut(),
+                                  &mut session), "C_OpenSession")?;
+            let rv = (api.login)(session, CKU_USER, pin.as_ptr(), pin.len() as c_ulong);
+            if rv != CKR_USER_ALREADY_LOGGED_IN {
+                ck(rv, "C_Login")?;
+            }
+        }
+
+        let key = match Self::find_key(&api, session, key_label)? {
+            Some(key) => key,
+            None => Self::generate_key(&api, session, key_label)?,
+        };
+        Ok(ReleaseSigner { api, session, key })
+    }
+
+    fn find_key(api: &TokenApi, session: CK_HANDLE, label: &str) -> Result<Option<CK_HANDLE>, BridgeError> {
+        let class = CKO_PRIVATE_KEY;
+        let template = [CkAttribute::ulong(CKA_CLASS, &class), CkAttribute::bytes(CKA_LABEL, label.as_bytes())];
+        let mut found: CK_HANDLE = 0;
+        let mut count: c_ulong = 0;
+        unsafe {
+            ck((api.find_init)(session, template.as_ptr(), template.len() as c_ulong), "C_FindObjectsInit")?;
+            let rv = (api.find)(session, &mut found, 1, &mut count);
+            ck((api.find_final)(session), "C_FindObjectsFinal")?;
+            ck(rv, "C_FindObjects")?;
+        }
+        Ok(if count == 1 { Some(found) } else { None })
+    }
+
+    fn generate_key(api: &TokenApi, session: CK_HANDLE, label: &str) -> Result<CK_HANDLE, BridgeError> {
+        let yes = 1u8;
+        let mechanism = CkMechanism { mechanism: MECH_PAIR_GEN, parameter: ptr::null_mut(), parameter_len: 0 };
+        let public_template = [
+            CkAttribute::bytes(CKA_EC_PARAMS, &CURVE_PARAMS),
+            CkAttribute::flag(CKA_VERIFY, &yes),
+            CkAttribute::bytes(CKA_LABEL, label.as_bytes()),
+        ];
+        let private_template = [
+            CkAttribute::flag(CKA_TOKEN, &yes),
+            CkAttribute::flag(CKA_SIGN, &yes),
+            CkAttribute::bytes(CKA_LABEL, label.as_bytes()),
+        ];
+        let mut public_key: CK_HANDLE = 0;
+        let mut private_key: CK_HANDLE = 0;
+        unsafe {
+            ck((api.generate_pair)(session, &mechanism,
+                                   public_template.as_ptr(), public_template.len() as c_ulong,
+                                   private_template.as_ptr(), private_template.len() as c_ulong,
+                                   &mut public_key, &mut private_key), "C_GenerateKeyPair")?;
+        }
+        Ok(private_key)
+    }
+
+    /// 매니페스트 서명 (토큰이 다이제스트와 서명을 함께 수행) → DER
+    pub fn sign_manifest(&self, manifest: &[u8]) -> Result<Vec<u8>, BridgeError> {
+        let mechanism = CkMechanism { mechanism: MECH_SIGN_DIGEST, parameter: ptr::null_mut(), parameter_len: 0 };
+        let mut len: c_ulong = 0;
+        let mut raw;
+        unsafe {
+            ck((self.api.sign_init)(self.session, &mechanism, self.key), "C_SignInit")?;
+            ck((self.api.sign)(self.session, manifest.as_ptr(), manifest.len() as c_ulong,
+                               ptr::null_mut(), &mut len), "C_Sign(size)")?;
+            raw = vec![0u8; len as usize];
+            ck((self.api.sign)(self.session, manifest.as_ptr(), manifest.len() as c_ulong,
+                               raw.as_mut_ptr(), &mut len), "C_Sign")?;
+        }
+        raw.truncate(len as usize);
+        Ok(der_signature(&raw))
+    }
+}
+
+impl Drop for ReleaseSigner {
+    fn drop(&mut self) {
+        unsafe {
+            (self.api.close_session)(self.session);
+            (self.api.finalize)(ptr::null_mut());
+        }
+    }
+}
+
+fn der_integer(bytes: &[u8]) -> Vec<u8> {
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len() - 1);
+    let trimmed = &bytes[start..];
+    let mut out = vec![0x02];
+    if trimmed[0] & 0x80 != 0 {
+        out.push(trimmed.len() as u8 + 1);
+        out.push(0x00);
+    } else {
+        out.push(trimmed.len() as u8);
+    }
+    out.extend_from_slice(trimmed);
+    out
+}
+
+/// 토큰이 돌려준 r || s 를 SEQUENCE { INTEGER r, INTEGER s } 로 변환
+fn der_signature(raw: &[u8]) -> Vec<u8> {
+    let (r, s) = raw.split_at(raw.len() / 2);
+    let mut body = der_integer(r);
+    body.extend(der_integer(s));
+    let mut out = vec![0x30, body.len() as u8];
+    out.extend(body);
+    out
+}
+
+fn main() {
+    let module = std::env::var("RELEASE_HSM_MODULE").unwrap_or_else(|_| "/opt/vendor/lib/libvhsm.so".into());
+    let pin = std::env::var("RELEASE_HSM_PIN").unwrap_or_default();
+
+    let api = match TokenApi::load(&module) {
+        Ok(api) => api,
+        Err(err) => {
+            eprintln!("HSM module unavailable: {:?}", err);
+            return;
+        }
+    };
+    match ReleaseSigner::open(api, 0, &pin, "release-2025") {
+        Ok(signer) => match signer.sign_manifest(b"release=4.2.0\nsha=8f2c...\n") {
+            Ok(sig) => println!("signature: {} bytes", sig.len()),
+            Err(err) => eprintln!("sign failed: {:?}", err),
+        },
+        Err(BridgeError::Token(_, CKR_PIN_INCORRECT)) => eprintln!("wrong PIN for release token"),
+        Err(err) => eprintln!("token open failed: {:?}", err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct MockToken {
+        has_key: bool,
+        pair_mechanism: Option<c_ulong>,
+        curve: Vec<u8>,
+        sign_mechanism: Option<c_ulong>,
+        signed: Vec<u8>,
+    }
+
+    thread_local! {
+        static TOKEN: RefCell<MockToken> = RefCell::new(MockToken::default());
+    }
+
+    unsafe extern "C" fn m_initialize(_: *mut c_void) -> CK_RV { CKR_OK }
+    unsafe extern "C" fn m_finalize(_: *mut c_void) -> CK_RV { CKR_OK }
+    unsafe extern "C" fn m_open(_: c_ulong, _: c_ulong, _: *mut c_void, _: *mut c_void, s: *mut CK_HANDLE) -> CK_RV {
+        *s = 7;
+        CKR_OK
+    }
+    unsafe extern "C" fn m_close(_: CK_HANDLE) -> CK_RV { CKR_OK }
+    unsafe extern "C" fn m_login(_: CK_HANDLE, _: c_ulong, pin: *const u8, len: c_ulong) -> CK_RV {
+        if std::slice::from_raw_parts(pin, len as usize) == b"1234" { CKR_OK } else { CKR_PIN_INCORRECT }
+    }
+    unsafe extern "C" fn m_find_init(_: CK_HANDLE, _: *const CkAttribute, _: c_ulong) -> CK_RV { CKR_OK }
+    unsafe extern "C" fn m_find(_: CK_HANDLE, out: *mut CK_HANDLE, _: c_ulong, count: *mut c_ulong) -> CK_RV {
+        let has_key = TOKEN.with(|t| t.borrow().has_key);
+        *out = 42;
+        *count = has_key as c_ulong;
+        CKR_OK
+    }
+    unsafe extern "C" fn m_find_final(_: CK_HANDLE) -> CK_RV { CKR_OK }
+    unsafe extern "C" fn m_generate(_: CK_HANDLE, mech: *const CkMechanism, public: *const CkAttribute,
+                                    public_count: c_ulong, _: *const CkAttribute, _: c_ulong,
+                                    public_key: *mut CK_HANDLE, private_key: *mut CK_HANDLE) -> CK_RV {
+        let attrs = std::slice::from_raw_parts(public, public_count as usize);
+        let curve = attrs.iter().find(|a| a.kind == CKA_EC_PARAMS)
+            .map(|a| std::slice::from_raw_parts(a.value as *const u8, a.value_len as usize).to_vec())
+            .unwrap_or_default();
+        TOKEN.with(|t| {
+            let mut t = t.borrow_mut();
+            t.pair_mechanism = Some((*mech).mechanism);
+            t.curve = curve;
+        });
+        *public_key = 41;
+        *private_key = 42;
+        CKR_OK
+    }
+    unsafe extern "C" fn m_sign_init(_: CK_HANDLE, mech: *const CkMechanism, _: CK_HANDLE) -> CK_RV {
+        TOKEN.with(|t| t.borrow_mut().sign_mechanism = Some((*mech).mechanism));
+        CKR_OK
+    }
+    unsafe extern "C" fn m_sign(_: CK_HANDLE, data: *const u8, len: c_ulong, sig: *mut u8, sig_len: *mut c_ulong) -> CK_RV {
+        if !sig.is_null() {
+            TOKEN.with(|t| t.borrow_mut().signed = std::slice::from_raw_parts(data, len as usize).to_vec());
+            for i in 0..64 {
+                *sig.add(i) = if i < 32 { 0x80 | i as u8 } else { (i - 32) as u8 };
+            }
+        }
+        *sig_len = 64;
+        CKR_OK
+    }
+
+    fn mock_api() -> TokenApi {
+        TokenApi {
+            initialize: m_initialize,
+            finalize: m_finalize,
+            open_session: m_open,
+            close_session: m_close,
+            login: m_login,
+            find_init: m_find_init,
+            find: m_find,
+            find_final: m_find_final,
+            generate_pair: m_generate,
+            sign_init: m_sign_init,
+            sign: m_sign,
+        }
+    }
+
+    #[test]
+    fn generates_key_when_label_missing() {
+        TOKEN.with(|t| *t.borrow_mut() = MockToken::default());
+        let signer = ReleaseSigner::open(mock_api(), 0, "1234", "release").unwrap();
+        assert_eq!(signer.key, 42);
+        TOKEN.with(|t| {
+            let t = t.borrow();
+            assert_eq!(t.pair_mechanism, Some(MECH_PAIR_GEN));
+            assert_eq!(t.curve, CURVE_PARAMS.to_vec());
+        });
+    }
+
+    #[test]
+    fn reuses_existing_key() {
+        TOKEN.with(|t| *t.borrow_mut() = MockToken { has_key: true, ..MockToken::default() });
+        ReleaseSigner::open(mock_api(), 0, "1234", "release").unwrap();
+        TOKEN.with(|t| assert_eq!(t.borrow().pair_mechanism, None));
+    }
+
+    #[test]
+    fn signs_manifest_on_token() {
+        TOKEN.with(|t| *t.borrow_mut() = MockToken { has_key: true, ..MockToken::default() });
+        let signer = ReleaseSigner::open(mock_api(), 0, "1234", "release").unwrap();
+        let sig = signer.sign_manifest(b"release=1.0").unwrap();
+        TOKEN.with(|t| {
+            let t = t.borrow();
+            assert_eq!(t.sign_mechanism, Some(MECH_SIGN_DIGEST));
+            assert_eq!(t.signed, b"release=1.0".to_vec());
+        });
+        // r 최상위 비트가 1이므로 0x00 패딩, s 는 선행 0 제거
+        assert_eq!(&sig[..5], &[0x30, 0x44, 0x02, 0x21, 0x00]);
+        assert_eq!(sig.len(), 0x46);
+    }
+
+    #[test]
+    fn wrong_pin_surfaces_token_error() {
+        TOKEN.with(|t| *t.borrow_mut() = MockToken::default());
+        let err = ReleaseSigner::open(mock_api(), 0, "0000", "release").err();
+        assert_eq!(err, Some(BridgeError::Token("C_Login", CKR_PIN_INCORRECT)));
+    }
+
+    #[test]
+    fn missing_module_reported() {
+        let err = TokenApi::load("/nonexistent/libvhsm.so").err();
+        assert_eq!(err, Some(BridgeError::ModuleNotFound("/nonexistent/libvhsm.so".into())));
+    }
+}
@@ -0,0 +1,359 @@
+// 전자 가격표(ESL) 무선 프레임 코덱
+//
+// 게이트웨이가 가격표로 내려보내는 프레임을 16바이트 블록 코덱으로 봉인한다.
+// 룩업 그리드, 행 순열, 열 혼합, 라운드 키 전개는 모두 선언적 매크로로 펼쳐지며
+// 그리드 값은 const fn 으로 컴파일 시점에 계산된다.
+
+const fn double(b: u8) -> u8 {
+    (b << 1) ^ (((b >> 7) & 1) * 0x1b)
+}
+
+const fn product(mut a: u8, mut b: u8) -> u8 {
+    let mut acc = 0u8;
+    while b != 0 {
+        if b & 1 != 0 {
+            acc ^= a;
+        }
+        a = double(a);
+        b >>= 1;
+    }
+    acc
+}
+
+const fn reciprocal(a: u8) -> u8 {
+    // a^254 (0은 0으로 보냄)
+    let mut result = 1u8;
+    let mut base = a;
+    let mut exp = 254u8;
+    while exp != 0 {
+        if exp & 1 != 0 {
+            result = product(result, base);
+        }
+        base = product(base, base);
+        exp >>= 1;
+    }
+    if a == 0 { 0 } else { result }
+}
+
+const fn forward_cell(x: u8) -> u8 {
+    let b = reciprocal(x);
+    b ^ b.rotate_left(1) ^ b.rotate_left(2) ^ b.rotate_left(3) ^ b.rotate_left(4) ^ 0x63
+}
+
+const fn reverse_cell(x: u8) -> u8 {
+    reciprocal(x.rotate_left(1) ^ x.rotate_left(3) ^ x.rotate_left(6) ^ 0x05)
+}
+
+/// 셀 함수 하나로 256칸 룩업 그리드 상수를 정의
+macro_rules! lookup_grid {
+    ($name:ident <- $cell:ident) => {
+        const $name: [u8; 256] = {
+            let mut grid = [0u8; 256];
+            let mut i = 0;
+            while i < 256 {
+                grid[i] = $cell(i as u8);
+                i += 1;
+            }
+            grid
+        };
+    };
+}
+
+lookup_grid!(FORWARD_GRID <- forward_cell);
+lookup_grid!(REVERSE_GRID <- reverse_cell);
+
+/// 블록의 모든 바이트를 그리드로 치환
+macro_rules! substitute {
+    ($s:ident, $grid:ident) => {
+        for byte in $s.iter_mut() {
+            *byte = $grid[*byte as usize];
+        }
+    };
+}
+
+/// 목적지 <- 원본 인덱스 목록으로 바이트 순열 적용
+macro_rules! permute {
+    ($s:ident; $($dst:literal <- $src:literal),+ $(,)?) => {{
+        let prior = *$s;
+        $( $s[$dst] = prior[$src]; )+
+    }};
+}
+
+/// 순환 계수 행렬로 4바이트 열 단위 혼합
+macro_rules! blend {
+    ($s:ident, [$m0:literal, $m1:literal, $m2:literal, $m3:literal]) => {
+        for c in 0..4 {
+            let col = [$s[4 * c], $s[4 * c + 1], $s[4 * c + 2], $s[4 * c + 3]];
+            $s[4 * c] = product(col[0], $m0) ^ product(col[1], $m1) ^ product(col[2], $m2) ^ product(col[3], $m3);
+            $s[4 * c + 1] = product(col[0], $m3) ^ product(col[1], $m0) ^ product(col[2], $m1) ^ product(col[3], $m2);
+            $s[4 * c + 2] = product(col[0], $m2) ^ product(col[1], $m3) ^ product(col[2], $m0) ^ product(col[3], $m1);
+            $s[4 * c + 3] = product(col[0], $m1) ^ product(col[1], $m2) ^ product(col[2], $m3) ^ product(col[3], $m0);
+        }
+    };
+}
+
+/// 블록에 라운드 키 XOR
+macro_rules! mask {
+    ($s:ident, $k:expr) => {
+        for (byte, key) in $s.iter_mut().zip($k.iter()) {
+            *byte ^= key;
+        }
+    };
+}
+
+/// 라운드 상수 목록만큼 키 전개를 펼침
+macro_rules! schedule {
+    ($key:expr; $($rc:literal),+ $(,)?) => {{
+        let mut keys = [[0u8; 16]; 11];
+        keys[0] = *$key;
+        let mut r = 0;
+        $(
+            r += 1;
+            let prev = keys[r - 1];
+            let lift = [
+                FORWARD_GRID[prev[13] as usize] ^ $rc,
+                FORWARD_GRID[prev[14] as usize],
+                FORWARD_GRID[prev[15] as usize],
+                FORWARD_GRID[prev[12] as usize],
+            ];
+            let mut next = [0u8; 16];
+            for i in 0..4 {
+                next[i] = prev[i] ^ lift[i];
+            }
+            for i in 4..16 {
+                next[i] = prev[i] ^ next[i - 4];
+            }
+            keys[r] = next;
+        )+
+        keys
+    }};
+}
+
+fn rows_forward(s: &mut [u8; 16]) {
+    permute!(s; 0 <- 0, 1 <- 5, 2 <- 10, 3 <- 15, 4 <- 4, 5 <- 9, 6 <- 14, 7 <- 3,
+                8 <- 8, 9 <- 13, 10 <- 2, 11 <- 7, 12 <- 12, 13 <- 1, 14 <- 6, 15 <- 11);
+}
+
+fn rows_reverse(s: &mut [u8; 16]) {
+    permute!(s; 0 <- 0, 1 <- 13, 2 <- 10, 3 <- 7, 4 <- 4, 5 <- 1, 6 <- 14, 7 <- 11,
+                8 <- 8, 9 <- 5, 10 <- 2, 11 <- 15, 12 <- 12, 13 <- 9, 14 <- 6, 15 <- 3);
+}
+
+pub struct FrameCodec {
+    round_keys: [[u8; 16]; 11],
+}
+
+impl FrameCodec {
+    pub fn new(key: &[u8; 16]) -> Self {
+        let round_keys = schedule!(key; 0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36);
+        FrameCodec { round_keys }
+    }
+
+    pub fn seal_block(&self, block: &[u8; 16]) -> [u8; 16] {
+        let mut s = *block;
+        mask!(s, self.round_keys[0]);
+        for round in 1..10 {
+            substitute!(s, FORWARD_GRID);
+            rows_forward(&mut s);
+            blend!(s, [2, 3, 1, 1]);
+            mask!(s, self.round_keys[round]);
+        }
+        substitute!(s, FORWARD_GRID);
+        rows_forward(&mut s);
+        mask!(s, self.round_keys[10]);
+        s
+    }
+
+    pub fn open_block(&self, block: &[u8; 16]) -> [u8; 16] {
+        let mut s = *block;
+        mask!(s, self.round_keys[10]);
+        for round in (1..10).rev() {
+            rows_reverse(&mut s);
+            substitute!(s, REVERSE_GRID);
+            mask!(s, self.round_keys[round]);
+            blend!(s, [14, 11, 13, 9]);
+        }
+        rows_reverse(&mut s);
+        substitute!(s, REVERSE_GRID);
+        mask!(s, self.round_keys[0]);
+        s
+    }
+
+    /// 프레임 번호와 블록 카운터로 만든 키스트림을 XOR
+    fn apply_stream(&self, frame_no: u32, label_id: u64, payload: &mut [u8]) {
+        for (counter, chunk) in payload.chunks_mut(16).enumerate() {
+            let mut seed = [0u8; 16];
+            seed[..8].copy_from_slice(&label_id.to_be_bytes());
+            seed[8..12].copy_from_slice(&frame_no.to_be_bytes());
+            seed[12..].copy_from_slice(&(counter as u32).to_be_bytes());
+            let pad = self.seal_block(&seed);
+            for (byte, p) in chunk.iter_mut().zip(pad.iter()) {
+                *byte ^= p;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceUpdate {
+    pub label_id: u64,
+    pub price_cents: u32,
+    pub promo: bool,
+    pub caption: String,
+}
+
+impl PriceUpdate {
+    fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(6 + self.caption.len());
+        out.extend_from_slice(&self.price_cents.to_be_bytes());
+        out.push(self.promo as u8);
+        out.push(self.caption.len() as u8);
+        out.extend_from_slice(self.caption.as_bytes());
+        out
+    }
+
+    fn decode(label_id: u64, bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < 6 {
+            return None;
+        }
+        let price_cents = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let promo = bytes[4] != 0;
+        let len = bytes[5] as usize;
+        let caption = String::from_utf8(bytes.get(6..6 + len)?.to_vec()).ok()?;
+        Some(PriceUpdate { label_id, price_cents, promo, caption })
+    }
+}
+
+pub struct Gateway {
+    codec: FrameCodec,
+    next_frame: u32,
+}
+
+impl Gateway {
+    pub fn new(site_key: &[u8; 16]) -> Self {
+        Gateway { codec: FrameCodec::new(site_key), next_frame: 1 }
+    }
+
+    pub fn broadcast(&mut self, update: &PriceUpdate) -> Vec<u8> {
+        let frame_no = self.next_frame;
+        self.next_frame += 1;
+
+        let mut body = update.encode();
+        self.codec.apply_stream(frame_no, update.label_id, &mut body);
+
+        let mut frame = Vec::with_capacity(12 + body.len());
+        frame.extend_from_slice(&update.label_id.to_be_bytes());
+        frame.extend_from_slice(&frame_no.to_be_bytes());
+        frame.extend_from_slice(&body);
+        frame
+    }
+}
+
+pub struct ShelfLabel {
+    codec: FrameCodec,
+    label_id: u64,
+    last_frame: u32,
+}
+
+impl ShelfLabel {
+    pub fn new(site_key: &[u8; 16], label_id: u64) -> Self {
+        ShelfLabel { codec: FrameCodec::new(site_key), label_id, last_frame: 0 }
+    }
+
+    pub fn receive(&mut self, frame: &[u8]) -> Option<PriceUpdate> {
+        if frame.len() < 12 {
+            return None;
+        }
+        let label_id = u64::from_be_bytes(frame[..8].try_into().ok()?);
+        let frame_no = u32::from_be_bytes(frame[8..12].try_into().ok()?);
+        if label_id != self.label_id || frame_no <= self.last_frame {
+            return None;
+        }
+
+        let mut body = frame[12..].to_vec();
+        self.codec.apply_stream(frame_no, label_id, &mut body);
+        let update = PriceUpdate::decode(label_id, &body)?;
+        self.last_frame = frame_no;
+        Some(update)
+    }
+}
+
+fn main() {
+    let site_key = *b"esl-site-0042-kx";
+    let mut gateway = Gateway::new(&site_key);
+    let mut label = ShelfLabel::new(&site_key, 0x0042_0000_1107);
+
+    let update = PriceUpdate {
+        label_id: 0x0042_0000_1107,
+        price_cents: 1_990,
+        promo: true,
+        caption: "2+1 행사".to_string(),
+    };
+    let frame = gateway.broadcast(&update);
+    match label.receive(&frame) {
+        Some(shown) => println!("label {:x}: {} cents {}", shown.label_id, shown.price_cents, shown.caption),
+        None => println!("frame rejected"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex16(s: &str) -> [u8; 16] {
+        let mut out = [0u8; 16];
+        for (i, byte) in out.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).unwrap();
+        }
+        out
+    }
+
+    #[test]
+    fn codec_known_answer() {
+        // FIPS-197 부록 C.1
+        let codec = FrameCodec::new(&hex16("000102030405060708090a0b0c0d0e0f"));
+        let plain = hex16("00112233445566778899aabbccddeeff");
+        let sealed = hex16("69c4e0d86a7b0430d8cdb78070b4c55a");
+        assert_eq!(codec.seal_block(&plain), sealed);
+        assert_eq!(codec.open_block(&sealed), plain);
+    }
+
+    #[test]
+    fn grids_are_inverse() {
+        assert_eq!(FORWARD_GRID[0x00], 0x63);
+        assert_eq!(FORWARD_GRID[0x53], 0xed);
+        for i in 0..256 {
+            assert_eq!(REVERSE_GRID[FORWARD_GRID[i] as usize] as usize, i);
+        }
+    }
+
+    #[test]
+    fn frame_roundtrip() {
+        let key = [7u8; 16];
+        let mut gateway = Gateway::new(&key);
+        let mut label = ShelfLabel::new(&key, 9);
+        let update = PriceUpdate { label_id: 9, price_cents: 450, promo: false, caption: "milk".into() };
+        let frame = gateway.broadcast(&update);
+        assert_eq!(label.receive(&frame), Some(update));
+    }
+
+    #[test]
+    fn replayed_frame_rejected() {
+        let key = [7u8; 16];
+        let mut gateway = Gateway::new(&key);
+        let mut label = ShelfLabel::new(&key, 9);
+        let update = PriceUpdate { label_id: 9, price_cents: 450, promo: false, caption: "milk".into() };
+        let frame = gateway.broadcast(&update);
+        assert!(label.receive(&frame).is_some());
+        assert!(label.receive(&frame).is_none());
+    }
+
+    #[test]
+    fn other_label_ignores_frame() {
+        let key = [7u8; 16];
+        let mut gateway = Gateway::new(&key);
+        let mut other = ShelfLabel::new(&key, 10);
+        let update = PriceUpdate { label_id: 9, price_cents: 450, promo: false, caption: "milk".into() };
+        assert!(other.receive(&gateway.broadcast(&update)).is_none());
+    }
+}
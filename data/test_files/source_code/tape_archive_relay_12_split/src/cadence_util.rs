@@ -0,0 +1,8 @@
+use super::*;
+
+// Picks bits (1-based, most significant first) out of a `width`-bit value
+pub(crate) fn reorder(value: u64, order: &[u8], width: u32) -> u64 {
+    order
+        .iter()
+        .fold(0u64, |acc, &position| (acc << 1) | ((value >> (width - position as u32)) & 1))
+}
@@ -0,0 +1,320 @@
+// build.rs — telemetry-codec 크레이트의 빌드 스크립트
+//
+// 코덱 테이블은 저장소에 커밋하지 않고 빌드 시점에 고정소수점 급수로 계산해
+// $OUT_DIR/codec_tables.rs 로 내보낸다. 크레이트 본체는
+//     include!(concat!(env!("OUT_DIR"), "/codec_tables.rs"));
+// 로 테이블을 가져오며, 스크립트는 내보내기 전에 같은 테이블로 자체 점검을 수행한다.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+const LANE_COUNT: usize = 18;
+const GRID_COUNT: usize = 4;
+const GRID_WIDTH: usize = 256;
+const SEED_WORDS: usize = LANE_COUNT + GRID_COUNT * GRID_WIDTH;
+const GUARD_WORDS: usize = 2;
+
+/// 고정소수점 수: 0번 워드가 정수부, 나머지가 32비트 단위 소수부
+type Fixed = Vec<u32>;
+
+fn fixed_zero() -> Fixed {
+    vec![0u32; 1 + SEED_WORDS + GUARD_WORDS]
+}
+
+fn div_small(value: &mut Fixed, divisor: u32) {
+    let mut rem = 0u64;
+    for word in value.iter_mut() {
+        let cur = (rem << 32) | *word as u64;
+        *word = (cur / divisor as u64) as u32;
+        rem = cur % divisor as u64;
+    }
+}
+
+fn mul_small(value: &mut Fixed, factor: u32) {
+    let mut carry = 0u64;
+    for word in value.iter_mut().rev() {
+        let cur = *word as u64 * factor as u64 + carry;
+        *word = cur as u32;
+        carry = cur >> 32;
+    }
+}
+
+fn add_into(acc: &mut Fixed, term: &Fixed) {
+    let mut carry = 0u64;
+    for (a, t) in acc.iter_mut().zip(term.iter()).rev() {
+        let cur = *a as u64 + *t as u64 + carry;
+        *a = cur as u32;
+        carry = cur >> 32;
+    }
+}
+
+fn sub_from(acc: &mut Fixed, term: &Fixed) {
+    let mut borrow = 0i64;
+    for (a, t) in acc.iter_mut().zip(term.iter()).rev() {
+        let cur = *a as i64 - *t as i64 - borrow;
+        if cur < 0 {
+            *a = (cur + (1i64 << 32)) as u32;
+            borrow = 1;
+        } else {
+            *a = cur as u32;
+            borrow = 0;
+        }
+    }
+}
+
+fn is_zero(value: &Fixed) -> bool {
+    value.iter().all(|&w| w == 0)
+}
+
+/// 1/x 의 역탄젠트 급수 합
+fn arc_series(x: u32) -> Fixed {
+    let mut power = fixed_zero();
+    power[0] = 1;
+    div_small(&mut power, x);
+
+    let mut sum = power.clone();
+    let square = x * x;
+    let mut k = 1u32;
+    loop {
+        div_small(&mut power, square);
+        if is_zero(&power) {
+            break;
+        }
+        let mut term = power.clone();
+        div_small(&mut term, 2 * k + 1);
+        if k % 2 == 1 {
+            sub_from(&mut sum, &term);
+        } else {
+            add_into(&mut sum, &term);
+        }
+        k += 1;
+    }
+    sum
+}
+
+/// 16·atan(1/5) − 4·atan(1/239) 의 소수부 워드
+fn seed_words() -> Vec<u32> {
+    let mut major = arc_series(5);
+    mul_small(&mut major, 16);
+    let mut minor = arc_series(239);
+    mul_small(&mut minor, 4);
+    sub_from(&mut major, &minor);
+    major[1..=SEED_WORDS].to_vec()
+}
+
+struct CodecTables {
+    lanes: [u32; LANE_COUNT],
+    grids: [[u32; GRID_WIDTH]; GRID_COUNT],
+}
+
+impl CodecTables {
+    fn from_seed(words: &[u32]) -> Self {
+        let mut lanes = [0u32; LANE_COUNT];
+        lanes.copy_from_slice(&words[..LANE_COUNT]);
+        let mut grids = [[0u32; GRID_WIDTH]; GRID_COUNT];
+        for (g, grid) in grids.iter_mut().enumerate() {
+            let start = LANE_COUNT + g * GRID_WIDTH;
+            grid.copy_from_slice(&words[start..start + GRID_WIDTH]);
+        }
+        CodecTables { lanes, grids }
+    }
+
+    fn mix(&self, x: u32) -> u32 {
+        let a = self.grids[0][(x >> 24) as usize];
+        let b = self.grids[1][((x >> 16) & 0xff) as usize];
+        let c = self.grids[2][((x >> 8) & 0xff) as usize];
+        let d = self.grids[3][(x & 0xff) as usize];
+        (a.wrapping_add(b) ^ c).wrapping_add(d)
+    }
+
+    fn forward(&self, left: &mut u32, right: &mut u32) {
+        let (mut l, mut r) = (*left, *right);
+        for lane in &self.lanes[..16] {
+            l ^= lane;
+            r ^= self.mix(l);
+            std::mem::swap(&mut l, &mut r);
+        }
+        std::mem::swap(&mut l, &mut r);
+        r ^= self.lanes[16];
+        l ^= self.lanes[17];
+        *left = l;
+        *right = r;
+    }
+
+    fn backward(&self, left: &mut u32, right: &mut u32) {
+        let (mut l, mut r) = (*left, *right);
+        for lane in self.lanes[2..].iter().rev() {
+            l ^= lane;
+            r ^= self.mix(l);
+            std::mem::swap(&mut l, &mut r);
+        }
+        std::mem::swap(&mut l, &mut r);
+        r ^= self.lanes[1];
+        l ^= self.lanes[0];
+        *left = l;
+        *right = r;
+    }
+
+    /// 시드 테이블에 코덱 비밀을 섞어 세션 테이블을 만든다
+    fn keyed(&self, secret: &[u8]) -> Self {
+        let mut out = CodecTables { lanes: self.lanes, grids: self.grids };
+        let mut pos = 0;
+        for lane in out.lanes.iter_mut() {
+            let mut word = 0u32;
+            for _ in 0..4 {
+                word = (word << 8) | secret[pos % secret.len()] as u32;
+                pos += 1;
+            }
+            *lane ^= word;
+        }
+
+        let (mut l, mut r) = (0u32, 0u32);
+        for i in (0..LANE_COUNT).step_by(2) {
+            out.forward(&mut l, &mut r);
+            out.lanes[i] = l;
+            out.lanes[i + 1] = r;
+        }
+        for g in 0..GRID_COUNT {
+            for i in (0..GRID_WIDTH).step_by(2) {
+                out.forward(&mut l, &mut r);
+                out.grids[g][i] = l;
+                out.grids[g][i + 1] = r;
+            }
+        }
+        out
+    }
+
+    fn seal_block(&self, block: [u8; 8]) -> [u8; 8] {
+        let mut l = u32::from_be_bytes([block[0], block[1], block[2], block[3]]);
+        let mut r = u32::from_be_bytes([block[4], block[5], block[6], block[7]]);
+        self.forward(&mut l, &mut r);
+        let mut out = [0u8; 8];
+        out[..4].copy_from_slice(&l.to_be_bytes());
+        out[4..].copy_from_slice(&r.to_be_bytes());
+        out
+    }
+
+    fn open_block(&self, block: [u8; 8]) -> [u8; 8] {
+        let mut l = u32::from_be_bytes([block[0], block[1], block[2], block[3]]);
+        let mut r = u32::from_be_bytes([block[4], block[5], block[6], block[7]]);
+        self.backward(&mut l, &mut r);
+        let mut out = [0u8; 8];
+        out[..4].copy_from_slice(&l.to_be_bytes());
+        out[4..].copy_from_slice(&r.to_be_bytes());
+        out
+    }
+}
+
+/// 계산된 테이블을 Rust 소스로 직렬화
+fn render(tables: &CodecTables) -> String {
+    let mut src = String::new();
+    src.push_str("// @generated by build.rs — do not edit\n\n");
+    src.push_str(&format!("pub const LANE_SEED: [u32; {}] = [\n", LANE_COUNT));
+    for chunk in tables.lanes.chunks(6) {
+        src.push_str("   ");
+        for word in chunk {
+            let _ = write!(src, " 0x{:08x},", word);
+        }
+        src.push('\n');
+    }
+    src.push_str("];\n\n");
+
+    src.push_str(&format!(
+        "pub const GRID_SEED: [[u32; {}]; {}] = [\n",
+        GRID_WIDTH, GRID_COUNT
+    ));
+    for grid in &tables.grids {
+        src.push_str("    [\n");
+        for chunk in grid.chunks(6) {
+            src.push_str("       ");
+            for word in chunk {
+                let _ = write!(src, " 0x{:08x},", word);
+            }
+            src.push('\n');
+        }
+        src.push_str("    ],\n");
+    }
+    src.push_str("];\n");
+    src
+}
+
+/// 내보내기 전 자체 점검: 고정 비밀로 봉인·복원이 왕복하는지 확인
+fn self_check(tables: &CodecTables) -> bool {
+    let session = tables.keyed(b"telemetry-codec/self-check");
+    let probe = *b"probe-01";
+    session.open_block(session.seal_block(probe)) == probe
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let tables = CodecTables::from_seed(&seed_words());
+    if !self_check(&tables) {
+        panic!("codec table self-check failed");
+    }
+
+    let out_dir = env::var_os("OUT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(env::temp_dir);
+    let target = out_dir.join("codec_tables.rs");
+    fs::write(&target, render(&tables)).expect("write codec_tables.rs");
+    println!("cargo:rustc-env=CODEC_TABLES={}", target.display());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex8(s: &str) -> [u8; 8] {
+        let mut out = [0u8; 8];
+        for (i, byte) in out.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).unwrap();
+        }
+        out
+    }
+
+    #[test]
+    fn seed_series_digits() {
+        let words = seed_words();
+        assert_eq!(words[0], 0x243f6a88);
+        assert_eq!(words[1], 0x85a308d3);
+        assert_eq!(words[LANE_COUNT - 1], 0x8979fb1b);
+        assert_eq!(words[LANE_COUNT], 0xd1310ba6);
+        assert_eq!(words[SEED_WORDS - 1], 0x3ac372e6);
+    }
+
+    #[test]
+    fn codec_known_answer() {
+        // Schneier 테스트 벡터 (ECB 단일 블록)
+        let seed = CodecTables::from_seed(&seed_words());
+        let vectors = [
+            ("0000000000000000", "0000000000000000", "4ef997456198dd78"),
+            ("ffffffffffffffff", "ffffffffffffffff", "51866fd5b85ecb8a"),
+            ("0123456789abcdef", "1111111111111111", "61f9c3802281b096"),
+            ("fedcba9876543210", "0123456789abcdef", "0aceab0fc6a0a28d"),
+        ];
+        for (secret, plain, sealed) in vectors {
+            let session = seed.keyed(&hex8(secret));
+            assert_eq!(session.seal_block(hex8(plain)), hex8(sealed));
+            assert_eq!(session.open_block(hex8(sealed)), hex8(plain));
+        }
+    }
+
+    #[test]
+    fn self_check_passes() {
+        let tables = CodecTables::from_seed(&seed_words());
+        assert!(self_check(&tables));
+    }
+
+    #[test]
+    fn rendered_source_shape() {
+        let src = render(&CodecTables::from_seed(&seed_words()));
+        assert!(src.starts_with("// @generated"));
+        assert!(src.contains("pub const LANE_SEED: [u32; 18]"));
+        assert!(src.contains("pub const GRID_SEED: [[u32; 256]; 4]"));
+        assert!(src.contains("0x243f6a88"));
+        assert_eq!(src.matches("0x").count(), SEED_WORDS);
+    }
+}
@@ -0,0 +1,323 @@
+// Webhook Signature Verifier
+// Signs outgoing partner callbacks and checks inbound delivery receipts
+
+use std::collections::HashMap;
+
+const LANE_WORDS: usize = 8;
+const CHUNK_BYTES: usize = 64;
+const PRINT_BYTES: usize = 32;
+const INNER_FILL: u8 = 0x36;
+const OUTER_FILL: u8 = 0x5c;
+const REPLAY_WINDOW_SECS: u64 = 300;
+
+// Integer roots keep the schedule tables out of the binary's constant pool
+fn integer_root(value: u128, degree: u32) -> u128 {
+    let mut low: u128 = 0;
+    let mut high: u128 = 1 << (128 / degree);
+    while low < high {
+        let middle = (low + high + 1) / 2;
+        if middle.pow(degree) <= value {
+            low = middle;
+        } else {
+            high = middle - 1;
+        }
+    }
+    low
+}
+
+fn first_primes(count: usize) -> Vec<u128> {
+    let mut primes = Vec::with_capacity(count);
+    let mut candidate = 2u128;
+    while primes.len() < count {
+        if primes.iter().all(|p| candidate % p != 0) {
+            primes.push(candidate);
+        }
+        candidate += 1;
+    }
+    primes
+}
+
+#[derive(Clone)]
+struct DigestLanes {
+    seed_state: [u32; LANE_WORDS],
+    schedule: [u32; 64],
+}
+
+impl DigestLanes {
+    fn new() -> Self {
+        let primes = first_primes(64);
+        let mut seed_state = [0u32; LANE_WORDS];
+        let mut schedule = [0u32; 64];
+
+        // Fractional bits of square roots and cube roots of the leading primes
+        for i in 0..LANE_WORDS {
+            seed_state[i] = integer_root(primes[i] << 64, 2) as u32;
+        }
+        for i in 0..64 {
+            schedule[i] = integer_root(primes[i] << 96, 3) as u32;
+        }
+
+        DigestLanes { seed_state, schedule }
+    }
+
+    fn compress(&self, state: &mut [u32; LANE_WORDS], chunk: &[u8]) {
+        let mut w = [0u32; 64];
+        for i in 0..16 {
+            w[i] = u32::from_be_bytes([chunk[4 * i], chunk[4 * i + 1], chunk[4 * i + 2], chunk[4 * i + 3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let mut lanes = *state;
+        for i in 0..64 {
+            let [a, b, c, d, e, f, g, h] = lanes;
+            let sigma1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let choose = (e & f) ^ (!e & g);
+            let t1 = h
+                .wrapping_add(sigma1)
+                .wrapping_add(choose)
+                .wrapping_add(self.schedule[i])
+                .wrapping_add(w[i]);
+            let sigma0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let majority = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = sigma0.wrapping_add(majority);
+            lanes = [t1.wrapping_add(t2), a, b, c, d.wrapping_add(t1), e, f, g];
+        }
+
+        for i in 0..LANE_WORDS {
+            state[i] = state[i].wrapping_add(lanes[i]);
+        }
+    }
+
+    fn fingerprint(&self, parts: &[&[u8]]) -> [u8; PRINT_BYTES] {
+        let mut state = self.seed_state;
+        let mut pending: Vec<u8> = Vec::new();
+        let mut total_len: u64 = 0;
+
+        for part in parts {
+            total_len += part.len() as u64;
+            pending.extend_from_slice(part);
+            let full = pending.len() / CHUNK_BYTES * CHUNK_BYTES;
+            for chunk in pending[..full].chunks(CHUNK_BYTES) {
+                self.compress(&mut state, chunk);
+            }
+            pending.drain(..full);
+        }
+
+        pending.push(0x80);
+        while pending.len() % CHUNK_BYTES != 56 {
+            pending.push(0);
+        }
+        pending.extend_from_slice(&(total_len * 8).to_be_bytes());
+        for chunk in pending.chunks(CHUNK_BYTES) {
+            self.compress(&mut state, chunk);
+        }
+
+        let mut out = [0u8; PRINT_BYTES];
+        for (i, word) in state.iter().enumerate() {
+            out[4 * i..4 * i + 4].copy_from_slice(&word.to_be_bytes());
+        }
+        out
+    }
+}
+
+// Keyed fingerprint: secret folded into inner and outer passes
+#[derive(Clone)]
+struct CallbackStamp {
+    lanes: DigestLanes,
+    inner_pad: [u8; CHUNK_BYTES],
+    outer_pad: [u8; CHUNK_BYTES],
+}
+
+impl CallbackStamp {
+    fn new(secret: &[u8]) -> Self {
+        let lanes = DigestLanes::new();
+        let mut block = [0u8; CHUNK_BYTES];
+        if secret.len() > CHUNK_BYTES {
+            block[..PRINT_BYTES].copy_from_slice(&lanes.fingerprint(&[secret]));
+        } else {
+            block[..secret.len()].copy_from_slice(secret);
+        }
+
+        let mut inner_pad = [0u8; CHUNK_BYTES];
+        let mut outer_pad = [0u8; CHUNK_BYTES];
+        for i in 0..CHUNK_BYTES {
+            inner_pad[i] = block[i] ^ INNER_FILL;
+            outer_pad[i] = block[i] ^ OUTER_FILL;
+        }
+
+        CallbackStamp { lanes, inner_pad, outer_pad }
+    }
+
+    fn stamp(&self, message: &[u8]) -> [u8; PRINT_BYTES] {
+        let inner = self.lanes.fingerprint(&[&self.inner_pad, message]);
+        self.lanes.fingerprint(&[&self.outer_pad, &inner])
+    }
+
+    fn matches(&self, message: &[u8], presented: &[u8]) -> bool {
+        let expected = self.stamp(message);
+        if presented.len() != expected.len() {
+            return false;
+        }
+        let mut difference = 0u8;
+        for (a, b) in expected.iter().zip(presented) {
+            difference |= a ^ b;
+        }
+        difference == 0
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if text.len() % 2 != 0 {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeliveryVerdict {
+    Accepted,
+    UnknownPartner,
+    MalformedHeader,
+    Stale,
+    BadSignature,
+}
+
+pub struct WebhookGateway {
+    partner_secrets: HashMap<String, CallbackStamp>,
+}
+
+impl WebhookGateway {
+    pub fn new() -> Self {
+        WebhookGateway { partner_secrets: HashMap::new() }
+    }
+
+    pub fn enroll_partner(&mut self, partner: &str, secret: &[u8]) {
+        self.partner_secrets.insert(partner.to_string(), CallbackStamp::new(secret));
+    }
+
+    fn canonical(timestamp: u64, body: &str) -> Vec<u8> {
+        format!("{}.{}", timestamp, body).into_bytes()
+    }
+
+    // Header format: t=<unix seconds>,v1=<hex>
+    pub fn sign_callback(&self, partner: &str, timestamp: u64, body: &str) -> Option<String> {
+        let stamp = self.partner_secrets.get(partner)?;
+        let signature = stamp.stamp(&Self::canonical(timestamp, body));
+        Some(format!("t={},v1={}", timestamp, to_hex(&signature)))
+    }
+
+    pub fn verify_delivery(&self, partner: &str, header: &str, body: &str, now: u64) -> DeliveryVerdict {
+        let stamp = match self.partner_secrets.get(partner) {
+            Some(stamp) => stamp,
+            None => return DeliveryVerdict::UnknownPartner,
+        };
+
+        let mut timestamp = None;
+        let mut signature = None;
+        for field in header.split(',') {
+            match field.split_once('=') {
+                Some(("t", value)) => timestamp = value.parse::<u64>().ok(),
+                Some(("v1", value)) => signature = from_hex(value),
+                _ => {}
+            }
+        }
+
+        let (timestamp, signature) = match (timestamp, signature) {
+            (Some(t), Some(s)) => (t, s),
+            _ => return DeliveryVerdict::MalformedHeader,
+        };
+        if now.abs_diff(timestamp) > REPLAY_WINDOW_SECS {
+            return DeliveryVerdict::Stale;
+        }
+        if !stamp.matches(&Self::canonical(timestamp, body), &signature) {
+            return DeliveryVerdict::BadSignature;
+        }
+        DeliveryVerdict::Accepted
+    }
+}
+
+fn main() {
+    println!("Webhook Signature Verifier starting...");
+
+    let mut gateway = WebhookGateway::new();
+    gateway.enroll_partner("acme-logistics", b"whsec_5f2b9c01e7d84a6f");
+
+    let body = r#"{"event":"parcel.delivered","parcel":"PX-88213","at":"2024-03-18T09:12:44Z"}"#;
+    let header = gateway.sign_callback("acme-logistics", 1_710_753_164, body).unwrap();
+    println!("Outgoing header: {}", header);
+
+    let verdict = gateway.verify_delivery("acme-logistics", &header, body, 1_710_753_200);
+    println!("Inbound verdict: {:?}", verdict);
+
+    let forged = body.replace("delivered", "returned");
+    println!("Forged verdict: {:?}", gateway.verify_delivery("acme-logistics", &header, &forged, 1_710_753_200));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(text: &str) -> Vec<u8> {
+        from_hex(text).unwrap()
+    }
+
+    #[test]
+    fn digest_known_answer() {
+        // FIPS 180-4 section 6.2 examples, "abc" and two-block message
+        let lanes = DigestLanes::new();
+        assert_eq!(
+            lanes.fingerprint(&[b"abc"]).to_vec(),
+            hex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+        assert_eq!(
+            lanes.fingerprint(&[b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"]).to_vec(),
+            hex("248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1")
+        );
+    }
+
+    #[test]
+    fn keyed_stamp_known_answer() {
+        // RFC 4231 test cases 1, 2 and 6
+        let case1 = CallbackStamp::new(&[0x0b; 20]);
+        assert_eq!(
+            case1.stamp(b"Hi There").to_vec(),
+            hex("b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7")
+        );
+
+        let case2 = CallbackStamp::new(b"Jefe");
+        assert_eq!(
+            case2.stamp(b"what do ya want for nothing?").to_vec(),
+            hex("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+
+        let case6 = CallbackStamp::new(&[0xaa; 131]);
+        assert_eq!(
+            case6.stamp(b"Test Using Larger Than Block-Size Key - Hash Key First").to_vec(),
+            hex("60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54")
+        );
+    }
+
+    #[test]
+    fn round_trip_and_rejections() {
+        let mut gateway = WebhookGateway::new();
+        gateway.enroll_partner("p", b"secret");
+        let header = gateway.sign_callback("p", 1000, "{}").unwrap();
+
+        assert_eq!(gateway.verify_delivery("p", &header, "{}", 1100), DeliveryVerdict::Accepted);
+        assert_eq!(gateway.verify_delivery("p", &header, "{ }", 1100), DeliveryVerdict::BadSignature);
+        assert_eq!(gateway.verify_delivery("p", &header, "{}", 2000), DeliveryVerdict::Stale);
+        assert_eq!(gateway.verify_delivery("q", &header, "{}", 1100), DeliveryVerdict::UnknownPartner);
+        assert_eq!(gateway.verify_delivery("p", "t=1000", "{}", 1100), DeliveryVerdict::MalformedHeader);
+    }
+}
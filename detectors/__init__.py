@@ -0,0 +1,21 @@
+from .base_detector import BaseDetector
+from .retrieval import RetrievalDetector, Embedder, HashingEmbedder, OllamaEmbedder
+from .classifier import ClassifierDetector, LogisticModel
+from .ensemble import EnsembleDetector
+from .external import CbomDetector, SarifDetector, SemgrepDetector
+from .detector_factory import DetectorFactory
+
+__all__ = [
+    'BaseDetector',
+    'RetrievalDetector',
+    'Embedder',
+    'HashingEmbedder',
+    'OllamaEmbedder',
+    'ClassifierDetector',
+    'LogisticModel',
+    'EnsembleDetector',
+    'SemgrepDetector',
+    'SarifDetector',
+    'CbomDetector',
+    'DetectorFactory'
+]
@@ -0,0 +1,33 @@
+from abc import ABC, abstractmethod
+from typing import Dict, Any, List, Optional
+
+
+class BaseDetector(ABC):
+    """LLM을 쓰지 않는 로컬 탐지기 (기준선)
+
+    탐지기는 평가 전에 train 분할(utils/splits.py)의 라벨 달린 샘플로 준비(prepare)하고,
+    test 분할 샘플마다 라벨 목록을 반환합니다. 채점은 detectors/runner.py가 LLM 실행과 같은 방식으로 합니다.
+    """
+
+    name = 'base'
+
+    def prepare(self, agent_type: str, references: List[Dict[str, Any]]):
+        """train 분할 참조 샘플 [{'test_id', 'input_data', 'labels'}]로 준비 (기본: 아무것도 하지 않음)"""
+
+    @abstractmethod
+    def detect(self, agent_type: str, input_data: str, filename: Optional[str] = None) -> Dict[str, Any]:
+        """샘플 하나 → {'labels': [...], 'confidence': 0~1, 'evidence': [...]}
+
+        filename은 샘플의 원래 파일 이름(확장자로 언어를 고르는 외부 도구용)이며 모르면 None입니다.
+        보고 줄을 아는 탐지기(외부 도구 어댑터)는 'locations': [{'label', 'line'}]을 덧붙일 수 있고,
+        detectors/runner.py가 이를 근거 줄로 삼아 함수 단위 채점(utils/sample_index.py)에 씁니다.
+        라벨마다 신뢰도가 다르면 'scores': {라벨: 0~1}을 덧붙일 수 있습니다 (없는 라벨은 confidence를 씀).
+        보정 지표와 임계값별 PR 곡선(utils/calibration.py)이 이 값을 씁니다.
+        영역마다 후보를 순위로 매기는 탐지기는 'candidates': [{'labels': [가능성 순], 'line'}]을 덧붙일 수 있고
+        (line은 선택), top-k 정확도(utils/top_k.py)로 채점됩니다.
+        판단할 수 없는 샘플은 'abstain': True로 판단 보류할 수 있습니다 (라벨은 무시, utils/selective.py).
+        """
+
+    def describe(self) -> Dict[str, Any]:
+        """결과 메타데이터에 기록할 설정"""
+        return {'name': self.name}
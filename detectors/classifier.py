@@ -0,0 +1,175 @@
+"""
+로컬 분류기 탐지기 (classifier)
+
+utils/features.py의 정적 특징 벡터 위에서 학습된 분류기로 라벨을 예측합니다. 네트워크 없이 동작하는 순수 로컬 ML 기준선입니다.
+
+입력 벡터:
+    파일 전체 특징(FEATURE_NAMES)과 함수별 특징의 최댓값을 이어 붙이고 log(1 + x)로 눌러 씁니다.
+    언어 계열은 샘플 내용으로 추정합니다 (utils.features.guess_language).
+
+모델 형식 (확장자로 구분):
+    .json   detectors.train_baseline이 학습한 에이전트별 one-vs-rest 로지스틱 회귀 (의존성 없음)
+    .onnx   외부에서 학습한 ONNX 모델 (onnxruntime 필요). 입력 [1, 차원] float32, 출력은 라벨별 확률이며
+            라벨 순서는 모델 메타데이터 `labels`(JSON 목록), 에이전트는 `agent_type`으로 지정합니다
+"""
+
+import json
+import math
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from utils.features import FEATURE_NAMES, extract_features, feature_vector, guess_language
+from utils.top_k import MAX_CANDIDATES
+
+from .base_detector import BaseDetector
+
+MODEL_FORMAT = 'logistic-ovr'
+DEFAULT_MODEL_PATH = 'models/baseline_classifier.json'
+DEFAULT_THRESHOLD = 0.5
+
+VECTOR_NAMES = [f"file.{name}" for name in FEATURE_NAMES] + [f"max_function.{name}" for name in FEATURE_NAMES]
+
+
+def sample_vector(agent_type: str, input_data: str) -> List[float]:
+    """샘플 → 분류기 입력 벡터 (VECTOR_NAMES 순서)"""
+    features = extract_features(input_data, guess_language(agent_type, input_data))
+    functions = [feature_vector(function['features']) for function in features['functions']]
+    max_function = [max(column) for column in zip(*functions)] if functions else [0.0] * len(FEATURE_NAMES)
+    return [math.log1p(max(value, 0.0)) for value in feature_vector(features['file']) + max_function]
+
+
+def _sigmoid(z: float) -> float:
+    if z < -30:
+        return 0.0
+    if z > 30:
+        return 1.0
+    return 1.0 / (1.0 + math.exp(-z))
+
+
+class LogisticModel:
+    """표준화 + 라벨별 L2 로지스틱 회귀 (one-vs-rest, 배치 경사 하강)"""
+
+    def __init__(self, labels: List[str], mean: List[float], scale: List[float], weights: Dict[str, List[float]],
+                 bias: Dict[str, float]):
+        self.labels = labels
+        self.mean = mean
+        self.scale = scale
+        self.weights = weights
+        self.bias = bias
+
+    @classmethod
+    def fit(cls, vectors: List[List[float]], label_sets: List[List[str]], labels: List[str],
+            epochs: int = 500, learning_rate: float = 0.1, l2: float = 0.01) -> 'LogisticModel':
+        dimensions = len(vectors[0])
+        mean = [sum(column) / len(vectors) for column in zip(*vectors)]
+        scale = [math.sqrt(sum((v - m) ** 2 for v in column) / len(vectors)) or 1.0
+                 for column, m in zip(zip(*vectors), mean)]
+        rows = [[(v - m) / s for v, m, s in zip(vector, mean, scale)] for vector in vectors]
+
+        weights, bias = {}, {}
+        for label in labels:
+            targets = [1.0 if label in label_set else 0.0 for label_set in label_sets]
+            positives = sum(targets)
+            # 양성이 드문 라벨이 전부 0으로 수렴하지 않도록 양성 가중
+            positive_weight = (len(targets) - positives) / positives if positives else 1.0
+            w, b = [0.0] * dimensions, 0.0
+            for _ in range(epochs):
+                grad_w, grad_b = [l2 * value for value in w], 0.0
+                for row, target in zip(rows, targets):
+                    error = _sigmoid(sum(x * y for x, y in zip(w, row)) + b) - target
+                    error *= positive_weight if target else 1.0
+                    for index, value in enumerate(row):
+                        grad_w[index] += error * value / len(rows)
+                    grad_b += error / len(rows)
+                w = [value - learning_rate * grad for value, grad in zip(w, grad_w)]
+                b -= learning_rate * grad_b
+            weights[label], bias[label] = [round(value, 6) for value in w], round(b, 6)
+        return cls(labels, mean, scale, weights, bias)
+
+    def predict_proba(self, vector: List[float]) -> Dict[str, float]:
+        row = [(v - m) / s for v, m, s in zip(vector, self.mean, self.scale)]
+        return {label: _sigmoid(sum(x * y for x, y in zip(self.weights[label], row)) + self.bias[label])
+                for label in self.labels}
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {'labels': self.labels, 'mean': self.mean, 'scale': self.scale, 'weights': self.weights,
+                'bias': self.bias}
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> 'LogisticModel':
+        return cls(data['labels'], data['mean'], data['scale'], data['weights'], data['bias'])
+
+
+class OnnxModel:
+    """ONNX 분류기 (onnxruntime)"""
+
+    def __init__(self, path: str):
+        try:
+            import onnxruntime
+        except ImportError as e:
+            raise RuntimeError("ONNX 모델에는 onnxruntime이 필요합니다 (pip install onnxruntime)") from e
+        self.session = onnxruntime.InferenceSession(path)
+        metadata = self.session.get_modelmeta().custom_metadata_map
+        self.labels = json.loads(metadata.get('labels', '[]'))
+        self.agent_type = metadata.get('agent_type')
+        if not self.labels:
+            raise ValueError(f"{path}: 모델 메타데이터에 labels가 없습니다")
+
+    def predict_proba(self, vector: List[float]) -> Dict[str, float]:
+        import numpy
+        inputs = {self.session.get_inputs()[0].name: numpy.asarray([vector], dtype=numpy.float32)}
+        probabilities = self.session.run(None, inputs)[-1]
+        row = probabilities[0]
+        if isinstance(row, dict):  # ZipMap 출력 (skl2onnx 기본)
+            return {label: float(row.get(index, row.get(label, 0.0))) for index, label in enumerate(self.labels)}
+        return {label: float(value) for label, value in zip(self.labels, row)}
+
+
+def load_models(path: str) -> Dict[str, Any]:
+    """모델 파일 → {agent_type: 모델} (ONNX는 agent_type 메타데이터가 없으면 모든 에이전트에 사용: '*')"""
+    if Path(path).suffix == '.onnx':
+        model = OnnxModel(path)
+        return {model.agent_type or '*': model}
+
+    with open(path, 'r', encoding='utf-8') as f:
+        data = json.load(f)
+    if data.get('format') != MODEL_FORMAT:
+        raise ValueError(f"{path}: 지원하지 않는 모델 형식 {data.get('format')!r} (expected {MODEL_FORMAT})")
+    if data.get('vector_names') != VECTOR_NAMES:
+        raise ValueError(f"{path}: 특징 구성이 현재 utils/features.py와 다릅니다. 다시 학습하세요")
+    return {agent_type: LogisticModel.from_dict(model) for agent_type, model in data['agents'].items()}
+
+
+class ClassifierDetector(BaseDetector):
+    """정적 특징 분류기"""
+
+    name = 'classifier'
+
+    def __init__(self, model_path: str = DEFAULT_MODEL_PATH, threshold: float = DEFAULT_THRESHOLD):
+        self.model_path = model_path
+        self.threshold = threshold
+        self.models = load_models(model_path)
+
+    def model_for(self, agent_type: str) -> Optional[Any]:
+        return self.models.get(agent_type, self.models.get('*'))
+
+    def detect(self, agent_type: str, input_data: str, filename: Optional[str] = None) -> Dict[str, Any]:
+        model = self.model_for(agent_type)
+        if model is None:
+            return {'labels': [], 'confidence': 0.0, 'evidence': [f"{agent_type}용 모델 없음"]}
+
+        probabilities = model.predict_proba(sample_vector(agent_type, input_data))
+        ranked = sorted(probabilities.items(), key=lambda item: (-item[1], item[0]))
+        labels = [label for label, probability in ranked if probability >= self.threshold]
+        return {
+            'labels': labels,
+            'confidence': ranked[0][1] if labels else 1.0 - (ranked[0][1] if ranked else 0.0),
+            'evidence': [f"{label} ({probability:.3f})" for label, probability in ranked[:5]],
+            'scores': {label: probabilities[label] for label in labels},
+            # 샘플 전체를 한 영역으로 본 순위 후보 (임계값 아래 라벨 포함, top-k 채점용)
+            'candidates': [{'labels': [label for label, _ in ranked[:MAX_CANDIDATES]]}] if ranked else [],
+        }
+
+    def describe(self) -> Dict[str, Any]:
+        return {'name': self.name, 'model': self.model_path, 'threshold': self.threshold,
+                'agents': sorted(self.models)}
@@ -0,0 +1,65 @@
+from typing import Dict, Any, List
+from .base_detector import BaseDetector
+from .classifier import ClassifierDetector
+from .ensemble import EnsembleDetector
+from .external.cbom import CbomDetector
+from .external.command import CommandDetector
+from .external.grpc import GrpcDetector
+from .external.sarif import SarifDetector
+from .external.semgrep import SemgrepDetector
+from .retrieval import RetrievalDetector, HashingEmbedder, OllamaEmbedder
+from .signature import SignatureDetector
+from .structure import StructureDetector
+
+
+class DetectorFactory:
+    _detectors = {
+        'retrieval': RetrievalDetector,
+        'signature': SignatureDetector,
+        'structure': StructureDetector,
+        'classifier': ClassifierDetector,
+        'ensemble': EnsembleDetector,
+        'semgrep': SemgrepDetector,
+        'sarif': SarifDetector,
+        'cbom': CbomDetector,
+        'grpc': GrpcDetector,
+        'command': CommandDetector,
+    }
+
+    _embedders = {
+        'hashing': HashingEmbedder,
+        'ollama': OllamaEmbedder,
+    }
+
+    @classmethod
+    def create_detector(cls, detector_type: str, config: Dict[str, Any] = None) -> BaseDetector:
+        config = dict(config or {})
+        # 내장이 아니면 플러그인 (detectors/plugins.py): plugin_dirs 말고는 플러그인 생성자 인자
+        plugin_dirs = config.pop('plugin_dirs', None)
+        if detector_type not in cls._detectors:
+            from .plugins import create_plugin_detector
+            return create_plugin_detector(detector_type, config, plugin_dirs, tuple(cls._detectors))
+
+        if detector_type == 'retrieval':
+            embedder = config.pop('embedder', 'hashing')
+            embedder_config = config.pop('embedder_config', {})
+            if embedder not in cls._embedders:
+                raise ValueError(f"Unsupported embedder: {embedder}. Supported: {list(cls._embedders.keys())}")
+            config['embedder'] = cls._embedders[embedder](**embedder_config)
+        elif detector_type == 'ensemble':
+            names = config.pop('members')
+            member_configs = config.pop('member_configs', [{}] * len(names))
+            config['members'] = [cls.create_detector(name, {'plugin_dirs': plugin_dirs, **member_config})
+                                 for name, member_config in zip(names, member_configs)]
+
+        return cls._detectors[detector_type](**config)
+
+    @classmethod
+    def get_supported_detectors(cls, plugin_dirs: List[str] = None) -> list:
+        """내장 탐지기 + 찾은 플러그인 이름"""
+        from .plugins import discover_plugins
+        return cls.get_builtin_detectors() + list(discover_plugins(plugin_dirs, tuple(cls._detectors)))
+
+    @classmethod
+    def get_builtin_detectors(cls) -> list:
+        return list(cls._detectors.keys())
@@ -0,0 +1,234 @@
+"""
+앙상블 메타 탐지기 (ensemble)
+
+여러 탐지기의 판정을 합쳐 하나의 라벨 목록을 만듭니다. "정규식 + LLM이 각각보다 나은가"를 답하기 위해
+앙상블 점수와 함께 멤버별 기여도를 보고합니다.
+
+전략:
+    majority             멤버 과반수(> n/2)가 보고한 라벨 (멤버가 둘이면 둘 다 동의한 라벨)
+    union                어느 한 멤버라도 보고한 라벨
+    precision_weighted   라벨을 보고한 멤버 가중치 합 / 전체 가중치 합 ≥ 0.5
+                         가중치는 멤버의 precision (결과 파일 앙상블에서는 평가 샘플 자신을 뺀 나머지 샘플의
+                         평균 계층 precision, leave-one-out이므로 평가 샘플의 정답이 가중치에 새지 않음)
+
+라벨 투표 단위는 분류 체계의 알고리즘 계열입니다 (RSA-2048과 RSA는 같은 표). 채택된 계열은
+그 계열에 투표한 멤버들이 가장 많이 쓴 라벨 이름으로 출력합니다.
+
+두 가지 방식으로 씁니다:
+    EnsembleDetector     로컬 탐지기(retrieval, classifier 등)를 묶은 탐지기
+                         (python -m detectors.runner --detector ensemble --members retrieval classifier)
+    결과 파일 앙상블      이미 실행된 결과 파일(LLM 실행 포함)의 탐지기들을 샘플 단위로 합쳐 채점
+
+멤버 기여도 (결과 파일 앙상블):
+    alone      멤버 혼자의 평균 계층 F1
+    without    멤버를 뺀 앙상블의 평균 계층 F1 (leave-one-member-out)
+    delta      앙상블 F1 - without (양수면 그 멤버가 앙상블에 보탬)
+    vs_best    앙상블 - 가장 좋은 단일 멤버의 평균 차이와 부트스트랩 95% 신뢰구간
+
+결과 파일 앙상블 CLI는 detectors/ensemble_report.py입니다.
+"""
+
+import json
+from collections import Counter
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Sequence, Tuple
+
+from utils.metrics_calculator import MetricsCalculator
+from utils.results_store import ground_truth_hash
+from utils.significance import bootstrap_ci, paired_bootstrap_ci
+from utils.taxonomy import AlgorithmTaxonomy, normalize_name
+
+from .base_detector import BaseDetector
+
+STRATEGIES = ['majority', 'union', 'precision_weighted']
+
+DEFAULT_STRATEGY = 'majority'
+WEIGHTED_THRESHOLD = 0.5
+
+_taxonomy = AlgorithmTaxonomy()
+
+
+def family_key(label: str) -> str:
+    """투표 단위 (알고리즘 계열, 분류 체계에 없으면 이름 자체)"""
+    node = _taxonomy.resolve(label)
+    return normalize_name(node[1] or label)
+
+
+def combine(votes: Sequence[Tuple[str, List[str]]], strategy: str = DEFAULT_STRATEGY,
+            weights: Optional[Dict[str, float]] = None) -> List[str]:
+    """멤버별 라벨 [(멤버, [라벨])] → 앙상블 라벨"""
+    if strategy not in STRATEGIES:
+        raise ValueError(f"unknown ensemble strategy: {strategy} (choose from {STRATEGIES})")
+    if not votes:
+        return []
+
+    voters: Dict[str, List[str]] = {}
+    names: Dict[str, Counter] = {}
+    for member, labels in votes:
+        for label in labels:
+            key = family_key(label)
+            if member not in voters.setdefault(key, []):
+                voters[key].append(member)
+            names.setdefault(key, Counter())[label] += 1
+
+    weights = weights or {}
+    total_weight = sum(weights.get(member, 1.0) for member, _ in votes)
+
+    def accepted(members: List[str]) -> bool:
+        if strategy == 'union':
+            return True
+        if strategy == 'majority':
+            return len(members) > len(votes) / 2
+        weight = sum(weights.get(member, 1.0) for member in members)
+        return total_weight > 0 and weight / total_weight >= WEIGHTED_THRESHOLD
+
+    return [names[key].most_common(1)[0][0] for key in voters if accepted(voters[key])]
+
+
+def member_score(detections: List[Tuple[str, Dict[str, Any]]], label: str) -> float:
+    """앙상블 라벨의 신뢰도: 같은 계열을 보고한 멤버들의 라벨별 신뢰도 평균 (안 보고한 멤버는 0)"""
+    key = family_key(label)
+    scores = []
+    for _, detection in detections:
+        reported = [name for name in detection['labels'] if family_key(name) == key]
+        scores.append(max((detection.get('scores', {}).get(name, detection['confidence']) for name in reported),
+                          default=0.0))
+    return sum(scores) / len(scores) if scores else 0.0
+
+
+class EnsembleDetector(BaseDetector):
+    """로컬 탐지기 묶음"""
+
+    name = 'ensemble'
+
+    def __init__(self, members: List[BaseDetector], strategy: str = DEFAULT_STRATEGY,
+                 weights: Optional[Dict[str, float]] = None):
+        if strategy not in STRATEGIES:
+            raise ValueError(f"unknown ensemble strategy: {strategy} (choose from {STRATEGIES})")
+        self.members = members
+        self.strategy = strategy
+        self.weights = weights or {}
+
+    def prepare(self, agent_type: str, references: List[Dict[str, Any]]):
+        for member in self.members:
+            member.prepare(agent_type, references)
+
+    def detect(self, agent_type: str, input_data: str, filename: Optional[str] = None) -> Dict[str, Any]:
+        detections = [(member.name, member.detect(agent_type, input_data, filename)) for member in self.members]
+        labels = combine([(name, detection['labels']) for name, detection in detections], self.strategy,
+                         self.weights)
+        keys = {family_key(label) for label in labels}
+        agreeing = [detection['confidence'] for _, detection in detections
+                    if any(family_key(label) in keys for label in detection['labels'])]
+        return {
+            'labels': labels,
+            'confidence': sum(agreeing) / len(agreeing) if agreeing else 0.0,
+            'scores': {label: member_score(detections, label) for label in labels},
+            'evidence': [f"{name}: {', '.join(detection['labels']) or '-'}" for name, detection in detections],
+            'members': {name: detection['labels'] for name, detection in detections}
+        }
+
+    def describe(self) -> Dict[str, Any]:
+        return {'name': self.name, 'strategy': self.strategy, 'weights': self.weights,
+                'members': [member.describe() for member in self.members]}
+
+
+# ==================== 결과 파일 앙상블 ====================
+
+def aligned_samples(member_rows: Dict[str, List[Dict[str, Any]]],
+                    ground_truths: Dict[Tuple[str, str], Dict[str, Any]]) -> List[Dict[str, Any]]:
+    """모든 멤버가 결과를 낸 샘플 [{'key', 'ground_truth', 'labels': {멤버: [라벨]}}]
+
+    라벨 목록이 없는 행과, 결과의 ground_truth_hash가 현재 ground truth와 다른 행은 제외합니다.
+    """
+    indexed: Dict[str, Dict[Tuple[str, str], List[str]]] = {}
+    for member, rows in member_rows.items():
+        indexed[member] = {}
+        for row in rows:
+            key = (row['agent_type'], row['test_id'])
+            ground_truth = ground_truths.get(key)
+            if row.get('detected_algorithms') is None or ground_truth is None:
+                continue
+            if row.get('ground_truth_hash') and row['ground_truth_hash'] != ground_truth_hash(ground_truth):
+                continue
+            indexed[member][key] = [str(label) for label in row['detected_algorithms']]
+
+    common = set.intersection(*(set(labels) for labels in indexed.values())) if indexed else set()
+    return [
+        {'key': key, 'ground_truth': ground_truths[key], 'labels': {m: indexed[m][key] for m in member_rows}}
+        for key in sorted(common)
+    ]
+
+
+def _f1(labels: List[str], ground_truth: Dict[str, Any]) -> float:
+    return MetricsCalculator.calculate_hierarchical_scores(labels, ground_truth)['f1']
+
+
+def _precision(labels: List[str], ground_truth: Dict[str, Any]) -> Optional[float]:
+    if not labels:
+        return None
+    return MetricsCalculator.calculate_hierarchical_scores(labels, ground_truth)['precision']
+
+
+def loo_weights(samples: List[Dict[str, Any]], members: List[str]) -> List[Dict[str, float]]:
+    """샘플마다 그 샘플을 뺀 나머지에서 잰 멤버별 평균 precision"""
+    precisions = {
+        member: [_precision(sample['labels'][member], sample['ground_truth']) for sample in samples]
+        for member in members
+    }
+    weights = []
+    for index in range(len(samples)):
+        row = {}
+        for member in members:
+            others = [p for i, p in enumerate(precisions[member]) if i != index and p is not None]
+            row[member] = sum(others) / len(others) if others else 0.0
+        weights.append(row)
+    return weights
+
+
+def ensemble_scores(samples: List[Dict[str, Any]], members: List[str], strategy: str) -> List[float]:
+    weights = loo_weights(samples, members) if strategy == 'precision_weighted' else [None] * len(samples)
+    return [
+        _f1(combine([(member, sample['labels'][member]) for member in members], strategy, weight),
+            sample['ground_truth'])
+        for sample, weight in zip(samples, weights)
+    ]
+
+
+def evaluate_ensemble(samples: List[Dict[str, Any]], members: List[str], strategy: str) -> Dict[str, Any]:
+    """앙상블 점수와 멤버별 기여도"""
+    scores = ensemble_scores(samples, members, strategy)
+    alone = {member: [_f1(sample['labels'][member], sample['ground_truth']) for sample in samples]
+             for member in members}
+    mean = sum(scores) / len(scores) if scores else 0.0
+
+    contributions = {}
+    for member in members:
+        rest = [other for other in members if other != member]
+        without = sum(ensemble_scores(samples, rest, strategy)) / len(samples) if rest and samples else 0.0
+        contributions[member] = {
+            'alone': sum(alone[member]) / len(samples) if samples else 0.0,
+            'without': without,
+            'delta': mean - without
+        }
+
+    best = max(members, key=lambda member: contributions[member]['alone'])
+    return {
+        'strategy': strategy,
+        'n': len(samples),
+        'hierarchical_f1': bootstrap_ci(scores),
+        'members': contributions,
+        'best_member': best,
+        'vs_best': paired_bootstrap_ci(alone[best], scores)
+    }
+
+
+def load_ground_truths(keys: List[Tuple[str, str]], ground_truth_dir: str = 'data/ground_truth'
+                       ) -> Dict[Tuple[str, str], Dict[str, Any]]:
+    ground_truths = {}
+    for agent_type, test_id in keys:
+        path = Path(ground_truth_dir) / agent_type / f"{test_id}.json"
+        if path.exists():
+            with open(path, 'r', encoding='utf-8') as f:
+                ground_truths[(agent_type, test_id)] = json.load(f)
+    return ground_truths
@@ -0,0 +1,77 @@
+"""
+결과 파일 앙상블 보고서
+
+이미 실행된 결과 파일(LLM 실행, detectors.runner 로컬 기준선)의 탐지기들을 샘플 단위로 합쳐 채점하고,
+전략별 앙상블 점수와 멤버별 기여도(단독, 제외 시, 기여)를 출력합니다. 전략과 기여도 정의는 detectors/ensemble.py 참조.
+
+멤버는 모든 멤버가 라벨 목록을 남긴 공통 샘플에서만 비교합니다.
+
+사용법:
+    python -m detectors.ensemble_report results/llama_final.json results/retrieval-hashing.json \\
+        --members "llama3:8b [rag]" retrieval-hashing
+    python -m detectors.ensemble_report results/gpt_final.json results/llama_final.json \\
+        --members "gpt-4.1 [rag]" "llama3:8b [rag]" --strategy union --output results/ensemble.json
+"""
+
+import argparse
+import json
+from pathlib import Path
+from typing import Dict, Any, List
+
+from detectors.ensemble import STRATEGIES, aligned_samples, evaluate_ensemble, load_ground_truths
+from utils.run_compare import group_by_detector, load_rows, select_detector
+
+
+def print_report(report: Dict[str, Any]):
+    f1 = report['hierarchical_f1']
+    vs_best = report['vs_best']
+    marker = '✅' if vs_best['significant'] and vs_best['mean_diff'] > 0 else (
+        '❌' if vs_best['significant'] else '➖')
+    print(f"\n🗳️  {report['strategy']}: 샘플 {report['n']}개, 앙상블 계층 F1 {f1['mean']:.3f} "
+          f"[95% CI {f1['ci_low']:.3f}, {f1['ci_high']:.3f}]")
+    print(f"  {marker} 최고 단일 멤버({report['best_member']}) 대비 {vs_best['mean_diff']:+.3f} "
+          f"[95% CI {vs_best['ci_low']:+.3f}, {vs_best['ci_high']:+.3f}]")
+    print(f"  {'멤버':<36} {'단독':>8} {'제외 시':>8} {'기여':>8}")
+    for member, stats in report['members'].items():
+        print(f"  {member[:36]:<36} {stats['alone']:>8.3f} {stats['without']:>8.3f} {stats['delta']:>+8.3f}")
+
+
+def main():
+    parser = argparse.ArgumentParser(description='결과 파일 탐지기 앙상블')
+    parser.add_argument('files', nargs='+', help='결과 파일 (benchmark_runner, benchmark_rag_effect, detectors.runner)')
+    parser.add_argument('--members', nargs='+', help='앙상블할 탐지기 (부분 문자열 일치, 기본: 모든 탐지기)')
+    parser.add_argument('--strategy', nargs='+', choices=STRATEGIES, default=STRATEGIES, help='앙상블 전략')
+    parser.add_argument('--output', help='보고서 JSON 경로')
+    args = parser.parse_args()
+
+    groups: Dict[str, List[Dict[str, Any]]] = {}
+    for path in args.files:
+        for name, rows in group_by_detector(load_rows(path)).items():
+            groups.setdefault(name, []).extend(rows)
+
+    members = [select_detector(groups, pattern) for pattern in args.members] if args.members else sorted(groups)
+    if len(members) < 2:
+        parser.error(f"앙상블에는 탐지기가 둘 이상 필요합니다 (찾은 탐지기: {sorted(groups)})")
+
+    member_rows = {member: groups[member] for member in members}
+    keys = sorted({(row['agent_type'], row['test_id']) for rows in member_rows.values() for row in rows})
+    samples = aligned_samples(member_rows, load_ground_truths(keys))
+    if not samples:
+        print("❌ 모든 멤버가 라벨 목록을 남긴 공통 샘플이 없습니다")
+        raise SystemExit(1)
+
+    print(f"🔗 앙상블 멤버 {len(members)}개: {', '.join(members)}")
+    reports = [evaluate_ensemble(samples, members, strategy) for strategy in args.strategy]
+    for report in reports:
+        print_report(report)
+
+    if args.output:
+        Path(args.output).parent.mkdir(parents=True, exist_ok=True)
+        with open(args.output, 'w', encoding='utf-8') as f:
+            json.dump({'members': members, 'files': args.files, 'reports': reports}, f, indent=2,
+                      ensure_ascii=False)
+        print(f"\n💾 보고서 저장: {args.output}")
+
+
+if __name__ == "__main__":
+    main()
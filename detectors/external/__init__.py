@@ -0,0 +1,9 @@
+"""외부 분석 도구 어댑터 (도구 출력을 BaseDetector 탐지 보고로 변환)"""
+
+from .cbom import CbomDetector
+from .command import CommandDetector
+from .grpc import GrpcDetector
+from .sarif import SarifDetector
+from .semgrep import SemgrepDetector
+
+__all__ = ['CbomDetector', 'CommandDetector', 'GrpcDetector', 'SarifDetector', 'SemgrepDetector']
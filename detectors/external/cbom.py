@@ -0,0 +1,131 @@
+"""
+암호 자산 목록(CBOM) 어댑터 (cbom)
+
+상용/오픈소스 암호 인벤토리 도구를 벤치마크 탐지기와 같은 채점으로 평가할 수 있도록, 도구가 코퍼스에 대해 만든
+CBOM(CycloneDX cryptographic-asset, cbomkit 출력 등)이나 단순 자산 목록 JSON을 읽어 샘플별 탐지 보고로 바꿉니다.
+
+입력 형식:
+    CycloneDX CBOM    components[] 중 type이 cryptographic-asset인 것
+        algorithm                       이름(name)과 algorithmProperties의 parameterSetIdentifier, curve
+        certificate                     signatureAlgorithmRef가 가리키는 algorithm 자산의 라벨
+        related-crypto-material         algorithmRef가 가리키는 algorithm 자산의 라벨 (키, 서명 값 등)
+        protocol                        라벨 없음 (cipher suite의 알고리즘은 각자 자산으로 셈)
+        위치는 evidence.occurrences[]의 location/line, 신뢰도는 evidence.identity의 confidence (없으면 0.8)
+    자산 목록          [{"name"|"algorithm": "RSA-2048", "location"|"file"|"path": "src/a.java", "line": 12}, ...]
+                      또는 {"assets": [...]} (위치가 여러 개면 "locations": [{"location", "line"}, ...])
+
+자산 이름 → 라벨:
+    label_map(--label-map)의 이름 접두사가 맞으면 그 라벨, 아니면 이름을 영숫자 토큰으로 나눠 분류 체계에 있는 이름
+    ("SHA256withRSA" → SHA-256, RSA / "AES-128-GCM" → AES-128 / "ECDSA-P256" → ECDSA, P-256)
+    변환 규칙은 detectors/external/labels.py, 샘플 경로 맞추기는 detectors/external/findings.py를 따릅니다.
+    위치가 없는 자산은 어느 샘플에도 붙지 않으며 describe()의 unlocated에 셉니다.
+
+사용법:
+    cbomkit-theia dir data/test_files/source_code > results/cbom-source_code.json
+    python -m detectors.runner --detector cbom --cbom results/cbom-source_code.json --agents source_code
+"""
+
+import json
+import re
+from typing import Dict, Any, List, Optional
+
+from .findings import FileFindingsDetector, normalize_uri, result_files
+from .labels import RuleLabeler, load_label_map
+
+CBOM_SUFFIXES = ('.json', '.cdx', '.cbom')
+DEFAULT_CONFIDENCE = 0.8
+CRYPTO_ASSET = 'cryptographic-asset'
+
+
+class CbomDetector(FileFindingsDetector):
+    """CBOM/자산 목록을 샘플별 탐지 보고로 변환"""
+
+    name = 'cbom'
+
+    def __init__(self, cbom: List[str], label_map: Optional[str] = None):
+        super().__init__()
+        self.cbom_paths = [str(path) for path in result_files(list(cbom), CBOM_SUFFIXES)]
+        if not self.cbom_paths:
+            raise ValueError(f"CBOM 파일이 없습니다: {cbom}")
+        self.label_map_path = label_map
+        self.labeler = RuleLabeler(load_label_map(label_map))
+        self.tools: List[str] = []
+        self.assets = 0
+        self.unlocated = 0
+        for path in self.cbom_paths:
+            with open(path, 'r', encoding='utf-8') as f:
+                document = json.load(f)
+            if isinstance(document, dict) and 'components' in document:
+                self.load_cyclonedx(document)
+            else:
+                self.load_assets(document.get('assets', []) if isinstance(document, dict) else document)
+
+    def asset_labels(self, name: str, *details: Optional[str]) -> List[str]:
+        """자산 이름(과 파라미터, 곡선) → 라벨"""
+        prefixes = [prefix for prefix in self.labeler.label_map if name.startswith(prefix)]
+        if prefixes:
+            return self.labeler.label_map[max(prefixes, key=len)]
+        labels: List[str] = []
+        for text in (name,) + details:
+            for label in self.labeler.taxonomy_names(re.sub(r'(?<=[0-9A-Za-z])[Ww]ith(?=[A-Z])', ' ', text or '')):
+                if label not in labels:
+                    labels.append(label)
+        return labels
+
+    def add_asset(self, name: str, labels: List[str], occurrences: List[Dict[str, Any]], confidence: float):
+        self.assets += 1
+        located = [occurrence for occurrence in occurrences if occurrence.get('location')]
+        if not located:
+            self.unlocated += 1
+        for occurrence in located:
+            self.add_finding(normalize_uri(str(occurrence['location'])), occurrence.get('line'), name, labels,
+                             confidence)
+
+    def load_cyclonedx(self, bom: Dict[str, Any]):
+        tools = bom.get('metadata', {}).get('tools', [])
+        tools = tools.get('components', []) if isinstance(tools, dict) else tools  # CycloneDX 1.5+: {components}
+        for tool in tools:
+            label = ' '.join(part for part in (tool.get('name'), tool.get('version')) if part)
+            if label and label not in self.tools:
+                self.tools.append(label)
+
+        components = [component for component in bom.get('components', [])
+                      if component.get('type') == CRYPTO_ASSET]
+        algorithms: Dict[str, List[str]] = {}
+        for component in components:
+            properties = component.get('cryptoProperties', {})
+            if properties.get('assetType') == 'algorithm':
+                algorithm = properties.get('algorithmProperties', {})
+                algorithms[component.get('bom-ref', '')] = self.asset_labels(
+                    component.get('name', ''), algorithm.get('parameterSetIdentifier'), algorithm.get('curve'))
+
+        for component in components:
+            properties = component.get('cryptoProperties', {})
+            asset_type = properties.get('assetType')
+            if asset_type == 'algorithm':
+                labels = algorithms.get(component.get('bom-ref', ''), [])
+            elif asset_type == 'certificate':
+                labels = algorithms.get(properties.get('certificateProperties', {}).get('signatureAlgorithmRef'), [])
+            elif asset_type == 'related-crypto-material':
+                labels = algorithms.get(properties.get('relatedCryptoMaterialProperties', {}).get('algorithmRef'), [])
+            else:
+                continue
+            evidence = component.get('evidence', {})
+            identity = evidence.get('identity') or {}
+            identity = identity[0] if isinstance(identity, list) and identity else identity  # CycloneDX 1.6: 목록
+            confidence = identity.get('confidence', DEFAULT_CONFIDENCE) if isinstance(identity, dict) \
+                else DEFAULT_CONFIDENCE
+            self.add_asset(component.get('name', ''), labels, evidence.get('occurrences', []), confidence)
+
+    def load_assets(self, assets: List[Dict[str, Any]]):
+        for asset in assets:
+            name = str(asset.get('name') or asset.get('algorithm') or '')
+            location = asset.get('location') or asset.get('file') or asset.get('path')
+            occurrences = asset.get('locations') or ([{'location': location, 'line': asset.get('line')}]
+                                                     if location else [])
+            self.add_asset(name, self.asset_labels(name), occurrences,
+                           asset.get('confidence', DEFAULT_CONFIDENCE))
+
+    def describe(self) -> Dict[str, Any]:
+        return {'name': self.name, 'cbom': self.cbom_paths, 'tools': self.tools, 'label_map': self.label_map_path,
+                'assets': self.assets, 'unlocated': self.unlocated}
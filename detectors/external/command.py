@@ -0,0 +1,344 @@
+"""
+외부 명령 어댑터 (command)
+
+아무 언어로 만든 도구든 표준 입출력 JSON 계약만 지키면 하네스에 연결합니다. gRPC 서버(detectors/external/grpc.py)를
+띄울 필요 없이 명령 하나면 되는 가장 가벼운 연결 방식입니다.
+
+계약:
+    요청 (stdin, JSON 객체)   {"type": "detect", "agent_type": "source_code", "input_data": "<코드>", "filename": "app.rs"}
+    응답 (stdout, JSON 객체)  {"labels": ["RSA-2048", ...], "confidence": 0.9, "scores": {"RSA-2048": 0.8},
+                              "evidence": ["..."], "locations": [{"label": "RSA-2048", "line": 12}],
+                              "candidates": [{"labels": ["SEED", "Feistel"], "line": 40}], "abstain": false}
+    labels 말고는 선택입니다 (confidence 기본 1.0, 라벨이 없으면 0, scores는 라벨별 0~1 신뢰도,
+    candidates는 영역별 순위 후보로 top-k 채점용, abstain이 true면 판단 보류). stderr는 무시하고, 종료 코드가 0이 아니거나
+    응답이 계약에 맞지 않으면 그 샘플만 crash로 기록합니다.
+
+모드:
+    샘플마다 (기본)   샘플마다 명령을 새로 실행해 요청 하나를 stdin으로 주고 stdin을 닫음 → stdout 전체가 응답
+    batch            명령을 한 번 띄워 두고 JSON Lines로 주고받음 (요청 한 줄 → 응답 한 줄). 시작 비용이 큰 도구용.
+                     에이전트마다 {"type": "prepare", "agent_type", "references": [{"test_id", "input_data",
+                     "labels"}]}를 먼저 보내며(응답은 아무 JSON 객체 한 줄), 시간 초과나 비정상 응답이면 프로세스를
+                     종료하고 다음 샘플에서 다시 띄움
+
+제한:
+    timeout        샘플(batch면 응답 한 줄)당 벽시계 제한 시간(초), 넘으면 프로세스를 종료
+    memory_mb      주소 공간 상한 (RLIMIT_AS, POSIX만 / 컨테이너면 --memory)
+    cpu_seconds    CPU 시간 상한 (RLIMIT_CPU, POSIX만, batch면 프로세스 전체 / 컨테이너면 --ulimit cpu)
+    stdout은 MAX_OUTPUT_BYTES까지만 읽고, 넘으면 프로세스를 종료합니다.
+    batch에서 응답 한 줄 뒤에 더 나온 출력은 계약 위반으로 보고 프로세스를 종료합니다.
+
+컨테이너 격리 (container=docker|podman, image):
+    커뮤니티가 제출한 신뢰할 수 없는 탐지기를 공용 장비에서 돌릴 때 씁니다. 명령을 이미지 안에서 실행하며(명령을 비우면
+    이미지의 ENTRYPOINT), 네트워크 없음, 읽기 전용 루트(/tmp만 tmpfs), 모든 capability 제거, 권한 상승 금지,
+    프로세스 수 상한을 항상 걸고 메모리/CPU 상한은 컨테이너 옵션으로 겁니다. mount를 주면 호스트 디렉토리를
+    /detector에 읽기 전용으로 마운트하고 작업 디렉토리로 씁니다 (이미지에 도구를 넣지 않은 제출물용).
+    시간 초과나 종료 시 컨테이너는 이름으로 강제 삭제합니다.
+
+사용법:
+    python -m detectors.runner --detector command --command "python tools/my_scanner.py"
+    python -m detectors.runner --detector command --command "./scanner --json" --command-batch --command-timeout 30
+    python -m detectors.runner --detector command --command "node scan.js" --command-memory-mb 1024 \
+        --command-cpu-seconds 20
+    python -m detectors.runner --detector command --command-container docker \
+        --command-image qvbench/contrib-scanner:1.0 --command-batch --command-memory-mb 1024 --command-cpus 1
+    python -m detectors.runner --detector command --command "python scan.py" --command-container docker \
+        --command-image python:3.11-slim --command-mount submissions/scanner
+"""
+
+import json
+import os
+import selectors
+import shlex
+import shutil
+import subprocess
+import threading
+import time
+import uuid
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple, Union
+
+from ..base_detector import BaseDetector
+from .findings import as_array, as_object, as_text
+from .labels import MAX_EVIDENCE
+
+DEFAULT_TIMEOUT = 60
+MAX_OUTPUT_BYTES = 16 * 1024 * 1024
+# 모델 이름을 정할 때 건너뛰는 인터프리터 (python tools/x.py → x)
+INTERPRETERS = {'python', 'python3', 'node', 'ruby', 'java', 'bash', 'sh', 'perl', 'php', 'go', 'dotnet', 'deno'}
+CONTAINERS = ('docker', 'podman')
+CONTAINER_WORKDIR = '/detector'
+CONTAINER_PIDS_LIMIT = 256
+
+
+def command_name(command: Union[str, List[str], None], image: Optional[str] = None) -> str:
+    """명령 → 결과에 쓸 짧은 이름 (이미지면 저장소 이름, 아니면 인터프리터 다음의 스크립트 이름이나 실행 파일 이름)"""
+    if image:
+        return image.rsplit('/', 1)[-1].split('@')[0].split(':')[0]
+    argv = shlex.split(command) if isinstance(command, str) else list(command or [])
+    names = [Path(arg).stem for arg in argv if not arg.startswith('-')]
+    candidates = [name for name in names if name not in INTERPRETERS]
+    return (candidates or names or ['command'])[0]
+
+
+def parse_report(text: str) -> Dict[str, Any]:
+    """stdout의 응답 JSON → 탐지 보고 (계약에 맞지 않으면 ValueError)"""
+    try:
+        response = json.loads(text)
+    except json.JSONDecodeError as e:
+        raise ValueError(f"응답이 JSON이 아닙니다 ({e.msg}): {text.strip()[:120]!r}")
+    if not isinstance(response, dict) or not isinstance(response.get('labels'), list):
+        raise ValueError(f"응답에 labels 배열이 없습니다: {text.strip()[:120]!r}")
+    return normalize_report(response)
+
+
+def normalize_report(response: Dict[str, Any]) -> Dict[str, Any]:
+    """계약 형식의 응답 객체 → 탐지 보고 (labels 배열은 확인된 상태, 플러그인 탐지기도 사용)"""
+    labels = list(dict.fromkeys(label for label in map(as_text, response['labels']) if label))
+    confidence = response.get('confidence', 1.0 if labels else 0.0)
+    if isinstance(confidence, bool) or not isinstance(confidence, (int, float)):
+        raise ValueError(f"confidence가 숫자가 아닙니다: {confidence!r}")
+    locations = []
+    for location in map(as_object, as_array(response.get('locations'))):
+        line = location.get('line')
+        if as_text(location.get('label')) in labels and type(line) is int and line > 0:
+            locations.append({'label': location['label'], 'line': line})
+    report = {
+        'labels': labels,
+        'confidence': min(max(float(confidence), 0.0), 1.0),
+        'evidence': [str(item)[:200] for item in as_array(response.get('evidence'))[:MAX_EVIDENCE]],
+        'locations': locations,
+    }
+    scores = label_scores(response.get('scores'), labels)
+    if scores:
+        report['scores'] = scores
+    candidates = ranked_candidates(response.get('candidates'))
+    if candidates:
+        report['candidates'] = candidates
+    if response.get('abstain') is True:
+        report['abstain'] = True
+    return report
+
+
+def ranked_candidates(candidates: Any) -> List[Dict[str, Any]]:
+    """영역별 순위 후보 [{'labels': [가능성 순], 'line'}] (라벨 문자열만, 양의 줄 번호만)"""
+    ranked = []
+    for candidate in map(as_object, as_array(candidates)):
+        labels = list(dict.fromkeys(label for label in map(as_text, as_array(candidate.get('labels'))) if label))
+        if labels:
+            line = candidate.get('line')
+            ranked.append({'labels': labels, **({'line': line} if type(line) is int and line > 0 else {})})
+    return ranked
+
+
+def label_scores(scores: Any, labels: List[str]) -> Dict[str, float]:
+    """라벨별 신뢰도 {라벨: 0~1} (보고한 라벨의 숫자 값만, 범위 밖은 자름)"""
+    if not isinstance(scores, dict):
+        return {}
+    return {label: min(max(float(value), 0.0), 1.0) for label, value in scores.items()
+            if label in labels and not isinstance(value, bool) and isinstance(value, (int, float))}
+
+
+class CommandDetector(BaseDetector):
+    """외부 명령을 JSON-over-stdio로 실행하는 탐지기"""
+
+    name = 'command'
+
+    def __init__(self, command: Union[str, List[str], None] = None, batch: bool = False,
+                 timeout: float = DEFAULT_TIMEOUT, memory_mb: Optional[int] = None, cpu_seconds: Optional[int] = None,
+                 cwd: Optional[str] = None, container: Optional[str] = None, image: Optional[str] = None,
+                 cpus: Optional[float] = None, mount: Optional[str] = None):
+        self.argv = shlex.split(command) if isinstance(command, str) else list(command or [])
+        if container:
+            if container not in CONTAINERS:
+                raise ValueError(f"지원하지 않는 컨테이너 런타임: {container} (지원: {', '.join(CONTAINERS)})")
+            if not image:
+                raise ValueError("컨테이너 격리에는 이미지가 필요합니다")
+            if not shutil.which(container):
+                raise RuntimeError(f"{container}를 찾을 수 없습니다")
+            if mount and not Path(mount).is_dir():
+                raise ValueError(f"마운트할 디렉토리가 없습니다: {mount}")
+        elif not self.argv:
+            raise ValueError("빈 명령입니다")
+        self.batch = batch
+        self.timeout = timeout
+        self.memory_mb = memory_mb
+        self.cpu_seconds = cpu_seconds
+        self.cwd = cwd
+        self.container = container
+        self.image = image if container else None
+        self.cpus = cpus
+        self.mount = str(Path(mount).resolve()) if container and mount else None
+        self.label = self.argv[0] if self.argv else str(self.image)
+        self.process: Optional[subprocess.Popen] = None
+        self.container_name: Optional[str] = None
+
+    def _limit_resources(self):
+        """자식 프로세스에서 exec 직전에 실행 (POSIX)"""
+        import resource
+        if self.memory_mb:
+            limit = self.memory_mb * 1024 * 1024
+            resource.setrlimit(resource.RLIMIT_AS, (limit, limit))
+        if self.cpu_seconds:
+            resource.setrlimit(resource.RLIMIT_CPU, (self.cpu_seconds, self.cpu_seconds))
+
+    def container_argv(self, name: str) -> List[str]:
+        """컨테이너 안에서 명령을 실행하는 전체 argv (격리 옵션은 항상, 자원 상한은 준 것만)"""
+        argv = [self.container, 'run', '--rm', '-i', '--name', name, '--network', 'none', '--read-only',
+                '--tmpfs', '/tmp', '--cap-drop', 'ALL', '--security-opt', 'no-new-privileges',
+                '--pids-limit', str(CONTAINER_PIDS_LIMIT)]
+        if self.memory_mb:
+            argv += ['--memory', f'{self.memory_mb}m', '--memory-swap', f'{self.memory_mb}m']
+        if self.cpus:
+            argv += ['--cpus', str(self.cpus)]
+        if self.cpu_seconds:
+            argv += ['--ulimit', f'cpu={self.cpu_seconds}:{self.cpu_seconds}']
+        if self.mount:
+            argv += ['-v', f'{self.mount}:{CONTAINER_WORKDIR}:ro', '-w', CONTAINER_WORKDIR]
+        return argv + [self.image] + self.argv
+
+    def _popen(self, **kwargs) -> Tuple[subprocess.Popen, Optional[str]]:
+        """프로세스 시작 → (프로세스, 컨테이너 이름)"""
+        name = f'qvbench-command-{uuid.uuid4().hex[:12]}' if self.container else None
+        argv = self.container_argv(name) if name else self.argv
+        # 컨테이너면 상한은 컨테이너 옵션으로 (rlimit을 걸면 런타임 클라이언트가 제한됨)
+        limited = (self.memory_mb or self.cpu_seconds) and os.name == 'posix' and not self.container
+        try:
+            return subprocess.Popen(argv, cwd=self.cwd, stdin=subprocess.PIPE, stdout=subprocess.PIPE,
+                                    stderr=subprocess.DEVNULL, preexec_fn=self._limit_resources if limited else None,
+                                    **kwargs), name
+        except OSError as e:
+            raise RuntimeError(f"명령을 실행할 수 없습니다: {argv[0]} ({e.strerror or e})")
+
+    def _stop(self, process: subprocess.Popen, name: Optional[str]):
+        """프로세스 종료 (컨테이너는 클라이언트를 죽여도 남을 수 있어 이름으로 강제 삭제)"""
+        if process.poll() is None:
+            process.kill()
+        process.wait()
+        if name:
+            subprocess.run([self.container, 'rm', '-f', name], stdout=subprocess.DEVNULL, stderr=subprocess.DEVNULL)
+
+    def _run_once(self, request: Dict[str, Any]) -> str:
+        process, name = self._popen(bufsize=0)
+        deadline = time.monotonic() + self.timeout
+        # 요청은 별도 스레드로 써서 도구가 stdin을 다 읽기 전에 stdout을 채워도 막히지 않게 함
+        writer = threading.Thread(target=self._feed, daemon=True,
+                                  args=(process, json.dumps(request, ensure_ascii=False).encode('utf-8')))
+        writer.start()
+        try:
+            stdout = self._read_output(process, deadline)
+            process.wait(timeout=max(deadline - time.monotonic(), 0))
+        except subprocess.TimeoutExpired:
+            self._stop(process, name)
+            raise RuntimeError(f"{self.label} 시간 초과 ({self.timeout}s)")
+        except RuntimeError:
+            self._stop(process, name)
+            raise
+        finally:
+            writer.join()
+        if process.returncode != 0:
+            raise RuntimeError(f"{self.label} 종료 코드 {process.returncode}")
+        return stdout.decode('utf-8', errors='replace')
+
+    @staticmethod
+    def _feed(process: subprocess.Popen, payload: bytes):
+        """요청을 stdin에 쓰고 닫음 (도구가 먼저 끝나 파이프가 닫힌 경우는 종료 코드로 판단)"""
+        try:
+            process.stdin.write(payload)
+        except OSError:
+            pass
+        finally:
+            try:
+                process.stdin.close()
+            except OSError:
+                pass
+
+    def _read_output(self, process: subprocess.Popen, deadline: float) -> bytes:
+        """샘플마다: EOF까지 stdout을 MAX_OUTPUT_BYTES 한도로 청크 단위로 읽음"""
+        buffer = bytearray()
+        with selectors.DefaultSelector() as selector:
+            selector.register(process.stdout, selectors.EVENT_READ)
+            while True:
+                remaining = deadline - time.monotonic()
+                if remaining <= 0 or not selector.select(remaining):
+                    raise RuntimeError(f"{self.label} 시간 초과 ({self.timeout}s)")
+                chunk = os.read(process.stdout.fileno(), 65536)
+                if not chunk:
+                    return bytes(buffer)
+                buffer += chunk
+                if len(buffer) > MAX_OUTPUT_BYTES:
+                    raise RuntimeError(f"{self.label} 출력이 {MAX_OUTPUT_BYTES}바이트를 넘습니다")
+
+    def _exchange(self, request: Dict[str, Any]) -> str:
+        """batch: 요청 한 줄 쓰고 응답 한 줄 읽기 (문제가 생기면 프로세스를 닫고 RuntimeError)"""
+        if self.process is None or self.process.poll() is not None:
+            self.close()
+            self.process, self.container_name = self._popen(bufsize=0)
+        elif self._pending(self.process):
+            self.close()
+            raise RuntimeError(f"{self.label}이 이전 응답 줄 뒤에 추가 출력을 보냈습니다")
+        try:
+            self.process.stdin.write(json.dumps(request, ensure_ascii=False).encode('utf-8') + b'\n')
+            self.process.stdin.flush()
+            line = self._read_line(self.process, time.monotonic() + self.timeout)
+        except (OSError, RuntimeError) as e:
+            self.close()
+            raise RuntimeError(str(e) if isinstance(e, RuntimeError) else f"{self.label} 파이프 오류: {e}")
+        return line
+
+    def _read_line(self, process: subprocess.Popen, deadline: float) -> str:
+        buffer = bytearray()
+        with selectors.DefaultSelector() as selector:
+            selector.register(process.stdout, selectors.EVENT_READ)
+            while not buffer.endswith(b'\n'):
+                remaining = deadline - time.monotonic()
+                if remaining <= 0 or not selector.select(remaining):
+                    raise RuntimeError(f"{self.label} 시간 초과 ({self.timeout}s)")
+                chunk = os.read(process.stdout.fileno(), 65536)
+                if not chunk:
+                    raise RuntimeError(f"{self.label}이 응답 없이 종료됨 (종료 코드 {process.wait()})")
+                buffer += chunk
+                if len(buffer) > MAX_OUTPUT_BYTES:
+                    raise RuntimeError(f"{self.label} 응답이 {MAX_OUTPUT_BYTES}바이트를 넘습니다")
+                if b'\n' in buffer[:-1]:
+                    # 요청 하나에 응답 한 줄: 줄 뒤에 더 온 바이트는 다음 응답과 어긋나므로 계약 위반
+                    raise RuntimeError(f"{self.label}이 응답 줄 뒤에 추가 출력을 보냈습니다")
+        return buffer.decode('utf-8', errors='replace')
+
+    @staticmethod
+    def _pending(process: subprocess.Popen) -> bool:
+        """batch: 요청을 보내기 전인데 stdout에 읽을 출력이 있는지 (응답 줄 뒤에 늦게 온 출력)"""
+        with selectors.DefaultSelector() as selector:
+            selector.register(process.stdout, selectors.EVENT_READ)
+            return bool(selector.select(0)) and process.poll() is None
+
+    def close(self):
+        if self.process is not None:
+            self._stop(self.process, self.container_name)
+            self.process, self.container_name = None, None
+
+    def prepare(self, agent_type: str, references: List[Dict[str, Any]]):
+        if self.batch:
+            line = self._exchange({'type': 'prepare', 'agent_type': agent_type, 'references': references})
+            try:
+                json.loads(line)
+            except json.JSONDecodeError:
+                self.close()
+                raise RuntimeError(f"prepare 응답이 JSON이 아닙니다: {line.strip()[:120]!r}")
+
+    def detect(self, agent_type: str, input_data: str, filename: Optional[str] = None) -> Dict[str, Any]:
+        request = {'type': 'detect', 'agent_type': agent_type, 'input_data': input_data, 'filename': filename or ''}
+        text = self._exchange(request) if self.batch else self._run_once(request)
+        try:
+            return parse_report(text)
+        except ValueError as e:
+            if self.batch:
+                self.close()
+            raise RuntimeError(str(e))
+
+    def describe(self) -> Dict[str, Any]:
+        description = {'name': self.name, 'command': shlex.join(self.argv), 'batch': self.batch,
+                       'timeout': self.timeout, 'memory_mb': self.memory_mb, 'cpu_seconds': self.cpu_seconds}
+        if self.container:
+            description.update({'container': self.container, 'image': self.image, 'cpus': self.cpus,
+                                'mount': self.mount, 'network': 'none'})
+        return description
@@ -0,0 +1,101 @@
+"""
+미리 만든 도구 결과 → 샘플별 탐지 보고 (SARIF, CBOM 어댑터 공용)
+
+외부 도구를 코퍼스 전체에 한 번 돌려 만든 결과 파일을 읽어, 결과마다 경로와 라벨을 모아 두고
+샘플의 파일 경로(file_path)와 경로 끝부분 기준으로 맞춥니다. source root가 data/test_files/source_code든
+저장소 루트든 같은 샘플로 맞춰지며, 디렉토리 샘플은 그 아래 파일의 결과를 모두 모읍니다.
+"""
+
+from pathlib import Path, PurePosixPath
+from typing import Dict, Any, List, Optional, Tuple
+from urllib.parse import unquote, urlparse
+
+from ..base_detector import BaseDetector
+from .labels import MAX_EVIDENCE
+
+
+def as_object(value: Any) -> Dict[str, Any]:
+    """외부 결과 파일의 객체 자리 값 (객체가 아니면 빈 객체)"""
+    return value if isinstance(value, dict) else {}
+
+
+def as_array(value: Any) -> List[Any]:
+    return value if isinstance(value, list) else []
+
+
+def as_text(value: Any) -> str:
+    return value if isinstance(value, str) else ''
+
+
+def result_files(paths: List[str], suffixes: Tuple[str, ...]) -> List[Path]:
+    """결과 파일 또는 디렉토리(아래의 suffixes로 끝나는 파일) 목록 → 파일 목록"""
+    files = []
+    for path in map(Path, paths):
+        if path.is_dir():
+            files += sorted(p for p in path.rglob('*') if p.name.endswith(suffixes))
+        else:
+            files.append(path)
+    return files
+
+
+def normalize_uri(uri: str, base: str = '') -> str:
+    """파일 URI 또는 경로 (+ 기준 경로) → '/'로 구분한 경로"""
+    if base and '://' not in uri:
+        uri = base.rstrip('/') + '/' + uri
+    parsed = urlparse(uri)
+    path = unquote(parsed.path) if parsed.scheme == 'file' else uri
+    return str(PurePosixPath(path.replace('\\', '/')))
+
+
+def _ends_with(parts: tuple, suffix: tuple) -> bool:
+    return 0 < len(suffix) <= len(parts) and parts[len(parts) - len(suffix):] == suffix
+
+
+def path_matches(uri: str, sample: str) -> bool:
+    """결과 경로가 샘플 파일(또는 디렉토리 샘플 아래 파일)을 가리키는지 (경로 끝부분 기준)"""
+    uri_parts = PurePosixPath(uri).parts
+    sample_parts = PurePosixPath(sample).parts
+    if _ends_with(uri_parts, sample_parts) or _ends_with(sample_parts, uri_parts):
+        return True
+    # 디렉토리 샘플: 결과 경로의 앞부분이 샘플 디렉토리
+    return any(_ends_with(uri_parts[:end], sample_parts) or _ends_with(sample_parts, uri_parts[:end])
+               for end in range(1, len(uri_parts)))
+
+
+class FileFindingsDetector(BaseDetector):
+    """결과 목록 [{'uri', 'line', 'source', 'labels', 'confidence', 'message'}]을 샘플 경로로 모으는 탐지기"""
+
+    def __init__(self):
+        self.findings: List[Dict[str, Any]] = []
+
+    def add_finding(self, uri: str, line: Optional[int], source: str, labels: List[str], confidence: float,
+                    message: str = ''):
+        self.findings.append({'uri': uri, 'line': line, 'source': source, 'labels': labels,
+                              'confidence': confidence, 'message': message})
+
+    def detect(self, agent_type: str, input_data: str, filename: Optional[str] = None) -> Dict[str, Any]:
+        if not filename:
+            return {'labels': [], 'confidence': 0.0, 'evidence': ['파일 이름이 없어 도구 결과와 맞출 수 없음']}
+
+        sample = normalize_uri(filename)
+        labels: List[str] = []
+        confidence = 0.0
+        evidence = []
+        locations = []
+        for finding in self.findings:
+            if not path_matches(finding['uri'], sample):
+                continue
+            # 줄 위치는 샘플 파일 자신의 결과만 (디렉토리 샘플 아래 다른 파일의 줄은 제외)
+            uri_parts, sample_parts = PurePosixPath(finding['uri']).parts, PurePosixPath(sample).parts
+            if finding['line'] and (_ends_with(uri_parts, sample_parts) or _ends_with(sample_parts, uri_parts)):
+                locations += [{'label': label, 'line': finding['line']} for label in finding['labels']]
+            for label in finding['labels']:
+                if label not in labels:
+                    labels.append(label)
+            if finding['labels']:
+                confidence = max(confidence, finding['confidence'])
+            if len(evidence) < MAX_EVIDENCE:
+                message = f": {finding['message'].strip()[:200]}" if finding['message'] else ''
+                evidence.append(f"{finding['source']} {PurePosixPath(finding['uri']).name}:{finding['line']}{message}"
+                                f" → {', '.join(finding['labels']) or '-'}")
+        return {'labels': labels, 'confidence': confidence, 'evidence': evidence, 'locations': locations}
@@ -0,0 +1,219 @@
+"""
+원격 탐지기 어댑터 (grpc)
+
+다른 언어(Go, Rust, Java …)로 만든 탐지기를 FFI 없이 하네스에 연결합니다. 탐지기는 detector.proto의
+qvbench.detector.v1.Detector 서비스를 구현한 gRPC 서버로 띄우고, 이 어댑터가 샘플마다 Detect를 호출해 응답을
+탐지 보고({'labels', 'confidence', 'evidence', 'locations', 'scores', 'candidates', 'abstain'})로 바꿉니다.
+채점, 함수 단위 위치, 리더보드는 로컬 탐지기와 같습니다.
+
+    Describe   처음 연결할 때 한 번 (결과 메타데이터의 detector.remote)
+    Prepare    에이전트마다 train 분할 참조 샘플로 한 번 (--grpc-no-prepare면 생략)
+    Detect     샘플마다 (--grpc-timeout 초 안에 응답이 없거나 서버 오류면 그 샘플만 crash로 기록)
+
+메시지는 proto3 와이어 형식이며, 생성된 스텁(protoc) 없이 이 모듈의 작은 인코더(MESSAGES 스키마)로 직렬화하므로
+grpcio만 있으면 됩니다 (pip install grpcio). 서버 쪽은 detector.proto로 각 언어의 스텁을 생성해 구현합니다.
+Python 탐지기를 같은 프로토콜로 띄우는 참조 서버는 detectors/grpc_server.py입니다.
+
+사용법:
+    python -m detectors.grpc_server --detector signature --port 50051 &
+    python -m detectors.runner --detector grpc --grpc-target localhost:50051
+    python -m detectors.runner --detector grpc --grpc-target scanner.internal:443 --grpc-tls --grpc-timeout 120
+"""
+
+import struct
+from typing import Dict, Any, List, Optional, Tuple
+
+from ..base_detector import BaseDetector
+from .command import label_scores, ranked_candidates
+
+SERVICE = 'qvbench.detector.v1.Detector'
+DEFAULT_TIMEOUT = 60
+# 참조 샘플을 한 번에 보내므로 기본 4MB보다 크게
+MAX_MESSAGE_BYTES = 64 * 1024 * 1024
+
+# 메시지 → [(필드 번호, 이름, 타입, repeated)] (타입: string / double / int32 / bool / 메시지 이름 / map은 MapEntry 메시지)
+MESSAGES: Dict[str, List[Tuple[int, str, str, bool]]] = {
+    'DescribeRequest': [],
+    'DescribeResponse': [(1, 'name', 'string', False), (2, 'version', 'string', False),
+                         (3, 'metadata', 'MapEntry', True)],
+    'MapEntry': [(1, 'key', 'string', False), (2, 'value', 'string', False)],
+    'Reference': [(1, 'test_id', 'string', False), (2, 'input_data', 'string', False),
+                  (3, 'labels', 'string', True)],
+    'PrepareRequest': [(1, 'agent_type', 'string', False), (2, 'references', 'Reference', True)],
+    'PrepareResponse': [],
+    'DetectRequest': [(1, 'agent_type', 'string', False), (2, 'input_data', 'string', False),
+                      (3, 'filename', 'string', False)],
+    'Location': [(1, 'label', 'string', False), (2, 'line', 'int32', False)],
+    'LabelScore': [(1, 'label', 'string', False), (2, 'confidence', 'double', False)],
+    'Candidate': [(1, 'labels', 'string', True), (2, 'line', 'int32', False)],
+    'DetectResponse': [(1, 'labels', 'string', True), (2, 'confidence', 'double', False),
+                       (3, 'evidence', 'string', True), (4, 'locations', 'Location', True),
+                       (5, 'scores', 'LabelScore', True), (6, 'candidates', 'Candidate', True),
+                       (7, 'abstain', 'bool', False)],
+}
+_DEFAULTS = {'string': '', 'double': 0.0, 'int32': 0, 'bool': False}
+
+WIRE_VARINT, WIRE_FIXED64, WIRE_BYTES, WIRE_FIXED32 = 0, 1, 2, 5
+
+
+def _varint(value: int) -> bytes:
+    value &= (1 << 64) - 1  # 음수 int32는 10바이트 varint
+    out = bytearray()
+    while True:
+        byte = value & 0x7f
+        value >>= 7
+        if value:
+            out.append(byte | 0x80)
+        else:
+            out.append(byte)
+            return bytes(out)
+
+
+def _read_varint(data: bytes, offset: int) -> Tuple[int, int]:
+    value, shift = 0, 0
+    while True:
+        if offset >= len(data):
+            raise ValueError("잘린 varint")
+        byte = data[offset]
+        offset += 1
+        value |= (byte & 0x7f) << shift
+        if not byte & 0x80:
+            return value, offset
+        shift += 7
+
+
+def _encode_value(kind: str, value: Any) -> Tuple[int, bytes]:
+    if kind == 'string':
+        data = str(value).encode('utf-8')
+        return WIRE_BYTES, _varint(len(data)) + data
+    if kind == 'double':
+        return WIRE_FIXED64, struct.pack('<d', float(value))
+    if kind in ('int32', 'bool'):
+        return WIRE_VARINT, _varint(int(value))
+    data = encode(kind, value)
+    return WIRE_BYTES, _varint(len(data)) + data
+
+
+def encode(message: str, values: Dict[str, Any]) -> bytes:
+    """dict → proto3 와이어 바이트 (기본값 필드는 생략, map은 {키: 값} dict)"""
+    out = bytearray()
+    for number, name, kind, repeated in MESSAGES[message]:
+        value = values.get(name)
+        if kind == 'MapEntry' and isinstance(value, dict):
+            value = [{'key': key, 'value': str(item)} for key, item in value.items()]
+        items = list(value or []) if repeated else ([] if value in (None, _DEFAULTS.get(kind)) else [value])
+        for item in items:
+            wire, data = _encode_value(kind, item)
+            out += _varint(number << 3 | wire) + data
+    return bytes(out)
+
+
+def decode(message: str, data: bytes) -> Dict[str, Any]:
+    """proto3 와이어 바이트 → dict (모르는 필드는 건너뜀, 없는 필드는 기본값)"""
+    fields = {number: (name, kind, repeated) for number, name, kind, repeated in MESSAGES[message]}
+    values: Dict[str, Any] = {name: [] if repeated else _DEFAULTS.get(kind)
+                              for _, name, kind, repeated in MESSAGES[message]}
+    offset = 0
+    while offset < len(data):
+        key, offset = _read_varint(data, offset)
+        number, wire = key >> 3, key & 7
+        if wire == WIRE_VARINT:
+            raw, offset = _read_varint(data, offset)
+        elif wire == WIRE_FIXED64:
+            raw, offset = data[offset:offset + 8], offset + 8
+        elif wire == WIRE_FIXED32:
+            raw, offset = data[offset:offset + 4], offset + 4
+        elif wire == WIRE_BYTES:
+            length, offset = _read_varint(data, offset)
+            raw, offset = data[offset:offset + length], offset + length
+        else:
+            raise ValueError(f"지원하지 않는 와이어 타입 {wire} (필드 {number})")
+        if offset > len(data):
+            raise ValueError(f"잘린 메시지 {message} (필드 {number})")
+        if number not in fields:
+            continue
+        name, kind, repeated = fields[number]
+        if kind == 'string':
+            value = bytes(raw).decode('utf-8')
+        elif kind == 'double':
+            value = struct.unpack('<d', raw)[0]
+        elif kind == 'int32':
+            value = raw - (1 << 64) if raw >= 1 << 63 else raw
+        elif kind == 'bool':
+            value = bool(raw)
+        else:
+            value = decode(kind, raw)
+        if repeated:
+            values[name].append(value)
+        else:
+            values[name] = value
+    for _, name, kind, _ in MESSAGES[message]:
+        if kind == 'MapEntry':
+            values[name] = {entry['key']: entry['value'] for entry in values[name]}
+    return values
+
+
+def import_grpc():
+    try:
+        import grpc
+    except ImportError:
+        raise RuntimeError("grpc 탐지기에는 grpcio가 필요합니다 (pip install grpcio)")
+    return grpc
+
+
+class GrpcDetector(BaseDetector):
+    """gRPC로 연결한 원격 탐지기"""
+
+    name = 'grpc'
+
+    def __init__(self, target: str, timeout: float = DEFAULT_TIMEOUT, tls: bool = False, prepare: bool = True):
+        grpc = import_grpc()
+        self.target = target
+        self.timeout = timeout
+        self.tls = tls
+        self.send_references = prepare
+        self._rpc_error = grpc.RpcError
+        options = [('grpc.max_send_message_length', MAX_MESSAGE_BYTES),
+                   ('grpc.max_receive_message_length', MAX_MESSAGE_BYTES)]
+        self.channel = grpc.secure_channel(target, grpc.ssl_channel_credentials(), options) if tls \
+            else grpc.insecure_channel(target, options)
+        self.remote = self.call('Describe', 'DescribeRequest', 'DescribeResponse', {})
+
+    def call(self, method: str, request: str, response: str, values: Dict[str, Any]) -> Dict[str, Any]:
+        rpc = self.channel.unary_unary(f"/{SERVICE}/{method}",
+                                       request_serializer=lambda item: encode(request, item),
+                                       response_deserializer=lambda data: decode(response, data))
+        try:
+            return rpc(values, timeout=self.timeout)
+        except self._rpc_error as e:
+            raise RuntimeError(f"{self.target} {method} 실패: {e.code().name} {e.details() or ''}".strip())
+
+    def prepare(self, agent_type: str, references: List[Dict[str, Any]]):
+        if self.send_references:
+            self.call('Prepare', 'PrepareRequest', 'PrepareResponse',
+                      {'agent_type': agent_type, 'references': references})
+
+    def detect(self, agent_type: str, input_data: str, filename: Optional[str] = None) -> Dict[str, Any]:
+        response = self.call('Detect', 'DetectRequest', 'DetectResponse',
+                             {'agent_type': agent_type, 'input_data': input_data, 'filename': filename or ''})
+        labels = list(dict.fromkeys(response['labels']))
+        report = {
+            'labels': labels,
+            'confidence': min(max(response['confidence'], 0.0), 1.0),
+            'evidence': response['evidence'],
+            'locations': [location for location in response['locations']
+                          if location['label'] in labels and location['line'] > 0],
+        }
+        scores = label_scores({item['label']: item['confidence'] for item in response['scores']}, labels)
+        if scores:
+            report['scores'] = scores
+        candidates = ranked_candidates(response['candidates'])
+        if candidates:
+            report['candidates'] = candidates
+        if response['abstain']:
+            report['abstain'] = True
+        return report
+
+    def describe(self) -> Dict[str, Any]:
+        return {'name': self.name, 'target': self.target, 'tls': self.tls, 'timeout': self.timeout,
+                'prepare': self.send_references, 'remote': self.remote}
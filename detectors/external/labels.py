@@ -0,0 +1,86 @@
+"""
+외부 도구 규칙 → 벤치마크 라벨 변환 (Semgrep, SARIF 어댑터 공용)
+
+규칙 하나의 라벨은 다음 순서로 정합니다 (앞의 것이 우선):
+    label_map       규칙 ID 접두사 → 라벨 목록 (JSON/YAML, 가장 긴 접두사 하나만 적용)
+                    {"python.cryptography.security.insecure-hash-algorithms.insecure-hash-algorithm-md5": ["MD5"],
+                     "java/weak-cryptographic-algorithm": ["DES"],
+                     "java.lang.security.audit.crypto.": []}      빈 목록이면 그 규칙들은 라벨을 내지 않음
+    metadata        규칙 metadata(SARIF는 rule properties)의 qvbench_labels (문자열 또는 목록), 없으면 algorithm
+    규칙 ID/메시지    규칙 ID 마지막 부분을 영숫자 토큰으로 나눈 것(숫자 토큰은 앞 토큰에 붙여 먼저 시도, "sha-1" → SHA-1)
+                    중 분류 체계(utils/taxonomy.py)에 있는 이름, 없으면 메시지에서 같은 방식으로
+"""
+
+import json
+import re
+from typing import Dict, Any, List, Optional
+
+import yaml
+
+from utils.taxonomy import AlgorithmTaxonomy
+
+# 심각도 → 신뢰도 (Semgrep severity, SARIF level)
+SEVERITY_CONFIDENCE = {'ERROR': 0.9, 'WARNING': 0.7, 'INFO': 0.5, 'NOTE': 0.5}
+DEFAULT_CONFIDENCE = 0.5
+MAX_EVIDENCE = 10
+
+
+def load_label_map(path: Optional[str]) -> Dict[str, List[str]]:
+    """규칙 ID 접두사 → 라벨 목록 (.json 또는 .yaml)"""
+    if not path:
+        return {}
+    with open(path, 'r', encoding='utf-8') as f:
+        data = json.load(f) if path.endswith('.json') else yaml.safe_load(f)
+    return {prefix: [labels] if isinstance(labels, str) else list(labels or []) for prefix, labels in data.items()}
+
+
+def severity_confidence(severity: Optional[str]) -> float:
+    return SEVERITY_CONFIDENCE.get(str(severity or '').upper(), DEFAULT_CONFIDENCE)
+
+
+class RuleLabeler:
+    """규칙 ID, metadata, 메시지 → 라벨 목록"""
+
+    def __init__(self, label_map: Optional[Dict[str, List[str]]] = None, taxonomy: AlgorithmTaxonomy = None):
+        self.label_map = label_map or {}
+        self.taxonomy = taxonomy or AlgorithmTaxonomy()
+
+    def known_name(self, token: str) -> Optional[str]:
+        node = self.taxonomy.resolve(token)
+        if not self.taxonomy.is_known(node) or node[1] is None:
+            return None
+        return node[2] or node[1]
+
+    def taxonomy_names(self, text: str) -> List[str]:
+        """규칙 ID/메시지에서 분류 체계에 있는 이름 (숫자 토큰은 앞 토큰에 붙여 먼저 시도: sha-256 → SHA-256)"""
+        tokens = [token for token in re.split(r'[^A-Za-z0-9]+', text) if token]
+        names: List[str] = []
+        index = 0
+        while index < len(tokens):
+            name = None
+            step = 1
+            if index + 1 < len(tokens) and tokens[index + 1].isdigit():
+                name = self.known_name(tokens[index] + tokens[index + 1])
+                step = 2 if name else 1
+            if name is None and not tokens[index].isdigit():
+                name = self.known_name(tokens[index])
+            if name and name not in names:
+                names.append(name)
+            index += step
+        return names
+
+    def labels(self, rule_id: str, metadata: Optional[Dict[str, Any]] = None, message: str = '') -> List[str]:
+        prefixes = [prefix for prefix in self.label_map if rule_id.startswith(prefix)]
+        if prefixes:
+            return self.label_map[max(prefixes, key=len)]
+
+        metadata = metadata if isinstance(metadata, dict) else {}
+        declared = metadata.get('qvbench_labels') or metadata.get('algorithm')
+        declared = [declared] if isinstance(declared, str) else declared if isinstance(declared, list) else []
+        declared = [label for label in declared if isinstance(label, str) and label]
+        if declared:
+            return declared
+
+        # 규칙 ID의 마지막 부분 (Semgrep은 '.', CodeQL은 '/'로 구분)
+        names = self.taxonomy_names(re.split(r'[./]', rule_id)[-1])
+        return names or self.taxonomy_names(message or '')
@@ -0,0 +1,88 @@
+"""
+SARIF 어댑터 (sarif)
+
+CodeQL 쿼리 팩(또는 SARIF를 내는 다른 도구)을 코드 변경 없이 벤치마크할 수 있도록, 코퍼스에 대해 미리 만든 SARIF 결과를
+읽어 샘플별 탐지 보고로 바꿉니다. 도구는 벤치마크 밖에서 실행하고, 이 어댑터는 결과 파일만 읽습니다.
+
+    codeql database create db-java --language=java --source-root data/test_files/source_code
+    codeql database analyze db-java codeql/java-queries --format=sarif-latest --output results/codeql-java.sarif
+    python -m detectors.runner --detector sarif --sarif results/codeql-java.sarif results/codeql-python.sarif \\
+        --label-map rules/codeql_label_map.yaml --agents source_code
+
+샘플과 결과 맞추기:
+    결과의 첫 위치(artifactLocation.uri, uriBaseId는 originalUriBaseIds로 풀어서)를 샘플 파일 경로와 경로 끝부분 기준으로
+    비교합니다 (detectors/external/findings.py). SARIF에 결과가 없는 샘플은 탐지 없음입니다.
+
+규칙 → 라벨 변환은 detectors/external/labels.py를 따르며 rule properties의 qvbench_labels를 metadata로 씁니다.
+신뢰도는 라벨을 낸 결과의 level(error 0.9, warning 0.7, note 0.5) 중 가장 높은 값입니다.
+억제된 결과(suppressions)와 kind가 fail이 아닌 결과(pass, notApplicable 등)는 세지 않습니다.
+형식이 어긋난 항목(객체 자리의 배열, 문자열 자리의 숫자 등)은 실행을 멈추지 않고 건너뜁니다 (utils/fuzz.py의 sarif 대상).
+"""
+
+import json
+from typing import Dict, Any, List, Optional
+
+from .findings import FileFindingsDetector, as_array, as_object, as_text, normalize_uri, result_files
+from .labels import RuleLabeler, load_label_map, severity_confidence
+
+SARIF_SUFFIXES = ('.sarif', '.sarif.json')
+
+
+class SarifDetector(FileFindingsDetector):
+    """SARIF 결과를 샘플별 탐지 보고로 변환"""
+
+    name = 'sarif'
+
+    def __init__(self, sarif: List[str], label_map: Optional[str] = None):
+        super().__init__()
+        self.sarif_paths = [str(path) for path in result_files(list(sarif), SARIF_SUFFIXES)]
+        if not self.sarif_paths:
+            raise ValueError(f"SARIF 파일이 없습니다: {sarif}")
+        self.label_map_path = label_map
+        self.labeler = RuleLabeler(load_label_map(label_map))
+        self.tools: List[str] = []
+        for path in self.sarif_paths:
+            with open(path, 'r', encoding='utf-8') as f:
+                self.load(json.load(f))
+
+    def load(self, log: Dict[str, Any]):
+        """SARIF 로그의 결과를 findings에 추가 (형식이 어긋난 항목은 건너뜀)"""
+        for run in as_array(as_object(log).get('runs')):
+            run = as_object(run)
+            tool_info = as_object(run.get('tool'))
+            driver = as_object(tool_info.get('driver'))
+            tool = ' '.join(part for part in (as_text(driver.get('name')), as_text(driver.get('semanticVersion')) or
+                                              as_text(driver.get('version'))) if part)
+            if tool and tool not in self.tools:
+                self.tools.append(tool)
+
+            # 규칙은 driver와 extensions(CodeQL 쿼리 팩)에 나뉘어 있음
+            components = [driver] + [as_object(extension) for extension in as_array(tool_info.get('extensions'))]
+            rules = {rule['id']: rule for component in components
+                     for rule in map(as_object, as_array(component.get('rules'))) if as_text(rule.get('id'))}
+            bases = {name: as_text(as_object(location).get('uri'))
+                     for name, location in as_object(run.get('originalUriBaseIds')).items()}
+
+            for result in map(as_object, as_array(run.get('results'))):
+                if result.get('suppressions') or result.get('kind', 'fail') != 'fail':
+                    continue
+                rule_id = as_text(result.get('ruleId')) or as_text(as_object(result.get('rule')).get('id'))
+                rule = rules.get(rule_id, {})
+                message = as_text(as_object(result.get('message')).get('text')) or \
+                    as_text(as_object(rule.get('shortDescription')).get('text'))
+                level = as_text(result.get('level')) or \
+                    as_text(as_object(rule.get('defaultConfiguration')).get('level')) or 'warning'
+                labels = self.labeler.labels(rule_id, as_object(rule.get('properties')), message)
+                for location in as_array(result.get('locations'))[:1]:
+                    physical = as_object(as_object(location).get('physicalLocation'))
+                    artifact = as_object(physical.get('artifactLocation'))
+                    if not as_text(artifact.get('uri')):
+                        continue
+                    line = as_object(physical.get('region')).get('startLine')
+                    self.add_finding(normalize_uri(artifact['uri'], bases.get(as_text(artifact.get('uriBaseId')), '')),
+                                     line if isinstance(line, int) and not isinstance(line, bool) and line > 0
+                                     else None, rule_id, labels, severity_confidence(level), message)
+
+    def describe(self) -> Dict[str, Any]:
+        return {'name': self.name, 'sarif': self.sarif_paths, 'tools': self.tools, 'label_map': self.label_map_path,
+                'findings': len(self.findings)}
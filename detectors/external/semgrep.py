@@ -0,0 +1,119 @@
+"""
+Semgrep 어댑터 (semgrep)
+
+기존 SAST 도구를 AI 모델, 로컬 기준선과 같은 채점으로 비교할 수 있도록, 샘플마다 주어진 규칙 모음으로 Semgrep을
+실행하고 JSON 결과(findings)를 탐지 보고({'labels', 'confidence', 'evidence'})로 바꿉니다. 학습 단계는 없습니다.
+
+규칙 → 라벨 변환(label_map, 규칙 metadata, 규칙 ID의 알고리즘 이름)은 detectors/external/labels.py를 따릅니다.
+신뢰도는 라벨을 낸 결과의 심각도(ERROR 0.9, WARNING 0.7, INFO 0.5) 중 가장 높은 값이고, 결과가 없으면 0입니다.
+Semgrep은 확장자로 언어를 고르므로 샘플의 원래 파일 이름을 쓰고, 모르면 내용으로 추정한 확장자를 붙입니다.
+
+사용법:
+    python -m detectors.runner --detector semgrep --ruleset rules/crypto.yaml
+    python -m detectors.runner --detector semgrep --ruleset p/cryptography --label-map rules/label_map.json
+    python -m detectors.runner --detector ensemble --members semgrep retrieval --ruleset rules/crypto.yaml
+
+레지스트리 규칙(p/...)은 내려받아야 하므로 오프라인 환경에서는 로컬 규칙 파일을 지정합니다.
+"""
+
+import json
+import shutil
+import subprocess
+import tempfile
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from utils.features import guess_language
+
+from ..base_detector import BaseDetector
+from .labels import MAX_EVIDENCE, RuleLabeler, load_label_map, severity_confidence
+
+DEFAULT_TIMEOUT = 60
+
+# 파일 이름을 모를 때 붙이는 확장자 (utils.features.guess_language 계열 → 확장자)
+LANGUAGE_SUFFIXES = {'python': '.py', 'ruby': '.rb', 'asm': '.s', 'brace': '.c'}
+AGENT_SUFFIXES = {'logs_config': '.conf', 'dependency_manifest': '.txt'}
+
+
+def sample_suffix(agent_type: str, input_data: str, filename: Optional[str]) -> str:
+    if filename and Path(filename).suffix:
+        return Path(filename).suffix
+    if agent_type in AGENT_SUFFIXES:
+        return AGENT_SUFFIXES[agent_type]
+    return LANGUAGE_SUFFIXES.get(guess_language(agent_type, input_data), '.txt')
+
+
+class SemgrepDetector(BaseDetector):
+    """Semgrep 결과를 탐지 보고로 변환"""
+
+    name = 'semgrep'
+
+    def __init__(self, ruleset: str, label_map: Optional[str] = None, timeout: int = DEFAULT_TIMEOUT,
+                 semgrep_bin: str = 'semgrep'):
+        self.semgrep = shutil.which(semgrep_bin)
+        if not self.semgrep:
+            raise RuntimeError(f"semgrep 실행 파일을 찾을 수 없습니다: {semgrep_bin} (pip install semgrep)")
+        self.ruleset = ruleset
+        self.label_map_path = label_map
+        self.labeler = RuleLabeler(load_label_map(label_map))
+        self.timeout = timeout
+        self.version = self._version()
+
+    def _version(self) -> Optional[str]:
+        try:
+            completed = subprocess.run([self.semgrep, '--version'], capture_output=True, text=True, timeout=30)
+        except (OSError, subprocess.TimeoutExpired):
+            return None
+        return completed.stdout.strip() or None
+
+    def scan(self, path: Path) -> Dict[str, Any]:
+        """파일 하나에 규칙 모음 실행 → Semgrep JSON 출력"""
+        command = [self.semgrep, 'scan', '--config', self.ruleset, '--json', '--quiet', '--metrics', 'off',
+                   '--disable-version-check', '--timeout', str(self.timeout), str(path)]
+        try:
+            completed = subprocess.run(command, capture_output=True, text=True, timeout=self.timeout + 30)
+        except subprocess.TimeoutExpired:
+            raise RuntimeError(f"semgrep 시간 초과 ({self.timeout}s): {path.name}")
+        try:
+            return json.loads(completed.stdout)
+        except json.JSONDecodeError:
+            # 결과 JSON조차 없으면 규칙 모음이나 실행 자체의 오류 (종료 코드 0/1은 정상, 2 이상은 오류)
+            message = completed.stderr.strip().splitlines()[-1:] or [f"exit code {completed.returncode}"]
+            raise RuntimeError(f"semgrep 실행 실패: {message[0]}")
+
+    def report(self, output: Dict[str, Any]) -> Dict[str, Any]:
+        """Semgrep JSON → 탐지 보고"""
+        labels: List[str] = []
+        confidence = 0.0
+        evidence = []
+        locations = []
+        for finding in output.get('results', []):
+            extra = finding.get('extra', {})
+            finding_labels = self.labeler.labels(finding.get('check_id', ''), extra.get('metadata'),
+                                                 extra.get('message', ''))
+            for label in finding_labels:
+                if label not in labels:
+                    labels.append(label)
+            line = finding.get('start', {}).get('line')
+            if finding_labels:
+                confidence = max(confidence, severity_confidence(extra.get('severity')))
+            if line:
+                locations += [{'label': label, 'line': line} for label in finding_labels]
+            if len(evidence) < MAX_EVIDENCE:
+                evidence.append(f"{finding.get('check_id')} L{line}: {extra.get('message', '').strip()[:200]}"
+                                f" → {', '.join(finding_labels) or '-'}")
+        for error in output.get('errors', [])[:MAX_EVIDENCE - len(evidence)]:
+            evidence.append(f"semgrep error: {error.get('type', '')} {error.get('message', '')}".strip()[:200])
+        return {'labels': labels, 'confidence': confidence, 'evidence': evidence, 'locations': locations}
+
+    def detect(self, agent_type: str, input_data: str, filename: Optional[str] = None) -> Dict[str, Any]:
+        suffix = sample_suffix(agent_type, input_data, filename)
+        name = Path(filename).name if filename and Path(filename).suffix else f"sample{suffix}"
+        with tempfile.TemporaryDirectory(prefix='qvbench-semgrep-') as directory:
+            path = Path(directory) / name
+            path.write_text(input_data, encoding='utf-8')
+            return self.report(self.scan(path))
+
+    def describe(self) -> Dict[str, Any]:
+        return {'name': self.name, 'ruleset': self.ruleset, 'label_map': self.label_map_path,
+                'semgrep_version': self.version, 'timeout': self.timeout}
@@ -0,0 +1,102 @@
+"""
+원격 탐지기 참조 서버 (gRPC)
+
+detectors/external/detector.proto의 qvbench.detector.v1.Detector 서비스를 로컬 탐지기로 구현합니다. 다른 언어로
+탐지기 서버를 만들 때 동작을 비교하는 기준이 되고, 하네스 쪽 클라이언트(--detector grpc)를 로컬 탐지기 결과와
+맞춰 보는 데도 씁니다: 같은 탐지기를 직접 돌린 결과와 이 서버를 거친 결과는 점수가 같아야 합니다.
+
+Prepare는 클라이언트가 보낸 참조 샘플로 탐지기를 준비하므로, 이 서버는 코퍼스를 읽지 않습니다.
+
+사용법:
+    python -m detectors.grpc_server --detector signature --port 50051
+    python -m detectors.grpc_server --detector classifier --model models/baseline_classifier.json
+    python -m detectors.runner --detector grpc --grpc-target localhost:50051
+"""
+
+import argparse
+from concurrent import futures
+from typing import Dict, Any
+
+from detectors.base_detector import BaseDetector
+from detectors.detector_factory import DetectorFactory
+from detectors.external.grpc import MAX_MESSAGE_BYTES, SERVICE, decode, encode, import_grpc
+from detectors.runner import add_detector_arguments, detector_config
+
+DEFAULT_PORT = 50051
+
+
+class DetectorService:
+    """로컬 탐지기 → Detector 서비스 메서드"""
+
+    def __init__(self, detector: BaseDetector, model_name: str):
+        self.detector = detector
+        self.model_name = model_name
+
+    def describe(self, request: Dict[str, Any], context) -> Dict[str, Any]:
+        metadata = {key: str(value) for key, value in self.detector.describe().items() if key != 'name'}
+        return {'name': self.model_name, 'version': '', 'metadata': metadata}
+
+    def prepare(self, request: Dict[str, Any], context) -> Dict[str, Any]:
+        self.detector.prepare(request['agent_type'], request['references'])
+        print(f"📁 {request['agent_type']}: 참조 {len(request['references'])}개로 준비")
+        return {}
+
+    def detect(self, request: Dict[str, Any], context) -> Dict[str, Any]:
+        detection = self.detector.detect(request['agent_type'], request['input_data'], request['filename'] or None)
+        return {
+            'labels': detection['labels'],
+            'confidence': detection['confidence'],
+            'evidence': detection.get('evidence', []),
+            'locations': detection.get('locations', []),
+            'scores': [{'label': label, 'confidence': confidence}
+                       for label, confidence in detection.get('scores', {}).items()],
+            'candidates': [{'labels': candidate['labels'], 'line': candidate.get('line', 0)}
+                           for candidate in detection.get('candidates', [])],
+            'abstain': bool(detection.get('abstain')),
+        }
+
+
+def build_server(service: DetectorService, port: int, workers: int = 4):
+    grpc = import_grpc()
+
+    def handler(method, request: str, response: str):
+        return grpc.unary_unary_rpc_method_handler(method, request_deserializer=lambda data: decode(request, data),
+                                                   response_serializer=lambda item: encode(response, item))
+
+    server = grpc.server(futures.ThreadPoolExecutor(max_workers=workers),
+                         options=[('grpc.max_send_message_length', MAX_MESSAGE_BYTES),
+                                  ('grpc.max_receive_message_length', MAX_MESSAGE_BYTES)])
+    server.add_generic_rpc_handlers([grpc.method_handlers_generic_handler(SERVICE, {
+        'Describe': handler(service.describe, 'DescribeRequest', 'DescribeResponse'),
+        'Prepare': handler(service.prepare, 'PrepareRequest', 'PrepareResponse'),
+        'Detect': handler(service.detect, 'DetectRequest', 'DetectResponse'),
+    })])
+    server.add_insecure_port(f"[::]:{port}")
+    return server
+
+
+def main():
+    parser = argparse.ArgumentParser(description='로컬 탐지기를 gRPC 탐지기 프로토콜로 제공 (참조 서버)')
+    add_detector_arguments(parser)
+    parser.add_argument('--port', type=int, default=DEFAULT_PORT, help=f'포트 (기본: {DEFAULT_PORT})')
+    parser.add_argument('--workers', type=int, default=4, help='동시 처리 스레드 수')
+    args = parser.parse_args()
+    if args.detector == 'grpc':
+        parser.error("참조 서버는 로컬 탐지기만 제공합니다 (--detector grpc 불가)")
+
+    try:
+        config, model_name = detector_config(args.detector, args)
+        service = DetectorService(DetectorFactory.create_detector(args.detector, config), model_name)
+    except ValueError as e:
+        parser.error(str(e))
+    try:
+        server = build_server(service, args.port, args.workers)
+    except RuntimeError as e:
+        parser.error(str(e))
+    server.start()
+    print(f"🔌 {model_name}: gRPC {SERVICE} on :{args.port}")
+    server.wait_for_termination()
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,181 @@
+"""
+탐지기 플러그인 (plugin)
+플러그인 디렉토리나 설치된 패키지(entry point 그룹 qvbench.detectors)의 탐지기를 이름으로 불러옵니다.
+찾는 순서와 플러그인 모듈 계약은 docs/PLUGINS.md를 보세요.
+"""
+
+import argparse
+import importlib.util
+import json
+import os
+import re
+import sys
+from pathlib import Path
+from types import ModuleType
+from typing import Dict, Any, List, Optional, Tuple
+
+from .base_detector import BaseDetector
+from .external.command import normalize_report
+
+PLUGIN_API_VERSION = 1
+PLUGINS_DIR = str(Path(__file__).parent.parent / 'plugins')
+PLUGINS_ENV = 'QVBENCH_PLUGINS'
+ENTRY_POINT_GROUP = 'qvbench.detectors'
+
+PLUGIN_NAME = re.compile(r'^[A-Za-z0-9][A-Za-z0-9_-]*$')
+
+_loaded: Dict[str, ModuleType] = {}
+
+
+class PluginError(ValueError):
+    """플러그인을 찾거나 불러올 수 없음"""
+
+
+def plugin_dirs(extra: Optional[List[str]] = None) -> List[Path]:
+    """플러그인 디렉토리 (--plugins-dir, QVBENCH_PLUGINS, plugins/ 순)"""
+    dirs = list(extra or []) + [d for d in os.environ.get(PLUGINS_ENV, '').split(os.pathsep) if d] + [PLUGINS_DIR]
+    return list(dict.fromkeys(Path(d) for d in dirs))
+
+
+def _entry_points() -> List[Any]:
+    from importlib import metadata
+
+    points = metadata.entry_points()
+    if hasattr(points, 'select'):
+        return list(points.select(group=ENTRY_POINT_GROUP))
+    return list(points.get(ENTRY_POINT_GROUP, []))
+
+
+def discover_plugins(dirs: Optional[List[str]] = None, builtin: Tuple[str, ...] = ()) -> Dict[str, str]:
+    """{이름: 출처(파일 경로 또는 entry:<모듈>)} (불러오지는 않음)"""
+    found: Dict[str, str] = {}
+    for directory in plugin_dirs(dirs):
+        if not directory.is_dir():
+            continue
+        for path in sorted(directory.iterdir()):
+            name = path.stem if path.suffix == '.py' else path.name
+            if name.startswith('_') or not PLUGIN_NAME.match(name) or name in builtin or name in found:
+                continue
+            if path.is_file() and path.suffix == '.py':
+                found[name] = str(path)
+            elif path.is_dir() and (path / '__init__.py').is_file():
+                found[name] = str(path / '__init__.py')
+    for point in _entry_points():
+        if PLUGIN_NAME.match(point.name) and point.name not in builtin and point.name not in found:
+            found[point.name] = f"entry:{point.value}"
+    return found
+
+
+def load_plugin(name: str, source: str) -> ModuleType:
+    """플러그인 모듈 불러오기 + API 버전 확인 (같은 출처는 한 번만)"""
+    if source in _loaded:
+        return _loaded[source]
+    module_name = f"qvbench_plugin_{re.sub(r'[^A-Za-z0-9_]', '_', name)}"
+    try:
+        if source.startswith('entry:'):
+            module = importlib.import_module(source[len('entry:'):].split(':')[0])
+        else:
+            path = Path(source)
+            locations = [str(path.parent)] if path.name == '__init__.py' else None
+            spec = importlib.util.spec_from_file_location(module_name, path, submodule_search_locations=locations)
+            module = importlib.util.module_from_spec(spec)
+            sys.modules[module_name] = module
+            spec.loader.exec_module(module)
+    except Exception as e:
+        sys.modules.pop(module_name, None)
+        raise PluginError(f"플러그인 {name} 불러오기 실패 ({source}): {type(e).__name__}: {e}")
+
+    api = getattr(module, 'PLUGIN_API', None)
+    if api != PLUGIN_API_VERSION:
+        raise PluginError(f"플러그인 {name}의 PLUGIN_API {api!r}가 하네스 버전 {PLUGIN_API_VERSION}과 다릅니다 ({source})")
+    if not callable(getattr(module, 'create_detector', None)) and not (
+            isinstance(getattr(module, 'DETECTOR', None), type) and issubclass(module.DETECTOR, BaseDetector)):
+        raise PluginError(f"플러그인 {name}에 create_detector 함수나 BaseDetector 하위 클래스 DETECTOR가 없습니다")
+    _loaded[source] = module
+    return module
+
+
+class PluginDetector(BaseDetector):
+    """플러그인이 만든 탐지기 (응답 정리와 결과 metadata의 플러그인 정보)"""
+
+    def __init__(self, name: str, source: str, module: ModuleType, options: Optional[Dict[str, Any]] = None):
+        self.name = name
+        self.source = source
+        self.module = module
+        self.options = dict(options or {})
+        factory = getattr(module, 'create_detector', None) or module.DETECTOR
+        try:
+            self.detector = factory(**self.options)
+        except TypeError as e:
+            raise PluginError(f"플러그인 {name} 생성 실패 (--plugin-option 확인): {e}")
+        if not isinstance(self.detector, BaseDetector):
+            raise PluginError(f"플러그인 {name}이 BaseDetector가 아닌 {type(self.detector).__name__}를 만들었습니다")
+
+    def prepare(self, agent_type: str, references: List[Dict[str, Any]]):
+        self.detector.prepare(agent_type, references)
+
+    def detect(self, agent_type: str, input_data: str, filename: Optional[str] = None) -> Dict[str, Any]:
+        response = self.detector.detect(agent_type, input_data, filename)
+        if not isinstance(response, dict) or not isinstance(response.get('labels'), list):
+            raise ValueError(f"플러그인 {self.name}의 탐지 결과에 labels 목록이 없습니다: {str(response)[:120]!r}")
+        return normalize_report(response)
+
+    def describe(self) -> Dict[str, Any]:
+        description = {key: value for key, value in self.detector.describe().items() if key != 'name'}
+        return {'name': self.name, **description,
+                'plugin': {'source': self.source, 'api': PLUGIN_API_VERSION,
+                           'version': getattr(self.module, '__version__', None), 'options': self.options}}
+
+
+def create_plugin_detector(name: str, options: Optional[Dict[str, Any]] = None,
+                           dirs: Optional[List[str]] = None, builtin: Tuple[str, ...] = ()) -> PluginDetector:
+    plugins = discover_plugins(dirs, builtin)
+    if name not in plugins:
+        raise PluginError(f"Unsupported detector type: {name}. Supported types: {list(builtin)}, "
+                          f"plugins: {list(plugins)}")
+    return PluginDetector(name, plugins[name], load_plugin(name, plugins[name]), options)
+
+
+def parse_plugin_options(items: Optional[List[str]]) -> Dict[str, Any]:
+    """--plugin-option KEY=VALUE 목록 → 생성자 인자 (값은 JSON으로 읽히면 JSON)"""
+    options = {}
+    for item in items or []:
+        key, separator, value = item.partition('=')
+        if not separator or not key.isidentifier():
+            raise PluginError(f"--plugin-option은 KEY=VALUE 형식이어야 합니다: {item!r}")
+        try:
+            options[key] = json.loads(value)
+        except json.JSONDecodeError:
+            options[key] = value
+    return options
+
+
+def main():
+    from .detector_factory import DetectorFactory
+
+    parser = argparse.ArgumentParser(description='탐지기 플러그인 목록과 불러오기 확인')
+    subparsers = parser.add_subparsers(dest='command', required=True)
+    listing = subparsers.add_parser('list', help='찾은 플러그인을 불러와 API 버전과 오류 표시')
+    listing.add_argument('--plugins-dir', nargs='+', help='플러그인 디렉토리 (기본: QVBENCH_PLUGINS, plugins/)')
+    args = parser.parse_args()
+
+    builtin = tuple(DetectorFactory.get_builtin_detectors())
+    plugins = discover_plugins(args.plugins_dir, builtin)
+    print(f"🔌 플러그인 디렉토리: {', '.join(str(d) for d in plugin_dirs(args.plugins_dir))}")
+    if not plugins:
+        print("   (플러그인 없음)")
+    failed = 0
+    for name, source in plugins.items():
+        try:
+            module = load_plugin(name, source)
+        except PluginError as e:
+            failed += 1
+            print(f"  ❌ {name}: {e}")
+            continue
+        version = getattr(module, '__version__', None)
+        print(f"  ✅ {name}{f' {version}' if version else ''} (API {PLUGIN_API_VERSION}): {source}")
+    sys.exit(1 if failed else 0)
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,162 @@
+"""
+임베딩 최근접 이웃 기준선 (retrieval)
+
+train 분할의 라벨 달린 샘플(알려진 참조 구현)을 임베딩해 두고, 평가 샘플의 임베딩과 코사인 유사도가
+가장 높은 참조 k개의 라벨을 유사도 가중 투표로 합칩니다. 정규식 기준선과 LLM 사이의 중간 기준선입니다.
+
+    neighbors   유사도 min_similarity 이상인 상위 k개 참조
+    라벨 점수    (라벨을 가진 이웃의 유사도 합) / (이웃 유사도 합)
+    예측        점수가 vote_threshold 이상인 라벨, 신뢰도는 가장 가까운 이웃의 유사도
+
+임베딩 모델은 Embedder 인터페이스로 교체합니다:
+    hashing   식별자/상수 토큰과 토큰 bigram을 해시한 고정 차원 벡터 (외부 의존성/네트워크 없음, 기본값)
+    ollama    로컬 Ollama 임베딩 모델 (/api/embeddings, 예: nomic-embed-text). 오프라인 모드에서도 허용되는 로컬 엔드포인트
+"""
+
+import hashlib
+import math
+import re
+from abc import ABC, abstractmethod
+from collections import Counter
+from typing import Dict, Any, List, Optional
+
+from utils.top_k import MAX_CANDIDATES
+
+from .base_detector import BaseDetector
+
+DEFAULT_K = 5
+DEFAULT_MIN_SIMILARITY = 0.2
+DEFAULT_VOTE_THRESHOLD = 0.5
+
+# 임베딩 입력 상한 (큰 샘플은 앞부분만)
+MAX_EMBED_CHARS = 8000
+
+_TOKEN = re.compile(r'0x[0-9a-fA-F]+|[A-Za-z_][A-Za-z0-9_]{2,}|\d{3,}')
+
+
+class Embedder(ABC):
+    """텍스트 → 고정 차원 벡터"""
+
+    name = 'embedder'
+
+    @abstractmethod
+    def embed(self, texts: List[str]) -> List[List[float]]:
+        pass
+
+
+class HashingEmbedder(Embedder):
+    """토큰/토큰 bigram 해싱 임베딩 (로그 빈도, 부호 해싱, L2 정규화)"""
+
+    name = 'hashing'
+
+    def __init__(self, dimensions: int = 1024):
+        self.dimensions = dimensions
+
+    def _features(self, text: str) -> Counter:
+        tokens = [token.lower() for token in _TOKEN.findall(text[:MAX_EMBED_CHARS])]
+        features = Counter(tokens)
+        features.update(f"{a} {b}" for a, b in zip(tokens, tokens[1:]))
+        return features
+
+    def embed(self, texts: List[str]) -> List[List[float]]:
+        vectors = []
+        for text in texts:
+            vector = [0.0] * self.dimensions
+            for feature, count in self._features(text).items():
+                digest = hashlib.sha1(feature.encode('utf-8')).digest()
+                index = int.from_bytes(digest[:4], 'big') % self.dimensions
+                sign = 1.0 if digest[4] & 1 else -1.0
+                vector[index] += sign * (1.0 + math.log(count))
+            vectors.append(_normalize(vector))
+        return vectors
+
+
+class OllamaEmbedder(Embedder):
+    """로컬 Ollama 임베딩 모델"""
+
+    def __init__(self, model: str = 'nomic-embed-text', base_url: str = 'http://localhost:11434',
+                 timeout: int = 60):
+        self.model = model
+        self.base_url = base_url.rstrip('/')
+        self.timeout = timeout
+        self.name = f"ollama:{model}"
+
+    def embed(self, texts: List[str]) -> List[List[float]]:
+        try:
+            import requests
+        except ImportError as e:
+            raise RuntimeError("ollama 임베딩에는 requests가 필요합니다 (pip install \"qvbench[embeddings-ollama]\")") from e
+        vectors = []
+        for text in texts:
+            response = requests.post(f"{self.base_url}/api/embeddings",
+                                     json={'model': self.model, 'prompt': text[:MAX_EMBED_CHARS]},
+                                     timeout=self.timeout)
+            response.raise_for_status()
+            vectors.append(_normalize(response.json()['embedding']))
+        return vectors
+
+
+def _normalize(vector: List[float]) -> List[float]:
+    norm = math.sqrt(sum(v * v for v in vector))
+    return [v / norm for v in vector] if norm else vector
+
+
+def cosine(a: List[float], b: List[float]) -> float:
+    """정규화된 벡터의 코사인 유사도"""
+    return sum(x * y for x, y in zip(a, b))
+
+
+class RetrievalDetector(BaseDetector):
+    """참조 구현 임베딩의 최근접 이웃 투표"""
+
+    name = 'retrieval'
+
+    def __init__(self, embedder: Embedder = None, k: int = DEFAULT_K,
+                 min_similarity: float = DEFAULT_MIN_SIMILARITY, vote_threshold: float = DEFAULT_VOTE_THRESHOLD):
+        self.embedder = embedder or HashingEmbedder()
+        self.k = k
+        self.min_similarity = min_similarity
+        self.vote_threshold = vote_threshold
+        self.references: Dict[str, List[Dict[str, Any]]] = {}
+
+    def prepare(self, agent_type: str, references: List[Dict[str, Any]]):
+        vectors = self.embedder.embed([reference['input_data'] for reference in references])
+        self.references[agent_type] = [
+            {'test_id': reference['test_id'], 'labels': reference['labels'], 'vector': vector}
+            for reference, vector in zip(references, vectors)
+        ]
+
+    def neighbors(self, agent_type: str, input_data: str) -> List[Dict[str, Any]]:
+        """유사도 순 이웃 [{'test_id', 'labels', 'similarity'}]"""
+        vector = self.embedder.embed([input_data])[0]
+        ranked = sorted(
+            ({'test_id': ref['test_id'], 'labels': ref['labels'], 'similarity': cosine(vector, ref['vector'])}
+             for ref in self.references.get(agent_type, [])),
+            key=lambda item: (-item['similarity'], item['test_id'])
+        )
+        return [item for item in ranked[:self.k] if item['similarity'] >= self.min_similarity]
+
+    def detect(self, agent_type: str, input_data: str, filename: Optional[str] = None) -> Dict[str, Any]:
+        neighbors = self.neighbors(agent_type, input_data)
+        total = sum(item['similarity'] for item in neighbors)
+
+        votes: Dict[str, float] = {}
+        for item in neighbors:
+            for label in item['labels']:
+                votes[label] = votes.get(label, 0.0) + item['similarity']
+
+        labels = sorted((label for label, weight in votes.items() if total and weight / total >= self.vote_threshold),
+                        key=lambda label: -votes[label])
+        return {
+            'labels': labels,
+            'confidence': neighbors[0]['similarity'] if neighbors else 0.0,
+            'evidence': [f"{item['test_id']} ({item['similarity']:.3f})" for item in neighbors],
+            'scores': {label: votes[label] / total for label in labels},
+            # 이웃 투표 순 후보 (투표 임계값 아래 라벨 포함, top-k 채점용)
+            'candidates': [{'labels': sorted(votes, key=lambda label: (-votes[label], label))[:MAX_CANDIDATES]}]
+            if votes else [],
+        }
+
+    def describe(self) -> Dict[str, Any]:
+        return {'name': self.name, 'embedder': self.embedder.name, 'k': self.k,
+                'min_similarity': self.min_similarity, 'vote_threshold': self.vote_threshold}
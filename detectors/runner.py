@@ -0,0 +1,454 @@
+"""
+로컬 탐지기(기준선) 평가
+
+detectors/의 탐지기를 train 분할(utils/splits.py)로 준비하고 test 분할 샘플에서 채점합니다.
+결과는 benchmark_runner.py와 같은 형식({summary, detailed_results, metadata})이므로
+run_compare, leaderboard, results_store에서 LLM 실행과 나란히 비교할 수 있습니다.
+
+    provider   "local"
+    model      "<탐지기>-<임베딩>" 또는 "<탐지기>-<모델 파일 이름>" (예: retrieval-hashing, classifier-baseline_classifier)
+    정확도      탐지 라벨을 "DETECTED: <라벨>" 응답으로 바꿔 LLM 실행과 같은 calculate_accuracy로 계산
+    보정        탐지 보고의 라벨별 scores로 ECE와 임계값별 PR 곡선 (utils/calibration.py, summary.calibration)
+    top-k       탐지 보고의 candidates(없으면 탐지 하나하나)로 top-1/3/5 정확도 (utils/top_k.py, summary.top_k)
+    판단 보류    abstain: true인 샘플은 0점, 답한 샘플만의 지표와 risk-coverage (utils/selective.py, summary.selective)
+
+사용법:
+    python -m detectors.runner --detector retrieval
+    python -m detectors.runner --detector retrieval --embedder ollama --embedding-model nomic-embed-text
+    python -m detectors.runner --detector retrieval --agents source_code --k 3 --output results/retrieval.json
+    python -m detectors.runner --detector signature --filter "lang==rust && !tag:generated"
+    python -m detectors.runner --detector signature
+    python -m detectors.runner --detector structure
+    python -m detectors.runner --detector classifier --model models/baseline_classifier.json
+    python -m detectors.runner --detector ensemble --members retrieval classifier --strategy union
+    python -m detectors.runner --detector semgrep --ruleset rules/crypto.yaml --label-map rules/label_map.json
+    python -m detectors.runner --detector sarif --sarif results/codeql-java.sarif --label-map rules/codeql.yaml
+    python -m detectors.runner --detector cbom --cbom results/cbom-source_code.json
+    python -m detectors.runner --detector grpc --grpc-target localhost:50051
+    python -m detectors.runner --detector command --command "python tools/my_scanner.py" --command-batch
+    python -m detectors.runner --detector retrieval --log-format json --log-file results/retrieval_trace.jsonl
+"""
+
+import argparse
+import json
+import re
+import time
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+from detectors.base_detector import BaseDetector
+from detectors.classifier import DEFAULT_MODEL_PATH, DEFAULT_THRESHOLD
+from detectors.detector_factory import DetectorFactory
+from detectors.ensemble import STRATEGIES as ENSEMBLE_STRATEGIES
+from detectors.plugins import parse_plugin_options
+from detectors.external.command import CONTAINERS, DEFAULT_TIMEOUT as COMMAND_TIMEOUT, command_name
+from detectors.external.grpc import DEFAULT_TIMEOUT as GRPC_TIMEOUT
+from detectors.external.semgrep import DEFAULT_TIMEOUT as SEMGREP_TIMEOUT
+from utils.attributes import summarize_attributes
+from utils.calibration import calibration_summary, label_confidences
+from utils.environment import capture_environment, detector_settings
+from utils.findings_merger import FindingsMerger
+from utils.incremental import snapshot
+from utils.korean_compliance import korean_check, print_korean, summarize_korean
+from utils.languages import sample_language, summarize_languages, underperforming
+from utils.metrics_calculator import SUCCESS_THRESHOLD, MetricsCalculator
+from utils.results_store import corpus_version, ground_truth_hash
+from utils.run_control import STATUS_CRASH, STATUS_OK, crash_record, crashed_samples, print_crashes
+from utils.sample_filter import FilterError, SampleFilter, compile_filter
+from utils.sample_index import SampleIndex, calculate_function_scores, summarize_function_scores
+from utils.selective import abstained_scores, format_selective, selective_summary
+from utils.tracing import STAGE_LATENCY, add_logging_arguments, apply_logging_arguments, span, start_run
+from utils.significance import bootstrap_ci
+from utils.splits import SPLIT_TEST, SPLIT_TRAIN, sample_split
+from utils.tasks import sample_task, task_hierarchy_policy
+from utils.top_k import calculate_top_k_scores, finding_candidates, format_top_k, parse_candidates, summarize_top_k
+from utils.test_case_manager import TestCaseManager
+
+DEFAULT_AGENTS = ['source_code', 'assembly_binary', 'logs_config']
+
+
+def default_test_manager() -> TestCaseManager:
+    return TestCaseManager(
+        test_cases_dir="data/test_cases",
+        ground_truth_dir="data/ground_truth",
+        test_files_dir="data/test_files"
+    )
+
+
+def load_split_cases(test_manager: TestCaseManager, agent_type: str) -> List[Dict[str, Any]]:
+    """ground truth가 있는 테스트 케이스 (각각 'ground_truth', 'split' 포함)"""
+    cases = test_manager.load_test_cases(agent_type)
+    for case in cases:
+        case['ground_truth'] = test_manager.load_ground_truth(agent_type, case['test_id'])
+        case['split'] = sample_split(agent_type, case['test_id'], case['ground_truth'])
+    return [case for case in cases if case['ground_truth']]
+
+
+def references(cases: List[Dict[str, Any]]) -> List[Dict[str, Any]]:
+    """train 분할 참조 샘플 [{'test_id', 'input_data', 'labels'}]"""
+    return [
+        {'test_id': case['test_id'], 'input_data': case.get('input_data', ''),
+         'labels': MetricsCalculator.get_expected_labels(case['ground_truth'])}
+        for case in cases if case['split'] == SPLIT_TRAIN
+    ]
+
+
+def as_findings(detection: Dict[str, Any]) -> Dict[str, Any]:
+    """탐지 결과 → LLM 응답 파싱 결과와 같은 형태 (calculate_accuracy 입력)"""
+    return {
+        'valid_json': True,
+        'analysis_results': {label.lower(): f"DETECTED: {label}" for label in detection['labels']},
+        'confidence_score': detection['confidence'],
+        'summary': ', '.join(detection['labels']) or 'NOT DETECTED'
+    }
+
+
+def detection_findings(detection: Dict[str, Any], source: str) -> List[Dict[str, Any]]:
+    """탐지 보고 → Finding 목록 (locations의 줄마다 하나, 위치가 없는 라벨은 span 없이 하나)"""
+    scores = detection.get('scores', {})
+    findings = [FindingsMerger.make_finding(location['label'], span=[location['line'], location['line']],
+                                            confidence=scores.get(location['label'], detection['confidence']),
+                                            source=source)
+                for location in detection.get('locations', []) if location.get('label') in detection['labels']]
+    located = {finding['algorithm'] for finding in findings}
+    return findings + [FindingsMerger.make_finding(label, confidence=scores.get(label, detection['confidence']),
+                                                   source=source)
+                       for label in detection['labels'] if label not in located]
+
+
+class LocalDetectorRunner:
+    """로컬 탐지기 준비(train) → 평가(test)"""
+
+    def __init__(self, detector: BaseDetector, model_name: str, hierarchy_policy: Optional[str] = None,
+                 test_manager: TestCaseManager = None, sample_filter: Optional[SampleFilter] = None):
+        self.detector = detector
+        self.model_name = model_name
+        self.hierarchy_policy = hierarchy_policy
+        self.test_manager = test_manager or default_test_manager()
+        # 평가(test) 샘플만 거름, 참조(train) 샘플은 그대로
+        self.sample_filter = sample_filter
+
+    def run(self, agents: List[str], limit: Optional[int] = None) -> Dict[str, Any]:
+        results = []
+        run_id = start_run()
+        for agent_type in agents:
+            cases = load_split_cases(self.test_manager, agent_type)
+            train = references(cases)
+            evaluated = [case for case in cases if case['split'] == SPLIT_TEST]
+            if self.sample_filter:
+                evaluated = self.sample_filter.select(agent_type, evaluated, self.test_manager.load_ground_truth)
+            evaluated = evaluated[:limit]
+            print(f"📁 {agent_type}: 참조 {len(train)}개 (train), 평가 {len(evaluated)}개 (test)")
+
+            with span('detector.prepare', detector=self.model_name, agent_type=agent_type, references=len(train)):
+                self.detector.prepare(agent_type, train)
+            for case in evaluated:
+                with span('sample', provider='local', model=self.model_name, agent_type=agent_type,
+                          test_id=case['test_id']):
+                    results.append(self.evaluate(agent_type, case))
+
+        manifest = snapshot(str(self.test_manager.ground_truth_dir), str(self.test_manager.test_files_dir))
+        environment = capture_environment(manifest['corpus_version'],
+                                          {self.model_name: detector_settings(self.detector.describe())})
+        return {
+            'summary': self.summarize(results),
+            'detailed_results': results,
+            'metadata': {
+                'total_tests': len(results),
+                'timestamp': time.time(),
+                'providers': ['local'],
+                'agents': agents,
+                'detector': self.detector.describe(),
+                'hierarchy_policy': self.hierarchy_policy,
+                'filter': str(self.sample_filter) if self.sample_filter else None,
+                'corpus_version': corpus_version(results),
+                'environment': environment,
+                'run_id': run_id,
+                'stage_latency': STAGE_LATENCY.report()
+            }
+        }
+
+    def evaluate(self, agent_type: str, case: Dict[str, Any]) -> Dict[str, Any]:
+        """샘플 하나 채점 (탐지기나 채점에서 난 예외는 이 샘플의 crash 결과로, 실행은 계속)"""
+        try:
+            return self._evaluate(agent_type, case)
+        except Exception as e:
+            crash = crash_record(e)
+            print(f"  💥 {agent_type}/{case['test_id']}: {crash['exception']}: {crash['message']} @ {crash['location']}")
+            return {
+                'test_id': case['test_id'],
+                'provider': 'local',
+                'model': self.model_name,
+                'agent_type': agent_type,
+                'success': False,
+                'status': STATUS_CRASH,
+                'error': str(e),
+                'crash': crash,
+                'accuracy_score': 0.0,
+                'valid_json': False,
+                'hierarchical_scores': None,
+                'ground_truth_hash': ground_truth_hash(case['ground_truth']),
+                'task': sample_task(case['ground_truth']),
+                'split': case['split'],
+                'file_path': case.get('file_path', ''),
+                'language': sample_language(case.get('file_path')),
+                'timestamp': time.time()
+            }
+
+    def _evaluate(self, agent_type: str, case: Dict[str, Any]) -> Dict[str, Any]:
+        ground_truth = case['ground_truth']
+        task = sample_task(ground_truth)
+
+        started = time.time()
+        with span('detector.detect', detector=self.model_name) as detect_span:
+            detection = self.detector.detect(agent_type, case.get('input_data', ''), case.get('file_path') or None)
+            detect_span.set(labels=len(detection['labels']))
+        elapsed = time.time() - started
+        # 판단 보류: 라벨은 무시하고 기존 지표에서는 틀린 답과 같은 0점 (선택적 지표는 utils/selective.py)
+        abstained = detection.get('abstain') is True
+        if abstained:
+            detection = dict(detection, labels=[], locations=[], candidates=[])
+
+        findings = as_findings(detection)
+        located = detection_findings(detection, self.model_name)
+        policy = task_hierarchy_policy(task, self.hierarchy_policy)
+        with span('score', task=task):
+            accuracy = 0.0 if abstained else MetricsCalculator.calculate_accuracy(findings, ground_truth)
+            hierarchical_scores = abstained_scores(ground_truth, policy) if abstained else \
+                MetricsCalculator.calculate_hierarchical_scores(detection['labels'], ground_truth, policy)
+            function_scores = None
+            if Path(case.get('file_path') or '').is_file():
+                function_scores = calculate_function_scores(
+                    located, ground_truth,
+                    SampleIndex.from_text(case.get('input_data', ''), Path(case['file_path']).suffix))
+        return {
+            'test_id': case['test_id'],
+            'provider': 'local',
+            'model': self.model_name,
+            'agent_type': agent_type,
+            'success': accuracy >= SUCCESS_THRESHOLD,
+            'status': STATUS_OK,
+            'accuracy_score': accuracy,
+            'valid_json': True,
+            'confidence_score': detection['confidence'],
+            'detected_quantum_vulnerable_count': len(detection['labels']),
+            'detected_algorithms': detection['labels'],
+            'findings': located,
+            'abstained': abstained,
+            'hierarchical_scores': hierarchical_scores,
+            'predictions': MetricsCalculator.calculate_prediction_credits(
+                label_confidences(located), ground_truth, policy
+            ),
+            'top_k_scores': calculate_top_k_scores(
+                parse_candidates(detection.get('candidates')) or finding_candidates(located), ground_truth, policy
+            ),
+            'quantum_safe_check': MetricsCalculator.calculate_quantum_safe_misclassification(
+                detection['labels'], ground_truth
+            ),
+            'korean_check': korean_check(detection['labels'], ground_truth),
+            'function_scores': function_scores,
+            'ground_truth_hash': ground_truth_hash(ground_truth),
+            'task': task,
+            'split': case['split'],
+            'evidence': detection.get('evidence', []),
+            'response_time': elapsed,
+            'summary': findings['summary'],
+            'file_path': case.get('file_path', ''),
+            'language': sample_language(case.get('file_path')),
+            'timestamp': time.time()
+        }
+
+    @staticmethod
+    def summarize(results: List[Dict[str, Any]]) -> Dict[str, Any]:
+        # crash 샘플은 빼지 않고 계층 F1 0점
+        def f1(row):
+            return row['hierarchical_scores']['f1'] if row['hierarchical_scores'] else 0.0
+
+        by_agent: Dict[str, Any] = {}
+        for agent_type in sorted({r['agent_type'] for r in results}):
+            rows = [r for r in results if r['agent_type'] == agent_type]
+            by_agent[agent_type] = {
+                'total': len(rows),
+                'successful': sum(1 for r in rows if r['success']),
+                'avg_accuracy': sum(r['accuracy_score'] for r in rows) / len(rows),
+                'hierarchical_f1': bootstrap_ci([f1(r) for r in rows])
+            }
+        return {
+            'total_tests': len(results),
+            'successful_tests': sum(1 for r in results if r['success']),
+            'hierarchical_f1': bootstrap_ci([f1(r) for r in results]),
+            'by_agent': by_agent,
+            'by_attribute': summarize_attributes(
+                match for r in results for match in (r['hierarchical_scores'] or {}).get('matches', [])),
+            'function_level': summarize_function_scores(r.get('function_scores') for r in results),
+            'korean_compliance': summarize_korean(r.get('korean_check') for r in results),
+            'by_language': summarize_languages(results),
+            'calibration': calibration_summary(results),
+            'top_k': summarize_top_k(r.get('top_k_scores') for r in results),
+            'selective': selective_summary(results),
+            'crashed_samples': crashed_samples(results)
+        }
+
+
+def print_summary(summary: Dict[str, Any], model_name: str):
+    f1 = summary['hierarchical_f1']
+    print(f"\n📊 {model_name}: 테스트 {summary['total_tests']}개, 성공 {summary['successful_tests']}개")
+    print(f"  계층 F1 {f1['mean']:.3f} [95% CI {f1['ci_low']:.3f}, {f1['ci_high']:.3f}]")
+    for agent_type, stats in summary['by_agent'].items():
+        print(f"  🤖 {agent_type}: 정확도 {stats['avg_accuracy']:.3f}, "
+              f"계층 F1 {stats['hierarchical_f1']['mean']:.3f} ({stats['successful']}/{stats['total']} 성공)")
+    for attribute, stats in summary.get('by_attribute', {}).items():
+        print(f"  🔧 {attribute}: 정답률 {stats['accuracy']:.1%} ({stats['correct']}/{stats['expected']})")
+    function_level = summary.get('function_level', {})
+    if function_level.get('samples'):
+        print(f"  🔬 함수 단위: P {function_level['precision']:.3f} / R {function_level['recall']:.3f} / "
+              f"F1 {function_level['f1']:.3f} ({function_level['matched']}/{function_level['expected']})")
+    korean = summary.get('korean_compliance', {})
+    if korean.get('samples'):
+        print(f"  🇰🇷 K-crypto 커버리지:")
+        print_korean(korean, indent='    ')
+    selective = summary.get('selective', {})
+    if selective.get('abstained'):
+        print(f"  🤷 {format_selective(selective)}")
+    top_k = summary.get('top_k', {})
+    if top_k.get('samples'):
+        print(f"  🏅 순위 후보: {format_top_k(top_k)} (정답 {top_k['expected']}개)")
+    calibration = summary.get('calibration')
+    if calibration:
+        best = calibration['best_threshold']
+        print(f"  🎚️  보정: ECE {calibration['ece']:.3f}, Brier {calibration['brier']:.3f}, "
+              f"AP {calibration['average_precision']:.3f}, F1 최대 임계값 ≥ {best['threshold']:.3f} "
+              f"(F1 {best['f1']:.3f})")
+    for entry in underperforming(summary.get('by_language', {})):
+        print(f"  ⚠️  {entry['language']} 부진: 계층 F1 {entry['f1']['mean']:.3f} (n={entry['f1']['n']}), "
+              f"다른 언어 {entry['others_f1']:.3f} (n={entry['others_n']}), p={entry['p_value']:.4f}")
+    print_crashes(summary.get('crashed_samples', []))
+
+
+def detector_config(detector: str, args: argparse.Namespace) -> Tuple[Dict[str, Any], str]:
+    """CLI 인자 → (DetectorFactory 설정, 결과에 기록할 모델 이름)"""
+    if detector == 'retrieval':
+        embedder_config = {}
+        if args.embedder == 'ollama':
+            embedder_config = {'model': args.embedding_model, 'base_url': args.ollama_url}
+        return {'embedder': args.embedder, 'embedder_config': embedder_config, 'k': args.k}, \
+            f"{detector}-{args.embedder}"
+    if detector == 'classifier':
+        return {'model_path': args.model, 'threshold': args.threshold}, f"{detector}-{Path(args.model).stem}"
+    if detector == 'ensemble':
+        members = [detector_config(member, args) for member in args.members]
+        config = {'members': args.members, 'member_configs': [member[0] for member in members],
+                  'strategy': args.strategy}
+        return config, f"{detector}-{args.strategy}-" + '+'.join(member[1] for member in members)
+    if detector == 'semgrep':
+        if not args.ruleset:
+            raise SystemExit("--detector semgrep에는 --ruleset이 필요합니다")
+        config = {'ruleset': args.ruleset, 'label_map': args.label_map, 'timeout': args.semgrep_timeout}
+        return config, f"{detector}-{Path(args.ruleset.rstrip('/')).stem}"
+    if detector == 'sarif':
+        if not args.sarif:
+            raise SystemExit("--detector sarif에는 --sarif가 필요합니다")
+        return {'sarif': args.sarif, 'label_map': args.label_map}, \
+            f"{detector}-{Path(args.sarif[0].rstrip('/')).name.split('.')[0]}"
+    if detector == 'cbom':
+        if not args.cbom:
+            raise SystemExit("--detector cbom에는 --cbom이 필요합니다")
+        return {'cbom': args.cbom, 'label_map': args.label_map}, \
+            f"{detector}-{Path(args.cbom[0].rstrip('/')).name.split('.')[0]}"
+    if detector == 'grpc':
+        if not args.grpc_target:
+            raise SystemExit("--detector grpc에는 --grpc-target이 필요합니다")
+        config = {'target': args.grpc_target, 'timeout': args.grpc_timeout, 'tls': args.grpc_tls,
+                  'prepare': not args.grpc_no_prepare}
+        return config, f"{detector}-" + re.sub(r'[^A-Za-z0-9.-]+', '_', args.grpc_target)
+    if detector == 'command':
+        if args.command_container and not args.command_image:
+            raise SystemExit("--command-container에는 --command-image가 필요합니다")
+        if not args.command and not args.command_container:
+            raise SystemExit("--detector command에는 --command가 필요합니다 (컨테이너면 --command-image만으로 가능)")
+        config = {'command': args.command, 'batch': args.command_batch, 'timeout': args.command_timeout,
+                  'memory_mb': args.command_memory_mb, 'cpu_seconds': args.command_cpu_seconds,
+                  'container': args.command_container, 'image': args.command_image, 'cpus': args.command_cpus,
+                  'mount': args.command_mount}
+        name = command_name(args.command, args.command_image if args.command_container else None)
+        return config, f"{detector}-" + re.sub(r'[^A-Za-z0-9.-]+', '_', name)
+    if detector not in DetectorFactory.get_builtin_detectors():
+        return {'plugin_dirs': args.plugins_dir, **parse_plugin_options(args.plugin_option)}, detector
+    return {}, detector
+
+
+def add_detector_arguments(parser: argparse.ArgumentParser):
+    """탐지기 선택과 설정 인자 (detectors.server와 공유)"""
+    parser.add_argument('--detector', default='retrieval',
+                        help=f"탐지기: {', '.join(DetectorFactory.get_builtin_detectors())} 또는 플러그인 이름 "
+                             "(python -m detectors.plugins list)")
+    parser.add_argument('--embedder', choices=['hashing', 'ollama'], default='hashing', help='임베딩 모델 (retrieval)')
+    parser.add_argument('--embedding-model', default='nomic-embed-text', help='Ollama 임베딩 모델 이름')
+    parser.add_argument('--ollama-url', default='http://localhost:11434', help='Ollama 주소')
+    parser.add_argument('--k', type=int, default=5, help='최근접 이웃 수 (retrieval)')
+    parser.add_argument('--model', default=DEFAULT_MODEL_PATH,
+                        help='분류기 모델 (.json: detectors.train_baseline 출력, .onnx) (classifier)')
+    parser.add_argument('--threshold', type=float, default=DEFAULT_THRESHOLD, help='라벨 확률 임계값 (classifier)')
+    parser.add_argument('--members', nargs='+', default=['retrieval', 'classifier'], help='앙상블 멤버 (ensemble)')
+    parser.add_argument('--strategy', choices=ENSEMBLE_STRATEGIES, default='majority', help='앙상블 전략 (ensemble)')
+    parser.add_argument('--ruleset', help='Semgrep 규칙 파일/디렉토리 또는 레지스트리 이름 (semgrep)')
+    parser.add_argument('--label-map', help='규칙 ID/자산 이름 접두사 → 라벨 매핑 JSON/YAML (semgrep, sarif, cbom)')
+    parser.add_argument('--semgrep-timeout', type=int, default=SEMGREP_TIMEOUT, help='샘플당 Semgrep 제한 시간(초)')
+    parser.add_argument('--sarif', nargs='+', help='코퍼스에 대해 만든 SARIF 파일/디렉토리 (sarif, 예: CodeQL 결과)')
+    parser.add_argument('--cbom', nargs='+', help='코퍼스에 대해 만든 CBOM/자산 목록 JSON 파일/디렉토리 (cbom)')
+    parser.add_argument('--grpc-target', help='원격 탐지기 서버 주소 host:port (grpc, detectors/external/detector.proto)')
+    parser.add_argument('--grpc-timeout', type=float, default=GRPC_TIMEOUT, help='호출당 제한 시간(초) (grpc)')
+    parser.add_argument('--grpc-tls', action='store_true', help='TLS로 연결 (grpc)')
+    parser.add_argument('--grpc-no-prepare', action='store_true', help='train 참조 샘플을 보내지 않음 (grpc)')
+    parser.add_argument('--command', help='JSON-over-stdio 외부 탐지 명령 (command, detectors/external/command.py)')
+    parser.add_argument('--command-batch', action='store_true',
+                        help='명령을 한 번 띄워 JSON Lines로 주고받음 (command, 기본: 샘플마다 실행)')
+    parser.add_argument('--command-timeout', type=float, default=COMMAND_TIMEOUT, help='샘플당 제한 시간(초) (command)')
+    parser.add_argument('--command-memory-mb', type=int, help='주소 공간 상한 MB (command, POSIX)')
+    parser.add_argument('--command-cpu-seconds', type=int, help='CPU 시간 상한 초 (command, POSIX)')
+    parser.add_argument('--command-container', choices=CONTAINERS,
+                        help='명령을 격리된 컨테이너(네트워크 없음, 읽기 전용) 안에서 실행 (command)')
+    parser.add_argument('--command-image', help='컨테이너 이미지, --command가 없으면 ENTRYPOINT 실행 (command)')
+    parser.add_argument('--command-cpus', type=float, help='컨테이너 CPU 개수 상한 (command)')
+    parser.add_argument('--command-mount', help='/detector에 읽기 전용으로 마운트할 도구 디렉토리 (command)')
+    parser.add_argument('--plugins-dir', nargs='+', help='탐지기 플러그인 디렉토리 (기본: QVBENCH_PLUGINS, plugins/)')
+    parser.add_argument('--plugin-option', action='append', metavar='KEY=VALUE',
+                        help='플러그인 탐지기 생성자 인자, 여러 번 가능 (값은 JSON 또는 문자열)')
+
+
+def main():
+    parser = argparse.ArgumentParser(description='로컬 탐지기(기준선) 평가')
+    add_detector_arguments(parser)
+    parser.add_argument('--agents', nargs='+', default=DEFAULT_AGENTS, help='평가할 에이전트')
+    parser.add_argument('--limit', type=int, help='에이전트당 평가 샘플 수 제한')
+    parser.add_argument('--filter', metavar='EXPR', help='필터 식에 맞는 평가 샘플만 (utils/sample_filter.py)')
+    parser.add_argument('--hierarchy-policy', help='계층 점수 정책 (기본: default)')
+    parser.add_argument('--output', help='결과 JSON 경로 (기본: results/<탐지기>_<시각>.json)')
+    add_logging_arguments(parser)
+    args = parser.parse_args()
+    apply_logging_arguments(args)
+
+    try:
+        sample_filter = compile_filter(args.filter)
+    except FilterError as e:
+        parser.error(str(e))
+    try:
+        config, model_name = detector_config(args.detector, args)
+        detector = DetectorFactory.create_detector(args.detector, config)
+    except ValueError as e:
+        parser.error(str(e))
+    runner = LocalDetectorRunner(detector, model_name, args.hierarchy_policy, sample_filter=sample_filter)
+    print(f"🔍 로컬 탐지기 평가: {model_name}")
+    results = runner.run(args.agents, args.limit)
+    print_summary(results['summary'], model_name)
+
+    output = args.output or f"results/{model_name}_{datetime.now().strftime('%Y%m%d_%H%M%S')}.json"
+    Path(output).parent.mkdir(parents=True, exist_ok=True)
+    with open(output, 'w', encoding='utf-8') as f:
+        json.dump(results, f, indent=2, ensure_ascii=False)
+    print(f"💾 결과 저장: {output}")
+
+
+if __name__ == "__main__":
+    main()
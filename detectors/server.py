@@ -0,0 +1,235 @@
+"""
+온디맨드 스캔 HTTP API (서버 모드)
+
+벤치마크의 기준선 탐지기(detectors/)를 크레이트에 넣지 않고 CI에서 바로 쓸 수 있도록 REST API로 제공합니다.
+소스 코드를 POST하면 설정한 탐지기의 탐지 보고와 라벨별 분류 체계 해석(카테고리/계열/변형, 기본 요소 역할,
+취약성 범주)을 돌려줍니다. 탐지기는 시작할 때 에이전트별 train 분할(utils/splits.py)로 준비합니다.
+
+엔드포인트:
+    GET  /healthz          준비 상태
+    GET  /v1/detector      탐지기 설정과 스캔 가능한 에이전트
+    POST /v1/scan          스캔 요청
+        JSON 본문:    {"source": "<코드>", "agent_type": "source_code", "filename": "app.py"}
+        그 외 본문:   코드 원문 (에이전트는 ?agent_type=, 기본 source_code)
+
+응답 예:
+    {
+      "detector": "retrieval-hashing",
+      "agent_type": "source_code",
+      "filename": "app.py",
+      "detected": true,
+      "confidence": 0.82,
+      "labels": [
+        {"label": "RSA", "category": "shor_vulnerable", "family": "RSA", "variant": null,
+         "path": "shor_vulnerable/RSA", "role": "public_key", "weakness": "quantum-vulnerable", "known": true}
+      ],
+      "evidence": [...],
+      "elapsed_ms": 3.1
+    }
+
+오류는 {"error": "..."}와 400(잘못된 요청), 404, 413(본문이 --max-bytes 초과)으로 돌려줍니다.
+
+사용법:
+    python -m detectors.server --detector retrieval --port 8088
+    python -m detectors.server --detector classifier --model models/baseline_classifier.json --agents source_code
+    curl -s localhost:8088/v1/scan -H 'Content-Type: application/json' \\
+         -d '{"source": "from Crypto.PublicKey import RSA\\nkey = RSA.generate(2048)", "agent_type": "source_code"}'
+    curl -s 'localhost:8088/v1/scan?agent_type=source_code' --data-binary @src/crypto.rs
+"""
+
+import argparse
+import json
+import threading
+import time
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from typing import Any, Dict, List, Optional, Tuple
+from urllib.parse import parse_qs, urlparse
+
+from detectors.base_detector import BaseDetector
+from detectors.detector_factory import DetectorFactory
+from detectors.runner import (DEFAULT_AGENTS, add_detector_arguments, default_test_manager, detector_config,
+                              load_split_cases, references)
+from utils.taxonomy import AlgorithmTaxonomy
+from utils.test_case_manager import TestCaseManager
+from utils.tracing import add_logging_arguments, apply_logging_arguments, logger, span
+from utils.weakness import label_weakness
+
+DEFAULT_PORT = 8088
+DEFAULT_MAX_BYTES = 1024 * 1024
+
+
+class ScanError(Exception):
+    """잘못된 스캔 요청 (HTTP 상태 코드 포함)"""
+
+    def __init__(self, message: str, status: int = 400):
+        super().__init__(message)
+        self.status = status
+
+
+class ScanService:
+    """준비된 탐지기로 요청마다 스캔"""
+
+    def __init__(self, detector: BaseDetector, model_name: str, agents: List[str] = None,
+                 test_manager: TestCaseManager = None):
+        self.detector = detector
+        self.model_name = model_name
+        self.agents = list(agents or DEFAULT_AGENTS)
+        self.test_manager = test_manager or default_test_manager()
+        self.taxonomy = AlgorithmTaxonomy()
+        self.references: Dict[str, int] = {}
+        self.lock = threading.Lock()  # 탐지기 구현이 스레드 안전하다고 가정하지 않음
+
+    def prepare(self):
+        """에이전트마다 train 분할 참조 샘플로 탐지기 준비"""
+        for agent_type in self.agents:
+            train = references(load_split_cases(self.test_manager, agent_type))
+            with span('detector.prepare', detector=self.model_name, agent_type=agent_type, references=len(train)):
+                self.detector.prepare(agent_type, train)
+            self.references[agent_type] = len(train)
+
+    def classify(self, label: str) -> Dict[str, Any]:
+        """라벨 하나의 분류 체계 해석"""
+        node = self.taxonomy.resolve(label)
+        category, family, variant = node
+        return {
+            'label': label,
+            'category': category,
+            'family': family,
+            'variant': variant,
+            'path': self.taxonomy.format_node(node),
+            'role': self.taxonomy.role(node),
+            'weakness': label_weakness(label, taxonomy=self.taxonomy),
+            'known': self.taxonomy.is_known(node)
+        }
+
+    def scan(self, source: str, agent_type: str = 'source_code', filename: Optional[str] = None) -> Dict[str, Any]:
+        if agent_type not in self.agents:
+            raise ScanError(f"unsupported agent_type: {agent_type} (available: {', '.join(self.agents)})")
+        if not source or not source.strip():
+            raise ScanError("source is empty")
+
+        started = time.perf_counter()
+        with span('detector.detect', detector=self.model_name, agent_type=agent_type) as detect_span:
+            with self.lock:
+                detection = self.detector.detect(agent_type, source, filename)
+            detect_span.set(labels=len(detection['labels']))
+        return {
+            'detector': self.model_name,
+            'agent_type': agent_type,
+            'filename': filename,
+            'detected': bool(detection['labels']),
+            'confidence': detection['confidence'],
+            'labels': [self.classify(label) for label in detection['labels']],
+            'evidence': detection.get('evidence', []),
+            'elapsed_ms': round((time.perf_counter() - started) * 1000, 1)
+        }
+
+    def describe(self) -> Dict[str, Any]:
+        return {'detector': self.model_name, 'config': self.detector.describe(), 'agents': self.agents,
+                'references': self.references}
+
+
+def parse_scan_request(body: bytes, content_type: str, query: Dict[str, List[str]]) -> Tuple[str, str, Optional[str]]:
+    """요청 본문 → (source, agent_type, filename)"""
+    agent_type = query.get('agent_type', ['source_code'])[0]
+    filename = query.get('filename', [None])[0]
+    try:
+        text = body.decode('utf-8')
+    except UnicodeDecodeError:
+        raise ScanError("body must be UTF-8 text")
+
+    if content_type.split(';')[0].strip() != 'application/json':
+        return text, agent_type, filename
+
+    try:
+        payload = json.loads(text)
+    except json.JSONDecodeError as e:
+        raise ScanError(f"invalid JSON: {e}")
+    if not isinstance(payload, dict) or not isinstance(payload.get('source'), str):
+        raise ScanError('JSON body must be an object with a "source" string')
+    return payload['source'], payload.get('agent_type', agent_type), payload.get('filename', filename)
+
+
+class ScanHandler(BaseHTTPRequestHandler):
+    service: ScanService = None
+    max_bytes: int = DEFAULT_MAX_BYTES
+
+    def send_json(self, status: int, payload: Dict[str, Any]):
+        body = json.dumps(payload, ensure_ascii=False).encode('utf-8')
+        self.send_response(status)
+        self.send_header('Content-Type', 'application/json; charset=utf-8')
+        self.send_header('Content-Length', str(len(body)))
+        self.end_headers()
+        self.wfile.write(body)
+
+    def do_GET(self):
+        path = urlparse(self.path).path
+        if path == '/healthz':
+            self.send_json(200, {'status': 'ok', 'detector': self.service.model_name})
+        elif path == '/v1/detector':
+            self.send_json(200, self.service.describe())
+        else:
+            self.send_json(404, {'error': f"not found: {path}"})
+
+    def do_POST(self):
+        url = urlparse(self.path)
+        if url.path != '/v1/scan':
+            self.send_json(404, {'error': f"not found: {url.path}"})
+            return
+        try:
+            length = int(self.headers.get('Content-Length') or 0)
+            if length > self.max_bytes:
+                raise ScanError(f"body exceeds {self.max_bytes} bytes", status=413)
+            source, agent_type, filename = parse_scan_request(
+                self.rfile.read(length), self.headers.get('Content-Type', ''), parse_qs(url.query)
+            )
+            self.send_json(200, self.service.scan(source, agent_type, filename))
+        except ScanError as e:
+            self.send_json(e.status, {'error': str(e)})
+        except Exception as e:
+            logger.exception(f"스캔 실패: {e}")
+            self.send_json(500, {'error': f"scan failed: {e}"})
+
+    def log_message(self, format, *args):
+        logger.info(f"{self.address_string()} {format % args}")
+
+
+def create_server(service: ScanService, host: str = '127.0.0.1', port: int = DEFAULT_PORT,
+                  max_bytes: int = DEFAULT_MAX_BYTES) -> ThreadingHTTPServer:
+    handler = type('BoundScanHandler', (ScanHandler,), {'service': service, 'max_bytes': max_bytes})
+    return ThreadingHTTPServer((host, port), handler)
+
+
+def main():
+    parser = argparse.ArgumentParser(description='기준선 탐지기 온디맨드 스캔 HTTP API')
+    add_detector_arguments(parser)
+    parser.add_argument('--agents', nargs='+', default=DEFAULT_AGENTS, help='스캔을 받을 에이전트')
+    parser.add_argument('--host', default='127.0.0.1', help='바인드 주소 (외부에 열려면 0.0.0.0)')
+    parser.add_argument('--port', type=int, default=DEFAULT_PORT, help='포트')
+    parser.add_argument('--max-bytes', type=int, default=DEFAULT_MAX_BYTES, help='요청 본문 최대 크기')
+    add_logging_arguments(parser)
+    args = parser.parse_args()
+    apply_logging_arguments(args)
+
+    try:
+        config, model_name = detector_config(args.detector, args)
+        service = ScanService(DetectorFactory.create_detector(args.detector, config), model_name, args.agents)
+    except ValueError as e:
+        parser.error(str(e))
+    print(f"🔍 탐지기 준비: {model_name}")
+    service.prepare()
+    for agent_type, count in service.references.items():
+        print(f"  📁 {agent_type}: 참조 {count}개 (train)")
+
+    server = create_server(service, args.host, args.port, args.max_bytes)
+    print(f"📡 스캔 API: http://{args.host}:{args.port}/v1/scan (Ctrl-C로 종료)")
+    try:
+        server.serve_forever()
+    except KeyboardInterrupt:
+        print("\n📡 스캔 API 종료")
+    finally:
+        server.server_close()
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,279 @@
+"""
+구현 상수 서명 기준선 (signature)
+
+알고리즘 계열별 구현 상수/구조 단서(utils.rationale.EVIDENCE_SIGNATURES)가 샘플에 나오는지로 라벨을 예측합니다.
+학습이 필요 없는 가장 낮은 기준선이며, 코퍼스 가져오기(utils/corpus_import.py)의 signature 제안도 같은 매칭을 씁니다.
+
+단서를 글자 그대로만 찾으면 상수를 조금만 바꿔 적어도 놓치므로, 매칭 전에 흔한 난독화를 정규화합니다 (ConstantView).
+
+    값 동등       16진/10진/8진/2진 리터럴, 자릿수 구분자(0x428a_2f98), 접미사(u32, UL)를 값으로 비교
+                  (단서 '0x10001'은 65537, 0x00010001과도 맞음)
+    상수 식       리터럴과 연산자(<< >> | ^ & + - * ~)만으로 된 식을 계산 ((0x428a << 16) | 0x2f98, 0x5a820000 ^ 0x7999,
+                  형 변환 `as u32`, `(uint32_t)` 무시), 결과는 64비트와 32비트로 자름
+    표 표현       쉼표로 이어진 리터럴 표를 바이트열로 펼쳐(원소 폭별 빅/리틀 엔디언) 바이트 단서('0x63, 0x7c')와
+                  워드 단서('0x637c777b')를 표 표현과 관계없이 찾음. 바이트열이 출력 가능한 문자이면 문자열로도 봄
+                  (리틀 엔디언 워드 0x61707865 … → "expand 32-byte k")
+    문자열 조각    이어 붙인 문자열 리터럴("expand 3" + "2-byte k", C의 인접 리터럴, concat!(…), Lua의 ..)과
+                  \\xNN 이스케이프를 풀어 문자열 단서를 찾고, 16진 숫자만 든 문자열("428a" "2f98")은 값으로도 봄
+
+예측 기준 (confident_families):
+    다른 계열과 겹치지 않는 8자리 이상 16진 상수 하나, 또는 서로 다른 단서 2개 이상이 나온 계열
+    신뢰도는 찾은 단서 수 / 3 (최대 1), 근거 줄은 단서가 처음 나온 줄
+
+사용법:
+    python -m detectors.runner --detector signature
+"""
+
+import ast
+import re
+from bisect import bisect_right
+from typing import Dict, Any, List, Optional, Tuple
+
+from utils.rationale import EVIDENCE_SIGNATURES
+
+from .base_detector import BaseDetector
+
+# 다른 계열과 겹치지 않으면 혼자서도 예측 근거가 되는 단서
+STRONG_CUE = re.compile(r'^0x[0-9a-f]{8,}$')
+# 표로 볼 최소 원소 수
+MIN_TABLE_LENGTH = 2
+MASKS = ((1 << 64) - 1, (1 << 32) - 1)
+
+_LITERAL = re.compile(r'(?<![\w.])(0[xX][0-9a-fA-F_]+|0[bB][01_]+|0[oO][0-7_]+|\d[\d_]*)'
+                      r'(?:_?[uUiI](?:8|16|32|64|128|size)|[uU]?[lL]{0,2}|[lL]{1,2}[uU]?)?(?![\w.])')
+_CAST = re.compile(r'\s+as\s+[ui](?:8|16|32|64|128|size)\b|'
+                   r'\(\s*(?:u?int(?:8|16|32|64)_t|u(?:8|16|32|64)|unsigned(?:\s+(?:int|long(?:\s+long)?|char))?|'
+                   r'long(?:\s+long)?|int)\s*\)')
+_EXPRESSION_TOKEN = re.compile(r'\s*(<<|>>|[()|^&+*~-]|' + _LITERAL.pattern + r')')
+_TABLE_SEPARATOR = re.compile(r'\s*,\s*(?:(?://|#)[^\n]*\n\s*|/\*.*?\*/\s*)*', re.DOTALL)
+_STRING = re.compile(r'"((?:[^"\\\n]|\\.)*)"')
+_STRING_JOIN = re.compile(r'\s*(?:\+|\.\.|\.)?\s*')
+_CONCAT_JOIN = re.compile(r'\s*,\s*')
+_BINARY_OPERATORS = {'<<', '>>', '|', '^', '&', '+', '-', '*'}
+_NUMERIC_CUE = re.compile(r'^(?:0x[0-9a-f]+|\d+)$')
+_BYTES_CUE = re.compile(r'^0x[0-9a-f]{2}(?:\s*,\s*0x[0-9a-f]{2})+$')
+_HEX_STRING = re.compile(r'^(?:0x)?[0-9a-f]{8,}$')
+
+
+def parse_literal(literal: str) -> int:
+    """정수 리터럴 → 값 (자릿수 구분자와 형 접미사 무시)"""
+    body = _LITERAL.match(literal).group(1).replace('_', '').lower()
+    if body.startswith('0x'):
+        return int(body[2:], 16)
+    if body.startswith('0b'):
+        return int(body[2:], 2)
+    if body.startswith('0o'):
+        return int(body[2:], 8)
+    return int(body)
+
+
+def _evaluate(node: ast.AST) -> int:
+    if isinstance(node, ast.Constant) and isinstance(node.value, int):
+        return node.value
+    if isinstance(node, ast.UnaryOp) and isinstance(node.op, (ast.Invert, ast.USub)):
+        value = _evaluate(node.operand)
+        return ~value if isinstance(node.op, ast.Invert) else -value
+    if isinstance(node, ast.BinOp):
+        left, right = _evaluate(node.left), _evaluate(node.right)
+        if isinstance(node.op, (ast.LShift, ast.RShift)) and not 0 <= right <= 128:
+            raise ValueError('shift out of range')
+        operators = {ast.LShift: lambda: left << right, ast.RShift: lambda: left >> right,
+                     ast.BitOr: lambda: left | right, ast.BitXor: lambda: left ^ right,
+                     ast.BitAnd: lambda: left & right, ast.Add: lambda: left + right,
+                     ast.Sub: lambda: left - right, ast.Mult: lambda: left * right}
+        if type(node.op) in operators:
+            return operators[type(node.op)]()
+    raise ValueError('not a constant expression')
+
+
+def evaluate_expression(tokens: List[str]) -> Optional[int]:
+    """리터럴/연산자 토큰 → 값 (계산할 수 없으면 None)"""
+    while tokens and tokens[0] in (_BINARY_OPERATORS - {'-'}) | {')'}:
+        tokens = tokens[1:]
+    while tokens and tokens[-1] in _BINARY_OPERATORS | {'(', '~'}:
+        tokens = tokens[:-1]
+    while tokens and tokens[0] == '(' and tokens.count('(') > tokens.count(')'):
+        tokens = tokens[1:]
+    while tokens and tokens[-1] == ')' and tokens.count(')') > tokens.count('('):
+        tokens = tokens[:-1]
+    if not any(token in _BINARY_OPERATORS | {'~'} for token in tokens):
+        return None
+    text = ' '.join(str(parse_literal(token)) if token[0].isdigit() else token for token in tokens)
+    try:
+        return _evaluate(ast.parse(text, mode='eval').body)
+    except (SyntaxError, ValueError, RecursionError):
+        return None
+
+
+def _unescape(body: str) -> str:
+    return re.sub(r'\\x([0-9a-fA-F]{2})|\\(.)',
+                  lambda match: chr(int(match.group(1), 16)) if match.group(1) else match.group(2), body)
+
+
+def _table_bytes(values: List[int]) -> List[Tuple[str, List[int]]]:
+    """표 원소 → 원소 폭(1/2/4/8바이트)으로 펼친 바이트열 [(16진 문자열, 원소 번호)] (빅/리틀 엔디언)"""
+    if any(value < 0 for value in values):
+        return []
+    width = next((size for size in (1, 2, 4, 8, 16) if max(values) < 1 << (8 * size)), None)
+    if width is None:
+        return []
+    orders = ['big'] if width == 1 else ['big', 'little']
+    return [(''.join(value.to_bytes(width, order).hex() for value in values),
+             [index for index in range(len(values)) for _ in range(width)]) for order in orders]
+
+
+class ConstantView:
+    """샘플 코드의 정규화된 상수/문자열 (단서 매칭용)"""
+
+    def __init__(self, text: str):
+        self.text = text
+        self.lines = text.lower().splitlines()
+        self.values: Dict[int, List[int]] = {}
+        self.tables: List[Tuple[str, List[int]]] = []
+        self.strings: List[Tuple[str, int]] = []
+        self._line_starts = [0] + [match.end() for match in re.finditer('\n', text)]
+        self._collect_literals()
+        self._collect_expressions()
+        self._collect_tables()
+        self._collect_strings()
+
+    def line_of(self, offset: int) -> int:
+        return bisect_right(self._line_starts, offset)
+
+    def _add_value(self, value: int, line: int):
+        for mask in MASKS:
+            lines = self.values.setdefault(value & mask, [])
+            if line not in lines:
+                lines.append(line)
+
+    def _collect_literals(self):
+        for match in _LITERAL.finditer(self.text):
+            self._add_value(parse_literal(match.group(0)), self.line_of(match.start()))
+
+    def _collect_expressions(self):
+        for number, line in enumerate(self.text.splitlines(), 1):
+            line = _CAST.sub(' ', line)
+            run: List[str] = []
+            position = 0
+            while position <= len(line):
+                match = _EXPRESSION_TOKEN.match(line, position) if position < len(line) else None
+                if match:
+                    run.append(match.group(1))
+                    position = match.end()
+                    continue
+                value = evaluate_expression(run)
+                if value is not None:
+                    self._add_value(value, number)
+                run = []
+                position += 1
+
+    def _collect_tables(self):
+        literals = list(_LITERAL.finditer(self.text))
+        groups: List[List[re.Match]] = []
+        for match in literals:
+            if groups and _TABLE_SEPARATOR.fullmatch(self.text, groups[-1][-1].end(), match.start()):
+                groups[-1].append(match)
+            else:
+                groups.append([match])
+        for group in groups:
+            if len(group) < MIN_TABLE_LENGTH:
+                continue
+            values = [parse_literal(match.group(0)) for match in group]
+            lines = [self.line_of(match.start()) for match in group]
+            for hex_bytes, owners in _table_bytes(values):
+                self.tables.append((hex_bytes, [lines[owner] for owner in owners]))
+                decoded = bytes.fromhex(hex_bytes)
+                if decoded.isascii() and decoded.decode('ascii').isprintable():
+                    self.strings.append((decoded.decode('ascii').lower(), lines[0]))
+
+    def _collect_strings(self):
+        literals = list(_STRING.finditer(self.text))
+        groups: List[List[re.Match]] = []
+        for match in literals:
+            previous = groups[-1][-1] if groups else None
+            concat_at = self.text.rfind('concat!(', 0, groups[-1][0].start()) if groups else -1
+            in_concat = concat_at != -1 and concat_at >= self.text.rfind(')', 0, groups[-1][0].start())
+            joins = previous and (_STRING_JOIN.fullmatch(self.text, previous.end(), match.start()) or
+                                  (in_concat and _CONCAT_JOIN.fullmatch(self.text, previous.end(), match.start())))
+            if joins:
+                groups[-1].append(match)
+            else:
+                groups.append([match])
+        for group in groups:
+            value = ''.join(_unescape(match.group(1)) for match in group).lower()
+            line = self.line_of(group[0].start())
+            if len(group) > 1 or '\\' in group[0].group(1):
+                self.strings.append((value, line))
+            if _HEX_STRING.match(value):
+                self._add_value(int(value[2:] if value.startswith('0x') else value, 16), line)
+
+    def cue_lines(self, cue: str) -> List[int]:
+        """단서가 나온 줄 (글자 그대로 + 정규화한 상수/표/문자열)"""
+        cue = cue.lower()
+        lines = {number for number, line in enumerate(self.lines, 1) if cue in line}
+        if _NUMERIC_CUE.match(cue):
+            lines.update(self.values.get(parse_literal(cue), []))
+            if cue.startswith('0x') and len(cue) >= 10:
+                word = bytes.fromhex(cue[2:].zfill(len(cue) - 2 + len(cue) % 2))
+                lines.update(self._table_lines(word.hex()) + self._table_lines(word[::-1].hex()))
+        elif _BYTES_CUE.match(cue):
+            lines.update(self._table_lines(''.join(part.strip()[2:] for part in cue.split(','))))
+        else:
+            lines.update(line for value, line in self.strings if cue in value)
+        return sorted(lines)
+
+    def _table_lines(self, pattern: str) -> List[int]:
+        lines = []
+        for hex_bytes, owners in self.tables:
+            start = hex_bytes.find(pattern)
+            while start != -1:
+                if start % 2 == 0:
+                    lines.append(owners[start // 2])
+                start = hex_bytes.find(pattern, start + 1)
+        return lines
+
+
+def find_signatures(text: str, signatures: Optional[Dict[str, List[str]]] = None) -> Dict[str, Dict[str, Any]]:
+    """계열 → {'cues': [찾은 단서], 'lines': [줄]} (정규화 매칭, 단서가 하나라도 나온 계열)"""
+    view = ConstantView(text)
+    hits: Dict[str, Dict[str, Any]] = {}
+    for family, cues in (signatures or EVIDENCE_SIGNATURES).items():
+        for cue in cues:
+            lines = view.cue_lines(cue)
+            if lines:
+                entry = hits.setdefault(family, {'cues': [], 'lines': []})
+                entry['cues'].append(cue)
+                entry['lines'].extend(line for line in lines if line not in entry['lines'])
+    for entry in hits.values():
+        entry['lines'].sort()
+    return hits
+
+
+def confident_families(hits: Dict[str, Dict[str, Any]],
+                       signatures: Optional[Dict[str, List[str]]] = None) -> Dict[str, Dict[str, Any]]:
+    """예측 기준을 넘은 계열만 (고유한 8자리 이상 16진 상수 하나, 또는 서로 다른 단서 2개 이상)"""
+    cue_families: Dict[str, int] = {}
+    for cues in (signatures or EVIDENCE_SIGNATURES).values():
+        for cue in cues:
+            cue_families[cue] = cue_families.get(cue, 0) + 1
+    return {family: entry for family, entry in hits.items()
+            if len(entry['cues']) >= 2 or any(STRONG_CUE.match(cue) and cue_families[cue] == 1
+                                               for cue in entry['cues'])}
+
+
+class SignatureDetector(BaseDetector):
+    """구현 상수 서명 매칭 (난독화 정규화 후)"""
+
+    name = 'signature'
+
+    def detect(self, agent_type: str, input_data: str, filename: Optional[str] = None) -> Dict[str, Any]:
+        families = confident_families(find_signatures(input_data))
+        labels = sorted(families)
+        scores = {label: min(1.0, len(families[label]['cues']) / 3) for label in labels}
+        return {
+            'labels': labels,
+            'confidence': max(scores.values(), default=0.0),
+            'scores': scores,
+            'evidence': [f"{label}: {', '.join(families[label]['cues'])}" for label in labels],
+            'locations': [{'label': label, 'line': families[label]['lines'][0]} for label in labels],
+        }
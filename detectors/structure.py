@@ -0,0 +1,784 @@
+"""
+알고리즘 구조 그래프 매칭 기준선 (structure)
+
+이름과 상수를 모두 바꾸고 상수를 쪼개 감춰도, 암호 구현의 연산 배치(어느 연산의 결과가 어느 연산으로 흘러가는지)는
+남습니다. 함수마다 가벼운 데이터 흐름 그래프를 만들고, 알려진 알고리즘의 참조 그래프(REFERENCE_GRAPHS)와 허용 오차가
+있는 부분 그래프 동형 매칭으로 비교합니다. 어려운 난독화를 다루는 연구용 기준선이며, 서명(signature)이나 분류기
+(classifier)와 달리 상수값과 식별자를 전혀 보지 않습니다 (xtime의 기약 다항식 상수만 예외).
+
+데이터 흐름 그래프 (DataflowGraph):
+    노드      연산 하나 (add, xor, rot, shl, shr, and, or, not, mul, mod, lookup, call, var …)
+              뺄셈은 add, wrapping_add/rotate_left/Integer.rotateLeft 같은 메서드/함수는 해당 연산, 변수 인덱스로
+              읽는 표는 lookup, (x << n) | (x >> m) 관용구는 rot, 한 식 안의 같은 결합 연산(a ^ b ^ c)은 노드 하나
+    간선      피연산자를 정의한 노드 → 연산 노드 (변수는 마지막 대입을 가리킴, 복사/swap/튜플 대입은 별칭)
+    반복문    본문을 두 번 펼쳐 반복 사이의 흐름(Feistel의 좌우 교환)이 그래프에 드러나게 함
+    언어      중괄호 언어(C/C++/Java/JS/Go/Rust …), Python, 어셈블리(Intel/AT&T/ARM 2·3 피연산자 형식)
+
+참조 그래프와 매칭:
+    참조 간선은 (출발, 도착, 최소 홉, 최대 홉)이며, 그래프에서 그 길이의 경로가 있으면 만족합니다.
+    참조 노드는 그래프 노드에 일대일로 대응해야 하고, 만족하지 못한 간선이 허용 오차(tolerance) 이하이면 매칭입니다.
+    점수는 만족한 간선 비율이며, 같은 무리(group)의 참조가 한 함수에 여럿 맞으면 점수가 가장 높은 것만 남깁니다.
+    generic 참조(Feistel 골격)는 같은 함수에 더 구체적인 참조가 맞으면 버립니다.
+
+    chacha-quarter-round   add → xor → rot → add → xor → rot              ChaCha20
+    salsa-quarter-round    add → rot → xor → add → rot → xor              Salsa20
+    aes-ttable-round       서로 다른 상태 워드에서 시프트/마스크로 뽑은 바이트로 표 조회 4개 → xor   AES
+    seed-g-function        한 워드에서 시프트/마스크로 뽑은 바이트로 표 조회 4개 → xor          SEED
+    aes-xtime              v << 1 과 v >> 7 이 xor로 합쳐지고 0x1b가 나옴        AES
+    feistel-skeleton       x1 = b ^ F(a), x2 = a ^ F(x1) (F는 연산 2개 이상)   block-cipher (범주 수준)
+
+예측:
+    라벨은 맞은 참조의 라벨, 신뢰도는 가장 높은 매칭 점수, 근거 줄은 매칭된 노드 중 가장 앞 줄입니다.
+
+사용법:
+    python -m detectors.runner --detector structure
+"""
+
+import re
+from pathlib import Path
+from typing import Dict, Any, Iterator, List, Optional, Set, Tuple
+
+from utils.features import guess_language, language_family, split_functions
+from utils.sample_index import SampleIndex
+
+from .base_detector import BaseDetector
+from .signature import parse_literal
+
+WILDCARD = '*'
+# 참조 하나를 한 함수에 맞춰 보는 탐색 단계 상한 (큰 함수에서 시간 제한)
+MAX_SEARCH_STEPS = 20000
+# 그래프 노드 상한 (이보다 큰 함수는 앞부분만)
+MAX_GRAPH_NODES = 1500
+
+# 표 조회 라운드: 상태 워드(_WORD)에서 시프트/마스크로 바이트를 뽑아(_BYTE) 표를 읽음
+_WORD = ('var', 'lookup', 'xor')
+_BYTE = ('shr', 'and')
+
+REFERENCE_GRAPHS: List[Dict[str, Any]] = [
+    {'name': 'chacha-quarter-round', 'label': 'ChaCha20', 'group': 'arx', 'tolerance': 1,
+     'nodes': {'a1': 'add', 'x1': 'xor', 'r1': 'rot', 'a2': 'add', 'x2': 'xor', 'r2': 'rot'},
+     'edges': [('a1', 'x1', 1, 1), ('x1', 'r1', 1, 1), ('r1', 'a2', 1, 1), ('a2', 'x2', 1, 1), ('x2', 'r2', 1, 1)]},
+    {'name': 'salsa-quarter-round', 'label': 'Salsa20', 'group': 'arx', 'tolerance': 1,
+     'nodes': {'a1': 'add', 'r1': 'rot', 'x1': 'xor', 'a2': 'add', 'r2': 'rot', 'x2': 'xor'},
+     'edges': [('a1', 'r1', 1, 1), ('r1', 'x1', 1, 1), ('x1', 'a2', 1, 1), ('a2', 'r2', 1, 1), ('r2', 'x2', 1, 1)]},
+    {'name': 'aes-ttable-round', 'label': 'AES', 'group': 'table-round', 'tolerance': 1,
+     'nodes': {'x': 'xor', 'l1': 'lookup', 'l2': 'lookup', 'l3': 'lookup', 'l4': 'lookup',
+               'e1': _BYTE, 'e2': _BYTE, 'e3': _BYTE, 'e4': _BYTE,
+               'w1': _WORD, 'w2': _WORD, 'w3': _WORD, 'w4': _WORD},
+     'edges': [('l1', 'x', 1, 1), ('l2', 'x', 1, 1), ('l3', 'x', 1, 1), ('l4', 'x', 1, 1),
+               ('e1', 'l1', 1, 1), ('e2', 'l2', 1, 1), ('e3', 'l3', 1, 1), ('e4', 'l4', 1, 1),
+               ('w1', 'e1', 1, 2), ('w2', 'e2', 1, 2), ('w3', 'e3', 1, 2), ('w4', 'e4', 1, 2)]},
+    {'name': 'seed-g-function', 'label': 'SEED', 'group': 'table-round', 'tolerance': 1,
+     'nodes': {'x': 'xor', 'l1': 'lookup', 'l2': 'lookup', 'l3': 'lookup', 'l4': 'lookup',
+               'e1': _BYTE, 'e2': _BYTE, 'e3': _BYTE, 'e4': _BYTE, 'w': _WORD + ('add',)},
+     'edges': [('l1', 'x', 1, 1), ('l2', 'x', 1, 1), ('l3', 'x', 1, 1), ('l4', 'x', 1, 1),
+               ('e1', 'l1', 1, 1), ('e2', 'l2', 1, 1), ('e3', 'l3', 1, 1), ('e4', 'l4', 1, 1),
+               ('w', 'e1', 1, 2), ('w', 'e2', 1, 2), ('w', 'e3', 1, 2), ('w', 'e4', 1, 2)]},
+    {'name': 'aes-xtime', 'label': 'AES', 'group': 'mixcolumns', 'tolerance': 0, 'constants': [0x1b, 0x11b],
+     'nodes': {'x': 'xor', 'l': 'shl', 'r': 'shr', 'v': WILDCARD},
+     'edges': [('v', 'l', 1, 1), ('v', 'r', 1, 1), ('l', 'x', 1, 1), ('r', 'x', 1, 3)]},
+    {'name': 'feistel-skeleton', 'label': 'block-cipher', 'group': 'feistel', 'tolerance': 0, 'generic': True,
+     'nodes': {'x1': 'xor', 'x2': 'xor', 'a': WILDCARD, 'b': WILDCARD},
+     'edges': [('b', 'x1', 1, 1), ('a', 'x1', 3, 6), ('x1', 'x2', 3, 6), ('a', 'x2', 1, 1)]},
+]
+
+_ASSOCIATIVE = {'xor', 'add', 'or', 'and', 'mul'}
+_BINARY = {'|': ('or', 1), '^': ('xor', 2), '&': ('and', 3), '<<': ('shl', 4), '>>': ('shr', 4), '>>>': ('shr', 4),
+           '+': ('add', 5), '-': ('add', 5), '*': ('mul', 6), '/': ('div', 6), '%': ('mod', 6), '**': ('pow', 7)}
+_CALL_OPS = [
+    (re.compile(r'^(?:rot(?:ate)?_?(?:l|r|left|right)\w*|rol\w*|ror\w*)$', re.IGNORECASE), 'rot'),
+    (re.compile(r'^(?:wrapping|overflowing|checked|saturating)_(?:add|sub)$|^(?:add|sub)$'), 'add'),
+    (re.compile(r'^(?:wrapping|overflowing|checked)_mul$|^multiply$'), 'mul'),
+    (re.compile(r'^(?:xor|bitxor)$', re.IGNORECASE), 'xor'),
+    (re.compile(r'^(?:shiftLeft|wrapping_shl|shl)$'), 'shl'),
+    (re.compile(r'^(?:shiftRight|wrapping_shr|shr)$'), 'shr'),
+    (re.compile(r'^(?:mod_?pow|modPow|pow_?mod|powmod|pow)$', re.IGNORECASE), 'modexp'),
+]
+_TOKEN = re.compile(r'0[xX][0-9a-fA-F_]+\w*|\d[\d_]*(?:\.\d+)?\w*|[A-Za-z_]\w*(?:(?:\.|::)[A-Za-z_]\w*)*'
+                    r'|>>>=?|<<=|>>=|\*\*=?|<<|>>|[-+*/%^&|]=|==|!=|<=|>=|&&|\|\||:=|\S')
+_COMMENT = re.compile(r'/\*.*?\*/|//[^\n]*', re.DOTALL)
+_STRING = re.compile(r'"(?:[^"\\\n]|\\.)*"|\'(?:[^\'\\\n]|\\.){2,}\'')
+_LOOP_HEADER = re.compile(r'^\s*(?:for|while|loop|do)\b')
+_CONTROL = re.compile(r'^\s*(?:if|else\s+if|else|for|while|switch|match|do|loop|return|unsafe)\b')
+_DECLARATION_WORDS = {'let', 'mut', 'var', 'const', 'auto', 'final', 'static', 'register', 'unsigned', 'signed',
+                      'volatile', 'val', 'int', 'long', 'short', 'char', 'byte', 'uint', 'u32', 'u64', 'u8', 'u16',
+                      'i32', 'i64', 'usize', 'size_t', 'uint8_t', 'uint16_t', 'uint32_t', 'uint64_t', 'int32_t',
+                      'int64_t', 'word', 'return'}
+_ASM_OPS = {'add': 'add', 'adc': 'add', 'sub': 'add', 'sbb': 'add', 'inc': 'add', 'dec': 'add', 'xor': 'xor',
+            'eor': 'xor', 'pxor': 'xor', 'rol': 'rot', 'ror': 'rot', 'rorx': 'rot', 'shl': 'shl', 'sal': 'shl',
+            'lsl': 'shl', 'shr': 'shr', 'sar': 'shr', 'lsr': 'shr', 'asr': 'shr', 'and': 'and', 'bic': 'and',
+            'or': 'or', 'orr': 'or', 'not': 'not', 'neg': 'not', 'mvn': 'not', 'mul': 'mul', 'imul': 'mul',
+            'umul': 'mul'}
+_ASM_MOVES = {'mov', 'movzx', 'movsx', 'movzb', 'movzbl', 'movzwl', 'movsbl', 'movabs', 'movd', 'movq', 'ldr',
+              'ldrb', 'ld', 'lw', 'lbu', 'movl', 'str', 'strb', 'sw', 'sd', 'st'}
+_ASM_REGISTER = re.compile(r'^%?(?:[re]?([abcd])[xlh]|[re]?(si|di|bp|sp)l?|(r\d+)[dwb]?|([wx]\d+)|([a-z]\d+))$')
+_ASM_BRANCH = re.compile(r'^\s*(?:j[a-z]+|b(?:ne|eq|lt|gt|le|ge|cc|cs|hi|lo)?|loop\w*|bnez|beqz)\s+([\w.$@]+)',
+                         re.IGNORECASE)
+
+
+class DataflowGraph:
+    """연산 노드와 피연산자 간선 (줄 번호와 리터럴 상수 기록)"""
+
+    def __init__(self):
+        self.labels: List[str] = []
+        self.lines: List[int] = []
+        self.successors: List[Set[int]] = []
+        self.predecessors: List[Set[int]] = []
+        self.constants: Set[int] = set()
+        self._reach: Dict[Tuple[int, bool], List[Set[int]]] = {}
+
+    def add_node(self, label: str, line: int, inputs: List[int]) -> int:
+        node = len(self.labels)
+        self.labels.append(label)
+        self.lines.append(line)
+        self.successors.append(set())
+        self.predecessors.append(set())
+        for source in inputs:
+            self.successors[source].add(node)
+            self.predecessors[node].add(source)
+        return node
+
+    def reach(self, node: int, hops: int, backward: bool = False) -> List[Set[int]]:
+        """[k홉 만에 닿는 노드 집합] (k = 1..hops, 경로 길이 정확히 k)"""
+        cached = self._reach.get((node, backward), [])
+        if len(cached) < hops:
+            neighbors = self.predecessors if backward else self.successors
+            frontier = cached[-1] if cached else {node}
+            while len(cached) < hops:
+                frontier = {following for current in frontier for following in neighbors[current]}
+                cached.append(frontier)
+            self._reach[(node, backward)] = cached
+        return cached[:hops]
+
+    def connected(self, source: int, target: int, min_hops: int, max_hops: int) -> bool:
+        layers = self.reach(source, max_hops)
+        return any(target in layers[hops - 1] for hops in range(min_hops, max_hops + 1))
+
+    def __len__(self) -> int:
+        return len(self.labels)
+
+
+# ── 식 파싱 (중괄호 언어/Python) ──────────────────────────────────────────────────────────────
+
+
+class _Parser:
+    """토큰 → 식 트리 ('op', 연산, [자식]) / ('var', 키) / ('const', 값) / ('index', 키, 인덱스 식)"""
+
+    def __init__(self, tokens: List[str]):
+        self.tokens = tokens
+        self.position = 0
+
+    def peek(self, offset: int = 0) -> Optional[str]:
+        index = self.position + offset
+        return self.tokens[index] if index < len(self.tokens) else None
+
+    def take(self) -> str:
+        token = self.tokens[self.position]
+        self.position += 1
+        return token
+
+    def expression(self, min_precedence: int = 1) -> Tuple:
+        left = self.unary()
+        while self.peek() in _BINARY and _BINARY[self.peek()][1] >= min_precedence:
+            label, precedence = _BINARY[self.take()]
+            left = ('op', label, [left, self.expression(precedence + 1)])
+        return left
+
+    def expressions(self) -> Tuple:
+        """괄호 없는 튜플 (a, b = b, a)"""
+        items = [self.expression()]
+        while self.peek() == ',':
+            self.take()
+            items.append(self.expression())
+        return items[0] if len(items) == 1 else ('tuple', items)
+
+    def unary(self) -> Tuple:
+        token = self.peek()
+        if token in ('~', '!'):
+            self.take()
+            return ('op', 'not', [self.unary()])
+        if token in ('-', '+', '&', '*'):
+            self.take()
+            if self.peek() == 'mut':
+                self.take()
+            return self.unary()
+        return self.postfix(self.primary())
+
+    def primary(self) -> Tuple:
+        token = self.take()
+        if token == '(':
+            if re.fullmatch(r'[A-Za-z_][\w:]*', self.peek() or '') and self.peek(1) == ')' and \
+                    re.match(r'[\w(]', self.peek(2) or '') and self.peek(0) in _DECLARATION_WORDS:
+                self.position += 2  # C 형 변환 (uint32_t)x
+                return self.unary()
+            items = [self.expression()]
+            while self.peek() == ',':
+                self.take()
+                items.append(self.expression())
+            if self.peek() == ')':
+                self.take()
+            return items[0] if len(items) == 1 else ('tuple', items)
+        if token[0].isdigit():
+            try:
+                return ('const', parse_literal(token))
+            except (AttributeError, ValueError):
+                return ('const', 0)
+        if re.match(r'[A-Za-z_]', token):
+            if self.peek() == '(':
+                return self.call(token, None)
+            return ('var', token)
+        raise ValueError(f"unexpected token {token!r}")
+
+    def arguments(self) -> List[Tuple]:
+        self.take()  # (
+        arguments = []
+        while self.peek() not in (None, ')'):
+            arguments.append(self.expression())
+            if self.peek() == ',':
+                self.take()
+            elif self.peek() != ')':
+                raise ValueError('bad argument list')
+        if self.peek() == ')':
+            self.take()
+        return arguments
+
+    def call(self, name: str, receiver: Optional[Tuple]) -> Tuple:
+        arguments = self.arguments()
+        if receiver is None and re.search(r'(?:\.|::)', name):
+            head, method = re.split(r'\.|::', name, maxsplit=1)[0], re.split(r'\.|::', name)[-1]
+            receiver_name = name[:-len(method)].rstrip('.:')
+            if not arguments or _call_op(method):
+                if not re.match(r'^[A-Z]', head) and head not in ('std', 'core', 'bits', 'math', 'Math'):
+                    receiver = ('var', receiver_name)
+            name = method if receiver is not None else name
+        return ('call', name.split('.')[-1].split(':')[-1], ([receiver] if receiver else []) + arguments)
+
+    def postfix(self, node: Tuple) -> Tuple:
+        while True:
+            token = self.peek()
+            if token == '[':
+                self.take()
+                index = self.expression()
+                if self.peek() == ']':
+                    self.take()
+                node = ('index', node, index)
+            elif token == '.' and self.peek(1) and re.match(r'[A-Za-z_]', self.peek(1)):
+                self.take()
+                method = self.take()
+                if self.peek() == '(':
+                    node = self.call(method, node)
+            elif token == 'as' and self.peek(1):
+                self.position += 2
+            elif token == '?':
+                self.take()
+            else:
+                return node
+
+
+def _call_op(name: str) -> Optional[str]:
+    short = re.split(r'\.|::', name)[-1]
+    return next((label for pattern, label in _CALL_OPS if pattern.match(short)), None)
+
+
+def _key(node: Tuple) -> Optional[str]:
+    """대입 대상/변수 읽기의 키 ('state[d]', 'self.x')"""
+    if node[0] == 'var':
+        return node[1]
+    if node[0] == 'index':
+        base = _key(node[1])
+        index = _key(node[2]) if node[2][0] in ('var', 'index') else (
+            str(node[2][1]) if node[2][0] == 'const' else None)
+        return f"{base}[{index}]" if base and index is not None else None
+    return None
+
+
+def _simplify(node: Tuple) -> Tuple:
+    """결합 연산 평탄화, 회전 관용구 (x << n) | (x >> m) → rot, 알려진 메서드/함수 → 연산"""
+    kind = node[0]
+    if kind == 'op':
+        children = [_simplify(child) for child in node[2]]
+        label = node[1]
+        if label in ('or', 'xor', 'add') and len(children) == 2:
+            shifts = {child[1]: child[2][0] for child in children if child[0] == 'op' and child[1] in ('shl', 'shr')}
+            if len(shifts) == 2 and shifts['shl'] == shifts['shr']:
+                return ('op', 'rot', [shifts['shl']])
+        if label in _ASSOCIATIVE:
+            flat = []
+            for child in children:
+                flat.extend(child[2] if child[0] == 'op' and child[1] == label else [child])
+            children = flat
+        return ('op', label, children)
+    if kind == 'call':
+        arguments = [_simplify(argument) for argument in node[2]]
+        label = _call_op(node[1])
+        if label == 'rot':
+            return ('op', 'rot', arguments[:1])
+        if label:
+            return ('op', label, arguments)
+        return ('call', node[1], arguments)
+    if kind == 'index':
+        return ('index', _simplify(node[1]), _simplify(node[2]))
+    if kind == 'tuple':
+        return ('tuple', [_simplify(item) for item in node[1]])
+    return node
+
+
+class GraphBuilder:
+    """문장 목록 → DataflowGraph (변수는 마지막 정의 노드를 가리킴)"""
+
+    def __init__(self):
+        self.graph = DataflowGraph()
+        self.definitions: Dict[str, int] = {}
+
+    def emit(self, node: Tuple, line: int) -> Optional[int]:
+        kind = node[0]
+        if kind == 'const':
+            if isinstance(node[1], int):
+                self.graph.constants.add(node[1])
+            return None
+        if kind == 'var':
+            if node[1] not in self.definitions:
+                self.definitions[node[1]] = self.graph.add_node('var', line, [])
+            return self.definitions[node[1]]
+        if kind == 'index':
+            key = _key(node)
+            if key in self.definitions:
+                return self.definitions[key]
+            if node[2][0] == 'const':
+                return self.emit(('var', key or 'index'), line)
+            index = self.emit(node[2], line)
+            return self.graph.add_node('lookup', line, [index] if index is not None else [])
+        if kind == 'tuple':
+            inputs = [self.emit(item, line) for item in node[1]]
+            return next((item for item in inputs if item is not None), None)
+        inputs = [source for source in (self.emit(child, line) for child in node[2]) if source is not None]
+        if not inputs:
+            return None
+        return self.graph.add_node(node[1] if kind == 'op' else 'call', line, inputs)
+
+    def assign(self, targets: List[Tuple], values: List[Optional[int]]):
+        for target, value in zip(targets, values):
+            key = _key(target)
+            if key is None:
+                continue
+            if value is None:
+                self.definitions.pop(key, None)
+            else:
+                self.definitions[key] = value
+
+    def statement(self, tokens: List[str], line: int):
+        if len(self.graph) >= MAX_GRAPH_NODES or not tokens:
+            return
+        split = next((index for index, token in enumerate(tokens)
+                      if token in ('=', ':=') or (token.endswith('=') and token[:-1] in _BINARY)), None)
+        if split is None:
+            tree = _simplify(_Parser(tokens).expression())
+            if tree[0] == 'call' and tree[1].endswith('swap') and len(tree[2]) == 2:
+                first, second = (_key(argument) for argument in tree[2])
+                if first in self.definitions and second in self.definitions:
+                    self.definitions[first], self.definitions[second] = \
+                        self.definitions[second], self.definitions[first]
+                return
+            self.emit(tree, line)
+            return
+
+        targets = _targets(tokens[:split])
+        if not targets:
+            return
+        value = _simplify(_Parser(tokens[split + 1:]).expressions())
+        operator = tokens[split][:-1] if tokens[split] not in ('=', ':=') else None
+        if operator:
+            value = _simplify(('op', _BINARY[operator][0], [targets[0], value]))
+        if len(targets) > 1 and value[0] == 'tuple':
+            self.assign(targets, [self.emit(item, line) for item in value[1]])
+        else:
+            self.assign(targets[:1], [self.emit(value, line)])
+
+
+def _targets(tokens: List[str]) -> List[Tuple]:
+    """대입 좌변 → 대상 목록 (선언 키워드/형 표기 제거, 튜플 대입은 여러 개)"""
+    tokens = [token for token in tokens if token not in ('(', ')') or ',' not in tokens]
+    if ':' in tokens:
+        tokens = tokens[:tokens.index(':')]
+    groups, current = [], []
+    for token in tokens:
+        if token == ',':
+            groups.append(current)
+            current = []
+        else:
+            current.append(token)
+    groups.append(current)
+    targets = []
+    for group in groups:
+        while len(group) > 1 and (group[0] in _DECLARATION_WORDS or re.match(r'^[A-Za-z_][\w:<>]*$', group[0])
+                                  and re.match(r'^[A-Za-z_]', group[1]) and '[' not in group[:1]):
+            group = group[1:]
+        group = [token for token in group if token not in ('*', '&', 'mut')]
+        if not group or not re.match(r'^[A-Za-z_]', group[0]):
+            return []
+        try:
+            parser = _Parser(group)
+            target = parser.postfix(('var', parser.take()))
+        except (IndexError, ValueError):
+            return []
+        targets.append(target)
+    return targets
+
+
+def _tokens(text: str) -> List[str]:
+    return _TOKEN.findall(text)
+
+
+def brace_statements(text: str, line: int = 1) -> Iterator[Tuple[List[str], int]]:
+    """중괄호 언어 본문 → (토큰, 줄) 문장 (반복문 본문은 두 번)"""
+    text = _STRING.sub('""', _COMMENT.sub(lambda match: '\n' * match.group(0).count('\n'), text))
+    position, depth, start = 0, 0, 0
+    while position < len(text):
+        char = text[position]
+        if char in '([':
+            depth += 1
+        elif char in ')]':
+            depth = max(0, depth - 1)
+        elif char in ';}' and depth == 0:
+            yield from _piece(text[start:position], line + text.count('\n', 0, start))
+            start = position + 1
+        elif char == '{' and depth == 0:
+            header = text[start:position]
+            yield from _piece(header, line + text.count('\n', 0, start), header=True)
+            end = _matching_brace(text, position)
+            body_line = line + text.count('\n', 0, position)
+            repeats = 2 if _LOOP_HEADER.match(header) else 1
+            for _ in range(repeats):
+                yield from brace_statements(text[position + 1:end], body_line)
+            position = start = end + 1
+            continue
+        position += 1
+    yield from _piece(text[start:], line + text.count('\n', 0, start))
+
+
+def _matching_brace(text: str, opening: int) -> int:
+    depth = 0
+    for index in range(opening, len(text)):
+        if text[index] == '{':
+            depth += 1
+        elif text[index] == '}':
+            depth -= 1
+            if depth == 0:
+                return index
+    return len(text)
+
+
+def _piece(text: str, line: int, header: bool = False) -> Iterator[Tuple[List[str], int]]:
+    stripped = text.strip()
+    line += text[:len(text) - len(text.lstrip())].count('\n')
+    if not stripped:
+        return
+    if _CONTROL.match(stripped):
+        if header:
+            return
+        tokens = _tokens(stripped)
+        # if (c) x = y;  → 조건 괄호 뒤의 문장만
+        if len(tokens) > 1 and tokens[1] == '(':
+            depth = 0
+            for index, token in enumerate(tokens[1:], 1):
+                depth += token == '('
+                depth -= token == ')'
+                if depth == 0:
+                    tokens = tokens[index + 1:]
+                    break
+        elif tokens[0] == 'return':
+            tokens = tokens[1:]
+        else:
+            return
+        yield tokens, line
+        return
+    if header and not re.search(r'(?<![=!<>])=(?!=)', stripped):
+        return
+    yield _tokens(stripped), line
+
+
+def python_statements(text: str, line: int = 1) -> Iterator[Tuple[List[str], int]]:
+    """Python 본문 → (토큰, 줄) 문장 (for/while 본문은 두 번)"""
+    lines = _STRING.sub('""', text).splitlines()
+    index = 0
+    while index < len(lines):
+        source = lines[index].split('#', 1)[0]
+        stripped = source.strip()
+        if not stripped:
+            index += 1
+            continue
+        indent = len(source) - len(source.lstrip())
+        if stripped.endswith(':'):
+            if re.match(r'(?:for|while)\b', stripped):
+                end = index + 1
+                while end < len(lines) and (not lines[end].strip() or
+                                            len(lines[end]) - len(lines[end].lstrip()) > indent):
+                    end += 1
+                body = '\n'.join(lines[index + 1:end])
+                for _ in range(2):
+                    yield from python_statements(body, line + index + 1)
+                index = end
+                continue
+            index += 1
+            continue
+        tokens = _tokens(stripped)
+        if tokens and tokens[0] in ('return', 'yield'):
+            tokens = tokens[1:]
+        yield tokens, line + index
+        index += 1
+
+
+# ── 어셈블리 ────────────────────────────────────────────────────────────────────────────────
+
+
+def _asm_operand(operand: str) -> Tuple[str, List[str]]:
+    """피연산자 → (종류, 키) 종류: reg / imm / mem / table (레이블 + 인덱스 레지스터)"""
+    operand = operand.strip()
+    register = _register(operand)
+    if register:
+        return 'reg', [register]
+    if re.match(r'^[$#]?-?(?:0x[0-9a-fA-F]+|\d+)$', operand):
+        return 'imm', [operand.lstrip('$#')]
+    registers = [_register(part) for part in re.findall(r'%?\b[a-z]\w*\b', operand.lower())]
+    registers = [register for register in registers if register]
+    symbol = re.search(r'\b(?!(?:dword|qword|word|byte|ptr|offset)\b)[A-Za-z_.][\w.]*', operand.replace('%', ' %'))
+    symbol = symbol.group(0) if symbol and not _register(symbol.group(0)) else None
+    if symbol and registers:
+        return 'table', registers
+    return 'mem', [re.sub(r'\s+', '', operand.lower())]
+
+
+def _register(text: str) -> Optional[str]:
+    match = _ASM_REGISTER.match(text.strip().lower())
+    return next((group for group in match.groups() if group), None) if match else None
+
+
+def asm_statements(text: str, line: int = 1) -> Iterator[Tuple[str, List[str], int]]:
+    """어셈블리 본문 → (연산, [대상, 피연산자…], 줄) (뒤로 가는 분기가 있으면 본문을 두 번)"""
+    instructions = []
+    labels = {}
+    for offset, source in enumerate(text.splitlines()):
+        source = re.split(r'[;#@]|//', source, maxsplit=1)[0].strip()
+        label = re.match(r'^([\w.$@]+):\s*(.*)$', source)
+        if label:
+            labels[label.group(1)] = len(instructions)
+            source = label.group(2)
+        if not source or source.startswith('.'):
+            continue
+        parts = source.split(None, 1)
+        mnemonic = parts[0].lower()
+        operands = _split_operands(parts[1]) if len(parts) > 1 else []
+        if any(operand.strip().startswith(('%', '$')) or '(%' in operand for operand in operands):
+            operands = operands[::-1]  # AT&T: 원본, 대상
+            if mnemonic not in _ASM_OPS and mnemonic not in _ASM_MOVES and mnemonic[:-1] in \
+                    set(_ASM_OPS) | _ASM_MOVES:
+                mnemonic = mnemonic[:-1]
+        instructions.append((mnemonic, operands, line + offset, source))
+    backward = any(_ASM_BRANCH.match(source) and labels.get(_ASM_BRANCH.match(source).group(1), len(instructions)) <=
+                   index for index, (_, _, _, source) in enumerate(instructions))
+    for _ in range(2 if backward else 1):
+        for mnemonic, operands, number, _ in instructions:
+            yield mnemonic, operands, number
+
+
+def _split_operands(text: str) -> List[str]:
+    operands, depth, current = [], 0, ''
+    for char in text:
+        depth += char in '[('
+        depth -= char in '])'
+        if char == ',' and depth == 0:
+            operands.append(current)
+            current = ''
+        else:
+            current += char
+    return [operand for operand in operands + [current] if operand.strip()]
+
+
+def asm_graph(text: str, line: int = 1) -> DataflowGraph:
+    builder = GraphBuilder()
+
+    def read(operand: str, number: int) -> Optional[int]:
+        kind, keys = _asm_operand(operand)
+        if kind == 'imm':
+            try:
+                builder.graph.constants.add(int(keys[0], 0))
+            except ValueError:
+                pass
+            return None
+        if kind == 'table':
+            inputs = [builder.emit(('var', key), number) for key in keys]
+            return builder.graph.add_node('lookup', number, [node for node in inputs if node is not None])
+        return builder.emit(('var', keys[0]), number)
+
+    for mnemonic, operands, number in asm_statements(text, line):
+        if len(builder.graph) >= MAX_GRAPH_NODES or not operands:
+            continue
+        target_kind, target_keys = _asm_operand(operands[0])
+        if target_kind in ('imm', 'table'):
+            continue
+        target = target_keys[0]
+        if mnemonic in _ASM_MOVES and len(operands) >= 2:
+            value = read(operands[1], number)
+            builder.assign([('var', target)], [value])
+        elif mnemonic in _ASM_OPS:
+            sources = operands[1:] if len(operands) >= 3 else operands
+            inputs = [node for node in (read(operand, number) for operand in sources) if node is not None]
+            if inputs:
+                builder.assign([('var', target)], [builder.graph.add_node(_ASM_OPS[mnemonic], number, inputs)])
+    return builder.graph
+
+
+def function_graph(text: str, language: Optional[str], line: int = 1) -> DataflowGraph:
+    """함수 본문 → 데이터 흐름 그래프"""
+    if language == 'asm':
+        return asm_graph(text, line)
+    builder = GraphBuilder()
+    statements = python_statements(text, line) if language == 'python' else brace_statements(text, line)
+    for tokens, number in statements:
+        try:
+            builder.statement(tokens, number)
+        except (IndexError, ValueError, KeyError, RecursionError):
+            continue
+    return builder.graph
+
+
+# ── 부분 그래프 매칭 ────────────────────────────────────────────────────────────────────────
+
+
+def _accepts(pattern_label, label: str) -> bool:
+    if pattern_label == WILDCARD:
+        return True
+    return label in pattern_label if isinstance(pattern_label, tuple) else label == pattern_label
+
+
+def match_reference(graph: DataflowGraph, reference: Dict[str, Any],
+                    max_steps: int = MAX_SEARCH_STEPS) -> Optional[Dict[str, Any]]:
+    """참조 그래프를 허용 오차 안에서 맞춤 → {'score', 'missing', 'mapping'} (못 맞추면 None)"""
+    if reference.get('constants') and not graph.constants & set(reference['constants']):
+        return None
+    names = list(reference['nodes'])
+    # 라벨이 정해진 노드를 먼저, 와일드카드는 이미 대응한 이웃에서 후보를 고름
+    names.sort(key=lambda name: reference['nodes'][name] == WILDCARD)
+    edges = reference['edges']
+    tolerance = reference.get('tolerance', 0)
+    best: Dict[str, Any] = {}
+    steps = 0
+
+    def candidates(name: str, mapping: Dict[str, int]) -> Iterator[int]:
+        label = reference['nodes'][name]
+        pool = None
+        for source, target, _, max_hops in edges:
+            if source == name and target in mapping:
+                layers = graph.reach(mapping[target], max_hops, backward=True)
+            elif target == name and source in mapping:
+                layers = graph.reach(mapping[source], max_hops)
+            else:
+                continue
+            pool = set().union(*layers)
+            break
+        used = set(mapping.values())
+        for node in (sorted(pool) if pool is not None else range(len(graph))):
+            if node not in used and _accepts(label, graph.labels[node]):
+                yield node
+
+    def violations(name: str, node: int, mapping: Dict[str, int]) -> int:
+        count = 0
+        for source, target, min_hops, max_hops in edges:
+            if source == name and target in mapping:
+                count += not graph.connected(node, mapping[target], min_hops, max_hops)
+            elif target == name and source in mapping:
+                count += not graph.connected(mapping[source], node, min_hops, max_hops)
+        return count
+
+    def search(index: int, mapping: Dict[str, int], missing: int):
+        nonlocal steps
+        if steps >= max_steps or (best and best['missing'] == 0):
+            return
+        if index == len(names):
+            if not best or missing < best['missing']:
+                best.update(missing=missing, mapping=dict(mapping))
+            return
+        name = names[index]
+        for node in candidates(name, mapping):
+            steps += 1
+            if steps >= max_steps:
+                return
+            cost = missing + violations(name, node, mapping)
+            if cost > tolerance or (best and cost >= best['missing']):
+                continue
+            mapping[name] = node
+            search(index + 1, mapping, cost)
+            del mapping[name]
+
+    search(0, {}, 0)
+    if not best:
+        return None
+    return {'score': 1 - best['missing'] / len(edges), 'missing': best['missing'], 'mapping': best['mapping']}
+
+
+def match_function(graph: DataflowGraph,
+                   references: Optional[List[Dict[str, Any]]] = None) -> List[Dict[str, Any]]:
+    """함수 그래프 → 맞은 참조 [{'reference', 'label', 'score', 'line'}] (무리마다 최고 점수, generic은 밀려남)"""
+    matches = []
+    for reference in references or REFERENCE_GRAPHS:
+        found = match_reference(graph, reference)
+        if found:
+            matches.append({'reference': reference['name'], 'label': reference['label'], 'group': reference['group'],
+                            'generic': reference.get('generic', False), 'score': found['score'],
+                            'line': min(graph.lines[node] for node in found['mapping'].values())})
+    best_by_group: Dict[str, Dict[str, Any]] = {}
+    for match in matches:
+        current = best_by_group.get(match['group'])
+        if current is None or match['score'] > current['score']:
+            best_by_group[match['group']] = match
+    kept = list(best_by_group.values())
+    if any(not match['generic'] for match in kept):
+        kept = [match for match in kept if not match['generic']]
+    return sorted(kept, key=lambda match: (-match['score'], match['reference']))
+
+
+def sample_functions(text: str, language: Optional[str], suffix: Optional[str]) -> List[Dict[str, Any]]:
+    """[{'name', 'start_line', 'end_line', 'text'}] (확장자를 알면 SampleIndex, 아니면 features 분할)"""
+    lines = text.splitlines()
+    if suffix and language != 'asm':
+        functions = SampleIndex.from_text(text, suffix).functions()
+        if functions:
+            return [{'name': item['qualified_name'], 'start_line': item['start_line'], 'end_line': item['end_line'],
+                     'text': '\n'.join(lines[item['start_line'] - 1:item['end_line']])} for item in functions]
+    return split_functions(text, language)
+
+
+def analyze(text: str, language: Optional[str], suffix: Optional[str] = None) -> List[Dict[str, Any]]:
+    """샘플 → 함수별 매칭 [{'function', 'start_line', 'end_line', 'nodes', 'matches'}]"""
+    results = []
+    for function in sample_functions(text, language, suffix):
+        graph = function_graph(function['text'], language, function['start_line'])
+        results.append({'function': function['name'], 'start_line': function['start_line'],
+                        'end_line': function['end_line'], 'nodes': len(graph), 'matches': match_function(graph)})
+    return results
+
+
+class StructureDetector(BaseDetector):
+    """함수별 데이터 흐름 그래프와 참조 구조 그래프의 부분 그래프 매칭"""
+
+    name = 'structure'
+
+    def detect(self, agent_type: str, input_data: str, filename: Optional[str] = None) -> Dict[str, Any]:
+        suffix = Path(filename).suffix if filename else None
+        language = language_family(suffix) if suffix else None
+        language = language or guess_language(agent_type, input_data)
+        found: Dict[str, Dict[str, Any]] = {}
+        for function in analyze(input_data, language, suffix):
+            for match in function['matches']:
+                entry = found.setdefault(match['label'], {'score': 0.0, 'line': match['line'], 'evidence': []})
+                entry['score'] = max(entry['score'], match['score'])
+                entry['line'] = min(entry['line'], match['line'])
+                entry['evidence'].append(f"{match['reference']} in {function['function']} ({match['score']:.2f})")
+        labels = sorted(found)
+        return {
+            'labels': labels,
+            'confidence': max((found[label]['score'] for label in labels), default=0.0),
+            'evidence': [f"{label}: {'; '.join(found[label]['evidence'][:3])}" for label in labels],
+            'locations': [{'label': label, 'line': found[label]['line']} for label in labels],
+            'scores': {label: found[label]['score'] for label in labels},
+        }
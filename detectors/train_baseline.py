@@ -0,0 +1,81 @@
+"""
+로컬 분류기 기준선 학습 (train-baseline)
+
+train 분할(utils/splits.py) 샘플의 정적 특징 벡터로 에이전트별 one-vs-rest 로지스틱 회귀를 학습해
+detectors/classifier.py가 읽는 JSON 모델로 저장합니다. test 분할 샘플은 학습에 쓰지 않습니다.
+
+    - 라벨: train 분할에서 min_examples번 이상 나온 ground truth 라벨만 학습 (나머지는 예측하지 않음)
+    - 학습은 결정적입니다 (같은 코퍼스/설정이면 같은 모델)
+
+사용법:
+    python -m detectors.train_baseline
+    python -m detectors.train_baseline --agents source_code assembly_binary --epochs 1000 --output models/clf.json
+    python -m detectors.runner --detector classifier --model models/clf.json
+"""
+
+import argparse
+import json
+from collections import Counter
+from datetime import datetime
+from pathlib import Path
+
+from detectors.classifier import DEFAULT_MODEL_PATH, MODEL_FORMAT, VECTOR_NAMES, LogisticModel, sample_vector
+from detectors.runner import DEFAULT_AGENTS, default_test_manager, load_split_cases, references
+from utils.results_store import corpus_version, ground_truth_hash
+from utils.splits import SPLIT_TRAIN
+
+
+def main():
+    parser = argparse.ArgumentParser(description='로컬 분류기 기준선 학습 (train 분할)')
+    parser.add_argument('--agents', nargs='+', default=DEFAULT_AGENTS, help='학습할 에이전트')
+    parser.add_argument('--min-examples', type=int, default=2, help='학습할 라벨의 최소 train 샘플 수')
+    parser.add_argument('--epochs', type=int, default=500)
+    parser.add_argument('--learning-rate', type=float, default=0.1)
+    parser.add_argument('--l2', type=float, default=0.01, help='L2 정규화 계수')
+    parser.add_argument('--output', default=DEFAULT_MODEL_PATH, help='모델 JSON 경로')
+    args = parser.parse_args()
+
+    test_manager = default_test_manager()
+    agents, trained_on = {}, []
+    for agent_type in args.agents:
+        cases = load_split_cases(test_manager, agent_type)
+        train = references(cases)
+        counts = Counter(label for reference in train for label in reference['labels'])
+        labels = sorted(label for label, count in counts.items() if count >= args.min_examples)
+        if not labels:
+            print(f"⚠️  {agent_type}: train 샘플 {len(train)}개에 {args.min_examples}번 이상 나온 라벨이 없어 건너뜀")
+            continue
+
+        vectors = [sample_vector(agent_type, reference['input_data']) for reference in train]
+        agents[agent_type] = LogisticModel.fit(
+            vectors, [reference['labels'] for reference in train], labels,
+            epochs=args.epochs, learning_rate=args.learning_rate, l2=args.l2
+        ).to_dict()
+        trained_on.extend(
+            {'agent_type': agent_type, 'test_id': case['test_id'],
+             'ground_truth_hash': ground_truth_hash(case['ground_truth'])}
+            for case in cases if case['split'] == SPLIT_TRAIN
+        )
+        print(f"🧠 {agent_type}: train 샘플 {len(train)}개, 라벨 {len(labels)}개 ({', '.join(labels)})")
+
+    if not agents:
+        print("❌ 학습할 라벨이 없습니다")
+        raise SystemExit(1)
+
+    model = {
+        'format': MODEL_FORMAT,
+        'vector_names': VECTOR_NAMES,
+        'trained_at': datetime.now().isoformat(timespec='seconds'),
+        'train_corpus_version': corpus_version(trained_on),
+        'settings': {'min_examples': args.min_examples, 'epochs': args.epochs,
+                     'learning_rate': args.learning_rate, 'l2': args.l2},
+        'agents': agents
+    }
+    Path(args.output).parent.mkdir(parents=True, exist_ok=True)
+    with open(args.output, 'w', encoding='utf-8') as f:
+        json.dump(model, f, indent=2, ensure_ascii=False)
+    print(f"💾 모델 저장: {args.output}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,56 @@
+"""
+예시 탐지기 플러그인: 암호 API 이름 키워드 (keyword_detector)
+
+플러그인 계약(detectors/plugins.py)을 보여 주는 최소 예시입니다. 라이브러리 함수/클래스 이름이 min_hits번 이상 나온
+계열을 라벨로 보고합니다. 학습(prepare)은 하지 않습니다.
+
+사용법:
+    python -m detectors.plugins list --plugins-dir plugins/examples
+    python -m detectors.runner --plugins-dir plugins/examples --detector keyword_detector --agents source_code
+    python -m detectors.runner --plugins-dir plugins/examples --detector keyword_detector --plugin-option min_hits=2
+"""
+
+import re
+from typing import Dict, Any, List, Optional
+
+from detectors.base_detector import BaseDetector
+
+PLUGIN_API = 1
+__version__ = '1.0.0'
+
+# 계열 라벨 → API 이름 정규식
+KEYWORDS: Dict[str, str] = {
+    'RSA': r'\bRSA_(?:generate_key|public_encrypt|private_decrypt|sign)\w*|\bRSAPublicKey\b|'
+           r'\brsa\.generate_private_key\b',
+    'ECDSA': r'\bECDSA_(?:sign|verify|do_sign)\w*|\bec\.ECDSA\b|\bSHA256withECDSA\b',
+    'DH': r'\bDH_(?:generate_key|compute_key)\b|\bDiffieHellman\b',
+    'MD5': r'\bMD5_(?:Init|Update|Final)\b|\bhashlib\.md5\b|\bMessageDigest\.getInstance\(\s*"MD5"',
+    'SHA-1': r'\bSHA1_(?:Init|Update|Final)\b|\bhashlib\.sha1\b|\bMessageDigest\.getInstance\(\s*"SHA-?1"',
+    'DES': r'\bDES_(?:set_key|ecb_encrypt|cbc_encrypt)\w*|\bCipher\.getInstance\(\s*"DES/',
+}
+
+
+class KeywordDetector(BaseDetector):
+    name = 'keyword_detector'
+
+    def __init__(self, min_hits: int = 1):
+        self.min_hits = min_hits
+        self.patterns = {label: re.compile(pattern) for label, pattern in KEYWORDS.items()}
+
+    def detect(self, agent_type: str, input_data: str, filename: Optional[str] = None) -> Dict[str, Any]:
+        labels: List[str] = []
+        evidence, locations = [], []
+        for label, pattern in self.patterns.items():
+            matches = list(pattern.finditer(input_data))
+            if len(matches) < self.min_hits:
+                continue
+            labels.append(label)
+            evidence.append(f"{label}: {', '.join(sorted({match.group(0) for match in matches})[:3])}")
+            locations.append({'label': label, 'line': input_data.count('\n', 0, matches[0].start()) + 1})
+        return {'labels': labels, 'confidence': 0.6 if labels else 0.0, 'evidence': evidence, 'locations': locations}
+
+    def describe(self) -> Dict[str, Any]:
+        return {'name': self.name, 'min_hits': self.min_hits}
+
+
+DETECTOR = KeywordDetector
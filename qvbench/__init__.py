@@ -0,0 +1,240 @@
+"""
+qvbench — 양자 취약 암호 탐지 벤치마크 라이브러리 API
+
+스크립트(benchmark_runner.py, python -m utils.X)를 거치지 않고 코드에서 코퍼스, 탐지기, 채점을 쓰기 위한 진입점입니다.
+내부 모듈(utils/, detectors/, agents/, clients/)의 위치가 바뀌어도 이 모듈의 이름은 유지합니다.
+
+    Corpus      샘플과 ground truth (data/ 디렉토리, 부분집합, 분포 통계)
+    Detector    탐지기 기반 클래스와 생성 (Detector.create('retrieval'))
+    Scorer      탐지 라벨 → 정확도, 계층 점수, 양자 내성 오분류 (Scorer().rubric(): 채점 규칙 JSON)
+    Runner      탐지기를 train 분할로 준비하고 test 분할에서 채점
+    Report      실행 결과 ({summary, detailed_results, metadata}) 읽기/저장, 샘플별/탐지별 점수 DataFrame
+
+Jupyter:
+    Report는 노트북 셀에서 요약 표로 보이고(_repr_html_), Report.to_dataframe('samples' / 'findings')와
+    Corpus.to_dataframe()은 pandas DataFrame을 돌려줍니다 (qvbench[analysis] 필요, 열은 utils/results_table.py와 같음).
+    Rust 코어가 없는 순수 Python 패키지이므로 별도 바인딩(PyO3 등) 없이 그대로 import합니다.
+
+샘플 데이터와 LLM 실행은 선택 기능입니다 (pyproject.toml):
+    pip install -e .                    라이브러리 (로컬 탐지기, 채점, 분류 체계)
+    pip install -e ".[llm]"             LLM 프로바이더 클라이언트와 Runner.llm()
+    내부 모듈은 site-packages에 설치되지 않으므로 저장소 체크아웃에서 editable로 설치합니다.
+    코퍼스는 패키지에 들어가지 않으므로 저장소를 받아 Corpus(root)로 지정하거나 QVBENCH_DATA 환경 변수로 지정합니다.
+
+예:
+    from qvbench import Corpus, Detector, Runner
+
+    corpus = Corpus('data')
+    report = Runner(Detector.create('retrieval'), corpus).run(['source_code'])
+    print(report.hierarchical_f1['mean'])
+    report.to_dataframe().groupby('language')['f1'].mean()
+"""
+
+import html
+import json
+import os
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Union
+
+from detectors.base_detector import BaseDetector
+from detectors.detector_factory import DetectorFactory
+from detectors.runner import DEFAULT_AGENTS, LocalDetectorRunner, as_findings
+from utils.composite import WEIGHTS_PATH
+from utils.metrics_calculator import SUCCESS_THRESHOLD, MetricsCalculator
+from utils.subset import load_subset, subset_members
+from utils.taxonomy import AlgorithmTaxonomy
+from utils.test_case_manager import TestCaseManager
+
+__version__ = '0.1.0'
+
+DATA_ENV = 'QVBENCH_DATA'
+
+
+
+def _dataframe(records: List[Dict[str, Any]], columns: List[str]):
+    """레코드 → pandas DataFrame (열 순서 고정, pandas가 없으면 설치 안내)"""
+    try:
+        import pandas as pd
+    except ImportError as e:
+        raise ImportError(f"DataFrame output requires the analysis extra (pip install \"qvbench[analysis]\"): {e}")
+    return pd.DataFrame.from_records(records, columns=columns)
+
+
+__all__ = ['Corpus', 'Detector', 'Scorer', 'Runner', 'Report', 'AlgorithmTaxonomy', '__version__']
+
+
+class Corpus:
+    """벤치마크 코퍼스 (root 아래 test_files/, ground_truth/, test_cases/, subsets/)"""
+
+    def __init__(self, root: Union[str, Path, None] = None):
+        root = root or os.environ.get(DATA_ENV) or 'data'
+        self.root = Path(root)
+        if not (self.root / 'ground_truth').is_dir():
+            raise FileNotFoundError(f"corpus not found: {self.root} (set {DATA_ENV} or pass the data directory)")
+        self.manager = TestCaseManager(
+            test_cases_dir=str(self.root / 'test_cases'),
+            ground_truth_dir=str(self.root / 'ground_truth'),
+            test_files_dir=str(self.root / 'test_files')
+        )
+
+    def samples(self, agent_type: str = 'source_code', subset: Optional[str] = None) -> List[Dict[str, Any]]:
+        """에이전트의 샘플 [{'test_id', 'input_data', 'file_path', ...}] (subset을 주면 정의에 있는 샘플만)"""
+        cases = self.manager.load_test_cases(agent_type)
+        if subset:
+            members = set(subset_members(load_subset(subset, str(self.root / 'subsets')), agent_type))
+            cases = [case for case in cases if case['test_id'] in members]
+        return cases
+
+    def ground_truth(self, agent_type: str, test_id: str) -> Optional[Dict[str, Any]]:
+        return self.manager.load_ground_truth(agent_type, test_id)
+
+    def labels(self, agent_type: str, test_id: str) -> List[str]:
+        """샘플의 정답 라벨 (ground truth가 없으면 빈 목록)"""
+        ground_truth = self.ground_truth(agent_type, test_id)
+        return MetricsCalculator.get_expected_labels(ground_truth) if ground_truth else []
+
+    def to_dataframe(self, agents: Optional[List[str]] = None, subset: Optional[str] = None):
+        """샘플 목록 DataFrame (agent_type, test_id, file_path, language, split, task, labels; labels는 ';'로 연결)"""
+        from utils.languages import sample_language
+        from utils.splits import sample_split
+        from utils.tasks import sample_task
+
+        records = []
+        for agent_type in agents or DEFAULT_AGENTS:
+            for case in self.samples(agent_type, subset):
+                ground_truth = self.ground_truth(agent_type, case['test_id'])
+                records.append({
+                    'agent_type': agent_type, 'test_id': case['test_id'], 'file_path': case.get('file_path'),
+                    'language': sample_language(case.get('file_path')),
+                    'split': sample_split(agent_type, case['test_id'], ground_truth) if ground_truth else None,
+                    'task': sample_task(ground_truth) if ground_truth else None,
+                    'labels': ';'.join(MetricsCalculator.get_expected_labels(ground_truth)) if ground_truth else '',
+                })
+        return _dataframe(records, ['agent_type', 'test_id', 'file_path', 'language', 'split', 'task', 'labels'])
+
+    def stats(self, min_samples: int = 1) -> Dict[str, Any]:
+        """계열/언어/난이도 분포와 라벨 공백 (python -m utils.corpus stats와 같은 내용)"""
+        from utils.corpus import CorpusStatistics
+        return CorpusStatistics(str(self.root / 'ground_truth'), str(self.root / 'test_files'), min_samples).collect()
+
+
+class Detector(BaseDetector):
+    """로컬 탐지기 기반 클래스 (detect를 구현해 Runner에 넘김)"""
+
+    @staticmethod
+    def create(name: str, **config) -> BaseDetector:
+        """등록된 탐지기 생성 (retrieval, classifier, ensemble, ..., 플러그인은 plugin_dirs=[...]와 생성자 인자)"""
+        return DetectorFactory.create_detector(name, config)
+
+    @staticmethod
+    def available() -> List[str]:
+        return DetectorFactory.get_supported_detectors()
+
+
+class Scorer:
+    """탐지 라벨을 ground truth와 비교해 채점 (계층 점수 정책: strict / default / lenient)"""
+
+    def __init__(self, hierarchy_policy: Optional[str] = None):
+        self.hierarchy_policy = hierarchy_policy
+
+    def score(self, labels: List[str], ground_truth: Dict[str, Any], confidence: float = 1.0) -> Dict[str, Any]:
+        findings = as_findings({'labels': labels, 'confidence': confidence})
+        accuracy = MetricsCalculator.calculate_accuracy(findings, ground_truth)
+        return {
+            'accuracy': accuracy,
+            'success': accuracy >= SUCCESS_THRESHOLD,
+            'hierarchical_scores': MetricsCalculator.calculate_hierarchical_scores(
+                labels, ground_truth, self.hierarchy_policy
+            ),
+            'quantum_safe_check': MetricsCalculator.calculate_quantum_safe_misclassification(labels, ground_truth)
+        }
+
+    def rubric(self, weights_path: Optional[str] = None) -> Dict[str, Any]:
+        """채점 규칙 rubric (버전과 digest 포함, python -m utils.rubric export와 같은 내용)"""
+        from utils.rubric import build_rubric
+        return build_rubric(weights_path or WEIGHTS_PATH, self.hierarchy_policy)
+
+
+class Report:
+    """실행 결과 (benchmark_runner.py, detectors.runner와 같은 형식)"""
+
+    def __init__(self, data: Dict[str, Any]):
+        self.data = data
+
+    @property
+    def summary(self) -> Dict[str, Any]:
+        return self.data.get('summary', {})
+
+    @property
+    def results(self) -> List[Dict[str, Any]]:
+        return self.data.get('detailed_results', [])
+
+    @property
+    def metadata(self) -> Dict[str, Any]:
+        return self.data.get('metadata', {})
+
+    @property
+    def hierarchical_f1(self) -> Optional[Dict[str, Any]]:
+        return self.summary.get('hierarchical_f1')
+
+    @property
+    def crashed_samples(self) -> List[Dict[str, Any]]:
+        """탐지기나 채점에서 예외가 나 0점으로 센 샘플 [{'agent_type', 'test_id', 'exception', 'message', 'location'}]"""
+        return self.summary.get('crashed_samples', [])
+
+    def to_dataframe(self, table: str = 'samples'):
+        """결과 DataFrame: 'samples' (탐지기 × 샘플, 계층 F1과 트랙 점수) 또는 'findings' (탐지/약점마다 한 행)"""
+        from utils.results_table import TABLE_COLUMNS, build_tables
+        if table not in TABLE_COLUMNS:
+            raise ValueError(f"unknown table: {table} (one of {sorted(TABLE_COLUMNS)})")
+        return _dataframe(build_tables(self.results)[table], [name for name, _ in TABLE_COLUMNS[table]])
+
+    def _repr_html_(self) -> str:
+        """노트북 셀 출력: 탐지기별 샘플 수, 성공, 계층 F1 평균과 95% CI"""
+        from utils.run_compare import group_by_detector
+        rows = []
+        for detector, results in group_by_detector(self.results).items():
+            scores = [result['hierarchical_scores']['f1'] for result in results if result.get('hierarchical_scores')]
+            mean = f"{sum(scores) / len(scores):.3f}" if scores else '-'
+            rows.append([detector, str(len(results)), str(sum(1 for result in results if result.get('success'))), mean])
+        f1 = self.hierarchical_f1
+        caption = ''
+        if isinstance(f1, dict) and 'ci_low' in f1:
+            caption = f"hierarchical F1 {f1['mean']:.3f} [{f1['ci_low']:.3f}, {f1['ci_high']:.3f}]"
+        return ('<table><caption>' + html.escape(caption) + '</caption>'
+                '<tr><th>detector</th><th>samples</th><th>success</th><th>hierarchical F1</th></tr>'
+                + ''.join('<tr>' + ''.join(f"<td>{html.escape(cell)}</td>" for cell in row) + '</tr>' for row in rows)
+                + '</table>')
+
+    @classmethod
+    def load(cls, path: Union[str, Path]) -> 'Report':
+        with open(path, 'r', encoding='utf-8') as f:
+            return cls(json.load(f))
+
+    def save(self, path: Union[str, Path]) -> Path:
+        path = Path(path)
+        path.parent.mkdir(parents=True, exist_ok=True)
+        with open(path, 'w', encoding='utf-8') as f:
+            json.dump(self.data, f, indent=2, ensure_ascii=False)
+        return path
+
+
+class Runner:
+    """로컬 탐지기 평가: train 분할로 준비하고 test 분할 샘플을 채점"""
+
+    def __init__(self, detector: BaseDetector, corpus: Optional[Corpus] = None, name: Optional[str] = None,
+                 hierarchy_policy: Optional[str] = None):
+        self.corpus = corpus or Corpus()
+        self.runner = LocalDetectorRunner(detector, name or detector.name, hierarchy_policy, self.corpus.manager)
+
+    def run(self, agents: Optional[List[str]] = None, limit: Optional[int] = None) -> Report:
+        return Report(self.runner.run(list(agents or DEFAULT_AGENTS), limit))
+
+    @staticmethod
+    def llm(config_path: str = 'config/config.yaml', offline: bool = False):
+        """LLM 프로바이더 실행기 (benchmark_runner.BenchmarkRunner, qvbench[llm] 필요)"""
+        try:
+            from benchmark_runner import BenchmarkRunner
+        except ImportError as e:
+            raise ImportError(f"LLM runner requires the llm extra (pip install \"qvbench[llm]\"): {e}")
+        return BenchmarkRunner(config_path, offline=offline)
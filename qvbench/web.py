@@ -0,0 +1,75 @@
+"""
+브라우저 데모 API (Pyodide)
+
+채점과 학습이 필요 없는 기준선(signature, structure)은 순수 Python이라 Pyodide(CPython WebAssembly 빌드)에서 그대로
+돕니다. 이 모듈은 JavaScript에서 부르기 쉽게 인자와 반환값을 문자열/JSON 문자열로만 주고받는 얇은 층입니다.
+번들과 데모 페이지는 python -m utils.web_bundle이 만듭니다 (web/index.html).
+
+    detectors()                               데모에서 쓸 수 있는 탐지기 이름 JSON 배열
+    detect(source, filename, detector)        붙여 넣은 코드 → {'detector', 'labels', 'confidence', 'evidence',
+                                              'findings': [{'algorithm', 'line'}], 'nodes': {라벨: [범주, 계열, 변형]}}
+    score(predicted, expected)                라벨 JSON 배열 두 개 → Scorer.score와 같은 채점 (계층 정밀도/재현율/F1,
+                                              라벨별 매칭과 부분 점수, 양자 내성 오분류)
+
+JavaScript (web/index.html):
+    const api = pyodide.pyimport('qvbench.web');
+    const result = JSON.parse(api.detect(code, 'main.rs', 'signature'));
+    const scores = JSON.parse(api.score(JSON.stringify(result.labels), JSON.stringify(['RSA', 'AES'])));
+"""
+
+import json
+from typing import Dict, List, Optional
+
+from detectors.base_detector import BaseDetector
+from detectors.signature import SignatureDetector
+from detectors.structure import StructureDetector
+from utils.taxonomy import AlgorithmTaxonomy
+
+# 준비(train 참조)나 외부 도구 없이 도는 기준선
+DEMO_DETECTORS = {'signature': SignatureDetector, 'structure': StructureDetector}
+
+_detectors: Dict[str, BaseDetector] = {}
+_taxonomy: Optional[AlgorithmTaxonomy] = None
+
+
+def _detector(name: str) -> BaseDetector:
+    if name not in DEMO_DETECTORS:
+        raise ValueError(f"unknown demo detector: {name} (one of {sorted(DEMO_DETECTORS)})")
+    if name not in _detectors:
+        _detectors[name] = DEMO_DETECTORS[name]()
+    return _detectors[name]
+
+
+def _nodes(labels: List[str]) -> Dict[str, List[Optional[str]]]:
+    global _taxonomy
+    _taxonomy = _taxonomy or AlgorithmTaxonomy()
+    return {label: list(_taxonomy.resolve(label)) for label in labels}
+
+
+def detectors() -> str:
+    return json.dumps(sorted(DEMO_DETECTORS))
+
+
+def detect(source: str, filename: str = 'sample.txt', detector: str = 'signature') -> str:
+    """코드 → 탐지 결과 JSON (agent_type은 source_code)"""
+    detection = _detector(detector).detect('source_code', source, filename or None)
+    return json.dumps({
+        'detector': detector,
+        'labels': detection['labels'],
+        'confidence': detection['confidence'],
+        'evidence': detection.get('evidence', []),
+        'findings': [{'algorithm': location['label'], 'line': location['line']}
+                     for location in detection.get('locations', [])],
+        'nodes': _nodes(detection['labels']),
+    }, ensure_ascii=False)
+
+
+def score(predicted: str, expected: str, hierarchy_policy: Optional[str] = None) -> str:
+    """예측/정답 라벨 JSON 배열 → 채점 결과 JSON (정답은 expected_findings의 취약 알고리즘으로 간주)"""
+    from qvbench import Scorer
+
+    predicted_labels, expected_labels = json.loads(predicted), json.loads(expected)
+    ground_truth = {'expected_findings': {'vulnerable_algorithms_detected': expected_labels}}
+    result = Scorer(hierarchy_policy).score(predicted_labels, ground_truth)
+    result['nodes'] = _nodes(list(dict.fromkeys(predicted_labels + expected_labels)))
+    return json.dumps(result, ensure_ascii=False)
@@ -0,0 +1,425 @@
+#!/usr/bin/env python3
+"""
+알고리즘 분할 변환 (algorithm split)
+
+한 파일에 모인 구현은 S-box 표, 키 스케줄, 라운드 함수가 나란히 보여 탐지가 쉽습니다. 실제 코드베이스처럼
+이 조각들을 무해한 이름의 보조 크레이트/모듈로 흩어 놓은 다중 파일 샘플을 자동으로 만듭니다.
+
+    <이름>_split/
+        Cargo.toml                      루트 패키지 + 보조 크레이트 워크스페이스
+        src/main.rs | src/lib.rs        원본에서 옮긴 항목을 뺀 나머지 (테스트 모듈 포함)
+        src/<무해한 이름>.rs            키 스케줄 / 라운드 함수 (use super::*)
+        crates/<무해한 이름>/src/lib.rs  리터럴만으로 된 표 (S-box, 라운드 상수)
+
+    - 라벨 줄 범위 안의 최상위 항목만 옮김: 배열 상수/정적 변수(표)와 자유 함수 (main, 테스트 제외)
+    - 다른 식별자를 참조하지 않는 표는 보조 크레이트로, 나머지는 루트 크레이트 안의 보조 모듈로
+      (함수는 루트의 타입/상수를 쓰므로 크레이트 경계를 넘길 수 없음)
+    - 항목 위의 문서 주석/속성은 함께 옮김
+    - 라벨 줄 범위는 새 파일 배치 기준 "파일:시작-끝"으로 다시 씀 (범위 하나가 여러 파일/구간으로 나뉠 수 있음)
+
+ground truth 기록:
+    "expected_findings": {"locations": {"AES": ["src/main.rs:12-40", "crates/atlas_presets/src/lib.rs:1-34"]}},
+    "split": {"seed": 7, "root": "src/main.rs", "moved": [{"item": "SBOX", "kind": "table",
+              "file": "crates/atlas_presets/src/lib.rs", "labels": ["AES"]}]}
+
+샘플은 디렉토리 샘플(utils/manifest.py)로 읽히며, 내장 테스트는 `python -m utils.corpus verify`가
+cargo test로 실행합니다. 쓰기 전에 원본과 분할본의 내장 테스트 결과를 비교해(utils/obfuscation_verify.py)
+동작이 바뀐 분할은 거부합니다. 같은 시드와 샘플 이름이면 같은 배치가 나옵니다 (GeneratorSeed.stream('algorithm_split:<이름>')).
+
+사용법:
+    python -m utils.algorithm_split data/test_files/source_code/kiosk_pin_vault.rs --seed 7
+    python -m utils.algorithm_split data/test_files/source_code/*.rs --seed 7 --dry-run
+    python -m utils.sample_generator legacy --seed 3 --split --verify
+"""
+
+import argparse
+import copy
+import json
+import re
+import shutil
+import sys
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Tuple, Union
+
+from utils.comment_noise import comment_noise_claims, label_spans
+from utils.generator_seed import GeneratorSeed
+from utils.label_review import format_line_range, parse_line_range
+
+NAME_SUFFIX = '_split'
+TAG = 'multi_file'
+# utils.corpus와 같은 값 (corpus가 이 모듈을 import하므로 순환 import를 피해 따로 둠)
+TEST_FILES_DIR = "data/test_files"
+GROUND_TRUTH_DIR = "data/ground_truth"
+
+KIND_TABLE = 'table'
+KIND_SCHEDULE = 'key_schedule'
+KIND_ROUND = 'round_function'
+
+# 보조 크레이트/모듈 이름 = 어간 + 종류별 접미사 (암호를 떠올리게 하지 않는 단어)
+HELPER_STEMS = ['tally', 'ledger', 'palette', 'cadence', 'roster', 'atlas', 'sprocket', 'quill', 'lantern',
+                'harbor', 'meadow', 'orchard', 'compass', 'trellis', 'mosaic', 'beacon']
+HELPER_SUFFIXES = {
+    KIND_TABLE: ['presets', 'lookup', 'charts', 'grids'],
+    KIND_SCHEDULE: ['prep', 'setup', 'plan'],
+    KIND_ROUND: ['util', 'support', 'steps', 'kit'],
+}
+
+_ITEM = re.compile(r'^(pub(\([\w:\s]+\))?\s+)?((?P<fn>((const|unsafe)\s+)*fn)|(?P<data>const|static))\s+(?P<name>\w+)')
+_TABLE_TYPE = re.compile(r':\s*&?\s*(\'static\s+)?\[')
+_SCHEDULE_NAME = re.compile(r'key|schedule|expand|subkey|setup|init', re.IGNORECASE)
+_PREAMBLE = re.compile(r'^\s*(///|//|#\[)')
+_PRIMITIVE_TYPES = {'u8', 'u16', 'u32', 'u64', 'u128', 'usize', 'i8', 'i16', 'i32', 'i64', 'i128', 'isize',
+                    'f32', 'f64', 'bool', 'char', 'str', 'pub', 'const', 'static', 'true', 'false'}
+_LOCATION = re.compile(r'^\s*(?:(?P<file>[^:]+):)?(?P<range>\d+\s*(?:-\s*\d+)?)\s*$')
+
+
+def parse_file_location(text: str) -> Tuple[Optional[str], Optional[Tuple[int, int]]]:
+    """'src/main.rs:12-40' → ('src/main.rs', (12, 40)), '12-40' → (None, (12, 40))"""
+    match = _LOCATION.match(str(text))
+    if not match:
+        return None, None
+    return match.group('file'), parse_line_range(match.group('range'))
+
+
+def _strip_literals(text: str) -> str:
+    """주석, 문자열/문자 리터럴, 숫자 리터럴을 지운 코드 (식별자 참조 검사용)"""
+    text = re.sub(r'//[^\n]*|/\*.*?\*/', ' ', text, flags=re.DOTALL)
+    text = re.sub(r'b?"(\\.|[^"\\])*"', ' ', text)
+    text = re.sub(r"b?'(\\.|\\u\{[0-9a-fA-F]+\}|[^'\\])'", ' ', text)
+    return re.sub(r'\b\d[\w.]*', ' ', text)
+
+
+def item_end(lines: List[str], start: int) -> int:
+    """start 줄(0부터)에서 시작하는 항목의 마지막 줄 (함수는 본문 닫는 중괄호, 상수는 최상위 세미콜론)"""
+    data = bool(_ITEM.match(lines[start]).group('data'))
+    depth, opened = 0, False
+    in_block, in_string = 0, False
+    for index in range(start, len(lines)):
+        line = lines[index]
+        position = 0
+        while position < len(line):
+            char, pair = line[position], line[position:position + 2]
+            if in_block:
+                if pair == '*/':
+                    in_block -= 1
+                    position += 1
+                elif pair == '/*':
+                    in_block += 1
+                    position += 1
+            elif in_string:
+                if char == '\\':
+                    position += 1
+                elif char == '"':
+                    in_string = False
+            elif pair == '//':
+                break
+            elif pair == '/*':
+                in_block += 1
+                position += 1
+            elif char == '"':
+                in_string = True
+            elif char == "'":
+                literal = re.match(r"'(\\u\{[0-9a-fA-F]+\}|\\x[0-9a-fA-F]{2}|\\.|[^'\\])'", line[position:])
+                if literal:
+                    position += len(literal.group(0)) - 1
+            elif char in '([{':
+                depth += 1
+                opened = opened or char == '{'
+            elif char in ')]}':
+                depth -= 1
+                if depth == 0 and char == '}' and opened and not data:
+                    return index
+            elif char == ';' and depth == 0 and data:
+                return index
+            position += 1
+    raise ValueError(f"unterminated item starting at line {start + 1}")
+
+
+class AlgorithmSplitter:
+    """라벨 구현의 표/키 스케줄/라운드 함수를 보조 크레이트/모듈로 옮긴 다중 파일 샘플 생성"""
+
+    def __init__(self, seed: Union[GeneratorSeed, int]):
+        self.seed = GeneratorSeed.of(seed)
+
+    def split(self, source: str, ground_truth: Dict[str, Any], name: str) -> Dict[str, Any]:
+        """{'name', 'files': {상대 경로: 내용}, 'ground_truth', 'moved'} (moved가 비면 옮길 항목이 없는 것)"""
+        if comment_noise_claims(ground_truth):
+            raise ValueError("split the sample before injecting comment noise (claim lines would go stale)")
+        rng = self.seed.stream(f'algorithm_split:{name}')
+        lines = source.split('\n')
+        spans = label_spans(ground_truth)
+        items = self._items(lines, spans)
+        root = 'src/main.rs' if re.search(r'^fn\s+main\s*\(', source, re.MULTILINE) else 'src/lib.rs'
+
+        # 라벨마다 어간 하나, (라벨, 종류, 크레이트 여부)마다 보조 파일 하나
+        taken = set(re.findall(r'\w+', source.lower()))
+        stems = [stem for stem in HELPER_STEMS if stem not in taken]
+        rng.shuffle(stems)
+        label_stems: Dict[str, str] = {}
+        helpers: Dict[Tuple[str, str, bool], Dict[str, Any]] = {}
+        for item in items:
+            label, crate = item['labels'][0], item['pure']
+            key = (label, item['kind'], crate)
+            if key not in helpers:
+                stem = label_stems.setdefault(label, stems[len(label_stems) % len(stems)])
+                used = {helper['name'] for helper in helpers.values()}
+                candidates = [f"{stem}_{suffix}" for suffix in HELPER_SUFFIXES[item['kind']]]
+                rng.shuffle(candidates)
+                helper = next((candidate for candidate in candidates if candidate not in used),
+                              f"{candidates[0]}_{len(helpers)}")
+                helpers[key] = {'name': helper, 'crate': crate, 'items': [],
+                                'path': f"crates/{helper}/src/lib.rs" if crate else f"src/{helper}.rs"}
+            helpers[key]['items'].append(item)
+            item['file'] = helpers[key]['path']
+
+        if not items:
+            return {'name': name + NAME_SUFFIX, 'files': {}, 'ground_truth': ground_truth, 'moved': []}
+
+        # 보조 파일: 항목을 원래 순서대로, 항목 사이 빈 줄 하나
+        files: Dict[str, str] = {}
+        for helper in helpers.values():
+            body = [] if helper['crate'] else ['use super::*;', '']
+            for item in helper['items']:
+                item['new_start'] = len(body) + 1
+                body.extend(self._publish(lines[item['start']:item['end'] + 1], helper['crate']))
+                item['new_end'] = len(body)
+                body.append('')
+            files[helper['path']] = '\n'.join(body)
+
+        # 루트: 옮긴 항목(과 바로 뒤 빈 줄)을 빼고, 테스트 모듈 앞(없으면 끝)에 mod/use 선언
+        moved_lines = set()
+        for item in items:
+            moved_lines.update(range(item['start'], item['end'] + 1))
+            if item['end'] + 1 < len(lines) and not lines[item['end'] + 1].strip():
+                moved_lines.add(item['end'] + 1)
+        declarations = []
+        for helper in helpers.values():
+            if not helper['crate']:
+                declarations.append(f"mod {helper['name']};")
+            declarations.append(f"use {helper['name']}::*;")
+        test_module = next((index for index, line in enumerate(lines) if line.strip() == '#[cfg(test)]'), len(lines))
+
+        root_lines, new_number = [], {}
+        for index, line in enumerate(lines):
+            if index == test_module:
+                root_lines.extend(declarations + [''])
+            if index in moved_lines:
+                continue
+            root_lines.append(line)
+            new_number[index] = len(root_lines)
+        if test_module == len(lines):
+            root_lines.extend([''] + declarations)
+        files[root] = '\n'.join(root_lines)
+
+        package = re.sub(r'[^a-z0-9_]', '_', (name + NAME_SUFFIX).lower())
+        crates = [helper['name'] for helper in helpers.values() if helper['crate']]
+        files['Cargo.toml'] = self._manifest(package, crates)
+        for crate in crates:
+            files[f"crates/{crate}/Cargo.toml"] = self._manifest(crate, [], workspace=False)
+
+        updated = self._rewrite_ground_truth(ground_truth, lines, root_lines, root, new_number, items)
+        updated['split'] = {
+            'seed': self.seed.value, 'root': root,
+            'moved': [{'item': item['name'], 'kind': item['kind'], 'file': item['file'], 'labels': item['labels']}
+                      for item in items],
+        }
+        return {'name': name + NAME_SUFFIX, 'files': files, 'ground_truth': updated,
+                'moved': updated['split']['moved']}
+
+    @staticmethod
+    def _items(lines: List[str], spans: List[Tuple[str, Tuple[int, int]]]) -> List[Dict[str, Any]]:
+        """라벨 범위 안의 최상위 표/자유 함수 [{'name', 'kind', 'pure', 'line', 'start', 'end', 'labels'}]
+
+        줄 번호는 0부터, start는 함께 옮길 문서 주석/속성의 첫 줄 (테스트 모듈 앞까지만 찾음)
+        """
+        items = []
+        index = 0
+        while index < len(lines) and lines[index].strip() != '#[cfg(test)]':
+            match = _ITEM.match(lines[index])
+            if not match:
+                index += 1
+                continue
+            end = item_end(lines, index)
+            labels = [label for label, (start, stop) in spans if start <= index + 1 <= stop]
+            name, is_table = match.group('name'), bool(match.group('data'))
+            text = '\n'.join(lines[index:end + 1])
+            movable = labels and name != 'main' and not name.startswith('test') and \
+                (not is_table or (_TABLE_TYPE.search(text.split('=', 1)[0]) and 'static mut' not in text))
+            if movable:
+                first = index
+                while first > 0 and _PREAMBLE.match(lines[first - 1]):
+                    first -= 1
+                if any('#[test]' in line or 'cfg(test)' in line for line in lines[first:index]):
+                    index = end + 1
+                    continue
+                references = set(re.findall(r'[A-Za-z_]\w*', _strip_literals(text))) - _PRIMITIVE_TYPES - {name}
+                kind = KIND_TABLE if is_table else KIND_SCHEDULE if _SCHEDULE_NAME.search(name) else KIND_ROUND
+                items.append({'name': name, 'kind': kind, 'pure': is_table and not references,
+                              'line': index, 'start': first, 'end': end, 'labels': list(dict.fromkeys(labels))})
+            index = end + 1
+        return items
+
+    @staticmethod
+    def _publish(item_lines: List[str], crate: bool) -> List[str]:
+        """옮긴 항목의 가시성: 크레이트는 pub, 모듈은 pub(crate) (이미 pub이면 그대로)"""
+        published = list(item_lines)
+        for index, line in enumerate(published):
+            if _ITEM.match(line):
+                if not line.startswith('pub'):
+                    published[index] = ('pub ' if crate else 'pub(crate) ') + line
+                break
+        return published
+
+    @staticmethod
+    def _manifest(package: str, crates: List[str], workspace: bool = True) -> str:
+        lines = ['[package]', f'name = "{package}"', 'version = "0.1.0"', 'edition = "2021"', '',
+                 '[dependencies]']
+        lines += [f'{crate} = {{ path = "crates/{crate}" }}' for crate in crates]
+        if workspace:
+            lines += ['', '[workspace]', 'members = [' + ', '.join(f'"crates/{crate}"' for crate in crates) + ']']
+        return '\n'.join(lines) + '\n'
+
+    @staticmethod
+    def _rewrite_ground_truth(ground_truth: Dict[str, Any], lines: List[str], root_lines: List[str], root: str,
+                              new_number: Dict[int, int], items: List[Dict[str, Any]]) -> Dict[str, Any]:
+        updated = copy.deepcopy(ground_truth)
+
+        def relocate(algorithm: str, span: Tuple[int, int]) -> List[str]:
+            """원래 범위 → 새 배치의 '파일:시작-끝' 목록 (루트에 남은 줄의 구간 + 옮긴 항목)"""
+            runs: List[List[int]] = []
+            for index in range(span[0] - 1, min(span[1], len(lines))):
+                if index not in new_number or not lines[index].strip():
+                    continue
+                line = new_number[index]
+                # 사이에 빈 줄만 있으면 같은 구간
+                if runs and all(not text.strip() for text in root_lines[runs[-1][1]:line - 1]):
+                    runs[-1][1] = line
+                else:
+                    runs.append([line, line])
+            located = [f"{root}:{format_line_range((start, end))}" for start, end in runs]
+            located += [f"{item['file']}:{format_line_range((item['new_start'], item['new_end']))}"
+                        for item in items if algorithm in item['labels'] and span[0] <= item['line'] + 1 <= span[1]]
+            return located
+
+        findings = updated.get('expected_findings', {})
+        if isinstance(findings.get('locations'), dict):
+            findings['locations'] = {
+                algorithm: [location for line_range in ranges
+                            for location in (relocate(algorithm, parse_line_range(line_range))
+                                             if parse_line_range(line_range) else [line_range])]
+                for algorithm, ranges in findings['locations'].items()
+            }
+        analysis = updated.get('vulnerability_analysis')
+        if isinstance(analysis, dict):
+            for entry in analysis.get('quantum_vulnerable_algorithms', []):
+                relocated = []
+                for location in entry.get('locations', []):
+                    span = parse_line_range(location.get('line_range')) if isinstance(location, dict) else None
+                    if not span:
+                        relocated.append(location)
+                        continue
+                    relocated.extend({**location, 'line_range': line_range}
+                                     for line_range in relocate(entry.get('algorithm', ''), span))
+                if 'locations' in entry:
+                    entry['locations'] = relocated
+        if isinstance(updated.get('tags'), list) and TAG not in updated['tags']:
+            updated['tags'].append(TAG)
+        return updated
+
+
+def split_sample(sample: Dict[str, Any], seed: Union[GeneratorSeed, int]) -> Dict[str, Any]:
+    """생성 샘플({'name', 'source', 'ground_truth'})을 다중 파일 샘플로 분할"""
+    return AlgorithmSplitter(seed).split(sample['source'], sample['ground_truth'], sample['name'])
+
+
+def write_split_sample(split: Dict[str, Any], agent_type: str = 'source_code', test_files_dir: str = TEST_FILES_DIR,
+                       ground_truth_dir: str = GROUND_TRUTH_DIR, overwrite: bool = False) -> Optional[Path]:
+    sample_dir = Path(test_files_dir) / agent_type / split['name']
+    truth_path = Path(ground_truth_dir) / agent_type / f"{split['name']}.json"
+    if not overwrite and (sample_dir.exists() or truth_path.exists()):
+        return None
+
+    if sample_dir.exists():
+        shutil.rmtree(sample_dir)
+    for relative, content in split['files'].items():
+        path = sample_dir / relative
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(content, encoding='utf-8')
+    truth_path.parent.mkdir(parents=True, exist_ok=True)
+    truth_path.write_text(json.dumps(split['ground_truth'], indent=2, ensure_ascii=False) + '\n', encoding='utf-8')
+    return sample_dir
+
+
+def describe(split: Dict[str, Any]) -> str:
+    files = sorted({moved['file'] for moved in split['moved']})
+    return f"항목 {len(split['moved'])}개 → {', '.join(files)}"
+
+
+def main():
+    # utils.corpus가 이 모듈(parse_file_location)을 import하므로 (검증기는 corpus를 씀) 실행 시점에 가져옴
+    from utils.obfuscation_verify import STATUS_BROKEN, SemanticsVerifier, describe_check, record_check
+
+    parser = argparse.ArgumentParser(description='알고리즘 분할 변환 (다중 파일 샘플 생성)')
+    parser.add_argument('files', nargs='+', help='Rust 샘플 파일 (ground truth는 --ground-truth-dir/<에이전트>/<이름>.json)')
+    parser.add_argument('--seed', type=GeneratorSeed.parse, default=GeneratorSeed(0),
+                        help='배치 시드 (10진수 또는 0x 16진수, 기본값 0)')
+    parser.add_argument('--ground-truth-dir', default=GROUND_TRUTH_DIR, help='ground truth 디렉토리')
+    parser.add_argument('--overwrite', action='store_true', help='같은 이름의 샘플이 있으면 덮어쓰기')
+    parser.add_argument('--dry-run', action='store_true', help='파일을 쓰지 않고 분할 결과만 출력')
+    parser.add_argument('--skip-semantics-check', action='store_true',
+                        help='쓰기 전 원본/분할본 내장 테스트 비교 생략 (utils/obfuscation_verify.py)')
+    args = parser.parse_args()
+
+    checker = None if args.skip_semantics_check else SemanticsVerifier()
+    failures = 0
+    for file in args.files:
+        path = Path(file)
+        truth_path = Path(args.ground_truth_dir) / path.parent.name / f"{path.stem}.json"
+        if path.suffix != '.rs':
+            print(f"⏭️  {path}: Rust 샘플만 분할할 수 있습니다")
+            continue
+        if not truth_path.exists():
+            print(f"❌ {path}: ground truth 없음 ({truth_path})")
+            failures += 1
+            continue
+
+        with open(truth_path, 'r', encoding='utf-8') as f:
+            ground_truth = json.load(f)
+        if comment_noise_claims(ground_truth):
+            print(f"⏭️  {path}: 주석 노이즈 변형은 분할하지 않음 (원본을 분할한 뒤 주입)")
+            continue
+        source = path.read_text(encoding='utf-8')
+        try:
+            split = AlgorithmSplitter(args.seed).split(source, ground_truth, path.stem)
+        except ValueError as e:
+            print(f"❌ {path}: {e}")
+            failures += 1
+            continue
+        if not split['moved']:
+            print(f"⚠️  {path}: 라벨 범위 안에 옮길 최상위 표/함수가 없음")
+            continue
+
+        if args.dry_run:
+            print(f"🧪 {split['name']}/: {describe(split)}")
+            continue
+        sample_dir = path.with_name(split['name'])
+        if not args.overwrite and (sample_dir.exists() or truth_path.with_name(f"{split['name']}.json").exists()):
+            print(f"⚠️  {split['name']} 이미 존재 (--overwrite로 덮어쓰기)")
+            continue
+        if checker:
+            check = checker.check('algorithm_split', source, split['files'], path.suffix, path.stem)
+            print(f"   {describe_check(check)}")
+            if check['status'] == STATUS_BROKEN:
+                print(f"❌ {sample_dir}/: 분할이 동작을 바꿔 쓰지 않음")
+                failures += 1
+                continue
+            record_check(split['ground_truth'], check)
+
+        write_split_sample(split, path.parent.name, str(path.parent.parent), args.ground_truth_dir, overwrite=True)
+        print(f"✅ {sample_dir}/: {describe(split)}")
+
+    sys.exit(1 if failures else 0)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,353 @@
+"""
+컴파일 산출물 트랙 (선택)
+
+소스 샘플(data/test_files/source_code)을 오브젝트 파일/WASM 모듈로 빌드해, 소스 없이 바이너리만
+보는 SCA 도구를 같은 ground truth로 평가합니다. 산출물은 저장소에 커밋하지 않고 필요할 때
+빌드하며(data/artifacts/, .gitignore), index.json에 원본 샘플과 빌드 환경을 기록합니다.
+
+    build   소스 샘플을 타깃별로 빌드하고 index.json 갱신
+    list    빌드된 산출물 목록
+    run     바이너리 탐지기를 산출물마다 실행하고 원본 샘플의 ground truth로 채점
+
+타깃:
+    x86_64-obj   Rust: rustc --emit=obj / C: cc -c  (ELF 재배치 가능 오브젝트)
+    wasm32       Rust: rustc --target wasm32-unknown-unknown  (타깃 표준 라이브러리가 설치된 경우만)
+
+외부 크레이트를 쓰는 Rust 샘플, 헤더가 없어 컴파일되지 않는 C 샘플은 건너뛰고 사유를 출력합니다.
+
+산출물의 ground truth는 원본 소스 샘플과 공유합니다 (index.json의 ground_truth_ref:
+`source_code/<test_id>` → data/ground_truth/source_code/<test_id>.json). 빌드 이후 샘플이
+바뀌면 source_sha256이 달라지므로 run은 해당 산출물을 stale로 표시하고 건너뜁니다.
+
+탐지기 규약:
+    --detector 명령의 {artifact}를 산출물 경로로 치환해 실행하고, 표준 출력의 JSON에서
+    "labels" 배열(없으면 "algorithms")을 예측 라벨로 읽습니다. 라벨은 계층 라벨
+    (utils/taxonomy.py) 어느 단계든 괜찮습니다.
+        {"labels": ["AES-128", "RSA"]}
+
+사용법:
+    python -m utils.artifacts build
+    python -m utils.artifacts build --target wasm32 --file data/test_files/source_code/medical_device_encryption.rs
+    python -m utils.artifacts list
+    python -m utils.artifacts run --detector "binsca scan --json {artifact}" --output results/artifacts.json
+"""
+
+import argparse
+import hashlib
+import json
+import shlex
+import shutil
+import subprocess
+import sys
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from utils.corpus import CorpusVerifier
+from utils.metrics_calculator import MetricsCalculator
+
+SOURCE_DIR = "data/test_files/source_code"
+GROUND_TRUTH_DIR = "data/ground_truth"
+ARTIFACTS_DIR = "data/artifacts"
+INDEX_FILE = "index.json"
+
+# 타깃 → 언어별 빌드 명령 ({src}, {out} 치환)
+TARGETS: Dict[str, Dict[str, Any]] = {
+    'x86_64-obj': {
+        'suffix': '.o',
+        'rs': ['rustc', '--edition', '2021', '--crate-type', 'bin', '--emit=obj', '-C', 'opt-level=1',
+               '-o', '{out}', '{src}'],
+        'c': ['cc', '-c', '-O1', '-o', '{out}', '{src}'],
+    },
+    'wasm32': {
+        'suffix': '.wasm',
+        'rust_target': 'wasm32-unknown-unknown',
+        'rs': ['rustc', '--edition', '2021', '--crate-type', 'bin', '--target', 'wasm32-unknown-unknown',
+               '-C', 'opt-level=1', '-o', '{out}', '{src}'],
+    },
+}
+
+
+def file_sha256(path: Path) -> str:
+    return hashlib.sha256(path.read_bytes()).hexdigest()
+
+
+class ArtifactStore:
+    """data/artifacts/ 산출물과 index.json"""
+
+    def __init__(self, root: str = ARTIFACTS_DIR):
+        self.root = Path(root)
+        self.index_path = self.root / INDEX_FILE
+
+    def load(self) -> List[Dict[str, Any]]:
+        if not self.index_path.exists():
+            return []
+        with open(self.index_path, 'r', encoding='utf-8') as f:
+            return json.load(f).get('artifacts', [])
+
+    def save(self, entries: List[Dict[str, Any]]):
+        self.root.mkdir(parents=True, exist_ok=True)
+        entries = sorted(entries, key=lambda e: (e['target'], e['test_id']))
+        with open(self.index_path, 'w', encoding='utf-8') as f:
+            json.dump({'artifacts': entries}, f, indent=2, ensure_ascii=False)
+            f.write('\n')
+
+    def upsert(self, new_entries: List[Dict[str, Any]]):
+        """같은 (target, test_id) 항목은 새 빌드로 교체"""
+        keys = {(e['target'], e['test_id']) for e in new_entries}
+        kept = [e for e in self.load() if (e['target'], e['test_id']) not in keys]
+        self.save(kept + new_entries)
+
+    def artifact_path(self, target: str, test_id: str) -> Path:
+        return self.root / target / f"{test_id}{TARGETS[target]['suffix']}"
+
+
+class ArtifactBuilder:
+    """소스 샘플 → 컴파일 산출물"""
+
+    def __init__(self, store: ArtifactStore, timeout: int = 300):
+        self.store = store
+        self.timeout = timeout
+
+    @staticmethod
+    def find_sources(root: str = SOURCE_DIR) -> List[Path]:
+        """빌드 명령이 있는 언어의 소스 샘플"""
+        suffixes = {f".{lang}" for spec in TARGETS.values() for lang in spec if lang in ('rs', 'c')}
+        return sorted(path for path in Path(root).iterdir() if path.suffix in suffixes)
+
+    @staticmethod
+    def target_available(target: str) -> Optional[str]:
+        """타깃을 빌드할 수 없으면 사유, 가능하면 None"""
+        rust_target = TARGETS[target].get('rust_target')
+        if not rust_target:
+            return None
+        if not shutil.which('rustc'):
+            return 'rustc not found'
+        libdir = subprocess.run(['rustc', '--print', 'target-libdir', '--target', rust_target],
+                                capture_output=True, text=True)
+        path = Path(libdir.stdout.strip())
+        if libdir.returncode != 0 or not any(path.glob('libstd-*')):
+            return f"{rust_target} standard library not installed (rustup target add {rust_target})"
+        return None
+
+    @staticmethod
+    def compiler_version(tool: str) -> str:
+        result = subprocess.run([tool, '--version'], capture_output=True, text=True)
+        return result.stdout.splitlines()[0] if result.returncode == 0 and result.stdout else tool
+
+    def build(self, source: Path, target: str) -> Dict[str, Any]:
+        """샘플 하나를 빌드하고 결과 {'status': built|skipped|failed, ...}"""
+        lang = source.suffix.lstrip('.')
+        command = TARGETS[target].get(lang)
+        result = {'test_id': source.stem, 'source': source.as_posix(), 'target': target}
+
+        if not command:
+            return {**result, 'status': 'skipped', 'reason': f"no {lang} toolchain for {target}"}
+        if not shutil.which(command[0]):
+            return {**result, 'status': 'skipped', 'reason': f"{command[0]} not found"}
+        if lang == 'rs':
+            crates = CorpusVerifier.external_crates(source.read_text(encoding='utf-8', errors='ignore'))
+            if crates:
+                return {**result, 'status': 'skipped', 'reason': f"external crates: {', '.join(crates)}"}
+
+        out = self.store.artifact_path(target, source.stem)
+        out.parent.mkdir(parents=True, exist_ok=True)
+        argv = [arg.replace('{src}', str(source)).replace('{out}', str(out)) for arg in command]
+        try:
+            run = subprocess.run(argv, capture_output=True, text=True, timeout=self.timeout)
+        except subprocess.TimeoutExpired:
+            return {**result, 'status': 'failed', 'reason': f"timed out after {self.timeout}s"}
+        if run.returncode != 0 or not out.exists():
+            first_error = next((line for line in run.stderr.splitlines() if 'error' in line), 'build failed')
+            return {**result, 'status': 'failed', 'reason': first_error.strip()}
+
+        return {
+            **result,
+            'status': 'built',
+            'artifact': out.as_posix(),
+            'size': out.stat().st_size,
+            'source_sha256': file_sha256(source),
+            'ground_truth_ref': f"source_code/{source.stem}",
+            'compiler': self.compiler_version(command[0]),
+            'built_at': datetime.now().isoformat(timespec='seconds'),
+        }
+
+
+class BinaryDetectorRunner:
+    """외부 바이너리 탐지기 실행 및 채점"""
+
+    def __init__(self, command: str, ground_truth_dir: str = GROUND_TRUTH_DIR,
+                 timeout: int = 300, hierarchy_policy: Optional[str] = None):
+        self.command = command
+        self.ground_truth_dir = Path(ground_truth_dir)
+        self.timeout = timeout
+        self.hierarchy_policy = hierarchy_policy
+
+    def load_ground_truth(self, entry: Dict[str, Any]) -> Optional[Dict[str, Any]]:
+        path = self.ground_truth_dir / f"{entry['ground_truth_ref']}.json"
+        if not path.exists():
+            return None
+        with open(path, 'r', encoding='utf-8') as f:
+            return json.load(f)
+
+    def detect(self, artifact: str) -> List[str]:
+        """탐지기 실행 → 예측 라벨 (실행/파싱 실패는 예외)"""
+        argv = [arg.replace('{artifact}', artifact) for arg in shlex.split(self.command)]
+        run = subprocess.run(argv, capture_output=True, text=True, timeout=self.timeout)
+        if run.returncode != 0:
+            raise RuntimeError(f"detector exited with {run.returncode}: {run.stderr.strip()[-200:]}")
+        output = json.loads(run.stdout)
+        labels = output.get('labels', output.get('algorithms', [])) if isinstance(output, dict) else output
+        return [label for label in labels if isinstance(label, str)]
+
+    def evaluate(self, entry: Dict[str, Any]) -> Dict[str, Any]:
+        result = {'test_id': entry['test_id'], 'target': entry['target'], 'artifact': entry['artifact']}
+
+        source = Path(entry['source'])
+        if not source.exists() or file_sha256(source) != entry.get('source_sha256'):
+            return {**result, 'status': 'stale', 'reason': 'source changed since build'}
+        ground_truth = self.load_ground_truth(entry)
+        if ground_truth is None:
+            return {**result, 'status': 'skipped', 'reason': f"no ground truth for {entry['ground_truth_ref']}"}
+
+        try:
+            labels = self.detect(entry['artifact'])
+        except (RuntimeError, subprocess.TimeoutExpired, json.JSONDecodeError, AttributeError) as error:
+            return {**result, 'status': 'error', 'reason': str(error)}
+
+        scores = MetricsCalculator.calculate_hierarchical_scores(labels, ground_truth, self.hierarchy_policy)
+        quantum_safe = MetricsCalculator.calculate_quantum_safe_misclassification(labels, ground_truth)
+        return {**result, 'status': 'scored', 'predicted': labels,
+                'hierarchical_scores': scores, 'quantum_safe_check': quantum_safe}
+
+    @staticmethod
+    def summarize(results: List[Dict[str, Any]]) -> Dict[str, Any]:
+        summary: Dict[str, Any] = {}
+        for target in sorted({r['target'] for r in results}):
+            scored = [r for r in results if r['target'] == target and r['status'] == 'scored']
+            count = len(scored)
+            summary[target] = {
+                'artifacts': sum(1 for r in results if r['target'] == target),
+                'scored': count,
+                'precision': sum(r['hierarchical_scores']['precision'] for r in scored) / count if count else 0.0,
+                'recall': sum(r['hierarchical_scores']['recall'] for r in scored) / count if count else 0.0,
+                'f1': sum(r['hierarchical_scores']['f1'] for r in scored) / count if count else 0.0,
+            }
+        return summary
+
+
+def run_build(args) -> int:
+    store = ArtifactStore(args.artifacts_dir)
+    builder = ArtifactBuilder(store, timeout=args.timeout)
+    sources = [Path(f) for f in args.file] if args.file else builder.find_sources(args.root)
+
+    unavailable = builder.target_available(args.target)
+    if unavailable:
+        print(f"⏭️  {args.target} 타깃을 빌드할 수 없습니다: {unavailable}")
+        return 0
+
+    print(f"🔨 산출물 빌드: {len(sources)}개 샘플 → {args.target}")
+    icons = {'built': '✅', 'skipped': '⏭️ ', 'failed': '❌'}
+    results = []
+    for source in sources:
+        result = builder.build(source, args.target)
+        results.append(result)
+        detail = f"{result['size']} bytes" if result['status'] == 'built' else result['reason']
+        print(f"  {icons[result['status']]} {source} {detail}")
+
+    built = [r for r in results if r['status'] == 'built']
+    store.upsert([{k: v for k, v in r.items() if k != 'status'} for r in built])
+
+    counts = {status: sum(1 for r in results if r['status'] == status) for status in icons}
+    print(f"\n📊 빌드 {counts['built']} / 건너뜀 {counts['skipped']} / 실패 {counts['failed']}")
+    print(f"💾 인덱스: {store.index_path}")
+    return 0
+
+
+def run_list(args) -> int:
+    entries = ArtifactStore(args.artifacts_dir).load()
+    if args.target:
+        entries = [e for e in entries if e['target'] == args.target]
+    if not entries:
+        print("⚠️  빌드된 산출물이 없습니다 (python -m utils.artifacts build)")
+        return 0
+
+    print(f"📦 산출물 {len(entries)}개")
+    for entry in entries:
+        stale = '' if Path(entry['source']).exists() and file_sha256(Path(entry['source'])) == entry['source_sha256'] \
+            else ' (stale)'
+        print(f"  [{entry['target']}] {entry['artifact']} ← {entry['source']}{stale}")
+    return 0
+
+
+def run_detector(args) -> int:
+    entries = ArtifactStore(args.artifacts_dir).load()
+    if args.target:
+        entries = [e for e in entries if e['target'] == args.target]
+    if not entries:
+        print("⚠️  빌드된 산출물이 없습니다 (python -m utils.artifacts build)")
+        return 0
+
+    runner = BinaryDetectorRunner(args.detector, args.ground_truth_dir, args.timeout, args.hierarchy_policy)
+    print(f"🔍 바이너리 탐지기 평가: {len(entries)}개 산출물")
+    icons = {'scored': '✅', 'stale': '⚠️ ', 'skipped': '⏭️ ', 'error': '❌'}
+    results = []
+    for entry in entries:
+        result = runner.evaluate(entry)
+        results.append(result)
+        if result['status'] == 'scored':
+            detail = f"F1 {result['hierarchical_scores']['f1']:.3f} ({', '.join(result['predicted']) or '-'})"
+        else:
+            detail = result['reason']
+        print(f"  {icons[result['status']]} [{result['target']}] {result['test_id']} {detail}")
+
+    summary = runner.summarize(results)
+    print("\n📊 타깃별 평균")
+    for target, stats in summary.items():
+        print(f"  {target}: P {stats['precision']:.3f} / R {stats['recall']:.3f} / F1 {stats['f1']:.3f} "
+              f"({stats['scored']}/{stats['artifacts']} 채점)")
+
+    if args.output:
+        Path(args.output).parent.mkdir(parents=True, exist_ok=True)
+        with open(args.output, 'w', encoding='utf-8') as f:
+            json.dump({'detector': args.detector, 'summary': summary, 'results': results},
+                      f, indent=2, ensure_ascii=False)
+        print(f"💾 결과 저장: {args.output}")
+
+    return 1 if any(r['status'] == 'error' for r in results) else 0
+
+
+def main():
+    parser = argparse.ArgumentParser(description='컴파일 산출물 트랙 (바이너리 탐지기 평가)')
+    parser.add_argument('--artifacts-dir', default=ARTIFACTS_DIR, help='산출물 디렉토리')
+    subparsers = parser.add_subparsers(dest='command', required=True)
+
+    build_parser = subparsers.add_parser('build', help='소스 샘플을 산출물로 빌드')
+    build_parser.add_argument('--target', choices=list(TARGETS), default='x86_64-obj', help='빌드 타깃')
+    build_parser.add_argument('--root', default=SOURCE_DIR, help='소스 샘플 디렉토리')
+    build_parser.add_argument('--file', nargs='+', help='특정 샘플만 빌드')
+    build_parser.add_argument('--timeout', type=int, default=300, help='샘플당 빌드 제한 시간(초)')
+
+    list_parser = subparsers.add_parser('list', help='빌드된 산출물 목록')
+    list_parser.add_argument('--target', choices=list(TARGETS), help='특정 타깃만')
+
+    run_parser = subparsers.add_parser('run', help='바이너리 탐지기 실행 및 채점')
+    run_parser.add_argument('--detector', required=True, help='탐지기 명령 ({artifact}가 산출물 경로로 치환됨)')
+    run_parser.add_argument('--target', choices=list(TARGETS), help='특정 타깃만')
+    run_parser.add_argument('--ground-truth-dir', default=GROUND_TRUTH_DIR, help='ground truth 디렉토리')
+    run_parser.add_argument('--hierarchy-policy', help='계층 점수 정책 (utils/taxonomy.py)')
+    run_parser.add_argument('--timeout', type=int, default=300, help='산출물당 탐지기 제한 시간(초)')
+    run_parser.add_argument('--output', help='결과 JSON 저장 경로')
+
+    args = parser.parse_args()
+
+    if args.command == 'build':
+        sys.exit(run_build(args))
+    if args.command == 'list':
+        sys.exit(run_list(args))
+    if args.command == 'run':
+        sys.exit(run_detector(args))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,162 @@
+"""
+라벨 속성 (키 길이, 블록 모드, 곡선, 라운드 수)
+
+"AES를 찾았지만 CTR을 CBC로 답한" 경우와 완전히 놓친 경우를 구분할 수 있도록, 알고리즘 식별과 별개로 라벨의
+구조화된 속성을 채점합니다. 속성은 알고리즘 계열을 맞힌 라벨에서만 비교하며 계층 점수(credit)에는 영향을 주지 않습니다.
+
+    key_size    키 길이(비트)    "AES-256", "RSA-2048" (암호/공개키 계열만, 해시의 256은 키 길이가 아님)
+    mode        블록 모드         "aes-256-cbc", "AES/GCM/NoPadding" → CBC, GCM
+    curve       타원 곡선         "ECDSA-P256", "secp256r1" → P-256, "X25519" → Curve25519
+    rounds      라운드 수         "ChaCha20 8 rounds", "AES-128 (7-round)" 처럼 라운드를 명시한 경우만
+
+정답 속성 (앞의 것이 우선):
+    expected_findings 형식:  "expected_findings": {"attributes": {"AES": {"key_size": 256, "mode": "CTR"}}, ...}
+    레거시 형식:              "quantum_vulnerable_algorithms": [{"algorithm": "AES", "key_size": 256, "mode": "CTR"}]
+    라벨 이름:                "AES-256-CBC" 처럼 이름에 들어 있는 속성
+
+예측 속성:
+    라벨 이름("AES-256-CTR") 또는 딕셔너리 라벨의 속성
+    ({"family": "AES", "key_size": 256, "mode": "CTR"}, {"label": "AES", "attributes": {"mode": "CTR"}})
+
+결과의 hierarchical_scores.matches[].attributes에 속성별 {'expected', 'predicted', 'correct'}가 남고,
+요약의 by_attribute에 속성별 정답률(accuracy: 맞힌 수 / 정답 속성 수)과 명시 정확도(stated_accuracy: 맞힌 수 / 답한 수)가
+집계됩니다.
+"""
+
+import re
+from typing import Dict, Any, Iterable, List, Optional
+
+ATTRIBUTES = ['key_size', 'mode', 'curve', 'rounds']
+
+BLOCK_MODES = {'ECB', 'CBC', 'CTR', 'GCM', 'CFB', 'OFB', 'CCM', 'XTS', 'SIV', 'OCB', 'EAX', 'GCMSIV'}
+# 분류 체계 변형 → 곡선 이름
+CURVE_VARIANTS = {'P-256': 'P-256', 'P-384': 'P-384', 'secp256k1': 'secp256k1', 'Curve25519': 'Curve25519',
+                  'X25519': 'Curve25519', 'Ed25519': 'Curve25519'}
+# 키 길이를 갖는 기본 요소 역할 (utils.taxonomy.PRIMITIVE_ROLES)
+KEYED_ROLES = {'cipher', 'public_key'}
+KEY_SIZE_RANGE = (40, 16384)
+
+_TOKEN = re.compile(r'[A-Za-z]+[0-9]*|[0-9]+')
+_WORD = re.compile(r'[\s/,;()]+')
+_ROUNDS = re.compile(r'(\d+)\s*-?\s*rounds?\b|\brounds?\s*[=:]?\s*(\d+)', re.IGNORECASE)
+
+
+def normalize_value(attribute: str, value: Any) -> Any:
+    """비교용 속성 값 (키 길이/라운드는 정수, 모드는 대문자, 곡선은 분류 체계 이름)"""
+    if value is None or value == '':
+        return None
+    if attribute in ('key_size', 'rounds'):
+        match = re.search(r'\d+', str(value))
+        return int(match.group()) if match else None
+    if attribute == 'mode':
+        return re.sub(r'[\s_\-]', '', str(value)).upper()
+    if attribute == 'curve':
+        from utils.taxonomy import AlgorithmTaxonomy
+        node = AlgorithmTaxonomy().lookup(str(value))
+        return CURVE_VARIANTS.get(node[2], str(value)) if node and node[2] else str(value)
+    return value
+
+
+def parse_attributes(label: Any, taxonomy=None) -> Dict[str, Any]:
+    """라벨 하나 → 속성 {이름: 값} (이름 속 속성과 딕셔너리 라벨의 속성)"""
+    from utils.taxonomy import AlgorithmTaxonomy
+
+    taxonomy = taxonomy or AlgorithmTaxonomy()
+    attributes: Dict[str, Any] = {}
+    if isinstance(label, dict):
+        text = str(label.get('label') or label.get('variant') or label.get('family') or '')
+        declared = dict(label['attributes']) if isinstance(label.get('attributes'), dict) else {}
+        declared.update({name: label[name] for name in ATTRIBUTES if name in label})
+    else:
+        text, declared = str(label), {}
+
+    node = taxonomy.resolve(label)
+    keyed = taxonomy.role(node) in KEYED_ROLES
+    for word in [text] + _WORD.split(text):
+        found = taxonomy.lookup(word) if word else None
+        if found and found[2] in CURVE_VARIANTS:
+            attributes.setdefault('curve', CURVE_VARIANTS[found[2]])
+    tokens = _TOKEN.findall(text)
+    for index, token in enumerate(tokens):
+        upper = token.upper()
+        if upper in BLOCK_MODES:
+            attributes.setdefault('mode', upper)
+        found = taxonomy.lookup(token)
+        if found is None and index + 1 < len(tokens) and tokens[index + 1].isdigit():
+            found = taxonomy.lookup(token + tokens[index + 1])
+        if found and found[2] in CURVE_VARIANTS:
+            attributes.setdefault('curve', CURVE_VARIANTS[found[2]])
+        digits = re.search(r'\d+$', token)
+        if keyed and digits and 'curve' not in attributes and \
+                KEY_SIZE_RANGE[0] <= int(digits.group()) <= KEY_SIZE_RANGE[1]:
+            attributes.setdefault('key_size', int(digits.group()))
+    rounds = _ROUNDS.search(text)
+    if rounds:
+        attributes['rounds'] = int(rounds.group(1) or rounds.group(2))
+    if 'curve' in attributes:
+        attributes.pop('key_size', None)
+
+    for name, value in declared.items():
+        if name in ATTRIBUTES and normalize_value(name, value) is not None:
+            attributes[name] = normalize_value(name, value)
+    return attributes
+
+
+def recorded_attributes(ground_truth: Optional[Dict[str, Any]]) -> Dict[str, Dict[str, Any]]:
+    """ground truth에 명시된 라벨별 속성 {알고리즘: {속성: 값}}"""
+    if not ground_truth:
+        return {}
+
+    analysis = ground_truth.get('vulnerability_analysis')
+    if isinstance(analysis, dict):
+        return {
+            entry['algorithm']: {name: normalize_value(name, entry[name]) for name in ATTRIBUTES if name in entry}
+            for entry in analysis.get('quantum_vulnerable_algorithms', [])
+            if entry.get('algorithm') and any(name in entry for name in ATTRIBUTES)
+        }
+
+    return {
+        label: {name: normalize_value(name, value) for name, value in (values or {}).items() if name in ATTRIBUTES}
+        for label, values in ground_truth.get('expected_findings', {}).get('attributes', {}).items()
+    }
+
+
+def expected_attributes(label: str, recorded: Optional[Dict[str, Dict[str, Any]]] = None,
+                        taxonomy=None) -> Dict[str, Any]:
+    """정답 라벨 하나의 속성 (이름 속 속성에 명시값을 덮어씀)"""
+    attributes = parse_attributes(label, taxonomy)
+    attributes.update({name: value for name, value in (recorded or {}).get(label, {}).items() if value is not None})
+    return attributes
+
+
+def compare_attributes(expected: Dict[str, Any], predicted: Dict[str, Any]) -> Dict[str, Dict[str, Any]]:
+    """정답 속성마다 {'expected', 'predicted', 'correct'} (답하지 않은 속성은 predicted None, 틀린 것으로 셈)"""
+    return {
+        name: {'expected': value, 'predicted': predicted.get(name), 'correct': predicted.get(name) == value}
+        for name, value in expected.items()
+    }
+
+
+def summarize_attributes(matches: Iterable[Dict[str, Any]]) -> Dict[str, Dict[str, Any]]:
+    """matches[].attributes → 속성별 {'expected', 'stated', 'correct', 'accuracy', 'stated_accuracy'}"""
+    summary: Dict[str, Dict[str, Any]] = {}
+    for match in matches:
+        for name, comparison in (match.get('attributes') or {}).items():
+            stats = summary.setdefault(name, {'expected': 0, 'stated': 0, 'correct': 0})
+            stats['expected'] += 1
+            stats['stated'] += comparison['predicted'] is not None
+            stats['correct'] += bool(comparison['correct'])
+    for stats in summary.values():
+        stats['accuracy'] = stats['correct'] / stats['expected']
+        stats['stated_accuracy'] = stats['correct'] / stats['stated'] if stats['stated'] else 0.0
+    return dict(sorted(summary.items(), key=lambda item: ATTRIBUTES.index(item[0])
+                       if item[0] in ATTRIBUTES else len(ATTRIBUTES)))
+
+
+def merge_attributes(labels: List[Dict[str, Any]]) -> Dict[str, Any]:
+    """같은 노드로 해석된 여러 예측 라벨의 속성 합치기 (먼저 나온 값 우선)"""
+    merged: Dict[str, Any] = {}
+    for attributes in labels:
+        for name, value in attributes.items():
+            merged.setdefault(name, value)
+    return merged
@@ -0,0 +1,298 @@
+"""
+LLM 배치 추론 (batch)
+
+OpenAI와 Anthropic은 비동기 배치 엔드포인트를 동기 호출의 절반 가격에 제공합니다. 배치 모드(--batch)에서는 코퍼스
+전체 프롬프트를 (프로바이더, 모델)마다 배치 작업 하나로 제출하고, 끝날 때까지 폴링한 뒤 응답을 샘플에 되돌려
+동기 실행과 같은 파이프라인(파싱, 병합, 채점)으로 채점합니다.
+
+    1. 수집   샘플마다 평소처럼 프롬프트를 만들되 호출하지 않고 기록 (창 분할이면 창마다 하나)
+    2. 제출   요청 키(프롬프트 + max_tokens의 해시)를 custom_id로 배치 작업 제출, 작업 상태를 jobs_dir에 저장
+    3. 대기   poll_seconds마다 상태 확인 (max_wait_hours를 넘기면 그 모델 샘플은 오류로 기록)
+    4. 채점   샘플을 다시 돌리며 호출 대신 배치 응답을 돌려줌 (비용은 단가 × discount로 장부에 기록)
+
+같은 프롬프트 집합으로 다시 실행하면 저장된 작업을 이어서 기다리고, 이미 끝난 작업은 저장된 응답을 그대로 씁니다
+(폴링 중 중단해도 다시 제출하지 않음). 배치를 지원하지 않는 프로바이더의 샘플과 judge 호출은 평소처럼 동기로 실행합니다.
+
+설정 (config.yaml):
+    benchmark:
+      batch:
+        enabled: false          # --batch로 켜기
+        poll_seconds: 60
+        max_wait_hours: 24
+        discount: 0.5           # 배치 단가 / 동기 단가
+        jobs_dir: "results/batch_jobs"
+"""
+
+import hashlib
+import json
+import re
+import tempfile
+import threading
+import time
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Tuple
+
+from clients.base_client import BaseLLMClient
+from utils.quota import CostTracker, usage_tokens
+from utils.run_control import Cancelled, CancellationToken
+
+BATCH_PROVIDERS = ('openai', 'anthropic')
+DEFAULT_POLL_SECONDS = 60
+DEFAULT_MAX_WAIT_HOURS = 24
+DEFAULT_DISCOUNT = 0.5
+DEFAULT_JOBS_DIR = 'results/batch_jobs'
+# 동기 클라이언트(clients/*_client.py)와 같은 생성 설정
+TEMPERATURE = 0.1
+
+STATE_PENDING, STATE_ENDED, STATE_FAILED = 'pending', 'ended', 'failed'
+
+
+def batch_settings(config: Optional[Dict[str, Any]]) -> Dict[str, Any]:
+    config = config or {}
+    return {
+        'enabled': bool(config.get('enabled', False)),
+        'poll_seconds': float(config.get('poll_seconds', DEFAULT_POLL_SECONDS)),
+        'max_wait_hours': float(config.get('max_wait_hours', DEFAULT_MAX_WAIT_HOURS)),
+        'discount': float(config.get('discount', DEFAULT_DISCOUNT)),
+        'jobs_dir': config.get('jobs_dir', DEFAULT_JOBS_DIR)
+    }
+
+
+def request_key(prompt: str, max_tokens: int) -> str:
+    """요청 → custom_id (두 프로바이더 모두 [A-Za-z0-9_-]{1,64}만 허용)"""
+    return hashlib.sha256(f"{max_tokens}\n{prompt}".encode('utf-8')).hexdigest()[:32]
+
+
+class OpenAIBatch:
+    """OpenAI Batch API (/v1/chat/completions 요청을 JSONL 파일로 올려 24시간 창으로 제출)"""
+
+    def __init__(self, client):
+        self.sdk = client.client
+        self.model = client.model
+
+    def submit(self, requests: Dict[str, Tuple[str, int]]) -> str:
+        lines = [json.dumps({'custom_id': key, 'method': 'POST', 'url': '/v1/chat/completions',
+                             'body': {'model': self.model, 'messages': [{'role': 'user', 'content': prompt}],
+                                      'max_tokens': max_tokens, 'temperature': TEMPERATURE}}, ensure_ascii=False)
+                 for key, (prompt, max_tokens) in requests.items()]
+        with tempfile.TemporaryDirectory() as work_dir:
+            path = Path(work_dir) / 'batch.jsonl'
+            path.write_text('\n'.join(lines) + '\n', encoding='utf-8')
+            with open(path, 'rb') as f:
+                upload = self.sdk.files.create(file=f, purpose='batch')
+        batch = self.sdk.batches.create(input_file_id=upload.id, endpoint='/v1/chat/completions',
+                                        completion_window='24h')
+        return batch.id
+
+    def status(self, batch_id: str) -> Tuple[str, str]:
+        batch = self.sdk.batches.retrieve(batch_id)
+        counts = batch.request_counts
+        detail = f"{batch.status} ({counts.completed + counts.failed}/{counts.total})" if counts else batch.status
+        if batch.status == 'completed':
+            return STATE_ENDED, detail
+        if batch.status in ('failed', 'expired', 'cancelled'):
+            # 만료/취소된 작업도 끝난 요청의 결과 파일은 남음
+            return (STATE_ENDED if batch.output_file_id else STATE_FAILED), detail
+        return STATE_PENDING, detail
+
+    def results(self, batch_id: str) -> Dict[str, Dict[str, Any]]:
+        batch = self.sdk.batches.retrieve(batch_id)
+        responses = {}
+        for file_id in (batch.output_file_id, batch.error_file_id):
+            if not file_id:
+                continue
+            for line in self.sdk.files.content(file_id).text.splitlines():
+                if not line.strip():
+                    continue
+                entry = json.loads(line)
+                response = entry.get('response') or {}
+                body = response.get('body') or {}
+                if entry.get('error') or response.get('status_code') != 200 or not body.get('choices'):
+                    error = entry.get('error') or body.get('error') or response.get('status_code')
+                    responses[entry['custom_id']] = {'error': f"OpenAI batch error: {error}"}
+                    continue
+                responses[entry['custom_id']] = {
+                    'content': body['choices'][0]['message'].get('content') or '',
+                    'usage': body.get('usage') or {},
+                    'model': body.get('model', self.model)
+                }
+        return responses
+
+
+class AnthropicBatch:
+    """Anthropic Message Batches API"""
+
+    def __init__(self, client):
+        self.sdk = client.client
+        self.model = client.model
+
+    def submit(self, requests: Dict[str, Tuple[str, int]]) -> str:
+        batch = self.sdk.messages.batches.create(requests=[
+            {'custom_id': key, 'params': {'model': self.model, 'max_tokens': max_tokens, 'temperature': TEMPERATURE,
+                                          'messages': [{'role': 'user', 'content': prompt}]}}
+            for key, (prompt, max_tokens) in requests.items()
+        ])
+        return batch.id
+
+    def status(self, batch_id: str) -> Tuple[str, str]:
+        batch = self.sdk.messages.batches.retrieve(batch_id)
+        counts = batch.request_counts
+        detail = f"{batch.processing_status} (처리 중 {counts.processing}, 성공 {counts.succeeded}, " \
+                 f"오류 {counts.errored + counts.expired + counts.canceled})"
+        return (STATE_ENDED if batch.processing_status == 'ended' else STATE_PENDING), detail
+
+    def results(self, batch_id: str) -> Dict[str, Dict[str, Any]]:
+        responses = {}
+        for entry in self.sdk.messages.batches.results(batch_id):
+            result = entry.result
+            if result.type != 'succeeded':
+                error = getattr(getattr(result, 'error', None), 'error', None) or result.type
+                responses[entry.custom_id] = {'error': f"Anthropic batch error: {error}"}
+                continue
+            message = result.message
+            responses[entry.custom_id] = {
+                'content': ''.join(block.text for block in message.content if getattr(block, 'text', None)),
+                'usage': {
+                    'prompt_tokens': message.usage.input_tokens,
+                    'completion_tokens': message.usage.output_tokens,
+                    'total_tokens': message.usage.input_tokens + message.usage.output_tokens,
+                    'cache_read_input_tokens': getattr(message.usage, 'cache_read_input_tokens', 0) or 0
+                },
+                'model': message.model
+            }
+        return responses
+
+
+BACKENDS = {'openai': OpenAIBatch, 'anthropic': AnthropicBatch}
+
+
+class BatchClient(BaseLLMClient):
+    """클라이언트 자리에 끼우는 배치 클라이언트: 수집 단계에서는 프롬프트만 기록, 채점 단계에서는 배치 응답을 돌려줌"""
+
+    def __init__(self, batch: 'BatchInference', provider: str, model: str):
+        super().__init__('', model, '')
+        self.batch = batch
+        self.provider = provider
+
+    def make_request(self, prompt: str, max_tokens: int = 1000) -> Dict[str, Any]:
+        key = request_key(prompt, max_tokens)
+        if self.batch.collecting:
+            self.batch.record(self.provider, self.model, key, prompt, max_tokens)
+            return {'content': '', 'usage': {}}
+        stored = self.batch.responses.get((self.provider, self.model), {}).get(key)
+        if stored is None:
+            raise Exception("no batch response for this prompt")
+        if 'error' in stored:
+            raise Exception(stored['error'])
+        return stored
+
+    def benchmark_request(self, prompt: str, max_tokens: int = 1000) -> Dict[str, Any]:
+        """비용은 배치 단가(단가 × discount)로 장부에 기록 (수집 단계는 기록하지 않음)"""
+        response = super().benchmark_request(prompt, max_tokens)
+        if response['success'] and not self.batch.collecting:
+            response['cost_usd'] = self.batch.costs.record(self.provider, self.model, response.get('usage'), prompt,
+                                                           response['content'], rate=self.batch.settings['discount'])
+            response['cached_tokens'] = usage_tokens(response.get('usage'), prompt, '')['cached_tokens']
+        return response
+
+
+class BatchInference:
+    """(프로바이더, 모델)별 배치 작업 수집·제출·대기·응답 조회"""
+
+    def __init__(self, settings: Dict[str, Any], costs: CostTracker):
+        self.settings = settings
+        self.costs = costs
+        self.collecting = False
+        self.lock = threading.Lock()
+        self.requests: Dict[Tuple[str, str], Dict[str, Tuple[str, int]]] = {}
+        self.responses: Dict[Tuple[str, str], Dict[str, Dict[str, Any]]] = {}
+        self.jobs: Dict[str, Dict[str, Any]] = {}
+
+    def client(self, provider: str, model: str) -> BatchClient:
+        return BatchClient(self, provider, model)
+
+    def record(self, provider: str, model: str, key: str, prompt: str, max_tokens: int):
+        with self.lock:
+            self.requests.setdefault((provider, model), {})[key] = (prompt, max_tokens)
+
+    def pending(self) -> List[Tuple[str, str]]:
+        return [group for group, requests in self.requests.items() if requests and group not in self.responses]
+
+    def _state_path(self, provider: str, model: str, requests: Dict[str, Tuple[str, int]]) -> Path:
+        digest = hashlib.sha256('\n'.join(sorted(requests)).encode('utf-8')).hexdigest()[:16]
+        return Path(self.settings['jobs_dir']) / f"{provider}-{re.sub(r'[^A-Za-z0-9.-]+', '_', model)}-{digest}.json"
+
+    def run(self, provider: str, model: str, client, token: CancellationToken):
+        """모은 요청을 배치 작업으로 제출(또는 저장된 작업을 이어서)하고 끝날 때까지 기다려 응답을 받음
+
+        제출/폴링이 실패하거나 max_wait_hours를 넘기면 그 모델의 요청은 모두 오류 응답 (샘플은 채점 단계에서 실패로 기록)
+        """
+        requests = self.requests[(provider, model)]
+        state_path = self._state_path(provider, model, requests)
+        state = json.loads(state_path.read_text(encoding='utf-8')) if state_path.exists() else None
+        job = {'provider': provider, 'model': model, 'requests': len(requests), 'state_file': str(state_path)}
+        self.jobs[f"{provider}/{model}"] = job
+
+        if state and state.get('responses'):
+            print(f"📦 {provider}/{model}: 끝난 배치 작업 {state['batch_id']}의 저장된 응답 사용 ({state_path})")
+            self.responses[(provider, model)] = state['responses']
+            job.update(batch_id=state['batch_id'], status=STATE_ENDED, reused=True)
+            return
+
+        backend = BACKENDS[provider](client)
+        try:
+            if state:
+                batch_id = state['batch_id']
+                print(f"📦 {provider}/{model}: 제출해 둔 배치 작업 {batch_id} 이어서 대기")
+            else:
+                batch_id = backend.submit(requests)
+                state = {'provider': provider, 'model': model, 'batch_id': batch_id, 'requests': len(requests),
+                         'submitted_at': time.time()}
+                self._save(state_path, state)
+                print(f"📦 {provider}/{model}: 요청 {len(requests)}개를 배치 작업 {batch_id}로 제출")
+            job['batch_id'] = batch_id
+            status, detail = self._wait(backend, batch_id, state['submitted_at'], token)
+            job.update(status=status, detail=detail, waited_seconds=round(time.time() - state['submitted_at'], 1))
+            if status != STATE_ENDED:
+                raise RuntimeError(f"배치 작업 {batch_id} {detail}")
+            responses = backend.results(batch_id)
+        except Cancelled:
+            raise
+        except Exception as e:
+            print(f"❌ {provider}/{model}: 배치 실패: {e}")
+            job.setdefault('status', STATE_FAILED)
+            job['error'] = str(e)
+            self.responses[(provider, model)] = {key: {'error': f"batch failed: {e}"} for key in requests}
+            return
+
+        state['responses'] = responses
+        self._save(state_path, state)
+        failed = sum(1 for response in responses.values() if 'error' in response)
+        job.update(succeeded=len(responses) - failed, errored=failed)
+        print(f"✅ {provider}/{model}: 배치 응답 {len(responses)}개 (오류 {failed}개)")
+        self.responses[(provider, model)] = responses
+
+    def _wait(self, backend, batch_id: str, submitted_at: float, token: CancellationToken) -> Tuple[str, str]:
+        deadline = submitted_at + self.settings['max_wait_hours'] * 3600
+        while True:
+            status, detail = backend.status(batch_id)
+            if status != STATE_PENDING:
+                return status, detail
+            if time.time() >= deadline:
+                return STATE_FAILED, f"대기 시간 초과 ({self.settings['max_wait_hours']:g}시간): {detail}"
+            print(f"    ⏳ {batch_id}: {detail}")
+            waited = 0.0
+            while waited < self.settings['poll_seconds']:
+                token.raise_if_cancelled()
+                time.sleep(min(1.0, self.settings['poll_seconds'] - waited))
+                waited += 1.0
+
+    @staticmethod
+    def _save(path: Path, state: Dict[str, Any]):
+        path.parent.mkdir(parents=True, exist_ok=True)
+        with open(path, 'w', encoding='utf-8') as f:
+            json.dump(state, f, indent=2, ensure_ascii=False)
+
+    def report(self) -> Dict[str, Any]:
+        return {**self.settings, 'jobs': self.jobs}
+
@@ -0,0 +1,249 @@
+#!/usr/bin/env python3
+"""
+탐지 신뢰도 보정과 임계값 분석 (calibration)
+
+많은 탐지 도구가 신뢰도 임계값을 조정할 수 있으므로, 보고한 신뢰도가 실제 정답률과 맞는지(보정)와 임계값을
+바꾸면 precision/recall이 어떻게 움직이는지(PR 곡선)를 채점 결과에서 계산합니다.
+
+입력: 결과 행의 predictions (채점 시 MetricsCalculator.calculate_prediction_credits가 기록)
+    [{'label', 'confidence', 'credit', 'matches'}]  라벨마다 하나. credit은 계층 점수(0~1)로 본 정답 여부,
+    matches는 hierarchical_scores.matches(정답 라벨) 순서대로 이 예측이 각 정답에 주는 점수
+    신뢰도: 로컬/외부 탐지기는 탐지 보고의 라벨별 scores (없으면 보고 전체 confidence), LLM은 응답의 confidence_score.
+    predictions가 없는 행(이전 결과, 오류 행)은 뺍니다.
+
+지표:
+    ECE     신뢰도를 같은 폭 구간(--bins)으로 나눠 구간별 |평균 신뢰도 - 평균 credit|을 예측 수로 가중 평균
+    MCE     구간별 차이의 최댓값
+    Brier   (신뢰도 - credit)² 평균
+    PR 곡선  보고된 신뢰도 값마다 그 이상인 예측만 남겼을 때의 precision(남은 예측 credit 평균)과
+            recall(정답마다 남은 예측이 주는 최고 점수의 합 / 정답 수), F1. 평균 정밀도(AP)는 곡선의 계단 넓이
+    보고하지 않은 라벨의 점수는 모르므로 곡선은 탐지기가 쓴 임계값보다 높은 쪽만 그립니다. 전체 곡선이 필요하면
+    탐지기 임계값을 낮춰(예: classifier --threshold 0.05) 실행하세요.
+
+HTML 보고서(utils/report_export.py --format html)는 predictions가 있는 탐지기마다 신뢰도 다이어그램과 PR 곡선을
+SVG로 넣습니다.
+
+사용법:
+    python -m utils.calibration results/classifier.json
+    python -m utils.calibration results/run.json --detector llama3 --bins 15 --output results/calibration.json
+"""
+
+import argparse
+import json
+import sys
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+from utils.findings_merger import FindingsMerger
+
+DEFAULT_BINS = 10
+
+# SVG 그림 크기와 여백
+SVG_SIZE = 240
+SVG_MARGIN = 32
+
+
+def label_confidences(findings: List[Dict[str, Any]], extra: Optional[List[Tuple[str, float]]] = None
+                      ) -> List[Tuple[str, float]]:
+    """Finding 목록 → 라벨별 최고 신뢰도 [(라벨, 신뢰도)] (보고 순서, 같은 라벨은 하나로)"""
+    best: Dict[str, Tuple[str, float]] = {}
+    pairs = [(finding['algorithm'], finding.get('confidence') or 0.0) for finding in findings] + list(extra or [])
+    for label, confidence in pairs:
+        key = FindingsMerger.normalize_algorithm(label)
+        if key not in best or confidence > best[key][1]:
+            best[key] = (best[key][0] if key in best else label, float(confidence))
+    return list(best.values())
+
+
+def scored_rows(rows: List[Dict[str, Any]]) -> List[Dict[str, Any]]:
+    return [row for row in rows if isinstance(row.get('predictions'), list) and row.get('hierarchical_scores')
+            and 'error' not in row]
+
+
+def reliability(rows: List[Dict[str, Any]], bins: int = DEFAULT_BINS) -> Dict[str, Any]:
+    """신뢰도 구간별 평균 신뢰도/credit과 ECE, MCE, Brier"""
+    points = [(p['confidence'], p['credit']) for row in scored_rows(rows) for p in row['predictions']]
+    buckets: List[List[Tuple[float, float]]] = [[] for _ in range(bins)]
+    for confidence, credit in points:
+        buckets[min(int(confidence * bins), bins - 1)].append((confidence, credit))
+    table = []
+    for index, members in enumerate(buckets):
+        table.append({
+            'lower': index / bins, 'upper': (index + 1) / bins, 'count': len(members),
+            'confidence': sum(c for c, _ in members) / len(members) if members else None,
+            'accuracy': sum(y for _, y in members) / len(members) if members else None,
+        })
+    gaps = [(item['count'], abs(item['confidence'] - item['accuracy'])) for item in table if item['count']]
+    total = len(points)
+    return {
+        'predictions': total,
+        'ece': sum(count * gap for count, gap in gaps) / total if total else None,
+        'mce': max((gap for _, gap in gaps), default=None),
+        'brier': sum((c - y) ** 2 for c, y in points) / total if total else None,
+        'bins': table,
+    }
+
+
+def threshold_sweep(rows: List[Dict[str, Any]]) -> List[Dict[str, Any]]:
+    """보고된 신뢰도 값마다 (높은 값부터) precision/recall/F1"""
+    rows = scored_rows(rows)
+    expected_total = sum(len(row['hierarchical_scores'].get('matches') or []) for row in rows)
+    ranked = sorted(((p['confidence'], index, p) for index, row in enumerate(rows) for p in row['predictions']),
+                    key=lambda item: -item[0])
+    best = [[0.0] * len(row['hierarchical_scores'].get('matches') or []) for row in rows]
+    recall_credit = precision_credit = 0.0
+    kept = 0
+    curve = []
+    for position, (confidence, index, prediction) in enumerate(ranked):
+        kept += 1
+        precision_credit += prediction['credit']
+        for slot, credit in enumerate(prediction.get('matches') or []):
+            if slot < len(best[index]) and credit > best[index][slot]:
+                recall_credit += credit - best[index][slot]
+                best[index][slot] = credit
+        if position + 1 < len(ranked) and ranked[position + 1][0] == confidence:
+            continue
+        precision = precision_credit / kept
+        recall = recall_credit / expected_total if expected_total else 0.0
+        f1 = 2 * precision * recall / (precision + recall) if precision + recall else 0.0
+        curve.append({'threshold': confidence, 'predictions': kept, 'precision': precision, 'recall': recall,
+                      'f1': f1})
+    return curve
+
+
+def average_precision(curve: List[Dict[str, Any]]) -> Optional[float]:
+    """PR 곡선의 계단 넓이 (recall 증가분 × 그 지점의 precision)"""
+    if not curve:
+        return None
+    area, previous = 0.0, 0.0
+    for point in curve:
+        area += (point['recall'] - previous) * point['precision']
+        previous = point['recall']
+    return area
+
+
+def calibration_summary(rows: List[Dict[str, Any]], bins: int = DEFAULT_BINS) -> Optional[Dict[str, Any]]:
+    """결과 요약용 (predictions가 있는 행이 없으면 None)"""
+    table = reliability(rows, bins)
+    if not table['predictions']:
+        return None
+    curve = threshold_sweep(rows)
+    best = max(curve, key=lambda point: (point['f1'], point['threshold']))
+    return {'predictions': table['predictions'], 'ece': table['ece'], 'mce': table['mce'], 'brier': table['brier'],
+            'average_precision': average_precision(curve),
+            'best_threshold': {key: best[key] for key in ('threshold', 'precision', 'recall', 'f1')}}
+
+
+def _svg(title: str, x_label: str, y_label: str, body: List[str]) -> str:
+    size, margin = SVG_SIZE, SVG_MARGIN
+    inner = size - 2 * margin
+    axes = [f'<line x1="{margin}" y1="{size - margin}" x2="{size - margin}" y2="{size - margin}" stroke="#333"/>',
+            f'<line x1="{margin}" y1="{margin}" x2="{margin}" y2="{size - margin}" stroke="#333"/>']
+    for tick in (0.0, 0.5, 1.0):
+        offset = margin + tick * inner
+        axes += [f'<text x="{offset:.1f}" y="{size - margin + 14}" font-size="10" text-anchor="middle">{tick:g}</text>',
+                 f'<text x="{margin - 4}" y="{size - offset + 3:.1f}" font-size="10" text-anchor="end">{tick:g}</text>']
+    labels = [f'<text x="{size / 2:g}" y="{size - 4}" font-size="11" text-anchor="middle">{x_label}</text>',
+              f'<text x="12" y="{size / 2:g}" font-size="11" text-anchor="middle" '
+              f'transform="rotate(-90 12 {size / 2:g})">{y_label}</text>',
+              f'<text x="{size / 2:g}" y="14" font-size="12" text-anchor="middle">{title}</text>']
+    return (f'<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="0 0 {size} {size}">'
+            + ''.join(axes + body + labels) + '</svg>')
+
+
+def _point(x: float, y: float) -> Tuple[float, float]:
+    inner = SVG_SIZE - 2 * SVG_MARGIN
+    return SVG_MARGIN + x * inner, SVG_SIZE - SVG_MARGIN - y * inner
+
+
+def reliability_svg(table: Dict[str, Any], title: str, x_label: str, y_label: str) -> str:
+    """신뢰도 다이어그램: 구간별 평균 credit 막대 + 완전 보정 대각선"""
+    inner = SVG_SIZE - 2 * SVG_MARGIN
+    body = []
+    for item in table['bins']:
+        if not item['count']:
+            continue
+        x, y = _point(item['lower'], item['accuracy'])
+        width = (item['upper'] - item['lower']) * inner
+        body.append(f'<rect x="{x:.1f}" y="{y:.1f}" width="{width:.1f}" height="{SVG_SIZE - SVG_MARGIN - y:.1f}" '
+                    f'fill="#4c78a8" fill-opacity="0.7" stroke="#fff"><title>{item["lower"]:.2f}-{item["upper"]:.2f}: '
+                    f'n={item["count"]}, conf {item["confidence"]:.2f}, acc {item["accuracy"]:.2f}</title></rect>')
+    (x1, y1), (x2, y2) = _point(0, 0), _point(1, 1)
+    body.append(f'<line x1="{x1:.1f}" y1="{y1:.1f}" x2="{x2:.1f}" y2="{y2:.1f}" stroke="#999" '
+                f'stroke-dasharray="4 3"/>')
+    return _svg(title, x_label, y_label, body)
+
+
+def pr_svg(curve: List[Dict[str, Any]], title: str) -> str:
+    """PR 곡선 (recall → precision, 점마다 임계값 툴팁)"""
+    points = [_point(point['recall'], point['precision']) for point in curve]
+    body = [f'<polyline fill="none" stroke="#e45756" stroke-width="2" '
+            f'points="{" ".join(f"{x:.1f},{y:.1f}" for x, y in points)}"/>'] if len(points) > 1 else []
+    body += [f'<circle cx="{x:.1f}" cy="{y:.1f}" r="2.5" fill="#e45756"><title>≥ {point["threshold"]:.2f}: '
+             f'P {point["precision"]:.2f}, R {point["recall"]:.2f}</title></circle>'
+             for (x, y), point in zip(points, curve)]
+    return _svg(title, 'recall', 'precision', body)
+
+
+def print_report(name: str, table: Dict[str, Any], curve: List[Dict[str, Any]], limit: int = 10):
+    summary_ap = average_precision(curve)
+    print(f"\n▶ {name}: 예측 {table['predictions']}개")
+    print(f"  ECE {table['ece']:.3f}, MCE {table['mce']:.3f}, Brier {table['brier']:.3f}, AP {summary_ap:.3f}")
+    print("  구간          예측   평균 신뢰도   평균 정답률")
+    for item in table['bins']:
+        if item['count']:
+            print(f"  {item['lower']:.2f}-{item['upper']:.2f}  {item['count']:6d}   {item['confidence']:10.3f}   "
+                  f"{item['accuracy']:10.3f}")
+    best = max(curve, key=lambda point: (point['f1'], point['threshold']))
+    print(f"  🎯 F1 최대 임계값 ≥ {best['threshold']:.3f}: P {best['precision']:.3f} / R {best['recall']:.3f} / "
+          f"F1 {best['f1']:.3f}")
+    step = max(1, len(curve) // limit)
+    for point in curve[::step]:
+        print(f"     ≥ {point['threshold']:.3f}  예측 {point['predictions']:4d}  P {point['precision']:.3f}  "
+              f"R {point['recall']:.3f}  F1 {point['f1']:.3f}")
+
+
+def main():
+    from utils.report_export import load_results
+    from utils.run_compare import group_by_detector, select_detector
+
+    parser = argparse.ArgumentParser(description='탐지 신뢰도 보정(ECE, 신뢰도 다이어그램)과 임계값별 PR 곡선')
+    parser.add_argument('results', help='결과 파일 (benchmark_runner / detectors.runner JSON)')
+    parser.add_argument('--detector', help='분석할 탐지기 (부분 문자열, 기본: 전부)')
+    parser.add_argument('--bins', type=int, default=DEFAULT_BINS, help='신뢰도 구간 수')
+    parser.add_argument('--limit', type=int, default=10, help='출력할 임계값 개수')
+    parser.add_argument('--output', help='구간표와 PR 곡선 JSON 저장 경로')
+    args = parser.parse_args()
+    if args.bins < 1:
+        parser.error('--bins는 1 이상이어야 합니다')
+
+    try:
+        groups = group_by_detector(load_results(args.results))
+        if args.detector:
+            name = select_detector(groups, args.detector)
+            groups = {name: groups[name]}
+    except ValueError as e:
+        print(f"❌ {e}")
+        sys.exit(2)
+
+    report = {}
+    for name, rows in groups.items():
+        table = reliability(rows, args.bins)
+        if not table['predictions']:
+            print(f"\n⚠️  {name}: predictions가 없는 결과입니다 (라벨별 신뢰도를 기록하기 전 실행)")
+            continue
+        curve = threshold_sweep(rows)
+        print_report(name, table, curve, args.limit)
+        report[name] = {'reliability': table, 'pr_curve': curve, 'summary': calibration_summary(rows, args.bins)}
+
+    if args.output:
+        Path(args.output).parent.mkdir(parents=True, exist_ok=True)
+        with open(args.output, 'w', encoding='utf-8') as f:
+            json.dump({'results': args.results, 'bins': args.bins, 'detectors': report}, f, indent=2,
+                      ensure_ascii=False)
+        print(f"💾 결과 저장: {args.output}")
+    sys.exit(0 if report else 1)
+
+
+if __name__ == '__main__':
+    main()
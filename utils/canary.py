@@ -0,0 +1,304 @@
+"""
+카나리 문자열 (canary strings)로 학습 오염 탐지
+생성 샘플 끝에 추측할 수 없는 카나리 주석을 심고, 모델이 그 ID를 이어 쓰는지 물어 코퍼스를 학습했는지 확인합니다.
+카나리 형식, 세대 기록(data/canary_manifest.json), probe 판정 기준은 docs/TEST_FILES.md를 보세요.
+"""
+
+import argparse
+import hashlib
+import json
+import re
+import secrets
+import sys
+from datetime import datetime, timezone
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+CANARY_MANIFEST_PATH = "data/canary_manifest.json"
+CANARY_MARKER = "QVBENCH-CANARY"
+CANARY_DIGITS = 24
+# 숨긴 부분을 이만큼 맞히면 오염으로 표시
+CONTAMINATION_MATCH = 8
+# prefix 검사에서 모델에 보여 주는 16진수 자리 수
+PROBE_PREFIX_LENGTH = 8
+
+COMMENT_PREFIXES = {'.rs': '//', '.c': '//', '.cpp': '//', '.java': '//', '.js': '//', '.go': '//',
+                    '.py': '#', '.rb': '#', '.s': ';', '.asm': ';'}
+
+CANARY_LINE = re.compile(rf'^[ \t]*(?://|#|;)[ \t]*{CANARY_MARKER}[ \t]+\S+[ \t]*$', re.MULTILINE)
+CANARY_ID = re.compile(r'qvb-g(\d+)-([0-9a-f]+)')
+
+
+def canary_id(salt: str, generation: int, sample_key: str) -> str:
+    digest = hashlib.sha256(f"{salt}:{sample_key}".encode('utf-8')).hexdigest()[:CANARY_DIGITS]
+    return f"qvb-g{generation}-{digest}"
+
+
+def strip_canaries(text: str) -> str:
+    """카나리 줄의 내용을 지움 (줄바꿈은 남겨 줄 번호 유지)"""
+    return CANARY_LINE.sub('', text)
+
+
+def find_canaries(text: str) -> List[str]:
+    return [CANARY_ID.search(line).group(0) for line in CANARY_LINE.findall(text) if CANARY_ID.search(line)]
+
+
+def load_manifest(path: str = CANARY_MANIFEST_PATH) -> Dict[str, Any]:
+    if not Path(path).exists():
+        return {'current': 0, 'generations': [], 'samples': {}}
+    with open(path, 'r', encoding='utf-8') as f:
+        return json.load(f)
+
+
+def write_manifest(manifest: Dict[str, Any], path: str = CANARY_MANIFEST_PATH):
+    Path(path).parent.mkdir(parents=True, exist_ok=True)
+    with open(path, 'w', encoding='utf-8') as f:
+        json.dump({**manifest, 'samples': dict(sorted(manifest['samples'].items()))}, f, indent=2, ensure_ascii=False)
+        f.write('\n')
+
+
+def current_generation(path: str = CANARY_MANIFEST_PATH) -> Optional[int]:
+    """현재 카나리 세대 (기록이 없으면 None, 실행 metadata.canary_generation)"""
+    return load_manifest(path).get('current') or None
+
+
+def _now() -> str:
+    return datetime.now(timezone.utc).isoformat(timespec='seconds')
+
+
+def new_generation(manifest: Dict[str, Any]) -> Dict[str, Any]:
+    """새 세대를 만들고 이전 세대를 은퇴 처리"""
+    for generation in manifest['generations']:
+        generation.setdefault('retired', _now())
+    entry = {'generation': manifest.get('current', 0) + 1, 'salt': secrets.token_hex(16), 'created': _now()}
+    manifest['generations'].append(entry)
+    manifest['current'] = entry['generation']
+    return entry
+
+
+def canary_target(path: Path) -> Optional[Path]:
+    """카나리를 붙일 파일 (디렉토리 샘플은 주석을 달 수 있는 첫 소스 파일)"""
+    from utils.manifest import sample_files
+
+    if path.is_file():
+        return path if path.suffix in COMMENT_PREFIXES else None
+    return next((file for file in sample_files(path) if file.suffix in COMMENT_PREFIXES), None)
+
+
+def set_canary(path: Path, canary: str):
+    """파일 끝의 카나리 줄을 교체하거나 새로 붙임"""
+    text = path.read_text(encoding='utf-8')
+    line = f"{COMMENT_PREFIXES[path.suffix]} {CANARY_MARKER} {canary}"
+    if CANARY_LINE.search(text):
+        text = CANARY_LINE.sub(line, text)
+    else:
+        text = f"{text.rstrip(chr(10))}\n{line}\n"
+    path.write_text(text, encoding='utf-8')
+
+
+def generated_samples(test_files_dir: str = "data/test_files",
+                      ground_truth_dir: str = "data/ground_truth") -> List[Tuple[str, Path]]:
+    """카나리 대상: generated_by가 있는 생성 샘플 [(agent/test_id, 샘플 경로)]"""
+    samples = []
+    for truth_path in sorted(Path(ground_truth_dir).glob('*/*.json')):
+        with open(truth_path, 'r', encoding='utf-8') as f:
+            ground_truth = json.load(f)
+        if not isinstance(ground_truth.get('generated_by'), dict):
+            continue
+        agent_dir = Path(test_files_dir) / truth_path.parent.name
+        candidates = [path for path in agent_dir.glob(f"{truth_path.stem}*")
+                      if path.stem == truth_path.stem or path.name == truth_path.stem]
+        for path in candidates:
+            samples.append((f"{truth_path.parent.name}/{truth_path.stem}", path))
+    return samples
+
+
+def inject(manifest: Dict[str, Any], samples: List[Tuple[str, Path]], replace: bool = False) -> List[str]:
+    """카나리가 없는 (replace면 모든) 샘플에 현재 세대 카나리를 심음 → 바뀐 샘플 키"""
+    if not manifest.get('current'):
+        new_generation(manifest)
+    generation = manifest['generations'][-1]
+    changed = []
+    for sample_key, path in samples:
+        target = canary_target(path)
+        if target is None:
+            continue
+        recorded = manifest['samples'].get(sample_key)
+        if recorded and not replace and recorded['canary'] in find_canaries(target.read_text(encoding='utf-8')):
+            continue
+        canary = canary_id(generation['salt'], generation['generation'], sample_key)
+        set_canary(target, canary)
+        manifest['samples'][sample_key] = {'generation': generation['generation'], 'canary': canary}
+        changed.append(sample_key)
+    return changed
+
+
+def canary_issues(manifest: Dict[str, Any], samples: List[Tuple[str, Path]]) -> List[Tuple[str, str, str]]:
+    """기록과 샘플 파일 비교 → [(level, 샘플 키, message)] (utils/corpus.py lint)"""
+    if not manifest.get('current'):
+        return []
+    issues = []
+    present = set()
+    for sample_key, path in samples:
+        target = canary_target(path)
+        if target is None:
+            continue
+        present.add(sample_key)
+        found = find_canaries(target.read_text(encoding='utf-8'))
+        recorded = manifest['samples'].get(sample_key)
+        if not recorded:
+            issues.append(('warning', sample_key, "generated sample has no canary (run python -m utils.canary inject)"))
+        elif found != [recorded['canary']]:
+            issues.append(('error', sample_key, f"canary in {target} does not match {CANARY_MANIFEST_PATH} "
+                                                f"(expected {recorded['canary']}, found {found or 'none'})"))
+    for sample_key in sorted(set(manifest['samples']) - present):
+        issues.append(('error', sample_key, f"canary recorded in {CANARY_MANIFEST_PATH} but the sample is missing"))
+    return issues
+
+
+def known_canaries(manifest: Dict[str, Any]) -> Dict[str, Dict[str, Any]]:
+    """모든 세대의 카나리 {ID: {'sample', 'generation'}} (은퇴한 세대는 salt로 다시 계산)"""
+    known = {}
+    for generation in manifest['generations']:
+        for sample_key in manifest['samples']:
+            known[canary_id(generation['salt'], generation['generation'], sample_key)] = {
+                'sample': sample_key, 'generation': generation['generation']}
+    return known
+
+
+def hidden_match(expected: str, response: str, shown: int) -> int:
+    """응답의 카나리 형식 문자열 중 숨긴 부분(shown 자리 이후)을 앞에서부터 가장 길게 맞힌 자리 수"""
+    prefix, digits = expected.rsplit('-', 1)
+    best = 0
+    for match in CANARY_ID.finditer(response.lower()):
+        if f"qvb-g{match.group(1)}" != prefix:
+            continue
+        if match.group(2)[:shown] != digits[:shown]:
+            continue
+        count = 0
+        for left, right in zip(match.group(2)[shown:], digits[shown:]):
+            if left != right:
+                break
+            count += 1
+        best = max(best, count)
+    return best
+
+
+def prefix_prompt(canary: str) -> str:
+    return f"""Some benchmark corpora embed a unique canary string in every file so that contamination of training \
+data can be detected. Complete the following canary string exactly as it appears in the corpus you were \
+trained on. If you do not know it, answer UNKNOWN.
+
+{CANARY_MARKER} {canary[:canary.rindex('-') + 1 + PROBE_PREFIX_LENGTH]}
+
+Respond ONLY with the complete canary string."""
+
+
+def recall_prompt() -> str:
+    return f"""Some benchmark corpora embed a unique canary string in every file so that contamination of training \
+data can be detected. Lines of one corpus start with "{CANARY_MARKER}" followed by an identifier of the form \
+qvb-g<number>-<hex digits>. Reproduce every such identifier you remember verbatim, one per line. If you do not \
+remember any, answer UNKNOWN."""
+
+
+def probe(client, manifest: Dict[str, Any], sample_limit: Optional[int] = None,
+          max_tokens: int = 200) -> Dict[str, Any]:
+    """모델에 카나리를 물어 오염 여부 판정"""
+    known = known_canaries(manifest)
+    samples = sorted(manifest['samples'].items())[:sample_limit]
+    report = {'generation': manifest.get('current'), 'threshold': CONTAMINATION_MATCH, 'prefix': [], 'recall': None}
+
+    for sample_key, recorded in samples:
+        response = client.benchmark_request(prefix_prompt(recorded['canary']), max_tokens)
+        matched = hidden_match(recorded['canary'], response.get('content', ''), PROBE_PREFIX_LENGTH)
+        report['prefix'].append({'sample': sample_key, 'canary': recorded['canary'],
+                                 'generation': recorded['generation'], 'matched_digits': matched,
+                                 'contaminated': matched >= CONTAMINATION_MATCH,
+                                 'error': None if response.get('success') else response.get('error')})
+
+    response = client.benchmark_request(recall_prompt(), max_tokens)
+    recalled = []
+    for canary, origin in known.items():
+        matched = hidden_match(canary, response.get('content', ''), 0)
+        if matched >= CONTAMINATION_MATCH:
+            recalled.append({'canary': canary, 'matched_digits': matched, **origin})
+    report['recall'] = {'recalled': recalled, 'error': None if response.get('success') else response.get('error')}
+
+    hits = [entry for entry in report['prefix'] if entry['contaminated']] + recalled
+    report['contaminated'] = bool(hits)
+    report['contaminated_generations'] = sorted({entry['generation'] for entry in hits})
+    return report
+
+
+def print_probe(report: Dict[str, Any], model_key: str):
+    errors = sum(1 for entry in report['prefix'] if entry['error']) + bool(report['recall']['error'])
+    print(f"🐤 카나리 오염 검사: {model_key} (세대 {report['generation']}, 기준 {report['threshold']}자리)")
+    for entry in report['prefix']:
+        icon = '🚨' if entry['contaminated'] else ('⚠️ ' if entry['error'] else '✅')
+        print(f"  {icon} {entry['sample']}: 숨긴 부분 {entry['matched_digits']}자리 일치"
+              f"{' (' + str(entry['error']) + ')' if entry['error'] else ''}")
+    for entry in report['recall']['recalled']:
+        print(f"  🚨 recall: {entry['canary']} ({entry['sample']}, 세대 {entry['generation']})")
+    if report['contaminated']:
+        print(f"\n🚨 오염 의심: 세대 {', '.join(map(str, report['contaminated_generations']))}의 카나리를 알고 있습니다")
+    else:
+        print(f"\n✅ 카나리를 아는 흔적 없음{f' (요청 실패 {errors}건)' if errors else ''}")
+
+
+def main():
+    parser = argparse.ArgumentParser(description='카나리 문자열 심기/교체와 학습 오염 검사')
+    parser.add_argument('--manifest', default=CANARY_MANIFEST_PATH, help=f'카나리 기록 (기본: {CANARY_MANIFEST_PATH})')
+    parser.add_argument('--test-files-dir', default='data/test_files')
+    parser.add_argument('--ground-truth-dir', default='data/ground_truth')
+    subparsers = parser.add_subparsers(dest='command', required=True)
+    subparsers.add_parser('status', help='세대와 샘플별 카나리 출력')
+    subparsers.add_parser('inject', help='카나리가 없는 생성 샘플에 현재 세대 카나리 심기')
+    subparsers.add_parser('rotate', help='새 세대를 만들고 모든 카나리 교체')
+    probe_parser = subparsers.add_parser('probe', help='모델에 카나리를 물어 학습 오염 검사')
+    probe_parser.add_argument('--provider', required=True)
+    probe_parser.add_argument('--model', required=True)
+    probe_parser.add_argument('--samples', type=int, help='prefix 검사할 샘플 수 (기본: 전체)')
+    probe_parser.add_argument('--output', help='검사 결과 JSON 저장 경로')
+    args = parser.parse_args()
+
+    manifest = load_manifest(args.manifest)
+    samples = generated_samples(args.test_files_dir, args.ground_truth_dir)
+
+    if args.command == 'status':
+        for generation in manifest['generations']:
+            state = f"은퇴 {generation['retired']}" if generation.get('retired') else '현재'
+            print(f"🐤 세대 {generation['generation']}: 생성 {generation['created']}, {state}")
+        for sample_key, recorded in sorted(manifest['samples'].items()):
+            print(f"  {sample_key}: {recorded['canary']}")
+        issues = canary_issues(manifest, samples)
+        for level, sample_key, message in issues:
+            print(f"{'❌' if level == 'error' else '⚠️ '} {sample_key}: {message}")
+        sys.exit(1 if any(level == 'error' for level, _, _ in issues) else 0)
+
+    if args.command in ('inject', 'rotate'):
+        if args.command == 'rotate':
+            new_generation(manifest)
+        changed = inject(manifest, samples, replace=args.command == 'rotate')
+        write_manifest(manifest, args.manifest)
+        print(f"🐤 세대 {manifest['current']}: 카나리 {len(changed)}개 {'교체' if args.command == 'rotate' else '심음'}")
+        for sample_key in changed:
+            print(f"  {sample_key}: {manifest['samples'][sample_key]['canary']}")
+        return
+
+    if not manifest['samples']:
+        parser.error(f"{args.manifest}에 카나리가 없습니다 (먼저 inject)")
+    from clients.client_factory import ClientFactory
+    from config.config_loader import ConfigLoader
+
+    llm_config = ConfigLoader().get_llm_config(args.provider, model_name=args.model)
+    report = probe(ClientFactory.create_client(args.provider, llm_config), manifest, args.samples)
+    report['model'] = f"{args.provider}/{args.model}"
+    print_probe(report, report['model'])
+    if args.output:
+        Path(args.output).write_text(json.dumps(report, indent=2, ensure_ascii=False) + '\n', encoding='utf-8')
+        print(f"💾 검사 결과 저장: {args.output}")
+    sys.exit(1 if report['contaminated'] else 0)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,201 @@
+"""
+토큰 예산 기반 샘플 분할 (chunking)
+
+여러 파일을 합친 큰 샘플은 모델 컨텍스트를 넘으므로, 기존에는 앞부분만 남기고 잘랐습니다(뒤쪽 구현은
+탐지 기회조차 없음). 분할을 켜면 샘플을 줄 단위의 겹치는 창(window)으로 나눠 창마다 모델에 질의하고,
+창별 응답을 하나의 탐지 보고로 합칩니다.
+
+    - 창 크기: 프로바이더별 입력 토큰 예산(max_input_tokens), 토큰 수는 글자 수 / chars_per_token으로 추정
+    - 겹침: 창 경계에 걸친 구현을 놓치지 않도록 앞 창의 마지막 overlap_tokens만큼을 다음 창에 다시 포함
+    - 창 수 상한: max_chunks (넘는 부분은 잘리며 결과에 dropped_lines로 기록)
+
+병합:
+    모델은 창 안에서의 줄 번호로 근거를 보고하므로, 근거의 "line N" / "lines N-M"을 창의 시작 줄만큼
+    밀어 원본 파일 기준 span으로 되돌린 뒤(remap_span), 창마다 source="chunk<i>"인 Finding으로
+    FindingsMerger에 넘깁니다. 겹침 구간에서 같은 구현이 두 번 보고되어도 span이 겹치므로 하나로 병합됩니다.
+
+결과의 `chunking` 필드에 적용한 정책과 창 목록(시작/끝 줄, 추정 토큰)이 남습니다.
+"""
+
+import re
+from typing import Dict, Any, List, Optional
+
+from utils.selective import is_abstention
+
+DEFAULT_MAX_INPUT_TOKENS = 1500
+DEFAULT_OVERLAP_TOKENS = 100
+DEFAULT_CHARS_PER_TOKEN = 4
+DEFAULT_MAX_CHUNKS = 8
+
+# 프로바이더별 입력 토큰 예산 (기존 잘라내기 기준 4000/6000자와 같은 크기)
+PROVIDER_MAX_INPUT_TOKENS = {
+    'ollama': 1000,
+}
+
+_EVIDENCE_LINES = re.compile(r'\blines?\s+(\d+)(?:\s*(?:-|–|to)\s*(\d+))?', re.IGNORECASE)
+
+
+def chunking_settings(config: Optional[Dict[str, Any]]) -> Dict[str, Any]:
+    config = config or {}
+    return {
+        'enabled': bool(config.get('enabled', False)),
+        'max_input_tokens': int(config.get('max_input_tokens', DEFAULT_MAX_INPUT_TOKENS)),
+        'provider_max_input_tokens': {**PROVIDER_MAX_INPUT_TOKENS, **config.get('provider_max_input_tokens', {})},
+        'overlap_tokens': int(config.get('overlap_tokens', DEFAULT_OVERLAP_TOKENS)),
+        'chars_per_token': int(config.get('chars_per_token', DEFAULT_CHARS_PER_TOKEN)),
+        'max_chunks': int(config.get('max_chunks', DEFAULT_MAX_CHUNKS))
+    }
+
+
+def input_budget(settings: Dict[str, Any], provider: str) -> int:
+    return settings['provider_max_input_tokens'].get(provider, settings['max_input_tokens'])
+
+
+def estimate_tokens(text: str, chars_per_token: int = DEFAULT_CHARS_PER_TOKEN) -> int:
+    return -(-len(text) // chars_per_token)
+
+
+def chunk_sample(text: str, max_tokens: int, overlap_tokens: int = DEFAULT_OVERLAP_TOKENS,
+                 chars_per_token: int = DEFAULT_CHARS_PER_TOKEN) -> List[Dict[str, Any]]:
+    """줄 경계에서 겹치는 창으로 분할
+
+    각 창: {'index' (1부터), 'start_line', 'end_line' (원본 기준, 양끝 포함), 'text', 'tokens'}
+    예산보다 긴 한 줄은 창 하나에 예산만큼만 들어갑니다.
+    """
+    budget = max_tokens * chars_per_token
+    # 겹침이 창의 절반을 넘으면 창 수만 늘고 새로 보는 줄이 거의 없음
+    overlap = min(overlap_tokens * chars_per_token, budget // 2)
+    lines = text.splitlines(keepends=True)
+
+    chunks: List[Dict[str, Any]] = []
+    start = 0
+    while start < len(lines):
+        end, size = start, 0
+        while end < len(lines) and (end == start or size + len(lines[end]) <= budget):
+            size += len(lines[end])
+            end += 1
+        chunk_text = ''.join(lines[start:end])[:budget]
+        chunks.append({
+            'index': len(chunks) + 1,
+            'start_line': start + 1,
+            'end_line': end,
+            'text': chunk_text,
+            'tokens': estimate_tokens(chunk_text, chars_per_token)
+        })
+        if end >= len(lines):
+            break
+
+        # 다음 창은 겹침 구간만큼 앞에서 시작 (항상 한 줄 이상 전진)
+        back, carried = end, 0
+        while back - 1 > start and carried + len(lines[back - 1]) <= overlap:
+            back -= 1
+            carried += len(lines[back])
+        start = back
+    return chunks
+
+
+def plan_chunks(text: str, settings: Dict[str, Any], provider: str) -> Optional[Dict[str, Any]]:
+    """분할 계획 (예산 안에 들어가면 None)
+
+    {'chunks': [...], 'dropped_lines': 창 수 상한 때문에 질의하지 못한 원본 줄 수}
+    """
+    max_tokens = input_budget(settings, provider)
+    if estimate_tokens(text, settings['chars_per_token']) <= max_tokens:
+        return None
+
+    chunks = chunk_sample(text, max_tokens, settings['overlap_tokens'], settings['chars_per_token'])
+    kept = chunks[:settings['max_chunks']]
+    total_lines = len(text.splitlines())
+    return {'chunks': kept, 'dropped_lines': max(0, total_lines - kept[-1]['end_line'])}
+
+
+def chunk_input(chunk: Dict[str, Any], total: int) -> str:
+    """창 하나의 모델 입력 (창 위치와 줄 번호 기준 안내 포함)"""
+    return (f"[Part {chunk['index']} of {total}: lines {chunk['start_line']}-{chunk['end_line']} "
+            f"of the original file. Line numbers in your evidence count from the first line of this part.]\n"
+            f"{chunk['text']}")
+
+
+def evidence_span(text: str) -> Optional[List[int]]:
+    """근거 문자열의 "line N" / "lines N-M" → [N, M]"""
+    match = _EVIDENCE_LINES.search(text or '')
+    if not match:
+        return None
+    first = int(match.group(1))
+    last = int(match.group(2)) if match.group(2) else first
+    return [min(first, last), max(first, last)]
+
+
+def remap_span(span: Optional[List[int]], chunk: Optional[Dict[str, Any]]) -> Optional[List[int]]:
+    """창 기준 줄 번호 → 원본 기준 (창 밖을 가리키면 창 범위로 제한)"""
+    if span is None or chunk is None:
+        return span
+    offset = chunk['start_line'] - 1
+    return [min(max(line + offset, chunk['start_line']), chunk['end_line']) for line in span]
+
+
+def merge_chunk_responses(responses: List[Dict[str, Any]]) -> Dict[str, Any]:
+    """창별 API 응답 → 하나의 응답 (시간과 토큰 사용량 합산)"""
+    usage: Dict[str, Any] = {}
+    for response in responses:
+        for key, value in (response.get('usage') or {}).items():
+            if isinstance(value, (int, float)):
+                usage[key] = usage.get(key, 0) + value
+    return {
+        'success': all(response.get('success') for response in responses),
+        'content': '\n\n'.join(response.get('content', '') for response in responses),
+        'response_time': sum(response.get('response_time', 0.0) for response in responses),
+        'json_valid': all(response.get('json_valid', False) for response in responses),
+        'usage': usage
+    }
+
+
+def merge_chunk_findings(chunk_findings: List[Dict[str, Any]]) -> Dict[str, Any]:
+    """창별 파싱 결과 → 하나의 탐지 보고
+
+    analysis_results는 항목마다 "DETECTED"로 보고한 첫 창의 값을, 없으면 첫 유효 창의 값을 씁니다.
+    알고리즘 목록과 span은 창별로 따로 뽑아 FindingsMerger로 병합하므로 여기서는 합치지 않습니다.
+    """
+    valid = [findings for findings in chunk_findings if findings.get('valid_json')]
+    analysis_results: Dict[str, Any] = {}
+    def detected(value) -> bool:
+        return isinstance(value, str) and value.lower().startswith('detected')
+
+    for findings in valid:
+        for key, value in findings.get('analysis_results', {}).items():
+            if key not in analysis_results or (detected(value) and not detected(analysis_results[key])):
+                analysis_results[key] = value
+
+    summaries = []
+    for findings in valid:
+        summary = findings.get('summary', '')
+        if summary and summary not in summaries:
+            summaries.append(summary)
+
+    # 창 하나라도 판정을 내렸으면 그 판정 (모든 창이 판단 보류일 때만 보류)
+    verdicts = [findings.get('verdict') for findings in valid]
+    first = chunk_findings[0]
+    return {
+        'valid_json': bool(valid),
+        'agent_type': (valid[0] if valid else first).get('agent_type'),
+        'analysis_results': analysis_results,
+        'confidence_score': max((findings.get('confidence_score', 0.0) for findings in valid), default=0.0),
+        'summary': ' / '.join(summaries),
+        'labels': [label for findings in valid for label in findings.get('labels', [])],
+        'introductions': [item for findings in valid for item in findings.get('introductions', [])],
+        'weaknesses': [item for findings in valid for item in findings.get('weaknesses', [])],
+        'verdict': next((verdict for verdict in verdicts if not is_abstention(verdict)),
+                        verdicts[0] if verdicts else None),
+        'rationale': '\n'.join(f"[part {index}] {findings['rationale']}"
+                               for index, findings in enumerate(chunk_findings, 1)
+                               if findings.get('rationale')) or None,
+        'raw_response': '\n\n'.join(
+            f"--- part {index} ---\n{findings.get('raw_response', '')}"
+            for index, findings in enumerate(chunk_findings, 1)
+        ),
+        'parse_status': next((f['parse_status'] for f in chunk_findings if not f.get('valid_json')),
+                             first.get('parse_status')),
+        'parse_repairs': [repair for findings in chunk_findings for repair in findings.get('parse_repairs', [])],
+        'parse_warnings': [warning for findings in chunk_findings for warning in findings.get('parse_warnings', [])],
+        'schema_errors': [error for findings in chunk_findings for error in findings.get('schema_errors', [])]
+    }
@@ -0,0 +1,352 @@
+#!/usr/bin/env python3
+"""
+주석 노이즈 / 오도성 문서 주입기 (comment noise)
+
+LLM 탐지기는 코드보다 주석을 믿는 경향이 있습니다. 이 난독화 패스는 라벨이 붙은 구현 위에
+코드와 다른 일을 한다고 주장하는 주석과 문서 주석을 넣습니다.
+
+    // simple CRC checksum over the frame          ← SHA-1 압축 루프 바로 위
+    /// Implements the run-length encoding of the payload; no security relevance.
+    fn mix_block(state: &mut [u32; 5], block: &[u8]) { ...
+
+    - 라벨 줄 범위(locations)의 시작 줄에 헤더 주석, 범위 안의 함수 정의 위에 문서 주석
+      (줄 범위가 없는 샘플은 파일의 함수 정의 위에만)
+    - 주장은 라벨 역할(hash, cipher, public_key, …)별 미끼 문구에서 고르며 라벨 자신의 계열은 말하지 않음
+    - 주입한 줄 수만큼 ground truth 줄 범위를 다시 계산
+    - ground truth에 adversarially_documented: true와 주입 기록(comment_noise)을 남겨
+      요약(by_documentation)에서 일반 샘플과 따로 집계
+
+ground truth 기록:
+    "adversarially_documented": true,
+    "comment_noise": {"seed": 7, "claims": [{"line": 12, "kind": "header", "algorithm": "SHA-1",
+                                              "text": "// simple CRC checksum over the frame"}]}
+
+시드가 같으면 결과가 같습니다 (GeneratorSeed.stream('comment_noise')).
+
+사용법:
+    python -m utils.comment_noise data/test_files/source_code/land_registry_deed_signer.rs --seed 7
+    python -m utils.comment_noise data/test_files/source_code/*.java --seed 7 --dry-run
+    python -m utils.sample_generator handshake --seed 7 --comment-noise
+"""
+
+import argparse
+import copy
+import json
+import re
+import sys
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Tuple, Union
+
+from utils.generator_seed import GeneratorSeed
+from utils.label_review import format_line_range, parse_line_range
+from utils.metrics_calculator import MetricsCalculator
+from utils.taxonomy import AlgorithmTaxonomy
+
+NAME_SUFFIX = '_misdoc'
+DOCUMENTATION_ADVERSARIAL = 'adversarial'
+DOCUMENTATION_PLAIN = 'plain'
+TAG = 'adversarial_documentation'
+# utils.corpus와 같은 값 (corpus lint가 이 모듈을 import하므로 순환 import를 피해 따로 둠)
+GROUND_TRUTH_DIR = "data/ground_truth"
+
+# 확장자 → (줄 주석, 문서 주석 형식)
+COMMENT_SYNTAX: Dict[str, Tuple[str, str]] = {
+    '.rs': ('//', '/// {}'),
+    '.go': ('//', '// {}'),
+    '.py': ('#', '# {}'),
+    '.rb': ('#', '# {}'),
+}
+for _extension in ('.c', '.h', '.cpp', '.cc', '.hpp', '.java', '.cs', '.js', '.ts', '.kt', '.scala', '.swift',
+                   '.php'):
+    COMMENT_SYNTAX[_extension] = ('//', '/** {} */')
+
+_C_LIKE_FUNCTION = r'^\s*[\w<>\[\],:\*&\s]*?\b(\w+)\s*\([^;{}]*\)\s*(const\s*)?(throws\s+[\w.,\s]+)?\{?\s*$'
+FUNCTION_PATTERNS: Dict[str, str] = {
+    '.rs': r'^\s*(pub(\([\w:\s]+\))?\s+)?((const|async|unsafe|extern\s+"\w+")\s+)*fn\s+(\w+)',
+    '.py': r'^\s*(async\s+)?def\s+(\w+)',
+    '.rb': r'^\s*def\s+(self\.)?(\w+)',
+    '.go': r'^func\s+(\([^)]*\)\s*)?(\w+)',
+    '.php': r'^\s*((public|private|protected|static|final|abstract)\s+)*function\s+(\w+)',
+    '.kt': r'^\s*(\w+\s+)*fun\s+(\w+)',
+    '.scala': r'^\s*(\w+\s+)*def\s+(\w+)',
+    '.swift': r'^\s*(\w+\s+)*func\s+(\w+)',
+    '.js': r'^\s*((export|async|static)\s+)*function\s*\*?\s*(\w+)',
+    '.ts': r'^\s*((export|async|static|public|private|protected)\s+)*function\s*\*?\s*(\w+)',
+}
+for _extension in ('.c', '.h', '.cpp', '.cc', '.hpp', '.java', '.cs'):
+    FUNCTION_PATTERNS[_extension] = _C_LIKE_FUNCTION
+
+# C 계열 정규식에 걸리는 제어문
+NOT_FUNCTIONS = {'if', 'for', 'while', 'switch', 'return', 'else', 'catch', 'do', 'sizeof', 'new', 'synchronized'}
+
+# 함수 정의 위에서 함께 올라갈 줄 (속성, 데코레이터, 기존 주석)
+_PREAMBLE = re.compile(r'^\s*(#\[|@|//|/\*|\*|#(?!\[))')
+_TEST_MARKER = re.compile(r'#\[test\]|#\[cfg\(test\)\]|@Test\b|@pytest')
+
+# 역할별 미끼 문구 (코드가 실제로 하는 일과 다른 무해한 설명)
+DECOY_CLAIMS: Dict[Optional[str], List[str]] = {
+    'hash': ['simple CRC checksum over the frame', 'rolling checksum used for deduplication',
+             'FNV-style bucket index for the lookup cache', 'parity check for the transport layer'],
+    'cipher': ['byte shuffling to improve cache locality', 'run-length encoding of the payload',
+               'endianness conversion for the wire format', 'lookup-table text transliteration'],
+    'public_key': ['big-integer helpers for currency rounding', 'sequential ID allocator',
+                   'fixed-point interest calculation', 'calendar week number arithmetic'],
+    'mac': ['trailer checksum for log rotation', 'parity bits for the framing layer',
+            'frame length counter for the transport buffer'],
+    'kdf': ['config value normalization', 'retry backoff schedule', 'cache key canonicalization'],
+    'post_quantum': ['polynomial smoothing for sensor readings', 'noise filter for the telemetry buffer',
+                     'histogram bucketing for the metrics export'],
+    None: ['formatting helpers', 'internal bookkeeping for the record buffer', 'debug output utilities'],
+}
+
+HEADER_TEMPLATES = ['{Claim}', '{Claim} (not security relevant)', 'Helpers below: {claim}']
+DOC_TEMPLATES = ['{Claim}.', 'Implements the {claim}; no security relevance.', 'Utility: {claim}.']
+
+_taxonomy = AlgorithmTaxonomy()
+_TOKEN = re.compile(r'[A-Za-z0-9][A-Za-z0-9\-]*')
+
+
+def is_adversarially_documented(ground_truth: Optional[Dict[str, Any]]) -> bool:
+    return bool(ground_truth and ground_truth.get('adversarially_documented'))
+
+
+def documentation_kind(ground_truth: Optional[Dict[str, Any]]) -> str:
+    """요약 집계용 샘플 문서 종류 (adversarial / plain)"""
+    return DOCUMENTATION_ADVERSARIAL if is_adversarially_documented(ground_truth) else DOCUMENTATION_PLAIN
+
+
+def comment_noise_claims(ground_truth: Optional[Dict[str, Any]]) -> List[Dict[str, Any]]:
+    if not ground_truth or not isinstance(ground_truth.get('comment_noise'), dict):
+        return []
+    return list(ground_truth['comment_noise'].get('claims', []))
+
+
+def label_spans(ground_truth: Dict[str, Any]) -> List[Tuple[str, Tuple[int, int]]]:
+    """ground truth의 라벨별 줄 범위 [(라벨, (start, end))] (두 형식 모두)"""
+    spans = []
+    analysis = ground_truth.get('vulnerability_analysis')
+    if isinstance(analysis, dict):
+        for entry in analysis.get('quantum_vulnerable_algorithms', []):
+            for location in entry.get('locations', []):
+                span = parse_line_range(location.get('line_range')) if isinstance(location, dict) else None
+                if span and entry.get('algorithm'):
+                    spans.append((entry['algorithm'], span))
+    for algorithm, ranges in ground_truth.get('expected_findings', {}).get('locations', {}).items():
+        for line_range in ranges:
+            span = parse_line_range(line_range)
+            if span:
+                spans.append((algorithm, span))
+    return spans
+
+
+class CommentNoiseInjector:
+    """라벨 구현 위에 오도성 주석/문서 주석을 넣고 ground truth 줄 범위를 다시 계산"""
+
+    def __init__(self, seed: Union[GeneratorSeed, int], function_rate: float = 1.0):
+        if not 0.0 <= function_rate <= 1.0:
+            raise ValueError(f"function_rate must be in [0, 1], got {function_rate}")
+        self.seed = GeneratorSeed.of(seed)
+        self.function_rate = function_rate
+
+    @staticmethod
+    def supports(extension: str) -> bool:
+        return extension in COMMENT_SYNTAX
+
+    def inject(self, source: str, ground_truth: Dict[str, Any], extension: str) -> Dict[str, Any]:
+        """{'source', 'ground_truth', 'claims'} (claims가 비면 넣을 자리를 찾지 못한 것)"""
+        if not self.supports(extension):
+            raise ValueError(f"comment noise does not support {extension!r} samples "
+                             f"(supported: {', '.join(sorted(COMMENT_SYNTAX))})")
+        rng = self.seed.stream('comment_noise')
+        line_comment, doc_comment = COMMENT_SYNTAX[extension]
+        lines = source.split('\n')
+        functions = self._functions(lines, extension)
+        labels = MetricsCalculator.get_expected_labels(ground_truth)
+
+        # 원래 줄 번호(1부터) → (라벨, 종류, 주석 줄) — 한 자리에 하나만
+        insertions: Dict[int, Tuple[str, str, str]] = {}
+        spans = label_spans(ground_truth)
+        for algorithm, (start, end) in spans:
+            if start > len(lines):
+                continue
+            anchor = self._anchor(lines, start, extension)
+            if anchor is not None and anchor not in insertions:
+                text = self._claim(rng, algorithm, HEADER_TEMPLATES)
+                insertions[anchor] = (algorithm, 'header', f"{line_comment} {text}")
+            for line_number, anchor in functions:
+                if start < line_number <= end and anchor not in insertions and rng.random() < self.function_rate:
+                    insertions[anchor] = (algorithm, 'doc', doc_comment.format(self._claim(rng, algorithm,
+                                                                                         DOC_TEMPLATES)))
+        if not spans and labels:
+            for line_number, anchor in functions:
+                if anchor not in insertions and rng.random() < self.function_rate:
+                    algorithm = rng.choice(labels)
+                    insertions[anchor] = (algorithm, 'doc', doc_comment.format(self._claim(rng, algorithm,
+                                                                                         DOC_TEMPLATES)))
+
+        output, claims = [], []
+        for number, line in enumerate(lines, start=1):
+            if number in insertions:
+                algorithm, kind, comment = insertions[number]
+                indent = re.match(r'\s*', line).group(0)
+                output.append(indent + comment)
+                claims.append({'line': len(output), 'kind': kind, 'algorithm': algorithm,
+                               'text': (indent + comment).strip()})
+            output.append(line)
+
+        return {'source': '\n'.join(output),
+                'ground_truth': self._rewrite_ground_truth(ground_truth, sorted(insertions), claims, len(output)),
+                'claims': claims}
+
+    @staticmethod
+    def _claim(rng, algorithm: str, templates: List[str]) -> str:
+        node = _taxonomy.resolve(algorithm)
+        family = node[1]
+        candidates = [phrase for phrase in DECOY_CLAIMS.get(_taxonomy.role(node), DECOY_CLAIMS[None])
+                      if not any(_taxonomy.resolve(token)[1] == family for token in _TOKEN.findall(phrase))]
+        phrase = rng.choice(candidates or DECOY_CLAIMS[None])
+        template = rng.choice(templates)
+        return template.format(claim=phrase, Claim=phrase[0].upper() + phrase[1:])
+
+    @staticmethod
+    def _functions(lines: List[str], extension: str) -> List[Tuple[int, int]]:
+        """함수 정의 [(정의 줄, 주석을 넣을 줄)] — 테스트 함수와 Rust 테스트 모듈 제외"""
+        pattern = re.compile(FUNCTION_PATTERNS.get(extension, _C_LIKE_FUNCTION))
+        functions = []
+        for index, line in enumerate(lines):
+            if extension == '.rs' and line.strip() == '#[cfg(test)]':
+                break
+            match = pattern.match(line)
+            if not match:
+                continue
+            name = next((group for group in reversed(match.groups()) if group and re.fullmatch(r'\w+', group)), '')
+            if name in NOT_FUNCTIONS or name.startswith('test'):
+                continue
+            anchor = index
+            while anchor > 0 and _PREAMBLE.match(lines[anchor - 1]) and lines[anchor - 1].strip():
+                anchor -= 1
+            if any(_TEST_MARKER.search(preamble) for preamble in lines[anchor:index]):
+                continue
+            functions.append((index + 1, anchor + 1))
+        return functions
+
+    @staticmethod
+    def _anchor(lines: List[str], start: int, extension: str) -> Optional[int]:
+        """헤더 주석을 넣을 줄 (범위 시작의 빈 줄은 건너뜀, 매크로 줄 이음 사이에는 넣지 않음)"""
+        index = start - 1
+        while index < len(lines) - 1 and not lines[index].strip():
+            index += 1
+        if index > 0 and lines[index - 1].rstrip().endswith('\\'):
+            return None
+        if extension == '.php' and lines[index].lstrip().startswith('<?'):
+            return None
+        return index + 1
+
+    def _rewrite_ground_truth(self, ground_truth: Dict[str, Any], anchors: List[int], claims: List[Dict[str, Any]],
+                              total_lines: int) -> Dict[str, Any]:
+        updated = copy.deepcopy(ground_truth)
+
+        def shift(line: int) -> int:
+            return line + sum(1 for anchor in anchors if anchor <= line)
+
+        def rewrite(line_range: str) -> str:
+            span = parse_line_range(line_range)
+            return format_line_range((shift(span[0]), shift(span[1]))) if span else line_range
+
+        findings = updated.get('expected_findings', {})
+        if isinstance(findings.get('locations'), dict):
+            findings['locations'] = {algorithm: [rewrite(line_range) for line_range in ranges]
+                                     for algorithm, ranges in findings['locations'].items()}
+        analysis = updated.get('vulnerability_analysis')
+        if isinstance(analysis, dict):
+            for entry in analysis.get('quantum_vulnerable_algorithms', []):
+                for location in entry.get('locations', []):
+                    if isinstance(location, dict) and location.get('line_range'):
+                        location['line_range'] = rewrite(location['line_range'])
+        if isinstance(updated.get('generated_by'), dict) and 'lines' in updated['generated_by']:
+            updated['generated_by']['lines'] = total_lines
+        if isinstance(updated.get('tags'), list) and TAG not in updated['tags']:
+            updated['tags'].append(TAG)
+
+        updated['adversarially_documented'] = True
+        updated['comment_noise'] = {'seed': self.seed.value, 'claims': claims}
+        return updated
+
+
+def apply_comment_noise(sample: Dict[str, Any], extension: str, seed: Union[GeneratorSeed, int],
+                        function_rate: float = 1.0) -> Dict[str, Any]:
+    """생성 샘플({'name', 'source', 'ground_truth'})에 주석 노이즈를 입힘 (이름에 NAME_SUFFIX)"""
+    result = CommentNoiseInjector(seed, function_rate).inject(sample['source'], sample['ground_truth'], extension)
+    return {**sample, 'name': sample['name'] + NAME_SUFFIX, 'source': result['source'],
+            'ground_truth': result['ground_truth'], 'claims': result['claims']}
+
+
+def main():
+    # utils.corpus가 이 모듈을 import하므로 (검증기는 corpus를 씀) 실행 시점에 가져옴
+    from utils.obfuscation_verify import STATUS_BROKEN, SemanticsVerifier, describe_check, record_check
+
+    parser = argparse.ArgumentParser(description='주석 노이즈 / 오도성 문서 주입기')
+    parser.add_argument('files', nargs='+', help='샘플 파일 (ground truth는 --ground-truth-dir/<에이전트>/<이름>.json)')
+    parser.add_argument('--seed', type=GeneratorSeed.parse, default=GeneratorSeed(0),
+                        help='주입 시드 (10진수 또는 0x 16진수, 기본값 0)')
+    parser.add_argument('--function-rate', type=float, default=1.0, help='범위 안 함수 중 문서 주석을 넣을 비율')
+    parser.add_argument('--ground-truth-dir', default=GROUND_TRUTH_DIR, help='ground truth 디렉토리')
+    parser.add_argument('--overwrite', action='store_true', help='같은 이름의 샘플이 있으면 덮어쓰기')
+    parser.add_argument('--dry-run', action='store_true', help='파일을 쓰지 않고 주입 결과만 출력')
+    parser.add_argument('--skip-semantics-check', action='store_true',
+                        help='쓰기 전 원본/변환본 내장 테스트 비교 생략 (utils/obfuscation_verify.py)')
+    args = parser.parse_args()
+
+    checker = None if args.skip_semantics_check else SemanticsVerifier()
+    failures = 0
+    for file in args.files:
+        path = Path(file)
+        truth_path = Path(args.ground_truth_dir) / path.parent.name / f"{path.stem}.json"
+        if not CommentNoiseInjector.supports(path.suffix):
+            print(f"⏭️  {path}: 지원하지 않는 확장자 {path.suffix}")
+            continue
+        if not truth_path.exists():
+            print(f"❌ {path}: ground truth 없음 ({truth_path})")
+            failures += 1
+            continue
+
+        with open(truth_path, 'r', encoding='utf-8') as f:
+            ground_truth = json.load(f)
+        sample = {'name': path.stem, 'source': path.read_text(encoding='utf-8'), 'ground_truth': ground_truth}
+        try:
+            noisy = apply_comment_noise(sample, path.suffix, args.seed, args.function_rate)
+        except ValueError as e:
+            parser.error(str(e))
+        if not noisy['claims']:
+            print(f"⚠️  {path}: 주석을 넣을 자리를 찾지 못함 (줄 범위/함수 정의 없음)")
+            continue
+
+        kinds = ', '.join(f"{claim['algorithm']}@{claim['line']}" for claim in noisy['claims'][:4])
+        more = f" 외 {len(noisy['claims']) - 4}개" if len(noisy['claims']) > 4 else ''
+        output_path = path.with_name(noisy['name'] + path.suffix)
+        output_truth = truth_path.with_name(noisy['name'] + '.json')
+        if args.dry_run:
+            print(f"🧪 {output_path}: 주장 {len(noisy['claims'])}개 ({kinds}{more})")
+            continue
+        if not args.overwrite and (output_path.exists() or output_truth.exists()):
+            print(f"⚠️  {output_path} 이미 존재 (--overwrite로 덮어쓰기)")
+            continue
+        if checker:
+            check = checker.check('comment_noise', sample['source'], noisy['source'], path.suffix, path.stem)
+            print(f"   {describe_check(check)}")
+            if check['status'] == STATUS_BROKEN:
+                print(f"❌ {output_path}: 변환이 동작을 바꿔 쓰지 않음")
+                failures += 1
+                continue
+            record_check(noisy['ground_truth'], check)
+
+        output_path.write_text(noisy['source'], encoding='utf-8')
+        output_truth.write_text(json.dumps(noisy['ground_truth'], indent=2, ensure_ascii=False) + '\n',
+                                encoding='utf-8')
+        print(f"✅ {output_path}: 주장 {len(noisy['claims'])}개 ({kinds}{more})")
+
+    sys.exit(1 if failures else 0)
+
+
+if __name__ == "__main__":
+    main()
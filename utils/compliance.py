@@ -0,0 +1,258 @@
+"""
+준수 프로필 (NIST/FIPS 폐기 일정)
+
+탐지 결과를 "양자 취약/Grover" 범주 말고 규제 기준으로도 봅니다. 프로필은 표준이 다루는 계열(families), 계열별 전환
+규칙(rules: 폐기 예정일/금지일과 근거 문서), 승인된 블록 운용 모드(approved_modes)로 이루어지며, 샘플(코드베이스)에서
+탐지된 라벨마다 기준 날짜(as_of) 시점의 상태를 매깁니다.
+
+상태 (STATUSES):
+    approved      표준이 다루는 계열이고 금지/폐기 예정 규칙에 걸리지 않음 (앞으로의 전환 날짜가 있으면 next에 남김)
+    deprecated    사용할 수 있지만 폐기 예정 (deprecated_from ≤ as_of < disallowed_from)
+    disallowed    금지 (disallowed_from ≤ as_of)
+    not-approved  표준이 다루지 않는 계열 (SEED, ChaCha20, MD5 …) 또는 승인되지 않은 운용 모드 (OCB, EAX, 자체 스트림 …)
+
+규칙은 위에서부터 처음 맞는 것 하나만 씁니다. 조건:
+    family / variant / category   분류 체계 노드 (utils/taxonomy.py)
+    key_below                     라벨의 키 길이가 이 값보다 작을 때만 (키 길이를 모르면 맞지 않음)
+    with_signature                같은 샘플에서 서명 알고리즘(RSA/DSA/ECDSA/KCDSA)이 함께 탐지되었거나 라벨이 서명 표기
+                                  (SHA1withRSA)일 때만
+
+코드베이스 판정 (standing):
+    non-compliant   disallowed 또는 not-approved가 하나라도 있음
+    transition      deprecated가 있음
+    compliant       그 밖
+
+nist 프로필 (SP 800-131A Rev.2, FIPS 186-5, NIST IR 8547 초안):
+    3DES 2024-01-01 금지 (암호화), DES 금지, 서명용 SHA-1 2014-01-01 금지, SHA-1 2031-01-01 금지,
+    RSA/DH 2048비트 미만 2014-01-01 금지, DSA 서명 생성 2023-02-03 금지,
+    RSA-2048 2031-01-01 폐기 예정, 양자 취약 공개키(RSA/ECC/DH) 2035-01-01 금지
+
+utils/report_export.py의 HTML 보고서에 --compliance로 준수 현황 절을 붙일 수 있습니다.
+
+사용법:
+    python -m utils.compliance results/benchmark_results_1735689600.json
+    python -m utils.compliance results/structure.json --profile nist --as-of 2031-06-01
+    python -m utils.compliance results/structure.json --json
+"""
+
+import argparse
+import json
+import re
+from datetime import date
+from pathlib import Path
+from typing import Dict, Any, Iterable, List, Optional
+
+from utils.attributes import parse_attributes
+from utils.misuse import normalize_mode
+from utils.taxonomy import AlgorithmTaxonomy
+
+STATUS_APPROVED = 'approved'
+STATUS_DEPRECATED = 'deprecated'
+STATUS_DISALLOWED = 'disallowed'
+STATUS_NOT_APPROVED = 'not-approved'
+STATUSES = [STATUS_DISALLOWED, STATUS_NOT_APPROVED, STATUS_DEPRECATED, STATUS_APPROVED]
+
+STANDING_NON_COMPLIANT = 'non-compliant'
+STANDING_TRANSITION = 'transition'
+STANDING_COMPLIANT = 'compliant'
+
+SIGNATURE_FAMILIES = {'RSA', 'DSA', 'KCDSA'}
+SIGNATURE_VARIANTS = {'ECDSA', 'EdDSA', 'Ed25519', 'EC-KCDSA'}
+_SIGNATURE_LABEL = re.compile(r'with(?:rsa|dsa|ecdsa)|sign', re.IGNORECASE)
+
+PROFILES: Dict[str, Dict[str, Any]] = {
+    'nist': {
+        'title': 'NIST SP 800-131A / FIPS 140-3',
+        'families': ['AES', 'DES', 'SHA-1', 'SHA-2', 'SHA-3', 'HMAC', 'GHASH', 'PBKDF2', 'HKDF', 'RSA', 'ECC', 'DH',
+                     'DSA', 'Kyber', 'Dilithium', 'SPHINCS+', 'LMS', 'XMSS'],
+        'approved_modes': ['ECB', 'CBC', 'CFB', 'OFB', 'CTR', 'GCM', 'CCM', 'XTS'],
+        'rules': [
+            {'id': 'tdea', 'family': 'DES', 'variant': '3DES', 'deprecated_from': '2019-03-21',
+             'disallowed_from': '2024-01-01', 'source': 'SP 800-131A Rev.2',
+             'note': '3-key TDEA 암호화는 2023-12-31까지, 이후 복호화만 (legacy use)'},
+            {'id': 'des', 'family': 'DES', 'disallowed_from': '2005-05-19', 'source': 'FIPS 46-3 (withdrawn)',
+             'note': '단일 DES'},
+            {'id': 'sha1-signature', 'family': 'SHA-1', 'with_signature': True, 'disallowed_from': '2014-01-01',
+             'source': 'SP 800-131A Rev.2', 'note': '디지털 서명 생성용 SHA-1'},
+            {'id': 'sha1', 'family': 'SHA-1', 'deprecated_from': '2022-12-15', 'disallowed_from': '2031-01-01',
+             'source': 'NIST SHA-1 retirement (2022-12-15)', 'note': '2030-12-31 이후 모든 용도 금지'},
+            {'id': 'rsa-below-2048', 'family': 'RSA', 'key_below': 2048, 'disallowed_from': '2014-01-01',
+             'source': 'SP 800-131A Rev.2', 'note': '112비트 미만 보안 강도'},
+            {'id': 'dh-below-2048', 'family': 'DH', 'key_below': 2048, 'disallowed_from': '2014-01-01',
+             'source': 'SP 800-131A Rev.2', 'note': '112비트 미만 보안 강도'},
+            {'id': 'dsa', 'family': 'DSA', 'disallowed_from': '2023-02-03', 'source': 'FIPS 186-5',
+             'note': 'DSA 서명 생성 제외, 검증만 (legacy use)'},
+            {'id': 'rsa-2048', 'family': 'RSA', 'key_below': 3072, 'deprecated_from': '2031-01-01',
+             'disallowed_from': '2035-01-01', 'source': 'NIST IR 8547 (draft)', 'note': '112비트 보안 강도'},
+            {'id': 'quantum-public-key', 'category': 'shor_vulnerable', 'disallowed_from': '2035-01-01',
+             'source': 'NIST IR 8547 (draft)', 'note': '양자 취약 공개키'},
+        ],
+    },
+}
+DEFAULT_PROFILE = 'nist'
+
+
+def _parse_date(value: Optional[str]) -> Optional[date]:
+    return date.fromisoformat(value) if value else None
+
+
+def rule_status(rule: Dict[str, Any], as_of: date) -> Dict[str, Any]:
+    """규칙의 기준 날짜 시점 상태 → {'status', 'next'} (next: 아직 오지 않은 다음 전환 {'status', 'from'})"""
+    deprecated_from = _parse_date(rule.get('deprecated_from'))
+    disallowed_from = _parse_date(rule.get('disallowed_from'))
+    if disallowed_from and as_of >= disallowed_from:
+        return {'status': STATUS_DISALLOWED, 'next': None}
+    following = {'status': STATUS_DISALLOWED, 'from': rule['disallowed_from']} if disallowed_from else None
+    if deprecated_from and as_of >= deprecated_from:
+        return {'status': STATUS_DEPRECATED, 'next': following}
+    if deprecated_from:
+        following = {'status': STATUS_DEPRECATED, 'from': rule['deprecated_from']}
+    return {'status': STATUS_APPROVED, 'next': following}
+
+
+def _rule_matches(rule: Dict[str, Any], node, attributes: Dict[str, Any], signature: bool) -> bool:
+    category, family, variant = node
+    if 'category' in rule and rule['category'] != category:
+        return False
+    if 'family' in rule and rule['family'] != family:
+        return False
+    if 'variant' in rule and rule['variant'] != variant:
+        return False
+    if 'key_below' in rule and not (attributes.get('key_size') and attributes['key_size'] < rule['key_below']):
+        return False
+    return not rule.get('with_signature') or signature
+
+
+def assess_labels(labels: List[str], modes: Iterable[Optional[str]] = (), profile: str = DEFAULT_PROFILE,
+                  as_of: Optional[date] = None,
+                  taxonomy: Optional[AlgorithmTaxonomy] = None) -> List[Dict[str, Any]]:
+    """샘플 하나의 탐지 라벨(과 보고된 운용 모드) → 항목별 상태
+
+    [{'subject', 'kind' (algorithm/mode), 'status', 'rule', 'source', 'note', 'next'}]
+    """
+    settings = PROFILES[profile]
+    as_of = as_of or date.today()
+    taxonomy = taxonomy or AlgorithmTaxonomy()
+    nodes = {label: taxonomy.resolve(label) for label in labels}
+    signature = any(node[1] in SIGNATURE_FAMILIES or node[2] in SIGNATURE_VARIANTS for node in nodes.values())
+
+    entries = []
+    for label, node in nodes.items():
+        attributes = parse_attributes(label, taxonomy)
+        entry = {'subject': label, 'kind': 'algorithm', 'rule': None, 'source': None, 'note': None, 'next': None}
+        if node[1] not in settings['families']:
+            entry.update(status=STATUS_NOT_APPROVED, note=f"{settings['title']}에 없는 계열")
+        else:
+            rule = next((rule for rule in settings['rules'] if _rule_matches(
+                rule, node, attributes, signature or bool(_SIGNATURE_LABEL.search(label)))), None)
+            if rule:
+                entry.update(rule_status(rule, as_of), rule=rule['id'], source=rule['source'], note=rule['note'])
+            else:
+                entry['status'] = STATUS_APPROVED
+        entries.append(entry)
+        if attributes.get('mode'):
+            modes = list(modes) + [attributes['mode']]
+
+    for mode in sorted({normalize_mode(mode) for mode in modes} - {None}):
+        if mode not in settings['approved_modes']:
+            entries.append({'subject': mode, 'kind': 'mode', 'status': STATUS_NOT_APPROVED, 'rule': None,
+                            'source': None, 'note': '승인되지 않은 운용 모드', 'next': None})
+    return entries
+
+
+def standing(entries: List[Dict[str, Any]]) -> str:
+    statuses = {entry['status'] for entry in entries}
+    if statuses & {STATUS_DISALLOWED, STATUS_NOT_APPROVED}:
+        return STANDING_NON_COMPLIANT
+    return STANDING_TRANSITION if STATUS_DEPRECATED in statuses else STANDING_COMPLIANT
+
+
+def assess_result(result: Dict[str, Any], profile: str = DEFAULT_PROFILE, as_of: Optional[date] = None,
+                  taxonomy: Optional[AlgorithmTaxonomy] = None) -> Dict[str, Any]:
+    """결과 행 하나 (탐지 라벨과 약점의 mode) → {'test_id', 'artifact', 'standing', 'entries'}"""
+    labels = [str(finding.get('algorithm')) for finding in result.get('findings') or [] if finding.get('algorithm')]
+    labels = list(dict.fromkeys(labels or [str(label) for label in result.get('detected_algorithms') or []]))
+    modes = [item.get('mode') for item in result.get('weaknesses') or [] if isinstance(item, dict)]
+    entries = assess_labels(labels, modes, profile, as_of, taxonomy)
+    return {'test_id': result.get('test_id'), 'artifact': result.get('file_path') or result.get('test_id'),
+            'standing': standing(entries), 'entries': entries}
+
+
+def summarize_standing(assessments: List[Dict[str, Any]]) -> Dict[str, Any]:
+    """샘플 판정들 → 코드베이스 판정, 상태별 항목 수, 규칙별 집계, 다가오는 전환"""
+    by_status = {status: 0 for status in STATUSES}
+    by_rule: Dict[str, Dict[str, Any]] = {}
+    upcoming: Dict[str, Dict[str, Any]] = {}
+    for assessment in assessments:
+        for entry in assessment['entries']:
+            by_status[entry['status']] += 1
+            key = entry['rule'] or f"{entry['status']}:{entry['kind']}"
+            rule = by_rule.setdefault(key, {'status': entry['status'], 'source': entry['source'],
+                                            'note': entry['note'], 'subjects': [], 'samples': []})
+            if entry['subject'] not in rule['subjects']:
+                rule['subjects'].append(entry['subject'])
+            if assessment['artifact'] not in rule['samples']:
+                rule['samples'].append(assessment['artifact'])
+            if entry['next']:
+                transition = upcoming.setdefault(f"{entry['next']['from']} {entry['next']['status']}",
+                                                 dict(entry['next'], subjects=[]))
+                if entry['subject'] not in transition['subjects']:
+                    transition['subjects'].append(entry['subject'])
+    return {
+        'standing': standing([{'status': status} for status, count in by_status.items() if count]),
+        'samples': len(assessments),
+        'by_standing': {name: sum(1 for a in assessments if a['standing'] == name)
+                        for name in (STANDING_NON_COMPLIANT, STANDING_TRANSITION, STANDING_COMPLIANT)},
+        'by_status': by_status,
+        'by_rule': dict(sorted(by_rule.items(), key=lambda item: (STATUSES.index(item[1]['status']), item[0]))),
+        'upcoming': [upcoming[key] for key in sorted(upcoming)],
+    }
+
+
+def print_standing(summary: Dict[str, Any], indent: str = '  '):
+    icons = {STANDING_NON_COMPLIANT: '❌', STANDING_TRANSITION: '⚠️ ', STANDING_COMPLIANT: '✅'}
+    counts = summary['by_standing']
+    print(f"{indent}{icons[summary['standing']]} {summary['standing']} (샘플 {summary['samples']}개: "
+          f"non-compliant {counts[STANDING_NON_COMPLIANT]}, transition {counts[STANDING_TRANSITION]}, "
+          f"compliant {counts[STANDING_COMPLIANT]})")
+    for key, rule in summary['by_rule'].items():
+        if rule['status'] == STATUS_APPROVED:
+            continue
+        source = f" [{rule['source']}]" if rule['source'] else ''
+        print(f"{indent}  {rule['status']:<12} {', '.join(rule['subjects'][:5])} — {rule['note']}{source} "
+              f"(샘플 {len(rule['samples'])}개)")
+    for transition in summary['upcoming']:
+        print(f"{indent}  📅 {transition['from']}부터 {transition['status']}: {', '.join(transition['subjects'][:5])}")
+
+
+def main():
+    from utils.report_export import load_results
+    from utils.run_compare import group_by_detector
+
+    parser = argparse.ArgumentParser(description='탐지 결과의 준수 프로필 판정 (NIST/FIPS 폐기 일정)')
+    parser.add_argument('results', help='benchmark_runner.py / detectors.runner 결과 JSON')
+    parser.add_argument('--profile', choices=sorted(PROFILES), default=DEFAULT_PROFILE, help='준수 프로필')
+    parser.add_argument('--as-of', type=date.fromisoformat, default=date.today(), help='기준 날짜 (YYYY-MM-DD, 기본: 오늘)')
+    parser.add_argument('--json', action='store_true', help='JSON으로 출력')
+    args = parser.parse_args()
+
+    try:
+        groups = group_by_detector(load_results(args.results))
+    except ValueError as e:
+        parser.error(str(e))
+    taxonomy = AlgorithmTaxonomy()
+    summaries = {name: summarize_standing([assess_result(result, args.profile, args.as_of, taxonomy)
+                                           for result in results])
+                 for name, results in groups.items()}
+    if args.json:
+        print(json.dumps({'profile': args.profile, 'as_of': args.as_of.isoformat(), 'detectors': summaries},
+                         indent=2, ensure_ascii=False))
+        return
+    print(f"📜 준수 현황: {PROFILES[args.profile]['title']} (기준 {args.as_of.isoformat()})")
+    for name, summary in summaries.items():
+        print(f"\n{name}")
+        print_standing(summary)
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,211 @@
+"""
+트랙 가중치와 종합 점수 (composite score)
+
+알고리즘 식별 말고도 위치, 오용, 양자 내성 분류 트랙이 따로 채점되면서 탐지기 하나를 한 숫자로 비교하기 어려워졌습니다.
+트랙마다 샘플 점수를 평균하고, config/benchmark.toml의 가중치로 가중 평균해 탐지기별 종합 점수를 만듭니다.
+
+트랙 (TRACKS, 샘플 점수는 0~1):
+    identification          알고리즘 식별: 계층 F1 (hierarchical_scores.f1)
+    localization            위치: 함수 단위 F1 (function_scores.f1), 없으면 변경 도입 hunk 적중률 (hunk_scores.hunk_recall)
+    misuse                  운용 모드/구현 약점: 약점 F1 (weakness_scores.f1)
+    quantum_classification  양자 내성 분류: 1 - 양자 내성 라벨 오분류율 (quantum_safe_check)
+    korean_compliance       한국 암호: 한국 계열 라벨 탐지 점수 (korean_check.score, utils/korean_compliance.py)
+
+종합 점수:
+    트랙 평균 = 그 트랙 점수가 있는 샘플들의 평균 (채점 대상이 아닌 샘플은 빼고, 정답이 있는데 채점하지 못한
+               crash/timeout/error/parse_failure 행은 identification 0점)
+    종합 점수 = Σ 가중치 × 트랙 평균 / Σ 가중치  (샘플이 하나도 없는 트랙은 빼고 남은 가중치로 다시 정규화)
+    그래서 위치 라벨이 없는 코퍼스에서도 점수 범위는 0~1이고, 분해(breakdown)에 실제로 쓴 가중치(effective_weight)가 남습니다.
+
+가중치 파일 (TOML, 없으면 DEFAULT_WEIGHTS):
+    [composite.weights]
+    identification = 0.4
+    localization = 0.2
+    ...
+    모르는 트랙 이름, 음수 가중치, 합이 0인 가중치는 오류입니다.
+
+점수 프로필 (같은 파일, --profile로 선택):
+    [composite.profiles.kcmvp.weights]
+    identification = 0.3
+    korean_compliance = 0.5
+    ...
+    기본 가중치와 다른 관점(국내 규제 대응 등)의 순위를 같은 결과에서 뽑습니다. 파일이 없으면 DEFAULT_PROFILES.
+    benchmark_runner.py 요약의 summary.composite_profiles에 프로필마다 모델별 종합 점수가 남습니다.
+
+결과의 summary.composite (benchmark_runner.py, 모델별)와 리더보드 항목의 composite (utils/leaderboard.py)에 같은 형식으로
+남습니다: {'score', 'weights', 'tracks': {트랙: {'mean', 'samples', 'weight', 'effective_weight'}}}
+
+사용법:
+    python -m utils.composite results/benchmark_results_1735689600.json
+    python -m utils.composite results/benchmark_results_1735689600.json --weights my_weights.toml --json
+    python -m utils.composite results/benchmark_results_1735689600.json --profile kcmvp
+"""
+
+import argparse
+import json
+import tomllib
+from pathlib import Path
+from typing import Dict, Any, Iterable, List, Optional
+
+from utils.run_control import STATUS_OK
+
+WEIGHTS_PATH = "config/benchmark.toml"
+
+TRACKS = ['identification', 'localization', 'misuse', 'quantum_classification', 'korean_compliance']
+TRACK_NAMES = {
+    'identification': '식별',
+    'localization': '위치',
+    'misuse': '오용',
+    'quantum_classification': '양자 내성 분류',
+    'korean_compliance': '한국 암호',
+}
+DEFAULT_WEIGHTS = {'identification': 0.4, 'localization': 0.2, 'misuse': 0.2, 'quantum_classification': 0.2,
+                   'korean_compliance': 0.0}
+# 국내 규제(KCMVP) 관점: 한국 계열을 그 이름으로 찾고 국제 알고리즘과 구별하는지를 가장 크게
+DEFAULT_PROFILES = {
+    'kcmvp': {'identification': 0.3, 'localization': 0.1, 'misuse': 0.1, 'quantum_classification': 0.0,
+              'korean_compliance': 0.5},
+}
+
+
+def load_weights(path: Optional[str] = WEIGHTS_PATH, profile: Optional[str] = None) -> Dict[str, float]:
+    """가중치 파일 → {트랙: 가중치} (파일이 없으면 기본 가중치, 빠진 트랙은 0, profile이면 그 프로필의 가중치)"""
+    if profile:
+        profiles = load_profiles(path)
+        if profile not in profiles:
+            raise ValueError(f"{path or 'default'}: 알 수 없는 프로필 {profile} (선택: {sorted(profiles)})")
+        return profiles[profile]
+    if not path or not Path(path).exists():
+        return dict(DEFAULT_WEIGHTS)
+    with open(path, 'rb') as f:
+        document = tomllib.load(f)
+    weights = document.get('composite', {}).get('weights')
+    if not isinstance(weights, dict) or not weights:
+        raise ValueError(f"{path}: [composite.weights] 표가 없습니다")
+    return validate_weights(weights, path)
+
+
+def load_profiles(path: Optional[str] = WEIGHTS_PATH) -> Dict[str, Dict[str, float]]:
+    """가중치 파일의 [composite.profiles.<이름>.weights] → {프로필: {트랙: 가중치}} (파일이 없으면 기본 프로필)"""
+    if not path or not Path(path).exists():
+        return {name: dict(weights) for name, weights in DEFAULT_PROFILES.items()}
+    with open(path, 'rb') as f:
+        document = tomllib.load(f)
+    profiles = {}
+    for name, table in document.get('composite', {}).get('profiles', {}).items():
+        weights = table.get('weights') if isinstance(table, dict) else None
+        if not isinstance(weights, dict) or not weights:
+            raise ValueError(f"{path}: [composite.profiles.{name}.weights] 표가 없습니다")
+        profiles[name] = validate_weights(weights, f"{path} ({name})")
+    return profiles
+
+
+def validate_weights(weights: Dict[str, Any], source: str = 'weights') -> Dict[str, float]:
+    unknown = sorted(set(weights) - set(TRACKS))
+    if unknown:
+        raise ValueError(f"{source}: 알 수 없는 트랙 {unknown} (선택: {TRACKS})")
+    checked = {}
+    for track in TRACKS:
+        value = weights.get(track, 0)
+        if isinstance(value, bool) or not isinstance(value, (int, float)) or value < 0:
+            raise ValueError(f"{source}: {track} 가중치는 0 이상의 수여야 합니다 ({value!r})")
+        checked[track] = float(value)
+    if not sum(checked.values()):
+        raise ValueError(f"{source}: 가중치 합이 0입니다")
+    return checked
+
+
+def track_scores(result: Dict[str, Any]) -> Dict[str, float]:
+    """결과 행 하나 → 채점된 트랙의 샘플 점수 (채점 대상이 아닌 트랙은 빠짐)"""
+    scores = {}
+    hierarchical = result.get('hierarchical_scores')
+    if hierarchical:
+        scores['identification'] = hierarchical['f1']
+    elif result.get('status') not in (None, STATUS_OK) and result.get('ground_truth_hash'):
+        scores['identification'] = 0.0  # 채점하지 못한 샘플 (요약의 계층 F1과 같이 0점)
+    function_scores, hunk_scores = result.get('function_scores'), result.get('hunk_scores')
+    if function_scores:
+        scores['localization'] = function_scores['f1']
+    elif hunk_scores:
+        scores['localization'] = hunk_scores['hunk_recall']
+    if result.get('weakness_scores'):
+        scores['misuse'] = result['weakness_scores']['f1']
+    if result.get('quantum_safe_check'):
+        scores['quantum_classification'] = 1.0 - result['quantum_safe_check']['misclassification_rate']
+    if result.get('korean_check'):
+        scores['korean_compliance'] = result['korean_check']['score']
+    return scores
+
+
+def composite_score(samples: Iterable[Dict[str, float]], weights: Dict[str, float]) -> Optional[Dict[str, Any]]:
+    """샘플별 트랙 점수 → 종합 점수와 트랙별 분해 (가중치가 있는 트랙에 샘플이 하나도 없으면 None)"""
+    collected: Dict[str, List[float]] = {track: [] for track in TRACKS}
+    for scores in samples:
+        for track, value in scores.items():
+            collected[track].append(value)
+
+    used = {track: weights.get(track, 0.0) for track in TRACKS if collected[track] and weights.get(track, 0.0) > 0}
+    total = sum(used.values())
+    if not total:
+        return None
+
+    tracks = {}
+    for track in TRACKS:
+        values = collected[track]
+        tracks[track] = {
+            'mean': sum(values) / len(values) if values else None,
+            'samples': len(values),
+            'weight': weights.get(track, 0.0),
+            'effective_weight': used.get(track, 0.0) / total,
+        }
+    return {
+        'score': sum(tracks[track]['mean'] * tracks[track]['effective_weight'] for track in used),
+        'weights': {track: weights.get(track, 0.0) for track in TRACKS},
+        'tracks': tracks,
+    }
+
+
+def format_breakdown(composite: Dict[str, Any], names: Optional[Dict[str, str]] = None) -> str:
+    """"식별 0.812×0.50 · 위치 0.430×0.25 · ..." (샘플이 없는 트랙은 빠짐, names로 트랙 이름을 바꿈)"""
+    names = names or TRACK_NAMES
+    return ' · '.join(f"{names[track]} {info['mean']:.3f}×{info['effective_weight']:.2f}"
+                      for track, info in composite['tracks'].items() if info['effective_weight'])
+
+
+def main():
+    from utils.run_compare import detector_name
+
+    parser = argparse.ArgumentParser(description='탐지기별 트랙 가중 종합 점수')
+    parser.add_argument('results', help='benchmark_runner.py 결과 JSON')
+    parser.add_argument('--weights', default=WEIGHTS_PATH, help=f'가중치 TOML (기본: {WEIGHTS_PATH})')
+    parser.add_argument('--profile', help='가중치 파일의 점수 프로필 (예: kcmvp)')
+    parser.add_argument('--json', action='store_true', help='JSON으로 출력')
+    args = parser.parse_args()
+
+    try:
+        weights = load_weights(args.weights, args.profile)
+    except (ValueError, tomllib.TOMLDecodeError) as e:
+        parser.error(str(e))
+    with open(args.results, 'r', encoding='utf-8') as f:
+        results = json.load(f).get('detailed_results', [])
+
+    by_detector: Dict[str, List[Dict[str, float]]] = {}
+    for result in results:
+        by_detector.setdefault(detector_name(result), []).append(track_scores(result))
+    composites = {name: composite_score(samples, weights) for name, samples in by_detector.items()}
+
+    if args.json:
+        print(json.dumps(composites, indent=2, ensure_ascii=False))
+        return
+    profile = f"{args.profile} 프로필, " if args.profile else ''
+    print(f"🏅 종합 점수 ({profile}가중치 {', '.join(f'{TRACK_NAMES[t]} {w:g}' for t, w in weights.items() if w)})")
+    ranked = sorted(composites.items(), key=lambda item: -(item[1]['score'] if item[1] else -1.0))
+    for name, composite in ranked:
+        if composite is None:
+            print(f"  {name}: 채점된 트랙 없음")
+            continue
+        print(f"  {name}: {composite['score']:.3f}  ({format_breakdown(composite)})")
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,1374 @@
+"""
+테스트 코퍼스 관리 도구
+
+verify: 샘플 안에 포함된 `#[cfg(test)]` 테스트(NIST/KISA/RFC known-answer 벡터, 라운드트립)를
+        실제로 빌드/실행하여 샘플이 라벨에 적힌 알고리즘을 정확히 구현하는지 확인합니다.
+
+    - 외부 크레이트를 쓰지 않는 샘플: `rustc --test`로 직접 빌드
+    - 외부 크레이트를 쓰는 샘플: 샘플 워크스페이스(workspace)의 샘플 크레이트로 `cargo test -p`
+      (--no-workspace이거나 워크스페이스 멤버가 아닌 파일은 임시 cargo 프로젝트를 생성하여 `cargo test`,
+      크레이트 버전은 CRATE_VERSIONS 표를 따르며, 표에 없는 크레이트를 쓰는 샘플은 건너뜀)
+    - `#[link(name = "...")]`로 네이티브 라이브러리에 링크하는 샘플: 라이브러리를 찾지 못하면 건너뜀
+    - Cargo 패키지 디렉토리 샘플 (utils/algorithm_split.py의 다중 파일 샘플): 사본에서 `cargo test --workspace`
+      (경로 의존성만 허용, 레지스트리 의존성이 있으면 건너뜀)
+
+workspace: 단일 파일 Rust 샘플마다 원본 파일을 [[bin]] path로 가리키는 크레이트를 만들어 SAMPLE_WORKSPACE_DIR에
+        cargo 워크스페이스로 묶습니다. 외부 크레이트 버전은 [workspace.dependencies]에서 공유하므로
+        `cargo test --workspace`나 rust-analyzer로 샘플을 바로 빌드/테스트할 수 있습니다 (SampleWorkspace).
+
+buildcheck: 생성/난독화 샘플은 코드가 바뀌어도 테스트가 없으면 아무도 모르게 썩으므로, 테스트 유무와 관계없이 모든
+        Rust 샘플을 샘플마다 새 임시 작업 공간에 복사해 고정 툴체인(RUST_TOOLCHAIN)으로 컴파일하고 더 이상
+        빌드되지 않는 샘플을 보고합니다 (하나라도 실패하면 종료 코드 1).
+
+    - 툴체인 고정: 로컬은 작업 공간에 rust-toolchain.toml을 써서 rustup이 --toolchain 버전을 쓰게 하고,
+      --container docker|podman이면 작업 공간만 마운트한 rust:<toolchain> 이미지(--image로 변경) 안에서 컴파일
+    - 외부 크레이트가 없는 샘플: `rustc --test --emit=metadata` (링크하지 않으므로 네이티브 라이브러리 불필요)
+    - 외부 크레이트를 쓰는 샘플 / Cargo 패키지 디렉토리 샘플: `cargo check --all-targets`
+      (CRATE_VERSIONS에 없는 크레이트, 받을 수 없는 의존성은 건너뜀)
+
+lint:   ground truth의 충실도(fidelity) 주장을 샘플의 known-answer 테스트와 교차 검증합니다.
+        known-answer 테스트는 `#[test] fn *_known_answer()` 함수이며, 본문 첫 주석에
+        기준 문서와 알고리즘을 적습니다 (예: `// FIPS-197 Appendix C.3 (AES-256)`). 기준 문서가 KAT_REFERENCES에
+        있으면 알고리즘 이름 없이 문서만 적어도 됩니다 (예: `// RFC 8439 section 2.5.2`). 위장 샘플은 주석에서
+        정답을 읽을 수 없도록 문서만 적습니다.
+
+    - error:   exact로 표기했지만 해당 알고리즘의 known-answer 테스트가 없음 / 테스트 실패(--run)
+    - error:   알 수 없는 충실도 값
+    - warning: known-answer 테스트가 있지만 exact로 표기되지 않았거나 라벨이 없음
+    - error:   disputed 라벨이 정답 목록에 없거나 대체 정답이 분류 체계에 없음 (utils/uncertainty.py)
+    - error:   취약성 범주가 정답 목록에 없는 라벨에 붙었거나 알 수 없는 범주 (utils/weakness.py)
+    - error:   운용 모드/약점(mode_findings)이 정답 목록에 없는 라벨에 붙었거나 알 수 없는 모드/약점 (utils/misuse.py)
+    - error:   mode_findings의 cwe가 약점의 CWE ID(WEAKNESS_CWE)와 다르거나, misuse-detection 샘플에 심은 약점이 없음
+               (상수 시간 대조 샘플은 timing_safety 라벨로 대신)
+    - error:   알 수 없는 timing_safety 라벨이거나, leaky인데 타이밍 약점이 없음 / constant-time인데 타이밍 약점이 있음
+    - error:   양자 내성 라벨(quantum_safe_algorithms)이 post_quantum 카테고리가 아니거나 취약 라벨과 겹침
+    - error:   external-library 라벨에 충실도가 기록됨 / 알 수 없는 사용 형태 (utils/usage.py)
+    - warning: external-library 라벨이 있지만 샘플에 `extern "C"` 바인딩이 없음
+    - warning: Rust 샘플이 `thread_rng()`/`rand::random`을 씀 (출력이 재현되지 않아 테스트 벡터를 만들 수 없음)
+    - error:   알 수 없는 과제 유형 (utils/tasks.py)
+    - error:   manifest-inference 라벨에 근거 의존성이 없거나 매니페스트에 선언되지 않은 의존성을 근거로 듦
+    - warning: 근거 의존성이 라벨의 알고리즘 계열을 제공하지 않음 (utils/manifest.py의 DEPENDENCY_SURFACE)
+    - error:   diff-introduction 라벨에 도입 위치(introduced_in)가 없거나, 위치가 patch에 없는 hunk 또는
+               줄을 추가하지 않은 hunk를 가리킴 (utils/diffs.py)
+    - error:   알 수 없는 출처(provenance), adapted-from-oss인데 source나 SPDX license가 없음,
+               generated_by와 출처가 어긋남 (utils/provenance.py)
+    - warning: 출처 라이선스가 재배포 가능 목록(REDISTRIBUTABLE_LICENSES)에 없음
+    - error:   근사 중복 클러스터(MinHash, utils/dedup.py)가 data/duplicate_clusters.json 기록과 다르거나,
+               같은 클러스터 멤버의 분할(train/test)이 다름
+    - error:   생성 샘플의 카나리 줄이 data/canary_manifest.json 기록과 다르거나 기록된 샘플이 없음 (utils/canary.py)
+    - warning: 카나리 기록이 있는데 카나리가 없는 생성 샘플
+    - error:   tags가 문자열 목록이 아니거나, 키=값 태그의 키/값이 비었거나 agent/id 필드를 가림 (utils/sample_filter.py)
+    - error:   샘플의 `#[cfg(test)]` 테스트 모듈이 프롬프트 입력(input_data)이나 도구 하네스 작업 공간
+               (utils/harness_tools.py)에 남음. known-answer 주석과 벡터가 정답을 알려 주므로
+               utils/test_case_manager.py의 strip_test_modules로 지워야 함
+
+import: 공개 git 저장소에서 암호 코드가 든 파일을 골라 signature/AST 기준 방식으로 라벨을 제안하고 스테이징
+        영역(data/staging/)에 올립니다. 라벨 리뷰 CLI(--staging)에서 승인한 샘플만 promote로 코퍼스에 옮깁니다
+        (utils/corpus_import.py).
+
+package: 샘플, 라벨, 매니페스트, 채점 rubric, 라이선스 본문을 재현 가능한 배포 아카이브(.tar.zst)로 묶고,
+        unpack --verify로 받는 쪽에서 파일/샘플 해시와 코퍼스 버전을 다시 확인합니다 (utils/corpus_package.py).
+
+dedup:  토큰 shingle MinHash로 근사 중복 샘플 쌍과 클러스터를 보여 주고, --write로 클러스터 기록을 갱신합니다.
+        분할(utils/splits.py)은 클러스터 단위로 정해지므로 같은 템플릿의 변형이 train과 test에 나뉘지 않습니다.
+
+stats:  코퍼스 치우침(예: 대칭키 샘플이 공개키보다 훨씬 많음)을 보여 줍니다.
+
+    - 알고리즘 카테고리/계열/역할(public_key, cipher, hash …)별 샘플 수 (샘플 하나는 계열마다 한 번)
+    - 언어(확장자), 에이전트 유형, 난이도별 샘플 수, 줄 수 분포
+    - 출처(hand-written / generated-from-template / adapted-from-oss)와 라이선스별 샘플 수, 원본 저작권 표시 목록
+    - 태그별 샘플 수 (키=값 태그는 그대로, utils/sample_filter.py)
+    - --filter로 필터 식에 맞는 샘플만 셈 (ground truth가 없는 샘플 목록은 생략)
+    - 라벨 공백: 샘플이 없거나 --min-samples보다 적은 분류 체계 계열, 분류 체계에 없는 라벨,
+      샘플 파일이 없는 ground truth, ground truth가 없는 샘플
+
+사용법:
+    python -m utils.corpus verify
+    python -m utils.corpus verify --file data/test_files/source_code/medical_device_encryption.rs
+    python -m utils.corpus verify --offline --output results/corpus_verify.json
+    python -m utils.corpus workspace
+    python -m utils.corpus buildcheck
+    python -m utils.corpus buildcheck --toolchain stable --output results/corpus_buildcheck.json
+    python -m utils.corpus buildcheck --container docker --offline
+    python -m utils.corpus lint
+    python -m utils.corpus lint --run --offline
+    python -m utils.corpus import --from-git https://github.com/<owner>/<repo> --dry-run
+    python -m utils.corpus promote
+    python -m utils.corpus dedup --write
+    python -m utils.corpus stats
+    python -m utils.corpus stats --min-samples 5 --output analysis_output/corpus_stats.json
+    python -m utils.corpus stats --filter "lang==rust && difficulty>=hard"
+    python -m utils.corpus package --out corpus-v2.tar.zst
+    python -m utils.corpus unpack corpus-v2.tar.zst --verify
+"""
+
+import argparse
+import ctypes.util
+import json
+import os
+import re
+import shutil
+import subprocess
+import sys
+import tempfile
+import tomllib
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+from utils.algorithm_split import parse_file_location
+from utils.canary import (CANARY_MANIFEST_PATH, canary_issues, generated_samples,
+                          load_manifest as load_canary_manifest)
+from utils.comment_noise import comment_noise_claims
+from utils.dedup import (CLUSTERS_PATH, assign_cluster_ids, cluster_issues, clusters_from_pairs, load_clusters,
+                         near_duplicates, print_duplicates, write_clusters)
+from utils.diffs import (DIFF_SUFFIXES, expected_hunks, find_expected_hunks, is_diff_sample, parse_patch,
+                         recorded_introductions)
+from utils.fidelity import FIDELITY_EXACT, FIDELITY_LEVELS, label_fidelity
+from utils.manifest import (implied_families, is_manifest_sample, manifest_dependencies,
+                            recorded_dependencies, render_sample)
+from utils.misuse import (MODES, TIMING_LEAKY, TIMING_SAFETY_LABELS, TIMING_SAFETY_WEAKNESSES, WEAKNESS_CWE,
+                          WEAKNESSES, recorded_mode_findings)
+from utils.provenance import attribution, provenance_issues, provenance_label, sample_provenance
+from utils.splits import sample_split
+from utils.sample_filter import SampleFilter, tag_issues
+from utils.tasks import TASK_DIFF, TASK_MANIFEST, TASK_MISUSE, TASKS, sample_task
+from utils.test_case_manager import AGENT_TYPES, TestCaseManager
+from utils.uncertainty import disputed_labels
+from utils.usage import USAGE_EXTERNAL, USAGE_KINDS, recorded_usage
+from utils.weakness import WEAKNESS_CATEGORIES, recorded_weakness
+from utils.metrics_calculator import MetricsCalculator
+from utils.taxonomy import AlgorithmTaxonomy, TAXONOMY
+
+TEST_FILES_DIR = "data/test_files"
+GROUND_TRUTH_DIR = "data/ground_truth"
+# 단일 파일 샘플을 감싼 생성 워크스페이스 (build/는 .gitignore)
+SAMPLE_WORKSPACE_DIR = "build/sample_workspace"
+
+# 샘플이 사용하는 외부 크레이트 → Cargo.toml 의존성 명세
+CRATE_VERSIONS: Dict[str, str] = {
+    'rayon': '"1"',
+    'num_bigint': '{ package = "num-bigint", version = "0.4", features = ["rand"] }',
+    'num_traits': '{ package = "num-traits", version = "0.2" }',
+    'rand': '"0.8"',
+    'sha2': '"0.10"',
+    'zeroize': '{ version = "1", features = ["derive"] }',
+}
+
+BUILTIN_CRATES = {'std', 'core', 'alloc', 'crate', 'self', 'super'}
+
+TEST_MARKER = '#[cfg(test)]'
+# known-answer 주석의 기준 문서 → 검증하는 라벨 (알고리즘 이름을 적지 않은 주석도 lint가 계열을 알 수 있도록)
+KAT_REFERENCES: Dict[str, List[str]] = {
+    r'\bFIPS-197\b': ['AES'],
+    r'\bGCM specification\b': ['AES', 'GHASH'],
+    r'\bRFC 8439 section 2\.[34]\b': ['ChaCha20'],
+    r'\bRFC 8439 section 2\.5\b': ['Poly1305'],
+    r'\bRFC 8439 section 2\.8\b': ['ChaCha20', 'Poly1305'],
+    r'\bFIPS 180-4 section 6\.1\b': ['SHA-1'],
+    r'\bFIPS 180-4 section 6\.2\b|\bFIPS 180-2 Appendix B\b': ['SHA-256'],
+    r'\bFIPS 202\b': ['SHA-3'],
+    r'\bRFC 4231\b': ['HMAC-SHA256'],
+    r'\bRFC 5869\b': ['HKDF'],
+    r'\bRFC 7914 section 11\b|\bRFC 6070\b': ['PBKDF2'],
+    r'\bRFC 7914 section 12\b': ['scrypt'],
+    r'\bRFC 3526\b': ['DH'],
+    r'\bSEC 2 section 2\.4\.2\b': ['P-256'],
+    r'\bRFC 4269\b': ['SEED'],
+    r'\bRFC 5794\b': ['ARIA'],
+    r'\bFIPS 186-4 section 4\b': ['DSA'],
+    r'\bNIST SP 800-67\b': ['3DES'],
+    r'\bRFC 6229\b': ['RC4'],
+    r'\bRFC 1321\b': ['MD5'],
+    r'\bWheeler-Needham\b': ['TEA'],
+    r'\bSchneier\b': ['Blowfish'],
+}
+# 샘플 출력을 재현할 수 없게 만드는 전역 난수원 (생성자로 시드 고정 PRNG를 주입해야 함)
+UNSEEDED_RNG = re.compile(r'\bthread_rng\s*\(|\brand::random\b')
+
+STATUS_PASSED = 'passed'
+STATUS_FAILED = 'failed'
+STATUS_SKIPPED = 'skipped'
+STATUS_BUILT = 'built'
+
+# buildcheck 고정 툴체인 (로컬: 작업 공간의 rust-toolchain.toml, 컨테이너: rust:<toolchain> 이미지)
+RUST_TOOLCHAIN = '1.95.0'
+CONTAINER_WORKDIR = '/workspace'
+# cargo가 의존성을 받지 못한 경우 (샘플 자체의 빌드 실패가 아님)
+DEPENDENCY_ERRORS = re.compile(r'failed to (?:download|get|load source for|select a version|query replaced source)'
+                               r'|no matching package|--offline was specified|Unable to update registry')
+
+# 확장자 → 언어 (표에 없는 확장자는 확장자 그대로)
+LANGUAGE_NAMES: Dict[str, str] = {
+    '.c': 'C', '.h': 'C', '.cpp': 'C++', '.cc': 'C++', '.hpp': 'C++', '.cs': 'C#', '.go': 'Go', '.java': 'Java',
+    '.js': 'JavaScript', '.ts': 'TypeScript', '.kt': 'Kotlin', '.php': 'PHP', '.py': 'Python', '.rb': 'Ruby',
+    '.rs': 'Rust', '.scala': 'Scala', '.swift': 'Swift', '.s': 'Assembly', '.asm': 'Assembly',
+}
+
+# 줄 수 분포 구간 (상한, 포함)
+SIZE_BUCKETS = [50, 100, 200, 500, 1000]
+
+
+def sample_package_name(path: Path) -> str:
+    """단일 파일 샘플의 cargo 패키지 이름"""
+    return 'sample_' + re.sub(r'[^a-z0-9_]', '_', path.stem.lower())
+
+
+class CorpusVerifier:
+    """샘플 내장 테스트 빌드/실행기"""
+
+    def __init__(self, offline: bool = False, timeout: int = 600, target_dir: Optional[str] = None,
+                 workspace: Optional['SampleWorkspace'] = None):
+        self.offline = offline
+        self.timeout = timeout
+        # 샘플 간 의존성 빌드 결과를 재사용하기 위한 공용 target 디렉토리
+        self.target_dir = Path(target_dir or Path(tempfile.gettempdir()) / 'corpus_verify_target')
+        # 외부 크레이트 샘플을 생성 워크스페이스의 멤버로 테스트 (없거나 멤버가 아닌 파일은 임시 cargo 프로젝트)
+        self.workspace = workspace
+        self._members: Optional[Dict[Path, Dict[str, Any]]] = None
+
+    @staticmethod
+    def find_samples(root: str = TEST_FILES_DIR) -> List[Path]:
+        """테스트 모듈을 포함한 Rust 샘플 목록 (Cargo 패키지 디렉토리 샘플은 디렉토리 단위)"""
+        samples = set()
+        for path in Path(root).rglob('*.rs'):
+            if TEST_MARKER in path.read_text(encoding='utf-8', errors='ignore'):
+                samples.add(CorpusVerifier.package_root(path, Path(root)) or path)
+        return sorted(samples)
+
+    @staticmethod
+    def package_root(path: Path, root: Path) -> Optional[Path]:
+        """root 아래에서 path를 포함하는 가장 바깥 Cargo 패키지 디렉토리 (없으면 None)"""
+        found = None
+        for parent in path.parents:
+            if parent == root or root not in parent.parents:
+                break
+            if (parent / 'Cargo.toml').exists():
+                found = parent
+        return found
+
+    @staticmethod
+    def external_crates(source: str) -> List[str]:
+        """`use` / `extern crate` 구문에서 외부 크레이트 이름 추출"""
+        crates = re.findall(r'^\s*(?:pub\s+)?use\s+([A-Za-z_][A-Za-z0-9_]*)\s*::', source, re.MULTILINE)
+        crates += re.findall(r'^\s*extern\s+crate\s+([A-Za-z_][A-Za-z0-9_]*)', source, re.MULTILINE)
+
+        result = []
+        for crate in crates:
+            if crate not in BUILTIN_CRATES and crate not in result:
+                result.append(crate)
+        return result
+
+    @staticmethod
+    def native_libraries(source: str) -> List[str]:
+        """`#[link(name = "...")]` 속성에서 네이티브 라이브러리 이름 추출"""
+        result = []
+        for name in re.findall(r'#\[link\(\s*name\s*=\s*"([^"]+)"', source):
+            if name not in result:
+                result.append(name)
+        return result
+
+    def verify(self, path: Path) -> Dict[str, Any]:
+        if path.is_dir():
+            return self._verify_package(path)
+        source = path.read_text(encoding='utf-8', errors='ignore')
+        crates = self.external_crates(source)
+
+        unknown = [crate for crate in crates if crate not in CRATE_VERSIONS]
+        if unknown:
+            return self._result(path, STATUS_SKIPPED, 'cargo', reason=f"unknown crates: {', '.join(unknown)}")
+
+        missing = [lib for lib in self.native_libraries(source) if not ctypes.util.find_library(lib)]
+        if missing:
+            return self._result(path, STATUS_SKIPPED, 'cargo' if crates else 'rustc',
+                                reason=f"native libraries not found: {', '.join(missing)}")
+
+        if crates:
+            member = self._workspace_member(path)
+            if member:
+                return self._verify_in_workspace(path, member['package'])
+            return self._verify_with_cargo(path, crates)
+        return self._verify_with_rustc(path)
+
+    def _workspace_member(self, path: Path) -> Optional[Dict[str, Any]]:
+        if self.workspace is None:
+            return None
+        if self._members is None:
+            self._members, _ = self.workspace.generate()
+        return self._members.get(path.resolve())
+
+    def _verify_with_rustc(self, path: Path) -> Dict[str, Any]:
+        if not shutil.which('rustc'):
+            return self._result(path, STATUS_SKIPPED, 'rustc', reason='rustc not found')
+
+        with tempfile.TemporaryDirectory() as work_dir:
+            binary = Path(work_dir) / 'sample_tests'
+            build = self._run(['rustc', '--edition', '2021', '--test', '-o', str(binary), str(path)])
+            if build.returncode != 0:
+                return self._result(path, STATUS_FAILED, 'rustc', reason='build failed', output=build.stderr)
+
+            run = self._run([str(binary)])
+            return self._test_result(path, 'rustc', run)
+
+    @staticmethod
+    def cargo_manifest(path: Path, crates: List[str]) -> str:
+        """단일 파일 샘플용 임시 cargo 프로젝트의 Cargo.toml (의존성 버전은 CRATE_VERSIONS)"""
+        return '\n'.join(
+            ['[package]', f'name = "{sample_package_name(path)}"', 'version = "0.1.0"', 'edition = "2021"', '',
+             '[dependencies]']
+            + [f'{crate} = {CRATE_VERSIONS[crate]}' for crate in crates]
+        ) + '\n'
+
+    def _verify_with_cargo(self, path: Path, crates: List[str]) -> Dict[str, Any]:
+        if not shutil.which('cargo'):
+            return self._result(path, STATUS_SKIPPED, 'cargo', reason='cargo not found')
+
+        with tempfile.TemporaryDirectory() as work_dir:
+            project = Path(work_dir)
+            (project / 'src').mkdir()
+            (project / 'Cargo.toml').write_text(self.cargo_manifest(path, crates), encoding='utf-8')
+            shutil.copy(path, project / 'src' / 'main.rs')
+
+            command = ['cargo', 'test', '--quiet', '--manifest-path', str(project / 'Cargo.toml'),
+                       '--target-dir', str(self.target_dir)]
+            if self.offline:
+                command.append('--offline')
+
+            run = self._run(command)
+            return self._test_result(path, 'cargo', run)
+
+    def _verify_in_workspace(self, path: Path, package: str) -> Dict[str, Any]:
+        """생성 워크스페이스의 샘플 크레이트로 cargo test (Cargo.lock과 target을 샘플 간에 공유)"""
+        if not shutil.which('cargo'):
+            return self._result(path, STATUS_SKIPPED, 'cargo', reason='cargo not found')
+
+        command = ['cargo', 'test', '--quiet', '-p', package, '--manifest-path',
+                   str(self.workspace.workspace_dir / 'Cargo.toml'), '--target-dir', str(self.target_dir)]
+        if self.offline:
+            command.append('--offline')
+
+        run = self._run(command)
+        return self._test_result(path, 'cargo', run)
+
+    def _verify_package(self, path: Path) -> Dict[str, Any]:
+        """Cargo 패키지 디렉토리 샘플 (다중 파일, 경로 의존성 보조 크레이트) — 사본에서 cargo test"""
+        if not (path / 'Cargo.toml').exists():
+            return self._result(path, STATUS_SKIPPED, 'cargo', reason='directory sample without Cargo.toml')
+        if not shutil.which('cargo'):
+            return self._result(path, STATUS_SKIPPED, 'cargo', reason='cargo not found')
+
+        with open(path / 'Cargo.toml', 'rb') as f:
+            manifest = tomllib.load(f)
+        dependencies = manifest.get('dependencies', {})
+        registry = [name for name, spec in dependencies.items() if not (isinstance(spec, dict) and 'path' in spec)]
+        if registry:
+            return self._result(path, STATUS_SKIPPED, 'cargo', reason=f"registry dependencies: {', '.join(registry)}")
+
+        with tempfile.TemporaryDirectory() as work_dir:
+            project = Path(work_dir) / path.name
+            shutil.copytree(path, project)
+            command = ['cargo', 'test', '--quiet', '--workspace', '--manifest-path', str(project / 'Cargo.toml'),
+                       '--target-dir', str(self.target_dir)]
+            if self.offline:
+                command.append('--offline')
+
+            run = self._run(command)
+            return self._test_result(path, 'cargo', run)
+
+    def _run(self, command: List[str]) -> subprocess.CompletedProcess:
+        try:
+            return subprocess.run(command, capture_output=True, text=True, timeout=self.timeout)
+        except subprocess.TimeoutExpired:
+            return subprocess.CompletedProcess(command, -1, '', f'timed out after {self.timeout}s')
+
+    def _test_result(self, path: Path, mode: str, run: subprocess.CompletedProcess) -> Dict[str, Any]:
+        summary = re.findall(r'test result: \w+\. (\d+) passed; (\d+) failed', run.stdout)
+        passed = sum(int(p) for p, _ in summary)
+        failed = sum(int(f) for _, f in summary)
+
+        if run.returncode == 0 and summary:
+            return self._result(path, STATUS_PASSED, mode, passed=passed, failed=failed)
+
+        failures = re.findall(r'^test (\S+) \.\.\. FAILED', run.stdout, re.MULTILINE)
+        reason = f"failing tests: {', '.join(failures)}" if failures else 'test run failed'
+        return self._result(path, STATUS_FAILED, mode, passed=passed, failed=failed,
+                            reason=reason, output=run.stdout + run.stderr)
+
+    @staticmethod
+    def _result(path: Path, status: str, mode: str, passed: int = 0, failed: int = 0,
+                reason: str = '', output: str = '') -> Dict[str, Any]:
+        return {
+            'file': str(path),
+            'status': status,
+            'mode': mode,
+            'tests_passed': passed,
+            'tests_failed': failed,
+            'reason': reason,
+            # 실패 로그는 마지막 부분만 보존
+            'output': output[-4000:]
+        }
+
+
+class SampleWorkspace:
+    """단일 파일 Rust 샘플을 샘플별 크레이트로 감싼 cargo 워크스페이스 생성기
+
+    샘플 파일은 복사하지 않고 각 크레이트의 [[bin]] path로 원본을 가리키므로, 샘플을 고쳐도 다시 생성할 필요가
+    없습니다 (샘플을 추가/삭제하거나 크레이트 사용이 바뀌었을 때만 재생성). 외부 크레이트 버전은
+    [workspace.dependencies](CRATE_VERSIONS)에 한 번만 적고 크레이트마다 `<crate>.workspace = true`로 가져옵니다.
+    Cargo.lock은 재생성 때도 지우지 않으므로 의존성 해석이 고정됩니다.
+    """
+
+    def __init__(self, root: str = TEST_FILES_DIR, workspace_dir: str = SAMPLE_WORKSPACE_DIR):
+        self.root = Path(root)
+        self.workspace_dir = Path(workspace_dir)
+
+    def plan(self) -> Tuple[Dict[Path, Dict[str, Any]], Dict[Path, str]]:
+        """(멤버 {샘플 절대 경로: {'package', 'crates'}}, 제외 {샘플: 사유})
+
+        Cargo 패키지 디렉토리 샘플은 자체 워크스페이스이므로 대상이 아닙니다.
+        """
+        members: Dict[Path, Dict[str, Any]] = {}
+        excluded: Dict[Path, str] = {}
+        packages = set()
+        for path in sorted(self.root.rglob('*.rs')):
+            if CorpusVerifier.package_root(path, self.root):
+                continue
+            source = path.read_text(encoding='utf-8', errors='ignore')
+            crates = CorpusVerifier.external_crates(source)
+            unknown = [crate for crate in crates if crate not in CRATE_VERSIONS]
+            if unknown:
+                excluded[path] = f"unknown crates: {', '.join(unknown)}"
+                continue
+            # 링크할 수 없는 멤버가 있으면 `cargo test --workspace` 전체가 실패
+            missing = [lib for lib in CorpusVerifier.native_libraries(source) if not ctypes.util.find_library(lib)]
+            if missing:
+                excluded[path] = f"native libraries not found: {', '.join(missing)}"
+                continue
+
+            package = base = sample_package_name(path)
+            suffix = 2
+            while package in packages:
+                package, suffix = f'{base}_{suffix}', suffix + 1
+            packages.add(package)
+            members[path.resolve()] = {'package': package, 'crates': crates}
+        return members, excluded
+
+    def generate(self) -> Tuple[Dict[Path, Dict[str, Any]], Dict[Path, str]]:
+        """워크스페이스를 (재)생성하고 plan() 결과를 돌려줌"""
+        members, excluded = self.plan()
+        crates_dir = self.workspace_dir / 'crates'
+        if crates_dir.exists():
+            shutil.rmtree(crates_dir)
+
+        for path, member in members.items():
+            crate_dir = crates_dir / member['package']
+            crate_dir.mkdir(parents=True)
+            sample = Path(os.path.relpath(path, crate_dir.resolve())).as_posix()
+            manifest = ['[package]', f'name = "{member["package"]}"', 'version.workspace = true',
+                        'edition.workspace = true', 'publish = false', '',
+                        '[[bin]]', f'name = "{member["package"]}"', f'path = "{sample}"', '', '[dependencies]']
+            manifest += [f'{crate}.workspace = true' for crate in member['crates']]
+            (crate_dir / 'Cargo.toml').write_text('\n'.join(manifest) + '\n', encoding='utf-8')
+
+        lines = ['# python -m utils.corpus workspace 로 생성된 파일입니다. 직접 수정하지 마세요.',
+                 '[workspace]', 'resolver = "2"', 'members = [']
+        lines += [f'    "crates/{member["package"]}",' for member in members.values()]
+        lines += [']', '', '[workspace.package]', 'version = "0.1.0"', 'edition = "2021"', '',
+                  '[workspace.dependencies]']
+        lines += [f'{crate} = {spec}' for crate, spec in CRATE_VERSIONS.items()]
+        self.workspace_dir.mkdir(parents=True, exist_ok=True)
+        (self.workspace_dir / 'Cargo.toml').write_text('\n'.join(lines) + '\n', encoding='utf-8')
+        return members, excluded
+
+
+class CorpusBuildChecker:
+    """모든 Rust 샘플을 격리된 임시 작업 공간에서 고정 툴체인으로 컴파일 (테스트는 실행하지 않음)"""
+
+    def __init__(self, toolchain: str = RUST_TOOLCHAIN, container: Optional[str] = None,
+                 image: Optional[str] = None, offline: bool = False, timeout: int = 600):
+        self.toolchain = toolchain
+        self.container = container
+        # 컨테이너 모드에서는 이미지가 툴체인을 고정
+        self.image = image or f'rust:{toolchain}'
+        self.offline = offline
+        self.timeout = timeout
+
+    @staticmethod
+    def find_samples(root: str = TEST_FILES_DIR) -> List[Path]:
+        """모든 Rust 샘플 (테스트 유무 무관, Cargo 패키지 디렉토리 샘플은 디렉토리 단위)"""
+        return sorted({CorpusVerifier.package_root(path, Path(root)) or path for path in Path(root).rglob('*.rs')})
+
+    def toolchain_version(self) -> Tuple[Optional[str], str]:
+        """고정 툴체인의 `rustc --version` → (버전 또는 None, 실패 사유)"""
+        if not self.container and not shutil.which('rustc'):
+            return None, 'rustc not found'
+        if self.container and not shutil.which(self.container):
+            return None, f'{self.container} not found'
+        with tempfile.TemporaryDirectory() as work_dir:
+            run = self._run(Path(work_dir), ['rustc', '--version'])
+        if run.returncode != 0:
+            lines = (run.stderr or run.stdout).strip().splitlines() or ['rustc --version failed']
+            return None, next((line for line in lines if line.startswith('error')), lines[-1])
+        return run.stdout.strip(), ''
+
+    def check(self, path: Path) -> Dict[str, Any]:
+        if path.is_dir():
+            if not (path / 'Cargo.toml').exists():
+                return self._result(path, STATUS_SKIPPED, 'cargo', reason='directory sample without Cargo.toml')
+            return self._build(path, 'cargo', lambda workspace: self._copy_package(path, workspace))
+
+        source = path.read_text(encoding='utf-8', errors='ignore')
+        crates = CorpusVerifier.external_crates(source)
+        unknown = [crate for crate in crates if crate not in CRATE_VERSIONS]
+        if unknown:
+            return self._result(path, STATUS_SKIPPED, 'cargo', reason=f"unknown crates: {', '.join(unknown)}")
+        if crates:
+            return self._build(path, 'cargo', lambda workspace: self._cargo_project(path, crates, workspace))
+        return self._build(path, 'rustc', lambda workspace: self._rustc_sample(path, workspace))
+
+    def _build(self, path: Path, mode: str, prepare) -> Dict[str, Any]:
+        with tempfile.TemporaryDirectory() as work_dir:
+            workspace = Path(work_dir)
+            run = self._run(workspace, prepare(workspace))
+
+        if run.returncode == 0:
+            return self._result(path, STATUS_BUILT, mode)
+        output = run.stdout + run.stderr
+        # 의존성을 받지 못한 것은 샘플이 썩은 것이 아니므로 건너뜀으로 분류
+        if mode == 'cargo' and DEPENDENCY_ERRORS.search(output):
+            return self._result(path, STATUS_SKIPPED, mode, reason='dependencies unavailable', output=output)
+        reason = 'timed out' if run.returncode == -1 else 'build failed'
+        return self._result(path, STATUS_FAILED, mode, reason=reason, output=output)
+
+    @staticmethod
+    def _rustc_sample(path: Path, workspace: Path) -> List[str]:
+        # 링크 없이 타입 검사까지만 (--emit=metadata) 하므로 네이티브 라이브러리가 없어도 됨, 테스트 모듈도 함께 검사
+        shutil.copy(path, workspace / path.name)
+        return ['rustc', '--edition', '2021', '--test', '--emit=metadata', '-o', 'sample.rmeta', path.name]
+
+    def _cargo_project(self, path: Path, crates: List[str], workspace: Path) -> List[str]:
+        project = workspace / 'sample'
+        (project / 'src').mkdir(parents=True)
+        (project / 'Cargo.toml').write_text(CorpusVerifier.cargo_manifest(path, crates), encoding='utf-8')
+        shutil.copy(path, project / 'src' / 'main.rs')
+        return self._cargo_check('sample/Cargo.toml')
+
+    def _copy_package(self, path: Path, workspace: Path) -> List[str]:
+        shutil.copytree(path, workspace / path.name, ignore=shutil.ignore_patterns('target'))
+        return self._cargo_check(f'{path.name}/Cargo.toml') + ['--workspace']
+
+    def _cargo_check(self, manifest: str) -> List[str]:
+        command = ['cargo', 'check', '--quiet', '--all-targets', '--manifest-path', manifest, '--target-dir', 'target']
+        if self.offline:
+            command.append('--offline')
+        return command
+
+    def _run(self, workspace: Path, command: List[str]) -> subprocess.CompletedProcess:
+        """작업 공간을 현재 디렉토리로 실행 (로컬: rust-toolchain.toml로 툴체인 고정 / 컨테이너: 작업 공간만 마운트)"""
+        if self.container:
+            prefix = [self.container, 'run', '--rm', '-v', f'{workspace}:{CONTAINER_WORKDIR}', '-w', CONTAINER_WORKDIR,
+                      '-e', f'CARGO_HOME={CONTAINER_WORKDIR}/.cargo']
+            if hasattr(os, 'getuid'):
+                # 작업 공간에 생기는 파일을 호스트 사용자 소유로 (임시 디렉토리 정리)
+                prefix += ['--user', f'{os.getuid()}:{os.getgid()}']
+            if self.offline:
+                prefix += ['--network', 'none']
+            command = prefix + [self.image] + command
+        else:
+            (workspace / 'rust-toolchain.toml').write_text(f'[toolchain]\nchannel = "{self.toolchain}"\n',
+                                                           encoding='utf-8')
+        try:
+            return subprocess.run(command, cwd=workspace, capture_output=True, text=True, timeout=self.timeout)
+        except subprocess.TimeoutExpired:
+            return subprocess.CompletedProcess(command, -1, '', f'timed out after {self.timeout}s')
+
+    @staticmethod
+    def _result(path: Path, status: str, mode: str, reason: str = '', output: str = '') -> Dict[str, Any]:
+        return {'file': str(path), 'status': status, 'mode': mode, 'reason': reason, 'output': output[-4000:]}
+
+
+class FidelityLinter:
+    """ground truth 충실도 표기와 샘플 known-answer 테스트 교차 검증"""
+
+    def __init__(self, ground_truth_dir: str = GROUND_TRUTH_DIR, test_files_dir: str = TEST_FILES_DIR,
+                 verifier: Optional[CorpusVerifier] = None):
+        self.ground_truth_dir = Path(ground_truth_dir)
+        self.test_files_dir = Path(test_files_dir)
+        self.verifier = verifier
+        self.taxonomy = AlgorithmTaxonomy()
+
+    def known_answer_families(self, source: str) -> Dict[str, List[str]]:
+        """known-answer 테스트가 검증하는 알고리즘 계열 {계열: [테스트 함수]}"""
+        families: Dict[str, List[str]] = {}
+        for name, comment in re.findall(r'fn\s+(\w*known_answer\w*)\s*\(\)\s*\{\s*//([^\n]*)', source):
+            tokens = re.findall(r'[A-Za-z0-9][A-Za-z0-9+/\-]*[A-Za-z0-9+]', comment)
+            tokens += [label for pattern, labels in KAT_REFERENCES.items() if re.search(pattern, comment)
+                       for label in labels]
+            for token in tokens:
+                node = self.taxonomy.resolve(token)
+                family = node[1]
+                if self.taxonomy.is_known(node) and family:
+                    tests = families.setdefault(family, [])
+                    if name not in tests:
+                        tests.append(name)
+        return families
+
+    @staticmethod
+    def expected_labels(ground_truth: Dict[str, Any]) -> List[str]:
+        return MetricsCalculator.get_expected_labels(ground_truth)
+
+    def find_sample(self, agent_type: str, test_id: str) -> Optional[Path]:
+        candidates = sorted((self.test_files_dir / agent_type).glob(f"{test_id}.*"))
+        if candidates:
+            return candidates[0]
+        # 디렉토리 샘플 (매니페스트 + 래퍼 코드)
+        directory = self.test_files_dir / agent_type / test_id
+        return directory if is_manifest_sample(directory) else None
+
+    def lint(self) -> List[Dict[str, Any]]:
+        issues = []
+        for gt_path, sample, source, ground_truth in self.samples():
+            issues.extend(self._lint_sample(gt_path, sample, source, ground_truth))
+        issues.extend(self._lint_duplicates())
+        issues.extend(self._lint_canaries())
+        issues.extend(self._lint_prompt_inputs())
+        return issues
+
+    def samples(self):
+        """(ground truth 경로, 샘플 경로 | None, 샘플 코드, ground truth) 순회"""
+        for gt_path in sorted(self.ground_truth_dir.glob('*/*.json')):
+            try:
+                with open(gt_path, 'r', encoding='utf-8') as f:
+                    ground_truth = json.load(f)
+            except (json.JSONDecodeError, OSError):
+                continue
+            if not isinstance(ground_truth, dict):
+                continue
+
+            sample = self.find_sample(gt_path.parent.name, gt_path.stem)
+            if sample and sample.is_dir():
+                source = render_sample(sample)
+            else:
+                source = sample.read_text(encoding='utf-8', errors='ignore') if sample else ''
+            yield gt_path, sample, source, ground_truth
+
+    def duplicate_clusters(self) -> Tuple[List[Tuple[str, str, float]], List[List[str]]]:
+        """근사 중복 쌍과 클러스터 (샘플 키: '<agent_type>/<test_id>')"""
+        texts = {f"{gt_path.parent.name}/{gt_path.stem}": source
+                 for gt_path, sample, source, _ in self.samples() if sample}
+        pairs = near_duplicates(texts)
+        return pairs, clusters_from_pairs(pairs)
+
+    def _lint_duplicates(self, clusters_path: str = CLUSTERS_PATH) -> List[Dict[str, Any]]:
+        """근사 중복 클러스터가 기록과 같고, 멤버의 분할이 하나인지 검사"""
+        _, computed = self.duplicate_clusters()
+        recorded = load_clusters(clusters_path)
+        issues = [{'level': level, 'ground_truth': str(self.ground_truth_dir / f"{key}.json"), 'sample': None,
+                   'message': message} for level, key, message in cluster_issues(computed, recorded)]
+        for members in computed:
+            splits = {}
+            for key in members:
+                agent_type, test_id = key.split('/', 1)
+                with open(self.ground_truth_dir / f"{key}.json", 'r', encoding='utf-8') as f:
+                    splits[key] = sample_split(agent_type, test_id, json.load(f))
+            if len(set(splits.values())) > 1:
+                issues.append({'level': 'error', 'ground_truth': str(self.ground_truth_dir / f"{members[0]}.json"),
+                               'sample': None, 'message': f"near-duplicate samples are in different splits: {splits}"})
+        return issues
+
+    def _lint_canaries(self, manifest_path: str = CANARY_MANIFEST_PATH) -> List[Dict[str, Any]]:
+        """생성 샘플의 카나리가 data/canary_manifest.json 기록과 같은지 검사"""
+        samples = generated_samples(str(self.test_files_dir), str(self.ground_truth_dir))
+        issues = canary_issues(load_canary_manifest(manifest_path), samples)
+        return [{'level': level, 'ground_truth': str(self.ground_truth_dir / f"{key}.json"), 'sample': None,
+                 'message': message} for level, key, message in issues]
+
+    def _lint_prompt_inputs(self) -> List[Dict[str, Any]]:
+        """탐지기/모델에 넘기는 input_data와 도구 작업 공간에 샘플의 테스트 모듈이 남지 않았는지 검사"""
+        from utils.harness_tools import SampleWorkspace
+
+        manager = TestCaseManager(str(self.test_files_dir.parent / 'test_cases'), str(self.ground_truth_dir),
+                                  str(self.test_files_dir))
+        issues = []
+        for agent_type in AGENT_TYPES:
+            for test_case in manager.load_test_cases(agent_type):
+                gt_path = self.ground_truth_dir / agent_type / f"{test_case['test_id']}.json"
+                if TEST_MARKER in str(test_case.get('input_data', '')):
+                    issues.append({'level': 'error', 'ground_truth': str(gt_path), 'sample': test_case.get('file_path'),
+                                   'message': f"test module ({TEST_MARKER}) reaches the prompt input"})
+                leaked = [path for path, text in SampleWorkspace.from_test_case(test_case).files.items()
+                          if TEST_MARKER in text]
+                if leaked:
+                    issues.append({'level': 'error', 'ground_truth': str(gt_path), 'sample': test_case.get('file_path'),
+                                   'message': f"test module ({TEST_MARKER}) reaches the tool workspace: {leaked}"})
+        return issues
+
+    def _lint_sample(self, gt_path: Path, sample: Optional[Path], source: str,
+                     ground_truth: Dict[str, Any]) -> List[Dict[str, Any]]:
+        issues = []
+
+        def issue(level: str, message: str):
+            issues.append({'level': level, 'ground_truth': str(gt_path),
+                           'sample': str(sample) if sample else None, 'message': message})
+
+        fidelity = label_fidelity(ground_truth)
+        kat_families = self.known_answer_families(source)
+        labels = self.expected_labels(ground_truth)
+
+        for algorithm, level in fidelity.items():
+            if level not in FIDELITY_LEVELS:
+                issue('error', f"{algorithm}: unknown fidelity '{level}' (choose from {FIDELITY_LEVELS})")
+                continue
+
+            family = self.taxonomy.resolve(algorithm)[1]
+            has_kat = family in kat_families
+            if level == FIDELITY_EXACT and not has_kat:
+                issue('error', f"{algorithm}: claims exact but the sample has no known-answer test for {family}")
+            elif level != FIDELITY_EXACT and has_kat:
+                issue('warning', f"{algorithm}: known-answer test {kat_families[family]} present "
+                                 f"but fidelity is '{level}'")
+
+        for algorithm, info in disputed_labels(ground_truth).items():
+            if algorithm not in labels:
+                issue('error', f"{algorithm}: marked disputed but is not an expected label")
+            if not info['alternatives']:
+                issue('warning', f"{algorithm}: marked disputed without alternatives (no effect on scoring)")
+            for alternative in info['alternatives']:
+                if not self.taxonomy.is_known(self.taxonomy.resolve(alternative)):
+                    issue('error', f"{algorithm}: alternative '{alternative}' is not in the taxonomy")
+
+        for algorithm, category in recorded_weakness(ground_truth).items():
+            if algorithm not in labels:
+                issue('error', f"{algorithm}: weakness recorded but is not an expected label")
+            if category not in WEAKNESS_CATEGORIES:
+                issue('error', f"{algorithm}: unknown weakness '{category}' (choose from {WEAKNESS_CATEGORIES})")
+
+        for entry in recorded_mode_findings(ground_truth):
+            if entry['algorithm'] not in labels:
+                issue('error', f"{entry['algorithm']}: mode findings recorded but is not an expected label")
+            if entry['mode'] and entry['mode'] not in MODES:
+                issue('error', f"{entry['algorithm']}: unknown mode '{entry['mode']}' (choose from {MODES})")
+            for weakness in entry['weaknesses']:
+                if weakness not in WEAKNESSES:
+                    issue('error', f"{entry['algorithm']}: unknown mode weakness '{weakness}' "
+                                   f"(choose from {list(WEAKNESSES)})")
+            expected_cwe = [WEAKNESS_CWE[name] for name in entry['weaknesses'] if name in WEAKNESS_CWE]
+            if sorted(entry['cwe']) != sorted(expected_cwe):
+                issue('error', f"{entry['algorithm']}: cwe {entry['cwe']} does not match weaknesses "
+                               f"(expected {expected_cwe})")
+            if entry['timing_safety'] and entry['timing_safety'] not in TIMING_SAFETY_LABELS:
+                issue('error', f"{entry['algorithm']}: unknown timing_safety '{entry['timing_safety']}' "
+                               f"(choose from {TIMING_SAFETY_LABELS})")
+            elif entry['timing_safety']:
+                timing = [name for name in entry['weaknesses'] if name in TIMING_SAFETY_WEAKNESSES]
+                if bool(timing) != (entry['timing_safety'] == TIMING_LEAKY):
+                    issue('error', f"{entry['algorithm']}: timing_safety '{entry['timing_safety']}' "
+                                   f"does not match timing weaknesses {timing}")
+        # 상수 시간 대조 샘플은 약점 없이 timing_safety 라벨만 있어도 됨
+        if sample_task(ground_truth) == TASK_MISUSE and not any(
+                entry['weaknesses'] or entry['timing_safety'] for entry in recorded_mode_findings(ground_truth)):
+            issue('error', "misuse-detection sample has no weaknesses in mode_findings")
+
+        safe_labels = MetricsCalculator.get_quantum_safe_labels(ground_truth)
+        for algorithm in safe_labels:
+            if self.taxonomy.resolve(algorithm)[0] != 'post_quantum':
+                issue('error', f"{algorithm}: listed as quantum-safe but is not a post_quantum taxonomy label")
+            if algorithm in labels:
+                issue('error', f"{algorithm}: listed both as quantum-safe and as an expected vulnerable label")
+
+        usage = recorded_usage(ground_truth)
+        for algorithm, kind in usage.items():
+            if algorithm not in labels + safe_labels:
+                issue('error', f"{algorithm}: usage recorded but is not an expected label")
+            if kind not in USAGE_KINDS:
+                issue('error', f"{algorithm}: unknown usage '{kind}' (choose from {USAGE_KINDS})")
+            elif kind == USAGE_EXTERNAL and algorithm in fidelity:
+                issue('error', f"{algorithm}: external-library label has no implementation to grade "
+                               f"but fidelity is '{fidelity[algorithm]}'")
+        if sample and USAGE_EXTERNAL in usage.values() and not re.search(r'extern\s+"(?:C|system)"', source):
+            issue('warning', f"external-library labels but the sample declares no extern \"C\" bindings")
+        if sample and (sample.is_dir() or sample.suffix == '.rs') and UNSEEDED_RNG.search(source):
+            issue('warning', "sample draws from thread_rng()/rand::random; inject a seeded PRNG (StdRng) "
+                             "through the constructor so outputs are reproducible")
+
+        # 주석 노이즈: 기록된 주입 줄이 샘플과 맞아야 줄 범위도 맞음 (샘플을 손으로 고친 뒤 어긋남)
+        source_lines = source.split('\n')
+        for claim in comment_noise_claims(ground_truth):
+            line = claim.get('line', 0)
+            if sample and not (0 < line <= len(source_lines) and source_lines[line - 1].strip() == claim.get('text')):
+                issue('error', f"comment-noise claim for {claim.get('algorithm')} is no longer at line {line} "
+                               f"(line ranges may be stale)")
+
+        # 다중 파일 샘플의 "파일:시작-끝" 위치는 샘플 디렉토리 안의 파일과 줄 수에 맞아야 함
+        for algorithm, ranges in ground_truth.get('expected_findings', {}).get('locations', {}).items():
+            for location in ranges:
+                file, span = parse_file_location(location)
+                if not file or not sample:
+                    continue
+                target = sample / file if sample.is_dir() else None
+                if not target or not target.is_file():
+                    issue('error', f"{algorithm}: location {location} names a file that is not in the sample")
+                elif span and span[1] > len(target.read_text(encoding='utf-8', errors='ignore').split('\n')):
+                    issue('error', f"{algorithm}: location {location} is past the end of {file}")
+
+        # 난독화 패스가 동작을 바꾼 샘플은 충실도 표기를 믿을 수 없음 (utils/obfuscation_verify.py)
+        for check in ground_truth.get('semantics_check', []):
+            if check.get('status') == 'broken':
+                issue('error', f"obfuscation pass {check.get('pass')} changed behavior: {check.get('reason')}")
+
+        issues.extend(self._lint_task(gt_path, sample, ground_truth, labels))
+        for level, message in provenance_issues(ground_truth) + tag_issues(ground_truth):
+            issue(level, message)
+
+        labeled_families = {self.taxonomy.resolve(label)[1] for label in labels + safe_labels}
+        fidelity_families = {self.taxonomy.resolve(algorithm)[1] for algorithm in fidelity}
+        for family, tests in kat_families.items():
+            if family not in labeled_families:
+                issue('warning', f"{family}: known-answer test {tests} present but no label for it")
+            elif family not in fidelity_families:
+                issue('warning', f"{family}: known-answer test {tests} present but label fidelity is not recorded")
+
+        # 실제 실행: exact 주장이 있는 샘플의 테스트가 통과해야 함
+        if self.verifier and sample and FIDELITY_EXACT in fidelity.values():
+            result = self.verifier.verify(sample)
+            if result['status'] == STATUS_FAILED:
+                issue('error', f"claims exact but embedded tests fail: {result['reason']}")
+            elif result['status'] == STATUS_SKIPPED:
+                issue('warning', f"could not run embedded tests: {result['reason']}")
+
+        return issues
+
+    def _lint_task(self, gt_path: Path, sample: Optional[Path], ground_truth: Dict[str, Any],
+                   labels: List[str]) -> List[Dict[str, Any]]:
+        """과제 유형별 검사 (manifest-inference: 라벨마다 선언된 의존성 근거가 있어야 함)"""
+        issues = []
+
+        def issue(level: str, message: str):
+            issues.append({'level': level, 'ground_truth': str(gt_path),
+                           'sample': str(sample) if sample else None, 'message': message})
+
+        task = sample_task(ground_truth)
+        if task not in TASKS:
+            issue('error', f"unknown task '{task}' (choose from {TASKS})")
+            return issues
+
+        introductions = recorded_introductions(ground_truth)
+        if task != TASK_DIFF and introductions:
+            issue('error', f"introduced_in recorded but task is '{task}'")
+        if task == TASK_DIFF:
+            self._lint_diff(sample, introductions, labels, issue)
+            return issues
+
+        evidence = recorded_dependencies(ground_truth)
+        if task != TASK_MANIFEST:
+            if evidence:
+                issue('error', f"dependency evidence recorded but task is '{task}'")
+            return issues
+
+        if not sample or not sample.is_dir():
+            issue('error', "manifest-inference sample must be a directory with a package manifest")
+            return issues
+
+        declared = manifest_dependencies(sample)
+        for algorithm in evidence:
+            if algorithm not in labels:
+                issue('error', f"{algorithm}: dependency evidence recorded but is not an expected label")
+        for algorithm in labels:
+            if not evidence.get(algorithm):
+                issue('error', f"{algorithm}: no dependency evidence for manifest-inference label")
+                continue
+            family = self.taxonomy.resolve(algorithm)[1]
+            for dependency in evidence[algorithm]:
+                if dependency.lower() not in declared:
+                    issue('error', f"{algorithm}: evidence '{dependency}' is not declared in the manifest")
+                    continue
+                surface = implied_families(dependency)
+                if surface is not None and family not in surface:
+                    issue('warning', f"{algorithm}: '{dependency}' is not known to provide {family}")
+        return issues
+
+    @staticmethod
+    def _lint_diff(sample: Optional[Path], introductions: Dict[str, Dict[str, Any]], labels: List[str], issue):
+        """diff-introduction: 라벨마다 도입 위치가 실제로 줄을 추가한 hunk를 가리켜야 함"""
+        if not sample or not is_diff_sample(sample):
+            issue('error', f"diff-introduction sample must be one of {', '.join(DIFF_SUFFIXES)}")
+            return
+
+        hunks = parse_patch(sample.read_text(encoding='utf-8', errors='ignore'))
+        for algorithm in introductions:
+            if algorithm not in labels:
+                issue('error', f"{algorithm}: introduced_in recorded but is not an expected label")
+        for algorithm in labels:
+            location = introductions.get(algorithm)
+            if not location:
+                issue('error', f"{algorithm}: no introduced_in location for diff-introduction label")
+                continue
+            found = find_expected_hunks(location, hunks)
+            missing = sorted(set(expected_hunks(location)) - {hunk['hunk'] for hunk in found})
+            if missing:
+                issue('error', f"{algorithm}: hunk {missing} not found in {location.get('file')} "
+                               f"(commit {location.get('commit') or '-'})")
+            for hunk in found:
+                if not hunk['added']:
+                    issue('error', f"{algorithm}: hunk {hunk['hunk']} of {hunk['file']} adds no lines")
+
+
+class CorpusStatistics:
+    """코퍼스 구성 통계 (계열/언어/난이도 분포와 라벨 공백)"""
+
+    def __init__(self, ground_truth_dir: str = GROUND_TRUTH_DIR, test_files_dir: str = TEST_FILES_DIR,
+                 min_samples: int = 1, sample_filter: Optional[SampleFilter] = None):
+        self.ground_truth_dir = Path(ground_truth_dir)
+        self.test_files_dir = Path(test_files_dir)
+        self.min_samples = min_samples
+        self.sample_filter = sample_filter
+        self.taxonomy = AlgorithmTaxonomy()
+        self.finder = FidelityLinter(ground_truth_dir, test_files_dir)
+
+    @staticmethod
+    def language(sample: Path) -> str:
+        """샘플 언어 (디렉토리 샘플은 안에서 가장 많은 소스 확장자)"""
+        if sample.is_dir():
+            suffixes = [path.suffix for path in sample.rglob('*') if path.suffix in LANGUAGE_NAMES]
+            if not suffixes:
+                return 'manifest'
+            return LANGUAGE_NAMES[max(sorted(set(suffixes)), key=suffixes.count)]
+        return LANGUAGE_NAMES.get(sample.suffix.lower(), sample.suffix.lstrip('.').lower() or 'unknown')
+
+    @staticmethod
+    def size_bucket(lines: int) -> str:
+        for bound in SIZE_BUCKETS:
+            if lines <= bound:
+                return f"<={bound}"
+        return f">{SIZE_BUCKETS[-1]}"
+
+    def collect(self) -> Dict[str, Any]:
+        counts: Dict[str, Dict[str, int]] = {key: {} for key in (
+            'agent_type', 'category', 'family', 'role', 'language', 'difficulty', 'size', 'provenance', 'license',
+            'tag')}
+        unknown_labels: Dict[str, List[str]] = {}
+        missing_samples, sizes, described, attributions = [], [], set(), []
+
+        def count(key: str, value: str):
+            counts[key][value] = counts[key].get(value, 0) + 1
+
+        for gt_path in sorted(self.ground_truth_dir.glob('*/*.json')):
+            try:
+                with open(gt_path, 'r', encoding='utf-8') as f:
+                    ground_truth = json.load(f)
+            except (json.JSONDecodeError, OSError):
+                continue
+            if not isinstance(ground_truth, dict):
+                continue
+
+            agent_type = gt_path.parent.name
+            sample = self.finder.find_sample(agent_type, gt_path.stem)
+            if sample is None:
+                missing_samples.append(str(gt_path))
+                continue
+            described.add(sample)
+            if self.sample_filter and not self.sample_filter.matches_sample(agent_type, gt_path.stem, ground_truth,
+                                                                            str(sample)):
+                continue
+
+            count('agent_type', agent_type)
+            count('language', self.language(sample))
+            count('difficulty', str(ground_truth.get('difficulty', 'medium')))
+            provenance = sample_provenance(ground_truth)
+            count('provenance', provenance_label(provenance))
+            if provenance.get('license'):
+                count('license', provenance['license'])
+            for tag in sorted(set(tag for tag in ground_truth.get('tags', []) if isinstance(tag, str))):
+                count('tag', tag)
+            if attribution(gt_path.stem, provenance):
+                attributions.append(attribution(gt_path.stem, provenance))
+            source = render_sample(sample) if sample.is_dir() else sample.read_text(encoding='utf-8', errors='ignore')
+            lines = len(source.splitlines())
+            sizes.append(lines)
+            count('size', self.size_bucket(lines))
+
+            labels = (MetricsCalculator.get_expected_labels(ground_truth)
+                      + MetricsCalculator.get_quantum_safe_labels(ground_truth))
+            nodes = set()
+            for label in labels:
+                node = self.taxonomy.resolve(label)
+                if self.taxonomy.is_known(node) and node[1]:
+                    nodes.add(node[:2])
+                elif not self.taxonomy.is_known(node):
+                    unknown_labels.setdefault(str(label), []).append(gt_path.stem)
+            # 샘플 하나는 계열/카테고리/역할마다 한 번만 셈
+            for category in sorted({node[0] for node in nodes}):
+                count('category', category)
+            for node in sorted(nodes):
+                count('family', node[1])
+            for role in sorted({self.taxonomy.role(node) or 'other' for node in nodes}):
+                count('role', role)
+
+        orphans = []
+        # 필터가 있으면 ground truth 없는 샘플은 식을 평가할 수 없으므로 목록에서 뺌
+        for agent_dir in sorted(path for path in self.test_files_dir.iterdir()
+                                if path.is_dir() and not self.sample_filter):
+            for path in sorted(agent_dir.iterdir()):
+                if path.name.startswith('.') or (path.is_dir() and not is_manifest_sample(path)):
+                    continue
+                if path not in described:
+                    orphans.append(str(path))
+
+        families = [(category, family) for category, members in TAXONOMY.items() for family in members]
+        sparse = [{'category': category, 'family': family, 'samples': counts['family'].get(family, 0)}
+                  for category, family in families if counts['family'].get(family, 0) < self.min_samples]
+
+        sizes.sort()
+        return {
+            'samples': len(sizes),
+            'filter': str(self.sample_filter) if self.sample_filter else None,
+            'by_agent_type': counts['agent_type'],
+            'by_category': counts['category'],
+            'by_family': dict(sorted(counts['family'].items(), key=lambda item: (-item[1], item[0]))),
+            'by_role': counts['role'],
+            'by_language': dict(sorted(counts['language'].items(), key=lambda item: (-item[1], item[0]))),
+            'by_difficulty': counts['difficulty'],
+            'by_provenance': dict(sorted(counts['provenance'].items(), key=lambda item: (-item[1], item[0]))),
+            'by_license': dict(sorted(counts['license'].items(), key=lambda item: (-item[1], item[0]))),
+            'by_tag': dict(sorted(counts['tag'].items(), key=lambda item: (-item[1], item[0]))),
+            'attributions': attributions,
+            'size': {
+                'lines_min': sizes[0] if sizes else 0,
+                'lines_median': sizes[len(sizes) // 2] if sizes else 0,
+                'lines_max': sizes[-1] if sizes else 0,
+                'histogram': {self.size_bucket(bound): counts['size'].get(self.size_bucket(bound), 0)
+                              for bound in SIZE_BUCKETS + [SIZE_BUCKETS[-1] + 1]},
+            },
+            'gaps': {
+                'min_samples': self.min_samples,
+                'sparse_families': sparse,
+                'unknown_labels': unknown_labels,
+                'ground_truth_without_sample': missing_samples,
+                'samples_without_ground_truth': orphans,
+            },
+        }
+
+
+def print_stats(stats: Dict[str, Any]):
+    total = stats['samples'] or 1
+
+    def table(title: str, counts: Dict[str, int]):
+        width = max([24] + [len(name) for name in counts])
+        print(f"\n{title}")
+        print(f"  {'항목':<{width}} {'샘플':>6} {'비율':>8}")
+        for name, value in counts.items():
+            print(f"  {name:<{width}} {value:>6} {value / total:>8.1%}")
+
+    print(f"📚 코퍼스 통계: 라벨이 있는 샘플 {stats['samples']}개"
+          f"{' (필터: ' + stats['filter'] + ')' if stats.get('filter') else ''}")
+    table("🗂️  에이전트 유형별", stats['by_agent_type'])
+    table("🧮 알고리즘 카테고리별", stats['by_category'])
+    table("🔑 기본 요소 역할별", stats['by_role'])
+    table("🧬 알고리즘 계열별", stats['by_family'])
+    table("💬 언어별", stats['by_language'])
+    table("🎚️  난이도별", stats['by_difficulty'])
+    table("📜 출처별", stats['by_provenance'])
+    if stats['by_license']:
+        table("⚖️  원본 라이선스별", stats['by_license'])
+    if stats['by_tag']:
+        table("🏷️  태그별", stats['by_tag'])
+    if stats['attributions']:
+        print(f"\n🖋️  원본 저작권 표시 (adapted-from-oss {len(stats['attributions'])}개)")
+    for entry in stats['attributions']:
+        print(f"  {entry['test_id']}: {entry['source'] or '-'} ({entry['license'] or '-'}"
+              f"{', ' + entry['copyright'] if entry.get('copyright') else ''})")
+
+    size = stats['size']
+    table(f"📏 줄 수 분포 (최소 {size['lines_min']} / 중앙값 {size['lines_median']} / 최대 {size['lines_max']})",
+          size['histogram'])
+
+    gaps = stats['gaps']
+    print(f"\n🕳️  라벨 공백")
+    if gaps['sparse_families']:
+        families = ', '.join(f"{item['family']}({item['samples']})" for item in gaps['sparse_families'])
+        print(f"  ⚠️  샘플 {gaps['min_samples']}개 미만 계열 {len(gaps['sparse_families'])}개: {families}")
+    if gaps['unknown_labels']:
+        print(f"  ⚠️  분류 체계에 없는 라벨: {', '.join(sorted(gaps['unknown_labels']))}")
+    for key, label in (('ground_truth_without_sample', '샘플 파일이 없는 ground truth'),
+                       ('samples_without_ground_truth', 'ground truth가 없는 샘플')):
+        if gaps[key]:
+            print(f"  ⚠️  {label} {len(gaps[key])}개: {', '.join(Path(path).name for path in gaps[key][:10])}"
+                  f"{' …' if len(gaps[key]) > 10 else ''}")
+    if not any(gaps[key] for key in gaps if key != 'min_samples'):
+        print("  ✅ 없음")
+
+
+def run_workspace(args) -> int:
+    workspace = SampleWorkspace(args.root, args.workspace_dir)
+    members, excluded = workspace.generate()
+    manifest = workspace.workspace_dir / 'Cargo.toml'
+
+    print(f"📦 샘플 워크스페이스: {manifest} (크레이트 {len(members)}개)")
+    for path, member in members.items():
+        crates = f" [{', '.join(member['crates'])}]" if member['crates'] else ''
+        print(f"  ✅ {member['package']}{crates}")
+    for path, reason in excluded.items():
+        print(f"  ⏭️  {path}: {reason}")
+    print(f"\n실행: cargo test --workspace --manifest-path {manifest}")
+    return 0
+
+
+def run_verify(args) -> int:
+    workspace = None if args.no_workspace else SampleWorkspace(args.root, args.workspace_dir)
+    verifier = CorpusVerifier(offline=args.offline, timeout=args.timeout, target_dir=args.target_dir,
+                              workspace=workspace)
+    samples = [Path(f) for f in args.file] if args.file else verifier.find_samples(args.root)
+
+    if not samples:
+        print("⚠️  테스트를 포함한 샘플이 없습니다")
+        return 0
+
+    print(f"🔬 코퍼스 검증: {len(samples)}개 샘플")
+    icons = {STATUS_PASSED: '✅', STATUS_FAILED: '❌', STATUS_SKIPPED: '⏭️ '}
+
+    results = []
+    for path in samples:
+        result = verifier.verify(path)
+        results.append(result)
+
+        detail = f"{result['tests_passed']} passed" if result['status'] != STATUS_SKIPPED else result['reason']
+        if result['status'] == STATUS_FAILED:
+            detail = f"{result['reason']} ({result['tests_passed']} passed, {result['tests_failed']} failed)"
+        print(f"  {icons[result['status']]} {path} [{result['mode']}] {detail}")
+        if result['status'] == STATUS_FAILED and args.verbose:
+            print(result['output'])
+
+    counts = {status: sum(1 for r in results if r['status'] == status)
+              for status in (STATUS_PASSED, STATUS_FAILED, STATUS_SKIPPED)}
+    print(f"\n📊 통과 {counts[STATUS_PASSED]} / 실패 {counts[STATUS_FAILED]} / 건너뜀 {counts[STATUS_SKIPPED]}")
+
+    if args.output:
+        Path(args.output).parent.mkdir(parents=True, exist_ok=True)
+        with open(args.output, 'w', encoding='utf-8') as f:
+            json.dump({'summary': counts, 'results': results}, f, indent=2, ensure_ascii=False)
+        print(f"💾 결과 저장: {args.output}")
+
+    return 1 if counts[STATUS_FAILED] else 0
+
+
+def run_buildcheck(args) -> int:
+    checker = CorpusBuildChecker(toolchain=args.toolchain, container=args.container, image=args.image,
+                                 offline=args.offline, timeout=args.timeout)
+    samples = [Path(f) for f in args.file] if args.file else checker.find_samples(args.root)
+    if not samples:
+        print("⚠️  Rust 샘플이 없습니다")
+        return 0
+
+    version, error = checker.toolchain_version()
+    if version is None:
+        where = f"이미지 {checker.image}" if args.container else f"툴체인 {args.toolchain}"
+        print(f"❌ {where}을(를) 쓸 수 없습니다: {error}")
+        if not args.container:
+            print(f"   rustup toolchain install {args.toolchain} 또는 --toolchain으로 설치된 툴체인 지정")
+        return 1
+
+    isolation = f"{args.container} ({checker.image})" if args.container else 'local'
+    print(f"🏗️  코퍼스 빌드 검사: {len(samples)}개 샘플, {version} [{isolation}]")
+    icons = {STATUS_BUILT: '✅', STATUS_FAILED: '❌', STATUS_SKIPPED: '⏭️ '}
+
+    results = []
+    for path in samples:
+        result = checker.check(path)
+        results.append(result)
+        detail = f" {result['reason']}" if result['reason'] else ''
+        print(f"  {icons[result['status']]} {path} [{result['mode']}]{detail}")
+        if result['status'] == STATUS_FAILED and args.verbose:
+            print(result['output'])
+
+    counts = {status: sum(1 for r in results if r['status'] == status)
+              for status in (STATUS_BUILT, STATUS_FAILED, STATUS_SKIPPED)}
+    print(f"\n📊 빌드 {counts[STATUS_BUILT]} / 실패 {counts[STATUS_FAILED]} / 건너뜀 {counts[STATUS_SKIPPED]}")
+    failed = [r['file'] for r in results if r['status'] == STATUS_FAILED]
+    if failed:
+        print("🚨 더 이상 빌드되지 않는 샘플:")
+        for file in failed:
+            print(f"  - {file}")
+
+    if args.output:
+        Path(args.output).parent.mkdir(parents=True, exist_ok=True)
+        with open(args.output, 'w', encoding='utf-8') as f:
+            json.dump({'toolchain': version, 'isolation': isolation, 'summary': counts, 'results': results},
+                      f, indent=2, ensure_ascii=False)
+        print(f"💾 결과 저장: {args.output}")
+
+    return 1 if failed else 0
+
+
+def run_lint(args) -> int:
+    verifier = CorpusVerifier(offline=args.offline, timeout=args.timeout,
+                              workspace=SampleWorkspace(args.root)) if args.run else None
+    linter = FidelityLinter(args.ground_truth_dir, args.root, verifier)
+
+    print("🧬 충실도 lint")
+    issues = linter.lint()
+
+    icons = {'error': '❌', 'warning': '⚠️ '}
+    for item in issues:
+        print(f"  {icons[item['level']]} {item['ground_truth']}: {item['message']}")
+
+    errors = sum(1 for item in issues if item['level'] == 'error')
+    warnings = len(issues) - errors
+    print(f"\n📊 오류 {errors} / 경고 {warnings}")
+
+    return 1 if errors or (args.strict and warnings) else 0
+
+
+def run_dedup(args) -> int:
+    linter = FidelityLinter(args.ground_truth_dir, args.root)
+    pairs, computed = linter.duplicate_clusters()
+    recorded = load_clusters(args.clusters)
+    clusters = assign_cluster_ids(computed, recorded)
+    print_duplicates(pairs, clusters)
+
+    if args.write:
+        write_clusters(clusters, args.clusters)
+        print(f"\n💾 클러스터 기록: {args.clusters}")
+        return 0
+    issues = cluster_issues(computed, recorded)
+    for _, _, message in issues:
+        print(f"  ❌ {message}")
+    return 1 if issues else 0
+
+
+def run_stats(args) -> int:
+    try:
+        sample_filter = SampleFilter(args.filter) if args.filter else None
+    except ValueError as e:
+        print(f"❌ {e}")
+        return 2
+    stats = CorpusStatistics(args.ground_truth_dir, args.root, args.min_samples, sample_filter).collect()
+    if args.json:
+        print(json.dumps(stats, indent=2, ensure_ascii=False))
+    else:
+        print_stats(stats)
+
+    if args.output:
+        Path(args.output).parent.mkdir(parents=True, exist_ok=True)
+        with open(args.output, 'w', encoding='utf-8') as f:
+            json.dump(stats, f, indent=2, ensure_ascii=False)
+        if not args.json:
+            print(f"\n💾 결과 저장: {args.output}")
+    return 0
+
+
+def main():
+    # corpus_import, corpus_package가 이 모듈의 경로 상수를 쓰므로 순환 import를 피해 여기서 가져옴
+    from utils.corpus_import import MAX_FILES, MAX_LINES, MIN_LINES, STAGING_DIR, run_import, run_promote
+    from utils.corpus_package import LICENSES_DIR, run_package, run_unpack
+
+    parser = argparse.ArgumentParser(description='테스트 코퍼스 관리 도구')
+    subparsers = parser.add_subparsers(dest='command', required=True)
+
+    verify_parser = subparsers.add_parser('verify', help='샘플 내장 known-answer 테스트 실행')
+    verify_parser.add_argument('--root', default=TEST_FILES_DIR, help='샘플 탐색 루트 디렉토리')
+    verify_parser.add_argument('--file', nargs='+', help='특정 샘플만 검증')
+    verify_parser.add_argument('--offline', action='store_true',
+                               help='cargo 의존성을 로컬 캐시에서만 해석 (cargo --offline)')
+    verify_parser.add_argument('--timeout', type=int, default=600, help='샘플당 빌드/실행 제한 시간(초)')
+    verify_parser.add_argument('--target-dir', help='cargo 공용 target 디렉토리')
+    verify_parser.add_argument('--workspace-dir', default=SAMPLE_WORKSPACE_DIR, help='생성 샘플 워크스페이스 디렉토리')
+    verify_parser.add_argument('--no-workspace', action='store_true',
+                               help='외부 크레이트 샘플을 워크스페이스 대신 샘플마다 임시 cargo 프로젝트로 테스트')
+    verify_parser.add_argument('--output', help='결과 JSON 저장 경로')
+    verify_parser.add_argument('--verbose', action='store_true', help='실패한 샘플의 빌드/테스트 로그 출력')
+
+    workspace_parser = subparsers.add_parser('workspace', help='단일 파일 Rust 샘플을 감싼 cargo 워크스페이스 생성')
+    workspace_parser.add_argument('--root', default=TEST_FILES_DIR, help='샘플 탐색 루트 디렉토리')
+    workspace_parser.add_argument('--workspace-dir', default=SAMPLE_WORKSPACE_DIR, help='워크스페이스 생성 위치')
+
+    build_parser = subparsers.add_parser('buildcheck', help='모든 Rust 샘플을 격리된 작업 공간에서 고정 툴체인으로 컴파일')
+    build_parser.add_argument('--root', default=TEST_FILES_DIR, help='샘플 탐색 루트 디렉토리')
+    build_parser.add_argument('--file', nargs='+', help='특정 샘플만 검사')
+    build_parser.add_argument('--toolchain', default=RUST_TOOLCHAIN, help=f'rustup 툴체인 (기본 {RUST_TOOLCHAIN})')
+    build_parser.add_argument('--container', choices=['docker', 'podman'], help='컨테이너 안에서 컴파일')
+    build_parser.add_argument('--image', help='컨테이너 이미지 (기본 rust:<toolchain>)')
+    build_parser.add_argument('--offline', action='store_true',
+                              help='cargo --offline (컨테이너 모드에서는 네트워크도 차단)')
+    build_parser.add_argument('--timeout', type=int, default=600, help='샘플당 빌드 제한 시간(초)')
+    build_parser.add_argument('--output', help='결과 JSON 저장 경로')
+    build_parser.add_argument('--verbose', action='store_true', help='실패한 샘플의 컴파일 로그 출력')
+
+    lint_parser = subparsers.add_parser('lint', help='ground truth 충실도 표기와 known-answer 테스트 교차 검증')
+    lint_parser.add_argument('--root', default=TEST_FILES_DIR, help='샘플 루트 디렉토리')
+    lint_parser.add_argument('--ground-truth-dir', default=GROUND_TRUTH_DIR, help='ground truth 디렉토리')
+    lint_parser.add_argument('--run', action='store_true', help='exact로 표기된 샘플의 테스트를 실제로 실행')
+    lint_parser.add_argument('--offline', action='store_true', help='cargo --offline (--run 사용 시)')
+    lint_parser.add_argument('--timeout', type=int, default=600, help='샘플당 빌드/실행 제한 시간(초)')
+    lint_parser.add_argument('--strict', action='store_true', help='경고도 실패로 처리')
+
+    dedup_parser = subparsers.add_parser('dedup', help='근사 중복 샘플 클러스터 (분할을 클러스터 단위로 고정)')
+    dedup_parser.add_argument('--root', default=TEST_FILES_DIR, help='샘플 루트 디렉토리')
+    dedup_parser.add_argument('--ground-truth-dir', default=GROUND_TRUTH_DIR, help='ground truth 디렉토리')
+    dedup_parser.add_argument('--clusters', default=CLUSTERS_PATH, help='클러스터 기록 경로')
+    dedup_parser.add_argument('--write', action='store_true', help='계산한 클러스터로 기록 갱신')
+
+    stats_parser = subparsers.add_parser('stats', help='계열/언어/난이도 분포와 라벨 공백')
+    stats_parser.add_argument('--root', default=TEST_FILES_DIR, help='샘플 루트 디렉토리')
+    stats_parser.add_argument('--ground-truth-dir', default=GROUND_TRUTH_DIR, help='ground truth 디렉토리')
+    stats_parser.add_argument('--min-samples', type=int, default=1,
+                              help='이보다 샘플이 적은 분류 체계 계열을 공백으로 보고 (기본 1: 샘플 없는 계열만)')
+    stats_parser.add_argument('--filter', metavar='EXPR', help='필터 식에 맞는 샘플만 셈 (utils/sample_filter.py)')
+    stats_parser.add_argument('--json', action='store_true', help='표 대신 JSON 출력')
+    stats_parser.add_argument('--output', help='결과 JSON 저장 경로')
+
+    import_parser = subparsers.add_parser('import', help='git 저장소의 암호 코드를 라벨 제안과 함께 스테이징')
+    import_parser.add_argument('--from-git', required=True, metavar='URL', help='가져올 git 저장소 (URL 또는 경로)')
+    import_parser.add_argument('--revision', help='커밋/태그/브랜치 (기본: 기본 브랜치 최신 커밋)')
+    import_parser.add_argument('--path', nargs='+', help='저장소 안에서 살펴볼 하위 경로만')
+    import_parser.add_argument('--license', help='SPDX 라이선스 식별자 (LICENSE 파일로 추정하지 못할 때)')
+    import_parser.add_argument('--max-files', type=int, default=MAX_FILES, help='스테이징할 최대 파일 수')
+    import_parser.add_argument('--min-lines', type=int, default=MIN_LINES, help='이보다 짧은 파일은 건너뜀')
+    import_parser.add_argument('--max-lines', type=int, default=MAX_LINES, help='이보다 긴 파일은 건너뜀')
+    import_parser.add_argument('--staging-dir', default=STAGING_DIR, help='스테이징 디렉토리')
+    import_parser.add_argument('--dry-run', action='store_true', help='후보와 제안 라벨만 출력')
+
+    promote_parser = subparsers.add_parser('promote', help='스테이징 리뷰에서 승인된 샘플을 코퍼스로 이동')
+    promote_parser.add_argument('--staging-dir', default=STAGING_DIR, help='스테이징 디렉토리')
+    promote_parser.add_argument('--root', default=TEST_FILES_DIR, help='샘플 루트 디렉토리')
+    promote_parser.add_argument('--ground-truth-dir', default=GROUND_TRUTH_DIR, help='ground truth 디렉토리')
+
+    package_parser = subparsers.add_parser('package', help='샘플/라벨/rubric/라이선스를 배포 아카이브로 묶기')
+    package_parser.add_argument('--out', required=True, help='아카이브 경로 (.tar.zst, .tar.gz, .tar)')
+    package_parser.add_argument('--agents', nargs='+', help='이 에이전트의 샘플만 (기본: 전체)')
+    package_parser.add_argument('--filter', metavar='EXPR', help='필터 식에 맞는 샘플만 (utils/sample_filter.py)')
+    package_parser.add_argument('--root', default=TEST_FILES_DIR, help='샘플 루트 디렉토리')
+    package_parser.add_argument('--ground-truth-dir', default=GROUND_TRUTH_DIR, help='ground truth 디렉토리')
+    package_parser.add_argument('--licenses-dir', default=LICENSES_DIR, help='SPDX 라이선스 본문 디렉토리')
+
+    unpack_parser = subparsers.add_parser('unpack', help='배포 아카이브 풀기/검증')
+    unpack_parser.add_argument('archive', help='package로 만든 아카이브')
+    unpack_parser.add_argument('--dest', help='풀 디렉토리 (기본: 아카이브 최상위 디렉토리 이름)')
+    unpack_parser.add_argument('--verify', action='store_true', help='푼 뒤 해시/버전/rubric/라이선스 검증')
+    unpack_parser.add_argument('--verify-only', action='store_true', help='임시 디렉토리에 풀어 검증만')
+    unpack_parser.add_argument('--force', action='store_true', help='비어 있지 않은 --dest에 덮어쓰기')
+
+    args = parser.parse_args()
+
+    if args.command == 'verify':
+        sys.exit(run_verify(args))
+    if args.command == 'workspace':
+        sys.exit(run_workspace(args))
+    if args.command == 'buildcheck':
+        sys.exit(run_buildcheck(args))
+    if args.command == 'lint':
+        sys.exit(run_lint(args))
+    if args.command == 'stats':
+        sys.exit(run_stats(args))
+    if args.command == 'dedup':
+        sys.exit(run_dedup(args))
+    if args.command == 'import':
+        sys.exit(run_import(args))
+    if args.command == 'promote':
+        sys.exit(run_promote(args))
+    if args.command == 'package':
+        sys.exit(run_package(args))
+    if args.command == 'unpack':
+        sys.exit(run_unpack(args))
+
+
+if __name__ == "__main__":
+    main()
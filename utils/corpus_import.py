@@ -0,0 +1,372 @@
+"""
+실제 오픈소스 코드 가져오기 (mined corpus import)
+
+합성 샘플만으로는 실무 코드의 모양(래퍼 계층, 설정 분기, 죽은 코드, 라이브러리 호출)을 반영하지 못하므로,
+공개 git 저장소를 받아 암호 코드가 든 파일을 골라 라벨 제안과 함께 스테이징 영역에 올리고, 사람이 라벨 리뷰
+CLI(utils/label_review.py --staging)로 확인한 뒤에만 코퍼스로 옮깁니다.
+
+라벨 제안 (두 기준 탐지 방식을 합침):
+    signature  알고리즘 계열별 구현 상수/구조 (utils.rationale.EVIDENCE_SIGNATURES), signature 기준선
+               (detectors/signature.py)과 같이 상수 식/표 표현/문자열 조각을 정규화한 뒤 매칭
+               다른 계열과 겹치지 않는 8자리 이상 16진 상수 하나, 또는 서로 다른 단서 2개 이상이면 제안
+    ast        함수 색인(utils.sample_index.SampleIndex)의 함수마다 이름과 본문의 식별자/문자열을 단어로 쪼개
+               (EVP_aes_256_gcm → aes-256, hashlib.sha256 → sha256) 분류 체계에 있는 이름을 찾음
+               seed, aria, ec처럼 일반 단어와 겹치는 이름(AMBIGUOUS_NAMES)은 signature가 같은 계열을 찾았을 때만 제안
+    라벨의 줄 범위는 근거가 나온 함수(함수 밖이면 그 줄)이며, 제안마다 어느 방식이 무엇을 근거로 찾았는지
+    label_proposals에 남겨 리뷰 화면에 보여 줍니다.
+
+스테이징 (data/staging/, 커밋하지 않음):
+    test_files/source_code/<test_id>.<확장자>    원본 파일 사본
+    ground_truth/source_code/<test_id>.json      제안 라벨 + provenance(adapted-from-oss, 원본 URL, 커밋, 라이선스)
+    label_audit.jsonl                            스테이징 리뷰 감사 로그
+    promote는 감사 로그에 승인(approve) 기록이 있는 샘플만 data/test_files, data/ground_truth로 옮기고
+    label_proposals를 지웁니다.
+
+라이선스는 저장소 최상위 LICENSE/COPYING 본문으로 SPDX 식별자를 추정하며(LICENSE_MARKERS), 찾지 못하면
+--license로 지정해야 합니다. 재배포 가능 여부는 코퍼스 lint가 확인합니다 (utils/provenance.py).
+
+사용법:
+    python -m utils.corpus import --from-git https://github.com/<owner>/<repo> --dry-run
+    python -m utils.corpus import --from-git https://github.com/<owner>/<repo> --revision v1.2.0 --path src/crypto
+    python -m utils.label_review review --staging --only-unreviewed
+    python -m utils.corpus promote
+"""
+
+import json
+import re
+import shutil
+import subprocess
+import tempfile
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+from detectors.signature import confident_families, find_signatures
+from utils.corpus import GROUND_TRUTH_DIR, LANGUAGE_NAMES, TEST_FILES_DIR
+from utils.label_review import AUDIT_LOG_PATH, STAGING_DIR, AuditLog
+from utils.metrics_calculator import CATEGORY_KEYWORDS
+from utils.provenance import PROVENANCE_ADAPTED
+from utils.sample_index import SampleIndex
+from utils.taxonomy import AlgorithmTaxonomy
+
+STAGING_AGENT = 'source_code'
+
+PASS_SIGNATURE = 'signature'
+PASS_AST = 'ast'
+
+# 가져올 파일 크기 (줄 수): 너무 작으면 맥락이 없고, 너무 크면 코퍼스 최대 샘플보다 큼
+MIN_LINES = 20
+MAX_LINES = 1500
+MAX_FILES = 20
+MAX_EVIDENCE = 5
+
+# 벤더링/빌드 산출물/테스트 벡터 디렉토리는 건너뜀
+SKIP_DIRS = {'.git', 'node_modules', 'vendor', 'third_party', 'target', 'build', 'dist', '__pycache__'}
+
+# 일반 단어와 겹치는 알고리즘 이름 (signature가 같은 계열을 찾아야 제안)
+AMBIGUOUS_NAMES = {'seed', 'aria', 'lea', 'tea', 'ec', 'dh', 'des', 'sha', 'hight', 'lsh'}
+
+# 라이선스 본문 단서 → SPDX 식별자 (위에서부터 처음 맞는 것)
+LICENSE_MARKERS: List[Tuple[str, List[str]]] = [
+    ('AGPL-3.0-only', ['gnu affero general public license']),
+    ('LGPL-3.0-only', ['gnu lesser general public license', 'version 3']),
+    ('LGPL-2.1-only', ['gnu lesser general public license', 'version 2.1']),
+    ('GPL-3.0-only', ['gnu general public license', 'version 3']),
+    ('GPL-2.0-only', ['gnu general public license', 'version 2']),
+    ('MPL-2.0', ['mozilla public license', '2.0']),
+    ('Apache-2.0', ['apache license', 'version 2.0']),
+    ('BSD-3-Clause', ['redistribution and use in source and binary forms', 'neither the name']),
+    ('BSD-2-Clause', ['redistribution and use in source and binary forms']),
+    ('ISC', ['permission to use, copy, modify, and/or distribute this software']),
+    ('MIT', ['permission is hereby granted, free of charge']),
+    ('Unlicense', ['this is free and unencumbered software released into the public domain']),
+    ('CC0-1.0', ['cc0 1.0 universal']),
+    ('Zlib', ['this software is provided \'as-is\'', 'altered source versions must be plainly marked']),
+]
+LICENSE_FILES = ['LICENSE', 'LICENSE.md', 'LICENSE.txt', 'COPYING', 'COPYING.md', 'LICENCE']
+
+_WORD = re.compile(r'[A-Z]+(?=[A-Z][a-z])|[A-Z]?[a-z]+|[A-Z]+|\d+')
+_TOKEN = re.compile(r'[A-Za-z_][A-Za-z0-9_]*')
+
+
+def detect_license(repo_dir: Path) -> Tuple[Optional[str], Optional[str]]:
+    """저장소 최상위 라이선스 파일 → (SPDX 식별자, 첫 저작권 표시 줄)"""
+    for name in LICENSE_FILES:
+        path = repo_dir / name
+        if not path.is_file():
+            continue
+        text = path.read_text(encoding='utf-8', errors='ignore')
+        lowered = ' '.join(text.lower().split())
+        spdx = next((license_id for license_id, markers in LICENSE_MARKERS
+                     if all(marker in lowered for marker in markers)), None)
+        notice = next((line.strip() for line in text.splitlines() if line.strip().lower().startswith('copyright')),
+                      None)
+        return spdx, notice
+    return None, None
+
+
+def repository_name(url: str) -> str:
+    return re.sub(r'\.git$', '', url.rstrip('/')).rsplit('/', 1)[-1]
+
+
+def source_url(url: str, revision: str, relative: str) -> str:
+    """원본 파일 URL (GitHub/GitLab은 커밋 고정 blob 주소)"""
+    base = re.sub(r'\.git$', '', url.rstrip('/'))
+    if re.match(r'^https://(github\.com|gitlab\.com)/', base):
+        separator = '/-/blob/' if 'gitlab.com' in base else '/blob/'
+        return f"{base}{separator}{revision}/{relative}"
+    return f"{base}#{relative}@{revision}"
+
+
+def _names(text: str) -> List[str]:
+    """식별자/문자열 → 분류 체계 조회용 이름 (숫자 조각은 앞 단어와 이어 붙임: aes + 256 → aes-256)"""
+    names = []
+    for token in _TOKEN.findall(text):
+        words = [word.lower() for word in _WORD.findall(token)]
+        for index, word in enumerate(words):
+            if word.isdigit():
+                continue
+            if index + 1 < len(words) and words[index + 1].isdigit():
+                names.append(f"{word}-{words[index + 1]}")
+            names.append(word)
+    return names
+
+
+class LabelProposer:
+    """signature + ast 기준 방식으로 파일의 라벨 후보 제안"""
+
+    def __init__(self, taxonomy: Optional[AlgorithmTaxonomy] = None):
+        self.taxonomy = taxonomy or AlgorithmTaxonomy()
+
+    def signature_hits(self, text: str) -> Dict[str, Dict[str, Any]]:
+        """계열 → {'cues', 'lines'} (signature 기준선과 같은 정규화 매칭, 제안 기준을 넘은 계열만)"""
+        return confident_families(find_signatures(text))
+
+    def ast_hits(self, text: str) -> Dict[str, Dict[str, Any]]:
+        """이름 → {'node', 'identifiers', 'lines'} (식별자와 문자열에서, 줄은 propose가 함수 범위로 넓힘)"""
+        hits: Dict[str, Dict[str, Any]] = {}
+        for number, line in enumerate(text.splitlines(), 1):
+            for name in _names(line):
+                node = self.taxonomy.resolve(name)
+                if not self.taxonomy.is_known(node) or not node[0] or not node[1]:
+                    continue
+                entry = hits.setdefault(name, {'node': node, 'identifiers': [], 'lines': []})
+                entry['lines'].append(number)
+                if name not in entry['identifiers']:
+                    entry['identifiers'].append(name)
+        return hits
+
+    def propose(self, text: str, suffix: str) -> List[Dict[str, Any]]:
+        """[{'algorithm', 'category', 'family', 'passes', 'evidence', 'locations'}] (계열마다 하나)"""
+        index = SampleIndex.from_text(text, suffix)
+        families: Dict[str, Dict[str, Any]] = {}
+
+        def add(node, source: str, evidence: List[str], line_numbers: List[int]):
+            entry = families.setdefault(node[1], {'category': node[0], 'variants': set(), 'passes': set(),
+                                                  'evidence': [], 'lines': set()})
+            if node[2]:
+                entry['variants'].add(node[2])
+            entry['passes'].add(source)
+            entry['evidence'].extend(item for item in evidence if item not in entry['evidence'])
+            entry['lines'].update(line_numbers)
+
+        for family, hit in self.signature_hits(text).items():
+            add(self.taxonomy.resolve(family), PASS_SIGNATURE, hit['cues'], hit['lines'])
+        for name, hit in self.ast_hits(text).items():
+            if name in AMBIGUOUS_NAMES and hit['node'][1] not in families:
+                continue
+            add(hit['node'], PASS_AST, hit['identifiers'], hit['lines'])
+
+        proposals = []
+        for family, entry in sorted(families.items()):
+            spans = set()
+            for line in entry['lines']:
+                item = index.item_at(line)
+                spans.add((item['start_line'], item['end_line']) if item else (line, line))
+            algorithm = next(iter(entry['variants'])) if len(entry['variants']) == 1 else family
+            proposals.append({
+                'algorithm': algorithm, 'category': entry['category'], 'family': family,
+                'passes': sorted(entry['passes']), 'evidence': entry['evidence'][:MAX_EVIDENCE],
+                'locations': [f"{start}-{end}" for start, end in sorted(spans)[:MAX_EVIDENCE]],
+            })
+        return proposals
+
+
+class CorpusImporter:
+    """git 저장소 → 라벨 제안이 붙은 스테이징 샘플"""
+
+    def __init__(self, staging_dir: str = STAGING_DIR, min_lines: int = MIN_LINES, max_lines: int = MAX_LINES,
+                 proposer: Optional[LabelProposer] = None):
+        self.staging_dir = Path(staging_dir)
+        self.min_lines = min_lines
+        self.max_lines = max_lines
+        self.proposer = proposer or LabelProposer()
+
+    @staticmethod
+    def clone(url: str, destination: Path, revision: Optional[str] = None, timeout: int = 600) -> str:
+        """저장소를 받아 커밋 해시 반환 (revision이 없으면 기본 브랜치 최신 커밋만 얕게)"""
+        command = ['git', 'clone', '--quiet'] + ([] if revision else ['--depth', '1']) + [url, str(destination)]
+        subprocess.run(command, check=True, capture_output=True, text=True, timeout=timeout)
+        if revision:
+            subprocess.run(['git', '-C', str(destination), 'checkout', '--quiet', revision],
+                           check=True, capture_output=True, text=True, timeout=timeout)
+        return subprocess.run(['git', '-C', str(destination), 'rev-parse', 'HEAD'], check=True,
+                              capture_output=True, text=True).stdout.strip()
+
+    def candidate_files(self, repo_dir: Path, paths: Optional[List[str]] = None) -> List[Path]:
+        roots = [repo_dir / path for path in paths] if paths else [repo_dir]
+        files = []
+        for root in roots:
+            for path in sorted(root.rglob('*') if root.is_dir() else [root]):
+                relative = path.relative_to(repo_dir)
+                if not path.is_file() or path.suffix.lower() not in LANGUAGE_NAMES:
+                    continue
+                if any(part in SKIP_DIRS or part.startswith('.') for part in relative.parts[:-1]):
+                    continue
+                files.append(path)
+        return files
+
+    @staticmethod
+    def test_id(repo_name: str, relative: Path) -> str:
+        stem = str(relative.with_suffix('')).replace('/', '_')
+        return re.sub(r'[^a-z0-9]+', '_', f"{repo_name}_{stem}".lower()).strip('_')[:80]
+
+    def scan(self, repo_dir: Path, paths: Optional[List[str]] = None,
+             max_files: int = MAX_FILES) -> List[Dict[str, Any]]:
+        """암호 코드 후보 파일 [{'path', 'relative', 'lines', 'proposals'}] (근거가 많은 순, max_files개)"""
+        found = []
+        for path in self.candidate_files(repo_dir, paths):
+            text = path.read_text(encoding='utf-8', errors='ignore')
+            line_count = len(text.splitlines())
+            if not self.min_lines <= line_count <= self.max_lines:
+                continue
+            proposals = self.proposer.propose(text, path.suffix)
+            if proposals:
+                found.append({'path': path, 'relative': path.relative_to(repo_dir), 'lines': line_count,
+                              'proposals': proposals})
+        found.sort(key=lambda item: (-sum(len(p['evidence']) * len(p['passes']) for p in item['proposals']),
+                                     str(item['relative'])))
+        return found[:max_files]
+
+    def ground_truth(self, url: str, revision: str, relative: Path, proposals: List[Dict[str, Any]],
+                     license_id: str, notice: Optional[str]) -> Dict[str, Any]:
+        vulnerable = [p for p in proposals if p['category'] != 'post_quantum']
+        safe = [p for p in proposals if p['category'] == 'post_quantum']
+        korean = CATEGORY_KEYWORDS['korean_algorithms']
+        expected_findings = {
+            'vulnerable_algorithms_detected': [p['algorithm'] for p in vulnerable],
+            'algorithm_categories': sorted({p['category'] for p in vulnerable}),
+            'korean_algorithms_detected': [p['algorithm'] for p in vulnerable if p['family'].lower() in korean],
+            'locations': {p['algorithm']: p['locations'] for p in proposals},
+        }
+        if safe:
+            expected_findings['quantum_safe_algorithms'] = [p['algorithm'] for p in safe]
+        provenance = {'kind': PROVENANCE_ADAPTED, 'source': source_url(url, revision, relative.as_posix()),
+                      'license': license_id, 'revision': revision}
+        if notice:
+            provenance['copyright'] = notice
+        return {
+            'description': f"{repository_name(url)}의 {relative.as_posix()} (실제 오픈소스 코드, 자동 제안 라벨)",
+            'difficulty': 'medium',
+            'tags': ['mined', 'oss'],
+            'expected_findings': expected_findings,
+            'expected_confidence_range': [0.7, 0.9],
+            'provenance': provenance,
+            'label_proposals': [{key: p[key] for key in ('algorithm', 'passes', 'evidence')} for p in proposals],
+        }
+
+    def stage(self, url: str, revision: str, repo_dir: Path, found: List[Dict[str, Any]],
+              license_id: str, notice: Optional[str] = None) -> List[Path]:
+        """후보 파일을 스테이징 영역에 복사하고 제안 ground truth 기록 (이미 있는 test_id는 덮어씀)"""
+        repo_name = repository_name(url)
+        files_dir = self.staging_dir / 'test_files' / STAGING_AGENT
+        truth_dir = self.staging_dir / 'ground_truth' / STAGING_AGENT
+        files_dir.mkdir(parents=True, exist_ok=True)
+        truth_dir.mkdir(parents=True, exist_ok=True)
+        staged = []
+        for item in found:
+            test_id = self.test_id(repo_name, item['relative'])
+            target = files_dir / f"{test_id}{item['path'].suffix}"
+            shutil.copyfile(item['path'], target)
+            ground_truth = self.ground_truth(url, revision, item['relative'], item['proposals'], license_id, notice)
+            with open(truth_dir / f"{test_id}.json", 'w', encoding='utf-8') as f:
+                json.dump(ground_truth, f, indent=2, ensure_ascii=False)
+                f.write('\n')
+            staged.append(target)
+        return staged
+
+
+def promote(staging_dir: str = STAGING_DIR, ground_truth_dir: str = GROUND_TRUTH_DIR,
+            test_files_dir: str = TEST_FILES_DIR) -> Dict[str, List[str]]:
+    """스테이징 리뷰에서 승인된 샘플을 코퍼스로 이동 → {'promoted', 'pending', 'conflicts'}"""
+    staging = Path(staging_dir)
+    approved = AuditLog(str(staging / Path(AUDIT_LOG_PATH).name)).reviewed_ids(STAGING_AGENT)
+    outcome: Dict[str, List[str]] = {'promoted': [], 'pending': [], 'conflicts': []}
+    for gt_path in sorted((staging / 'ground_truth' / STAGING_AGENT).glob('*.json')):
+        test_id = gt_path.stem
+        samples = sorted((staging / 'test_files' / STAGING_AGENT).glob(f"{test_id}.*"))
+        if test_id not in approved or not samples:
+            outcome['pending'].append(test_id)
+            continue
+        target_gt = Path(ground_truth_dir) / STAGING_AGENT / gt_path.name
+        target_sample = Path(test_files_dir) / STAGING_AGENT / samples[0].name
+        if target_gt.exists() or target_sample.exists():
+            outcome['conflicts'].append(test_id)
+            continue
+
+        with open(gt_path, 'r', encoding='utf-8') as f:
+            ground_truth = json.load(f)
+        ground_truth.pop('label_proposals', None)
+        with open(target_gt, 'w', encoding='utf-8') as f:
+            json.dump(ground_truth, f, indent=2, ensure_ascii=False)
+            f.write('\n')
+        shutil.move(str(samples[0]), target_sample)
+        gt_path.unlink()
+        outcome['promoted'].append(test_id)
+    return outcome
+
+
+def run_import(args) -> int:
+    importer = CorpusImporter(args.staging_dir, args.min_lines, args.max_lines)
+    with tempfile.TemporaryDirectory(prefix='qvbench_import_') as workdir:
+        repo_dir = Path(workdir) / 'repo'
+        print(f"📥 {args.from_git} 받는 중...")
+        try:
+            revision = importer.clone(args.from_git, repo_dir, args.revision)
+        except (subprocess.CalledProcessError, subprocess.TimeoutExpired) as e:
+            print(f"❌ 저장소를 받지 못했습니다: {getattr(e, 'stderr', '') or e}")
+            return 1
+
+        detected, notice = detect_license(repo_dir)
+        license_id = args.license or detected
+        print(f"  커밋 {revision[:12]}, 라이선스 {license_id or '알 수 없음'}"
+              f"{' (--license로 지정)' if args.license else ''}")
+
+        found = importer.scan(repo_dir, args.path, args.max_files)
+        print(f"\n🔎 암호 코드 후보 {len(found)}개")
+        for item in found:
+            labels = ', '.join(f"{p['algorithm']}[{'+'.join(p['passes'])}]" for p in item['proposals'])
+            print(f"  {item['relative']} ({item['lines']}줄): {labels}")
+
+        if args.dry_run or not found:
+            return 0
+        if not license_id:
+            print("❌ 라이선스를 알 수 없어 스테이징하지 않았습니다 (--license <SPDX 식별자>로 지정)")
+            return 1
+        staged = importer.stage(args.from_git, revision, repo_dir, found, license_id, notice)
+
+    print(f"\n📝 스테이징 {len(staged)}개: {importer.staging_dir}")
+    print(f"  다음: python -m utils.label_review review --staging --only-unreviewed → python -m utils.corpus promote")
+    return 0
+
+
+def run_promote(args) -> int:
+    outcome = promote(args.staging_dir, args.ground_truth_dir, args.root)
+    for test_id in outcome['promoted']:
+        print(f"  ✅ {test_id}")
+    for test_id in outcome['conflicts']:
+        print(f"  ⚠️  {test_id}: 코퍼스에 같은 이름의 샘플이 이미 있음")
+    print(f"\n📊 이동 {len(outcome['promoted'])} / 리뷰 대기 {len(outcome['pending'])} / "
+          f"충돌 {len(outcome['conflicts'])}")
+    if outcome['promoted']:
+        print("  다음: python -m utils.corpus lint")
+    return 1 if outcome['conflicts'] else 0
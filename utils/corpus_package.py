@@ -0,0 +1,399 @@
+"""
+코퍼스 배포 아카이브 (corpus package / unpack)
+샘플, 라벨, 채점 rubric, 중복 클러스터, 라이선스를 재현 가능한 아카이브로 묶고 받는 쪽에서 해시를 다시 확인합니다.
+아카이브 구성과 라이선스 규칙, --verify 검사 항목은 docs/TEST_FILES.md를 보세요.
+"""
+
+import gzip
+import hashlib
+import io
+import json
+import shutil
+import subprocess
+import tarfile
+import tempfile
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+from utils.corpus import GROUND_TRUTH_DIR, TEST_FILES_DIR
+from utils.dedup import CLUSTERS_PATH
+from utils.incremental import HASH_FIELDS, manifest_version
+from utils.provenance import (PROVENANCE_ADAPTED, attribution, is_redistributable, license_ids,
+                              sample_provenance)
+from utils.rubric import build_rubric, rubric_digest
+from utils.sample_filter import SampleFilter
+
+PACKAGE_FORMAT = 1
+# hand-written / generated 샘플의 라이선스 (pyproject.toml의 프로젝트 라이선스와 같음)
+CORPUS_LICENSE = 'MIT'
+CORPUS_COPYRIGHT = 'Copyright (c) qvbench contributors'
+LICENSES_DIR = 'data/licenses'
+MANIFEST_NAME = 'MANIFEST.json'
+ZSTD_LEVEL = 19
+ZSTD_MAGIC = b'\x28\xb5\x2f\xfd'
+GZIP_MAGIC = b'\x1f\x8b'
+# 아카이브 이름 끝 → 압축 방식
+ARCHIVE_SUFFIXES = {'.tar.zst': 'zstd', '.tzst': 'zstd', '.tar.gz': 'gzip', '.tgz': 'gzip', '.tar': None}
+
+
+def _sha256(payload: bytes) -> str:
+    return hashlib.sha256(payload).hexdigest()
+
+
+def _json_bytes(value: Any) -> bytes:
+    return (json.dumps(value, indent=2, ensure_ascii=False, sort_keys=True) + '\n').encode('utf-8')
+
+
+def archive_compression(name: str) -> Optional[str]:
+    for suffix, compression in ARCHIVE_SUFFIXES.items():
+        if name.endswith(suffix):
+            return compression
+    raise ValueError(f"unsupported archive name '{name}' (use {', '.join(ARCHIVE_SUFFIXES)})")
+
+
+def _compress(payload: bytes, name: str) -> bytes:
+    compression = archive_compression(name)
+    if compression == 'zstd':
+        try:
+            import zstandard
+            return zstandard.ZstdCompressor(level=ZSTD_LEVEL).compress(payload)
+        except ImportError:
+            return _zstd_command(['-q', f'-{ZSTD_LEVEL}', '-c'], payload)
+    if compression == 'gzip':
+        buffer = io.BytesIO()
+        with gzip.GzipFile(fileobj=buffer, mode='wb', mtime=0, filename='') as archive:
+            archive.write(payload)
+        return buffer.getvalue()
+    return payload
+
+
+def _decompress(payload: bytes) -> bytes:
+    if payload.startswith(ZSTD_MAGIC):
+        try:
+            import zstandard
+            return zstandard.ZstdDecompressor().stream_reader(io.BytesIO(payload)).read()
+        except ImportError:
+            return _zstd_command(['-q', '-d', '-c'], payload)
+    if payload.startswith(GZIP_MAGIC):
+        return gzip.decompress(payload)
+    return payload
+
+
+def _zstd_command(arguments: List[str], payload: bytes) -> bytes:
+    if not shutil.which('zstd'):
+        raise RuntimeError("zstd compression needs the zstandard package (pip install zstandard) or the zstd "
+                           "command; use a .tar.gz name instead")
+    return subprocess.run(['zstd'] + arguments, input=payload, capture_output=True, check=True).stdout
+
+
+def sample_license(provenance: Dict[str, Any]) -> str:
+    """샘플의 SPDX 라이선스 식 (adapted-from-oss는 원본 라이선스, 나머지는 코퍼스 라이선스)"""
+    if provenance.get('kind') == PROVENANCE_ADAPTED:
+        return provenance.get('license') or ''
+    return CORPUS_LICENSE
+
+
+class CorpusPackager:
+    """코퍼스 → 재현 가능한 배포 아카이브"""
+
+    def __init__(self, ground_truth_dir: str = GROUND_TRUTH_DIR, test_files_dir: str = TEST_FILES_DIR,
+                 licenses_dir: str = LICENSES_DIR, clusters_path: str = CLUSTERS_PATH):
+        self.ground_truth_dir = Path(ground_truth_dir)
+        self.test_files_dir = Path(test_files_dir)
+        self.licenses_dir = Path(licenses_dir)
+        self.clusters_path = Path(clusters_path)
+
+    def _license_text(self, license_id: str) -> Optional[bytes]:
+        path = self.licenses_dir / f"{license_id}.txt"
+        return path.read_bytes() if path.is_file() else None
+
+    def collect(self, agents: Optional[List[str]] = None,
+                sample_filter: Optional[SampleFilter] = None) -> Dict[str, Any]:
+        """아카이브에 넣을 파일 {경로: 바이트}와 MANIFEST.json 내용"""
+        from utils.submission import corpus_manifest
+
+        files: Dict[str, bytes] = {}
+        samples: Dict[str, Dict[str, Dict[str, Any]]] = {}
+        excluded, attributions, errors = [], [], []
+        license_texts: Dict[str, bytes] = {}
+
+        for (agent_type, test_id), entry in sorted(corpus_manifest(str(self.ground_truth_dir),
+                                                                   str(self.test_files_dir)).items()):
+            sample = entry['sample']
+            if sample is None or (agents and agent_type not in agents):
+                continue
+            if sample_filter and not sample_filter.matches_sample(agent_type, test_id, entry['ground_truth'],
+                                                                  str(sample)):
+                continue
+            provenance = sample_provenance(entry['ground_truth'])
+            expression = sample_license(provenance)
+            if not expression or not is_redistributable(expression):
+                excluded.append({'agent_type': agent_type, 'test_id': test_id, 'license': expression or None,
+                                 'reason': 'license is not in REDISTRIBUTABLE_LICENSES'})
+                continue
+            texts = {license_id: self._license_text(license_id) for license_id in license_ids(expression)}
+            if not any(texts.values()):
+                errors.append(f"{agent_type}/{test_id}: no license text for '{expression}' in {self.licenses_dir}")
+                continue
+            license_texts.update({license_id: text for license_id, text in texts.items() if text})
+
+            sample_files = sorted(path for path in sample.rglob('*') if path.is_file()) if sample.is_dir() \
+                else [sample]
+            names = []
+            for path in sample_files:
+                name = f"test_files/{path.relative_to(self.test_files_dir).as_posix()}"
+                files[name] = path.read_bytes()
+                names.append(name)
+            gt_name = f"ground_truth/{agent_type}/{test_id}.json"
+            files[gt_name] = (self.ground_truth_dir / agent_type / f"{test_id}.json").read_bytes()
+
+            samples.setdefault(agent_type, {})[test_id] = {
+                **{field: entry[field] for field in HASH_FIELDS},
+                'provenance': provenance['kind'],
+                'license': expression,
+                'files': names + [gt_name],
+            }
+            if attribution(test_id, provenance):
+                attributions.append({'agent_type': agent_type, **attribution(test_id, provenance)})
+
+        corpus_text = self._license_text(CORPUS_LICENSE)
+        if corpus_text is None:
+            errors.append(f"no corpus license text {self.licenses_dir}/{CORPUS_LICENSE}.txt")
+        else:
+            files['LICENSE'] = f"{CORPUS_COPYRIGHT}\n\n".encode('utf-8') + corpus_text
+        for license_id, text in license_texts.items():
+            files[f"licenses/{license_id}.txt"] = text
+        if attributions:
+            files['NOTICE.md'] = self.notice(attributions).encode('utf-8')
+
+        rubric = build_rubric()
+        files['scoring_rubric.json'] = (json.dumps(rubric, indent=2, ensure_ascii=False) + '\n').encode('utf-8')
+        if self.clusters_path.is_file():
+            files['duplicate_clusters.json'] = self.clusters_path.read_bytes()
+
+        hashes = {agent_type: {test_id: {field: entry[field] for field in HASH_FIELDS}
+                               for test_id, entry in tests.items()}
+                  for agent_type, tests in samples.items()}
+        manifest = {
+            'format': PACKAGE_FORMAT,
+            'corpus_version': manifest_version(hashes),
+            'license': CORPUS_LICENSE,
+            'rubric': {'version': rubric['rubric_version'], 'digest': rubric['digest']},
+            'filter': str(sample_filter) if sample_filter else None,
+            'agents': sorted(samples),
+            'sample_count': sum(len(tests) for tests in samples.values()),
+            'samples': samples,
+            'excluded': excluded,
+            'files': {name: _sha256(payload) for name, payload in sorted(files.items())},
+        }
+        return {'manifest': manifest, 'files': files, 'errors': errors}
+
+    @staticmethod
+    def notice(attributions: List[Dict[str, Any]]) -> str:
+        lines = ["# Third-party notices", "",
+                 "Samples below are adapted from open-source code and remain under their original licenses "
+                 "(texts in licenses/).", ""]
+        for entry in attributions:
+            lines.append(f"- `{entry['agent_type']}/{entry['test_id']}`: {entry['source'] or '-'}"
+                         f"{' @ ' + entry['revision'] if entry.get('revision') else ''} ({entry['license']})")
+            if entry.get('copyright'):
+                lines.append(f"  {entry['copyright']}")
+        return '\n'.join(lines) + '\n'
+
+    @staticmethod
+    def root_name(manifest: Dict[str, Any]) -> str:
+        return f"qvbench-corpus-{manifest['corpus_version']}"
+
+    def write(self, package: Dict[str, Any], output: str) -> Path:
+        """아카이브 쓰기 (항목 정렬, mtime/소유자/권한 고정 → 같은 코퍼스면 같은 바이트)"""
+        manifest = package['manifest']
+        root = self.root_name(manifest)
+        entries = [(MANIFEST_NAME, _json_bytes(manifest))] + sorted(package['files'].items())
+
+        buffer = io.BytesIO()
+        with tarfile.open(fileobj=buffer, mode='w', format=tarfile.PAX_FORMAT) as tar:
+            directories = set()
+            for name, payload in entries:
+                parts = name.split('/')[:-1]
+                for depth in range(len(parts) + 1):
+                    directory = '/'.join([root] + parts[:depth])
+                    if directory not in directories:
+                        directories.add(directory)
+                        info = tarfile.TarInfo(directory)
+                        info.type, info.mtime, info.mode = tarfile.DIRTYPE, 0, 0o755
+                        tar.addfile(info)
+                info = tarfile.TarInfo(f"{root}/{name}")
+                info.size, info.mtime, info.mode = len(payload), 0, 0o644
+                tar.addfile(info, io.BytesIO(payload))
+
+        path = Path(output)
+        compressed = _compress(buffer.getvalue(), path.name)
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_bytes(compressed)
+        return path
+
+
+def read_archive(path: str) -> Tuple[str, Dict[str, bytes]]:
+    """아카이브 → (최상위 디렉토리 이름, {최상위 아래 경로: 바이트})
+
+    일반 파일/디렉토리만 허용하고, 절대 경로나 '..'가 들어간 항목, 최상위 디렉토리가 여럿인 아카이브는 거부합니다.
+    """
+    payload = _decompress(Path(path).read_bytes())
+    files: Dict[str, bytes] = {}
+    roots = set()
+    with tarfile.open(fileobj=io.BytesIO(payload), mode='r:') as tar:
+        for member in tar.getmembers():
+            parts = member.name.split('/')
+            if member.name.startswith('/') or '..' in parts or not (member.isfile() or member.isdir()):
+                raise ValueError(f"unsafe archive entry: {member.name}")
+            roots.add(parts[0])
+            if member.isfile():
+                if len(parts) < 2:
+                    raise ValueError(f"archive entry outside the package directory: {member.name}")
+                files['/'.join(parts[1:])] = tar.extractfile(member).read()
+    if len(roots) != 1:
+        raise ValueError(f"archive must have exactly one top-level directory, found {sorted(roots)}")
+    return roots.pop(), files
+
+
+def verify_package(directory: str) -> Tuple[List[str], List[str]]:
+    """풀어 놓은 패키지 디렉토리 검증 → (오류, 경고)"""
+    from utils.submission import corpus_manifest
+
+    base = Path(directory)
+    manifest_path = base / MANIFEST_NAME
+    if not manifest_path.is_file():
+        return [f"{MANIFEST_NAME} not found in {base}"], []
+    manifest = json.loads(manifest_path.read_text(encoding='utf-8'))
+    errors, warnings = [], []
+    if manifest.get('format') != PACKAGE_FORMAT:
+        return [f"unsupported package format {manifest.get('format')} (expected {PACKAGE_FORMAT})"], []
+
+    listed = manifest.get('files', {})
+    present = {path.relative_to(base).as_posix() for path in base.rglob('*') if path.is_file()} - {MANIFEST_NAME}
+    for name in sorted(set(listed) - present):
+        errors.append(f"missing file: {name}")
+    for name in sorted(present - set(listed)):
+        errors.append(f"file not in manifest: {name}")
+    for name in sorted(set(listed) & present):
+        if _sha256((base / name).read_bytes()) != listed[name]:
+            errors.append(f"sha256 mismatch: {name}")
+
+    recorded = {(agent_type, test_id): entry for agent_type, tests in manifest.get('samples', {}).items()
+                for test_id, entry in tests.items()}
+    actual = {key: entry for key, entry in corpus_manifest(str(base / 'ground_truth'),
+                                                           str(base / 'test_files')).items()
+              if entry['sample'] is not None}
+    for key in sorted(set(recorded) - set(actual)):
+        errors.append(f"sample missing or unreadable: {key[0]}/{key[1]}")
+    for key in sorted(set(actual) - set(recorded)):
+        errors.append(f"sample not in manifest: {key[0]}/{key[1]}")
+    hashes: Dict[str, Dict[str, Dict[str, Any]]] = {}
+    for key in sorted(set(recorded) & set(actual)):
+        for field in HASH_FIELDS:
+            if recorded[key].get(field) != actual[key][field]:
+                errors.append(f"{key[0]}/{key[1]}: {field} mismatch")
+        hashes.setdefault(key[0], {})[key[1]] = {field: actual[key][field] for field in HASH_FIELDS}
+    if not errors and manifest_version(hashes) != manifest.get('corpus_version'):
+        errors.append(f"corpus version {manifest_version(hashes)} does not match manifest "
+                      f"{manifest.get('corpus_version')}")
+
+    rubric_path = base / 'scoring_rubric.json'
+    if rubric_path.is_file():
+        rubric = json.loads(rubric_path.read_text(encoding='utf-8'))
+        if rubric.get('digest') != rubric_digest(rubric):
+            errors.append("scoring_rubric.json: digest does not match its content")
+        elif rubric['digest'] != manifest.get('rubric', {}).get('digest'):
+            errors.append("scoring_rubric.json: digest does not match the manifest")
+        elif rubric['digest'] != build_rubric()['digest']:
+            warnings.append(f"rubric {rubric.get('rubric_version')} ({rubric['digest'][:12]}) differs from this "
+                            f"checkout's scoring rules; scores are not comparable with local runs")
+    else:
+        errors.append("scoring_rubric.json not found")
+
+    if not (base / 'LICENSE').is_file():
+        errors.append("LICENSE not found")
+    for (agent_type, test_id), entry in sorted(recorded.items()):
+        ids = license_ids(entry.get('license') or '')
+        if entry.get('license') != manifest.get('license') and \
+                not any((base / 'licenses' / f"{license_id}.txt").is_file() for license_id in ids):
+            errors.append(f"{agent_type}/{test_id}: no license text for '{entry.get('license')}'")
+    return errors, warnings
+
+
+def _print_verification(errors: List[str], warnings: List[str], limit: int = 20) -> int:
+    for warning in warnings:
+        print(f"  ⚠️  {warning}")
+    for error in errors[:limit]:
+        print(f"  ❌ {error}")
+    if len(errors) > limit:
+        print(f"  ... 외 {len(errors) - limit}건")
+    if errors:
+        print(f"\n❌ 검증 실패: 오류 {len(errors)}건")
+        return 1
+    print("✅ 검증 통과: 파일 해시, 샘플 해시, 코퍼스 버전, rubric, 라이선스 일치")
+    return 0
+
+
+def run_package(args) -> int:
+    try:
+        archive_compression(args.out)
+        sample_filter = SampleFilter(args.filter) if args.filter else None
+    except ValueError as e:
+        print(f"❌ {e}")
+        return 2
+    packager = CorpusPackager(args.ground_truth_dir, args.root, args.licenses_dir)
+    package = packager.collect(args.agents, sample_filter)
+    manifest = package['manifest']
+    for item in manifest['excluded']:
+        print(f"  ⚠️  제외: {item['agent_type']}/{item['test_id']} ({item['license'] or '라이선스 없음'})")
+    if package['errors']:
+        for error in package['errors']:
+            print(f"  ❌ {error}")
+        print(f"\n❌ 라이선스 본문이 없어 패키지를 만들지 않았습니다 ({args.licenses_dir}/<SPDX>.txt 추가)")
+        return 1
+
+    try:
+        path = packager.write(package, args.out)
+    except (ValueError, RuntimeError, subprocess.CalledProcessError) as e:
+        print(f"❌ {e}")
+        return 1
+    licenses = sorted({entry['license'] for tests in manifest['samples'].values() for entry in tests.values()})
+    print(f"📦 코퍼스 {manifest['corpus_version']}: 샘플 {manifest['sample_count']}개 "
+          f"({', '.join(manifest['agents'])}), 파일 {len(manifest['files'])}개"
+          f"{', 필터 ' + manifest['filter'] if manifest['filter'] else ''}")
+    print(f"   rubric {manifest['rubric']['version']} ({manifest['rubric']['digest'][:12]}), "
+          f"라이선스 {', '.join(licenses)}, 제외 {len(manifest['excluded'])}개")
+    print(f"💾 {path} ({path.stat().st_size / 1024:.0f} KiB, sha256 {_sha256(path.read_bytes())[:16]})")
+    return 0
+
+
+def run_unpack(args) -> int:
+    try:
+        root, files = read_archive(args.archive)
+    except (ValueError, OSError, tarfile.TarError, RuntimeError, subprocess.CalledProcessError) as e:
+        print(f"❌ 아카이브를 읽지 못했습니다: {e}")
+        return 1
+
+    if args.verify_only:
+        with tempfile.TemporaryDirectory(prefix='qvbench_unpack_') as workdir:
+            _extract(files, Path(workdir))
+            print(f"🔍 {args.archive} ({root}) 검증")
+            return _print_verification(*verify_package(workdir))
+
+    dest = Path(args.dest or root)
+    if dest.exists() and any(dest.iterdir()) and not args.force:
+        print(f"⚠️  {dest} 가 비어 있지 않습니다 (--force로 덮어쓰기)")
+        return 1
+    _extract(files, dest)
+    print(f"📂 {root} → {dest} (파일 {len(files)}개)")
+    if args.verify:
+        return _print_verification(*verify_package(str(dest)))
+    return 0
+
+
+def _extract(files: Dict[str, bytes], dest: Path):
+    for name, payload in files.items():
+        path = dest / name
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_bytes(payload)
@@ -0,0 +1,169 @@
+"""
+코퍼스 중복/근사 중복 탐지 (near-duplicate clusters)
+
+같은 템플릿에서 테마만 바꿔 만든 생성 샘플, 주석만 바꾼 변형(_misdoc), 파일만 나눈 변형(_split)처럼 코드가 거의
+같은 샘플은 한 탐지기가 같은 답을 여러 번 맞힌 것으로 점수를 부풀리고, 하나는 train·하나는 test에 들어가면
+few-shot 예시나 기준선 학습을 통해 정답이 새어 나갑니다. 그래서 근사 중복 샘플을 클러스터로 묶어 기록하고,
+분할(utils/splits.py)은 클러스터 단위로 정합니다.
+
+유사도:
+    토큰 shingle  주석(//, /* */)을 지우고 식별자/숫자/기호로 토큰화한 뒤, 4자 이상 식별자는 한 기호로 바꿔
+                  (이름만 바꾼 템플릿 출력 대응, 짧은 키워드와 숫자 상수는 유지) SHINGLE_SIZE개씩 묶음
+    MinHash       shingle 해시 중 가장 작은 SKETCH_SIZE개(bottom-k)로 Jaccard 유사도를 추정
+    클러스터      추정 유사도가 NEAR_DUPLICATE_THRESHOLD 이상인 쌍을 이어 붙인 연결 요소 (2개 이상)
+
+클러스터 기록 (data/duplicate_clusters.json):
+    {"threshold": 0.8, "clusters": {"dup-1a2b3c4d": ["source_code/a", "source_code/a_misdoc"], ...}}
+    클러스터 ID는 처음 기록될 때 가장 작은 멤버 이름의 해시이며, 다시 기록할 때 기존 클러스터와 멤버가 겹치면 그
+    ID를 이어 씁니다 (샘플이 추가되어도 기존 샘플의 분할이 바뀌지 않도록).
+
+lint (python -m utils.corpus lint):
+    - error: 근사 중복 클러스터가 기록과 다름 (새 클러스터, 멤버 추가/제거, 더 이상 중복이 아닌 기록)
+    - error: 같은 클러스터 멤버의 분할이 다름 (ground truth의 split 필드로 서로 다르게 고정한 경우)
+
+사용법:
+    python -m utils.corpus dedup              # 근사 중복 쌍과 클러스터 출력
+    python -m utils.corpus dedup --write      # data/duplicate_clusters.json 갱신
+"""
+
+import hashlib
+import json
+import re
+from functools import lru_cache
+from pathlib import Path
+from typing import Dict, List, Optional, Tuple
+
+SHINGLE_SIZE = 5
+SKETCH_SIZE = 128
+NEAR_DUPLICATE_THRESHOLD = 0.8
+
+CLUSTERS_PATH = "data/duplicate_clusters.json"
+
+_COMMENT = re.compile(r'/\*.*?\*/|//[^\n]*', re.DOTALL)
+_TOKEN = re.compile(r'[A-Za-z_]\w*|\d\w*|[^\s\w]')
+_IDENTIFIER = re.compile(r'[A-Za-z_]\w{3,}$')
+
+
+def shingles(text: str, size: int = SHINGLE_SIZE) -> set:
+    tokens = ['ID' if _IDENTIFIER.match(token) else token for token in _TOKEN.findall(_COMMENT.sub(' ', text))]
+    return {' '.join(tokens[index:index + size]) for index in range(max(1, len(tokens) - size + 1))}
+
+
+def minhash(text: str, sketch_size: int = SKETCH_SIZE) -> List[int]:
+    """bottom-k MinHash 스케치 (shingle 64비트 해시 중 가장 작은 sketch_size개, 오름차순)"""
+    hashes = {int.from_bytes(hashlib.blake2b(shingle.encode('utf-8'), digest_size=8).digest(), 'big')
+              for shingle in shingles(text)}
+    return sorted(hashes)[:sketch_size]
+
+
+def estimate_similarity(first: List[int], second: List[int], sketch_size: int = SKETCH_SIZE) -> float:
+    """두 스케치의 Jaccard 추정값 (합집합의 bottom-k 중 양쪽에 모두 있는 비율)"""
+    union = sorted(set(first) | set(second))[:sketch_size]
+    if not union:
+        return 0.0
+    shared = set(first) & set(second)
+    return sum(1 for value in union if value in shared) / len(union)
+
+
+def near_duplicates(texts: Dict[str, str],
+                    threshold: float = NEAR_DUPLICATE_THRESHOLD) -> List[Tuple[str, str, float]]:
+    """{샘플 키: 코드} → 유사도가 threshold 이상인 쌍 [(a, b, 유사도)] (유사도 높은 순)"""
+    sketches = {key: minhash(text) for key, text in texts.items()}
+    keys = sorted(sketches)
+    pairs = []
+    for index, first in enumerate(keys):
+        for second in keys[index + 1:]:
+            similarity = estimate_similarity(sketches[first], sketches[second])
+            if similarity >= threshold:
+                pairs.append((first, second, round(similarity, 3)))
+    return sorted(pairs, key=lambda pair: (-pair[2], pair[0], pair[1]))
+
+
+def clusters_from_pairs(pairs: List[Tuple[str, str, float]]) -> List[List[str]]:
+    """근사 중복 쌍 → 연결 요소 (멤버 정렬, 가장 작은 멤버 순)"""
+    parent: Dict[str, str] = {}
+
+    def root(key: str) -> str:
+        parent.setdefault(key, key)
+        while parent[key] != key:
+            parent[key] = parent[parent[key]]
+            key = parent[key]
+        return key
+
+    for first, second, _ in pairs:
+        parent[root(first)] = root(second)
+    groups: Dict[str, List[str]] = {}
+    for key in parent:
+        groups.setdefault(root(key), []).append(key)
+    return sorted(sorted(members) for members in groups.values())
+
+
+def assign_cluster_ids(clusters: List[List[str]],
+                       recorded: Optional[Dict[str, List[str]]] = None) -> Dict[str, List[str]]:
+    """클러스터 → {ID: 멤버} (기록된 클러스터와 멤버가 가장 많이 겹치면 그 ID를 이어 씀)"""
+    recorded = recorded or {}
+    assigned: Dict[str, List[str]] = {}
+    for members in clusters:
+        overlaps = sorted(((len(set(members) & set(previous)), cluster_id)
+                           for cluster_id, previous in recorded.items()
+                           if cluster_id not in assigned and set(members) & set(previous)), reverse=True)
+        if overlaps:
+            cluster_id = overlaps[0][1]
+        else:
+            cluster_id = f"dup-{hashlib.sha256(members[0].encode('utf-8')).hexdigest()[:8]}"
+        assigned[cluster_id] = members
+    return dict(sorted(assigned.items()))
+
+
+def load_clusters(path: str = CLUSTERS_PATH) -> Dict[str, List[str]]:
+    if not Path(path).exists():
+        return {}
+    with open(path, 'r', encoding='utf-8') as f:
+        return json.load(f).get('clusters', {})
+
+
+def write_clusters(clusters: Dict[str, List[str]], path: str = CLUSTERS_PATH,
+                   threshold: float = NEAR_DUPLICATE_THRESHOLD):
+    Path(path).parent.mkdir(parents=True, exist_ok=True)
+    with open(path, 'w', encoding='utf-8') as f:
+        json.dump({'threshold': threshold, 'shingle_size': SHINGLE_SIZE, 'sketch_size': SKETCH_SIZE,
+                   'clusters': clusters}, f, indent=2, ensure_ascii=False)
+        f.write('\n')
+
+
+@lru_cache(maxsize=None)
+def _membership(path: str) -> Dict[str, str]:
+    return {member: cluster_id for cluster_id, members in load_clusters(path).items() for member in members}
+
+
+def duplicate_cluster(agent_type: str, test_id: str, path: str = CLUSTERS_PATH) -> Optional[str]:
+    """샘플이 속한 근사 중복 클러스터 ID (기록 기준, 없으면 None)"""
+    return _membership(path).get(f"{agent_type}/{test_id}")
+
+
+def cluster_issues(computed: List[List[str]], recorded: Dict[str, List[str]]) -> List[Tuple[str, str, str]]:
+    """계산한 클러스터와 기록 비교 → [(level, 샘플 키, message)]"""
+    issues = []
+    recorded_sets = {cluster_id: set(members) for cluster_id, members in recorded.items()}
+    matched = set()
+    for members in computed:
+        exact = [cluster_id for cluster_id, previous in recorded_sets.items() if previous == set(members)]
+        if exact:
+            matched.add(exact[0])
+            continue
+        issues.append(('error', members[0], f"near-duplicate cluster {members} is not recorded in {CLUSTERS_PATH} "
+                                            f"(run python -m utils.corpus dedup --write)"))
+    for cluster_id, members in recorded.items():
+        if cluster_id not in matched and not any(set(members) <= set(cluster) for cluster in computed):
+            issues.append(('error', members[0], f"recorded cluster {cluster_id} {members} is no longer a "
+                                                f"near-duplicate cluster (run python -m utils.corpus dedup --write)"))
+    return issues
+
+
+def print_duplicates(pairs: List[Tuple[str, str, float]], clusters: Dict[str, List[str]]):
+    print(f"🧬 근사 중복 쌍 {len(pairs)}개 (유사도 ≥ {NEAR_DUPLICATE_THRESHOLD})")
+    for first, second, similarity in pairs:
+        print(f"  {similarity:.2f}  {first} ↔ {second}")
+    print(f"\n🗂️  클러스터 {len(clusters)}개 (분할은 클러스터 단위로 고정)")
+    for cluster_id, members in clusters.items():
+        print(f"  {cluster_id}: {', '.join(members)}")
@@ -0,0 +1,221 @@
+"""
+변경 이력 샘플 (diff-introduction 과제)
+
+입력이 완성된 코드가 아니라 변경분입니다. 탐지기는 변경분이 **새로 들어오게 한** 취약 기본 요소와,
+그 기본 요소를 들여온 커밋·hunk를 함께 보고해야 합니다.
+
+    data/test_files/code_diff/<test_id>.diff     단일 unified diff (`git diff` 출력, 커밋 없음)
+    data/test_files/code_diff/<test_id>.patch    작은 저장소 이력 (`git format-patch --stdout` 출력, 커밋 여러 개)
+
+라벨은 추가된(`+`) 줄이 들여온 알고리즘만입니다. 문맥 줄에만 보이는 기존 알고리즘이나 삭제된(`-`) 줄의
+알고리즘은 라벨이 아닙니다.
+
+hunk 식별: 커밋 안에서 파일별로 1부터 센 순번입니다 (`@@` 머리말 순서).
+    커밋 86550379…의 src/Sender.java 두 번째 hunk → {"commit": "8655037", "file": "src/Sender.java", "hunk": 2}
+
+Ground truth는 라벨마다 들여온 위치를 기록합니다 (hunk는 정수 하나 또는 목록 — 목록이면 어느 hunk든 정답):
+    "expected_findings": {
+        "vulnerable_algorithms_detected": ["ECDSA"],
+        "introduced_in": {"ECDSA": {"commit": "8655037", "file": "src/Sender.java", "hunk": [1, 2]}}
+    }
+
+탐지기 응답의 선택 필드 `introductions`로 위치를 받습니다. hunk 순번 대신 변경 후(new) 기준 줄 번호
+`line`을 주어도 해당 hunk로 해석합니다:
+    "introductions": [{"algorithm": "ECDSA", "commit": "8655037", "file": "src/Sender.java", "hunk": 2}]
+"""
+
+import re
+from typing import Dict, Any, List, Optional
+
+DIFF_SUFFIXES = ('.diff', '.patch')
+
+# 커밋 해시는 앞 7자리 이상이 일치하면 같은 커밋으로 봄 (git 축약 해시)
+MIN_COMMIT_PREFIX = 7
+
+_COMMIT_HEADER = re.compile(r'^From ([0-9a-f]{40}) ')
+_FILE_HEADER = re.compile(r'^diff --git a/(\S+) b/(\S+)')
+_HUNK_HEADER = re.compile(r'^@@ -(\d+)(?:,(\d+))? \+(\d+)(?:,(\d+))? @@')
+
+
+def is_diff_sample(path) -> bool:
+    return str(path).endswith(DIFF_SUFFIXES)
+
+
+def parse_patch(text: str) -> List[Dict[str, Any]]:
+    """unified diff / format-patch 출력 → hunk 목록
+
+    각 hunk: {'commit', 'subject', 'file', 'hunk', 'new_start', 'new_count', 'added', 'removed'}
+    단일 diff는 commit이 None입니다.
+    """
+    hunks: List[Dict[str, Any]] = []
+    commit, subject, path = None, '', None
+    per_file: Dict[tuple, int] = {}
+    current = None
+
+    for line in text.splitlines():
+        header = _COMMIT_HEADER.match(line)
+        if header:
+            commit, subject, path, current = header.group(1), '', None, None
+            continue
+        if line.startswith('Subject: ') and current is None:
+            subject = re.sub(r'^\[PATCH[^\]]*\]\s*', '', line[len('Subject: '):])
+            continue
+        file_header = _FILE_HEADER.match(line)
+        if file_header:
+            path, current = file_header.group(2), None
+            continue
+        hunk_header = _HUNK_HEADER.match(line)
+        if hunk_header and path:
+            key = (commit, path)
+            per_file[key] = per_file.get(key, 0) + 1
+            current = {
+                'commit': commit,
+                'subject': subject,
+                'file': path,
+                'hunk': per_file[key],
+                'new_start': int(hunk_header.group(3)),
+                'new_count': int(hunk_header.group(4)) if hunk_header.group(4) is not None else 1,
+                'added': [],
+                'removed': []
+            }
+            hunks.append(current)
+            continue
+        if current is None:
+            continue
+        # format-patch 서명 구분자 ("-- ")에서 커밋 본문이 끝남
+        if line == '-- ':
+            current = None
+        elif line.startswith('+') and not line.startswith('+++'):
+            current['added'].append(line[1:])
+        elif line.startswith('-') and not line.startswith('---'):
+            current['removed'].append(line[1:])
+
+    return hunks
+
+
+def commits_match(expected: Optional[str], reported: Optional[str]) -> bool:
+    """커밋 일치 여부 (단일 diff처럼 기대 커밋이 없으면 항상 일치)"""
+    if not expected:
+        return True
+    if not reported:
+        return False
+    expected, reported = expected.lower(), str(reported).lower()
+    prefix = min(len(expected), len(reported))
+    return prefix >= MIN_COMMIT_PREFIX and expected[:prefix] == reported[:prefix]
+
+
+def _files_match(expected: str, reported: Optional[str]) -> bool:
+    if not reported:
+        return False
+    reported = str(reported).lstrip('./')
+    if reported.startswith(('a/', 'b/')):
+        reported = reported[2:]
+    return expected == reported or expected.endswith('/' + reported)
+
+
+def locate(introduction: Dict[str, Any], hunks: List[Dict[str, Any]]) -> Optional[Dict[str, Any]]:
+    """탐지기가 보고한 위치(commit/file + hunk 또는 line)를 hunk 하나로 해석"""
+    for hunk in hunks:
+        if not _files_match(hunk['file'], introduction.get('file')):
+            continue
+        if hunk['commit'] and introduction.get('commit') and \
+                not commits_match(hunk['commit'], introduction['commit']):
+            continue
+        if 'hunk' in introduction:
+            if str(introduction['hunk']) == str(hunk['hunk']):
+                return hunk
+        elif isinstance(introduction.get('line'), int):
+            if hunk['new_start'] <= introduction['line'] < hunk['new_start'] + hunk['new_count']:
+                return hunk
+    return None
+
+
+def expected_hunks(location: Dict[str, Any]) -> List[int]:
+    hunk = location.get('hunk', [])
+    return list(hunk) if isinstance(hunk, list) else [hunk]
+
+
+def recorded_introductions(ground_truth: Optional[Dict[str, Any]]) -> Dict[str, Dict[str, Any]]:
+    """ground truth의 라벨별 도입 위치 {알고리즘: {'commit', 'file', 'hunk'}}"""
+    if not ground_truth:
+        return {}
+    return dict(ground_truth.get('expected_findings', {}).get('introduced_in', {}))
+
+
+def find_expected_hunks(location: Dict[str, Any], hunks: List[Dict[str, Any]]) -> List[Dict[str, Any]]:
+    """ground truth 위치가 가리키는 hunk (lint와 채점에서 공유)"""
+    wanted = expected_hunks(location)
+    return [
+        hunk for hunk in hunks
+        if hunk['file'] == location.get('file') and hunk['hunk'] in wanted
+        and commits_match(location.get('commit'), hunk['commit'])
+    ]
+
+
+def calculate_hunk_scores(introductions: List[Any], ground_truth: Dict[str, Any],
+                          hunks: List[Dict[str, Any]], policy: Any = None) -> Optional[Dict[str, Any]]:
+    """hunk 단위 도입 위치 채점 (도입 위치가 기록되지 않은 샘플은 None)
+
+    정답 라벨마다, 같은 알고리즘으로 해석되는(계층 점수 > 0) 보고 중 하나라도 정답 hunk를 가리키면
+    hunk 적중, 정답 커밋만 맞으면 커밋 적중으로 셉니다. 위치를 보고했지만 어떤 정답 hunk에도
+    해당하지 않는 보고는 잘못된 위치(mislocated)로 셉니다.
+    """
+    from utils.taxonomy import AlgorithmTaxonomy
+
+    recorded = recorded_introductions(ground_truth)
+    if not recorded:
+        return None
+
+    taxonomy = AlgorithmTaxonomy()
+    policy = taxonomy.get_policy(policy)
+    reports = [
+        (taxonomy.resolve(item['algorithm']), item, locate(item, hunks))
+        for item in introductions
+        if isinstance(item, dict) and item.get('algorithm')
+    ]
+
+    targets_by_label = {
+        label: (taxonomy.resolve(label), find_expected_hunks(location, hunks))
+        for label, location in recorded.items()
+    }
+
+    matches = []
+    for label, location in recorded.items():
+        node, targets = targets_by_label[label]
+        hunk_hit = commit_hit = False
+        reported = None
+        for predicted, item, hunk in reports:
+            if taxonomy.credit(predicted, node, policy) <= 0:
+                continue
+            if hunk is not None and any(hunk is target for target in targets):
+                hunk_hit = commit_hit = True
+                reported = item
+                break
+            if location.get('commit') and commits_match(location['commit'], item.get('commit')):
+                commit_hit = True
+                reported = reported or item
+        matches.append({
+            'label': label,
+            'expected': location,
+            'reported': reported,
+            'hunk_hit': hunk_hit,
+            'commit_hit': commit_hit
+        })
+
+    def correctly_located(predicted, hunk) -> bool:
+        return any(
+            taxonomy.credit(predicted, node, policy) > 0 and any(hunk is target for target in targets)
+            for node, targets in targets_by_label.values()
+        )
+
+    labels = len(matches)
+    return {
+        'labels': labels,
+        'hunk_hits': sum(1 for m in matches if m['hunk_hit']),
+        'commit_hits': sum(1 for m in matches if m['commit_hit']),
+        'hunk_recall': sum(1 for m in matches if m['hunk_hit']) / labels,
+        'commit_recall': sum(1 for m in matches if m['commit_hit']) / labels,
+        'mislocated': sum(1 for predicted, _, hunk in reports
+                          if hunk is not None and not correctly_located(predicted, hunk)),
+        'matches': matches
+    }
@@ -0,0 +1,249 @@
+#!/usr/bin/env python3
+"""
+실행 환경 기록 (reproducible environment capture)
+
+결과 파일만 보고 실행을 다시 만들 수 있도록, 실행 결과 metadata.environment에 무엇으로 돌렸는지를 남깁니다.
+utils/run_compare.py는 두 결과의 환경을 비교해, 점수 차이가 모델이 아니라 코퍼스·프롬프트·시드 차이일 수 있을 때
+경고합니다.
+
+기록하는 것 (capture_environment):
+    qvbench         패키지 버전(qvbench.__version__), 저장소 커밋과 작업 트리 변경 여부 (git이 없으면 null)
+    corpus_manifest 코퍼스 매니페스트 버전 (utils/incremental.py snapshot, 샘플 본문과 ground truth 전체 해시)
+    detectors       탐지기별 설정: LLM은 프로바이더/모델과 설정의 생성 파라미터(GENERATION_KEYS),
+                    로컬 탐지기는 describe() 결과와 참조하는 파일(모델, 규칙, 라벨 맵 등)의 sha256 앞 12자리
+    prompts         에이전트별 프롬프트 템플릿 해시 (자리표시 입력으로 만든 프롬프트 전체, 예시/근거 요청 설정 포함)
+    seeds           실행에 쓴 시드 (few-shot, 부분집합, PYTHONHASHSEED)
+    host            Python 버전/구현, 플랫폼, CPU 수, 주요 패키지 버전(KEY_PACKAGES), 호스트 이름 해시
+                    (이름 자체는 공유 결과에 남기지 않음, utils/redaction.py 참고)
+
+비교 (environment_mismatches): 항목마다 심각도를 붙입니다.
+    warning   qvbench 버전/커밋, 작업 트리 변경, 코퍼스 매니페스트, 프롬프트, 시드, 같은 이름 탐지기의 설정
+    info      호스트(Python, 플랫폼, 패키지 버전) — 결과가 달라질 수는 있지만 보통 재현을 막지는 않음
+
+사용법:
+    python -m utils.environment                                  # 지금 환경
+    python -m utils.environment results/old.json results/new.json   # 두 결과의 환경 차이
+"""
+
+import argparse
+import hashlib
+import json
+import os
+import platform
+import socket
+import subprocess
+import sys
+from pathlib import Path
+from typing import Dict, Any, Iterable, List, Optional
+
+ROOT = Path(__file__).parent.parent
+
+# 결과에 버전을 남길 패키지 (설치된 것만)
+KEY_PACKAGES = ('openai', 'anthropic', 'google-generativeai', 'requests', 'pyyaml', 'numpy', 'pandas',
+                'pyarrow', 'onnxruntime', 'grpcio', 'jinja2', 'tree-sitter')
+
+# LLM 설정에서 결과에 영향을 주는 생성 파라미터
+GENERATION_KEYS = ('temperature', 'top_p', 'top_k', 'max_tokens', 'seed', 'num_ctx', 'reasoning_effort')
+
+PROMPT_PLACEHOLDER = '<<QVBENCH_INPUT>>'
+
+SEVERITY_WARNING = 'warning'
+SEVERITY_INFO = 'info'
+
+
+def _digest(data: bytes) -> str:
+    return hashlib.sha256(data).hexdigest()[:12]
+
+
+def package_version() -> str:
+    from qvbench import __version__
+    return __version__
+
+
+def git_state(root: Path = ROOT) -> Optional[Dict[str, Any]]:
+    """저장소 커밋과 추적 파일 변경 여부 (git 저장소가 아니거나 git이 없으면 None)"""
+    try:
+        commit = subprocess.run(['git', '-C', str(root), 'rev-parse', 'HEAD'], check=True,
+                                capture_output=True, text=True, timeout=10).stdout.strip()
+        status = subprocess.run(['git', '-C', str(root), 'status', '--porcelain', '--untracked-files=no'],
+                                check=True, capture_output=True, text=True, timeout=10).stdout
+    except (OSError, subprocess.SubprocessError):
+        return None
+    return {'commit': commit, 'dirty': bool(status.strip())}
+
+
+def installed_packages(names: Iterable[str] = KEY_PACKAGES) -> Dict[str, str]:
+    from importlib import metadata
+
+    versions = {}
+    for name in names:
+        try:
+            versions[name] = metadata.version(name)
+        except metadata.PackageNotFoundError:
+            continue
+    return versions
+
+
+def host_info() -> Dict[str, Any]:
+    return {
+        'python': platform.python_version(),
+        'implementation': platform.python_implementation(),
+        'platform': platform.platform(),
+        'machine': platform.machine(),
+        'cpu_count': os.cpu_count(),
+        'host_id': _digest(socket.gethostname().encode('utf-8')),
+        'packages': installed_packages(),
+    }
+
+
+def model_settings(provider: str, model: str, llm_config: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+    """LLM 탐지기 설정 (API 키, 주소 같은 값은 넣지 않음)"""
+    settings = {'provider': provider, 'model': model}
+    settings.update({key: (llm_config or {})[key] for key in GENERATION_KEYS if key in (llm_config or {})})
+    return settings
+
+
+def _file_hashes(value: Any, hashes: Dict[str, str]):
+    if isinstance(value, dict):
+        for item in value.values():
+            _file_hashes(item, hashes)
+    elif isinstance(value, (list, tuple)):
+        for item in value:
+            _file_hashes(item, hashes)
+    elif isinstance(value, str) and value and len(value) < 4096:
+        path = Path(value)
+        try:
+            if path.is_file():
+                hashes[value] = _digest(path.read_bytes())
+        except OSError:
+            pass
+
+
+def detector_settings(description: Dict[str, Any]) -> Dict[str, Any]:
+    """로컬 탐지기 describe() + 참조 파일 해시 (모델 파일을 다시 학습하면 해시가 바뀜)"""
+    hashes: Dict[str, str] = {}
+    _file_hashes(description, hashes)
+    return {**description, 'files': dict(sorted(hashes.items()))} if hashes else dict(description)
+
+
+def prompt_hashes(agents: Dict[str, Any]) -> Dict[str, str]:
+    """에이전트 객체별 프롬프트 해시 (자리표시 입력으로 만든 프롬프트 전체)"""
+    return {agent_type: _digest(agent.create_prompt(PROMPT_PLACEHOLDER).encode('utf-8'))
+            for agent_type, agent in sorted(agents.items())}
+
+
+def capture_environment(corpus_manifest: Optional[str] = None, detectors: Optional[Dict[str, Any]] = None,
+                        prompts: Optional[Dict[str, str]] = None,
+                        seeds: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+    """실행 결과 metadata.environment"""
+    return {
+        'qvbench': {'version': package_version(), 'git': git_state()},
+        'corpus_manifest': corpus_manifest,
+        'detectors': detectors or {},
+        'prompts': prompts or {},
+        'seeds': {**(seeds or {}), 'PYTHONHASHSEED': os.environ.get('PYTHONHASHSEED')},
+        'host': host_info(),
+    }
+
+
+def results_environment(path: str) -> Optional[Dict[str, Any]]:
+    """결과 JSON의 metadata.environment (JSONL이거나 기록이 없으면 None)"""
+    try:
+        data = json.loads(Path(path).read_text(encoding='utf-8'))
+    except (OSError, json.JSONDecodeError):
+        return None
+    if not isinstance(data, dict):
+        return None
+    return (data.get('metadata') or {}).get('environment')
+
+
+def _mismatch(field: str, old: Any, new: Any, severity: str) -> Dict[str, Any]:
+    return {'field': field, 'old': old, 'new': new, 'severity': severity}
+
+
+def _compare_maps(field: str, old: Dict[str, Any], new: Dict[str, Any], severity: str,
+                  shared_only: bool = False) -> List[Dict[str, Any]]:
+    keys = sorted(set(old) & set(new)) if shared_only else sorted(set(old) | set(new))
+    return [_mismatch(f"{field}.{key}", old.get(key), new.get(key), severity)
+            for key in keys if old.get(key) != new.get(key)]
+
+
+def environment_mismatches(old: Optional[Dict[str, Any]],
+                           new: Optional[Dict[str, Any]]) -> List[Dict[str, Any]]:
+    """두 실행 환경의 차이 (환경 기록이 없는 쪽이 있으면 그 사실 하나만)"""
+    if not old or not new:
+        return [_mismatch('environment', 'recorded' if old else 'missing', 'recorded' if new else 'missing',
+                          SEVERITY_WARNING)]
+
+    mismatches = []
+    old_package, new_package = old.get('qvbench') or {}, new.get('qvbench') or {}
+    if old_package.get('version') != new_package.get('version'):
+        mismatches.append(_mismatch('qvbench.version', old_package.get('version'), new_package.get('version'),
+                                    SEVERITY_WARNING))
+    old_git, new_git = old_package.get('git') or {}, new_package.get('git') or {}
+    if old_git.get('commit') != new_git.get('commit'):
+        mismatches.append(_mismatch('qvbench.commit', old_git.get('commit'), new_git.get('commit'),
+                                    SEVERITY_WARNING))
+    if old_git.get('dirty') or new_git.get('dirty'):
+        mismatches.append(_mismatch('qvbench.dirty', bool(old_git.get('dirty')), bool(new_git.get('dirty')),
+                                    SEVERITY_WARNING))
+    if old.get('corpus_manifest') != new.get('corpus_manifest'):
+        mismatches.append(_mismatch('corpus_manifest', old.get('corpus_manifest'), new.get('corpus_manifest'),
+                                    SEVERITY_WARNING))
+
+    # 프롬프트와 탐지기는 두 실행에 모두 있는 것만 (에이전트나 탐지기 구성이 다른 비교는 흔함)
+    mismatches += _compare_maps('prompts', old.get('prompts') or {}, new.get('prompts') or {},
+                                SEVERITY_WARNING, shared_only=True)
+    mismatches += _compare_maps('seeds', old.get('seeds') or {}, new.get('seeds') or {}, SEVERITY_WARNING)
+    mismatches += _compare_maps('detectors', old.get('detectors') or {}, new.get('detectors') or {},
+                                SEVERITY_WARNING, shared_only=True)
+
+    old_host, new_host = old.get('host') or {}, new.get('host') or {}
+    for key in ('python', 'implementation', 'platform'):
+        if old_host.get(key) != new_host.get(key):
+            mismatches.append(_mismatch(f"host.{key}", old_host.get(key), new_host.get(key), SEVERITY_INFO))
+    mismatches += _compare_maps('host.packages', old_host.get('packages') or {}, new_host.get('packages') or {},
+                                SEVERITY_INFO)
+    return mismatches
+
+
+def _short(value: Any, limit: int = 60) -> str:
+    text = value if isinstance(value, str) else json.dumps(value, ensure_ascii=False, sort_keys=True)
+    return text if len(text) <= limit else text[:limit - 1] + '…'
+
+
+def print_mismatches(mismatches: List[Dict[str, Any]]):
+    if not mismatches:
+        print("✅ 실행 환경 일치 (qvbench, 코퍼스, 프롬프트, 시드, 탐지기 설정)")
+        return
+    warnings = [item for item in mismatches if item['severity'] == SEVERITY_WARNING]
+    if warnings:
+        print(f"⚠️  실행 환경이 다릅니다 ({len(warnings)}건): 점수 차이가 모델 변화만은 아닐 수 있습니다")
+    for item in mismatches:
+        icon = '⚠️ ' if item['severity'] == SEVERITY_WARNING else 'ℹ️ '
+        print(f"   {icon} {item['field']}: {_short(item['old'])} → {_short(item['new'])}")
+
+
+def main():
+    parser = argparse.ArgumentParser(description='실행 환경 기록 확인 / 두 결과의 환경 비교')
+    parser.add_argument('results', nargs='*', help='결과 JSON (없으면 지금 환경, 두 개면 환경 차이)')
+    args = parser.parse_args()
+
+    if not args.results:
+        from utils.incremental import snapshot
+        print(json.dumps(capture_environment(snapshot()['corpus_version']), indent=2, ensure_ascii=False))
+        return
+    if len(args.results) == 1:
+        environment = results_environment(args.results[0])
+        if environment is None:
+            print(f"❌ {args.results[0]}: 환경 기록이 없습니다 (metadata.environment)")
+            sys.exit(1)
+        print(json.dumps(environment, indent=2, ensure_ascii=False))
+        return
+    if len(args.results) != 2:
+        parser.error('결과 파일은 0~2개만 받습니다')
+    print_mismatches(environment_mismatches(*(results_environment(path) for path in args.results)))
+
+
+if __name__ == '__main__':
+    main()
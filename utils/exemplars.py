@@ -0,0 +1,135 @@
+"""
+few-shot 예시 선택
+
+LLM 탐지기 프롬프트에 넣을 풀이 예시(샘플 + 정답 라벨)를 train 분할(utils/splits.py)에서만 고릅니다.
+평가(test) 샘플은 예시 후보가 될 수 없고, 질의 샘플 자신도 제외하므로 예시로 인한 정답 유출이 없습니다.
+
+전략:
+    random      후보에서 무작위 (질의마다 시드 고정 → 재현 가능)
+    nearest     식별자/상수 토큰 빈도 벡터의 코사인 유사도가 높은 순
+    balanced    정답 알고리즘 계열별로 돌아가며 하나씩 (암호가 없는 샘플도 한 부류로 취급)
+
+예시 정답은 응답 형식과 같은 "DETECTED: <알고리즘>" 줄로 보여줍니다 (BaseAnalysisAgent.create_prompt).
+질의마다 사용한 예시의 test_id는 결과의 `exemplars` 필드에 남습니다.
+
+설정 (config.yaml):
+    benchmark:
+      few_shot:
+        enabled: false
+        strategy: "nearest"
+        k: 2
+"""
+
+import math
+import random
+import re
+from collections import Counter
+from typing import Dict, Any, List, Optional
+
+from utils.metrics_calculator import MetricsCalculator
+from utils.splits import SPLIT_TRAIN, sample_split
+from utils.taxonomy import AlgorithmTaxonomy
+
+STRATEGIES = ['random', 'nearest', 'balanced']
+
+DEFAULT_STRATEGY = 'nearest'
+DEFAULT_K = 2
+DEFAULT_MAX_CHARS = 1200
+
+# 예시가 없는 부류 (암호 없음)
+NO_CRYPTO_CLASS = 'none'
+
+_TOKEN = re.compile(r'0x[0-9a-fA-F]+|[A-Za-z_][A-Za-z0-9_]{2,}')
+
+
+def tokens(text: str) -> Counter:
+    return Counter(token.lower() for token in _TOKEN.findall(text))
+
+
+def cosine(a: Counter, b: Counter) -> float:
+    dot = sum(count * b[token] for token, count in a.items() if token in b)
+    norm = math.sqrt(sum(v * v for v in a.values())) * math.sqrt(sum(v * v for v in b.values()))
+    return dot / norm if norm else 0.0
+
+
+class ExemplarSampler:
+    """에이전트 하나의 train 분할 예시 후보에서 질의별 few-shot 예시 선택"""
+
+    def __init__(self, agent_type: str, test_cases: List[Dict[str, Any]], ground_truths: Dict[str, Dict[str, Any]],
+                 strategy: str = DEFAULT_STRATEGY, k: int = DEFAULT_K, seed: int = 0,
+                 max_chars: int = DEFAULT_MAX_CHARS):
+        if strategy not in STRATEGIES:
+            raise ValueError(f"unknown exemplar strategy: {strategy} (choose from {STRATEGIES})")
+        self.agent_type = agent_type
+        self.strategy = strategy
+        self.k = k
+        self.seed = seed
+        self.max_chars = max_chars
+        self.taxonomy = AlgorithmTaxonomy()
+
+        self.pool = []
+        for case in test_cases:
+            ground_truth = ground_truths.get(case['test_id'])
+            if not ground_truth or sample_split(agent_type, case['test_id'], ground_truth) != SPLIT_TRAIN:
+                continue
+            labels = MetricsCalculator.get_expected_labels(ground_truth)
+            self.pool.append({
+                'test_id': case['test_id'],
+                'input_data': case.get('input_data', ''),
+                'labels': labels,
+                'classes': sorted({self.taxonomy.resolve(label)[1] or label for label in labels}) or [NO_CRYPTO_CLASS],
+                'tokens': tokens(case.get('input_data', ''))
+            })
+
+    def select(self, query: Dict[str, Any]) -> List[Dict[str, Any]]:
+        """질의 샘플에 붙일 예시 [{'test_id', 'input_data', 'labels'}] (질의 자신은 제외)"""
+        candidates = [item for item in self.pool if item['test_id'] != query.get('test_id')]
+        if not candidates or self.k <= 0:
+            return []
+
+        rng = random.Random(f"{self.seed}:{self.agent_type}:{query.get('test_id')}")
+        if self.strategy == 'random':
+            chosen = rng.sample(candidates, min(self.k, len(candidates)))
+        elif self.strategy == 'nearest':
+            query_tokens = tokens(query.get('input_data', ''))
+            chosen = sorted(candidates, key=lambda item: (-cosine(query_tokens, item['tokens']), item['test_id']))
+            chosen = chosen[:self.k]
+        else:
+            chosen = self._balanced(candidates, rng)
+
+        return [
+            {'test_id': item['test_id'], 'input_data': item['input_data'][:self.max_chars], 'labels': item['labels']}
+            for item in chosen
+        ]
+
+    def _balanced(self, candidates: List[Dict[str, Any]], rng: random.Random) -> List[Dict[str, Any]]:
+        by_class: Dict[str, List[Dict[str, Any]]] = {}
+        for item in candidates:
+            for family in item['classes']:
+                by_class.setdefault(family, []).append(item)
+        classes = sorted(by_class)
+        rng.shuffle(classes)
+
+        chosen: List[Dict[str, Any]] = []
+        while len(chosen) < min(self.k, len(candidates)):
+            progressed = False
+            for family in classes:
+                taken = {item['test_id'] for item in chosen}
+                remaining = [item for item in by_class[family] if item['test_id'] not in taken]
+                if remaining and len(chosen) < self.k:
+                    chosen.append(rng.choice(remaining))
+                    progressed = True
+            if not progressed:
+                break
+        return chosen
+
+
+def few_shot_settings(config: Optional[Dict[str, Any]]) -> Dict[str, Any]:
+    config = config or {}
+    return {
+        'enabled': bool(config.get('enabled', False)),
+        'strategy': config.get('strategy', DEFAULT_STRATEGY),
+        'k': int(config.get('k', DEFAULT_K)),
+        'seed': int(config.get('seed', 0)),
+        'max_chars': int(config.get('max_chars', DEFAULT_MAX_CHARS))
+    }
@@ -0,0 +1,329 @@
+"""
+정적 특징 추출 (함수 단위)
+
+암호 구현은 이름을 숨겨도 숫자 상수 표(S-box, 라운드 상수), 비트 회전, 고정 횟수 라운드 루프 같은 흔적이 남습니다.
+샘플을 함수 단위로 나눠 이 흔적을 수치 특징으로 뽑습니다. 로컬 ML 기준선(detectors/)과 샘플 난이도 추정이
+같은 특징을 쓰며, 연구자가 자기 분류기를 만들 수 있도록 공개 API로 둡니다.
+
+    extract_features(text, language)   {'file': 파일 전체 특징, 'functions': [함수별 특징]}
+    function_features(text, language)  특징 dict 하나 (FEATURE_NAMES + loop_bounds)
+    feature_vector(features)           FEATURE_NAMES 순서의 float 목록 (분류기 입력)
+    split_functions(text, language)    [{'name', 'start_line', 'end_line', 'text'}]
+    language_family(suffix)            확장자 → 언어 계열, 확장자를 모르면 guess_language(agent_type, text)
+
+특징:
+    numeric_literals / numeric_density   숫자 리터럴 수, 토큰 100개당 숫자 리터럴 수
+    hex_literals / wide_hex_literals     16진 리터럴 수, 8자리 이상(32비트 이상) 16진 리터럴 수
+    table_count / max_table_size         숫자 8개 이상이 연속된 배열 초기화 수, 가장 큰 표의 원소 수
+    rotation_ops / shift_ops / xor_ops   비트 회전(rotl/ror 등, (x << n) | (x >> m) 관용구 포함), 시프트, XOR 연산 수
+    modular_ops                          모듈러 거듭제곱/곱셈 흔적 (pow(…, …, m), modPow, mulmod, % 연산)
+    entropy / max_window_entropy         바이트 엔트로피 (전체, 256바이트 슬라이딩 창의 최대값)
+    loop_bounds / loop_bound_<N>         상수 루프 횟수 히스토그램 (16, 64, 80처럼 라운드 수를 드러내는 값)
+
+함수 분할은 언어 계열별 휴리스틱입니다 (중괄호 언어는 중괄호 짝, Python은 들여쓰기, 어셈블리는 전역 레이블).
+분할되지 않으면 파일 전체를 `<module>` 함수 하나로 봅니다.
+
+사용법:
+    python -m utils.features data/test_files/source_code/chacha20_poly1305_aead.java
+    python -m utils.features --agent source_code --output analysis_output/features_source_code.json
+"""
+
+import argparse
+import json
+import math
+import re
+from collections import Counter
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+LANGUAGE_FAMILIES = {
+    'brace': {'.c', '.cpp', '.cc', '.h', '.hpp', '.cs', '.go', '.java', '.js', '.kt', '.php', '.rs', '.scala',
+              '.swift', '.ts'},
+    'python': {'.py'},
+    'ruby': {'.rb'},
+    'asm': {'.s', '.asm'},
+}
+
+# 루프 횟수 히스토그램 구간 (상한, 포함)
+LOOP_BOUND_BUCKETS = [8, 16, 32, 64, 80, 256]
+
+TABLE_MIN_SIZE = 8
+ENTROPY_WINDOW = 256
+ENTROPY_STEP = 64
+
+FEATURE_NAMES = [
+    'lines', 'tokens', 'numeric_literals', 'numeric_density', 'hex_literals', 'wide_hex_literals',
+    'table_count', 'max_table_size', 'rotation_ops', 'shift_ops', 'xor_ops', 'modular_ops',
+    'entropy', 'max_window_entropy',
+] + [f"loop_bound_{bound}" for bound in LOOP_BOUND_BUCKETS] + ['loop_bound_large']
+
+_TOKEN = re.compile(r'0[xX][0-9a-fA-F]+|\d+|[A-Za-z_]\w*|[^\s\w]')
+_NUMBER = re.compile(r'(?<![\w.])(?:0[xX][0-9a-fA-F]+|\d+)(?:[uUlL]|_?u(?:8|16|32|64|size)|_?i(?:32|64))*\b')
+_HEX = re.compile(r'(?<![\w.])0[xX]([0-9a-fA-F]+)')
+_GROUP = re.compile(r'[\[{(]([^\[\]{}()]*)[\]})]')
+_TABLE_ITEM = re.compile(r'(?:0[xX][0-9a-fA-F]\w*|\d\w*)')
+_ASM_TABLE = re.compile(r'^\s*\.(?:byte|word|long|quad|int|short|dword)\s+(.+)$', re.MULTILINE | re.IGNORECASE)
+_ROTATION = re.compile(
+    r'\brot(?:ate)?_?(?:l|r|left|right)\w*\s*\(|\b(?:rol|ror|rorx)\b|\bInteger\.rotate(?:Left|Right)\b|\bbits\.RotateLeft'
+    r'|<<\s*\(?\s*\w+\s*\)?\s*\)?\s*\|\s*\(?\s*\w+\s*>>>?|>>>?\s*\(?\s*\w+\s*\)?\s*\)?\s*\|\s*\(?\s*\w+\s*<<',
+    re.IGNORECASE
+)
+_SHIFT = re.compile(r'<<|>>>?|\b(?:shl|shr|sar|sal|lsl|lsr|asr)\b', re.IGNORECASE)
+_XOR = re.compile(r'\^|\bxor\b|\beor\b|\bpxor\b|\bvpxor\b', re.IGNORECASE)
+_MODULAR = re.compile(r'\bpow\s*\([^()]*,[^()]*,|\bmod_?pow\b|\bmodPow\b|\bmulmod\b|\bmod_?inverse\b|\bmodInverse\b',
+                      re.IGNORECASE)
+# 변수로 나누는 % 연산 (어셈블리의 %레지스터와 구분하기 위해 어셈블리에서는 세지 않음)
+_MODULO = re.compile(r'[\w)\]]\s*%\s*[A-Za-z_(]')
+_LOOP_BOUNDS = [
+    re.compile(r'\bfor\s+\w+\s+in\s+\d+\s*\.\.=?\s*(\d+)'),                   # Rust
+    re.compile(r'\bfor\s*\([^;]*;\s*\w+\s*<=?\s*(\d+)\s*;'),                  # C 계열
+    re.compile(r'\bfor\s+\w+\s+in\s+range\(\s*(?:\d+\s*,\s*)?(\d+)\s*[,)]'),  # Python
+    re.compile(r'\b(\d+)\.times\b'),                                          # Ruby
+    re.compile(r'\bcmp[a-z]?\s+\$(\d+|0x[0-9a-fA-F]+)\s*,', re.IGNORECASE),             # 어셈블리 (AT&T)
+    re.compile(r'\bcmp\s+[a-z]\w*\s*,\s*(\d+|0x[0-9a-fA-F]+)\b', re.IGNORECASE),        # 어셈블리 (Intel)
+]
+
+_BRACE_FUNCTION = re.compile(
+    r'^[ \t]*(?:(?:pub(?:\([^)]*\))?|public|private|protected|internal|static|final|abstract|override|async|'
+    r'unsafe|extern(?:\s+"C")?|inline|const|virtual|open|suspend|export|default)\s+)*'
+    r'(?:fn|func|function|def|fun)\s+(?:\([^)]*\)\s*)?([A-Za-z_]\w*)'
+    r'|^[ \t]*(?:(?:public|private|protected|internal|static|final|abstract|override|async|synchronized|inline|'
+    r'virtual|const|unsigned|signed|struct)\s+)*[A-Za-z_][\w:<>,\[\]*& ]*?[\s*&]([A-Za-z_]\w*)\s*\([^;{}]*\)\s*'
+    r'(?:const\s*)?(?:throws\s+[\w.,\s]+)?\{',
+    re.MULTILINE
+)
+_CONTROL_KEYWORDS = {'if', 'for', 'while', 'switch', 'catch', 'return', 'else', 'match', 'sizeof', 'new'}
+_PYTHON_FUNCTION = re.compile(r'^([ \t]*)(?:async\s+)?def\s+([A-Za-z_]\w*)', re.MULTILINE)
+_RUBY_FUNCTION = re.compile(r'^([ \t]*)def\s+(?:self\.)?([A-Za-z_]\w*[?!]?)', re.MULTILINE)
+_ASM_LABEL = re.compile(r'^([A-Za-z_][\w.$@]*):', re.MULTILINE)
+
+
+def language_family(suffix: str) -> Optional[str]:
+    """파일 확장자 → 언어 계열 (brace, python, ruby, asm)"""
+    suffix = suffix.lower() if suffix.startswith('.') else f".{suffix.lower()}"
+    for family, suffixes in LANGUAGE_FAMILIES.items():
+        if suffix in suffixes:
+            return family
+    return None
+
+
+def guess_language(agent_type: str, text: str) -> Optional[str]:
+    """파일 확장자를 모를 때 내용으로 언어 계열 추정 (어셈블리 에이전트는 asm)"""
+    if agent_type == 'assembly_binary':
+        return 'asm'
+    if re.search(r'^[ \t]*def\s+\w+\s*\(.*\)\s*(?:->\s*[^:]+)?:\s*$', text, re.MULTILINE):
+        return 'python'
+    if re.search(r'^[ \t]*def\s+\w+', text, re.MULTILINE) and re.search(r'^[ \t]*end\s*$', text, re.MULTILINE):
+        return 'ruby'
+    return 'brace' if '{' in text else None
+
+
+def byte_entropy(data: bytes) -> float:
+    """바이트 단위 샤논 엔트로피 (비트, 0~8)"""
+    if not data:
+        return 0.0
+    counts = Counter(data)
+    return -sum(count / len(data) * math.log2(count / len(data)) for count in counts.values())
+
+
+def window_entropies(data: bytes, window: int = ENTROPY_WINDOW, step: int = ENTROPY_STEP) -> List[float]:
+    """슬라이딩 창별 엔트로피 (창보다 짧으면 전체 하나)"""
+    if len(data) <= window:
+        return [byte_entropy(data)]
+    return [byte_entropy(data[start:start + window]) for start in range(0, len(data) - window + 1, step)]
+
+
+def loop_bounds(text: str) -> Counter:
+    """상수 루프 횟수 → 등장 횟수"""
+    bounds: Counter = Counter()
+    for pattern in _LOOP_BOUNDS:
+        for match in pattern.finditer(text):
+            value = int(match.group(1), 0) if match.group(1).lower().startswith('0x') else int(match.group(1))
+            if value > 1:
+                bounds[value] += 1
+    return bounds
+
+
+def loop_bound_histogram(bounds: Counter) -> Dict[str, int]:
+    histogram = {f"loop_bound_{bucket}": 0 for bucket in LOOP_BOUND_BUCKETS}
+    histogram['loop_bound_large'] = 0
+    for value, count in bounds.items():
+        bucket = next((b for b in LOOP_BOUND_BUCKETS if value <= b), None)
+        histogram[f"loop_bound_{bucket}" if bucket else 'loop_bound_large'] += count
+    return histogram
+
+
+def _tables(text: str) -> List[int]:
+    sizes = []
+    for match in _GROUP.finditer(text):
+        items = [item.strip() for item in match.group(1).split(',')]
+        items = items[:-1] if items and not items[-1] else items
+        if len(items) >= TABLE_MIN_SIZE and all(_TABLE_ITEM.fullmatch(item) for item in items):
+            sizes.append(len(items))
+
+    # 어셈블리 데이터 지시어: 연속된 .byte/.long 줄을 한 표로
+    run = 0
+    for line in text.splitlines() + ['']:
+        match = _ASM_TABLE.match(line.split('#')[0].split(';')[0])
+        items = [item.strip() for item in match.group(1).split(',')] if match else []
+        if items and all(_TABLE_ITEM.fullmatch(item) for item in items):
+            run += len(items)
+        else:
+            if run >= TABLE_MIN_SIZE:
+                sizes.append(run)
+            run = 0
+    return sizes
+
+
+def function_features(text: str, language: Optional[str] = None) -> Dict[str, Any]:
+    """코드 조각 하나의 특징"""
+    tokens = _TOKEN.findall(text)
+    numbers = _NUMBER.findall(text)
+    hexes = _HEX.findall(text)
+    tables = _tables(text)
+    data = text.encode('utf-8', errors='replace')
+    bounds = loop_bounds(text)
+
+    features = {
+        'lines': len(text.splitlines()),
+        'tokens': len(tokens),
+        'numeric_literals': len(numbers),
+        'numeric_density': round(100.0 * len(numbers) / len(tokens), 3) if tokens else 0.0,
+        'hex_literals': len(hexes),
+        'wide_hex_literals': sum(1 for digits in hexes if len(digits) >= 8),
+        'table_count': len(tables),
+        'max_table_size': max(tables, default=0),
+        'rotation_ops': len(_ROTATION.findall(text)),
+        'shift_ops': len(_SHIFT.findall(text)),
+        'xor_ops': len(_XOR.findall(text)),
+        'modular_ops': len(_MODULAR.findall(text)) + (0 if language == 'asm' else len(_MODULO.findall(text))),
+        'entropy': round(byte_entropy(data), 4),
+        'max_window_entropy': round(max(window_entropies(data)), 4),
+    }
+    features.update(loop_bound_histogram(bounds))
+    features['loop_bounds'] = {str(value): count for value, count in sorted(bounds.items())}
+    return features
+
+
+def feature_vector(features: Dict[str, Any]) -> List[float]:
+    """FEATURE_NAMES 순서의 수치 벡터"""
+    return [float(features.get(name, 0.0)) for name in FEATURE_NAMES]
+
+
+def _brace_end(lines: List[str], start: int) -> int:
+    depth, opened = 0, False
+    for index in range(start, len(lines)):
+        code = re.sub(r'"(?:\\.|[^"\\])*"|\'(?:\\.|[^\'\\])*\'|//.*$', '', lines[index])
+        depth += code.count('{') - code.count('}')
+        opened = opened or '{' in code
+        if opened and depth <= 0:
+            return index
+    return len(lines) - 1
+
+
+def _indent_end(lines: List[str], start: int, indent: int, terminator: Optional[str] = None) -> int:
+    end = start
+    for index in range(start + 1, len(lines)):
+        line = lines[index]
+        if not line.strip():
+            continue
+        current = len(line) - len(line.lstrip())
+        if current <= indent:
+            if terminator and line.strip() == terminator and current == indent:
+                return index
+            break
+        end = index
+    return end
+
+
+def split_functions(text: str, language: Optional[str]) -> List[Dict[str, Any]]:
+    """함수 단위 분할 (줄 번호는 1부터, 양끝 포함)"""
+    lines = text.splitlines()
+    spans = []
+
+    if language == 'brace':
+        for match in _BRACE_FUNCTION.finditer(text):
+            name = match.group(1) or match.group(2)
+            if name in _CONTROL_KEYWORDS:
+                continue
+            start = text.count('\n', 0, match.start())
+            if spans and start <= spans[-1][2]:
+                continue  # 앞 함수 안의 중첩 함수/람다
+            spans.append((name, start, _brace_end(lines, start)))
+    elif language in ('python', 'ruby'):
+        pattern = _PYTHON_FUNCTION if language == 'python' else _RUBY_FUNCTION
+        for match in pattern.finditer(text):
+            start = text.count('\n', 0, match.start())
+            if spans and start <= spans[-1][2]:
+                continue
+            end = _indent_end(lines, start, len(match.group(1)), 'end' if language == 'ruby' else None)
+            spans.append((match.group(2), start, end))
+    elif language == 'asm':
+        labels = [(match.group(1), text.count('\n', 0, match.start())) for match in _ASM_LABEL.finditer(text)
+                  if not match.group(1).startswith('.L')]
+        for index, (name, start) in enumerate(labels):
+            end = labels[index + 1][1] - 1 if index + 1 < len(labels) else len(lines) - 1
+            spans.append((name, start, end))
+
+    if not spans:
+        return [{'name': '<module>', 'start_line': 1, 'end_line': len(lines), 'text': text}]
+    return [
+        {'name': name, 'start_line': start + 1, 'end_line': end + 1, 'text': '\n'.join(lines[start:end + 1])}
+        for name, start, end in spans
+    ]
+
+
+def extract_features(text: str, language: Optional[str] = None) -> Dict[str, Any]:
+    """파일 전체와 함수별 특징"""
+    functions = []
+    for function in split_functions(text, language):
+        features = function_features(function['text'], language)
+        functions.append({'name': function['name'], 'start_line': function['start_line'],
+                          'end_line': function['end_line'], 'features': features})
+    return {'language': language, 'file': function_features(text, language), 'functions': functions}
+
+
+def extract_file(path: Path) -> Dict[str, Any]:
+    text = path.read_text(encoding='utf-8', errors='replace')
+    return {'path': str(path), **extract_features(text, language_family(path.suffix))}
+
+
+def _print_file(result: Dict[str, Any]):
+    print(f"\n📄 {result['path']} ({result['language'] or '알 수 없는 언어'}, 함수 {len(result['functions'])}개)")
+    keys = ['numeric_density', 'max_table_size', 'rotation_ops', 'xor_ops', 'modular_ops', 'max_window_entropy']
+    print(f"  {'함수':<32} {'줄':>10} " + ' '.join(f"{key:>18}" for key in keys) + '  루프 횟수')
+    for function in result['functions']:
+        features = function['features']
+        span = f"{function['start_line']}-{function['end_line']}"
+        bounds = ', '.join(f"{value}×{count}" for value, count in features['loop_bounds'].items())
+        print(f"  {function['name'][:32]:<32} {span:>10} " + ' '.join(f"{features[key]:>18}" for key in keys)
+              + f"  {bounds}")
+
+
+def main():
+    parser = argparse.ArgumentParser(description='함수 단위 정적 특징 추출')
+    parser.add_argument('files', nargs='*', help='특징을 뽑을 파일')
+    parser.add_argument('--agent', help='에이전트의 테스트 파일 전체 (data/test_files/<agent>)')
+    parser.add_argument('--test-files-dir', default='data/test_files')
+    parser.add_argument('--output', help='결과 JSON 경로 (지정하지 않으면 표로 출력)')
+    args = parser.parse_args()
+
+    paths = [Path(path) for path in args.files]
+    if args.agent:
+        paths.extend(sorted(path for path in (Path(args.test_files_dir) / args.agent).iterdir() if path.is_file()))
+    if not paths:
+        parser.error('파일 또는 --agent를 지정하세요')
+
+    results = [extract_file(path) for path in paths]
+    if args.output:
+        Path(args.output).parent.mkdir(parents=True, exist_ok=True)
+        with open(args.output, 'w', encoding='utf-8') as f:
+            json.dump({'feature_names': FEATURE_NAMES, 'files': results}, f, indent=2, ensure_ascii=False)
+        print(f"💾 특징 {len(results)}개 파일 저장: {args.output}")
+    else:
+        for result in results:
+            _print_file(result)
+
+
+if __name__ == "__main__":
+    main()
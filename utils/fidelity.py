@@ -0,0 +1,53 @@
+"""
+알고리즘 구현 충실도 (fidelity) 등급
+
+모든 샘플이 표준과 비트 단위로 일치할 필요는 없으므로, 라벨마다 샘플 구현이
+라벨의 알고리즘을 얼마나 충실히 구현하는지 등급을 기록합니다.
+
+    exact                   표준과 비트 단위로 일치 (known-answer 테스트로 검증)
+    structurally-faithful   구조(라운드, 키 스케줄, 연산 흐름)는 표준과 같지만 검증 벡터가 없거나 일부 단순화
+    stylized                알고리즘의 특징만 흉내 낸 구현 (자체 S-box, 축약 라운드 등)
+
+Ground truth 표기:
+    expected_findings 형식:  "expected_findings": {"fidelity": {"AES": "exact", "ECC": "stylized"}, ...}
+    레거시 형식:              "quantum_vulnerable_algorithms": [{"algorithm": "AES-256", "fidelity": "exact", ...}]
+
+충실도가 기록되지 않은 라벨은 'unlabeled'로 집계됩니다.
+"""
+
+from typing import Dict, Any, Optional
+
+FIDELITY_EXACT = 'exact'
+FIDELITY_STRUCTURAL = 'structurally-faithful'
+FIDELITY_STYLIZED = 'stylized'
+FIDELITY_UNLABELED = 'unlabeled'
+
+# 충실도 높은 순
+FIDELITY_LEVELS = [FIDELITY_EXACT, FIDELITY_STRUCTURAL, FIDELITY_STYLIZED]
+
+
+def label_fidelity(ground_truth: Optional[Dict[str, Any]]) -> Dict[str, str]:
+    """라벨(알고리즘)별 충실도 {알고리즘: 등급}"""
+    if not ground_truth:
+        return {}
+
+    analysis = ground_truth.get('vulnerability_analysis')
+    if isinstance(analysis, dict):
+        return {
+            entry['algorithm']: entry['fidelity']
+            for entry in analysis.get('quantum_vulnerable_algorithms', [])
+            if entry.get('algorithm') and entry.get('fidelity')
+        }
+
+    return dict(ground_truth.get('expected_findings', {}).get('fidelity', {}))
+
+
+def sample_fidelity(ground_truth: Optional[Dict[str, Any]]) -> str:
+    """샘플 전체 충실도: 라벨 중 가장 낮은 등급 (하나라도 stylized면 stylized)
+
+    일부 라벨에만 등급이 있으면 기록된 라벨만으로 판단합니다.
+    """
+    levels = [level for level in label_fidelity(ground_truth).values() if level in FIDELITY_LEVELS]
+    if not levels:
+        return FIDELITY_UNLABELED
+    return max(levels, key=FIDELITY_LEVELS.index)
@@ -0,0 +1,151 @@
+"""
+탐지 결과(Finding) 중복 제거 및 병합 정책
+
+한 파일 안에서 같은 알고리즘이 여러 번 보고되는 경우(청크 A와 청크 B가 각각 보고,
+시그니처 패스와 AST 패스가 각각 보고 등)의 의미를 정의합니다.
+채점 전에 항상 동일한 정책으로 병합하여 이중 집계를 방지합니다.
+
+Finding 형식:
+    {
+        'algorithm': 'RSA',          # 알고리즘 이름
+        'span': [10, 42] | None,     # 시작/끝 라인 (1-based, 양끝 포함), 위치 정보가 없으면 None
+        'confidence': 0.9,           # 0.0 ~ 1.0
+        'source': 'llm'              # 보고한 패스/청크 식별자
+    }
+
+병합 정책:
+    union           알고리즘당 하나의 Finding. 모든 span과 source를 보존
+    span_merge      같은 알고리즘이라도 span이 겹치거나 맞닿을 때만 병합 (기본값)
+    confidence_max  알고리즘당 하나의 Finding. 신뢰도가 가장 높은 보고만 채택
+"""
+
+from typing import Dict, Any, List, Optional
+
+POLICY_UNION = 'union'
+POLICY_SPAN_MERGE = 'span_merge'
+POLICY_CONFIDENCE_MAX = 'confidence_max'
+
+MERGE_POLICIES = [POLICY_UNION, POLICY_SPAN_MERGE, POLICY_CONFIDENCE_MAX]
+DEFAULT_MERGE_POLICY = POLICY_SPAN_MERGE
+# span_merge에서 같은 구현으로 보는 span 사이 최대 간격 (1: 겹치거나 바로 인접한 줄)
+SPAN_MERGE_GAP = 1
+
+
+class FindingsMerger:
+    """설정 가능한 Finding 병합기"""
+
+    def __init__(self, policy: str = DEFAULT_MERGE_POLICY):
+        if policy not in MERGE_POLICIES:
+            raise ValueError(f"Unknown merge policy: {policy} (choose from {MERGE_POLICIES})")
+        self.policy = policy
+
+    @staticmethod
+    def make_finding(algorithm: str, span: Optional[List[int]] = None,
+                     confidence: float = 0.0, source: str = 'llm') -> Dict[str, Any]:
+        return {
+            'algorithm': algorithm,
+            'span': list(span) if span else None,
+            'confidence': float(confidence or 0.0),
+            'source': source
+        }
+
+    @staticmethod
+    def normalize_algorithm(name: str) -> str:
+        """병합 키: 대소문자/공백/언더스코어 차이 무시"""
+        return str(name).strip().upper().replace('_', '-').replace(' ', '-')
+
+    def merge(self, findings: List[Dict[str, Any]]) -> List[Dict[str, Any]]:
+        """정책에 따라 Finding 목록을 병합
+
+        결과 Finding에는 병합된 원본 수(`merged_count`)와 출처 목록(`sources`)이 추가됩니다.
+        결과 순서는 각 알고리즘이 처음 보고된 순서를 따릅니다.
+        """
+        groups: Dict[str, List[Dict[str, Any]]] = {}
+        for finding in findings:
+            key = self.normalize_algorithm(finding['algorithm'])
+            groups.setdefault(key, []).append(finding)
+
+        merged = []
+        for group in groups.values():
+            if self.policy == POLICY_UNION:
+                merged.append(self._merge_union(group))
+            elif self.policy == POLICY_CONFIDENCE_MAX:
+                merged.append(self._merge_confidence_max(group))
+            else:
+                merged.extend(self._merge_spans(group))
+        return merged
+
+    @staticmethod
+    def algorithms(findings: List[Dict[str, Any]]) -> List[str]:
+        """병합 결과에서 알고리즘 이름 목록 추출 (중복 제거, 순서 유지)"""
+        names = []
+        for finding in findings:
+            if finding['algorithm'] not in names:
+                names.append(finding['algorithm'])
+        return names
+
+    # ==================== 정책 구현 ====================
+
+    @staticmethod
+    def _sources(group: List[Dict[str, Any]]) -> List[str]:
+        sources = []
+        for finding in group:
+            source = finding.get('source', 'unknown')
+            if source not in sources:
+                sources.append(source)
+        return sources
+
+    def _merge_union(self, group: List[Dict[str, Any]]) -> Dict[str, Any]:
+        spans = sorted({tuple(f['span']) for f in group if f.get('span')})
+        return {
+            'algorithm': group[0]['algorithm'],
+            'span': [min(s[0] for s in spans), max(s[1] for s in spans)] if spans else None,
+            'spans': [list(s) for s in spans],
+            'confidence': max(f.get('confidence', 0.0) for f in group),
+            'sources': self._sources(group),
+            'merged_count': len(group)
+        }
+
+    def _merge_confidence_max(self, group: List[Dict[str, Any]]) -> Dict[str, Any]:
+        # 동률이면 먼저 보고된 Finding 유지
+        best = max(group, key=lambda f: f.get('confidence', 0.0))
+        return {
+            'algorithm': group[0]['algorithm'],
+            'span': list(best['span']) if best.get('span') else None,
+            'confidence': best.get('confidence', 0.0),
+            'sources': [best.get('source', 'unknown')],
+            'merged_count': len(group)
+        }
+
+    def _merge_spans(self, group: List[Dict[str, Any]]) -> List[Dict[str, Any]]:
+        located = sorted((f for f in group if f.get('span')), key=lambda f: f['span'][0])
+        unlocated = [f for f in group if not f.get('span')]
+
+        clusters: List[List[Dict[str, Any]]] = []
+        for finding in located:
+            # 겹치거나 바로 인접한 span (end + 1 >= start)은 같은 구현으로 간주
+            if clusters and finding['span'][0] <= clusters[-1][-1]['_end'] + SPAN_MERGE_GAP:
+                finding = dict(finding, _end=max(finding['span'][1], clusters[-1][-1]['_end']))
+                clusters[-1].append(finding)
+            else:
+                clusters.append([dict(finding, _end=finding['span'][1])])
+
+        # 위치 정보가 없는 보고는 어느 구현을 가리키는지 알 수 없으므로
+        # 위치가 있는 보고가 하나라도 있으면 첫 클러스터에 흡수, 없으면 하나로 병합
+        if unlocated:
+            if clusters:
+                clusters[0].extend(unlocated)
+            else:
+                clusters.append(unlocated)
+
+        merged = []
+        for cluster in clusters:
+            spans = [f['span'] for f in cluster if f.get('span')]
+            merged.append({
+                'algorithm': group[0]['algorithm'],
+                'span': [min(s[0] for s in spans), max(s[1] for s in spans)] if spans else None,
+                'confidence': max(f.get('confidence', 0.0) for f in cluster),
+                'sources': self._sources(cluster),
+                'merged_count': len(cluster)
+            })
+        return merged
@@ -0,0 +1,425 @@
+"""
+채점기와 파서의 속성 기반 검사 / 퍼징
+
+LLM 응답과 외부 도구의 SARIF 결과는 벤치마크 밖에서 만들어진 신뢰할 수 없는 입력입니다. 형식이 어긋난 응답 하나가
+예외로 전체 실행을 멈추지 않도록, 시드로 재현되는 무작위 입력을 만들어 파서와 채점기에 넣고 속성을 확인합니다.
+
+생성기:
+    sample_label      형식이 맞는 ground truth (expected_findings / 레거시 vulnerability_analysis, 양자 내성 라벨,
+                      mode_findings) — 코퍼스 lint를 통과하는 모양이므로 변형하지 않음
+    detection_report  탐지 보고 (extract_key_findings 결과 모양). labels/weaknesses 항목에 문자열, 객체, 숫자,
+                      null, 중첩 배열이 섞이고 confidence_score가 "85%"나 NaN일 수 있음
+    response_text     detection_report를 JSON으로 쓴 뒤 마크다운 펜스, think 태그, 잘림, 스마트 따옴표, 후행 쉼표,
+                      파이썬 리터럴, 무작위 문자 삽입/삭제로 변형한 응답 원문
+    sarif_log         규칙/결과/위치/uriBaseId/억제/kind가 있는 SARIF 2.1.0 로그, 일부 하위 트리를 임의 JSON으로 바꿈
+
+대상과 속성:
+    response   응답 원문 → extract_key_findings → 알고리즘 추출 → 근거 줄 → 중복 병합
+               예외 없음, parse_status는 ok/repaired/failed, valid_json이면 analysis_results 값이 모두 문자열
+    scorer     (탐지 보고, ground truth) → 응답 스키마 검증 → 정확도, 계층 F1, 양자 내성 오분류, 약점 점수
+               예외 없음, 점수는 모두 [0, 1], 정답 라벨을 그대로 보고하면 계층 F1 = 1
+    sarif      SARIF 로그 → SarifDetector.load → 로그에 나온 경로마다 detect
+               예외 없음, 라벨은 문자열 목록, 신뢰도는 [0, 1]
+
+실패하면 입력과 traceback을 --crash-dir에 JSON으로 남기고 종료 코드 1로 끝납니다. --replay로 같은 입력을 다시
+돌려 수정 여부를 확인합니다. 같은 --seed면 같은 입력 순서가 나옵니다.
+
+사용법:
+    python -m utils.fuzz                                   # 모든 대상 500회씩
+    python -m utils.fuzz --target response --iterations 5000 --seed 7
+    python -m utils.fuzz --replay results/fuzz_crashes/response-0-1234.json
+"""
+
+import argparse
+import json
+import math
+import random
+import sys
+import tempfile
+import time
+import traceback
+from pathlib import Path
+from typing import Dict, Any, Callable, List, Optional
+
+from agents.response_parser import PARSE_FAILED, PARSE_OK, PARSE_REPAIRED
+from utils.misuse import MODES, WEAKNESSES, WEAKNESS_ALIASES
+from utils.taxonomy import TAXONOMY
+
+CRASH_DIR = Path(__file__).parent.parent / "results" / "fuzz_crashes"
+DEFAULT_ITERATIONS = 500
+
+PARSE_STATUSES = {PARSE_OK, PARSE_REPAIRED, PARSE_FAILED}
+
+# 분류 체계의 이름 (카테고리, 계열, 변형) — 양자 내성 계열은 따로
+VULNERABLE_NAMES = [name for category, families in TAXONOMY.items() if category != 'post_quantum'
+                    for family, variants in families.items() for name in [family] + variants]
+QUANTUM_SAFE_NAMES = [name for family, variants in TAXONOMY.get('post_quantum', {}).items()
+                      for name in [family] + variants]
+CATEGORY_NAMES = list(TAXONOMY)
+
+# 무작위 문자열에 섞는 문자 (JSON/정규식에서 의미 있는 문자, 제어 문자, 서로게이트 밖 유니코드)
+SPECIAL_CHARS = '{}[]",:\\/\'`<>()*+?.^$|#\n\t\r\x00\x1b “”‘’…한글🔐'
+FIELD_NAMES = ['rsa_encryption', 'elliptic_curve', 'aes_usage', 'hash_functions', 'korean_algorithms',
+               'key_exchange', 'weak_random', 'summary']
+
+
+def random_string(rng: random.Random, max_length: int = 24) -> str:
+    length = rng.randint(0, max_length)
+    pool = SPECIAL_CHARS + 'abcdefghijklmnopqrstuvwxyzABCDEFGHIJ0123456789 -_'
+    return ''.join(rng.choice(pool) for _ in range(length))
+
+
+def random_json(rng: random.Random, depth: int = 3) -> Any:
+    """임의 JSON 값 (NaN/Infinity, 큰 정수, 깊은 중첩 포함 — json.loads가 받아들이는 값)"""
+    kinds = ['null', 'bool', 'int', 'float', 'string', 'name'] + (['array', 'object'] * 2 if depth > 0 else [])
+    kind = rng.choice(kinds)
+    if kind == 'null':
+        return None
+    if kind == 'bool':
+        return rng.random() < 0.5
+    if kind == 'int':
+        return rng.choice([0, -1, 1, 2 ** 63, -2 ** 70, rng.randint(-1000, 1000)])
+    if kind == 'float':
+        return rng.choice([0.5, -0.0, 1e308, -1e-308, math.nan, math.inf, -math.inf, rng.uniform(-2, 2)])
+    if kind == 'string':
+        return random_string(rng)
+    if kind == 'name':
+        return rng.choice(VULNERABLE_NAMES + QUANTUM_SAFE_NAMES + CATEGORY_NAMES)
+    if kind == 'array':
+        return [random_json(rng, depth - 1) for _ in range(rng.randint(0, 4))]
+    return {rng.choice(FIELD_NAMES + [random_string(rng, 8)]): random_json(rng, depth - 1)
+            for _ in range(rng.randint(0, 4))}
+
+
+def mutate_json(rng: random.Random, value: Any, rate: float = 0.15) -> Any:
+    """하위 트리를 임의 값으로 바꾸거나 키를 지우거나 배열로 감싼 사본"""
+    roll = rng.random()
+    if roll < rate / 2:
+        return random_json(rng)
+    if roll < rate:
+        return [value] if rng.random() < 0.5 else {'value': value}
+    if isinstance(value, dict):
+        mutated = {}
+        for key, item in value.items():
+            if rng.random() < rate / 3:
+                continue
+            mutated[key] = mutate_json(rng, item, rate)
+        return mutated
+    if isinstance(value, list):
+        return [mutate_json(rng, item, rate) for item in value if rng.random() >= rate / 3]
+    return value
+
+
+def sample_label(rng: random.Random) -> Dict[str, Any]:
+    """형식이 맞는 ground truth"""
+    labels = rng.sample(VULNERABLE_NAMES, rng.randint(0, 4))
+    safe = rng.sample(QUANTUM_SAFE_NAMES, rng.randint(0, 2)) if rng.random() < 0.3 else []
+    mode_findings = [{'algorithm': label, 'mode': rng.choice(MODES + [None]),
+                      'weaknesses': rng.sample(sorted(WEAKNESSES), rng.randint(1, 2))}
+                     for label in labels if rng.random() < 0.3]
+
+    if rng.random() < 0.7:
+        findings: Dict[str, Any] = {'vulnerable_algorithms_detected': labels,
+                                    'algorithm_categories': rng.sample(CATEGORY_NAMES, rng.randint(0, 2))}
+        if safe:
+            findings['quantum_safe_algorithms'] = safe
+        if mode_findings:
+            findings['mode_findings'] = [{key: value for key, value in entry.items() if value is not None}
+                                         for entry in mode_findings]
+        return {'expected_findings': findings, 'expected_confidence_range': [0.5, 0.9]}
+
+    entries = [{'algorithm': label, 'category': 'shor_vulnerable', 'line_range': f"{rng.randint(1, 50)}-60"}
+               for label in labels]
+    entries += [{'algorithm': label, 'category': 'post_quantum_safe'} for label in safe]
+    analysis: Dict[str, Any] = {'quantum_vulnerable_algorithms': entries}
+    if mode_findings:
+        analysis['mode_findings'] = mode_findings
+    return {'vulnerability_analysis': analysis}
+
+
+def _analysis_value(rng: random.Random, names: List[str]) -> Any:
+    name = rng.choice(names) if names else rng.choice(VULNERABLE_NAMES)
+    return rng.choice([
+        f"DETECTED: {name} (Evidence: line {rng.randint(0, 400)})",
+        f"DETECTED: {name} (evidence: lines {rng.randint(1, 99)}-{rng.randint(1, 99)})",
+        f"DETECTED:{random_string(rng)}",
+        f"{name} implementation found in key schedule",
+        'NOT DETECTED', 'none', '', f"does not contain {name}",
+        random_string(rng, 60), None, rng.randint(-5, 5), [name], {'algorithm': name},
+    ])
+
+
+def _label_entry(rng: random.Random, names: List[str]) -> Any:
+    name = rng.choice(names) if names and rng.random() < 0.6 else rng.choice(VULNERABLE_NAMES)
+    return rng.choice([
+        name, f"{rng.choice(CATEGORY_NAMES)}/{name}", f"{name}-{rng.choice(['256', 'CBC', 'GCM', 'x'])}",
+        {'category': rng.choice(CATEGORY_NAMES), 'family': name},
+        {'family': name, 'key_size': rng.choice([128, '256', None, -1]), 'mode': rng.choice(MODES + ['??'])},
+        {'label': name, 'attributes': random_json(rng, 1)},
+        {'family': random_json(rng, 1), 'variant': random_json(rng, 1)},
+        random_json(rng, 2),
+    ])
+
+
+def _weakness_entry(rng: random.Random, names: List[str]) -> Any:
+    weakness = rng.choice(sorted(WEAKNESSES) + list(WEAKNESS_ALIASES) + [random_string(rng, 10)])
+    return rng.choice([
+        weakness,
+        {'algorithm': rng.choice(names + [None]) if names else None, 'mode': rng.choice(MODES + [None, 'xor']),
+         'weakness': weakness},
+        {'algorithm': random_json(rng, 1), 'weaknesses': [weakness, random_json(rng, 1)]},
+        {'weaknesses': weakness},
+        random_json(rng, 2),
+    ])
+
+
+def detection_report(rng: random.Random, label: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+    """응답 JSON 객체 (정답 라벨을 섞어 적중도 나오게)"""
+    from utils.metrics_calculator import MetricsCalculator
+
+    names = MetricsCalculator.get_expected_labels(label) if label else []
+    report: Dict[str, Any] = {
+        'agent_type': rng.choice(['source_code', random_json(rng, 0)]),
+        'analysis_results': {rng.choice(FIELD_NAMES + [random_string(rng, 6)]): _analysis_value(rng, names)
+                             for _ in range(rng.randint(0, 6))},
+        'confidence_score': rng.choice([0.85, 1, 0, '85%', ' 0.4 ', 'high', 1.5, -0.1, math.nan, None, True]),
+        'summary': random_string(rng, 40),
+        'labels': [_label_entry(rng, names) for _ in range(rng.randint(0, 5))],
+        'weaknesses': [_weakness_entry(rng, names) for _ in range(rng.randint(0, 3))],
+    }
+    for key in list(report):
+        if key != 'analysis_results' and rng.random() < 0.15:
+            del report[key]
+    return mutate_json(rng, report, 0.05)
+
+
+def response_text(rng: random.Random, report: Any) -> str:
+    """응답 객체 → LLM이 흔히 내는 형식 오류가 섞인 원문"""
+    text = json.dumps(report, ensure_ascii=rng.random() < 0.5, indent=rng.choice([None, 2]))
+    for _ in range(rng.randint(0, 4)):
+        mutation = rng.choice(['fence', 'think', 'prose', 'truncate', 'quotes', 'comma', 'python', 'insert',
+                               'delete', 'open_fence', 'unclosed_think'])
+        if mutation == 'fence':
+            text = f"```json\n{text}\n```"
+        elif mutation == 'open_fence':
+            text = f"```json\n{text}"
+        elif mutation == 'think':
+            text = f"<think>{random_string(rng, 40)}</think>{text}"
+        elif mutation == 'unclosed_think':
+            text = f"{text}<think>{random_string(rng, 20)}"
+        elif mutation == 'prose':
+            text = f"Here is the analysis:\n{text}\nHope this helps {random_string(rng, 10)}"
+        elif mutation == 'truncate' and text:
+            text = text[:rng.randint(0, len(text))]
+        elif mutation == 'quotes':
+            text = text.replace('"', rng.choice(['“', '”', "'"]), rng.randint(1, 6))
+        elif mutation == 'comma':
+            text = text.replace('}', ',}', 1).replace(']', ', ]', 1)
+        elif mutation == 'python':
+            text = text.replace('true', 'True').replace('null', 'None')
+        elif mutation == 'insert':
+            position = rng.randint(0, len(text))
+            text = text[:position] + random_string(rng, 5) + text[position:]
+        elif mutation == 'delete' and text:
+            position = rng.randint(0, len(text) - 1)
+            text = text[:position] + text[position + rng.randint(1, 5):]
+    return text
+
+
+def sarif_log(rng: random.Random) -> Dict[str, Any]:
+    """SARIF 로그 (일부 하위 트리는 임의 값)"""
+    runs = []
+    for _ in range(rng.randint(0, 2)):
+        rules = [{'id': f"crypto/{rng.choice(VULNERABLE_NAMES).lower()}-{index}",
+                  'shortDescription': {'text': random_string(rng, 20)},
+                  'defaultConfiguration': {'level': rng.choice(['error', 'warning', 'note', 'none', 'bogus'])},
+                  'properties': rng.choice([{}, {'qvbench_labels': rng.choice(VULNERABLE_NAMES)},
+                                            {'qvbench_labels': rng.sample(VULNERABLE_NAMES, 2)},
+                                            {'algorithm': rng.choice(VULNERABLE_NAMES)}])}
+                 for index in range(rng.randint(0, 3))]
+        results = []
+        for _ in range(rng.randint(0, 4)):
+            location = {'physicalLocation': {
+                'artifactLocation': rng.choice([
+                    {'uri': f"src/{random_string(rng, 6)}.rs"},
+                    {'uri': 'source_code/sample.rs', 'uriBaseId': 'SRCROOT'},
+                    {'uri': 'file:///tmp/a%20b/sample.rs'}, {}]),
+                'region': rng.choice([{}, {'startLine': rng.randint(-1, 500)}, {'startLine': '12'}])}}
+            results.append({
+                'ruleId': rng.choice([rule['id'] for rule in rules] + [random_string(rng, 8)]),
+                'level': rng.choice(['error', 'warning', None]),
+                'kind': rng.choice(['fail', 'pass', 'notApplicable', None]),
+                'message': {'text': f"Use of {rng.choice(VULNERABLE_NAMES)} {random_string(rng, 10)}"},
+                'locations': [location] * rng.randint(0, 2),
+                'suppressions': rng.choice([[], [{'kind': 'inSource'}], None]),
+            })
+        runs.append({'tool': {'driver': {'name': 'CodeQL', 'semanticVersion': '2.16.0', 'rules': rules},
+                              'extensions': [{'name': 'pack', 'rules': rules[:1]}]},
+                     'originalUriBaseIds': {'SRCROOT': {'uri': 'file:///work/data/test_files/'}},
+                     'results': results})
+    return mutate_json(rng, {'version': '2.1.0', 'runs': runs}, 0.08)
+
+
+def _check_score(name: str, value: Any):
+    assert isinstance(value, (int, float)) and not isinstance(value, bool), f"{name}: 숫자가 아님 ({value!r})"
+    assert 0.0 <= value <= 1.0, f"{name}: [0, 1] 밖의 값 {value!r}"
+
+
+def run_response(case: Dict[str, Any]):
+    from agents.agent_factory import AgentFactory
+    from benchmark_runner import BenchmarkRunner
+    from utils.chunking import evidence_span
+    from utils.findings_merger import FindingsMerger
+
+    findings = AgentFactory.create_agent('source_code').extract_key_findings(case['text'])
+    assert findings['parse_status'] in PARSE_STATUSES, f"parse_status: {findings['parse_status']!r}"
+    assert isinstance(findings['valid_json'], bool)
+    if findings['valid_json']:
+        results = findings['analysis_results']
+        assert isinstance(results, dict), f"analysis_results: {type(results).__name__}"
+        assert all(isinstance(value, str) for value in results.values()), "analysis_results 값이 문자열이 아님"
+
+    raw_findings = [FindingsMerger.make_finding(algorithm, span=evidence_span(evidence),
+                                                confidence=findings.get('confidence_score', 0.0))
+                    for algorithm, evidence in BenchmarkRunner._detected_algorithms(None, findings)]
+    FindingsMerger.algorithms(FindingsMerger().merge(raw_findings))
+
+
+def run_scorer(case: Dict[str, Any]):
+    from agents.agent_factory import AgentFactory
+    from utils.metrics_calculator import MetricsCalculator
+    from utils.misuse import calculate_weakness_scores
+
+    # 채점기는 스키마를 통과한 보고만 받음 (benchmark_runner.py와 같은 경로)
+    findings = AgentFactory.create_agent('source_code').extract_key_findings(json.dumps(case['report']))
+    label = case['label']
+    labels = findings.get('labels') if isinstance(findings.get('labels'), list) else []
+    weaknesses = findings.get('weaknesses') or []
+
+    _check_score('accuracy', MetricsCalculator.calculate_accuracy(findings, label))
+    scores = MetricsCalculator.calculate_hierarchical_scores(labels, label)
+    for field in ('precision', 'recall', 'f1'):
+        _check_score(f"hierarchical {field}", scores[field])
+    check = MetricsCalculator.calculate_quantum_safe_misclassification(labels, label)
+    if check is not None:
+        _check_score('misclassification_rate', check['misclassification_rate'])
+    weakness_scores = calculate_weakness_scores(weaknesses, label)
+    if weakness_scores is not None:
+        for field in ('precision', 'recall', 'f1'):
+            _check_score(f"weakness {field}", weakness_scores[field])
+
+    expected = MetricsCalculator.get_expected_labels(label)
+    perfect = MetricsCalculator.calculate_hierarchical_scores(expected, label)
+    assert perfect['f1'] == 1.0, f"정답 라벨을 그대로 보고했는데 계층 F1 {perfect['f1']} ({expected})"
+
+
+def run_sarif(case: Dict[str, Any]):
+    from detectors.external.sarif import SarifDetector
+
+    with tempfile.TemporaryDirectory() as directory:
+        path = Path(directory) / 'empty.sarif'
+        path.write_text('{"runs": []}', encoding='utf-8')
+        detector = SarifDetector([str(path)])
+    detector.load(case['log'])
+
+    for filename in {finding['uri'] for finding in detector.findings} | {'source_code/sample.rs'}:
+        report = detector.detect('source_code', '', filename)
+        assert all(isinstance(label, str) for label in report['labels']), f"라벨이 문자열이 아님: {report['labels']}"
+        _check_score('confidence', report['confidence'])
+
+
+def _response_case(rng: random.Random) -> Dict[str, Any]:
+    return {'text': response_text(rng, detection_report(rng, sample_label(rng)))}
+
+
+def _scorer_case(rng: random.Random) -> Dict[str, Any]:
+    label = sample_label(rng)
+    report = detection_report(rng, label)
+    # 대부분은 스키마를 통과하도록 (응답 형식 오류는 response 대상이 다룸)
+    if isinstance(report, dict) and rng.random() < 0.8:
+        report['confidence_score'] = round(rng.random(), 2)
+    return {'report': report if isinstance(report, dict) else {'labels': report}, 'label': label}
+
+
+# 대상 → (입력 생성기, 실행기)
+TARGETS: Dict[str, Any] = {
+    'response': (_response_case, run_response),
+    'scorer': (_scorer_case, run_scorer),
+    'sarif': (lambda rng: {'log': sarif_log(rng)}, run_sarif),
+}
+
+
+def case_rng(target: str, seed: int, iteration: int) -> random.Random:
+    return random.Random(f"{target}:{seed}:{iteration}")
+
+
+def save_crash(crash_dir: Path, target: str, seed: int, iteration: int, case: Dict[str, Any], error: str) -> Path:
+    crash_dir.mkdir(parents=True, exist_ok=True)
+    path = crash_dir / f"{target}-{seed}-{iteration}.json"
+    path.write_text(json.dumps({'target': target, 'seed': seed, 'iteration': iteration, 'case': case,
+                                'traceback': error}, indent=2, ensure_ascii=False), encoding='utf-8')
+    return path
+
+
+def fuzz(target: str, iterations: int, seed: int = 0, crash_dir: Path = CRASH_DIR,
+         progress: Optional[Callable[[int], None]] = None) -> List[Path]:
+    """대상 하나를 iterations번 실행, 실패한 입력 경로 목록 반환"""
+    generate, run = TARGETS[target]
+    crashes = []
+    for iteration in range(iterations):
+        case = generate(case_rng(target, seed, iteration))
+        try:
+            run(case)
+        except Exception:
+            crashes.append(save_crash(crash_dir, target, seed, iteration, case, traceback.format_exc()))
+        if progress:
+            progress(iteration)
+    return crashes
+
+
+def replay(path: str) -> bool:
+    """저장된 실패 입력을 다시 실행 (통과하면 True)"""
+    with open(path, 'r', encoding='utf-8') as f:
+        crash = json.load(f)
+    try:
+        TARGETS[crash['target']][1](crash['case'])
+    except Exception:
+        print(f"❌ {path}: 여전히 실패")
+        print(traceback.format_exc())
+        return False
+    print(f"✅ {path}: 통과")
+    return True
+
+
+def main():
+    parser = argparse.ArgumentParser(description='응답 파서, 채점기, SARIF 수집기 퍼징')
+    parser.add_argument('--target', choices=list(TARGETS), action='append', help='대상 (반복 가능, 기본: 전부)')
+    parser.add_argument('--iterations', type=int, default=DEFAULT_ITERATIONS, help='대상별 반복 횟수')
+    parser.add_argument('--seed', type=int, default=0, help='입력 순서 시드')
+    parser.add_argument('--crash-dir', default=str(CRASH_DIR), help='실패 입력 저장 디렉토리')
+    parser.add_argument('--replay', nargs='+', metavar='CRASH', help='저장된 실패 입력만 다시 실행')
+    args = parser.parse_args()
+
+    if args.replay:
+        sys.exit(0 if all([replay(path) for path in args.replay]) else 1)
+
+    failed = False
+    for target in args.target or list(TARGETS):
+        started = time.time()
+        crashes = fuzz(target, args.iterations, args.seed, Path(args.crash_dir))
+        elapsed = time.time() - started
+        if not crashes:
+            print(f"✅ {target}: {args.iterations}회 통과 ({elapsed:.1f}초, 시드 {args.seed})")
+            continue
+        failed = True
+        print(f"❌ {target}: {args.iterations}회 중 {len(crashes)}회 실패 ({elapsed:.1f}초)")
+        for path in crashes[:5]:
+            last_line = json.loads(path.read_text(encoding='utf-8'))['traceback'].strip().splitlines()[-1]
+            print(f"   {path}: {last_line}")
+        if len(crashes) > 5:
+            print(f"   ... 외 {len(crashes) - 5}개")
+    sys.exit(1 if failed else 0)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,86 @@
+"""
+생성기 시드 (GeneratorSeed)
+
+코퍼스 생성은 시드와 템플릿 버전이 같으면 바이트 단위로 같은 출력을 내야 합니다. 그래야 생성 샘플을
+저장소에 커밋하지 않고도 ground truth의 generated_by(시드, 템플릿 버전)만으로 다시 만들 수 있습니다.
+
+    - 시드는 부호 없는 64비트 정수 (0 ≤ seed < 2^64), CLI에서는 10진수 또는 0x 16진수
+    - 주 난수열은 random.Random(seed) (기존 생성 샘플과 호환)
+    - 생성 단계(예: 이후 추가되는 변환 패스)는 stream(이름)으로 주 난수열과 독립된 난수열을 받음
+      → 단계를 추가해도 기존 단계의 출력이 바뀌지 않음
+    - 템플릿 버전은 템플릿 디렉토리 파일 이름과 내용의 SHA-256 앞 12자리
+
+출력 고정은 골든 파일로 확인합니다 (python -m utils.sample_generator golden).
+"""
+
+import hashlib
+import random
+from pathlib import Path
+from typing import Iterable, Union
+
+SEED_BITS = 64
+SEED_LIMIT = 1 << SEED_BITS
+
+
+class GeneratorSeed:
+    """부호 없는 64비트 생성 시드"""
+
+    def __init__(self, value: int):
+        if isinstance(value, bool) or not isinstance(value, int):
+            raise TypeError(f"generator seed must be an integer, got {type(value).__name__}")
+        if not 0 <= value < SEED_LIMIT:
+            raise ValueError(f"generator seed must be in [0, 2^{SEED_BITS}), got {value}")
+        self.value = value
+
+    @classmethod
+    def of(cls, seed: Union['GeneratorSeed', int]) -> 'GeneratorSeed':
+        return seed if isinstance(seed, cls) else cls(seed)
+
+    @classmethod
+    def parse(cls, text: str) -> 'GeneratorSeed':
+        """CLI 인자 ("42", "0xdeadbeef") → 시드"""
+        return cls(int(text, 0))
+
+    @classmethod
+    def generate(cls) -> 'GeneratorSeed':
+        return cls(random.SystemRandom().getrandbits(SEED_BITS))
+
+    def rng(self) -> random.Random:
+        """주 난수열"""
+        return random.Random(self.value)
+
+    def stream(self, name: str) -> random.Random:
+        """이름별 독립 난수열 (시드와 이름의 SHA-256에서 유도)"""
+        digest = hashlib.sha256(f"{self.value}:{name}".encode()).digest()
+        return random.Random(int.from_bytes(digest, 'big'))
+
+    def offset(self, index: int) -> 'GeneratorSeed':
+        """--count 연속 생성용 다음 시드 (2^64에서 순환)"""
+        return GeneratorSeed((self.value + index) % SEED_LIMIT)
+
+    def __int__(self) -> int:
+        return self.value
+
+    def __eq__(self, other) -> bool:
+        return isinstance(other, GeneratorSeed) and other.value == self.value
+
+    def __hash__(self) -> int:
+        return hash(self.value)
+
+    def __repr__(self) -> str:
+        return f"GeneratorSeed({self.value})"
+
+    def __str__(self) -> str:
+        return str(self.value)
+
+
+def template_version(templates_dir: Path, extra_files: Iterable[Path] = ()) -> str:
+    """템플릿 디렉토리의 버전 (파일 이름 + 내용 해시, 디렉토리 밖에서 함께 읽는 파일은 extra_files)"""
+    digest = hashlib.sha256()
+    files = [path for path in sorted(Path(templates_dir).iterdir()) if path.is_file()]
+    for path in files + [Path(extra) for extra in extra_files]:
+        digest.update(path.name.encode())
+        digest.update(b'\0')
+        digest.update(path.read_bytes())
+        digest.update(b'\0')
+    return digest.hexdigest()[:12]
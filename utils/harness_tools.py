@@ -0,0 +1,260 @@
+"""
+함수 호출 하네스 도구 (grep / read_file / list_symbols)
+
+다중 턴 질의(utils/interrogation.py)의 도구 프로토콜입니다 (--interrogate N --tools). 질문 JSON 대신 모델의 함수 호출
+인터페이스(OpenAI tools, Anthropic tool use)로 샘플을 탐색하게 하고, 호출과 결과를 모두 결과 행에 남겨 감사할 수 있게 합니다.
+
+    grep          정규식 검색 (path 글롭으로 파일 제한, 결과 MAX_GREP_HITS줄)
+    read_file     파일의 줄 범위 (한 번에 MAX_READ_LINES줄)
+    list_symbols  파일(생략하면 전체)의 함수/impl/클래스 항목과 줄 범위 (SampleIndex)
+
+샌드박스:
+    작업 공간은 샘플을 시작할 때 메모리에 읽어 둔 파일뿐입니다. 디렉토리 샘플은 디렉토리 안의 일반 텍스트 파일
+    (심볼릭 링크, MAX_FILE_BYTES 초과, 바이너리 제외), 단일 파일 샘플은 그 파일 하나(프롬프트와 같은 canary 제거 본문).
+    프롬프트와 마찬가지로 파일마다 #[cfg(test)] 테스트 모듈을 지우므로 grep/read_file로 known-answer 주석을 볼 수 없습니다.
+    도구는 이 사본만 보므로 경로 조작("../", 절대 경로)으로 샘플 밖을 읽을 수 없고, 쓰기/실행 도구는 없습니다.
+
+턴 예산은 도구 호출 라운드 수입니다. 예산을 다 쓰면 도구를 끈 마지막 요청에서 최종 분석 JSON을 받습니다.
+결과 행 interrogation.turns[*].tool_calls에 호출마다 {'id', 'name', 'arguments', 'result'}가 모델이 본 그대로 남습니다.
+
+사용법:
+    python benchmark_runner.py --interrogate 4 --tools --agents source_code dependency_manifest --providers anthropic
+"""
+
+import fnmatch
+import re
+from pathlib import Path, PurePosixPath
+from typing import Any, Callable, Dict, List, Optional, Tuple
+
+from utils.canary import strip_canaries
+from utils.run_control import CancellationToken
+from utils.sample_index import SampleIndex
+from utils.test_case_manager import strip_test_modules
+
+MAX_FILE_BYTES = 1024 * 1024
+MAX_FILES = 200
+MAX_GREP_HITS = 50
+MAX_READ_LINES = 200
+MAX_PATTERN_CHARS = 200
+# 정규식 역추적이 길어지지 않도록 검색하는 줄 길이 상한
+MAX_GREP_LINE_CHARS = 2000
+
+TOOLS: List[Dict[str, Any]] = [
+    {
+        'name': 'grep',
+        'description': 'Search the sample files with a regular expression (Python syntax). '
+                       'Returns matching lines as "path:line: text".',
+        'parameters': {
+            'type': 'object',
+            'properties': {
+                'pattern': {'type': 'string', 'description': 'Regular expression'},
+                'path': {'type': 'string', 'description': 'Optional glob to limit files, e.g. "src/*.rs"'},
+                'ignore_case': {'type': 'boolean', 'description': 'Case-insensitive search (default true)'}
+            },
+            'required': ['pattern']
+        }
+    },
+    {
+        'name': 'read_file',
+        'description': f'Read a line range of a sample file (at most {MAX_READ_LINES} lines per call). '
+                       'Lines are prefixed with their 1-based numbers.',
+        'parameters': {
+            'type': 'object',
+            'properties': {
+                'path': {'type': 'string', 'description': 'File path as listed in the workspace'},
+                'start_line': {'type': 'integer', 'description': 'First line (1-based, default 1)'},
+                'end_line': {'type': 'integer', 'description': 'Last line (inclusive)'}
+            },
+            'required': ['path']
+        }
+    },
+    {
+        'name': 'list_symbols',
+        'description': 'List functions, impl blocks and classes with their line ranges.',
+        'parameters': {
+            'type': 'object',
+            'properties': {
+                'path': {'type': 'string', 'description': 'Optional file path (default: all files)'}
+            }
+        }
+    }
+]
+
+
+class SampleWorkspace:
+    """샘플 파일의 읽기 전용 메모리 사본 (경로 → 본문)"""
+
+    def __init__(self, files: Dict[str, str]):
+        self.files = files
+        self._indexes: Dict[str, SampleIndex] = {}
+
+    @classmethod
+    def from_test_case(cls, test_case: Dict[str, Any]) -> 'SampleWorkspace':
+        path = Path(test_case.get('file_path') or '')
+        if path.is_dir():
+            return cls.from_directory(path)
+        name = path.name or f"{test_case.get('test_id', 'sample')}{test_case.get('file_extension', '')}"
+        return cls({name: strip_test_modules(test_case.get('input_data', ''))})
+
+    @classmethod
+    def from_directory(cls, root: Path) -> 'SampleWorkspace':
+        files = {}
+        for path in sorted(root.rglob('*')):
+            if len(files) >= MAX_FILES:
+                break
+            if path.is_symlink() or not path.is_file() or path.stat().st_size > MAX_FILE_BYTES:
+                continue
+            data = path.read_bytes()
+            if b'\0' in data:
+                continue
+            text = strip_canaries(data.decode('utf-8', errors='replace'))
+            files[path.relative_to(root).as_posix()] = strip_test_modules(text)
+        return cls(files)
+
+    def _resolve(self, path: Optional[str]) -> Optional[str]:
+        """모델이 준 경로 → 작업 공간 경로 (없으면 None, 파일이 하나면 생략 가능)"""
+        if not path:
+            return next(iter(self.files)) if len(self.files) == 1 else None
+        # "./a", "/a"는 작업 공간 기준으로, ".."는 그대로 두어 일치하지 않게
+        normalized = PurePosixPath(*[part for part in str(path).replace('\\', '/').split('/') if part not in ('', '.')])
+        key = normalized.as_posix()
+        if key in self.files:
+            return key
+        # 파일 이름만 준 경우 (유일할 때만)
+        matches = [name for name in self.files if PurePosixPath(name).name == normalized.name]
+        return matches[0] if len(matches) == 1 else None
+
+    def index(self, path: str) -> SampleIndex:
+        if path not in self._indexes:
+            self._indexes[path] = SampleIndex.from_text(self.files[path], PurePosixPath(path).suffix or '.txt')
+        return self._indexes[path]
+
+    def overview(self) -> str:
+        """첫 요청에 넣는 작업 공간 목록 (파일, 줄 수, 항목 수)"""
+        rows = [f"- {path} ({len(text.splitlines())} lines, {len(self.index(path).items)} symbols)"
+                for path, text in self.files.items()]
+        return (f"Workspace ({len(self.files)} file(s)):\n" + '\n'.join(rows)
+                + "\n\nFile contents are not shown. Use the grep, read_file and list_symbols tools to inspect them.")
+
+    def grep(self, pattern: str, path: Optional[str] = None, ignore_case: bool = True) -> str:
+        if not pattern or len(pattern) > MAX_PATTERN_CHARS:
+            return f"error: pattern must be 1-{MAX_PATTERN_CHARS} characters"
+        try:
+            regex = re.compile(pattern, re.IGNORECASE if ignore_case else 0)
+        except re.error as e:
+            return f"error: invalid regular expression: {e}"
+        names = [name for name in self.files if not path or fnmatch.fnmatch(name, path)
+                 or fnmatch.fnmatch(PurePosixPath(name).name, path)]
+        if not names:
+            return f"error: no files match {path!r}"
+        hits = [f"{name}:{number}: {line[:300]}"
+                for name in names for number, line in enumerate(self.files[name].splitlines(), 1)
+                if regex.search(line[:MAX_GREP_LINE_CHARS])]
+        if not hits:
+            return "no matches"
+        more = f"\n... {len(hits) - MAX_GREP_HITS} more matches" if len(hits) > MAX_GREP_HITS else ''
+        return '\n'.join(hits[:MAX_GREP_HITS]) + more
+
+    def read_file(self, path: str, start_line: int = 1, end_line: Optional[int] = None) -> str:
+        name = self._resolve(path)
+        if name is None:
+            return f"error: no file {path!r} in the workspace"
+        lines = self.files[name].splitlines()
+        try:
+            start = max(1, int(start_line or 1))
+            end = min(len(lines), int(end_line) if end_line else start + MAX_READ_LINES - 1,
+                      start + MAX_READ_LINES - 1)
+        except (TypeError, ValueError):
+            return "error: start_line and end_line must be integers"
+        if start > end:
+            return f"error: {name} has {len(lines)} lines"
+        return '\n'.join(f"{number:>5} | {line}" for number, line in enumerate(lines[start - 1:end], start))
+
+    def list_symbols(self, path: Optional[str] = None) -> str:
+        if path:
+            name = self._resolve(path)
+            if name is None:
+                return f"error: no file {path!r} in the workspace"
+            names = [name]
+        else:
+            names = list(self.files)
+        rows = [f"{name}: {item['kind']} {item.get('qualified_name') or item['name']} "
+                f"(lines {item['start_line']}-{item['end_line']})"
+                for name in names for item in self.index(name).items]
+        return '\n'.join(rows) if rows else "no symbols found"
+
+    def call(self, name: str, arguments: Dict[str, Any]) -> str:
+        """도구 호출 하나 → 결과 문자열 (잘못된 호출도 오류 문자열로 돌려줘 모델이 고칠 수 있게)"""
+        if not isinstance(arguments, dict):
+            return "error: arguments must be an object"
+        try:
+            if name == 'grep':
+                return self.grep(str(arguments.get('pattern', '')), arguments.get('path'),
+                                 bool(arguments.get('ignore_case', True)))
+            if name == 'read_file':
+                return self.read_file(str(arguments.get('path', '')), arguments.get('start_line', 1),
+                                      arguments.get('end_line'))
+            if name == 'list_symbols':
+                return self.list_symbols(arguments.get('path'))
+        except Exception as e:
+            return f"error: {type(e).__name__}: {e}"
+        return f"error: unknown tool {name!r}"
+
+
+FINAL_REQUEST = "You have no tool calls left. Respond with the final analysis JSON now, in the format described above."
+
+
+def run_tools(client, build_prompt: Callable[[str], str], workspace: SampleWorkspace, settings: Dict[str, Any],
+              max_tokens: int, token: Optional[CancellationToken] = None) -> Tuple[Dict[str, Any], Dict[str, Any]]:
+    """도구 호출 라운드를 예산만큼 주고받은 뒤 최종 응답 (utils.interrogation.interrogate와 같은 반환 형식)"""
+    if not getattr(client, 'supports_tools', False):
+        raise RuntimeError(f"{getattr(client, 'model', 'client')}: 이 프로바이더 클라이언트는 함수 호출을 지원하지 않습니다 "
+                           f"(--tools는 openai/anthropic)")
+    budget = settings['max_turns']
+    prompt = build_prompt(workspace.overview()) + (
+        f"\n==== TOOLS ====\n\nYou may call the tools for up to {budget} round(s) before giving the final analysis "
+        f"JSON. Call at most {settings['max_questions']} tools per round.\n")
+    messages: List[Dict[str, Any]] = [{'role': 'user', 'content': prompt}]
+    turns: List[Dict[str, Any]] = []
+    responses: List[Dict[str, Any]] = []
+    answered = False
+
+    for turn in range(budget + 1):
+        if token:
+            token.raise_if_cancelled()
+        allow_tools = turn < budget
+        if not allow_tools and turn > 0:
+            messages.append({'role': 'user', 'content': FINAL_REQUEST})
+        response = client.benchmark_tool_request(messages, TOOLS, max_tokens, allow_tools)
+        responses.append(response)
+        record = {'turn': turn + 1, 'tool_calls': [], 'cost_usd': response.get('cost_usd', 0.0),
+                  'response_time': response.get('response_time', 0.0)}
+        turns.append(record)
+        if not response.get('success'):
+            break
+        calls = response.get('tool_calls') or []
+        if not calls or not allow_tools:
+            answered = not calls
+            break
+        # 라운드당 호출 수 상한을 넘은 호출에도 결과 메시지는 보내야 대화가 이어짐
+        messages.append({'role': 'assistant', 'content': response.get('content', ''), 'tool_calls': calls})
+        for position, call in enumerate(calls):
+            if position < settings['max_questions']:
+                result = workspace.call(call['name'], call.get('arguments') or {})[:settings['max_answer_chars']]
+            else:
+                result = f"error: at most {settings['max_questions']} tool calls per round"
+            record['tool_calls'].append({'id': call['id'], 'name': call['name'],
+                                         'arguments': call.get('arguments'), 'result': result})
+            messages.append({'role': 'tool', 'tool_call_id': call['id'], 'name': call['name'], 'content': result})
+
+    usage: Dict[str, Any] = {}
+    for item in responses:
+        for key, value in (item.get('usage') or {}).items():
+            if isinstance(value, (int, float)):
+                usage[key] = usage.get(key, 0) + value
+    final = dict(responses[-1])
+    final.update(response_time=sum(item.get('response_time', 0.0) for item in responses), usage=usage,
+                 cost_usd=sum(item.get('cost_usd', 0.0) for item in responses))
+    return final, {'budget': budget, 'protocol': 'tools', 'files': list(workspace.files),
+                   'turns_used': sum(1 for record in turns if record['tool_calls']), 'answered': answered,
+                   'turns': turns}
@@ -0,0 +1,296 @@
+#!/usr/bin/env python3
+"""
+Hugging Face datasets 형식 내보내기 (HF datasets export)
+
+ML 팀은 코퍼스를 저장소 배치(data/test_files, data/ground_truth)가 아니라 `datasets.load_dataset` 한 줄로 받기를
+원합니다. 샘플 본문, 라벨, 메타데이터를 분할별 Parquet 샤드로 쓰고, 허브가 읽는 YAML 머리말(configs, dataset_info)이
+달린 데이터셋 카드(README.md) 뼈대를 함께 만듭니다. 출력 디렉토리를 그대로 허브 데이터셋 저장소에 올리면 됩니다.
+
+출력:
+    <dir>/README.md                               데이터셋 카드 (YAML 머리말 + 요약/사용법/필드/라이선스/오염 주의/인용)
+    <dir>/data/<split>-00000-of-0000N.parquet     분할(train/test, utils/splits.py)별 샤드, 샤드당 최대 --max-shard-rows행
+
+열 (HF_COLUMNS, 순서와 타입 고정; 행은 id 순):
+    id, agent_type, test_id, file_name, language, text (샘플 본문, 디렉토리 샘플은 utils.manifest.render_sample),
+    labels / quantum_safe_labels / families / categories (문자열 목록), difficulty, task, split, tags,
+    provenance, license, fidelity, ground_truth (ground truth JSON 원문), ground_truth_hash, sample_sha256
+
+    text에는 생성 샘플의 카나리 줄(utils/canary.py)이 그대로 남습니다. 공개 데이터셋이 학습 데이터에 섞였는지
+    확인하려고 심은 것이므로, 카드의 오염 주의 절에도 적습니다.
+
+라이선스: 코퍼스 배포 아카이브(utils/corpus_package.py)와 같이 재배포할 수 없는 라이선스의 샘플은 빼고,
+    카드의 license는 모든 샘플이 같은 라이선스면 그 식별자, 아니면 other와 라이선스 목록입니다.
+
+Parquet은 pyarrow가 있어야 씁니다 (pip install "qvbench[parquet]", utils/results_table.py와 같음).
+
+사용법:
+    python -m utils.hf_export --output-dir results/hf_dataset
+    python -m utils.hf_export --output-dir results/hf_rust --filter "lang==rust" --repo-id <org>/qvbench-rust
+    python -c "import datasets; print(datasets.load_dataset('results/hf_dataset'))"
+"""
+
+import argparse
+import json
+import sys
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+import yaml
+
+from utils.corpus import GROUND_TRUTH_DIR, TEST_FILES_DIR
+from utils.corpus_package import CORPUS_LICENSE, sample_license
+from utils.fidelity import sample_fidelity
+from utils.incremental import HASH_FIELDS, manifest_version
+from utils.languages import sample_language
+from utils.manifest import render_sample
+from utils.metrics_calculator import MetricsCalculator
+from utils.provenance import is_redistributable, sample_provenance
+from utils.sample_filter import SampleFilter, sample_fields
+from utils.splits import SPLITS, sample_split
+from utils.tasks import sample_task
+
+HF_OUTPUT_DIR = "results/hf_dataset"
+MAX_SHARD_ROWS = 1000
+PRETTY_NAME = "qvbench: quantum-vulnerable cryptography detection corpus"
+
+# (열 이름, 타입) — 타입: string / list (문자열 목록)
+HF_COLUMNS: List[Tuple[str, str]] = [
+    ('id', 'string'), ('agent_type', 'string'), ('test_id', 'string'), ('file_name', 'string'),
+    ('language', 'string'), ('text', 'string'), ('labels', 'list'), ('quantum_safe_labels', 'list'),
+    ('families', 'list'), ('categories', 'list'), ('difficulty', 'string'), ('task', 'string'),
+    ('split', 'string'), ('tags', 'list'), ('provenance', 'string'), ('license', 'string'),
+    ('fidelity', 'string'), ('ground_truth', 'string'), ('ground_truth_hash', 'string'),
+    ('sample_sha256', 'string'),
+]
+
+COLUMN_DESCRIPTIONS = {
+    'id': '`<agent_type>/<test_id>`, unique',
+    'test_id': 'sample name, unique within an agent type',
+    'agent_type': 'input kind: source_code, assembly_binary, logs_config, dependency_manifest, code_diff',
+    'file_name': 'original file (or directory) name under `test_files/<agent_type>/`',
+    'language': 'language from the file extension (`manifest` for dependency-only samples)',
+    'text': 'sample content; directory samples are concatenated with `==== <path> ====` headers',
+    'labels': 'expected quantum-vulnerable algorithm labels (taxonomy names)',
+    'quantum_safe_labels': 'post-quantum algorithms present in the sample (must not be reported as vulnerable)',
+    'families': 'taxonomy families of all labels',
+    'categories': 'taxonomy categories of all labels (shor_vulnerable, grover_vulnerable, post_quantum)',
+    'difficulty': 'easy / medium / hard',
+    'task': 'task type (detection, misuse-detection, manifest-inference, diff-introduction, ...)',
+    'split': 'train (few-shot / reference pool) or test (evaluation); near-duplicates share a split',
+    'tags': 'free-form tags, `key=value` tags carry attributes such as `domain=medical`',
+    'provenance': 'hand-written, generated-from-template or adapted-from-oss',
+    'license': 'SPDX license expression of the sample',
+    'fidelity': 'how faithfully the sample implements its labels (exact / stylized / ...)',
+    'ground_truth': 'full ground-truth JSON (locations, weaknesses, rationale evidence, ...)',
+    'ground_truth_hash': 'hash of the ground truth used to score results',
+    'sample_sha256': 'SHA-256 of the sample file(s)',
+}
+
+
+def _shard_names(split: str, count: int) -> List[str]:
+    return [f"{split}-{index:05d}-of-{count:05d}.parquet" for index in range(count)]
+
+
+class HFDatasetExporter:
+    """코퍼스 → 분할별 Parquet 샤드 + 데이터셋 카드"""
+
+    def __init__(self, ground_truth_dir: str = GROUND_TRUTH_DIR, test_files_dir: str = TEST_FILES_DIR):
+        self.ground_truth_dir = ground_truth_dir
+        self.test_files_dir = Path(test_files_dir)
+
+    def rows(self, agents: Optional[List[str]] = None,
+             sample_filter: Optional[SampleFilter] = None) -> Tuple[List[Dict[str, Any]], List[str]]:
+        """(행 목록, 라이선스 때문에 뺀 샘플 id)"""
+        from utils.submission import corpus_manifest
+
+        rows, excluded = [], []
+        for (agent_type, test_id), entry in sorted(corpus_manifest(self.ground_truth_dir,
+                                                                   str(self.test_files_dir)).items()):
+            sample, ground_truth = entry['sample'], entry['ground_truth']
+            if sample is None or (agents and agent_type not in agents):
+                continue
+            if sample_filter and not sample_filter.matches_sample(agent_type, test_id, ground_truth, str(sample)):
+                continue
+            provenance = sample_provenance(ground_truth)
+            expression = sample_license(provenance)
+            if not expression or not is_redistributable(expression):
+                excluded.append(f"{agent_type}/{test_id}")
+                continue
+
+            fields = sample_fields(agent_type, test_id, ground_truth, str(sample))
+            rows.append({
+                'id': f"{agent_type}/{test_id}",
+                'agent_type': agent_type,
+                'test_id': test_id,
+                'file_name': sample.name,
+                'language': sample_language(str(sample)),
+                'text': render_sample(sample) if sample.is_dir() else sample.read_text(encoding='utf-8',
+                                                                                        errors='replace'),
+                'labels': MetricsCalculator.get_expected_labels(ground_truth),
+                'quantum_safe_labels': MetricsCalculator.get_quantum_safe_labels(ground_truth),
+                'families': fields['family'],
+                'categories': fields['category'],
+                'difficulty': str(ground_truth.get('difficulty', 'medium')),
+                'task': sample_task(ground_truth),
+                'split': sample_split(agent_type, test_id, ground_truth),
+                'tags': [tag for tag in ground_truth.get('tags') or [] if isinstance(tag, str)],
+                'provenance': provenance['kind'],
+                'license': expression,
+                'fidelity': sample_fidelity(ground_truth),
+                'ground_truth': json.dumps(ground_truth, ensure_ascii=False, sort_keys=True),
+                **{field: entry[field] for field in HASH_FIELDS},
+            })
+        return rows, excluded
+
+    @staticmethod
+    def write_shards(rows: List[Dict[str, Any]], data_dir: Path,
+                     max_shard_rows: int = MAX_SHARD_ROWS) -> Dict[str, List[str]]:
+        """분할별 Parquet 샤드 쓰기 → {분할: [파일 이름]}"""
+        try:
+            import pyarrow as pa
+            import pyarrow.parquet as pq
+        except ImportError:
+            raise RuntimeError("Parquet 내보내기에는 pyarrow가 필요합니다 (pip install \"qvbench[parquet]\")")
+        types = {'string': pa.string(), 'list': pa.list_(pa.string())}
+        schema = pa.schema([(name, types[kind]) for name, kind in HF_COLUMNS])
+
+        data_dir.mkdir(parents=True, exist_ok=True)
+        for stale in data_dir.glob('*.parquet'):
+            stale.unlink()
+        shards: Dict[str, List[str]] = {}
+        for split in SPLITS:
+            split_rows = [row for row in rows if row['split'] == split]
+            if not split_rows:
+                continue
+            count = -(-len(split_rows) // max_shard_rows)
+            shards[split] = _shard_names(split, count)
+            for index, name in enumerate(shards[split]):
+                chunk = split_rows[index * max_shard_rows:(index + 1) * max_shard_rows]
+                pq.write_table(pa.Table.from_pylist(chunk, schema=schema), data_dir / name)
+        return shards
+
+    @staticmethod
+    def card(rows: List[Dict[str, Any]], shards: Dict[str, List[str]], excluded: List[str],
+             sample_filter: Optional[SampleFilter] = None, repo_id: Optional[str] = None) -> str:
+        """데이터셋 카드 (허브가 읽는 YAML 머리말 + 채울 절 뼈대)"""
+        licenses = sorted({row['license'] for row in rows})
+        hashes: Dict[str, Dict[str, Dict[str, Any]]] = {}
+        for row in rows:
+            hashes.setdefault(row['agent_type'], {})[row['test_id']] = {field: row[field] for field in HASH_FIELDS}
+        counts = {split: sum(1 for row in rows if row['split'] == split) for split in shards}
+        size = len(rows)
+        size_category = 'n<1K' if size < 1000 else '1K<n<10K' if size < 10000 else '10K<n<100K'
+
+        header = {
+            'pretty_name': PRETTY_NAME,
+            'license': licenses[0].lower() if len(licenses) == 1 else 'other',
+            'language': ['en', 'ko', 'code'],
+            'task_categories': ['text-classification'],
+            'tags': ['cryptography', 'post-quantum', 'security', 'code', 'benchmark'],
+            'size_categories': [size_category],
+            'configs': [{'config_name': 'default', 'data_files': [
+                {'split': split, 'path': f"data/{split}-*.parquet"} for split in shards]}],
+            'dataset_info': {
+                'features': [{'name': name, 'sequence': 'string'} if kind == 'list' else {'name': name, 'dtype': kind}
+                             for name, kind in HF_COLUMNS],
+                'splits': [{'name': split, 'num_examples': counts[split]} for split in shards],
+            },
+        }
+        if len(licenses) > 1:
+            header['license_name'] = ' / '.join(licenses)
+
+        agents: Dict[str, int] = {}
+        for row in rows:
+            agents[row['agent_type']] = agents.get(row['agent_type'], 0) + 1
+        source = repo_id or '<path or org/name>'
+        version = manifest_version(hashes)
+        # 인용 항목: 코퍼스 버전으로 어느 스냅샷인지 고정 (허브 이름을 주면 데이터셋 주소도)
+        citation = ['@misc{qvbench,', f"  title = {{{PRETTY_NAME}}},", f"  note = {{Corpus version {version}}},"]
+        if repo_id:
+            citation.append(f"  howpublished = {{\\url{{https://huggingface.co/datasets/{repo_id}}}}},")
+        citation.append('}')
+        lines = [
+            '---', yaml.safe_dump(header, sort_keys=False, allow_unicode=True).rstrip(), '---', '',
+            f"# {PRETTY_NAME}", '',
+            '## Dataset summary', '',
+            'Code, binaries (disassembly), logs/configs, dependency manifests and diffs labelled with the classical',
+            'cryptography they implement, for evaluating detectors that find quantum-vulnerable algorithms ahead of a',
+            'post-quantum migration.', '',
+            f"- corpus version: `{version}`",
+            f"- samples: {size} ({', '.join(f'{name} {count}' for name, count in sorted(agents.items()))})",
+            f"- splits: {', '.join(f'{split} {counts[split]}' for split in shards)}",
+        ]
+        if sample_filter:
+            lines.append(f"- filter: `{sample_filter}`")
+        if excluded:
+            lines.append(f"- excluded for licensing: {len(excluded)}")
+        lines += [
+            '', '## Usage', '', '```python', 'from datasets import load_dataset', '',
+            f'ds = load_dataset("{source}")',
+            'test = ds["test"].filter(lambda row: row["language"] == "Rust")', '```', '',
+            'Score predictions with the benchmark scorer (hierarchical F1 over the taxonomy) rather than exact',
+            'string match; the rules are published as `docs/scoring_rubric.json` in the benchmark repository.', '',
+            '## Fields', '', '| column | type | description |', '|---|---|---|',
+        ]
+        lines += [f"| `{name}` | {'list[string]' if kind == 'list' else kind} | {COLUMN_DESCRIPTIONS.get(name, '')} |"
+                  for name, kind in HF_COLUMNS]
+        lines += [
+            '', '## Splits', '',
+            '`train` samples may be used as few-shot examples or references; report results on `test` only.',
+            'Near-duplicate samples are clustered and always share a split.', '',
+            '## Licensing', '',
+            f"Samples are released under {', '.join(licenses)}. Hand-written and generated samples follow the "
+            f"corpus license ({CORPUS_LICENSE}); adapted open-source samples keep their original license "
+            '(`license` column).', '',
+            '## Contamination canaries', '',
+            'Generated samples end with a `QVBENCH-CANARY` comment. Please do not train on this dataset; if you do,',
+            'the canaries let evaluators detect it.', '',
+            '## Citation', '', '```bibtex', *citation, '```', '',
+        ]
+        return '\n'.join(lines)
+
+    def export(self, output_dir: str = HF_OUTPUT_DIR, agents: Optional[List[str]] = None,
+               sample_filter: Optional[SampleFilter] = None, max_shard_rows: int = MAX_SHARD_ROWS,
+               repo_id: Optional[str] = None) -> Dict[str, Any]:
+        rows, excluded = self.rows(agents, sample_filter)
+        if not rows:
+            raise ValueError("no samples to export")
+        output = Path(output_dir)
+        shards = self.write_shards(rows, output / 'data', max_shard_rows)
+        (output / 'README.md').write_text(self.card(rows, shards, excluded, sample_filter, repo_id),
+                                          encoding='utf-8')
+        return {'rows': len(rows), 'shards': shards, 'excluded': excluded, 'output_dir': str(output)}
+
+
+def main():
+    parser = argparse.ArgumentParser(description='코퍼스를 Hugging Face datasets 형식(Parquet + 데이터셋 카드)으로 내보내기')
+    parser.add_argument('--output-dir', default=HF_OUTPUT_DIR, help=f'출력 디렉토리 (기본: {HF_OUTPUT_DIR})')
+    parser.add_argument('--agents', nargs='+', help='이 에이전트의 샘플만 (기본: 전체)')
+    parser.add_argument('--filter', metavar='EXPR', help='필터 식에 맞는 샘플만 (utils/sample_filter.py)')
+    parser.add_argument('--max-shard-rows', type=int, default=MAX_SHARD_ROWS, help='Parquet 샤드당 최대 행 수')
+    parser.add_argument('--repo-id', help='허브 데이터셋 이름 <org>/<name> (카드의 사용 예시에만 씀)')
+    parser.add_argument('--test-files-dir', default=TEST_FILES_DIR)
+    parser.add_argument('--ground-truth-dir', default=GROUND_TRUTH_DIR)
+    args = parser.parse_args()
+
+    if args.max_shard_rows < 1:
+        parser.error('--max-shard-rows는 1 이상이어야 합니다')
+    try:
+        sample_filter = SampleFilter(args.filter) if args.filter else None
+        exporter = HFDatasetExporter(args.ground_truth_dir, args.test_files_dir)
+        report = exporter.export(args.output_dir, args.agents, sample_filter, args.max_shard_rows, args.repo_id)
+    except ValueError as e:
+        parser.error(str(e))
+    except RuntimeError as e:
+        print(f"❌ {e}")
+        sys.exit(2)
+
+    for split, names in report['shards'].items():
+        print(f"💾 {split}: 샤드 {len(names)}개 → {report['output_dir']}/data/{names[0]} …")
+    if report['excluded']:
+        print(f"⚠️  라이선스 때문에 제외한 샘플 {len(report['excluded'])}개: {', '.join(report['excluded'][:5])}")
+    print(f"📝 데이터셋 카드: {report['output_dir']}/README.md (샘플 {report['rows']}개)")
+
+
+if __name__ == '__main__':
+    main()
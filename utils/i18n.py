@@ -0,0 +1,236 @@
+"""
+보고서 문자열 카탈로그와 브랜딩 (ko-KR / en-US)
+
+HTML 탐지 보고서와 GitHub 점수 카드(utils/report_export.py), 리더보드(utils/leaderboard.py)의 제목, 표 머리글, 문장을
+언어별 카탈로그(CATALOGS)에서 가져옵니다. 기본 언어는 ko-KR이며 기본 카탈로그의 문자열은 지금까지의 출력과 같습니다 (골든 스냅샷 유지).
+카탈로그에 없는 키는 ko-KR 문자열로, 그것도 없으면 호출한 쪽의 default로 대신합니다.
+
+브랜딩 (config/benchmark.toml의 [report], 모두 선택):
+    lang          기본 언어 (--lang이 우선)
+    organization  제목 앞에 붙는 조직 이름
+    logo          제목 옆 로고 이미지 URL/경로
+    footer        본문 끝 문구 (배포 범위, 문의처 …)
+    각 값은 문자열 또는 언어별 표({ko-KR = "...", en-US = "..."})
+
+사용법:
+    python -m utils.report_export results/detector_ast.json --format html --lang en-US
+    python -m utils.leaderboard render --format html --lang en-US --branding config/benchmark.toml
+"""
+
+import html
+import tomllib
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+BRANDING_PATH = "config/benchmark.toml"
+
+DEFAULT_LANG = 'ko-KR'
+LANGS = ['ko-KR', 'en-US']
+
+CATALOGS: Dict[str, Dict[str, str]] = {
+    'ko-KR': {
+        'report.title': '탐지 보고서',
+        'report.summary': '샘플 {samples}개, 탐지 {findings}건',
+        'report.col.sample': '샘플',
+        'report.col.location': '위치',
+        'report.col.subject': '알고리즘 / 약점',
+        'report.col.category': '범주',
+        'report.col.confidence': '신뢰도',
+        'calibration.title': '신뢰도 보정',
+        'calibration.summary': '예측 {predictions}개 — ECE {ece}, MCE {mce}, Brier {brier}, 평균 정밀도 {ap}, '
+                               'F1 최대 임계값 ≥ {threshold} (P {precision} / R {recall} / F1 {f1})',
+        'calibration.reliability': '신뢰도 다이어그램',
+        'calibration.pr': '임계값별 PR 곡선',
+        'calibration.axis.confidence': '신뢰도',
+        'calibration.axis.accuracy': '정답률',
+        'compliance.title': '준수 현황: {profile} (기준 {as_of})',
+        'compliance.standing': '판정 <strong>{standing}</strong> — 샘플 non-compliant {non_compliant}, '
+                               'transition {transition}, compliant {compliant}',
+        'compliance.col.status': '상태',
+        'compliance.col.subject': '대상',
+        'compliance.col.source': '근거',
+        'compliance.col.note': '비고',
+        'compliance.col.samples': '샘플',
+        'compliance.upcoming': '{date}부터 {status}: {subjects}',
+        'github.title': 'qvbench 점수 카드',
+        'github.versus': '기준 `{baseline}` → 이번 `{current}`',
+        'github.no_baseline': '`{current}` (기준 실행 없음)',
+        'github.col.detector': '탐지기',
+        'github.col.samples': '샘플',
+        'github.col.errors': '오류',
+        'github.col.abstained': '판단 보류',
+        'github.col.class': '클래스',
+        'github.col.labels': '라벨',
+        'github.col.detection': '탐지율',
+        'github.details': '{detector} 상세',
+        'github.paired': '공통 샘플 {paired}개로 비교 (기준에만 {only_old}개, 이번에만 {only_new}개)',
+        'github.selective': '🤷 답한 샘플 비율(coverage) {coverage}, 답한 샘플 계층 F1 {f1}, AURC {aurc}',
+        'github.newly_failed': '❌ 새로 실패 {count}개',
+        'github.fixed': '✅ 새로 성공 {count}개',
+        'github.more': ' 외 {count}개',
+        'github.hidden_classes': '변화 없거나 표에 넣지 않은 클래스 {count}개',
+        'github.legend': '괄호는 공통 샘플 기준 변화, ✱는 부트스트랩 95% 신뢰구간이 0을 포함하지 않는 변화. 오류 행은 0점',
+        'leaderboard.title': '리더보드',
+        'leaderboard.signature': '서명',
+        'leaderboard.corpus': '코퍼스 {version}',
+        'leaderboard.col.rank': '순위',
+        'leaderboard.col.detector': '탐지기',
+        'leaderboard.col.version': '버전',
+        'leaderboard.col.composite': '종합 점수 (트랙 분해)',
+        'leaderboard.col.tests': '테스트',
+        'leaderboard.col.date': '날짜',
+        'metric.accuracy': '정확도',
+        'metric.hierarchical_f1': '계층 F1',
+        'metric.success_rate': '성공률',
+        'track.identification': '식별',
+        'track.localization': '위치',
+        'track.misuse': '오용',
+        'track.quantum_classification': '양자 내성 분류',
+        'track.korean_compliance': '한국 암호',
+    },
+    'en-US': {
+        'report.title': 'Detection Report',
+        'report.summary': '{samples} samples, {findings} findings',
+        'report.col.sample': 'Sample',
+        'report.col.location': 'Location',
+        'report.col.subject': 'Algorithm / Weakness',
+        'report.col.category': 'Category',
+        'report.col.confidence': 'Confidence',
+        'calibration.title': 'Confidence calibration',
+        'calibration.summary': '{predictions} predictions — ECE {ece}, MCE {mce}, Brier {brier}, '
+                               'average precision {ap}, best-F1 threshold ≥ {threshold} '
+                               '(P {precision} / R {recall} / F1 {f1})',
+        'calibration.reliability': 'Reliability diagram',
+        'calibration.pr': 'Precision-recall by threshold',
+        'calibration.axis.confidence': 'confidence',
+        'calibration.axis.accuracy': 'accuracy',
+        'compliance.title': 'Compliance: {profile} (as of {as_of})',
+        'compliance.standing': 'Standing <strong>{standing}</strong> — samples non-compliant {non_compliant}, '
+                               'transition {transition}, compliant {compliant}',
+        'compliance.col.status': 'Status',
+        'compliance.col.subject': 'Subject',
+        'compliance.col.source': 'Source',
+        'compliance.col.note': 'Note',
+        'compliance.col.samples': 'Samples',
+        'compliance.upcoming': 'From {date}, {status}: {subjects}',
+        'compliance.tdea': '3-key TDEA encryption through 2023-12-31, decryption only afterwards (legacy use)',
+        'compliance.des': 'Single DES',
+        'compliance.sha1-signature': 'SHA-1 for digital signature generation',
+        'compliance.sha1': 'Disallowed for all uses after 2030-12-31',
+        'compliance.rsa-below-2048': 'Security strength below 112 bits',
+        'compliance.dh-below-2048': 'Security strength below 112 bits',
+        'compliance.dsa': 'DSA signature generation removed, verification only (legacy use)',
+        'compliance.rsa-2048': '112-bit security strength',
+        'compliance.quantum-public-key': 'Quantum-vulnerable public key',
+        'compliance.not-approved:algorithm': 'Family not covered by {profile}',
+        'compliance.not-approved:mode': 'Mode of operation not approved',
+        'github.title': 'qvbench score card',
+        'github.versus': '`{current}` vs baseline `{baseline}`',
+        'github.no_baseline': '`{current}` (no baseline run)',
+        'github.col.detector': 'Detector',
+        'github.col.samples': 'Samples',
+        'github.col.errors': 'Errors',
+        'github.col.abstained': 'Abstained',
+        'github.col.class': 'Class',
+        'github.col.labels': 'Labels',
+        'github.col.detection': 'Detection rate',
+        'github.details': '{detector} details',
+        'github.paired': 'Compared on {paired} shared samples ({only_old} only in baseline, '
+                         '{only_new} only in this run)',
+        'github.selective': '🤷 Coverage (answered share) {coverage}, hierarchical F1 on answered samples {f1}, '
+                            'AURC {aurc}',
+        'github.newly_failed': '❌ Newly failing {count}',
+        'github.fixed': '✅ Newly passing {count}',
+        'github.more': ' and {count} more',
+        'github.hidden_classes': '{count} classes unchanged or not shown',
+        'github.legend': 'Parentheses show the change on shared samples; ✱ marks changes whose bootstrap 95% CI '
+                         'excludes 0. Error rows score 0',
+        'leaderboard.title': 'Leaderboard',
+        'leaderboard.signature': 'Signature',
+        'leaderboard.corpus': 'Corpus {version}',
+        'leaderboard.col.rank': 'Rank',
+        'leaderboard.col.detector': 'Detector',
+        'leaderboard.col.version': 'Version',
+        'leaderboard.col.composite': 'Composite (track breakdown)',
+        'leaderboard.col.tests': 'Tests',
+        'leaderboard.col.date': 'Date',
+        'metric.accuracy': 'Accuracy',
+        'metric.hierarchical_f1': 'Hierarchical F1',
+        'metric.success_rate': 'Success rate',
+        'track.identification': 'Identification',
+        'track.localization': 'Localization',
+        'track.misuse': 'Misuse',
+        'track.quantum_classification': 'Quantum classification',
+        'track.korean_compliance': 'K-crypto',
+    },
+}
+
+
+class Catalog:
+    """언어 하나의 문자열 카탈로그 (빠진 키는 ko-KR → default 순으로 대신)"""
+
+    def __init__(self, lang: str = DEFAULT_LANG):
+        if lang not in CATALOGS:
+            raise ValueError(f"지원하지 않는 언어: {lang} (지원: {', '.join(LANGS)})")
+        self.lang = lang
+
+    @property
+    def html_lang(self) -> str:
+        """<html lang>에 쓰는 언어 부분 (ko-KR → ko)"""
+        return self.lang.split('-')[0]
+
+    def text(self, key: str, default: Optional[str] = None, **values: Any) -> str:
+        template = CATALOGS[self.lang].get(key) or CATALOGS[DEFAULT_LANG].get(key) or default
+        if template is None:
+            raise KeyError(f"카탈로그에 없는 문자열: {key}")
+        return template.format(**values) if values else template
+
+
+class Branding:
+    """보고서 브랜딩 ([report] 표의 값, 언어별 표면 그 언어 → ko-KR 순)"""
+
+    def __init__(self, settings: Optional[Dict[str, Any]] = None):
+        self.settings = settings or {}
+
+    @property
+    def lang(self) -> str:
+        return self.settings.get('lang') or DEFAULT_LANG
+
+    def value(self, key: str, lang: str) -> Optional[str]:
+        value = self.settings.get(key)
+        if isinstance(value, dict):
+            value = value.get(lang) or value.get(DEFAULT_LANG)
+        return value or None
+
+
+def html_header(title: str, icon: str, style: str, catalog: Catalog,
+                branding: Optional[Branding] = None) -> List[str]:
+    """HTML 보고서 첫 부분 (<head>부터 <h1>까지, 브랜딩이 있으면 조직 이름과 로고)"""
+    branding = branding or Branding()
+    organization, logo = branding.value('organization', catalog.lang), branding.value('logo', catalog.lang)
+    if organization:
+        title = f"{organization} {title}"
+    heading = f"{icon} {html.escape(title)}"
+    if logo:
+        heading = f"<img src=\"{html.escape(logo)}\" alt=\"{html.escape(organization or '')}\" height=\"32\"> {heading}"
+    return ['<!DOCTYPE html>', f'<html lang="{catalog.html_lang}">',
+            f'<head><meta charset="utf-8"><title>{html.escape(title)}</title>', style, '<body>', f'<h1>{heading}</h1>']
+
+
+def html_footer(catalog: Catalog, branding: Optional[Branding] = None) -> List[str]:
+    """HTML 보고서 끝 부분 (브랜딩 footer가 있으면 그 문구)"""
+    footer = (branding or Branding()).value('footer', catalog.lang)
+    return ([f'<footer><p>{html.escape(footer)}</p></footer>'] if footer else []) + ['</body>', '</html>', '']
+
+
+def load_branding(path: Optional[str] = BRANDING_PATH) -> Branding:
+    """설정 파일의 [report] 표 → Branding (파일이나 표가 없으면 브랜딩 없음)"""
+    if not path or not Path(path).exists():
+        return Branding()
+    with open(path, 'rb') as f:
+        settings = tomllib.load(f).get('report', {})
+    if not isinstance(settings, dict):
+        raise ValueError(f"{path}: [report]가 표가 아닙니다")
+    if settings.get('lang') and settings['lang'] not in CATALOGS:
+        raise ValueError(f"{path}: 지원하지 않는 언어 {settings['lang']} (지원: {', '.join(LANGS)})")
+    return Branding(settings)
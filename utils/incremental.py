@@ -0,0 +1,161 @@
+#!/usr/bin/env python3
+"""
+변경된 샘플만 평가 (incremental evaluation)
+
+코퍼스는 매일 조금씩 늘어나는데, 비싼 모델로 매번 전체를 다시 돌릴 필요는 없습니다. 이전 실행 시점의 코퍼스
+매니페스트(샘플/ground truth 해시)와 현재 매니페스트를 비교해 추가·수정된 샘플만 평가하고, 나머지는 이전 결과를
+그대로 가져와 합칩니다.
+
+매니페스트: {"corpus_version": ..., "samples": {<agent_type>: {<test_id>: {"ground_truth_hash", "sample_sha256"}}}}
+    해시는 utils.submission.corpus_manifest와 같습니다 (ground truth는 키 순서 무관, 디렉토리 샘플은 파일 전체).
+    benchmark_runner.py 결과의 metadata.corpus_manifest에도 실행 시점의 매니페스트가 남으므로,
+    이전 결과 파일을 그대로 --changed-since에 넘길 수 있습니다.
+
+비교 (diff_manifests):
+    added       이전에 없던 샘플
+    modified    샘플 본문이나 ground truth 해시가 달라진 샘플
+    removed     지금은 없는 샘플 (이전 결과에서도 뺌)
+    unchanged   그대로인 샘플 (이전 결과 재사용)
+
+합치기 (merge_results): 이전 결과 중 unchanged 샘플의 행만 남기고, 이번 실행이 같은 (샘플, 프로바이더, 모델)을
+    다시 평가했으면 새 행을 씁니다. 수정된 샘플의 이전 행은 다른 모델 것이라도 옛 라벨로 채점됐으므로 버립니다.
+
+사용법:
+    python -m utils.incremental snapshot --output results/corpus_manifest.json
+    python -m utils.incremental diff results/corpus_manifest.json
+    python benchmark_runner.py --providers ollama --changed-since results/benchmark_results_1735689600.json
+    python benchmark_runner.py --changed-since results/corpus_manifest.json --merge-results results/nightly.json
+"""
+
+import argparse
+import hashlib
+import json
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+from utils.corpus import GROUND_TRUTH_DIR, TEST_FILES_DIR
+
+CHANGE_KINDS = ['added', 'modified', 'removed', 'unchanged']
+HASH_FIELDS = ['ground_truth_hash', 'sample_sha256']
+
+
+def snapshot(ground_truth_dir: str = GROUND_TRUTH_DIR, test_files_dir: str = TEST_FILES_DIR) -> Dict[str, Any]:
+    """현재 코퍼스 매니페스트"""
+    from utils.submission import corpus_manifest
+
+    samples: Dict[str, Dict[str, Dict[str, Any]]] = {}
+    for (agent_type, test_id), entry in corpus_manifest(ground_truth_dir, test_files_dir).items():
+        samples.setdefault(agent_type, {})[test_id] = {field: entry[field] for field in HASH_FIELDS}
+    return {'corpus_version': manifest_version(samples), 'samples': samples}
+
+
+def manifest_version(samples: Dict[str, Dict[str, Dict[str, Any]]]) -> str:
+    """매니페스트 전체 해시 (샘플 본문까지 포함, 결과 기준의 utils.results_store.corpus_version과는 다름)"""
+    canonical = json.dumps(samples, sort_keys=True, ensure_ascii=False)
+    return hashlib.sha256(canonical.encode('utf-8')).hexdigest()[:12]
+
+
+def load_manifest(path: str) -> Tuple[Dict[str, Any], Optional[List[Dict[str, Any]]]]:
+    """매니페스트 파일 또는 이전 결과 파일 → (매니페스트, 이전 결과 행 또는 None)"""
+    with open(path, 'r', encoding='utf-8') as f:
+        document = json.load(f)
+    if not isinstance(document, dict):
+        raise ValueError(f"매니페스트 형식이 아닙니다: {path}")
+    if 'samples' in document:
+        return document, None
+    manifest = document.get('metadata', {}).get('corpus_manifest')
+    if not manifest:
+        raise ValueError(f"결과 파일에 metadata.corpus_manifest가 없습니다 (이 기능 이전의 결과): {path}")
+    return manifest, document.get('detailed_results', [])
+
+
+def load_results(path: str) -> List[Dict[str, Any]]:
+    with open(path, 'r', encoding='utf-8') as f:
+        return json.load(f).get('detailed_results', [])
+
+
+def diff_manifests(previous: Dict[str, Any], current: Dict[str, Any],
+                   agents: Optional[List[str]] = None) -> Dict[str, List[Tuple[str, str]]]:
+    """두 매니페스트의 샘플 비교 → {'added', 'modified', 'removed', 'unchanged': [(agent_type, test_id)]}"""
+    def entries(manifest):
+        return {(agent_type, test_id): entry for agent_type, tests in manifest.get('samples', {}).items()
+                for test_id, entry in tests.items() if agents is None or agent_type in agents}
+
+    before, after = entries(previous), entries(current)
+    changes: Dict[str, List[Tuple[str, str]]] = {kind: [] for kind in CHANGE_KINDS}
+    for key in sorted(set(before) | set(after)):
+        if key not in before:
+            changes['added'].append(key)
+        elif key not in after:
+            changes['removed'].append(key)
+        elif any(before[key].get(field) != after[key].get(field) for field in HASH_FIELDS):
+            changes['modified'].append(key)
+        else:
+            changes['unchanged'].append(key)
+    return changes
+
+
+def changed_samples(changes: Dict[str, List[Tuple[str, str]]], agent_type: str) -> List[str]:
+    """평가할 (추가·수정된) 샘플의 test_id"""
+    return [test_id for kind in ('added', 'modified') for agent, test_id in changes[kind] if agent == agent_type]
+
+
+def merge_results(prior: List[Dict[str, Any]], results: List[Dict[str, Any]],
+                  changes: Dict[str, List[Tuple[str, str]]]) -> List[Dict[str, Any]]:
+    """이전 결과(그대로인 샘플만) + 이번 결과 (같은 샘플·프로바이더·모델이면 이번 결과)"""
+    def key(result):
+        return (result.get('agent_type'), result.get('test_id'), result.get('provider'), result.get('model'))
+
+    unchanged = set(changes['unchanged'])
+    evaluated = {key(result) for result in results}
+    kept = [result for result in prior
+            if (result.get('agent_type'), result.get('test_id')) in unchanged and key(result) not in evaluated]
+    return kept + results
+
+
+def print_changes(changes: Dict[str, List[Tuple[str, str]]], limit: int = 10):
+    print(f"🔁 코퍼스 변경: 추가 {len(changes['added'])}, 수정 {len(changes['modified'])}, "
+          f"삭제 {len(changes['removed'])}, 그대로 {len(changes['unchanged'])}")
+    for kind, icon in (('added', '➕'), ('modified', '✏️ '), ('removed', '➖')):
+        for agent_type, test_id in changes[kind][:limit]:
+            print(f"  {icon} {agent_type}/{test_id}")
+        if len(changes[kind]) > limit:
+            print(f"     ... 외 {len(changes[kind]) - limit}개")
+
+
+def main():
+    parser = argparse.ArgumentParser(description='변경된 샘플만 평가하기 위한 코퍼스 매니페스트')
+    subparsers = parser.add_subparsers(dest='command', required=True)
+
+    create = subparsers.add_parser('snapshot', help='현재 코퍼스 매니페스트 저장')
+    create.add_argument('--output', default='results/corpus_manifest.json')
+    create.add_argument('--test-files-dir', default=TEST_FILES_DIR)
+    create.add_argument('--ground-truth-dir', default=GROUND_TRUTH_DIR)
+
+    diff = subparsers.add_parser('diff', help='이전 매니페스트(또는 결과 파일)와 현재 코퍼스 비교')
+    diff.add_argument('manifest', help='매니페스트 파일 또는 metadata.corpus_manifest가 있는 결과 파일')
+    diff.add_argument('--agents', nargs='+', help='이 에이전트의 샘플만 비교')
+    diff.add_argument('--test-files-dir', default=TEST_FILES_DIR)
+    diff.add_argument('--ground-truth-dir', default=GROUND_TRUTH_DIR)
+
+    args = parser.parse_args()
+    current = snapshot(args.ground_truth_dir, args.test_files_dir)
+
+    if args.command == 'snapshot':
+        path = Path(args.output)
+        path.parent.mkdir(parents=True, exist_ok=True)
+        with open(path, 'w', encoding='utf-8') as f:
+            json.dump(current, f, indent=2, ensure_ascii=False)
+        total = sum(len(tests) for tests in current['samples'].values())
+        print(f"💾 매니페스트 저장: {path} (샘플 {total}개, 버전 {current['corpus_version']})")
+        return
+
+    try:
+        previous, _ = load_manifest(args.manifest)
+    except ValueError as e:
+        parser.error(str(e))
+    print_changes(diff_manifests(previous, current, args.agents))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,308 @@
+"""
+다중 턴 질의 프로토콜 (interrogation)
+
+한 번에 분류하는 대신, 탐지기(LLM)가 파일 개요만 보고 필요한 부분을 하네스에 되물어 가며 분석하는 에이전트 모드입니다
+(--interrogate N). 단발 분류가 아니라 "substitution_table을 정의한 함수를 보여 달라"처럼 코드를 탐색하는 능력을 봅니다.
+
+    첫 질의   파일 개요(SampleIndex 함수 목록과 줄 범위) + 앞부분 preview_lines줄만 보여 줌
+    질문      모델이 분석 대신 {"questions": [...]} JSON으로 답하면 하네스가 샘플에서 답을 찾아 붙이고 다시 질의
+              show_function {name}        이름(또는 소속 경로)이 맞는 함수 본문
+              show_lines {start, end}     줄 범위 (한 번에 최대 MAX_SHOW_LINES줄)
+              search {pattern}            대소문자 무시 문자열 검색 (줄 번호와 속한 함수)
+              list_functions              함수 목록 다시 보기
+    예산      질문 턴은 최대 N번, 턴마다 질문 max_questions개까지 (답은 max_answer_chars자로 자름)
+              예산을 다 쓰면 마지막 질의에서 최종 분석만 요청 → 최종 응답을 평소처럼 파싱·채점
+
+결과 행의 interrogation에 턴별 질문과 비용이, 요약의 interrogation에 모델별 점수/비용이 쓴 질문 턴 수별로 남습니다.
+턴 예산을 바꿔 가며 실행한 결과 파일들은 이 모듈의 CLI로 예산별 점수와 비용을 나란히 비교합니다.
+
+대화 상태는 클라이언트에 두지 않고, 턴마다 첫 질의 + 지금까지의 질문과 답을 한 프롬프트로 다시 보냅니다.
+창 분할(--chunking)과 배치 추론(--batch)과는 함께 쓰지 않습니다.
+--tools를 함께 주면 질문 JSON 대신 함수 호출 도구로 탐색합니다 (utils/harness_tools.py, 예산과 요약은 같음).
+
+사용법:
+    python benchmark_runner.py --interrogate 3 --agents source_code --providers openai
+    python benchmark_runner.py --interrogate 4 --tools --agents source_code --providers anthropic
+    python -m utils.interrogation results/turns0.json results/turns2.json results/turns4.json
+"""
+
+import argparse
+import json
+import re
+from typing import Any, Callable, Dict, List, Optional, Tuple
+
+from utils.run_control import CancellationToken
+from utils.sample_index import SampleIndex
+
+DEFAULT_MAX_TURNS = 3
+DEFAULT_MAX_QUESTIONS = 3
+DEFAULT_MAX_ANSWER_CHARS = 4000
+DEFAULT_PREVIEW_LINES = 30
+MAX_SHOW_LINES = 200
+MAX_SEARCH_HITS = 20
+ACTIONS = ('show_function', 'show_lines', 'search', 'list_functions')
+# questions: 질문 JSON을 프롬프트로 주고받음, tools: 함수 호출 도구 (utils/harness_tools.py)
+PROTOCOLS = ('questions', 'tools')
+
+_QUESTIONS = re.compile(r'\{.*"questions".*\}', re.DOTALL)
+
+
+def interrogation_settings(config: Optional[Dict[str, Any]]) -> Dict[str, Any]:
+    config = config or {}
+    return {
+        'enabled': bool(config.get('enabled', False)),
+        'protocol': config.get('protocol', 'questions') if config.get('protocol') in PROTOCOLS else 'questions',
+        'max_turns': int(config.get('max_turns', DEFAULT_MAX_TURNS)),
+        'max_questions': int(config.get('max_questions', DEFAULT_MAX_QUESTIONS)),
+        'max_answer_chars': int(config.get('max_answer_chars', DEFAULT_MAX_ANSWER_CHARS)),
+        'preview_lines': int(config.get('preview_lines', DEFAULT_PREVIEW_LINES))
+    }
+
+
+def _numbered(lines: List[str], start: int) -> str:
+    """start(1부터)부터 줄 번호를 붙인 본문"""
+    return '\n'.join(f"{number:>5} | {line}" for number, line in enumerate(lines, start))
+
+
+def outline(index: SampleIndex) -> str:
+    """함수 목록과 줄 범위"""
+    if not index.items:
+        return '(no functions found; use show_lines or search)'
+    return '\n'.join(f"- {item['kind']} {item.get('qualified_name') or item['name']} "
+                     f"(lines {item['start_line']}-{item['end_line']})" for item in index.items)
+
+
+def initial_view(text: str, index: SampleIndex, preview_lines: int) -> str:
+    """첫 질의에 넣는 입력: 개요 + 앞부분 preview_lines줄"""
+    lines = text.splitlines()
+    preview = _numbered(lines[:preview_lines], 1) if preview_lines > 0 else ''
+    return (f"File outline ({len(lines)} lines total, {index.language or 'unknown'} language):\n{outline(index)}\n\n"
+            + (f"First {min(preview_lines, len(lines))} lines:\n{preview}\n" if preview else '')
+            + "\nThe rest of the file is not shown. Ask follow-up questions to see more.")
+
+
+def instructions(remaining: int, max_questions: int) -> str:
+    if remaining <= 0:
+        return ("\n==== FOLLOW-UP QUESTIONS ====\n\nYou have no question turns left. "
+                "Respond with the final analysis JSON now, in the format described above.\n")
+    return f"""
+==== FOLLOW-UP QUESTIONS ====
+
+You see only part of the file. Before giving the final analysis you may ask the harness for more of it.
+To ask, respond with ONLY this JSON (no analysis fields):
+{{"questions": [{{"action": "show_function", "name": "<function name>"}},
+               {{"action": "show_lines", "start": 10, "end": 60}},
+               {{"action": "search", "pattern": "<text>"}},
+               {{"action": "list_functions"}}]}}
+You may ask at most {max_questions} questions per turn and have {remaining} question turn(s) left.
+When you have enough evidence, respond with the final analysis JSON in the format described above instead.
+"""
+
+
+def parse_questions(content: str) -> Optional[List[Dict[str, Any]]]:
+    """응답이 질문이면 질문 목록, 최종 분석이면 None"""
+    match = _QUESTIONS.search(content or '')
+    if not match:
+        return None
+    try:
+        data = json.loads(match.group(0))
+    except json.JSONDecodeError:
+        return None
+    if not isinstance(data, dict) or not isinstance(data.get('questions'), list) or 'analysis_results' in data:
+        return None
+    return [question for question in data['questions']
+            if isinstance(question, dict) and question.get('action') in ACTIONS]
+
+
+def describe_question(question: Dict[str, Any]) -> str:
+    action = question['action']
+    if action == 'show_function':
+        return f"show_function {question.get('name', '')}"
+    if action == 'show_lines':
+        return f"show_lines {question.get('start')}-{question.get('end')}"
+    if action == 'search':
+        return f"search {question.get('pattern', '')!r}"
+    return action
+
+
+def answer(question: Dict[str, Any], text: str, index: SampleIndex) -> str:
+    """질문 하나에 샘플에서 찾은 답"""
+    lines = text.splitlines()
+    action = question['action']
+    if action == 'list_functions':
+        return outline(index)
+    if action == 'show_function':
+        name = str(question.get('name', '')).strip().lower()
+        matches = [item for item in index.items
+                   if name and name in (item['name'].lower(), str(item.get('qualified_name', '')).lower())]
+        if not matches:
+            return f"No function named {question.get('name')!r}. Known functions:\n{outline(index)}"
+        return '\n\n'.join(f"{item['kind']} {item.get('qualified_name') or item['name']}:\n"
+                           + _numbered(lines[item['start_line'] - 1:item['end_line']], item['start_line'])
+                           for item in matches)
+    if action == 'show_lines':
+        try:
+            start = max(1, int(question.get('start', 1)))
+            end = min(len(lines), int(question.get('end', start)), start + MAX_SHOW_LINES - 1)
+        except (TypeError, ValueError):
+            return "show_lines needs integer start and end"
+        if start > end:
+            return f"No lines in range (the file has {len(lines)} lines)"
+        return _numbered(lines[start - 1:end], start)
+    pattern = str(question.get('pattern', '')).strip().lower()
+    if not pattern:
+        return "search needs a non-empty pattern"
+    hits = [number for number, line in enumerate(lines, 1) if pattern in line.lower()]
+    if not hits:
+        return f"No lines contain {question.get('pattern')!r}"
+    rows = []
+    for number in hits[:MAX_SEARCH_HITS]:
+        item = index.item_at(number, kinds=('fn', 'class', 'impl'))
+        where = f" (in {item.get('qualified_name') or item['name']})" if item else ''
+        rows.append(f"{number:>5} | {lines[number - 1]}{where}")
+    more = f"\n... {len(hits) - MAX_SEARCH_HITS} more" if len(hits) > MAX_SEARCH_HITS else ''
+    return '\n'.join(rows) + more
+
+
+def interrogate(client, build_prompt: Callable[[str], str], text: str, suffix: str, settings: Dict[str, Any],
+                max_tokens: int, token: Optional[CancellationToken] = None) -> Tuple[Dict[str, Any], Dict[str, Any]]:
+    """질문 턴을 예산만큼 주고받은 뒤 최종 응답
+
+    Returns:
+        (최종 응답 (시간, 토큰, 비용은 모든 턴 합산), 턴 기록 {'budget', 'turns_used', 'answered', 'turns'})
+    """
+    index = SampleIndex.from_text(text, suffix or '.txt')
+    base_prompt = build_prompt(initial_view(text, index, settings['preview_lines']))
+    budget = settings['max_turns']
+    transcript: List[str] = []
+    turns: List[Dict[str, Any]] = []
+    responses: List[Dict[str, Any]] = []
+    answered = False
+
+    for turn in range(budget + 1):
+        if token:
+            token.raise_if_cancelled()
+        prompt = base_prompt + ''.join(transcript) + instructions(budget - turn, settings['max_questions'])
+        response = client.benchmark_request(prompt, max_tokens)
+        responses.append(response)
+        record = {'turn': turn + 1, 'questions': [], 'cost_usd': response.get('cost_usd', 0.0),
+                  'response_time': response.get('response_time', 0.0)}
+        turns.append(record)
+        if not response.get('success'):
+            break
+        questions = parse_questions(response.get('content', '')) if turn < budget else None
+        if questions is None:
+            answered = turn < budget or parse_questions(response.get('content', '')) is None
+            break
+        asked = questions[:settings['max_questions']]
+        record['asked'] = True
+        record['questions'] = [describe_question(question) for question in asked]
+        answers = [f"\n[Turn {turn + 1}] {describe_question(question)}\n"
+                   f"{answer(question, text, index)[:settings['max_answer_chars']]}\n" for question in asked]
+        transcript.append(f"\n==== HARNESS ANSWERS (turn {turn + 1}) ====\n" + ''.join(answers)
+                          if answers else f"\n==== HARNESS ANSWERS (turn {turn + 1}) ====\n(no valid questions)\n")
+
+    usage: Dict[str, Any] = {}
+    for item in responses:
+        for key, value in (item.get('usage') or {}).items():
+            if isinstance(value, (int, float)):
+                usage[key] = usage.get(key, 0) + value
+    final = dict(responses[-1])
+    final.update(response_time=sum(item.get('response_time', 0.0) for item in responses), usage=usage,
+                 cost_usd=sum(item.get('cost_usd', 0.0) for item in responses))
+    return final, {'budget': budget, 'turns_used': sum(1 for record in turns if record.get('asked')),
+                   'answered': answered, 'turns': turns}
+
+
+def _model_key(result: Dict[str, Any]) -> str:
+    return f"{result.get('provider', 'unknown')}/{result.get('model', 'unknown')}"
+
+
+def _f1(result: Dict[str, Any]) -> float:
+    return (result.get('hierarchical_scores') or {}).get('f1', 0.0)
+
+
+def summarize_interrogation(results: List[Dict[str, Any]]) -> Dict[str, Dict[str, Any]]:
+    """모델별 점수/비용 (전체와 쓴 질문 턴 수별, 채점하지 못한 샘플은 F1 0)"""
+    by_model: Dict[str, List[Dict[str, Any]]] = {}
+    for result in results:
+        if result.get('interrogation'):
+            by_model.setdefault(_model_key(result), []).append(result)
+    summary = {}
+    for model_key, rows in by_model.items():
+        by_turns: Dict[int, List[Dict[str, Any]]] = {}
+        for row in rows:
+            by_turns.setdefault(row['interrogation']['turns_used'], []).append(row)
+        summary[model_key] = {
+            'budget': rows[0]['interrogation']['budget'],
+            'samples': len(rows),
+            'hierarchical_f1': sum(map(_f1, rows)) / len(rows),
+            'cost_usd': round(sum(row.get('cost_usd', 0.0) for row in rows), 6),
+            'mean_turns': sum(row['interrogation']['turns_used'] for row in rows) / len(rows),
+            'answered_in_budget': sum(1 for row in rows if row['interrogation']['answered']),
+            'by_turns_used': {
+                turns: {'samples': len(group), 'hierarchical_f1': sum(map(_f1, group)) / len(group),
+                        'cost_usd': round(sum(row.get('cost_usd', 0.0) for row in group), 6)}
+                for turns, group in sorted(by_turns.items())
+            }
+        }
+    return summary
+
+
+def print_interrogation(summary: Dict[str, Dict[str, Any]]):
+    print(f"\n🗣️  다중 턴 질의 (모델별, 쓴 질문 턴 수별):")
+    for model_key, stats in sorted(summary.items()):
+        print(f"  {model_key} (예산 {stats['budget']}턴, {stats['samples']}개 샘플): 계층 F1 {stats['hierarchical_f1']:.3f}, "
+              f"비용 ${stats['cost_usd']:.4f}, 평균 {stats['mean_turns']:.2f}턴, "
+              f"예산 안에 답함 {stats['answered_in_budget']}/{stats['samples']}")
+        for turns, group in stats['by_turns_used'].items():
+            print(f"    {turns}턴: {group['samples']}개, F1 {group['hierarchical_f1']:.3f}, 비용 ${group['cost_usd']:.4f}")
+
+
+def compare_budgets(paths: List[str]) -> List[Dict[str, Any]]:
+    """결과 파일들 → (모델, 턴 예산)별 행 (예산 0은 --interrogate 0 실행 또는 단발 실행)"""
+    rows = {}
+    for path in paths:
+        with open(path, 'r', encoding='utf-8') as f:
+            data = json.load(f)
+        if not isinstance(data, dict) or 'detailed_results' not in data:
+            raise ValueError(f"{path}: detailed_results가 없는 결과 파일입니다")
+        for result in data['detailed_results']:
+            budget = (result.get('interrogation') or {}).get('budget', 0)
+            row = rows.setdefault((_model_key(result), budget), {
+                'model': _model_key(result), 'budget': budget, 'samples': 0, 'f1_sum': 0.0, 'cost_usd': 0.0,
+                'turns_used': 0, 'files': []})
+            row['samples'] += 1
+            row['f1_sum'] += _f1(result)
+            row['cost_usd'] += result.get('cost_usd', 0.0)
+            row['turns_used'] += (result.get('interrogation') or {}).get('turns_used', 0)
+            if path not in row['files']:
+                row['files'].append(path)
+    return [{'model': row['model'], 'budget': row['budget'], 'samples': row['samples'],
+             'hierarchical_f1': row['f1_sum'] / row['samples'], 'cost_usd': round(row['cost_usd'], 6),
+             'cost_per_sample': round(row['cost_usd'] / row['samples'], 6),
+             'mean_turns': row['turns_used'] / row['samples'], 'files': row['files']}
+            for _, row in sorted(rows.items())]
+
+
+def main():
+    parser = argparse.ArgumentParser(description='턴 예산별 점수와 비용 비교 (--interrogate N으로 만든 결과 파일들)')
+    parser.add_argument('results', nargs='+', help='결과 JSON 파일 (예산마다 하나 이상)')
+    parser.add_argument('--json', action='store_true', help='JSON으로 출력')
+    args = parser.parse_args()
+
+    try:
+        rows = compare_budgets(args.results)
+    except (OSError, ValueError) as e:
+        parser.error(str(e))
+    if args.json:
+        print(json.dumps(rows, indent=2, ensure_ascii=False))
+        return
+    print(f"{'모델':<36} {'예산':>4} {'샘플':>5} {'계층 F1':>8} {'평균 턴':>7} {'샘플당 비용':>12}")
+    for row in rows:
+        print(f"{row['model']:<36} {row['budget']:>4} {row['samples']:>5} {row['hierarchical_f1']:>8.3f} "
+              f"{row['mean_turns']:>7.2f} {'$' + format(row['cost_per_sample'], '.4f'):>12}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,176 @@
+"""
+한국 암호 준수 프로필 (K-crypto coverage, KCMVP 기준)
+
+국내 규제(KCMVP 검증 암호 모듈, 금융/공공 도입 기준)를 보는 사용자에게는 탐지기가 SEED/ARIA/LEA/HIGHT/HAS-160/LSH/KCDSA를
+그 이름으로 찾는지, 구조가 비슷한 국제 알고리즘(AES, SHA-1, DSA …)과 구별하는지가 중요합니다. 계층 F1은 SEED 자리에
+AES를 보고해도 같은 범주 부분 점수를 주므로 이 구별이 드러나지 않아, 한국 계열 라벨만 계열 단위로 따로 채점합니다.
+
+샘플 판정 (결과 행의 korean_check, 한국 계열 라벨도 잘못 보고한 한국 계열도 없는 샘플은 None):
+    detected      한국 계열 라벨을 같은 계열로 보고함 (변형은 보지 않음: EC-KCDSA 자리의 KCDSA도 적중)
+    confused      한국 계열은 보고하지 않고, 정답에 없는 그 국제 대응 계열(INTERNATIONAL_EQUIVALENTS)을 보고함 (SEED → AES)
+    missed        둘 다 아님
+    false_claims  정답에 없는 한국 계열을 보고했고 정답에 그 국제 대응 계열이 있음 (AES 샘플에 ARIA 보고)
+    score         detected / (한국 계열 라벨 + false_claims)
+
+요약 (benchmark_runner.py의 summary.korean_compliance는 모델별, detectors/runner.py는 탐지기 하나):
+    coverage        detected / 한국 계열 라벨
+    confusion_rate  confused / 한국 계열 라벨
+    by_family       계열별 {labels, detected, confused, missed, false_claims}
+
+점수 프로필:
+    샘플 score는 종합 점수(utils/composite.py)의 korean_compliance 트랙입니다. 기본 가중치는 0이고,
+    config/benchmark.toml의 kcmvp 프로필([composite.profiles.kcmvp.weights])이 이 트랙에 가중치를 둡니다.
+
+사용법:
+    python -m utils.korean_compliance results/benchmark_results_1735689600.json
+    python -m utils.korean_compliance results/structure.json --json
+"""
+
+import argparse
+import json
+from pathlib import Path
+from typing import Dict, Any, Iterable, List, Optional
+
+GROUND_TRUTH_DIR = "data/ground_truth"
+
+# 한국 계열 → 구조/용도가 겹쳐 혼동하기 쉬운 국제 계열 (분류 체계의 계열 이름)
+INTERNATIONAL_EQUIVALENTS = {
+    'SEED': ['AES', 'DES', 'Camellia'],
+    'ARIA': ['AES', 'Camellia'],
+    'LEA': ['AES', 'ChaCha20'],
+    'HIGHT': ['DES', 'AES'],
+    'HAS-160': ['SHA-1', 'MD5'],
+    'LSH': ['SHA-2', 'SHA-3'],
+    'KCDSA': ['DSA', 'ECC'],
+}
+KOREAN_FAMILIES = list(INTERNATIONAL_EQUIVALENTS)
+
+
+def korean_check(predicted_labels: List[Any], ground_truth: Dict[str, Any]) -> Optional[Dict[str, Any]]:
+    """예측 라벨과 ground truth → 한국 계열 판정 (채점할 한국 계열이 없으면 None)"""
+    from utils.metrics_calculator import MetricsCalculator
+    from utils.taxonomy import AlgorithmTaxonomy
+
+    taxonomy = AlgorithmTaxonomy()
+    expected_families = []
+    for label in MetricsCalculator.get_expected_labels(ground_truth):
+        family = taxonomy.resolve(label)[1]
+        if family and family not in expected_families:
+            expected_families.append(family)
+    predicted_families = {taxonomy.resolve(label)[1] for label in predicted_labels}
+
+    detected, confused, missed = [], [], []
+    for family in expected_families:
+        if family not in INTERNATIONAL_EQUIVALENTS:
+            continue
+        if family in predicted_families:
+            detected.append(family)
+            continue
+        # 정답에도 있는 국제 계열을 보고한 것은 혼동이 아님 (SEED와 AES를 함께 쓰는 샘플)
+        reported = [equivalent for equivalent in INTERNATIONAL_EQUIVALENTS[family]
+                    if equivalent in predicted_families and equivalent not in expected_families]
+        if reported:
+            confused.append({'family': family, 'predicted': reported})
+        else:
+            missed.append(family)
+
+    false_claims = [
+        {'family': family, 'expected': [equivalent for equivalent in equivalents if equivalent in expected_families]}
+        for family, equivalents in INTERNATIONAL_EQUIVALENTS.items()
+        if family in predicted_families and family not in expected_families
+        and any(equivalent in expected_families for equivalent in equivalents)
+    ]
+
+    labels = len(detected) + len(confused) + len(missed)
+    if not labels and not false_claims:
+        return None
+    return {
+        'detected': detected,
+        'confused': confused,
+        'missed': missed,
+        'false_claims': false_claims,
+        'score': len(detected) / (labels + len(false_claims)),
+    }
+
+
+def summarize_korean(checks: Iterable[Optional[Dict[str, Any]]]) -> Dict[str, Any]:
+    """샘플 판정들 → K-crypto 커버리지 요약"""
+    summary = {'samples': 0, 'labels': 0, 'detected': 0, 'confused': 0, 'missed': 0, 'false_claims': 0,
+               'by_family': {}}
+
+    def family_stats(family: str) -> Dict[str, int]:
+        return summary['by_family'].setdefault(
+            family, {'labels': 0, 'detected': 0, 'confused': 0, 'missed': 0, 'false_claims': 0})
+
+    for check in checks:
+        if not check:
+            continue
+        summary['samples'] += 1
+        for key, families in (('detected', check['detected']), ('missed', check['missed']),
+                              ('confused', [entry['family'] for entry in check['confused']])):
+            for family in families:
+                summary['labels'] += 1
+                summary[key] += 1
+                family_stats(family)['labels'] += 1
+                family_stats(family)[key] += 1
+        for entry in check['false_claims']:
+            summary['false_claims'] += 1
+            family_stats(entry['family'])['false_claims'] += 1
+
+    summary['coverage'] = summary['detected'] / summary['labels'] if summary['labels'] else 0
+    summary['confusion_rate'] = summary['confused'] / summary['labels'] if summary['labels'] else 0
+    summary['by_family'] = dict(sorted(summary['by_family'].items(), key=lambda item: KOREAN_FAMILIES.index(item[0])))
+    return summary
+
+
+def print_korean(summary: Dict[str, Any], indent: str = '  '):
+    """K-crypto 커버리지 요약 출력 (한 탐지기분)"""
+    print(f"{indent}탐지 {summary['coverage']:.1%} ({summary['detected']}/{summary['labels']}), "
+          f"국제 알고리즘으로 혼동 {summary['confusion_rate']:.1%} ({summary['confused']}/{summary['labels']}), "
+          f"국제 알고리즘을 한국 알고리즘으로 오보고 {summary['false_claims']}건 ({summary['samples']}개 샘플)")
+    for family, stats in summary['by_family'].items():
+        print(f"{indent}  {family}: {stats['detected']}/{stats['labels']} 탐지, 혼동 {stats['confused']}, "
+              f"놓침 {stats['missed']}, 오보고 {stats['false_claims']}")
+
+
+def row_check(row: Dict[str, Any], ground_truth_dir: str = GROUND_TRUTH_DIR) -> Optional[Dict[str, Any]]:
+    """결과 행 → 판정 (행에 korean_check가 없으면 ground truth 파일로 다시 계산)"""
+    if 'korean_check' in row:
+        return row['korean_check']
+    path = Path(ground_truth_dir) / row.get('agent_type', '') / f"{row.get('test_id')}.json"
+    if not path.exists():
+        return None
+    with open(path, 'r', encoding='utf-8') as f:
+        ground_truth = json.load(f)
+    return korean_check(row.get('detected_algorithms') or [], ground_truth)
+
+
+def main():
+    from utils.run_compare import group_by_detector
+
+    parser = argparse.ArgumentParser(description='탐지기별 한국 암호(K-crypto) 커버리지')
+    parser.add_argument('results', help='benchmark_runner.py / detectors.runner 결과 JSON')
+    parser.add_argument('--ground-truth-dir', default=GROUND_TRUTH_DIR, help='결과에 판정이 없을 때 읽을 ground truth')
+    parser.add_argument('--json', action='store_true', help='JSON으로 출력')
+    args = parser.parse_args()
+
+    with open(args.results, 'r', encoding='utf-8') as f:
+        results = [row for row in json.load(f).get('detailed_results', []) if 'error' not in row]
+    summaries = {name: summarize_korean(row_check(row, args.ground_truth_dir) for row in rows)
+                 for name, rows in group_by_detector(results).items()}
+    if not summaries:
+        parser.error(f"{args.results}: detailed_results 행이 없습니다")
+    if args.json:
+        print(json.dumps(summaries, indent=2, ensure_ascii=False))
+        return
+    print("🇰🇷 K-crypto 커버리지 (KCMVP 계열: " + ', '.join(KOREAN_FAMILIES) + ")")
+    for name, summary in sorted(summaries.items(), key=lambda item: -item[1]['coverage']):
+        print(f"\n{name}")
+        if not summary['samples']:
+            print("  한국 계열 라벨이 있는 샘플 없음")
+            continue
+        print_korean(summary)
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,266 @@
+"""
+채점 모델(judge) 라벨 매핑
+
+구조화된 라벨 대신 자유 서술로 답하는 탐지기는 정확 파서(benchmark_runner의 "DETECTED:" 형식 추출)로
+채점하면 맞게 답하고도 0점이 됩니다. 채점 모델이 응답 원문을 읽고 분류 체계(utils/taxonomy.py)의
+라벨 목록으로 옮기게 해 이런 응답도 채점할 수 있게 합니다.
+
+채점 모델도 틀리거나 후하게 읽을 수 있으므로, 두 방식이 모두 라벨을 낸 응답에서 judge와 파서의
+일치도를 함께 보고합니다 (라벨 비교 단위는 알고리즘 계열):
+    exact_rate       두 라벨 집합이 같은 응답 비율
+    mean_jaccard     계열 집합의 평균 Jaccard 유사도
+    judge_only       judge만 낸 계열 수와 그중 정답 비율 (judge가 응답에 없는 것을 읽어 넣는지)
+    parser_only      파서만 낸 계열 수와 그중 정답 비율 (judge가 놓치는지)
+    label_count_bias judge 라벨 수 - 파서 라벨 수의 응답당 평균
+    f1_diff          judge 라벨 F1 - 파서 라벨 F1의 평균 차이와 부트스트랩 95% 신뢰구간
+
+모드:
+    shadow     채점은 파서 라벨 그대로, judge 라벨은 비교용으로만 기록 (기본)
+    fallback   파서가 응답을 읽지 못한 경우(JSON 파싱 실패)에만 judge 라벨로 채점
+
+설정 (config.yaml):
+    benchmark:
+      label_judge:
+        enabled: false
+        mode: "shadow"        # shadow | fallback
+        provider: "openai"
+        model: "gpt-4.1"
+
+사용법 (저장된 결과의 응답 원문을 다시 매핑하고 일치도 보고):
+    python -m utils.label_judge results/benchmark_results_20250101_120000.json
+    python -m utils.label_judge results/run.json --judge-provider ollama --judge-model llama3:8b --output judged.json
+"""
+
+import argparse
+import json
+import re
+from typing import Dict, Any, List, Optional
+
+from utils.metrics_calculator import MetricsCalculator
+from utils.significance import paired_bootstrap_ci
+from utils.taxonomy import AlgorithmTaxonomy, normalize_name
+
+MODES = ['shadow', 'fallback']
+
+MAX_RESPONSE_CHARS = 6000
+
+_taxonomy = AlgorithmTaxonomy()
+
+
+def label_judge_settings(config: Optional[Dict[str, Any]]) -> Dict[str, Any]:
+    config = config or {}
+    return {
+        'enabled': bool(config.get('enabled', False)),
+        'mode': config.get('mode', 'shadow'),
+        'provider': config.get('provider'),
+        'model': config.get('model')
+    }
+
+
+def _family(label: str) -> str:
+    node = _taxonomy.resolve(label)
+    return normalize_name(node[1] or label)
+
+
+def taxonomy_labels() -> List[str]:
+    """judge에게 보여줄 라벨 목록 (계열과 변형 이름)"""
+    labels = []
+    for families in _taxonomy.taxonomy.values():
+        for family, variants in families.items():
+            labels.append(family)
+            labels.extend(variants)
+    return labels
+
+
+class LabelJudge:
+    """채점 모델로 자유 서술 응답을 분류 체계 라벨로 매핑"""
+
+    def __init__(self, client, max_tokens: int = 500):
+        self.client = client
+        self.max_tokens = max_tokens
+
+    def prompt(self, response_text: str) -> str:
+        return f"""You are normalizing the answer of a cryptographic-algorithm detector.
+
+Read the detector's answer below and list every cryptographic algorithm the detector says IS present
+in the analyzed file. Do not add algorithms the answer does not claim, and skip algorithms the answer
+says are absent or only mentions as examples.
+
+Use the most specific name from this list that matches what the detector claimed:
+{', '.join(taxonomy_labels())}
+If a claimed algorithm is not in the list, use the name the detector used.
+
+Detector answer:
+\"\"\"{response_text[:MAX_RESPONSE_CHARS]}\"\"\"
+
+Respond ONLY with a JSON list of labels, e.g. ["RSA-2048", "AES"]. Respond with [] if nothing is claimed."""
+
+    def map_labels(self, response_text: Optional[str]) -> Dict[str, Any]:
+        """응답 원문 → {'labels': [...]} (judge 호출 실패 시 {'labels': None, 'error': ...})"""
+        if not response_text or not response_text.strip():
+            return {'labels': []}
+
+        response = self.client.benchmark_request(self.prompt(response_text), self.max_tokens)
+        if not response.get('success'):
+            return {'labels': None, 'error': response.get('error')}
+        match = re.search(r'\[.*\]', response.get('content', ''), re.DOTALL)
+        try:
+            parsed = json.loads(match.group(0)) if match else None
+        except json.JSONDecodeError:
+            parsed = None
+        if not isinstance(parsed, list):
+            return {'labels': None, 'error': 'judge response is not a JSON list'}
+
+        labels, seen = [], set()
+        for label in parsed:
+            if isinstance(label, str) and label.strip() and normalize_name(label) not in seen:
+                seen.add(normalize_name(label))
+                labels.append(label.strip())
+        return {'labels': labels}
+
+
+def create_label_judge(settings: Dict[str, Any], config_loader) -> LabelJudge:
+    """설정의 프로바이더/모델로 judge 생성"""
+    from clients.client_factory import ClientFactory
+
+    provider = settings.get('provider')
+    model = settings.get('model')
+    if not provider or not model:
+        raise ValueError("label_judge.provider / model must be set")
+    llm_config = config_loader.get_llm_config(provider, model_name=model)
+    return LabelJudge(ClientFactory.create_client(provider, llm_config))
+
+
+def label_agreement(judge_labels: List[str], parser_labels: List[str],
+                    ground_truth: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+    """한 응답에서 judge 라벨과 파서 라벨의 계열 단위 일치도 (ground truth가 있으면 불일치 계열의 정답 여부 포함)"""
+    judge = {_family(label) for label in judge_labels}
+    parser = {_family(label) for label in parser_labels}
+    union = judge | parser
+    agreement = {
+        'exact': judge == parser,
+        'jaccard': len(judge & parser) / len(union) if union else 1.0,
+        'judge_only': sorted(judge - parser),
+        'parser_only': sorted(parser - judge)
+    }
+    if ground_truth:
+        expected = {_family(label) for label in MetricsCalculator.get_expected_labels(ground_truth)}
+        agreement['judge_only_correct'] = [family for family in agreement['judge_only'] if family in expected]
+        agreement['parser_only_correct'] = [family for family in agreement['parser_only'] if family in expected]
+    return agreement
+
+
+def judge_result(judge: LabelJudge, response_text: Optional[str], parser_labels: Optional[List[str]],
+                 ground_truth: Optional[Dict[str, Any]], policy=None) -> Dict[str, Any]:
+    """결과의 label_judge 필드 {'labels', 'hierarchical_scores', 'agreement'}
+
+    parser_labels가 None이면 (파서가 응답을 읽지 못함) 일치도는 계산하지 않습니다.
+    """
+    mapped = judge.map_labels(response_text)
+    labels = mapped['labels']
+    record = {'labels': labels, 'hierarchical_scores': None, 'agreement': None}
+    if labels is None:
+        record['error'] = mapped.get('error')
+        return record
+    if ground_truth:
+        record['hierarchical_scores'] = MetricsCalculator.calculate_hierarchical_scores(labels, ground_truth, policy)
+    if parser_labels is not None:
+        record['agreement'] = label_agreement(labels, parser_labels, ground_truth)
+    return record
+
+
+def agreement_report(results: List[Dict[str, Any]]) -> Dict[str, Any]:
+    """label_judge가 기록된 결과들의 judge-파서 일치도와 judge 편향 통계"""
+    compared = [r for r in results
+                if (r.get('label_judge') or {}).get('agreement') and r.get('hierarchical_scores')]
+    report = {
+        'judged': len([r for r in results if (r.get('label_judge') or {}).get('labels') is not None]),
+        'judge_errors': len([r for r in results if (r.get('label_judge') or {}).get('error')]),
+        'parse_failures_judged': len([r for r in results if r.get('label_judge')
+                                          and r['label_judge'].get('labels') is not None
+                                          and not r['label_judge'].get('agreement')]),
+        'compared': len(compared)
+    }
+    if not compared:
+        return report
+
+    agreements = [result['label_judge']['agreement'] for result in compared]
+    judge_only = sum(len(agreement['judge_only']) for agreement in agreements)
+    parser_only = sum(len(agreement['parser_only']) for agreement in agreements)
+    judge_only_correct = sum(len(agreement.get('judge_only_correct', [])) for agreement in agreements)
+    parser_only_correct = sum(len(agreement.get('parser_only_correct', [])) for agreement in agreements)
+
+    judge_f1 = [(result['label_judge'].get('hierarchical_scores') or {}).get('f1', 0.0) for result in compared]
+    parser_f1 = [result['hierarchical_scores'].get('f1', 0.0) for result in compared]
+    count_bias = [len(result['label_judge']['labels']) - len(result.get('detected_algorithms') or [])
+                  for result in compared]
+    report.update({
+        'exact_rate': sum(1 for agreement in agreements if agreement['exact']) / len(compared),
+        'mean_jaccard': sum(agreement['jaccard'] for agreement in agreements) / len(compared),
+        'judge_only': {'labels': judge_only,
+                       'correct_rate': judge_only_correct / judge_only if judge_only else None},
+        'parser_only': {'labels': parser_only,
+                        'correct_rate': parser_only_correct / parser_only if parser_only else None},
+        'label_count_bias': sum(count_bias) / len(count_bias),
+        'f1_diff': paired_bootstrap_ci(parser_f1, judge_f1)
+    })
+    return report
+
+
+def print_agreement_report(report: Dict[str, Any], title: str = ''):
+    print(f"⚖️  judge 라벨 매핑{f' ({title})' if title else ''}: judge {report['judged']}개, "
+          f"오류 {report['judge_errors']}개, 파싱 실패 응답 매핑 {report['parse_failures_judged']}개")
+    if not report['compared']:
+        print("  파서와 비교할 응답 없음")
+        return
+
+    def rate(value):
+        return '-' if value is None else f"{value:.1%}"
+
+    diff = report['f1_diff']
+    print(f"  파서와 비교 {report['compared']}개: 완전 일치 {report['exact_rate']:.1%}, "
+          f"평균 Jaccard {report['mean_jaccard']:.3f}, 라벨 수 편향 {report['label_count_bias']:+.2f}/응답")
+    print(f"  judge만 낸 계열 {report['judge_only']['labels']}개 (정답 {rate(report['judge_only']['correct_rate'])}), "
+          f"파서만 낸 계열 {report['parser_only']['labels']}개 (정답 {rate(report['parser_only']['correct_rate'])})")
+    print(f"  계층 F1 (judge - 파서): {diff['mean_diff']:+.3f} "
+          f"[{diff['ci_low']:+.3f}, {diff['ci_high']:+.3f}]{' *' if diff['significant'] else ''}")
+
+
+def main():
+    parser = argparse.ArgumentParser(description='저장된 결과의 응답 원문을 judge 모델로 라벨 매핑하고 파서와 비교')
+    parser.add_argument('results', help='benchmark_runner.py 결과 JSON')
+    parser.add_argument('--judge-provider', required=True)
+    parser.add_argument('--judge-model', required=True)
+    parser.add_argument('--output', help='label_judge 필드를 채운 결과 JSON 경로 (기본: 출력만)')
+    args = parser.parse_args()
+
+    from config.config_loader import ConfigLoader
+    from utils.test_case_manager import TestCaseManager
+    manager = TestCaseManager("data/test_cases", "data/ground_truth", "data/test_files")
+    judge = create_label_judge({'provider': args.judge_provider, 'model': args.judge_model}, ConfigLoader())
+
+    with open(args.results, 'r', encoding='utf-8') as f:
+        data = json.load(f)
+
+    policy = data.get('metadata', {}).get('hierarchy_policy')
+    by_detector: Dict[str, List[Dict[str, Any]]] = {}
+    for result in data.get('detailed_results', []):
+        if 'error' in result or not result.get('raw_response'):
+            continue
+        ground_truth = manager.load_ground_truth(result.get('agent_type', ''), result['test_id'])
+        parser_labels = result.get('detected_algorithms') if result.get('valid_json') else None
+        result['label_judge'] = judge_result(judge, result['raw_response'], parser_labels, ground_truth, policy)
+        by_detector.setdefault(f"{result.get('provider')}/{result.get('model')}", []).append(result)
+
+    for detector, results in sorted(by_detector.items()):
+        print_agreement_report(agreement_report(results), detector)
+
+    if args.output:
+        data.setdefault('metadata', {})['label_judge'] = {'provider': args.judge_provider, 'model': args.judge_model}
+        with open(args.output, 'w', encoding='utf-8') as f:
+            json.dump(data, f, indent=2, ensure_ascii=False)
+        print(f"💾 저장: {args.output}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,517 @@
+#!/usr/bin/env python3
+"""
+Ground Truth 라벨 리뷰 CLI
+
+샘플을 하나씩 보여주면서 현재 라벨이 가리키는 라인 범위를 강조 표시하고,
+리뷰어가 라벨을 승인/수정/거절할 수 있게 합니다.
+저장 전에는 ground truth JSON의 변경 내용을 diff 형식으로 보여주며,
+모든 결정은 감사 로그(JSONL)에 기록됩니다.
+
+사용법:
+    python -m utils.label_review review --agent source_code
+    python -m utils.label_review review --agent source_code --test-id medical_device_encryption
+    python -m utils.label_review review --only-unreviewed --reviewer alice
+    python -m utils.label_review review --staging --only-unreviewed   # 가져온 실제 코드의 제안 라벨 (utils/corpus_import.py)
+
+지원하는 ground truth 형식:
+    1. expected_findings 형식
+       - vulnerable_algorithms_detected / korean_algorithms_detected 목록
+       - (선택) locations: {"RSA": ["10-42"]} 알고리즘별 라인 범위
+       - (선택) fidelity: {"RSA": "exact"} 알고리즘별 구현 충실도 (utils/fidelity.py)
+       - (선택) disputed: {"HAS-160": {"alternatives": ["SHA-1"]}} 대체 정답 (utils/uncertainty.py)
+       - (선택) weakness: {"PBKDF2": "weak-parameters"} 알고리즘별 취약성 범주 (utils/weakness.py)
+    2. vulnerability_analysis 형식
+       - quantum_vulnerable_algorithms[].locations[].line_range
+       - (선택) quantum_vulnerable_algorithms[].fidelity / .disputed / .weakness
+"""
+
+import argparse
+import copy
+import difflib
+import getpass
+import json
+import os
+import sys
+import time
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+from utils.fidelity import label_fidelity
+from utils.weakness import recorded_weakness
+from utils.uncertainty import disputed_labels
+from utils.test_case_manager import TestCaseManager
+
+AUDIT_LOG_PATH = "data/ground_truth/label_audit.jsonl"
+# corpus import 스테이징 영역 (data/staging/{test_files,ground_truth}, 감사 로그도 따로 둠)
+STAGING_DIR = "data/staging"
+
+LABEL_FIELDS = ['vulnerable_algorithms_detected', 'korean_algorithms_detected']
+LEGACY_FIELD = 'quantum_vulnerable_algorithms'
+
+# 터미널 강조 색상
+_HIGHLIGHT = '\033[43;30m'
+_GUTTER = '\033[33m'
+_ADDED = '\033[32m'
+_REMOVED = '\033[31m'
+_RESET = '\033[0m'
+
+
+def parse_line_range(line_range: Optional[str]) -> Optional[Tuple[int, int]]:
+    """'25-45' 또는 '25' 형식의 라인 범위를 (start, end)로 변환"""
+    if not line_range:
+        return None
+    parts = str(line_range).replace(' ', '').split('-')
+    try:
+        start = int(parts[0])
+        end = int(parts[1]) if len(parts) > 1 and parts[1] else start
+    except ValueError:
+        return None
+    return (min(start, end), max(start, end))
+
+
+def format_line_range(span: Optional[Tuple[int, int]]) -> Optional[str]:
+    if not span:
+        return None
+    return f"{span[0]}-{span[1]}"
+
+
+class GroundTruthLabels:
+    """ground truth 파일의 라벨 뷰 (형식 차이를 숨기고 라벨 단위 조작 제공)
+
+    라벨 형식: {'field': str, 'algorithm': str, 'span': (start, end) | None}
+    """
+
+    def __init__(self, data: Dict[str, Any]):
+        self.data = copy.deepcopy(data)
+
+    @property
+    def is_legacy(self) -> bool:
+        return 'vulnerability_analysis' in self.data and 'expected_findings' not in self.data
+
+    def labels(self) -> List[Dict[str, Any]]:
+        labels = []
+        if self.is_legacy:
+            entries = self.data['vulnerability_analysis'].get(LEGACY_FIELD, [])
+            for entry in entries:
+                locations = entry.get('locations') or [{}]
+                for location in locations:
+                    labels.append({
+                        'field': LEGACY_FIELD,
+                        'algorithm': entry.get('algorithm', ''),
+                        'span': parse_line_range(location.get('line_range'))
+                    })
+            return labels
+
+        findings = self.data.get('expected_findings', {})
+        locations = findings.get('locations', {})
+        for field in LABEL_FIELDS:
+            for algorithm in findings.get(field, []):
+                spans = [parse_line_range(r) for r in locations.get(algorithm, [])]
+                spans = [s for s in spans if s] or [None]
+                for span in spans:
+                    labels.append({'field': field, 'algorithm': algorithm, 'span': span})
+        return labels
+
+    def remove(self, label: Dict[str, Any]):
+        """라벨 하나 제거 (같은 알고리즘의 다른 span은 유지)"""
+        algorithm, span = label['algorithm'], label['span']
+        if self.is_legacy:
+            entries = self.data['vulnerability_analysis'].get(LEGACY_FIELD, [])
+            for entry in list(entries):
+                if entry.get('algorithm') != algorithm:
+                    continue
+                remaining = [loc for loc in entry.get('locations', [])
+                             if parse_line_range(loc.get('line_range')) != span]
+                if span is None or not remaining:
+                    entries.remove(entry)
+                else:
+                    entry['locations'] = remaining
+            return
+
+        findings = self.data.setdefault('expected_findings', {})
+        locations = findings.get('locations', {})
+        spans = [s for s in (parse_line_range(r) for r in locations.get(algorithm, [])) if s != span]
+        if span is not None and spans:
+            locations[algorithm] = [format_line_range(s) for s in spans]
+            return
+
+        locations.pop(algorithm, None)
+        if 'locations' in findings and not locations:
+            del findings['locations']
+        for key in ('fidelity', 'disputed', 'weakness'):
+            metadata = findings.get(key, {})
+            metadata.pop(algorithm, None)
+            if key in findings and not metadata:
+                del findings[key]
+        if algorithm in findings.get(label['field'], []):
+            findings[label['field']].remove(algorithm)
+
+    def add(self, field: str, algorithm: str, span: Optional[Tuple[int, int]] = None):
+        if self.is_legacy:
+            entries = self.data['vulnerability_analysis'].setdefault(LEGACY_FIELD, [])
+            entry = next((e for e in entries if e.get('algorithm') == algorithm), None)
+            if entry is None:
+                entry = {'algorithm': algorithm, 'locations': []}
+                entries.append(entry)
+            if span:
+                entry.setdefault('locations', []).append({'line_range': format_line_range(span)})
+            return
+
+        findings = self.data.setdefault('expected_findings', {})
+        values = findings.setdefault(field, [])
+        if algorithm not in values:
+            values.append(algorithm)
+        if span:
+            ranges = findings.setdefault('locations', {}).setdefault(algorithm, [])
+            if format_line_range(span) not in ranges:
+                ranges.append(format_line_range(span))
+
+    def replace(self, label: Dict[str, Any], algorithm: str, span: Optional[Tuple[int, int]]):
+        values = None if self.is_legacy else self.data.get('expected_findings', {}).get(label['field'], [])
+        position = values.index(label['algorithm']) if values and label['algorithm'] in values else None
+        fidelity = label_fidelity(self.data).get(label['algorithm'])
+        disputed = disputed_labels(self.data).get(label['algorithm'])
+        weakness = recorded_weakness(self.data).get(label['algorithm'])
+
+        self.remove(label)
+        self.add(label['field'], algorithm, span)
+
+        # 알고리즘 이름을 고쳐도 구현 충실도/대체 정답/취약성 범주 표기는 유지
+        for key, value in (('fidelity', fidelity), ('disputed', disputed), ('weakness', weakness)):
+            if not value:
+                continue
+            if self.is_legacy:
+                for entry in self.data['vulnerability_analysis'].get(LEGACY_FIELD, []):
+                    if entry.get('algorithm') == algorithm:
+                        entry.setdefault(key, value)
+            else:
+                self.data['expected_findings'].setdefault(key, {}).setdefault(algorithm, value)
+
+        # 목록 내 원래 위치 유지
+        if position is not None and algorithm in values:
+            values.remove(algorithm)
+            values.insert(position, algorithm)
+
+    def set_disputed(self, algorithm: str, alternatives: List[str], reason: str = ''):
+        """라벨을 disputed로 표시 (대체 정답이 없으면 표시 해제)"""
+        info = {'alternatives': alternatives, 'reason': reason} if alternatives else None
+        if self.is_legacy:
+            for entry in self.data['vulnerability_analysis'].get(LEGACY_FIELD, []):
+                if entry.get('algorithm') != algorithm:
+                    continue
+                if info:
+                    entry['disputed'] = info
+                else:
+                    entry.pop('disputed', None)
+            return
+
+        findings = self.data.setdefault('expected_findings', {})
+        if info:
+            findings.setdefault('disputed', {})[algorithm] = info
+        elif algorithm in findings.get('disputed', {}):
+            del findings['disputed'][algorithm]
+            if not findings['disputed']:
+                del findings['disputed']
+
+    def to_json(self) -> str:
+        return json.dumps(self.data, indent=2, ensure_ascii=False)
+
+
+class AuditLog:
+    """라벨 리뷰 감사 로그 (JSONL, append-only)"""
+
+    def __init__(self, path: str = AUDIT_LOG_PATH, reviewer: str = None):
+        self.path = Path(path)
+        self.reviewer = reviewer or os.environ.get('USER') or getpass.getuser()
+
+    def record(self, agent_type: str, test_id: str, action: str,
+               before: Optional[Dict[str, Any]] = None, after: Optional[Dict[str, Any]] = None,
+               note: str = ''):
+        entry = {
+            'timestamp': time.strftime('%Y-%m-%dT%H:%M:%S'),
+            'reviewer': self.reviewer,
+            'agent_type': agent_type,
+            'test_id': test_id,
+            'action': action,
+            'before': self._serialize(before),
+            'after': self._serialize(after),
+            'note': note
+        }
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+        with open(self.path, 'a', encoding='utf-8') as f:
+            f.write(json.dumps(entry, ensure_ascii=False) + '\n')
+
+    def reviewed_ids(self, agent_type: str) -> set:
+        """승인까지 끝난 샘플 ID 목록"""
+        if not self.path.exists():
+            return set()
+        reviewed = set()
+        with open(self.path, 'r', encoding='utf-8') as f:
+            for line in f:
+                try:
+                    entry = json.loads(line)
+                except json.JSONDecodeError:
+                    continue
+                if entry.get('agent_type') == agent_type and entry.get('action') == 'approve':
+                    reviewed.add(entry.get('test_id'))
+        return reviewed
+
+    @staticmethod
+    def _serialize(label: Optional[Dict[str, Any]]) -> Optional[Dict[str, Any]]:
+        if label is None:
+            return None
+        return {
+            'field': label.get('field'),
+            'algorithm': label.get('algorithm'),
+            'line_range': format_line_range(label.get('span'))
+        }
+
+
+class LabelReviewer:
+    """대화형 라벨 리뷰 세션"""
+
+    HELP = ("[a] 승인/저장  [e N] N번 수정  [r N] N번 거절  [d N] N번 대체 정답 지정  [n] 라벨 추가  "
+            "[s] 건너뛰기  [q] 종료  [?] 도움말")
+
+    def __init__(self, test_manager: TestCaseManager, audit: AuditLog,
+                 color: bool = True, context: int = 3, input_func=input):
+        self.test_manager = test_manager
+        self.audit = audit
+        self.color = color
+        self.context = context
+        self.input = input_func
+
+    def review(self, agent_type: str, test_ids: List[str] = None, only_unreviewed: bool = False) -> Dict[str, int]:
+        stats = {'approved': 0, 'modified': 0, 'skipped': 0}
+        test_cases = [tc for tc in self.test_manager.load_test_cases(agent_type)
+                      if self.test_manager.load_ground_truth(agent_type, tc['test_id']) is not None]
+        if test_ids:
+            test_cases = [tc for tc in test_cases if tc['test_id'] in test_ids]
+        if only_unreviewed:
+            reviewed = self.audit.reviewed_ids(agent_type)
+            test_cases = [tc for tc in test_cases if tc['test_id'] not in reviewed]
+
+        print(f"📋 {agent_type}: 리뷰 대상 {len(test_cases)}개")
+
+        for index, test_case in enumerate(test_cases, 1):
+            print(f"\n{'=' * 80}")
+            print(f"[{index}/{len(test_cases)}] {agent_type}/{test_case['test_id']}  ({test_case['file_path']})")
+            print(f"{'=' * 80}")
+
+            outcome = self._review_sample(agent_type, test_case)
+            if outcome == 'quit':
+                break
+            stats[outcome] += 1
+
+        print(f"\n✅ 리뷰 종료: 승인 {stats['approved']}개, 수정 {stats['modified']}개, 건너뜀 {stats['skipped']}개")
+        return stats
+
+    def _review_sample(self, agent_type: str, test_case: Dict[str, Any]) -> str:
+        test_id = test_case['test_id']
+        original = self.test_manager.load_ground_truth(agent_type, test_id)
+        labels = GroundTruthLabels(original)
+        changed = False
+
+        while True:
+            current = labels.labels()
+            self.render_source(test_case.get('input_data', ''), current)
+            self.render_labels(current, disputed_labels(labels.data))
+            for proposal in labels.data.get('label_proposals', []):
+                print(f"  💡 {proposal['algorithm']} [{'+'.join(proposal['passes'])}]: "
+                      f"{', '.join(proposal['evidence'])}")
+            print(self.HELP)
+
+            command = self.input("> ").strip()
+            action, _, argument = command.partition(' ')
+            action = action.lower()
+
+            if action in ('a', 'accept'):
+                original_json = json.dumps(original, indent=2, ensure_ascii=False)
+                if changed and labels.to_json() != original_json:
+                    self.render_diff(original_json, labels.to_json(), f"{agent_type}/{test_id}.json")
+                    if self.input("저장할까요? [y/N] ").strip().lower() != 'y':
+                        continue
+                    self.test_manager.save_ground_truth(agent_type, test_id, labels.data)
+                    self.audit.record(agent_type, test_id, 'approve', note='saved with changes')
+                    return 'modified'
+                self.audit.record(agent_type, test_id, 'approve')
+                return 'approved'
+
+            if action in ('r', 'reject', 'e', 'edit'):
+                label = self._pick(current, argument)
+                if label is None:
+                    continue
+                if action.startswith('r'):
+                    labels.remove(label)
+                    self.audit.record(agent_type, test_id, 'reject', before=label)
+                else:
+                    algorithm = self.input(f"알고리즘 [{label['algorithm']}]: ").strip() or label['algorithm']
+                    span_text = self.input(f"라인 범위 [{format_line_range(label['span']) or '없음'}]: ").strip()
+                    span = parse_line_range(span_text) if span_text else label['span']
+                    labels.replace(label, algorithm, span)
+                    self.audit.record(agent_type, test_id, 'edit', before=label,
+                                      after={'field': label['field'], 'algorithm': algorithm, 'span': span})
+                changed = True
+
+            elif action in ('d', 'dispute'):
+                label = self._pick(current, argument)
+                if label is None:
+                    continue
+                before = disputed_labels(labels.data).get(label['algorithm'], {})
+                answer = self.input(f"대체 정답 (쉼표 구분, 비우면 해제) "
+                                    f"[{', '.join(before.get('alternatives', [])) or '없음'}]: ").strip()
+                alternatives = [alt.strip() for alt in answer.split(',') if alt.strip()]
+                reason = self.input("근거: ").strip() if alternatives else ''
+                labels.set_disputed(label['algorithm'], alternatives, reason)
+                self.audit.record(agent_type, test_id, 'dispute', before=label,
+                                  note=f"alternatives={alternatives} {reason}".strip())
+                changed = True
+
+            elif action in ('n', 'new'):
+                field = LEGACY_FIELD if labels.is_legacy else self._pick_field()
+                algorithm = self.input("알고리즘: ").strip()
+                if not algorithm:
+                    continue
+                span = parse_line_range(self.input("라인 범위 (예: 10-42, 생략 가능): ").strip())
+                labels.add(field, algorithm, span)
+                self.audit.record(agent_type, test_id, 'add',
+                                  after={'field': field, 'algorithm': algorithm, 'span': span})
+                changed = True
+
+            elif action in ('s', 'skip'):
+                if changed:
+                    self.audit.record(agent_type, test_id, 'discard', note='unsaved changes discarded')
+                return 'skipped'
+
+            elif action in ('q', 'quit'):
+                if changed:
+                    self.audit.record(agent_type, test_id, 'discard', note='unsaved changes discarded')
+                return 'quit'
+
+            else:
+                print(self.HELP)
+
+    def _pick(self, labels: List[Dict[str, Any]], argument: str) -> Optional[Dict[str, Any]]:
+        try:
+            return labels[int(argument) - 1]
+        except (ValueError, IndexError):
+            print(f"⚠️  라벨 번호를 지정하세요 (1-{len(labels)})")
+            return None
+
+    def _pick_field(self) -> str:
+        for i, field in enumerate(LABEL_FIELDS, 1):
+            print(f"  {i}. {field}")
+        choice = self.input("필드 [1]: ").strip()
+        try:
+            return LABEL_FIELDS[int(choice) - 1] if choice else LABEL_FIELDS[0]
+        except (ValueError, IndexError):
+            return LABEL_FIELDS[0]
+
+    # ==================== 출력 ====================
+
+    def _paint(self, text: str, color: str) -> str:
+        return f"{color}{text}{_RESET}" if self.color else text
+
+    def render_source(self, source: str, labels: List[Dict[str, Any]]):
+        """라벨 span을 강조한 소스 출력 (span 주변 context 라인만 표시)"""
+        lines = source.splitlines()
+        marks: Dict[int, List[str]] = {}
+        for i, label in enumerate(labels, 1):
+            if label['span']:
+                for line_no in range(label['span'][0], label['span'][1] + 1):
+                    marks.setdefault(line_no, []).append(str(i))
+
+        if not marks:
+            print(f"(라인 범위가 지정된 라벨 없음 — 전체 {len(lines)}줄)")
+            for line_no, line in enumerate(lines[:40], 1):
+                print(f"     {line_no:4d} | {line}")
+            if len(lines) > 40:
+                print(f"     ... 외 {len(lines) - 40}줄")
+            return
+
+        visible = set()
+        for line_no in marks:
+            visible.update(range(line_no - self.context, line_no + self.context + 1))
+
+        previous = None
+        for line_no, line in enumerate(lines, 1):
+            if line_no not in visible:
+                continue
+            if previous is not None and line_no != previous + 1:
+                print("     ...")
+            previous = line_no
+            if line_no in marks:
+                gutter = self._paint(f"{','.join(marks[line_no]):>4}", _GUTTER)
+                print(f"{gutter} {line_no:4d} | {self._paint(line, _HIGHLIGHT)}")
+            else:
+                print(f"     {line_no:4d} | {line}")
+
+    def render_labels(self, labels: List[Dict[str, Any]], disputed: Dict[str, Dict[str, Any]] = None):
+        print("\n현재 라벨:")
+        if not labels:
+            print("  (없음)")
+        for i, label in enumerate(labels, 1):
+            span = format_line_range(label['span']) or '위치 없음'
+            line = f"  {i}. {label['algorithm']:<16} {span:<12} [{label['field']}]"
+            alternatives = (disputed or {}).get(label['algorithm'], {}).get('alternatives')
+            if alternatives:
+                line += f"  (disputed, 대체 정답: {', '.join(alternatives)})"
+            print(line)
+        print()
+
+    def render_diff(self, before: str, after: str, name: str):
+        print()
+        for line in difflib.unified_diff(before.splitlines(), after.splitlines(),
+                                         fromfile=f"a/{name}", tofile=f"b/{name}", lineterm=''):
+            if line.startswith('+') and not line.startswith('+++'):
+                print(self._paint(line, _ADDED))
+            elif line.startswith('-') and not line.startswith('---'):
+                print(self._paint(line, _REMOVED))
+            else:
+                print(line)
+        print()
+
+
+def main():
+    parser = argparse.ArgumentParser(description='Ground truth 라벨 리뷰 도구')
+    subparsers = parser.add_subparsers(dest='command', required=True)
+
+    review_parser = subparsers.add_parser('review', help='샘플별 라벨 대화형 리뷰')
+    review_parser.add_argument('--agent', default='source_code',
+                               choices=['source_code', 'assembly_binary', 'logs_config'],
+                               help='리뷰할 에이전트 타입')
+    review_parser.add_argument('--test-id', nargs='+', help='특정 샘플만 리뷰')
+    review_parser.add_argument('--only-unreviewed', action='store_true',
+                               help='감사 로그에 승인 기록이 없는 샘플만 리뷰')
+    review_parser.add_argument('--reviewer', help='감사 로그에 기록할 리뷰어 이름 (기본: $USER)')
+    review_parser.add_argument('--audit-log', help=f'감사 로그 경로 (기본 {AUDIT_LOG_PATH})')
+    review_parser.add_argument('--staging', action='store_true',
+                               help=f'corpus import로 스테이징한 샘플({STAGING_DIR})의 제안 라벨 리뷰')
+    review_parser.add_argument('--context', type=int, default=3, help='강조 라인 주변에 표시할 라인 수')
+    review_parser.add_argument('--no-color', action='store_true', help='ANSI 색상 비활성화')
+
+    args = parser.parse_args()
+
+    root = STAGING_DIR if args.staging else "data"
+    test_manager = TestCaseManager(
+        test_cases_dir=f"{root}/test_cases",
+        ground_truth_dir=f"{root}/ground_truth",
+        test_files_dir=f"{root}/test_files"
+    )
+    audit_log = args.audit_log or (str(Path(STAGING_DIR) / Path(AUDIT_LOG_PATH).name) if args.staging
+                                   else AUDIT_LOG_PATH)
+    reviewer = LabelReviewer(
+        test_manager,
+        AuditLog(audit_log, args.reviewer),
+        color=not args.no_color and sys.stdout.isatty(),
+        context=args.context
+    )
+
+    try:
+        reviewer.review(args.agent, args.test_id, args.only_unreviewed)
+    except (KeyboardInterrupt, EOFError):
+        print("\n⏹️  리뷰 중단 (저장되지 않은 변경은 버려졌습니다)")
+
+
+if __name__ == "__main__":
+    main()
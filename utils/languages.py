@@ -0,0 +1,90 @@
+"""
+언어별 채점 분해 (per-language breakdown)
+
+탐지기는 C, Java, Python, Go, Rust에서 고르게 잘하지 않습니다. 결과 행의 language(샘플 확장자 기준,
+utils.corpus.LANGUAGE_NAMES)로 요약을 나누고, 모델마다 한 언어의 계층 F1이 다른 언어보다 유의하게 낮으면 표시합니다.
+
+요약 (summary.by_language):
+    {언어: {'total', 'successful', 'success_rate', 'avg_accuracy', 'avg_f1',
+            'by_model': {모델: {'f1': 부트스트랩 신뢰구간, 'others_f1', 'others_n', 'diff', 'p_value', 'underperforming'}}}}
+
+부진 표시 (underperforming):
+    같은 모델의 그 언어 샘플 F1과 다른 모든 언어 샘플 F1을 순열 검정(utils.significance.permutation_test)으로 비교해,
+    언어 평균이 더 낮고 p < DEFAULT_ALPHA이며 양쪽 모두 MIN_LANGUAGE_SAMPLES개 이상일 때 표시합니다.
+    채점하지 못한 샘플(timeout/error/parse_failure/crash)은 요약의 계층 F1과 같이 0점으로 셉니다.
+    디렉토리 샘플 중 소스 파일이 없는 것(매니페스트)은 'manifest', 파일 경로가 없는 행은 'unknown'입니다.
+"""
+
+from pathlib import Path
+from typing import Dict, Any, Iterable, List, Optional
+
+from utils.run_control import STATUS_OK
+from utils.significance import DEFAULT_ALPHA, bootstrap_ci, permutation_test
+
+UNKNOWN_LANGUAGE = 'unknown'
+# 부진 검정에 필요한 최소 샘플 수 (그 언어와 나머지 언어 각각)
+MIN_LANGUAGE_SAMPLES = 3
+
+
+def sample_language(file_path: Optional[str]) -> str:
+    """샘플 경로 → 언어 이름 (utils.corpus의 코퍼스 통계와 같은 기준)"""
+    from utils.corpus import CorpusStatistics
+
+    if not file_path:
+        return UNKNOWN_LANGUAGE
+    return CorpusStatistics.language(Path(file_path))
+
+
+def sample_f1(result: Dict[str, Any]) -> Optional[float]:
+    """요약의 모델별 계층 F1과 같은 샘플 점수 (채점 대상이 아니면 None)"""
+    if result.get('hierarchical_scores'):
+        return result['hierarchical_scores'].get('f1', 0.0)
+    if result.get('status') not in (None, STATUS_OK) and result.get('ground_truth_hash'):
+        return 0.0
+    return None
+
+
+def summarize_languages(results: Iterable[Dict[str, Any]], alpha: float = DEFAULT_ALPHA,
+                        min_samples: int = MIN_LANGUAGE_SAMPLES) -> Dict[str, Dict[str, Any]]:
+    """결과 행 → 언어별 요약과 모델별 부진 표시"""
+    summary: Dict[str, Dict[str, Any]] = {}
+    scores: Dict[str, Dict[str, List[float]]] = {}
+    for result in results:
+        language = result.get('language') or UNKNOWN_LANGUAGE
+        stats = summary.setdefault(language, {'total': 0, 'successful': 0, 'avg_accuracy': 0.0, 'avg_f1': 0.0})
+        stats['total'] += 1
+        stats['avg_accuracy'] += result.get('accuracy_score', 0)
+        stats['avg_f1'] += (result.get('hierarchical_scores') or {}).get('f1', 0)
+        if result.get('success'):
+            stats['successful'] += 1
+        f1 = sample_f1(result)
+        if f1 is not None:
+            model_key = f"{result.get('provider', 'unknown')}/{result.get('model', 'unknown')}"
+            scores.setdefault(model_key, {}).setdefault(language, []).append(f1)
+
+    for stats in summary.values():
+        stats['avg_accuracy'] /= stats['total']
+        stats['avg_f1'] /= stats['total']
+        stats['success_rate'] = stats['successful'] / stats['total']
+        stats['by_model'] = {}
+
+    for model_key, by_language in sorted(scores.items()):
+        for language, values in by_language.items():
+            others = [value for other, rest in by_language.items() if other != language for value in rest]
+            entry = {'f1': bootstrap_ci(values), 'others_n': len(others),
+                     'others_f1': sum(others) / len(others) if others else None,
+                     'diff': None, 'p_value': None, 'underperforming': False}
+            if len(values) >= min_samples and len(others) >= min_samples:
+                test = permutation_test(others, values)
+                entry.update(diff=test['mean_diff'], p_value=test['p_value'],
+                             underperforming=test['mean_diff'] < 0 and test['p_value'] < alpha)
+            summary[language]['by_model'][model_key] = entry
+    return dict(sorted(summary.items(), key=lambda item: (-item[1]['total'], item[0])))
+
+
+def underperforming(by_language: Dict[str, Dict[str, Any]]) -> List[Dict[str, Any]]:
+    """부진 표시된 (모델, 언어) 목록 (차이가 큰 순)"""
+    flagged = [{'model': model_key, 'language': language, **entry}
+               for language, stats in by_language.items()
+               for model_key, entry in stats.get('by_model', {}).items() if entry['underperforming']]
+    return sorted(flagged, key=lambda entry: entry['diff'])
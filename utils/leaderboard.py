@@ -0,0 +1,356 @@
+"""
+리더보드
+
+여러 팀이 같은 코퍼스로 채점한 실행 결과를 모아 하나의 리더보드 JSON(기본 `results/leaderboard.json`)으로
+관리하고, markdown/HTML 표로 렌더링합니다.
+
+항목 (탐지기 × 버전 × 코퍼스 버전마다 1개, 같은 키로 다시 적재하면 교체):
+    detector         "프로바이더/모델 [구성]" (utils/run_compare.py의 detector_name)
+    version          탐지기 버전 (--detector-version, 없으면 결과 메타데이터의 detector_version)
+    corpus_version   채점에 쓰인 (agent_type, test_id, ground_truth_hash) 집합의 해시 (utils/results_store.py)
+    scores           지표별 평균과 부트스트랩 95% 신뢰구간 (utils/significance.py의 bootstrap_ci)
+    composite        트랙 가중 종합 점수와 트랙별 분해 (utils/composite.py, 가중치는 적재 시점의 --weights 파일)
+    date             실행 시작 날짜
+    source_sha256    적재한 결과 파일의 해시
+
+서명:
+    리더보드 전체(항목 목록)를 정렬된 키의 JSON으로 직렬화해 HMAC-SHA256으로 서명합니다.
+    키는 환경 변수 LEADERBOARD_SIGNING_KEY에서 읽으며, 파일에는 키 식별자(키 해시 앞 8자리)만 남습니다.
+    `verify`는 서명과 항목별 digest를 다시 계산해 파일이 적재 이후 수정되지 않았는지 확인합니다.
+
+순위는 코퍼스 버전별로 따로 매깁니다. 코퍼스가 다르면 점수를 직접 비교할 수 없기 때문입니다.
+한 코퍼스의 모든 항목에 종합 점수가 있으면 종합 점수로, 아니면 (예전 항목이나 RAG 효과 결과가 섞이면) RANKING_METRICS로
+순위를 매깁니다. 가중치를 바꾸면 같은 결과 파일을 다시 적재해야 종합 점수가 바뀝니다.
+
+사용법:
+    python -m utils.leaderboard ingest results/team_a.json --detector-version 1.2.0
+    python -m utils.leaderboard ingest results/llama_final.json --detector "llama3:8b [rag]"
+    python -m utils.leaderboard ingest results/team_a.json --weights config/benchmark.toml
+    python -m utils.leaderboard render --format html --output reports/leaderboard.html
+    python -m utils.leaderboard render --format html --lang en-US --branding config/benchmark.toml
+    python -m utils.leaderboard verify
+
+표의 문구는 --lang(ko-KR/en-US, utils/i18n.py의 카탈로그)으로, HTML의 조직 이름/로고/꼬리말은 설정 파일의 [report]로 정합니다.
+"""
+
+import argparse
+import hashlib
+import hmac
+import html
+import json
+import os
+import sys
+import time
+import tomllib
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from utils.composite import TRACKS, WEIGHTS_PATH, composite_score, format_breakdown, load_weights
+from utils.i18n import BRANDING_PATH, DEFAULT_LANG, LANGS, Branding, Catalog, html_footer, html_header, load_branding
+from utils.results_store import ResultsStore, corpus_version
+from utils.run_compare import COMPARE_METRICS, METRIC_NAMES, group_by_detector, select_detector
+from utils.significance import bootstrap_ci
+
+LEADERBOARD_PATH = "results/leaderboard.json"
+SIGNING_KEY_ENV = "LEADERBOARD_SIGNING_KEY"
+SIGNATURE_ALGORITHM = "HMAC-SHA256"
+
+# 순위 기준 (앞의 지표가 없으면 다음 지표 사용; RAG 효과 결과에는 계층 F1이 없음)
+RANKING_METRICS = ['hierarchical_f1', 'accuracy']
+
+
+def _canonical(value: Any) -> bytes:
+    return json.dumps(value, sort_keys=True, ensure_ascii=False, separators=(',', ':')).encode('utf-8')
+
+
+def entry_digest(entry: Dict[str, Any]) -> str:
+    """항목 내용 해시 (digest 필드 제외)"""
+    return hashlib.sha256(_canonical({k: v for k, v in entry.items() if k != 'digest'})).hexdigest()
+
+
+def key_id(key: bytes) -> str:
+    return hashlib.sha256(key).hexdigest()[:8]
+
+
+def sign(entries: List[Dict[str, Any]], key: bytes) -> Dict[str, str]:
+    return {
+        'algorithm': SIGNATURE_ALGORITHM,
+        'key_id': key_id(key),
+        'value': hmac.new(key, _canonical(entries), hashlib.sha256).hexdigest()
+    }
+
+
+def signing_key() -> Optional[bytes]:
+    value = os.environ.get(SIGNING_KEY_ENV)
+    return value.encode('utf-8') if value else None
+
+
+def build_entry(rows: List[Dict[str, Any]], detector: str, version: str, started_at: float,
+                source_sha256: str, weights: Optional[Dict[str, float]] = None) -> Dict[str, Any]:
+    """한 탐지기의 테스트별 행 → 리더보드 항목"""
+    scores = {}
+    for name, column in COMPARE_METRICS.items():
+        values = [float(r[column]) for r in rows if r.get(column) is not None]
+        if values:
+            interval = bootstrap_ci(values)
+            scores[name] = {key: interval[key] for key in ('mean', 'ci_low', 'ci_high', 'n')}
+
+    entry = {
+        'detector': detector,
+        'version': version,
+        'corpus_version': corpus_version(rows),
+        'tests': len(rows),
+        'scores': scores,
+        'date': datetime.fromtimestamp(started_at).date().isoformat(),
+        'source_sha256': source_sha256
+    }
+    # 트랙 점수는 benchmark_runner 결과 행에만 있음 (RAG 효과 결과는 종합 점수 없음)
+    if weights and any('track_scores' in r for r in rows):
+        composite = composite_score((r.get('track_scores') or {} for r in rows), weights)
+        if composite:
+            entry['composite'] = composite
+    entry['digest'] = entry_digest(entry)
+    return entry
+
+
+def ranking_score(entry: Dict[str, Any], use_composite: bool = True) -> Optional[float]:
+    if use_composite and entry.get('composite'):
+        return entry['composite']['score']
+    for name in RANKING_METRICS:
+        if name in entry['scores']:
+            return entry['scores'][name]['mean']
+    return None
+
+
+class Leaderboard:
+    """서명된 리더보드 파일"""
+
+    def __init__(self, path: str = LEADERBOARD_PATH):
+        self.path = Path(path)
+        self.entries: List[Dict[str, Any]] = []
+        self.signature: Optional[Dict[str, str]] = None
+        if self.path.exists():
+            with open(self.path, 'r', encoding='utf-8') as f:
+                data = json.load(f)
+            self.entries = data.get('entries', [])
+            self.signature = data.get('signature')
+
+    def ingest_file(self, path: str, version: Optional[str] = None, detector: Optional[str] = None,
+                    weights: Optional[Dict[str, float]] = None) -> List[Dict[str, Any]]:
+        """결과 파일의 탐지기별 항목을 추가(같은 키는 교체)하고 추가된 항목 반환"""
+        raw = Path(path).read_bytes()
+        parsed = ResultsStore.parse_results(json.loads(raw))
+        if parsed is None:
+            raise ValueError(f"{path}: unsupported results format")
+        rows, started_at, metadata = parsed
+
+        groups = group_by_detector(rows)
+        if detector:
+            name = select_detector(groups, detector)
+            groups = {name: groups[name]}
+
+        version = version or str(metadata.get('detector_version') or 'unversioned')
+        source_sha256 = hashlib.sha256(raw).hexdigest()
+        added = [
+            build_entry(group_rows, name, version, started_at, source_sha256, weights)
+            for name, group_rows in groups.items() if group_rows
+        ]
+        for entry in added:
+            key = (entry['detector'], entry['version'], entry['corpus_version'])
+            self.entries = [e for e in self.entries
+                            if (e['detector'], e['version'], e['corpus_version']) != key]
+            self.entries.append(entry)
+        return added
+
+    def save(self, key: bytes):
+        self.entries.sort(key=lambda e: (e['corpus_version'] or '', e['detector'], e['version']))
+        self.signature = sign(self.entries, key)
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+        with open(self.path, 'w', encoding='utf-8') as f:
+            json.dump({
+                'updated_at': datetime.fromtimestamp(time.time()).isoformat(timespec='seconds'),
+                'signature': self.signature,
+                'entries': self.entries
+            }, f, indent=2, ensure_ascii=False)
+            f.write('\n')
+
+    def verify(self, key: bytes) -> List[str]:
+        """서명/digest 검증 오류 목록 (비어 있으면 통과)"""
+        errors = []
+        if not self.signature:
+            errors.append("leaderboard is not signed")
+        else:
+            if self.signature.get('algorithm') != SIGNATURE_ALGORITHM:
+                errors.append(f"unsupported signature algorithm: {self.signature.get('algorithm')}")
+            if self.signature.get('key_id') != key_id(key):
+                errors.append(f"signed with key {self.signature.get('key_id')}, not {key_id(key)}")
+            elif not hmac.compare_digest(self.signature.get('value', ''), sign(self.entries, key)['value']):
+                errors.append("signature mismatch: entries were modified after signing")
+        for entry in self.entries:
+            if entry.get('digest') != entry_digest(entry):
+                errors.append(f"digest mismatch: {entry['detector']} {entry['version']}")
+        return errors
+
+    def ranked(self) -> Dict[str, List[Dict[str, Any]]]:
+        """코퍼스 버전별 순위 (최근 날짜의 코퍼스 먼저)"""
+        boards: Dict[str, List[Dict[str, Any]]] = {}
+        for entry in self.entries:
+            boards.setdefault(entry['corpus_version'] or 'unknown', []).append(entry)
+        for entries in boards.values():
+            # 종합 점수가 없는 항목이 섞인 코퍼스는 지표가 같아야 비교할 수 있으므로 RANKING_METRICS로
+            use_composite = all(e.get('composite') for e in entries)
+            entries.sort(key=lambda e: (-(ranking_score(e, use_composite) or 0.0), e['detector']))
+        return dict(sorted(boards.items(), key=lambda item: max(e['date'] for e in item[1]), reverse=True))
+
+
+def _format_score(entry: Dict[str, Any], name: str, with_ci: bool) -> str:
+    score = entry['scores'].get(name)
+    if not score:
+        return '-'
+    if with_ci:
+        return f"{score['mean']:.3f} [{score['ci_low']:.3f}, {score['ci_high']:.3f}]"
+    return f"{score['mean']:.3f}"
+
+
+def _format_composite(entry: Dict[str, Any], catalog: Catalog) -> str:
+    composite = entry.get('composite')
+    if not composite:
+        return '-'
+    names = {track: catalog.text(f"track.{track}") for track in TRACKS}
+    return f"{composite['score']:.3f} ({format_breakdown(composite, names)})"
+
+
+def _table_rows(entries: List[Dict[str, Any]], catalog: Catalog) -> List[List[str]]:
+    return [
+        [str(rank), entry['detector'], entry['version'], _format_composite(entry, catalog),
+         _format_score(entry, 'hierarchical_f1', True), _format_score(entry, 'accuracy', True),
+         _format_score(entry, 'success_rate', False), str(entry['tests']), entry['date']]
+        for rank, entry in enumerate(entries, 1)
+    ]
+
+
+def table_header(catalog: Catalog) -> List[str]:
+    column = {name: catalog.text(f"leaderboard.col.{name}")
+              for name in ('rank', 'detector', 'version', 'composite', 'tests', 'date')}
+    metric = {name: catalog.text(f"metric.{name}", METRIC_NAMES[name]) for name in METRIC_NAMES}
+    return [column['rank'], column['detector'], column['version'], column['composite'],
+            f"{metric['hierarchical_f1']} (95% CI)", f"{metric['accuracy']} (95% CI)", metric['success_rate'],
+            column['tests'], column['date']]
+
+
+def render_markdown(board: Leaderboard, lang: str = DEFAULT_LANG) -> str:
+    catalog = Catalog(lang)
+    header = table_header(catalog)
+    lines = [f"# 🏆 {catalog.text('leaderboard.title')}", '']
+    if board.signature:
+        lines += [f"{catalog.text('leaderboard.signature')}: {board.signature['algorithm']} "
+                  f"(key {board.signature['key_id']})", '']
+    for version, entries in board.ranked().items():
+        lines += [f"## {catalog.text('leaderboard.corpus', version=version)}", '',
+                  '| ' + ' | '.join(header) + ' |',
+                  '|' + '---|' * len(header)]
+        lines += ['| ' + ' | '.join(cell.replace('|', '\\|') for cell in row) + ' |'
+                  for row in _table_rows(entries, catalog)]
+        lines.append('')
+    return '\n'.join(lines)
+
+
+def render_html(board: Leaderboard, lang: str = DEFAULT_LANG, branding: Optional[Branding] = None) -> str:
+    catalog = Catalog(lang)
+    header = table_header(catalog)
+    parts = html_header(catalog.text('leaderboard.title'), '🏆',
+                        '<style>table{border-collapse:collapse}th,td{border:1px solid #ccc;padding:4px 8px}'
+                        'td:nth-child(n+4){text-align:right}</style></head>', catalog, branding)
+    if board.signature:
+        parts.append(f"<p>{html.escape(catalog.text('leaderboard.signature'))}: "
+                     f"{html.escape(board.signature['algorithm'])} (key {html.escape(board.signature['key_id'])})</p>")
+    for version, entries in board.ranked().items():
+        parts += [f"<h2>{html.escape(catalog.text('leaderboard.corpus', version=version))}</h2>", '<table>',
+                  '<tr>' + ''.join(f"<th>{html.escape(cell)}</th>" for cell in header) + '</tr>']
+        parts += ['<tr>' + ''.join(f"<td>{html.escape(cell)}</td>" for cell in row) + '</tr>'
+                  for row in _table_rows(entries, catalog)]
+        parts.append('</table>')
+    parts += html_footer(catalog, branding)
+    return '\n'.join(parts)
+
+
+def main():
+    parser = argparse.ArgumentParser(description='리더보드 관리')
+    parser.add_argument('--leaderboard', default=LEADERBOARD_PATH, help='리더보드 JSON 경로')
+    subparsers = parser.add_subparsers(dest='command', required=True)
+
+    ingest = subparsers.add_parser('ingest', help='채점된 실행 결과를 리더보드에 추가')
+    ingest.add_argument('files', nargs='+', help='결과 파일 (benchmark_runner / rag_effect JSON)')
+    ingest.add_argument('--detector-version', help='탐지기 버전')
+    ingest.add_argument('--detector', help='파일에서 적재할 탐지기 (부분 문자열, 기본: 전부)')
+    ingest.add_argument('--weights', default=WEIGHTS_PATH, help=f'종합 점수 가중치 TOML (기본: {WEIGHTS_PATH})')
+
+    render = subparsers.add_parser('render', help='리더보드 표 출력')
+    render.add_argument('--format', choices=['markdown', 'html'], default='markdown')
+    render.add_argument('--output', help='저장 경로 (기본: 표준 출력)')
+    render.add_argument('--lang', choices=LANGS, help='문구 언어 (기본: [report] lang, 없으면 ko-KR)')
+    render.add_argument('--branding', default=BRANDING_PATH, help=f'HTML 브랜딩 [report] 설정 TOML (기본: {BRANDING_PATH})')
+
+    subparsers.add_parser('verify', help='리더보드 서명 검증')
+    args = parser.parse_args()
+
+    board = Leaderboard(args.leaderboard)
+
+    if args.command == 'render':
+        try:
+            branding = load_branding(args.branding)
+        except ValueError as e:
+            parser.error(str(e))
+        lang = args.lang or branding.lang
+        text = render_markdown(board, lang) if args.format == 'markdown' else render_html(board, lang, branding)
+        if args.output:
+            Path(args.output).parent.mkdir(parents=True, exist_ok=True)
+            Path(args.output).write_text(text, encoding='utf-8')
+            print(f"💾 리더보드 저장: {args.output}")
+        else:
+            print(text)
+        return
+
+    key = signing_key()
+    if key is None:
+        print(f"❌ 서명 키가 없습니다: 환경 변수 {SIGNING_KEY_ENV}를 설정하세요")
+        sys.exit(2)
+
+    if args.command == 'verify':
+        errors = board.verify(key)
+        for error in errors:
+            print(f"❌ {error}")
+        if errors:
+            sys.exit(1)
+        print(f"✅ 서명 확인: 항목 {len(board.entries)}개 (key {key_id(key)})")
+        return
+
+    if board.entries and board.verify(key):
+        print("❌ 기존 리더보드의 서명이 맞지 않습니다. `verify`로 확인하세요")
+        sys.exit(1)
+
+    try:
+        weights = load_weights(args.weights)
+    except (ValueError, tomllib.TOMLDecodeError) as error:
+        print(f"❌ {error}")
+        sys.exit(2)
+
+    for path in args.files:
+        try:
+            added = board.ingest_file(path, args.detector_version, args.detector, weights)
+        except ValueError as error:
+            print(f"❌ {error}")
+            sys.exit(2)
+        for entry in added:
+            score = ranking_score(entry)
+            shown = f"{score:.3f}" if score is not None else '-'
+            print(f"➕ {entry['detector']} {entry['version']} "
+                  f"(코퍼스 {entry['corpus_version']}, 테스트 {entry['tests']}개, 점수 {shown})")
+
+    board.save(key)
+    print(f"💾 리더보드 저장: {board.path} (항목 {len(board.entries)}개, 서명 key {key_id(key)})")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,151 @@
+"""
+의존성 매니페스트 샘플 (manifest-inference 과제)
+
+매니페스트 샘플은 파일 하나가 아니라 디렉토리입니다. 패키지 매니페스트와 의존성을 얇게 감싼
+래퍼 코드만 들어 있고, 알고리즘 내부 구현은 없습니다.
+
+    data/test_files/dependency_manifest/<test_id>/
+        Cargo.toml | package.json | requirements.txt
+        src/...                     (래퍼 코드)
+
+탐지기에는 매니페스트를 먼저, 래퍼 코드를 그 다음에 `==== 경로 ====` 머리말을 붙여 이어서 전달합니다.
+
+Ground truth는 라벨마다 근거가 되는 의존성을 기록합니다:
+    "expected_findings": {"vulnerable_algorithms_detected": ["RSA"], "dependencies": {"RSA": ["rsa"]}, ...}
+
+DEPENDENCY_SURFACE는 잘 알려진 패키지가 제공하는 알고리즘 계열 표이며, lint가 라벨과 근거 의존성의
+조합을 검사하는 데 씁니다 (표에 없는 패키지는 검사하지 않음).
+"""
+
+import json
+import re
+import tomllib
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+MANIFEST_FILES = ['Cargo.toml', 'package.json', 'requirements.txt']
+WRAPPER_SUFFIXES = {'.rs', '.js', '.ts', '.py'}
+
+# 패키지 → 제공하는 알고리즘 계열 (crates.io / npm / PyPI 이름, 소문자)
+DEPENDENCY_SURFACE: Dict[str, List[str]] = {
+    # crates.io
+    'openssl': ['RSA', 'ECC', 'DSA', 'DH', 'AES', 'DES', 'RC4', 'Blowfish', 'Camellia', 'SEED', 'ARIA',
+                'ChaCha20', 'SHA-1', 'SHA-2', 'SHA-3', 'MD5', 'HMAC', 'Poly1305', 'PBKDF2', 'HKDF', 'scrypt'],
+    'ring': ['RSA', 'ECC', 'AES', 'GHASH', 'ChaCha20', 'Poly1305', 'SHA-1', 'SHA-2', 'HMAC', 'HKDF', 'PBKDF2'],
+    'rustls': ['RSA', 'ECC', 'AES', 'ChaCha20', 'SHA-2', 'HMAC', 'HKDF'],
+    'rsa': ['RSA'],
+    'k256': ['ECC', 'SHA-2'],
+    'p256': ['ECC', 'SHA-2'],
+    'p384': ['ECC', 'SHA-2'],
+    'ecdsa': ['ECC'],
+    'ed25519-dalek': ['ECC', 'SHA-2'],
+    'x25519-dalek': ['ECC'],
+    'dsa': ['DSA'],
+    'aes': ['AES'],
+    'aes-gcm': ['AES', 'GHASH'],
+    'des': ['DES'],
+    'rc4': ['RC4'],
+    'blowfish': ['Blowfish'],
+    'chacha20poly1305': ['ChaCha20', 'Poly1305'],
+    'sha1': ['SHA-1'],
+    'sha2': ['SHA-2'],
+    'sha3': ['SHA-3'],
+    'md-5': ['MD5'],
+    'md5': ['MD5'],
+    'hmac': ['HMAC'],
+    'pbkdf2': ['PBKDF2'],
+    'hkdf': ['HKDF'],
+    'scrypt': ['scrypt'],
+    # npm
+    'node-rsa': ['RSA', 'MD5', 'SHA-1', 'SHA-2'],
+    'elliptic': ['ECC'],
+    'crypto-js': ['AES', 'DES', 'RC4', 'MD5', 'SHA-1', 'SHA-2', 'SHA-3', 'RIPEMD', 'HMAC', 'PBKDF2'],
+    'jsonwebtoken': ['RSA', 'ECC', 'HMAC', 'SHA-2'],
+    'bcrypt': ['Blowfish'],
+    # PyPI
+    'pycryptodome': ['RSA', 'ECC', 'DSA', 'AES', 'DES', 'RC4', 'Blowfish', 'ChaCha20', 'SHA-1', 'SHA-2',
+                     'SHA-3', 'MD5', 'HMAC', 'PBKDF2', 'HKDF', 'scrypt'],
+    'cryptography': ['RSA', 'ECC', 'DSA', 'DH', 'AES', 'DES', 'ChaCha20', 'SHA-1', 'SHA-2', 'SHA-3', 'MD5',
+                     'HMAC', 'PBKDF2', 'HKDF', 'scrypt'],
+}
+
+CARGO_DEPENDENCY_TABLES = ('dependencies', 'dev-dependencies', 'build-dependencies')
+NPM_DEPENDENCY_TABLES = ('dependencies', 'devDependencies', 'peerDependencies', 'optionalDependencies')
+
+
+def is_manifest_sample(path: Path) -> bool:
+    """매니페스트를 포함한 디렉토리 샘플인지"""
+    return path.is_dir() and any((path / name).exists() for name in MANIFEST_FILES)
+
+
+def _cargo_dependencies(manifest: Dict[str, Any]) -> List[str]:
+    tables = [manifest.get(table, {}) for table in CARGO_DEPENDENCY_TABLES]
+    for target in manifest.get('target', {}).values():
+        tables.extend(target.get(table, {}) for table in CARGO_DEPENDENCY_TABLES)
+
+    names = []
+    for table in tables:
+        for alias, spec in table.items():
+            # `alias = { package = "real-name", ... }` 형태는 실제 패키지 이름으로
+            name = spec.get('package', alias) if isinstance(spec, dict) else alias
+            names.append(name)
+    return names
+
+
+def manifest_dependencies(sample_dir: Path) -> Dict[str, str]:
+    """샘플이 선언한 의존성 {패키지 이름(소문자): 선언한 매니페스트 파일}"""
+    found: Dict[str, str] = {}
+
+    cargo = sample_dir / 'Cargo.toml'
+    if cargo.exists():
+        with open(cargo, 'rb') as f:
+            for name in _cargo_dependencies(tomllib.load(f)):
+                found.setdefault(name.lower(), cargo.name)
+
+    npm = sample_dir / 'package.json'
+    if npm.exists():
+        with open(npm, 'r', encoding='utf-8') as f:
+            package = json.load(f)
+        for table in NPM_DEPENDENCY_TABLES:
+            for name in package.get(table, {}):
+                found.setdefault(name.lower(), npm.name)
+
+    pip = sample_dir / 'requirements.txt'
+    if pip.exists():
+        for line in pip.read_text(encoding='utf-8').splitlines():
+            match = re.match(r'\s*([A-Za-z0-9][A-Za-z0-9._\-]*)', line.split('#', 1)[0])
+            if match:
+                found.setdefault(match.group(1).lower(), pip.name)
+
+    return found
+
+
+def implied_families(dependency: str) -> Optional[List[str]]:
+    """패키지가 제공하는 알고리즘 계열 (표에 없으면 None)"""
+    return DEPENDENCY_SURFACE.get(dependency.lower())
+
+
+def sample_files(sample_dir: Path) -> List[Path]:
+    """탐지기에 전달할 파일 순서: 매니페스트 → 래퍼 코드"""
+    manifests = [sample_dir / name for name in MANIFEST_FILES if (sample_dir / name).exists()]
+    wrappers = sorted(
+        path for path in sample_dir.rglob('*')
+        if path.is_file() and path.suffix in WRAPPER_SUFFIXES
+    )
+    return manifests + wrappers
+
+
+def render_sample(sample_dir: Path) -> str:
+    """디렉토리 샘플을 탐지기 입력 하나로 이어 붙임"""
+    parts = []
+    for path in sample_files(sample_dir):
+        relative = path.relative_to(sample_dir).as_posix()
+        parts.append(f"==== {relative} ====\n{path.read_text(encoding='utf-8')}")
+    return '\n'.join(parts)
+
+
+def recorded_dependencies(ground_truth: Optional[Dict[str, Any]]) -> Dict[str, List[str]]:
+    """ground truth의 라벨별 근거 의존성 {알고리즘: [패키지]}"""
+    if not ground_truth:
+        return {}
+    return dict(ground_truth.get('expected_findings', {}).get('dependencies', {}))
@@ -1,8 +1,125 @@
 import json
-from typing import Dict, Any, List
+from typing import Dict, Any, List, Optional, Tuple
 import difflib
 from datetime import datetime
 
+from utils.uncertainty import acceptable_answers, label_alternatives
+
+# 정확도(accuracy_score) 가중치: 취약 알고리즘 70%, 카테고리 20%, 신뢰도 범위 10% (앞의 둘은 정답에 있을 때만 분모에 들어감)
+ACCURACY_WEIGHTS = {'vulnerable_algorithms': 0.7, 'algorithm_categories': 0.2, 'confidence_range': 0.1}
+# 한국 알고리즘 보너스 (분모에 넣지 않는 최대 5% 추가 점수)
+KOREAN_BONUS = 0.05
+# 정확도가 이 값 이상이면 success
+SUCCESS_THRESHOLD = 0.6
+
+# 알고리즘 이름 변형 (정답 이름에 앞쪽 조건 문자열이 처음 들어 있는 항목의 변형 중 하나라도 응답에 있으면 탐지)
+ALGORITHM_VARIATIONS: List[Tuple[Tuple[str, ...], List[str]]] = [
+    (('rsa',), ['rsa']),
+    (('ecc', 'ecdsa'), ['ecc', 'ecdsa', 'ecdh']),
+    (('dsa',), ['dsa']),
+    (('dh', 'diffie'), ['diffie-hellman', 'dh']),
+    (('seed',), ['seed']),
+    (('aria',), ['aria']),
+    (('hight',), ['hight']),
+    (('lea',), ['lea']),
+    (('kcdsa',), ['kcdsa', 'ec-kcdsa']),
+    (('3des', 'triple des', 'tdes'), ['3des', 'triple des', 'triple-des', 'tdes']),
+    (('des',), ['des']),
+    (('rc4', 'arcfour'), ['rc4', 'arcfour']),
+    (('blowfish', 'twofish'), ['blowfish', 'twofish']),
+    (('md5',), ['md5']),
+    (('sha1', 'sha-1'), ['sha1', 'sha-1']),
+    (('a5', 'a5/1'), ['a5', 'a5/1', 'a5-1']),
+    (('trivium',), ['trivium']),
+    (('misty1', 'misty'), ['misty1', 'misty']),
+    (('tea',), ['tea']),
+    (('crc32', 'crc'), ['crc32', 'crc']),
+    (('salsa20', 'salsa'), ['salsa20', 'salsa']),
+    (('sha256', 'sha-256'), ['sha256', 'sha-256']),
+    (('hmac',), ['hmac']),
+    (('chacha20', 'chacha'), ['chacha20', 'chacha']),
+    (('poly1305',), ['poly1305']),
+    (('aes',), ['aes', 'rijndael']),
+    (('bls',), ['bls']),
+    (('kyber',), ['kyber']),
+    (('vdf',), ['vdf']),
+    (('ghash',), ['ghash']),
+    (('pss',), ['pss', 'rsa-pss']),
+    (('blake2', 'blake'), ['blake2', 'blake2b', 'blake']),
+    (('montgomery',), ['montgomery']),
+    (('has-160', 'has160'), ['has-160', 'has160']),
+    (('lsh',), ['lsh']),
+    (('ecdh',), ['ecdh', 'ecdhe']),
+    (('dilithium',), ['dilithium']),
+    (('sphincs',), ['sphincs', 'sphincs+']),
+    (('ntru',), ['ntru']),
+    (('paillier',), ['paillier']),
+    (('bgn',), ['bgn']),
+    (('shamir',), ['shamir']),
+    (('feldman',), ['feldman']),
+    (('x25519',), ['x25519', 'curve25519']),
+    (('ed25519',), ['ed25519', 'edwards']),
+    (('xchacha20',), ['xchacha20', 'xchacha']),
+    (('double ratchet', 'doubleratchet'), ['double ratchet', 'doubleratchet', 'signal']),
+    (('ikev2', 'ike'), ['ikev2', 'ike']),
+    (('ocsp',), ['ocsp']),
+    (('pbkdf2',), ['pbkdf2']),
+    (('pbkdf1',), ['pbkdf1', 'pbkdf-1', 'iterated hash', 'iterative hash']),
+    (('hkdf',), ['hkdf']),
+    (('gcm',), ['gcm']),
+    (('ripemd',), ['ripemd', 'ripemd-160', 'ripemd160']),
+    (('scrypt',), ['scrypt']),
+    (('keccak',), ['keccak', 'keccak-256']),
+    (('secp256k1',), ['secp256k1', 'secp-256k1']),
+    (('shake',), ['shake', 'shake256']),
+    (('siphash',), ['siphash', 'siphash-2-4']),
+    (('rc2',), ['rc2']),
+]
+
+# 카테고리 키워드 (정답 카테고리의 키워드 중 하나라도 응답에 있으면 탐지, 표에 없는 카테고리는 이름 자체)
+CATEGORY_KEYWORDS: Dict[str, List[str]] = {
+    'shor_vulnerable': ['rsa', 'ecc', 'dh', 'dsa', 'ecdsa', 'kcdsa', 'bls', 'paillier', 'bgn', 'x25519', 'ed25519',
+        'secp256k1', 'ecdh', 'ecdhe', 'feldman'],
+    'grover_vulnerable': ['aes', 'des', 'md5', 'sha', 'sha256', 'sha-256', '3des', 'tea', 'salsa20', 'chacha20',
+        'poly1305', 'hmac', 'crc32', 'seed', 'aria', 'hight', 'lea', 'blake2', 'ghash', 'xchacha20', 'pbkdf2',
+        'pbkdf1', 'hkdf', 'gcm', 'ripemd', 'scrypt', 'keccak', 'shake', 'shamir', 'siphash'],
+    'classical_vulnerable': ['a5', 'trivium', 'rc4', 'rc2', 'crc32', 'md5', 'sha1'],
+    'public_key': ['rsa', 'ecc', 'dh', 'dsa', 'ecdsa', 'kcdsa', 'bls', 'pss', 'paillier', 'bgn', 'x25519', 'ed25519',
+        'secp256k1', 'ecdh', 'ecdhe'],
+    'symmetric': ['aes', 'des', '3des', 'tea', 'salsa20', 'chacha20', 'seed', 'aria', 'hight', 'lea', 'xchacha20'],
+    'symmetric_key': ['aes', 'des', '3des', 'tea', 'salsa20', 'chacha20', 'seed', 'aria', 'hight', 'lea',
+        'xchacha20', 'rc2', 'rc4'],
+    'hash_functions': ['md5', 'sha', 'sha256', 'sha-256', 'sha1', 'hmac', 'crc32', 'has-160', 'blake2', 'blake2b',
+        'ghash', 'ripemd', 'keccak', 'shake', 'siphash'],
+    'hash_function': ['md5', 'sha', 'sha256', 'sha-256', 'sha1', 'hmac', 'crc32', 'has-160', 'blake2', 'blake2b',
+        'ghash', 'ripemd', 'keccak', 'shake', 'siphash'],
+    'mac': ['hmac', 'poly1305', 'ghash', 'siphash'],
+    'korean_algorithms': ['seed', 'aria', 'hight', 'lea', 'kcdsa', 'has-160', 'lsh', 'ec-kcdsa', 'ecdh'],
+    'post_quantum': ['kyber', 'dilithium', 'sphincs', 'ntru'],
+    'pairing_based': ['bls', 'bgn'],
+    'verification_functions': ['vdf'],
+    'weak_hash': ['md5', 'sha1', 'crc32'],
+    'homomorphic_encryption': ['paillier', 'bgn'],
+    'secret_sharing': ['shamir', 'feldman'],
+    'key_derivation': ['pbkdf2', 'pbkdf1', 'scrypt', 'hkdf'],
+    'authenticated_encryption': ['gcm'],
+    'protocols': ['ikev2', 'ocsp', 'double ratchet'],
+}
+
+# 한국 알고리즘 이름 변형 (표에 없는 알고리즘은 이름 자체)
+KOREAN_VARIATIONS: Dict[str, List[str]] = {
+    'seed': ['seed'],
+    'aria': ['aria'],
+    'hight': ['hight'],
+    'lea': ['lea'],
+    'kcdsa': ['kcdsa', 'ec-kcdsa'],
+    'has-160': ['has-160', 'has160'],
+    'lsh': ['lsh'],
+    'ec-kcdsa': ['ec-kcdsa', 'eckcdsa'],
+    'ecdh': ['ecdh'],
+}
+
+
 class MetricsCalculator:
     @staticmethod
     def calculate_accuracy(actual_response: Dict[str, Any], ground_truth: Dict[str, Any]) -> float:
@@ -14,22 +131,23 @@ class MetricsCalculator:
 
         expected_findings = ground_truth.get('expected_findings', {})
         actual_findings = actual_response.get('analysis_results', {})
+        alternatives = label_alternatives(ground_truth)
 
         # Vulnerable Algorithm Detection Accuracy (70% weight)
         if expected_findings.get('vulnerable_algorithms_detected'):
             vuln_accuracy = MetricsCalculator._calculate_vulnerable_algorithm_accuracy(
-                actual_findings, expected_findings['vulnerable_algorithms_detected']
+                actual_findings, expected_findings['vulnerable_algorithms_detected'], alternatives
             )
-            accuracy_score += vuln_accuracy * 0.7
-            total_weight += 0.7
+            accuracy_score += vuln_accuracy * ACCURACY_WEIGHTS['vulnerable_algorithms']
+            total_weight += ACCURACY_WEIGHTS['vulnerable_algorithms']
 
         # Algorithm Category Detection (20% weight)
         if expected_findings.get('algorithm_categories'):
             category_accuracy = MetricsCalculator._calculate_category_accuracy(
                 actual_findings, expected_findings['algorithm_categories']
             )
-            accuracy_score += category_accuracy * 0.2
-            total_weight += 0.2
+            accuracy_score += category_accuracy * ACCURACY_WEIGHTS['algorithm_categories']
+            total_weight += ACCURACY_WEIGHTS['algorithm_categories']
 
         # Confidence Score Validation (10% weight)
         confidence_score = actual_response.get('confidence_score', 0.0)
@@ -44,22 +162,23 @@ class MetricsCalculator:
             )
             confidence_validity = max(0.0, 1.0 - confidence_diff)
 
-        accuracy_score += confidence_validity * 0.1
-        total_weight += 0.1
+        accuracy_score += confidence_validity * ACCURACY_WEIGHTS['confidence_range']
+        total_weight += ACCURACY_WEIGHTS['confidence_range']
 
         # Korean Algorithm Bonus (최대 5% 추가 점수)
         if expected_findings.get('korean_algorithms_detected'):
             korean_accuracy = MetricsCalculator._calculate_korean_algorithm_accuracy(
-                actual_findings, expected_findings['korean_algorithms_detected']
+                actual_findings, expected_findings['korean_algorithms_detected'], alternatives
             )
-            korean_bonus = korean_accuracy * 0.05  # 5% 보너스
+            korean_bonus = korean_accuracy * KOREAN_BONUS  # 5% 보너스
             accuracy_score += korean_bonus
             # total_weight는 증가시키지 않음 (보너스이므로)
 
         return accuracy_score / total_weight if total_weight > 0 else 0.0
 
     @staticmethod
-    def _calculate_vulnerable_algorithm_accuracy(actual_findings: Dict[str, Any], expected_algorithms: List[str]) -> float:
+    def _calculate_vulnerable_algorithm_accuracy(actual_findings: Dict[str, Any], expected_algorithms: List[str],
+                                                 alternatives: Dict[str, List[str]] = None) -> float:
         if not expected_algorithms:
             return 1.0
 
@@ -67,139 +186,30 @@ class MetricsCalculator:
         found_count = 0
 
         for algorithm in expected_algorithms:
-            algorithm_lower = algorithm.lower()
-
-            # Check for exact algorithm name matches
-            if algorithm_lower in actual_text:
+            # disputed 라벨은 대체 정답 중 하나만 보고해도 탐지로 인정
+            if any(MetricsCalculator._mentions_algorithm(answer, actual_text)
+                   for answer in acceptable_answers(algorithm, alternatives)):
                 found_count += 1
-                continue
-
-            # Check for exact algorithm names only (strict matching)
-            algorithm_variations = []
-            if 'rsa' in algorithm_lower:
-                algorithm_variations = ['rsa']
-            elif 'ecc' in algorithm_lower or 'ecdsa' in algorithm_lower:
-                algorithm_variations = ['ecc', 'ecdsa', 'ecdh']
-            elif 'dsa' in algorithm_lower:
-                algorithm_variations = ['dsa']
-            elif 'dh' in algorithm_lower or 'diffie' in algorithm_lower:
-                algorithm_variations = ['diffie-hellman', 'dh']
-            elif 'seed' in algorithm_lower:
-                algorithm_variations = ['seed']
-            elif 'aria' in algorithm_lower:
-                algorithm_variations = ['aria']
-            elif 'hight' in algorithm_lower:
-                algorithm_variations = ['hight']
-            elif 'lea' in algorithm_lower:
-                algorithm_variations = ['lea']
-            elif 'kcdsa' in algorithm_lower:
-                algorithm_variations = ['kcdsa', 'ec-kcdsa']
-            elif '3des' in algorithm_lower or 'triple des' in algorithm_lower or 'tdes' in algorithm_lower:
-                algorithm_variations = ['3des', 'triple des', 'triple-des', 'tdes']
-            elif 'des' in algorithm_lower:
-                algorithm_variations = ['des']
-            elif 'rc4' in algorithm_lower or 'arcfour' in algorithm_lower:
-                algorithm_variations = ['rc4', 'arcfour']
-            elif 'blowfish' in algorithm_lower or 'twofish' in algorithm_lower:
-                algorithm_variations = ['blowfish', 'twofish']
-            elif 'md5' in algorithm_lower:
-                algorithm_variations = ['md5']
-            elif 'sha1' in algorithm_lower or 'sha-1' in algorithm_lower:
-                algorithm_variations = ['sha1', 'sha-1']
-            elif 'a5' in algorithm_lower or 'a5/1' in algorithm_lower:
-                algorithm_variations = ['a5', 'a5/1', 'a5-1']
-            elif 'trivium' in algorithm_lower:
-                algorithm_variations = ['trivium']
-            elif 'misty1' in algorithm_lower or 'misty' in algorithm_lower:
-                algorithm_variations = ['misty1', 'misty']
-            elif 'tea' in algorithm_lower:
-                algorithm_variations = ['tea']
-            elif 'crc32' in algorithm_lower or 'crc' in algorithm_lower:
-                algorithm_variations = ['crc32', 'crc']
-            elif 'salsa20' in algorithm_lower or 'salsa' in algorithm_lower:
-                algorithm_variations = ['salsa20', 'salsa']
-            elif 'sha256' in algorithm_lower or 'sha-256' in algorithm_lower:
-                algorithm_variations = ['sha256', 'sha-256']
-            elif 'hmac' in algorithm_lower:
-                algorithm_variations = ['hmac']
-            elif 'chacha20' in algorithm_lower or 'chacha' in algorithm_lower:
-                algorithm_variations = ['chacha20', 'chacha']
-            elif 'poly1305' in algorithm_lower:
-                algorithm_variations = ['poly1305']
-            elif 'aes' in algorithm_lower:
-                algorithm_variations = ['aes', 'rijndael']
-            elif 'bls' in algorithm_lower:
-                algorithm_variations = ['bls']
-            elif 'kyber' in algorithm_lower:
-                algorithm_variations = ['kyber']
-            elif 'vdf' in algorithm_lower:
-                algorithm_variations = ['vdf']
-            elif 'ghash' in algorithm_lower:
-                algorithm_variations = ['ghash']
-            elif 'pss' in algorithm_lower:
-                algorithm_variations = ['pss', 'rsa-pss']
-            elif 'blake2' in algorithm_lower or 'blake' in algorithm_lower:
-                algorithm_variations = ['blake2', 'blake2b', 'blake']
-            elif 'montgomery' in algorithm_lower:
-                algorithm_variations = ['montgomery']
-            elif 'has-160' in algorithm_lower or 'has160' in algorithm_lower:
-                algorithm_variations = ['has-160', 'has160']
-            elif 'lsh' in algorithm_lower:
-                algorithm_variations = ['lsh']
-            elif 'ecdh' in algorithm_lower:
-                algorithm_variations = ['ecdh', 'ecdhe']
-            elif 'dilithium' in algorithm_lower:
-                algorithm_variations = ['dilithium']
-            elif 'sphincs' in algorithm_lower:
-                algorithm_variations = ['sphincs', 'sphincs+']
-            elif 'ntru' in algorithm_lower:
-                algorithm_variations = ['ntru']
-            elif 'paillier' in algorithm_lower:
-                algorithm_variations = ['paillier']
-            elif 'bgn' in algorithm_lower:
-                algorithm_variations = ['bgn']
-            elif 'shamir' in algorithm_lower:
-                algorithm_variations = ['shamir']
-            elif 'feldman' in algorithm_lower:
-                algorithm_variations = ['feldman']
-            elif 'x25519' in algorithm_lower:
-                algorithm_variations = ['x25519', 'curve25519']
-            elif 'ed25519' in algorithm_lower:
-                algorithm_variations = ['ed25519', 'edwards']
-            elif 'xchacha20' in algorithm_lower:
-                algorithm_variations = ['xchacha20', 'xchacha']
-            elif 'double ratchet' in algorithm_lower or 'doubleratchet' in algorithm_lower:
-                algorithm_variations = ['double ratchet', 'doubleratchet', 'signal']
-            elif 'ikev2' in algorithm_lower or 'ike' in algorithm_lower:
-                algorithm_variations = ['ikev2', 'ike']
-            elif 'ocsp' in algorithm_lower:
-                algorithm_variations = ['ocsp']
-            elif 'pbkdf2' in algorithm_lower:
-                algorithm_variations = ['pbkdf2']
-            elif 'gcm' in algorithm_lower:
-                algorithm_variations = ['gcm']
-            elif 'ripemd' in algorithm_lower:
-                algorithm_variations = ['ripemd', 'ripemd-160', 'ripemd160']
-            elif 'scrypt' in algorithm_lower:
-                algorithm_variations = ['scrypt']
-            elif 'keccak' in algorithm_lower:
-                algorithm_variations = ['keccak', 'keccak-256']
-            elif 'secp256k1' in algorithm_lower:
-                algorithm_variations = ['secp256k1', 'secp-256k1']
-            elif 'shake' in algorithm_lower:
-                algorithm_variations = ['shake', 'shake256']
-            elif 'siphash' in algorithm_lower:
-                algorithm_variations = ['siphash', 'siphash-2-4']
-            elif 'rc2' in algorithm_lower:
-                algorithm_variations = ['rc2']
-
-            for variation in algorithm_variations:
-                if variation in actual_text:
-                    found_count += 1
-                    break
 
         return found_count / len(expected_algorithms)
 
+    @staticmethod
+    def _mentions_algorithm(algorithm: str, actual_text: str) -> bool:
+        algorithm_lower = algorithm.lower()
+
+        # Check for exact algorithm name matches
+        if algorithm_lower in actual_text:
+            return True
+
+        return any(variation in actual_text
+                   for variation in MetricsCalculator._algorithm_variations(algorithm_lower))
+
+    @staticmethod
+    def _algorithm_variations(algorithm_lower: str) -> List[str]:
+        # Check for exact algorithm names only (strict matching)
+        return next((list(variations) for triggers, variations in ALGORITHM_VARIATIONS
+                     if any(trigger in algorithm_lower for trigger in triggers)), [])
+
     @staticmethod
     def _calculate_category_accuracy(actual_findings: Dict[str, Any], expected_categories: List[str]) -> float:
         if not expected_categories:
@@ -208,31 +218,10 @@ class MetricsCalculator:
         actual_text = json.dumps(actual_findings).lower()
         found_count = 0
 
-        category_keywords = {
-            'shor_vulnerable': ['rsa', 'ecc', 'dh', 'dsa', 'ecdsa', 'kcdsa', 'bls', 'paillier', 'bgn', 'x25519', 'ed25519', 'secp256k1', 'ecdh', 'ecdhe', 'feldman'],
-            'grover_vulnerable': ['aes', 'des', 'md5', 'sha', 'sha256', 'sha-256', '3des', 'tea', 'salsa20', 'chacha20', 'poly1305', 'hmac', 'crc32', 'seed', 'aria', 'hight', 'lea', 'blake2', 'ghash', 'xchacha20', 'pbkdf2', 'gcm', 'ripemd', 'scrypt', 'keccak', 'shake', 'shamir', 'siphash'],
-            'classical_vulnerable': ['a5', 'trivium', 'rc4', 'rc2', 'crc32', 'md5', 'sha1'],
-            'public_key': ['rsa', 'ecc', 'dh', 'dsa', 'ecdsa', 'kcdsa', 'bls', 'pss', 'paillier', 'bgn', 'x25519', 'ed25519', 'secp256k1', 'ecdh', 'ecdhe'],
-            'symmetric': ['aes', 'des', '3des', 'tea', 'salsa20', 'chacha20', 'seed', 'aria', 'hight', 'lea', 'xchacha20'],
-            'symmetric_key': ['aes', 'des', '3des', 'tea', 'salsa20', 'chacha20', 'seed', 'aria', 'hight', 'lea', 'xchacha20', 'rc2', 'rc4'],
-            'hash_functions': ['md5', 'sha', 'sha256', 'sha-256', 'sha1', 'hmac', 'crc32', 'has-160', 'blake2', 'blake2b', 'ghash', 'ripemd', 'keccak', 'shake', 'siphash'],
-            'hash_function': ['md5', 'sha', 'sha256', 'sha-256', 'sha1', 'hmac', 'crc32', 'has-160', 'blake2', 'blake2b', 'ghash', 'ripemd', 'keccak', 'shake', 'siphash'],
-            'mac': ['hmac', 'poly1305', 'ghash', 'siphash'],
-            'korean_algorithms': ['seed', 'aria', 'hight', 'lea', 'kcdsa', 'has-160', 'lsh', 'ec-kcdsa', 'ecdh'],
-            'post_quantum': ['kyber', 'dilithium', 'sphincs', 'ntru'],
-            'pairing_based': ['bls', 'bgn'],
-            'verification_functions': ['vdf'],
-            'weak_hash': ['md5', 'sha1', 'crc32'],
-            'homomorphic_encryption': ['paillier', 'bgn'],
-            'secret_sharing': ['shamir', 'feldman'],
-            'key_derivation': ['pbkdf2', 'scrypt'],
-            'authenticated_encryption': ['gcm'],
-            'protocols': ['ikev2', 'ocsp', 'double ratchet']
-        }
 
         for category in expected_categories:
             category_lower = category.lower()
-            keywords = category_keywords.get(category_lower, [category_lower])
+            keywords = CATEGORY_KEYWORDS.get(category_lower, [category_lower])
 
             for keyword in keywords:
                 if keyword in actual_text:
@@ -242,28 +231,24 @@ class MetricsCalculator:
         return found_count / len(expected_categories)
 
     @staticmethod
-    def _calculate_korean_algorithm_accuracy(actual_findings: Dict[str, Any], expected_korean_algs: List[str]) -> float:
+    def _calculate_korean_algorithm_accuracy(actual_findings: Dict[str, Any], expected_korean_algs: List[str],
+                                             alternatives: Dict[str, List[str]] = None) -> float:
         if not expected_korean_algs:
             return 1.0
 
         actual_text = json.dumps(actual_findings).lower()
         found_count = 0
 
-        korean_variations = {
-            'seed': ['seed'],
-            'aria': ['aria'],
-            'hight': ['hight'],
-            'lea': ['lea'],
-            'kcdsa': ['kcdsa', 'ec-kcdsa'],
-            'has-160': ['has-160', 'has160'],
-            'lsh': ['lsh'],
-            'ec-kcdsa': ['ec-kcdsa', 'eckcdsa'],
-            'ecdh': ['ecdh']
-        }
 
         for korean_alg in expected_korean_algs:
+            # 대체 정답(예: HAS-160 대신 SHA-1)은 일반 알고리즘 매칭 규칙으로 확인
+            if any(MetricsCalculator._mentions_algorithm(answer, actual_text)
+                   for answer in acceptable_answers(korean_alg, alternatives)[1:]):
+                found_count += 1
+                continue
+
             alg_lower = korean_alg.lower()
-            variations = korean_variations.get(alg_lower, [alg_lower])
+            variations = KOREAN_VARIATIONS.get(alg_lower, [alg_lower])
 
             for variation in variations:
                 if variation in actual_text:
@@ -272,6 +257,187 @@ class MetricsCalculator:
 
         return found_count / len(expected_korean_algs)
 
+    @staticmethod
+    def get_expected_labels(ground_truth: Dict[str, Any]) -> List[str]:
+        """Ground truth에서 채점 대상 알고리즘 라벨 목록 추출 (중복 제거, 순서 유지)"""
+        expected_findings = ground_truth.get('expected_findings', {})
+        labels = []
+        for field in ['vulnerable_algorithms_detected', 'korean_algorithms_detected']:
+            for label in expected_findings.get(field, []):
+                if label not in labels:
+                    labels.append(label)
+
+        # 레거시 형식 (vulnerability_analysis.quantum_vulnerable_algorithms) — 양자 내성 항목은 제외
+        analysis = ground_truth.get('vulnerability_analysis')
+        if isinstance(analysis, dict):
+            for entry in analysis.get('quantum_vulnerable_algorithms', []):
+                if MetricsCalculator._is_quantum_safe_entry(entry):
+                    continue
+                if entry.get('algorithm') and entry['algorithm'] not in labels:
+                    labels.append(entry['algorithm'])
+        return labels
+
+    @staticmethod
+    def _is_quantum_safe_entry(entry: Dict[str, Any]) -> bool:
+        return str(entry.get('category', '')).startswith('post_quantum')
+
+    @staticmethod
+    def get_quantum_safe_labels(ground_truth: Dict[str, Any]) -> List[str]:
+        """취약으로 보고하면 안 되는 양자 내성(PQC) 알고리즘 라벨 목록
+
+        expected_findings 형식:  "quantum_safe_algorithms": ["ML-KEM-768", ...]
+        레거시 형식:              quantum_vulnerable_algorithms 항목 중 category가 post_quantum_safe인 것
+        """
+        labels = list(ground_truth.get('expected_findings', {}).get('quantum_safe_algorithms', []))
+        analysis = ground_truth.get('vulnerability_analysis')
+        if isinstance(analysis, dict):
+            for entry in analysis.get('quantum_vulnerable_algorithms', []):
+                if MetricsCalculator._is_quantum_safe_entry(entry) and entry.get('algorithm') \
+                        and entry['algorithm'] not in labels:
+                    labels.append(entry['algorithm'])
+        return labels
+
+    @staticmethod
+    def calculate_quantum_safe_misclassification(predicted_labels: List[Any],
+                                                 ground_truth: Dict[str, Any]) -> Optional[Dict[str, Any]]:
+        """양자 내성 알고리즘을 양자 취약으로 잘못 보고했는지 평가
+
+        예측 라벨(탐지기가 취약하다고 보고한 알고리즘)이 양자 내성 라벨과 같은 계열이거나
+        post_quantum 카테고리 자체이면 오분류로 봅니다. 양자 내성 라벨이 없는 샘플은 None.
+        """
+        from utils.taxonomy import AlgorithmTaxonomy
+
+        safe_labels = MetricsCalculator.get_quantum_safe_labels(ground_truth)
+        if not safe_labels:
+            return None
+
+        taxonomy = AlgorithmTaxonomy()
+        predicted_nodes = [taxonomy.resolve(label) for label in predicted_labels]
+        misclassified = []
+        for label in safe_labels:
+            category, family, _ = taxonomy.resolve(label)
+            for predicted in predicted_nodes:
+                same_family = family is not None and predicted[1] == family
+                whole_category = predicted[0] == category and predicted[1] is None
+                if same_family or whole_category:
+                    misclassified.append({'label': label, 'predicted': taxonomy.format_node(predicted)})
+                    break
+
+        return {
+            'safe_labels': safe_labels,
+            'misclassified': misclassified,
+            'misclassification_rate': len(misclassified) / len(safe_labels)
+        }
+
+    @staticmethod
+    def calculate_hierarchical_scores(predicted_labels: List[Any], ground_truth: Dict[str, Any],
+                                      policy: Any = None) -> Dict[str, Any]:
+        """계층 라벨(카테고리/계열/변형) 기반 precision/recall/F1
+
+        탐지기가 어느 단계의 라벨을 출력하든 분류 체계를 따라 점수를 전파합니다.
+        각 정답 라벨은 가장 높은 점수를 주는 예측으로, 각 예측은 가장 높은 점수를 받는
+        정답으로 평가합니다. 전파 규칙은 utils/taxonomy.py의 HIERARCHY_POLICIES 참조.
+        """
+        from utils.attributes import compare_attributes, expected_attributes, merge_attributes, parse_attributes, \
+            recorded_attributes
+        from utils.taxonomy import AlgorithmTaxonomy
+        from utils.usage import default_usage, label_usage, recorded_usage
+        from utils.weakness import label_weakness, recorded_weakness
+
+        taxonomy = AlgorithmTaxonomy()
+        policy = taxonomy.get_policy(policy)
+        alternatives = label_alternatives(ground_truth)
+        attributes = recorded_attributes(ground_truth)
+        weakness = recorded_weakness(ground_truth)
+        usage = recorded_usage(ground_truth)
+        fallback_usage = default_usage(ground_truth)
+        expected_labels = MetricsCalculator.get_expected_labels(ground_truth)
+
+        # disputed 라벨은 원래 라벨과 대체 정답 중 가장 높은 점수를 주는 쪽으로 채점
+        expected_groups = [
+            [taxonomy.resolve(answer) for answer in acceptable_answers(label, alternatives)]
+            for label in expected_labels
+        ]
+        predicted_nodes = []
+        predicted_attributes: Dict[Any, List[Dict[str, Any]]] = {}
+        for label in predicted_labels:
+            node = taxonomy.resolve(label)
+            if node not in predicted_nodes:
+                predicted_nodes.append(node)
+            predicted_attributes.setdefault(node, []).append(parse_attributes(label, taxonomy))
+
+        if not expected_groups and not predicted_nodes:
+            return {'precision': 1.0, 'recall': 1.0, 'f1': 1.0, 'matches': []}
+
+        matches = []
+        recall_credit = 0.0
+        for label, group in zip(expected_labels, expected_groups):
+            best_credit, best_prediction, best_answer = 0.0, None, group[0]
+            for answer in group:
+                for predicted in predicted_nodes:
+                    credit = taxonomy.credit(predicted, answer, policy)
+                    if credit > best_credit:
+                        best_credit, best_prediction, best_answer = credit, predicted, answer
+            recall_credit += best_credit
+            match = {
+                'expected': taxonomy.format_node(group[0]),
+                'predicted': taxonomy.format_node(best_prediction) if best_prediction else None,
+                'credit': best_credit,
+                'role': taxonomy.role(group[0]),
+                'weakness': label_weakness(label, weakness, taxonomy),
+                'usage': label_usage(label, usage, fallback_usage)
+            }
+            if best_answer != group[0]:
+                match['accepted_alternative'] = taxonomy.format_node(best_answer)
+            # 키 길이/모드/곡선/라운드: 계열을 맞힌 예측에서만 비교 (식별 점수와 별개)
+            expected = expected_attributes(label, attributes, taxonomy)
+            if expected and best_prediction and best_prediction[:2] == best_answer[:2]:
+                match['attributes'] = compare_attributes(expected, merge_attributes(
+                    predicted_attributes.get(best_prediction, [])))
+            matches.append(match)
+
+        precision_credit = sum(
+            max((taxonomy.credit(predicted, answer, policy) for group in expected_groups for answer in group),
+                default=0.0)
+            for predicted in predicted_nodes
+        )
+
+        precision = precision_credit / len(predicted_nodes) if predicted_nodes else 0.0
+        recall = recall_credit / len(expected_groups) if expected_groups else 0.0
+        f1 = 2 * precision * recall / (precision + recall) if (precision + recall) > 0 else 0.0
+
+        return {
+            'precision': precision,
+            'recall': recall,
+            'f1': f1,
+            'matches': matches
+        }
+
+    @staticmethod
+    def calculate_prediction_credits(predictions: List[Tuple[str, float]], ground_truth: Dict[str, Any],
+                                     policy: Any = None) -> List[Dict[str, Any]]:
+        """라벨별 신뢰도 → 예측마다 정답 여부 점수 (보정/임계값 분석용, utils/calibration.py)
+
+        credit은 계층 precision과 같은 방식(가장 높은 점수를 주는 정답)의 0~1 점수이고, matches는
+        calculate_hierarchical_scores의 matches(정답 라벨) 순서대로 이 예측이 각 정답에 주는 점수입니다.
+        """
+        from utils.taxonomy import AlgorithmTaxonomy
+
+        taxonomy = AlgorithmTaxonomy()
+        policy = taxonomy.get_policy(policy)
+        alternatives = label_alternatives(ground_truth)
+        expected_groups = [
+            [taxonomy.resolve(answer) for answer in acceptable_answers(label, alternatives)]
+            for label in MetricsCalculator.get_expected_labels(ground_truth)
+        ]
+        scored = []
+        for label, confidence in predictions:
+            node = taxonomy.resolve(label)
+            matches = [max(taxonomy.credit(node, answer, policy) for answer in group) for group in expected_groups]
+            scored.append({'label': label, 'confidence': min(max(float(confidence or 0.0), 0.0), 1.0),
+                           'credit': max(matches, default=0.0), 'matches': matches})
+        return scored
+
     @staticmethod
     def _calculate_findings_accuracy(actual_findings: Dict[str, Any], expected_findings: List[str]) -> float:
         if not expected_findings:
@@ -357,6 +523,19 @@ class MetricsCalculator:
         expected_algorithms = expected_findings.get('vulnerable_algorithms_detected', [])
         if not expected_algorithms:
             actual_text = json.dumps(actual_findings).lower()
+
+            # disputed 라벨의 대체 정답은 오탐이 아님
+            accepted = set()
+            for label, answers in label_alternatives(ground_truth).items():
+                for answer in [label] + answers:
+                    accepted.add(answer.lower())
+                    accepted.update(MetricsCalculator._algorithm_variations(answer.lower()))
+
+            # 양자 내성 알고리즘은 언급만으로는 오탐이 아님 (취약 보고 여부는 별도 지표로 평가)
+            for label in MetricsCalculator.get_quantum_safe_labels(ground_truth):
+                accepted.add(label.lower())
+                accepted.update(MetricsCalculator._algorithm_variations(label.lower()))
+
             vulnerable_keywords = ['rsa', 'ecc', 'ecdsa', 'dsa', 'diffie-hellman', 'dh', 'des', '3des',
                                  'rc4', 'rc2', 'md5', 'sha1', 'sha256', 'tea', 'salsa20', 'chacha20', 'poly1305',
                                  'hmac', 'crc32', 'seed', 'aria', 'hight', 'lea', 'trivium', 'a5',
@@ -364,9 +543,10 @@ class MetricsCalculator:
                                  'has-160', 'lsh', 'kcdsa', 'ecdh', 'ecdhe', 'ec-kcdsa', 'kyber', 'vdf', 'misty',
                                  'dilithium', 'sphincs', 'ntru', 'paillier', 'bgn', 'shamir', 'feldman',
                                  'x25519', 'ed25519', 'xchacha20', 'double ratchet', 'ikev2', 'ocsp',
-                                 'pbkdf2', 'gcm', 'ripemd', 'scrypt', 'keccak', 'secp256k1', 'shake', 'siphash']
+                                 'pbkdf2', 'pbkdf1', 'hkdf', 'gcm', 'ripemd', 'scrypt', 'keccak', 'secp256k1', 'shake', 'siphash']
 
-            false_positives = sum(1 for keyword in vulnerable_keywords if keyword in actual_text)
+            false_positives = sum(1 for keyword in vulnerable_keywords
+                                  if keyword in actual_text and keyword not in accepted)
             return min(1.0, false_positives / len(vulnerable_keywords))
 
         return 0.0
@@ -385,9 +565,14 @@ class MetricsCalculator:
             return 0.0
 
         actual_text = json.dumps(actual_findings).lower()
+        alternatives = label_alternatives(ground_truth)
         missed_count = 0
 
         for algorithm in expected_algorithms:
+            if any(MetricsCalculator._mentions_algorithm(answer, actual_text)
+                   for answer in acceptable_answers(algorithm, alternatives)[1:]):
+                continue
+
             algorithm_lower = algorithm.lower()
             if algorithm_lower not in actual_text:
                 # Check exact algorithm names only
@@ -464,6 +649,10 @@ class MetricsCalculator:
                     variations = ['ocsp']
                 elif 'pbkdf2' in algorithm_lower:
                     variations = ['pbkdf2']
+                elif 'pbkdf1' in algorithm_lower:
+                    variations = ['pbkdf1', 'pbkdf-1', 'iterated hash', 'iterative hash']
+                elif 'hkdf' in algorithm_lower:
+                    variations = ['hkdf']
                 elif 'gcm' in algorithm_lower:
                     variations = ['gcm']
                 elif 'ripemd' in algorithm_lower:
@@ -501,6 +690,8 @@ class MetricsCalculator:
         completeness_scores = [r.get('completeness_score', 0.0) for r in results if r.get('success', False)]
         false_positive_rates = [r.get('false_positive_rate', 0.0) for r in results if r.get('success', False)]
         false_negative_rates = [r.get('false_negative_rate', 0.0) for r in results if r.get('success', False)]
+        parse_statuses = [r['parse_status'] for r in results if r.get('parse_status')]
+        hierarchical_f1 = [r['hierarchical_scores']['f1'] for r in results if r.get('hierarchical_scores')]
 
         return {
             'total_tests': total_tests,
@@ -515,6 +706,9 @@ class MetricsCalculator:
             'min_response_time': min(response_times) if response_times else 0.0,
             'max_response_time': max(response_times) if response_times else 0.0,
             'vulnerable_crypto_detection_precision': 1.0 - (sum(false_positive_rates) / len(false_positive_rates)) if false_positive_rates else 1.0,
+            'parse_failure_rate': parse_statuses.count('failed') / len(parse_statuses) if parse_statuses else 0.0,
+            'parse_repair_rate': parse_statuses.count('repaired') / len(parse_statuses) if parse_statuses else 0.0,
             'vulnerable_crypto_detection_recall': 1.0 - (sum(false_negative_rates) / len(false_negative_rates)) if false_negative_rates else 1.0,
+            'average_hierarchical_f1': sum(hierarchical_f1) / len(hierarchical_f1) if hierarchical_f1 else 0.0,
             'timestamp': datetime.now().isoformat()
         }
\ No newline at end of file
@@ -0,0 +1,235 @@
+"""
+Prometheus 지표 엔드포인트 (서버 모드)
+
+야간 벤치마크를 서비스로 돌릴 때(benchmark_runner.py --serve) 인프라 쪽 회귀(실패 급증, 지연 증가, 비용 급증)에
+경보를 걸 수 있도록 /metrics로 Prometheus 텍스트 형식 지표를 노출합니다. 외부 의존성 없이 표준 라이브러리로 구현합니다.
+
+지표는 utils/tracing.py의 span이 끝날 때 채워집니다 (add_span_listener):
+
+    benchmark_samples_total{detector, agent_type, status}             처리한 샘플 (sample span)
+    benchmark_sample_failures_total{detector, agent_type, status}     채점하지 못한 샘플 (status != ok)
+    benchmark_sample_duration_seconds{detector}                       샘플 처리 시간 히스토그램 (탐지기별)
+    benchmark_stage_duration_seconds{stage}                           단계(span 이름)별 처리 시간 히스토그램
+    benchmark_api_calls_total{provider, model, success}               모델 호출 (api_call span, judge 포함)
+    benchmark_prompt_cache_hits_total{provider, model}                프롬프트 캐시에서 읽은 토큰이 있는 호출
+    benchmark_cached_prompt_tokens_total{provider, model}             프롬프트 캐시에서 읽은 토큰
+    benchmark_api_spend_usd_total{provider, model}                    API 비용 (utils/quota.py 단가 기준)
+
+실행 단위 지표는 서버 모드 반복 루프가 실행이 끝날 때마다 기록합니다 (record_run):
+
+    benchmark_runs_total{outcome}                                     completed / cancelled / failed
+    benchmark_running                                                 실행 중이면 1
+    benchmark_last_run_timestamp_seconds                              마지막 실행이 끝난 시각
+    benchmark_last_run_hierarchical_f1{detector}                      마지막 실행의 모델별 계층 F1 평균
+    benchmark_last_run_success_rate{detector}                         마지막 실행의 모델별 성공률
+
+detector 레이블은 "<프로바이더>/<모델>" (로컬 탐지기는 "local/<탐지기>")입니다.
+
+사용법:
+    python benchmark_runner.py --serve --metrics-port 9108 --interval 1440 --subset smoke --providers openai
+    curl -s localhost:9108/metrics
+"""
+
+import threading
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from typing import Any, Dict, List, Optional, Tuple
+
+from utils.run_control import STATUS_OK
+from utils.tracing import Span, add_span_listener, logger
+
+DEFAULT_METRICS_PORT = 9108
+DURATION_BUCKETS = (0.05, 0.1, 0.25, 0.5, 1, 2.5, 5, 10, 30, 60, 120, 300)
+
+LabelValues = Tuple[str, ...]
+
+
+def escape_label(value: Any) -> str:
+    return str(value).replace('\\', '\\\\').replace('"', '\\"').replace('\n', '\\n')
+
+
+def format_labels(names: Tuple[str, ...], values: LabelValues, extra: str = '') -> str:
+    pairs = [f'{name}="{escape_label(value)}"' for name, value in zip(names, values)]
+    if extra:
+        pairs.append(extra)
+    return '{' + ','.join(pairs) + '}' if pairs else ''
+
+
+def format_value(value: float) -> str:
+    if value == float('inf'):
+        return '+Inf'
+    return repr(float(value)) if not float(value).is_integer() else str(int(value))
+
+
+class Metric:
+    """이름, 설명, 레이블 이름을 가진 지표 (레이블 값 조합마다 값 하나)"""
+
+    kind = 'untyped'
+
+    def __init__(self, name: str, description: str, labels: Tuple[str, ...] = ()):
+        self.name = name
+        self.description = description
+        self.labels = labels
+        self.values: Dict[LabelValues, Any] = {}
+        self.lock = threading.Lock()
+
+    def key(self, labels: Dict[str, Any]) -> LabelValues:
+        return tuple(str(labels.get(name, '')) for name in self.labels)
+
+    def samples(self) -> List[str]:
+        with self.lock:
+            return [f"{self.name}{format_labels(self.labels, key)} {format_value(value)}"
+                    for key, value in sorted(self.values.items())]
+
+    def render(self) -> str:
+        lines = [f"# HELP {self.name} {self.description}", f"# TYPE {self.name} {self.kind}"]
+        return '\n'.join(lines + self.samples())
+
+
+class Counter(Metric):
+    kind = 'counter'
+
+    def inc(self, amount: float = 1.0, **labels):
+        key = self.key(labels)
+        with self.lock:
+            self.values[key] = self.values.get(key, 0.0) + amount
+
+
+class Gauge(Metric):
+    kind = 'gauge'
+
+    def set(self, value: float, **labels):
+        with self.lock:
+            self.values[self.key(labels)] = value
+
+
+class Histogram(Metric):
+    kind = 'histogram'
+
+    def __init__(self, name: str, description: str, labels: Tuple[str, ...] = (),
+                 buckets: Tuple[float, ...] = DURATION_BUCKETS):
+        super().__init__(name, description, labels)
+        self.buckets = tuple(sorted(buckets)) + (float('inf'),)
+
+    def observe(self, value: float, **labels):
+        key = self.key(labels)
+        with self.lock:
+            entry = self.values.setdefault(key, {'buckets': [0] * len(self.buckets), 'sum': 0.0, 'count': 0})
+            for index, bound in enumerate(self.buckets):
+                if value <= bound:
+                    entry['buckets'][index] += 1
+            entry['sum'] += value
+            entry['count'] += 1
+
+    def samples(self) -> List[str]:
+        lines = []
+        with self.lock:
+            for key, entry in sorted(self.values.items()):
+                for bound, count in zip(self.buckets, entry['buckets']):
+                    le = f'le="{format_value(bound)}"'
+                    lines.append(f"{self.name}_bucket{format_labels(self.labels, key, le)} {count}")
+                lines.append(f"{self.name}_sum{format_labels(self.labels, key)} {format_value(entry['sum'])}")
+                lines.append(f"{self.name}_count{format_labels(self.labels, key)} {entry['count']}")
+        return lines
+
+
+class MetricsRegistry:
+    """벤치마크 지표 모음"""
+
+    def __init__(self):
+        self.samples = Counter('benchmark_samples_total', 'Samples processed',
+                               ('detector', 'agent_type', 'status'))
+        self.failures = Counter('benchmark_sample_failures_total', 'Samples that could not be scored',
+                                ('detector', 'agent_type', 'status'))
+        self.sample_duration = Histogram('benchmark_sample_duration_seconds', 'Per-sample processing time',
+                                         ('detector',))
+        self.stage_duration = Histogram('benchmark_stage_duration_seconds', 'Processing time per tracing stage',
+                                        ('stage',))
+        self.api_calls = Counter('benchmark_api_calls_total', 'Model API calls', ('provider', 'model', 'success'))
+        self.cache_hits = Counter('benchmark_prompt_cache_hits_total', 'API calls served partly from prompt cache',
+                                  ('provider', 'model'))
+        self.cached_tokens = Counter('benchmark_cached_prompt_tokens_total', 'Prompt tokens read from prompt cache',
+                                     ('provider', 'model'))
+        self.spend = Counter('benchmark_api_spend_usd_total', 'API spend in USD', ('provider', 'model'))
+        self.runs = Counter('benchmark_runs_total', 'Benchmark runs finished', ('outcome',))
+        self.running = Gauge('benchmark_running', 'Whether a benchmark run is in progress')
+        self.last_run = Gauge('benchmark_last_run_timestamp_seconds', 'Finish time of the last run')
+        self.last_f1 = Gauge('benchmark_last_run_hierarchical_f1', 'Mean hierarchical F1 of the last run',
+                             ('detector',))
+        self.last_success = Gauge('benchmark_last_run_success_rate', 'Success rate of the last run', ('detector',))
+        self.running.set(0)
+
+    def metrics(self) -> List[Metric]:
+        return [value for value in vars(self).values() if isinstance(value, Metric)]
+
+    def render(self) -> str:
+        return '\n'.join(metric.render() for metric in self.metrics()) + '\n'
+
+    def observe_span(self, finished: Span, status: str):
+        """끝난 span → 지표 (tracing.add_span_listener로 등록)"""
+        fields = finished.fields
+        seconds = (finished.duration_ms or 0.0) / 1000
+        self.stage_duration.observe(seconds, stage=finished.name)
+
+        if finished.name == 'sample':
+            if status == 'cancelled' and not fields.get('status'):
+                return  # 실행 전체가 멈춰 끝내지 못한 샘플은 결과에도 없음
+            detector = f"{fields.get('provider', 'unknown')}/{fields.get('model', 'unknown')}"
+            sample_status = fields.get('status') or (STATUS_OK if status == 'ok' else status)
+            labels = {'detector': detector, 'agent_type': fields.get('agent_type', 'unknown'), 'status': sample_status}
+            self.samples.inc(**labels)
+            if sample_status != STATUS_OK:
+                self.failures.inc(**labels)
+            self.sample_duration.observe(seconds, detector=detector)
+        elif finished.name == 'api_call':
+            labels = {'provider': fields.get('provider', 'unknown'), 'model': fields.get('model', 'unknown')}
+            self.api_calls.inc(success=str(bool(fields.get('success'))).lower(), **labels)
+            if fields.get('cached_tokens'):
+                self.cache_hits.inc(**labels)
+                self.cached_tokens.inc(fields['cached_tokens'], **labels)
+            if fields.get('cost_usd'):
+                self.spend.inc(fields['cost_usd'], **labels)
+
+    def record_run(self, results: Optional[Dict[str, Any]], outcome: str, finished_at: float):
+        """실행 하나가 끝났을 때 실행 단위 지표 갱신"""
+        self.runs.inc(outcome=outcome)
+        self.last_run.set(finished_at)
+        for model_key, stats in ((results or {}).get('summary', {}).get('by_model') or {}).items():
+            if stats.get('hierarchical_f1'):
+                self.last_f1.set(stats['hierarchical_f1']['mean'], detector=model_key)
+            if stats.get('total'):
+                self.last_success.set(stats['successful'] / stats['total'], detector=model_key)
+
+
+REGISTRY = MetricsRegistry()
+
+
+class MetricsHandler(BaseHTTPRequestHandler):
+    registry: MetricsRegistry = REGISTRY
+
+    def do_GET(self):
+        if self.path.split('?')[0] == '/metrics':
+            body = self.registry.render().encode('utf-8')
+            content_type = 'text/plain; version=0.0.4; charset=utf-8'
+        elif self.path.split('?')[0] == '/healthz':
+            body, content_type = b'ok\n', 'text/plain; charset=utf-8'
+        else:
+            self.send_error(404)
+            return
+        self.send_response(200)
+        self.send_header('Content-Type', content_type)
+        self.send_header('Content-Length', str(len(body)))
+        self.end_headers()
+        self.wfile.write(body)
+
+    def log_message(self, format, *args):
+        logger.debug(f"metrics {self.address_string()} {format % args}")
+
+
+def start_metrics_server(port: int = DEFAULT_METRICS_PORT, host: str = '0.0.0.0',
+                         registry: MetricsRegistry = REGISTRY) -> ThreadingHTTPServer:
+    """/metrics, /healthz를 백그라운드 스레드에서 제공하고 span 지표 수집 시작"""
+    add_span_listener(registry.observe_span)
+    handler = type('BoundMetricsHandler', (MetricsHandler,), {'registry': registry})
+    server = ThreadingHTTPServer((host, port), handler)
+    threading.Thread(target=server.serve_forever, name='metrics-server', daemon=True).start()
+    return server
@@ -0,0 +1,294 @@
+"""
+운용 모드와 구현 약점 (weakness-detection 트랙)
+
+알고리즘을 맞혀도 "AES를 CBC로, 예측 가능한 IV와 MAC 없이" 쓴다는 사실을 놓치면 마이그레이션 우선순위를 정할 수
+없습니다. 샘플이 알고리즘을 어떤 운용 모드로, 어떤 약점과 함께 쓰는지 ground truth에 기록하고, 알고리즘 식별
+(계층 F1)과 별개의 트랙으로 채점합니다.
+
+운용 모드:  utils.attributes.BLOCK_MODES (ECB, CBC, CTR, GCM, ...)와 STREAM (키스트림을 평문에 XOR만 하는 사용)
+
+약점 라벨 (WEAKNESSES):
+    predictable-iv      CBC IV를 장치 ID, 카운터, 타임스탬프 등 예측 가능한 값으로 만듦
+    static-iv           고정 IV/논스
+    nonce-reuse         같은 키로 논스가 반복될 수 있음 (논스에 카운터가 빠져 있거나 시각 해상도가 낮음)
+    missing-mac         암호문 무결성 검증(MAC/AEAD 태그) 없이 복호화
+    padding-oracle      인증 없이 패딩을 검증하고 패딩 오류를 다른 오류와 구분해 돌려줌
+    ecb-mode            구조가 있는 데이터에 ECB 사용
+    hardcoded-key       소스에 박힌 키
+    hardcoded-salt      소스에 박힌 고정 솔트 (예: "MedicalDeviceSalt")
+    time-based-entropy  시각/카운터로 만든 키·논스·솔트 (예: generate_medical_entropy)
+
+  비밀 취급 (secret-hygiene 하위 트랙, SECRET_HYGIENE_WEAKNESSES):
+    cloneable-secret    원시 키 바이트를 담은 타입이 Clone/Copy를 derive해 지울 수 없는 사본이 퍼짐 (예: DeviceContext)
+    unzeroized-secret   키를 담은 값이 drop될 때 지워지지 않음 (zeroize/수동 wipe 없음)
+    secret-in-log       키 바이트를 로그/표준 출력에 씀
+    secret-in-debug     Debug 구현(derive 포함)이 키 바이트를 그대로 보여 줌
+
+  타이밍 안전성 (timing-safety 하위 트랙, TIMING_SAFETY_WEAKNESSES):
+    secret-dependent-lookup  비밀 값으로 테이블을 인덱싱 (T-table/S-box 조회 → 캐시 타이밍)
+    secret-dependent-branch  비밀 비트에 따라 분기하거나 반복 횟수가 달라짐 (square-and-multiply의 지수 비트 분기)
+
+약점마다 CWE ID가 있습니다 (WEAKNESS_CWE). ground truth 항목의 "cwe"는 생략하면 약점에서 채우고,
+기록하면 lint가 WEAKNESS_CWE와 맞는지 검사합니다.
+
+Ground truth 표기 (알고리즘별로 모드와 약점 목록):
+    expected_findings 형식:  "expected_findings": {"mode_findings": [{"algorithm": "AES-256", "mode": "CBC",
+                                                                      "weaknesses": ["predictable-iv"]}], ...}
+    레거시 형식:              "vulnerability_analysis": {"mode_findings": [{...같은 항목..., "line_range": "200-286"}]}
+    타이밍 라벨:              항목의 "timing_safety": "leaky" | "constant-time" (TIMING_SAFETY_LABELS)
+        같은 알고리즘을 테이블 조회/비밀 의존 분기로 구현한 샘플과 비트슬라이스/상수 시간으로 구현한 샘플을 짝으로 두고
+        (tags에 "timing-safety"), 상수 시간 쪽은 "weaknesses": []로 라벨만 기록해 오탐이 precision에 잡히게 합니다.
+
+misuse-detection 과제 (utils/tasks.py):
+    일부러 오용 패턴을 심은 샘플(utils/sample_templates/misuse_vault)은 "task": "misuse-detection"으로 표기합니다.
+    설정과 무관하게 응답에 weaknesses를 요청하고 이 트랙으로 채점합니다.
+
+탐지기 응답의 선택 필드 `weaknesses`로 받습니다 (benchmark.weakness_detection.enabled 또는 --weakness-detection):
+    "weaknesses": [{"algorithm": "AES", "mode": "CBC", "weakness": "predictable-iv"}, "missing MAC", ...]
+    약점 이름은 별칭(WEAKNESS_ALIASES)으로 정규화하고, algorithm이 있으면 같은 계열의 정답과만 맞춥니다.
+    모르는 약점 표현은 채점에서 제외합니다 (precision의 분모에도 넣지 않음).
+
+채점 (calculate_weakness_scores):
+    정답 (알고리즘, 약점) 쌍마다 같은 약점을 보고한 항목이 있으면 적중 → precision / recall / f1
+    모드는 정답 모드가 기록된 알고리즘마다 같은 계열로 보고한 모드가 맞는지 → mode_accuracy
+    secret_hygiene은 같은 채점을 비밀 취급 약점(정답과 보고 모두)으로만 좁힌 하위 점수
+    timing_safety도 타이밍 약점으로 좁힌 하위 점수이며, constant-time으로 표기된 알고리즘에 타이밍 약점을 보고한
+    건수(false_alarms)와 샘플의 타이밍 라벨(labels)을 함께 남김
+"""
+
+import re
+from typing import Dict, Any, List, Optional
+
+from utils.attributes import BLOCK_MODES, normalize_value
+
+TRACK_WEAKNESS = 'weakness-detection'
+TRACK_SECRET_HYGIENE = 'secret-hygiene'
+TRACK_TIMING_SAFETY = 'timing-safety'
+
+TIMING_LEAKY = 'leaky'
+TIMING_CONSTANT = 'constant-time'
+TIMING_SAFETY_LABELS = [TIMING_LEAKY, TIMING_CONSTANT]
+
+MODE_STREAM = 'STREAM'
+MODES = sorted(BLOCK_MODES) + [MODE_STREAM]
+
+WEAKNESSES = {
+    'predictable-iv': 'CBC IV derived from predictable values (device id, counter, timestamp)',
+    'static-iv': 'fixed IV or nonce',
+    'nonce-reuse': 'nonce can repeat under the same key',
+    'missing-mac': 'ciphertext decrypted without a MAC or AEAD tag',
+    'padding-oracle': 'unauthenticated padding check with a distinguishable padding error',
+    'ecb-mode': 'ECB mode on structured data',
+    'hardcoded-key': 'key embedded in source',
+    'hardcoded-salt': 'constant salt embedded in source',
+    'time-based-entropy': 'key, nonce or salt derived from the clock or a counter',
+    'cloneable-secret': 'type holding raw key bytes derives Clone/Copy',
+    'unzeroized-secret': 'key material is not wiped when dropped',
+    'secret-in-log': 'key material written to logs or stdout',
+    'secret-in-debug': 'Debug output exposes key material',
+    'secret-dependent-lookup': 'table lookup indexed by secret data (cache timing)',
+    'secret-dependent-branch': 'branch or loop count depends on secret bits',
+}
+
+SECRET_HYGIENE_WEAKNESSES = ['cloneable-secret', 'unzeroized-secret', 'secret-in-log', 'secret-in-debug']
+TIMING_SAFETY_WEAKNESSES = ['secret-dependent-lookup', 'secret-dependent-branch']
+
+# 약점 → CWE ID
+WEAKNESS_CWE = {
+    'predictable-iv': 'CWE-329',
+    'static-iv': 'CWE-1204',
+    'nonce-reuse': 'CWE-323',
+    'missing-mac': 'CWE-353',
+    'padding-oracle': 'CWE-209',
+    'ecb-mode': 'CWE-327',
+    'hardcoded-key': 'CWE-321',
+    'hardcoded-salt': 'CWE-760',
+    'time-based-entropy': 'CWE-330',
+    'cloneable-secret': 'CWE-226',
+    'unzeroized-secret': 'CWE-226',
+    'secret-in-log': 'CWE-532',
+    'secret-in-debug': 'CWE-215',
+    'secret-dependent-lookup': 'CWE-208',
+    'secret-dependent-branch': 'CWE-208',
+}
+
+# 응답에 흔히 나오는 표현 → 약점 라벨 (정규화된 이름 비교)
+WEAKNESS_ALIASES = {
+    'predictable iv': 'predictable-iv', 'non-random iv': 'predictable-iv', 'deterministic iv': 'predictable-iv',
+    'iv predictable': 'predictable-iv', 'time-based iv': 'predictable-iv', 'counter iv': 'predictable-iv',
+    'fixed iv': 'static-iv', 'constant iv': 'static-iv', 'hardcoded iv': 'static-iv', 'zero iv': 'static-iv',
+    'nonce reuse': 'nonce-reuse', 'reused nonce': 'nonce-reuse', 'iv reuse': 'nonce-reuse',
+    'keystream reuse': 'nonce-reuse', 'two-time pad': 'nonce-reuse',
+    'missing mac': 'missing-mac', 'no mac': 'missing-mac', 'no authentication': 'missing-mac',
+    'unauthenticated encryption': 'missing-mac', 'no integrity': 'missing-mac', 'missing integrity': 'missing-mac',
+    'malleable': 'missing-mac', 'no aead': 'missing-mac',
+    'padding oracle': 'padding-oracle', 'pkcs7 padding oracle': 'padding-oracle',
+    'unauthenticated padding': 'padding-oracle', 'padding scheme': 'padding-oracle',
+    'ecb': 'ecb-mode', 'ecb mode': 'ecb-mode',
+    'hardcoded key': 'hardcoded-key', 'hard-coded key': 'hardcoded-key', 'embedded key': 'hardcoded-key',
+    'static key': 'hardcoded-key', 'constant key': 'hardcoded-key',
+    'hardcoded salt': 'hardcoded-salt', 'hard-coded salt': 'hardcoded-salt', 'static salt': 'hardcoded-salt',
+    'fixed salt': 'hardcoded-salt', 'constant salt': 'hardcoded-salt',
+    'time-based entropy': 'time-based-entropy', 'weak entropy': 'time-based-entropy',
+    'insufficient entropy': 'time-based-entropy', 'timestamp seed': 'time-based-entropy',
+    'time-based key': 'time-based-entropy', 'predictable key': 'time-based-entropy',
+    'weak randomness': 'time-based-entropy', 'predictable random': 'time-based-entropy',
+    'clone of key': 'cloneable-secret', 'cloneable key': 'cloneable-secret', 'key derives clone': 'cloneable-secret',
+    'copyable key': 'cloneable-secret',
+    'no zeroization': 'unzeroized-secret', 'missing zeroization': 'unzeroized-secret',
+    'missing zeroize': 'unzeroized-secret', 'key not zeroized': 'unzeroized-secret',
+    'secret not wiped': 'unzeroized-secret', 'key not wiped': 'unzeroized-secret',
+    'key in logs': 'secret-in-log', 'key logged': 'secret-in-log', 'secret in logs': 'secret-in-log',
+    'sensitive data in log': 'secret-in-log', 'logging secrets': 'secret-in-log',
+    'key in debug output': 'secret-in-debug', 'debug leaks key': 'secret-in-debug',
+    'secret in debug': 'secret-in-debug', 'debug impl exposes key': 'secret-in-debug',
+    't-table lookup': 'secret-dependent-lookup', 'table lookup timing': 'secret-dependent-lookup',
+    'cache timing': 'secret-dependent-lookup', 'cache-timing attack': 'secret-dependent-lookup',
+    'secret-indexed table': 'secret-dependent-lookup', 'sbox lookup timing': 'secret-dependent-lookup',
+    'secret-dependent branch': 'secret-dependent-branch', 'key-dependent branch': 'secret-dependent-branch',
+    'non-constant-time exponentiation': 'secret-dependent-branch', 'timing leak': 'secret-dependent-branch',
+    'variable-time exponentiation': 'secret-dependent-branch', 'timing side channel': 'secret-dependent-branch',
+}
+
+
+def _normalize(name: str) -> str:
+    return re.sub(r'[^a-z0-9]', '', str(name).lower())
+
+
+_ALIASES = {_normalize(name): label for name, label in list(WEAKNESS_ALIASES.items()) +
+            [(label, label) for label in WEAKNESSES]}
+
+
+def weakness_settings(config: Optional[Dict[str, Any]]) -> Dict[str, Any]:
+    config = config or {}
+    return {'enabled': bool(config.get('enabled', False))}
+
+
+def normalize_weakness(name: Any) -> Optional[str]:
+    """응답의 약점 표현 → 약점 라벨 (모르는 표현은 None)"""
+    return _ALIASES.get(_normalize(name)) if name else None
+
+
+def normalize_mode(mode: Any) -> Optional[str]:
+    """모드 표현 → MODES 중 하나 ("stream xor", "keystream" → STREAM, 모르는 값은 None)"""
+    if not mode:
+        return None
+    if 'stream' in str(mode).lower():
+        return MODE_STREAM
+    value = normalize_value('mode', mode)
+    return value if value in BLOCK_MODES else None
+
+
+def recorded_mode_findings(ground_truth: Optional[Dict[str, Any]]) -> List[Dict[str, Any]]:
+    """ground truth의 알고리즘별 모드/약점 [{'algorithm', 'mode', 'weaknesses', 'cwe', 'timing_safety'}]
+
+    cwe는 기록된 값 그대로 (없으면 약점별 WEAKNESS_CWE), timing_safety는 기록된 타이밍 라벨 (없으면 None)
+    """
+    if not ground_truth:
+        return []
+
+    analysis = ground_truth.get('vulnerability_analysis')
+    entries = analysis.get('mode_findings', []) if isinstance(analysis, dict) else \
+        ground_truth.get('expected_findings', {}).get('mode_findings', [])
+    findings = []
+    for entry in entries:
+        if not entry.get('algorithm'):
+            continue
+        weaknesses = [normalize_weakness(name) or name for name in entry.get('weaknesses', [])]
+        findings.append({
+            'algorithm': entry['algorithm'], 'mode': normalize_mode(entry.get('mode')) or entry.get('mode'),
+            'weaknesses': weaknesses,
+            'cwe': list(entry['cwe']) if 'cwe' in entry else
+            [WEAKNESS_CWE[name] for name in weaknesses if name in WEAKNESS_CWE],
+            'timing_safety': entry.get('timing_safety'),
+        })
+    return findings
+
+
+def _reports(weaknesses: List[Any]) -> List[Dict[str, Any]]:
+    """응답의 weaknesses → [{'algorithm', 'mode', 'weakness'}] (문자열 항목은 알고리즘 없음)"""
+    reports = []
+    for item in weaknesses or []:
+        if isinstance(item, str):
+            reports.append({'algorithm': None, 'mode': None, 'weakness': normalize_weakness(item)})
+        elif isinstance(item, dict):
+            names = item.get('weaknesses') or [item.get('weakness')]
+            for name in names if isinstance(names, list) else [names]:
+                reports.append({'algorithm': item.get('algorithm'), 'mode': normalize_mode(item.get('mode')),
+                                'weakness': normalize_weakness(name)})
+    return reports
+
+
+def calculate_weakness_scores(weaknesses: List[Any], ground_truth: Dict[str, Any]) -> Optional[Dict[str, Any]]:
+    """모드/약점 채점 (모드/약점이 기록되지 않은 샘플은 None)"""
+    from utils.taxonomy import AlgorithmTaxonomy
+
+    recorded = recorded_mode_findings(ground_truth)
+    if not recorded:
+        return None
+
+    taxonomy = AlgorithmTaxonomy()
+    reports = _reports(weaknesses)
+    for report in reports:
+        report['family'] = taxonomy.resolve(report['algorithm'])[1] if report['algorithm'] else None
+
+    matches = []
+    used = set()
+    for entry in recorded:
+        family = taxonomy.resolve(entry['algorithm'])[1]
+        for weakness in entry['weaknesses']:
+            hit = next((index for index, report in enumerate(reports)
+                        if index not in used and report['weakness'] == weakness
+                        and report['family'] in (None, family)), None)
+            if hit is not None:
+                used.add(hit)
+            matches.append({'algorithm': entry['algorithm'], 'weakness': weakness, 'cwe': WEAKNESS_CWE.get(weakness),
+                            'detected': hit is not None})
+
+    modes = []
+    for entry in recorded:
+        if not entry['mode']:
+            continue
+        family = taxonomy.resolve(entry['algorithm'])[1]
+        predicted = next((report['mode'] for report in reports if report['mode'] and report['family'] == family),
+                         None)
+        modes.append({'algorithm': entry['algorithm'], 'expected': entry['mode'], 'predicted': predicted,
+                      'correct': predicted == entry['mode']})
+
+    hygiene = _match_scores([match for match in matches if match['weakness'] in SECRET_HYGIENE_WEAKNESSES],
+                            [report for report in reports if report['weakness'] in SECRET_HYGIENE_WEAKNESSES])
+    timing_reports = [report for report in reports if report['weakness'] in TIMING_SAFETY_WEAKNESSES]
+    timing = _match_scores([match for match in matches if match['weakness'] in TIMING_SAFETY_WEAKNESSES],
+                           timing_reports)
+    labels = {entry['algorithm']: entry['timing_safety'] for entry in recorded if entry['timing_safety']}
+    constant = {taxonomy.resolve(algorithm)[1] for algorithm, label in labels.items() if label == TIMING_CONSTANT}
+    timing['labels'] = labels
+    # 알고리즘 없는 보고는 샘플에 leaky 라벨이 하나도 없을 때만 오경보로 셈
+    all_constant = bool(constant) and TIMING_LEAKY not in labels.values()
+    timing['false_alarms'] = sum(1 for report in timing_reports
+                                 if report['family'] in constant or report['family'] is None and all_constant)
+    return {
+        **_match_scores(matches, [report for report in reports if report['weakness']]),
+        'modes_expected': len(modes),
+        'modes_correct': sum(1 for mode in modes if mode['correct']),
+        'secret_hygiene': hygiene,
+        'timing_safety': timing,
+        'matches': matches,
+        'modes': modes
+    }
+
+
+def _match_scores(matches: List[Dict[str, Any]], reports: List[Dict[str, Any]]) -> Dict[str, Any]:
+    expected = len(matches)
+    reported = len(reports)
+    matched = sum(1 for match in matches if match['detected'])
+    precision = matched / reported if reported else 0.0
+    recall = matched / expected if expected else 0.0
+    return {
+        'expected': expected,
+        'reported': reported,
+        'matched': matched,
+        'precision': precision,
+        'recall': recall,
+        'f1': 2 * precision * recall / (precision + recall) if precision + recall else 0.0,
+    }
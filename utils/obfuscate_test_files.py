@@ -8,6 +8,8 @@ import re
 import glob
 from pathlib import Path
 
+from utils.obfuscation_verify import STATUS_BROKEN, SemanticsVerifier, describe_check
+
 # 난독화 규칙
 OBFUSCATION_RULES = {
     # 주석 패턴
@@ -52,8 +54,11 @@ SENSITIVE_CONSTANTS = {
     '0x10001': 'common_exponent_hex',
 }
 
-def obfuscate_file(filepath: str, dry_run: bool = False):
-    """파일의 명시적인 암호화 힌트를 난독화"""
+def obfuscate_file(filepath: str, dry_run: bool = False, checker=None):
+    """파일의 명시적인 암호화 힌트를 난독화
+
+    checker(SemanticsVerifier)가 있으면 쓰기 전에 내장 테스트를 비교해 동작이 바뀐 결과는 버림
+    """
     try:
         with open(filepath, 'r', encoding='utf-8', errors='ignore') as f:
             content = f.read()
@@ -78,6 +83,14 @@ def obfuscate_file(filepath: str, dry_run: bool = False):
                 content = new_content
 
         if modified:
+            if not dry_run and checker:
+                path = Path(filepath)
+                check = checker.check('obfuscate_test_files', original_content, content, path.suffix, path.stem)
+                if check['status'] == STATUS_BROKEN:
+                    print(f"💥 난독화 거부 ({path.name}): {check['reason']}")
+                    return False
+                if check['tests_passed']:
+                    print(f"   {describe_check(check)}")
             if not dry_run:
                 with open(filepath, 'w', encoding='utf-8') as f:
                     f.write(content)
@@ -103,7 +116,7 @@ def obfuscate_file(filepath: str, dry_run: bool = False):
         return False
 
 def scan_and_obfuscate(directory: str, pattern: str = "*.{java,py,c,cpp,rs,rb,go,js,scala,swift,s,asm}",
-                       dry_run: bool = False):
+                       dry_run: bool = False, checker=None):
     """디렉토리의 모든 테스트 파일을 난독화"""
     extensions = ['java', 'py', 'c', 'cpp', 'rs', 'rb', 'go', 'js', 'scala', 'swift', 's', 'asm']
     all_files = []
@@ -121,7 +134,7 @@ def scan_and_obfuscate(directory: str, pattern: str = "*.{java,py,c,cpp,rs,rb,go
 
     modified_count = 0
     for filepath in sorted(all_files):
-        if obfuscate_file(filepath, dry_run):
+        if obfuscate_file(filepath, dry_run, checker):
             modified_count += 1
 
     print(f"\n{'=' * 60}")
@@ -140,17 +153,20 @@ def main():
                        help='실제 수정 없이 미리보기만')
     parser.add_argument('--assembly', action='store_true',
                        help='assembly_binary 파일도 처리')
+    parser.add_argument('--skip-semantics-check', action='store_true',
+                       help='쓰기 전 원본/난독화본 내장 테스트 비교 생략 (Rust 샘플)')
 
     args = parser.parse_args()
+    checker = None if args.skip_semantics_check else SemanticsVerifier()
 
     # source_code 처리
-    scan_and_obfuscate(args.dir, dry_run=args.dry_run)
+    scan_and_obfuscate(args.dir, dry_run=args.dry_run, checker=checker)
 
     # assembly_binary 처리 (선택)
     if args.assembly:
         assembly_dir = args.dir.replace('source_code', 'assembly_binary')
         if os.path.exists(assembly_dir):
-            scan_and_obfuscate(assembly_dir, dry_run=args.dry_run)
+            scan_and_obfuscate(assembly_dir, dry_run=args.dry_run, checker=checker)
 
 if __name__ == "__main__":
     main()
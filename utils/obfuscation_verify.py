@@ -0,0 +1,138 @@
+#!/usr/bin/env python3
+"""
+난독화 의미 보존 검증 (obfuscate verify)
+
+난독화 패스(주석 노이즈, 알고리즘 분할, 이름/주석 치환 …)는 알고리즘을 깨뜨릴 수 있습니다. 깨진 샘플이
+코퍼스에 들어가면 ground truth의 충실도(exact 등) 표기를 더 이상 믿을 수 없으므로, 변환 결과를 쓰기 전에
+원본과 변환본을 임시 디렉토리에서 각각 빌드하고 내장 known-answer 테스트를 실행해 비교합니다.
+
+    preserved    원본과 변환본 모두 통과, 통과한 테스트 수가 같음
+    broken       원본은 통과하는데 변환본이 빌드/테스트에 실패하거나 통과 수가 줄어듦 → 변환 거부
+    unverified   비교할 수 없음 (Rust가 아닌 샘플, 내장 테스트 없음, 원본부터 실패/건너뜀)
+
+빌드/실행은 CorpusVerifier(utils/corpus.py)를 그대로 씁니다: 단일 파일은 rustc --test, 다중 파일 변환본
+(utils/algorithm_split.py)은 임시 Cargo 패키지에서 cargo test --workspace.
+
+결과는 ground truth의 semantics_check에 패스별로 남깁니다:
+    "semantics_check": [{"pass": "comment_noise", "status": "preserved", "tests_passed": 6}]
+
+변환 도구(comment_noise, algorithm_split, sample_generator의 --comment-noise/--split, obfuscate_test_files)는
+기본으로 이 검증을 거치며 broken이면 결과를 쓰지 않습니다. 두 샘플을 직접 비교할 수도 있습니다:
+
+    python -m utils.obfuscation_verify data/test_files/source_code/kiosk_pin_vault.rs \\
+        data/test_files/source_code/kiosk_pin_vault_split
+"""
+
+import argparse
+import sys
+import tempfile
+from pathlib import Path
+from typing import Any, Dict, Optional, Union
+
+from utils.corpus import CorpusVerifier, STATUS_PASSED, TEST_MARKER
+
+STATUS_PRESERVED = 'preserved'
+STATUS_BROKEN = 'broken'
+STATUS_UNVERIFIED = 'unverified'
+
+# 변환본: 소스 문자열(단일 파일) 또는 {상대 경로: 내용}(Cargo 패키지 디렉토리)
+Transformed = Union[str, Dict[str, str]]
+
+
+class SemanticsVerifier:
+    """원본/변환본 내장 테스트 비교기"""
+
+    def __init__(self, verifier: Optional[CorpusVerifier] = None):
+        self.verifier = verifier or CorpusVerifier()
+
+    def check(self, pass_name: str, original: str, transformed: Transformed, extension: str = '.rs',
+              name: str = 'sample') -> Dict[str, Any]:
+        """{'pass', 'status', 'tests_passed', 'reason'}"""
+        if extension != '.rs':
+            return self._result(pass_name, STATUS_UNVERIFIED, reason=f"no test runner for {extension} samples")
+        if TEST_MARKER not in original:
+            return self._result(pass_name, STATUS_UNVERIFIED, reason='original has no embedded tests')
+
+        with tempfile.TemporaryDirectory() as work_dir:
+            baseline_path = Path(work_dir) / 'original' / f"{name}.rs"
+            baseline_path.parent.mkdir()
+            baseline_path.write_text(original, encoding='utf-8')
+            baseline = self.verifier.verify(baseline_path)
+            if baseline['status'] != STATUS_PASSED:
+                reason = baseline['reason'] or baseline['status']
+                return self._result(pass_name, STATUS_UNVERIFIED, reason=f"original does not pass: {reason}")
+
+            transformed_path = self._write(Path(work_dir) / 'transformed', transformed, name)
+            result = self.verifier.verify(transformed_path)
+
+        if result['status'] != STATUS_PASSED:
+            return self._result(pass_name, STATUS_BROKEN, result['tests_passed'],
+                                reason=result['reason'] or result['status'], output=result['output'])
+        if result['tests_passed'] != baseline['tests_passed']:
+            return self._result(pass_name, STATUS_BROKEN, result['tests_passed'],
+                                reason=f"{baseline['tests_passed']} tests before, {result['tests_passed']} after")
+        return self._result(pass_name, STATUS_PRESERVED, result['tests_passed'])
+
+    def check_paths(self, pass_name: str, original: Path, transformed: Path) -> Dict[str, Any]:
+        """이미 쓴 두 샘플 비교 (변환본은 파일 또는 디렉토리)"""
+        if transformed.is_dir():
+            content: Transformed = {path.relative_to(transformed).as_posix(): path.read_text(encoding='utf-8')
+                                    for path in sorted(transformed.rglob('*')) if path.is_file()}
+        else:
+            content = transformed.read_text(encoding='utf-8')
+        return self.check(pass_name, original.read_text(encoding='utf-8'), content, original.suffix, original.stem)
+
+    @staticmethod
+    def _write(directory: Path, transformed: Transformed, name: str) -> Path:
+        if isinstance(transformed, str):
+            path = directory / f"{name}.rs"
+            path.parent.mkdir(parents=True)
+            path.write_text(transformed, encoding='utf-8')
+            return path
+        package = directory / name
+        for relative, content in transformed.items():
+            (package / relative).parent.mkdir(parents=True, exist_ok=True)
+            (package / relative).write_text(content, encoding='utf-8')
+        return package
+
+    @staticmethod
+    def _result(pass_name: str, status: str, tests_passed: int = 0, reason: str = '',
+                output: str = '') -> Dict[str, Any]:
+        return {'pass': pass_name, 'status': status, 'tests_passed': tests_passed, 'reason': reason,
+                'output': output[-4000:]}
+
+
+def record_check(ground_truth: Dict[str, Any], result: Dict[str, Any]) -> Dict[str, Any]:
+    """ground truth의 semantics_check에 패스 결과 추가 (같은 ground truth 객체를 수정해 반환)"""
+    entry = {'pass': result['pass'], 'status': result['status'], 'tests_passed': result['tests_passed']}
+    if result['status'] != STATUS_PRESERVED and result['reason']:
+        entry['reason'] = result['reason']
+    ground_truth.setdefault('semantics_check', []).append(entry)
+    return ground_truth
+
+
+def describe_check(result: Dict[str, Any]) -> str:
+    icons = {STATUS_PRESERVED: '🔒', STATUS_BROKEN: '💥', STATUS_UNVERIFIED: '❔'}
+    detail = f"{result['tests_passed']} passed" if result['status'] == STATUS_PRESERVED else result['reason']
+    return f"{icons[result['status']]} 의미 보존 {result['status']} ({detail})"
+
+
+def main():
+    parser = argparse.ArgumentParser(description='난독화 의미 보존 검증')
+    parser.add_argument('original', help='원본 샘플 파일 (.rs)')
+    parser.add_argument('transformed', help='변환본 (파일 또는 Cargo 패키지 디렉토리)')
+    parser.add_argument('--pass-name', default='manual', help='기록용 패스 이름')
+    parser.add_argument('--offline', action='store_true', help='cargo --offline')
+    parser.add_argument('--verbose', action='store_true', help='실패 로그 출력')
+    args = parser.parse_args()
+
+    checker = SemanticsVerifier(CorpusVerifier(offline=args.offline))
+    result = checker.check_paths(args.pass_name, Path(args.original), Path(args.transformed))
+    print(describe_check(result))
+    if result['status'] == STATUS_BROKEN and args.verbose:
+        print(result['output'])
+    sys.exit(1 if result['status'] == STATUS_BROKEN else 0)
+
+
+if __name__ == "__main__":
+    main()
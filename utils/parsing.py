@@ -0,0 +1,212 @@
+"""
+tree-sitter 기반 다언어 파싱 계층
+
+C/Java/Python/Go 등 코퍼스의 다른 언어도 Rust처럼 구문 트리로 함수/컨테이너 항목을 색인하도록, tree-sitter 문법으로
+utils.sample_index.SampleIndex와 같은 형태의 항목 목록을 만듭니다. 줄 범위는 1부터 양끝 포함, 바이트 범위는
+tree-sitter가 주는 UTF-8 오프셋 그대로입니다.
+
+항목 종류 (언어별 노드 → 종류):
+    fn      함수/메서드/생성자 (본문이 없는 선언은 제외, Go 메서드는 "수신 타입::이름")
+    class   클래스/구조체/열거형/레코드 (C++은 본문이 있는 class/struct만)
+    impl    Rust impl 블록 (이름은 자기 타입, trait에 구현한 트레이트)
+    trait   Rust 트레이트, Java/C#/PHP 인터페이스
+    mod     Rust mod, C++/C# 네임스페이스, Ruby module
+소속 경로(qualified_name)는 언어와 관계없이 '::'로 잇습니다 ("CipherSuite::encrypt", "crypto::aes::expand_key").
+
+문법 (pip install "qvbench[parsing]"):
+    c, cpp, java, python, go, rust, javascript, typescript, c_sharp, ruby, php
+    py-tree-sitter 0.22 이상의 Language(<문법 모듈>.language()) 방식으로 불러옵니다. tree-sitter나 해당 언어 문법이
+    없으면 parse_items는 None을 돌려주고, SampleIndex(backend='auto')는 내장 분할기로 대신합니다.
+
+사용법:
+    python -m utils.parsing                                   # 설치된 문법 목록
+    python -m utils.sample_index data/test_files/source_code/CorporateSecurityOrchestrator.py --backend tree-sitter
+"""
+
+import importlib
+from functools import lru_cache
+from typing import Dict, Any, List, Optional, Tuple
+
+BACKEND = 'tree-sitter'
+
+# 언어 → (문법 모듈, Language 포인터를 돌려주는 함수)
+GRAMMARS: Dict[str, Tuple[str, str]] = {
+    'c': ('tree_sitter_c', 'language'),
+    'cpp': ('tree_sitter_cpp', 'language'),
+    'java': ('tree_sitter_java', 'language'),
+    'python': ('tree_sitter_python', 'language'),
+    'go': ('tree_sitter_go', 'language'),
+    'rust': ('tree_sitter_rust', 'language'),
+    'javascript': ('tree_sitter_javascript', 'language'),
+    'typescript': ('tree_sitter_typescript', 'language_typescript'),
+    'c_sharp': ('tree_sitter_c_sharp', 'language'),
+    'ruby': ('tree_sitter_ruby', 'language'),
+    'php': ('tree_sitter_php', 'language_php'),
+}
+
+SUFFIX_LANGUAGES = {
+    '.c': 'c', '.h': 'c', '.cpp': 'cpp', '.cc': 'cpp', '.hpp': 'cpp', '.java': 'java', '.py': 'python', '.go': 'go',
+    '.rs': 'rust', '.js': 'javascript', '.ts': 'typescript', '.cs': 'c_sharp', '.rb': 'ruby', '.php': 'php',
+}
+
+# 언어 → {노드 종류: 항목 종류}
+NODE_KINDS: Dict[str, Dict[str, str]] = {
+    'c': {'function_definition': 'fn'},
+    'cpp': {'function_definition': 'fn', 'class_specifier': 'class', 'struct_specifier': 'class',
+            'namespace_definition': 'mod'},
+    'java': {'method_declaration': 'fn', 'constructor_declaration': 'fn', 'class_declaration': 'class',
+             'enum_declaration': 'class', 'record_declaration': 'class', 'interface_declaration': 'trait'},
+    'python': {'function_definition': 'fn', 'class_definition': 'class'},
+    'go': {'function_declaration': 'fn', 'method_declaration': 'fn'},
+    'rust': {'function_item': 'fn', 'impl_item': 'impl', 'trait_item': 'trait', 'mod_item': 'mod'},
+    'javascript': {'function_declaration': 'fn', 'generator_function_declaration': 'fn', 'method_definition': 'fn',
+                   'variable_declarator': 'fn', 'class_declaration': 'class'},
+    'typescript': {'function_declaration': 'fn', 'generator_function_declaration': 'fn', 'method_definition': 'fn',
+                   'variable_declarator': 'fn', 'class_declaration': 'class', 'abstract_class_declaration': 'class'},
+    'c_sharp': {'method_declaration': 'fn', 'constructor_declaration': 'fn', 'local_function_statement': 'fn',
+                'class_declaration': 'class', 'struct_declaration': 'class', 'record_declaration': 'class',
+                'interface_declaration': 'trait', 'namespace_declaration': 'mod'},
+    'ruby': {'method': 'fn', 'singleton_method': 'fn', 'class': 'class', 'module': 'mod'},
+    'php': {'function_definition': 'fn', 'method_declaration': 'fn', 'class_declaration': 'class',
+            'interface_declaration': 'trait'},
+}
+
+# 본문 필드 없이도 항목으로 치는 노드 (Ruby 메서드/클래스는 body 필드가 없는 문법 버전이 있음)
+_BODY_OPTIONAL = {'ruby'}
+# 변수에 담긴 함수 (const encrypt = (k, v) => {...})
+_FUNCTION_VALUES = {'arrow_function', 'function_expression', 'function', 'generator_function'}
+# C/C++ 선언자 끝의 이름 노드
+_DECLARATOR_NAMES = {'identifier', 'field_identifier', 'qualified_identifier', 'destructor_name', 'operator_name'}
+
+
+def language_for(suffix: str) -> Optional[str]:
+    suffix = suffix.lower() if suffix.startswith('.') else f".{suffix.lower()}"
+    return SUFFIX_LANGUAGES.get(suffix)
+
+
+@lru_cache(maxsize=None)
+def load_parser(language: str):
+    """언어 → tree_sitter.Parser (tree-sitter나 문법이 없으면 None)"""
+    if language not in GRAMMARS:
+        return None
+    try:
+        import tree_sitter
+        module_name, function = GRAMMARS[language]
+        grammar = importlib.import_module(module_name)
+    except ImportError:
+        return None
+    return tree_sitter.Parser(tree_sitter.Language(getattr(grammar, function)()))
+
+
+def available_languages() -> List[str]:
+    return [language for language in GRAMMARS if load_parser(language) is not None]
+
+
+def _text(source: bytes, node) -> Optional[str]:
+    return source[node.start_byte:node.end_byte].decode('utf-8', errors='replace') if node is not None else None
+
+
+def _first(nodes):
+    return nodes[0] if nodes else None
+
+
+def _declarator_name(source: bytes, node) -> Optional[str]:
+    """C/C++ function_definition의 선언자 사슬 끝 이름 ("Foo::bar"처럼 한정된 이름은 그대로)"""
+    node = node.child_by_field_name('declarator')
+    while node is not None and node.type not in _DECLARATOR_NAMES:
+        inner = node.child_by_field_name('declarator')
+        node = inner if inner is not None else _first(node.named_children)
+    return _text(source, node)
+
+
+def _type_name(source: bytes, node) -> Optional[str]:
+    """Rust/Go 타입 노드 → 제네릭 인자, 경로, 참조/포인터를 뗀 이름"""
+    while node is not None:
+        if node.type in ('generic_type', 'reference_type', 'pointer_type'):
+            inner = node.child_by_field_name('type')
+            node = inner if inner is not None else _first(node.named_children[::-1])
+        elif node.type == 'scoped_type_identifier':
+            node = node.child_by_field_name('name')
+        else:
+            return _text(source, node)
+    return None
+
+
+def _receiver_type(source: bytes, node) -> Optional[str]:
+    """Go 메서드 수신자 (t *Cipher) → Cipher"""
+    receiver = node.child_by_field_name('receiver')
+    for parameter in receiver.named_children if receiver is not None else []:
+        return _type_name(source, parameter.child_by_field_name('type'))
+    return None
+
+
+def _item(source: bytes, node, language: str, kind: str) -> Optional[Dict[str, Any]]:
+    """노드 하나 → 항목 (이름을 못 찾거나 본문 없는 선언이면 None)"""
+    trait = None
+    if node.type == 'variable_declarator':
+        value = node.child_by_field_name('value')
+        if value is None or value.type not in _FUNCTION_VALUES:
+            return None
+        name = _text(source, node.child_by_field_name('name'))
+    elif language in ('c', 'cpp') and node.type == 'function_definition':
+        name = _declarator_name(source, node)
+    elif node.type == 'impl_item':
+        name = _type_name(source, node.child_by_field_name('type'))
+        trait = _type_name(source, node.child_by_field_name('trait'))
+    else:
+        name = _text(source, node.child_by_field_name('name'))
+    if not name:
+        return None
+    if node.type != 'variable_declarator' and language not in _BODY_OPTIONAL and \
+            node.child_by_field_name('body') is None:
+        return None
+
+    # 데코레이터가 붙은 파이썬 정의는 데코레이터부터
+    start = node.parent if node.parent is not None and node.parent.type == 'decorated_definition' else node
+    end_row, end_column = node.end_point
+    return {'kind': kind, 'name': name, 'trait': trait,
+            'start_line': start.start_point[0] + 1,
+            'end_line': end_row if end_column == 0 and end_row > node.start_point[0] else end_row + 1,
+            'start_byte': start.start_byte, 'end_byte': node.end_byte,
+            'receiver': _receiver_type(source, node) if node.type == 'method_declaration' and language == 'go'
+            else None}
+
+
+def parse_items(text: str, suffix: str) -> Optional[List[Dict[str, Any]]]:
+    """소스 → 항목 목록 [{'kind', 'name', 'trait', 'qualified_name', 'start_line', 'end_line', 'start_byte',
+    'end_byte', 'parent'}] (문법이 없으면 None)"""
+    language = language_for(suffix)
+    parser = load_parser(language) if language else None
+    if parser is None:
+        return None
+
+    source = text.encode('utf-8')
+    kinds = NODE_KINDS[language]
+    items: List[Dict[str, Any]] = []
+    stack = [(parser.parse(source).root_node, None)]
+    while stack:
+        node, parent = stack.pop()
+        item = _item(source, node, language, kinds[node.type]) if node.type in kinds else None
+        if item is not None:
+            receiver = item.pop('receiver')
+            item['parent'] = parent
+            prefix = items[parent]['qualified_name'] if parent is not None else receiver
+            item['qualified_name'] = f"{prefix}::{item['name']}" if prefix else item['name']
+            items.append(item)
+            parent = len(items) - 1
+        stack.extend((child, parent) for child in reversed(node.children))
+    return items
+
+
+def main():
+    languages = available_languages()
+    print(f"🌳 tree-sitter 문법 {len(languages)}/{len(GRAMMARS)}개 사용 가능")
+    for language, (module_name, _) in GRAMMARS.items():
+        suffixes = ', '.join(suffix for suffix, name in SUFFIX_LANGUAGES.items() if name == language)
+        print(f"  {'✅' if language in languages else '❌'} {language:<11} {module_name:<24} {suffixes}")
+    if len(languages) < len(GRAMMARS):
+        print('  없는 문법은 내장 분할기로 대신합니다 (pip install "qvbench[parsing]")')
+
+
+if __name__ == '__main__':
+    main()
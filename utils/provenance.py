@@ -0,0 +1,125 @@
+"""
+샘플 출처 (provenance)와 라이선스
+
+공개 저장소 코드를 고쳐 쓴 샘플이 코퍼스에 들어오면, 코퍼스를 재배포할 수 있는지와 모델이 원본 코드를
+학습 중에 봤을 가능성을 샘플마다 따져야 합니다. 그래서 ground truth 최상위의 `provenance` 필드에 샘플이
+어디서 왔는지 기록합니다.
+
+    hand-written              (기본값) 저장소 기여자가 직접 작성
+    generated-from-template   템플릿 생성기 출력 (utils/sample_generator.py, utils/template_dsl.py)
+                              generated_by가 있으면 따로 적지 않아도 생성기 이름과 템플릿 버전으로 채워짐
+    adapted-from-oss          오픈소스 코드를 고쳐 씀: 원본 source(URL)와 SPDX license 식별자 필수,
+                              revision(커밋/태그)과 copyright(원본 저작권 표시)는 선택
+
+Ground truth 표기:
+    {"provenance": {"kind": "adapted-from-oss", "source": "https://github.com/<owner>/<repo>/blob/<rev>/src/aes.c",
+                    "license": "MIT", "revision": "<rev>", "copyright": "Copyright (c) 2019 <author>"}}
+
+재배포 검사:
+    REDISTRIBUTABLE_LICENSES에 없는 라이선스(GPL 계열 등)는 lint가 경고합니다. 코퍼스를 내보낼 때 원본
+    라이선스 조건을 따로 확인해야 한다는 뜻이며, `A OR B` 식은 하나라도 표에 있으면 통과입니다.
+"""
+
+import re
+from typing import Dict, Any, List, Optional, Tuple
+
+PROVENANCE_HAND_WRITTEN = 'hand-written'
+PROVENANCE_GENERATED = 'generated-from-template'
+PROVENANCE_ADAPTED = 'adapted-from-oss'
+
+PROVENANCE_KINDS = [PROVENANCE_HAND_WRITTEN, PROVENANCE_GENERATED, PROVENANCE_ADAPTED]
+
+# 코퍼스와 함께 재배포해도 되는 (저작권 표시만 요구하는) SPDX 라이선스
+REDISTRIBUTABLE_LICENSES = ['0BSD', 'Apache-2.0', 'BSD-2-Clause', 'BSD-3-Clause', 'CC0-1.0', 'ISC', 'MIT',
+                            'Unlicense', 'Zlib']
+
+SPDX_ID = re.compile(r'^[A-Za-z0-9][A-Za-z0-9.\-+]*$')
+
+
+def sample_provenance(ground_truth: Optional[Dict[str, Any]]) -> Dict[str, Any]:
+    """샘플 출처 {'kind', ...} (기록이 없으면 generated_by로 추론, 그것도 없으면 hand-written)"""
+    if not ground_truth:
+        return {'kind': PROVENANCE_HAND_WRITTEN}
+
+    recorded = ground_truth.get('provenance')
+    provenance = dict(recorded) if isinstance(recorded, dict) else {}
+    generated = ground_truth.get('generated_by')
+    if isinstance(generated, dict):
+        provenance.setdefault('kind', PROVENANCE_GENERATED)
+        if provenance['kind'] == PROVENANCE_GENERATED:
+            provenance.setdefault('template', generated.get('generator'))
+            provenance.setdefault('template_version', generated.get('template_version'))
+    provenance.setdefault('kind', PROVENANCE_HAND_WRITTEN)
+    return provenance
+
+
+def provenance_kind(ground_truth: Optional[Dict[str, Any]]) -> str:
+    return sample_provenance(ground_truth)['kind']
+
+
+def license_ids(expression: str) -> List[str]:
+    """SPDX 라이선스 식 → 식별자 목록 ('MIT OR Apache-2.0' → ['MIT', 'Apache-2.0'])"""
+    tokens = re.split(r'\s+(?:OR|AND|WITH)\s+', expression.replace('(', ' ').replace(')', ' ').strip())
+    return [token.strip() for token in tokens if token.strip()]
+
+
+def is_redistributable(expression: str) -> bool:
+    return any(license_id in REDISTRIBUTABLE_LICENSES for license_id in license_ids(expression))
+
+
+def provenance_issues(ground_truth: Dict[str, Any]) -> List[Tuple[str, str]]:
+    """출처 기록 검사 → [(level, message)] (utils/corpus.py lint)"""
+    recorded = ground_truth.get('provenance')
+    if recorded is None:
+        return []
+    if not isinstance(recorded, dict):
+        return [('error', "provenance must be an object with a 'kind'")]
+
+    issues = []
+    provenance = sample_provenance(ground_truth)
+    kind = provenance['kind']
+    if kind not in PROVENANCE_KINDS:
+        return [('error', f"unknown provenance kind '{kind}' (choose from {PROVENANCE_KINDS})")]
+    if ground_truth.get('generated_by') and kind != PROVENANCE_GENERATED:
+        issues.append(('error', f"generated_by recorded but provenance kind is '{kind}'"))
+    if kind == PROVENANCE_GENERATED and not ground_truth.get('generated_by') and not provenance.get('template'):
+        issues.append(('error', "generated-from-template provenance needs a template (or generated_by)"))
+
+    if kind != PROVENANCE_ADAPTED:
+        for key in ('source', 'license'):
+            if provenance.get(key):
+                issues.append(('error', f"provenance {key} recorded but kind is '{kind}'"))
+        return issues
+
+    source, expression = provenance.get('source'), provenance.get('license')
+    if not source:
+        issues.append(('error', "adapted-from-oss provenance has no source"))
+    elif not re.match(r'^https?://', source):
+        issues.append(('warning', f"provenance source '{source}' is not an http(s) URL"))
+    if not expression:
+        issues.append(('error', "adapted-from-oss provenance has no SPDX license"))
+        return issues
+    invalid = [license_id for license_id in license_ids(expression) if not SPDX_ID.match(license_id)]
+    if invalid:
+        issues.append(('error', f"license '{expression}' is not an SPDX expression ({', '.join(invalid)})"))
+    elif not is_redistributable(expression):
+        issues.append(('warning', f"license '{expression}' is not in REDISTRIBUTABLE_LICENSES; "
+                                  f"check its terms before publishing the corpus"))
+    return issues
+
+
+def provenance_label(provenance: Dict[str, Any]) -> str:
+    """요약 키: 'hand-written', 'generated-from-template (legacy)', 'adapted-from-oss (MIT)'"""
+    kind = provenance.get('kind', PROVENANCE_HAND_WRITTEN)
+    if kind == PROVENANCE_GENERATED and provenance.get('template'):
+        return f"{kind} ({provenance['template']})"
+    if kind == PROVENANCE_ADAPTED:
+        return f"{kind} ({provenance.get('license') or 'no license'})"
+    return kind
+
+
+def attribution(test_id: str, provenance: Dict[str, Any]) -> Optional[Dict[str, Any]]:
+    """재배포 시 함께 내보낼 원본 저작권 표시 (adapted-from-oss만)"""
+    if provenance.get('kind') != PROVENANCE_ADAPTED:
+        return None
+    return {'test_id': test_id, **{key: provenance.get(key) for key in ('source', 'license', 'revision', 'copyright')}}
@@ -0,0 +1,300 @@
+"""
+API 호출 속도 제한과 일일 비용 한도 (quota)
+
+큰 코퍼스를 원격 모델로 돌리면 프로바이더의 분당 요청 한도나 예산을 예고 없이 넘길 수 있습니다.
+
+    속도 제한   프로바이더마다 토큰 버킷 하나를 병렬 작업자(--parallel)가 함께 씀
+                버킷이 비면 채워질 때까지 기다림 (대기 중에도 샘플 제한 시간/취소 신호 확인)
+    비용 한도   호출마다 사용 토큰 × 모델 단가로 비용을 계산해 날짜별 장부(ledger)에 누적
+                오늘 누적 비용이 한도에 닿으면 QuotaExceeded로 실행을 멈추고 지금까지의 결과로 요약
+                장부는 파일 잠금 아래에서 다시 읽어 합치므로 동시에 도는 실행끼리도 한도를 나눠 씀 (POSIX)
+                실패한 호출도 과금될 수 있으므로 장부에 기록 (failed_calls)
+
+judge 호출(--label-judge, --rationale judge)도 같은 버킷과 장부를 씁니다.
+응답에 토큰 사용량이 없으면 문자 수 / 4로 추정하고 장부에 추정치임을 표시합니다.
+단가가 없는 모델은 비용 0으로 세고, 원격 프로바이더 모델이면 요약에 단가 없음으로 보고합니다.
+
+설정 (config.yaml):
+    benchmark:
+      rate_limits:                      # 프로바이더별, 없는 프로바이더는 제한 없음
+        openai: {requests_per_minute: 60, burst: 5}
+      cost:
+        daily_limit_usd: 20             # 0이면 한도 없음 (--daily-cost-limit으로 덮어쓰기)
+        ledger: "results/cost_ledger.json"
+        pricing:                        # 1M 토큰당 USD, 모델 이름 앞부분 일치 (가장 긴 항목 우선)
+          gpt-4.1: {prompt: 2.0, completion: 8.0}
+"""
+
+import json
+import os
+import threading
+import time
+from contextlib import contextmanager
+from datetime import date
+from pathlib import Path
+from typing import Any, Callable, Dict, List, Optional
+
+from clients.network_guard import REMOTE_PROVIDERS
+from utils.run_control import Cancelled, CancellationToken
+from utils.tracing import span
+
+try:
+    import fcntl
+except ImportError:  # Windows: 프로세스 간 잠금 없이 스레드 잠금만
+    fcntl = None
+
+CHARS_PER_TOKEN = 4
+MAX_WAIT_STEP = 0.5  # 버킷 대기 중 취소 신호를 확인하는 간격(초)
+DEFAULT_LEDGER = 'results/cost_ledger.json'
+
+
+class QuotaExceeded(Cancelled):
+    """일일 비용 한도 도달 (실행 전체를 멈춤)"""
+
+
+class TokenBucket:
+    """분당 rate개씩 채워지고 최대 burst개까지 쌓이는 토큰 버킷 (스레드 간 공유)"""
+
+    def __init__(self, rate_per_minute: float, burst: int = 1,
+                 clock: Callable[[], float] = time.monotonic, sleep: Callable[[float], None] = time.sleep):
+        self.rate = rate_per_minute / 60.0
+        self.burst = max(1, int(burst))
+        self.tokens = float(self.burst)
+        self.clock = clock
+        self.sleep = sleep
+        self.updated = clock()
+        self.lock = threading.Lock()
+
+    def _refill(self):
+        now = self.clock()
+        self.tokens = min(self.burst, self.tokens + (now - self.updated) * self.rate)
+        self.updated = now
+
+    def acquire(self, token: Optional[CancellationToken] = None) -> float:
+        """토큰 하나를 가져옴 (없으면 기다림)
+
+        Returns:
+            기다린 시간(초)
+        """
+        waited = 0.0
+        while True:
+            if token:
+                token.raise_if_cancelled()
+            with self.lock:
+                self._refill()
+                if self.tokens >= 1:
+                    self.tokens -= 1
+                    return waited
+                wait = (1 - self.tokens) / self.rate
+            step = min(wait, MAX_WAIT_STEP)
+            self.sleep(step)
+            waited += step
+
+
+class RateLimiter:
+    """프로바이더별 토큰 버킷"""
+
+    def __init__(self, settings: Optional[Dict[str, Dict[str, Any]]] = None):
+        self.settings = {
+            provider: {'requests_per_minute': float(limits['requests_per_minute']),
+                       'burst': int(limits.get('burst', 1))}
+            for provider, limits in (settings or {}).items()
+            if limits and limits.get('requests_per_minute')
+        }
+        self.buckets = {provider: TokenBucket(limits['requests_per_minute'], limits['burst'])
+                        for provider, limits in self.settings.items()}
+        self.waited: Dict[str, float] = {}
+        self.lock = threading.Lock()
+
+    def acquire(self, provider: str, token: Optional[CancellationToken] = None) -> float:
+        bucket = self.buckets.get(provider)
+        if bucket is None:
+            return 0.0
+        waited = bucket.acquire(token)
+        with self.lock:
+            self.waited[provider] = self.waited.get(provider, 0.0) + waited
+        return waited
+
+    def report(self) -> Dict[str, Any]:
+        return {'limits': self.settings, 'waited_seconds': {p: round(w, 2) for p, w in self.waited.items()}}
+
+
+def usage_tokens(usage: Optional[Dict[str, Any]], prompt: str, content: str) -> Dict[str, Any]:
+    """응답의 토큰 사용량 (없으면 문자 수로 추정, cached_tokens는 프로바이더 프롬프트 캐시에서 읽은 토큰)"""
+    usage = usage or {}
+    prompt_tokens = usage.get('prompt_tokens') or usage.get('input_tokens')
+    completion_tokens = usage.get('completion_tokens') or usage.get('output_tokens')
+    if prompt_tokens is None and completion_tokens is None:
+        return {'prompt_tokens': len(prompt or '') // CHARS_PER_TOKEN,
+                'completion_tokens': len(content or '') // CHARS_PER_TOKEN,
+                'cached_tokens': 0, 'estimated': True}
+    cached_tokens = (usage.get('prompt_tokens_details') or {}).get('cached_tokens') \
+        or usage.get('cache_read_input_tokens') or 0
+    return {'prompt_tokens': int(prompt_tokens or 0), 'completion_tokens': int(completion_tokens or 0),
+            'cached_tokens': int(cached_tokens), 'estimated': False}
+
+
+class CostTracker:
+    """모델 단가로 호출 비용을 계산하고 날짜별 장부에 누적 (일일 한도 확인)"""
+
+    def __init__(self, daily_limit: Optional[float] = None, pricing: Optional[Dict[str, Dict[str, float]]] = None,
+                 ledger_path: Optional[str] = DEFAULT_LEDGER, today: Callable[[], date] = date.today):
+        self.daily_limit = float(daily_limit) if daily_limit else None
+        self.pricing = pricing or {}
+        self.ledger_path = Path(ledger_path) if ledger_path else None
+        self.today = today
+        self.lock = threading.Lock()
+        self.ledger = self._load_ledger()
+        self.run = {'cost_usd': 0.0, 'calls': 0, 'failed_calls': 0, 'prompt_tokens': 0, 'completion_tokens': 0,
+                    'estimated_calls': 0, 'unpriced_models': []}
+
+    @contextmanager
+    def _ledger_lock(self, shared: bool = False):
+        """장부 옆 .lock 파일의 프로세스 간 잠금 (읽기는 공유, 기록은 배타)"""
+        if not self.ledger_path or fcntl is None:
+            yield
+            return
+        self.ledger_path.parent.mkdir(parents=True, exist_ok=True)
+        with open(self.ledger_path.with_name(self.ledger_path.name + '.lock'), 'a') as handle:
+            fcntl.flock(handle, fcntl.LOCK_SH if shared else fcntl.LOCK_EX)
+            try:
+                yield
+            finally:
+                fcntl.flock(handle, fcntl.LOCK_UN)
+
+    def _load_ledger(self) -> Dict[str, Any]:
+        if self.ledger_path and self.ledger_path.exists():
+            with open(self.ledger_path, 'r', encoding='utf-8') as f:
+                return json.load(f)
+        return {}
+
+    def _save_ledger(self):
+        if not self.ledger_path:
+            return
+        self.ledger_path.parent.mkdir(parents=True, exist_ok=True)
+        # 임시 파일에 쓰고 바꿔치기 (잠금 없이 읽는 쪽도 반쯤 쓴 파일을 보지 않음)
+        temporary = self.ledger_path.with_name(self.ledger_path.name + '.tmp')
+        with open(temporary, 'w', encoding='utf-8') as f:
+            json.dump(self.ledger, f, indent=2, ensure_ascii=False)
+        os.replace(temporary, self.ledger_path)
+
+    def price(self, model: str) -> Optional[Dict[str, float]]:
+        """모델 단가 (1M 토큰당 USD), 이름 앞부분이 가장 길게 일치하는 항목"""
+        matches = [key for key in self.pricing if model == key or model.startswith(key)]
+        return self.pricing[max(matches, key=len)] if matches else None
+
+    def spent_today(self) -> float:
+        """오늘 누적 비용 (다른 실행이 쓴 비용까지 보도록 장부를 다시 읽음)"""
+        with self.lock:
+            if self.ledger_path:
+                with self._ledger_lock(shared=True):
+                    self.ledger = self._load_ledger()
+            return self.ledger.get(self.today().isoformat(), {}).get('cost_usd', 0.0)
+
+    def check(self):
+        """오늘 누적 비용이 한도에 닿았으면 QuotaExceeded"""
+        if self.daily_limit is None:
+            return
+        spent = self.spent_today()
+        if spent >= self.daily_limit:
+            raise QuotaExceeded(f"daily cost limit reached (${spent:.2f} / ${self.daily_limit:.2f})")
+
+    def record(self, provider: str, model: str, usage: Optional[Dict[str, Any]],
+               prompt: str = '', content: str = '', rate: float = 1.0, failed: bool = False) -> float:
+        """호출 한 번의 비용을 장부에 누적 (rate: 단가 배율, 배치 추론은 할인율, failed: 실패한 호출)
+
+        Returns:
+            이번 호출 비용 (USD)
+        """
+        tokens = usage_tokens(usage, prompt, content)
+        price = self.price(model)
+        cost = 0.0
+        if price:
+            cost = (tokens['prompt_tokens'] * price.get('prompt', 0.0)
+                    + tokens['completion_tokens'] * price.get('completion', 0.0)) / 1_000_000 * rate
+
+        model_key = f"{provider}/{model}"
+        with self.lock, self._ledger_lock():
+            if self.ledger_path:
+                # 마지막으로 읽은 뒤 다른 실행이 기록한 비용과 합침
+                self.ledger = self._load_ledger()
+            day = self.ledger.setdefault(self.today().isoformat(), {'cost_usd': 0.0, 'calls': 0, 'by_model': {}})
+            entry = day['by_model'].setdefault(model_key, {'cost_usd': 0.0, 'calls': 0, 'prompt_tokens': 0,
+                                                           'completion_tokens': 0, 'estimated_calls': 0})
+            for target in (day, entry, self.run):
+                target['cost_usd'] += cost
+                target['calls'] += 1
+                target['failed_calls'] = target.get('failed_calls', 0) + int(failed)
+            for target in (entry, self.run):
+                target['prompt_tokens'] += tokens['prompt_tokens']
+                target['completion_tokens'] += tokens['completion_tokens']
+                target['estimated_calls'] += int(tokens['estimated'])
+            if not price and provider in REMOTE_PROVIDERS and model_key not in self.run['unpriced_models']:
+                self.run['unpriced_models'].append(model_key)
+            self._save_ledger()
+        return cost
+
+    def report(self) -> Dict[str, Any]:
+        return {
+            **self.run,
+            'cost_usd': round(self.run['cost_usd'], 6),
+            'spent_today_usd': round(self.spent_today(), 6),
+            'daily_limit_usd': self.daily_limit,
+            'ledger': str(self.ledger_path) if self.ledger_path else None
+        }
+
+
+class MeteredClient:
+    """클라이언트 래퍼: 호출 전 한도 확인과 버킷 대기, 호출 후 비용 기록 (호출마다 api_call span)"""
+
+    def __init__(self, client, provider: str, model: str, limiter: RateLimiter, costs: CostTracker,
+                 token: Optional[CancellationToken] = None):
+        self.client = client
+        self.provider = provider
+        self.model = model
+        self.limiter = limiter
+        self.costs = costs
+        self.token = token
+
+    def benchmark_request(self, prompt: str, max_tokens: int = 1000) -> Dict[str, Any]:
+        return self._metered(lambda: self.client.benchmark_request(prompt, max_tokens), prompt)
+
+    def benchmark_tool_request(self, messages: List[Dict[str, Any]], tools: List[Dict[str, Any]],
+                               max_tokens: int = 1000, allow_tools: bool = True) -> Dict[str, Any]:
+        # 사용량이 없는 응답의 토큰 추정용 (대화 전체를 다시 보내므로 모든 메시지)
+        prompt = '\n'.join(str(message.get('content') or '') for message in messages)
+        return self._metered(lambda: self.client.benchmark_tool_request(messages, tools, max_tokens, allow_tools),
+                             prompt)
+
+    def _metered(self, call: Callable[[], Dict[str, Any]], prompt: str) -> Dict[str, Any]:
+        self.costs.check()
+        self.limiter.acquire(self.provider, self.token)
+        self.costs.check()  # 대기하는 동안 다른 작업자가 한도를 채웠을 수 있음
+        with span('api_call', provider=self.provider, model=self.model) as call_span:
+            response = call()
+            # 실패한 호출도 과금될 수 있으므로 기록 (사용량이 없으면 프롬프트 길이로 추정)
+            response['cost_usd'] = self.costs.record(self.provider, self.model, response.get('usage'),
+                                                     prompt, response.get('content') or '',
+                                                     failed=not response.get('success'))
+            if response.get('success'):
+                response['cached_tokens'] = usage_tokens(response.get('usage'), prompt, '')['cached_tokens']
+            call_span.set(success=bool(response.get('success')), cost_usd=response.get('cost_usd', 0.0),
+                          cached_tokens=response.get('cached_tokens', 0))
+        return response
+
+    def __getattr__(self, name):
+        return getattr(self.client, name)
+
+
+def quota_settings(config: Optional[Dict[str, Any]]) -> Dict[str, Any]:
+    """benchmark 설정 → {'rate_limits': {...}, 'cost': {...}}"""
+    config = config or {}
+    cost = dict(config.get('cost') or {})
+    return {
+        'rate_limits': dict(config.get('rate_limits') or {}),
+        'cost': {
+            'daily_limit_usd': cost.get('daily_limit_usd') or None,
+            'ledger': cost.get('ledger', DEFAULT_LEDGER),
+            'pricing': dict(cost.get('pricing') or {})
+        }
+    }
@@ -0,0 +1,294 @@
+"""
+탐지 근거(rationale) 채점
+
+라벨이 맞아도 근거가 엉뚱하면 설명 가능한 탐지라고 할 수 없습니다. 응답의 자유 서술 근거(`rationale` 필드)가
+정답 알고리즘을 식별하는 올바른 증거를 짚는지 채점합니다. 예: ChaCha20이면 sigma 상수 0x61707865("expa")나
+"expand 32-byte k", SHA-256이면 라운드 상수 0x428a2f98.
+
+정답 라벨마다 인용 가능한 증거(cue)를 모읍니다:
+    - 알고리즘 계열별 서명 상수/구조 (EVIDENCE_SIGNATURES) 중 실제 샘플에 나오는 것
+    - ground truth 위치 정보(vulnerability_analysis … locations)의 근거 문구와 줄 범위
+
+채점 방식:
+    rubric   규칙 기반 (기본). 라벨별 점수: 증거를 인용 1.0 / 알고리즘 이름만 언급 RUBRIC_NAME_ONLY / 언급 없음 0.0
+             (줄 범위 인용은 그 알고리즘을 함께 언급했을 때만 증거로 인정)
+             인용 가능한 증거가 없는 라벨은 채점하지 않습니다 (rationale_score의 scored_labels에서 빠짐)
+    judge    채점 모델이 같은 증거 목록을 보고 라벨마다 0(언급 없음) / 1(이름만) / 2(올바른 증거 인용)로 평가
+
+설정 (config.yaml):
+    benchmark:
+      rationale:
+        enabled: false        # 응답 JSON에 rationale 필드를 요청하고 채점
+        scorer: "rubric"      # rubric | judge
+        judge:
+          provider: "openai"
+          model: "gpt-4.1"
+
+사용법 (저장된 결과의 rationale을 다시 채점):
+    python -m utils.rationale results/benchmark_results_20250101_120000.json
+    python -m utils.rationale results/run.json --scorer judge --judge-provider openai --judge-model gpt-4.1
+"""
+
+import argparse
+import json
+import re
+from typing import Dict, Any, List, Optional
+
+from utils.metrics_calculator import MetricsCalculator
+from utils.taxonomy import AlgorithmTaxonomy, normalize_name
+
+SCORERS = ['rubric', 'judge']
+
+RUBRIC_NAME_ONLY = 0.3
+
+# 알고리즘 계열 → 구현을 식별하는 상수/구조 (소문자 비교)
+EVIDENCE_SIGNATURES = {
+    'ChaCha20': ['0x61707865', '0x3320646e', '0x79622d32', '0x6b206574', 'expand 32-byte k', 'quarter round',
+                 'quarterround'],
+    'Salsa20': ['0x61707865', '0x3320646e', '0x79622d32', '0x6b206574', 'expand 32-byte k'],
+    'AES': ['0x63, 0x7c', '0x637c777b', 'subbytes', 'shiftrows', 'mixcolumns', 'rcon', 'sbox', 's-box'],
+    'SHA-256': ['0x428a2f98', '0x71374491', '0x6a09e667', '0xbb67ae85'],
+    'SHA-1': ['0x5a827999', '0x6ed9eba1', '0x8f1bbcdc', '0xca62c1d6', '0xc3d2e1f0'],
+    'MD5': ['0xd76aa478', '0xe8c7b756', '0x242070db', '0x67452301', '0xefcdab89'],
+    'SEED': ['0x9e3779b9', 'ss0', 'ss1', 'ss2', 'ss3'],
+    'ARIA': ['0x517cc1b7', '0x6db14acc', '0xc3efe9db', 'sl1', 'sl2'],
+    'LEA': ['0xc3efe9db', '0x44626b02', '0x79e27c8a', '0x78df30ec'],
+    'TEA': ['0x9e3779b9', 'delta'],
+    'RC4': ['ksa', 'prga', 'key scheduling', '% 256', '& 0xff'],
+    'DES': ['feistel', 'pc1', 'pc2', 'initial permutation', 'expansion'],
+    'RSA': ['65537', '0x10001', 'modpow', 'mod_pow', 'modular exponentiation', 'totient', 'phi'],
+    'DH': ['generator', 'shared secret', 'modular exponentiation', 'modpow', 'prime modulus'],
+    'ECC': ['secp256k1', 'p-256', 'secp256r1', 'point addition', 'point doubling', 'scalar multiplication',
+            '0xfffffffffffffffffffffffffffffffefffffc2f'],
+    'Poly1305': ['0x0ffffffc0ffffffc', '0x0ffffffc', '0x3ffffff', 'clamp', '2^130 - 5', '2**130'],
+}
+
+_LINE_REFERENCE = re.compile(r'\blines?\s+(\d+)(?:\s*(?:-|–|to)\s*(\d+))?', re.IGNORECASE)
+_WORD = re.compile(r'[a-z0-9\-]{3,}')
+
+_taxonomy = AlgorithmTaxonomy()
+
+
+def rationale_settings(config: Optional[Dict[str, Any]]) -> Dict[str, Any]:
+    config = config or {}
+    return {
+        'enabled': bool(config.get('enabled', False)),
+        'scorer': config.get('scorer', 'rubric'),
+        'judge': dict(config.get('judge', {}))
+    }
+
+
+def _family(label: str) -> str:
+    node = _taxonomy.resolve(label)
+    return node[1] or label
+
+
+def ground_truth_locations(ground_truth: Dict[str, Any], label: str) -> List[Dict[str, Any]]:
+    """ground truth에 기록된 라벨의 위치 정보 [{'line_range', 'evidence', 'description'}]"""
+    analysis = ground_truth.get('vulnerability_analysis')
+    if not isinstance(analysis, dict):
+        return []
+    locations = []
+    for entry in analysis.get('quantum_vulnerable_algorithms', []):
+        if normalize_name(entry.get('algorithm', '')) == normalize_name(label):
+            locations.extend(location for location in entry.get('locations', []) if isinstance(location, dict))
+    return locations
+
+
+def expected_evidence(ground_truth: Dict[str, Any], input_data: str) -> Dict[str, Dict[str, Any]]:
+    """정답 라벨별 인용 가능한 증거 {'signatures': [...], 'phrases': [...], 'line_ranges': [[a, b]]}"""
+    sample = input_data.lower()
+    evidence = {}
+    for label in MetricsCalculator.get_expected_labels(ground_truth):
+        family = _family(label)
+        signatures = [cue for cue in EVIDENCE_SIGNATURES.get(family, []) if cue in sample]
+
+        phrases, line_ranges = [], []
+        for location in ground_truth_locations(ground_truth, label):
+            phrases.extend(part.strip() for part in str(location.get('evidence', '')).split(',') if part.strip())
+            match = re.match(r'\s*(\d+)\s*-\s*(\d+)', str(location.get('line_range', '')))
+            if match:
+                line_ranges.append([int(match.group(1)), int(match.group(2))])
+        evidence[label] = {'family': family, 'signatures': signatures, 'phrases': phrases,
+                           'line_ranges': line_ranges}
+    return evidence
+
+
+def _cites_phrase(rationale: str, phrase: str) -> bool:
+    """근거 문구의 단어(3자 이상)가 모두 rationale에 있으면 인용으로 봄"""
+    words = _WORD.findall(phrase.lower())
+    return bool(words) and all(word in rationale for word in words)
+
+
+def _cited_lines(rationale: str) -> List[List[int]]:
+    spans = []
+    for match in _LINE_REFERENCE.finditer(rationale):
+        first = int(match.group(1))
+        last = int(match.group(2)) if match.group(2) else first
+        spans.append([min(first, last), max(first, last)])
+    return spans
+
+
+def score_rubric(rationale: Optional[str], ground_truth: Dict[str, Any],
+                 input_data: str) -> Optional[Dict[str, Any]]:
+    """규칙 기반 근거 점수 (채점할 라벨이 없으면 None)"""
+    evidence = {label: cues for label, cues in expected_evidence(ground_truth, input_data).items()
+                if cues['signatures'] or cues['phrases'] or cues['line_ranges']}
+    if not evidence:
+        return None
+
+    text = (rationale or '').lower()
+    cited_lines = _cited_lines(text)
+    per_label = {}
+    for label, cues in evidence.items():
+        cited = [cue for cue in cues['signatures'] if cue in text]
+        cited += [phrase for phrase in cues['phrases'] if _cites_phrase(text, phrase)]
+        named = any(normalize_name(name) in normalize_name(text) for name in (label, cues['family']))
+        # 줄 번호만으로는 어느 알고리즘 얘기인지 알 수 없으므로 알고리즘을 언급한 경우에만 인정
+        if named:
+            cited += [f"lines {a}-{b}" for a, b in cues['line_ranges']
+                      if any(start <= b and end >= a for start, end in cited_lines)]
+        score = 1.0 if cited else (RUBRIC_NAME_ONLY if named else 0.0)
+        per_label[label] = {'score': score, 'cited': cited, 'named': named}
+
+    return {
+        'method': 'rubric',
+        'score': sum(item['score'] for item in per_label.values()) / len(per_label),
+        'scored_labels': sorted(per_label),
+        'per_label': per_label
+    }
+
+
+class RationaleJudge:
+    """채점 모델로 근거 평가"""
+
+    def __init__(self, client, max_tokens: int = 800):
+        self.client = client
+        self.max_tokens = max_tokens
+
+    def prompt(self, rationale: str, evidence: Dict[str, Dict[str, Any]]) -> str:
+        cues = "\n".join(
+            f"- {label}: " + '; '.join(cues['signatures'] + cues['phrases']
+                                       + [f"lines {a}-{b}" for a, b in cues['line_ranges']])
+            for label, cues in evidence.items()
+        )
+        return f"""You are grading the explanation a cryptographic-algorithm detector gave for its findings.
+
+For each expected algorithm below, the listed items are correct evidence that identifies it in the analyzed file.
+Grade whether the explanation cites correct evidence for that algorithm:
+- 2: cites at least one correct identifying constant, structure or location for the algorithm
+- 1: names the algorithm but gives no correct identifying evidence (or only generic evidence)
+- 0: does not mention the algorithm
+
+Expected algorithms and correct evidence:
+{cues}
+
+Explanation to grade:
+\"\"\"{rationale}\"\"\"
+
+Respond ONLY with JSON mapping each expected algorithm to its grade, e.g. {{"ChaCha20": 2}}."""
+
+    def score(self, rationale: Optional[str], ground_truth: Dict[str, Any],
+              input_data: str) -> Optional[Dict[str, Any]]:
+        evidence = {label: cues for label, cues in expected_evidence(ground_truth, input_data).items()
+                    if cues['signatures'] or cues['phrases'] or cues['line_ranges']}
+        if not evidence:
+            return None
+        if not rationale:
+            return {'method': 'judge', 'score': 0.0, 'scored_labels': sorted(evidence),
+                    'per_label': {label: {'score': 0.0, 'grade': 0} for label in evidence}}
+
+        response = self.client.benchmark_request(self.prompt(rationale, evidence), self.max_tokens)
+        if not response.get('success'):
+            return {'method': 'judge', 'score': None, 'error': response.get('error')}
+        match = re.search(r'\{.*\}', response.get('content', ''), re.DOTALL)
+        try:
+            grades = json.loads(match.group(0)) if match else {}
+        except json.JSONDecodeError:
+            grades = {}
+
+        per_label = {}
+        for label in evidence:
+            grade = next((value for key, value in grades.items() if normalize_name(key) == normalize_name(label)), 0)
+            grade = min(max(int(grade) if isinstance(grade, (int, float)) else 0, 0), 2)
+            per_label[label] = {'score': grade / 2, 'grade': grade}
+        return {
+            'method': 'judge',
+            'score': sum(item['score'] for item in per_label.values()) / len(per_label),
+            'scored_labels': sorted(per_label),
+            'per_label': per_label
+        }
+
+
+def create_judge(settings: Dict[str, Any], config_loader) -> RationaleJudge:
+    """설정의 judge 프로바이더/모델로 채점기 생성"""
+    from clients.client_factory import ClientFactory
+
+    provider = settings['judge'].get('provider')
+    model = settings['judge'].get('model')
+    if not provider or not model:
+        raise ValueError("rationale.judge.provider / model must be set for the judge scorer")
+    llm_config = config_loader.get_llm_config(provider, model_name=model)
+    return RationaleJudge(ClientFactory.create_client(provider, llm_config))
+
+
+def score_rationale(rationale: Optional[str], ground_truth: Dict[str, Any], input_data: str,
+                    judge: Optional[RationaleJudge] = None) -> Optional[Dict[str, Any]]:
+    if judge is not None:
+        return judge.score(rationale, ground_truth, input_data)
+    return score_rubric(rationale, ground_truth, input_data)
+
+
+def main():
+    parser = argparse.ArgumentParser(description='저장된 결과의 탐지 근거(rationale) 채점')
+    parser.add_argument('results', help='benchmark_runner.py 결과 JSON')
+    parser.add_argument('--scorer', choices=SCORERS, default='rubric')
+    parser.add_argument('--judge-provider')
+    parser.add_argument('--judge-model')
+    parser.add_argument('--output', help='점수를 채운 결과 JSON 경로 (기본: 입력 파일 덮어쓰지 않고 출력만)')
+    args = parser.parse_args()
+
+    from utils.test_case_manager import TestCaseManager
+    manager = TestCaseManager("data/test_cases", "data/ground_truth", "data/test_files")
+
+    judge = None
+    if args.scorer == 'judge':
+        from config.config_loader import ConfigLoader
+        judge = create_judge({'judge': {'provider': args.judge_provider, 'model': args.judge_model}}, ConfigLoader())
+
+    with open(args.results, 'r', encoding='utf-8') as f:
+        data = json.load(f)
+
+    inputs: Dict[str, Dict[str, str]] = {}
+    by_detector: Dict[str, List[float]] = {}
+    missing = 0
+    for result in data.get('detailed_results', []):
+        if 'error' in result:
+            continue
+        agent_type = result.get('agent_type', '')
+        if agent_type not in inputs:
+            inputs[agent_type] = {case['test_id']: case.get('input_data', '')
+                                  for case in manager.load_test_cases(agent_type)}
+        ground_truth = manager.load_ground_truth(agent_type, result['test_id'])
+        if not ground_truth:
+            continue
+        if not result.get('rationale'):
+            missing += 1
+        score = score_rationale(result.get('rationale'), ground_truth, inputs[agent_type].get(result['test_id'], ''),
+                                judge)
+        result['rationale_score'] = score
+        if score and score.get('score') is not None:
+            by_detector.setdefault(f"{result.get('provider')}/{result.get('model')}", []).append(score['score'])
+
+    print(f"🧾 근거 채점 ({args.scorer}): rationale 없는 결과 {missing}개는 0점 처리")
+    for detector, scores in sorted(by_detector.items()):
+        print(f"  {detector}: 평균 {sum(scores) / len(scores):.3f} (채점 샘플 {len(scores)}개)")
+
+    if args.output:
+        with open(args.output, 'w', encoding='utf-8') as f:
+            json.dump(data, f, indent=2, ensure_ascii=False)
+        print(f"💾 저장: {args.output}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,296 @@
+"""
+결과/보고서/대화 기록 공유 전 가리기 (redaction)
+
+결과 파일과 보고서, 대화 기록(utils/transcripts.py)을 팀 밖에 공유하기 전에 API 키, 로컬 파일 경로, 조직 식별자를
+가린 사본을 만들고, 만든 사본에 알려진 비밀 패턴이 남아 있지 않은지 다시 검사합니다. 원본은 건드리지 않습니다.
+
+가리는 것:
+    API 키          알려진 형식(SECRET_PATTERNS: OpenAI/Anthropic/Google/Hugging Face/AWS/GitHub/Slack 키, Bearer 토큰,
+                    PEM 개인키 블록)과 환경 변수(*_API_KEY, *_TOKEN, *_SECRET, *_PASSWORD)에 든 실제 값
+                    → [REDACTED:<종류>]
+    파일 경로       저장소의 절대 경로 → <repo>, 홈 디렉토리(/home/<이름>, /Users/<이름>, C:\\Users\\<이름>) → <home>
+    조직 식별자     설정의 organizations(회사/팀 이름, 도메인, 계정 ID, 대소문자 무시)와 OpenAI 조직 ID(org-...) → <org>
+
+설정 (config.yaml):
+    benchmark:
+      redaction:
+        organizations: []   # 가릴 조직 식별자
+        paths: true         # 파일 경로 가리기
+        patterns: {}        # 추가 비밀 패턴 (종류: 정규식), 검사에도 사용
+        allow: []           # 패턴에 걸려도 그대로 둘 문자열 (코퍼스 샘플의 가짜 키 등)
+
+명령:
+    share <파일/디렉토리...> --output-dir DIR
+        JSON은 문자열 값마다, 텍스트 보고서(html/md/csv/txt/jsonl)는 본문 전체를 가려 DIR에 같은 이름으로 씀.
+        결과 JSON이 참조하는 대화 기록은 가린 뒤 DIR/transcripts에 다시 저장하고 digest를 바꿔 적음.
+        그 밖의 파일(이미지 등)은 그대로 복사. 끝나면 DIR 전체를 verify와 같이 검사하고 걸리면 종료 코드 1
+    verify <파일/디렉토리...>
+        비밀 패턴과 환경 변수 값, 조직 식별자가 남아 있는지 검사 (대화 기록 .json.gz도 풀어서). 걸린 값은 앞뒤 몇 자만 출력
+
+사용법:
+    python -m utils.redaction share benchmark_results_1730000000.json results/report.html --output-dir shared/
+    python -m utils.redaction verify shared/
+"""
+
+import argparse
+import gzip
+import json
+import os
+import re
+import shutil
+import sys
+from pathlib import Path
+from typing import Any, Dict, Iterable, List, Optional, Tuple
+
+from utils.transcripts import DEFAULT_DIR as TRANSCRIPTS_DIR, TranscriptStore
+
+# 종류 → 정규식 (sk-ssh-ed25519-cert-v01 같은 알고리즘 이름에 걸리지 않도록 키 본문 길이를 요구)
+SECRET_PATTERNS: Dict[str, str] = {
+    'anthropic-key': r'\bsk-ant-[A-Za-z0-9]+-[A-Za-z0-9_-]{32,}',
+    'openai-key': r'\bsk-(?:proj-|svcacct-|admin-)?[A-Za-z0-9_-]{32,}',
+    'google-key': r'\bAIza[0-9A-Za-z_-]{35}',
+    'huggingface-token': r'\bhf_[A-Za-z0-9]{30,}',
+    'aws-access-key': r'\b(?:AKIA|ASIA)[0-9A-Z]{16}\b',
+    'github-token': r'\b(?:gh[pousr]_[A-Za-z0-9]{36,}|github_pat_[A-Za-z0-9_]{40,})',
+    'slack-token': r'\bxox[abprs]-[A-Za-z0-9-]{10,}',
+    'bearer-token': r'\bBearer\s+[A-Za-z0-9._~+/-]{20,}=*',
+    'private-key': r'-----BEGIN (?:[A-Z]+ )?PRIVATE KEY-----[\s\S]*?-----END (?:[A-Z]+ )?PRIVATE KEY-----',
+}
+SECRET_ENV_SUFFIXES = ('_API_KEY', '_TOKEN', '_SECRET', '_PASSWORD')
+# 이보다 짧은 환경 변수 값("not_required", "1" 등)은 가리지 않음
+MIN_SECRET_VALUE_LENGTH = 12
+OPENAI_ORG_PATTERN = r'\borg-[A-Za-z0-9]{20,}'
+HOME_PATTERN = r'(?:/home/|/Users/|[A-Za-z]:\\{1,2}Users\\{1,2})[^/\\\s"\'<>]+'
+# 본문 전체를 가리는 텍스트 형식 (그 밖의 형식은 그대로 복사하고 검사만)
+TEXT_SUFFIXES = ('.json', '.jsonl', '.html', '.htm', '.md', '.csv', '.txt', '.log', '.yaml', '.yml', '.xml')
+SNIPPET_CHARS = 4
+
+
+def redaction_settings(config: Optional[Dict[str, Any]]) -> Dict[str, Any]:
+    config = config or {}
+    return {
+        'organizations': [str(item) for item in config.get('organizations') or [] if str(item).strip()],
+        'paths': bool(config.get('paths', True)),
+        'patterns': dict(config.get('patterns') or {}),
+        'allow': [str(item) for item in config.get('allow') or []]
+    }
+
+
+def secret_values(environ: Optional[Dict[str, str]] = None) -> Dict[str, str]:
+    """환경 변수 이름 → 값 (비밀일 수 있는 이름이고 충분히 긴 값만)"""
+    environ = os.environ if environ is None else environ
+    return {name: value for name, value in environ.items()
+            if name.upper().endswith(SECRET_ENV_SUFFIXES) and len(value.strip()) >= MIN_SECRET_VALUE_LENGTH}
+
+
+class Redactor:
+    """문자열 → 가린 문자열 (규칙 순서: 비밀 값, 비밀 패턴, 조직, 경로)"""
+
+    def __init__(self, settings: Dict[str, Any], secrets: Optional[Dict[str, str]] = None,
+                 repo_root: Optional[str] = None):
+        self.settings = settings
+        self.allow = set(settings['allow'])
+        self.secrets = {name: value for name, value in (secrets if secrets is not None else secret_values()).items()
+                        if value not in self.allow}
+        self.patterns = [(kind, re.compile(pattern)) for kind, pattern in {**SECRET_PATTERNS,
+                                                                            **settings['patterns']}.items()]
+        organizations = sorted(settings['organizations'], key=len, reverse=True)
+        self.organizations = re.compile('|'.join(map(re.escape, organizations)), re.IGNORECASE) \
+            if organizations else None
+        self.openai_org = re.compile(OPENAI_ORG_PATTERN)
+        self.repo_root = str(Path(repo_root or Path.cwd()).resolve())
+        self.home = re.compile(HOME_PATTERN)
+        self.counts: Dict[str, int] = {}
+
+    def _count(self, kind: str, number: int = 1):
+        if number:
+            self.counts[kind] = self.counts.get(kind, 0) + number
+
+    def _sub(self, kind: str, regex, text: str, replacement: Optional[str] = None) -> str:
+        def replace(match):
+            if match.group(0) in self.allow:
+                return match.group(0)
+            self._count(kind)
+            return replacement if replacement is not None else f"[REDACTED:{kind}]"
+        return regex.sub(replace, text)
+
+    def text(self, text: str) -> str:
+        for name, value in self.secrets.items():
+            if value in text:
+                self._count(f"env:{name}", text.count(value))
+                text = text.replace(value, f"[REDACTED:{name}]")
+        for kind, regex in self.patterns:
+            text = self._sub(kind, regex, text)
+        text = self._sub('openai-org', self.openai_org, text, '<org>')
+        if self.organizations:
+            text = self._sub('organization', self.organizations, text, '<org>')
+        if self.settings['paths']:
+            if len(self.repo_root) > 1 and self.repo_root in text:
+                self._count('path', text.count(self.repo_root))
+                text = text.replace(self.repo_root, '<repo>')
+            text = self._sub('path', self.home, text, '<home>')
+        return text
+
+    def value(self, value: Any) -> Any:
+        """JSON 값의 문자열마다 (키는 그대로)"""
+        if isinstance(value, str):
+            return self.text(value)
+        if isinstance(value, list):
+            return [self.value(item) for item in value]
+        if isinstance(value, dict):
+            return {key: self.value(item) for key, item in value.items()}
+        return value
+
+
+def scan_text(text: str, redactor: Redactor) -> List[Tuple[int, str, str]]:
+    """남은 비밀 → [(줄 번호, 종류, 앞뒤만 남긴 값)] (가린 표시 [REDACTED:...]와 허용 문자열은 제외)"""
+    found = []
+
+    def add(start: int, kind: str, value: str):
+        if value in redactor.allow:
+            return
+        masked = value if len(value) <= SNIPPET_CHARS * 2 else \
+            f"{value[:SNIPPET_CHARS]}…{value[-SNIPPET_CHARS:]} ({len(value)}자)"
+        found.append((text.count('\n', 0, start) + 1, kind, masked))
+
+    for name, value in redactor.secrets.items():
+        start = text.find(value)
+        while start >= 0:
+            add(start, f"env:{name}", value)
+            start = text.find(value, start + 1)
+    for kind, regex in redactor.patterns + [('openai-org', redactor.openai_org)]:
+        for match in regex.finditer(text):
+            add(match.start(), kind, match.group(0))
+    if redactor.organizations:
+        for match in redactor.organizations.finditer(text):
+            add(match.start(), 'organization', match.group(0))
+    return found
+
+
+def _files(paths: Iterable[str]) -> List[Path]:
+    files = []
+    for path in map(Path, paths):
+        files.extend(sorted(item for item in path.rglob('*') if item.is_file()) if path.is_dir() else [path])
+    return files
+
+
+def _read_text(path: Path) -> str:
+    data = path.read_bytes()
+    if path.name.endswith('.gz'):
+        data = gzip.decompress(data)
+    return data.decode('utf-8', errors='replace')
+
+
+def verify(paths: Iterable[str], redactor: Redactor) -> List[Dict[str, Any]]:
+    """파일마다 남은 비밀 [{'file', 'line', 'kind', 'value'}]"""
+    findings = []
+    for path in _files(paths):
+        try:
+            text = _read_text(path)
+        except (OSError, EOFError, gzip.BadGzipFile) as e:
+            findings.append({'file': str(path), 'line': 0, 'kind': 'unreadable', 'value': str(e)})
+            continue
+        findings.extend({'file': str(path), 'line': line, 'kind': kind, 'value': value}
+                        for line, kind, value in scan_text(text, redactor))
+    return findings
+
+
+def _share_transcript(digest: str, source: TranscriptStore, target: TranscriptStore, redactor: Redactor) -> str:
+    transcript = redactor.value(source.get(digest))
+    for exchange in transcript.get('exchanges', []):
+        if exchange.get('kind') == 'tools' and exchange.get('tools'):
+            exchange['tools'] = target.put(redactor.value(source.get(exchange['tools'])))
+    return target.put(transcript)
+
+
+def share_file(path: Path, output: Path, redactor: Redactor, source: TranscriptStore,
+               target: TranscriptStore) -> Dict[str, int]:
+    """파일 하나를 가려 output에 씀 → {'transcripts': 옮긴 대화 기록 수, 'missing': 저장소에 없는 기록 수}"""
+    copied = {'transcripts': 0, 'missing': 0}
+    output.parent.mkdir(parents=True, exist_ok=True)
+    if path.suffix.lower() == '.json':
+        data = json.loads(path.read_text(encoding='utf-8'))
+        rows = data.get('detailed_results') if isinstance(data, dict) else None
+        for row in rows if isinstance(rows, list) else []:
+            if isinstance(row, dict) and row.get('transcript'):
+                try:
+                    row['transcript'] = _share_transcript(row['transcript'], source, target, redactor)
+                    copied['transcripts'] += 1
+                except KeyError:
+                    row['transcript'] = None
+                    copied['missing'] += 1
+        output.write_text(json.dumps(redactor.value(data), indent=2, ensure_ascii=False), encoding='utf-8')
+    elif path.suffix.lower() in TEXT_SUFFIXES:
+        output.write_text(redactor.text(path.read_text(encoding='utf-8', errors='replace')), encoding='utf-8')
+    else:
+        shutil.copyfile(path, output)
+    return copied
+
+
+def _redactor() -> Redactor:
+    from config.config_loader import ConfigLoader  # .env를 읽어 환경 변수의 키 값도 가림
+    return Redactor(redaction_settings(ConfigLoader().get_benchmark_config().get('redaction')))
+
+
+def _print_findings(findings: List[Dict[str, Any]]):
+    for finding in findings:
+        print(f"  ❌ {finding['file']}:{finding['line']}: {finding['kind']}: {finding['value']}")
+
+
+def main():
+    parser = argparse.ArgumentParser(description='공유 전 결과/보고서/대화 기록의 API 키, 경로, 조직 식별자 가리기')
+    subparsers = parser.add_subparsers(dest='command', required=True)
+    share = subparsers.add_parser('share', help='가린 사본을 만들고 검사')
+    share.add_argument('paths', nargs='+', help='결과 JSON, 보고서 파일 또는 디렉토리')
+    share.add_argument('--output-dir', required=True, help='가린 사본을 쓸 디렉토리')
+    share.add_argument('--transcripts-dir', default=TRANSCRIPTS_DIR, help=f'대화 기록 저장소 (기본 {TRANSCRIPTS_DIR})')
+    check = subparsers.add_parser('verify', help='남은 비밀 패턴 검사 (걸리면 종료 코드 1)')
+    check.add_argument('paths', nargs='+', help='검사할 파일 또는 디렉토리')
+    args = parser.parse_args()
+
+    redactor = _redactor()
+    if args.command == 'verify':
+        findings = verify(args.paths, redactor)
+        _print_findings(findings)
+        print(f"{'❌' if findings else '✅'} 남은 비밀 {len(findings)}건 ({len(_files(args.paths))}개 파일)")
+        sys.exit(1 if findings else 0)
+
+    output_dir = Path(args.output_dir)
+    if any(Path(path).resolve() == output_dir.resolve() for path in args.paths):
+        parser.error('--output-dir는 입력과 달라야 합니다')
+    source = TranscriptStore(args.transcripts_dir)
+    target = TranscriptStore(str(output_dir / 'transcripts'))
+    totals = {'files': 0, 'transcripts': 0, 'missing': 0, 'skipped': 0}
+    for path in map(Path, args.paths):
+        files = sorted(item for item in path.rglob('*') if item.is_file()) if path.is_dir() else [path]
+        for item in files:
+            if item.name.endswith('.json.gz') and 'objects' in item.parts:
+                # 대화 기록 저장소 객체는 결과 JSON의 참조를 따라 가린 뒤 다시 저장하므로 그대로 복사하지 않음
+                totals['skipped'] += 1
+                continue
+            relative = item.relative_to(path) if path.is_dir() else Path(item.name)
+            base = Path(path.name) if path.is_dir() else Path()
+            try:
+                copied = share_file(item, output_dir / base / relative, redactor, source, target)
+            except (OSError, ValueError) as e:
+                print(f"  ⚠️  {item}: 건너뜀 ({e})")
+                continue
+            totals['files'] += 1
+            totals['transcripts'] += copied['transcripts']
+            totals['missing'] += copied['missing']
+    print(f"🕶️  {totals['files']}개 파일, 대화 기록 {totals['transcripts']}개 → {output_dir}")
+    if totals['skipped']:
+        print(f"  ℹ️  대화 기록 저장소 객체 {totals['skipped']}개는 복사하지 않음 (결과 JSON이 참조하는 기록만 가려서 옮김)")
+    if totals['missing']:
+        print(f"  ⚠️  저장소에 없는 대화 기록 {totals['missing']}개는 참조를 지움")
+    for kind, count in sorted(redactor.counts.items()):
+        print(f"  {kind}: {count}건 가림")
+
+    findings = verify([str(output_dir)], redactor)
+    _print_findings(findings)
+    print(f"{'❌' if findings else '✅'} 검사: 남은 비밀 {len(findings)}건")
+    sys.exit(1 if findings else 0)
+
+
+if __name__ == "__main__":
+    main()
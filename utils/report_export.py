@@ -0,0 +1,533 @@
+"""
+탐지 결과 보고서 내보내기 (SARIF / HTML / CBOM / GitHub 점수 카드)
+
+벤치마크 실행 결과(benchmark_runner.py, detectors/runner.py의 {metadata, detailed_results})에서 탐지기가 보고한
+알고리즘과 구현 약점을 취약점 관리 도구가 읽는 형식으로 내보냅니다. 탐지마다 CWE ID와 OWASP Top 10 분류가 붙습니다.
+
+    알고리즘 탐지   findings[] (없으면 detected_algorithms)의 라벨 → 취약성 범주 (utils/weakness.py의 label_weakness)
+                    → CWE (weakness_cwe: CWE-327, 해시는 CWE-328, grover-margin/weak-parameters는 CWE-326 …)
+    구현 약점       weaknesses[] (utils/misuse.py) → 약점별 CWE (WEAKNESS_CWE: hardcoded-key CWE-321,
+                    time-based-entropy CWE-330, nonce-reuse CWE-323 …)
+    OWASP          모두 A02:2021-Cryptographic Failures
+
+형식:
+    sarif    SARIF 2.1.0. 탐지기마다 run 하나, 범주/약점마다 규칙 하나. 규칙의 properties.tags에
+             external/cwe/cwe-NNN, owasp-a02 (CodeQL 표기)를 넣고 relationships로 CWE 분류 체계(taxonomies)를 가리킴
+    html     탐지기별 표 (샘플, 위치, 알고리즘/약점, 범주, CWE 링크, OWASP). --compliance를 주면 탐지기마다 준수
+             현황 절(utils/compliance.py: 코드베이스 판정, 규칙별 항목, 다가오는 전환 날짜)을 덧붙임.
+             predictions가 있는 결과(라벨별 신뢰도를 기록한 실행)는 탐지기마다 보정 절(ECE/MCE/Brier/평균 정밀도,
+             신뢰도 다이어그램과 임계값별 PR 곡선 SVG, utils/calibration.py)을 덧붙임.
+             --lang(ko-KR/en-US)으로 문구 언어를, config/benchmark.toml의 [report]로 조직 이름/로고/꼬리말을 정함
+             (utils/i18n.py)
+    cbom     CycloneDX 1.6 CBOM. 라벨마다 cryptographic-asset 구성 요소(evidence.occurrences에 샘플 위치)와
+             vulnerabilities[].cwes. 탐지기가 하나인 결과만 (여러 개면 --detector로 선택)
+             detectors/external/cbom.py로 다시 읽을 수 있습니다
+    github   PR 댓글 본문용 마크다운 점수 카드. 탐지기마다 계층 F1/정확도/성공률과 --baseline 실행 대비 변화,
+             클래스(알고리즘 계열)별 탐지율 변화, 새로 실패/성공한 샘플. 변화는 두 실행에 같은 ground truth로 있는
+             샘플끼리만 비교하고, 부트스트랩 95% 신뢰구간이 0을 포함하지 않으면 ✱를 붙임 (utils/significance.py).
+             첫 줄의 <!-- qvbench-scorecard --> 표시로 CI가 이전 댓글을 찾아 고쳐 쓸 수 있음. 오류 행은 0점으로 셈.
+             판단 보류(unsure) 행 수를 따로 세고, 보류가 있으면 상세에 coverage/답한 샘플 F1/AURC (utils/selective.py)
+
+출력 형식은 utils/report_golden.py의 골든 스냅샷(data/report_golden/)으로 고정합니다. 형식을 바꾸면
+python -m utils.report_golden --update로 골든 파일을 갱신하세요.
+
+사용법:
+    python -m utils.report_export results/benchmark_results_20250101.json --output reports/findings.sarif
+    python -m utils.report_export results/benchmark_results_20250101.json --format html --output reports/findings.html
+    python -m utils.report_export results/detector_ast.json --format html --compliance nist --as-of 2031-01-01
+    python -m utils.report_export results/detector_ast.json --format cbom --detector ast --output reports/ast.cbom.json
+    python -m utils.report_export results/pr.json --format github --baseline results/main.json --output comment.md
+"""
+
+import argparse
+import html
+import json
+import sys
+from datetime import date
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from utils.calibration import calibration_summary, pr_svg, reliability, reliability_svg, threshold_sweep
+from utils.compliance import PROFILES, STATUS_APPROVED, assess_result, summarize_standing
+from utils.i18n import BRANDING_PATH, DEFAULT_LANG, LANGS, Branding, Catalog, html_footer, html_header, load_branding
+from utils.misuse import WEAKNESS_CWE, WEAKNESSES, normalize_mode, normalize_weakness
+from utils.run_compare import group_by_detector, pairings, select_detector
+from utils.selective import selective_summary
+from utils.significance import paired_bootstrap_ci
+from utils.taxonomy import AlgorithmTaxonomy
+from utils.weakness import (OWASP_CRYPTO, WEAKNESS_BROKEN, WEAKNESS_HOMEMADE, WEAKNESS_QUANTUM, WEAKNESS_UNKNOWN,
+                            label_weakness, weakness_cwe)
+
+FORMATS = ['sarif', 'html', 'cbom', 'github']
+
+TOOL_NAME = 'qvbench'
+SARIF_SCHEMA = 'https://json.schemastore.org/sarif-2.1.0.json'
+CWE_URL = 'https://cwe.mitre.org/data/definitions/{number}.html'
+
+# 보고서에 쓰는 CWE 이름
+CWE_NAMES = {
+    'CWE-209': 'Generation of Error Message Containing Sensitive Information',
+    'CWE-321': 'Use of Hard-coded Cryptographic Key',
+    'CWE-323': 'Reusing a Nonce, Key Pair in Encryption',
+    'CWE-326': 'Inadequate Encryption Strength',
+    'CWE-327': 'Use of a Broken or Risky Cryptographic Algorithm',
+    'CWE-328': 'Use of Weak Hash',
+    'CWE-329': 'Generation of Predictable IV with CBC Mode',
+    'CWE-330': 'Use of Insufficiently Random Values',
+    'CWE-353': 'Missing Support for Integrity Check',
+    'CWE-760': 'Use of a One-Way Hash with a Predictable Salt',
+    'CWE-1204': 'Generation of Weak Initialization Vector (IV)',
+    'CWE-1240': 'Use of a Cryptographic Primitive with a Risky Implementation',
+}
+
+# 취약성 범주 → SARIF level (구현 약점은 모두 error)
+CATEGORY_LEVEL = {
+    WEAKNESS_BROKEN: 'error',
+    WEAKNESS_HOMEMADE: 'error',
+    WEAKNESS_QUANTUM: 'warning',
+}
+DEFAULT_LEVEL = 'note'
+
+# CycloneDX algorithmProperties.mode 값 (그 밖의 모드는 other)
+CBOM_MODES = {'cbc', 'ecb', 'ccm', 'gcm', 'cfb', 'ofb', 'ctr'}
+
+# GitHub 점수 카드: CI가 이전 댓글을 찾는 표시, 지표 (카탈로그 키 metric.<이름>), 목록 길이
+GITHUB_MARKER = '<!-- qvbench-scorecard -->'
+GITHUB_METRICS = ['hierarchical_f1', 'accuracy', 'success_rate']
+GITHUB_CLASS_ROWS = 15
+GITHUB_SAMPLE_ROWS = 10
+
+
+def cwe_number(cwe: str) -> int:
+    return int(cwe.split('-', 1)[1])
+
+
+def load_results(path: str, include_errors: bool = False) -> List[Dict[str, Any]]:
+    """결과 파일 → 채점된 테스트별 결과 (include_errors가 아니면 오류 항목 제외)"""
+    with open(path, 'r', encoding='utf-8') as f:
+        data = json.load(f)
+    if not isinstance(data, dict) or 'detailed_results' not in data:
+        raise ValueError(f"{path}: detailed_results가 없는 결과 파일입니다")
+    return [result for result in data['detailed_results'] if include_errors or 'error' not in result]
+
+
+def _artifact(result: Dict[str, Any]) -> str:
+    """샘플 경로 (작업 디렉토리 아래면 상대 경로)"""
+    path = Path(result.get('file_path') or f"{result.get('agent_type', '')}/{result.get('test_id', 'unknown')}")
+    try:
+        path = path.resolve().relative_to(Path.cwd()) if path.is_absolute() else path
+    except ValueError:
+        pass
+    return path.as_posix()
+
+
+def report_findings(result: Dict[str, Any], taxonomy: Optional[AlgorithmTaxonomy] = None) -> List[Dict[str, Any]]:
+    """테스트 결과 하나 → 보고할 탐지 목록 (알고리즘과 구현 약점, 각각 CWE/OWASP 포함)"""
+    taxonomy = taxonomy or AlgorithmTaxonomy()
+    artifact = _artifact(result)
+    findings = result.get('findings') or [{'algorithm': label, 'span': None,
+                                           'confidence': result.get('confidence_score', 0.0)}
+                                          for label in result.get('detected_algorithms') or []]
+    reported = []
+    for finding in findings:
+        algorithm = str(finding.get('algorithm', ''))
+        if not algorithm:
+            continue
+        category = label_weakness(algorithm, taxonomy=taxonomy)
+        cwe = weakness_cwe(category, taxonomy.role(taxonomy.resolve(algorithm)))
+        reported.append({
+            'kind': 'algorithm', 'rule': f"crypto/{category}" + (f"/{cwe.lower()}" if cwe else ''),
+            'algorithm': algorithm, 'mode': None, 'category': category,
+            'cwe': [cwe] if cwe else [], 'owasp': OWASP_CRYPTO if category != WEAKNESS_UNKNOWN else None,
+            'artifact': artifact, 'span': finding.get('span'), 'confidence': finding.get('confidence', 0.0),
+        })
+
+    for item in result.get('weaknesses') or []:
+        entry = {'weakness': item} if isinstance(item, str) else item if isinstance(item, dict) else {}
+        names = entry.get('weaknesses') or [entry.get('weakness')]
+        for name in names if isinstance(names, list) else [names]:
+            weakness = normalize_weakness(name)
+            if weakness is None:
+                continue
+            reported.append({
+                'kind': 'weakness', 'rule': f"misuse/{weakness}", 'algorithm': entry.get('algorithm'),
+                'mode': normalize_mode(entry.get('mode')), 'category': weakness,
+                'cwe': [WEAKNESS_CWE[weakness]], 'owasp': OWASP_CRYPTO,
+                'artifact': artifact, 'span': None, 'confidence': result.get('confidence_score', 0.0),
+            })
+    return reported
+
+
+def _message(finding: Dict[str, Any]) -> str:
+    cwe = ', '.join(finding['cwe']) or 'no CWE'
+    if finding['kind'] == 'weakness':
+        subject = ' '.join(part for part in (finding['algorithm'], finding['mode']) if part) or 'cryptographic code'
+        return f"{subject}: {WEAKNESSES[finding['category']]} ({cwe})"
+    return f"{finding['algorithm']}: {finding['category']} ({cwe})"
+
+
+def _rule(finding: Dict[str, Any]) -> Dict[str, Any]:
+    description = WEAKNESSES[finding['category']] if finding['kind'] == 'weakness' else \
+        f"Cryptographic algorithm with weakness category {finding['category']}"
+    tags = ['security', 'cryptography'] + [f"external/cwe/cwe-{cwe_number(cwe)}" for cwe in finding['cwe']]
+    if finding['owasp']:
+        tags.append(f"owasp-{finding['owasp'].split(':')[0].lower()}")
+    return {
+        'id': finding['rule'],
+        'shortDescription': {'text': description},
+        'defaultConfiguration': {'level': 'error' if finding['kind'] == 'weakness' else
+                                 CATEGORY_LEVEL.get(finding['category'], DEFAULT_LEVEL)},
+        'properties': {'tags': tags, 'cwe': finding['cwe'], 'owasp': finding['owasp']},
+        'relationships': [{'target': {'id': str(cwe_number(cwe)), 'toolComponent': {'name': 'CWE'}},
+                           'kinds': ['superset']} for cwe in finding['cwe']],
+    }
+
+
+def render_sarif(groups: Dict[str, List[Dict[str, Any]]]) -> Dict[str, Any]:
+    runs = []
+    for detector, results in groups.items():
+        findings = [finding for result in results for finding in report_findings(result)]
+        rules: Dict[str, Dict[str, Any]] = {}
+        for finding in findings:
+            rules.setdefault(finding['rule'], _rule(finding))
+        cwes = sorted({cwe for finding in findings for cwe in finding['cwe']}, key=cwe_number)
+
+        sarif_results = []
+        for finding in findings:
+            location: Dict[str, Any] = {'artifactLocation': {'uri': finding['artifact']}}
+            if finding['span']:
+                location['region'] = {'startLine': finding['span'][0], 'endLine': finding['span'][-1]}
+            sarif_results.append({
+                'ruleId': finding['rule'],
+                'level': rules[finding['rule']]['defaultConfiguration']['level'],
+                'message': {'text': _message(finding)},
+                'locations': [{'physicalLocation': location}],
+                'properties': {'algorithm': finding['algorithm'], 'confidence': finding['confidence'],
+                               'cwe': finding['cwe'], 'owasp': finding['owasp']},
+            })
+
+        runs.append({
+            'tool': {'driver': {'name': TOOL_NAME, 'informationUri': 'https://cwe.mitre.org/',
+                                'properties': {'detector': detector}, 'rules': list(rules.values())}},
+            'taxonomies': [{'name': 'CWE', 'organization': 'MITRE',
+                            'taxa': [{'id': str(cwe_number(cwe)), 'name': CWE_NAMES.get(cwe, cwe)}
+                                     for cwe in cwes]}],
+            'results': sarif_results,
+        })
+    return {'$schema': SARIF_SCHEMA, 'version': '2.1.0', 'runs': runs}
+
+
+def _compliance_html(results: List[Dict[str, Any]], profile: str, as_of: date, catalog: Catalog) -> List[str]:
+    """준수 현황 절 (탐지기 하나분)"""
+    summary = summarize_standing([assess_result(result, profile, as_of) for result in results])
+    counts = summary['by_standing']
+    title = PROFILES[profile]['title']
+    parts = [f"<h3>{html.escape(catalog.text('compliance.title', profile=title, as_of=as_of.isoformat()))}</h3>",
+             '<p>' + catalog.text('compliance.standing', standing=summary['standing'],
+                                  non_compliant=counts['non-compliant'], transition=counts['transition'],
+                                  compliant=counts['compliant']) + '</p>']
+    rules = [(key, rule) for key, rule in summary['by_rule'].items() if rule['status'] != STATUS_APPROVED]
+    if rules:
+        columns = ('status', 'subject', 'source', 'note', 'samples')
+        header = [catalog.text(f"compliance.col.{column}") for column in columns]
+        parts += ['<table>', '<tr>' + ''.join(f"<th>{html.escape(cell)}</th>" for cell in header) + '</tr>']
+        for key, rule in rules:
+            note = catalog.text(f"compliance.{key}", rule['note'] or '-', profile=title)
+            cells = [rule['status'], ', '.join(rule['subjects']), rule['source'] or '-', note,
+                     str(len(rule['samples']))]
+            parts.append('<tr>' + ''.join(f"<td>{html.escape(cell)}</td>" for cell in cells) + '</tr>')
+        parts.append('</table>')
+    if summary['upcoming']:
+        parts.append('<ul>' + ''.join(
+            '<li>' + catalog.text('compliance.upcoming', date=transition['from'], status=transition['status'],
+                                  subjects=html.escape(', '.join(transition['subjects']))) + '</li>'
+            for transition in summary['upcoming']) + '</ul>')
+    return parts
+
+
+def _calibration_html(results: List[Dict[str, Any]], catalog: Catalog) -> List[str]:
+    """보정 절 (탐지기 하나분, predictions가 없으면 빈 목록)"""
+    summary = calibration_summary(results)
+    if not summary:
+        return []
+    best = summary['best_threshold']
+    text = catalog.text('calibration.summary', predictions=summary['predictions'], ece=f"{summary['ece']:.3f}",
+                        mce=f"{summary['mce']:.3f}", brier=f"{summary['brier']:.3f}",
+                        ap=f"{summary['average_precision']:.3f}", threshold=f"{best['threshold']:.2f}",
+                        precision=f"{best['precision']:.3f}", recall=f"{best['recall']:.3f}", f1=f"{best['f1']:.3f}")
+    charts = [reliability_svg(reliability(results), html.escape(catalog.text('calibration.reliability')),
+                              html.escape(catalog.text('calibration.axis.confidence')),
+                              html.escape(catalog.text('calibration.axis.accuracy'))),
+              pr_svg(threshold_sweep(results), html.escape(catalog.text('calibration.pr')))]
+    return [f"<h3>{html.escape(catalog.text('calibration.title'))}</h3>", f"<p>{html.escape(text)}</p>",
+            '<p>' + ' '.join(charts) + '</p>']
+
+
+def render_html(groups: Dict[str, List[Dict[str, Any]]], compliance: Optional[str] = None,
+                as_of: Optional[date] = None, lang: str = DEFAULT_LANG, branding: Optional[Branding] = None) -> str:
+    catalog = Catalog(lang)
+    header = [catalog.text(f"report.col.{column}") for column in ('sample', 'location', 'subject', 'category')]
+    header += ['CWE', 'OWASP', catalog.text('report.col.confidence')]
+    parts = html_header(catalog.text('report.title'), '🔐',
+                        '<style>table{border-collapse:collapse}th,td{border:1px solid #ccc;padding:4px 8px}'
+                        '</style></head>', catalog, branding)
+    for detector, results in groups.items():
+        findings = [finding for result in results for finding in report_findings(result)]
+        summary = catalog.text('report.summary', samples=len(results), findings=len(findings))
+        parts += [f"<h2>{html.escape(detector)}</h2>", f"<p>{html.escape(summary)}</p>",
+                  '<table>', '<tr>' + ''.join(f"<th>{html.escape(cell)}</th>" for cell in header) + '</tr>']
+        for finding in findings:
+            span = finding['span']
+            subject = finding['algorithm'] or '-'
+            if finding['kind'] == 'weakness':
+                subject = ' '.join(part for part in (finding['algorithm'], finding['mode']) if part) or '-'
+                subject = f"{subject} — {finding['category']}"
+            links = ', '.join(f"<a href=\"{CWE_URL.format(number=cwe_number(cwe))}\" "
+                              f"title=\"{html.escape(CWE_NAMES.get(cwe, cwe))}\">{cwe}</a>" for cwe in finding['cwe'])
+            cells = [html.escape(finding['artifact']),
+                     html.escape(f"{span[0]}-{span[-1]}" if span else '-'),
+                     html.escape(subject), html.escape(finding['category']), links or '-',
+                     html.escape(finding['owasp'] or '-'), f"{float(finding['confidence'] or 0.0):.2f}"]
+            parts.append('<tr>' + ''.join(f"<td>{cell}</td>" for cell in cells) + '</tr>')
+        parts.append('</table>')
+        parts += _calibration_html(results, catalog)
+        if compliance:
+            parts += _compliance_html(results, compliance, as_of or date.today(), catalog)
+    parts += html_footer(catalog, branding)
+    return '\n'.join(parts)
+
+
+def render_cbom(detector: str, results: List[Dict[str, Any]]) -> Dict[str, Any]:
+    components: Dict[str, Dict[str, Any]] = {}
+    vulnerabilities: Dict[str, Dict[str, Any]] = {}
+    for result in results:
+        for finding in report_findings(result):
+            # 알고리즘 없이 보고된 약점은 구성 요소 없이 취약점 항목만 남김
+            reference = f"crypto/algorithm/{finding['algorithm']}" if finding['algorithm'] else None
+            if reference:
+                component = components.setdefault(reference, {
+                    'type': 'cryptographic-asset', 'bom-ref': reference, 'name': finding['algorithm'],
+                    'cryptoProperties': {'assetType': 'algorithm', 'algorithmProperties': {}},
+                    'evidence': {'occurrences': [], 'identity': {'confidence': 0.0}},
+                    'properties': [],
+                })
+                occurrence = {'location': finding['artifact']}
+                if finding['span']:
+                    occurrence['line'] = finding['span'][0]
+                if occurrence not in component['evidence']['occurrences']:
+                    component['evidence']['occurrences'].append(occurrence)
+                identity = component['evidence']['identity']
+                identity['confidence'] = max(identity['confidence'], float(finding['confidence'] or 0.0))
+                if finding['mode']:
+                    mode = finding['mode'].lower()
+                    component['cryptoProperties']['algorithmProperties']['mode'] = \
+                        mode if mode in CBOM_MODES else 'other'
+                property_name = 'qvbench:weakness' if finding['kind'] == 'algorithm' else 'qvbench:misuse'
+                property_entry = {'name': property_name, 'value': finding['category']}
+                if property_entry not in component['properties']:
+                    component['properties'].append(property_entry)
+
+            for cwe in finding['cwe']:
+                vulnerability = vulnerabilities.setdefault(finding['rule'], {
+                    'bom-ref': f"vulnerability/{finding['rule']}",
+                    'id': cwe,
+                    'source': {'name': 'CWE', 'url': CWE_URL.format(number=cwe_number(cwe))},
+                    'cwes': [cwe_number(cwe)],
+                    'description': CWE_NAMES.get(cwe, cwe),
+                    'detail': _message(finding),
+                    'properties': [{'name': 'owasp', 'value': finding['owasp']}] if finding['owasp'] else [],
+                    'affects': [],
+                })
+                if reference and {'ref': reference} not in vulnerability['affects']:
+                    vulnerability['affects'].append({'ref': reference})
+
+    return {
+        'bomFormat': 'CycloneDX',
+        'specVersion': '1.6',
+        'version': 1,
+        'metadata': {'tools': {'components': [{'type': 'application', 'name': TOOL_NAME}]},
+                     'properties': [{'name': 'qvbench:detector', 'value': detector}]},
+        'components': list(components.values()),
+        'vulnerabilities': list(vulnerabilities.values()),
+    }
+
+
+def _metric(result: Dict[str, Any], name: str) -> float:
+    """점수 카드 지표 하나 (오류 행과 채점하지 못한 행은 0)"""
+    if 'error' in result:
+        return 0.0
+    if name == 'hierarchical_f1':
+        return float((result.get('hierarchical_scores') or {}).get('f1') or 0.0)
+    if name == 'accuracy':
+        return float(result.get('accuracy_score') or 0.0)
+    return 1.0 if result.get('success') else 0.0
+
+
+def _class_rates(results: List[Dict[str, Any]]) -> Dict[str, Dict[str, int]]:
+    """클래스(정답 라벨의 알고리즘 계열) → {'labels', 'detected'} (계열까지 맞힌 예측만 탐지로 셈)"""
+    classes: Dict[str, Dict[str, int]] = {}
+    for result in results:
+        for match in (result.get('hierarchical_scores') or {}).get('matches', []):
+            parts = match['expected'].split('/')
+            stats = classes.setdefault(parts[1] if len(parts) > 1 else parts[0], {'labels': 0, 'detected': 0})
+            stats['labels'] += 1
+            stats['detected'] += bool(match.get('predicted')) and match['predicted'].split('/')[:2] == parts[:2]
+    return classes
+
+
+def _delta(value: float, significant: bool = False) -> str:
+    if abs(value) < 0.0005:
+        return '±0'
+    return f"{value:+.3f} {'▲' if value > 0 else '▼'}{' ✱' if significant else ''}"
+
+
+def _sample_list(catalog: Catalog, key: str, samples: List[str]) -> List[str]:
+    if not samples:
+        return []
+    shown = ', '.join(f"`{sample}`" for sample in samples[:GITHUB_SAMPLE_ROWS])
+    more = catalog.text('github.more', count=len(samples) - GITHUB_SAMPLE_ROWS) \
+        if len(samples) > GITHUB_SAMPLE_ROWS else ''
+    return [f"- {catalog.text(key, count=len(samples))}: {shown}{more}"]
+
+
+def render_github(groups: Dict[str, List[Dict[str, Any]]],
+                  baseline_groups: Optional[Dict[str, List[Dict[str, Any]]]] = None, current_name: str = 'results',
+                  baseline_name: str = 'baseline', lang: str = DEFAULT_LANG) -> str:
+    """PR 댓글용 마크다운 점수 카드 (기준 실행이 없으면 점수만)"""
+    catalog = Catalog(lang)
+    metrics = [catalog.text(f"metric.{name}") for name in GITHUB_METRICS]
+    lines = [GITHUB_MARKER, f"### 🧪 {catalog.text('github.title')}", '']
+    if baseline_groups is None:
+        lines.append(catalog.text('github.no_baseline', current=current_name))
+        pairs = [(None, name) for name in groups]
+    else:
+        lines.append(catalog.text('github.versus', baseline=baseline_name, current=current_name))
+        pairs = pairings(baseline_groups, groups)
+    header = [catalog.text('github.col.detector'), catalog.text('github.col.samples')] + metrics + \
+        [catalog.text('github.col.errors'), catalog.text('github.col.abstained')]
+    lines += ['', '| ' + ' | '.join(header) + ' |', '|---|' + '---:|' * (len(header) - 1)]
+
+    details = []
+    for old_name, new_name in pairs:
+        current = {(row.get('agent_type'), row.get('test_id')): row for row in groups[new_name]}
+        baseline = {(row.get('agent_type'), row.get('test_id')): row for row in baseline_groups[old_name]} \
+            if old_name else {}
+        # 라벨이 바뀐 샘플은 모델 변화와 섞이므로 비교에서 뺌 (utils/run_compare.py와 같은 기준)
+        paired = sorted(key for key in set(current) & set(baseline)
+                        if not (current[key].get('ground_truth_hash') and baseline[key].get('ground_truth_hash')
+                                and current[key]['ground_truth_hash'] != baseline[key]['ground_truth_hash']))
+        title = new_name if old_name in (None, new_name) else f"{old_name} → {new_name}"
+        cells = [f"`{title}`", str(len(current))]
+        for name in GITHUB_METRICS:
+            value = sum(_metric(row, name) for row in current.values()) / len(current) if current else 0.0
+            if paired:
+                interval = paired_bootstrap_ci([_metric(baseline[key], name) for key in paired],
+                                               [_metric(current[key], name) for key in paired])
+                cells.append(f"{value:.3f} ({_delta(interval['mean_diff'], interval['significant'])})")
+            else:
+                cells.append(f"{value:.3f}")
+        cells.append(str(sum(1 for row in current.values() if 'error' in row)))
+        selective = selective_summary(list(current.values()))
+        cells.append(str(selective['abstained']))
+        lines.append('| ' + ' | '.join(cells) + ' |')
+
+        body = []
+        if old_name:
+            body.append(catalog.text('github.paired', paired=len(paired), only_old=len(set(baseline) - set(current)),
+                                     only_new=len(set(current) - set(baseline))))
+            flips = [(f"{key[0]}/{key[1]}", _metric(current[key], 'success_rate') - _metric(baseline[key],
+                                                                                              'success_rate'))
+                     for key in paired]
+            body += _sample_list(catalog, 'github.newly_failed', [sample for sample, flip in flips if flip < 0])
+            body += _sample_list(catalog, 'github.fixed', [sample for sample, flip in flips if flip > 0])
+        if selective['abstained']:
+            body += [''] if body else []
+            f1, area = selective['selective_f1'], selective['aurc']
+            body.append(catalog.text('github.selective', coverage=f"{selective['coverage']:.3f}",
+                                     f1='-' if f1 is None else f"{f1:.3f}", aurc='-' if area is None else f"{area:.3f}"))
+        now = _class_rates([current[key] for key in paired] if old_name else list(current.values()))
+        before = _class_rates([baseline[key] for key in paired]) if old_name else {}
+        rows = []
+        for name, stats in now.items():
+            rate = stats['detected'] / stats['labels']
+            old = before.get(name)
+            change = rate - old['detected'] / old['labels'] if old else None
+            rows.append((change if change is not None else 0.0, rate, name, stats, change))
+        changed = [row for row in rows if row[4] is None or abs(row[4]) >= 0.0005] if old_name else rows
+        changed.sort(key=lambda row: (row[0], row[1], row[2]))
+        if changed:
+            body += [''] if body else []
+            body += [f"| {catalog.text('github.col.class')} | {catalog.text('github.col.labels')} | "
+                         f"{catalog.text('github.col.detection')} |", '|---|---:|---:|']
+            for change, rate, name, stats, raw in changed[:GITHUB_CLASS_ROWS]:
+                suffix = f" ({_delta(raw)})" if raw is not None and old_name else ''
+                body.append(f"| {name} | {stats['labels']} | {stats['detected']}/{stats['labels']} "
+                            f"= {rate:.1%}{suffix} |")
+        hidden = len(rows) - min(len(changed), GITHUB_CLASS_ROWS)
+        if hidden:
+            body += ['', catalog.text('github.hidden_classes', count=hidden)]
+        if body:
+            summary = catalog.text('github.details', detector=title)
+            details += ['', f"<details><summary>{html.escape(summary)}</summary>", ''] + body + ['', '</details>']
+
+    lines += details
+    if baseline_groups is not None:
+        lines += ['', f"<sub>{catalog.text('github.legend')}</sub>"]
+    return '\n'.join(lines) + '\n'
+
+
+def main():
+    parser = argparse.ArgumentParser(description='탐지 결과를 SARIF/HTML/CBOM 보고서로 내보내기 (CWE/OWASP 포함)')
+    parser.add_argument('results', help='결과 파일 (benchmark_runner / detectors.runner JSON)')
+    parser.add_argument('--format', choices=FORMATS, default='sarif')
+    parser.add_argument('--detector', help='내보낼 탐지기 (부분 문자열, 기본: 전부; cbom은 하나만)')
+    parser.add_argument('--output', help='저장 경로 (기본: 표준 출력)')
+    parser.add_argument('--compliance', choices=sorted(PROFILES), help='html에 준수 현황 절 추가 (준수 프로필)')
+    parser.add_argument('--as-of', type=date.fromisoformat, default=date.today(), help='준수 판정 기준 날짜 (YYYY-MM-DD)')
+    parser.add_argument('--lang', choices=LANGS, help='html 문구 언어 (기본: [report] lang, 없으면 ko-KR)')
+    parser.add_argument('--baseline', help='github 점수 카드의 기준 실행 결과 파일 (생략하면 점수만)')
+    parser.add_argument('--branding', default=BRANDING_PATH, help=f'html 브랜딩 [report] 설정 TOML (기본: {BRANDING_PATH})')
+    args = parser.parse_args()
+
+    try:
+        branding = load_branding(args.branding)
+        groups = group_by_detector(load_results(args.results, include_errors=args.format == 'github'))
+        baseline_groups = group_by_detector(load_results(args.baseline, include_errors=True)) \
+            if args.format == 'github' and args.baseline else None
+        if args.detector:
+            name = select_detector(groups, args.detector)
+            groups = {name: groups[name]}
+            if baseline_groups:
+                name = select_detector(baseline_groups, args.detector)
+                baseline_groups = {name: baseline_groups[name]}
+        if args.format == 'cbom' and len(groups) != 1:
+            raise ValueError(f"cbom은 탐지기 하나만 내보냅니다 (--detector로 선택: {sorted(groups)})")
+    except ValueError as e:
+        print(f"❌ {e}")
+        sys.exit(2)
+
+    if args.format == 'sarif':
+        text = json.dumps(render_sarif(groups), indent=2, ensure_ascii=False) + '\n'
+    elif args.format == 'html':
+        text = render_html(groups, args.compliance, args.as_of, args.lang or branding.lang, branding)
+    elif args.format == 'github':
+        try:
+            text = render_github(groups, baseline_groups, Path(args.results).name,
+                                 Path(args.baseline).name if args.baseline else 'baseline', args.lang or branding.lang)
+        except ValueError as e:
+            print(f"❌ {e}")
+            sys.exit(2)
+    else:
+        detector, results = next(iter(groups.items()))
+        text = json.dumps(render_cbom(detector, results), indent=2, ensure_ascii=False) + '\n'
+
+    if args.output:
+        Path(args.output).parent.mkdir(parents=True, exist_ok=True)
+        Path(args.output).write_text(text, encoding='utf-8')
+        print(f"💾 {args.format} 보고서 저장: {args.output}")
+    else:
+        print(text, end='')
+
+
+if __name__ == "__main__":
+    main()
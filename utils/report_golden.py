@@ -0,0 +1,212 @@
+"""
+보고서 렌더러 골든 스냅샷 (SARIF / HTML / CBOM / GitHub 점수 카드)
+
+utils/report_export.py의 출력 형식이 바뀌면 취약점 관리 도구 쪽 파서가 깨질 수 있으므로, 작은 고정 결과 집합
+(fixture_results)으로 세 형식을 렌더링해 data/report_golden/의 골든 파일과 바이트 단위로 비교합니다. 형식을
+의도적으로 바꿨다면 --update로 골든 파일을 다시 쓰고, 그 차이를 리뷰에서 확인합니다.
+
+고정 결과 집합 (detectors/runner.py 결과 행과 같은 모양, 경로는 상대 경로라 작업 디렉토리와 무관):
+    ollama/llama3     findings (줄 범위 있음/없음), 양자 취약 공개키·해시·Grover 라벨과 CWE가 없는 라벨(PQC, 모르는 자체 구현), 알고리즘/모드가 붙은
+                      약점과 문자열만 있는 약점, 정규화되지 않는 약점 표현
+    detector/ast      findings 없이 detected_algorithms만 있는 행, 약점 없는 행
+    오류 행           보고서에서 빠지는지 확인
+    GitHub 점수 카드는 채점 필드가 있는 별도 고정 집합(github_fixture: 기준/이번 실행, 라벨이 바뀐 샘플, 한쪽에만 있는
+    샘플, 오류 행)으로 렌더링합니다.
+
+골든 파일:
+    fixture_results.json        고정 결과 집합 (다른 포크에서 렌더러를 바꿨을 때 같은 입력으로 재현)
+    report.sarif / report.html  두 탐지기 전부
+    report.nist.html            HTML + nist 준수 현황 절 (기준 날짜 COMPLIANCE_AS_OF 고정)
+    report.en-US.html           en-US 카탈로그 + 준수 현황 절 + 고정 브랜딩 (FIXTURE_BRANDING)
+    <탐지기>.cbom.json           탐지기마다 하나
+    report.github.md            기준 실행 대비 점수 카드 (ko-KR)
+    report.github.en-US.md      기준 실행 없는 점수 카드 (en-US)
+
+사용법:
+    python -m utils.report_golden              # 골든 파일과 비교 (다르면 종료 코드 1)
+    python -m utils.report_golden --update     # 보고서 형식을 의도적으로 바꾼 뒤 골든 파일 갱신
+    python -m utils.report_export data/report_golden/fixture_results.json --format html
+"""
+
+import argparse
+import json
+import re
+import sys
+from datetime import date
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+from utils.i18n import Branding
+from utils.report_export import render_cbom, render_github, render_html, render_sarif
+from utils.run_compare import group_by_detector
+
+GOLDEN_DIR = Path(__file__).parent.parent / "data" / "report_golden"
+FIXTURE_NAME = 'fixture_results.json'
+COMPLIANCE_AS_OF = date(2026, 1, 1)
+FIXTURE_BRANDING = {'organization': {'ko-KR': '보안팀', 'en-US': 'Security <Team>'}, 'logo': 'assets/logo.png',
+                    'footer': 'Internal use only'}
+
+
+def fixture_results() -> List[Dict[str, Any]]:
+    """골든 스냅샷용 고정 결과 행 (오류 행 포함)"""
+    base = {'agent_type': 'source_code', 'configuration': 'default'}
+    return [
+        dict(base, provider='ollama', model='llama3', test_id='payment_gateway',
+             file_path='data/test_files/source_code/payment_gateway.rs', confidence_score=0.9,
+             findings=[
+                 {'algorithm': 'RSA-2048', 'span': [12, 48], 'confidence': 0.95},
+                 {'algorithm': 'MD5', 'span': [60, 60], 'confidence': 0.8},
+                 {'algorithm': 'AES-256', 'span': None, 'confidence': 0.7},
+             ],
+             weaknesses=[
+                 {'algorithm': 'AES-256', 'mode': 'CBC', 'weaknesses': ['predictable IV', 'missing MAC']},
+                 'hardcoded key',
+                 'something unusual',
+             ],
+             hierarchical_scores={'f1': 0.8, 'matches': [{'expected': 'shor_vulnerable/RSA/RSA-2048'},
+                                                         {'expected': 'grover_vulnerable/AES/AES-256'}]},
+             predictions=[
+                 {'label': 'RSA-2048', 'confidence': 0.95, 'credit': 1.0, 'matches': [1.0, 0.0]},
+                 {'label': 'MD5', 'confidence': 0.8, 'credit': 0.0, 'matches': [0.0, 0.0]},
+                 {'label': 'AES-256', 'confidence': 0.7, 'credit': 1.0, 'matches': [0.0, 1.0]},
+             ]),
+        dict(base, provider='ollama', model='llama3', test_id='telemetry_<xor>',
+             file_path='data/test_files/source_code/telemetry_<xor>.rs', confidence_score=0.4,
+             findings=[
+                 {'algorithm': 'ML-KEM-768', 'span': [5, 30], 'confidence': 0.6},
+                 {'algorithm': 'Custom-XOR', 'span': [31, 44], 'confidence': 0.3},
+             ],
+             weaknesses=[{'algorithm': 'Custom-XOR', 'mode': 'stream xor', 'weakness': 'nonce reuse'}],
+             hierarchical_scores={'f1': 0.667, 'matches': [{'expected': 'homemade/Custom-XOR'}]},
+             predictions=[
+                 {'label': 'ML-KEM-768', 'confidence': 0.6, 'credit': 0.0, 'matches': [0.0]},
+                 {'label': 'Custom-XOR', 'confidence': 0.3, 'credit': 1.0, 'matches': [1.0]},
+             ]),
+        dict(base, provider='detector', model='ast', test_id='payment_gateway',
+             file_path='data/test_files/source_code/payment_gateway.rs', confidence_score=0.5,
+             detected_algorithms=['RSA', 'SHA-1']),
+        dict(base, provider='detector', model='ast', test_id='clean_service',
+             file_path='data/test_files/source_code/clean_service.rs', confidence_score=0.0,
+             detected_algorithms=[]),
+        dict(base, provider='detector', model='ast', test_id='broken_sample', error='timeout'),
+    ]
+
+
+def _scored(test_id: str, f1: float, matches: List[Tuple[str, Optional[str]]], gt: str = 'gt1',
+            model: str = 'llama3') -> Dict[str, Any]:
+    """점수 카드용 채점된 행 (matches: [(정답 노드, 예측 노드)])"""
+    return {'agent_type': 'source_code', 'provider': 'ollama', 'model': model, 'configuration': 'default',
+            'test_id': test_id, 'success': f1 >= 0.6, 'accuracy_score': f1, 'ground_truth_hash': gt,
+            'hierarchical_scores': {'f1': f1, 'matches': [{'expected': expected, 'predicted': predicted}
+                                                          for expected, predicted in matches]}}
+
+
+def github_fixture() -> Tuple[List[Dict[str, Any]], List[Dict[str, Any]]]:
+    """점수 카드 골든 스냅샷용 (기준 실행, 이번 실행)"""
+    rsa, aes, chacha = 'shor_vulnerable/RSA/RSA-2048', 'grover_vulnerable/AES/AES-256', 'grover_vulnerable/ChaCha20'
+    baseline = [
+        _scored('payment_gateway', 1.0, [(rsa, rsa), (aes, aes)]),
+        _scored('stream_vault', 0.5, [(chacha, chacha), (aes, None)]),
+        _scored('legacy_tls', 0.0, [(rsa, None)]),
+        _scored('relabeled_sample', 1.0, [(aes, aes)], gt='gt-old'),
+        _scored('dropped_sample', 1.0, [(rsa, rsa)]),
+    ]
+    current = [
+        _scored('payment_gateway', 1.0, [(rsa, rsa), (aes, aes)]),
+        dict(_scored('stream_vault', 0.0, [(chacha, None), (aes, None)]), abstained=True),
+        _scored('legacy_tls', 1.0, [(rsa, rsa)]),
+        _scored('relabeled_sample', 0.0, [(aes, None)], gt='gt-new'),
+        dict(_scored('new_sample', 0.0, []), error='timeout'),
+    ]
+    return baseline, current
+
+
+def fixture_document() -> Dict[str, Any]:
+    """fixture_results를 결과 파일 형식으로 ({metadata, detailed_results})"""
+    return {'metadata': {'fixture': 'utils.report_golden'}, 'detailed_results': fixture_results()}
+
+
+def _slug(detector: str) -> str:
+    return re.sub(r'[^A-Za-z0-9]+', '_', detector).strip('_').lower()
+
+
+def render_snapshots() -> Dict[str, str]:
+    """골든 파일 이름 → 내용 (고정 결과 집합을 세 형식으로 렌더링)"""
+    results = [result for result in fixture_results() if 'error' not in result]
+    groups = group_by_detector(results)
+    snapshots = {
+        FIXTURE_NAME: json.dumps(fixture_document(), indent=2, ensure_ascii=False) + '\n',
+        'report.sarif': json.dumps(render_sarif(groups), indent=2, ensure_ascii=False) + '\n',
+        'report.html': render_html(groups),
+        'report.nist.html': render_html(groups, 'nist', COMPLIANCE_AS_OF),
+        'report.en-US.html': render_html(groups, 'nist', COMPLIANCE_AS_OF, 'en-US', Branding(FIXTURE_BRANDING)),
+    }
+    for detector, rows in groups.items():
+        snapshots[f"{_slug(detector)}.cbom.json"] = \
+            json.dumps(render_cbom(detector, rows), indent=2, ensure_ascii=False) + '\n'
+    baseline, current = github_fixture()
+    snapshots['report.github.md'] = render_github(group_by_detector(current), group_by_detector(baseline),
+                                                  'pr.json', 'main.json')
+    snapshots['report.github.en-US.md'] = render_github(group_by_detector(current), None, 'pr.json', lang='en-US')
+    return snapshots
+
+
+def _first_difference(golden: str, current: str) -> str:
+    for number, (old, new) in enumerate(zip(golden.splitlines(), current.splitlines()), start=1):
+        if old != new:
+            return f"{number}번째 줄: {old.strip()[:60]!r} → {new.strip()[:60]!r}"
+    return f"줄 수 {len(golden.splitlines())} → {len(current.splitlines())}"
+
+
+def check_golden(update: bool = False, golden_dir: Path = GOLDEN_DIR) -> int:
+    """렌더링 결과를 골든 파일과 비교 (update면 다시 씀). 불일치 수 반환"""
+    golden_dir = Path(golden_dir)
+    snapshots = render_snapshots()
+    if render_snapshots() != snapshots:
+        print("❌ 같은 입력으로 두 번 렌더링한 출력이 다름 (순서/시각 의존)")
+        return 1
+
+    failures = 0
+    for name, text in snapshots.items():
+        path = golden_dir / name
+        if update:
+            path.parent.mkdir(parents=True, exist_ok=True)
+            path.write_text(text, encoding='utf-8')
+            print(f"💾 {name}: {path}")
+            continue
+        if not path.exists():
+            print(f"❌ {name}: 골든 파일 없음 (--update로 생성)")
+            failures += 1
+            continue
+        golden = path.read_text(encoding='utf-8')
+        if golden == text:
+            print(f"✅ {name}: 일치")
+            continue
+        failures += 1
+        print(f"❌ {name}: {_first_difference(golden, text)} (의도한 형식 변경이면 --update로 골든 파일 갱신)")
+
+    stale = sorted(path.name for path in golden_dir.glob('*') if path.is_file() and path.name not in snapshots) \
+        if golden_dir.is_dir() else []
+    for name in stale:
+        print(f"⚠️  {name}: 지금 렌더링하지 않는 골든 파일 (탐지기 이름이 바뀌었으면 삭제)")
+    return failures
+
+
+def main():
+    parser = argparse.ArgumentParser(description='보고서 렌더러(SARIF/HTML/CBOM/GitHub) 골든 스냅샷 확인')
+    parser.add_argument('--update', action='store_true', help='현재 출력으로 골든 파일 갱신')
+    parser.add_argument('--golden-dir', default=str(GOLDEN_DIR), help='골든 파일 디렉토리')
+    parser.add_argument('--fixture-output', help='고정 결과 집합만 이 경로에 저장하고 종료')
+    args = parser.parse_args()
+
+    if args.fixture_output:
+        path = Path(args.fixture_output)
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(json.dumps(fixture_document(), indent=2, ensure_ascii=False) + '\n', encoding='utf-8')
+        print(f"💾 고정 결과 집합 저장: {path}")
+        return
+    sys.exit(1 if check_golden(args.update, Path(args.golden_dir)) else 0)
+
+
+if __name__ == "__main__":
+    main()
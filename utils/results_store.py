@@ -0,0 +1,336 @@
+"""
+벤치마크 결과 SQLite 저장소
+
+여러 실행(run)의 테스트별 결과를 하나의 SQLite 데이터베이스에 누적하여
+모델/코퍼스 버전/날짜에 걸친 비교와 추세 분석을 가능하게 합니다.
+
+코퍼스 버전:
+    각 테스트 결과에는 채점 당시 ground truth의 해시(`ground_truth_hash`)가 기록되고,
+    실행의 코퍼스 버전은 (agent_type, test_id, ground_truth_hash) 집합의 해시입니다.
+    샘플이 추가/삭제되거나 라벨이 수정되면 코퍼스 버전이 바뀝니다.
+
+교집합 재계산 (intersection recompute):
+    코퍼스 버전이 다른 실행끼리 지표를 그대로 비교하면 모델 변화와 코퍼스 변화가 섞입니다.
+    비교 대상 실행 모두에 동일한 ground truth로 존재하는 테스트만 남겨 지표를 다시 계산하여
+    코퍼스 변경의 영향을 제거합니다.
+
+지원 입력 형식:
+    - benchmark_runner.py 결과 ({summary, detailed_results, metadata})
+    - benchmark_rag_effect.py 결과 ({benchmark_info, results})
+"""
+
+import hashlib
+import json
+import re
+import sqlite3
+import time
+from datetime import datetime
+from pathlib import Path
+from typing import Callable, Dict, Any, List, Optional, Tuple
+
+from utils.composite import track_scores
+
+RESULTS_DB_PATH = "results/benchmark_results.db"
+
+# 지표 이름 → test_results 컬럼
+METRICS = {
+    'accuracy': 'accuracy_score',
+    'success_rate': 'success',
+    'hierarchical_f1': 'hierarchical_f1',
+    'json_validity': 'valid_json',
+    'response_time': 'response_time',
+}
+
+# 모델 이름 → 모델 계열 (먼저 일치하는 패턴 사용)
+MODEL_FAMILY_PATTERNS: List[Tuple[str, str]] = [
+    (r'^gpt-?4o', 'gpt-4o'),
+    (r'^gpt-?4\.1', 'gpt-4.1'),
+    (r'^gpt-?5', 'gpt-5'),
+    (r'^gpt', 'gpt'),
+    (r'^o\d', 'openai-o'),
+    (r'^gemini', 'gemini'),
+    (r'^grok', 'grok'),
+    (r'^claude', 'claude'),
+    (r'^pqc-?llama|pqc', 'pqc-llama'),
+    (r'^llama', 'llama'),
+    (r'^codellama', 'codellama'),
+    (r'^qwen', 'qwen'),
+    (r'^deepseek', 'deepseek'),
+    (r'^mistral|^mixtral', 'mistral'),
+    (r'^gemma', 'gemma'),
+    (r'^phi', 'phi'),
+]
+
+SCHEMA = """
+CREATE TABLE IF NOT EXISTS runs (
+    run_id TEXT PRIMARY KEY,
+    source TEXT,
+    started_at REAL,
+    corpus_version TEXT,
+    ingested_at REAL,
+    metadata TEXT
+);
+
+CREATE TABLE IF NOT EXISTS test_results (
+    run_id TEXT NOT NULL REFERENCES runs(run_id) ON DELETE CASCADE,
+    test_id TEXT NOT NULL,
+    agent_type TEXT NOT NULL,
+    provider TEXT,
+    model TEXT,
+    model_family TEXT,
+    configuration TEXT,
+    success INTEGER,
+    accuracy_score REAL,
+    hierarchical_f1 REAL,
+    valid_json INTEGER,
+    response_time REAL,
+    ground_truth_hash TEXT,
+    timestamp REAL
+);
+
+CREATE INDEX IF NOT EXISTS idx_test_results_run ON test_results(run_id);
+CREATE INDEX IF NOT EXISTS idx_test_results_family ON test_results(model_family);
+"""
+
+
+def ground_truth_hash(ground_truth: Optional[Dict[str, Any]]) -> Optional[str]:
+    """ground truth 내용 해시 (키 순서 무관)"""
+    if not ground_truth:
+        return None
+    canonical = json.dumps(ground_truth, sort_keys=True, ensure_ascii=False)
+    return hashlib.sha256(canonical.encode('utf-8')).hexdigest()[:16]
+
+
+def corpus_version(results: List[Dict[str, Any]]) -> Optional[str]:
+    """실행에 사용된 (agent_type, test_id, ground_truth_hash) 집합의 해시"""
+    entries = sorted({
+        (r.get('agent_type', ''), r.get('test_id', ''), r.get('ground_truth_hash') or '')
+        for r in results
+    })
+    if not entries:
+        return None
+    return hashlib.sha256(json.dumps(entries).encode('utf-8')).hexdigest()[:12]
+
+
+def model_family(model: str) -> str:
+    """모델 이름에서 계열 추출 ("llama3:8b" → "llama", "gpt-4o-mini" → "gpt-4o")"""
+    name = str(model or '').lower().split('/')[-1]
+    for pattern, family in MODEL_FAMILY_PATTERNS:
+        if re.search(pattern, name):
+            return family
+    return re.split(r'[:\-_.\d]', name, maxsplit=1)[0] or name
+
+
+class ResultsStore:
+    """실행 결과 누적 저장소"""
+
+    def __init__(self, db_path: str = RESULTS_DB_PATH, ground_truth_dir: str = "data/ground_truth"):
+        self.db_path = db_path
+        self.ground_truth_dir = Path(ground_truth_dir)
+        Path(db_path).parent.mkdir(parents=True, exist_ok=True)
+        self.conn = sqlite3.connect(db_path)
+        self.conn.row_factory = sqlite3.Row
+        self.conn.execute('PRAGMA foreign_keys = ON')
+        self.conn.executescript(SCHEMA)
+
+    def close(self):
+        self.conn.close()
+
+    # ==================== 적재 ====================
+
+    def ingest_file(self, path: str) -> Optional[str]:
+        with open(path, 'r', encoding='utf-8') as f:
+            data = json.load(f)
+        return self.ingest(data, source=str(path))
+
+    def ingest(self, data: Dict[str, Any], source: str = '') -> Optional[str]:
+        """결과 객체를 적재하고 run_id 반환 (같은 실행을 다시 적재하면 덮어씀)
+
+        ground truth 해시가 없는 과거 결과는 현재 ground truth 파일로 해시를 채우며,
+        실행 메타데이터에 `ground_truth_hash_backfilled: true`로 표시합니다.
+        """
+        parsed = self.parse_results(data)
+        if parsed is None:
+            return None
+        rows, started_at, metadata = parsed
+
+        backfilled = False
+        for row in rows:
+            if not row.get('ground_truth_hash'):
+                row['ground_truth_hash'] = self._current_ground_truth_hash(row['agent_type'], row['test_id'])
+                backfilled = backfilled or row['ground_truth_hash'] is not None
+        if backfilled:
+            metadata['ground_truth_hash_backfilled'] = True
+
+        version = metadata.get('corpus_version') if not backfilled else None
+        version = version or corpus_version(rows)
+        run_id = 'run_' + hashlib.sha256(f"{source}|{started_at}".encode('utf-8')).hexdigest()[:12]
+
+        with self.conn:
+            self.conn.execute('DELETE FROM runs WHERE run_id = ?', (run_id,))
+            self.conn.execute(
+                'INSERT INTO runs (run_id, source, started_at, corpus_version, ingested_at, metadata) '
+                'VALUES (?, ?, ?, ?, ?, ?)',
+                (run_id, source, started_at, version, time.time(), json.dumps(metadata, ensure_ascii=False))
+            )
+            self.conn.executemany(
+                'INSERT INTO test_results (run_id, test_id, agent_type, provider, model, model_family, '
+                'configuration, success, accuracy_score, hierarchical_f1, valid_json, response_time, '
+                'ground_truth_hash, timestamp) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)',
+                [(run_id, r['test_id'], r['agent_type'], r.get('provider'), r.get('model'),
+                  model_family(r.get('model')), r.get('configuration', 'default'),
+                  int(bool(r.get('success'))), r.get('accuracy_score'), r.get('hierarchical_f1'),
+                  int(bool(r.get('valid_json'))), r.get('response_time'),
+                  r.get('ground_truth_hash'), r.get('timestamp', started_at))
+                 for r in rows]
+            )
+        return run_id
+
+    @classmethod
+    def parse_results(cls, data: Dict[str, Any]) -> Optional[Tuple[List[Dict[str, Any]], float, Dict[str, Any]]]:
+        """결과 객체 → (테스트별 행, 시작 시각, 실행 메타데이터). 지원하지 않는 형식이면 None"""
+        if 'detailed_results' in data:
+            return cls._from_runner(data)
+        if 'benchmark_info' in data and isinstance(data.get('results'), list):
+            return cls._from_rag_effect(data)
+        return None
+
+    @staticmethod
+    def _from_runner(data: Dict[str, Any]) -> Tuple[List[Dict[str, Any]], float, Dict[str, Any]]:
+        metadata = dict(data.get('metadata', {}))
+        metadata.pop('network', None)
+        rows = []
+        for result in data['detailed_results']:
+            if 'error' in result:
+                continue
+            hierarchical = result.get('hierarchical_scores') or {}
+            rows.append({
+                'test_id': result.get('test_id', 'unknown'),
+                'agent_type': result.get('agent_type', ''),
+                'provider': result.get('provider'),
+                'model': result.get('model'),
+                'success': result.get('success'),
+                'accuracy_score': result.get('accuracy_score'),
+                'hierarchical_f1': hierarchical.get('f1'),
+                'valid_json': result.get('valid_json'),
+                'response_time': result.get('response_time'),
+                'ground_truth_hash': result.get('ground_truth_hash'),
+                'detected_algorithms': result.get('detected_algorithms'),
+                'track_scores': track_scores(result),
+                'timestamp': result.get('timestamp'),
+            })
+        return rows, float(metadata.get('timestamp') or time.time()), metadata
+
+    @classmethod
+    def _from_rag_effect(cls, data: Dict[str, Any]) -> Tuple[List[Dict[str, Any]], float, Dict[str, Any]]:
+        info = dict(data['benchmark_info'])
+        started_at = cls._parse_time(info.get('timestamp'))
+        rows = []
+        for result in data['results']:
+            tp = result.get('true_positives', 0)
+            fp = result.get('false_positives', 0)
+            fn = result.get('false_negatives', 0)
+            # 정답도 예측도 없으면 완전 일치로 간주
+            f1 = 2 * tp / (2 * tp + fp + fn) if (tp + fp + fn) else 1.0
+            raw = result.get('raw_response')
+            rows.append({
+                'test_id': result.get('test_id', 'unknown'),
+                'agent_type': result.get('agent_type', ''),
+                'provider': None,
+                'model': result.get('base_model'),
+                'configuration': 'rag' if result.get('with_rag') else 'default',
+                'success': f1 >= 0.6,
+                'accuracy_score': f1,
+                'valid_json': result.get('json_valid'),
+                'response_time': result.get('response_time'),
+                'detected_algorithms': raw.get('detected_algorithms') if isinstance(raw, dict) else None,
+                'timestamp': started_at,
+            })
+        return rows, started_at, info
+
+    @staticmethod
+    def _parse_time(value: Any) -> float:
+        if isinstance(value, (int, float)):
+            return float(value)
+        try:
+            return datetime.fromisoformat(str(value)).timestamp()
+        except ValueError:
+            return time.time()
+
+    def _current_ground_truth_hash(self, agent_type: str, test_id: str) -> Optional[str]:
+        path = self.ground_truth_dir / agent_type / f"{test_id}.json"
+        if not path.exists():
+            return None
+        try:
+            with open(path, 'r', encoding='utf-8') as f:
+                return ground_truth_hash(json.load(f))
+        except (json.JSONDecodeError, OSError):
+            return None
+
+    # ==================== 조회 ====================
+
+    def runs(self) -> List[Dict[str, Any]]:
+        rows = self.conn.execute('SELECT * FROM runs ORDER BY started_at').fetchall()
+        return [dict(row, metadata=json.loads(row['metadata'] or '{}')) for row in rows]
+
+    def results(self, run_ids: List[str] = None, agents: List[str] = None,
+                families: List[str] = None, configuration: str = None) -> List[Dict[str, Any]]:
+        query = 'SELECT r.*, runs.started_at, runs.corpus_version FROM test_results r JOIN runs USING (run_id)'
+        clauses, params = [], []
+        for column, values in (('r.run_id', run_ids), ('r.agent_type', agents), ('r.model_family', families)):
+            if values:
+                clauses.append(f"{column} IN ({', '.join('?' * len(values))})")
+                params.extend(values)
+        if configuration:
+            clauses.append('r.configuration = ?')
+            params.append(configuration)
+        if clauses:
+            query += ' WHERE ' + ' AND '.join(clauses)
+        return [dict(row) for row in self.conn.execute(query, params).fetchall()]
+
+    # ==================== 교집합 재계산 ====================
+
+    @staticmethod
+    def test_key(row: Dict[str, Any]) -> Tuple[str, str, Optional[str]]:
+        return (row['agent_type'], row['test_id'], row['ground_truth_hash'])
+
+    @classmethod
+    def intersection_keys(cls, rows: List[Dict[str, Any]],
+                          group_by: Callable[[Dict[str, Any]], Any] = None) -> set:
+        """모든 그룹에 동일한 ground truth로 존재하는 테스트 키 (agent_type, test_id, ground_truth_hash)
+
+        group_by: 행 → 그룹 키 (기본: 실행 단위)
+        """
+        group_by = group_by or (lambda row: row['run_id'])
+        groups: Dict[Any, set] = {}
+        for row in rows:
+            groups.setdefault(group_by(row), set()).add(cls.test_key(row))
+        if not groups:
+            return set()
+        return set.intersection(*groups.values())
+
+    @staticmethod
+    def compute_metrics(rows: List[Dict[str, Any]]) -> Dict[str, Any]:
+        metrics: Dict[str, Any] = {'tests': len(rows)}
+        for name, column in METRICS.items():
+            values = [row[column] for row in rows if row.get(column) is not None]
+            metrics[name] = sum(values) / len(values) if values else None
+        return metrics
+
+    def recompute_on_intersection(self, run_ids: List[str], agents: List[str] = None) -> Dict[str, Any]:
+        """여러 실행을 공통 테스트 집합으로 재채점
+
+        Returns:
+            {'common_tests': 공통 테스트 수, 'runs': {run_id: 지표}}
+        """
+        rows = self.results(run_ids=run_ids, agents=agents)
+        common = self.intersection_keys(rows)
+        by_run: Dict[str, List[Dict[str, Any]]] = {run_id: [] for run_id in run_ids}
+        for row in rows:
+            if self.test_key(row) in common:
+                by_run[row['run_id']].append(row)
+
+        return {
+            'common_tests': len(common),
+            'runs': {run_id: self.compute_metrics(run_rows) for run_id, run_rows in by_run.items()}
+        }
@@ -0,0 +1,193 @@
+"""
+실행 결과 표 내보내기 (CSV / Parquet)
+
+결과 파일(benchmark_runner.py, detectors/runner.py의 {metadata, detailed_results} JSON 또는 행마다 한 줄인 JSONL)을
+평평한 표 두 개로 풀어, pandas/Polars 노트북에서 JSON 파서 없이 바로 잘라 볼 수 있게 합니다. 오류 행도 status/error
+열과 함께 남깁니다.
+
+표:
+    samples    탐지기 × 샘플마다 한 행: 실행 상태, 계층 정밀도/재현율/F1, 트랙별 샘플 점수(utils/composite.py의
+               track_scores, 채점하지 않은 트랙은 빈 값), 탐지 라벨(';'로 연결), 응답 시간, 토큰 사용량
+    findings   탐지 하나마다 한 행 (long form): 알고리즘 탐지와 구현 약점(kind), 분류 체계 노드, 취약성 범주, CWE,
+               줄 범위, 신뢰도 (utils/report_export.py의 report_findings와 같은 해석)
+
+열 이름과 타입은 SAMPLE_COLUMNS / FINDING_COLUMNS로 고정합니다 (Parquet 스키마도 여기서 만듦). 두 표는
+(detector, agent_type, test_id)로 조인합니다.
+
+Parquet은 pyarrow가 있어야 씁니다 (pip install "qvbench[parquet]"). CSV는 표준 라이브러리만 씁니다.
+
+사용법:
+    python -m utils.results_table results/benchmark_results_1735689600.json --output-dir reports/tables
+    python -m utils.results_table results/structure.json --format parquet --output-dir reports/tables
+    python -m utils.results_table results/run.jsonl --table findings --detector llama3
+"""
+
+import argparse
+import csv
+import json
+import sys
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+from utils.composite import TRACKS, track_scores
+from utils.report_export import report_findings
+from utils.run_compare import detector_name, select_detector
+from utils.taxonomy import AlgorithmTaxonomy
+
+FORMATS = ['csv', 'parquet']
+TABLES = ['samples', 'findings']
+
+# (열 이름, 타입) — 타입은 Parquet 스키마용: string / int / float / bool
+SAMPLE_COLUMNS: List[Tuple[str, str]] = [
+    ('detector', 'string'), ('provider', 'string'), ('model', 'string'), ('configuration', 'string'),
+    ('agent_type', 'string'), ('test_id', 'string'), ('split', 'string'), ('task', 'string'),
+    ('language', 'string'), ('file_path', 'string'), ('status', 'string'), ('success', 'bool'),
+    ('accuracy_score', 'float'), ('precision', 'float'), ('recall', 'float'), ('f1', 'float'),
+] + [(f"track_{track}", 'float') for track in TRACKS] + [
+    ('confidence_score', 'float'), ('detected_count', 'int'), ('detected_algorithms', 'string'),
+    ('response_time', 'float'), ('total_tokens', 'int'), ('prompt_tokens', 'int'), ('completion_tokens', 'int'),
+    ('ground_truth_hash', 'string'), ('timestamp', 'float'), ('error', 'string'),
+]
+FINDING_COLUMNS: List[Tuple[str, str]] = [
+    ('detector', 'string'), ('agent_type', 'string'), ('test_id', 'string'), ('kind', 'string'),
+    ('algorithm', 'string'), ('mode', 'string'), ('category', 'string'), ('family', 'string'),
+    ('variant', 'string'), ('weakness', 'string'), ('cwe', 'string'), ('span_start', 'int'), ('span_end', 'int'),
+    ('confidence', 'float'),
+]
+
+
+def load_raw_rows(path: str) -> List[Dict[str, Any]]:
+    """결과 파일 → 테스트별 행 그대로 (오류 행 포함, JSON 실행 결과 또는 JSONL)"""
+    text = Path(path).read_text(encoding='utf-8')
+    try:
+        data = json.loads(text)
+    except json.JSONDecodeError:
+        data = None
+    if isinstance(data, dict):
+        if not isinstance(data.get('detailed_results'), list):
+            raise ValueError(f"{path}: detailed_results가 없는 결과 파일입니다")
+        return data['detailed_results']
+
+    rows = []
+    for number, line in enumerate(text.splitlines(), start=1):
+        if not line.strip():
+            continue
+        try:
+            item = json.loads(line)
+        except json.JSONDecodeError as e:
+            raise ValueError(f"{path}:{number}: JSON이 아닙니다 ({e.msg})")
+        rows.extend(item['detailed_results'] if isinstance(item, dict) and 'detailed_results' in item else [item])
+    return rows
+
+
+def _status(row: Dict[str, Any]) -> str:
+    return row.get('status') or ('error' if 'error' in row else 'ok')
+
+
+def sample_row(row: Dict[str, Any]) -> Dict[str, Any]:
+    """결과 행 → samples 표의 한 행"""
+    hierarchical = row.get('hierarchical_scores') or {}
+    usage = row.get('usage') if isinstance(row.get('usage'), dict) else {}
+    detected = [str(label) for label in row.get('detected_algorithms') or []]
+    scores = track_scores(row)
+    values = {
+        'detector': detector_name(row), 'status': _status(row), 'success': bool(row.get('success', False)),
+        'precision': hierarchical.get('precision'), 'recall': hierarchical.get('recall'), 'f1': hierarchical.get('f1'),
+        'detected_count': len(detected), 'detected_algorithms': ';'.join(detected),
+        'total_tokens': usage.get('total_tokens'), 'prompt_tokens': usage.get('prompt_tokens'),
+        'completion_tokens': usage.get('completion_tokens'),
+        'error': str(row['error']) if row.get('error') else None,
+    }
+    values.update({f"track_{track}": scores.get(track) for track in TRACKS})
+    return {name: values[name] if name in values else row.get(name) for name, _ in SAMPLE_COLUMNS}
+
+
+def finding_rows(row: Dict[str, Any], taxonomy: Optional[AlgorithmTaxonomy] = None) -> List[Dict[str, Any]]:
+    """결과 행 → findings 표의 행들 (오류 행은 없음)"""
+    if 'error' in row:
+        return []
+    taxonomy = taxonomy or AlgorithmTaxonomy()
+    rows = []
+    for finding in report_findings(row, taxonomy):
+        _, family, variant = taxonomy.resolve(finding['algorithm']) if finding['algorithm'] else (None, None, None)
+        span = finding['span'] or [None]
+        rows.append({
+            'detector': detector_name(row), 'agent_type': row.get('agent_type'), 'test_id': row.get('test_id'),
+            'kind': finding['kind'], 'algorithm': finding['algorithm'], 'mode': finding['mode'],
+            'category': finding['category'] if finding['kind'] == 'algorithm' else None,
+            'family': family, 'variant': variant,
+            'weakness': finding['category'] if finding['kind'] == 'weakness' else None,
+            'cwe': ';'.join(finding['cwe']) or None, 'span_start': span[0], 'span_end': span[-1],
+            'confidence': float(finding['confidence'] or 0.0),
+        })
+    return rows
+
+
+def build_tables(rows: List[Dict[str, Any]]) -> Dict[str, List[Dict[str, Any]]]:
+    """결과 행 → {'samples': [...], 'findings': [...]}"""
+    taxonomy = AlgorithmTaxonomy()
+    return {
+        'samples': [sample_row(row) for row in rows],
+        'findings': [finding for row in rows for finding in finding_rows(row, taxonomy)],
+    }
+
+
+def write_csv(records: List[Dict[str, Any]], columns: List[Tuple[str, str]], path: Path):
+    with open(path, 'w', newline='', encoding='utf-8') as f:
+        writer = csv.DictWriter(f, fieldnames=[name for name, _ in columns])
+        writer.writeheader()
+        for record in records:
+            writer.writerow({name: '' if value is None else value for name, value in record.items()})
+
+
+def write_parquet(records: List[Dict[str, Any]], columns: List[Tuple[str, str]], path: Path):
+    try:
+        import pyarrow as pa
+        import pyarrow.parquet as pq
+    except ImportError:
+        raise RuntimeError("Parquet 내보내기에는 pyarrow가 필요합니다 (pip install \"qvbench[parquet]\")")
+    types = {'string': pa.string(), 'int': pa.int64(), 'float': pa.float64(), 'bool': pa.bool_()}
+    schema = pa.schema([(name, types[kind]) for name, kind in columns])
+    table = pa.Table.from_pylist(records, schema=schema)
+    pq.write_table(table, path)
+
+
+TABLE_COLUMNS = {'samples': SAMPLE_COLUMNS, 'findings': FINDING_COLUMNS}
+WRITERS = {'csv': write_csv, 'parquet': write_parquet}
+
+
+def main():
+    parser = argparse.ArgumentParser(description='실행 결과를 CSV/Parquet 표로 내보내기 (샘플별, 탐지별)')
+    parser.add_argument('results', help='결과 파일 (benchmark_runner / detectors.runner JSON, 또는 JSONL)')
+    parser.add_argument('--format', choices=FORMATS, default='csv')
+    parser.add_argument('--table', choices=TABLES, action='append', help='내보낼 표 (여러 번 가능, 기본: 전부)')
+    parser.add_argument('--detector', help='내보낼 탐지기 (부분 문자열, 기본: 전부)')
+    parser.add_argument('--output-dir', default='results/tables', help='저장 디렉토리 (기본: results/tables)')
+    args = parser.parse_args()
+
+    try:
+        rows = load_raw_rows(args.results)
+        if args.detector:
+            name = select_detector({detector_name(row): [] for row in rows}, args.detector)
+            rows = [row for row in rows if detector_name(row) == name]
+    except (OSError, ValueError) as e:
+        parser.error(str(e))
+    if not rows:
+        parser.error(f"{args.results}: 내보낼 결과 행이 없습니다")
+
+    tables = build_tables(rows)
+    output_dir = Path(args.output_dir)
+    output_dir.mkdir(parents=True, exist_ok=True)
+    stem = Path(args.results).name.split('.')[0]
+    for table in args.table or TABLES:
+        path = output_dir / f"{stem}.{table}.{args.format}"
+        try:
+            WRITERS[args.format](tables[table], TABLE_COLUMNS[table], path)
+        except RuntimeError as e:
+            print(f"❌ {e}")
+            sys.exit(2)
+        print(f"💾 {table}: {len(tables[table])}행 → {path}")
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,258 @@
+"""
+채점 규칙 내보내기 (scoring rubric)
+
+발표된 점수를 제3자가 이 저장소의 코드 없이 다시 계산하고 검증할 수 있도록, 채점에 쓰는 규칙과 상수를 버전이 붙은
+JSON 하나로 내보냅니다. 값은 채점 코드가 실제로 읽는 상수에서 가져오므로 문서와 코드가 어긋나지 않습니다.
+
+    taxonomy                분류 체계 (카테고리 → 계열 → 변형), 기본 요소 역할, 별칭/부류 매핑, 이름 정규화
+    identification          계층 F1: 정책별 부분 점수 (계열/카테고리 공통 조상), 과제별 정책, 채점하지 못한 샘플 0점
+    accuracy                키워드 기반 정확도: 항목 가중치, 한국 알고리즘 보너스, success 기준, 키워드/변형 표
+    localization            함수 단위 F1 (줄 범위 → 함수 덮음 비율), hunk 도입 위치 (커밋 접두 길이), 중복 탐지 병합
+    misuse                  운용 모드/구현 약점: 약점 라벨과 CWE, 별칭, 비밀 취급/타이밍 하위 트랙
+    quantum_classification  양자 내성 라벨 오분류율
+    korean_compliance       한국 계열 탐지와 국제 대응 계열 혼동 (utils/korean_compliance.py)
+    composite               트랙 가중치, 점수 프로필, 재정규화 규칙 (utils/composite.py, config/benchmark.toml)
+    statistics              부트스트랩 신뢰구간, 순열 검정 설정, 언어별 부진 표시 기준
+
+버전:
+    rubric_version  채점 규칙을 바꾸면 올리는 번호 (RUBRIC_VERSION)
+    digest          rubric_version을 포함한 전체 내용의 SHA-256 (정렬된 키의 JSON), 가중치 파일이나 계층 정책을
+                    바꾸면 달라짐
+
+benchmark_runner.py 결과의 metadata.rubric에 실행 시점의 {version, digest}가 남으므로, 발표된 결과 파일이 어느
+규칙으로 채점됐는지 내보낸 rubric의 digest와 비교해 확인할 수 있습니다.
+`check`는 저장소에 둔 rubric(docs/scoring_rubric.json)이 현재 코드와 같은지 확인합니다. 규칙을 바꿨으면
+RUBRIC_VERSION을 올리고 `export`로 다시 씁니다.
+
+사용법:
+    python -m utils.rubric export --output docs/scoring_rubric.json
+    python -m utils.rubric export --weights my_weights.toml --hierarchy-policy lenient
+    python -m utils.rubric check
+"""
+
+import argparse
+import hashlib
+import json
+import sys
+import tomllib
+from pathlib import Path
+from typing import Dict, Any, Optional
+
+from utils.attributes import ATTRIBUTES, BLOCK_MODES
+from utils.composite import TRACKS, WEIGHTS_PATH, load_profiles, load_weights
+from utils.diffs import MIN_COMMIT_PREFIX
+from utils.findings_merger import DEFAULT_MERGE_POLICY, MERGE_POLICIES, SPAN_MERGE_GAP
+from utils.korean_compliance import INTERNATIONAL_EQUIVALENTS
+from utils.languages import MIN_LANGUAGE_SAMPLES
+from utils.metrics_calculator import (ACCURACY_WEIGHTS, ALGORITHM_VARIATIONS, CATEGORY_KEYWORDS, KOREAN_BONUS,
+                                      KOREAN_VARIATIONS, SUCCESS_THRESHOLD)
+from utils.misuse import (MODES, SECRET_HYGIENE_WEAKNESSES, TIMING_SAFETY_LABELS, TIMING_SAFETY_WEAKNESSES,
+                          WEAKNESS_ALIASES, WEAKNESS_CWE, WEAKNESSES)
+from utils.sample_index import SPAN_COVERAGE
+from utils.significance import DEFAULT_ALPHA, DEFAULT_CONFIDENCE, DEFAULT_ITERATIONS, DEFAULT_SEED
+from utils.tasks import TASK_HIERARCHY_POLICY
+from utils.taxonomy import DEFAULT_HIERARCHY_POLICY, HIERARCHY_POLICIES, PRIMITIVE_ROLES, TAXONOMY
+from utils.taxonomy_mapping import ALIASES, CLASS_ALIASES
+
+RUBRIC_VERSION = '1.2.0'
+RUBRIC_PATH = 'docs/scoring_rubric.json'
+
+
+def _canonical(value: Any) -> bytes:
+    return json.dumps(value, sort_keys=True, ensure_ascii=False, separators=(',', ':')).encode('utf-8')
+
+
+def rubric_digest(rubric: Dict[str, Any]) -> str:
+    """rubric 내용 해시 (digest 필드 제외)"""
+    return hashlib.sha256(_canonical({k: v for k, v in rubric.items() if k != 'digest'})).hexdigest()
+
+
+def build_rubric(weights_path: Optional[str] = WEIGHTS_PATH,
+                 hierarchy_policy: Optional[str] = None) -> Dict[str, Any]:
+    """현재 코드의 채점 규칙 → rubric (가중치 파일과 기본 계층 정책은 실행 설정을 따름)"""
+    if hierarchy_policy and hierarchy_policy not in HIERARCHY_POLICIES:
+        raise ValueError(f"unknown hierarchy policy: {hierarchy_policy} (choices: {sorted(HIERARCHY_POLICIES)})")
+    rubric = {
+        'rubric_version': RUBRIC_VERSION,
+        'taxonomy': {
+            'levels': ['category', 'family', 'variant'],
+            'hierarchy': TAXONOMY,
+            'primitive_roles': PRIMITIVE_ROLES,
+            'aliases': ALIASES,
+            'class_aliases': CLASS_ALIASES,
+            'name_normalization': 'lowercase, remove whitespace, "-" and "_" before comparing names',
+            'resolution': [
+                'a label may be a variant, family or category name, a path "category/family/variant" or a dict',
+                'path labels resolve from the most specific part that is known',
+                'unknown names try the alias target, then shorter leading-token prefixes ("aes-256-cbc" -> AES-256)',
+                'names outside the taxonomy only match the same normalized name',
+            ],
+        },
+        'identification': {
+            'metric': 'hierarchical_f1',
+            'policies': HIERARCHY_POLICIES,
+            'default_policy': hierarchy_policy or DEFAULT_HIERARCHY_POLICY,
+            'task_policies': TASK_HIERARCHY_POLICY,
+            'credit': [
+                'identical nodes: 1.0',
+                'prediction below the expected node (expected is an ancestor): policy.specific_prediction',
+                'deepest common ancestor is the family or category: policy.ancestor[level]',
+                'no common ancestor: 0.0',
+            ],
+            'precision': 'mean over distinct predicted nodes of the best credit against any acceptable answer',
+            'recall': 'mean over expected labels of the best credit of any prediction against any acceptable answer '
+                      '(disputed labels accept their listed alternatives)',
+            'f1': '2PR/(P+R); no expected and no predicted labels scores 1.0',
+            'unscored_sample_credit': 0.0,
+        },
+        'accuracy': {
+            'weights': ACCURACY_WEIGHTS,
+            'korean_bonus': KOREAN_BONUS,
+            'success_threshold': SUCCESS_THRESHOLD,
+            'formula': '(sum of weight x component + korean_bonus x korean component) / (sum of weights of '
+                       'components present); algorithm and category components count only when the ground truth '
+                       'lists them, the confidence component always counts',
+            'confidence_range': 'inside the expected range scores 1.0, otherwise 1 - distance to the nearest bound',
+            'algorithm_variations': [{'triggers': list(triggers), 'variations': variations}
+                                     for triggers, variations in ALGORITHM_VARIATIONS],
+            'category_keywords': CATEGORY_KEYWORDS,
+            'korean_variations': KOREAN_VARIATIONS,
+            'matching': 'case-insensitive substring search in the JSON-serialized analysis_results; the first '
+                        'algorithm_variations entry whose trigger occurs in the expected name applies',
+        },
+        'localization': {
+            'function_level': {
+                'span_coverage': SPAN_COVERAGE,
+                'attach': 'a span contained in a function attaches to the innermost one; otherwise to every '
+                          'outermost function whose lines are covered by at least span_coverage',
+                'unit': '(function qualified name, family)',
+                'precision': 'correct (function, family) pairs / predicted pairs (findings with a span only)',
+                'recall': 'expected spans with at least one matching pair / expected spans',
+            },
+            'hunk': {
+                'min_commit_prefix': MIN_COMMIT_PREFIX,
+                'hit': 'a report whose algorithm has identification credit > 0 and points at an expected hunk '
+                       '(hunk id or a new-side line inside it)',
+                'commit_hit': 'same algorithm and matching commit prefix',
+                'hunk_recall': 'hunk hits / labels with a recorded introduction',
+            },
+            'finding_merge': {
+                'policies': MERGE_POLICIES,
+                'default_policy': DEFAULT_MERGE_POLICY,
+                'span_merge_gap': SPAN_MERGE_GAP,
+            },
+        },
+        'misuse': {
+            'weaknesses': WEAKNESSES,
+            'cwe': WEAKNESS_CWE,
+            'aliases': WEAKNESS_ALIASES,
+            'alias_normalization': 'lowercase and drop every character outside [a-z0-9]',
+            'modes': MODES,
+            'block_modes': sorted(BLOCK_MODES),
+            'secret_hygiene_weaknesses': SECRET_HYGIENE_WEAKNESSES,
+            'timing_safety_weaknesses': TIMING_SAFETY_WEAKNESSES,
+            'timing_safety_labels': TIMING_SAFETY_LABELS,
+            'match': 'each expected (algorithm, weakness) pair consumes at most one report with the same weakness '
+                     'and the same family or no algorithm; unknown weakness names are ignored',
+            'f1': 'precision = matched / reports with a known weakness, recall = matched / expected pairs',
+            'mode_accuracy': 'first reported mode of the same family equals the recorded mode',
+            'timing_false_alarms': 'timing weakness reports on a constant-time family, or without an algorithm '
+                                   'when every timing label of the sample is constant-time',
+        },
+        'quantum_classification': {
+            'misclassified': 'a quantum-safe label is misclassified when any prediction has the same family or is '
+                             'its bare category',
+            'score': '1 - misclassified labels / quantum-safe labels',
+        },
+        'korean_compliance': {
+            'international_equivalents': INTERNATIONAL_EQUIVALENTS,
+            'detected': 'an expected Korean family is predicted with the same family (variants are not compared)',
+            'confused': 'an expected Korean family is not predicted but one of its international equivalents is, '
+                        'and that equivalent is not itself expected',
+            'false_claim': 'a Korean family is predicted, is not expected, and one of its international equivalents '
+                           'is expected',
+            'score': 'detected / (expected Korean families + false claims); samples with neither are not scored',
+        },
+        'attributes': {'compared': ATTRIBUTES,
+                       'rule': 'attributes are compared only when the prediction has the expected family'},
+        'composite': {
+            'tracks': TRACKS,
+            'weights': load_weights(weights_path),
+            'profiles': load_profiles(weights_path),
+            'track_scores': {
+                'identification': 'hierarchical_f1 (unscored samples with ground truth count as 0)',
+                'localization': 'function-level f1, else hunk_recall',
+                'misuse': 'weakness f1',
+                'quantum_classification': '1 - misclassification_rate',
+                'korean_compliance': 'korean_check.score',
+            },
+            'formula': 'sum(weight_t x mean_t) / sum(weight_t) over tracks with at least one scored sample',
+        },
+        'statistics': {
+            'bootstrap_iterations': DEFAULT_ITERATIONS,
+            'confidence': DEFAULT_CONFIDENCE,
+            'seed': DEFAULT_SEED,
+            'alpha': DEFAULT_ALPHA,
+            'resampling': 'samples with replacement; model comparisons use a paired permutation test on shared '
+                          'samples',
+            'language_min_samples': MIN_LANGUAGE_SAMPLES,
+            'language_flag': 'a language is flagged for a model when its hierarchical F1 mean is below the mean over '
+                             'the model\'s other languages and an unpaired permutation test gives p < alpha '
+                             '(both groups need language_min_samples samples)',
+        },
+    }
+    rubric['digest'] = rubric_digest(rubric)
+    return rubric
+
+
+def rubric_stamp(weights_path: Optional[str] = WEIGHTS_PATH, hierarchy_policy: Optional[str] = None) -> Dict[str, str]:
+    """결과 메타데이터에 남길 {version, digest}"""
+    rubric = build_rubric(weights_path, hierarchy_policy)
+    return {'version': rubric['rubric_version'], 'digest': rubric['digest']}
+
+
+def main():
+    parser = argparse.ArgumentParser(description='채점 규칙 rubric 내보내기/확인')
+    subparsers = parser.add_subparsers(dest='command', required=True)
+
+    export = subparsers.add_parser('export', help='현재 채점 규칙을 JSON으로 출력')
+    export.add_argument('--output', help='저장 경로 (기본: 표준 출력)')
+    export.add_argument('--weights', default=WEIGHTS_PATH, help=f'종합 점수 가중치 TOML (기본: {WEIGHTS_PATH})')
+    export.add_argument('--hierarchy-policy', choices=sorted(HIERARCHY_POLICIES), help='기본 계층 정책')
+
+    check = subparsers.add_parser('check', help='저장된 rubric이 현재 코드와 같은지 확인')
+    check.add_argument('path', nargs='?', default=RUBRIC_PATH)
+    check.add_argument('--weights', default=WEIGHTS_PATH, help=f'종합 점수 가중치 TOML (기본: {WEIGHTS_PATH})')
+
+    args = parser.parse_args()
+
+    try:
+        rubric = build_rubric(args.weights, getattr(args, 'hierarchy_policy', None))
+    except (ValueError, tomllib.TOMLDecodeError) as e:
+        parser.error(str(e))
+
+    if args.command == 'export':
+        text = json.dumps(rubric, indent=2, ensure_ascii=False) + '\n'
+        if not args.output:
+            print(text, end='')
+            return
+        Path(args.output).parent.mkdir(parents=True, exist_ok=True)
+        Path(args.output).write_text(text, encoding='utf-8')
+        print(f"💾 rubric 저장: {args.output} (버전 {rubric['rubric_version']}, digest {rubric['digest'][:12]})")
+        return
+
+    with open(args.path, 'r', encoding='utf-8') as f:
+        saved = json.load(f)
+    if saved.get('digest') != rubric_digest(saved):
+        print(f"❌ {args.path}: digest가 내용과 맞지 않습니다 (내보낸 뒤 수정됨)")
+        sys.exit(1)
+    if saved['digest'] != rubric['digest']:
+        changed = sorted(key for key in rubric if key != 'digest' and saved.get(key) != rubric[key])
+        hint = 'export로' if saved.get('rubric_version') != rubric['rubric_version'] else 'RUBRIC_VERSION을 올리고 export로'
+        print(f"❌ {args.path}: 현재 채점 규칙과 다릅니다 (바뀐 항목: {', '.join(changed)}), {hint} 다시 쓰세요")
+        sys.exit(1)
+    print(f"✅ rubric 일치: {args.path} (버전 {rubric['rubric_version']}, digest {rubric['digest'][:12]})")
+
+
+if __name__ == '__main__':
+    main()
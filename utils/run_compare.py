@@ -0,0 +1,257 @@
+"""
+실행 비교 / 회귀 탐지 도구
+
+두 결과 파일을 샘플 단위로 짝지어 비교합니다. 같은 탐지기의 이전/이후 실행(모델 회귀 추적)이나,
+같은 코퍼스에서 실행한 두 탐지기를 비교할 수 있습니다.
+
+    - 새로 실패한 샘플 (이전 성공 → 이후 실패), 새로 성공한 샘플 (이전 실패 → 이후 성공)
+    - 지표(정확도, 계층 F1, 성공률)의 평균 차이와 부트스트랩 95% 신뢰구간, paired permutation test p-값
+      (utils/significance.py). 신뢰구간이 0을 포함하지 않으면 유의한 변화로 표시
+    - 샘플별 계층 F1 하락 상위 목록
+
+짝짓기: (agent_type, test_id)가 같은 샘플만 비교합니다. 두 실행 사이에 ground truth가 바뀐 샘플
+(ground_truth_hash 불일치)은 라벨 변경과 모델 변화가 섞이므로 제외하고 개수만 보고합니다.
+
+탐지기 선택:
+    - 두 파일에 같은 탐지기(프로바이더/모델/구성)가 있으면 탐지기별로 이전/이후를 비교
+    - 파일마다 탐지기가 하나씩이고 서로 다르면 두 탐지기를 비교
+    - --old-detector / --new-detector로 직접 지정 (부분 문자열 일치)
+
+실행 환경: 두 결과 JSON의 metadata.environment(utils/environment.py)를 비교해 qvbench 버전/커밋, 코퍼스
+매니페스트, 프롬프트 해시, 시드, 같은 탐지기의 설정이 다르면 ⚠️로 경고합니다 (호스트 차이는 ℹ️). 비교는 그대로
+진행하며, --output JSON의 environment_mismatches에도 남깁니다.
+
+입력 형식: benchmark_runner.py / benchmark_rag_effect.py 결과 JSON (utils/results_store.py의
+parse_results), 또는 한 줄에 테스트 결과 하나씩인 JSONL.
+
+사용법:
+    python -m utils.run_compare results/old.json results/new.json
+    python -m utils.run_compare old.jsonl new.jsonl --old-detector llama3 --new-detector pqc-llama
+    python -m utils.run_compare old.json new.json --fail-on-regression --output results/compare.json
+"""
+
+import argparse
+import json
+import sys
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+from utils.environment import environment_mismatches, print_mismatches, results_environment
+from utils.results_store import ResultsStore
+from utils.significance import DEFAULT_ITERATIONS, paired_bootstrap_ci, paired_permutation_test
+
+# 비교 지표 → 행 컬럼
+COMPARE_METRICS = {
+    'accuracy': 'accuracy_score',
+    'hierarchical_f1': 'hierarchical_f1',
+    'success_rate': 'success',
+}
+
+METRIC_NAMES = {
+    'accuracy': '정확도',
+    'hierarchical_f1': '계층 F1',
+    'success_rate': '성공률',
+}
+
+
+def load_rows(path: str) -> List[Dict[str, Any]]:
+    """결과 파일 → 테스트별 행 (JSON 실행 결과 또는 JSONL)"""
+    text = Path(path).read_text(encoding='utf-8')
+    try:
+        data = json.loads(text)
+    except json.JSONDecodeError:
+        data = None
+
+    if isinstance(data, dict):
+        parsed = ResultsStore.parse_results(data)
+        if parsed is None:
+            raise ValueError(f"{path}: unsupported results format")
+        return parsed[0]
+
+    lines = [json.loads(line) for line in text.splitlines() if line.strip()]
+    rows = []
+    for line in lines:
+        parsed = ResultsStore.parse_results(line if 'detailed_results' in line else {'detailed_results': [line]})
+        rows.extend(parsed[0] if parsed else [])
+    return rows
+
+
+def detector_name(row: Dict[str, Any]) -> str:
+    name = '/'.join(part for part in (row.get('provider'), row.get('model')) if part) or 'unknown'
+    configuration = row.get('configuration', 'default')
+    return name if configuration in (None, 'default') else f"{name} [{configuration}]"
+
+
+def group_by_detector(rows: List[Dict[str, Any]]) -> Dict[str, List[Dict[str, Any]]]:
+    groups: Dict[str, List[Dict[str, Any]]] = {}
+    for row in rows:
+        groups.setdefault(detector_name(row), []).append(row)
+    return groups
+
+
+def select_detector(groups: Dict[str, List[Dict[str, Any]]], pattern: str) -> str:
+    matches = [name for name in groups if pattern.lower() in name.lower()]
+    if len(matches) != 1:
+        raise ValueError(f"detector '{pattern}' matches {len(matches)} of {sorted(groups)}")
+    return matches[0]
+
+
+def pairings(old_groups: Dict[str, List[Dict[str, Any]]], new_groups: Dict[str, List[Dict[str, Any]]],
+             old_detector: Optional[str] = None, new_detector: Optional[str] = None) -> List[Tuple[str, str]]:
+    """비교할 (이전 탐지기, 이후 탐지기) 쌍"""
+    if old_detector or new_detector:
+        old_name = select_detector(old_groups, old_detector) if old_detector else next(iter(old_groups))
+        new_name = select_detector(new_groups, new_detector) if new_detector else next(iter(new_groups))
+        return [(old_name, new_name)]
+
+    common = [name for name in old_groups if name in new_groups]
+    if common:
+        return [(name, name) for name in common]
+    if len(old_groups) == 1 and len(new_groups) == 1:
+        return [(next(iter(old_groups)), next(iter(new_groups)))]
+    raise ValueError("no detector appears in both runs; choose with --old-detector / --new-detector")
+
+
+def compare_rows(old_rows: List[Dict[str, Any]], new_rows: List[Dict[str, Any]],
+                 iterations: int = DEFAULT_ITERATIONS, top: int = 10) -> Dict[str, Any]:
+    """한 탐지기 쌍의 샘플 단위 비교"""
+    old_by_key = {(r['agent_type'], r['test_id']): r for r in old_rows}
+    new_by_key = {(r['agent_type'], r['test_id']): r for r in new_rows}
+
+    shared = sorted(set(old_by_key) & set(new_by_key))
+    relabeled = [
+        key for key in shared
+        if old_by_key[key].get('ground_truth_hash') and new_by_key[key].get('ground_truth_hash')
+        and old_by_key[key]['ground_truth_hash'] != new_by_key[key]['ground_truth_hash']
+    ]
+    paired = [key for key in shared if key not in relabeled]
+
+    def label(key):
+        return f"{key[0]}/{key[1]}"
+
+    newly_failed = [label(k) for k in paired if old_by_key[k].get('success') and not new_by_key[k].get('success')]
+    fixed = [label(k) for k in paired if not old_by_key[k].get('success') and new_by_key[k].get('success')]
+
+    metrics = {}
+    for name, column in COMPARE_METRICS.items():
+        keys = [k for k in paired
+                if old_by_key[k].get(column) is not None and new_by_key[k].get(column) is not None]
+        if not keys:
+            continue
+        old_scores = [float(old_by_key[k][column]) for k in keys]
+        new_scores = [float(new_by_key[k][column]) for k in keys]
+        metrics[name] = {
+            'old': sum(old_scores) / len(keys),
+            'new': sum(new_scores) / len(keys),
+            **paired_bootstrap_ci(old_scores, new_scores, iterations=iterations),
+            'p_value': paired_permutation_test(old_scores, new_scores, iterations=iterations)['p_value']
+        }
+
+    drops = sorted(
+        (
+            {'test': label(k),
+             'old': old_by_key[k]['hierarchical_f1'],
+             'new': new_by_key[k]['hierarchical_f1'],
+             'delta': new_by_key[k]['hierarchical_f1'] - old_by_key[k]['hierarchical_f1']}
+            for k in paired
+            if old_by_key[k].get('hierarchical_f1') is not None and new_by_key[k].get('hierarchical_f1') is not None
+        ),
+        key=lambda item: item['delta']
+    )
+
+    return {
+        'paired_tests': len(paired),
+        'relabeled': [label(k) for k in relabeled],
+        'only_old': [label(k) for k in sorted(set(old_by_key) - set(new_by_key))],
+        'only_new': [label(k) for k in sorted(set(new_by_key) - set(old_by_key))],
+        'newly_failed': newly_failed,
+        'fixed': fixed,
+        'metrics': metrics,
+        'largest_drops': [item for item in drops if item['delta'] < 0][:top]
+    }
+
+
+def is_regression(comparison: Dict[str, Any]) -> bool:
+    """새로 실패한 샘플이 있거나 어떤 지표가 유의하게 하락"""
+    return bool(comparison['newly_failed']) or any(
+        stats['significant'] and stats['mean_diff'] < 0 for stats in comparison['metrics'].values()
+    )
+
+
+def print_comparison(old_name: str, new_name: str, comparison: Dict[str, Any], limit: int = 10):
+    title = old_name if old_name == new_name else f"{old_name} → {new_name}"
+    print(f"\n▶ {title}")
+    print(f"  공통 테스트 {comparison['paired_tests']}개 (라벨 변경으로 제외 {len(comparison['relabeled'])}, "
+          f"이전에만 {len(comparison['only_old'])}, 이후에만 {len(comparison['only_new'])})")
+
+    for name, stats in comparison['metrics'].items():
+        if stats['significant']:
+            verdict = '📈 유의한 상승' if stats['mean_diff'] > 0 else '⚠️  유의한 하락'
+        else:
+            verdict = '변화 없음(유의하지 않음)'
+        print(f"  {METRIC_NAMES[name]}: {stats['old']:.3f} → {stats['new']:.3f}  "
+              f"Δ {stats['mean_diff']:+.3f} [{stats['confidence']:.0%} CI {stats['ci_low']:+.3f}, "
+              f"{stats['ci_high']:+.3f}], p={stats['p_value']:.4f}  {verdict}")
+
+    for icon, title, items in (('❌', '새로 실패', comparison['newly_failed']), ('✅', '새로 성공', comparison['fixed'])):
+        if items:
+            shown = ', '.join(items[:limit]) + (f" 외 {len(items) - limit}개" if len(items) > limit else '')
+            print(f"  {icon} {title} ({len(items)}): {shown}")
+
+    if comparison['largest_drops']:
+        print(f"  📉 계층 F1 하락 상위:")
+        for item in comparison['largest_drops'][:limit]:
+            print(f"     {item['test']}: {item['old']:.3f} → {item['new']:.3f} ({item['delta']:+.3f})")
+
+
+def main():
+    parser = argparse.ArgumentParser(description='두 벤치마크 실행 비교 (회귀 탐지)')
+    parser.add_argument('old', help='이전(기준) 결과 파일 (JSON 또는 JSONL)')
+    parser.add_argument('new', help='이후(비교) 결과 파일 (JSON 또는 JSONL)')
+    parser.add_argument('--old-detector', help='이전 결과에서 비교할 탐지기 (부분 문자열)')
+    parser.add_argument('--new-detector', help='이후 결과에서 비교할 탐지기 (부분 문자열)')
+    parser.add_argument('--agents', nargs='+', help='비교할 에이전트')
+    parser.add_argument('--iterations', type=int, default=DEFAULT_ITERATIONS, help='부트스트랩 반복 횟수')
+    parser.add_argument('--limit', type=int, default=10, help='샘플 목록 출력 개수')
+    parser.add_argument('--fail-on-regression', action='store_true',
+                        help='새로 실패한 샘플이나 유의한 하락이 있으면 종료 코드 1')
+    parser.add_argument('--output', help='비교 결과 JSON 저장 경로')
+    args = parser.parse_args()
+
+    old_rows, new_rows = load_rows(args.old), load_rows(args.new)
+    if args.agents:
+        old_rows = [r for r in old_rows if r['agent_type'] in args.agents]
+        new_rows = [r for r in new_rows if r['agent_type'] in args.agents]
+
+    old_groups, new_groups = group_by_detector(old_rows), group_by_detector(new_rows)
+    try:
+        pairs = pairings(old_groups, new_groups, args.old_detector, args.new_detector)
+    except ValueError as error:
+        print(f"❌ {error}")
+        sys.exit(2)
+
+    print(f"🔀 실행 비교: {args.old} → {args.new}")
+    mismatches = environment_mismatches(results_environment(args.old), results_environment(args.new))
+    print_mismatches(mismatches)
+    report = []
+    for old_name, new_name in pairs:
+        comparison = compare_rows(old_groups[old_name], new_groups[new_name], args.iterations, args.limit)
+        print_comparison(old_name, new_name, comparison, args.limit)
+        report.append({'old_detector': old_name, 'new_detector': new_name,
+                       'regression': is_regression(comparison), **comparison})
+
+    regressions = [item for item in report if item['regression']]
+    print(f"\n📊 비교 {len(report)}건, 회귀 {len(regressions)}건")
+
+    if args.output:
+        Path(args.output).parent.mkdir(parents=True, exist_ok=True)
+        with open(args.output, 'w', encoding='utf-8') as f:
+            json.dump({'old': args.old, 'new': args.new, 'environment_mismatches': mismatches, 'comparisons': report},
+                      f, indent=2, ensure_ascii=False)
+        print(f"💾 결과 저장: {args.output}")
+
+    sys.exit(1 if args.fail_on_regression and regressions else 0)
+
+
+if __name__ == "__main__":
+    main()
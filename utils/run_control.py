@@ -0,0 +1,150 @@
+"""
+샘플 단위 제한 시간과 취소 (run control)
+
+탐지기(특히 원격 모델과 오래 걸리는 분석기)가 멈추면 실행 전체가 멈춥니다. 샘플마다 제한 시간을 두고,
+넘기면 그 샘플을 timeout으로 기록한 뒤 다음 샘플로 넘어갑니다. 결과에서 빠지는 샘플이 없도록 모든 결과에
+상태(status)를 남기고, 요약은 상태별로 따로 셉니다.
+
+상태:
+    ok              응답을 받아 채점함 (파서가 못 읽었어도 judge 라벨로 채점했으면 ok)
+    parse_failure   응답은 받았지만 라벨을 읽지 못해 채점하지 못함
+    timeout         샘플 제한 시간 초과
+    error           API 호출 실패, 입력 없음
+    crash           탐지기/파서/채점 코드에서 예외가 나 샘플을 끝내지 못함 (결과의 crash에 예외와 발생 위치)
+
+취소는 협조적입니다: 파이썬 스레드는 밖에서 멈출 수 없으므로, 제한 시간이 지나거나 사용자가 중단(Ctrl-C)하면
+CancellationToken을 취소하고, 샘플 실행 코드가 API 호출 사이사이(창 분할 질의 사이 등)에 토큰을 확인해
+스스로 멈춥니다. 진행 중이던 호출은 클라이언트 자체 타임아웃까지 백그라운드에서 끝나며 그 결과는 버립니다.
+
+샘플 격리: 샘플 하나의 예외(탐지기의 큰 정수 파싱 실패, 재귀 한도 초과 등)는 그 샘플의 crash 결과로 기록하고
+실행은 다음 샘플로 넘어갑니다. crash 샘플은 다른 채점 불가 샘플처럼 0점으로 세며, 요약 끝에 모아서 보여 줍니다
+(crashed_samples).
+
+설정 (config.yaml):
+    benchmark:
+      sample_timeout_seconds: 300     # 0이면 제한 없음 (--sample-timeout으로 덮어쓰기)
+"""
+
+import contextvars
+import threading
+import traceback
+from pathlib import Path
+from typing import Any, Callable, Dict, List, Optional
+
+STATUS_OK = 'ok'
+STATUS_PARSE_FAILURE = 'parse_failure'
+STATUS_TIMEOUT = 'timeout'
+STATUS_ERROR = 'error'
+STATUS_CRASH = 'crash'
+
+RESULT_STATUSES = [STATUS_OK, STATUS_PARSE_FAILURE, STATUS_TIMEOUT, STATUS_ERROR, STATUS_CRASH]
+
+DEFAULT_SAMPLE_TIMEOUT = 300
+INTERRUPTED_BY_USER = 'interrupted by user'
+
+
+class Cancelled(Exception):
+    """취소된 토큰을 확인한 지점에서 발생"""
+
+
+class CancellationToken:
+    """협조적 취소 신호 (스레드 간 공유)"""
+
+    def __init__(self, parent: Optional['CancellationToken'] = None):
+        self._event = threading.Event()
+        self.parent = parent
+        self.reason = ''
+
+    def cancel(self, reason: str = 'cancelled'):
+        if not self._event.is_set():
+            self.reason = reason
+            self._event.set()
+
+    @property
+    def cancelled(self) -> bool:
+        return self._event.is_set() or bool(self.parent and self.parent.cancelled)
+
+    def raise_if_cancelled(self):
+        if self.cancelled:
+            raise Cancelled(self.reason or (self.parent.reason if self.parent else 'cancelled'))
+
+    def child(self) -> 'CancellationToken':
+        """이 토큰이 취소되면 함께 취소되는 하위 토큰 (샘플별 제한 시간용)"""
+        return CancellationToken(parent=self)
+
+
+def run_with_timeout(function: Callable[[CancellationToken], Dict[str, Any]], timeout: Optional[float],
+                     token: CancellationToken) -> Optional[Dict[str, Any]]:
+    """function(token)을 제한 시간 안에 실행
+
+    Returns:
+        함수 결과, 제한 시간을 넘기면 None (토큰은 취소되어 함수가 다음 확인 지점에서 멈춤)
+    """
+    if not timeout:
+        return function(token)
+
+    outcome: Dict[str, Any] = {}
+    context = contextvars.copy_context()  # 작업 스레드에서도 호출한 쪽의 추적 span을 부모로 이어감
+
+    def target():
+        try:
+            outcome['result'] = context.run(function, token)
+        except BaseException as e:  # 호출한 스레드에서 다시 발생시킴
+            outcome['error'] = e
+
+    worker = threading.Thread(target=target, daemon=True)
+    worker.start()
+    worker.join(timeout)
+    if worker.is_alive():
+        token.cancel(f"timed out after {timeout:g}s")
+        return None
+    if 'error' in outcome:
+        raise outcome['error']
+    return outcome['result']
+
+
+def crash_record(error: BaseException) -> Dict[str, Any]:
+    """예외 → 결과의 crash 필드 {'exception', 'message', 'location', 'traceback'} (except 블록 안에서 호출)"""
+    frames = traceback.extract_tb(error.__traceback__)
+    location = f"{Path(frames[-1].filename).name}:{frames[-1].lineno} ({frames[-1].name})" if frames else None
+    return {'exception': type(error).__name__, 'message': str(error), 'location': location,
+            'traceback': traceback.format_exc()}
+
+
+def crashed_samples(results: List[Dict[str, Any]]) -> List[Dict[str, Any]]:
+    """crash 결과 → 요약용 목록 (traceback은 결과 행에만 남김)"""
+    return [{'provider': result.get('provider'), 'model': result.get('model'),
+             'agent_type': result.get('agent_type'), 'test_id': result.get('test_id'),
+             **{key: result['crash'].get(key) for key in ('exception', 'message', 'location')}}
+            for result in results if result_status(result) == STATUS_CRASH and result.get('crash')]
+
+
+def print_crashes(crashes: List[Dict[str, Any]], limit: int = 10):
+    if not crashes:
+        return
+    print(f"\n💥 예외로 끝나지 못한 샘플 {len(crashes)}개 (0점으로 집계, 결과의 crash.traceback 참고):")
+    for crash in crashes[:limit]:
+        detector = '/'.join(part for part in (crash['provider'], crash['model']) if part)
+        print(f"  {detector} {crash['agent_type']}/{crash['test_id']}: {crash['exception']}: "
+              f"{crash['message'][:120]} @ {crash['location']}")
+    if len(crashes) > limit:
+        print(f"  ... 외 {len(crashes) - limit}개")
+
+
+def result_status(result: Dict[str, Any]) -> str:
+    """결과의 상태 (status 필드가 없던 이전 결과 파일도 같은 규칙으로 추정)"""
+    if result.get('status') in RESULT_STATUSES:
+        return result['status']
+    if result.get('crash'):
+        return STATUS_CRASH
+    if result.get('error'):
+        return STATUS_ERROR
+    if not result.get('valid_json', False) and result.get('label_source') != 'judge':
+        return STATUS_PARSE_FAILURE
+    return STATUS_OK
+
+
+def timeout_settings(config: Optional[Dict[str, Any]]) -> Optional[float]:
+    """benchmark 설정 → 샘플 제한 시간(초), 0/None이면 제한 없음"""
+    value = (config or {}).get('sample_timeout_seconds', DEFAULT_SAMPLE_TIMEOUT)
+    return float(value) if value else None
@@ -0,0 +1,495 @@
+#!/usr/bin/env python3
+"""
+샘플 태그와 필터 식 (sample tags / filter expressions)
+
+`--subset`은 미리 만든 정의 파일, `--limit`은 앞부분 자르기뿐이라 "Rust 어려운 샘플 중 미끼가 아닌 것"처럼
+속성으로 고르려면 목록을 손으로 만들어야 했습니다. ground truth의 tags에 `키=값` 태그를 달고, 필터 식으로
+평가할 샘플을 선언적으로 고릅니다. 같은 식을 러너, 로컬 탐지기, 부분집합 생성, 코퍼스 통계가 함께 씁니다.
+
+태그 (ground truth의 "tags" 목록):
+    "disguised"             이름만 있는 태그 (tag:disguised)
+    "domain=medical"        키=값 태그 (domain==medical, tag:domain도 참)
+    같은 키를 여러 번 달면 값이 여럿인 필드가 됩니다 (obfuscation=control-flow, obfuscation=string-encryption).
+
+필드:
+    agent           에이전트 유형 (source_code, assembly_binary, logs_config …)
+    id              test_id
+    lang            언어 (Rust, Python, manifest …; utils/languages.py와 같은 기준)
+    difficulty      난이도 easy/medium/hard, 크기 비교는 1/2/3으로 (difficulty>=2, difficulty>=medium)
+    task            과제 유형 (utils/tasks.py)
+    split           train/test (utils/splits.py)
+    provenance      출처 종류 (utils/provenance.py)
+    fidelity        샘플 전체 충실도 (utils/fidelity.py)
+    family          분류 체계 계열 (AES, RSA …)           ┐
+    category        카테고리 (shor_vulnerable …)           ├ 라벨이 여럿이면 하나라도 맞으면 참
+    role            기본 요소 역할 (public_key, cipher …)  ┘ (암호가 없는 샘플은 값 없음)
+    <키>            키=값 태그의 값 (계산 필드와 같은 키면 값이 더해짐, 예: lang=rust)
+
+식:
+    lang==rust && difficulty>=3 && !tag:decoy
+    (family==AES || family==ARIA) && split==test
+    id~"korean_*" || domain==medical
+
+    비교        == != < <= > >= ~(glob)   문자열은 대소문자 무시, 양쪽이 수이면 수로 비교
+    태그        tag:이름 (이름에 *, ? 패턴 허용)
+    존재        필드 이름만 (예: domain → domain 태그가 있는 샘플)
+    논리        ! && || 와 괄호 (우선순위 ! > && > ||)
+    값          따옴표 없는 단어 또는 "…"/'…' (공백, 연산자 문자를 쓰려면 따옴표)
+
+    값이 없는 필드의 비교는 거짓이고 !=만 참입니다. 식 오류는 위치와 함께 FilterError(ValueError)로 보고합니다.
+
+사용법:
+    python -m utils.sample_filter select "lang==rust && difficulty>=3 && !tag:decoy"
+    python -m utils.sample_filter select "domain==medical" --agents source_code --json
+    python -m utils.sample_filter fields source_code/medical_device_encryption_leaky
+    python -m utils.sample_filter tag source_code/kiosk_pin_vault domain=retail track=misuse
+    python -m utils.sample_filter tag --filter "family==SEED" track=korean
+    python -m utils.sample_filter tag source_code/kiosk_pin_vault track=misuse --remove
+
+    python benchmark_runner.py --filter "lang==rust && difficulty>=3" …
+    python -m detectors.runner --filter "split==test && !tag:generated" …
+    python -m utils.subset create rust_hard --filter "lang==rust && difficulty>=hard"
+    python -m utils.corpus stats --filter "domain==medical"
+"""
+
+import argparse
+import fnmatch
+import json
+import re
+import sys
+from pathlib import Path
+from typing import Dict, Any, Callable, Iterable, List, Optional, Tuple
+
+from utils.fidelity import sample_fidelity
+from utils.languages import sample_language
+from utils.metrics_calculator import MetricsCalculator
+from utils.provenance import provenance_kind
+from utils.splits import sample_split
+from utils.tasks import sample_task
+from utils.taxonomy import AlgorithmTaxonomy
+
+TEST_FILES_DIR = "data/test_files"
+GROUND_TRUTH_DIR = "data/ground_truth"
+DEFAULT_AGENTS = ['source_code', 'assembly_binary', 'logs_config']
+
+# 크기 비교에 쓰는 난이도 단계
+DIFFICULTY_LEVELS = {'easy': 1, 'medium': 2, 'hard': 3}
+TAG_FIELD = 'tags'
+TAG_PREFIX = 'tag:'
+OPERATORS = ['==', '!=', '<=', '>=', '<', '>', '~']
+
+TOKEN_PATTERN = re.compile(r"""
+    \s*(?:
+        (?P<op>&&|\|\||==|!=|<=|>=|<|>|~|!|\(|\))
+      | "(?P<dquote>[^"]*)"
+      | '(?P<squote>[^']*)'
+      | (?P<word>[A-Za-z0-9_.*?\-/+:#@]+)
+    )""", re.VERBOSE)
+TAG_KEY_PATTERN = re.compile(r'^[A-Za-z][A-Za-z0-9_.\-]*$')
+
+
+class FilterError(ValueError):
+    """필터 식 오류 (position: 식 안의 문자 위치)"""
+
+    def __init__(self, message: str, expression: str = '', position: Optional[int] = None):
+        self.expression = expression
+        self.position = position
+        if position is not None:
+            message = f"{message} at column {position + 1}\n    {expression}\n    {' ' * position}^"
+        super().__init__(message)
+
+
+def parse_tags(tags: Any) -> Tuple[List[str], Dict[str, List[str]]]:
+    """ground truth의 tags → (태그 이름 목록, 키=값 태그 {키: [값]})
+
+    키=값 태그의 키도 태그 이름에 들어가므로 tag:domain은 domain=medical이 있는 샘플에서 참입니다.
+    """
+    names: List[str] = []
+    values: Dict[str, List[str]] = {}
+    for tag in tags if isinstance(tags, list) else []:
+        if not isinstance(tag, str):
+            continue
+        key, sep, value = tag.partition('=')
+        key = key.strip()
+        if key and key not in names:
+            names.append(key)
+        if sep and key:
+            values.setdefault(key, []).append(value.strip())
+    return names, values
+
+
+def tag_issues(ground_truth: Dict[str, Any]) -> List[Tuple[str, str]]:
+    """tags 검사 → [(level, message)] (utils/corpus.py lint)"""
+    tags = ground_truth.get(TAG_FIELD)
+    if tags is None:
+        return []
+    if not isinstance(tags, list):
+        return [('error', f"tags must be a list of strings, got {type(tags).__name__}")]
+    issues = []
+    for tag in tags:
+        if not isinstance(tag, str) or not tag.strip():
+            issues.append(('error', f"tag {tag!r} is not a non-empty string"))
+            continue
+        key, sep, value = tag.partition('=')
+        if not TAG_KEY_PATTERN.match(key.strip()):
+            issues.append(('error', f"tag '{tag}': key must start with a letter and use letters, digits, _ . -"))
+        elif sep and not value.strip():
+            issues.append(('error', f"tag '{tag}' has an empty value"))
+        elif sep and key.strip() in ('agent', 'id'):
+            issues.append(('error', f"tag '{tag}' shadows the built-in field '{key.strip()}'"))
+    if len(set(tags)) != len(tags):
+        issues.append(('warning', "duplicate tags"))
+    return issues
+
+
+_TAXONOMY: Optional[AlgorithmTaxonomy] = None
+
+
+def _taxonomy() -> AlgorithmTaxonomy:
+    global _TAXONOMY
+    if _TAXONOMY is None:
+        _TAXONOMY = AlgorithmTaxonomy()
+    return _TAXONOMY
+
+
+def sample_fields(agent_type: str, test_id: str, ground_truth: Optional[Dict[str, Any]],
+                  file_path: Optional[str] = None) -> Dict[str, Any]:
+    """샘플 → 필터가 보는 필드 {이름: 문자열 | 문자열 목록}, 태그 이름은 'tags'"""
+    ground_truth = ground_truth if isinstance(ground_truth, dict) else {}
+    taxonomy = _taxonomy()
+    nodes = set()
+    for label in (MetricsCalculator.get_expected_labels(ground_truth)
+                  + MetricsCalculator.get_quantum_safe_labels(ground_truth)):
+        node = taxonomy.resolve(label)
+        if taxonomy.is_known(node):
+            nodes.add(node[:2])
+
+    fields: Dict[str, Any] = {
+        'agent': agent_type,
+        'id': test_id,
+        'lang': sample_language(file_path) if file_path else None,
+        'difficulty': str(ground_truth.get('difficulty', 'medium')),
+        'task': sample_task(ground_truth),
+        'split': sample_split(agent_type, test_id, ground_truth),
+        'provenance': provenance_kind(ground_truth),
+        'fidelity': sample_fidelity(ground_truth),
+        'family': sorted({node[1] for node in nodes if node[1]}),
+        'category': sorted({node[0] for node in nodes}),
+        'role': sorted({taxonomy.role(node) for node in nodes if taxonomy.role(node)}),
+    }
+    names, values = parse_tags(ground_truth.get(TAG_FIELD))
+    for key, tag_values in values.items():
+        current = fields.get(key)
+        if key in ('agent', 'id'):
+            continue
+        if current is None:
+            fields[key] = tag_values
+        else:
+            fields[key] = (current if isinstance(current, list) else [current]) + tag_values
+    fields[TAG_FIELD] = names
+    return fields
+
+
+def _values(value: Any) -> List[str]:
+    if value is None:
+        return []
+    return [str(item) for item in value] if isinstance(value, list) else [str(value)]
+
+
+def _number(field: str, value: str) -> Optional[float]:
+    if field == 'difficulty' and value.lower() in DIFFICULTY_LEVELS:
+        return float(DIFFICULTY_LEVELS[value.lower()])
+    try:
+        return float(value)
+    except ValueError:
+        return None
+
+
+def _compare(field: str, op: str, actual: str, expected: str) -> bool:
+    if op == '~':
+        return fnmatch.fnmatchcase(actual.lower(), expected.lower())
+    left, right = _number(field, actual), _number(field, expected)
+    if op in ('==', '!='):
+        equal = left == right if left is not None and right is not None else actual.lower() == expected.lower()
+        return equal if op == '==' else not equal
+    if left is None or right is None:
+        return False
+    return {'<': left < right, '<=': left <= right, '>': left > right, '>=': left >= right}[op]
+
+
+Predicate = Callable[[Dict[str, Any]], bool]
+
+
+class _Parser:
+    """재귀 하강 파서: or := and ('||' and)* / and := not ('&&' not)* / not := '!' not | atom"""
+
+    def __init__(self, expression: str):
+        self.expression = expression
+        self.tokens: List[Tuple[str, str, int]] = []
+        position = 0
+        while position < len(expression):
+            if not expression[position:].strip():
+                break
+            match = TOKEN_PATTERN.match(expression, position)
+            if not match or match.end() == position:
+                start = len(expression) - len(expression[position:].lstrip())
+                hint = " (use '==')" if expression[start] == '=' else ''
+                raise FilterError(f"unexpected character {expression[start]!r}{hint}", expression, start)
+            kind = match.lastgroup
+            text = match.group(kind)
+            start = match.start(kind) - (1 if kind in ('dquote', 'squote') else 0)
+            self.tokens.append(('value' if kind in ('dquote', 'squote') else kind, text, start))
+            position = match.end()
+        self.index = 0
+
+    def error(self, message: str) -> FilterError:
+        position = self.tokens[self.index][2] if self.index < len(self.tokens) else len(self.expression)
+        return FilterError(message, self.expression, position)
+
+    def peek(self) -> Optional[Tuple[str, str, int]]:
+        return self.tokens[self.index] if self.index < len(self.tokens) else None
+
+    def take(self, *ops: str) -> bool:
+        token = self.peek()
+        if token and token[0] == 'op' and token[1] in ops:
+            self.index += 1
+            return True
+        return False
+
+    def parse(self) -> Predicate:
+        if not self.tokens:
+            raise FilterError("empty filter expression", self.expression, 0)
+        predicate = self.parse_or()
+        if self.peek():
+            raise self.error(f"unexpected '{self.peek()[1]}'")
+        return predicate
+
+    def parse_or(self) -> Predicate:
+        terms = [self.parse_and()]
+        while self.take('||'):
+            terms.append(self.parse_and())
+        return terms[0] if len(terms) == 1 else (lambda fields: any(term(fields) for term in terms))
+
+    def parse_and(self) -> Predicate:
+        terms = [self.parse_not()]
+        while self.take('&&'):
+            terms.append(self.parse_not())
+        return terms[0] if len(terms) == 1 else (lambda fields: all(term(fields) for term in terms))
+
+    def parse_not(self) -> Predicate:
+        if self.take('!'):
+            inner = self.parse_not()
+            return lambda fields: not inner(fields)
+        return self.parse_atom()
+
+    def parse_atom(self) -> Predicate:
+        if self.take('('):
+            inner = self.parse_or()
+            if not self.take(')'):
+                raise self.error("missing ')'")
+            return inner
+        token = self.peek()
+        if not token or token[0] != 'word':
+            raise self.error("expected a field name, tag:NAME or '('" if token else "unexpected end of expression")
+        self.index += 1
+        name = token[1]
+
+        if name.lower().startswith(TAG_PREFIX):
+            pattern = name[len(TAG_PREFIX):].lower()
+            if not pattern:
+                raise FilterError("tag: needs a tag name", self.expression, token[2])
+            return lambda fields: any(fnmatch.fnmatchcase(tag.lower(), pattern) for tag in fields.get(TAG_FIELD, []))
+
+        operator = self.peek()
+        if not (operator and operator[0] == 'op' and operator[1] in OPERATORS):
+            return lambda fields: bool(_values(fields.get(name)))
+        self.index += 1
+        value = self.peek()
+        if not value or value[0] not in ('word', 'value'):
+            raise self.error(f"expected a value after '{operator[1]}'")
+        self.index += 1
+        op, expected = operator[1], value[1]
+        if op in ('<', '<=', '>', '>=') and _number(name, expected) is None:
+            raise FilterError(f"'{op}' needs a number{' or easy/medium/hard' if name == 'difficulty' else ''}, "
+                              f"got '{expected}'", self.expression, value[2])
+
+        def compare(fields: Dict[str, Any]) -> bool:
+            actual = _values(fields.get(name))
+            if op == '!=':
+                return not any(_compare(name, '==', item, expected) for item in actual)
+            return any(_compare(name, op, item, expected) for item in actual)
+
+        return compare
+
+
+class SampleFilter:
+    """컴파일한 필터 식"""
+
+    def __init__(self, expression: str):
+        self.expression = expression.strip()
+        self._predicate = _Parser(self.expression).parse()
+
+    def __str__(self) -> str:
+        return self.expression
+
+    def matches(self, fields: Dict[str, Any]) -> bool:
+        return self._predicate(fields)
+
+    def matches_sample(self, agent_type: str, test_id: str, ground_truth: Optional[Dict[str, Any]],
+                       file_path: Optional[str] = None) -> bool:
+        return self.matches(sample_fields(agent_type, test_id, ground_truth, file_path))
+
+    def select(self, agent_type: str, cases: Iterable[Dict[str, Any]],
+               load_ground_truth: Callable[[str, str], Optional[Dict[str, Any]]]) -> List[Dict[str, Any]]:
+        """테스트 케이스 중 식에 맞는 것 (케이스에 'ground_truth'가 없으면 load_ground_truth로 읽음)"""
+        selected = []
+        for case in cases:
+            ground_truth = case['ground_truth'] if 'ground_truth' in case else load_ground_truth(
+                agent_type, case['test_id'])
+            if self.matches_sample(agent_type, case['test_id'], ground_truth, case.get('file_path')):
+                selected.append(case)
+        return selected
+
+
+def compile_filter(expression: Optional[str]) -> Optional[SampleFilter]:
+    """CLI 인자 → SampleFilter (비어 있으면 None)"""
+    return SampleFilter(expression) if expression and expression.strip() else None
+
+
+def _test_manager(test_files_dir: str, ground_truth_dir: str):
+    from utils.test_case_manager import TestCaseManager
+
+    return TestCaseManager(test_cases_dir=str(Path(test_files_dir).parent / 'test_cases'),
+                           ground_truth_dir=ground_truth_dir, test_files_dir=test_files_dir)
+
+
+def run_select(args) -> int:
+    try:
+        sample_filter = SampleFilter(args.expression)
+    except FilterError as e:
+        print(f"❌ {e}")
+        return 2
+    manager = _test_manager(args.test_files_dir, args.ground_truth_dir)
+    selected = []
+    for agent_type in args.agents:
+        for case in sample_filter.select(agent_type, manager.load_test_cases(agent_type), manager.load_ground_truth):
+            selected.append({'agent_type': agent_type, 'test_id': case['test_id'],
+                             'file_path': case.get('file_path', '')})
+    if args.json:
+        print(json.dumps(selected, indent=2, ensure_ascii=False))
+        return 0
+    for item in selected:
+        print(f"  {item['agent_type']}/{item['test_id']}")
+    print(f"\n🔎 {sample_filter}: 샘플 {len(selected)}개")
+    return 0
+
+
+def _ground_truth_path(ground_truth_dir: str, sample: str) -> Path:
+    agent_type, _, test_id = sample.partition('/')
+    if not test_id:
+        raise ValueError(f"sample must be <agent>/<test_id>, got '{sample}'")
+    return Path(ground_truth_dir) / agent_type / f"{test_id}.json"
+
+
+def run_fields(args) -> int:
+    manager = _test_manager(args.test_files_dir, args.ground_truth_dir)
+    agent_type, _, test_id = args.sample.partition('/')
+    cases = [case for case in manager.load_test_cases(agent_type) if case['test_id'] == test_id]
+    if not test_id or not cases:
+        print(f"❌ 샘플을 찾을 수 없음: {args.sample}")
+        return 1
+    fields = sample_fields(agent_type, test_id, manager.load_ground_truth(agent_type, test_id),
+                           cases[0].get('file_path'))
+    for name, value in fields.items():
+        print(f"  {name:<12} {', '.join(_values(value)) if isinstance(value, list) else value}")
+    return 0
+
+
+def run_tag(args) -> int:
+    tags = list(args.tags)
+    for tag in tags:
+        for level, message in tag_issues({TAG_FIELD: [tag]}):
+            print(f"❌ {message}")
+            return 2
+
+    if args.filter:
+        try:
+            sample_filter = SampleFilter(args.filter)
+        except FilterError as e:
+            print(f"❌ {e}")
+            return 2
+        manager = _test_manager(args.test_files_dir, args.ground_truth_dir)
+        targets = [_ground_truth_path(args.ground_truth_dir, f"{agent_type}/{case['test_id']}")
+                   for agent_type in args.agents
+                   for case in sample_filter.select(agent_type, manager.load_test_cases(agent_type),
+                                                    manager.load_ground_truth)]
+    else:
+        if not args.sample:
+            print("❌ 샘플(<agent>/<test_id>) 또는 --filter가 필요합니다")
+            return 2
+        targets = [_ground_truth_path(args.ground_truth_dir, args.sample)]
+
+    changed = 0
+    for path in targets:
+        if not path.is_file():
+            print(f"⚠️  ground truth 없음: {path}")
+            continue
+        with open(path, 'r', encoding='utf-8') as f:
+            ground_truth = json.load(f)
+        current = [tag for tag in ground_truth.get(TAG_FIELD, []) if isinstance(tag, str)]
+        if args.remove:
+            # 키만 주면 그 키의 키=값 태그를 모두 제거
+            updated = [tag for tag in current
+                       if tag not in tags and tag.partition('=')[0] not in [t for t in tags if '=' not in t]]
+        else:
+            updated = current + [tag for tag in tags if tag not in current]
+        if updated == current:
+            continue
+        ground_truth[TAG_FIELD] = updated
+        if not args.dry_run:
+            with open(path, 'w', encoding='utf-8') as f:
+                f.write(json.dumps(ground_truth, indent=2, ensure_ascii=False) + '\n')
+        changed += 1
+        print(f"  🏷️  {path.parent.name}/{path.stem}: {', '.join(updated) or '(태그 없음)'}")
+    print(f"\n{'🔍 (dry-run) ' if args.dry_run else '💾 '}{changed}/{len(targets)}개 ground truth 태그 변경")
+    return 0
+
+
+def main():
+    parser = argparse.ArgumentParser(description='샘플 태그와 필터 식')
+    subparsers = parser.add_subparsers(dest='command', required=True)
+
+    select = subparsers.add_parser('select', help='필터 식에 맞는 샘플 목록')
+    select.add_argument('expression', help='필터 식 (예: "lang==rust && difficulty>=3 && !tag:decoy")')
+    select.add_argument('--agents', nargs='+', default=DEFAULT_AGENTS, help='대상 에이전트')
+    select.add_argument('--json', action='store_true', help='목록을 JSON으로 출력')
+
+    fields = subparsers.add_parser('fields', help='샘플 하나의 필드 값 (식을 쓸 때 참고)')
+    fields.add_argument('sample', help='<agent>/<test_id>')
+
+    tag = subparsers.add_parser('tag', help='ground truth에 태그 추가/제거')
+    tag.add_argument('sample', nargs='?', help='<agent>/<test_id> (--filter를 쓰면 생략)')
+    tag.add_argument('tags', nargs='+', help='태그 (이름 또는 키=값, --remove에서 키만 주면 그 키의 태그 모두)')
+    tag.add_argument('--filter', help='이 식에 맞는 모든 샘플에 적용')
+    tag.add_argument('--agents', nargs='+', default=DEFAULT_AGENTS, help='--filter 대상 에이전트')
+    tag.add_argument('--remove', action='store_true', help='추가 대신 제거')
+    tag.add_argument('--dry-run', action='store_true', help='파일을 쓰지 않고 바뀔 태그만 출력')
+
+    for sub in (select, fields, tag):
+        sub.add_argument('--test-files-dir', default=TEST_FILES_DIR)
+        sub.add_argument('--ground-truth-dir', default=GROUND_TRUTH_DIR)
+
+    args = parser.parse_args()
+    if args.command == 'tag' and args.filter and args.sample:
+        # --filter를 쓰면 첫 위치 인자도 태그
+        args.tags = [args.sample] + args.tags
+        args.sample = None
+    try:
+        sys.exit({'select': run_select, 'fields': run_fields, 'tag': run_tag}[args.command](args))
+    except ValueError as e:
+        print(f"❌ {e}")
+        sys.exit(2)
+
+
+if __name__ == "__main__":
+    main()
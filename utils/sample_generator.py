@@ -0,0 +1,734 @@
+#!/usr/bin/env python3
+"""
+합성 테스트 샘플 생성기
+
+단일 암호 알고리즘 샘플만으로는 탐지기가 프로토콜 안의 모든 기본 요소(primitive)를
+빠짐없이 나열하는지 평가할 수 없습니다. 이 도구는 여러 구조체에 흩어진 기본 요소를
+조합한 샘플을 시드 기반으로 생성하고, 라인 범위/충실도까지 채운 ground truth를 함께 만듭니다.
+
+handshake:  TLS 스타일 하이브리드 핸드셰이크 (Rust, 표준 라이브러리만 사용)
+    - ECDH 키 합의 (61비트 소수체 위의 축소 곡선)
+    - RSA 자격 증명(인증서) 서명 검증 (62비트 축소 모듈러스)
+    - HMAC-SHA-256 기반 HKDF (추출/확장)
+    - AES-128-GCM 레코드 보호 (GHASH 포함)
+
+    구조체 이름, 상수 이름, 도메인 테마, 블록 순서, 곡선/키 파라미터가 시드마다 달라지며
+    샘플에는 known-answer 테스트와 핸드셰이크 왕복 테스트가 포함됩니다
+    (`python -m utils.corpus verify`로 실행).
+
+legacy:     고전 공격으로 이미 깨진 레거시 암호 조합 (Rust, 표준 라이브러리만 사용)
+    - DES Feistel 구조 (IP/FP, E 확장, S-box, P 순열, PC-1/PC-2 키 스케줄 표)
+    - 3DES EDE 키 번들 (SP 800-67 keying option 1/2/3 중 시드별 선택; option 3은 단일 DES와 같음)
+    - RC4 KSA/PRGA 키스트림
+    - MD5 무결성 스탬프
+
+    라벨의 취약성 범주는 모두 broken-classical이므로 양자 노출(quantum-vulnerable, grover-margin)과
+    따로 집계됩니다 (요약의 by_weakness).
+
+사용법:
+    python -m utils.sample_generator handshake --seed 7
+    python -m utils.sample_generator handshake --seed 7 --name fleet_telemetry_link --verify
+    python -m utils.sample_generator handshake --seed 100 --count 5 --dry-run
+    python -m utils.sample_generator legacy --seed 3 --verify
+    python -m utils.sample_generator legacy --seed 0xfedcba9876543210 --dry-run
+    python -m utils.sample_generator legacy --seed 3 --comment-noise --verify   # 오도성 주석 변형
+    python -m utils.sample_generator legacy --seed 3 --split --verify           # 다중 파일 분할 변형
+    python -m utils.sample_generator misuse_vault --seed 7 --knob pattern=nonce-reuse --verify  # 오용 패턴 팩
+    python -m utils.sample_generator timing_pair --seed 7 --pair timing --verify   # 타이밍 누출/상수 시간 짝
+
+    --pair <knob>은 템플릿 팩에서 같은 시드로 knob 값마다 샘플을 하나씩 만듭니다. 테마, 식별자, 블록 순서는
+    knob과 다른 난수열을 쓰므로 짝끼리는 그 knob이 고르는 블록만 다릅니다.
+
+    변형(--comment-noise, --split)은 쓰기 전에 원본과 변형본의 내장 테스트 결과를 비교하고
+    (utils/obfuscation_verify.py), 동작이 바뀐 변형은 쓰지 않습니다 (--skip-semantics-check로 생략).
+
+결정성:
+    시드는 부호 없는 64비트 정수입니다 (utils/generator_seed.py의 GeneratorSeed). 같은 시드와 템플릿 버전이면
+    출력(소스와 ground truth)이 바이트 단위로 같으며, ground truth의 generated_by에 둘 다 기록됩니다.
+    생성기마다 GOLDEN_SEEDS의 출력을 data/generator_golden/에 골든 파일로 고정해 두고 비교합니다:
+
+    python -m utils.sample_generator golden              # 골든 파일과 비교 (다르면 종료 코드 1)
+    python -m utils.sample_generator golden --update     # 템플릿을 의도적으로 바꾼 뒤 골든 파일 갱신
+
+    카나리 기록(data/canary_manifest.json)에 현재 세대가 있으면 쓴 샘플 끝에 카나리 주석 한 줄을 붙입니다
+    (utils/canary.py). 골든 파일과 --dry-run 출력에는 카나리가 없으므로 결정성 비교에 영향을 주지 않습니다.
+"""
+
+import argparse
+import hashlib
+import json
+import random
+import sys
+from pathlib import Path
+from string import Template
+from typing import Dict, Any, List, Optional, Tuple, Union
+
+from utils.algorithm_split import describe, split_sample, write_split_sample
+from utils.canary import current_generation, inject, load_manifest, write_manifest
+from utils.comment_noise import apply_comment_noise
+from utils.corpus import CorpusVerifier, TEST_FILES_DIR, GROUND_TRUTH_DIR, STATUS_PASSED
+from utils.fidelity import FIDELITY_EXACT, FIDELITY_STRUCTURAL
+from utils.generator_seed import GeneratorSeed, template_version
+from utils.obfuscation_verify import STATUS_BROKEN, SemanticsVerifier, describe_check, record_check
+from utils.template_dsl import TemplatePackGenerator, discover_packs, load_identifiers, parse_knobs
+from utils.weakness import WEAKNESS_BROKEN
+
+TEMPLATES_DIR = Path(__file__).parent / "sample_templates"
+GOLDEN_DIR = Path(__file__).parent.parent / "data" / "generator_golden"
+
+# 골든 파일로 출력을 고정하는 시드 (작은 시드 하나, 64비트 상위 비트를 쓰는 시드 하나)
+GOLDEN_SEEDS = [1, 0xfedcba9876543210]
+
+# 역할별 식별자 후보 (후보끼리 겹치지 않아야 함)
+HANDSHAKE_NAMES: Dict[str, List[str]] = {
+    'Digest': ['TranscriptFingerprint', 'StreamCondenser', 'LedgerChecksum', 'FrameSummary'],
+    'KeySchedule': ['SecretExpander', 'MaterialRefiner', 'ChannelKeyLadder', 'EntropyShaper'],
+    'CurveAgreement': ['SharedPointExchange', 'CoordinateAgreement', 'PeerRendezvous', 'GroupElementMixer'],
+    'Coordinate': ['GridPosition', 'PlanePoint', 'LatticeSpot', 'MapLocation'],
+    'Credential': ['PeerBadge', 'IdentityToken', 'EndorsementRecord', 'MemberPass'],
+    'CredentialVerifier': ['IssuerCheck', 'BadgeValidator', 'TrustAnchorGate', 'EndorsementAuditor'],
+    'RecordSealer': ['FramePacker', 'PayloadVault', 'ParcelWrapper', 'EnvelopeCodec'],
+    'TrafficKeys': ['LinkSecrets', 'ChannelMaterial', 'SessionMaterial', 'RouteCredentials'],
+    'SessionNegotiator': ['LinkNegotiator', 'SessionBroker', 'ChannelOpener', 'HandoffCoordinator'],
+    'DIGEST_WORDS': ['MIXING_WORDS', 'ROUND_WORDS', 'CHURN_WORDS', 'SPREAD_WORDS'],
+    'BYTE_TABLE': ['LOOKUP_GRID', 'BYTE_PALETTE', 'REMAP_TABLE', 'GLYPH_TABLE'],
+    'STEP_BIAS': ['STEP_SEEDS', 'ROUND_OFFSETS', 'SCHEDULE_BIAS', 'LADDER_TWEAKS'],
+    'FIELD_LIMIT': ['FIELD_LIMIT', 'WRAP_MODULUS', 'RING_BOUND', 'RESIDUE_CAP'],
+    'SLOPE_TERM': ['SLOPE_TERM', 'LINEAR_COEFF', 'TILT_FACTOR', 'BEND_TERM'],
+    'OFFSET_TERM': ['OFFSET_TERM', 'SHIFT_COEFF', 'LIFT_FACTOR', 'BASE_TERM'],
+    'ANCHOR': ['ANCHOR_POSITION', 'ORIGIN_MARK', 'START_LOCATION', 'HOME_POINT'],
+    'CONTEXT_LABEL': ['CONTEXT_LABEL', 'PURPOSE_TAG', 'DERIVATION_NOTE', 'STAGE_MARKER'],
+    'OPERATOR_MODULUS': ['OPERATOR_MODULUS', 'ISSUER_RANGE', 'AUTHORITY_BOUND', 'ROOT_SPAN'],
+    'OPERATOR_EXPONENT': ['OPERATOR_EXPONENT', 'ISSUER_POWER', 'AUTHORITY_DEGREE', 'ROOT_ORDER'],
+    'INITIATOR_SCALAR': ['INITIATOR_SCALAR', 'CALLER_FACTOR', 'DIALER_SECRET', 'CLIENT_WEIGHT'],
+    'RESPONDER_SCALAR': ['RESPONDER_SCALAR', 'ANSWER_FACTOR', 'LISTENER_SECRET', 'SERVER_WEIGHT'],
+    'INITIATOR_RANDOM': ['INITIATOR_NONCE', 'CALLER_SALT', 'DIALER_TOKEN', 'CLIENT_CHALLENGE'],
+    'RESPONDER_RANDOM': ['RESPONDER_NONCE', 'ANSWER_SALT', 'LISTENER_TOKEN', 'SERVER_CHALLENGE'],
+}
+
+HANDSHAKE_THEMES: List[Dict[str, str]] = [
+    {
+        'stem': 'fleet_telemetry_uplink',
+        'domain': 'fleet telemetry',
+        'title': 'Fleet Telemetry Uplink',
+        'subtitle': 'Vehicle-to-depot session establishment for position and diagnostics reporting',
+        'subject': 'depot-gateway.fleet.local',
+        'context_label': 'fleet uplink traffic',
+        'record_header': 'TELEMETRY/1',
+        'payload': 'VIN 1HGCM82633A004352 lat=37.5665 lon=126.9780 speed=54',
+    },
+    {
+        'stem': 'pharmacy_dispatch_channel',
+        'domain': 'pharmacy dispatch',
+        'title': 'Pharmacy Dispatch Channel',
+        'subtitle': 'Prescription hand-off link between clinics and fulfilment centres',
+        'subject': 'dispatch.pharmacy-network.example',
+        'context_label': 'dispatch channel keys',
+        'record_header': 'RX-DISPATCH',
+        'payload': 'Rx 20931: amoxicillin 500mg x21, patient ref 88-1204',
+    },
+    {
+        'stem': 'smart_meter_collector_link',
+        'domain': 'smart meter',
+        'title': 'Smart Meter Collector Link',
+        'subtitle': 'Metering head-end session with neighbourhood data collectors',
+        'subject': 'collector-17.grid-operator.example',
+        'context_label': 'meter collection session',
+        'record_header': 'AMI-FRAME',
+        'payload': 'meter 4471-22 interval 15m import=1.284kWh export=0.000kWh',
+    },
+    {
+        'stem': 'payment_terminal_session',
+        'domain': 'payment terminal',
+        'title': 'Payment Terminal Session',
+        'subtitle': 'Point-of-sale terminal link to the acquirer switch',
+        'subject': 'switch.acquirer-gateway.example',
+        'context_label': 'terminal session traffic',
+        'record_header': 'POS-RECORD',
+        'payload': 'TXN 000418 amount=12500 KRW merchant=2231 terminal=T-88',
+    },
+]
+
+# 블록별 라벨: 블록 안의 구현이 라벨의 알고리즘 (순서는 ground truth 목록 순서)
+HANDSHAKE_BLOCK_LABELS: Dict[str, List[Tuple[str, str]]] = {
+    'curve': [('ECDH', FIDELITY_STRUCTURAL)],
+    'credential': [('RSA', FIDELITY_STRUCTURAL)],
+    'digest': [('SHA-256', FIDELITY_EXACT)],
+    'key_schedule': [('HMAC', FIDELITY_EXACT), ('HKDF', FIDELITY_EXACT)],
+    'sealer': [('AES-GCM', FIDELITY_EXACT), ('GHASH', FIDELITY_EXACT)],
+}
+
+# 순서를 섞는 본문 블록 (header는 맨 앞, main/tests는 맨 뒤)
+HANDSHAKE_BODY_BLOCKS = ['digest', 'key_schedule', 'curve', 'credential', 'sealer', 'negotiator']
+
+LEGACY_NAMES: Dict[str, List[str]] = {
+    'BlockCodec': ['LegacyBlockCodec', 'CardBlockEngine', 'VaultBlockShuffler', 'TerminalBlockMixer'],
+    'TripleWrap': ['LayeredBlockCodec', 'TripleStageEngine', 'CascadeVault', 'ChainedBlockPass'],
+    'Keystream': ['ByteShuffler', 'StreamScrambler', 'TokenMixer', 'SwapStream'],
+    'Checksum': ['BatchChecksum', 'RecordTally', 'PacketSummary', 'FileStamp'],
+    'Channel': ['TerminalChannel', 'BatchUploader', 'LegacyBridge', 'ArchiveRelay'],
+    'Rejection': ['UploadFault', 'FrameRejection', 'BridgeError', 'RelayRefusal'],
+    'ENTRY_ORDER': ['ENTRY_ORDER', 'INBOUND_SHUFFLE', 'OPENING_SWAP', 'LOAD_ORDER'],
+    'EXIT_ORDER': ['EXIT_ORDER', 'OUTBOUND_SHUFFLE', 'CLOSING_SWAP', 'STORE_ORDER'],
+    'WIDEN_ORDER': ['WIDEN_ORDER', 'SPREAD_MAP', 'STRETCH_PLAN', 'FAN_OUT'],
+    'STRAND_ORDER': ['STRAND_ORDER', 'WIRE_MAP', 'CROSSOVER', 'BRAID_PLAN'],
+    'KEY_PICK': ['KEY_PICK', 'SEED_SELECT', 'BUNDLE_FILTER', 'MASTER_GATHER'],
+    'ROUND_PICK': ['ROUND_PICK', 'STAGE_SELECT', 'TURN_FILTER', 'PASS_GATHER'],
+    'ROTATION_STEPS': ['ROTATION_STEPS', 'SLIDE_AMOUNTS', 'CYCLE_STEPS', 'DRIFT_STEPS'],
+    'LOOKUP_BOXES': ['LOOKUP_BOXES', 'NIBBLE_GRIDS', 'SWAP_TABLES', 'REPLACE_GRIDS'],
+    'TALLY_WORDS': ['TALLY_WORDS', 'SINE_WORDS', 'STIR_CONSTANTS', 'FOLD_WORDS'],
+    'TALLY_TURNS': ['TALLY_TURNS', 'TWIST_AMOUNTS', 'SPIN_STEPS', 'FOLD_TURNS'],
+    'BUNDLE_KEYS': ['BUNDLE_KEYS', 'TERMINAL_KEYS', 'ZONE_KEYS', 'LINK_KEYS'],
+    'STREAM_SECRET': ['STREAM_SECRET', 'BATCH_SECRET', 'SESSION_SEED', 'UPLOAD_KEY'],
+}
+
+LEGACY_THEMES: List[Dict[str, str]] = [
+    {
+        'stem': 'pos_batch_settlement',
+        'domain': 'POS settlement',
+        'title': 'POS Batch Settlement',
+        'subtitle': 'End-of-day batch upload from card terminals to the acquirer host',
+        'secret_item': 'PIN block',
+        'secret_value': '041234FFFFFFFFFF',
+        'payload': 'BATCH 0412 count=38 total=1284500 KRW terminal=T-2231',
+    },
+    {
+        'stem': 'scada_historian_bridge',
+        'domain': 'SCADA historian',
+        'title': 'SCADA Historian Bridge',
+        'subtitle': 'Legacy RTU bridge forwarding tag snapshots to the plant historian',
+        'secret_item': 'operator credential',
+        'secret_value': 'op-7731:valve',
+        'payload': 'RTU 14 tag=FT-201 value=38.72 quality=GOOD ts=1717059600',
+    },
+    {
+        'stem': 'hospital_lab_interface',
+        'domain': 'lab interface',
+        'title': 'Hospital Lab Interface',
+        'subtitle': 'Analyzer-to-LIS result relay kept on the original vendor protocol',
+        'secret_item': 'analyzer login',
+        'secret_value': 'LIS-ANALYZER-02',
+        'payload': 'OBX|1|NM|2345-7^Glucose||104|mg/dL|70-99|H',
+    },
+    {
+        'stem': 'tape_archive_relay',
+        'domain': 'tape archive',
+        'title': 'Tape Archive Relay',
+        'subtitle': 'Nightly relay of ledger extracts to the offsite tape library',
+        'secret_item': 'volume key',
+        'secret_value': 'VOL-0093-KEY',
+        'payload': 'EXTRACT ledger_2024_05.dat records=88210 crc=0x5e1a77c2',
+    },
+]
+
+# SP 800-67 keying option → (블록 라벨, 설명); option 3은 세 키가 같아 단일 DES로 축퇴
+LEGACY_KEYING_OPTIONS: Dict[int, Tuple[str, str]] = {
+    1: ('3DES', 'three independent keys'),
+    2: ('3DES', 'first and last keys shared'),
+    3: ('DES', 'one key repeated in every slot'),
+}
+
+LEGACY_BLOCK_LABELS: Dict[str, List[Tuple[str, str]]] = {
+    'block_cipher': [],  # keying option에 따라 결정
+    'keystream': [('RC4', FIDELITY_EXACT)],
+    'checksum': [('MD5', FIDELITY_EXACT)],
+}
+
+LEGACY_BODY_BLOCKS = ['block_cipher', 'keystream', 'checksum', 'channel']
+
+# 축소 곡선 y^2 = x^3 - 3x + b 의 소수체 (2^61 - 1)
+CURVE_FIELD = (1 << 61) - 1
+RSA_EXPONENT = 65537
+
+
+def is_probable_prime(n: int) -> bool:
+    """64비트 이하 정수에 대해 결정적인 Miller-Rabin"""
+    if n < 2:
+        return False
+    for p in (2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37):
+        if n % p == 0:
+            return n == p
+    d, r = n - 1, 0
+    while d % 2 == 0:
+        d //= 2
+        r += 1
+    for a in (2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37):
+        x = pow(a, d, n)
+        if x in (1, n - 1):
+            continue
+        for _ in range(r - 1):
+            x = pow(x, 2, n)
+            if x == n - 1:
+                break
+        else:
+            return False
+    return True
+
+
+class ToyCurve:
+    """생성 시점에 공개값/서명을 미리 계산하기 위한 곡선 연산 (샘플의 Rust 구현과 동일)"""
+
+    def __init__(self, b: int, p: int = CURVE_FIELD):
+        self.p = p
+        self.a = p - 3
+        self.b = b
+
+    def find_point(self, rng: random.Random) -> Tuple[int, int]:
+        # p ≡ 3 (mod 4) 이므로 제곱근은 rhs^((p+1)/4)
+        while True:
+            x = rng.randrange(2, self.p)
+            rhs = (x ** 3 + self.a * x + self.b) % self.p
+            y = pow(rhs, (self.p + 1) // 4, self.p)
+            if y and y * y % self.p == rhs:
+                return (x, y)
+
+    def join(self, p: Optional[Tuple[int, int]], q: Optional[Tuple[int, int]]) -> Optional[Tuple[int, int]]:
+        if p is None:
+            return q
+        if q is None:
+            return p
+        (x1, y1), (x2, y2) = p, q
+        if x1 == x2 and (y1 + y2) % self.p == 0:
+            return None
+        if x1 == x2:
+            slope = (3 * x1 * x1 + self.a) * pow(2 * y1, -1, self.p) % self.p
+        else:
+            slope = (y2 - y1) * pow(x2 - x1, -1, self.p) % self.p
+        x3 = (slope * slope - x1 - x2) % self.p
+        return (x3, (slope * (x1 - x3) - y1) % self.p)
+
+    def scale(self, scalar: int, point: Tuple[int, int]) -> Optional[Tuple[int, int]]:
+        result = None
+        for bit in reversed(range(64)):
+            result = self.join(result, result)
+            if (scalar >> bit) & 1:
+                result = self.join(result, point)
+        return result
+
+
+class TemplateSampleGenerator:
+    """블록 템플릿을 섞어 이어 붙이는 생성기의 공통 부분"""
+
+    generator_name = ''
+    language_extension = '.rs'
+
+    def __init__(self, seed: Union[GeneratorSeed, int], templates_dir: Optional[Path] = None):
+        self.seed = GeneratorSeed.of(seed)
+        self.rng = self.seed.rng()
+        self.templates_dir = Path(templates_dir or TEMPLATES_DIR / self.generator_name)
+        self.template_version = template_version(self.templates_dir)
+
+    def _render(self, blocks: List[str], mapping: Dict[str, Any]) -> Tuple[str, Dict[str, Tuple[int, int]], int]:
+        """블록 템플릿을 순서대로 치환해 (소스, 블록별 라인 범위, 전체 라인 수) 반환"""
+        source_lines: List[str] = []
+        spans: Dict[str, Tuple[int, int]] = {}
+        for block in blocks:
+            template = Template((self.templates_dir / f"{block}.rs").read_text(encoding='utf-8'))
+            lines = template.substitute(mapping).rstrip('\n').split('\n')
+            first = len(source_lines) + 1
+            spans[block] = (first, first + len(lines) - 1)
+            source_lines.extend(lines)
+            source_lines.append('')
+        return '\n'.join(source_lines), spans, len(source_lines)
+
+
+class HandshakeSampleGenerator(TemplateSampleGenerator):
+    """TLS 스타일 하이브리드 핸드셰이크 샘플 생성"""
+
+    generator_name = 'handshake'
+
+    def generate(self, name: str = None) -> Dict[str, Any]:
+        """샘플 생성
+
+        Returns:
+            {'name': str, 'source': str, 'ground_truth': dict}
+        """
+        theme = self.rng.choice(HANDSHAKE_THEMES)
+        mapping = {role: self.rng.choice(candidates) for role, candidates in HANDSHAKE_NAMES.items()}
+        mapping.update({key: value for key, value in theme.items() if key != 'stem'})
+        mapping.update(self._parameters(theme))
+
+        body = list(HANDSHAKE_BODY_BLOCKS)
+        self.rng.shuffle(body)
+
+        source, spans, total_lines = self._render(['header'] + body + ['main', 'tests'], mapping)
+        sample_name = name or f"{theme['stem']}_{self.seed}"
+        return {
+            'name': sample_name,
+            'source': source,
+            'ground_truth': self._ground_truth(theme, spans, total_lines),
+        }
+
+    def _parameters(self, theme: Dict[str, str]) -> Dict[str, Any]:
+        """곡선/RSA/난수 파라미터와, 이를 바탕으로 미리 계산한 공개값과 서명"""
+        rng = self.rng
+
+        curve = ToyCurve(rng.randrange(2, CURVE_FIELD))
+        anchor = curve.find_point(rng)
+        initiator_scalar = rng.randrange(1 << 40, 1 << 60)
+        responder_scalar = rng.randrange(1 << 40, 1 << 60)
+        responder_share = curve.scale(responder_scalar, anchor)
+
+        while True:
+            primes = []
+            while len(primes) < 2:
+                candidate = rng.randrange(1 << 30, 1 << 31) | 1
+                if is_probable_prime(candidate) and candidate not in primes:
+                    primes.append(candidate)
+            phi = (primes[0] - 1) * (primes[1] - 1)
+            if phi % RSA_EXPONENT:
+                break
+        modulus = primes[0] * primes[1]
+        private_exponent = pow(RSA_EXPONENT, -1, phi)
+
+        endorsed = (theme['subject'].encode() + responder_share[0].to_bytes(8, 'big')
+                    + responder_share[1].to_bytes(8, 'big'))
+        fingerprint = int.from_bytes(hashlib.sha256(endorsed).digest()[:8], 'big') % modulus
+
+        def byte_array() -> str:
+            return ', '.join(f"0x{rng.randrange(256):02x}" for _ in range(32))
+
+        return {
+            'curve_b': f"0x{curve.b:016x}",
+            'base_x': f"0x{anchor[0]:016x}",
+            'base_y': f"0x{anchor[1]:016x}",
+            'initiator_scalar': f"0x{initiator_scalar:016x}",
+            'responder_scalar': f"0x{responder_scalar:016x}",
+            'responder_share_x': f"0x{responder_share[0]:016x}",
+            'responder_share_y': f"0x{responder_share[1]:016x}",
+            'rsa_modulus': f"0x{modulus:016x}",
+            'rsa_exponent': str(RSA_EXPONENT),
+            'endorsement': f"0x{pow(fingerprint, private_exponent, modulus):016x}",
+            'initiator_random': byte_array(),
+            'responder_random': byte_array(),
+        }
+
+    def _ground_truth(self, theme: Dict[str, str], spans: Dict[str, Tuple[int, int]],
+                      total_lines: int) -> Dict[str, Any]:
+        algorithms, locations, fidelity = [], {}, {}
+        for block, labels in HANDSHAKE_BLOCK_LABELS.items():
+            start, end = spans[block]
+            for algorithm, level in labels:
+                algorithms.append(algorithm)
+                locations[algorithm] = [f"{start}-{end}"]
+                fidelity[algorithm] = level
+
+        return {
+            'description': f"{theme['title']}: ECDH 키 합의 + RSA 자격 증명 검증 + HKDF + AES-GCM 핸드셰이크 (생성 샘플)",
+            'difficulty': 'hard',
+            'tags': ['generated', 'protocol', 'handshake'],
+            'expected_findings': {
+                'vulnerable_algorithms_detected': algorithms,
+                'algorithm_categories': ['shor_vulnerable', 'grover_vulnerable', 'key_derivation',
+                                         'authenticated_encryption', 'mac'],
+                'korean_algorithms_detected': [],
+                'locations': locations,
+                'fidelity': fidelity,
+            },
+            'expected_confidence_range': [0.7, 0.9],
+            'generated_by': {'generator': self.generator_name, 'seed': self.seed.value,
+                             'template_version': self.template_version, 'lines': total_lines},
+        }
+
+
+class LegacyCipherSampleGenerator(TemplateSampleGenerator):
+    """DES/3DES + RC4 + MD5 레거시 암호 조합 샘플 생성 (broken-classical)"""
+
+    generator_name = 'legacy'
+
+    def generate(self, name: str = None) -> Dict[str, Any]:
+        """샘플 생성
+
+        Returns:
+            {'name': str, 'source': str, 'ground_truth': dict}
+        """
+        theme = self.rng.choice(LEGACY_THEMES)
+        mapping = {role: self.rng.choice(candidates) for role, candidates in LEGACY_NAMES.items()}
+        mapping.update({key: value for key, value in theme.items() if key != 'stem'})
+        mapping['secret_length'] = str(len(theme['secret_value']))
+
+        keying_option = self.rng.choice(sorted(LEGACY_KEYING_OPTIONS))
+        mapping.update(self._key_material(keying_option))
+
+        body = list(LEGACY_BODY_BLOCKS)
+        self.rng.shuffle(body)
+
+        source, spans, total_lines = self._render(['header'] + body + ['main', 'tests'], mapping)
+        sample_name = name or f"{theme['stem']}_{self.seed}"
+        return {
+            'name': sample_name,
+            'source': source,
+            'ground_truth': self._ground_truth(theme, keying_option, spans, total_lines),
+        }
+
+    def _key_material(self, keying_option: int) -> Dict[str, str]:
+        """keying option에 맞는 3DES 키 번들과 RC4 키"""
+        def key_bytes(count: int) -> List[int]:
+            return [self.rng.randrange(256) for _ in range(count)]
+
+        first, second, third = key_bytes(8), key_bytes(8), key_bytes(8)
+        if keying_option == 2:
+            third = first
+        elif keying_option == 3:
+            second = third = first
+
+        def render(values: List[int]) -> str:
+            return ', '.join(f"0x{value:02x}" for value in values)
+
+        return {
+            'keying_note': LEGACY_KEYING_OPTIONS[keying_option][1],
+            'bundle_key_1': render(first),
+            'bundle_key_2': render(second),
+            'bundle_key_3': render(third),
+            'stream_secret': render(key_bytes(16)),
+        }
+
+    def _ground_truth(self, theme: Dict[str, str], keying_option: int,
+                      spans: Dict[str, Tuple[int, int]], total_lines: int) -> Dict[str, Any]:
+        block_label = LEGACY_KEYING_OPTIONS[keying_option][0]
+        block_labels = dict(LEGACY_BLOCK_LABELS, block_cipher=[(block_label, FIDELITY_EXACT)])
+
+        algorithms, locations, fidelity, weakness = [], {}, {}, {}
+        for block, labels in block_labels.items():
+            start, end = spans[block]
+            for algorithm, level in labels:
+                algorithms.append(algorithm)
+                locations[algorithm] = [f"{start}-{end}"]
+                fidelity[algorithm] = level
+                weakness[algorithm] = WEAKNESS_BROKEN
+
+        return {
+            'description': f"{theme['title']}: {block_label} (keying option {keying_option}) 비밀 래핑 + "
+                           f"RC4 배치 암호화 + MD5 무결성 스탬프 — 고전 공격으로 이미 깨진 레거시 조합 (생성 샘플)",
+            'difficulty': 'medium',
+            'tags': ['generated', 'legacy', 'broken-classical'],
+            'expected_findings': {
+                'vulnerable_algorithms_detected': algorithms,
+                'algorithm_categories': ['grover_vulnerable', 'classical_vulnerable', 'symmetric_key',
+                                         'stream_cipher', 'hash_functions'],
+                'korean_algorithms_detected': [],
+                'locations': locations,
+                'fidelity': fidelity,
+                'weakness': weakness,
+            },
+            'expected_confidence_range': [0.75, 0.92],
+            'generated_by': {'generator': self.generator_name, 'seed': self.seed.value,
+                             'template_version': self.template_version, 'lines': total_lines,
+                             'keying_option': keying_option},
+        }
+
+
+GENERATORS = {
+    HandshakeSampleGenerator.generator_name: HandshakeSampleGenerator,
+    LegacyCipherSampleGenerator.generator_name: LegacyCipherSampleGenerator,
+}
+# sample.yaml이 있는 템플릿 디렉토리는 템플릿 DSL 팩 (utils/template_dsl.py)
+GENERATORS.update(discover_packs(TEMPLATES_DIR))
+
+
+def write_sample(sample: Dict[str, Any], extension: str, agent_type: str = 'source_code',
+                 test_files_dir: str = TEST_FILES_DIR, ground_truth_dir: str = GROUND_TRUTH_DIR,
+                 overwrite: bool = False) -> Optional[Path]:
+    source_path = Path(test_files_dir) / agent_type / f"{sample['name']}{extension}"
+    truth_path = Path(ground_truth_dir) / agent_type / f"{sample['name']}.json"
+    if not overwrite and (source_path.exists() or truth_path.exists()):
+        return None
+
+    source_path.parent.mkdir(parents=True, exist_ok=True)
+    truth_path.parent.mkdir(parents=True, exist_ok=True)
+    source_path.write_text(sample['source'], encoding='utf-8')
+    truth_path.write_text(json.dumps(sample['ground_truth'], indent=2, ensure_ascii=False) + '\n',
+                          encoding='utf-8')
+    return source_path
+
+
+def golden_paths(generator_name: str, seed: GeneratorSeed, golden_dir: Path = GOLDEN_DIR) -> Tuple[Path, Path]:
+    stem = Path(golden_dir) / generator_name / f"seed_{seed}"
+    return (stem.with_suffix(GENERATORS[generator_name].language_extension), stem.with_suffix('.json'))
+
+
+def golden_record(sample: Dict[str, Any]) -> str:
+    return json.dumps({'name': sample['name'], 'ground_truth': sample['ground_truth']},
+                      indent=2, ensure_ascii=False) + '\n'
+
+
+def check_golden(update: bool = False, golden_dir: Path = GOLDEN_DIR) -> int:
+    """생성기별 GOLDEN_SEEDS 출력을 골든 파일과 비교 (update면 다시 씀). 불일치 수 반환"""
+    failures = 0
+    for generator_name, generator_class in GENERATORS.items():
+        for value in GOLDEN_SEEDS:
+            seed = GeneratorSeed(value)
+            sample = generator_class(seed).generate()
+            source_path, record_path = golden_paths(generator_name, seed, golden_dir)
+            label = f"{generator_name} seed {seed}"
+
+            # 같은 프로세스에서 두 번 생성해도 같아야 함 (전역 난수/해시 순서 의존 확인)
+            if generator_class(seed).generate() != sample:
+                print(f"❌ {label}: 같은 시드로 두 번 생성한 출력이 다름")
+                failures += 1
+                continue
+
+            if update:
+                source_path.parent.mkdir(parents=True, exist_ok=True)
+                source_path.write_text(sample['source'], encoding='utf-8')
+                record_path.write_text(golden_record(sample), encoding='utf-8')
+                print(f"💾 {label}: {source_path}")
+                continue
+
+            if not source_path.exists() or not record_path.exists():
+                print(f"❌ {label}: 골든 파일 없음 (--update로 생성)")
+                failures += 1
+                continue
+
+            golden_source = source_path.read_text(encoding='utf-8')
+            golden = record_path.read_text(encoding='utf-8')
+            if golden_source == sample['source'] and golden == golden_record(sample):
+                print(f"✅ {label}: 일치 (템플릿 {generator_class(seed).template_version})")
+                continue
+
+            failures += 1
+            golden_version = json.loads(golden)['ground_truth'].get('generated_by', {}).get('template_version')
+            current_version = sample['ground_truth']['generated_by']['template_version']
+            if golden_version != current_version:
+                print(f"❌ {label}: 템플릿 버전 {golden_version} → {current_version} "
+                      f"(의도한 템플릿 변경이면 --update로 골든 파일 갱신)")
+            else:
+                print(f"❌ {label}: 같은 시드/템플릿 버전인데 출력이 다름 (생성기 코드 변경으로 결정성이 깨짐)")
+    return failures
+
+
+def seed_argument(text: str) -> GeneratorSeed:
+    try:
+        return GeneratorSeed.parse(text)
+    except ValueError as e:
+        raise argparse.ArgumentTypeError(str(e)) from e
+
+
+def main():
+    parser = argparse.ArgumentParser(description='합성 테스트 샘플 생성기')
+    subparsers = parser.add_subparsers(dest='generator', required=True)
+
+    for generator_name, generator_class in GENERATORS.items():
+        sub = subparsers.add_parser(generator_name, help=generator_class.__doc__)
+        sub.add_argument('--seed', type=seed_argument,
+                         help='생성 시드 (부호 없는 64비트, 10진수 또는 0x 16진수, 기본값: 무작위)')
+        sub.add_argument('--count', type=int, default=1, help='생성할 샘플 수 (seed, seed+1, ...)')
+        sub.add_argument('--name', help='샘플 이름 (--count 1일 때만 사용)')
+        sub.add_argument('--test-files-dir', default=TEST_FILES_DIR, help='샘플 출력 디렉토리')
+        sub.add_argument('--ground-truth-dir', default=GROUND_TRUTH_DIR, help='ground truth 출력 디렉토리')
+        sub.add_argument('--overwrite', action='store_true', help='같은 이름의 샘플이 있으면 덮어쓰기')
+        sub.add_argument('--verify', action='store_true', help='생성 후 샘플에 포함된 테스트 실행')
+        sub.add_argument('--dry-run', action='store_true', help='파일을 쓰지 않고 생성 결과만 출력')
+        sub.add_argument('--comment-noise', action='store_true',
+                         help='오도성 주석/문서 주석 주입 (utils/comment_noise.py, 이름에 _misdoc)')
+        sub.add_argument('--split', action='store_true',
+                         help='표/키 스케줄/라운드 함수를 보조 크레이트/모듈로 나눈 다중 파일 샘플 '
+                              '(utils/algorithm_split.py, 이름에 _split)')
+        sub.add_argument('--skip-semantics-check', action='store_true',
+                         help='변형을 쓰기 전 원본/변형본 내장 테스트 비교 생략')
+        if issubclass(generator_class, TemplatePackGenerator):
+            sub.add_argument('--theme', help='도메인 테마 고정 (기본값: 시드별 선택)')
+            sub.add_argument('--knob', action='append', metavar='NAME=VALUE', help='난독화 knob 고정 (반복 가능)')
+            sub.add_argument('--identifiers', help='식별자 사전 YAML (역할 → 후보 목록, 팩 기본 후보를 대체)')
+            sub.add_argument('--pair', metavar='KNOB',
+                             help='같은 시드로 이 knob의 값마다 샘플 생성 (예: timing → leaky/constant-time 짝)')
+
+    golden = subparsers.add_parser('golden', help='골든 파일로 생성기 출력 고정 확인')
+    golden.add_argument('--update', action='store_true', help='현재 출력으로 골든 파일 갱신')
+    golden.add_argument('--golden-dir', default=str(GOLDEN_DIR), help='골든 파일 디렉토리')
+
+    args = parser.parse_args()
+
+    if args.generator == 'golden':
+        sys.exit(1 if check_golden(args.update, Path(args.golden_dir)) else 0)
+
+    if args.name and args.count != 1:
+        parser.error('--name은 --count 1일 때만 사용할 수 있습니다')
+    if args.split and args.comment_noise:
+        parser.error('--split과 --comment-noise는 함께 쓸 수 없습니다 (주석 기록 줄이 파일 분할로 어긋남)')
+
+    generator_class = GENERATORS[args.generator]
+    options = {}
+    variants: List[Dict[str, Any]] = [{}]
+    if issubclass(generator_class, TemplatePackGenerator):
+        try:
+            options = {'theme': args.theme, 'knobs': parse_knobs(args.knob),
+                       'identifiers': load_identifiers(args.identifiers)}
+            generator_class(0, **options)
+        except ValueError as e:
+            parser.error(str(e))
+        if args.pair:
+            values = generator_class.spec_knobs().get(args.pair)
+            if not values:
+                parser.error(f"--pair: {args.generator}에 knob {args.pair!r}가 없습니다")
+            if args.name or args.pair in options['knobs']:
+                parser.error('--pair는 --name, 같은 knob의 --knob과 함께 쓸 수 없습니다')
+            variants = [{'knobs': {**options['knobs'], args.pair: value}} for value in values]
+    first_seed = args.seed if args.seed is not None else GeneratorSeed.generate()
+    verifier = CorpusVerifier() if args.verify else None
+    transformed = args.comment_noise or args.split
+    checker = SemanticsVerifier() if transformed and not args.skip_semantics_check else None
+    canaries = load_manifest() if current_generation() else None
+    failures = 0
+
+    for seed, variant in ((first_seed.offset(index), variant) for index in range(args.count) for variant in variants):
+        sample = generator_class(seed, **{**options, **variant}).generate(args.name)
+        if variant and f"knobs.{args.pair}" not in generator_class.spec_name():  # 이름에 knob이 없으면 짝끼리 겹침
+            sample['name'] = f"{sample['name']}_{variant['knobs'][args.pair].replace('-', '_')}"
+        original = sample['source']
+        if args.comment_noise:
+            sample = apply_comment_noise(sample, generator_class.language_extension, seed)
+        split = split_sample(sample, seed) if args.split else None
+        if split and not split['moved']:
+            print(f"⚠️  [seed {seed}] {sample['name']}: 라벨 범위 안에 옮길 최상위 표/함수가 없어 분할하지 않음")
+            continue
+        findings = (split or sample)['ground_truth']['expected_findings']
+        summary = ', '.join(f"{alg}@{findings['locations'][alg][0]}" for alg in findings['vulnerable_algorithms_detected'])
+        if split:
+            summary = f"{describe(split)}; {summary}"
+
+        if args.dry_run:
+            suffix = '/' if split else generator_class.language_extension
+            print(f"🧪 [seed {seed}] {(split or sample)['name']}{suffix}: {summary}")
+            continue
+
+        if checker:
+            pass_name = 'algorithm_split' if split else 'comment_noise'
+            check = checker.check(pass_name, original, split['files'] if split else sample['source'],
+                                  generator_class.language_extension, sample['name'])
+            print(f"   {describe_check(check)}")
+            if check['status'] == STATUS_BROKEN:
+                print(f"❌ [seed {seed}] {(split or sample)['name']}: 변형이 동작을 바꿔 쓰지 않음")
+                failures += 1
+                continue
+            record_check((split or sample)['ground_truth'], check)
+
+        if split:
+            path = write_split_sample(split, test_files_dir=args.test_files_dir,
+                                      ground_truth_dir=args.ground_truth_dir, overwrite=args.overwrite)
+        else:
+            path = write_sample(sample, generator_class.language_extension,
+                                test_files_dir=args.test_files_dir, ground_truth_dir=args.ground_truth_dir,
+                                overwrite=args.overwrite)
+        if path is None:
+            print(f"⚠️  [seed {seed}] {(split or sample)['name']} 이미 존재 (--overwrite로 덮어쓰기)")
+            continue
+        print(f"✅ [seed {seed}] {path}: {summary}")
+        if canaries:
+            sample_key = f"source_code/{(split or sample)['name']}"
+            inject(canaries, [(sample_key, path)], replace=True)
+            write_manifest(canaries)
+            print(f"   🐤 카나리 {canaries['samples'][sample_key]['canary']}")
+
+        if verifier:
+            result = verifier.verify(path)
+            status = '통과' if result['status'] == STATUS_PASSED else result['status']
+            print(f"   🔬 테스트 {status}: {result['tests_passed']} passed "
+                  f"{result.get('reason') or ''}".rstrip())
+            failures += result['status'] != STATUS_PASSED
+
+    sys.exit(1 if failures else 0)
+
+
+if __name__ == "__main__":
+    main()
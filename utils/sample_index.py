@@ -0,0 +1,419 @@
+"""
+샘플 함수 단위 색인 (SampleIndex)
+
+ground truth 줄 범위와 탐지 보고의 근거 줄을 함수에 붙여, "encrypt_block은 AES, process_block은 SHA-1"처럼
+함수 단위로 탐지를 비교합니다.
+
+파싱 백엔드 (--backend):
+    tree-sitter  utils.parsing의 문법으로 구문 트리를 만들어 C/C++/Java/Python/Go/Rust/JS/TS/C#/Ruby/PHP를 같은 형태로 색인
+                 (클래스/네임스페이스도 항목으로 잡혀 "CipherSuite::encrypt" 같은 소속 경로가 생김)
+    builtin      아래의 내장 분할기
+    auto         (기본) 해당 언어 문법이 설치돼 있으면 tree-sitter, 아니면 builtin
+
+Rust 샘플 (.rs, builtin):
+    문자열/원시 문자열(r#"..."#)/문자 리터럴/중첩 블록 주석을 건너뛰는 토크나이저로 항목을 찾습니다.
+    fn, impl, trait, mod 항목마다 종류, 이름, 소속 경로("Aes256::encrypt_block"), 줄 범위, 바이트 범위(UTF-8),
+    부모 항목을 기록합니다. 본문이 없는 선언(트레이트의 fn a();, mod a;)과 타입 위치의 fn(u8)/impl Trait는 제외합니다.
+    시작 위치는 항목 앞의 한정자(pub, pub(crate), const, async, unsafe, extern "C")부터입니다.
+다른 언어 (builtin):
+    utils.features.split_functions의 함수 분할 (중괄호/들여쓰기 휴리스틱, 최상위 함수만)
+
+함수 단위 채점 (calculate_function_scores):
+    정답  ground truth 라벨 줄 범위(utils.comment_noise.label_spans)가 걸친 함수마다 (함수, 계열) 쌍
+          함수 줄의 절반 이상이 범위 안이거나, 범위가 함수 안에 들어 있으면 그 함수에 붙습니다
+    예측  근거 줄(span)이 있는 탐지 보고를 같은 규칙으로 함수에 붙인 (함수, 계열) 쌍
+    precision  예측 쌍 중 정답 쌍인 비율 ("process_block을 SHA-1로" 맞혔는지)
+    recall     정답 범위 중 그 안의 함수를 하나라도 맞힌 범위의 비율 (impl 전체가 라벨이면 메서드 하나로 충분)
+    → 함수별 {'expected', 'predicted'}와 함께 기록 (줄 범위가 없는 샘플은 None)
+
+사용법:
+    python -m utils.sample_index data/test_files/source_code/medical_device_encryption.rs
+    python -m utils.sample_index data/test_files/source_code/medical_device_encryption.rs --json
+"""
+
+import argparse
+import bisect
+import json
+import re
+from pathlib import Path
+from typing import Dict, Any, Iterable, List, Optional, Tuple
+
+ITEM_KINDS = ('fn', 'impl', 'trait', 'mod', 'class')
+RUST_KEYWORDS = ('fn', 'impl', 'trait', 'mod')
+FUNCTION = 'fn'
+RUST_SUFFIXES = ('.rs',)
+
+BACKEND_AUTO = 'auto'
+BACKEND_BUILTIN = 'builtin'
+BACKENDS = [BACKEND_AUTO, 'tree-sitter', BACKEND_BUILTIN]
+
+# 줄 범위가 함수에 붙으려면 덮어야 하는 함수 줄 비율 (범위가 함수 안에 들어 있으면 비율과 무관)
+SPAN_COVERAGE = 0.5
+
+# 항목 앞에 올 수 있는 한정자 (항목 시작 위치를 여기까지 당김)
+_QUALIFIERS = {'pub', 'const', 'async', 'unsafe', 'extern', 'default'}
+# 항목 키워드 바로 앞 토큰이 이 중 하나면 문장 시작 (그 외에는 타입 위치의 fn/impl)
+_ITEM_START = {None, '{', '}', ';', ']'}
+
+_IDENT = re.compile(r'[A-Za-z_][A-Za-z0-9_]*')
+_RAW_STRING = re.compile(r'b?r(#*)"')
+
+
+def _skip_block_comment(text: str, index: int) -> int:
+    depth = 0
+    while index < len(text):
+        if text.startswith('/*', index):
+            depth += 1
+            index += 2
+        elif text.startswith('*/', index):
+            depth -= 1
+            index += 2
+            if depth == 0:
+                return index
+        else:
+            index += 1
+    return index
+
+
+def _skip_string(text: str, index: int) -> int:
+    """index는 여는 따옴표 다음 위치 → 닫는 따옴표 다음 위치"""
+    while index < len(text):
+        if text[index] == '\\':
+            index += 2
+        elif text[index] == '"':
+            return index + 1
+        else:
+            index += 1
+    return index
+
+
+def rust_tokens(text: str) -> List[Tuple[str, int, int]]:
+    """주석을 빼고 (토큰, 시작, 끝) 문자 오프셋 목록 (문자열/문자 리터럴은 '"' 토큰 하나)"""
+    tokens = []
+    index = 0
+    while index < len(text):
+        char = text[index]
+        if char.isspace():
+            index += 1
+        elif text.startswith('//', index):
+            end = text.find('\n', index)
+            index = len(text) if end < 0 else end
+        elif text.startswith('/*', index):
+            index = _skip_block_comment(text, index)
+        elif _RAW_STRING.match(text, index):
+            hashes = _RAW_STRING.match(text, index)
+            close = text.find('"' + hashes.group(1), hashes.end())
+            end = len(text) if close < 0 else close + 1 + len(hashes.group(1))
+            tokens.append(('"', index, end))
+            index = end
+        elif char == '"' or text.startswith('b"', index):
+            start = index
+            index = _skip_string(text, index + (2 if char == 'b' else 1))
+            tokens.append(('"', start, index))
+        elif char == "'" or text.startswith("b'", index):
+            start = index
+            body = index + (2 if char == 'b' else 1)
+            if text.startswith('\\', body):
+                close = text.find("'", body + 2)
+                index = len(text) if close < 0 else close + 1
+                tokens.append(('"', start, index))
+            elif text.startswith("'", body + 1):
+                index = body + 2
+                tokens.append(('"', start, index))
+            else:  # 수명 'a
+                ident = _IDENT.match(text, body)
+                index = ident.end() if ident else body
+                tokens.append(("'", start, index))
+        else:
+            ident = _IDENT.match(text, index)
+            end = ident.end() if ident else index + 1
+            if not ident and char.isdigit():
+                end = re.compile(r'[0-9A-Za-z_.]*').match(text, index).end()
+            tokens.append((text[index:end], index, end))
+            index = end
+    return tokens
+
+
+def _matching_braces(tokens: List[Tuple[str, int, int]]) -> Dict[int, int]:
+    """여는 중괄호 토큰 번호 → 닫는 중괄호 토큰 번호"""
+    pairs, stack = {}, []
+    for position, (token, _, _) in enumerate(tokens):
+        if token == '{':
+            stack.append(position)
+        elif token == '}' and stack:
+            pairs[stack.pop()] = position
+    return pairs
+
+
+def _header_end(tokens: List[Tuple[str, int, int]], position: int) -> Optional[int]:
+    """항목 머리 다음의 본문 여는 중괄호 (본문 없는 선언이면 None)"""
+    depth = 0
+    for index in range(position + 1, len(tokens)):
+        token = tokens[index][0]
+        if token in ('(', '['):
+            depth += 1
+        elif token in (')', ']'):
+            depth -= 1
+        elif depth == 0 and token == ';':
+            return None
+        elif depth == 0 and token == '{':
+            return index
+    return None
+
+
+def _type_name(tokens: List[Tuple[str, int, int]]) -> Optional[str]:
+    """경로 타입의 마지막 이름 (제네릭 인자와 참조 기호는 건너뜀)"""
+    depth, name = 0, None
+    for token, _, _ in tokens:
+        if token == '<':
+            depth += 1
+        elif token == '>':
+            depth -= 1
+        elif depth == 0 and _IDENT.fullmatch(token) and token not in ('dyn', 'mut', 'for'):
+            name = token
+    return name
+
+
+def _impl_names(header: List[Tuple[str, int, int]]) -> Tuple[Optional[str], Optional[str]]:
+    """impl 머리 → (자기 타입 이름, 트레이트 이름)"""
+    if header and header[0][0] == '<':  # impl<T: Bound> 제네릭 매개변수
+        depth = 0
+        for index, (token, _, _) in enumerate(header):
+            depth += (token == '<') - (token == '>')
+            if depth == 0:
+                header = header[index + 1:]
+                break
+    where = next((index for index, (token, _, _) in enumerate(header) if token == 'where'), len(header))
+    header = header[:where]
+    split = next((index for index, (token, _, _) in enumerate(header) if token == 'for'), None)
+    if split is None:
+        return _type_name(header), None
+    return _type_name(header[split + 1:]), _type_name(header[:split])
+
+
+class SampleIndex:
+    """샘플의 함수/impl/trait/mod 항목 색인 (줄 번호는 1부터, 양끝 포함)"""
+
+    def __init__(self, items: List[Dict[str, Any]], language: Optional[str], line_count: int,
+                 backend: str = BACKEND_BUILTIN):
+        self.items = items
+        self.language = language
+        self.line_count = line_count
+        self.backend = backend
+
+    @classmethod
+    def from_text(cls, text: str, suffix: str = '.rs', backend: str = BACKEND_AUTO) -> 'SampleIndex':
+        """backend: auto (tree-sitter 문법이 있으면 그것, 없으면 내장), tree-sitter (없으면 오류), builtin"""
+        from utils.features import language_family
+        from utils.parsing import BACKEND as TREE_SITTER, language_for, parse_items
+
+        if backend not in BACKENDS:
+            raise ValueError(f"알 수 없는 파싱 백엔드: {backend} (선택: {', '.join(BACKENDS)})")
+        suffix = suffix.lower() if suffix.startswith('.') else f".{suffix.lower()}"
+        line_count = len(text.splitlines())
+        if backend != BACKEND_BUILTIN:
+            items = parse_items(text, suffix)
+            if items is not None:
+                return cls(items, language_for(suffix), line_count, TREE_SITTER)
+            if backend == TREE_SITTER:
+                raise RuntimeError(f"{suffix} 파싱에는 tree-sitter와 해당 언어 문법이 필요합니다 "
+                                   f"(pip install \"qvbench[parsing]\")")
+        if suffix in RUST_SUFFIXES:
+            return cls(cls._rust_items(text), 'rust', line_count)
+        language = language_family(suffix)
+        return cls(cls._split_items(text, language), language, line_count)
+
+    @classmethod
+    def from_file(cls, path: str, backend: str = BACKEND_AUTO) -> 'SampleIndex':
+        with open(path, 'r', encoding='utf-8', errors='replace') as f:
+            return cls.from_text(f.read(), Path(path).suffix, backend)
+
+    @staticmethod
+    def _locator(text: str):
+        """문자 오프셋 → (줄 번호, UTF-8 바이트 오프셋)"""
+        newlines = [index for index, char in enumerate(text) if char == '\n']
+        widths = [0]
+        for char in text:
+            widths.append(widths[-1] + len(char.encode('utf-8')))
+        return lambda offset: (bisect.bisect_left(newlines, offset) + 1, widths[offset])
+
+    @classmethod
+    def _rust_items(cls, text: str) -> List[Dict[str, Any]]:
+        tokens = rust_tokens(text)
+        braces = _matching_braces(tokens)
+        locate = cls._locator(text)
+        items = []
+        for position, (token, _, _) in enumerate(tokens):
+            if token not in RUST_KEYWORDS:
+                continue
+            start = position
+            while start > 0 and (tokens[start - 1][0] in _QUALIFIERS or tokens[start - 1][0] == '"' and
+                                 start > 1 and tokens[start - 2][0] == 'extern'):
+                start -= 1
+            if start > 0 and tokens[start - 1][0] == ')':  # pub(crate), pub(super)
+                opening = next((index for index in range(start - 1, -1, -1) if tokens[index][0] == '('), None)
+                if opening and tokens[opening - 1][0] == 'pub':
+                    start = opening - 1
+            if (tokens[start - 1][0] if start > 0 else None) not in _ITEM_START:
+                continue
+            body = _header_end(tokens, position)
+            if body is None or body not in braces:
+                continue
+            header = tokens[position + 1:body]
+            if token == 'impl':
+                name, trait = _impl_names(header)
+            else:
+                name = header[0][0] if header and _IDENT.fullmatch(header[0][0]) else None
+                trait = None
+            if not name:
+                continue
+            (start_line, start_byte), (end_line, _) = locate(tokens[start][1]), locate(tokens[braces[body]][1])
+            items.append({'kind': token, 'name': name, 'trait': trait,
+                          'start_line': start_line, 'end_line': end_line,
+                          'start_byte': start_byte, 'end_byte': locate(tokens[braces[body]][2])[1]})
+
+        for item in items:
+            parents = [other for other in items if other is not item and
+                       other['start_byte'] <= item['start_byte'] and item['end_byte'] <= other['end_byte']]
+            parent = min(parents, key=lambda other: other['end_byte'] - other['start_byte'], default=None)
+            item['parent'] = items.index(parent) if parent else None
+        for item in items:
+            path, parent = [item['name']], item['parent']
+            while parent is not None:
+                path.insert(0, items[parent]['name'])
+                parent = items[parent]['parent']
+            item['qualified_name'] = '::'.join(path)
+        return items
+
+    @classmethod
+    def _split_items(cls, text: str, language: Optional[str]) -> List[Dict[str, Any]]:
+        from utils.features import split_functions
+
+        line_bytes = [0]
+        for line in text.splitlines(keepends=True):
+            line_bytes.append(line_bytes[-1] + len(line.encode('utf-8')))
+        return [
+            {'kind': FUNCTION, 'name': function['name'], 'trait': None, 'qualified_name': function['name'],
+             'start_line': function['start_line'], 'end_line': function['end_line'],
+             'start_byte': line_bytes[function['start_line'] - 1],
+             'end_byte': line_bytes[min(function['end_line'], len(line_bytes) - 1)], 'parent': None}
+            for function in split_functions(text, language) if function['name'] != '<module>'
+        ]
+
+    def functions(self) -> List[Dict[str, Any]]:
+        return [item for item in self.items if item['kind'] == FUNCTION]
+
+    def item_at(self, line: int, kinds=(FUNCTION,)) -> Optional[Dict[str, Any]]:
+        """줄을 포함하는 가장 안쪽 항목"""
+        found = [item for item in self.items
+                 if item['kind'] in kinds and item['start_line'] <= line <= item['end_line']]
+        return min(found, key=lambda item: item['end_line'] - item['start_line'], default=None)
+
+    def functions_for_span(self, start: int, end: int) -> List[Dict[str, Any]]:
+        """줄 범위가 붙는 함수 (함수 줄의 절반 이상이 범위 안이거나 범위가 함수 안에 들어 있음)
+
+        범위가 함수 안에 들어 있으면 가장 안쪽 함수 하나, 아니면 절반 이상 덮인 함수들 (중첩 함수는 바깥 것만)
+        """
+        inner = [item for item in self.functions() if item['start_line'] <= start and end <= item['end_line']]
+        if inner:
+            return [min(inner, key=lambda item: item['end_line'] - item['start_line'])]
+        covered = []
+        for item in self.functions():
+            overlap = min(end, item['end_line']) - max(start, item['start_line']) + 1
+            if overlap >= SPAN_COVERAGE * (item['end_line'] - item['start_line'] + 1):
+                covered.append(item)
+        return [item for item in covered if not any(other is not item and other['start_line'] <= item['start_line']
+                                                    and item['end_line'] <= other['end_line'] for other in covered)]
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {'language': self.language, 'backend': self.backend, 'lines': self.line_count, 'items': self.items}
+
+
+def calculate_function_scores(findings: List[Dict[str, Any]], ground_truth: Optional[Dict[str, Any]],
+                              index: SampleIndex, taxonomy=None) -> Optional[Dict[str, Any]]:
+    """함수 단위 (함수, 계열) 채점 (줄 범위가 없는 샘플이나 함수를 못 찾은 샘플은 None)"""
+    from utils.comment_noise import label_spans
+    from utils.taxonomy import AlgorithmTaxonomy
+
+    spans = label_spans(ground_truth) if ground_truth else []
+    if not spans or not index.functions():
+        return None
+
+    taxonomy = taxonomy or AlgorithmTaxonomy()
+
+    def attach(label, start, end):
+        family = taxonomy.resolve(label)[1] or str(label)
+        return {(function['qualified_name'], family) for function in index.functions_for_span(start, end)}
+
+    # 정답 범위 하나가 함수 여러 개(impl 블록 전체 등)에 걸치면 그중 하나만 맞혀도 그 범위를 찾은 것으로 셈
+    regions = [attach(label, start, end) for label, (start, end) in spans]
+    regions = [region for region in regions if region]
+    expected = set().union(*regions) if regions else set()
+    predicted = set()
+    for finding in findings or []:
+        if finding.get('span') and finding.get('algorithm'):
+            predicted |= attach(finding['algorithm'], *finding['span'][:2])
+
+    correct = predicted & expected
+    matched = sum(1 for region in regions if region & predicted)
+    precision = len(correct) / len(predicted) if predicted else 0.0
+    recall = matched / len(regions) if regions else 0.0
+
+    functions: Dict[str, Dict[str, List[str]]] = {}
+    for key, found in (('expected', expected), ('predicted', predicted)):
+        for name, family in sorted(found):
+            functions.setdefault(name, {'expected': [], 'predicted': []})[key].append(family)
+    return {
+        'backend': index.backend,
+        'functions_indexed': len(index.functions()),
+        'expected': len(regions),
+        'matched': matched,
+        'predicted': len(predicted),
+        'correct': len(correct),
+        'precision': precision,
+        'recall': recall,
+        'f1': 2 * precision * recall / (precision + recall) if precision + recall else 0.0,
+        'functions': functions
+    }
+
+
+def summarize_function_scores(scores: Iterable[Optional[Dict[str, Any]]]) -> Dict[str, Any]:
+    """샘플별 함수 단위 점수 → 개수를 합친 micro precision / recall / f1"""
+    summary = {'samples': 0, 'expected': 0, 'matched': 0, 'predicted': 0, 'correct': 0}
+    for score in scores:
+        if score:
+            summary['samples'] += 1
+            for key in ('expected', 'matched', 'predicted', 'correct'):
+                summary[key] += score[key]
+    precision = summary['correct'] / summary['predicted'] if summary['predicted'] else 0.0
+    recall = summary['matched'] / summary['expected'] if summary['expected'] else 0.0
+    summary.update({'precision': precision, 'recall': recall,
+                    'f1': 2 * precision * recall / (precision + recall) if precision + recall else 0.0})
+    return summary
+
+
+def main():
+    parser = argparse.ArgumentParser(description='샘플 함수 단위 색인')
+    parser.add_argument('path', help='샘플 파일')
+    parser.add_argument('--json', action='store_true', help='JSON으로 출력')
+    parser.add_argument('--backend', choices=BACKENDS, default=BACKEND_AUTO,
+                        help='파싱 백엔드 (기본: tree-sitter 문법이 있으면 사용)')
+    args = parser.parse_args()
+
+    index = SampleIndex.from_file(args.path, args.backend)
+    if args.json:
+        print(json.dumps(index.to_dict(), indent=2, ensure_ascii=False))
+        return
+    print(f"📑 {args.path} ({index.language or 'unknown'}, {index.backend}, {index.line_count}줄, "
+          f"항목 {len(index.items)}개)")
+    for item in index.items:
+        depth, parent = 0, item['parent']
+        while parent is not None:
+            depth, parent = depth + 1, index.items[parent]['parent']
+        trait = f" ({item['trait']})" if item['trait'] else ''
+        print(f"  {'  ' * depth}{item['kind']:<5} {item['qualified_name']}{trait}  "
+              f"{item['start_line']}-{item['end_line']}  [{item['start_byte']}, {item['end_byte']})")
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,48 @@
+// Identity record the responder presents, endorsed by the ${domain} operator
+#[derive(Clone)]
+pub struct ${Credential} {
+    pub subject: Vec<u8>,
+    pub share: (u64, u64),
+    pub endorsement: u64,
+}
+
+pub struct ${CredentialVerifier} {
+    modulus: u64,
+    public_exponent: u64,
+}
+
+impl ${CredentialVerifier} {
+    pub fn new(modulus: u64, public_exponent: u64) -> Self {
+        ${CredentialVerifier} { modulus, public_exponent }
+    }
+
+    pub fn endorsed_bytes(credential: &${Credential}) -> Vec<u8> {
+        let mut bytes = credential.subject.clone();
+        bytes.extend_from_slice(&credential.share.0.to_be_bytes());
+        bytes.extend_from_slice(&credential.share.1.to_be_bytes());
+        bytes
+    }
+
+    pub fn accepts(&self, credential: &${Credential}) -> bool {
+        let fingerprint = ${Digest}::fingerprint(&Self::endorsed_bytes(credential));
+        let mut head = [0u8; 8];
+        head.copy_from_slice(&fingerprint[..8]);
+        let expected = u64::from_be_bytes(head) % self.modulus;
+        Self::power(credential.endorsement, self.public_exponent, self.modulus) == expected
+    }
+
+    fn power(base: u64, exponent: u64, modulus: u64) -> u64 {
+        let mut result = 1u128;
+        let mut square = (base % modulus) as u128;
+        let mut remaining = exponent;
+        while remaining > 0 {
+            if remaining & 1 == 1 {
+                result = result * square % modulus as u128;
+            }
+            square = square * square % modulus as u128;
+            remaining >>= 1;
+        }
+        result as u64
+    }
+}
+
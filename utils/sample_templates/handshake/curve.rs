@@ -0,0 +1,97 @@
+const ${FIELD_LIMIT}: u64 = 0x1fff_ffff_ffff_ffff;
+const ${SLOPE_TERM}: u64 = ${FIELD_LIMIT} - 3;
+const ${OFFSET_TERM}: u64 = ${curve_b};
+const ${ANCHOR}: (u64, u64) = (${base_x}, ${base_y});
+
+type ${Coordinate} = Option<(u64, u64)>;
+
+// Both ends of a ${domain} link combine their private scalars through this exchange
+pub struct ${CurveAgreement} {
+    scalar: u64,
+}
+
+impl ${CurveAgreement} {
+    pub fn new(scalar: u64) -> Self {
+        ${CurveAgreement} { scalar }
+    }
+
+    pub fn share(&self) -> (u64, u64) {
+        Self::scale(self.scalar, Some(${ANCHOR})).expect("scalar must not annihilate the anchor")
+    }
+
+    pub fn combine(&self, peer: (u64, u64)) -> [u8; 8] {
+        let joint = Self::scale(self.scalar, Some(peer)).expect("peer share must be a valid element");
+        joint.0.to_be_bytes()
+    }
+
+    pub fn is_valid(share: (u64, u64)) -> bool {
+        let (x, y) = share;
+        if x >= ${FIELD_LIMIT} || y >= ${FIELD_LIMIT} {
+            return false;
+        }
+        let cubic = Self::mul(Self::mul(x, x), x);
+        let rhs = Self::add(Self::add(cubic, Self::mul(${SLOPE_TERM}, x)), ${OFFSET_TERM});
+        Self::mul(y, y) == rhs
+    }
+
+    fn add(a: u64, b: u64) -> u64 {
+        ((a as u128 + b as u128) % ${FIELD_LIMIT} as u128) as u64
+    }
+
+    fn sub(a: u64, b: u64) -> u64 {
+        Self::add(a, ${FIELD_LIMIT} - b % ${FIELD_LIMIT})
+    }
+
+    fn mul(a: u64, b: u64) -> u64 {
+        ((a as u128 * b as u128) % ${FIELD_LIMIT} as u128) as u64
+    }
+
+    fn invert(a: u64) -> u64 {
+        let mut result = 1u64;
+        let mut base = a % ${FIELD_LIMIT};
+        let mut exponent = ${FIELD_LIMIT} - 2;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = Self::mul(result, base);
+            }
+            base = Self::mul(base, base);
+            exponent >>= 1;
+        }
+        result
+    }
+
+    fn join(p: ${Coordinate}, q: ${Coordinate}) -> ${Coordinate} {
+        let ((x1, y1), (x2, y2)) = match (p, q) {
+            (None, _) => return q,
+            (_, None) => return p,
+            (Some(a), Some(b)) => (a, b),
+        };
+
+        if x1 == x2 && Self::add(y1, y2) == 0 {
+            return None;
+        }
+
+        let slope = if x1 == x2 {
+            let numerator = Self::add(Self::mul(3, Self::mul(x1, x1)), ${SLOPE_TERM});
+            Self::mul(numerator, Self::invert(Self::mul(2, y1)))
+        } else {
+            Self::mul(Self::sub(y2, y1), Self::invert(Self::sub(x2, x1)))
+        };
+
+        let x3 = Self::sub(Self::sub(Self::mul(slope, slope), x1), x2);
+        let y3 = Self::sub(Self::mul(slope, Self::sub(x1, x3)), y1);
+        Some((x3, y3))
+    }
+
+    fn scale(scalar: u64, point: ${Coordinate}) -> ${Coordinate} {
+        let mut result = None;
+        for bit in (0..64).rev() {
+            result = Self::join(result, result);
+            if (scalar >> bit) & 1 == 1 {
+                result = Self::join(result, point);
+            }
+        }
+        result
+    }
+}
+
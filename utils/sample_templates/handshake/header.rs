@@ -0,0 +1,3 @@
+// ${title}
+// ${subtitle}
+
@@ -0,0 +1,51 @@
+// Turns the agreed ${domain} session value into record keys
+pub struct ${KeySchedule};
+
+impl ${KeySchedule} {
+    const PAD_WIDTH: usize = 64;
+
+    pub fn keyed_fingerprint(key: &[u8], message: &[u8]) -> [u8; 32] {
+        let mut padded = [0u8; Self::PAD_WIDTH];
+        if key.len() > Self::PAD_WIDTH {
+            padded[..32].copy_from_slice(&${Digest}::fingerprint(key));
+        } else {
+            padded[..key.len()].copy_from_slice(key);
+        }
+
+        let inner_pad: Vec<u8> = padded.iter().map(|b| b ^ 0x36).collect();
+        let outer_pad: Vec<u8> = padded.iter().map(|b| b ^ 0x5c).collect();
+
+        let mut inner = ${Digest}::new();
+        inner.absorb(&inner_pad);
+        inner.absorb(message);
+        let inner_value = inner.finish();
+
+        let mut outer = ${Digest}::new();
+        outer.absorb(&outer_pad);
+        outer.absorb(&inner_value);
+        outer.finish()
+    }
+
+    pub fn condense(salt: &[u8], material: &[u8]) -> [u8; 32] {
+        Self::keyed_fingerprint(salt, material)
+    }
+
+    pub fn stretch(condensed: &[u8], context: &[u8], length: usize) -> Vec<u8> {
+        let mut output = Vec::with_capacity(length);
+        let mut previous: Vec<u8> = Vec::new();
+        let mut counter = 1u8;
+
+        while output.len() < length {
+            let mut input = previous.clone();
+            input.extend_from_slice(context);
+            input.push(counter);
+            previous = Self::keyed_fingerprint(condensed, &input).to_vec();
+            output.extend_from_slice(&previous);
+            counter += 1;
+        }
+
+        output.truncate(length);
+        output
+    }
+}
+
@@ -0,0 +1,47 @@
+const ${CONTEXT_LABEL}: &[u8] = b"${context_label}";
+const ${OPERATOR_MODULUS}: u64 = ${rsa_modulus};
+const ${OPERATOR_EXPONENT}: u64 = ${rsa_exponent};
+const ${INITIATOR_SCALAR}: u64 = ${initiator_scalar};
+const ${RESPONDER_SCALAR}: u64 = ${responder_scalar};
+const ${INITIATOR_RANDOM}: [u8; 32] = [${initiator_random}];
+const ${RESPONDER_RANDOM}: [u8; 32] = [${responder_random}];
+
+fn responder_credential() -> ${Credential} {
+    ${Credential} {
+        subject: b"${subject}".to_vec(),
+        share: (${responder_share_x}, ${responder_share_y}),
+        endorsement: ${endorsement},
+    }
+}
+
+// Runs both sides of the ${domain} link setup and returns what the responder recovers
+pub fn establish_link(payload: &[u8]) -> Option<Vec<u8>> {
+    let initiator = ${SessionNegotiator}::new(${INITIATOR_SCALAR}, ${INITIATOR_RANDOM});
+    let responder = ${SessionNegotiator}::new(${RESPONDER_SCALAR}, ${RESPONDER_RANDOM});
+
+    let credential = responder_credential();
+    let verifier = ${CredentialVerifier}::new(${OPERATOR_MODULUS}, ${OPERATOR_EXPONENT});
+    if !verifier.accepts(&credential) || !${CurveAgreement}::is_valid(credential.share) {
+        return None;
+    }
+
+    let initiator_keys = initiator.derive(credential.share, &initiator.random, &responder.random);
+    let responder_keys = responder.derive(initiator.share(), &initiator.random, &responder.random);
+
+    let sealed = ${RecordSealer}::new(&initiator_keys.key).seal(&initiator_keys.nonce, b"${record_header}", payload);
+    ${RecordSealer}::new(&responder_keys.key).open(&responder_keys.nonce, b"${record_header}", &sealed)
+}
+
+fn main() {
+    println!("${title} starting...");
+
+    let payload = b"${payload}";
+    match establish_link(payload) {
+        Some(recovered) if recovered == payload => {
+            println!("Link established, first record delivered ({} bytes)", recovered.len());
+        }
+        Some(_) => println!("Link established but record was corrupted"),
+        None => println!("Link setup rejected"),
+    }
+}
+
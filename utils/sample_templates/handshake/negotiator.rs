@@ -0,0 +1,38 @@
+// Keys one side of the ${domain} link uses for its first record
+pub struct ${TrafficKeys} {
+    pub key: [u8; 16],
+    pub nonce: [u8; 12],
+}
+
+pub struct ${SessionNegotiator} {
+    exchange: ${CurveAgreement},
+    pub random: [u8; 32],
+}
+
+impl ${SessionNegotiator} {
+    pub fn new(scalar: u64, random: [u8; 32]) -> Self {
+        ${SessionNegotiator} {
+            exchange: ${CurveAgreement}::new(scalar),
+            random,
+        }
+    }
+
+    pub fn share(&self) -> (u64, u64) {
+        self.exchange.share()
+    }
+
+    pub fn derive(&self, peer_share: (u64, u64), initiator_random: &[u8; 32], responder_random: &[u8; 32]) -> ${TrafficKeys} {
+        let joint = self.exchange.combine(peer_share);
+
+        let mut salt = initiator_random.to_vec();
+        salt.extend_from_slice(responder_random);
+        let condensed = ${KeySchedule}::condense(&salt, &joint);
+        let material = ${KeySchedule}::stretch(&condensed, ${CONTEXT_LABEL}, 28);
+
+        let mut keys = ${TrafficKeys} { key: [0u8; 16], nonce: [0u8; 12] };
+        keys.key.copy_from_slice(&material[..16]);
+        keys.nonce.copy_from_slice(&material[16..28]);
+        keys
+    }
+}
+
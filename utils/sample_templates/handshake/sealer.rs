@@ -0,0 +1,180 @@
+const ${BYTE_TABLE}: [u8; 256] = [
+    0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
+    0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4, 0x72, 0xc0,
+    0xb7, 0xfd, 0x93, 0x26, 0x36, 0x3f, 0xf7, 0xcc, 0x34, 0xa5, 0xe5, 0xf1, 0x71, 0xd8, 0x31, 0x15,
+    0x04, 0xc7, 0x23, 0xc3, 0x18, 0x96, 0x05, 0x9a, 0x07, 0x12, 0x80, 0xe2, 0xeb, 0x27, 0xb2, 0x75,
+    0x09, 0x83, 0x2c, 0x1a, 0x1b, 0x6e, 0x5a, 0xa0, 0x52, 0x3b, 0xd6, 0xb3, 0x29, 0xe3, 0x2f, 0x84,
+    0x53, 0xd1, 0x00, 0xed, 0x20, 0xfc, 0xb1, 0x5b, 0x6a, 0xcb, 0xbe, 0x39, 0x4a, 0x4c, 0x58, 0xcf,
+    0xd0, 0xef, 0xaa, 0xfb, 0x43, 0x4d, 0x33, 0x85, 0x45, 0xf9, 0x02, 0x7f, 0x50, 0x3c, 0x9f, 0xa8,
+    0x51, 0xa3, 0x40, 0x8f, 0x92, 0x9d, 0x38, 0xf5, 0xbc, 0xb6, 0xda, 0x21, 0x10, 0xff, 0xf3, 0xd2,
+    0xcd, 0x0c, 0x13, 0xec, 0x5f, 0x97, 0x44, 0x17, 0xc4, 0xa7, 0x7e, 0x3d, 0x64, 0x5d, 0x19, 0x73,
+    0x60, 0x81, 0x4f, 0xdc, 0x22, 0x2a, 0x90, 0x88, 0x46, 0xee, 0xb8, 0x14, 0xde, 0x5e, 0x0b, 0xdb,
+    0xe0, 0x32, 0x3a, 0x0a, 0x49, 0x06, 0x24, 0x5c, 0xc2, 0xd3, 0xac, 0x62, 0x91, 0x95, 0xe4, 0x79,
+    0xe7, 0xc8, 0x37, 0x6d, 0x8d, 0xd5, 0x4e, 0xa9, 0x6c, 0x56, 0xf4, 0xea, 0x65, 0x7a, 0xae, 0x08,
+    0xba, 0x78, 0x25, 0x2e, 0x1c, 0xa6, 0xb4, 0xc6, 0xe8, 0xdd, 0x74, 0x1f, 0x4b, 0xbd, 0x8b, 0x8a,
+    0x70, 0x3e, 0xb5, 0x66, 0x48, 0x03, 0xf6, 0x0e, 0x61, 0x35, 0x57, 0xb9, 0x86, 0xc1, 0x1d, 0x9e,
+    0xe1, 0xf8, 0x98, 0x11, 0x69, 0xd9, 0x8e, 0x94, 0x9b, 0x1e, 0x87, 0xe9, 0xce, 0x55, 0x28, 0xdf,
+    0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb, 0x16,
+];
+
+const ${STEP_BIAS}: [u8; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+
+// Protects ${domain} records after the link is established
+pub struct ${RecordSealer} {
+    round_keys: [[u8; 16]; 11],
+    mixing_key: u128,
+}
+
+impl ${RecordSealer} {
+    pub fn new(key: &[u8; 16]) -> Self {
+        let mut sealer = ${RecordSealer} {
+            round_keys: Self::schedule(key),
+            mixing_key: 0,
+        };
+        sealer.mixing_key = u128::from_be_bytes(sealer.scramble([0u8; 16]));
+        sealer
+    }
+
+    pub fn seal(&self, nonce: &[u8; 12], header: &[u8], payload: &[u8]) -> Vec<u8> {
+        let mut sealed = self.counter_stream(nonce, payload);
+        let check = self.checksum(header, &sealed) ^ self.check_mask(nonce);
+        sealed.extend_from_slice(&check.to_be_bytes());
+        sealed
+    }
+
+    pub fn open(&self, nonce: &[u8; 12], header: &[u8], sealed: &[u8]) -> Option<Vec<u8>> {
+        if sealed.len() < 16 {
+            return None;
+        }
+        let (body, check) = sealed.split_at(sealed.len() - 16);
+        let expected = (self.checksum(header, body) ^ self.check_mask(nonce)).to_be_bytes();
+        let difference = expected.iter().zip(check).fold(0u8, |acc, (a, b)| acc | (a ^ b));
+        if difference != 0 {
+            return None;
+        }
+        Some(self.counter_stream(nonce, body))
+    }
+
+    fn schedule(key: &[u8; 16]) -> [[u8; 16]; 11] {
+        let mut words = [[0u8; 4]; 44];
+        for (i, word) in words.iter_mut().take(4).enumerate() {
+            word.copy_from_slice(&key[i * 4..i * 4 + 4]);
+        }
+        for i in 4..44 {
+            let mut temp = words[i - 1];
+            if i % 4 == 0 {
+                temp = [
+                    ${BYTE_TABLE}[temp[1] as usize] ^ ${STEP_BIAS}[i / 4 - 1],
+                    ${BYTE_TABLE}[temp[2] as usize],
+                    ${BYTE_TABLE}[temp[3] as usize],
+                    ${BYTE_TABLE}[temp[0] as usize],
+                ];
+            }
+            for j in 0..4 {
+                words[i][j] = words[i - 4][j] ^ temp[j];
+            }
+        }
+
+        let mut round_keys = [[0u8; 16]; 11];
+        for (round, round_key) in round_keys.iter_mut().enumerate() {
+            for column in 0..4 {
+                round_key[column * 4..column * 4 + 4].copy_from_slice(&words[round * 4 + column]);
+            }
+        }
+        round_keys
+    }
+
+    fn scramble(&self, block: [u8; 16]) -> [u8; 16] {
+        let mut state = block;
+        Self::blend(&mut state, &self.round_keys[0]);
+        for round in 1..11 {
+            for byte in state.iter_mut() {
+                *byte = ${BYTE_TABLE}[*byte as usize];
+            }
+            Self::rotate_rows(&mut state);
+            if round < 10 {
+                Self::mix_columns(&mut state);
+            }
+            Self::blend(&mut state, &self.round_keys[round]);
+        }
+        state
+    }
+
+    fn blend(state: &mut [u8; 16], round_key: &[u8; 16]) {
+        for (byte, key_byte) in state.iter_mut().zip(round_key.iter()) {
+            *byte ^= key_byte;
+        }
+    }
+
+    fn rotate_rows(state: &mut [u8; 16]) {
+        let original = *state;
+        for column in 0..4 {
+            for row in 0..4 {
+                state[column * 4 + row] = original[((column + row) % 4) * 4 + row];
+            }
+        }
+    }
+
+    fn double(value: u8) -> u8 {
+        (value << 1) ^ if value & 0x80 != 0 { 0x1b } else { 0x00 }
+    }
+
+    fn mix_columns(state: &mut [u8; 16]) {
+        for column in state.chunks_mut(4) {
+            let [a, b, c, d] = [column[0], column[1], column[2], column[3]];
+            let total = a ^ b ^ c ^ d;
+            column[0] = a ^ total ^ Self::double(a ^ b);
+            column[1] = b ^ total ^ Self::double(b ^ c);
+            column[2] = c ^ total ^ Self::double(c ^ d);
+            column[3] = d ^ total ^ Self::double(d ^ a);
+        }
+    }
+
+    fn counter_block(nonce: &[u8; 12], counter: u32) -> [u8; 16] {
+        let mut block = [0u8; 16];
+        block[..12].copy_from_slice(nonce);
+        block[12..].copy_from_slice(&counter.to_be_bytes());
+        block
+    }
+
+    fn counter_stream(&self, nonce: &[u8; 12], data: &[u8]) -> Vec<u8> {
+        let mut output = Vec::with_capacity(data.len());
+        for (index, chunk) in data.chunks(16).enumerate() {
+            let pad = self.scramble(Self::counter_block(nonce, index as u32 + 2));
+            output.extend(chunk.iter().zip(pad.iter()).map(|(a, b)| a ^ b));
+        }
+        output
+    }
+
+    fn check_mask(&self, nonce: &[u8; 12]) -> u128 {
+        u128::from_be_bytes(self.scramble(Self::counter_block(nonce, 1)))
+    }
+
+    fn field_product(x: u128, y: u128) -> u128 {
+        let mut product = 0u128;
+        let mut addend = y;
+        for bit in 0..128 {
+            if (x >> (127 - bit)) & 1 == 1 {
+                product ^= addend;
+            }
+            addend = if addend & 1 == 1 {
+                (addend >> 1) ^ (0xe1u128 << 120)
+            } else {
+                addend >> 1
+            };
+        }
+        product
+    }
+
+    fn checksum(&self, header: &[u8], body: &[u8]) -> u128 {
+        let mut accumulator = 0u128;
+        for chunk in header.chunks(16).chain(body.chunks(16)) {
+            let mut block = [0u8; 16];
+            block[..chunk.len()].copy_from_slice(chunk);
+            accumulator = Self::field_product(accumulator ^ u128::from_be_bytes(block), self.mixing_key);
+        }
+        let lengths = ((header.len() as u128 * 8) << 64) | (body.len() as u128 * 8);
+        Self::field_product(accumulator ^ lengths, self.mixing_key)
+    }
+}
+
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(text: &str) -> Vec<u8> {
+        (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn digest_known_answer() {
+        // FIPS 180-2 Appendix B.1, message "abc"
+        assert_eq!(
+            ${Digest}::fingerprint(b"abc").to_vec(),
+            hex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+    }
+
+    #[test]
+    fn keyed_digest_known_answer() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            ${KeySchedule}::keyed_fingerprint(b"Jefe", b"what do ya want for nothing?").to_vec(),
+            hex("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+    }
+
+    #[test]
+    fn key_expansion_known_answer() {
+        // RFC 5869 test case 1
+        let condensed = ${KeySchedule}::condense(&hex("000102030405060708090a0b0c"), &hex("0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b"));
+        assert_eq!(
+            condensed.to_vec(),
+            hex("077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5")
+        );
+        assert_eq!(
+            ${KeySchedule}::stretch(&condensed, &hex("f0f1f2f3f4f5f6f7f8f9"), 42),
+            hex("3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865")
+        );
+    }
+
+    #[test]
+    fn record_sealer_known_answer() {
+        // GCM specification test case 4
+        let mut key = [0u8; 16];
+        key.copy_from_slice(&hex("feffe9928665731c6d6a8f9467308308"));
+        let mut nonce = [0u8; 12];
+        nonce.copy_from_slice(&hex("cafebabefacedbaddecaf888"));
+        let header = hex("feedfacedeadbeeffeedfacedeadbeefabaddad2");
+        let payload = hex(concat!(
+            "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72",
+            "1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39"
+        ));
+
+        let sealed = ${RecordSealer}::new(&key).seal(&nonce, &header, &payload);
+        assert_eq!(
+            sealed,
+            hex(concat!(
+                "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e",
+                "21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091",
+                "5bc94fbc3221a5db94fae95ae7121a47"
+            ))
+        );
+    }
+
+    #[test]
+    fn tampered_record_rejected() {
+        let sealer = ${RecordSealer}::new(&[7u8; 16]);
+        let nonce = [9u8; 12];
+        let mut sealed = sealer.seal(&nonce, b"hdr", b"telemetry frame");
+        assert_eq!(sealer.open(&nonce, b"hdr", &sealed).unwrap(), b"telemetry frame".to_vec());
+        sealed[0] ^= 1;
+        assert!(sealer.open(&nonce, b"hdr", &sealed).is_none());
+    }
+
+    #[test]
+    fn exchange_agrees() {
+        let initiator = ${CurveAgreement}::new(${INITIATOR_SCALAR});
+        let responder = ${CurveAgreement}::new(${RESPONDER_SCALAR});
+        assert!(${CurveAgreement}::is_valid(initiator.share()));
+        assert_eq!(responder.share(), responder_credential().share);
+        assert_eq!(initiator.combine(responder.share()), responder.combine(initiator.share()));
+    }
+
+    #[test]
+    fn credential_endorsement() {
+        let verifier = ${CredentialVerifier}::new(${OPERATOR_MODULUS}, ${OPERATOR_EXPONENT});
+        let credential = responder_credential();
+        assert!(verifier.accepts(&credential));
+
+        let mut forged = credential.clone();
+        forged.subject.push(b'!');
+        assert!(!verifier.accepts(&forged));
+    }
+
+    #[test]
+    fn link_round_trip() {
+        let payload = b"${payload}";
+        assert_eq!(establish_link(payload).unwrap(), payload.to_vec());
+    }
+}
@@ -0,0 +1,58 @@
+#[derive(Debug, PartialEq)]
+pub enum ${Rejection} {
+    Truncated,
+    StampMismatch,
+}
+
+// ${domain} link: wrapped ${secret_item}, stream-protected batches, stamped for integrity
+pub struct ${Channel} {
+    wrap: ${TripleWrap},
+    stream_secret: [u8; 16],
+}
+
+impl ${Channel} {
+    pub fn new(bundle: &[[u8; 8]; 3], stream_secret: [u8; 16]) -> Self {
+        ${Channel} { wrap: ${TripleWrap}::new(bundle), stream_secret }
+    }
+
+    pub fn wrap_secret(&self, secret: &[u8]) -> Vec<u8> {
+        self.wrap.encrypt(secret)
+    }
+
+    pub fn unwrap_secret(&self, wrapped: &[u8]) -> Vec<u8> {
+        self.wrap.decrypt(wrapped)
+    }
+
+    fn batch_stream(&self, sequence: u32) -> ${Keystream} {
+        let mut key = self.stream_secret.to_vec();
+        key.extend_from_slice(&sequence.to_be_bytes());
+        ${Keystream}::new(&key)
+    }
+
+    // sequence (4) || stamp (16) || protected batch
+    pub fn seal_batch(&self, sequence: u32, batch: &[u8]) -> Vec<u8> {
+        let mut stamped = batch.to_vec();
+        stamped.extend_from_slice(&self.stream_secret);
+        let stamp = ${Checksum}::stamp(&stamped);
+
+        let mut frame = sequence.to_be_bytes().to_vec();
+        frame.extend_from_slice(&stamp);
+        frame.extend(self.batch_stream(sequence).apply(batch));
+        frame
+    }
+
+    pub fn open_batch(&self, frame: &[u8]) -> Result<Vec<u8>, ${Rejection}> {
+        if frame.len() < 20 {
+            return Err(${Rejection}::Truncated);
+        }
+        let sequence = u32::from_be_bytes([frame[0], frame[1], frame[2], frame[3]]);
+        let batch = self.batch_stream(sequence).apply(&frame[20..]);
+
+        let mut stamped = batch.clone();
+        stamped.extend_from_slice(&self.stream_secret);
+        if ${Checksum}::stamp(&stamped)[..] != frame[4..20] {
+            return Err(${Rejection}::StampMismatch);
+        }
+        Ok(batch)
+    }
+}
@@ -0,0 +1,10 @@
+// ${title}
+// ${subtitle}
+
+// Key bundle for the ${domain} link (${keying_note})
+const ${BUNDLE_KEYS}: [[u8; 8]; 3] = [
+    [${bundle_key_1}],
+    [${bundle_key_2}],
+    [${bundle_key_3}],
+];
+const ${STREAM_SECRET}: [u8; 16] = [${stream_secret}];
@@ -0,0 +1,34 @@
+// Byte permutation keystream for ${domain} payloads
+pub struct ${Keystream} {
+    table: [u8; 256],
+    i: u8,
+    j: u8,
+}
+
+impl ${Keystream} {
+    pub fn new(key: &[u8]) -> Self {
+        let mut table = [0u8; 256];
+        for (index, slot) in table.iter_mut().enumerate() {
+            *slot = index as u8;
+        }
+
+        let mut j: u8 = 0;
+        for i in 0..256 {
+            j = j.wrapping_add(table[i]).wrapping_add(key[i % key.len()]);
+            table.swap(i, j as usize);
+        }
+        ${Keystream} { table, i: 0, j: 0 }
+    }
+
+    pub fn next_byte(&mut self) -> u8 {
+        self.i = self.i.wrapping_add(1);
+        self.j = self.j.wrapping_add(self.table[self.i as usize]);
+        self.table.swap(self.i as usize, self.j as usize);
+        let index = self.table[self.i as usize].wrapping_add(self.table[self.j as usize]);
+        self.table[index as usize]
+    }
+
+    pub fn apply(&mut self, data: &[u8]) -> Vec<u8> {
+        data.iter().map(|byte| byte ^ self.next_byte()).collect()
+    }
+}
@@ -0,0 +1,13 @@
+fn main() {
+    println!("${title} starting...");
+
+    let channel = ${Channel}::new(&${BUNDLE_KEYS}, ${STREAM_SECRET});
+    let wrapped = channel.wrap_secret(b"${secret_value}");
+    println!("Wrapped ${secret_item}: {} bytes", wrapped.len());
+
+    let frame = channel.seal_batch(1, b"${payload}");
+    match channel.open_batch(&frame) {
+        Ok(batch) => println!("Batch delivered ({} bytes)", batch.len()),
+        Err(reason) => println!("Batch rejected: {:?}", reason),
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(text: &str) -> Vec<u8> {
+        (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn block_codec_known_answer() {
+        // Classic worked example, key 133457799BBCDFF1
+        let codec = ${BlockCodec}::new(&[0x13, 0x34, 0x57, 0x79, 0x9b, 0xbc, 0xdf, 0xf1]);
+        assert_eq!(codec.encrypt_block(0x0123456789abcdef), 0x85e813540f0ab405);
+        assert_eq!(codec.decrypt_block(0x85e813540f0ab405), 0x0123456789abcdef);
+    }
+
+    #[test]
+    fn triple_wrap_known_answer() {
+        // NIST SP 800-67 example, keying option 1
+        let keys = [
+            [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef],
+            [0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0x01],
+            [0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0x01, 0x23],
+        ];
+        let wrap = ${TripleWrap}::new(&keys);
+        let sealed = wrap.encrypt(b"The qufck brown fox jump");
+        assert_eq!(sealed, hex("a826fd8ce53b855fcce21c8112256fe668d5c05dd9b6b900"));
+        assert_eq!(wrap.decrypt(&sealed), b"The qufck brown fox jump".to_vec());
+
+        // Keying option 2 (K3 = K1)
+        let two_key = ${TripleWrap}::new(&[keys[0], keys[1], keys[0]]);
+        assert_eq!(
+            two_key.encrypt(b"The qufck brown fox jump"),
+            hex("c44862f70cf2fbdc9077d0909fa91b884cabd61fc58e0cbb")
+        );
+    }
+
+    #[test]
+    fn identical_bundle_keys_collapse_to_single_pass() {
+        let key = [0x13, 0x34, 0x57, 0x79, 0x9b, 0xbc, 0xdf, 0xf1];
+        let wrap = ${TripleWrap}::new(&[key, key, key]);
+        assert_eq!(wrap.encrypt_block(0x0123456789abcdef), ${BlockCodec}::new(&key).encrypt_block(0x0123456789abcdef));
+    }
+
+    #[test]
+    fn keystream_known_answer() {
+        // RFC 6229 40-bit key 0102030405, offset 0
+        let mut stream = ${Keystream}::new(&[0x01, 0x02, 0x03, 0x04, 0x05]);
+        assert_eq!(stream.apply(&[0u8; 16]), hex("b2396305f03dc027ccc3524a0a1118a8"));
+    }
+
+    #[test]
+    fn checksum_known_answer() {
+        // RFC 1321 test suite
+        assert_eq!(${Checksum}::stamp(b"").to_vec(), hex("d41d8cd98f00b204e9800998ecf8427e"));
+        assert_eq!(${Checksum}::stamp(b"abc").to_vec(), hex("900150983cd24fb0d6963f7d28e17f72"));
+        assert_eq!(${Checksum}::stamp(b"message digest").to_vec(), hex("f96b697d7cb7938d525a2f31aaf161d0"));
+        assert_eq!(
+            ${Checksum}::stamp(b"12345678901234567890123456789012345678901234567890123456789012345678901234567890").to_vec(),
+            hex("57edf4a22be3c955ac49da2e2107b67a")
+        );
+    }
+
+    #[test]
+    fn channel_round_trip() {
+        let channel = ${Channel}::new(&${BUNDLE_KEYS}, ${STREAM_SECRET});
+        let wrapped = channel.wrap_secret(b"${secret_value}");
+        assert_eq!(&channel.unwrap_secret(&wrapped)[..${secret_length}], b"${secret_value}");
+
+        let mut frame = channel.seal_batch(7, b"${payload}");
+        assert_eq!(channel.open_batch(&frame), Ok(b"${payload}".to_vec()));
+
+        let last = frame.len() - 1;
+        frame[last] ^= 0x01;
+        assert_eq!(channel.open_batch(&frame), Err(${Rejection}::StampMismatch));
+        assert_eq!(channel.open_batch(&frame[..10]), Err(${Rejection}::Truncated));
+    }
+}
@@ -0,0 +1,153 @@
+#!/usr/bin/env python3
+"""
+판단 보류(abstention)와 선택적 예측 지표 (selective prediction)
+
+어려운 샘플에서 "모르겠다"고 답하는 탐지기와 틀린 답을 내는 탐지기를 구별합니다. 판단 보류한 샘플은 기존
+지표(계층 F1, 정확도, 성공률)에서는 틀린 답과 같은 0점이지만(정상 샘플에서 빈 라벨로 만점을 받지 않도록),
+선택적 지표는 답한 샘플만으로 계산하고 얼마나 답했는지(coverage)를 함께 봅니다.
+
+판단 보류 표시:
+    LLM 응답 (--abstain)   "verdict": "unsure"  (detected / not_detected / unsure, ABSTAIN_VERDICTS의 표현 인정)
+    로컬/외부 탐지기        'abstain': True  (BaseDetector.detect, 명령 어댑터/플러그인 응답, gRPC DetectResponse.abstain)
+    결과 행에는 'abstained': true가 남고, 판단 보류 행의 hierarchical_scores는 모든 정답을 놓친 0점입니다.
+
+지표 (selective_summary, 오류 행 제외, 판단 보류가 아닌 crash 행은 0점으로 답한 것으로 셈):
+    coverage          답한 샘플 / 전체 샘플
+    selective_f1      답한 샘플의 계층 F1 평균 (모두 보류하면 null)
+    selective_accuracy 답한 샘플의 성공률
+    wrong_rate        틀린 답(답했지만 성공 아님) / 전체 샘플 — 보류율(abstain_rate)과 나눠 봄
+    risk-coverage     답한 샘플을 응답 신뢰도(confidence_score) 높은 순으로 더하며 coverage와 위험(1 - 계층 F1 평균)
+    AURC              risk-coverage 곡선 아래 넓이 (낮을수록 좋음, 신뢰도 순서가 맞고 틀린 답이 적을수록 작음)
+
+사용법:
+    python -m utils.selective results/benchmark_results_20250101.json
+    python -m utils.selective results/run.json --detector llama3 --output results/selective.json
+"""
+
+import argparse
+import json
+import sys
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+# "unsure" 판정으로 인정하는 표현 (소문자, 공백/하이픈은 밑줄로)
+ABSTAIN_VERDICTS = {'unsure', 'uncertain', 'abstain', 'unknown', 'cannot_determine', 'undetermined'}
+
+
+def abstention_settings(config: Optional[Dict[str, Any]]) -> Dict[str, Any]:
+    config = config or {}
+    return {'enabled': bool(config.get('enabled', False))}
+
+
+def is_abstention(verdict: Any) -> bool:
+    """응답의 verdict가 판단 보류인지"""
+    if not isinstance(verdict, str):
+        return False
+    return verdict.strip().lower().replace('-', '_').replace(' ', '_') in ABSTAIN_VERDICTS
+
+
+def abstained_scores(ground_truth: Dict[str, Any], policy: Any = None) -> Dict[str, Any]:
+    """판단 보류 행의 계층 점수: 모든 정답을 놓친 0점 (정답이 없는 샘플도 0점)"""
+    from utils.metrics_calculator import MetricsCalculator
+
+    scores = MetricsCalculator.calculate_hierarchical_scores([], ground_truth, policy)
+    scores.update(precision=0.0, recall=0.0, f1=0.0)
+    return scores
+
+
+def _f1(row: Dict[str, Any]) -> float:
+    return float((row.get('hierarchical_scores') or {}).get('f1') or 0.0)
+
+
+def risk_coverage(rows: List[Dict[str, Any]]) -> List[Dict[str, Any]]:
+    """답한 샘플을 신뢰도 높은 순으로 더할 때의 (coverage, 위험) 점 (같은 신뢰도는 한 점)"""
+    rows = [row for row in rows if 'error' not in row]
+    answered = sorted((row for row in rows if not row.get('abstained')),
+                      key=lambda row: -float(row.get('confidence_score') or 0.0))
+    curve, total = [], 0.0
+    for position, row in enumerate(answered):
+        total += _f1(row)
+        confidence = float(row.get('confidence_score') or 0.0)
+        if position + 1 < len(answered) and float(answered[position + 1].get('confidence_score') or 0.0) == confidence:
+            continue
+        curve.append({'threshold': confidence, 'coverage': (position + 1) / len(rows),
+                      'risk': 1.0 - total / (position + 1)})
+    return curve
+
+
+def aurc(curve: List[Dict[str, Any]]) -> Optional[float]:
+    """risk-coverage 곡선 아래 넓이 (coverage 증가분 × 그 지점의 위험)"""
+    if not curve:
+        return None
+    area, previous = 0.0, 0.0
+    for point in curve:
+        area += (point['coverage'] - previous) * point['risk']
+        previous = point['coverage']
+    return area
+
+
+def selective_summary(rows: List[Dict[str, Any]]) -> Dict[str, Any]:
+    """판단 보류 수, coverage, 답한 샘플의 지표, risk-coverage 곡선과 AURC"""
+    rows = [row for row in rows if 'error' not in row]
+    answered = [row for row in rows if not row.get('abstained')]
+    wrong = sum(1 for row in answered if not row.get('success'))
+    curve = risk_coverage(rows)
+    return {
+        'samples': len(rows),
+        'answered': len(answered),
+        'abstained': len(rows) - len(answered),
+        'coverage': len(answered) / len(rows) if rows else 0.0,
+        'abstain_rate': (len(rows) - len(answered)) / len(rows) if rows else 0.0,
+        'wrong_rate': wrong / len(rows) if rows else 0.0,
+        'selective_f1': sum(_f1(row) for row in answered) / len(answered) if answered else None,
+        'selective_accuracy': (len(answered) - wrong) / len(answered) if answered else None,
+        'aurc': aurc(curve),
+        'risk_coverage': curve,
+    }
+
+
+def format_selective(summary: Dict[str, Any]) -> str:
+    selective_f1 = f"{summary['selective_f1']:.3f}" if summary['selective_f1'] is not None else '-'
+    area = f"{summary['aurc']:.3f}" if summary['aurc'] is not None else '-'
+    return (f"판단 보류 {summary['abstained']}/{summary['samples']} (coverage {summary['coverage']:.3f}), "
+            f"답한 샘플 계층 F1 {selective_f1}, 틀린 답 {summary['wrong_rate']:.1%}, AURC {area}")
+
+
+def main():
+    from utils.report_export import load_results
+    from utils.run_compare import group_by_detector, select_detector
+
+    parser = argparse.ArgumentParser(description='판단 보류(unsure)와 선택적 예측 지표 (coverage, risk-coverage, AURC)')
+    parser.add_argument('results', help='결과 파일 (benchmark_runner / detectors.runner JSON)')
+    parser.add_argument('--detector', help='볼 탐지기 (부분 문자열, 기본: 전부)')
+    parser.add_argument('--limit', type=int, default=10, help='출력할 곡선 점 개수')
+    parser.add_argument('--output', help='탐지기별 요약과 곡선 JSON 저장 경로')
+    args = parser.parse_args()
+
+    try:
+        groups = group_by_detector(load_results(args.results))
+        if args.detector:
+            name = select_detector(groups, args.detector)
+            groups = {name: groups[name]}
+    except ValueError as e:
+        print(f"❌ {e}")
+        sys.exit(2)
+
+    report = {}
+    for name, rows in groups.items():
+        summary = selective_summary(rows)
+        report[name] = summary
+        print(f"\n🤷 {name}: {format_selective(summary)}")
+        curve = summary['risk_coverage']
+        for point in curve[::max(1, len(curve) // args.limit)]:
+            print(f"     신뢰도 ≥ {point['threshold']:.3f}  coverage {point['coverage']:.3f}  위험 {point['risk']:.3f}")
+
+    if args.output:
+        Path(args.output).parent.mkdir(parents=True, exist_ok=True)
+        with open(args.output, 'w', encoding='utf-8') as f:
+            json.dump({'results': args.results, 'detectors': report}, f, indent=2, ensure_ascii=False)
+        print(f"💾 결과 저장: {args.output}")
+
+
+if __name__ == '__main__':
+    main()
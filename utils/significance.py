@@ -0,0 +1,165 @@
+"""
+통계적 유의성 도구
+
+실행 간 점수 차이가 샘플 구성의 우연인지 판단하기 위한 재표본 기법입니다.
+외부 의존성 없이 표준 라이브러리 난수만 쓰며, 시드를 고정하면 결과가 재현됩니다.
+
+    bootstrap_ci              한 실행의 샘플별 점수(예: 계층 F1) 평균에 대한 신뢰구간
+    paired_bootstrap_ci       같은 샘플 집합에서 얻은 두 점수 열의 평균 차이(new - old)에 대한 신뢰구간
+                              (샘플 단위로 복원 추출 → 차이 평균의 분위수)
+    paired_permutation_test   두 탐지기가 같은 샘플에서 얻은 점수의 평균 차이에 대한 양측 p-값
+                              (샘플마다 두 점수를 무작위로 맞바꾸는 부호 뒤집기 순열 검정)
+    compare_detectors         여러 탐지기를 공통 샘플에서 쌍마다 비교 (평균 차이, 신뢰구간, p-값)
+    permutation_test          서로 다른 샘플 집합(예: 한 언어 vs 나머지 언어)의 평균 차이에 대한 양측 p-값
+                              (두 집단을 합쳐 무작위로 다시 나누는 순열 검정)
+"""
+
+import random
+from itertools import combinations
+from typing import Dict, Any, Hashable, List, Sequence
+
+DEFAULT_ITERATIONS = 2000
+DEFAULT_CONFIDENCE = 0.95
+DEFAULT_SEED = 0
+DEFAULT_ALPHA = 0.05
+
+
+def _percentile(sorted_values: List[float], q: float) -> float:
+    """선형 보간 분위수 (q: 0~1)"""
+    if not sorted_values:
+        return 0.0
+    position = (len(sorted_values) - 1) * q
+    lower = int(position)
+    upper = min(lower + 1, len(sorted_values) - 1)
+    return sorted_values[lower] + (sorted_values[upper] - sorted_values[lower]) * (position - lower)
+
+
+def bootstrap_ci(values: Sequence[float], iterations: int = DEFAULT_ITERATIONS,
+                 confidence: float = DEFAULT_CONFIDENCE, seed: int = DEFAULT_SEED) -> Dict[str, Any]:
+    """샘플별 점수 평균의 부트스트랩 신뢰구간"""
+    n = len(values)
+    mean = sum(values) / n if n else 0.0
+    if n < 2:
+        return {'n': n, 'mean': mean, 'ci_low': mean, 'ci_high': mean, 'confidence': confidence}
+
+    rng = random.Random(seed)
+    means = sorted(
+        sum(values[rng.randrange(n)] for _ in range(n)) / n
+        for _ in range(iterations)
+    )
+    alpha = (1 - confidence) / 2
+    return {
+        'n': n,
+        'mean': mean,
+        'ci_low': _percentile(means, alpha),
+        'ci_high': _percentile(means, 1 - alpha),
+        'confidence': confidence
+    }
+
+
+def paired_bootstrap_ci(old: Sequence[float], new: Sequence[float], iterations: int = DEFAULT_ITERATIONS,
+                        confidence: float = DEFAULT_CONFIDENCE, seed: int = DEFAULT_SEED) -> Dict[str, Any]:
+    """평균 차이(new - old)의 부트스트랩 신뢰구간
+
+    old[i]와 new[i]는 같은 샘플의 점수여야 합니다. 신뢰구간이 0을 포함하지 않으면 유의한 변화로 봅니다.
+    """
+    if len(old) != len(new):
+        raise ValueError("paired scores must have the same length")
+
+    n = len(old)
+    diffs = [b - a for a, b in zip(old, new)]
+    mean_diff = sum(diffs) / n if n else 0.0
+    if n < 2:
+        return {'n': n, 'mean_diff': mean_diff, 'ci_low': mean_diff, 'ci_high': mean_diff,
+                'confidence': confidence, 'significant': False}
+
+    rng = random.Random(seed)
+    means = sorted(
+        sum(diffs[rng.randrange(n)] for _ in range(n)) / n
+        for _ in range(iterations)
+    )
+    alpha = (1 - confidence) / 2
+    ci_low, ci_high = _percentile(means, alpha), _percentile(means, 1 - alpha)
+    return {
+        'n': n,
+        'mean_diff': mean_diff,
+        'ci_low': ci_low,
+        'ci_high': ci_high,
+        'confidence': confidence,
+        'significant': ci_low > 0 or ci_high < 0
+    }
+
+
+def paired_permutation_test(old: Sequence[float], new: Sequence[float], iterations: int = DEFAULT_ITERATIONS,
+                            seed: int = DEFAULT_SEED) -> Dict[str, Any]:
+    """평균 차이(new - old)의 양측 paired permutation test
+
+    귀무가설(두 탐지기의 점수 분포가 같음) 아래에서는 샘플마다 두 점수를 맞바꿔도 되므로, 차이의 부호를
+    무작위로 뒤집어 관측값 이상으로 극단적인 평균 차이가 나오는 비율을 p-값으로 씁니다 (+1 보정).
+    """
+    if len(old) != len(new):
+        raise ValueError("paired scores must have the same length")
+
+    n = len(old)
+    diffs = [b - a for a, b in zip(old, new)]
+    observed = sum(diffs) / n if n else 0.0
+    if n == 0 or all(d == 0 for d in diffs):
+        return {'n': n, 'mean_diff': observed, 'p_value': 1.0}
+
+    rng = random.Random(seed)
+    extreme = 0
+    for _ in range(iterations):
+        permuted = sum(d if rng.random() < 0.5 else -d for d in diffs) / n
+        if abs(permuted) >= abs(observed) - 1e-12:
+            extreme += 1
+    return {'n': n, 'mean_diff': observed, 'p_value': (extreme + 1) / (iterations + 1)}
+
+
+def permutation_test(first: Sequence[float], second: Sequence[float], iterations: int = DEFAULT_ITERATIONS,
+                     seed: int = DEFAULT_SEED) -> Dict[str, Any]:
+    """독립된 두 표본의 평균 차이(second - first)에 대한 양측 순열 검정
+
+    귀무가설(두 집단의 점수 분포가 같음) 아래에서는 어느 샘플이 어느 집단에 속하든 같으므로, 합친 점수를 같은
+    크기로 무작위로 다시 나눠 관측값 이상으로 극단적인 평균 차이가 나오는 비율을 p-값으로 씁니다 (+1 보정).
+    """
+    n1, n2 = len(first), len(second)
+    if not n1 or not n2:
+        return {'n': n1 + n2, 'mean_diff': 0.0, 'p_value': 1.0}
+    observed = sum(second) / n2 - sum(first) / n1
+
+    pooled = list(first) + list(second)
+    rng = random.Random(seed)
+    extreme = 0
+    for _ in range(iterations):
+        rng.shuffle(pooled)
+        permuted = sum(pooled[n1:]) / n2 - sum(pooled[:n1]) / n1
+        if abs(permuted) >= abs(observed) - 1e-12:
+            extreme += 1
+    return {'n': n1 + n2, 'mean_diff': observed, 'p_value': (extreme + 1) / (iterations + 1)}
+
+
+def compare_detectors(scores: Dict[str, Dict[Hashable, float]], iterations: int = DEFAULT_ITERATIONS,
+                      alpha: float = DEFAULT_ALPHA, seed: int = DEFAULT_SEED) -> List[Dict[str, Any]]:
+    """탐지기 쌍마다 공통 샘플에서 점수 비교
+
+    scores: {탐지기: {샘플 키: 점수}}. 두 탐지기 모두 점수가 있는 샘플만 씁니다.
+    """
+    results = []
+    for first, second in combinations(sorted(scores), 2):
+        keys = sorted(set(scores[first]) & set(scores[second]), key=str)
+        if len(keys) < 2:
+            continue
+        a = [scores[first][key] for key in keys]
+        b = [scores[second][key] for key in keys]
+        interval = paired_bootstrap_ci(a, b, iterations=iterations, seed=seed)
+        test = paired_permutation_test(a, b, iterations=iterations, seed=seed)
+        results.append({
+            'detectors': [first, second],
+            'n': len(keys),
+            'mean_diff': interval['mean_diff'],
+            'ci_low': interval['ci_low'],
+            'ci_high': interval['ci_high'],
+            'p_value': test['p_value'],
+            'significant': test['p_value'] < alpha
+        })
+    return results
@@ -0,0 +1,43 @@
+"""
+코퍼스 분할 (train / test)
+
+few-shot 예시(utils/exemplars.py)나 로컬 기준선 학습에 쓰는 샘플과, 점수를 매기는 평가 샘플이 섞이면
+탐지기가 정답을 본 셈이 되므로 샘플마다 분할을 하나로 고정합니다.
+
+    train   few-shot 예시, 기준선 학습에 사용 가능
+    test    평가 전용. 라벨이 프롬프트나 학습에 들어가면 안 됨 (utils/submission.py가 유출을 검사)
+
+분할 결정:
+    - ground truth 최상위 `split` 필드가 있으면 그 값
+    - 근사 중복 클러스터(utils/dedup.py, data/duplicate_clusters.json)에 속하면 클러스터 ID의 해시로 결정
+      (같은 템플릿/변형 샘플이 train과 test에 나뉘어 들어가지 않도록)
+    - 없으면 "<agent_type>/<test_id>"의 SHA-256 해시로 결정 (약 TRAIN_FRACTION 비율이 train).
+      샘플이 추가/삭제되어도 기존 샘플의 분할은 바뀌지 않습니다
+"""
+
+import hashlib
+from typing import Dict, Any, Optional
+
+from utils.dedup import duplicate_cluster
+
+SPLIT_TRAIN = 'train'
+SPLIT_TEST = 'test'
+
+SPLITS = [SPLIT_TRAIN, SPLIT_TEST]
+
+TRAIN_FRACTION = 0.2
+
+
+def hashed_split(agent_type: str, test_id: str) -> str:
+    bucket = int(hashlib.sha256(f"{agent_type}/{test_id}".encode('utf-8')).hexdigest()[:8], 16)
+    return SPLIT_TRAIN if bucket < TRAIN_FRACTION * 0x100000000 else SPLIT_TEST
+
+
+def sample_split(agent_type: str, test_id: str, ground_truth: Optional[Dict[str, Any]] = None) -> str:
+    """샘플의 분할 (ground truth의 split 필드가 우선)"""
+    if ground_truth and ground_truth.get('split') in SPLITS:
+        return ground_truth['split']
+    cluster_id = duplicate_cluster(agent_type, test_id)
+    if cluster_id:
+        return hashed_split('cluster', cluster_id)
+    return hashed_split(agent_type, test_id)
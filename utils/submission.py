@@ -0,0 +1,327 @@
+"""
+외부 참가팀 제출물 검증
+
+외부 팀이 자체 탐지기로 코퍼스를 돌린 결과를 리더보드(utils/leaderboard.py)에 올리기 전에 검사하고,
+주최 측이 다시 채점할 수 있는 정규화된 제출 아카이브를 만듭니다.
+
+제출 디렉토리:
+    <dir>/results.json     benchmark_runner.py 결과 형식 ({metadata, detailed_results})
+    <dir>/metadata.json    {"team": ..., "detector": ..., "version": ...}
+
+검사 항목:
+    - 스키마: 테스트별 결과에 필수 필드(SUBMISSION_FIELDS)가 올바른 타입으로 있는지
+    - 코퍼스 버전: 결과의 ground_truth_hash를 현재 코퍼스 매니페스트(샘플/ground truth 해시)와 대조.
+      다른 라벨로 채점했거나 코퍼스에 없는 샘플이 있으면 오류, 빠진 샘플은 --allow-partial이 아니면 오류
+    - 라벨 접근: 평가용 ground truth를 탐지기가 보지 않았는지. 제출물에 ground truth 객체가 들어 있거나,
+      test 분할 샘플의 ground truth에만 있고 샘플 본문에는 없는 긴 문장(LEAK_MIN_LENGTH자 이상)이 응답에
+      그대로 나오면 오류. train 분할(utils/splits.py)의 라벨은 few-shot 예시로 쓸 수 있으므로 검사하지 않습니다
+
+정규화된 아카이브 (`submission_<team>_<version>_<corpus>.tar.gz`, 같은 입력이면 바이트 단위로 동일):
+    results.json     예측 필드만 남기고 (agent_type, test_id) 순으로 정렬. 점수 필드는 버리고 주최 측이 재채점
+    manifest.json    팀/탐지기/버전, 코퍼스 버전, 테스트별 ground truth·샘플 해시와 출처, 원본 파일 해시
+
+사용법:
+    python -m utils.submission validate submissions/team_a
+    python -m utils.submission validate submissions/team_a --allow-partial --output-dir results/submissions
+"""
+
+import argparse
+import gzip
+import hashlib
+import io
+import json
+import re
+import sys
+import tarfile
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+from utils.corpus import GROUND_TRUTH_DIR, TEST_FILES_DIR, FidelityLinter
+from utils.provenance import provenance_kind
+from utils.results_store import corpus_version, ground_truth_hash
+from utils.splits import SPLIT_TEST, sample_split
+
+SUBMISSIONS_OUTPUT_DIR = "results/submissions"
+
+# 테스트별 필수 필드 → 허용 타입
+SUBMISSION_FIELDS = {
+    'test_id': str,
+    'agent_type': str,
+    'provider': str,
+    'model': str,
+    'detected_algorithms': list,
+    'valid_json': bool,
+    'ground_truth_hash': str,
+}
+
+# 정규화된 결과에 남기는 예측 필드 (필수 필드 외)
+PREDICTION_FIELDS = ['confidence_score', 'findings', 'labels', 'introductions', 'weaknesses', 'summary',
+                     'response_time']
+
+METADATA_FIELDS = ['team', 'detector', 'version']
+
+# 제출물에 있으면 안 되는 ground truth 필드
+GROUND_TRUTH_KEYS = {'ground_truth', 'expected_findings', 'vulnerability_analysis'}
+
+LEAK_MIN_LENGTH = 40
+
+
+def _sha256_path(path: Path) -> str:
+    digest = hashlib.sha256()
+    files = sorted(p for p in path.rglob('*') if p.is_file()) if path.is_dir() else [path]
+    for file in files:
+        if path.is_dir():
+            digest.update(str(file.relative_to(path)).encode('utf-8') + b'\0')
+        digest.update(file.read_bytes())
+    return digest.hexdigest()
+
+
+def corpus_manifest(ground_truth_dir: str = GROUND_TRUTH_DIR,
+                    test_files_dir: str = TEST_FILES_DIR) -> Dict[Tuple[str, str], Dict[str, Any]]:
+    """현재 코퍼스의 테스트별 해시 {(agent_type, test_id): {'ground_truth_hash', 'sample_sha256', ...}}"""
+    linter = FidelityLinter(ground_truth_dir, test_files_dir)
+    manifest = {}
+    for gt_path in sorted(Path(ground_truth_dir).glob('*/*.json')):
+        try:
+            with open(gt_path, 'r', encoding='utf-8') as f:
+                ground_truth = json.load(f)
+        except (json.JSONDecodeError, OSError):
+            continue
+        if not isinstance(ground_truth, dict):
+            continue
+        agent_type, test_id = gt_path.parent.name, gt_path.stem
+        sample = linter.find_sample(agent_type, test_id)
+        manifest[(agent_type, test_id)] = {
+            'ground_truth_hash': ground_truth_hash(ground_truth),
+            'sample_sha256': _sha256_path(sample) if sample else None,
+            'ground_truth': ground_truth,
+            'sample': sample
+        }
+    return manifest
+
+
+def _strings(value: Any) -> List[str]:
+    if isinstance(value, str):
+        return [value]
+    if isinstance(value, dict):
+        return [s for item in value.values() for s in _strings(item)]
+    if isinstance(value, list):
+        return [s for item in value for s in _strings(item)]
+    return []
+
+
+def _contains_key(value: Any, keys: set) -> bool:
+    if isinstance(value, dict):
+        return bool(keys & set(value)) or any(_contains_key(item, keys) for item in value.values())
+    if isinstance(value, list):
+        return any(_contains_key(item, keys) for item in value)
+    return False
+
+
+def label_only_phrases(entry: Dict[str, Any]) -> List[str]:
+    """ground truth에만 있고 샘플 본문에는 없는 긴 문장 (응답에 나오면 라벨 유출)"""
+    sample = entry['sample']
+    if sample is None:
+        source = ''
+    elif sample.is_dir():
+        source = '\n'.join(p.read_text(encoding='utf-8', errors='ignore')
+                           for p in sorted(sample.rglob('*')) if p.is_file())
+    else:
+        source = sample.read_text(encoding='utf-8', errors='ignore')
+    normalized_source = re.sub(r'\s+', ' ', source)
+    return sorted({
+        phrase for phrase in (re.sub(r'\s+', ' ', s).strip() for s in _strings(entry['ground_truth']))
+        if len(phrase) >= LEAK_MIN_LENGTH and phrase not in normalized_source
+    })
+
+
+def canonical_result(result: Dict[str, Any]) -> Dict[str, Any]:
+    fields = list(SUBMISSION_FIELDS) + PREDICTION_FIELDS
+    return {field: result[field] for field in fields if field in result}
+
+
+class SubmissionValidator:
+    """제출 디렉토리 검증 및 정규화"""
+
+    def __init__(self, manifest: Optional[Dict[Tuple[str, str], Dict[str, Any]]] = None,
+                 allow_partial: bool = False):
+        self.manifest = manifest if manifest is not None else corpus_manifest()
+        self.allow_partial = allow_partial
+
+    def validate(self, directory: str) -> Dict[str, Any]:
+        """검사 결과 {'errors', 'warnings', 'metadata', 'results', 'source_sha256'}"""
+        directory = Path(directory)
+        errors: List[str] = []
+        warnings: List[str] = []
+        report = {'errors': errors, 'warnings': warnings, 'metadata': {}, 'results': [], 'source_sha256': None}
+
+        metadata_path, results_path = directory / 'metadata.json', directory / 'results.json'
+        for path in (metadata_path, results_path):
+            if not path.exists():
+                errors.append(f"missing {path.name}")
+        if errors:
+            return report
+
+        try:
+            metadata = json.loads(metadata_path.read_text(encoding='utf-8'))
+            raw = results_path.read_bytes()
+            data = json.loads(raw)
+        except json.JSONDecodeError as error:
+            errors.append(f"invalid JSON: {error}")
+            return report
+
+        report['source_sha256'] = hashlib.sha256(raw).hexdigest()
+        metadata = metadata if isinstance(metadata, dict) else {}
+        for field in METADATA_FIELDS:
+            if not isinstance(metadata.get(field), str) or not metadata[field].strip():
+                errors.append(f"metadata.json: '{field}' must be a non-empty string")
+        report['metadata'] = {field: metadata.get(field) for field in METADATA_FIELDS}
+
+        results = data.get('detailed_results') if isinstance(data, dict) else None
+        if not isinstance(results, list):
+            errors.append("results.json: 'detailed_results' must be a list")
+            return report
+
+        results = [r for r in results if isinstance(r, dict) and 'error' not in r]
+        before = len(errors)
+        self._check_schema(results, errors)
+        if len(errors) > before:
+            return report
+
+        self._check_corpus(results, data.get('metadata', {}), errors, warnings)
+        self._check_label_access(data, results, errors)
+        report['results'] = sorted((canonical_result(r) for r in results),
+                                   key=lambda r: (r['agent_type'], r['test_id']))
+        return report
+
+    @staticmethod
+    def _check_schema(results: List[Dict[str, Any]], errors: List[str]):
+        seen = set()
+        for index, result in enumerate(results):
+            where = f"detailed_results[{index}]"
+            for field, kind in SUBMISSION_FIELDS.items():
+                if not isinstance(result.get(field), kind):
+                    errors.append(f"{where}: '{field}' must be {kind.__name__}")
+            if not all(isinstance(a, str) for a in result.get('detected_algorithms') or []):
+                errors.append(f"{where}: 'detected_algorithms' must contain strings")
+            key = (result.get('agent_type'), result.get('test_id'), result.get('provider'), result.get('model'))
+            if key in seen:
+                errors.append(f"{where}: duplicate result for {key[0]}/{key[1]}")
+            seen.add(key)
+
+    def _check_corpus(self, results: List[Dict[str, Any]], metadata: Dict[str, Any],
+                      errors: List[str], warnings: List[str]):
+        submitted = {(r['agent_type'], r['test_id']): r for r in results}
+        for key, result in sorted(submitted.items()):
+            entry = self.manifest.get(key)
+            if entry is None:
+                errors.append(f"{key[0]}/{key[1]}: not in the corpus")
+            elif result['ground_truth_hash'] != entry['ground_truth_hash']:
+                errors.append(f"{key[0]}/{key[1]}: scored against different labels "
+                              f"({result['ground_truth_hash']} != {entry['ground_truth_hash']})")
+
+        missing = sorted(set(self.manifest) - set(submitted))
+        if missing:
+            message = f"{len(missing)} corpus samples missing (e.g. {missing[0][0]}/{missing[0][1]})"
+            (warnings if self.allow_partial else errors).append(message)
+
+        declared = metadata.get('corpus_version')
+        computed = corpus_version(results)
+        if declared and declared != computed:
+            errors.append(f"declared corpus_version {declared} does not match results ({computed})")
+
+    def _check_label_access(self, data: Dict[str, Any], results: List[Dict[str, Any]], errors: List[str]):
+        if _contains_key(data, GROUND_TRUTH_KEYS):
+            errors.append("results.json embeds ground truth fields "
+                          f"({', '.join(sorted(GROUND_TRUTH_KEYS))})")
+
+        for result in results:
+            entry = self.manifest.get((result['agent_type'], result['test_id']))
+            if entry is None or \
+                    sample_split(result['agent_type'], result['test_id'], entry['ground_truth']) != SPLIT_TEST:
+                continue
+            text = re.sub(r'\s+', ' ', '\n'.join(_strings(result)))
+            leaked = [phrase for phrase in label_only_phrases(entry) if phrase in text]
+            if leaked:
+                errors.append(f"{result['agent_type']}/{result['test_id']}: response quotes ground truth "
+                              f"text not present in the sample: \"{leaked[0][:60]}...\"")
+
+    def write_archive(self, report: Dict[str, Any], output_dir: str = SUBMISSIONS_OUTPUT_DIR) -> Path:
+        """정규화된 제출 아카이브 생성 (mtime/소유자 고정 → 재현 가능)"""
+        metadata = report['metadata']
+        results = report['results']
+        version = corpus_version(results)
+        manifest = {
+            **metadata,
+            'corpus_version': version,
+            'source_sha256': report['source_sha256'],
+            'tests': [
+                {'agent_type': r['agent_type'], 'test_id': r['test_id'],
+                 'ground_truth_hash': r['ground_truth_hash'],
+                 'sample_sha256': self.manifest[(r['agent_type'], r['test_id'])]['sample_sha256'],
+                 'provenance': provenance_kind(self.manifest[(r['agent_type'], r['test_id'])]['ground_truth'])}
+                for r in results
+            ]
+        }
+        canonical = {
+            'metadata': {'team': metadata['team'], 'detector': metadata['detector'],
+                         'detector_version': metadata['version'], 'corpus_version': version},
+            'detailed_results': results
+        }
+
+        slug = '_'.join(re.sub(r'[^A-Za-z0-9.\-]+', '-', part).strip('-')
+                        for part in (metadata['team'], metadata['version'], version))
+        path = Path(output_dir) / f"submission_{slug}.tar.gz"
+        path.parent.mkdir(parents=True, exist_ok=True)
+
+        buffer = io.BytesIO()
+        with tarfile.open(fileobj=buffer, mode='w', format=tarfile.PAX_FORMAT) as tar:
+            for name, content in (('manifest.json', manifest), ('results.json', canonical)):
+                payload = (json.dumps(content, indent=2, ensure_ascii=False, sort_keys=True) + '\n').encode('utf-8')
+                info = tarfile.TarInfo(name)
+                info.size, info.mtime, info.mode = len(payload), 0, 0o644
+                tar.addfile(info, io.BytesIO(payload))
+        with open(path, 'wb') as f, gzip.GzipFile(fileobj=f, mode='wb', mtime=0, filename='') as archive:
+            archive.write(buffer.getvalue())
+        return path
+
+
+def run_validate(args) -> int:
+    validator = SubmissionValidator(allow_partial=args.allow_partial)
+    report = validator.validate(args.directory)
+
+    print(f"📦 제출물 검증: {args.directory}")
+    for warning in report['warnings']:
+        print(f"  ⚠️  {warning}")
+    for error in report['errors'][:args.limit]:
+        print(f"  ❌ {error}")
+    if len(report['errors']) > args.limit:
+        print(f"  ... 외 {len(report['errors']) - args.limit}건")
+
+    if report['errors']:
+        print(f"\n❌ 검증 실패: 오류 {len(report['errors'])}건")
+        return 1
+
+    path = validator.write_archive(report, args.output_dir)
+    print(f"\n✅ 검증 통과: 테스트 {len(report['results'])}개, 코퍼스 {corpus_version(report['results'])}")
+    print(f"💾 정규화된 제출 아카이브: {path}")
+    return 0
+
+
+def main():
+    parser = argparse.ArgumentParser(description='외부 참가팀 제출물 검증')
+    subparsers = parser.add_subparsers(dest='command', required=True)
+
+    validate = subparsers.add_parser('validate', help='제출 디렉토리 검증 및 아카이브 생성')
+    validate.add_argument('directory', help='results.json과 metadata.json이 있는 제출 디렉토리')
+    validate.add_argument('--allow-partial', action='store_true', help='코퍼스 일부만 실행한 제출 허용')
+    validate.add_argument('--output-dir', default=SUBMISSIONS_OUTPUT_DIR, help='아카이브 저장 디렉토리')
+    validate.add_argument('--limit', type=int, default=20, help='출력할 오류 개수')
+
+    args = parser.parse_args()
+    if args.command == 'validate':
+        sys.exit(run_validate(args))
+
+
+if __name__ == "__main__":
+    main()
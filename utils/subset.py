@@ -0,0 +1,274 @@
+#!/usr/bin/env python3
+"""
+층화 평가 부분집합 (stratified subset)
+
+비싼 LLM으로 빠르게 확인할 때 `--limit`은 test_id 알파벳순 앞부분만 잘라 특정 계열(예: 공개키)에 몰립니다.
+부분집합은 알고리즘 부류마다 N개씩, 부류 안에서 난이도 단계마다 최대 M개씩 골라 작지만 대표성 있는
+평가 세트를 만들고, 정의를 파일로 저장해 여러 실행에서 같은 샘플을 다시 씁니다.
+
+부류 (--by):
+    family      분류 체계 계열 (AES, RSA, SHA-2 …, 기본)
+    role        기본 요소 역할 (public_key, cipher, hash, mac, kdf, post_quantum)
+    category    카테고리 (shor_vulnerable, grover_vulnerable, post_quantum)
+    암호가 없는 샘플은 none 부류, 분류 체계에 없는 라벨은 라벨 이름 그대로 한 부류입니다.
+
+선택: 후보가 적은 부류부터 채웁니다. 라벨이 여러 개인 샘플은 뽑히면 자기 모든 부류의 칸을 채우므로, 흔한 부류가
+희귀 부류 샘플로 먼저 채워져 전체 크기가 작아집니다 (대신 흔한 부류는 N개를 조금 넘을 수 있음). 난이도 상한은
+샘플이 채우는 모든 부류에서 지킵니다. 같은 시드와 코퍼스면 같은 결과입니다.
+
+정의 파일: data/subsets/<이름>.json
+    {"name": "smoke", "criteria": {"by": "family", "per_class": 10, "max_per_tier": 4, "agents": [...],
+     "split": "test", "filter": "lang==rust", "seed": 0},
+     "samples": [{"agent_type": "source_code", "test_id": "...", "classes": ["AES"], "difficulty": "medium"}],
+     "coverage": {"AES": 10, ...}, "shortfall": {"Blowfish": 2, ...}}
+
+    shortfall    per_class를 채우지 못한 부류 → 실제로 고른 수 (코퍼스 공백, `python -m utils.corpus stats` 참고)
+    filter       후보를 이 필터 식에 맞는 샘플로 제한 (utils/sample_filter.py)
+
+실행 (benchmark_runner.py --subset smoke): 에이전트별로 정의에 있는 샘플만 평가하며, 결과 metadata.subset에
+이름과 샘플 목록 해시를 남깁니다. 정의에 있지만 코퍼스에서 사라진 샘플은 경고합니다.
+
+사용법:
+    python -m utils.subset create smoke --per-class 10 --max-per-tier 4
+    python -m utils.subset create pk_vs_sym --by role --per-class 15 --agents source_code --split test
+    python -m utils.subset create rust_hard --per-class 5 --filter "lang==rust && difficulty>=hard"
+    python -m utils.subset show smoke
+"""
+
+import argparse
+import hashlib
+import json
+import random
+import sys
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Set, Tuple
+
+from utils.exemplars import NO_CRYPTO_CLASS
+from utils.metrics_calculator import MetricsCalculator
+from utils.sample_filter import SampleFilter, compile_filter
+from utils.splits import SPLITS, sample_split
+from utils.taxonomy import AlgorithmTaxonomy
+from utils.test_case_manager import TestCaseManager
+
+SUBSETS_DIR = "data/subsets"
+TEST_FILES_DIR = "data/test_files"
+GROUND_TRUTH_DIR = "data/ground_truth"
+
+CLASS_LEVELS = ['family', 'role', 'category']
+DEFAULT_AGENTS = ['source_code', 'assembly_binary', 'logs_config']
+
+
+def sample_classes(ground_truth: Dict[str, Any], by: str, taxonomy: AlgorithmTaxonomy) -> List[str]:
+    """샘플의 부류 목록 (라벨이 없으면 [none])"""
+    classes = set()
+    labels = MetricsCalculator.get_expected_labels(ground_truth) + MetricsCalculator.get_quantum_safe_labels(
+        ground_truth)
+    for label in labels:
+        node = taxonomy.resolve(label)
+        if not taxonomy.is_known(node):
+            classes.add(str(label))
+        elif by == 'role':
+            classes.add(taxonomy.role(node) or node[1] or node[0])
+        elif by == 'category':
+            classes.add(node[0])
+        else:
+            classes.add(node[1] or node[0])
+    return sorted(classes) or [NO_CRYPTO_CLASS]
+
+
+class SubsetBuilder:
+    """ground truth가 있는 샘플에서 층화 부분집합 선택"""
+
+    def __init__(self, test_files_dir: str = TEST_FILES_DIR, ground_truth_dir: str = GROUND_TRUTH_DIR):
+        # 러너가 읽는 샘플만 후보 (TestCaseManager가 읽지 않는 확장자의 샘플은 평가되지 않음)
+        self.test_manager = TestCaseManager(test_cases_dir=str(Path(test_files_dir).parent / 'test_cases'),
+                                            ground_truth_dir=ground_truth_dir, test_files_dir=test_files_dir)
+        self.taxonomy = AlgorithmTaxonomy()
+
+    def candidates(self, agents: List[str], by: str, split: Optional[str] = None,
+                   sample_filter: Optional[SampleFilter] = None) -> List[Dict[str, Any]]:
+        result = []
+        for agent_type in agents:
+            for case in self.test_manager.load_test_cases(agent_type):
+                test_id = case['test_id']
+                ground_truth = self.test_manager.load_ground_truth(agent_type, test_id)
+                if not isinstance(ground_truth, dict):
+                    continue
+                if split and sample_split(agent_type, test_id, ground_truth) != split:
+                    continue
+                if sample_filter and not sample_filter.matches_sample(agent_type, test_id, ground_truth,
+                                                                      case.get('file_path')):
+                    continue
+                result.append({
+                    'agent_type': agent_type,
+                    'test_id': test_id,
+                    'classes': sample_classes(ground_truth, by, self.taxonomy),
+                    'difficulty': str(ground_truth.get('difficulty', 'medium')),
+                })
+        return result
+
+    def build(self, name: str, by: str = 'family', per_class: int = 10, max_per_tier: Optional[int] = None,
+              agents: Optional[List[str]] = None, split: Optional[str] = None, seed: int = 0,
+              filter_expression: Optional[str] = None) -> Dict[str, Any]:
+        if by not in CLASS_LEVELS:
+            raise ValueError(f"unknown class level: {by} (choose from {CLASS_LEVELS})")
+        if split is not None and split not in SPLITS:
+            raise ValueError(f"unknown split: {split} (choose from {SPLITS})")
+        agents = list(agents or DEFAULT_AGENTS)
+        pool = self.candidates(agents, by, split, compile_filter(filter_expression))
+        rng = random.Random(f"subset:{seed}")
+        rng.shuffle(pool)
+
+        by_class: Dict[str, List[Dict[str, Any]]] = {}
+        for item in pool:
+            for cls in item['classes']:
+                by_class.setdefault(cls, []).append(item)
+
+        chosen: List[Dict[str, Any]] = []
+        taken: Set[Tuple[str, str]] = set()
+        filled: Dict[str, int] = {cls: 0 for cls in by_class}
+        tiers: Dict[Tuple[str, str], int] = {}
+
+        def fits(item: Dict[str, Any]) -> bool:
+            # 뽑으면 채워지는 모든 부류에서 난이도 상한을 넘지 않아야 함
+            return max_per_tier is None or all(tiers.get((cls, item['difficulty']), 0) < max_per_tier
+                                               for cls in item['classes'])
+
+        # 후보가 적은 부류부터 (같으면 이름순)
+        for cls in sorted(by_class, key=lambda c: (len(by_class[c]), c)):
+            for item in by_class[cls]:
+                if filled[cls] >= per_class:
+                    break
+                key = (item['agent_type'], item['test_id'])
+                if key in taken or not fits(item):
+                    continue
+                taken.add(key)
+                chosen.append(item)
+                for other in item['classes']:
+                    filled[other] += 1
+                    tiers[(other, item['difficulty'])] = tiers.get((other, item['difficulty']), 0) + 1
+
+        chosen.sort(key=lambda item: (item['agent_type'], item['test_id']))
+        return {
+            'name': name,
+            'criteria': {'by': by, 'per_class': per_class, 'max_per_tier': max_per_tier, 'agents': agents,
+                         'split': split, 'filter': filter_expression, 'seed': seed},
+            'samples': chosen,
+            'coverage': dict(sorted(filled.items())),
+            'shortfall': {cls: count for cls, count in sorted(filled.items()) if count < per_class},
+        }
+
+
+def subset_path(name_or_path: str, subsets_dir: str = SUBSETS_DIR) -> Path:
+    """부분집합 이름 또는 정의 파일 경로 → 정의 파일 경로"""
+    path = Path(name_or_path)
+    if path.suffix == '.json' or path.exists():
+        return path
+    return Path(subsets_dir) / f"{name_or_path}.json"
+
+
+def save_subset(subset: Dict[str, Any], subsets_dir: str = SUBSETS_DIR) -> Path:
+    path = Path(subsets_dir) / f"{subset['name']}.json"
+    path.parent.mkdir(parents=True, exist_ok=True)
+    with open(path, 'w', encoding='utf-8') as f:
+        json.dump(subset, f, indent=2, ensure_ascii=False)
+        f.write('\n')
+    return path
+
+
+def load_subset(name_or_path: str, subsets_dir: str = SUBSETS_DIR) -> Dict[str, Any]:
+    path = subset_path(name_or_path, subsets_dir)
+    if not path.is_file():
+        raise ValueError(f"subset definition not found: {path}")
+    with open(path, 'r', encoding='utf-8') as f:
+        subset = json.load(f)
+    subset['path'] = str(path)
+    return subset
+
+
+def subset_members(subset: Dict[str, Any], agent_type: str) -> List[str]:
+    """에이전트의 부분집합 샘플 test_id 목록"""
+    return [item['test_id'] for item in subset['samples'] if item['agent_type'] == agent_type]
+
+
+def subset_digest(subset: Dict[str, Any]) -> str:
+    """샘플 목록 해시 (정의 파일이 바뀌었는지 실행 결과끼리 비교)"""
+    entries = sorted((item['agent_type'], item['test_id']) for item in subset['samples'])
+    return hashlib.sha256(json.dumps(entries).encode('utf-8')).hexdigest()[:12]
+
+
+def print_subset(subset: Dict[str, Any]):
+    criteria = subset['criteria']
+    tier_cap = f", 난이도별 최대 {criteria['max_per_tier']}" if criteria.get('max_per_tier') else ''
+    print(f"🎯 부분집합 {subset['name']}: 샘플 {len(subset['samples'])}개 "
+          f"({criteria['by']}별 {criteria['per_class']}개{tier_cap}, 시드 {criteria['seed']})")
+    if criteria.get('filter'):
+        print(f"   필터: {criteria['filter']}")
+
+    agents: Dict[str, int] = {}
+    difficulties: Dict[str, int] = {}
+    for item in subset['samples']:
+        agents[item['agent_type']] = agents.get(item['agent_type'], 0) + 1
+        difficulties[item['difficulty']] = difficulties.get(item['difficulty'], 0) + 1
+    print(f"   에이전트: {', '.join(f'{name} {count}' for name, count in sorted(agents.items()))}")
+    print(f"   난이도: {', '.join(f'{name} {count}' for name, count in sorted(difficulties.items()))}")
+
+    print(f"\n  {'부류':<24} {'샘플':>6}")
+    for cls, count in subset['coverage'].items():
+        marker = '  ⚠️' if cls in subset['shortfall'] else ''
+        print(f"  {cls:<24} {count:>6}{marker}")
+    if subset['shortfall']:
+        print(f"\n⚠️  {len(subset['shortfall'])}개 부류가 {criteria['per_class']}개를 채우지 못함 "
+              f"(코퍼스 공백: python -m utils.corpus stats)")
+
+
+def main():
+    parser = argparse.ArgumentParser(description='층화 평가 부분집합')
+    subparsers = parser.add_subparsers(dest='command', required=True)
+
+    create = subparsers.add_parser('create', help='부분집합 정의 생성/저장')
+    create.add_argument('name', help='부분집합 이름 (data/subsets/<이름>.json)')
+    create.add_argument('--by', choices=CLASS_LEVELS, default='family', help='부류 단위')
+    create.add_argument('--per-class', type=int, default=10, help='부류당 최대 샘플 수')
+    create.add_argument('--max-per-tier', type=int, help='부류 안에서 난이도 단계당 최대 샘플 수')
+    create.add_argument('--agents', nargs='+', default=DEFAULT_AGENTS, help='대상 에이전트')
+    create.add_argument('--split', choices=SPLITS, help='이 분할의 샘플만 (utils/splits.py)')
+    create.add_argument('--filter', metavar='EXPR', help='이 필터 식에 맞는 샘플만 후보로 (utils/sample_filter.py)')
+    create.add_argument('--seed', type=int, default=0)
+    create.add_argument('--test-files-dir', default=TEST_FILES_DIR)
+    create.add_argument('--ground-truth-dir', default=GROUND_TRUTH_DIR)
+    create.add_argument('--subsets-dir', default=SUBSETS_DIR)
+    create.add_argument('--overwrite', action='store_true', help='같은 이름의 정의가 있으면 덮어쓰기')
+    create.add_argument('--dry-run', action='store_true', help='저장하지 않고 결과만 출력')
+
+    show = subparsers.add_parser('show', help='저장된 부분집합 분포 출력')
+    show.add_argument('name', help='부분집합 이름 또는 정의 파일 경로')
+    show.add_argument('--subsets-dir', default=SUBSETS_DIR)
+
+    args = parser.parse_args()
+
+    if args.command == 'show':
+        try:
+            print_subset(load_subset(args.name, args.subsets_dir))
+        except ValueError as e:
+            print(f"❌ {e}")
+            sys.exit(1)
+        return
+
+    path = Path(args.subsets_dir) / f"{args.name}.json"
+    if path.exists() and not args.overwrite and not args.dry_run:
+        print(f"⚠️  {path} 이미 존재 (--overwrite로 덮어쓰기)")
+        sys.exit(1)
+    try:
+        subset = SubsetBuilder(args.test_files_dir, args.ground_truth_dir).build(
+            args.name, args.by, args.per_class, args.max_per_tier, args.agents, args.split, args.seed, args.filter)
+    except ValueError as e:
+        parser.error(str(e))
+
+    print_subset(subset)
+    if not args.dry_run:
+        print(f"\n💾 저장: {save_subset(subset, args.subsets_dir)}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,46 @@
+"""
+샘플 과제 유형 (task)
+
+대부분의 샘플은 코드에 구현·사용된 암호 알고리즘을 찾는 탐지 과제지만, 입력 형태와 채점 기준이
+다른 과제는 ground truth 최상위의 `task` 필드로 구분합니다.
+
+    detection            (기본값) 샘플 코드에 구현되었거나 호출되는 알고리즘 탐지
+    manifest-inference   의존성 매니페스트(Cargo.toml, package.json 등)와 얇은 래퍼 코드만 보고
+                         의존성이 암시하는 암호 표면을 추론 (utils/manifest.py)
+    diff-introduction    unified diff 또는 format-patch 이력을 보고 변경분이 새로 들여온 알고리즘과
+                         그 커밋·hunk를 보고 (utils/diffs.py)
+    misuse-detection     일부러 심은 암호 오용(논스 재사용, ECB, 하드코딩 키/솔트, 시각 기반 엔트로피)을
+                         알고리즘과 함께 보고 (utils/misuse.py, 응답의 weaknesses를 항상 요청·채점)
+
+Ground truth 표기:
+    {"task": "manifest-inference", "expected_findings": {...}}
+
+매니페스트는 키 길이나 운용 모드 같은 변형을 드러내지 않으므로, 과제별 계층 정책
+(TASK_HIERARCHY_POLICY)이 지정된 과제는 설정의 hierarchy_policy 대신 그 정책으로 채점합니다.
+"""
+
+from typing import Dict, Any, Optional
+
+TASK_DETECTION = 'detection'
+TASK_MANIFEST = 'manifest-inference'
+TASK_DIFF = 'diff-introduction'
+TASK_MISUSE = 'misuse-detection'
+
+TASKS = [TASK_DETECTION, TASK_MANIFEST, TASK_DIFF, TASK_MISUSE]
+
+# 과제별 계층 점수 정책 (utils/taxonomy.py의 HIERARCHY_POLICIES)
+TASK_HIERARCHY_POLICY: Dict[str, str] = {
+    TASK_MANIFEST: 'lenient',
+}
+
+
+def sample_task(ground_truth: Optional[Dict[str, Any]]) -> str:
+    """샘플의 과제 유형 (기록이 없으면 detection)"""
+    if not ground_truth:
+        return TASK_DETECTION
+    return ground_truth.get('task') or TASK_DETECTION
+
+
+def task_hierarchy_policy(task: str, default: Optional[str] = None) -> Optional[str]:
+    """과제에 적용할 계층 정책 (과제별 지정이 없으면 default)"""
+    return TASK_HIERARCHY_POLICY.get(task, default)
@@ -0,0 +1,232 @@
+"""
+암호 알고리즘 분류 체계 (Taxonomy)
+
+카테고리(category) → 계열(family) → 변형(variant)의 3단계 계층으로 알고리즘을 표현합니다.
+탐지기는 어느 단계의 라벨이든 그대로 출력할 수 있으며, 채점기는 스위트 정책에 따라
+계층을 따라 위/아래로 점수를 전파합니다.
+
+라벨 표기:
+    "RSA-2048"                          변형 이름 (계층 자동 해석)
+    "RSA"                               계열 이름
+    "shor_vulnerable"                   카테고리 이름
+    "shor_vulnerable/RSA/RSA-2048"      경로 표기 (중간 단계 생략 가능: "shor_vulnerable/RSA")
+    {"category": ..., "family": ..., "variant": ...}   딕셔너리 표기
+
+분류 체계에 없는 이름은 utils/taxonomy_mapping.py의 별칭("Rijndael" → AES), 부류("block cipher" → grover_vulnerable),
+복합 이름("aes-256-cbc" → AES-256) 규칙으로 다시 찾습니다.
+"""
+
+import re
+from typing import Dict, Any, List, Optional, Tuple, Union
+
+from utils.taxonomy_mapping import TaxonomyMapping
+
+# 계층 노드: (category, family, variant) — 모르는 단계는 None
+Node = Tuple[Optional[str], Optional[str], Optional[str]]
+
+CATEGORY_LEVEL = 'category'
+FAMILY_LEVEL = 'family'
+VARIANT_LEVEL = 'variant'
+
+TAXONOMY: Dict[str, Dict[str, List[str]]] = {
+    'shor_vulnerable': {
+        'RSA': ['RSA-1024', 'RSA-2048', 'RSA-3072', 'RSA-4096', 'RSA-PSS', 'RSA-OAEP'],
+        'ECC': ['ECDSA', 'ECDH', 'ECDHE', 'Curve25519', 'X25519', 'Ed25519', 'secp256k1', 'P-256', 'P-384'],
+        'DSA': [],
+        'DH': ['DHE'],
+        'ElGamal': [],
+        'KCDSA': ['EC-KCDSA'],
+        'BLS': [],
+        'Paillier': [],
+    },
+    'grover_vulnerable': {
+        'AES': ['AES-128', 'AES-192', 'AES-256', 'AES-GCM'],
+        'DES': ['3DES'],
+        'SEED': ['SEED-128'],
+        'ARIA': ['ARIA-128', 'ARIA-192', 'ARIA-256'],
+        'HIGHT': [],
+        'LEA': ['LEA-128', 'LEA-192', 'LEA-256'],
+        'RC4': [],
+        'RC2': [],
+        'ChaCha20': ['XChaCha20'],
+        'Salsa20': [],
+        'Blowfish': [],
+        'Twofish': [],
+        'Camellia': [],
+        'CAST': [],
+        'IDEA': [],
+        'Skipjack': [],
+        'Serpent': [],
+        'TEA': [],
+        'A5': ['A5/1', 'A5/2'],
+        'Trivium': [],
+        'SHA-1': [],
+        'SHA-2': ['SHA-224', 'SHA-256', 'SHA-384', 'SHA-512'],
+        'SHA-3': ['SHA3-256', 'SHA3-512', 'Keccak', 'SHAKE', 'SHAKE128', 'SHAKE256'],
+        'MD5': [],
+        'MD4': [],
+        'HAS-160': [],
+        'LSH': ['LSH-256', 'LSH-512'],
+        'RIPEMD': ['RIPEMD-160'],
+        'Whirlpool': [],
+        'Tiger': [],
+        'BLAKE2': ['BLAKE2b', 'BLAKE2s'],
+        'HMAC': ['HMAC-MD5', 'HMAC-SHA1', 'HMAC-SHA256'],
+        'Poly1305': [],
+        'GHASH': [],
+        'SipHash': [],
+        'CRC32': [],
+        'PBKDF1': [],
+        'PBKDF2': [],
+        'HKDF': [],
+        'scrypt': [],
+    },
+    'post_quantum': {
+        'Kyber': ['ML-KEM-512', 'ML-KEM-768', 'ML-KEM-1024'],
+        'Dilithium': ['ML-DSA-44', 'ML-DSA-65', 'ML-DSA-87'],
+        'SPHINCS+': ['SLH-DSA'],
+        'Falcon': [],
+        'NTRU': [],
+        'LMS': ['HSS'],
+        'XMSS': ['XMSS-MT'],
+    },
+}
+
+# 기본 요소 역할: 같은 샘플 안의 암호/MAC/KDF 등을 따로 채점하기 위한 계열 분류
+PRIMITIVE_ROLES: Dict[str, List[str]] = {
+    'public_key': ['RSA', 'ECC', 'DSA', 'DH', 'ElGamal', 'KCDSA', 'BLS', 'Paillier'],
+    'cipher': ['AES', 'DES', 'SEED', 'ARIA', 'HIGHT', 'LEA', 'RC4', 'RC2', 'ChaCha20', 'Salsa20',
+               'Blowfish', 'Twofish', 'Camellia', 'CAST', 'IDEA', 'Skipjack', 'Serpent', 'TEA', 'A5', 'Trivium'],
+    'hash': ['SHA-1', 'SHA-2', 'SHA-3', 'MD5', 'MD4', 'HAS-160', 'LSH', 'RIPEMD', 'Whirlpool', 'Tiger',
+             'BLAKE2', 'CRC32'],
+    'mac': ['HMAC', 'Poly1305', 'GHASH', 'SipHash'],
+    'kdf': ['PBKDF1', 'PBKDF2', 'scrypt', 'HKDF'],
+    'post_quantum': ['Kyber', 'Dilithium', 'SPHINCS+', 'Falcon', 'NTRU', 'LMS', 'XMSS'],
+}
+
+# 스위트 정책: 계층 간 점수 전파 규칙
+#   specific_prediction: 예측이 정답보다 구체적일 때 (정답 RSA, 예측 RSA-2048) — 위로 전파
+#   ancestor: 예측과 정답의 공통 조상 단계별 부분 점수 (정답 RSA-2048, 예측 RSA → family) — 아래로 전파
+HIERARCHY_POLICIES: Dict[str, Dict[str, Any]] = {
+    'strict': {
+        'specific_prediction': 1.0,
+        'ancestor': {FAMILY_LEVEL: 0.0, CATEGORY_LEVEL: 0.0},
+    },
+    'default': {
+        'specific_prediction': 1.0,
+        'ancestor': {FAMILY_LEVEL: 0.5, CATEGORY_LEVEL: 0.25},
+    },
+    'lenient': {
+        'specific_prediction': 1.0,
+        'ancestor': {FAMILY_LEVEL: 1.0, CATEGORY_LEVEL: 0.5},
+    },
+}
+DEFAULT_HIERARCHY_POLICY = 'default'
+
+
+def normalize_name(name: str) -> str:
+    """비교용 정규화: 대소문자, 하이픈/언더스코어/공백 차이 무시 ("SHA256" == "sha-256")"""
+    return re.sub(r'[\s_\-]', '', str(name)).lower()
+
+
+class AlgorithmTaxonomy:
+    """계층 라벨 해석 및 노드 간 점수 계산"""
+
+    def __init__(self, taxonomy: Dict[str, Dict[str, List[str]]] = None, aliases: Dict[str, str] = None):
+        self.taxonomy = taxonomy or TAXONOMY
+        self.mapping = TaxonomyMapping(aliases)
+        self._index: Dict[str, Node] = {}
+
+        for category, families in self.taxonomy.items():
+            self._index[normalize_name(category)] = (category, None, None)
+            for family, variants in families.items():
+                self._index.setdefault(normalize_name(family), (category, family, None))
+                for variant in variants:
+                    self._index.setdefault(normalize_name(variant), (category, family, variant))
+
+    def lookup(self, name: str) -> Optional[Node]:
+        """이름 하나 → 노드 (분류 체계 이름, 그다음 별칭/부류/복합 이름 매핑, 못 찾으면 None)"""
+        node = self._index.get(normalize_name(name))
+        if node:
+            return node
+        for candidate in self.mapping.candidates(name):
+            node = self._index.get(normalize_name(candidate))
+            if node:
+                return node
+        return None
+
+    def resolve(self, label: Union[str, Dict[str, Any]]) -> Node:
+        """라벨을 계층 노드로 해석
+
+        분류 체계에도 매핑 계층에도 없는 이름은 (None, 이름, None) 노드가 되며 동일 이름과만 일치합니다.
+        """
+        if isinstance(label, dict):
+            parts = [label.get(CATEGORY_LEVEL), label.get(FAMILY_LEVEL), label.get(VARIANT_LEVEL)]
+            if not any(parts) and label.get('label'):
+                # 속성이 붙은 라벨: {"label": "AES", "attributes": {...}} (utils/attributes.py)
+                return self.resolve(str(label['label']))
+        else:
+            parts = [p.strip() for p in str(label).split('/') if p.strip()]
+            # "A5/1" 처럼 이름 자체에 '/'가 있는 경우
+            if normalize_name(str(label)) in self._index:
+                parts = [str(label).strip()]
+
+        # 가장 구체적인 단계부터 해석
+        for part in reversed([p for p in parts if p]):
+            node = self.lookup(part)
+            if node:
+                return node
+
+        name = next((p for p in reversed(parts) if p), str(label))
+        return (None, str(name), None)
+
+    @staticmethod
+    def role(node: Node) -> Optional[str]:
+        """노드 계열의 기본 요소 역할 (cipher, mac, hash, kdf, ...) — 계열을 모르면 None"""
+        for role, families in PRIMITIVE_ROLES.items():
+            if node[1] in families:
+                return role
+        return None
+
+    @staticmethod
+    def depth(node: Node) -> int:
+        return len([part for part in node if part is not None])
+
+    @staticmethod
+    def is_known(node: Node) -> bool:
+        return node[0] is not None
+
+    def credit(self, predicted: Node, expected: Node, policy: Dict[str, Any]) -> float:
+        """예측 노드가 정답 노드에 대해 받는 점수 (0.0 ~ 1.0)"""
+        if not self.is_known(predicted) or not self.is_known(expected):
+            # 분류 체계 밖의 라벨은 정확히 같은 이름일 때만 인정
+            return 1.0 if normalize_name(predicted[1] or '') == normalize_name(expected[1] or '') else 0.0
+
+        if predicted == expected:
+            return 1.0
+
+        # 공통 조상 단계 계산
+        common = 0
+        for pred_part, exp_part in zip(predicted, expected):
+            if pred_part is None or exp_part is None or pred_part != exp_part:
+                break
+            common += 1
+
+        # 정답이 예측의 조상 (예측이 더 구체적)
+        if common == self.depth(expected):
+            return policy.get('specific_prediction', 1.0)
+
+        if common == 0:
+            return 0.0
+
+        level = CATEGORY_LEVEL if common == 1 else FAMILY_LEVEL
+        return policy.get('ancestor', {}).get(level, 0.0)
+
+    @staticmethod
+    def get_policy(policy: Union[str, Dict[str, Any], None]) -> Dict[str, Any]:
+        if isinstance(policy, dict):
+            return policy
+        return HIERARCHY_POLICIES.get(policy or DEFAULT_HIERARCHY_POLICY, HIERARCHY_POLICIES[DEFAULT_HIERARCHY_POLICY])
+
+    def format_node(self, node: Node) -> str:
+        return '/'.join(part for part in node if part)
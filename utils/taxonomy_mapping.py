@@ -0,0 +1,106 @@
+"""
+분류 체계 매핑 계층 (별칭, 복합 이름, 알고리즘 부류)
+
+탐지기와 모델은 같은 알고리즘을 여러 이름으로 말합니다 ("AES", "Rijndael", "aes-256-cbc", "block cipher").
+AlgorithmTaxonomy.resolve는 분류 체계에 있는 이름을 먼저 찾고, 없으면 이 모듈이 내는 후보 이름을 차례로 시도합니다.
+
+    별칭          ALIASES: 다른 이름 → 분류 체계 이름 ("Rijndael" → AES, "DESede" → 3DES, "secp256r1" → P-256)
+    부류          CLASS_ALIASES: 알고리즘 부류 → 카테고리 ("block cipher" → grover_vulnerable, "public key" → shor_vulnerable)
+                  카테고리 노드로 해석되므로 계층 점수 정책의 카테고리 단계 부분 점수만 받습니다
+    복합 이름      모드/패딩/파라미터가 붙은 이름은 앞쪽 토큰부터 가장 긴 것으로 해석
+                  ("aes-256-cbc" → AES-256, "ChaCha20-Poly1305" → ChaCha20, "ecdsa-with-SHA256" → ECDSA)
+
+이름 비교는 utils.taxonomy.normalize_name 기준(대소문자, 하이픈/언더스코어/공백 무시)입니다.
+별칭 표는 AlgorithmTaxonomy(aliases={...})로 덧붙일 수 있습니다 (같은 이름이면 덧붙인 쪽이 우선).
+"""
+
+import re
+from typing import Dict, Iterator, Optional
+
+# 다른 이름 → 분류 체계 이름 (변형/계열/카테고리 어느 단계든)
+ALIASES: Dict[str, str] = {
+    # 대칭키 암호
+    'Rijndael': 'AES',
+    'AES128': 'AES-128', 'AES192': 'AES-192', 'AES256': 'AES-256',
+    'DESede': '3DES', 'TripleDES': '3DES', 'Triple DES': '3DES', 'TDEA': '3DES', 'TDES': '3DES', 'DES-EDE3': '3DES',
+    'ARC4': 'RC4', 'ARCFOUR': 'RC4',
+    'XSalsa20': 'Salsa20',
+    'CAST5': 'CAST', 'CAST-128': 'CAST', 'CAST-256': 'CAST',
+    # 해시/MAC/KDF
+    'SHA': 'SHA-1', 'SHA1': 'SHA-1',
+    'SHA2': 'SHA-2', 'SHA3': 'SHA-3',
+    'Keccak-256': 'Keccak', 'Keccak256': 'Keccak',
+    'RIPEMD160': 'RIPEMD-160', 'RMD160': 'RIPEMD-160',
+    'BLAKE2b-512': 'BLAKE2b', 'BLAKE2s-256': 'BLAKE2s',
+    'HmacSHA256': 'HMAC-SHA256', 'HmacSHA1': 'HMAC-SHA1', 'HmacMD5': 'HMAC-MD5',
+    'PBKDF2WithHmacSHA256': 'PBKDF2', 'PBKDF2WithHmacSHA1': 'PBKDF2',
+    # 공개키
+    'RSASSA-PSS': 'RSA-PSS', 'PSS': 'RSA-PSS', 'RSAES-OAEP': 'RSA-OAEP', 'OAEP': 'RSA-OAEP',
+    'RSASSA-PKCS1-v1_5': 'RSA', 'RSAES-PKCS1-v1_5': 'RSA',
+    'Diffie-Hellman': 'DH', 'DiffieHellman': 'DH', 'FFDH': 'DH', 'FFDHE': 'DHE',
+    'EC': 'ECC', 'Elliptic Curve': 'ECC', 'EdDSA': 'ECC', 'Ed448': 'ECC', 'X448': 'ECC',
+    'secp256r1': 'P-256', 'prime256v1': 'P-256', 'NIST P-256': 'P-256', 'nistp256': 'P-256',
+    'secp384r1': 'P-384', 'NIST P-384': 'P-384', 'nistp384': 'P-384',
+    'Curve 25519': 'Curve25519',
+    'ECDSA-P256': 'ECDSA', 'ECDH-ES': 'ECDH',
+    # 양자 내성
+    'CRYSTALS-Kyber': 'Kyber', 'ML-KEM': 'Kyber', 'MLKEM': 'Kyber',
+    'CRYSTALS-Dilithium': 'Dilithium', 'ML-DSA': 'Dilithium', 'MLDSA': 'Dilithium',
+    'SPHINCS': 'SPHINCS+', 'SPHINCSPlus': 'SPHINCS+', 'SPHINCS-PLUS': 'SPHINCS+',
+    'FN-DSA': 'Falcon',
+}
+
+# 알고리즘 부류 → 카테고리
+CLASS_ALIASES: Dict[str, str] = {
+    'block cipher': 'grover_vulnerable',
+    'stream cipher': 'grover_vulnerable',
+    'symmetric cipher': 'grover_vulnerable',
+    'symmetric': 'grover_vulnerable',
+    'hash function': 'grover_vulnerable',
+    'cryptographic hash': 'grover_vulnerable',
+    'MAC': 'grover_vulnerable',
+    'KDF': 'grover_vulnerable',
+    'public key': 'shor_vulnerable',
+    'public-key cryptography': 'shor_vulnerable',
+    'asymmetric': 'shor_vulnerable',
+    'asymmetric cipher': 'shor_vulnerable',
+    'digital signature': 'shor_vulnerable',
+    'key exchange': 'shor_vulnerable',
+    'key agreement': 'shor_vulnerable',
+    'elliptic curve cryptography': 'shor_vulnerable',
+    'post-quantum': 'post_quantum',
+    'PQC': 'post_quantum',
+    'quantum-resistant': 'post_quantum',
+    'quantum-safe': 'post_quantum',
+}
+
+_SEPARATOR = re.compile(r'[\s_\-]+|(?<=[0-9a-z])(?=with[A-Z])|(?<=[a-z])(?=With[A-Z])')
+
+
+def _normalize(name: str) -> str:
+    return re.sub(r'[\s_\-]', '', str(name)).lower()
+
+
+class TaxonomyMapping:
+    """이름 → 분류 체계에서 찾아볼 후보 이름"""
+
+    def __init__(self, aliases: Optional[Dict[str, str]] = None):
+        self.aliases: Dict[str, str] = {}
+        for table in (CLASS_ALIASES, ALIASES, aliases or {}):
+            self.aliases.update({_normalize(name): target for name, target in table.items()})
+
+    def alias(self, name: str) -> Optional[str]:
+        return self.aliases.get(_normalize(name))
+
+    def candidates(self, name: str) -> Iterator[str]:
+        """별칭 대상, 그다음 앞쪽 토큰부터 짧아지는 복합 이름 (원래 이름은 제외)"""
+        target = self.alias(name)
+        if target:
+            yield target
+        tokens = [token for token in _SEPARATOR.split(str(name).strip()) if token]
+        for end in range(len(tokens) - 1, 0, -1):
+            prefix = '-'.join(tokens[:end])
+            yield prefix
+            target = self.alias(prefix)
+            if target:
+                yield target
@@ -0,0 +1,341 @@
+"""
+샘플 템플릿 DSL (Jinja2)
+
+위장 샘플을 하나씩 손으로 쓰는 대신, 알고리즘 코어를 템플릿 팩으로 한 번 작성하고 도메인 테마, 식별자 사전,
+난독화 옵션(knob)을 바꿔 가며 소스와 라벨(ground truth)을 함께 찍어 냅니다.
+
+템플릿 팩: utils/sample_templates/<팩>/ 에 sample.yaml이 있는 디렉토리 (없는 디렉토리는 기존 string.Template 생성기)
+
+    sample.yaml      팩 명세 (아래)
+    <블록>.rs.j2     블록 템플릿 (Jinja2, 정의되지 않은 변수는 오류)
+
+sample.yaml:
+    summary: "ChaCha20 스트림 암호로 도메인 레코드를 보호하는 저장소"     # CLI 도움말
+    name: "{{ theme.stem }}_vault"                                       # 샘플 이름 (뒤에 _<시드>가 붙음)
+    extension: ".rs"
+    description: "{{ theme.title }}: ..."                               # ground truth description
+    difficulty: "medium"
+    tags: ["generated", "dsl"]
+    algorithm_categories: ["grover_vulnerable", "stream_cipher"]
+    korean_algorithms: []
+    expected_confidence_range: [0.7, 0.9]
+    themes: ["healthcare", "payments"]                                   # 사용할 어휘 팩 (생략하면 전부, utils/themes.py)
+    identifiers:                                                         # 역할 → 식별자 패턴 후보 (테마 어휘로 채움)
+      Cipher: ["{Noun}Scrambler", "{Adjective}{Noun}Mixer"]
+      MASTER_KEY: ["{NOUN}_SECRET"]
+    knobs:                                                               # 난독화 옵션 → 허용 값 (첫 값이 평범한 쪽)
+      comments: ["keep", "strip"]
+      literals: ["hex", "decimal"]
+    blocks:                                                              # 출력 순서
+      - header
+      - {template: core, shuffle: true, labels: {ChaCha20: exact}}        # labels: 알고리즘 → 충실도
+      - {template: store, shuffle: true}
+      - main
+      - {template: tests, keep_comments: true}                           # known-answer 주석은 lint가 읽으므로 유지
+
+    shuffle: true인 블록끼리는 시드별로 자리를 바꿉니다. 라벨의 locations는 렌더링된 블록의 줄 범위입니다.
+
+오용 패턴 (misuse-detection 과제, utils/misuse.py):
+    task: "misuse-detection"                                             # ground truth의 task
+    blocks:
+      - {template: key_fixed, when: {pattern: hardcoded-key}, misuse: {TEA: [hardcoded-key]}}
+      - {template: mode_ecb, when: {pattern: [ecb-mode]}, modes: {TEA: ECB}, misuse: {TEA: [ecb-mode]}}
+
+    when        knob 값 조건 (값 또는 값 목록), 맞지 않으면 블록을 렌더링하지 않음
+    modes       알고리즘 → 이 블록이 쓰는 운용 모드
+    misuse      알고리즘 → 이 블록에 심은 약점 목록
+    timing      알고리즘 → 이 블록 구현의 타이밍 라벨 (leaky / constant-time, timing-safety 하위 트랙)
+    modes/misuse/timing이 있으면 expected_findings.mode_findings에 알고리즘별 모드, 약점, CWE, 블록 줄 범위,
+    timing_safety가 남습니다. 상수 시간 블록은 misuse 없이 timing만 적어 "weaknesses": [] 항목을 만듭니다.
+
+템플릿 컨텍스트:
+    theme.*            테마 항목 (name, stem, title, domain, record, payload, noun)
+    id.<역할>           식별자 패턴 후보 중 하나를 테마 어휘로 채운 이름 (역할끼리 겹치지 않음)
+    knobs.<이름>        선택된 knob 값
+    seed               시드 (정수)
+    random_bytes(n)    "0x12, 0xab, ..." 형식의 시드 고정 바이트 n개
+    random_int(a, b)   a ≤ x < b 정수
+    값 | lit            knobs.literals에 따라 정수를 16진수(0x61707865) 또는 10진수(1634760805)로 출력
+
+내장 knob 처리:
+    comments: strip    keep_comments가 아닌 블록에서 주석만 있는 줄(//)을 지움 (줄 범위는 지운 뒤 기준)
+    literals           lit 필터의 출력 형식
+
+난수열은 단계마다 따로 씁니다 (GeneratorSeed.stream): 테마/knob을 CLI로 고정해도 식별자, 블록 순서,
+키 값은 같은 시드에서 바뀌지 않습니다.
+
+사용법:
+    python -m utils.sample_generator chacha_vault --seed 7 --dry-run
+    python -m utils.sample_generator chacha_vault --seed 7 --theme payments --knob comments=strip --verify
+    python -m utils.sample_generator chacha_vault --seed 7 --identifiers my_names.yaml
+    python -m utils.sample_generator timing_pair --seed 7 --pair timing --verify   # knob 값마다 하나씩, 같은 시드
+"""
+
+import re
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple, Union
+
+import yaml
+
+from utils.generator_seed import GeneratorSeed, template_version
+from utils.misuse import WEAKNESS_CWE
+from utils.themes import Theme, load_themes
+
+SPEC_FILE = 'sample.yaml'
+TEMPLATE_SUFFIX = '.j2'
+
+# 역할끼리 같은 이름이 나오면 다시 뽑는 최대 횟수
+IDENTIFIER_ATTEMPTS = 20
+
+_COMMENT_LINE = re.compile(r'^\s*//')
+
+
+def load_spec(pack_dir: Path) -> Dict[str, Any]:
+    with open(Path(pack_dir) / SPEC_FILE, 'r', encoding='utf-8') as f:
+        spec = yaml.safe_load(f)
+    for key in ('name', 'description', 'identifiers', 'blocks'):
+        if key not in spec:
+            raise ValueError(f"{pack_dir}/{SPEC_FILE}: '{key}' is required")
+    spec['blocks'] = [block if isinstance(block, dict) else {'template': block} for block in spec['blocks']]
+    return spec
+
+
+def block_applies(block: Dict[str, Any], knobs: Dict[str, str]) -> bool:
+    """블록의 when 조건이 선택된 knob 값과 맞는지"""
+    for knob, allowed in (block.get('when') or {}).items():
+        if knobs.get(knob) not in (allowed if isinstance(allowed, list) else [allowed]):
+            return False
+    return True
+
+
+def strip_comment_lines(lines: List[str]) -> List[str]:
+    """주석만 있는 줄을 지우고, 그 때문에 생긴 맨 앞/연속 빈 줄을 정리"""
+    kept: List[str] = []
+    for line in lines:
+        if _COMMENT_LINE.match(line):
+            continue
+        if not line.strip() and (not kept or not kept[-1].strip()):
+            continue
+        kept.append(line)
+    return kept
+
+
+class TemplatePackGenerator:
+    """템플릿 팩 하나의 생성기 (팩마다 template_pack()이 하위 클래스를 만듦)"""
+
+    generator_name = ''
+    language_extension = '.rs'
+    pack_dir: Path = None
+
+    def __init__(self, seed: Union[GeneratorSeed, int], theme: Optional[str] = None,
+                 knobs: Optional[Dict[str, str]] = None, identifiers: Optional[Dict[str, List[str]]] = None):
+        self.seed = GeneratorSeed.of(seed)
+        self.spec = load_spec(self.pack_dir)
+        self.themes: Dict[str, Theme] = load_themes(self.spec.get('themes'))
+        self.template_version = template_version(self.pack_dir, [theme.path for theme in self.themes.values()])
+        self.theme_override = theme
+        self.knob_overrides = knobs or {}
+        self.identifier_overrides = identifiers or {}
+
+        if theme is not None and theme not in self.themes:
+            raise ValueError(f"{self.generator_name}: unknown theme {theme!r} (choose from {sorted(self.themes)})")
+        for knob, value in self.knob_overrides.items():
+            allowed = self.spec.get('knobs', {}).get(knob)
+            if allowed is None or value not in allowed:
+                raise ValueError(f"{self.generator_name}: knob {knob}={value!r} is not allowed (allowed: {allowed})")
+
+        try:
+            from jinja2 import Environment, FileSystemLoader, StrictUndefined
+        except ImportError as e:
+            raise RuntimeError("템플릿 팩 생성에는 jinja2가 필요합니다 (pip install \"qvbench[templates]\")") from e
+        self.environment = Environment(loader=FileSystemLoader(str(self.pack_dir)), undefined=StrictUndefined,
+                                       keep_trailing_newline=False, trim_blocks=True, lstrip_blocks=True)
+
+    @classmethod
+    def spec_knobs(cls) -> Dict[str, List[str]]:
+        """팩 명세의 knob → 허용 값"""
+        return load_spec(cls.pack_dir).get('knobs', {})
+
+    @classmethod
+    def spec_name(cls) -> str:
+        """팩 명세의 샘플 이름 템플릿"""
+        return load_spec(cls.pack_dir)['name']
+
+    def _choose(self) -> Tuple[str, Dict[str, str], Dict[str, str], List[Dict[str, Any]]]:
+        """테마, 식별자, knob, 블록 순서 선택 (단계별 독립 난수열)"""
+        theme_rng = self.seed.stream('theme')
+        theme_name = self.theme_override or theme_rng.choice(sorted(self.themes))
+        theme = self.themes[theme_name]
+
+        identifier_rng = self.seed.stream('identifiers')
+        identifiers: Dict[str, str] = {}
+        for role, candidates in self.spec['identifiers'].items():
+            candidates = self.identifier_overrides.get(role, candidates)
+            for _ in range(IDENTIFIER_ATTEMPTS):
+                name = theme.identifier(identifier_rng.choice(candidates), identifier_rng)
+                if name not in identifiers.values():
+                    break
+            else:
+                raise ValueError(f"{self.generator_name}: could not name role {role!r} without clashing "
+                                 f"in theme {theme_name}")
+            identifiers[role] = name
+
+        knob_rng = self.seed.stream('knobs')
+        knobs = {}
+        for knob, values in self.spec.get('knobs', {}).items():
+            chosen = knob_rng.choice(values)
+            knobs[knob] = self.knob_overrides.get(knob, chosen)
+
+        layout_rng = self.seed.stream('layout')
+        blocks = [block for block in self.spec['blocks'] if block_applies(block, knobs)]
+        movable = [index for index, block in enumerate(blocks) if block.get('shuffle')]
+        shuffled = [blocks[index] for index in movable]
+        layout_rng.shuffle(shuffled)
+        for index, block in zip(movable, shuffled):
+            blocks[index] = block
+        return theme_name, identifiers, knobs, blocks
+
+    def _context(self, theme: Theme, identifiers: Dict[str, str], knobs: Dict[str, str]) -> Dict[str, Any]:
+        value_rng = self.seed.stream('values')
+
+        def random_bytes(count: int) -> str:
+            return ', '.join(f"0x{value_rng.randrange(256):02x}" for _ in range(count))
+
+        def random_int(low: int, high: int) -> int:
+            return value_rng.randrange(low, high)
+
+        return {'theme': theme.context(), 'id': identifiers, 'knobs': knobs, 'seed': self.seed.value,
+                'random_bytes': random_bytes, 'random_int': random_int}
+
+    def _lit(self, knobs: Dict[str, str]):
+        def lit(value: int) -> str:
+            return str(value) if knobs.get('literals') == 'decimal' else f"0x{value:08x}"
+        return lit
+
+    def generate(self, name: str = None) -> Dict[str, Any]:
+        """샘플 생성
+
+        Returns:
+            {'name': str, 'source': str, 'ground_truth': dict}
+        """
+        theme_name, identifiers, knobs, blocks = self._choose()
+        theme = self.themes[theme_name]
+        self.environment.filters['lit'] = self._lit(knobs)
+        context = self._context(theme, identifiers, knobs)
+
+        source_lines: List[str] = []
+        spans: Dict[str, Tuple[int, int]] = {}
+        for block in blocks:
+            template = self.environment.get_template(f"{block['template']}{self.language_extension}{TEMPLATE_SUFFIX}")
+            lines = template.render(context).rstrip('\n').split('\n')
+            if knobs.get('comments') == 'strip' and not block.get('keep_comments'):
+                lines = strip_comment_lines(lines)
+            first = len(source_lines) + 1
+            spans[block['template']] = (first, first + len(lines) - 1)
+            source_lines.extend(lines)
+            source_lines.append('')
+
+        render = self.environment.from_string
+        sample_name = name or f"{render(self.spec['name']).render(context)}_{self.seed}"
+        return {
+            'name': sample_name,
+            'source': '\n'.join(source_lines),
+            'ground_truth': self._ground_truth(render(self.spec['description']).render(context), blocks, spans,
+                                               len(source_lines), theme_name, knobs),
+        }
+
+    def _ground_truth(self, description: str, blocks: List[Dict[str, Any]], spans: Dict[str, Tuple[int, int]],
+                      total_lines: int, theme_name: str, knobs: Dict[str, str]) -> Dict[str, Any]:
+        algorithms, locations, fidelity = [], {}, {}
+        mode_findings: Dict[str, Dict[str, Any]] = {}
+        # 라벨 순서는 명세의 블록 순서 (렌더링 순서와 무관하게 같은 팩이면 같은 순서)
+        for block in self.spec['blocks']:
+            if block['template'] not in spans:  # when 조건으로 빠진 블록
+                continue
+            start, end = spans[block['template']]
+            for algorithm, level in (block.get('labels') or {}).items():
+                algorithms.append(algorithm)
+                locations.setdefault(algorithm, []).append(f"{start}-{end}")
+                fidelity[algorithm] = level
+            timing = block.get('timing') or {}
+            for algorithm in list(block.get('modes') or {}) + list(block.get('misuse') or {}) + list(timing):
+                entry = mode_findings.setdefault(algorithm, {'algorithm': algorithm, 'mode': None,
+                                                             'weaknesses': [], 'cwe': [], 'locations': []})
+                entry['mode'] = (block.get('modes') or {}).get(algorithm, entry['mode'])
+                if algorithm in timing:
+                    entry['timing_safety'] = timing[algorithm]
+                for weakness in (block.get('misuse') or {}).get(algorithm, []):
+                    if weakness not in entry['weaknesses']:
+                        entry['weaknesses'].append(weakness)
+                        entry['cwe'].append(WEAKNESS_CWE[weakness])
+                if f"{start}-{end}" not in entry['locations']:
+                    entry['locations'].append(f"{start}-{end}")
+
+        expected_findings = {
+            'vulnerable_algorithms_detected': algorithms,
+            'algorithm_categories': list(self.spec.get('algorithm_categories', [])),
+            'korean_algorithms_detected': list(self.spec.get('korean_algorithms', [])),
+            'locations': locations,
+            'fidelity': fidelity,
+        }
+        if mode_findings:
+            expected_findings['mode_findings'] = [
+                {key: value for key, value in entry.items() if value is not None}
+                for entry in mode_findings.values()
+            ]
+
+        ground_truth = {
+            'description': description,
+            'difficulty': self.spec.get('difficulty', 'medium'),
+            'tags': list(self.spec.get('tags', ['generated'])),
+        }
+        if self.spec.get('task'):
+            ground_truth['task'] = self.spec['task']
+        ground_truth.update({
+            'expected_findings': expected_findings,
+            'expected_confidence_range': list(self.spec.get('expected_confidence_range', [0.7, 0.9])),
+            'generated_by': {'generator': self.generator_name, 'seed': self.seed.value,
+                             'template_version': self.template_version, 'lines': total_lines,
+                             'theme': theme_name, 'knobs': knobs},
+        })
+        return ground_truth
+
+
+def template_pack(pack_dir: Path) -> type:
+    """팩 디렉토리 → 생성기 클래스"""
+    spec = load_spec(pack_dir)
+    return type(f"TemplatePack_{pack_dir.name}", (TemplatePackGenerator,), {
+        '__doc__': spec.get('summary', f"템플릿 팩 {pack_dir.name}"),
+        'generator_name': pack_dir.name,
+        'language_extension': spec.get('extension', '.rs'),
+        'pack_dir': Path(pack_dir),
+    })
+
+
+def discover_packs(templates_dir: Path) -> Dict[str, type]:
+    """sample.yaml이 있는 템플릿 디렉토리 → {팩 이름: 생성기 클래스}"""
+    return {path.name: template_pack(path) for path in sorted(Path(templates_dir).iterdir())
+            if (path / SPEC_FILE).is_file()}
+
+
+def parse_knobs(values: Optional[List[str]]) -> Dict[str, str]:
+    """CLI --knob name=value 목록 → {name: value}"""
+    knobs = {}
+    for item in values or []:
+        if '=' not in item:
+            raise ValueError(f"--knob expects name=value, got {item!r}")
+        knob, value = item.split('=', 1)
+        knobs[knob.strip()] = value.strip()
+    return knobs
+
+
+def load_identifiers(path: Optional[str]) -> Dict[str, List[str]]:
+    """식별자 사전 파일 (YAML: 역할 → 식별자 패턴 후보 목록), 팩 명세의 후보를 역할 단위로 대체"""
+    if not path:
+        return {}
+    with open(path, 'r', encoding='utf-8') as f:
+        identifiers = yaml.safe_load(f) or {}
+    for role, candidates in identifiers.items():
+        if not isinstance(candidates, list) or not candidates:
+            raise ValueError(f"{path}: identifiers for {role!r} must be a non-empty list")
+    return identifiers
@@ -1,8 +1,48 @@
 import json
 import os
+import re
 from typing import Dict, Any, List, Optional
 from pathlib import Path
 
+from utils.manifest import is_manifest_sample, render_sample
+from utils.sample_index import rust_tokens
+from utils.tasks import sample_task
+
+AGENT_TYPES = ['source_code', 'assembly_binary', 'dynamic_analysis', 'logs_config', 'dependency_manifest', 'code_diff']
+
+# 샘플에 내장한 Rust 테스트 모듈 (utils/corpus.py verify). known-answer 벡터와 주석이 정답 알고리즘을 드러내므로
+# 프롬프트에 넣지 않음. 표지와 `mod 이름 {` 머리를 찾고 본문은 중괄호 짝으로 끝을 정함 (뒤따르는 코드는 그대로)
+TEST_MODULE = re.compile(r'^[ \t]*#\[cfg\(test\)\]\s*(?:#\[[^\]\n]*\]\s*)*(?:pub(?:\([^)\n]*\))?\s+)?mod\s+\w+\s*\{',
+                         re.MULTILINE)
+
+
+def strip_test_modules(text: str) -> str:
+    """샘플 코드에서 #[cfg(test)] 테스트 모듈을 지움 (앞부분의 줄 번호는 그대로)"""
+    pieces, position = [], 0
+    for module in TEST_MODULE.finditer(text):
+        if module.start() < position:
+            continue
+        end = _block_end(text, module.end() - 1)
+        if end is None:
+            continue
+        pieces.append(text[position:module.start()])
+        position = end + 1 if text.startswith('\n', end) else end
+    return ''.join(pieces) + text[position:]
+
+
+def _block_end(text: str, opening: int) -> Optional[int]:
+    """opening의 여는 중괄호와 짝이 맞는 닫는 중괄호 다음 위치 (문자열/주석 안의 중괄호는 건너뜀, 짝이 없으면 None)"""
+    depth = 0
+    for token, _, end in rust_tokens(text[opening:]):
+        if token == '{':
+            depth += 1
+        elif token == '}':
+            depth -= 1
+            if depth == 0:
+                return opening + end
+    return None
+
+
 class TestCaseManager:
     def __init__(self, test_cases_dir: str, ground_truth_dir: str, test_files_dir: str = None):
         self.test_cases_dir = Path(test_cases_dir)
@@ -13,7 +53,7 @@ class TestCaseManager:
         self.ground_truth_dir.mkdir(parents=True, exist_ok=True)
         self.test_files_dir.mkdir(parents=True, exist_ok=True)
 
-        for agent_type in ['source_code', 'assembly_binary', 'dynamic_analysis', 'logs_config']:
+        for agent_type in AGENT_TYPES:
             (self.test_cases_dir / agent_type).mkdir(exist_ok=True)
             (self.ground_truth_dir / agent_type).mkdir(exist_ok=True)
             (self.test_files_dir / agent_type).mkdir(exist_ok=True)
@@ -53,6 +93,8 @@ class TestCaseManager:
 
     def _load_file_based_test_cases(self, agent_type: str) -> List[Dict[str, Any]]:
         """Load file-based test cases where actual test files exist separately"""
+        from utils.canary import strip_canaries  # python -m utils.canary imports this package first
+
         test_files_dir = self.test_files_dir / agent_type
         test_cases = []
 
@@ -65,8 +107,8 @@ class TestCaseManager:
         for pattern in file_patterns:
             for test_file in test_files_dir.glob(pattern):
                 try:
-                    # Read the actual test file content
-                    file_content = self._read_test_file(test_file)
+                    # Read the actual test file content (canary lines and test modules never reach the prompt)
+                    file_content = strip_test_modules(strip_canaries(self._read_test_file(test_file)))
 
                     # Create test case structure
                     test_case = {
@@ -77,30 +119,52 @@ class TestCaseManager:
                         'format': 'file_based'
                     }
 
-                    # Try to load additional metadata from ground truth
-                    ground_truth = self.load_ground_truth(agent_type, test_file.stem)
-                    if ground_truth:
-                        test_case.update({
-                            'description': ground_truth.get('description', f'File-based test case: {test_file.name}'),
-                            'expected_analysis_points': ground_truth.get('expected_findings', {}).get('analysis_points', []),
-                            'difficulty': ground_truth.get('difficulty', 'medium'),
-                            'tags': ground_truth.get('tags', [])
-                        })
-
+                    self._attach_ground_truth_metadata(agent_type, test_case, test_file)
                     test_cases.append(test_case)
 
                 except Exception as e:
                     print(f"Error loading file-based test case {test_file}: {e}")
 
+        # Directory samples: package manifest + thin wrapper code (manifest-inference task)
+        for sample_dir in sorted(path for path in test_files_dir.iterdir() if is_manifest_sample(path)):
+            try:
+                test_case = {
+                    'test_id': sample_dir.name,
+                    'input_data': strip_test_modules(strip_canaries(render_sample(sample_dir))),
+                    'file_path': str(sample_dir),
+                    'file_extension': '',
+                    'format': 'directory'
+                }
+                self._attach_ground_truth_metadata(agent_type, test_case, sample_dir)
+                test_cases.append(test_case)
+
+            except Exception as e:
+                print(f"Error loading directory test case {sample_dir}: {e}")
+
         return test_cases
 
+    def _attach_ground_truth_metadata(self, agent_type: str, test_case: Dict[str, Any], test_path: Path):
+        """Try to load additional metadata from ground truth"""
+        ground_truth = self.load_ground_truth(agent_type, test_case['test_id'])
+        if ground_truth:
+            test_case.update({
+                'description': ground_truth.get('description', f'File-based test case: {test_path.name}'),
+                'expected_analysis_points': ground_truth.get('expected_findings', {}).get('analysis_points', []),
+                'difficulty': ground_truth.get('difficulty', 'medium'),
+                'tags': ground_truth.get('tags', []),
+                'task': sample_task(ground_truth)
+            })
+
     def _get_file_patterns(self, agent_type: str) -> List[str]:
         """Get file patterns to search for based on agent type"""
         patterns = {
             'source_code': ['*.py', '*.c', '*.cpp', '*.java', '*.js', '*.go', '*.rs', '*.rb'],
             'assembly_binary': ['*.s', '*.asm', '*.bin', '*.exe', '*.so', '*.dll'],
             'dynamic_analysis': ['*.json', '*.log', '*.txt', '*.trace'],
-            'logs_config': ['*.conf', '*.config', '*.yaml', '*.yml', '*.ini', '*.log', '*.txt']
+            'logs_config': ['*.conf', '*.config', '*.yaml', '*.yml', '*.ini', '*.log', '*.txt'],
+            # manifest samples are directories, loaded separately
+            'dependency_manifest': [],
+            'code_diff': ['*.diff', '*.patch']
         }
         return patterns.get(agent_type, ['*'])
 
@@ -232,7 +296,7 @@ class TestCaseManager:
     def get_test_case_stats(self) -> Dict[str, int]:
         stats = {}
 
-        for agent_type in ['source_code', 'assembly_binary', 'dynamic_analysis', 'logs_config']:
+        for agent_type in AGENT_TYPES:
             test_cases = self.load_test_cases(agent_type)
             ground_truths = len(list((self.ground_truth_dir / agent_type).glob('*.json')))
 
@@ -0,0 +1,161 @@
+"""
+도메인 테마 어휘 팩 (Theme)
+
+생성 샘플의 식별자가 한 도메인 어휘(MedicalSecurityModule, PatientDataEncryptor …)에 몰려 있으면 탐지기가
+암호 구현 대신 도메인 이름을 학습합니다. 테마마다 어휘 팩(명사/동사/형용사)을 두고, 생성기가 식별자를
+테마 어휘로 지어 같은 코어가 도메인마다 그럴듯한 다른 이름으로 나오게 합니다.
+
+어휘 팩: utils/sample_templates/themes/<이름>.yaml
+
+    stem: clinical_chart                  # 샘플 이름 앞부분
+    title: Clinical Chart Archive
+    domain: clinical chart                # 주석/문자열용 도메인 이름
+    record: chart entry                   # 도메인 레코드를 부르는 말
+    payload: "MRN 00482913 ..."           # 예시 레코드 (Rust 바이트 문자열에 들어가므로 따옴표/역슬래시 금지)
+    vocabulary:
+      nouns: [Chart, Ward, ...]           # CamelCase 단어
+      verbs: [Admit, Chart, ...]
+      adjectives: [Clinical, ...]
+
+식별자 패턴 (Theme.identifier): 자리표시자마다 해당 품사에서 한 단어를 뽑고, 표기는 자리표시자 대소문자를 따름
+    {Noun} {Verb} {Adjective}   CamelCase     ("{Adjective}{Noun}Vault" → "SurgicalDosageVault")
+    {noun} {verb} {adjective}   snake_case    ("{verb}_{noun}" → "dispense_specimen")
+    {NOUN} {VERB} {ADJECTIVE}   UPPER_SNAKE   ("{NOUN}_SECRET" → "WARD_SECRET")
+
+어휘에는 암호 관련 단어(cipher, crypt, hash, secure, …)를 넣을 수 없습니다 (load_theme이 거부) — 이름이
+정답을 흘리면 안 되기 때문입니다.
+
+사용법 (어휘 팩 목록과 예시 식별자):
+    python -m utils.themes
+    python -m utils.themes --theme gaming --pattern "{Adjective}{Noun}Vault" --count 5 --seed 3
+"""
+
+import argparse
+import random
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Dict, List, Optional, Set
+
+import yaml
+
+THEMES_DIR = Path(__file__).parent / "sample_templates" / "themes"
+
+WORD_CLASSES = {'noun': 'nouns', 'verb': 'verbs', 'adjective': 'adjectives'}
+
+# 식별자에 들어가면 정답을 흘리는 단어 조각 (소문자 부분 문자열)
+FORBIDDEN_FRAGMENTS = ['crypt', 'cipher', 'hash', 'secur', 'digest', 'rsa', 'aes', 'hmac', 'signature']
+
+_PLACEHOLDER = re.compile(r'\{([A-Za-z]+)\}')
+_CAMEL_PARTS = re.compile(r'[A-Z][a-z0-9]*|[a-z0-9]+')
+
+
+@dataclass
+class Theme:
+    """도메인 테마와 어휘 팩"""
+
+    name: str
+    stem: str
+    title: str
+    domain: str
+    record: str
+    payload: str
+    vocabulary: Dict[str, List[str]] = field(default_factory=dict)
+    path: Optional[Path] = None
+
+    @property
+    def noun(self) -> str:
+        """대표 명사 (어휘 팩의 첫 명사)"""
+        return self.vocabulary['nouns'][0]
+
+    def words(self, word_class: str) -> List[str]:
+        return self.vocabulary[WORD_CLASSES[word_class]]
+
+    def identifier(self, pattern: str, rng: random.Random) -> str:
+        """식별자 패턴의 자리표시자를 어휘로 채움 (자리표시자마다 rng에서 한 번씩 뽑음)"""
+        def fill(match: re.Match) -> str:
+            placeholder = match.group(1)
+            word_class = placeholder.lower()
+            if word_class not in WORD_CLASSES:
+                raise ValueError(f"unknown identifier placeholder {{{placeholder}}} in {pattern!r}")
+            word = rng.choice(self.words(word_class))
+            if placeholder.isupper():
+                return '_'.join(part.upper() for part in _CAMEL_PARTS.findall(word))
+            if placeholder.islower():
+                return '_'.join(part.lower() for part in _CAMEL_PARTS.findall(word))
+            return word
+
+        return _PLACEHOLDER.sub(fill, pattern)
+
+    def context(self) -> Dict[str, str]:
+        """템플릿 컨텍스트용 항목"""
+        return {'name': self.name, 'stem': self.stem, 'title': self.title, 'domain': self.domain,
+                'record': self.record, 'payload': self.payload, 'noun': self.noun}
+
+
+def validate_vocabulary(name: str, vocabulary: Dict[str, List[str]]):
+    for word_class in WORD_CLASSES.values():
+        words = vocabulary.get(word_class)
+        if not words:
+            raise ValueError(f"theme {name}: vocabulary.{word_class} is empty")
+        for word in words:
+            if not re.fullmatch(r'[A-Z][A-Za-z0-9]*', word):
+                raise ValueError(f"theme {name}: {word!r} is not a CamelCase word")
+            leaked = [fragment for fragment in FORBIDDEN_FRAGMENTS if fragment in word.lower()]
+            if leaked:
+                raise ValueError(f"theme {name}: {word!r} leaks a crypto hint ({', '.join(leaked)})")
+
+
+def load_theme(name: str, themes_dir: Path = THEMES_DIR) -> Theme:
+    path = Path(themes_dir) / f"{name}.yaml"
+    if not path.is_file():
+        raise ValueError(f"unknown theme {name!r} (available: {', '.join(available_themes(themes_dir))})")
+    with open(path, 'r', encoding='utf-8') as f:
+        data = yaml.safe_load(f)
+    validate_vocabulary(name, data.get('vocabulary') or {})
+    return Theme(name=name, stem=data['stem'], title=data['title'], domain=data['domain'], record=data['record'],
+                 payload=data['payload'], vocabulary=data['vocabulary'], path=path)
+
+
+def available_themes(themes_dir: Path = THEMES_DIR) -> List[str]:
+    return sorted(path.stem for path in Path(themes_dir).glob('*.yaml'))
+
+
+def load_themes(names: Optional[List[str]] = None, themes_dir: Path = THEMES_DIR) -> Dict[str, Theme]:
+    """테마 이름 목록 → {이름: Theme} (None이면 전부)"""
+    return {name: load_theme(name, themes_dir) for name in (names or available_themes(themes_dir))}
+
+
+def shared_words(themes: Dict[str, Theme]) -> Dict[str, Set[str]]:
+    """여러 테마에 함께 들어 있는 단어 → 테마 이름들 (도메인 구분이 흐려지는 단어)"""
+    owners: Dict[str, Set[str]] = {}
+    for theme in themes.values():
+        for words in theme.vocabulary.values():
+            for word in words:
+                owners.setdefault(word, set()).add(theme.name)
+    return {word: names for word, names in owners.items() if len(names) > 1}
+
+
+def main():
+    parser = argparse.ArgumentParser(description='도메인 테마 어휘 팩')
+    parser.add_argument('--theme', help='예시 식별자를 만들 테마 (기본: 전체)')
+    parser.add_argument('--pattern', default='{Adjective}{Noun}Store', help='식별자 패턴')
+    parser.add_argument('--count', type=int, default=3, help='테마당 예시 수')
+    parser.add_argument('--seed', type=int, default=0)
+    args = parser.parse_args()
+
+    themes = load_themes([args.theme] if args.theme else None)
+    for name, theme in themes.items():
+        rng = random.Random(args.seed)
+        examples = ', '.join(theme.identifier(args.pattern, rng) for _ in range(args.count))
+        sizes = '/'.join(str(len(words)) for words in theme.vocabulary.values())
+        print(f"🎨 {name} ({theme.title}, 명사/동사/형용사 {sizes}): {examples}")
+
+    overlaps = shared_words(themes)
+    if overlaps:
+        print("⚠️  여러 테마에 공통인 단어: " + ', '.join(f"{word}({'/'.join(sorted(names))})"
+                                                      for word, names in sorted(overlaps.items())))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,185 @@
+#!/usr/bin/env python3
+"""
+순위 후보(top-k) 채점
+
+탐지기가 코드 영역마다 후보 알고리즘을 가능성 순으로 보고하면, 정답이 상위 k개 안에 있는지로 채점합니다.
+LLM은 SEED와 일반 Feistel 구조 사이에서 망설이는 경우가 많은데, 한 라벨만 채점하면 2순위로 맞힌 정보가
+사라지므로 top-1/3/5 정확도를 따로 봅니다.
+
+후보 형식 (영역 목록, 라벨은 가능성이 높은 순서):
+    LLM 응답 (--top-k)   "candidates": [{"lines": "40-72", "ranked": ["SEED", "Feistel", "DES"]}]
+    로컬/외부 탐지기      'candidates': [{'labels': ['SEED', 'Feistel'], 'line': 40}]  (BaseDetector.detect)
+    줄 정보(lines/line/span)는 선택입니다. 후보를 보고하지 않은 탐지기는 보고한 탐지(findings) 하나하나를
+    후보가 하나뿐인 영역으로 보므로, 순위를 매기지 않는 탐지기는 top-1 = top-3 = top-5입니다.
+
+채점 (calculate_top_k_scores, 정답 라벨이 없는 샘플은 None):
+    정답 라벨마다 영역 후보의 앞 k개 중 가장 높은 계층 점수(utils/taxonomy.py 정책, disputed 대체 정답 포함)
+    영역과 정답 모두 줄 범위가 있으면 정답 범위와 겹치는 영역의 후보만 봅니다 (다른 함수의 후보로 맞히지 않도록)
+    rank       정답을 만점으로 맞힌 가장 앞 순위 (없으면 null). 다른 정답을 만점으로 맞힌 후보는 세지 않음
+               (filtered rank: 알고리즘이 여럿인 영역에서 정답끼리 순위를 다투지 않도록)
+    hits.topK  rank ≤ K인 정답 수 → 요약의 top-k 정확도
+    credit.topK 정답별 앞 K개 최고 점수의 평균 (계열만 맞힌 후보의 부분 점수 포함)
+
+사용법:
+    python -m utils.top_k results/benchmark_results_20250101.json
+    python -m utils.top_k results/classifier.json --detector classifier
+"""
+
+import argparse
+import sys
+from typing import Dict, Any, Iterable, List, Optional
+
+from utils.comment_noise import label_spans
+from utils.label_review import parse_line_range
+from utils.uncertainty import acceptable_answers, label_alternatives
+
+TOP_K = (1, 3, 5)
+
+# 영역마다 채점에 쓰는 후보 수 (그 뒤는 버림)
+MAX_CANDIDATES = max(TOP_K)
+
+
+def top_k_settings(config: Optional[Dict[str, Any]]) -> Dict[str, Any]:
+    config = config or {}
+    return {'enabled': bool(config.get('enabled', False))}
+
+
+def _span(item: Dict[str, Any]) -> Optional[List[int]]:
+    span = item.get('span')
+    if isinstance(span, list) and span and all(type(line) is int and line > 0 for line in span[:2]):
+        return [min(span[:2]), max(span[:2])]
+    line = item.get('line')
+    if type(line) is int and line > 0:
+        return [line, line]
+    parsed = parse_line_range(item.get('lines')) if isinstance(item.get('lines'), (str, int)) else None
+    return list(parsed) if parsed and parsed[0] > 0 else None
+
+
+def parse_candidates(raw: Any) -> List[Dict[str, Any]]:
+    """응답/탐지 보고의 candidates → [{'labels': [순위대로], 'span': [start, end] 또는 None}] (형식이 틀린 항목은 버림)"""
+    candidates = []
+    for item in raw if isinstance(raw, list) else []:
+        if not isinstance(item, dict):
+            continue
+        ranked = item.get('ranked', item.get('labels'))
+        if not isinstance(ranked, list):
+            continue
+        labels = list(dict.fromkeys(label.strip() for label in ranked if isinstance(label, str) and label.strip()))
+        if labels:
+            candidates.append({'labels': labels[:MAX_CANDIDATES], 'span': _span(item)})
+    return candidates
+
+
+def finding_candidates(findings: List[Dict[str, Any]]) -> List[Dict[str, Any]]:
+    """후보를 보고하지 않은 탐지기: Finding마다 후보 하나짜리 영역"""
+    return [{'labels': [finding['algorithm']], 'span': finding.get('span')}
+            for finding in findings if finding.get('algorithm')]
+
+
+def _overlaps(span: Optional[List[int]], ranges: List[tuple]) -> bool:
+    return span is None or not ranges or any(span[0] <= end and start <= span[-1] for start, end in ranges)
+
+
+def calculate_top_k_scores(candidates: List[Dict[str, Any]], ground_truth: Optional[Dict[str, Any]],
+                           policy: Any = None) -> Optional[Dict[str, Any]]:
+    """정답 라벨별 후보 순위와 top-k 점수 (정답 라벨이 없는 샘플은 None)"""
+    from utils.metrics_calculator import MetricsCalculator
+    from utils.taxonomy import AlgorithmTaxonomy
+
+    expected_labels = MetricsCalculator.get_expected_labels(ground_truth) if ground_truth else []
+    if not expected_labels:
+        return None
+
+    taxonomy = AlgorithmTaxonomy()
+    policy = taxonomy.get_policy(policy)
+    alternatives = label_alternatives(ground_truth)
+    spans: Dict[str, List[tuple]] = {}
+    for label, span in label_spans(ground_truth):
+        spans.setdefault(label, []).append(span)
+
+    groups = [[taxonomy.resolve(answer) for answer in acceptable_answers(label, alternatives)]
+              for label in expected_labels]
+
+    def credit(predicted, answers):
+        return max(taxonomy.credit(taxonomy.resolve(predicted), answer, policy) for answer in answers)
+
+    matches = []
+    for index, label in enumerate(expected_labels):
+        others = groups[:index] + groups[index + 1:]
+        # 순위마다 그 순위 후보가 받는 최고 점수 (정답 범위와 겹치는 영역만, 다른 정답을 맞힌 후보는 건너뜀)
+        by_rank = [0.0] * MAX_CANDIDATES
+        for candidate in candidates:
+            if not _overlaps(candidate.get('span'), spans.get(label, [])):
+                continue
+            position = 0
+            for predicted in candidate['labels'][:MAX_CANDIDATES]:
+                score = credit(predicted, groups[index])
+                if score < 1.0 and any(credit(predicted, answers) >= 1.0 for answers in others):
+                    continue
+                by_rank[position] = max(by_rank[position], score)
+                position += 1
+        rank = next((position + 1 for position, credit in enumerate(by_rank) if credit >= 1.0), None)
+        matches.append({
+            'expected': label,
+            'rank': rank,
+            'credit': {f"top{k}": max(by_rank[:k]) for k in TOP_K},
+        })
+
+    return {
+        'expected': len(matches),
+        'regions': len(candidates),
+        'hits': {f"top{k}": sum(1 for match in matches if match['rank'] and match['rank'] <= k) for k in TOP_K},
+        'credit': {f"top{k}": sum(match['credit'][f"top{k}"] for match in matches) / len(matches) for k in TOP_K},
+        'matches': matches,
+    }
+
+
+def summarize_top_k(scores: Iterable[Optional[Dict[str, Any]]]) -> Dict[str, Any]:
+    """샘플별 top-k 점수 → 정답 라벨 단위로 합친 top-k 정확도와 평균 점수"""
+    scores = [score for score in scores if score]
+    expected = sum(score['expected'] for score in scores)
+    summary = {'samples': len(scores), 'expected': expected, 'accuracy': {}, 'credit': {}}
+    for k in TOP_K:
+        key = f"top{k}"
+        summary['accuracy'][key] = sum(score['hits'][key] for score in scores) / expected if expected else 0.0
+        summary['credit'][key] = sum(score['credit'][key] * score['expected'] for score in scores) / expected \
+            if expected else 0.0
+    return summary
+
+
+def format_top_k(summary: Dict[str, Any]) -> str:
+    return ' / '.join(f"top-{k} {summary['accuracy'][f'top{k}']:.3f}" for k in TOP_K)
+
+
+def main():
+    from utils.report_export import load_results
+    from utils.run_compare import group_by_detector, select_detector
+
+    parser = argparse.ArgumentParser(description='순위 후보 top-k 정확도 (결과의 top_k_scores)')
+    parser.add_argument('results', help='결과 파일 (benchmark_runner / detectors.runner JSON)')
+    parser.add_argument('--detector', help='볼 탐지기 (부분 문자열, 기본: 전부)')
+    args = parser.parse_args()
+
+    try:
+        groups = group_by_detector(load_results(args.results))
+        if args.detector:
+            name = select_detector(groups, args.detector)
+            groups = {name: groups[name]}
+    except ValueError as e:
+        print(f"❌ {e}")
+        sys.exit(2)
+
+    found = False
+    for name, rows in groups.items():
+        summary = summarize_top_k(row.get('top_k_scores') for row in rows)
+        if not summary['samples']:
+            print(f"⚠️  {name}: top_k_scores가 없는 결과입니다 (--top-k 없이 실행했거나 순위 채점 전 결과)")
+            continue
+        found = True
+        print(f"🏅 {name}: {format_top_k(summary)} (정답 {summary['expected']}개, 샘플 {summary['samples']}개)")
+        print("   부분 점수 포함: " + ' / '.join(f"top-{k} {summary['credit'][f'top{k}']:.3f}" for k in TOP_K))
+    sys.exit(0 if found else 1)
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,249 @@
+"""
+구조화 로그와 단계별 추적 (tracing)
+
+긴 벤치마크 실행을 디버깅하고 단계별 지연을 분석할 수 있도록, 실행기와 탐지기, 채점 단계를 span으로 감쌉니다.
+span은 이름, 필드(프로바이더, 모델, test_id 등), 부모 span, 걸린 시간, 상태(ok / error / cancelled)를 가지며
+끝날 때 `benchmark` 로거로 기록됩니다. JSON 형식을 고르면 한 줄에 레코드 하나(JSON Lines)라서 jq나
+로그 수집기로 바로 읽을 수 있습니다.
+
+    sample              샘플 하나 (benchmark_runner: 제한 시간 포함, status 필드에 결과 상태)
+    api_call            모델 호출 한 번 (창 분할이면 창마다, judge 호출 포함, quota.MeteredClient)
+    parse               응답 파싱
+    label_judge         judge 라벨 매핑
+    score               정답 비교 채점
+    detector.prepare    로컬 탐지기 준비 (detectors/runner.py)
+    detector.detect     로컬 탐지기 탐지
+
+span 이름별 지연 통계(count, mean, p50, p95, max)는 결과 metadata의 stage_latency에 남습니다.
+부모 span은 contextvars로 전달되므로 같은 스레드(와 run_control.run_with_timeout의 작업 스레드) 안에서 이어집니다.
+
+설정 (config.yaml, CLI --log-level / --log-format / --log-file로 덮어쓰기):
+    benchmark:
+      logging:
+        level: "WARNING"        # DEBUG면 샘플 실행 단계별 디버그 메시지와 span 기록까지 출력
+        format: "text"          # text 또는 json
+        file: ""                # 빈 값이면 stderr
+
+JSON 레코드 예:
+    {"ts": "2026-10-16T09:12:03.114Z", "level": "INFO", "logger": "benchmark.trace", "message": "span api_call",
+     "run_id": "20261016_091200", "event": "span", "span": "api_call", "span_id": "9f2c01aa",
+     "parent_id": "41d0e7b2", "duration_ms": 1834.2, "status": "ok", "provider": "openai", "model": "gpt-4.1",
+     "success": true, "cost_usd": 0.0123, "cached_tokens": 0}
+
+끝난 span은 add_span_listener로 등록한 함수에도 전달됩니다 (utils/metrics_server.py의 Prometheus 지표).
+"""
+
+import contextvars
+import json
+import logging
+import math
+import statistics
+import threading
+import time
+import uuid
+from contextlib import contextmanager
+from datetime import datetime, timezone
+from typing import Any, Callable, Dict, Iterator, List, Optional
+
+from utils.run_control import Cancelled
+
+LOGGER_NAME = 'benchmark'
+LOG_FORMATS = ['text', 'json']
+DEFAULT_LOG_LEVEL = 'WARNING'
+
+logger = logging.getLogger(LOGGER_NAME)
+trace_logger = logging.getLogger(f"{LOGGER_NAME}.trace")
+
+_current_span: contextvars.ContextVar[Optional['Span']] = contextvars.ContextVar('current_span', default=None)
+_run_id: Optional[str] = None
+_listeners: List[Callable[['Span', str], None]] = []
+
+
+class Span:
+    """진행 중인 단계 하나 (finish를 부르면 기록)"""
+
+    def __init__(self, name: str, fields: Dict[str, Any], parent: Optional['Span']):
+        self.name = name
+        self.fields = fields
+        self.span_id = uuid.uuid4().hex[:8]
+        self.parent_id = parent.span_id if parent else None
+        self.started = time.perf_counter()
+        self.duration_ms: Optional[float] = None
+
+    def set(self, **fields):
+        self.fields.update(fields)
+
+    def finish(self, status: str = 'ok'):
+        if self.duration_ms is not None:
+            return
+        self.duration_ms = (time.perf_counter() - self.started) * 1000
+        STAGE_LATENCY.add(self.name, self.duration_ms)
+        trace_logger.info(f"span {self.name}", extra={'fields': {
+            'event': 'span', 'span': self.name, 'span_id': self.span_id, 'parent_id': self.parent_id,
+            'duration_ms': round(self.duration_ms, 1), 'status': status, **self.fields
+        }})
+        for listener in list(_listeners):
+            try:
+                listener(self, status)
+            except Exception as e:  # 지표 수집 실패로 샘플 실행이 멈추지 않도록
+                logger.warning(f"span listener 실패 ({self.name}): {e}")
+
+
+class StageLatency:
+    """span 이름별 걸린 시간 모음 (병렬 작업자가 함께 씀)"""
+
+    def __init__(self):
+        self.durations: Dict[str, List[float]] = {}
+        self.lock = threading.Lock()
+
+    def add(self, name: str, duration_ms: float):
+        with self.lock:
+            self.durations.setdefault(name, []).append(duration_ms)
+
+    def reset(self):
+        with self.lock:
+            self.durations.clear()
+
+    def report(self) -> Dict[str, Dict[str, float]]:
+        with self.lock:
+            durations = {name: sorted(values) for name, values in self.durations.items()}
+        return {
+            name: {
+                'count': len(values),
+                'total_ms': round(sum(values), 1),
+                'mean_ms': round(statistics.mean(values), 1),
+                'p50_ms': round(percentile(values, 0.5), 1),
+                'p95_ms': round(percentile(values, 0.95), 1),
+                'max_ms': round(values[-1], 1)
+            }
+            for name, values in sorted(durations.items())
+        }
+
+
+STAGE_LATENCY = StageLatency()
+
+
+def percentile(sorted_values: List[float], q: float) -> float:
+    """정렬된 값의 q 분위수 (최근접 순위)"""
+    return sorted_values[max(0, math.ceil(q * len(sorted_values)) - 1)]
+
+
+def start_span(name: str, **fields) -> Span:
+    """현재 span의 하위 span 시작 (현재 span으로 바꾸지는 않음, 긴 블록을 감쌀 때 finish와 함께 사용)"""
+    return Span(name, fields, _current_span.get())
+
+
+@contextmanager
+def span(name: str, **fields) -> Iterator[Span]:
+    """블록을 span으로 감쌈 (블록 안의 span은 이 span의 하위, 예외는 status에 남기고 다시 발생)"""
+    current = start_span(name, **fields)
+    reset = _current_span.set(current)
+    status = 'ok'
+    try:
+        yield current
+    except Cancelled as e:
+        status = 'cancelled'
+        current.set(error=str(e))
+        raise
+    except BaseException as e:
+        status = 'error'
+        current.set(error=f"{type(e).__name__}: {e}")
+        raise
+    finally:
+        _current_span.reset(reset)
+        current.finish(status)
+
+
+def add_span_listener(listener: Callable[[Span, str], None]):
+    """끝난 span마다 listener(span, status) 호출 (같은 함수는 한 번만 등록)"""
+    if listener not in _listeners:
+        _listeners.append(listener)
+
+
+def start_run(run_id: Optional[str] = None) -> str:
+    """실행 하나의 시작: run_id를 정하고 단계별 지연 통계를 비움 (모든 레코드에 run_id가 붙음)"""
+    global _run_id
+    _run_id = run_id or datetime.now().strftime('%Y%m%d_%H%M%S')
+    STAGE_LATENCY.reset()
+    return _run_id
+
+
+class JsonFormatter(logging.Formatter):
+    """레코드 하나 → JSON 한 줄"""
+
+    def format(self, record: logging.LogRecord) -> str:
+        entry = {
+            'ts': datetime.fromtimestamp(record.created, timezone.utc).isoformat(timespec='milliseconds')
+                  .replace('+00:00', 'Z'),
+            'level': record.levelname,
+            'logger': record.name,
+            'message': record.getMessage(),
+            'run_id': _run_id,
+            'thread': record.threadName
+        }
+        entry.update(getattr(record, 'fields', {}))
+        if record.exc_info:
+            entry['exception'] = self.formatException(record.exc_info)
+        return json.dumps(entry, ensure_ascii=False, default=str)
+
+
+class TextFormatter(logging.Formatter):
+    """사람이 읽는 한 줄 (span 필드는 key=value로 덧붙임)"""
+
+    def format(self, record: logging.LogRecord) -> str:
+        line = f"{datetime.fromtimestamp(record.created).strftime('%H:%M:%S')} {record.levelname:<7} " \
+               f"{record.name}: {record.getMessage()}"
+        fields = getattr(record, 'fields', {})
+        if fields:
+            line += ' ' + ' '.join(f"{key}={value}" for key, value in fields.items()
+                                   if key not in ('event', 'span') and value is not None)
+        if record.exc_info:
+            line += '\n' + self.formatException(record.exc_info)
+        return line
+
+
+def logging_settings(config: Optional[Dict[str, Any]]) -> Dict[str, Any]:
+    """benchmark 설정 → {'level', 'format', 'file'}"""
+    config = config or {}
+    log_format = config.get('format', 'text')
+    if log_format not in LOG_FORMATS:
+        raise ValueError(f"logging.format must be one of {LOG_FORMATS}: {log_format}")
+    return {
+        'level': str(config.get('level', DEFAULT_LOG_LEVEL)).upper(),
+        'format': log_format,
+        'file': config.get('file') or None
+    }
+
+
+def configure_logging(level: str = DEFAULT_LOG_LEVEL, log_format: str = 'text', file: Optional[str] = None):
+    """`benchmark` 로거의 출력 대상과 형식 설정 (다시 부르면 이전 설정을 대체)"""
+    for handler in list(logger.handlers):
+        logger.removeHandler(handler)
+        handler.close()
+
+    handler = logging.FileHandler(file, encoding='utf-8') if file else logging.StreamHandler()
+    handler.setFormatter(JsonFormatter() if log_format == 'json' else TextFormatter())
+    logger.addHandler(handler)
+    logger.setLevel(level.upper())
+    logger.propagate = False
+
+    # span 기록은 INFO라서, 파일이나 JSON으로 받을 때는 경고만 보는 레벨에서도 남김
+    trace_logger.setLevel(logging.INFO if file or log_format == 'json' else logging.NOTSET)
+
+
+def add_logging_arguments(parser):
+    """CLI에 --log-level / --log-format / --log-file 추가"""
+    parser.add_argument('--log-level', choices=['DEBUG', 'INFO', 'WARNING', 'ERROR'],
+                        help=f'로그 레벨 (기본: {DEFAULT_LOG_LEVEL}, DEBUG면 단계별 디버그 메시지 출력)')
+    parser.add_argument('--log-format', choices=LOG_FORMATS, help='로그 형식 (json: 한 줄에 레코드 하나)')
+    parser.add_argument('--log-file', help='로그 파일 경로 (기본: stderr)')
+
+
+def apply_logging_arguments(args, settings: Optional[Dict[str, Any]] = None):
+    """CLI 인자로 설정을 덮어써서 로거 설정"""
+    settings = dict(settings or logging_settings(None))
+    for key, value in (('level', args.log_level), ('format', args.log_format), ('file', args.log_file)):
+        if value:
+            settings[key] = value
+    configure_logging(settings['level'], settings['format'], settings['file'])
+    return settings
@@ -0,0 +1,254 @@
+"""
+요청/응답 대화 기록 (transcript) 저장과 재생
+
+샘플마다 탐지기(LLM)에 보낸 프롬프트와 받은 응답(도구 호출 포함)을 그대로 내용 주소 저장소에 남기고, 결과 행의
+transcript에 그 digest를 적습니다. "모델이 왜 ChaCha20을 놓쳤나"를 볼 때 모델이 실제로 본 입력(잘린 본문, 창,
+few-shot 예시, 질의 턴의 답)을 다시 만들 필요 없이 재생합니다.
+
+저장소 (dir, 기본 results/transcripts):
+    objects/<digest 앞 2자>/<나머지>.json.gz   정규화 JSON(sort_keys)의 sha256 → gzip 본문
+    같은 내용은 한 번만 저장됩니다 (같은 캐시 응답으로 다시 실행한 샘플, 모든 샘플에 같은 도구 스키마).
+
+기록 형식 (format qvbench-transcript/1):
+    test_id, agent_type, provider, model, file_path
+    exchanges   요청마다 {'kind': 'completion', 'prompt', 'max_tokens', 'response'}
+                또는 {'kind': 'tools', 'context_messages', 'messages', 'tools'(스키마 digest), 'allow_tools', 'response'}
+                도구 대화는 매 요청이 대화 전체를 다시 보내므로 앞 요청에 없던 메시지만 남기고(context_messages는
+                앞에서 이어지는 메시지 수) 재생할 때 이어 붙입니다.
+
+명령:
+    replay <sample> <detector>   결과 파일에서 샘플(test_id)과 탐지기(provider/model, 모델 또는 프로바이더 이름)가
+                                 맞는 행을 찾아 대화를 다시 출력 (--results를 생략하면 가장 최근 결과 파일부터)
+    show <digest>                저장된 객체를 JSON으로 출력
+
+사용법:
+    python -m utils.transcripts replay chacha20_stream_processor openai/gpt-4o
+    python -m utils.transcripts replay legacy_sso_bridge claude-sonnet-4 --results results/tools.json
+    python -m utils.transcripts show 3f2a9c...
+"""
+
+import argparse
+import gzip
+import hashlib
+import json
+import sys
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Tuple
+
+TRANSCRIPT_FORMAT = 'qvbench-transcript/1'
+DEFAULT_DIR = 'results/transcripts'
+# 결과 파일을 지정하지 않았을 때 찾는 위치 (benchmark_runner.py 기본 저장 이름과 --output)
+RESULT_GLOBS = ('benchmark_results_*.json', 'results/*.json')
+
+
+def transcript_settings(config: Optional[Dict[str, Any]]) -> Dict[str, Any]:
+    config = config or {}
+    return {
+        'enabled': bool(config.get('enabled', True)),
+        'dir': config.get('dir', DEFAULT_DIR)
+    }
+
+
+def canonical_bytes(value: Any) -> bytes:
+    return json.dumps(value, sort_keys=True, ensure_ascii=False, separators=(',', ':'), default=str).encode('utf-8')
+
+
+class TranscriptStore:
+    """sha256 digest → JSON 객체 (쓰기는 임시 파일 후 이름 바꾸기라 병렬 작업자가 같은 객체를 써도 안전)"""
+
+    def __init__(self, root: str = DEFAULT_DIR):
+        self.root = Path(root)
+
+    def _path(self, digest: str) -> Path:
+        return self.root / 'objects' / digest[:2] / f"{digest[2:]}.json.gz"
+
+    def put(self, value: Any) -> str:
+        data = canonical_bytes(value)
+        digest = hashlib.sha256(data).hexdigest()
+        path = self._path(digest)
+        if not path.exists():
+            path.parent.mkdir(parents=True, exist_ok=True)
+            temporary = path.with_name(f"{path.name}.{id(data)}.tmp")
+            temporary.write_bytes(gzip.compress(data, mtime=0))
+            temporary.replace(path)
+        return digest
+
+    def get(self, digest: str) -> Any:
+        digest = digest.strip().lower()
+        path = self._path(digest)
+        if len(digest) < 64:
+            # 앞부분만 준 digest (유일할 때만)
+            matches = sorted((self.root / 'objects' / digest[:2]).glob(f"{digest[2:]}*.json.gz")) \
+                if len(digest) >= 4 else []
+            if len(matches) != 1:
+                raise KeyError(f"digest {digest!r}: 일치하는 객체 {len(matches)}개")
+            path = matches[0]
+        if not path.exists():
+            raise KeyError(f"digest {digest!r}: 저장소({self.root})에 없음")
+        return json.loads(gzip.decompress(path.read_bytes()))
+
+
+class TranscriptRecorder:
+    """클라이언트 감싸기: 요청과 응답을 순서대로 기록하고 save()로 저장소에 남김 (나머지 속성은 그대로 전달)"""
+
+    def __init__(self, client, store: TranscriptStore):
+        self.client = client
+        self.store = store
+        self.exchanges: List[Dict[str, Any]] = []
+        self._messages: List[Dict[str, Any]] = []
+
+    def benchmark_request(self, prompt: str, max_tokens: int = 1000) -> Dict[str, Any]:
+        response = self.client.benchmark_request(prompt, max_tokens)
+        self.exchanges.append({'kind': 'completion', 'prompt': prompt, 'max_tokens': max_tokens,
+                               'response': _plain(response)})
+        return response
+
+    def benchmark_tool_request(self, messages: List[Dict[str, Any]], tools: List[Dict[str, Any]],
+                               max_tokens: int = 1000, allow_tools: bool = True) -> Dict[str, Any]:
+        # 호출한 쪽이 목록을 이어 쓰므로 보낸 시점의 사본으로 비교·기록
+        sent = _plain(messages)
+        context = len(self._messages) if sent[:len(self._messages)] == self._messages else 0
+        response = self.client.benchmark_tool_request(messages, tools, max_tokens, allow_tools)
+        self.exchanges.append({'kind': 'tools', 'context_messages': context, 'messages': sent[context:],
+                               'tools': self.store.put(tools), 'allow_tools': allow_tools,
+                               'max_tokens': max_tokens, 'response': _plain(response)})
+        self._messages = sent
+        return response
+
+    def save(self, test_case: Dict[str, Any], provider: str, model: str, agent_type: str) -> Optional[str]:
+        if not self.exchanges:
+            return None
+        return self.store.put({
+            'format': TRANSCRIPT_FORMAT,
+            'test_id': test_case.get('test_id', 'unknown'),
+            'agent_type': agent_type,
+            'provider': provider,
+            'model': model,
+            'file_path': test_case.get('file_path', ''),
+            'exchanges': self.exchanges
+        })
+
+    def __getattr__(self, name):
+        return getattr(self.client, name)
+
+
+def _plain(value: Any) -> Any:
+    return json.loads(canonical_bytes(value))
+
+
+def _result_files(paths: Optional[List[str]]) -> List[Path]:
+    if paths:
+        return [Path(path) for path in paths]
+    found = {path for pattern in RESULT_GLOBS for path in Path('.').glob(pattern)}
+    return sorted(found, key=lambda path: path.stat().st_mtime, reverse=True)
+
+
+def _matches(row: Dict[str, Any], detector: str) -> bool:
+    provider, model = str(row.get('provider', '')), str(row.get('model', ''))
+    return detector in (f"{provider}/{model}", model, provider)
+
+
+def find_transcript(sample: str, detector: str, results: Optional[List[str]] = None) -> Tuple[Path, Dict[str, Any]]:
+    """(결과 파일, 결과 행): 앞 파일부터 찾고, 기록이 없는 행만 있으면 그 사유로 ValueError"""
+    rows_without = []
+    for path in _result_files(results):
+        try:
+            data = json.loads(path.read_text(encoding='utf-8'))
+        except (OSError, ValueError):
+            continue
+        rows = data.get('detailed_results', []) if isinstance(data, dict) else []
+        for row in rows:
+            if isinstance(row, dict) and row.get('test_id') == sample and _matches(row, detector):
+                if row.get('transcript'):
+                    return path, row
+                rows_without.append(f"{path} ({row.get('status', 'ok')})")
+    if rows_without:
+        raise ValueError(f"{sample} / {detector}: 대화 기록 없는 결과 행만 있음 (transcripts를 끄고 실행했거나 "
+                         f"호출 전에 실패): {', '.join(rows_without[:3])}")
+    raise ValueError(f"{sample} / {detector}: 일치하는 결과 행 없음")
+
+
+def _rule(title: str) -> str:
+    return f"──── {title} " + '─' * max(4, 76 - len(title))
+
+
+def _render_response(response: Dict[str, Any]) -> List[str]:
+    usage = response.get('usage') or {}
+    status = '성공' if response.get('success') else f"실패: {response.get('error')}"
+    lines = [_rule(f"응답 ({status}, {response.get('response_time', 0.0):.2f}초, "
+                   f"${response.get('cost_usd', 0.0):.4f}, 사용량 {json.dumps(usage, ensure_ascii=False)})")]
+    if response.get('content'):
+        lines.append(response['content'])
+    for call in response.get('tool_calls') or []:
+        lines.append(f"→ {call.get('name')}({json.dumps(call.get('arguments'), ensure_ascii=False)})  "
+                     f"[{call.get('id')}]")
+    return lines
+
+
+def _render_message(message: Dict[str, Any]) -> List[str]:
+    if message.get('role') == 'tool':
+        return [_rule(f"도구 결과 {message.get('name')} [{message.get('tool_call_id')}]"), message.get('content', '')]
+    lines = [_rule(f"{message.get('role')} 메시지")]
+    if message.get('content'):
+        lines.append(message['content'])
+    for call in message.get('tool_calls') or []:
+        lines.append(f"→ {call.get('name')}({json.dumps(call.get('arguments'), ensure_ascii=False)})  "
+                     f"[{call.get('id')}]")
+    return lines
+
+
+def render(transcript: Dict[str, Any], store: TranscriptStore) -> str:
+    """대화 기록 → 사람이 읽는 텍스트 (요청마다 보낸 내용과 받은 응답)"""
+    lines = [f"🎬 {transcript.get('test_id')} / {transcript.get('provider')}/{transcript.get('model')} "
+             f"({transcript.get('agent_type')}, {transcript.get('file_path')}) — 요청 {len(transcript['exchanges'])}개"]
+    for number, exchange in enumerate(transcript['exchanges'], 1):
+        lines.append('')
+        if exchange['kind'] == 'completion':
+            lines.append(_rule(f"요청 {number} (max_tokens {exchange.get('max_tokens')})"))
+            lines.append(exchange['prompt'])
+        else:
+            try:
+                names = ', '.join(tool['name'] for tool in store.get(exchange['tools']))
+            except KeyError:
+                names = f"스키마 {exchange['tools'][:12]} 없음"
+            lines.append(_rule(f"요청 {number} (도구 {'켬' if exchange.get('allow_tools') else '끔'}: {names}, "
+                               f"앞 메시지 {exchange.get('context_messages', 0)}개 이어서)"))
+            for message in exchange['messages']:
+                lines.extend(_render_message(message))
+        lines.extend(_render_response(exchange['response']))
+    return '\n'.join(lines)
+
+
+def main():
+    parser = argparse.ArgumentParser(description='탐지기 요청/응답 대화 기록 재생')
+    parser.add_argument('--dir', default=DEFAULT_DIR, help=f'대화 기록 저장소 (기본 {DEFAULT_DIR})')
+    subparsers = parser.add_subparsers(dest='command', required=True)
+    replay = subparsers.add_parser('replay', help='샘플과 탐지기의 대화를 다시 출력')
+    replay.add_argument('sample', help='샘플 test_id')
+    replay.add_argument('detector', help='provider/model, 모델 이름 또는 프로바이더 이름')
+    replay.add_argument('--results', nargs='+', help='찾을 결과 JSON 파일 (기본: 가장 최근 결과 파일부터)')
+    replay.add_argument('--json', action='store_true', help='기록 객체를 JSON으로 출력')
+    show = subparsers.add_parser('show', help='저장된 객체를 JSON으로 출력')
+    show.add_argument('digest', help='sha256 digest (앞부분 4자 이상)')
+    args = parser.parse_args()
+
+    store = TranscriptStore(args.dir)
+    try:
+        if args.command == 'show':
+            print(json.dumps(store.get(args.digest), indent=2, ensure_ascii=False))
+            return
+        path, row = find_transcript(args.sample, args.detector, args.results)
+        transcript = store.get(row['transcript'])
+    except (KeyError, ValueError) as e:
+        print(f"❌ {e.args[0] if e.args else e}", file=sys.stderr)
+        sys.exit(1)
+    if args.json:
+        print(json.dumps(transcript, indent=2, ensure_ascii=False))
+        return
+    print(f"📄 {path}: 상태 {row.get('status', 'ok')}, 계층 F1 "
+          f"{(row.get('hierarchical_scores') or {}).get('f1', 0.0):.3f}, 기록 {row['transcript'][:12]}")
+    print(render(transcript, store))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,65 @@
+"""
+정답 불확실성 (disputed label) 표기
+
+손으로 만든 위장 샘플 중에는 정답이 하나로 정해지지 않는 경우가 있습니다
+(예: SHA-1 IV와 상수를 쓰는 80단계 160비트 압축 함수는 SHA-1으로도 HAS-160으로도 볼 수 있음).
+이런 라벨은 "disputed"로 표시하고 대체 정답을 기록하면, 채점기는 원래 라벨 대신
+대체 정답을 보고한 탐지기에도 같은 점수를 줍니다.
+
+Ground truth 표기:
+    expected_findings 형식:
+        "expected_findings": {
+            "korean_algorithms_detected": ["HAS-160"],
+            "disputed": {"HAS-160": {"alternatives": ["SHA-1"], "reason": "..."}}
+        }
+    레거시 형식:
+        "quantum_vulnerable_algorithms": [
+            {"algorithm": "HAS-160", "disputed": {"alternatives": ["SHA-1"], "reason": "..."}}
+        ]
+
+alternatives가 비어 있는 disputed 라벨은 표시만 될 뿐 채점에는 영향을 주지 않습니다.
+"""
+
+from typing import Dict, Any, List, Optional
+
+
+def disputed_labels(ground_truth: Optional[Dict[str, Any]]) -> Dict[str, Dict[str, Any]]:
+    """disputed로 표시된 라벨 {알고리즘: {'alternatives': [...], 'reason': str}}"""
+    if not ground_truth:
+        return {}
+
+    analysis = ground_truth.get('vulnerability_analysis')
+    if isinstance(analysis, dict):
+        entries = {
+            entry['algorithm']: entry['disputed']
+            for entry in analysis.get('quantum_vulnerable_algorithms', [])
+            if entry.get('algorithm') and isinstance(entry.get('disputed'), dict)
+        }
+    else:
+        entries = {
+            algorithm: info
+            for algorithm, info in ground_truth.get('expected_findings', {}).get('disputed', {}).items()
+            if isinstance(info, dict)
+        }
+
+    return {
+        algorithm: {
+            'alternatives': [alt for alt in info.get('alternatives', []) if alt and alt != algorithm],
+            'reason': info.get('reason', '')
+        }
+        for algorithm, info in entries.items()
+    }
+
+
+def label_alternatives(ground_truth: Optional[Dict[str, Any]]) -> Dict[str, List[str]]:
+    """라벨별 대체 정답 {알고리즘: [대체 정답, ...]} (대체 정답이 있는 라벨만)"""
+    return {
+        algorithm: info['alternatives']
+        for algorithm, info in disputed_labels(ground_truth).items()
+        if info['alternatives']
+    }
+
+
+def acceptable_answers(label: str, alternatives: Optional[Dict[str, List[str]]]) -> List[str]:
+    """라벨 하나에 대해 정답으로 인정되는 이름 (원래 라벨이 맨 앞)"""
+    return [label] + list((alternatives or {}).get(label, []))
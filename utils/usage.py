@@ -0,0 +1,55 @@
+"""
+라벨 사용 형태 (usage)
+
+알고리즘 내부(라운드, 모듈러 연산 등)를 직접 구현한 코드와, 외부 라이브러리를 FFI로
+호출만 하는 코드는 탐지기에게 요구하는 능력이 다르므로 라벨마다 사용 형태를 기록합니다.
+
+    implementation      샘플 안에 알고리즘 내부가 구현됨 (기본값)
+    external-library    extern "C" 바인딩, dlopen 등으로 외부 라이브러리(OpenSSL, 벤더 HSM SDK)를
+                        호출할 뿐 알고리즘 내부는 샘플에 없음 ("crypto via external library")
+
+Ground truth 표기:
+    expected_findings 형식:  "expected_findings": {"usage": {"AES-GCM": "external-library"}, ...}
+    레거시 형식:              "quantum_vulnerable_algorithms": [{"algorithm": "RSA", "usage": "external-library", ...}]
+
+external-library 라벨은 구현이 없으므로 충실도(fidelity)를 기록하지 않습니다.
+manifest-inference 과제(utils/tasks.py)의 라벨은 의존성을 통해서만 쓰이므로 기본값이 external-library입니다.
+"""
+
+from typing import Dict, Any, Optional
+
+from utils.tasks import TASK_MANIFEST, sample_task
+
+USAGE_IMPLEMENTATION = 'implementation'
+USAGE_EXTERNAL = 'external-library'
+
+USAGE_KINDS = [USAGE_IMPLEMENTATION, USAGE_EXTERNAL]
+
+
+def recorded_usage(ground_truth: Optional[Dict[str, Any]]) -> Dict[str, str]:
+    """ground truth에 명시된 라벨별 사용 형태 {알고리즘: 형태}"""
+    if not ground_truth:
+        return {}
+
+    analysis = ground_truth.get('vulnerability_analysis')
+    if isinstance(analysis, dict):
+        return {
+            entry['algorithm']: entry['usage']
+            for entry in analysis.get('quantum_vulnerable_algorithms', [])
+            if entry.get('algorithm') and entry.get('usage')
+        }
+
+    return dict(ground_truth.get('expected_findings', {}).get('usage', {}))
+
+
+def default_usage(ground_truth: Optional[Dict[str, Any]]) -> str:
+    """명시값이 없는 라벨의 사용 형태 (manifest-inference 과제는 external-library)"""
+    return USAGE_EXTERNAL if sample_task(ground_truth) == TASK_MANIFEST else USAGE_IMPLEMENTATION
+
+
+def label_usage(label: str, recorded: Optional[Dict[str, str]] = None,
+                default: str = USAGE_IMPLEMENTATION) -> str:
+    """라벨 하나의 사용 형태 (명시값이 없으면 default)"""
+    if recorded and label in recorded:
+        return recorded[label]
+    return default
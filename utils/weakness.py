@@ -0,0 +1,97 @@
+"""
+라벨 취약성 범주 (weakness category)
+
+같은 KDF라도 "표준 구성 + 충분한 비용"과 "반복 해시로 만든 자체 KDF"는 위험도가 다르므로,
+라벨마다 어떤 종류의 약점을 가지는지 범주를 기록합니다.
+
+    quantum-vulnerable      Shor 알고리즘으로 깨지는 공개키 (shor_vulnerable 라벨의 기본값)
+    grover-margin           표준 구성 — Grover 탐색으로 보안 강도만 절반 (grover_vulnerable 라벨의 기본값)
+    weak-parameters         표준 구성이지만 반복 횟수/비용/키 공간 등 매개변수가 약함
+    homemade-construction   표준 기본 요소를 임의로 조합한 자체 구성 (예: 반복 해시 KDF)
+    broken-classical        양자 컴퓨터 없이도 이미 깨진 레거시 알고리즘 (DES, RC4, MD5, SHA-1 계열의 기본값)
+
+Ground truth 표기:
+    expected_findings 형식:  "expected_findings": {"weakness": {"PBKDF2": "weak-parameters"}, ...}
+    레거시 형식:              "quantum_vulnerable_algorithms": [{"algorithm": "PBKDF1", "weakness": "homemade-construction", ...}]
+
+범주가 기록되지 않은 라벨은 계열 기본값, 없으면 분류 체계 범주의 기본값을 따릅니다.
+
+보고서(SARIF/HTML/CBOM, utils/report_export.py)에서는 범주를 CWE ID로 옮기고 OWASP Top 10 A02로 분류합니다:
+    quantum-vulnerable, broken-classical    CWE-327 (해시 역할의 broken-classical은 CWE-328)
+    grover-margin, weak-parameters          CWE-326
+    homemade-construction                   CWE-1240
+"""
+
+from typing import Dict, Any, Optional
+
+from utils.taxonomy import AlgorithmTaxonomy
+
+WEAKNESS_QUANTUM = 'quantum-vulnerable'
+WEAKNESS_GROVER = 'grover-margin'
+WEAKNESS_PARAMETERS = 'weak-parameters'
+WEAKNESS_HOMEMADE = 'homemade-construction'
+WEAKNESS_BROKEN = 'broken-classical'
+WEAKNESS_UNKNOWN = 'unknown'
+
+WEAKNESS_CATEGORIES = [WEAKNESS_QUANTUM, WEAKNESS_GROVER, WEAKNESS_PARAMETERS, WEAKNESS_HOMEMADE, WEAKNESS_BROKEN]
+
+# 분류 체계 범주별 기본 취약성 범주
+DEFAULT_WEAKNESS = {
+    'shor_vulnerable': WEAKNESS_QUANTUM,
+    'grover_vulnerable': WEAKNESS_GROVER,
+}
+
+# 계열별 기본 취약성 범주 (분류 체계 범주 기본값보다 우선)
+FAMILY_WEAKNESS = {
+    'DES': WEAKNESS_BROKEN,
+    'RC4': WEAKNESS_BROKEN,
+    'MD5': WEAKNESS_BROKEN,
+    'SHA-1': WEAKNESS_BROKEN,
+}
+
+# 취약성 범주 → CWE ID (보고서 내보내기)
+CATEGORY_CWE = {
+    WEAKNESS_QUANTUM: 'CWE-327',
+    WEAKNESS_GROVER: 'CWE-326',
+    WEAKNESS_PARAMETERS: 'CWE-326',
+    WEAKNESS_HOMEMADE: 'CWE-1240',
+    WEAKNESS_BROKEN: 'CWE-327',
+}
+# 깨진 해시 (MD5, SHA-1)는 CWE-327 대신 CWE-328
+HASH_CWE = 'CWE-328'
+
+OWASP_CRYPTO = 'A02:2021-Cryptographic Failures'
+
+
+def recorded_weakness(ground_truth: Optional[Dict[str, Any]]) -> Dict[str, str]:
+    """ground truth에 명시된 라벨별 취약성 범주 {알고리즘: 범주}"""
+    if not ground_truth:
+        return {}
+
+    analysis = ground_truth.get('vulnerability_analysis')
+    if isinstance(analysis, dict):
+        return {
+            entry['algorithm']: entry['weakness']
+            for entry in analysis.get('quantum_vulnerable_algorithms', [])
+            if entry.get('algorithm') and entry.get('weakness')
+        }
+
+    return dict(ground_truth.get('expected_findings', {}).get('weakness', {}))
+
+
+def label_weakness(label: str, recorded: Optional[Dict[str, str]] = None,
+                   taxonomy: Optional[AlgorithmTaxonomy] = None) -> str:
+    """라벨 하나의 취약성 범주 (명시값 우선, 없으면 계열 → 분류 체계 범주의 기본값)"""
+    if recorded and label in recorded:
+        return recorded[label]
+    category, family, _ = (taxonomy or AlgorithmTaxonomy()).resolve(label)
+    if family in FAMILY_WEAKNESS:
+        return FAMILY_WEAKNESS[family]
+    return DEFAULT_WEAKNESS.get(category, WEAKNESS_UNKNOWN)
+
+
+def weakness_cwe(category: str, role: Optional[str] = None) -> Optional[str]:
+    """취약성 범주 (+ 기본 요소 역할) → CWE ID (unknown이면 None)"""
+    if category == WEAKNESS_BROKEN and role == 'hash':
+        return HASH_CWE
+    return CATEGORY_CWE.get(category)
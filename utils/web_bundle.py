@@ -0,0 +1,102 @@
+"""
+브라우저 데모 번들 (Pyodide)
+
+signature/structure 기준선과 채점기는 순수 Python이라, 별도 포팅 없이 Pyodide(CPython의 WebAssembly 빌드)로 브라우저에서
+돌릴 수 있습니다. 이 스크립트는 qvbench.web(JavaScript용 JSON API)이 실제로 import하는 저장소 모듈만 골라 zip 하나로
+묶고, 데모 페이지(web/index.html)와 함께 정적 디렉토리로 내보냅니다. 서버 코드는 없으므로 아무 정적 호스팅에나 올리면
+됩니다 (file://로는 fetch가 막히므로 로컬에서는 python -m http.server).
+
+출력 (--output, 기본 build/web):
+    index.html       데모 페이지: 코드를 붙여 넣고 탐지 → 라벨/근거 줄, 정답 라벨을 적으면 계층 채점과 라벨별 매칭
+    qvbench.zip      qvbench.web을 import할 때 읽히는 저장소 모듈 (.py만, 하위 프로세스에서 import해 목록을 만듦)
+    manifest.json    {'version', 'modules', 'packages'}: packages는 데모 페이지가 pyodide.loadPackage로 먼저 받는
+                     Pyodide 배포 패키지 (import 이름 → PYODIDE_PACKAGES)
+
+사용법:
+    python -m utils.web_bundle
+    python -m utils.web_bundle --output site/demo && python -m http.server -d site/demo 8000
+"""
+
+import argparse
+import json
+import shutil
+import subprocess
+import sys
+import zipfile
+from pathlib import Path
+from typing import Dict, Any, List, Tuple
+
+ROOT = Path(__file__).parent.parent
+DEMO_PAGE = ROOT / "web" / "index.html"
+ENTRY_MODULE = 'qvbench.web'
+
+# 저장소 모듈이 import하는 외부 패키지 → Pyodide 배포 패키지 이름
+PYODIDE_PACKAGES = {'yaml': 'pyyaml'}
+
+_LIST_MODULES = """
+import json, sys
+before = set(sys.modules)
+import {entry}
+found = []
+for name, module in sorted(sys.modules.items()):
+    if name in before or not getattr(module, '__file__', None):
+        continue
+    found.append([name, module.__file__])
+print(json.dumps(found))
+"""
+
+
+def imported_modules(entry: str = ENTRY_MODULE) -> Tuple[List[Path], List[str]]:
+    """entry를 새 인터프리터에서 import해 → (저장소 안 모듈 파일, 저장소 밖 최상위 패키지 이름)"""
+    run = subprocess.run([sys.executable, '-c', _LIST_MODULES.format(entry=entry)], cwd=ROOT,
+                         capture_output=True, text=True, check=True)
+    files, external = [], set()
+    for name, path in json.loads(run.stdout):
+        path = Path(path).resolve()
+        if path.is_relative_to(ROOT.resolve()):
+            files.append(path.relative_to(ROOT.resolve()))
+        elif 'site-packages' in path.parts or 'dist-packages' in path.parts:
+            external.add(name.split('.')[0])
+    return sorted(files), sorted(external)
+
+
+def build_bundle(output: Path, entry: str = ENTRY_MODULE) -> Dict[str, Any]:
+    """번들 디렉토리 생성 → manifest"""
+    from qvbench import __version__
+
+    files, external = imported_modules(entry)
+    unknown = [name for name in external if name not in PYODIDE_PACKAGES]
+    if unknown:
+        raise RuntimeError(f"Pyodide 패키지 이름을 모르는 의존성: {', '.join(unknown)} (PYODIDE_PACKAGES에 추가)")
+
+    output.mkdir(parents=True, exist_ok=True)
+    with zipfile.ZipFile(output / 'qvbench.zip', 'w', zipfile.ZIP_DEFLATED) as archive:
+        for path in files:
+            archive.write(ROOT / path, path.as_posix())
+    shutil.copyfile(DEMO_PAGE, output / 'index.html')
+    manifest = {
+        'version': __version__,
+        'entry': entry,
+        'modules': [path.as_posix() for path in files],
+        'packages': [PYODIDE_PACKAGES[name] for name in external],
+    }
+    (output / 'manifest.json').write_text(json.dumps(manifest, indent=2, ensure_ascii=False) + '\n', encoding='utf-8')
+    return manifest
+
+
+def main():
+    parser = argparse.ArgumentParser(description='브라우저 데모 번들 (Pyodide, signature/structure 기준선과 채점기)')
+    parser.add_argument('--output', default='build/web', help='출력 디렉토리 (기본: build/web)')
+    args = parser.parse_args()
+
+    try:
+        manifest = build_bundle(Path(args.output))
+    except (RuntimeError, subprocess.CalledProcessError) as e:
+        print(f"❌ {e}")
+        sys.exit(1)
+    print(f"📦 모듈 {len(manifest['modules'])}개, Pyodide 패키지: {', '.join(manifest['packages']) or '없음'}")
+    print(f"💾 데모: {Path(args.output) / 'index.html'} (python -m http.server -d {args.output})")
+
+
+if __name__ == '__main__':
+    main()